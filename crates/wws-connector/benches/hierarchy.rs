@@ -0,0 +1,91 @@
+//! Benchmarks for `ConnectorState::recompute_hierarchy`, which places every
+//! known member in the pyramid each time membership changes.
+//!
+//! Run with `cargo bench -p wws-connector`.
+
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use wws_connector::config::ConnectorConfig;
+use wws_connector::connector::{ConnectorState, WwsConnector};
+
+fn members(count: usize, generation: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| format!("did:swarm:member-{}-{}", generation, i))
+        .collect()
+}
+
+/// `members` with one in a hundred replaced by a newcomer.
+fn churned(members: &[String]) -> Vec<String> {
+    members
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            if i % 100 == 0 {
+                format!("{}-rejoined", id)
+            } else {
+                id.clone()
+            }
+        })
+        .collect()
+}
+
+fn reset(state: &mut ConnectorState) {
+    state.agent_tiers.clear();
+    state.agent_parents.clear();
+    state.subordinates.clear();
+}
+
+fn bench_recompute(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    // The benchmarked state needs no peers.
+    let mut config = ConnectorConfig::default();
+    config.network.listen_addr = "/ip4/127.0.0.1/tcp/0".to_string();
+    config.network.mdns_enabled = false;
+    let shared = runtime.block_on(async { WwsConnector::new(config).unwrap().shared_state() });
+    let mut state = shared.try_write().unwrap();
+
+    let mut group = c.benchmark_group("recompute_hierarchy");
+    group.sample_size(10);
+    for count in [1_000, 10_000] {
+        let before = members(count, 0);
+        let after = churned(&before);
+        group.throughput(Throughput::Elements(count as u64));
+
+        // Every member is new: the first layout after startup.
+        group.bench_with_input(BenchmarkId::new("fresh", count), &before, |b, members| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    reset(&mut state);
+                    let start = Instant::now();
+                    state.recompute_hierarchy(members);
+                    total += start.elapsed();
+                }
+                total
+            })
+        });
+
+        // Nothing changed: the common case on a keepalive.
+        reset(&mut state);
+        state.recompute_hierarchy(&before);
+        group.bench_with_input(
+            BenchmarkId::new("unchanged", count),
+            &before,
+            |b, members| b.iter(|| state.recompute_hierarchy(members)),
+        );
+
+        // One member in a hundred replaced, alternating back and forth.
+        group.bench_with_input(BenchmarkId::new("churn_1pct", count), &after, |b, after| {
+            let mut flip = false;
+            b.iter(|| {
+                flip = !flip;
+                state.recompute_hierarchy(if flip { after } else { &before });
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_recompute);
+criterion_main!(benches);
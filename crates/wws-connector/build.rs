@@ -0,0 +1,16 @@
+//! Generates the gRPC service from `proto/` when the `grpc` feature is on,
+//! with a vendored `protoc` so building needs no system install. Only the
+//! server is generated: clients generate their own from the published
+//! proto, and a Rust client's `connect` constructor would clash with the
+//! `Connect` rpc.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "grpc")]
+    {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+        tonic_prost_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/wws/connector/v1/connector.proto"], &["proto"])?;
+    }
+    Ok(())
+}
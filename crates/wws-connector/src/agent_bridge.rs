@@ -196,7 +196,7 @@ impl AgentBridge {
     /// Convert an agent result into a protocol Artifact.
     pub fn result_to_artifact(&self, result: &AgentResult) -> Artifact {
         let content_cid = wws_protocol::crypto::compute_cid(&result.content);
-        let merkle_hash = content_cid.clone(); // Simplified; full implementation chains hashes.
+        let merkle_hash = wws_state::MerkleDag::leaf_hash(content_cid.as_bytes());
 
         Artifact {
             artifact_id: uuid::Uuid::new_v4().to_string(),
@@ -208,6 +208,7 @@ impl AgentBridge {
             size_bytes: result.content.len() as u64,
             created_at: Utc::now(),
             content: String::from_utf8_lossy(&result.content).into_owned(),
+            sealed: None,
         }
     }
 
@@ -0,0 +1,86 @@
+//! Aggregation strategies for combining subtask results.
+//!
+//! When every subtask of a parent has completed, the parent's result is
+//! built according to its [`AggregationStrategy`]:
+//! - `Concatenate` lists each subtask's content CID.
+//! - `JsonMerge` deep-merges the subtasks' JSON results into one document.
+//! - `ChairSynthesis` first assigns a synthesis subtask to the board chair;
+//!   the parent only completes once the chair submits it, and takes the
+//!   synthesis artifact as its result.
+
+use serde_json::{Map, Value};
+
+/// Plan ID carried by synthesis subtask assignments.
+pub const SYNTHESIS_PLAN_ID: &str = "chair-synthesis";
+
+/// ID of the synthesis subtask opened for `parent_task_id`.
+pub fn synthesis_task_id(parent_task_id: &str) -> String {
+    format!("{}-synthesis", parent_task_id)
+}
+
+/// ID of the artifact the connector builds for `parent_task_id`.
+pub fn aggregated_artifact_id(parent_task_id: &str) -> String {
+    format!("{}-aggregated", parent_task_id)
+}
+
+/// Deep-merge `(subtask_id, content)` results in order. JSON objects are
+/// merged key by key, nested arrays are concatenated and later scalars win.
+/// Results that are not JSON objects are kept under their subtask ID.
+pub fn json_merge<'a>(results: impl IntoIterator<Item = (&'a str, &'a str)>) -> Value {
+    let mut merged = Value::Object(Map::new());
+    for (subtask_id, content) in results {
+        match serde_json::from_str::<Value>(content) {
+            Ok(value @ Value::Object(_)) => merge_into(&mut merged, value),
+            Ok(value) => merge_into(&mut merged, single(subtask_id, value)),
+            Err(_) => merge_into(&mut merged, single(subtask_id, Value::String(content.to_string()))),
+        }
+    }
+    merged
+}
+
+fn single(key: &str, value: Value) -> Value {
+    let mut map = Map::new();
+    map.insert(key.to_string(), value);
+    Value::Object(map)
+}
+
+fn merge_into(target: &mut Value, source: Value) {
+    match (target, source) {
+        (Value::Object(target), Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_into(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(target), Value::Array(source)) => target.extend(source),
+        (target, source) => *target = source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_merge_combines_objects_and_keeps_other_results_by_subtask() {
+        let merged = json_merge([
+            ("s1", r#"{"findings": ["a"], "meta": {"sources": 1, "lang": "en"}}"#),
+            ("s2", r#"{"findings": ["b"], "meta": {"sources": 3}}"#),
+            ("s3", "plain text"),
+            ("s4", "[1, 2]"),
+        ]);
+        assert_eq!(
+            merged,
+            serde_json::json!({
+                "findings": ["a", "b"],
+                "meta": {"sources": 3, "lang": "en"},
+                "s3": "plain text",
+                "s4": [1, 2],
+            })
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Assignment strategies for directly assigned subtasks.
+//!
+//! A coordinator hands each low-complexity subtask of a winning plan to one
+//! of its subordinates. Which one is decided by an [`Assigner`] built from
+//! the task's [`AssignmentStrategy`]: the task's own override, else
+//! `consensus.assignment`. Each assigner sees only the [`Candidate`]s, so a
+//! strategy can be exercised without a running connector.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha2::{Digest, Sha256};
+use wws_protocol::{AgentLoad, AssignmentStrategy};
+
+/// What a strategy knows about one subordinate.
+#[derive(Debug, Clone, Default)]
+pub struct Candidate {
+    /// Load to assume, including the subtasks handed to it so far.
+    pub load: AgentLoad,
+    /// Capabilities the subordinate has advertised, in canonical form.
+    pub capabilities: Vec<String>,
+    /// The subordinate's reputation score.
+    pub reputation: u64,
+}
+
+/// Picks the subordinate for each subtask in turn.
+pub trait Assigner: Send {
+    /// Index into `candidates` of the subordinate for the subtask numbered
+    /// `turn` that requires `required` capabilities, in canonical form.
+    /// `candidates` is never empty.
+    fn pick(&mut self, candidates: &[Candidate], required: &[String], turn: usize) -> usize;
+}
+
+/// The assigner for `strategy`. `seed` drives [`AssignmentStrategy::RandomSeeded`].
+pub fn assigner(strategy: AssignmentStrategy, seed: u64) -> Box<dyn Assigner> {
+    match strategy {
+        AssignmentStrategy::RoundRobin => Box::new(RoundRobin),
+        AssignmentStrategy::LeastLoaded => Box::new(LeastLoaded),
+        AssignmentStrategy::CapabilityScore => Box::new(CapabilityScore),
+        AssignmentStrategy::ReputationWeighted => Box::new(ReputationWeighted),
+        AssignmentStrategy::RandomSeeded => Box::new(RandomSeeded(StdRng::seed_from_u64(seed))),
+    }
+}
+
+/// Seed for a task's random assignment: the configured seed, else one
+/// derived from the task ID so every coordinator draws the same subordinates.
+pub fn task_seed(configured: Option<u64>, task_id: &str) -> u64 {
+    configured.unwrap_or_else(|| {
+        let digest = Sha256::digest(task_id.as_bytes());
+        u64::from_le_bytes(digest[..8].try_into().expect("digest has 8 bytes"))
+    })
+}
+
+/// Candidate indices starting at `turn`, so ties go to the next in turn.
+fn in_turn(candidates: &[Candidate], turn: usize) -> impl Iterator<Item = usize> {
+    let n = candidates.len().max(1);
+    (0..candidates.len()).map(move |i| (turn + i) % n)
+}
+
+struct RoundRobin;
+
+impl Assigner for RoundRobin {
+    fn pick(&mut self, candidates: &[Candidate], _required: &[String], turn: usize) -> usize {
+        turn % candidates.len().max(1)
+    }
+}
+
+struct LeastLoaded;
+
+impl Assigner for LeastLoaded {
+    fn pick(&mut self, candidates: &[Candidate], _required: &[String], turn: usize) -> usize {
+        let loads: Vec<AgentLoad> = candidates.iter().map(|c| c.load.clone()).collect();
+        crate::load::least_loaded(&loads, turn)
+    }
+}
+
+struct CapabilityScore;
+
+impl CapabilityScore {
+    fn matched(candidate: &Candidate, required: &[String]) -> f64 {
+        crate::capabilities::coverage(&candidate.capabilities, required)
+    }
+}
+
+impl Assigner for CapabilityScore {
+    fn pick(&mut self, candidates: &[Candidate], required: &[String], turn: usize) -> usize {
+        in_turn(candidates, turn)
+            .min_by(|&a, &b| {
+                let (a, b) = (&candidates[a], &candidates[b]);
+                Self::matched(b, required)
+                    .total_cmp(&Self::matched(a, required))
+                    .then(a.load.is_saturated().cmp(&b.load.is_saturated()))
+                    .then(a.load.utilization().total_cmp(&b.load.utilization()))
+            })
+            .unwrap_or(0)
+    }
+}
+
+struct ReputationWeighted;
+
+impl ReputationWeighted {
+    fn weight(candidate: &Candidate) -> f64 {
+        candidate.reputation as f64 / (1 + candidate.load.held()) as f64
+    }
+}
+
+impl Assigner for ReputationWeighted {
+    fn pick(&mut self, candidates: &[Candidate], _required: &[String], turn: usize) -> usize {
+        in_turn(candidates, turn)
+            .min_by(|&a, &b| {
+                let (a, b) = (&candidates[a], &candidates[b]);
+                a.load
+                    .is_saturated()
+                    .cmp(&b.load.is_saturated())
+                    .then(Self::weight(b).total_cmp(&Self::weight(a)))
+            })
+            .unwrap_or(0)
+    }
+}
+
+struct RandomSeeded(StdRng);
+
+impl Assigner for RandomSeeded {
+    fn pick(&mut self, candidates: &[Candidate], _required: &[String], _turn: usize) -> usize {
+        let roomy: Vec<usize> = (0..candidates.len())
+            .filter(|&i| !candidates[i].load.is_saturated())
+            .collect();
+        if roomy.is_empty() {
+            self.0.gen_range(0..candidates.len().max(1))
+        } else {
+            roomy[self.0.gen_range(0..roomy.len())]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(held: u32, capabilities: &[&str], reputation: u64) -> Candidate {
+        Candidate {
+            load: AgentLoad {
+                queue_depth: held,
+                ..AgentLoad::default()
+            },
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            reputation,
+        }
+    }
+
+    /// Assign `n` subtasks requiring `required`, counting each pick against
+    /// the chosen candidate as the connector does.
+    fn assign(
+        strategy: AssignmentStrategy,
+        mut candidates: Vec<Candidate>,
+        required: &[&str],
+        n: usize,
+    ) -> Vec<usize> {
+        let required: Vec<String> = required.iter().map(|c| c.to_string()).collect();
+        let mut assigner = assigner(strategy, 7);
+        (0..n)
+            .map(|turn| {
+                let pick = assigner.pick(&candidates, &required, turn);
+                candidates[pick].load.queue_depth += 1;
+                pick
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_robin_ignores_load() {
+        let candidates = vec![candidate(5, &[], 10), candidate(0, &[], 10)];
+        assert_eq!(
+            assign(AssignmentStrategy::RoundRobin, candidates, &[], 4),
+            vec![0, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn least_loaded_fills_the_idle_subordinate_first() {
+        let candidates = vec![candidate(2, &[], 10), candidate(0, &[], 10)];
+        assert_eq!(
+            assign(AssignmentStrategy::LeastLoaded, candidates, &[], 4),
+            vec![1, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn capability_score_prefers_matching_subordinates() {
+        let candidates = vec![
+            candidate(0, &["web_search"], 10),
+            candidate(3, &["web_search", "summarization"], 10),
+            candidate(0, &["summarization"], 10),
+        ];
+        assert_eq!(
+            assign(
+                AssignmentStrategy::CapabilityScore,
+                candidates.clone(),
+                &["web_search", "summarization"],
+                2
+            ),
+            vec![1, 1]
+        );
+        // Without requirements it falls back to load.
+        assert_eq!(
+            assign(AssignmentStrategy::CapabilityScore, candidates, &[], 2),
+            vec![0, 2]
+        );
+        // A specialist beats a generalist of the same namespace.
+        let candidates = vec![candidate(0, &["code"], 10), candidate(2, &["code.rust"], 10)];
+        assert_eq!(
+            assign(AssignmentStrategy::CapabilityScore, candidates, &["code.rust"], 1),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn reputation_weighted_gives_more_work_to_reputable_subordinates() {
+        let candidates = vec![candidate(0, &[], 30), candidate(0, &[], 10)];
+        let picks = assign(AssignmentStrategy::ReputationWeighted, candidates, &[], 4);
+        assert_eq!(picks.iter().filter(|&&p| p == 0).count(), 3);
+        assert_eq!(picks.iter().filter(|&&p| p == 1).count(), 1);
+    }
+
+    #[test]
+    fn random_seeded_is_reproducible_and_skips_saturated() {
+        let mut candidates: Vec<Candidate> = (0..5).map(|_| candidate(0, &[], 10)).collect();
+        candidates[2].load.max_concurrency = Some(0);
+        let a = assign(AssignmentStrategy::RandomSeeded, candidates.clone(), &[], 20);
+        let b = assign(AssignmentStrategy::RandomSeeded, candidates, &[], 20);
+        assert_eq!(a, b);
+        assert!(!a.contains(&2));
+        assert_eq!(task_seed(None, "task-1"), task_seed(None, "task-1"));
+        assert_ne!(task_seed(None, "task-1"), task_seed(None, "task-2"));
+        assert_eq!(task_seed(Some(3), "task-1"), 3);
+    }
+}
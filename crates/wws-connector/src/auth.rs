@@ -1,8 +1,149 @@
-//! RPC rate limiting — token bucket per agent DID.
+//! RPC authentication, authorization and rate limiting.
+//!
+//! With `[[rpc.clients]]` configured, every request on the RPC port must
+//! carry one client's token in its top-level `auth` field. A client's role
+//! decides what it may call: an operator may call every method, an agent
+//! only [`AGENT_METHODS`]. An agent client bound to a `local_agent` acts
+//! only as that local agent. Without clients the port stays open, as
+//! before. The connector's in-process callers, such as the echo agent,
+//! bypass this layer.
 
 use std::collections::HashMap;
 use std::time::Instant;
 
+use serde::{Deserialize, Serialize};
+
+/// What an RPC client may call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcRole {
+    /// Every method.
+    Operator,
+    /// The methods an agent needs to work on tasks: [`AGENT_METHODS`].
+    #[default]
+    Agent,
+}
+
+impl std::fmt::Display for RpcRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Operator => write!(f, "operator"),
+            Self::Agent => write!(f, "agent"),
+        }
+    }
+}
+
+/// A client allowed on the RPC port.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcClientConfig {
+    /// Name recorded in the audit log.
+    pub name: String,
+    /// Secret the client sends as `auth`.
+    pub token: String,
+    #[serde(default)]
+    pub role: RpcRole,
+    /// Name of the local agent this client acts as. Its requests act for
+    /// that agent and may not name another `local_agent`.
+    #[serde(default)]
+    pub local_agent: Option<String>,
+}
+
+/// Methods open to agent clients: taking, discussing and delivering
+/// tasks, and reading what that needs. Any method not listed, including
+/// ones added later, needs an operator.
+pub const AGENT_METHODS: &[&str] = &[
+    "swarm.get_status",
+    "swarm.get_network_stats",
+    "swarm.health",
+    "swarm.describe_errors",
+    "swarm.register_agent",
+    "swarm.get_agent_endpoints",
+    "swarm.get_reputation",
+    "swarm.register_local_agent",
+    "swarm.list_local_agents",
+    "swarm.list_capabilities",
+    "swarm.list_swarms",
+    "swarm.get_shard_status",
+    "swarm.get_hierarchy",
+    "swarm.get_swarm_params",
+    "swarm.receive_task",
+    "swarm.ack_task",
+    "swarm.get_task",
+    "swarm.search_tasks",
+    "swarm.get_attachment",
+    "swarm.get_task_context",
+    "swarm.get_result",
+    "swarm.get_task_timeline",
+    "swarm.get_task_costs",
+    "swarm.propose_plan",
+    "swarm.revise_plan",
+    "swarm.withdraw_plan",
+    "swarm.delegate_subtask",
+    "swarm.submit_critique",
+    "swarm.submit_vote",
+    "swarm.get_voting_state",
+    "swarm.get_deliberation",
+    "swarm.get_ballots",
+    "swarm.get_irv_rounds",
+    "swarm.submit_result",
+    "swarm.report_progress",
+    "swarm.get_verification",
+    "swarm.form_board",
+    "swarm.answer_board_invite",
+    "swarm.get_board_status",
+    "swarm.send_message",
+    "swarm.register_name",
+    "swarm.renew_name",
+    "swarm.resolve_name",
+    "swarm.resolve_did",
+    "swarm.my_names",
+];
+
+/// Whether `role` may call `method`.
+pub fn permits(role: RpcRole, method: &str) -> bool {
+    role == RpcRole::Operator || AGENT_METHODS.contains(&method)
+}
+
+/// Whether `method` only reads state; such calls are not audited.
+pub fn is_read_only(method: &str) -> bool {
+    let name = method.strip_prefix("swarm.").unwrap_or(method);
+    ["get_", "list_", "resolve_"].iter().any(|p| name.starts_with(p))
+        || matches!(
+            name,
+            "health" | "describe_errors" | "my_names" | "receive_task" | "search_tasks"
+        )
+}
+
+/// The configured RPC clients.
+#[derive(Debug, Clone, Default)]
+pub struct RpcAuth {
+    clients: Vec<RpcClientConfig>,
+}
+
+impl RpcAuth {
+    pub fn new(clients: Vec<RpcClientConfig>) -> Self {
+        Self { clients }
+    }
+
+    /// Whether requests must authenticate.
+    pub fn is_enabled(&self) -> bool {
+        !self.clients.is_empty()
+    }
+
+    /// The client whose token is `token`.
+    pub fn authenticate(&self, token: Option<&str>) -> Option<&RpcClientConfig> {
+        let token = token?;
+        self.clients
+            .iter()
+            .find(|c| !c.token.is_empty() && constant_time_eq(c.token.as_bytes(), token.as_bytes()))
+    }
+}
+
+/// Compare secrets without an early exit on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Simple token bucket rate limiter per caller identity.
 pub struct RateLimiter {
     buckets: HashMap<String, TokenBucket>,
@@ -60,6 +201,33 @@ mod tests {
         assert!(!rl.check("agent1"), "should block when over capacity");
     }
 
+    #[test]
+    fn clients_authenticate_by_token_and_roles_limit_methods() {
+        let client = |name: &str, token: &str, role| RpcClientConfig {
+            name: name.to_string(),
+            token: token.to_string(),
+            role,
+            local_agent: None,
+        };
+        let auth = RpcAuth::new(vec![
+            client("ops", "s3cret", RpcRole::Operator),
+            client("bot", "b0t", RpcRole::Agent),
+            client("blank", "", RpcRole::Operator),
+        ]);
+        assert!(auth.is_enabled());
+        assert_eq!(auth.authenticate(Some("b0t")).unwrap().name, "bot");
+        assert!(auth.authenticate(Some("s3cre")).is_none());
+        assert!(auth.authenticate(Some("")).is_none());
+        assert!(auth.authenticate(None).is_none());
+
+        assert!(permits(RpcRole::Agent, "swarm.submit_vote"));
+        assert!(!permits(RpcRole::Agent, "swarm.inject_task"));
+        assert!(permits(RpcRole::Operator, "swarm.inject_task"));
+        assert!(is_read_only("swarm.get_task"));
+        assert!(is_read_only("swarm.search_tasks"));
+        assert!(!is_read_only("swarm.submit_vote"));
+    }
+
     #[test]
     fn test_rate_limiter_separate_buckets() {
         let mut rl = RateLimiter::new(1, 0.0); // 1 token max, no refill
@@ -0,0 +1,233 @@
+//! Holonic board formation from `board.invite` acceptances.
+//!
+//! The chair of a task opens a [`BoardFormation`] when it broadcasts
+//! `board.invite`, collects `board.accept` / `board.decline` responses and
+//! closes it once enough agents accepted or the acceptance window ends. The
+//! chosen members and adversarial critic are announced with `board.ready`.
+//!
+//! A private board only seats agents that sent a channel key with their
+//! acceptance; `board.ready` then carries the board's group key sealed to
+//! each member (see [`wws_protocol::board_channel`]).
+
+use chrono::{DateTime, Utc};
+use rand::seq::SliceRandom;
+use wws_protocol::board_channel::{ChannelKeypair, GroupKey};
+use wws_protocol::{AgentId, BoardAcceptParams, BoardInviteParams, BoardReadyParams};
+
+/// Seats offered when an invite does not say otherwise.
+pub const DEFAULT_BOARD_CAPACITY: usize = 5;
+
+/// A board invitation awaiting responses, tracked by its chair.
+#[derive(Debug, Clone)]
+pub struct BoardFormation {
+    pub invite: BoardInviteParams,
+    pub opened_at: DateTime<Utc>,
+    /// Acceptances stop counting after this instant.
+    pub deadline: DateTime<Utc>,
+    pub accepted: Vec<BoardAcceptParams>,
+    pub declined: Vec<AgentId>,
+}
+
+impl BoardFormation {
+    pub fn new(invite: BoardInviteParams, now: DateTime<Utc>, timeout_secs: u64) -> Self {
+        Self {
+            invite,
+            opened_at: now,
+            deadline: crate::connector::deadline_after(now, timeout_secs)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            accepted: Vec::new(),
+            declined: Vec::new(),
+        }
+    }
+
+    /// Record an acceptance. Returns `false` for the chair itself, repeats,
+    /// agents that already declined, and acceptances of a private board
+    /// without a channel key.
+    pub fn record_accept(&mut self, accept: BoardAcceptParams) -> bool {
+        if accept.agent_id == self.invite.chair
+            || (self.invite.private && accept.channel_key.is_none())
+            || self.declined.contains(&accept.agent_id)
+            || self.accepted.iter().any(|a| a.agent_id == accept.agent_id)
+        {
+            return false;
+        }
+        self.accepted.push(accept);
+        true
+    }
+
+    pub fn record_decline(&mut self, agent_id: AgentId) {
+        if !self.declined.contains(&agent_id) {
+            self.accepted.retain(|a| a.agent_id != agent_id);
+            self.declined.push(agent_id);
+        }
+    }
+
+    /// Whether every seat has an accepted candidate.
+    pub fn is_full(&self) -> bool {
+        self.accepted.len() >= self.capacity()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.deadline
+    }
+
+    fn capacity(&self) -> usize {
+        if self.invite.capacity == 0 {
+            DEFAULT_BOARD_CAPACITY
+        } else {
+            self.invite.capacity
+        }
+    }
+
+    /// Up to `capacity` accepted agents: fewest active tasks first, then
+    /// highest affinity for the required capabilities.
+    pub fn select_members(&self) -> Vec<AgentId> {
+        let mut candidates: Vec<&BoardAcceptParams> = self.accepted.iter().collect();
+        candidates.sort_by(|a, b| {
+            a.active_tasks
+                .cmp(&b.active_tasks)
+                .then_with(|| {
+                    let required = &self.invite.required_capabilities;
+                    affinity(b, required).total_cmp(&affinity(a, required))
+                })
+                .then_with(|| a.agent_id.as_str().cmp(b.agent_id.as_str()))
+        });
+        candidates
+            .into_iter()
+            .take(self.capacity())
+            .map(|a| a.agent_id.clone())
+            .collect()
+    }
+
+    /// The `board.ready` announcement for `members`, with one of them drawn
+    /// at random as adversarial critic.
+    pub fn announce(&self, members: Vec<AgentId>) -> BoardReadyParams {
+        let adversarial_critic = members.choose(&mut rand::thread_rng()).cloned();
+        BoardReadyParams {
+            task_id: self.invite.task_id.clone(),
+            chair_id: self.invite.chair.clone(),
+            members,
+            adversarial_critic,
+            sealed_keys: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Seal `group_key` to every member of `ready` using the channel key
+    /// from their acceptance.
+    pub fn seal_group_key(&self, ready: &mut BoardReadyParams, group_key: &GroupKey) {
+        for member in &ready.members {
+            let channel_key = self
+                .accepted
+                .iter()
+                .find(|a| &a.agent_id == member)
+                .and_then(|a| a.channel_key.as_deref());
+            match channel_key.map(|key| group_key.seal_for(key)) {
+                Some(Ok(sealed)) => {
+                    ready.sealed_keys.insert(member.to_string(), sealed);
+                }
+                Some(Err(e)) => {
+                    tracing::warn!(member = %member, error = %e, "Cannot seal board key to member");
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// This connector's key material for a private board.
+#[derive(Default)]
+pub struct BoardChannel {
+    /// Our channel key pair, generated when accepting the invitation.
+    pub keypair: Option<ChannelKeypair>,
+    /// The board's group key, drawn by the chair or opened from
+    /// `board.ready`.
+    pub group_key: Option<GroupKey>,
+}
+
+/// Summed affinity over the required capabilities, or over everything the
+/// agent reported when the invite requires none.
+fn affinity(accept: &BoardAcceptParams, required: &[String]) -> f64 {
+    if required.is_empty() {
+        accept.affinity_scores.values().sum()
+    } else {
+        required
+            .iter()
+            .filter_map(|cap| accept.affinity_scores.get(cap))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invite(capacity: usize) -> BoardInviteParams {
+        BoardInviteParams {
+            task_id: "t1".to_string(),
+            task_digest: "Plan the launch".to_string(),
+            complexity_estimate: 0.8,
+            depth: 0,
+            required_capabilities: vec!["planning".to_string()],
+            capacity,
+            chair: AgentId::new("chair".to_string()),
+            private: false,
+        }
+    }
+
+    fn accept(agent: &str, active_tasks: u32, planning: f64) -> BoardAcceptParams {
+        BoardAcceptParams {
+            task_id: "t1".to_string(),
+            agent_id: AgentId::new(agent.to_string()),
+            active_tasks,
+            capabilities: vec!["planning".to_string()],
+            affinity_scores: [("planning".to_string(), planning)].into_iter().collect(),
+            channel_key: None,
+        }
+    }
+
+    #[test]
+    fn members_are_ranked_by_load_then_affinity() {
+        let mut formation = BoardFormation::new(invite(2), Utc::now(), 5);
+        assert!(formation.record_accept(accept("busy", 4, 0.9)));
+        assert!(formation.record_accept(accept("weak", 0, 0.1)));
+        assert!(formation.record_accept(accept("strong", 0, 0.8)));
+        assert!(!formation.record_accept(accept("strong", 0, 0.8)));
+        assert!(!formation.record_accept(accept("chair", 0, 1.0)));
+        assert!(formation.is_full());
+
+        let members = formation.select_members();
+        assert_eq!(
+            members,
+            vec![AgentId::new("strong".to_string()), AgentId::new("weak".to_string())]
+        );
+        let ready = formation.announce(members.clone());
+        assert!(members.contains(ready.adversarial_critic.as_ref().unwrap()));
+    }
+
+    #[test]
+    fn decline_withdraws_an_acceptance() {
+        let mut formation = BoardFormation::new(invite(3), Utc::now(), 5);
+        formation.record_accept(accept("a", 0, 0.5));
+        formation.record_decline(AgentId::new("a".to_string()));
+        assert!(formation.accepted.is_empty());
+        assert!(!formation.record_accept(accept("a", 0, 0.5)));
+        assert!(formation.is_expired(formation.deadline));
+    }
+
+    #[test]
+    fn private_boards_seat_only_members_with_a_channel_key() {
+        let mut formation = BoardFormation::new(BoardInviteParams { private: true, ..invite(3) }, Utc::now(), 5);
+        let keypair = ChannelKeypair::generate();
+        assert!(!formation.record_accept(accept("keyless", 0, 0.5)));
+        assert!(formation.record_accept(BoardAcceptParams {
+            channel_key: Some(keypair.public_key_hex()),
+            ..accept("member", 0, 0.5)
+        }));
+
+        let group_key = GroupKey::generate();
+        let mut ready = formation.announce(formation.select_members());
+        formation.seal_group_key(&mut ready, &group_key);
+        let sealed = &ready.sealed_keys["member"];
+        assert!(GroupKey::open(sealed, &keypair).is_ok());
+    }
+}
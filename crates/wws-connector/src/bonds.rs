@@ -0,0 +1,103 @@
+//! Proposal bonds.
+//!
+//! A proposer may stake reputation on its plan by committing with a
+//! non-zero `bond`. The bond is held per task until the round ends and is
+//! slashed — deducted from the proposer's reputation — if the plan is
+//! withdrawn after ballots have been cast, if its reveal does not match the
+//! committed hash, or if the board's adversarial critic flags it as
+//! malicious. A bond is slashed at most once.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Why a proposer's bond was slashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashReason {
+    /// The plan was withdrawn after ballots had been cast in its round.
+    LateWithdrawal,
+    /// The revealed plan did not hash to the committed value.
+    HashMismatch,
+    /// The board's adversarial critic flagged the plan as malicious.
+    FlaggedMalicious,
+}
+
+impl std::fmt::Display for SlashReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::LateWithdrawal => "late withdrawal",
+            Self::HashMismatch => "hash mismatch",
+            Self::FlaggedMalicious => "flagged malicious",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Bonds held by this connector, keyed by task ID, then proposer.
+#[derive(Debug, Default)]
+pub struct ProposalBonds {
+    held: HashMap<String, HashMap<String, u64>>,
+}
+
+impl ProposalBonds {
+    /// Hold `bond` points of `proposer` on `task_id`, capped at the
+    /// proposer's `reputation`. A zero bond holds nothing; a later commit
+    /// from the same proposer replaces the earlier bond.
+    pub fn hold(&mut self, task_id: &str, proposer: &str, bond: u64, reputation: u64) {
+        let bond = bond.min(reputation);
+        if bond == 0 {
+            return;
+        }
+        self.held
+            .entry(task_id.to_string())
+            .or_default()
+            .insert(proposer.to_string(), bond);
+    }
+
+    /// Points `proposer` has at stake on `task_id`.
+    pub fn held(&self, task_id: &str, proposer: &str) -> u64 {
+        self.held
+            .get(task_id)
+            .and_then(|bonds| bonds.get(proposer))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Take the bond of `proposer` on `task_id` for slashing.
+    pub fn slash(&mut self, task_id: &str, proposer: &str) -> Option<u64> {
+        let bonds = self.held.get_mut(task_id)?;
+        let bond = bonds.remove(proposer);
+        if bonds.is_empty() {
+            self.held.remove(task_id);
+        }
+        bond
+    }
+
+    /// Return every bond held on `task_id`, once its round is over.
+    pub fn release(&mut self, task_id: &str) {
+        self.held.remove(task_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonds_are_capped_and_slashed_once() {
+        let mut bonds = ProposalBonds::default();
+        bonds.hold("t1", "alice", 50, 30);
+        bonds.hold("t1", "bob", 0, 30);
+        assert_eq!(bonds.held("t1", "alice"), 30);
+        assert_eq!(bonds.held("t1", "bob"), 0);
+
+        assert_eq!(bonds.slash("t1", "alice"), Some(30));
+        assert_eq!(bonds.slash("t1", "alice"), None);
+        assert_eq!(bonds.slash("t1", "bob"), None);
+
+        bonds.hold("t2", "carol", 5, 100);
+        bonds.release("t2");
+        assert_eq!(bonds.slash("t2", "carol"), None);
+    }
+}
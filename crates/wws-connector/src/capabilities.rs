@@ -0,0 +1,309 @@
+//! Capability ontology: namespaced capability names, aliases and matching.
+//!
+//! Capabilities are dotted paths, most general first: `code.rust` is a kind
+//! of `code`. An agent holding `code.rust` fully meets a requirement for
+//! `code`, while one holding just `code` only partly meets a requirement for
+//! `code.rust`: half, as it shares one of the two levels. Other capabilities,
+//! siblings such as `code.python` included, do not count.
+//!
+//! The swarm may declare its capabilities and aliases for them:
+//!
+//! ```toml
+//! [capabilities]
+//! known = ["code.rust", "code.python", "research.web_search"]
+//!
+//! [capabilities.aliases]
+//! rust = "code.rust"
+//! web_search = "research.web_search"
+//! ```
+//!
+//! Names are compared after trimming, lowercasing and resolving aliases; an
+//! alias may also stand for the leading part of a longer name, so
+//! `rust.async` is `code.rust.async`. An agent's coverage of a requirement
+//! list is the mean of how well it meets each one, from 0 to 1. Assignment
+//! and reassignment use it to choose among subordinates, plan validation to
+//! reject unregistered or unserved capabilities, and `swarm.list_capabilities`
+//! to report it.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+/// Declared capabilities and aliases.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityConfig {
+    /// Capabilities registered in the swarm. Their namespaces count as
+    /// registered too.
+    #[serde(default)]
+    pub known: Vec<String>,
+    /// Alternative names, each mapped to the capability it stands for.
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+}
+
+/// One capability as listed by `swarm.list_capabilities`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CapabilityEntry {
+    pub name: String,
+    /// The enclosing namespace, if any.
+    pub parent: Option<String>,
+    /// Whether the capability, or one inside its namespace, is registered.
+    pub registered: bool,
+    pub aliases: Vec<String>,
+    /// Agents holding the capability or one inside its namespace.
+    pub agents: Vec<String>,
+}
+
+/// How well holding `held` meets a requirement for `required`, both
+/// canonical: 1 when it is the same capability or a more specific one, the
+/// share of levels `held` covers when it is a namespace of `required`, and
+/// 0 otherwise.
+pub fn match_score(held: &str, required: &str) -> f64 {
+    if held == required || is_within(held, required) {
+        return 1.0;
+    }
+    if is_within(required, held) {
+        return depth(held) as f64 / depth(required) as f64;
+    }
+    0.0
+}
+
+/// Mean over `required` of the best match in `held`, all canonical. 1 when
+/// nothing is required.
+pub fn coverage(held: &[String], required: &[String]) -> f64 {
+    if required.is_empty() {
+        return 1.0;
+    }
+    let total: f64 = required
+        .iter()
+        .map(|r| held.iter().map(|h| match_score(h, r)).fold(0.0, f64::max))
+        .sum();
+    total / required.len() as f64
+}
+
+/// Whether `name` lies inside the namespace `namespace`.
+fn is_within(name: &str, namespace: &str) -> bool {
+    name.len() > namespace.len()
+        && name.starts_with(namespace)
+        && name.as_bytes()[namespace.len()] == b'.'
+}
+
+fn depth(name: &str) -> usize {
+    name.split('.').count()
+}
+
+/// `name` and each namespace enclosing it, innermost first.
+fn namespaces(name: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(name), |n| n.rfind('.').map(|i| &n[..i]))
+}
+
+/// The swarm's capabilities and aliases, resolved.
+#[derive(Debug, Clone, Default)]
+pub struct CapabilityRegistry {
+    /// Canonical registered capabilities.
+    known: Vec<String>,
+    /// Lowercased alias to canonical capability.
+    aliases: HashMap<String, String>,
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+impl CapabilityRegistry {
+    pub fn new(config: &CapabilityConfig) -> Self {
+        let aliases = config
+            .aliases
+            .iter()
+            .map(|(alias, target)| (normalize(alias), normalize(target)))
+            .filter(|(alias, target)| !alias.is_empty() && !target.is_empty())
+            .collect();
+        let mut registry = Self {
+            known: Vec::new(),
+            aliases,
+        };
+        let mut known: Vec<String> = config.known.iter().map(|c| registry.canonical(c)).collect();
+        known.retain(|c| !c.is_empty());
+        known.sort();
+        known.dedup();
+        registry.known = known;
+        registry
+    }
+
+    /// The canonical form of `name`: trimmed, lowercased, with an alias for
+    /// the whole name or its leading part resolved.
+    pub fn canonical(&self, name: &str) -> String {
+        let name = normalize(name);
+        for prefix in namespaces(&name) {
+            if let Some(target) = self.aliases.get(prefix) {
+                return format!("{}{}", target, &name[prefix.len()..]);
+            }
+        }
+        name
+    }
+
+    pub fn canonicalize(&self, names: &[String]) -> Vec<String> {
+        names.iter().map(|n| self.canonical(n)).collect()
+    }
+
+    /// Whether capabilities are declared at all.
+    pub fn has_known(&self) -> bool {
+        !self.known.is_empty()
+    }
+
+    /// Whether `name` is registered in `known`, as itself or as the
+    /// namespace of a registered capability. Both are resolved first.
+    pub fn is_registered_in(&self, known: &[String], name: &str) -> bool {
+        let name = self.canonical(name);
+        known.iter().any(|k| {
+            let k = self.canonical(k);
+            k == name || is_within(&k, &name)
+        })
+    }
+
+    /// Whether `name` is registered in the declared capabilities.
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.is_registered_in(&self.known, name)
+    }
+
+    /// How well `held` covers `required`, from 0 to 1, after resolving both.
+    pub fn coverage(&self, held: &[String], required: &[String]) -> f64 {
+        coverage(&self.canonicalize(held), &self.canonicalize(required))
+    }
+
+    /// The requirements in `required` that `held` does not fully meet.
+    pub fn missing<'a>(&self, held: &[String], required: &'a [String]) -> Vec<&'a str> {
+        let held = self.canonicalize(held);
+        required
+            .iter()
+            .filter(|r| coverage(&held, &[self.canonical(r)]) < 1.0)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether `held` fully meets every requirement in `required`.
+    pub fn covers(&self, held: &[String], required: &[String]) -> bool {
+        self.missing(held, required).is_empty()
+    }
+
+    /// Every declared capability and every capability an agent in
+    /// `agent_capabilities` holds, with their namespaces, sorted by name.
+    pub fn entries(&self, agent_capabilities: &HashMap<String, Vec<String>>) -> Vec<CapabilityEntry> {
+        let mut entries: BTreeMap<String, CapabilityEntry> = BTreeMap::new();
+        let mut add = |name: &str| {
+            for namespace in namespaces(name) {
+                entries
+                    .entry(namespace.to_string())
+                    .or_insert_with(|| CapabilityEntry {
+                        name: namespace.to_string(),
+                        parent: namespace.rfind('.').map(|i| namespace[..i].to_string()),
+                        registered: false,
+                        aliases: Vec::new(),
+                        agents: Vec::new(),
+                    });
+            }
+        };
+        let held: Vec<(&String, Vec<String>)> = agent_capabilities
+            .iter()
+            .map(|(agent, caps)| (agent, self.canonicalize(caps)))
+            .collect();
+        for name in self.known.iter().chain(self.aliases.values()) {
+            add(name);
+        }
+        for (_, caps) in &held {
+            for name in caps.iter().filter(|c| !c.is_empty()) {
+                add(name);
+            }
+        }
+        for entry in entries.values_mut() {
+            entry.registered = self.is_registered(&entry.name);
+            entry.aliases = self
+                .aliases
+                .iter()
+                .filter(|(_, target)| **target == entry.name)
+                .map(|(alias, _)| alias.clone())
+                .collect();
+            entry.aliases.sort();
+            entry.agents = held
+                .iter()
+                .filter(|(_, caps)| caps.iter().any(|c| match_score(c, &entry.name) == 1.0))
+                .map(|(agent, _)| agent.to_string())
+                .collect();
+            entry.agents.sort();
+        }
+        entries.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caps(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn registry() -> CapabilityRegistry {
+        CapabilityRegistry::new(&CapabilityConfig {
+            known: caps(&["code.rust", "Code.Python", "research.web_search"]),
+            aliases: [("rust", "code.rust"), ("Web_Search", "research.web_search")]
+                .into_iter()
+                .map(|(a, t)| (a.to_string(), t.to_string()))
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn namespaces_and_aliases_shape_coverage() {
+        let registry = registry();
+        assert_eq!(registry.canonical(" RUST "), "code.rust");
+        assert_eq!(registry.canonical("rust.async"), "code.rust.async");
+        assert_eq!(registry.canonical("rustacean"), "rustacean");
+
+        // Specific meets general fully; general meets specific in part.
+        assert_eq!(match_score("code.rust", "code"), 1.0);
+        assert_eq!(match_score("code", "code.rust"), 0.5);
+        assert_eq!(match_score("code.python", "code.rust"), 0.0);
+        assert_eq!(match_score("codec", "code"), 0.0);
+
+        let rustacean = caps(&["rust", "web_search"]);
+        let generalist = caps(&["code", "research"]);
+        let required = caps(&["code.rust", "research.web_search"]);
+        assert_eq!(registry.coverage(&rustacean, &required), 1.0);
+        assert_eq!(registry.coverage(&generalist, &required), 0.5);
+        assert_eq!(registry.coverage(&[], &[]), 1.0);
+        assert!(registry.covers(&rustacean, &caps(&["code"])));
+        assert_eq!(registry.missing(&generalist, &required), ["code.rust", "research.web_search"]);
+
+        assert!(registry.is_registered("code"));
+        assert!(registry.is_registered("Rust"));
+        assert!(registry.is_registered("code.python"));
+        assert!(!registry.is_registered("code.go"));
+        assert!(!registry.is_registered("code.rust.async"));
+    }
+
+    #[test]
+    fn entries_list_namespaces_aliases_and_holders() {
+        let registry = registry();
+        let held: HashMap<String, Vec<String>> = [
+            ("did:swarm:a".to_string(), caps(&["rust"])),
+            ("did:swarm:b".to_string(), caps(&["code", "cooking"])),
+        ]
+        .into_iter()
+        .collect();
+        let entries = registry.entries(&held);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["code", "code.python", "code.rust", "cooking", "research", "research.web_search"]
+        );
+        let code = &entries[0];
+        assert!(code.registered && code.parent.is_none());
+        assert_eq!(code.agents, ["did:swarm:a", "did:swarm:b"]);
+        let rust = &entries[2];
+        assert_eq!(rust.parent.as_deref(), Some("code"));
+        assert_eq!(rust.aliases, ["rust"]);
+        assert_eq!(rust.agents, ["did:swarm:a"]);
+        assert!(!entries[3].registered);
+    }
+}
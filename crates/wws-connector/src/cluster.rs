@@ -0,0 +1,190 @@
+//! Multi-agent mode: several local agents behind one connector.
+//!
+//! Each local agent registers under a name and gets its own Ed25519 key and
+//! DID. The connector keeps one P2P node for all of them, sends a keepalive
+//! per agent, routes task assignments to the right agent's queue and signs
+//! outgoing messages with the key of the agent they are sent for.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::SigningKey;
+use serde::Serialize;
+use wws_protocol::crypto::{derive_agent_id, generate_keypair, load_or_create_keypair, sign_message};
+use wws_protocol::{AgentId, SwarmMessage};
+
+/// Errors returned when registering a local agent.
+#[derive(Debug, thiserror::Error)]
+pub enum ClusterError {
+    #[error("Multi-agent mode is disabled (cluster.max_local_agents = 0)")]
+    Disabled,
+
+    #[error("Local agent limit of {0} reached")]
+    Full(usize),
+
+    #[error("Invalid local agent name {0:?}: use 1-64 letters, digits, '-' or '_'")]
+    InvalidName(String),
+
+    #[error("Failed to load key for local agent {0}: {1}")]
+    Key(String, String),
+}
+
+/// A local agent sharing this connector.
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalAgent {
+    pub agent_id: AgentId,
+    pub name: String,
+    #[serde(skip)]
+    signing_key: SigningKey,
+    pub registered_at: DateTime<Utc>,
+}
+
+impl LocalAgent {
+    /// Hex-encoded signature over the message's signing payload.
+    pub fn sign(&self, method: &str, params: &serde_json::Value) -> String {
+        let payload = SwarmMessage::signing_payload(method, params);
+        sign_message(&self.signing_key, &payload)
+            .to_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    pub fn verifying_key(&self) -> ed25519_dalek::VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Decrypt a result of `task_id` sealed to this agent.
+    pub fn open_result(
+        &self,
+        task_id: &str,
+        sealed: &wws_protocol::result_seal::SealedResult,
+    ) -> Result<Vec<u8>, wws_protocol::ProtocolError> {
+        sealed.open(task_id, self.agent_id.as_str(), &self.signing_key)
+    }
+
+    /// The agent's DID document, signed with its key.
+    pub fn did_document(&self, capabilities: Vec<String>) -> wws_protocol::DidDocument {
+        wws_protocol::DidDocument::new(self.agent_id.as_str(), &self.signing_key, Vec::new(), capabilities)
+    }
+}
+
+/// Local agents registered with this connector, keyed by DID.
+#[derive(Debug, Clone, Default)]
+pub struct AgentCluster {
+    max_agents: usize,
+    /// Where per-agent keys are kept; keys are ephemeral when unset.
+    key_dir: Option<PathBuf>,
+    agents: BTreeMap<String, LocalAgent>,
+}
+
+impl AgentCluster {
+    pub fn new(max_agents: usize, key_dir: Option<PathBuf>) -> Self {
+        Self {
+            max_agents,
+            key_dir,
+            agents: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_agents > 0
+    }
+
+    /// Register `name`, or return the agent already registered under it.
+    pub fn register(&mut self, name: &str, now: DateTime<Utc>) -> Result<&LocalAgent, ClusterError> {
+        if !self.is_enabled() {
+            return Err(ClusterError::Disabled);
+        }
+        let valid = !name.is_empty()
+            && name.len() <= 64
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ClusterError::InvalidName(name.to_string()));
+        }
+        if let Some(did) = self.agents.values().find(|a| a.name == name).map(|a| a.agent_id.0.clone()) {
+            return Ok(&self.agents[&did]);
+        }
+        if self.agents.len() >= self.max_agents {
+            return Err(ClusterError::Full(self.max_agents));
+        }
+
+        let signing_key = match &self.key_dir {
+            Some(dir) => load_or_create_keypair(&dir.join(format!("{}.key", name)))
+                .map_err(|e| ClusterError::Key(name.to_string(), e.to_string()))?,
+            None => generate_keypair(),
+        };
+        let did = derive_agent_id(&signing_key.verifying_key());
+        let agent = LocalAgent {
+            agent_id: AgentId::new(did.clone()),
+            name: name.to_string(),
+            signing_key,
+            registered_at: now,
+        };
+        Ok(self.agents.entry(did).or_insert(agent))
+    }
+
+    pub fn get(&self, agent_id: &str) -> Option<&LocalAgent> {
+        self.agents.get(agent_id)
+    }
+
+    pub fn contains(&self, agent_id: &str) -> bool {
+        self.agents.contains_key(agent_id)
+    }
+
+    pub fn agents(&self) -> impl Iterator<Item = &LocalAgent> {
+        self.agents.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agents_keep_their_did_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cluster = AgentCluster::new(2, Some(dir.path().to_path_buf()));
+        let first = cluster.register("worker-1", Utc::now()).unwrap().agent_id.clone();
+        assert_eq!(cluster.register("worker-1", Utc::now()).unwrap().agent_id, first);
+        cluster.register("worker-2", Utc::now()).unwrap();
+        assert!(matches!(cluster.register("worker-3", Utc::now()), Err(ClusterError::Full(2))));
+        assert!(matches!(cluster.register("bad name", Utc::now()), Err(ClusterError::InvalidName(_))));
+
+        let mut restarted = AgentCluster::new(2, Some(dir.path().to_path_buf()));
+        assert_eq!(restarted.register("worker-1", Utc::now()).unwrap().agent_id, first);
+    }
+
+    #[test]
+    fn signatures_verify_against_the_agent_key() {
+        let mut cluster = AgentCluster::new(1, None);
+        let agent = cluster.register("worker", Utc::now()).unwrap();
+        let params = serde_json::json!({"task_id": "t1"});
+        let sig_hex = agent.sign("task.submit_result", &params);
+        let bytes: Vec<u8> = (0..sig_hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&sig_hex[i..i + 2], 16).unwrap())
+            .collect();
+        let signature = ed25519_dalek::Signature::from_slice(&bytes).unwrap();
+        let payload = SwarmMessage::signing_payload("task.submit_result", &params);
+        assert!(wws_protocol::crypto::verify_signature(
+            &agent.signing_key.verifying_key(),
+            &payload,
+            &signature
+        )
+        .is_ok());
+        assert!(matches!(
+            AgentCluster::default().register("worker", Utc::now()),
+            Err(ClusterError::Disabled)
+        ));
+    }
+}
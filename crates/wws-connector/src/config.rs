@@ -10,6 +10,17 @@ use std::net::SocketAddr;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
+use wws_consensus::cascade::CascadeLimits;
+use wws_consensus::voting::{BallotMode, TieBreakPolicy, VotingConfig};
+use wws_protocol::{AssignmentStrategy, LayoutRole, QuorumPolicy, ShardStrategy};
+
+use crate::auth::RpcClientConfig;
+use crate::election::ElectionConfig;
+use crate::log_sinks::LogSinkConfig;
+use crate::names::NamesConfig;
+use crate::capabilities::CapabilityConfig;
+use crate::plan_policy::PlanPolicyConfig;
+use crate::webhooks::WebhookConfig;
 
 /// Top-level connector configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +34,12 @@ pub struct ConnectorConfig {
     /// RPC server configuration.
     #[serde(default)]
     pub rpc: RpcConfig,
+    /// gRPC server configuration.
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    /// MQTT bridge for agents without libp2p.
+    #[serde(default)]
+    pub mqtt: MqttConfig,
     /// Agent configuration.
     #[serde(default)]
     pub agent: AgentConfig,
@@ -35,9 +52,104 @@ pub struct ConnectorConfig {
     /// HTTP file server configuration for agent onboarding.
     #[serde(default)]
     pub file_server: FileServerConfig,
+    /// Plan-selection settings.
+    #[serde(default)]
+    pub consensus: ConsensusConfig,
     /// Agent identity configuration.
     #[serde(default)]
     pub identity: IdentityConfig,
+    /// Local artifact content storage.
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// Holonic board formation.
+    #[serde(default)]
+    pub board: BoardConfig,
+    /// Additional local agents served by this connector.
+    #[serde(default)]
+    pub cluster: ClusterConfig,
+    /// Named task templates for recurring jobs, keyed by template name.
+    #[serde(default)]
+    pub templates: std::collections::HashMap<String, TaskTemplate>,
+    /// Recurring task injections, keyed by schedule ID.
+    #[serde(default)]
+    pub schedules: std::collections::HashMap<String, ScheduleConfig>,
+    /// Rules proposed plans must satisfy, per swarm.
+    #[serde(default)]
+    pub plan_policy: PlanPolicyConfig,
+    /// Declared capabilities and their aliases.
+    #[serde(default)]
+    pub capabilities: CapabilityConfig,
+    /// Candidacy and voting in Tier-1 elections.
+    #[serde(default)]
+    pub election: ElectionConfig,
+    /// Guardians that settle disputed wws:// names.
+    #[serde(default)]
+    pub names: NamesConfig,
+    /// On-disk message trace.
+    #[serde(default)]
+    pub trace: TraceConfig,
+    /// URLs notified of swarm activity.
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    /// OpenTelemetry export of task lifecycle spans.
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// Built-in agent that answers tasks with canned plans and results.
+    #[serde(default)]
+    pub echo_agent: EchoAgentConfig,
+    /// Supervision when running with `--daemon`.
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+    /// Encrypted keystore for the identity key and swarm tokens.
+    #[serde(default)]
+    pub keystore: KeystoreConfig,
+}
+
+/// A cron schedule that injects a task each time it fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    /// Cron expression, either standard 5-field (`min hour dom month dow`,
+    /// Sunday 0 or 7) or with seconds in the `cron` crate's syntax.
+    pub cron: String,
+    /// Task injected on every run.
+    #[serde(flatten)]
+    pub task: TaskTemplate,
+}
+
+/// A reusable task definition that can be injected by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    /// Description given to every task created from this template.
+    pub description: String,
+    /// Tier level the task is injected at.
+    #[serde(default = "default_template_tier")]
+    pub tier_level: u32,
+    /// Capabilities an agent needs to work on the task.
+    #[serde(default)]
+    pub capabilities_required: Vec<String>,
+    /// Priority copied onto the injected task.
+    #[serde(default)]
+    pub priority: u8,
+}
+
+impl TaskTemplate {
+    /// Create a fresh task from this template in the given epoch.
+    pub fn instantiate(&self, epoch: u64) -> wws_protocol::Task {
+        let mut task = wws_protocol::Task::new(self.description.clone(), self.tier_level, epoch);
+        task.capabilities_required = self.capabilities_required.clone();
+        task.priority = self.priority;
+        task
+    }
+
+    /// Parameters for `swarm.inject_task` that reproduce this template.
+    pub fn to_inject_params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "description": self.description,
+            "tier_level": self.tier_level,
+            "capabilities_required": self.capabilities_required,
+            "priority": self.priority,
+        })
+    }
 }
 
 /// Agent identity configuration.
@@ -64,6 +176,170 @@ impl Default for IdentityConfig {
     }
 }
 
+/// Local content store configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Directory for artifact content, one file per CID. Content is kept in
+    /// memory when unset.
+    #[serde(default)]
+    pub content_dir: Option<std::path::PathBuf>,
+    /// Size cap for stored content in bytes. Least recently used content not
+    /// referenced by an incomplete task is evicted beyond it.
+    #[serde(default)]
+    pub max_content_bytes: Option<u64>,
+}
+
+/// On-disk persistence of the peer-to-peer message trace, as rotating
+/// JSONL files that the `trace` subcommand can query offline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceConfig {
+    /// Directory for trace files. The trace is kept in memory only when
+    /// unset.
+    #[serde(default)]
+    pub dir: Option<std::path::PathBuf>,
+    /// A new file is started once the current one would exceed this size.
+    #[serde(default = "default_trace_file_bytes")]
+    pub max_file_bytes: u64,
+    /// The oldest files are deleted beyond this total size.
+    #[serde(default = "default_trace_total_bytes")]
+    pub max_total_bytes: u64,
+    /// Files last written longer ago than this are deleted.
+    #[serde(default = "default_trace_age_secs")]
+    pub max_age_secs: u64,
+    /// Also write the raw content of each handled inbound message, so the
+    /// run can be reproduced with `wws-connector replay`. Payloads of a
+    /// private swarm are written decrypted.
+    #[serde(default)]
+    pub record_payloads: bool,
+}
+
+impl Default for TraceConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            max_file_bytes: default_trace_file_bytes(),
+            max_total_bytes: default_trace_total_bytes(),
+            max_age_secs: default_trace_age_secs(),
+            record_payloads: false,
+        }
+    }
+}
+
+/// OpenTelemetry export of task lifecycle spans over OTLP/HTTP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    /// OTLP/HTTP traces URL, e.g. `http://localhost:4318/v1/traces`.
+    /// Nothing is exported when unset.
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// `service.name` of the exported spans.
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            service_name: default_service_name(),
+        }
+    }
+}
+
+/// Built-in echo agent that proposes, votes and submits canned results, for
+/// trying the consensus pipeline on one machine without a real agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EchoAgentConfig {
+    /// Whether the echo agent runs.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Extra local agents (`echo-1`, `echo-2`, ...) the echo agent registers
+    /// and acts for besides the connector's own agent, so plans have
+    /// competitors and votes have a quorum.
+    #[serde(default)]
+    pub local_agents: usize,
+    /// Seconds between task polls.
+    #[serde(default = "default_echo_poll_interval")]
+    pub poll_interval_secs: u64,
+}
+
+impl Default for EchoAgentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            local_agents: 0,
+            poll_interval_secs: default_echo_poll_interval(),
+        }
+    }
+}
+
+/// Daemon mode: where the pid file goes and how quickly a failed
+/// connector is restarted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonConfig {
+    /// Pid file written while the daemon runs. None writes no pid file.
+    #[serde(default)]
+    pub pid_file: Option<std::path::PathBuf>,
+    /// Seconds before the first restart after a failure; doubles with each
+    /// further failure.
+    #[serde(default = "default_restart_backoff")]
+    pub restart_backoff_secs: u64,
+    /// Longest pause between restarts.
+    #[serde(default = "default_max_restart_backoff")]
+    pub max_restart_backoff_secs: u64,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            pid_file: None,
+            restart_backoff_secs: default_restart_backoff(),
+            max_restart_backoff_secs: default_max_restart_backoff(),
+        }
+    }
+}
+
+/// Encrypted keystore (see [`crate::keystore`]). When `path` is set, the
+/// identity key is read from the keystore instead of `[identity] path`,
+/// and a swarm token missing from `[swarm]` is looked up there.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeystoreConfig {
+    /// Keystore file. None keeps secrets in their plaintext files.
+    #[serde(default)]
+    pub path: Option<std::path::PathBuf>,
+    /// File holding the passphrase, for unattended starts. Without it the
+    /// passphrase comes from `WWS_KEYSTORE_PASSPHRASE` or a prompt.
+    #[serde(default)]
+    pub passphrase_file: Option<std::path::PathBuf>,
+}
+
+/// Holonic board formation: how long a chair waits for `board.accept`
+/// responses and how small a board may be.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardConfig {
+    /// Seconds the chair collects acceptances after sending `board.invite`.
+    #[serde(default = "default_board_acceptance_timeout")]
+    pub acceptance_timeout_secs: u64,
+    /// Fewest accepted members a board may form with; below this the board
+    /// is dissolved and the chair proceeds alone.
+    #[serde(default = "default_min_board_size")]
+    pub min_size: usize,
+}
+
+/// Multi-agent mode: several local agents, each with its own DID and task
+/// queue, sharing this connector's P2P node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfig {
+    /// Most local agents that may register besides the connector's own
+    /// identity. Multi-agent mode is off when 0.
+    #[serde(default)]
+    pub max_local_agents: usize,
+    /// Directory holding one Ed25519 key file per local agent name, so an
+    /// agent keeps its DID across restarts.
+    #[serde(default = "default_cluster_key_dir")]
+    pub key_dir: std::path::PathBuf,
+}
+
 /// HTTP file server configuration for serving agent onboarding docs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileServerConfig {
@@ -84,6 +360,11 @@ pub struct NetworkConfig {
     /// Bootstrap peer multiaddresses.
     #[serde(default)]
     pub bootstrap_peers: Vec<String>,
+    /// Public multiaddrs this node is reachable at, e.g. a port forwarded
+    /// to `listen_addr`. Advertised to peers, in DHT records and in swarm
+    /// announcements.
+    #[serde(default)]
+    pub external_addrs: Vec<String>,
     /// Whether mDNS local discovery is enabled.
     #[serde(default = "default_true")]
     pub mdns_enabled: bool,
@@ -96,7 +377,8 @@ pub struct NetworkConfig {
     /// Enable QUIC transport alongside TCP.
     #[serde(default = "default_true")]
     pub enable_quic: bool,
-    /// Enable circuit relay client (for NAT traversal).
+    /// Enable circuit relay client (for NAT traversal): listen through a
+    /// connected relay while AutoNAT finds the node private.
     #[serde(default = "default_true")]
     pub enable_relay_client: bool,
     /// Enable relay server (bootstrap nodes set this true automatically).
@@ -105,6 +387,14 @@ pub struct NetworkConfig {
     /// Enable hole-punching upgrade (DCUtR).
     #[serde(default = "default_true")]
     pub enable_dcutr: bool,
+    /// Reject inbound messages whose params carry fields this version of
+    /// the protocol does not define, instead of ignoring them.
+    #[serde(default)]
+    pub deny_unknown_params: bool,
+    /// Bytes per second in and out of one topic above which the node logs
+    /// a warning. Unset never warns.
+    #[serde(default)]
+    pub topic_rate_warn_bytes_per_sec: Option<u64>,
 }
 
 /// Hierarchy and epoch configuration.
@@ -113,15 +403,30 @@ pub struct HierarchyConfig {
     /// Branching factor (k).
     #[serde(default = "default_branching_factor")]
     pub branching_factor: u32,
-    /// Epoch duration in seconds.
+    /// Epoch duration in seconds; the first epoch's duration when
+    /// `adaptive_epochs` is on.
     #[serde(default = "default_epoch_duration")]
     pub epoch_duration_secs: u64,
+    /// Size each epoch from the membership churn and Tier-1 leader failures
+    /// of the previous one.
+    #[serde(default)]
+    pub adaptive_epochs: bool,
+    /// Shortest epoch adaptive sizing may choose, in seconds.
+    #[serde(default = "default_min_epoch_duration")]
+    pub min_epoch_duration_secs: u64,
+    /// Longest epoch adaptive sizing may choose, in seconds.
+    #[serde(default = "default_max_epoch_duration")]
+    pub max_epoch_duration_secs: u64,
     /// Leader timeout in seconds.
     #[serde(default = "default_leader_timeout")]
     pub leader_timeout_secs: u64,
     /// Keep-alive interval in seconds.
     #[serde(default = "default_keepalive_interval")]
     pub keepalive_interval_secs: u64,
+    /// Fraction of active members that must vanish at once for the node
+    /// to consider itself partitioned from them.
+    #[serde(default = "default_partition_threshold")]
+    pub partition_threshold: f64,
 }
 
 /// JSON-RPC server configuration.
@@ -136,6 +441,50 @@ pub struct RpcConfig {
     /// Request timeout in seconds.
     #[serde(default = "default_rpc_timeout")]
     pub request_timeout_secs: u64,
+    /// Clients allowed on the RPC port, each with its token and role. The
+    /// port is open to anyone who can reach it when empty.
+    #[serde(default)]
+    pub clients: Vec<RpcClientConfig>,
+}
+
+/// gRPC server configuration. The server mirrors the JSON-RPC methods
+/// and admits the clients configured under `[rpc]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcConfig {
+    /// Whether the gRPC server is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address to bind the gRPC server to.
+    #[serde(default = "default_grpc_addr")]
+    pub bind_addr: String,
+}
+
+/// `[mqtt]`: the MQTT bridge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttConfig {
+    /// Whether the bridge runs.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Broker address as `host:port`.
+    #[serde(default = "default_broker")]
+    pub broker: String,
+    /// Client ID the bridge connects with.
+    #[serde(default = "default_client_id")]
+    pub client_id: String,
+    /// Broker login, if it requires one.
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// First level of every bridged topic.
+    #[serde(default = "default_topic_prefix")]
+    pub topic_prefix: String,
+    /// Local agents the bridge registers and acts for.
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// Seconds between task polls.
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_secs: u64,
 }
 
 /// Agent bridge configuration.
@@ -150,6 +499,14 @@ pub struct AgentConfig {
     /// Whether MCP compatibility mode is enabled.
     #[serde(default)]
     pub mcp_compatible: bool,
+    /// Tasks the agent can work on at once, announced in keep-alives so
+    /// coordinators do not overload it. Unset declares no limit.
+    #[serde(default)]
+    pub max_concurrent_tasks: Option<u32>,
+    /// Pin the agent to Tier-1 ("coordinator") or keep it out of
+    /// coordination ("executor"). Unset lets the layout place it.
+    #[serde(default)]
+    pub role: Option<LayoutRole>,
 }
 
 /// Logging configuration.
@@ -161,6 +518,9 @@ pub struct LoggingConfig {
     /// Whether to output JSON-formatted logs.
     #[serde(default)]
     pub json_format: bool,
+    /// Durable copies of the event log, each taking a set of categories.
+    #[serde(default)]
+    pub sinks: Vec<LogSinkConfig>,
 }
 
 /// Swarm identity and multi-swarm configuration.
@@ -181,6 +541,118 @@ pub struct SwarmConfig {
     /// Interval in seconds between swarm announcements on the DHT.
     #[serde(default = "default_swarm_announce_interval")]
     pub announce_interval_secs: u64,
+    /// Encrypt the swarm's topics under per-epoch keys derived from
+    /// `token`, rekeyed at every epoch transition. Needs `token`.
+    #[serde(default)]
+    pub encrypt_topics: bool,
+    /// Active members above which the swarm's Tier-1 leaders split it into
+    /// child swarms (default 4000). 0 never splits.
+    #[serde(default = "default_shard_threshold")]
+    pub shard_threshold: usize,
+    /// How members are spread over the child swarms: "hash" (default) or
+    /// "geo", by Tier-1 branch.
+    #[serde(default)]
+    pub shard_strategy: ShardStrategy,
+}
+
+/// Plan-selection settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsensusConfig {
+    /// Seconds an operator has to approve the winning plan of a task
+    /// injected with `requires_approval` (default 600).
+    #[serde(default)]
+    pub plan_approval_timeout_secs: Option<u64>,
+    /// IRV tie-break strategy: "lowest_first_preference", "critic_score",
+    /// "random" or "escalate_to_chair".
+    #[serde(default)]
+    pub tie_break: TieBreakPolicy,
+    /// Fixed seed for the "random" tie-break (derived from the task ID and
+    /// epoch when unset, so every member eliminates the same plan).
+    #[serde(default)]
+    pub tie_break_seed: Option<u64>,
+    /// Swarm-wide quorum policy; tasks may override it individually.
+    /// Defaults to every active member of the task's tier.
+    #[serde(default)]
+    pub quorum: QuorumPolicy,
+    /// Probability (0.0-1.0) that a completed subtask is re-executed by a
+    /// second agent to verify its result. Disabled by default.
+    #[serde(default)]
+    pub verification_rate: f64,
+    /// Reputation points this connector's agents stake on each plan they
+    /// propose, unless a proposal names its own bond. Disabled (0) by
+    /// default.
+    #[serde(default)]
+    pub proposal_bond: u64,
+    /// Ballot mode: "open", or "blind" to have voters commit to a ballot
+    /// while voting is open and reveal it afterwards.
+    #[serde(default)]
+    pub ballot_mode: BallotMode,
+    /// Seconds blind ballots have to be revealed once the commitments
+    /// close. Defaults to the voting timeout.
+    #[serde(default)]
+    pub reveal_timeout_secs: Option<u64>,
+    /// Deepest sub-holon a root task may spawn through recursive
+    /// decomposition (default 3).
+    #[serde(default)]
+    pub max_cascade_depth: Option<u32>,
+    /// Most sub-holons a single task may spawn (default 10).
+    #[serde(default)]
+    pub max_cascade_fan_out: Option<usize>,
+    /// Most sub-holons in one root task's cascade tree (default 50).
+    #[serde(default)]
+    pub max_cascade_nodes: Option<usize>,
+    /// Seconds a task's proposal and voting round may stay open before it
+    /// is abandoned: its consensus state is dropped, the task marked
+    /// failed and its topics released (default 3600).
+    #[serde(default)]
+    pub max_round_age_secs: Option<u64>,
+    /// Times a task that draws no plan by the proposal timeout is tendered
+    /// before it is marked failed (default 3); 1 never re-tenders.
+    #[serde(default)]
+    pub max_tender_attempts: Option<u32>,
+    /// Move a re-tendered task one tier up, toward Tier-1, in case its own
+    /// tier has no one to propose.
+    #[serde(default)]
+    pub retender_relax_tier: bool,
+    /// How directly assigned subtasks are spread over subordinates:
+    /// "round_robin", "least_loaded" (default), "capability_score",
+    /// "reputation_weighted" or "random_seeded". Tasks may override it
+    /// individually.
+    #[serde(default)]
+    pub assignment: AssignmentStrategy,
+    /// Fixed seed for "random_seeded" assignment (derived from the task ID
+    /// when unset).
+    #[serde(default)]
+    pub assignment_seed: Option<u64>,
+    /// Seats on each task's sortition committee. When a tier has more
+    /// active members, only a committee of this size drawn from the task's
+    /// epoch and ID proposes and votes, weighted by the task's weighted
+    /// quorum if it has one. 0 (the default) lets every member take part.
+    #[serde(default)]
+    pub committee_size: usize,
+}
+
+impl ConsensusConfig {
+    /// Voting engine configuration for a new plan-selection round.
+    pub fn voting_config(&self) -> VotingConfig {
+        VotingConfig {
+            tie_break: self.tie_break,
+            tie_break_seed: self.tie_break_seed,
+            ballot_mode: self.ballot_mode,
+            ..VotingConfig::default()
+        }
+    }
+
+    /// Limits on recursive decomposition; subtasks that would exceed them
+    /// are assigned directly instead of spawning a sub-holon.
+    pub fn cascade_limits(&self) -> CascadeLimits {
+        let defaults = CascadeLimits::default();
+        CascadeLimits {
+            max_depth: self.max_cascade_depth.unwrap_or(defaults.max_depth),
+            max_fan_out: self.max_cascade_fan_out.unwrap_or(defaults.max_fan_out),
+            max_nodes: self.max_cascade_nodes.unwrap_or(defaults.max_nodes),
+        }
+    }
 }
 
 // -- Defaults --
@@ -200,15 +672,39 @@ fn default_branching_factor() -> u32 {
 fn default_epoch_duration() -> u64 {
     wws_protocol::DEFAULT_EPOCH_DURATION_SECS
 }
+fn default_min_epoch_duration() -> u64 {
+    wws_protocol::DEFAULT_EPOCH_DURATION_SECS / 12
+}
+fn default_max_epoch_duration() -> u64 {
+    wws_protocol::DEFAULT_EPOCH_DURATION_SECS * 4
+}
 fn default_leader_timeout() -> u64 {
     wws_protocol::LEADER_TIMEOUT_SECS
 }
 fn default_keepalive_interval() -> u64 {
     wws_protocol::KEEPALIVE_INTERVAL_SECS
 }
+fn default_partition_threshold() -> f64 {
+    crate::partition::DEFAULT_PARTITION_THRESHOLD
+}
 fn default_rpc_addr() -> String {
     "127.0.0.1:9370".to_string()
 }
+fn default_grpc_addr() -> String {
+    "127.0.0.1:9372".to_string()
+}
+fn default_broker() -> String {
+    "127.0.0.1:1883".to_string()
+}
+fn default_client_id() -> String {
+    "wws-connector".to_string()
+}
+fn default_topic_prefix() -> String {
+    "wws".to_string()
+}
+fn default_poll_interval() -> u64 {
+    2
+}
 fn default_max_connections() -> usize {
     10
 }
@@ -235,9 +731,45 @@ fn default_swarm_name() -> String {
 fn default_swarm_announce_interval() -> u64 {
     wws_protocol::SWARM_ANNOUNCE_INTERVAL_SECS
 }
+fn default_shard_threshold() -> usize {
+    crate::shard::DEFAULT_SHARD_THRESHOLD
+}
 fn default_file_server_addr() -> String {
     "127.0.0.1:9371".to_string()
 }
+fn default_board_acceptance_timeout() -> u64 {
+    5
+}
+fn default_min_board_size() -> usize {
+    3
+}
+fn default_cluster_key_dir() -> std::path::PathBuf {
+    default_identity_dir().join("agents")
+}
+fn default_trace_file_bytes() -> u64 {
+    8 * 1024 * 1024
+}
+fn default_trace_total_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+fn default_trace_age_secs() -> u64 {
+    7 * 24 * 3600
+}
+fn default_service_name() -> String {
+    "wws-connector".to_string()
+}
+fn default_echo_poll_interval() -> u64 {
+    2
+}
+fn default_restart_backoff() -> u64 {
+    1
+}
+fn default_max_restart_backoff() -> u64 {
+    60
+}
+fn default_template_tier() -> u32 {
+    1
+}
 
 // -- Trait impls --
 
@@ -249,6 +781,9 @@ impl Default for SwarmConfig {
             name: default_swarm_name(),
             is_public: true,
             announce_interval_secs: default_swarm_announce_interval(),
+            encrypt_topics: false,
+            shard_threshold: default_shard_threshold(),
+            shard_strategy: ShardStrategy::default(),
         }
     }
 }
@@ -262,17 +797,53 @@ impl Default for FileServerConfig {
     }
 }
 
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self {
+            acceptance_timeout_secs: default_board_acceptance_timeout(),
+            min_size: default_min_board_size(),
+        }
+    }
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            max_local_agents: 0,
+            key_dir: default_cluster_key_dir(),
+        }
+    }
+}
+
 impl Default for ConnectorConfig {
     fn default() -> Self {
         Self {
             network: NetworkConfig::default(),
             hierarchy: HierarchyConfig::default(),
             rpc: RpcConfig::default(),
+            grpc: GrpcConfig::default(),
+            mqtt: MqttConfig::default(),
             agent: AgentConfig::default(),
             logging: LoggingConfig::default(),
             swarm: SwarmConfig::default(),
             file_server: FileServerConfig::default(),
+            consensus: ConsensusConfig::default(),
             identity: IdentityConfig::default(),
+            storage: StorageConfig::default(),
+            board: BoardConfig::default(),
+            cluster: ClusterConfig::default(),
+            templates: std::collections::HashMap::new(),
+            schedules: std::collections::HashMap::new(),
+            plan_policy: PlanPolicyConfig::default(),
+            capabilities: CapabilityConfig::default(),
+            election: ElectionConfig::default(),
+            names: NamesConfig::default(),
+            trace: TraceConfig::default(),
+            webhooks: Vec::new(),
+            telemetry: TelemetryConfig::default(),
+            echo_agent: EchoAgentConfig::default(),
+            daemon: DaemonConfig::default(),
+            keystore: KeystoreConfig::default(),
         }
     }
 }
@@ -282,6 +853,7 @@ impl Default for NetworkConfig {
         Self {
             listen_addr: default_listen_addr(),
             bootstrap_peers: Vec::new(),
+            external_addrs: Vec::new(),
             mdns_enabled: true,
             idle_connection_timeout_secs: default_idle_timeout(),
             bootstrap_mode: false,
@@ -289,6 +861,8 @@ impl Default for NetworkConfig {
             enable_relay_client: true,
             enable_relay_server: false,
             enable_dcutr: true,
+            deny_unknown_params: false,
+            topic_rate_warn_bytes_per_sec: None,
         }
     }
 }
@@ -298,8 +872,12 @@ impl Default for HierarchyConfig {
         Self {
             branching_factor: default_branching_factor(),
             epoch_duration_secs: default_epoch_duration(),
+            adaptive_epochs: false,
+            min_epoch_duration_secs: default_min_epoch_duration(),
+            max_epoch_duration_secs: default_max_epoch_duration(),
             leader_timeout_secs: default_leader_timeout(),
             keepalive_interval_secs: default_keepalive_interval(),
+            partition_threshold: default_partition_threshold(),
         }
     }
 }
@@ -310,6 +888,31 @@ impl Default for RpcConfig {
             bind_addr: default_rpc_addr(),
             max_connections: default_max_connections(),
             request_timeout_secs: default_rpc_timeout(),
+            clients: Vec::new(),
+        }
+    }
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_addr: default_grpc_addr(),
+        }
+    }
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            broker: default_broker(),
+            client_id: default_client_id(),
+            username: None,
+            password: None,
+            topic_prefix: default_topic_prefix(),
+            agents: Vec::new(),
+            poll_interval_secs: default_poll_interval(),
         }
     }
 }
@@ -320,6 +923,8 @@ impl Default for AgentConfig {
             name: default_agent_name(),
             capabilities: Vec::new(),
             mcp_compatible: false,
+            max_concurrent_tasks: None,
+            role: None,
         }
     }
 }
@@ -329,6 +934,7 @@ impl Default for LoggingConfig {
         Self {
             level: default_log_level(),
             json_format: false,
+            sinks: Vec::new(),
         }
     }
 }
@@ -374,6 +980,12 @@ impl ConnectorConfig {
         if let Ok(val) = std::env::var("WWS_RPC_BIND_ADDR") {
             self.rpc.bind_addr = val;
         }
+        if let Ok(val) = std::env::var("WWS_GRPC_BIND_ADDR") {
+            self.grpc.bind_addr = val;
+        }
+        if let Ok(val) = std::env::var("WWS_GRPC_ENABLED") {
+            self.grpc.enabled = val == "true" || val == "1";
+        }
         if let Ok(val) = std::env::var("WWS_LOG_LEVEL") {
             self.logging.level = val;
         }
@@ -393,6 +1005,9 @@ impl ConnectorConfig {
         if let Ok(val) = std::env::var("WWS_BOOTSTRAP_PEERS") {
             self.network.bootstrap_peers = val.split(',').map(|s| s.trim().to_string()).collect();
         }
+        if let Ok(val) = std::env::var("WWS_EXTERNAL_ADDRS") {
+            self.network.external_addrs = val.split(',').map(|s| s.trim().to_string()).collect();
+        }
         if let Ok(val) = std::env::var("WWS_SWARM_ID") {
             self.swarm.swarm_id = val;
         }
@@ -414,9 +1029,15 @@ impl ConnectorConfig {
         if let Ok(val) = std::env::var("WWS_IDENTITY_PATH") {
             self.identity.path = std::path::PathBuf::from(val);
         }
+        if let Ok(val) = std::env::var("WWS_KEYSTORE_PATH") {
+            self.keystore.path = Some(std::path::PathBuf::from(val));
+        }
         if let Ok(val) = std::env::var("WWS_NAME") {
             self.identity.wws_name = Some(val);
         }
+        if let Ok(val) = std::env::var("WWS_OTLP_ENDPOINT") {
+            self.telemetry.otlp_endpoint = Some(val);
+        }
     }
 
     /// Parse the RPC bind address into a SocketAddr.
@@ -424,3 +1045,189 @@ impl ConnectorConfig {
         Ok(self.rpc.bind_addr.parse()?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_sinks::LogSinkKind;
+
+    #[test]
+    fn templates_parse_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [templates.nightly-report]
+            description = "Summarise yesterday's results"
+            capabilities_required = ["summarization"]
+            priority = 3
+
+            [templates.triage]
+            description = "Triage open issues"
+            tier_level = 2
+            "#,
+        )
+        .unwrap();
+
+        let nightly = &config.templates["nightly-report"];
+        assert_eq!(nightly.tier_level, 1);
+        assert_eq!(nightly.priority, 3);
+        assert_eq!(config.templates["triage"].tier_level, 2);
+    }
+
+    #[test]
+    fn consensus_tie_break_parses_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [consensus]
+            tie_break = "random"
+            tie_break_seed = 42
+            "#,
+        )
+        .unwrap();
+
+        let voting = config.consensus.voting_config();
+        assert_eq!(voting.tie_break, TieBreakPolicy::Random);
+        assert_eq!(voting.tie_break_seed, Some(42));
+        assert_eq!(
+            ConnectorConfig::default().consensus.tie_break,
+            TieBreakPolicy::LowestFirstPreference
+        );
+    }
+
+    #[test]
+    fn consensus_ballot_mode_parses_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [consensus]
+            ballot_mode = "blind"
+            reveal_timeout_secs = 20
+            plan_approval_timeout_secs = 120
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.consensus.voting_config().ballot_mode, BallotMode::Blind);
+        assert_eq!(config.consensus.reveal_timeout_secs, Some(20));
+        assert_eq!(config.consensus.plan_approval_timeout_secs, Some(120));
+        assert_eq!(
+            ConnectorConfig::default().consensus.voting_config().ballot_mode,
+            BallotMode::Open
+        );
+    }
+
+    #[test]
+    fn consensus_cascade_limits_parse_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [consensus]
+            max_cascade_depth = 2
+            max_cascade_nodes = 8
+            "#,
+        )
+        .unwrap();
+
+        let limits = config.consensus.cascade_limits();
+        assert_eq!(limits.max_depth, 2);
+        assert_eq!(limits.max_nodes, 8);
+        assert_eq!(limits.max_fan_out, CascadeLimits::default().max_fan_out);
+    }
+
+    #[test]
+    fn consensus_quorum_parses_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [consensus.quorum]
+            kind = "tier_fraction"
+            fraction = 0.66
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.consensus.quorum,
+            QuorumPolicy::TierFraction { fraction: 0.66 }
+        );
+        assert_eq!(ConnectorConfig::default().consensus.quorum, QuorumPolicy::default());
+    }
+
+    #[test]
+    fn consensus_assignment_parses_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [consensus]
+            assignment = "capability_score"
+            assignment_seed = 42
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.consensus.assignment, AssignmentStrategy::CapabilityScore);
+        assert_eq!(config.consensus.assignment_seed, Some(42));
+        assert_eq!(
+            ConnectorConfig::default().consensus.assignment,
+            AssignmentStrategy::LeastLoaded
+        );
+    }
+
+    #[test]
+    fn log_sinks_parse_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [[logging.sinks]]
+            kind = "jsonl"
+            dir = "/var/log/wws"
+            categories = ["ERR", "TASK"]
+            max_file_bytes = 1024
+
+            [[logging.sinks]]
+            kind = "syslog"
+            "#,
+        )
+        .unwrap();
+
+        let sinks = &config.logging.sinks;
+        assert_eq!(sinks[0].kind, LogSinkKind::Jsonl);
+        assert_eq!(sinks[0].file.dir.as_deref(), Some(Path::new("/var/log/wws")));
+        assert_eq!(sinks[0].file.max_file_bytes, 1024);
+        assert_eq!(sinks[0].file.max_age_secs, default_trace_age_secs());
+        assert_eq!(sinks[1].address, "127.0.0.1:514");
+        assert!(sinks[1].categories.is_empty());
+    }
+
+    #[test]
+    fn schedules_parse_from_toml() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+            [schedules.nightly]
+            cron = "0 2 * * *"
+            description = "Summarise yesterday's results"
+            priority = 1
+            "#,
+        )
+        .unwrap();
+
+        let nightly = &config.schedules["nightly"];
+        assert_eq!(nightly.cron, "0 2 * * *");
+        assert_eq!(nightly.task.description, "Summarise yesterday's results");
+        assert_eq!(nightly.task.tier_level, 1);
+        assert_eq!(nightly.task.priority, 1);
+    }
+
+    #[test]
+    fn template_instantiates_fresh_tasks() {
+        let template = TaskTemplate {
+            description: "Triage open issues".to_string(),
+            tier_level: 2,
+            capabilities_required: vec!["github".to_string()],
+            priority: 5,
+        };
+
+        let a = template.instantiate(7);
+        let b = template.instantiate(7);
+        assert_ne!(a.task_id, b.task_id);
+        assert_eq!(a.description, "Triage open issues");
+        assert_eq!(a.tier_level, 2);
+        assert_eq!(a.epoch, 7);
+        assert_eq!(a.capabilities_required, vec!["github"]);
+        assert_eq!(a.priority, 5);
+    }
+}
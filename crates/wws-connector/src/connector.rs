@@ -7,14 +7,16 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use futures_util::stream::{FuturesOrdered, StreamExt};
 use tokio::sync::{mpsc, RwLock};
 
-use wws_consensus::{CascadeEngine, RfpCoordinator, VotingEngine};
+use wws_consensus::{CascadeEngine, ConsensusError, RfpCoordinator, VotingEngine};
 use wws_hierarchy::{
     EpochManager, GeoCluster, PyramidAllocator, SuccessionManager,
     elections::ElectionManager,
     epoch::EpochConfig,
     pyramid::PyramidConfig,
+    rendezvous::{HierarchyAssignment, HierarchyChange},
 };
 use wws_network::{
     Multiaddr, PeerId,
@@ -23,16 +25,82 @@ use wws_network::{
     transport::TransportConfig,
 };
 use wws_protocol::*;
+use wws_protocol::board_channel::GroupKey;
+use wws_protocol::topic_keys::{EpochSeed, SealedEpochSeed, TopicKeyring};
+use wws_state::granularity::RecursionInputs;
 use wws_state::{ContentStore, GranularityAlgorithm, MerkleDag, OrSet};
 
-use crate::config::ConnectorConfig;
+use crate::board::{BoardChannel, BoardFormation};
+use crate::bonds::{ProposalBonds, SlashReason};
+use crate::cluster::AgentCluster;
+use crate::config::{BoardConfig, ConnectorConfig, TaskTemplate};
+use crate::delivery::TaskDeliveries;
+use crate::reload::ConfigReload;
+use crate::did::{DidDirectory, DID_REPUBLISH_INTERVAL_SECS};
+use crate::election::ElectionConfig;
+use crate::inbound::{DecodedMessage, InboundMessage, InboundParams, InboundRejection, MAX_PENDING_DECODES};
+use crate::membership::SwarmMembership;
+use crate::migration::{MigrationOutcome, MigrationReport};
+use crate::shard::{FederationRelation, ShardOutcome};
+use crate::names::NameDirectory;
+use crate::capabilities::CapabilityRegistry;
+use crate::plan_policy::PlanPolicyConfig;
+use crate::scheduler::{Scheduler, TaskSchedule};
+use wws_protocol::result_seal::SealedResult;
+use crate::task_context::{
+    AncestorContext, AttachmentContext, ContextBudget, ContextLimits, PlanContext, SiblingContext,
+    TaskContext, MAX_CONTEXT_ANCESTORS,
+};
+use crate::storage::{ArtifactStore, Stores, TaskStore, TimelineStore, STORE_FLUSH_INTERVAL_SECS};
+use crate::swarm_params::{ParamsDecision, ParamsProposal};
+use crate::transcript::{RunTranscript, TRANSCRIPT_FORMAT_VERSION};
+use crate::work_steal::{WorkSteal, WorkStealing, STEAL_PLAN_ID};
+use crate::telemetry::StageSpan;
+use crate::log_sinks::LogSink;
+use crate::replay::RecordedMessage;
+use crate::trace_log::TraceLog;
+use crate::webhooks::{Delivery, WebhookEvent, Webhooks};
 use crate::tui::{LogCategory, LogEntry};
+use crate::verification::{
+    DisputeError, ResultVerification, VerificationStatus, DISPUTE_VOTE_TIMEOUT_SECS,
+    VERIFICATION_FAIL_POINTS, VERIFICATION_PASS_POINTS, VERIFICATION_PLAN_ID,
+};
 
 const ACTIVE_MEMBER_STALENESS_SECS: u64 = 45;
 const PARTICIPATION_POLL_STALENESS_SECS: u64 = 180;
 const EXECUTION_ASSIGNMENT_TIMEOUT_SECS: i64 = 420;
+/// Share of the parent's remaining time a subtask gets; the rest is left
+/// for aggregating the subtask results.
+const SUBTASK_DEADLINE_SHARE: f64 = 0.8;
+const MIN_SUBTASK_DEADLINE_SECS: i64 = 30;
 const PROPOSAL_STAGE_TIMEOUT_SECS: i64 = 30;
 const VOTING_STAGE_TIMEOUT_SECS: i64 = 30;
+/// Default for `consensus.plan_approval_timeout_secs`.
+pub const DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS: u64 = 600;
+const CONTENT_GC_INTERVAL_SECS: u64 = 60;
+/// Seconds between audits of required topic subscriptions.
+const SUBSCRIPTION_AUDIT_INTERVAL_SECS: u64 = 30;
+/// Method of the reply to a direct request; params carry `accepted` and,
+/// when the request was rejected, `reason`.
+pub const DIRECT_ACK_METHOD: &str = "direct.ack";
+const PREREQUISITE_CONTEXT_MAX_CHARS: usize = 2000;
+/// Total attachment content sent inline with a task injection. GossipSub
+/// drops messages over 64 KiB, so larger content travels by CID only.
+pub const MAX_INLINE_ATTACHMENT_BYTES: usize = 32 * 1024;
+const DEAD_LETTER_CAPACITY: usize = 500;
+/// Addresses taken from one swarm announcement.
+const MAX_ANNOUNCED_ADDRS: usize = 8;
+/// Default for `consensus.max_round_age_secs`.
+pub const DEFAULT_MAX_ROUND_AGE_SECS: u64 = 3600;
+/// Default for `consensus.max_tender_attempts`.
+pub const DEFAULT_MAX_TENDER_ATTEMPTS: u32 = 3;
+/// A chair sends at most one task state snapshot per task in this window;
+/// the snapshot is broadcast, so it serves every joiner asking meanwhile.
+const TASK_STATE_REPLY_INTERVAL_SECS: i64 = 5;
+/// How long a task state request waits for its snapshot before it may be
+/// sent again.
+const TASK_STATE_REQUEST_TTL_SECS: i64 = 60;
+const DEAD_LETTER_PAYLOAD_MAX_BYTES: usize = 16 * 1024;
 
 /// Information about a known swarm tracked by this connector.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -58,6 +126,9 @@ pub struct TaskTimelineEvent {
     pub stage: String,
     pub detail: String,
     pub actor: Option<String>,
+    /// Hybrid logical clock of the event; orders events across nodes.
+    #[serde(default)]
+    pub hlc: HlcTimestamp,
 }
 
 /// Debug trace record for peer-to-peer traffic.
@@ -71,6 +142,60 @@ pub struct MessageTraceEvent {
     pub task_id: Option<String>,
     pub size_bytes: usize,
     pub outcome: String,
+    /// Raw message of a handled inbound event, written to the trace files
+    /// with `[trace] record_payloads` for `wws-connector replay`. Never
+    /// kept in memory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+}
+
+/// An inbound message that was dropped instead of processed, kept for
+/// diagnosing interop problems with other agent implementations.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DeadLetter {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub topic: String,
+    pub source: String,
+    pub method: Option<String>,
+    pub reason: String,
+    pub size_bytes: usize,
+    /// Raw payload (lossy UTF-8), cut at `DEAD_LETTER_PAYLOAD_MAX_BYTES`.
+    pub payload: String,
+    pub truncated: bool,
+    /// Each field that failed strict decoding, for `invalid_params`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_errors: Vec<FieldError>,
+}
+
+/// Task-flow stage counts, as shown in the operator console's flow panel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct FlowCounts {
+    pub injected: usize,
+    pub proposed: usize,
+    pub commits: usize,
+    pub reveals: usize,
+    pub votes: usize,
+    pub selected: usize,
+    pub subtasks: usize,
+    pub assigned: usize,
+    pub results: usize,
+}
+
+impl FlowCounts {
+    fn record(&mut self, stage: &str) {
+        match stage {
+            "injected" => self.injected += 1,
+            "proposed" => self.proposed += 1,
+            "proposal_commit" => self.commits += 1,
+            "proposal_reveal" => self.reveals += 1,
+            "vote_recorded" => self.votes += 1,
+            "plan_selected" => self.selected += 1,
+            "subtask_created" => self.subtasks += 1,
+            "subtask_assigned" | "assigned" => self.assigned += 1,
+            "result_submitted" => self.results += 1,
+            _ => {}
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -80,6 +205,89 @@ pub struct AgentActivity {
     pub plans_proposed_count: u64,
     pub plans_revealed_count: u64,
     pub votes_cast_count: u64,
+    /// Results confirmed by re-execution or upheld in a dispute.
+    pub verifications_passed_count: u64,
+    /// Results rejected in a dispute.
+    pub verifications_failed_count: u64,
+    /// Results submitted by the task's deadline.
+    pub results_on_time_count: u64,
+    /// Results submitted after the task's deadline.
+    pub results_late_count: u64,
+    /// Sum of how late the late results were.
+    pub total_lateness_secs: f64,
+    /// Proposal bonds slashed.
+    pub bonds_slashed_count: u64,
+    /// Reputation points lost to slashed proposal bonds.
+    pub bond_points_slashed: u64,
+}
+
+impl AgentActivity {
+    /// Reputation score earned from processed tasks and verification
+    /// outcomes, less slashed proposal bonds; agents without recorded
+    /// activity start at 10.
+    pub fn reputation_of(activity: Option<&AgentActivity>) -> u64 {
+        let Some(a) = activity else {
+            return 10;
+        };
+        (10 + a.tasks_processed_count * 5 + a.verifications_passed_count * VERIFICATION_PASS_POINTS)
+            .saturating_sub(a.verifications_failed_count * VERIFICATION_FAIL_POINTS)
+            .saturating_sub(a.bond_points_slashed)
+    }
+
+    /// Percentage of deadline-bound results submitted on time.
+    pub fn on_time_pct(&self) -> Option<f64> {
+        let total = self.results_on_time_count + self.results_late_count;
+        (total > 0).then(|| self.results_on_time_count as f64 * 100.0 / total as f64)
+    }
+
+    /// Mean lateness of the late results.
+    pub fn average_lateness_secs(&self) -> Option<f64> {
+        (self.results_late_count > 0)
+            .then(|| self.total_lateness_secs / self.results_late_count as f64)
+    }
+}
+
+/// A winning plan held back from assignment until an operator signs off.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PendingPlanApproval {
+    pub task_id: String,
+    pub winner_plan_id: String,
+    pub selected_at: chrono::DateTime<chrono::Utc>,
+    /// The plan is rejected if no decision arrives before this instant.
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    /// Set once approved; the voting check loop then assigns the subtasks.
+    pub approved_by: Option<String>,
+}
+
+/// `now` plus `secs`, or `None` when that is past the last representable
+/// instant.
+pub(crate) fn deadline_after(
+    now: chrono::DateTime<chrono::Utc>,
+    secs: u64,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let delay = chrono::Duration::try_seconds(i64::try_from(secs).ok()?)?;
+    now.checked_add_signed(delay)
+}
+
+/// A task this node injected that has not drawn a plan yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskTender {
+    /// 0 for the first injection, counting up with each re-tender.
+    pub attempt: u32,
+    pub tendered_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// What became of a task that drew no plan by the proposal timeout.
+#[derive(Debug, Clone)]
+pub enum TenderOutcome {
+    /// Tendered again, as the task now stands.
+    Retendered(Box<Task>),
+    /// Failed after its last attempt; the failure is announced in the
+    /// task's swarm.
+    GaveUp {
+        swarm_id: String,
+        failure: TaskTenderFailedParams,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -89,6 +297,15 @@ pub struct TaskVoteRequirement {
     pub tier_level: u32,
 }
 
+/// What an epoch handoff did with an in-flight consensus round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandoffOutcome {
+    /// Every participant kept its tier; the round continues with a recomputed quorum.
+    Migrated,
+    /// A participant changed tier; the round starts over from the commit phase.
+    Restarted,
+}
+
 /// Status of the connector.
 #[derive(Debug, Clone)]
 pub enum ConnectorStatus {
@@ -127,9 +344,9 @@ pub struct ConnectorState {
     /// CRDT set tracking active tasks.
     pub task_set: OrSet<String>,
     /// Full task metadata keyed by task ID.
-    pub task_details: std::collections::HashMap<String, Task>,
+    pub task_details: Box<dyn TaskStore>,
     /// Per-task timeline events keyed by task ID.
-    pub task_timelines: std::collections::HashMap<String, Vec<TaskTimelineEvent>>,
+    pub task_timelines: Box<dyn TimelineStore>,
     /// CRDT set tracking active agents.
     pub agent_set: OrSet<String>,
     /// CRDT set tracking known swarm members (agent identities).
@@ -148,8 +365,15 @@ pub struct ConnectorState {
     pub member_last_result: std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
     /// Optional textual result payload by task ID.
     pub task_result_text: std::collections::HashMap<String, String>,
+    /// Plan each parent task was split by, keyed by parent task ID.
+    pub task_winning_plans: std::collections::HashMap<String, String>,
     /// Deferred plan reveals waiting for commit quorum, keyed by task/proposer.
     pub pending_plan_reveals: std::collections::HashMap<String, std::collections::HashMap<String, Plan>>,
+    /// Blind ballots cast here, with their salts, waiting for the
+    /// commitments to close, keyed by task.
+    pub pending_vote_reveals: std::collections::HashMap<String, Vec<ConsensusVoteRevealParams>>,
+    /// Open voting windows, keyed by task.
+    pub voting_windows: std::collections::HashMap<String, crate::voting_window::VotingWindow>,
     /// Merkle DAG for result verification.
     pub merkle_dag: MerkleDag,
     /// Content-addressed storage.
@@ -169,13 +393,17 @@ pub struct ConnectorState {
     /// Tracks subordinates for each coordinator: parent_id -> [child_ids].
     pub subordinates: std::collections::HashMap<String, Vec<String>>,
     /// Stores task results (artifacts) keyed by task_id.
-    pub task_results: std::collections::HashMap<String, Artifact>,
+    pub task_results: Box<dyn ArtifactStore>,
     /// Network statistics cache.
     pub network_stats: NetworkStats,
     /// Event log for the TUI.
     pub event_log: Vec<LogEntry>,
+    /// Entries ever pushed to `event_log`, including those since dropped.
+    pub log_count: u64,
     /// P2P message trace log for debugging and web dashboard.
     pub message_trace: Vec<MessageTraceEvent>,
+    /// Undecodable or rejected inbound messages, oldest first.
+    pub dead_letters: Vec<DeadLetter>,
     /// Timestamp when the connector started.
     pub start_time: chrono::DateTime<chrono::Utc>,
     /// The swarm ID this connector is currently a member of.
@@ -184,18 +412,28 @@ pub struct ConnectorState {
     pub known_swarms: std::collections::HashMap<String, SwarmRecord>,
     /// Swarm token for private swarm authentication (if any).
     pub swarm_token: Option<SwarmToken>,
+    /// Keys of the swarm's topics when `swarm.encrypt_topics` is set,
+    /// shared with the network host.
+    pub topic_keys: Option<Arc<std::sync::RwLock<TopicKeyring>>>,
     /// Active holonic boards, keyed by task_id.
     pub active_holons: std::collections::HashMap<String, HolonState>,
     /// Deliberation messages per task (proposal submissions, critiques, synthesis).
     pub deliberation_messages: std::collections::HashMap<String, Vec<DeliberationMessage>>,
     /// Per-voter ballot records per task, for full visibility.
     pub ballot_records: std::collections::HashMap<String, Vec<BallotRecord>>,
+    /// Ballot receipts per task: those this node issued for ballots it
+    /// recorded, and those other recorders issued to its local agents.
+    pub ballot_receipts: std::collections::HashMap<String, Vec<BallotReceiptParams>>,
+    /// Task state requests this node sent and is waiting on: task_id -> sent at.
+    pub task_state_requests: std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
+    /// When this node last answered a task state request: task_id -> sent at.
+    pub task_state_replies: std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
     /// IRV round history per task (populated after voting completes).
     pub irv_rounds: std::collections::HashMap<String, Vec<IrvRound>>,
     /// Board invitation acceptances per task: task_id -> Vec<BoardAcceptParams>.
     pub board_acceptances: std::collections::HashMap<String, Vec<BoardAcceptParams>>,
-    /// In-memory wws:// name registry: lowercase name -> NameRecord.
-    /// DHT-backed persistence is a future enhancement.
+    /// Holder of each wws:// name, as settled by `name_directory`:
+    /// lowercase name -> NameRecord.
     pub registered_names: std::collections::HashMap<String, wws_network::name_registry::NameRecord>,
 
     // Anti-bot verification
@@ -204,28 +442,207 @@ pub struct ConnectorState {
 
     // Direct P2P messages
     pub direct_messages: Vec<DirectMessage>,
+
+    /// Seconds an operator has to approve a winning plan.
+    pub plan_approval_timeout_secs: u64,
+    /// Winning plans awaiting operator approval, keyed by task ID.
+    pub pending_plan_approvals: std::collections::HashMap<String, PendingPlanApproval>,
+    /// Named task templates (seeded from config, extended over RPC).
+    pub task_templates: std::collections::HashMap<String, TaskTemplate>,
+    /// Recurring task injections.
+    pub scheduler: Scheduler,
+    /// Cost reported with each task's own result (subtasks excluded).
+    pub task_costs: std::collections::HashMap<String, f64>,
+    /// Configuration for new plan-selection voting engines.
+    pub voting_config: wws_consensus::voting::VotingConfig,
+    /// Seconds blind ballots have to be revealed, when not the voting timeout.
+    pub reveal_timeout_secs: Option<u64>,
+    /// Seconds before an unfinished proposal or voting round is abandoned.
+    pub max_round_age_secs: u64,
+    /// Times a task that draws no plan is tendered before it fails.
+    pub max_tender_attempts: u32,
+    /// Whether a re-tender moves the task one tier up.
+    pub retender_relax_tier: bool,
+    /// Tasks injected here that have not drawn a plan yet, keyed by task.
+    pub task_tenders: std::collections::HashMap<String, TaskTender>,
+    /// Membership samples and reports of network partitions.
+    pub partition_detector: crate::partition::PartitionDetector,
+    /// This node's swarm lineage and the merges with other lineages.
+    pub merge: crate::merge::MergeTracker,
+    /// Sharding policy, splits seen and the federation links they left.
+    pub shards: crate::shard::ShardTracker,
+    /// Tasks injected here as a bridge whose results go back to another
+    /// swarm.
+    pub relays: crate::delegation::RelayTracker,
+    /// Signed reputation attestations and the scores they add up to.
+    pub reputation: crate::reputation::ReputationLedger,
+    /// Swarm-wide quorum policy, used when a task does not set its own.
+    pub quorum_policy: QuorumPolicy,
+    /// Swarm-wide assignment strategy, used when a task does not set its own.
+    pub assignment_strategy: AssignmentStrategy,
+    /// Fixed seed for "random_seeded" assignment.
+    pub assignment_seed: Option<u64>,
+    /// Seats on each task's sortition committee, 0 for no committee.
+    pub committee_size: usize,
+    /// Injected tasks waiting for their `depends_on` prerequisites to complete.
+    pub held_tasks: std::collections::HashMap<String, Task>,
+    /// Probability that a completed subtask is re-executed for verification.
+    pub verification_rate: f64,
+    /// Bond this connector's agents stake on their plans unless a proposal
+    /// names its own.
+    pub proposal_bond: u64,
+    /// Bonds staked on plans of rounds still open.
+    pub proposal_bonds: ProposalBonds,
+    /// Re-execution checks and disputes, keyed by task ID.
+    pub verifications: std::collections::HashMap<String, ResultVerification>,
+    /// When an epoch handoff last restarted each task's consensus round.
+    pub round_restarts: std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
+    /// Acceptance window and minimum size for boards this connector chairs.
+    pub board_config: BoardConfig,
+    /// Boards this connector chairs that are still collecting acceptances.
+    pub board_formations: std::collections::HashMap<String, BoardFormation>,
+    /// Additional local agents served by this connector (multi-agent mode).
+    pub local_agents: AgentCluster,
+    /// Key behind this node's PeerId and DID; also signs the swarm
+    /// parameter records this node issues.
+    pub identity_key: ed25519_dalek::SigningKey,
+    /// DID documents of this node, its local agents and the agents
+    /// resolved from the DHT.
+    pub did_documents: DidDirectory,
+    /// Adopted swarm-wide parameters, if the swarm has any.
+    pub swarm_params: Option<SwarmParameters>,
+    /// Open swarm parameter change proposals, keyed by proposal ID.
+    pub params_proposals: std::collections::HashMap<String, ParamsProposal>,
+    /// Accepted results that break their task's non-strict result schema,
+    /// keyed by task ID.
+    pub result_schema_violations: std::collections::HashMap<String, Vec<String>>,
+    /// Latest progress report for each task, keyed by task ID.
+    pub task_progress: std::collections::HashMap<String, TaskProgressParams>,
+    /// Escalations of tasks no agent could take over, keyed by task ID.
+    pub blocked_tasks: std::collections::HashMap<String, TaskEscalationParams>,
+    /// Rules `swarm.propose_plan` checks plans against, per swarm.
+    pub plan_policy: PlanPolicyConfig,
+    /// Key material for private boards this connector chairs or sits on.
+    pub board_channels: std::collections::HashMap<String, BoardChannel>,
+    /// When this connector casts its vote in the running election.
+    pub election_vote_due: Option<chrono::DateTime<chrono::Utc>>,
+    /// Swarms this connector has joined, keyed by swarm ID, including the
+    /// primary swarm `current_swarm_id`.
+    pub memberships: std::collections::HashMap<String, SwarmMembership>,
+    /// Swarm of each root task that was injected into or assigned from a
+    /// swarm other than the primary one.
+    pub task_swarms: std::collections::HashMap<String, String>,
+    /// Every wws:// name claim and guardian vote seen.
+    pub name_directory: NameDirectory,
+    /// Capabilities agents have advertised in candidacies and board
+    /// acceptances, keyed by agent ID.
+    pub agent_capabilities: std::collections::HashMap<String, Vec<String>>,
+    /// Services agents offer, keyed by agent ID: this node's agent's as
+    /// registered, others' as announced in their keep-alives.
+    pub agent_services: std::collections::HashMap<String, crate::did::AnnouncedServices>,
+    /// Set when this node's DID document changed and should be published
+    /// before the hourly republish.
+    pub did_republish_due: bool,
+    /// Declared capabilities and aliases, for matching agents to tasks.
+    pub capabilities: CapabilityRegistry,
+    /// Load each member last reported in its keep-alives.
+    pub agent_loads: std::collections::HashMap<String, AgentLoad>,
+    /// Tasks this connector's agent declares it can work on at once.
+    pub max_concurrent_tasks: Option<u32>,
+    /// Assigned tasks this connector's agents have fetched with
+    /// `swarm.receive_task`; the others are still queued.
+    pub picked_up_tasks: std::collections::HashSet<String>,
+    /// Tasks handed out by cursor-based `swarm.receive_task` polls.
+    pub task_deliveries: TaskDeliveries,
+    /// Times the daemon supervisor restarted the connector before this run.
+    pub restarts: u32,
+    /// Tier whose task topics this node is subscribed to, per joined
+    /// swarm. A swarm missing here is subscribed at every level.
+    pub tier_subscriptions: std::collections::HashMap<String, Tier>,
+    /// The running configuration and how `swarm.reload_config` re-reads it.
+    pub config_reload: ConfigReload,
+    /// Steals this connector arbitrates as a parent or asks for as an
+    /// idle executor.
+    pub work_stealing: WorkStealing,
+    /// Layout roles set by operators, from this connector's config and
+    /// registrations and from members' keep-alives.
+    pub agent_roles: std::collections::HashMap<String, LayoutRole>,
+    /// On-disk copy of the message trace, when `[trace] dir` is set.
+    pub trace_log: Option<TraceLog>,
+    /// Durable copies of the event log from `[[logging.sinks]]`.
+    pub log_sinks: Vec<LogSink>,
+    /// Outbound webhook notifications of swarm activity.
+    pub webhooks: Webhooks,
+    /// Last ping round-trip time to each connected peer, by the peer's
+    /// `did:swarm:` DID.
+    pub peer_rtt: std::collections::HashMap<String, Duration>,
 }
 
 impl ConnectorState {
-    /// Push a log entry, capping the log at 1000 entries.
+    /// Push a log entry, capping the log at 1000 entries, and write it to
+    /// the log sinks that take its category. A sink that fails is dropped.
     pub fn push_log(&mut self, category: LogCategory, message: String) {
         if self.event_log.len() >= 1000 {
             self.event_log.remove(0);
         }
-        self.event_log.push(LogEntry {
+        let entry = LogEntry {
             timestamp: chrono::Utc::now(),
             category,
             message,
+        };
+        self.log_sinks.retain_mut(|sink| match sink.write(&entry) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(error = %e, sink = ?sink.kind(), "Failed to write event log sink; dropping it");
+                false
+            }
         });
+        self.event_log.push(entry);
+        self.log_count += 1;
     }
 
-    pub fn push_message_trace(&mut self, event: MessageTraceEvent) {
+    pub fn push_message_trace(&mut self, mut event: MessageTraceEvent) {
+        if let Some(log) = self.trace_log.as_mut() {
+            if let Err(e) = log.append(&event) {
+                tracing::warn!(error = %e, "Failed to persist message trace; keeping it in memory only");
+                self.trace_log = None;
+            }
+        }
+        event.payload = None;
         if self.message_trace.len() >= 5000 {
             self.message_trace.remove(0);
         }
         self.message_trace.push(event);
     }
 
+    /// Keep a dropped inbound message, evicting the oldest past
+    /// `DEAD_LETTER_CAPACITY`.
+    pub fn push_dead_letter(
+        &mut self,
+        topic: &str,
+        source: &str,
+        method: Option<String>,
+        reason: impl Into<String>,
+        data: &[u8],
+    ) {
+        if self.dead_letters.len() >= DEAD_LETTER_CAPACITY {
+            self.dead_letters.remove(0);
+        }
+        let truncated = data.len() > DEAD_LETTER_PAYLOAD_MAX_BYTES;
+        let kept = &data[..data.len().min(DEAD_LETTER_PAYLOAD_MAX_BYTES)];
+        self.dead_letters.push(DeadLetter {
+            timestamp: chrono::Utc::now(),
+            topic: topic.to_string(),
+            source: source.to_string(),
+            method,
+            reason: reason.into(),
+            size_bytes: data.len(),
+            payload: String::from_utf8_lossy(kept).into_owned(),
+            truncated,
+            field_errors: Vec::new(),
+        });
+    }
+
     pub fn push_task_timeline_event(
         &mut self,
         task_id: &str,
@@ -233,16 +650,74 @@ impl ConnectorState {
         detail: impl Into<String>,
         actor: Option<String>,
     ) {
-        let timeline = self.task_timelines.entry(task_id.to_string()).or_default();
-        timeline.push(TaskTimelineEvent {
+        let event = TaskTimelineEvent {
             timestamp: chrono::Utc::now(),
             stage: stage.to_string(),
             detail: detail.into(),
             actor,
-        });
-        if timeline.len() > 500 {
-            timeline.remove(0);
+            hlc: hlc::global().now(),
+        };
+        let started = self
+            .task_timelines
+            .get(task_id)
+            .and_then(|timeline| timeline.last())
+            .map_or(event.timestamp, |e| e.timestamp);
+        self.task_timelines.push(task_id, event.clone());
+        if let Some(timeline) = self.task_timelines.get_mut(task_id) {
+            if timeline.len() > 500 {
+                timeline.remove(0);
+            }
+        }
+        if let Some(kind) = WebhookEvent::for_stage(stage) {
+            self.webhooks.notify(
+                kind,
+                serde_json::json!({
+                    "task_id": task_id,
+                    "stage": event.stage,
+                    "detail": event.detail,
+                    "actor": event.actor,
+                }),
+            );
+        }
+        if crate::telemetry::is_enabled() {
+            let root_task_id = self.root_task_id(task_id);
+            let node = self.agent_id.to_string();
+            crate::telemetry::record_stage(&StageSpan {
+                root_task_id: &root_task_id,
+                task_id,
+                stage,
+                detail: &event.detail,
+                actor: event.actor.as_deref(),
+                node: &node,
+                started,
+                ended: event.timestamp,
+            });
+        }
+    }
+
+    /// ID of the root of the task tree `task_id` belongs to, as far as its
+    /// ancestry is known here.
+    pub fn root_task_id(&self, task_id: &str) -> String {
+        let mut root = task_id;
+        for _ in 0..64 {
+            match self.task_details.get(root).and_then(|t| t.parent_task_id.as_deref()) {
+                Some(parent) => root = parent,
+                None => break,
+            }
+        }
+        root.to_string()
+    }
+
+    /// Count task-flow timeline events at or after `since` (all retained
+    /// events when `None`).
+    pub fn flow_counts(&self, since: Option<chrono::DateTime<chrono::Utc>>) -> FlowCounts {
+        let mut counts = FlowCounts::default();
+        for event in self.task_timelines.values().flatten() {
+            if since.is_none_or(|since| event.timestamp >= since) {
+                counts.record(&event.stage);
+            }
         }
+        counts
     }
 
     /// Add a direct message, capping the list at 500 entries (FIFO eviction).
@@ -253,2778 +728,12120 @@ impl ConnectorState {
         self.direct_messages.push(msg);
     }
 
-    pub fn mark_member_seen(&mut self, agent_id: &str) {
-        self.mark_member_seen_with_name(agent_id, None);
-    }
-
-    pub fn mark_member_seen_with_name(&mut self, agent_id: &str, name: Option<&str>) {
-        if agent_id.trim().is_empty() {
-            return;
+    /// Park the winning plan of a task until an operator approves or rejects it.
+    pub fn hold_plan_for_approval(&mut self, task_id: &str, winner_plan_id: &str) {
+        let now = chrono::Utc::now();
+        self.pending_plan_approvals.insert(
+            task_id.to_string(),
+            PendingPlanApproval {
+                task_id: task_id.to_string(),
+                winner_plan_id: winner_plan_id.to_string(),
+                selected_at: now,
+                // A timeout past the last representable instant never expires.
+                deadline: deadline_after(now, self.plan_approval_timeout_secs)
+                    .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC),
+                approved_by: None,
+            },
+        );
+        if let Some(task) = self.task_details.get_mut(task_id) {
+            task.status = TaskStatus::VotingPhase;
         }
-        self.member_set.add(agent_id.to_string());
-        self.member_last_seen
-            .insert(agent_id.to_string(), chrono::Utc::now());
-        if let Some(n) = name.map(str::trim).filter(|n| !n.is_empty()) {
-            self.agent_names.insert(agent_id.to_string(), n.to_string());
+        if let Some(holon) = self.active_holons.get_mut(task_id) {
+            holon.status = HolonStatus::Voting;
         }
+        self.push_task_timeline_event(
+            task_id,
+            "awaiting_approval",
+            format!(
+                "Plan {} held for operator approval ({}s timeout)",
+                winner_plan_id, self.plan_approval_timeout_secs
+            ),
+            None,
+        );
+        self.push_log(
+            LogCategory::Task,
+            format!(
+                "Task {} awaiting approval of plan {} (/approve {} to proceed)",
+                task_id, winner_plan_id, task_id
+            ),
+        );
     }
 
-    pub fn mark_member_polled_tasks(&mut self, agent_id: &str) {
-        self.member_last_task_poll
-            .insert(agent_id.to_string(), chrono::Utc::now());
+    /// Approve a held plan. Returns the plan ID, or `None` if nothing is pending.
+    pub fn approve_plan(&mut self, task_id: &str, actor: &str) -> Option<String> {
+        let pending = self.pending_plan_approvals.get_mut(task_id)?;
+        pending.approved_by = Some(actor.to_string());
+        let plan_id = pending.winner_plan_id.clone();
+        self.push_task_timeline_event(
+            task_id,
+            "plan_approved",
+            format!("Plan {} approved", plan_id),
+            Some(actor.to_string()),
+        );
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT plan.approve actor={} task_id={} plan_id={}",
+                actor, task_id, plan_id
+            ),
+        );
+        Some(plan_id)
     }
 
-    pub fn mark_member_submitted_result(&mut self, agent_id: &str) {
-        self.member_last_result
-            .insert(agent_id.to_string(), chrono::Utc::now());
+    /// Reject a held plan and close the task. Returns the plan ID, or `None`
+    /// if nothing is pending.
+    pub fn reject_plan(&mut self, task_id: &str, actor: &str, reason: &str) -> Option<String> {
+        let pending = self.pending_plan_approvals.remove(task_id)?;
+        let detail = if reason.is_empty() {
+            format!("Plan {} rejected", pending.winner_plan_id)
+        } else {
+            format!("Plan {} rejected: {}", pending.winner_plan_id, reason)
+        };
+        self.close_rejected_task(task_id, "plan_rejected", detail, Some(actor.to_string()));
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT plan.reject actor={} task_id={} plan_id={}",
+                actor, task_id, pending.winner_plan_id
+            ),
+        );
+        Some(pending.winner_plan_id)
     }
 
-    fn activity_mut(&mut self, agent_id: &str) -> &mut AgentActivity {
-        self.agent_activity
-            .entry(agent_id.to_string())
-            .or_default()
+    /// Cancel an open task on this connector together with its open
+    /// subtasks. Returns the IDs of the tasks closed.
+    pub fn cancel_task(&mut self, task_id: &str, actor: &str) -> Result<Vec<String>, String> {
+        let task = self
+            .task_details
+            .get(task_id)
+            .ok_or_else(|| format!("unknown task {}", task_id))?;
+        if !crate::migration::is_open(&task.status) {
+            return Err(format!("task {} is already closed", task_id));
+        }
+        let mut pending = vec![task_id.to_string()];
+        let mut closed = Vec::new();
+        while let Some(id) = pending.pop() {
+            let Some(task) = self.task_details.get(&id) else {
+                continue;
+            };
+            if !crate::migration::is_open(&task.status) {
+                continue;
+            }
+            pending.extend(task.subtasks.iter().cloned());
+            self.held_tasks.remove(&id);
+            self.pending_plan_approvals.remove(&id);
+            self.close_rejected_task(
+                &id,
+                "cancelled",
+                "Cancelled by operator".to_string(),
+                Some(actor.to_string()),
+            );
+            closed.push(id);
+        }
+        self.push_log(
+            LogCategory::System,
+            format!("AUDIT task.cancel actor={} task_id={} closed={}", actor, task_id, closed.len()),
+        );
+        Ok(closed)
     }
 
-    pub fn bump_tasks_assigned(&mut self, agent_id: &str) {
-        self.activity_mut(agent_id).tasks_assigned_count += 1;
+    /// Hand an open subtask to `assignee` in place of its current assignee.
+    /// The new assignee must be a known member, hold the previous assignee's
+    /// tier and offer the task's required capabilities, where those are
+    /// known. Returns the assignment to publish.
+    pub fn reassign_task(
+        &mut self,
+        task_id: &str,
+        assignee: &str,
+        actor: &str,
+    ) -> Result<TaskAssignmentParams, String> {
+        let (assignment, previous) = self.hand_over(task_id, assignee, "reassign-operator")?;
+        self.push_task_timeline_event(
+            task_id,
+            "reassigned",
+            format!("Task reassigned by operator: {} -> {}", previous, assignee),
+            Some(actor.to_string()),
+        );
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT task.reassign actor={} task_id={} from={} to={}",
+                actor, task_id, previous, assignee
+            ),
+        );
+        Ok(assignment)
     }
 
-    pub fn bump_tasks_processed(&mut self, agent_id: &str) {
-        self.activity_mut(agent_id).tasks_processed_count += 1;
+    /// Mark a task `Blocked` after its coordinator found no agent to take it
+    /// over, and keep the escalation for the operator. Escalations for
+    /// closed tasks and repeats of an open one are ignored; returns whether
+    /// the escalation was recorded.
+    pub fn block_task(&mut self, escalation: TaskEscalationParams) -> bool {
+        if let Some(task) = self.task_details.get_mut(&escalation.task_id) {
+            if !crate::migration::is_open(&task.status)
+                || (task.status == TaskStatus::Blocked
+                    && self.blocked_tasks.contains_key(&escalation.task_id))
+            {
+                return false;
+            }
+            task.status = TaskStatus::Blocked;
+        } else if self.blocked_tasks.contains_key(&escalation.task_id) {
+            return false;
+        }
+        self.push_task_timeline_event(
+            &escalation.task_id,
+            "blocked",
+            escalation.reason.clone(),
+            Some(escalation.coordinator.to_string()),
+        );
+        self.push_log(
+            LogCategory::Task,
+            format!("Task {} is blocked: {}", escalation.task_id, escalation.reason),
+        );
+        self.blocked_tasks.insert(escalation.task_id.clone(), escalation);
+        true
     }
 
-    pub fn bump_plans_proposed(&mut self, agent_id: &str) {
-        self.activity_mut(agent_id).plans_proposed_count += 1;
+    /// Escalations still awaiting a new assignee or the operator, oldest
+    /// first.
+    pub fn open_escalations(&self) -> Vec<&TaskEscalationParams> {
+        let mut open: Vec<&TaskEscalationParams> = self
+            .blocked_tasks
+            .values()
+            .filter(|e| {
+                self.task_details
+                    .get(&e.task_id)
+                    .is_none_or(|t| t.status == TaskStatus::Blocked)
+            })
+            .collect();
+        open.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.task_id.cmp(&b.task_id)));
+        open
     }
 
-    pub fn bump_plans_revealed(&mut self, agent_id: &str) {
-        self.activity_mut(agent_id).plans_revealed_count += 1;
-    }
+    /// Hand a blocked task back to its current assignee with a fresh
+    /// deadline, at the operator's request. Returns the assignment to
+    /// publish.
+    pub fn retry_blocked_task(
+        &mut self,
+        task_id: &str,
+        actor: &str,
+    ) -> Result<TaskAssignmentParams, String> {
+        let task = self
+            .task_details
+            .get(task_id)
+            .ok_or_else(|| format!("unknown task {}", task_id))?;
+        if task.status != TaskStatus::Blocked {
+            return Err(format!("task {} is not blocked", task_id));
+        }
+        let Some(parent_task_id) = task.parent_task_id.clone() else {
+            return Err(format!("task {} is a root task; only subtasks are assigned", task_id));
+        };
+        let Some(assignee) = task.assigned_to.clone() else {
+            return Err(format!("task {} has no assignee to retry with", task_id));
+        };
 
-    pub fn bump_votes_cast(&mut self, agent_id: &str) {
-        self.activity_mut(agent_id).votes_cast_count += 1;
+        let deadline = self.subtask_deadline(&parent_task_id, chrono::Utc::now());
+        let Some(task) = self.task_details.get_mut(task_id) else {
+            return Err(format!("unknown task {}", task_id));
+        };
+        task.status = TaskStatus::InProgress;
+        task.deadline = Some(deadline);
+        let task = task.clone();
+        self.blocked_tasks.remove(task_id);
+        self.push_task_timeline_event(
+            task_id,
+            "retried",
+            format!("Blocked task handed back to {} by operator", assignee),
+            Some(actor.to_string()),
+        );
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT task.retry actor={} task_id={} assignee={}",
+                actor, task_id, assignee
+            ),
+        );
+        Ok(self.signed_assignment(task, assignee, parent_task_id, "retry-operator"))
     }
 
-    pub fn active_member_ids(&self, max_staleness: Duration) -> Vec<String> {
-        let now = chrono::Utc::now();
-        let mut ids: Vec<String> = self
-            .member_last_seen
-            .iter()
-            .filter_map(|(agent_id, seen)| {
-                now.signed_duration_since(*seen)
-                    .to_std()
-                    .ok()
-                    .filter(|age| *age <= max_staleness)
-                    .map(|_| agent_id.clone())
-            })
-            .collect();
+    /// Move an open subtask to `assignee` after the checks described at
+    /// [`Self::reassign_task`]. Returns the assignment to publish and the
+    /// previous assignee.
+    fn hand_over(
+        &mut self,
+        task_id: &str,
+        assignee: &str,
+        plan_id: &str,
+    ) -> Result<(TaskAssignmentParams, String), String> {
+        let task = self
+            .task_details
+            .get(task_id)
+            .ok_or_else(|| format!("unknown task {}", task_id))?;
+        if !crate::migration::is_open(&task.status) {
+            return Err(format!("task {} is already closed", task_id));
+        }
+        let Some(parent_task_id) = task.parent_task_id.clone() else {
+            return Err(format!("task {} is a root task; only subtasks are assigned", task_id));
+        };
+        let previous = task
+            .assigned_to
+            .as_ref()
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| "unassigned".to_string());
+        if previous == assignee {
+            return Err(format!("task {} is already assigned to {}", task_id, assignee));
+        }
+        let assignee_id = AgentId::new(assignee.to_string());
+        if !self.is_local_agent(&assignee_id) && !self.member_set.contains(assignee) {
+            return Err(format!("unknown agent {}", assignee));
+        }
+        // Like a timeout reassignment, stay within the previous assignee's tier.
+        let expected_tier = self.agent_tiers.get(&previous).copied();
+        let assignee_tier = self.agent_tiers.get(assignee).copied();
+        if let (Some(expected), Some(actual)) = (expected_tier, assignee_tier) {
+            if expected != actual {
+                return Err(format!(
+                    "{} is {:?} but task {} is held at {:?}",
+                    assignee, actual, task_id, expected
+                ));
+            }
+        }
+        if let Some(capabilities) = self.agent_capabilities.get(assignee) {
+            let missing = self
+                .capabilities
+                .missing(capabilities, &task.capabilities_required);
+            if !missing.is_empty() {
+                return Err(format!("{} lacks capabilities: {}", assignee, missing.join(", ")));
+            }
+        }
 
-        let self_id = self.agent_id.to_string();
-        if !ids.iter().any(|id| id == &self_id) {
-            ids.push(self_id);
+        let deadline = self.subtask_deadline(&parent_task_id, chrono::Utc::now());
+        let Some(task) = self.task_details.get_mut(task_id) else {
+            return Err(format!("unknown task {}", task_id));
+        };
+        task.assigned_to = Some(assignee_id.clone());
+        task.status = TaskStatus::InProgress;
+        task.deadline = Some(deadline);
+        let task = task.clone();
+        self.picked_up_tasks.remove(task_id);
+        self.task_deliveries.forget(task_id);
+        self.blocked_tasks.remove(task_id);
+        if self.is_local_agent(&assignee_id) {
+            self.task_set.add(task_id.to_string());
+        } else if self.is_local_agent(&AgentId::new(previous.clone())) {
+            self.task_set.remove(task_id);
         }
+        self.bump_tasks_assigned(assignee);
+        let assignment = self.signed_assignment(task, assignee_id, parent_task_id, plan_id);
+        Ok((assignment, previous))
+    }
 
-        ids.sort();
-        ids.dedup();
-        ids
+    /// Everything this connector holds on the task tree rooted at
+    /// `root_task_id`, as a self-contained transcript.
+    pub fn export_run(&self, root_task_id: &str) -> Result<RunTranscript, String> {
+        if !self.task_details.contains_key(root_task_id) {
+            return Err(format!("Task not found: {}", root_task_id));
+        }
+        let mut tree = std::collections::HashSet::from([root_task_id.to_string()]);
+        let mut pending = vec![root_task_id.to_string()];
+        while let Some(id) = pending.pop() {
+            let listed = self.task_details[&id].subtasks.iter().cloned();
+            let linked = self
+                .task_details
+                .values()
+                .filter(|t| t.parent_task_id.as_deref() == Some(id.as_str()))
+                .map(|t| t.task_id.clone());
+            for child in listed.chain(linked) {
+                if self.task_details.contains_key(&child) && tree.insert(child.clone()) {
+                    pending.push(child);
+                }
+            }
+        }
+        let mut tasks: Vec<Task> = tree.iter().map(|id| self.task_details[id].clone()).collect();
+        tasks.sort_by(|a, b| {
+            (a.task_id != root_task_id, a.created_at, &a.task_id)
+                .cmp(&(b.task_id != root_task_id, b.created_at, &b.task_id))
+        });
+
+        fn collect<'a, T: Clone + 'a>(
+            records: impl IntoIterator<Item = (&'a String, &'a T)>,
+            tree: &std::collections::HashSet<String>,
+        ) -> std::collections::BTreeMap<String, T> {
+            records
+                .into_iter()
+                .filter(|(id, _)| tree.contains(*id))
+                .map(|(id, v)| (id.clone(), v.clone()))
+                .collect()
+        }
+        Ok(RunTranscript {
+            format_version: TRANSCRIPT_FORMAT_VERSION,
+            root_task_id: root_task_id.to_string(),
+            exported_at: chrono::Utc::now(),
+            exported_by: self.agent_id.to_string(),
+            tasks,
+            timelines: collect(self.task_timelines.iter(), &tree),
+            deliberation: collect(&self.deliberation_messages, &tree),
+            ballots: collect(&self.ballot_records, &tree),
+            irv_rounds: collect(&self.irv_rounds, &tree),
+            artifacts: collect(self.task_results.iter(), &tree),
+            result_text: collect(&self.task_result_text, &tree),
+            costs: collect(&self.task_costs, &tree),
+        })
     }
 
-    pub fn active_member_count(&self, max_staleness: Duration) -> usize {
-        self.active_member_ids(max_staleness).len()
+    /// Load an exported run for inspection. Tasks this connector already
+    /// knows keep their live state; the others are added closed to new work,
+    /// outside the task set, so nothing picks them up for execution.
+    /// Returns the IDs of the tasks added.
+    pub fn import_run(&mut self, transcript: RunTranscript) -> Result<Vec<String>, String> {
+        transcript.validate()?;
+        let RunTranscript {
+            root_task_id,
+            exported_by,
+            tasks,
+            mut timelines,
+            mut deliberation,
+            mut ballots,
+            mut irv_rounds,
+            mut artifacts,
+            mut result_text,
+            mut costs,
+            ..
+        } = transcript;
+        let mut imported = Vec::new();
+        for task in tasks {
+            let id = task.task_id.clone();
+            if self.task_details.contains_key(&id) {
+                continue;
+            }
+            self.task_details.insert(id.clone(), task);
+            if let Some(events) = timelines.remove(&id) {
+                self.task_timelines.insert(id.clone(), events);
+            }
+            if let Some(messages) = deliberation.remove(&id) {
+                self.deliberation_messages.insert(id.clone(), messages);
+            }
+            if let Some(records) = ballots.remove(&id) {
+                self.ballot_records.insert(id.clone(), records);
+            }
+            if let Some(rounds) = irv_rounds.remove(&id) {
+                self.irv_rounds.insert(id.clone(), rounds);
+            }
+            if let Some(artifact) = artifacts.remove(&id) {
+                self.task_results.insert(id.clone(), artifact);
+            }
+            if let Some(text) = result_text.remove(&id) {
+                self.task_result_text.insert(id.clone(), text);
+            }
+            if let Some(cost) = costs.remove(&id) {
+                self.task_costs.insert(id.clone(), cost);
+            }
+            imported.push(id);
+        }
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT run.import root_task_id={} exported_by={} tasks={}",
+                root_task_id,
+                exported_by,
+                imported.len()
+            ),
+        );
+        Ok(imported)
     }
 
-    pub fn prune_stale_members(&mut self, max_staleness: Duration) {
-        let now = chrono::Utc::now();
-        let stale_ids: Vec<String> = self
-            .member_last_seen
-            .iter()
-            .filter_map(|(agent_id, seen)| {
-                let age = now.signed_duration_since(*seen).to_std().ok()?;
-                if age > max_staleness {
-                    Some(agent_id.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
+    /// Drain approved plans ready for assignment, rejecting any whose
+    /// approval deadline has passed.
+    pub fn take_approved_plans(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<(String, String)> {
+        let mut approved = Vec::new();
+        let mut expired = Vec::new();
+        for (task_id, pending) in &self.pending_plan_approvals {
+            if pending.approved_by.is_some() {
+                approved.push((task_id.clone(), pending.winner_plan_id.clone()));
+            } else if pending.deadline <= now {
+                expired.push((task_id.clone(), pending.winner_plan_id.clone()));
+            }
+        }
 
-        for stale in stale_ids {
-            if stale != self.agent_id.to_string() {
-                self.member_set.remove(&stale);
-                self.member_last_seen.remove(&stale);
-                self.member_last_task_poll.remove(&stale);
-                self.member_last_result.remove(&stale);
-                self.agent_activity.remove(&stale);
-                self.agent_tiers.remove(&stale);
-                self.agent_parents.remove(&stale);
+        for (task_id, _) in &approved {
+            self.pending_plan_approvals.remove(task_id);
+            if let Some(task) = self.task_details.get_mut(task_id) {
+                task.status = TaskStatus::InProgress;
             }
+            if let Some(holon) = self.active_holons.get_mut(task_id) {
+                holon.status = HolonStatus::Executing;
+            }
+        }
+        for (task_id, plan_id) in expired {
+            self.pending_plan_approvals.remove(&task_id);
+            self.close_rejected_task(
+                &task_id,
+                "approval_timeout",
+                format!("Plan {} rejected: no approval before deadline", plan_id),
+                None,
+            );
         }
+
+        approved.sort();
+        approved
     }
-}
 
-/// The main WWS.Connector that orchestrates all subsystems.
-///
-/// Created from a configuration, it initializes the network, hierarchy,
-/// consensus, and state modules, then runs the event loop that ties
-/// them together.
-pub struct WwsConnector {
-    /// Shared mutable state.
-    pub state: Arc<RwLock<ConnectorState>>,
-    /// Network handle for sending commands to the swarm.
-    pub network_handle: SwarmHandle,
-    /// Channel for receiving network events.
-    event_rx: Option<mpsc::Receiver<NetworkEvent>>,
-    /// The swarm host (to be spawned).
-    swarm_host: Option<SwarmHost>,
-    /// Configuration.
-    config: ConnectorConfig,
-}
-
-impl WwsConnector {
-    /// Create a new connector from configuration.
-    ///
-    /// Initializes all subsystems but does not start the event loop.
-    /// Call `run()` to start processing.
-    pub fn new(config: ConnectorConfig) -> Result<Self, anyhow::Error> {
-        // Build network configuration.
-        let listen_addr = config.network.listen_addr.parse()
-            .map_err(|e| anyhow::anyhow!("Invalid listen address: {}", e))?;
+    /// Apply a proposer's post-critique plan revision to the RFP and voting
+    /// engine, recording both versions in the deliberation transcript.
+    pub fn apply_plan_revision(
+        &mut self,
+        params: &ProposalRevisionParams,
+    ) -> Result<(), String> {
+        let rfp = self
+            .rfp_coordinators
+            .get_mut(&params.task_id)
+            .ok_or_else(|| format!("No RFP in progress for task {}", params.task_id))?;
+        let previous = rfp.record_revision(params).map_err(|e| e.to_string())?;
+        if let Some(voting) = self.voting_engines.get_mut(&params.task_id) {
+            voting
+                .replace_proposal(&params.original_plan_id, &params.plan.plan_id)
+                .map_err(|e| e.to_string())?;
+        }
 
-        // Parse bootstrap peer multiaddresses into (PeerId, Multiaddr) pairs.
-        let bootstrap_peers = Self::parse_bootstrap_peers(&config.network.bootstrap_peers);
+        let proposer = params.plan.proposer.clone();
+        let mut content = format!(
+            "Revised plan {} -> {}",
+            params.original_plan_id, params.plan.plan_id
+        );
+        if !params.reason.trim().is_empty() {
+            content.push_str(&format!(": {}", params.reason.trim()));
+        }
+        for (label, plan) in [("Original", &previous.plan), ("Revised", &params.plan)] {
+            content.push_str(&format!("\n\n{} ({}):", label, plan.plan_id));
+            for st in &plan.subtasks {
+                content.push_str(&format!("\n{}. {}", st.index, st.description));
+            }
+        }
+        self.deliberation_messages
+            .entry(params.task_id.clone())
+            .or_default()
+            .push(DeliberationMessage {
+                id: uuid::Uuid::new_v4().to_string(),
+                task_id: params.task_id.clone(),
+                timestamp: chrono::Utc::now(),
+                speaker: proposer.clone(),
+                round: 3,
+                message_type: DeliberationType::PlanRevision,
+                content,
+                referenced_plan_id: Some(params.plan.plan_id.clone()),
+                critic_scores: None,
+                hlc: Some(hlc::global().now()),
+            });
+        self.push_task_timeline_event(
+            &params.task_id,
+            "plan_revised",
+            format!(
+                "Plan {} revised as {}",
+                params.original_plan_id, params.plan.plan_id
+            ),
+            Some(proposer.to_string()),
+        );
+        self.push_log(
+            LogCategory::Vote,
+            format!(
+                "Plan revision for task {} from {} ({} -> {})",
+                params.task_id, proposer, params.original_plan_id, params.plan.plan_id
+            ),
+        );
+        Ok(())
+    }
 
-        let swarm_config = SwarmHostConfig {
-            listen_addr,
-            transport: TransportConfig::default(),
-            discovery: DiscoveryConfig {
-                mdns_enabled: config.network.mdns_enabled,
-                bootstrap_peers,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
+    /// Withdraw a proposer's plan from a task's RFP and voting, recording
+    /// the withdrawal in the deliberation transcript. A withdrawal after
+    /// ballots have been cast slashes the proposer's bond.
+    pub fn apply_plan_withdrawal(&mut self, params: &ProposalWithdrawParams) -> Result<(), String> {
+        if self
+            .voting_engines
+            .get(&params.task_id)
+            .is_some_and(|voting| voting.is_finalized())
+        {
+            return Err(format!("Voting for task {} has closed", params.task_id));
+        }
+        let rfp = self
+            .rfp_coordinators
+            .get_mut(&params.task_id)
+            .ok_or_else(|| format!("No RFP in progress for task {}", params.task_id))?;
+        rfp.record_withdrawal(params).map_err(|e| e.to_string())?;
+        let mut late = false;
+        if let Some(voting) = self.voting_engines.get_mut(&params.task_id) {
+            late = voting.ballot_count() > 0;
+            // The engine only knows plans that reached it; one that did
+            // not is already out of the running.
+            let _ = voting.withdraw_proposal(&params.plan_id);
+        }
 
-        let (swarm_host, network_handle, event_rx) = SwarmHost::new(swarm_config)?;
-        let local_peer_id = network_handle.local_peer_id();
-        let agent_id = AgentId::new(format!("did:swarm:{}", local_peer_id));
+        let mut content = format!("Withdrew plan {}", params.plan_id);
+        if !params.reason.trim().is_empty() {
+            content.push_str(&format!(": {}", params.reason.trim()));
+        }
+        self.deliberation_messages
+            .entry(params.task_id.clone())
+            .or_default()
+            .push(DeliberationMessage {
+                id: uuid::Uuid::new_v4().to_string(),
+                task_id: params.task_id.clone(),
+                timestamp: chrono::Utc::now(),
+                speaker: params.proposer.clone(),
+                round: 3,
+                message_type: DeliberationType::PlanWithdrawal,
+                content,
+                referenced_plan_id: Some(params.plan_id.clone()),
+                critic_scores: None,
+                hlc: Some(hlc::global().now()),
+            });
+        self.push_task_timeline_event(
+            &params.task_id,
+            "plan_withdrawn",
+            format!("Plan {} withdrawn", params.plan_id),
+            Some(params.proposer.to_string()),
+        );
+        self.push_log(
+            LogCategory::Vote,
+            format!(
+                "Plan withdrawal for task {} from {} ({})",
+                params.task_id, params.proposer, params.plan_id
+            ),
+        );
+        if late {
+            self.slash_proposal_bond(&params.task_id, params.proposer.as_str(), SlashReason::LateWithdrawal);
+        }
+        Ok(())
+    }
 
-        // Initialize hierarchy.
-        let pyramid_config = PyramidConfig {
-            branching_factor: config.hierarchy.branching_factor,
-            ..Default::default()
-        };
-        let epoch_config = EpochConfig {
-            duration_secs: config.hierarchy.epoch_duration_secs,
-            ..Default::default()
-        };
+    /// `agent`'s reputation: its score in the attestation ledger less the
+    /// bond points slashed here, or, before anything has been attested
+    /// about it, the score from its activity seen here.
+    pub fn reputation_of(&self, agent: &str) -> u64 {
+        let activity = self.agent_activity.get(agent);
+        match self.reputation.score(agent) {
+            Some(score) => {
+                let slashed = activity.map(|a| a.bond_points_slashed).unwrap_or(0) as i64;
+                (score - slashed).max(0) as u64
+            }
+            None => AgentActivity::reputation_of(activity),
+        }
+    }
 
-        // Build swarm identity.
-        let current_swarm_id = SwarmId::new(config.swarm.swarm_id.clone());
-        let swarm_token = config.swarm.token.as_ref().map(|t| SwarmToken::new(t.clone()));
+    /// Hold the bond a proposal commit stakes, capped at the proposer's
+    /// current reputation.
+    pub fn hold_proposal_bond(&mut self, commit: &ProposalCommitParams) {
+        let reputation = self.reputation_of(commit.proposer.as_str());
+        self.proposal_bonds
+            .hold(&commit.task_id, commit.proposer.as_str(), commit.bond, reputation);
+    }
 
-        // Initialize known swarms with our own swarm.
-        let mut known_swarms = std::collections::HashMap::new();
-        known_swarms.insert(
-            current_swarm_id.as_str().to_string(),
-            SwarmRecord {
-                swarm_id: current_swarm_id.clone(),
-                name: config.swarm.name.clone(),
-                is_public: config.swarm.is_public,
-                agent_count: 1,
-                joined: true,
-                last_seen: chrono::Utc::now(),
-            },
+    /// Slash the bond `proposer` staked on `task_id`, charging it against
+    /// the proposer's reputation. Returns the points slashed, or `None`
+    /// when no bond was held.
+    pub fn slash_proposal_bond(&mut self, task_id: &str, proposer: &str, reason: SlashReason) -> Option<u64> {
+        let bond = self.proposal_bonds.slash(task_id, proposer)?;
+        let activity = self.activity_mut(proposer);
+        activity.bonds_slashed_count += 1;
+        activity.bond_points_slashed += bond;
+        self.push_task_timeline_event(
+            task_id,
+            "bond_slashed",
+            format!("Bond of {} points slashed ({})", bond, reason),
+            Some(proposer.to_string()),
         );
+        self.push_log(
+            LogCategory::Vote,
+            format!(
+                "Slashed {} bond points of {} on task {} ({})",
+                bond, proposer, task_id, reason
+            ),
+        );
+        Some(bond)
+    }
 
-        let state = ConnectorState {
-            agent_id: agent_id.clone(),
-            status: ConnectorStatus::Initializing,
-            epoch_manager: EpochManager::new(epoch_config),
-            pyramid: PyramidAllocator::new(pyramid_config),
-            election: None,
-            geo_cluster: GeoCluster::default(),
-            succession: SuccessionManager::new(),
-            rfp_coordinators: std::collections::HashMap::new(),
-            voting_engines: std::collections::HashMap::new(),
-            cascade: CascadeEngine::new(),
-            task_set: OrSet::new(agent_id.to_string()),
-            task_details: std::collections::HashMap::new(),
-            task_timelines: std::collections::HashMap::new(),
-            agent_set: OrSet::new(agent_id.to_string()),
-            member_set: OrSet::new(agent_id.to_string()),
-            member_last_seen: {
-                let mut m = std::collections::HashMap::new();
-                m.insert(agent_id.to_string(), chrono::Utc::now());
-                m
-            },
-            agent_names: {
-                let mut m = std::collections::HashMap::new();
-                m.insert(agent_id.to_string(), config.agent.name.clone());
-                m
-            },
-            agent_activity: {
-                let mut m = std::collections::HashMap::new();
-                m.insert(agent_id.to_string(), AgentActivity::default());
-                m
-            },
-            task_vote_requirements: std::collections::HashMap::new(),
-            member_last_task_poll: std::collections::HashMap::new(),
-            member_last_result: std::collections::HashMap::new(),
-            task_result_text: std::collections::HashMap::new(),
-            pending_plan_reveals: std::collections::HashMap::new(),
-            merkle_dag: MerkleDag::new(),
-            content_store: ContentStore::new(),
-            granularity: GranularityAlgorithm::default(),
-            my_tier: Tier::Executor,
-            parent_id: None,
-            agent_tiers: std::collections::HashMap::new(),
-            agent_parents: std::collections::HashMap::new(),
-            current_layout: None,
-            subordinates: std::collections::HashMap::new(),
-            task_results: std::collections::HashMap::new(),
-            network_stats: NetworkStats {
-                total_agents: 1,
-                hierarchy_depth: 1,
-                branching_factor: config.hierarchy.branching_factor,
-                current_epoch: 1,
-                my_tier: Tier::Executor,
-                subordinate_count: 0,
-                parent_id: None,
-            },
-            event_log: Vec::new(),
-            message_trace: Vec::new(),
-            start_time: chrono::Utc::now(),
-            current_swarm_id,
-            known_swarms,
-            swarm_token,
-            active_holons: std::collections::HashMap::new(),
-            deliberation_messages: std::collections::HashMap::new(),
-            ballot_records: std::collections::HashMap::new(),
-            irv_rounds: std::collections::HashMap::new(),
-            board_acceptances: std::collections::HashMap::new(),
-            registered_names: std::collections::HashMap::new(),
-            pending_verifications: std::collections::HashMap::new(),
-            verified_agents: std::collections::HashSet::new(),
-            direct_messages: Vec::new(),
-        };
-
-        Ok(Self {
-            state: Arc::new(RwLock::new(state)),
-            network_handle,
-            event_rx: Some(event_rx),
-            swarm_host: Some(swarm_host),
-            config,
-        })
+    /// Slash the bonds of the plans in `flagged` when `critic` is the
+    /// adversarial critic of the task's board; flags from other members
+    /// are only advisory.
+    pub fn apply_malicious_flags(&mut self, task_id: &str, critic: &str, flagged: &[String]) {
+        let is_adversary = self
+            .active_holons
+            .get(task_id)
+            .and_then(|holon| holon.adversarial_critic.as_ref())
+            .is_some_and(|adversary| adversary.as_str() == critic);
+        if !is_adversary || flagged.is_empty() {
+            return;
+        }
+        let proposers: Vec<String> = self
+            .rfp_coordinators
+            .get(task_id)
+            .map(|rfp| {
+                rfp.reveals
+                    .values()
+                    .filter(|r| flagged.contains(&r.plan.plan_id))
+                    .map(|r| r.plan.proposer.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        for proposer in proposers {
+            self.slash_proposal_bond(task_id, &proposer, SlashReason::FlaggedMalicious);
+        }
     }
 
-    /// Start the connector, running the swarm and event loop.
-    ///
-    /// This spawns the swarm host as a background task and runs
-    /// the main event processing loop.
-    pub async fn run(mut self) -> Result<(), anyhow::Error> {
-        // Take and spawn the swarm host.
-        let swarm_host = self
-            .swarm_host
-            .take()
-            .ok_or_else(|| anyhow::anyhow!("SwarmHost already consumed"))?;
+    /// Record the cost reported with a task's result. A re-submission
+    /// replaces the earlier figure rather than adding to it.
+    pub fn record_task_cost(&mut self, task_id: &str, cost: f64) {
+        if !cost.is_finite() || cost < 0.0 {
+            return;
+        }
+        self.task_costs.insert(task_id.to_string(), cost);
+    }
 
-        tokio::spawn(async move {
-            if let Err(e) = swarm_host.run().await {
-                tracing::error!(error = %e, "Swarm host error");
+    /// Total spend of a task: its own reported cost plus that of every
+    /// known descendant. Subtask lists come from peers, so each task is
+    /// counted once even if they form a cycle.
+    pub fn task_spend(&self, task_id: &str) -> f64 {
+        let mut seen = std::collections::HashSet::from([task_id.to_string()]);
+        let mut pending = vec![task_id.to_string()];
+        let mut total = 0.0;
+        while let Some(id) = pending.pop() {
+            total += self.task_costs.get(&id).copied().unwrap_or(0.0);
+            if let Some(task) = self.task_details.get(&id) {
+                for sub_id in &task.subtasks {
+                    if seen.insert(sub_id.clone()) {
+                        pending.push(sub_id.clone());
+                    }
+                }
             }
-        });
-
-        // Subscribe to core topics.
-        self.network_handle.subscribe_core_topics().await?;
+        }
+        total
+    }
 
-        // Subscribe to our swarm's topics (if not the default public swarm,
-        // since core topics already include the public swarm).
-        let swarm_id_str = self.config.swarm.swarm_id.clone();
-        if swarm_id_str != wws_protocol::DEFAULT_SWARM_ID {
-            self.network_handle
-                .subscribe_swarm_topics(&swarm_id_str)
-                .await?;
+    /// Tightest remaining budget across a task and its ancestors, or `None`
+    /// when no budget applies.
+    pub fn budget_remaining(&self, task_id: &str) -> Option<f64> {
+        let mut remaining: Option<f64> = None;
+        let mut current = Some(task_id.to_string());
+        let mut depth = 0;
+        while let Some(id) = current {
+            let Some(task) = self.task_details.get(&id) else {
+                break;
+            };
+            if let Some(budget) = task.budget {
+                let left = budget - self.task_spend(&id);
+                remaining = Some(remaining.map_or(left, |r: f64| r.min(left)));
+            }
+            depth += 1;
+            if depth > wws_protocol::MAX_HIERARCHY_DEPTH {
+                break;
+            }
+            current = task.parent_task_id.clone();
         }
+        remaining
+    }
 
-        self.subscribe_task_assignment_topics(&swarm_id_str).await;
+    pub fn budget_exhausted(&self, task_id: &str) -> bool {
+        self.budget_remaining(task_id).is_some_and(|r| r <= 0.0)
+    }
 
-        // Subscribe to the direct messages topic for this swarm.
-        let messages_topic = SwarmTopics::messages_for(&swarm_id_str);
-        if let Err(e) = self.network_handle.subscribe(&messages_topic).await {
-            tracing::debug!(error = %e, topic = %messages_topic, "Failed to subscribe messages topic");
+    /// Keep a submitted result's content in the local content store and
+    /// advertise `provider` as holding it.
+    pub fn store_result_content(&mut self, content: &str, provider: &str) {
+        // Refresh pins first so making room never evicts content still in use.
+        self.refresh_content_pins();
+        let cid = self.content_store.store(content.as_bytes());
+        if self.content_store.exists(&cid) {
+            self.content_store.publish_provider(&cid, provider.to_string());
         }
+    }
 
-        // Connect to bootstrap peers to join the swarm network immediately.
-        self.connect_to_bootstrap_peers().await;
+    /// Members who may read the result `producer` submits for `task_id`, or
+    /// `None` when it is public. The producer, the coordinator it reports
+    /// to and the assignee of the parent task are always among them.
+    pub fn result_audience(&self, task_id: &str, producer: &str) -> Option<Vec<String>> {
+        let task = self.task_details.get(task_id)?;
+        let mut audience = vec![producer.to_string()];
+        audience.extend(self.agent_parents.get(producer).cloned());
+        audience.extend(
+            task.parent_task_id
+                .as_deref()
+                .and_then(|p| self.task_details.get(p))
+                .and_then(|p| p.assigned_to.as_ref())
+                .map(|a| a.to_string()),
+        );
+        match task.visibility {
+            ResultVisibility::Public => return None,
+            ResultVisibility::TierAndAbove => {
+                let own = (&self.agent_id.to_string(), &self.my_tier);
+                audience.extend(
+                    self.agent_tiers
+                        .iter()
+                        .chain([own])
+                        .filter(|(_, tier)| tier.depth() <= task.tier_level)
+                        .map(|(agent, _)| agent.clone()),
+                );
+            }
+            ResultVisibility::BoardOnly => {
+                if let Some(board) = self.result_board(task_id) {
+                    audience.push(board.chair.to_string());
+                    audience.extend(board.members.iter().map(|m| m.to_string()));
+                }
+            }
+        }
+        audience.sort();
+        audience.dedup();
+        Some(audience)
+    }
 
-        // Initiate Kademlia bootstrap to populate the DHT routing table.
-        if !self.config.network.bootstrap_peers.is_empty() {
-            if let Err(e) = self.network_handle.bootstrap().await {
-                tracing::warn!(error = %e, "Kademlia bootstrap initiation failed");
+    /// The board nearest to `task_id`: its own, or the one that split the
+    /// closest ancestor that had one.
+    fn result_board(&self, task_id: &str) -> Option<&HolonState> {
+        let mut next = Some(task_id);
+        for _ in 0..=MAX_HIERARCHY_DEPTH {
+            let id = next?;
+            if let Some(board) = self.active_holons.get(id) {
+                return Some(board);
             }
+            next = self.task_details.get(id)?.parent_task_id.as_deref();
         }
+        None
+    }
 
-        // Update status.
-        {
-            let mut state = self.state.write().await;
-            state.status = ConnectorStatus::Running;
-            state.push_log(
-                LogCategory::System,
-                format!(
-                    "WWS.Connector started (swarm: {} [{}])",
-                    self.config.swarm.name, swarm_id_str
-                ),
-            );
+    /// Seal `artifact`, the result `producer` submits for `task_id`, to the
+    /// members allowed to read it, taking `content` as the result text when
+    /// the artifact carries none. Public results are left as they are.
+    pub fn seal_result(
+        &self,
+        task_id: &str,
+        producer: &str,
+        artifact: &mut Artifact,
+        content: &str,
+    ) -> Result<(), String> {
+        let Some(audience) = self.result_audience(task_id, producer) else {
+            return Ok(());
+        };
+        let recipients: Vec<(String, ed25519_dalek::VerifyingKey)> = audience
+            .into_iter()
+            .filter_map(|member| self.member_key(&member).map(|key| (member, key)))
+            .collect();
+        let text = if artifact.content.is_empty() { content } else { &artifact.content };
+        let sealed = SealedResult::seal(task_id, text.as_bytes(), &recipients).map_err(|e| e.to_string())?;
+        artifact.sealed = Some(sealed);
+        Ok(())
+    }
+
+    /// The identity key of `member`: embedded in its DID, held by a local
+    /// agent, or published in its DID document. Members whose key is not
+    /// known cannot be sealed to.
+    fn member_key(&self, member: &str) -> Option<ed25519_dalek::VerifyingKey> {
+        if member == self.agent_id.as_str() {
+            return Some(self.identity_key.verifying_key());
         }
+        crate::did::key_of(member)
+            .or_else(|| self.local_agents.get(member).map(|agent| agent.verifying_key()))
+            .or_else(|| self.did_documents.key(member))
+    }
 
-        tracing::info!("WWS.Connector is running");
+    /// Open a sealed result of `task_id` with the key of this node's agent
+    /// or one of its local agents. `None` when it is sealed to none of them.
+    pub fn open_result(&self, task_id: &str, sealed: &SealedResult) -> Option<String> {
+        let content = sealed
+            .open(task_id, self.agent_id.as_str(), &self.identity_key)
+            .ok()
+            .or_else(|| {
+                self.local_agents
+                    .agents()
+                    .find_map(|agent| agent.open_result(task_id, sealed).ok())
+            })?;
+        Some(String::from_utf8_lossy(&content).into_owned())
+    }
 
-        // Take the event receiver out of self so we can use both in the loop.
-        let mut event_rx = self
-            .event_rx
-            .take()
-            .ok_or_else(|| anyhow::anyhow!("Event receiver already consumed"))?;
-        let keepalive_secs = self.config.hierarchy.keepalive_interval_secs;
-        let mut keepalive_interval =
-            tokio::time::interval(Duration::from_secs(keepalive_secs));
-        let mut epoch_tick = tokio::time::interval(Duration::from_secs(1));
-        let announce_secs = self.config.swarm.announce_interval_secs;
-        let mut swarm_announce_interval =
-            tokio::time::interval(Duration::from_secs(announce_secs));
-        let mut bootstrap_retry_interval = tokio::time::interval(Duration::from_secs(20));
-        // Voting completion check every 5 seconds
-        let mut voting_check_interval = tokio::time::interval(Duration::from_secs(5));
-        let mut execution_timeout_interval = tokio::time::interval(Duration::from_secs(10));
+    /// Whether `viewer` may read the result of `task_id`: it is public, or
+    /// sealed to `viewer` among others.
+    pub fn may_view_result(&self, task_id: &str, viewer: &str) -> bool {
+        self.task_results
+            .get(task_id)
+            .and_then(|artifact| artifact.sealed.as_ref())
+            .is_none_or(|sealed| sealed.members().any(|member| member == viewer))
+    }
 
-        loop {
-            tokio::select! {
-                Some(event) = event_rx.recv() => {
-                    self.handle_network_event(event).await;
-                }
-                _ = keepalive_interval.tick() => {
-                    self.send_keepalive().await;
-                }
-                _ = epoch_tick.tick() => {
-                    self.check_epoch_transition().await;
-                }
-                _ = swarm_announce_interval.tick() => {
-                    self.announce_swarm().await;
-                }
-                _ = bootstrap_retry_interval.tick() => {
-                    self.connect_to_bootstrap_peers().await;
-                    if !self.config.network.bootstrap_peers.is_empty() {
-                        let _ = self.network_handle.bootstrap().await;
-                    }
-                }
-                _ = voting_check_interval.tick() => {
-                    self.check_voting_completion().await;
-                }
-                _ = execution_timeout_interval.tick() => {
-                    self.check_execution_timeouts().await;
-                }
-            }
+    /// Keep an attachment's content in the local content store and
+    /// advertise this agent as holding it. Returns the content's CID.
+    pub fn store_attachment(&mut self, content: &str) -> String {
+        self.refresh_content_pins();
+        let cid = self.content_store.store(content.as_bytes());
+        if self.content_store.exists(&cid) {
+            let provider = self.agent_id.to_string();
+            self.content_store.publish_provider(&cid, provider);
         }
+        cid
     }
 
-    /// Handle a network event from the swarm.
-    async fn handle_network_event(&self, event: NetworkEvent) {
-        match event {
-            NetworkEvent::MessageReceived { topic, data, source, .. } => {
-                {
-                    let mut state = self.state.write().await;
-                    let decoded = serde_json::from_slice::<SwarmMessage>(&data).ok();
-                    let task_id = decoded
-                        .as_ref()
-                        .and_then(|m| m.params.get("task_id").and_then(|v| v.as_str()).map(|s| s.to_string()));
-                    state.push_message_trace(MessageTraceEvent {
-                        timestamp: chrono::Utc::now(),
-                        direction: "inbound".to_string(),
-                        peer: Some(source.to_string()),
-                        topic: topic.clone(),
-                        method: decoded.as_ref().map(|m| m.method.clone()),
-                        task_id,
-                        size_bytes: data.len(),
-                        outcome: "received".to_string(),
-                    });
-                    state.push_log(
-                        LogCategory::Message,
-                        format!("Message received on {} from {}", topic, source),
-                    );
-                }
-                self.handle_message(&topic, &data, source).await;
-            }
-            NetworkEvent::PeerConnected(peer) => {
-                tracing::debug!(peer = %peer, "Peer connected");
-                let mut state = self.state.write().await;
-                state.agent_set.add(peer.to_string());
-                state.mark_member_seen(&format!("did:swarm:{}", peer));
-                state.push_log(
-                    LogCategory::Peer,
-                    format!("Connected: {}", peer),
-                );
-                state.network_stats.total_agents = state
-                    .active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
-                    as u64;
-            }
-            NetworkEvent::PeerDisconnected(peer) => {
-                tracing::debug!(peer = %peer, "Peer disconnected");
-                let mut state = self.state.write().await;
-                state.agent_set.remove(&peer.to_string());
-                state.push_log(
-                    LogCategory::Peer,
-                    format!("Disconnected: {}", peer),
-                );
-            }
-            NetworkEvent::PingRtt { peer, rtt } => {
-                tracing::trace!(peer = %peer, rtt_ms = rtt.as_millis(), "Ping RTT");
+    /// Store the inline attachment content that came with an injected task.
+    /// Blobs for CIDs the task does not reference, or whose content does not
+    /// hash to their CID, are dropped. Returns the number dropped.
+    pub fn store_attachment_blobs(&mut self, task: &Task, blobs: &[AttachmentBlob]) -> usize {
+        let mut dropped = 0;
+        for blob in blobs {
+            let referenced = task.attachments.iter().any(|a| a.cid == blob.cid);
+            if referenced && ContentStore::compute_cid(blob.content.as_bytes()) == blob.cid {
+                self.store_attachment(&blob.content);
+            } else {
+                dropped += 1;
             }
-            _ => {}
         }
+        dropped
     }
 
-    /// Handle a protocol message received on a topic.
-    async fn handle_message(
-        &self,
-        topic: &str,
-        data: &[u8],
-        source: wws_network::PeerId,
-    ) {
-        let message: SwarmMessage = match serde_json::from_slice(data) {
-            Ok(m) => m,
-            Err(e) => {
-                tracing::warn!(error = %e, "Failed to parse swarm message");
-                let mut state = self.state.write().await;
-                state.push_log(
-                    LogCategory::Error,
-                    format!("Failed to parse message on {}: {}", topic, e),
-                );
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "inbound".to_string(),
-                    peer: Some(source.to_string()),
-                    topic: topic.to_string(),
-                    method: None,
-                    task_id: None,
-                    size_bytes: data.len(),
-                    outcome: "parse_error".to_string(),
+    /// Inline blobs for the attachments of `task` held locally, up to
+    /// `MAX_INLINE_ATTACHMENT_BYTES` in total. Content that does not fit is
+    /// left for peers to reference by CID.
+    pub fn attachment_blobs(&self, task: &Task) -> Vec<AttachmentBlob> {
+        let mut room = MAX_INLINE_ATTACHMENT_BYTES;
+        let mut blobs = Vec::new();
+        for attachment in &task.attachments {
+            let Some(content) = self
+                .content_store
+                .get(&attachment.cid)
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+            else {
+                continue;
+            };
+            if content.len() <= room {
+                room -= content.len();
+                blobs.push(AttachmentBlob {
+                    cid: attachment.cid.clone(),
+                    content,
                 });
-                return;
             }
-        };
+        }
+        blobs
+    }
 
-        match ProtocolMethod::from_str(&message.method) {
-            Some(ProtocolMethod::KeepAlive) => {
-                if let Ok(params) = serde_json::from_value::<KeepAliveParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    state.succession.record_keepalive(&params.agent_id);
-                    state.mark_member_seen_with_name(
-                        params.agent_id.as_str(),
-                        params.agent_name.as_deref(),
-                    );
-                    if let Some(ts) = params.last_task_poll_at {
-                        state
-                            .member_last_task_poll
-                            .insert(params.agent_id.to_string(), ts);
-                    }
-                    if let Some(ts) = params.last_result_at {
-                        state.member_last_result.insert(params.agent_id.to_string(), ts);
-                    }
-                    let active_members =
-                        state.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
-                    Self::recompute_hierarchy_from_members(&mut state, &active_members);
-                    state.push_log(
-                        LogCategory::Message,
-                        format!(
-                            "KeepAlive from {}",
-                            params
-                                .agent_name
-                                .clone()
-                                .unwrap_or_else(|| params.agent_id.to_string())
-                        ),
-                    );
-                }
-            }
-            Some(ProtocolMethod::AgentKeepAlive) => {
-                if let Ok(params) = serde_json::from_value::<KeepAliveParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    state.mark_member_seen_with_name(
-                        params.agent_id.as_str(),
-                        params.agent_name.as_deref(),
-                    );
-                    if let Some(ts) = params.last_task_poll_at {
-                        state
-                            .member_last_task_poll
-                            .insert(params.agent_id.to_string(), ts);
-                    }
-                    if let Some(ts) = params.last_result_at {
-                        state.member_last_result.insert(params.agent_id.to_string(), ts);
-                    }
-                    let active_members =
-                        state.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
-                    Self::recompute_hierarchy_from_members(&mut state, &active_members);
-                    state.push_log(
-                        LogCategory::System,
-                        format!(
-                            "Agent heartbeat: {}",
-                            params
-                                .agent_name
-                                .clone()
-                                .unwrap_or_else(|| params.agent_id.to_string())
-                        ),
-                    );
-                }
+    /// Assemble what `viewer` needs to work on `task_id`, within `limits`,
+    /// leaving out sibling results it may not read. `None` when the task is
+    /// unknown here.
+    pub fn task_context(&self, task_id: &str, viewer: &str, limits: ContextLimits) -> Option<TaskContext> {
+        let task = self.task_details.get(task_id)?;
+        let mut budget = ContextBudget::new(limits);
+        let description = budget.take(&task.description);
+
+        let mut lineage: Vec<&Task> = vec![task];
+        let mut ancestors = Vec::new();
+        while let Some(parent_id) = lineage[lineage.len() - 1].parent_task_id.as_deref() {
+            if ancestors.len() >= MAX_CONTEXT_ANCESTORS || lineage.iter().any(|t| t.task_id == parent_id) {
+                break;
             }
-            Some(ProtocolMethod::Candidacy) => {
-                if let Ok(params) = serde_json::from_value::<CandidacyParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    if let Some(ref mut election) = state.election {
-                        if let Err(e) = election.register_candidate(&params) {
-                            tracing::warn!(error = %e, "Failed to register candidate");
-                        }
-                    }
-                }
-            }
-            Some(ProtocolMethod::ElectionVote) => {
-                if let Ok(params) = serde_json::from_value::<ElectionVoteParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    if let Some(ref mut election) = state.election {
-                        if let Err(e) = election.record_vote(params) {
-                            tracing::warn!(error = %e, "Failed to record election vote");
-                        }
-                    }
-                }
+            let Some(parent) = self.task_details.get(parent_id) else {
+                break;
+            };
+            ancestors.push(AncestorContext {
+                task_id: parent.task_id.clone(),
+                description: budget.take(&parent.description),
+            });
+            lineage.push(parent);
+        }
+
+        let parent_id = task.parent_task_id.as_deref();
+        let plan = parent_id.and_then(|parent_id| {
+            let plan_id = self.task_winning_plans.get(parent_id)?;
+            let held = self
+                .rfp_coordinators
+                .get(parent_id)
+                .and_then(|rfp| rfp.reveals.values().find(|p| p.plan.plan_id == *plan_id))
+                .map(|p| &p.plan);
+            Some(PlanContext {
+                plan_id: plan_id.clone(),
+                proposer: held.map(|p| p.proposer.clone()),
+                rationale: held.map(|p| budget.take(&p.rationale)),
+                subtask_index: task_id
+                    .strip_prefix(parent_id)
+                    .and_then(|rest| rest.strip_prefix("-st-"))
+                    .and_then(|n| n.parse().ok()),
+                subtask_count: held.map(|p| p.subtasks.len()),
+            })
+        });
+
+        let sibling_tasks: Vec<&Task> = parent_id
+            .and_then(|p| self.task_details.get(p))
+            .map(|parent| {
+                parent
+                    .subtasks
+                    .iter()
+                    .filter(|id| *id != task_id)
+                    .filter_map(|id| self.task_details.get(id))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut siblings: Vec<SiblingContext> = sibling_tasks
+            .iter()
+            .map(|sibling| SiblingContext {
+                task_id: sibling.task_id.clone(),
+                status: sibling.status,
+                assigned_to: sibling.assigned_to.clone(),
+                description: budget.take(&sibling.description),
+                result: None,
+            })
+            .collect();
+        for sibling in &mut siblings {
+            if !self.may_view_result(&sibling.task_id, viewer) {
+                continue;
             }
-            Some(ProtocolMethod::TierAssignment) => {
-                if let Ok(params) = serde_json::from_value::<TierAssignmentParams>(message.params)
-                {
-                    let level = Self::tier_to_level(params.tier);
-                    let mut state = self.state.write().await;
-                    if params.assigned_agent == state.agent_id {
-                        state.my_tier = params.tier;
-                        state.parent_id = Some(params.parent_id);
-                        state.network_stats.my_tier = params.tier;
-                        tracing::info!(tier = ?params.tier, "Tier assignment received");
-                    }
-                    drop(state);
+            let result = self.task_result_text.get(&sibling.task_id).cloned().or_else(|| {
+                self.task_results
+                    .get(&sibling.task_id)
+                    .map(|artifact| artifact.content.clone())
+                    .filter(|content| !content.is_empty())
+            });
+            sibling.result = result.map(|text| budget.take(&text));
+        }
 
-                    if let Some(level) = level {
-                        let swarm_id = {
-                            let state = self.state.read().await;
-                            state.current_swarm_id.as_str().to_string()
-                        };
-                        let topic = SwarmTopics::tasks_for(&swarm_id, level);
-                        if let Err(e) = self.network_handle.subscribe(&topic).await {
-                            tracing::debug!(error = %e, topic = %topic, "Failed to subscribe assigned tier topic");
-                        }
-                    }
+        // The task's own attachments first; subtasks usually repeat their
+        // parent's, so each CID is listed once.
+        let mut seen = std::collections::HashSet::new();
+        let mut attachments = Vec::new();
+        for holder in &lineage {
+            for attachment in &holder.attachments {
+                if !seen.insert(attachment.cid.as_str()) {
+                    continue;
                 }
+                let content = self
+                    .content_store
+                    .get(&attachment.cid)
+                    .map(|bytes| budget.take(&String::from_utf8_lossy(&bytes)));
+                attachments.push(AttachmentContext {
+                    task_id: holder.task_id.clone(),
+                    name: attachment.name.clone(),
+                    cid: attachment.cid.clone(),
+                    content_type: attachment.content_type.clone(),
+                    size_bytes: attachment.size_bytes,
+                    content,
+                });
             }
-            Some(ProtocolMethod::TaskInjection) => {
-                if let Ok(params) = serde_json::from_value::<TaskInjectionParams>(message.params) {
-                    let mut state = self.state.write().await;
-
-                    // Tier-filtered task reception: only process tasks for our tier level
-                    let my_tier = state.my_tier;
-                    let task_tier_level = params.task.tier_level;
-
-                    // Each tier processes tasks at its level:
-                    // - Tier1 processes tier_level 1
-                    // - Tier2 processes tier_level 2
-                    // - TierN(n) processes tier_level n
-                    // - Executor processes any tier_level (leaf workers)
-                    let my_tier_level = my_tier.depth();
-                    let should_process = match my_tier {
-                        Tier::Executor => true, // Executors handle any level (leaf work)
-                        _ => my_tier_level == task_tier_level, // Coordinators only handle their level
-                    };
+        }
 
-                    if !should_process {
-                        tracing::debug!(
-                            task_id = %params.task.task_id,
-                            my_tier = ?my_tier,
-                            task_tier = task_tier_level,
-                            "Ignoring task for different tier"
-                        );
-                        drop(state);
-                        return;
-                    }
+        Some(TaskContext {
+            task_id: task.task_id.clone(),
+            status: task.status,
+            tier_level: task.tier_level,
+            description,
+            capabilities_required: task.capabilities_required.clone(),
+            deadline: task.deadline,
+            ancestors,
+            plan,
+            siblings,
+            attachments,
+            max_bytes: limits.max_bytes,
+            used_bytes: budget.used(),
+            truncated: budget.truncated(),
+        })
+    }
 
-                    state.task_set.add(params.task.task_id.clone());
-                    let injected_id = params.task.task_id.clone();
-                    let injected_parent_id = params.task.parent_task_id.clone();
-                    state
-                        .task_details
-                        .insert(injected_id.clone(), params.task.clone());
-                    // Update parent's subtasks list when a sub-holon task arrives
-                    if let Some(parent_id) = &injected_parent_id {
-                        if let Some(parent) = state.task_details.get_mut(parent_id) {
-                            if !parent.subtasks.iter().any(|id| id == &injected_id) {
-                                parent.subtasks.push(injected_id.clone());
-                            }
-                        }
-                    }
-                    state.push_task_timeline_event(
-                        &params.task.task_id,
-                        "injected",
-                        format!("Task injected: {}", params.task.description),
-                        None,
-                    );
-                    state.push_log(
-                        LogCategory::Task,
-                        format!(
-                            "Task injected at my tier: {} ({})",
-                            params.task.task_id, params.task.description
-                        ),
-                    );
+    /// Ask the task, artifact and timeline stores to persist pending
+    /// changes. Every store is flushed even if an earlier one fails.
+    pub fn flush_stores(&mut self) -> Result<(), String> {
+        let failures: Vec<String> = [
+            ("tasks", self.task_details.flush()),
+            ("artifacts", self.task_results.flush()),
+            ("timelines", self.task_timelines.flush()),
+            ("attestations", self.reputation.flush()),
+        ]
+        .into_iter()
+        .filter_map(|(store, result)| result.err().map(|e| format!("{}: {}", store, e)))
+        .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("; "))
+        }
+    }
 
-                    // All coordinator tiers initialize RFP for competitive planning
-                    let is_coordinator = my_tier != Tier::Executor;
-                    let task_id = params.task.task_id.clone();
-                    let epoch = params.task.epoch;
+    /// Pin the content of results still needed by an incomplete task (the
+    /// task itself or the parent that will aggregate it) and the
+    /// attachments of incomplete tasks, then evict
+    /// unpinned content down to the store's size cap. Returns the number of
+    /// items evicted.
+    pub fn collect_content_garbage(&mut self) -> usize {
+        self.refresh_content_pins();
+        self.content_store.gc()
+    }
 
-                    if is_coordinator {
-                        // Count agents at my tier level for quorum
-                        let my_tier_agents = state.agent_tiers.values()
-                            .filter(|t| **t == my_tier)
-                            .count();
+    fn refresh_content_pins(&mut self) {
+        let incomplete = |task_id: &str| {
+            self.task_details.get(task_id).is_some_and(|t| {
+                !matches!(t.status, TaskStatus::Completed | TaskStatus::Rejected)
+            })
+        };
+        let mut pins = std::collections::HashSet::new();
+        for task in self.task_details.values() {
+            if incomplete(&task.task_id) {
+                pins.extend(task.attachments.iter().map(|a| a.cid.clone()));
+            }
+        }
+        for (task_id, artifact) in self.task_results.iter() {
+            let parent_incomplete = self
+                .task_details
+                .get(task_id)
+                .and_then(|t| t.parent_task_id.as_deref())
+                .is_some_and(&incomplete);
+            if incomplete(task_id) || parent_incomplete {
+                pins.insert(artifact.content_cid.clone());
+                if let Some(text) = self.task_result_text.get(task_id) {
+                    pins.insert(ContentStore::compute_cid(text.as_bytes()));
+                }
+            }
+        }
+        self.content_store.set_pins(pins);
+    }
 
-                        if my_tier_agents > 0 {
-                            let mut rfp = RfpCoordinator::new(
-                                task_id.clone(),
-                                epoch,
-                                my_tier_agents,
-                            );
+    /// Drop causally stable tombstones from the member, task and peer sets.
+    /// None of them is gossiped to peers yet, so this connector is their only
+    /// replica and every local removal is stable. Returns the number dropped.
+    pub fn compact_tombstones(&mut self) -> usize {
+        let replicas = [self.agent_id.to_string()];
+        self.member_set.compact(&replicas)
+            + self.task_set.compact(&replicas)
+            + self.agent_set.compact(&replicas)
+    }
 
-                            if let Err(e) = rfp.inject_task(&params.task) {
-                                tracing::error!(error = %e, "Failed to initialize RFP");
-                            } else {
-                                state.rfp_coordinators.insert(task_id.clone(), rfp);
-                                state.push_log(
-                                    LogCategory::Task,
-                                    format!("RFP initialized for task {} with {} {:?} agents", task_id, my_tier_agents, my_tier),
-                                );
-                            }
-                        }
-                    }
+    /// Quorum policy for a task: its own override, else the swarm default.
+    pub fn quorum_policy_for(&self, task_id: &str) -> QuorumPolicy {
+        self.task_details
+            .get(task_id)
+            .and_then(|t| t.quorum.clone())
+            .unwrap_or_else(|| self.quorum_policy.clone())
+    }
 
-                    // Create holon record for this task in Forming status
-                    let my_agent_id = state.agent_id.clone();
-                    let task_tier = params.task.tier_level;
-                    let parent_task_id = params.task.parent_task_id.clone();
-                    state.active_holons.entry(task_id.clone()).or_insert_with(|| HolonState {
-                        task_id: task_id.clone(),
-                        chair: my_agent_id,
-                        members: Vec::new(),
-                        adversarial_critic: None,
-                        depth: task_tier,
-                        parent_holon: parent_task_id,
-                        child_holons: Vec::new(),
-                        subtask_assignments: std::collections::HashMap::new(),
-                        status: HolonStatus::Forming,
-                        created_at: chrono::Utc::now(),
-                    });
+    /// Assignment strategy for a task's subtasks: its own override, else
+    /// the swarm default.
+    pub fn assignment_strategy_for(&self, task_id: &str) -> AssignmentStrategy {
+        self.task_details
+            .get(task_id)
+            .and_then(|t| t.assignment)
+            .unwrap_or(self.assignment_strategy)
+    }
 
-                    tracing::info!(
-                        task_id = %params.task.task_id,
-                        my_tier = ?my_tier,
-                        is_coordinator,
-                        "Task received and accepted"
-                    );
+    /// What an assignment strategy knows about `agent_id`.
+    pub fn assignment_candidate(&self, agent_id: &AgentId) -> crate::assignment::Candidate {
+        crate::assignment::Candidate {
+            load: self.load_of(agent_id),
+            capabilities: self
+                .agent_capabilities
+                .get(agent_id.as_str())
+                .map(|c| self.capabilities.canonicalize(c))
+                .unwrap_or_default(),
+            reputation: self.reputation_of(agent_id.as_str()),
+        }
+    }
 
-                    let swarm_id = state.current_swarm_id.as_str().to_string();
-                    drop(state);
+    /// Agents that accepted the board invitation for a task.
+    pub fn board_members_for(&self, task_id: &str) -> Vec<String> {
+        let mut members: Vec<String> = self
+            .board_acceptances
+            .get(task_id)
+            .map(|accepts| accepts.iter().map(|a| a.agent_id.to_string()).collect())
+            .unwrap_or_default();
+        members.sort();
+        members.dedup();
+        members
+    }
 
-                    self.subscribe_task_flow_topics(&swarm_id, &task_id).await;
-                }
-            }
-            Some(ProtocolMethod::TaskAssignment) => {
-                if let Ok(params) = serde_json::from_value::<TaskAssignmentParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    let mut task = params.task.clone();
-                    task.assigned_to = Some(params.assignee.clone());
-                    task.status = TaskStatus::InProgress;
-                    if task.deadline.is_none() {
-                        task.deadline = Some(
-                            chrono::Utc::now()
-                                + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS),
-                        );
-                    }
+    /// Proposal and ballot counts a task needs, given the members of its
+    /// tier that are eligible to take part. Proposers that withdrew their
+    /// plan are no longer waited for.
+    pub fn quorum_requirement(
+        &self,
+        task_id: &str,
+        tier_level: u32,
+        eligible: &[String],
+    ) -> TaskVoteRequirement {
+        let required = self
+            .quorum_policy_for(task_id)
+            .required(eligible, &self.board_members_for(task_id));
+        let withdrawn = self
+            .rfp_coordinators
+            .get(task_id)
+            .map(|rfp| rfp.withdrawn.len())
+            .unwrap_or(0);
+        TaskVoteRequirement {
+            expected_proposers: required.saturating_sub(withdrawn),
+            expected_voters: required,
+            tier_level,
+        }
+    }
 
-                    let task_id = task.task_id.clone();
-                    let parent_task_id = params.parent_task_id.clone();
-                    let assigned_here = params.assignee == state.agent_id;
+    /// Epoch a task's sortition committee is drawn for: the task's own,
+    /// else that of its proposal or voting round.
+    pub fn committee_epoch(&self, task_id: &str) -> u64 {
+        self.task_details
+            .get(task_id)
+            .map(|t| t.epoch)
+            .or_else(|| self.rfp_coordinators.get(task_id).map(|rfp| rfp.epoch()))
+            .or_else(|| self.voting_engines.get(task_id).map(|v| v.epoch()))
+            .unwrap_or_else(|| self.epoch_manager.current_epoch())
+    }
 
-                    if let Some(existing) = state.task_details.get(&task_id) {
-                        if matches!(existing.status, TaskStatus::Completed) {
-                            task.status = TaskStatus::Completed;
-                        }
-                    }
+    /// The sortition committee for a task, drawn from `eligible`, when a
+    /// committee size is set and `eligible` has more members than seats.
+    pub fn committee_for(
+        &self,
+        task_id: &str,
+        eligible: &[String],
+    ) -> Option<wws_consensus::Committee> {
+        if self.committee_size == 0 || eligible.len() <= self.committee_size {
+            return None;
+        }
+        let policy = self.quorum_policy_for(task_id);
+        Some(wws_consensus::Committee::draw(
+            self.committee_epoch(task_id),
+            task_id,
+            eligible,
+            self.committee_size,
+            |agent_id| policy.weight(agent_id),
+        ))
+    }
 
-                    state.task_details.insert(task_id.clone(), task);
-                    if let Some(parent) = state.task_details.get_mut(&parent_task_id) {
-                        if !parent.subtasks.iter().any(|id| id == &task_id) {
-                            parent.subtasks.push(task_id.clone());
-                        }
-                    }
-                    if assigned_here {
-                        state.task_set.add(task_id.clone());
-                    }
+    /// Check `agent_id`'s seat on a task's committee, drawn from `eligible`,
+    /// against its selection proof if it sent one. Always passes when the
+    /// task draws no committee.
+    pub fn check_committee_seat(
+        &self,
+        task_id: &str,
+        eligible: &[String],
+        agent_id: &str,
+        proof: Option<&SelectionProof>,
+    ) -> Result<(), String> {
+        let Some(committee) = self.committee_for(task_id, eligible) else {
+            return Ok(());
+        };
+        let weight = self.quorum_policy_for(task_id).weight(agent_id);
+        match proof {
+            Some(proof) => committee.verify(agent_id, weight, proof).map_err(|e| e.to_string()),
+            None if committee.admits(agent_id, weight) => Ok(()),
+            None => Err(format!("Agent {} holds no seat on the committee", agent_id)),
+        }
+    }
 
-                    state.mark_member_seen(params.assignee.as_str());
-                    state.bump_tasks_assigned(params.assignee.as_str());
-                    state.push_task_timeline_event(
-                        &task_id,
-                        if assigned_here { "assigned" } else { "assignment_observed" },
-                        format!(
-                            "Assigned by plan {} under parent {}",
-                            params.winning_plan_id, params.parent_task_id
-                        ),
-                        Some(params.assignee.to_string()),
-                    );
-                    state.push_log(
-                        LogCategory::Task,
-                        if assigned_here {
-                            format!(
-                                "Task assigned: {} to {} (plan={}, parent={})",
-                                task_id, params.assignee, params.winning_plan_id, params.parent_task_id
-                            )
-                        } else {
-                            format!(
-                                "Task assignment observed: {} -> {} (plan={}, parent={})",
-                                task_id, params.assignee, params.winning_plan_id, params.parent_task_id
-                            )
-                        },
-                    );
-                    state.push_log(
-                        LogCategory::System,
-                        format!(
-                            "AUDIT assignment.observe task_id={} assignee={} parent={} local_assignee={}",
-                            task_id,
-                            params.assignee,
-                            params.parent_task_id,
-                            assigned_here
-                        ),
-                    );
+    /// `agent_id`'s proof of its seat on a task's committee, or `None` when
+    /// the task draws no committee. An error when the agent was not drawn.
+    pub fn committee_proof(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+    ) -> Result<Option<SelectionProof>, String> {
+        let (_, tier_members) = WwsConnector::tier_members_for_task(self, task_id);
+        let Some(committee) = self.committee_for(task_id, &tier_members) else {
+            return Ok(None);
+        };
+        let weight = self.quorum_policy_for(task_id).weight(agent_id);
+        committee.proof_for(agent_id, weight).map(Some).ok_or_else(|| {
+            format!(
+                "Agent {} was not drawn onto the {}-seat committee for task {}",
+                agent_id, self.committee_size, task_id
+            )
+        })
+    }
 
-                    let swarm_id = state.current_swarm_id.as_str().to_string();
-                    drop(state);
-                    self.subscribe_task_flow_topics(&swarm_id, &task_id).await;
-                }
-            }
-            Some(ProtocolMethod::ProposalCommit) => {
-                if let Ok(params) =
-                    serde_json::from_value::<ProposalCommitParams>(message.params)
-                {
-                    let mut state = self.state.write().await;
-                    // A ProposalCommit is proof of activity — mark proposer as active before
-                    // the participation check to avoid KeepAlive propagation race conditions.
-                    state.mark_member_seen(params.proposer.as_str());
-                    state.mark_member_polled_tasks(params.proposer.as_str());
-                    if !Self::is_participating_member_for_task(
-                        &state,
-                        &params.task_id,
-                        params.proposer.as_str(),
-                        Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
-                    ) {
-                        state.push_log(
-                            LogCategory::Task,
-                            format!(
-                                "Ignoring proposal commit from non-responding proposer {} for task {}",
-                                params.proposer, params.task_id
-                            ),
-                        );
-                        return;
-                    }
-                    if let Some(task) = state.task_details.get_mut(&params.task_id) {
-                        if matches!(task.status, TaskStatus::Pending | TaskStatus::ProposalPhase) {
-                            task.status = TaskStatus::ProposalPhase;
-                        }
-                    }
+    /// Whether `responders` satisfy the task's quorum policy.
+    pub fn quorum_met(&self, task_id: &str, eligible: &[String], responders: &[String]) -> bool {
+        self.quorum_policy_for(task_id)
+            .is_met(eligible, &self.board_members_for(task_id), responders)
+    }
 
-                    let requirement = Self::expected_vote_requirement_for_task(&state, &params.task_id);
-                    state
-                        .task_vote_requirements
-                        .insert(params.task_id.clone(), requirement.clone());
-                    let injected_task = state
-                        .task_details
-                        .get(&params.task_id)
-                        .cloned()
-                        .unwrap_or(Task {
-                            task_id: params.task_id.clone(),
-                            parent_task_id: None,
-                            epoch: params.epoch,
-                            status: TaskStatus::Pending,
-                            description: "Observed proposal commit".to_string(),
-                            assigned_to: None,
-                            tier_level: requirement.tier_level,
-                            subtasks: Vec::new(),
-                            created_at: chrono::Utc::now(),
-                            deadline: None,
-                            ..Default::default()
-                        });
-                    {
-                        let rfp = state
-                            .rfp_coordinators
-                            .entry(params.task_id.clone())
-                            .or_insert_with(|| {
-                                RfpCoordinator::new(
-                                    params.task_id.clone(),
-                                    params.epoch,
-                                    requirement.expected_proposers,
-                                )
-                            });
-                        if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::Idle) {
-                            let _ = rfp.inject_task(&injected_task);
-                        }
-                        if let Err(e) = rfp.record_commit(&params) {
-                            tracing::warn!(error = %e, "Failed to record proposal commit");
-                        } else if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::CommitPhase) {
-                            let _ = rfp.transition_to_reveal();
-                        }
-                    }
+    /// Whether enough agents have accepted the board invitation for the
+    /// board to convene. Board-fraction policies count against the tier here,
+    /// since the board is what is being formed.
+    pub fn board_quorum_reached(&self, task_id: &str, eligible: &[String]) -> bool {
+        self.quorum_policy_for(task_id)
+            .is_met(eligible, &[], &self.board_members_for(task_id))
+    }
 
-                    let flush_pending = state
-                        .rfp_coordinators
-                        .get(&params.task_id)
-                        .map(|rfp| matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::RevealPhase))
-                        .unwrap_or(false);
-
-                    if flush_pending {
-                        let mut pending_reveals = state
-                            .pending_plan_reveals
-                            .remove(&params.task_id)
-                            .unwrap_or_default()
-                            .into_iter()
-                            .collect::<Vec<(String, Plan)>>();
-                        pending_reveals.sort_by(|a, b| a.0.cmp(&b.0));
-                        let mut revealed_proposers = Vec::new();
-                        if let Some(rfp) = state.rfp_coordinators.get_mut(&params.task_id) {
-                            for (_, pending_plan) in pending_reveals {
-                                let reveal = ProposalRevealParams {
-                                    task_id: params.task_id.clone(),
-                                    plan: pending_plan,
-                                };
-                                if let Err(e) = rfp.record_reveal(&reveal) {
-                                    tracing::warn!(error = %e, "Failed to record deferred proposal reveal");
-                                } else {
-                                    revealed_proposers.push(reveal.plan.proposer.to_string());
-                                }
-                            }
-                        }
-                        for proposer in revealed_proposers {
-                            state.bump_plans_revealed(&proposer);
-                        }
-                    }
+    /// Prerequisites of `task` that have not completed yet.
+    pub fn unfinished_prerequisites(&self, task: &Task) -> Vec<String> {
+        task.depends_on
+            .iter()
+            .filter(|dep| {
+                self.task_details
+                    .get(dep)
+                    .map(|t| t.status != TaskStatus::Completed)
+                    .unwrap_or(true)
+            })
+            .cloned()
+            .collect()
+    }
 
-                    let proposal_owners = state
-                        .rfp_coordinators
-                        .get(&params.task_id)
-                        .map(|rfp| {
-                            rfp.reveals
-                                .values()
-                                .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
-                                .collect::<std::collections::HashMap<String, AgentId>>()
-                        })
-                        .unwrap_or_default();
-                    if !proposal_owners.is_empty() {
-                        let voting = state.voting_engines.entry(params.task_id.clone()).or_insert_with(|| {
-                            VotingEngine::new(
-                                wws_consensus::voting::VotingConfig::default(),
-                                params.task_id.clone(),
-                                params.epoch,
-                            )
-                        });
-                        voting.set_proposals(proposal_owners);
-                    }
+    /// Keep an injected task back until its prerequisites complete.
+    pub fn hold_task(&mut self, task: Task) {
+        let task_id = task.task_id.clone();
+        let waiting_on = self.unfinished_prerequisites(&task).join(", ");
+        self.task_details.insert(task_id.clone(), task.clone());
+        self.held_tasks.insert(task_id.clone(), task);
+        self.push_task_timeline_event(
+            &task_id,
+            "held",
+            format!("Waiting on prerequisites: {}", waiting_on),
+            None,
+        );
+        self.push_log(
+            LogCategory::Task,
+            format!("Task {} held until {} complete", task_id, waiting_on),
+        );
+    }
 
-                    state.push_task_timeline_event(
-                        &params.task_id,
-                        "proposal_commit",
-                        format!("Commit hash {}", params.plan_hash),
-                        Some(params.proposer.to_string()),
-                    );
-                    state.bump_plans_proposed(params.proposer.as_str());
-                    state.push_log(
-                        LogCategory::Task,
-                        format!(
-                            "Plan commit for task {} from {} (hash={})",
-                            params.task_id,
-                            params.proposer,
-                            params.plan_hash
-                        ),
-                    );
-                    state.push_log(
-                        LogCategory::System,
-                        format!(
-                            "AUDIT proposal.commit task_id={} proposer={} hash={}",
-                            params.task_id, params.proposer, params.plan_hash
-                        ),
-                    );
-                }
+    /// Append the results of a task's prerequisites to its description so
+    /// planners and executors see them.
+    pub fn attach_prerequisite_context(&self, task: &mut Task) {
+        if task.depends_on.is_empty() {
+            return;
+        }
+        let mut context = String::from("\n\nPrerequisite results:");
+        for dep in &task.depends_on {
+            let description = self
+                .task_details
+                .get(dep)
+                .map(|t| t.description.as_str())
+                .unwrap_or("");
+            let result = self
+                .task_result_text
+                .get(dep)
+                .map(|t| truncate_chars(t, PREREQUISITE_CONTEXT_MAX_CHARS))
+                .unwrap_or_else(|| "(no result text)".to_string());
+            context.push_str(&format!("\n- {} ({}): {}", dep, description, result));
+            if let Some(cid) = self
+                .task_results
+                .get(dep)
+                .map(|a| a.content_cid.as_str())
+                .filter(|c| !c.is_empty())
+            {
+                context.push_str(&format!(" [cid: {}]", cid));
             }
-            Some(ProtocolMethod::ProposalReveal) => {
-                if let Ok(params) =
-                    serde_json::from_value::<ProposalRevealParams>(message.params)
-                {
-                    let mut state = self.state.write().await;
-                    // A ProposalReveal is proof of activity — mark proposer as active before
-                    // the participation check to avoid KeepAlive propagation race conditions.
-                    state.mark_member_seen(params.plan.proposer.as_str());
-                    state.mark_member_polled_tasks(params.plan.proposer.as_str());
-                    if !Self::is_participating_member_for_task(
-                        &state,
-                        &params.task_id,
-                        params.plan.proposer.as_str(),
-                        Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
-                    ) {
-                        state.push_log(
-                            LogCategory::Task,
-                            format!(
-                                "Ignoring proposal reveal from non-responding proposer {} for task {}",
-                                params.plan.proposer, params.task_id
-                            ),
-                        );
-                        return;
-                    }
-                    state
-                        .task_details
-                        .entry(params.task_id.clone())
-                        .and_modify(|task| {
-                            if matches!(task.status, TaskStatus::Pending | TaskStatus::ProposalPhase)
-                            {
-                                task.status = TaskStatus::VotingPhase;
-                            }
-                        });
-
-                    let requirement = Self::expected_vote_requirement_for_task(&state, &params.task_id);
-                    state
-                        .task_vote_requirements
-                        .insert(params.task_id.clone(), requirement.clone());
+        }
+        task.description.push_str(&context);
+    }
 
-                    let injected_task = state
-                        .task_details
-                        .get(&params.task_id)
-                        .cloned()
-                        .unwrap_or(Task {
-                            task_id: params.task_id.clone(),
-                            parent_task_id: None,
-                            epoch: params.plan.epoch,
-                            status: TaskStatus::Pending,
-                            description: "Observed proposal reveal".to_string(),
-                            assigned_to: None,
-                            tier_level: requirement.tier_level,
-                            subtasks: Vec::new(),
-                            created_at: chrono::Utc::now(),
-                            deadline: None,
-                            ..Default::default()
-                        });
+    /// Release held tasks whose prerequisites have all completed, with
+    /// prerequisite results attached. Tasks depending on a rejected task are
+    /// rejected too.
+    pub fn take_ready_dependents(&mut self) -> Vec<Task> {
+        let mut held_ids: Vec<String> = self.held_tasks.keys().cloned().collect();
+        held_ids.sort();
+        let mut ready = Vec::new();
+        for task_id in held_ids {
+            let depends_on = self.held_tasks[&task_id].depends_on.clone();
+            let rejected = depends_on.iter().find(|dep| {
+                self.task_details
+                    .get(dep)
+                    .is_some_and(|t| t.status == TaskStatus::Rejected)
+            });
+            if let Some(rejected) = rejected.cloned() {
+                self.held_tasks.remove(&task_id);
+                self.close_rejected_task(
+                    &task_id,
+                    "dependency_failed",
+                    format!("Prerequisite {} was rejected", rejected),
+                    None,
+                );
+                continue;
+            }
+            if !self.unfinished_prerequisites(&self.held_tasks[&task_id]).is_empty() {
+                continue;
+            }
+            if let Some(mut task) = self.held_tasks.remove(&task_id) {
+                self.attach_prerequisite_context(&mut task);
+                self.push_task_timeline_event(
+                    &task_id,
+                    "released",
+                    "All prerequisites completed".to_string(),
+                    None,
+                );
+                ready.push(task);
+            }
+        }
+        ready
+    }
 
-                    let should_queue_reveal = {
-                        let rfp = state
-                            .rfp_coordinators
-                            .entry(params.task_id.clone())
-                            .or_insert_with(|| {
-                                RfpCoordinator::new(
-                                    params.task_id.clone(),
-                                    params.plan.epoch,
-                                    requirement.expected_proposers,
-                                )
-                            });
-                        if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::Idle) {
-                            let _ = rfp.inject_task(&injected_task);
-                        }
-                        if matches!(
-                            rfp.phase(),
-                            wws_consensus::rfp::RfpPhase::RevealPhase
-                                | wws_consensus::rfp::RfpPhase::ReadyForVoting
-                        ) {
-                            if let Err(e) = rfp.record_reveal(&params) {
-                                tracing::warn!(error = %e, "Failed to record proposal reveal");
-                            }
-                            false
-                        } else {
-                            true
-                        }
-                    };
+    /// Hand a completed task to `verifier` for independent re-execution.
+    /// Returns the task as it should be reassigned, or `None` if it has no
+    /// result to check, is already being verified, or `verifier` produced
+    /// the result under check.
+    pub fn begin_verification(&mut self, task_id: &str, verifier: &str) -> Option<Task> {
+        if self.verifications.contains_key(task_id) {
+            return None;
+        }
+        let artifact = self.task_results.get(task_id).cloned()?;
+        let text = self.task_result_text.get(task_id).cloned().unwrap_or_default();
+        let now = chrono::Utc::now();
+        let task = self.task_details.get_mut(task_id)?;
+        let original_agent = task
+            .assigned_to
+            .as_ref()
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| artifact.producer.to_string());
+        if original_agent == verifier {
+            return None;
+        }
+        task.status = TaskStatus::InProgress;
+        task.assigned_to = Some(AgentId::new(verifier.to_string()));
+        task.deadline = Some(now + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS));
+        let task = task.clone();
+
+        self.verifications.insert(
+            task_id.to_string(),
+            ResultVerification::new(
+                task_id.to_string(),
+                original_agent.clone(),
+                artifact,
+                text,
+                verifier.to_string(),
+                now,
+            ),
+        );
+        if verifier == self.agent_id.as_str() {
+            self.task_set.add(task_id.to_string());
+        }
+        self.bump_tasks_assigned(verifier);
+        self.push_task_timeline_event(
+            task_id,
+            "verification_assigned",
+            format!("Re-executing result from {} to verify it", original_agent),
+            Some(verifier.to_string()),
+        );
+        self.push_log(
+            LogCategory::Task,
+            format!(
+                "Task {} re-assigned to {} to verify the result from {}",
+                task_id, verifier, original_agent
+            ),
+        );
+        Some(task)
+    }
 
-                    if should_queue_reveal {
-                        state
-                            .pending_plan_reveals
-                            .entry(params.task_id.clone())
-                            .or_default()
-                            .insert(params.plan.proposer.to_string(), params.plan.clone());
-                    }
+    /// Compare a verifier's result with the original once it has been
+    /// recorded. A match keeps the original result; a mismatch restores it
+    /// too but marks the task `Disputed` until the parent's board votes.
+    /// Returns `None` unless `agent` is verifying `task_id`.
+    pub fn settle_verification(
+        &mut self,
+        task_id: &str,
+        agent: &str,
+        artifact: &Artifact,
+        text: &str,
+    ) -> Option<VerificationStatus> {
+        let record = self.verifications.get_mut(task_id)?;
+        if record.status != VerificationStatus::Pending || record.verifier != agent {
+            return None;
+        }
+        let status =
+            record.record_verifier_result(artifact.clone(), text.to_string(), chrono::Utc::now());
+        let original_agent = record.original_agent.clone();
+        self.restore_result(task_id, &original_agent);
+
+        if status == VerificationStatus::Matched {
+            self.activity_mut(&original_agent).verifications_passed_count += 1;
+            self.activity_mut(agent).verifications_passed_count += 1;
+            for subject in [original_agent.as_str(), agent] {
+                crate::reputation::attest(self, subject, task_id, AttestationOutcome::VerificationPassed);
+            }
+            self.push_task_timeline_event(
+                task_id,
+                "verified",
+                format!("Re-execution by {} matched the original result", agent),
+                Some(agent.to_string()),
+            );
+            return Some(status);
+        }
 
-                    let proposal_owners = state
-                        .rfp_coordinators
-                        .get(&params.task_id)
-                        .map(|rfp| {
-                            rfp.reveals
-                                .values()
-                                .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
-                                .collect::<std::collections::HashMap<String, AgentId>>()
-                        })
-                        .unwrap_or_default();
+        let voters = self.dispute_board(task_id, &original_agent, agent);
+        if let Some(task) = self.task_details.get_mut(task_id) {
+            task.status = TaskStatus::Disputed;
+        }
+        self.push_task_timeline_event(
+            task_id,
+            "disputed",
+            format!(
+                "Results from {} and {} differ; board of {} voting",
+                original_agent,
+                agent,
+                voters.len()
+            ),
+            Some(agent.to_string()),
+        );
+        self.push_log(
+            LogCategory::Vote,
+            format!(
+                "Task {} disputed: {} and {} produced different results",
+                task_id, original_agent, agent
+            ),
+        );
+        if voters.is_empty() {
+            self.resolve_dispute(task_id, original_agent);
+        } else if let Some(record) = self.verifications.get_mut(task_id) {
+            record.voters = voters;
+        }
+        Some(status)
+    }
 
-                    let voting = state.voting_engines.entry(params.task_id.clone()).or_insert_with(|| {
-                        VotingEngine::new(
-                            wws_consensus::voting::VotingConfig::default(),
-                            params.task_id.clone(),
-                            params.plan.epoch,
-                        )
-                    });
-                    voting.set_proposals(proposal_owners);
+    /// Record a board member's dispute vote and resolve the dispute once the
+    /// parent task's quorum policy is met. Returns the accepted agent if
+    /// this vote settled it.
+    pub fn record_dispute_vote(
+        &mut self,
+        params: &DisputeVoteParams,
+    ) -> Result<Option<String>, DisputeError> {
+        let record = self
+            .verifications
+            .get_mut(&params.task_id)
+            .ok_or_else(|| DisputeError::NotDisputed(params.task_id.clone()))?;
+        record.record_vote(params.voter.as_str(), params.accept_agent.as_str())?;
+        let voters = record.voters.clone();
+
+        let policy_task = self
+            .task_details
+            .get(&params.task_id)
+            .and_then(|t| t.parent_task_id.clone())
+            .unwrap_or_else(|| params.task_id.clone());
+        let required = self.quorum_policy_for(&policy_task).required(&voters, &[]);
+        let winner = self.verifications[&params.task_id].tally(required);
+        self.push_task_timeline_event(
+            &params.task_id,
+            "dispute_vote",
+            format!("Accepts the result from {}", params.accept_agent),
+            Some(params.voter.to_string()),
+        );
+        if let Some(winner) = winner.clone() {
+            self.resolve_dispute(&params.task_id, winner);
+        }
+        Ok(winner)
+    }
 
-                    state.push_task_timeline_event(
-                        &params.task_id,
-                        "proposal_reveal",
-                        format!("{} subtasks revealed", params.plan.subtasks.len()),
-                        Some(params.plan.proposer.to_string()),
-                    );
-                    state.bump_plans_revealed(params.plan.proposer.as_str());
-                    state.push_log(
-                        LogCategory::Task,
-                        format!(
-                            "Plan reveal for task {} by {} ({} subtasks): {}",
-                            params.task_id,
-                            params.plan.proposer,
-                            params.plan.subtasks.len(),
-                            params
-                                .plan
-                                .subtasks
-                                .iter()
-                                .map(|s| format!("{}:{}", s.index, s.description))
-                                .collect::<Vec<_>>()
-                                .join(" | ")
-                        ),
-                    );
-                    state.push_log(
-                        LogCategory::System,
-                        format!(
-                            "AUDIT proposal.reveal task_id={} proposer={} subtasks={}",
-                            params.task_id,
-                            params.plan.proposer,
-                            params.plan.subtasks.len()
-                        ),
-                    );
-                }
-            }
-            Some(ProtocolMethod::ConsensusVote) => {
-                if let Ok(params) =
-                    serde_json::from_value::<ConsensusVoteParams>(message.params)
-                {
-                    let task_id = params.task_id.clone();
-                    let voter = params.voter.clone();
-                    let rankings_preview = params.rankings.join(" > ");
-                    let mut state = self.state.write().await;
-                    if !Self::is_participating_member_for_task(
-                        &state,
-                        &task_id,
-                        voter.as_str(),
-                        Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
-                    ) {
-                        state.push_log(
-                            LogCategory::Vote,
-                            format!(
-                                "Ignoring vote from non-responding voter {} for task {}",
-                                voter, task_id
-                            ),
-                        );
-                        return;
-                    }
-                    state.mark_member_seen(voter.as_str());
-                    if let Some(task) = state.task_details.get_mut(&task_id) {
-                        // Only advance to VotingPhase from pre-voting states.
-                        // Never overwrite InProgress/Completed/Failed tasks — a stale
-                        // ConsensusVote arriving after TaskAssignment must not revert the task.
-                        if matches!(
-                            task.status,
-                            TaskStatus::Pending | TaskStatus::ProposalPhase | TaskStatus::VotingPhase
-                        ) {
-                            task.status = TaskStatus::VotingPhase;
-                        }
-                    }
-                    if let Some(voting) = state.voting_engines.get_mut(&task_id) {
-                        let ranked_vote = RankedVote {
-                            voter: voter.clone(),
-                            task_id: params.task_id.clone(),
-                            epoch: params.epoch,
-                            rankings: params.rankings.clone(),
-                            critic_scores: params.critic_scores.clone(),
-                        };
-                        if let Err(e) = voting.record_vote(ranked_vote) {
-                            tracing::warn!(error = %e, "Failed to record consensus vote");
-                        }
+    /// Close verifications that ran out of time. An unanswered re-execution
+    /// keeps the original result; a dispute short of quorum goes to the
+    /// majority of the votes cast (the original on a tie).
+    pub fn expire_verifications(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        let mut abandoned = Vec::new();
+        let mut undecided = Vec::new();
+        for (task_id, record) in &self.verifications {
+            match record.status {
+                VerificationStatus::Pending => {
+                    let expired = self
+                        .task_details
+                        .get(task_id)
+                        .and_then(|t| t.deadline)
+                        .is_none_or(|deadline| deadline <= now);
+                    if expired {
+                        abandoned.push((task_id.clone(), record.original_agent.clone()));
                     }
-                    // Record ballot for deliberation visibility
-                    state.ballot_records.entry(task_id.clone()).or_default().push(BallotRecord {
-                        task_id: task_id.clone(),
-                        voter: voter.clone(),
-                        rankings: params.rankings,
-                        critic_scores: params.critic_scores,
-                        timestamp: chrono::Utc::now(),
-                        irv_round_when_eliminated: None,
+                }
+                VerificationStatus::Disputed => {
+                    let expired = record.disputed_at.is_some_and(|at| {
+                        at + chrono::Duration::seconds(DISPUTE_VOTE_TIMEOUT_SECS) <= now
                     });
-                    // Also record as a deliberation message (proposal score phase)
-                    {
-                        let rankings_str = format!("Rankings: {}", rankings_preview);
-                        state.deliberation_messages.entry(task_id.clone()).or_default().push(DeliberationMessage {
-                            id: uuid::Uuid::new_v4().to_string(),
-                            task_id: task_id.clone(),
-                            timestamp: chrono::Utc::now(),
-                            speaker: voter.clone(),
-                            round: 2,
-                            message_type: DeliberationType::CritiqueFeedback,
-                            content: rankings_str,
-                            referenced_plan_id: None,
-                            critic_scores: None,
-                        });
+                    if expired {
+                        undecided.push((task_id.clone(), record.majority()));
                     }
-                    state.push_task_timeline_event(
-                        &task_id,
-                        "vote_recorded",
-                        format!("Rankings: {}", rankings_preview),
-                        Some(voter.to_string()),
-                    );
-                    state.bump_votes_cast(voter.as_str());
-                    state.push_log(
-                        LogCategory::Vote,
-                        format!(
-                            "Vote for task {} from {}: {}",
-                            task_id,
-                            voter,
-                            rankings_preview
-                        ),
-                    );
                 }
+                _ => {}
             }
-            Some(ProtocolMethod::ResultSubmission) => {
-                let raw_params = message.params.clone();
-                if let Ok(params) =
-                    serde_json::from_value::<ResultSubmissionParams>(raw_params.clone())
-                {
-                    let mut state = self.state.write().await;
-                    if let Some(task) = state.task_details.get(&params.task_id) {
-                        // Only reject if explicitly assigned to a different agent.
-                        // If assigned_to is None (coordinator/synthesis tasks), allow through.
-                        if task.assigned_to.is_some()
-                            && task.assigned_to.as_ref() != Some(&params.agent_id)
-                        {
-                            state.push_log(
-                                LogCategory::Task,
-                                format!(
-                                    "Ignoring late result for task {} from replaced assignee {}",
-                                    params.task_id, params.agent_id
-                                ),
-                            );
-                            return;
-                        }
-                    }
-                    if let Some(task) = state.task_details.get_mut(&params.task_id) {
-                        task.status = TaskStatus::Completed;
-                        task.assigned_to = Some(params.agent_id.clone());
-                    }
-                    state.task_set.remove(&params.task_id);
-                    state.mark_member_submitted_result(params.agent_id.as_str());
-                    state.bump_tasks_processed(params.agent_id.as_str());
-                    state.mark_member_seen(params.agent_id.as_str());
-                    // Update holon status to Done on result submission
-                    if let Some(holon) = state.active_holons.get_mut(&params.task_id) {
-                        holon.status = HolonStatus::Done;
-                    }
-                    // Store the artifact in task_results so /api/tasks returns result_artifact
-                    state.task_results.insert(params.task_id.clone(), params.artifact.clone());
-
-                    // Store content text for API and synthesis messages
-                    let content_text = if !params.artifact.content.is_empty() {
-                        params.artifact.content.clone()
-                    } else if let Some(c) = raw_params.get("content").and_then(|v| v.as_str()) {
-                        c.to_string()
-                    } else {
-                        String::new()
-                    };
-                    if !content_text.is_empty() {
-                        state.task_result_text.insert(params.task_id.clone(), content_text.clone());
-                    }
+        }
+        abandoned.sort();
+        undecided.sort();
 
-                    // Record synthesis result as deliberation message if this is a synthesis
-                    let synth_text = if !content_text.is_empty() {
-                        content_text.clone()
-                    } else {
-                        state.task_result_text.get(&params.task_id).cloned().unwrap_or_default()
-                    };
-                    if params.is_synthesis && !synth_text.is_empty() {
-                        state.deliberation_messages.entry(params.task_id.clone()).or_default().push(DeliberationMessage {
-                            id: uuid::Uuid::new_v4().to_string(),
-                            task_id: params.task_id.clone(),
-                            timestamp: chrono::Utc::now(),
-                            speaker: params.agent_id.clone(),
-                            round: 3,
-                            message_type: DeliberationType::SynthesisResult,
-                            content: synth_text,
-                            referenced_plan_id: None,
-                            critic_scores: None,
-                        });
-                    }
-                    // Store the artifact content CID as leaf content bytes in the DAG.
-                    state.merkle_dag.add_leaf(
-                        params.task_id.clone(),
-                        params.artifact.content_cid.as_bytes(),
-                    );
-                    let dag_nodes = state.merkle_dag.node_count();
-                    state.push_task_timeline_event(
-                        &params.task_id,
-                        "result_submitted",
-                        format!("Artifact {} (dag_nodes={})", params.artifact.artifact_id, dag_nodes),
-                        Some(params.agent_id.to_string()),
-                    );
-                    state.push_log(
-                        LogCategory::Task,
-                        format!(
-                            "Result received for task {} from {} (artifact={}, dag_nodes={})",
-                            params.task_id,
-                            params.agent_id,
-                            params.artifact.artifact_id,
-                            dag_nodes
-                        ),
-                    );
-                    state.push_log(
-                        LogCategory::System,
-                        format!(
-                            "AUDIT result.observe task_id={} agent={} artifact={}",
-                            params.task_id, params.agent_id, params.artifact.artifact_id
-                        ),
-                    );
-                }
-            }
-            Some(ProtocolMethod::Succession) => {
-                if let Ok(params) = serde_json::from_value::<SuccessionParams>(message.params) {
-                    tracing::info!(
-                        failed = %params.failed_leader,
-                        new = %params.new_leader,
-                        "Succession notification received"
-                    );
-                }
+        for (task_id, original_agent) in abandoned {
+            if let Some(record) = self.verifications.get_mut(&task_id) {
+                record.status = VerificationStatus::Abandoned;
             }
-            Some(ProtocolMethod::SwarmAnnounce) => {
-                if let Ok(params) =
-                    serde_json::from_value::<SwarmAnnounceParams>(message.params)
-                {
-                    let mut state = self.state.write().await;
-                    let swarm_key = params.swarm_id.as_str().to_string();
-                    let is_new = !state.known_swarms.contains_key(&swarm_key);
-
-                    let record = state
-                        .known_swarms
-                        .entry(swarm_key.clone())
-                        .or_insert_with(|| SwarmRecord {
-                            swarm_id: params.swarm_id.clone(),
-                            name: params.name.clone(),
-                            is_public: params.is_public,
-                            agent_count: params.agent_count,
-                            joined: false,
-                            last_seen: chrono::Utc::now(),
-                        });
-
-                    record.agent_count = params.agent_count;
-                    record.last_seen = chrono::Utc::now();
-                    record.name = params.name.clone();
+            self.restore_result(&task_id, &original_agent);
+            self.push_task_timeline_event(
+                &task_id,
+                "verification_abandoned",
+                "Verifier did not respond; original result stands".to_string(),
+                None,
+            );
+        }
+        for (task_id, winner) in undecided {
+            self.resolve_dispute(&task_id, winner);
+        }
+    }
 
-                    if is_new {
-                        state.push_log(
-                            LogCategory::System,
-                            format!(
-                                "Discovered swarm: {} ({}, {} agents)",
-                                params.name,
-                                if params.is_public { "public" } else { "private" },
-                                params.agent_count
-                            ),
-                        );
-                        tracing::info!(
-                            swarm_id = %params.swarm_id,
-                            name = %params.name,
-                            public = params.is_public,
-                            agents = params.agent_count,
-                            "Discovered new swarm"
-                        );
-                    }
-                }
-            }
-            Some(ProtocolMethod::SwarmJoin) => {
-                if let Ok(params) =
-                    serde_json::from_value::<SwarmJoinParams>(message.params)
-                {
-                    let state = self.state.read().await;
-                    // Only process join requests for our swarm.
-                    if params.swarm_id == state.current_swarm_id {
-                        tracing::info!(
-                            agent = %params.agent_id,
-                            swarm = %params.swarm_id,
-                            "Join request for our swarm"
-                        );
-                    }
-                }
-            }
-            Some(ProtocolMethod::SwarmLeave) => {
-                if let Ok(params) =
-                    serde_json::from_value::<SwarmLeaveParams>(message.params)
-                {
-                    let mut state = self.state.write().await;
-                    if let Some(record) = state.known_swarms.get_mut(params.swarm_id.as_str()) {
-                        record.agent_count = record.agent_count.saturating_sub(1);
-                    }
-                    state.push_log(
-                        LogCategory::Peer,
-                        format!("{} left swarm {}", params.agent_id, params.swarm_id),
-                    );
-                }
-            }
-            Some(ProtocolMethod::BoardInvite) => {
-                if let Ok(params) = serde_json::from_value::<BoardInviteParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    // Create or update holon in Forming state
-                    let holon = state.active_holons.entry(params.task_id.clone()).or_insert_with(|| {
-                        HolonState {
-                            task_id: params.task_id.clone(),
-                            chair: params.chair.clone(),
-                            members: Vec::new(),
-                            adversarial_critic: None,
-                            depth: params.depth,
-                            parent_holon: None,
-                            child_holons: Vec::new(),
-                            subtask_assignments: std::collections::HashMap::new(),
-                            status: HolonStatus::Forming,
-                            created_at: chrono::Utc::now(),
-                        }
-                    });
-                    holon.status = HolonStatus::Forming;
-                    state.push_log(
-                        LogCategory::Task,
-                        format!(
-                            "Board invite for task {} (depth={}, chair={}, complexity={:.2})",
-                            params.task_id, params.depth, params.chair, params.complexity_estimate
-                        ),
-                    );
-                }
-            }
-            Some(ProtocolMethod::BoardAccept) => {
-                if let Ok(params) = serde_json::from_value::<BoardAcceptParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    state.board_acceptances
-                        .entry(params.task_id.clone())
-                        .or_default()
-                        .push(params.clone());
-                    if let Some(holon) = state.active_holons.get_mut(&params.task_id) {
-                        if !holon.members.iter().any(|m| m == &params.agent_id) {
-                            holon.members.push(params.agent_id.clone());
-                        }
-                    }
-                    state.push_log(
-                        LogCategory::Task,
-                        format!("Board accept: {} for task {}", params.agent_id, params.task_id),
-                    );
-                }
-            }
-            Some(ProtocolMethod::BoardDecline) => {
-                if let Ok(params) = serde_json::from_value::<BoardDeclineParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    state.push_log(
-                        LogCategory::Task,
-                        format!("Board decline: {} for task {}", params.agent_id, params.task_id),
-                    );
-                }
-            }
-            Some(ProtocolMethod::BoardReady) => {
-                if let Ok(params) = serde_json::from_value::<BoardReadyParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    let holon = state.active_holons.entry(params.task_id.clone()).or_insert_with(|| {
-                        HolonState {
-                            task_id: params.task_id.clone(),
-                            chair: params.chair_id.clone(),
-                            members: params.members.clone(),
-                            adversarial_critic: params.adversarial_critic.clone(),
-                            depth: 0,
-                            parent_holon: None,
-                            child_holons: Vec::new(),
-                            subtask_assignments: std::collections::HashMap::new(),
-                            status: HolonStatus::Deliberating,
-                            created_at: chrono::Utc::now(),
-                        }
-                    });
-                    holon.chair = params.chair_id.clone();
-                    holon.members = params.members.clone();
-                    holon.adversarial_critic = params.adversarial_critic.clone();
-                    holon.status = HolonStatus::Deliberating;
-                    state.push_log(
-                        LogCategory::Task,
-                        format!(
-                            "Board ready for task {} ({} members, chair={})",
-                            params.task_id, params.members.len(), params.chair_id
-                        ),
-                    );
-                }
-            }
-            Some(ProtocolMethod::BoardDissolve) => {
-                if let Ok(params) = serde_json::from_value::<BoardDissolveParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    if let Some(holon) = state.active_holons.get_mut(&params.task_id) {
-                        holon.status = HolonStatus::Done;
-                    }
-                    state.push_log(
-                        LogCategory::Task,
-                        format!("Board dissolved for task {}", params.task_id),
-                    );
-                }
-            }
-            Some(ProtocolMethod::DiscussionCritique) => {
-                if let Ok(params) = serde_json::from_value::<DiscussionCritiqueParams>(message.params) {
-                    let mut state = self.state.write().await;
-                    // Store as deliberation message
-                    let msg = DeliberationMessage {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        task_id: params.task_id.clone(),
-                        timestamp: chrono::Utc::now(),
-                        speaker: params.voter_id.clone(),
-                        round: params.round,
-                        message_type: DeliberationType::CritiqueFeedback,
-                        content: params.content.clone(),
-                        referenced_plan_id: None,
-                        critic_scores: Some(params.plan_scores.clone()),
-                    };
-                    state.deliberation_messages
-                        .entry(params.task_id.clone())
-                        .or_default()
-                        .push(msg);
-                    // Also record in the rfp coordinator
-                    if let Some(rfp) = state.rfp_coordinators.get_mut(&params.task_id) {
-                        let _ = rfp.record_critique(
-                            params.voter_id.clone(),
-                            params.plan_scores.clone(),
-                            params.content.clone(),
-                        );
-                    }
-                    // Update holon status to Voting after critique
-                    if let Some(holon) = state.active_holons.get_mut(&params.task_id) {
-                        if matches!(holon.status, HolonStatus::Deliberating) {
-                            holon.status = HolonStatus::Voting;
-                        }
-                    }
-                    state.push_log(
-                        LogCategory::Vote,
-                        format!(
-                            "Critique from {} for task {} (round {}, {} plan scores)",
-                            params.voter_id, params.task_id, params.round, params.plan_scores.len()
-                        ),
-                    );
-                }
-            }
-            Some(ProtocolMethod::AgentDirectMessage) => {
-                if let Ok(params) = serde_json::from_value::<DirectMessageParams>(message.params) {
-                    // recipient_did is advisory — all nodes store all messages on this topic;
-                    // agents filter by recipient_did when reading /api/messages.
-                    let dm = DirectMessage {
-                        id: params.message_id.clone(),
-                        sender_did: params.sender_did.clone(),
-                        recipient_did: params.recipient_did.clone(),
-                        content: params.content.clone(),
-                        message_type: MessageType::from(params.message_type.as_str()),
-                        timestamp: chrono::Utc::now(),
-                    };
-                    let mut state = self.state.write().await;
-                    state.push_log(
-                        LogCategory::Message,
-                        format!(
-                            "Direct message from {}: {}",
-                            params.sender_did, params.content
-                        ),
-                    );
-                    state.push_direct_message(dm);
-                }
-            }
-            _ => {
-                tracing::debug!(
-                    method = %message.method,
-                    topic = %topic,
-                    "Unhandled protocol message"
-                );
-            }
+    fn resolve_dispute(&mut self, task_id: &str, winner: String) {
+        let Some(record) = self.verifications.get_mut(task_id) else {
+            return;
+        };
+        record.resolve(winner.clone());
+        let loser = record.rejected_agent().map(str::to_string);
+        self.restore_result(task_id, &winner);
+        self.activity_mut(&winner).verifications_passed_count += 1;
+        crate::reputation::attest(self, &winner, task_id, AttestationOutcome::VerificationPassed);
+        if let Some(loser) = &loser {
+            self.activity_mut(loser).verifications_failed_count += 1;
+            crate::reputation::attest(self, loser, task_id, AttestationOutcome::VerificationFailed);
         }
+        self.push_task_timeline_event(
+            task_id,
+            "dispute_resolved",
+            format!("Board accepted the result from {}", winner),
+            None,
+        );
+        self.push_log(
+            LogCategory::Vote,
+            format!(
+                "Dispute on task {} resolved for {} over {}",
+                task_id,
+                winner,
+                loser.as_deref().unwrap_or("unknown")
+            ),
+        );
     }
 
-    /// Announce this node's swarm to the network via GossipSub.
-    ///
-    /// Periodically broadcasts a SwarmAnnounce message on the global
-    /// swarm discovery topic and the swarm-specific announcement topic.
-    /// Also publishes the swarm info to the Kademlia DHT for internet-wide
-    /// discovery.
-    async fn announce_swarm(&self) {
-        let state = self.state.read().await;
-        let staleness = Duration::from_secs(self.config.hierarchy.keepalive_interval_secs.saturating_mul(3).max(30));
-        let agent_count = state.active_member_count(staleness) as u64;
-        let params = SwarmAnnounceParams {
-            swarm_id: state.current_swarm_id.clone(),
-            name: self.config.swarm.name.clone(),
-            is_public: self.config.swarm.is_public,
-            agent_id: state.agent_id.clone(),
-            agent_count,
-            description: String::new(),
-            timestamp: chrono::Utc::now(),
+    /// Make `agent`'s result the task's result and mark the task completed.
+    fn restore_result(&mut self, task_id: &str, agent: &str) {
+        let Some((artifact, text)) = self
+            .verifications
+            .get(task_id)
+            .and_then(|record| record.result_of(agent))
+        else {
+            return;
         };
-        drop(state);
+        self.task_results.insert(task_id.to_string(), artifact);
+        if text.is_empty() {
+            self.task_result_text.remove(task_id);
+        } else {
+            self.task_result_text.insert(task_id.to_string(), text);
+        }
+        if let Some(task) = self.task_details.get_mut(task_id) {
+            task.status = TaskStatus::Completed;
+            task.assigned_to = Some(AgentId::new(agent.to_string()));
+        }
+        self.task_set.remove(task_id);
+    }
 
-        let msg = SwarmMessage::new(
-            ProtocolMethod::SwarmAnnounce.as_str(),
-            serde_json::to_value(&params).unwrap_or_default(),
-            String::new(),
+    /// Agents who vote on a disputed result: the parent task's board, or
+    /// the coordinator of the original agent when no board formed. Neither
+    /// producer votes.
+    fn dispute_board(&self, task_id: &str, original_agent: &str, verifier: &str) -> Vec<String> {
+        let parent = self
+            .task_details
+            .get(task_id)
+            .and_then(|t| t.parent_task_id.as_deref());
+        let mut voters: Vec<String> = parent
+            .and_then(|p| self.active_holons.get(p))
+            .map(|h| h.members.iter().map(|m| m.to_string()).collect())
+            .unwrap_or_default();
+        if let Some(parent) = parent {
+            voters.extend(self.board_members_for(parent));
+        }
+        if voters.is_empty() {
+            voters.extend(self.agent_parents.get(original_agent).cloned());
+        }
+        voters.retain(|v| v != original_agent && v != verifier);
+        voters.sort();
+        voters.dedup();
+        voters
+    }
+
+    /// Start collecting acceptances for a board this connector chairs, and
+    /// track the holon as forming.
+    pub fn open_board_formation(&mut self, invite: BoardInviteParams) {
+        let task_id = invite.task_id.clone();
+        let holon = self
+            .active_holons
+            .entry(task_id.clone())
+            .or_insert_with(|| HolonState {
+                task_id: task_id.clone(),
+                chair: invite.chair.clone(),
+                members: Vec::new(),
+                adversarial_critic: None,
+                depth: invite.depth,
+                parent_holon: None,
+                child_holons: Vec::new(),
+                subtask_assignments: std::collections::HashMap::new(),
+                child_artifacts: std::collections::HashMap::new(),
+                status: HolonStatus::Forming,
+                created_at: chrono::Utc::now(),
+            });
+        holon.status = HolonStatus::Forming;
+        let formation = BoardFormation::new(
+            invite,
+            chrono::Utc::now(),
+            self.board_config.acceptance_timeout_secs,
+        );
+        self.push_task_timeline_event(
+            &task_id,
+            "board_invited",
+            format!(
+                "Collecting acceptances until {} (capacity {})",
+                formation.deadline.format("%H:%M:%S"),
+                formation.invite.capacity
+            ),
+            Some(formation.invite.chair.to_string()),
         );
+        self.board_formations.insert(task_id, formation);
+    }
 
-        if let Ok(data) = serde_json::to_vec(&msg) {
-            // Publish to the global discovery topic.
-            let discovery_topic = SwarmTopics::swarm_discovery();
-            if let Err(e) = self.network_handle.publish(&discovery_topic, data.clone()).await {
-                tracing::debug!(error = %e, "Failed to publish swarm announcement to discovery topic");
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic: discovery_topic.clone(),
-                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
-                    task_id: None,
-                    size_bytes: data.len(),
-                    outcome: format!("error: {}", e),
-                });
-            } else {
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic: discovery_topic.clone(),
-                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
-                    task_id: None,
-                    size_bytes: data.len(),
-                    outcome: "published".to_string(),
-                });
+    /// Record a `board.accept`, visible to every observer of the board.
+    pub fn note_board_accept(&mut self, params: &BoardAcceptParams) {
+        self.note_capabilities(params.agent_id.as_str(), &params.capabilities);
+        let acceptances = self.board_acceptances.entry(params.task_id.clone()).or_default();
+        if !acceptances.iter().any(|a| a.agent_id == params.agent_id) {
+            acceptances.push(params.clone());
+        }
+        if let Some(holon) = self.active_holons.get_mut(&params.task_id) {
+            if !holon.members.iter().any(|m| m == &params.agent_id) {
+                holon.members.push(params.agent_id.clone());
             }
+        }
+    }
 
-            // Publish to the swarm-specific announcement topic.
-            let announce_topic = SwarmTopics::swarm_announce(params.swarm_id.as_str());
-            if let Err(e) = self.network_handle.publish(&announce_topic, data).await {
-                tracing::debug!(error = %e, "Failed to publish swarm announcement to swarm topic");
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic: announce_topic,
-                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
-                    task_id: None,
-                    size_bytes: 0,
-                    outcome: format!("error: {}", e),
-                });
-            } else {
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic: announce_topic,
-                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
-                    task_id: None,
-                    size_bytes: 0,
-                    outcome: "published".to_string(),
-                });
-            }
+    /// Remember the capabilities an agent advertised. An empty list tells
+    /// nothing, so it does not replace a known one.
+    pub fn note_capabilities(&mut self, agent_id: &str, capabilities: &[String]) {
+        if !capabilities.is_empty() {
+            self.agent_capabilities.insert(agent_id.to_string(), capabilities.to_vec());
         }
+    }
 
-        // Also register in DHT for internet-wide discovery.
-        let dht_key = format!(
-            "{}{}",
-            wws_protocol::SWARM_REGISTRY_PREFIX,
-            params.swarm_id
-        );
-        let dht_value = serde_json::json!({
-            "swarm_id": params.swarm_id.as_str(),
-            "name": params.name,
-            "is_public": params.is_public,
-            "agent_count": params.agent_count,
-            "timestamp": params.timestamp.to_rfc3339(),
-        });
-        if let Ok(value_bytes) = serde_json::to_vec(&dht_value) {
-            if let Err(e) = self
-                .network_handle
-                .put_dht_record(dht_key.into_bytes(), value_bytes)
-                .await
-            {
-                tracing::debug!(error = %e, "Failed to publish swarm info to DHT");
-            }
+    /// Remember the services another agent announced; `None` tells nothing.
+    /// This node's own agents' services are only changed by registering.
+    pub fn note_services(&mut self, agent_id: &str, services: Option<&[ServiceEndpoint]>) {
+        let Some(services) = services else {
+            return;
+        };
+        if agent_id == self.agent_id.as_str() || self.is_local_agent(&AgentId::new(agent_id.to_string())) {
+            return;
         }
+        self.agent_services.insert(
+            agent_id.to_string(),
+            crate::did::AnnouncedServices::from_announcement(agent_id, services, chrono::Utc::now()),
+        );
     }
 
-    /// Send a keep-alive message to the swarm.
-    async fn send_keepalive(&self) {
-        let state = self.state.read().await;
-        let swarm_id = state.current_swarm_id.clone();
-        let self_id = state.agent_id.to_string();
-        let params = KeepAliveParams {
-            agent_id: state.agent_id.clone(),
-            agent_name: Some(self.config.agent.name.clone()),
-            last_task_poll_at: state.member_last_task_poll.get(&self_id).cloned(),
-            last_result_at: state.member_last_result.get(&self_id).cloned(),
-            epoch: state.epoch_manager.current_epoch(),
-            timestamp: chrono::Utc::now(),
-        };
-        drop(state);
-
-        let msg = SwarmMessage::new(
-            ProtocolMethod::KeepAlive.as_str(),
-            serde_json::to_value(&params).unwrap_or_default(),
-            String::new(), // Signature would be computed in production.
+    /// Set the services this node's agent offers, to be announced in its
+    /// keep-alives and DID document.
+    pub fn register_services(&mut self, services: Vec<ServiceEndpoint>) {
+        let agent_id = self.agent_id.to_string();
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT agent.services agent={} services=[{}]",
+                agent_id,
+                services.iter().map(|s| s.id.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+        );
+        self.agent_services.insert(
+            agent_id,
+            crate::did::AnnouncedServices {
+                services,
+                announced_at: chrono::Utc::now(),
+            },
         );
+        self.did_republish_due = true;
+    }
 
-        if let Ok(data) = serde_json::to_vec(&msg) {
-            let topic = SwarmTopics::keepalive_for(swarm_id.as_str());
-            if let Err(e) = self.network_handle.publish(&topic, data).await {
-                tracing::debug!(error = %e, "Failed to send keepalive");
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic,
-                    method: Some(ProtocolMethod::KeepAlive.as_str().to_string()),
-                    task_id: None,
-                    size_bytes: 0,
-                    outcome: format!("error: {}", e),
-                });
-            } else {
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic,
-                    method: Some(ProtocolMethod::KeepAlive.as_str().to_string()),
-                    task_id: None,
-                    size_bytes: 0,
-                    outcome: "published".to_string(),
-                });
-            }
+    /// The services `agent_id` offers and where they were learned, from its
+    /// announcements or else its DID document.
+    pub fn services_of(&self, agent_id: &str) -> Option<(Vec<ServiceEndpoint>, &'static str)> {
+        if let Some(announced) = self.agent_services.get(agent_id) {
+            return Some((announced.services.clone(), "announced"));
         }
+        let document = self.did_documents.get(agent_id)?;
+        let services = document
+            .service
+            .iter()
+            .filter(|s| crate::did::is_agent_service(agent_id, s))
+            .cloned()
+            .collect();
+        Some((services, "did_document"))
     }
 
-    /// Check for epoch transitions and trigger elections if needed.
-    async fn check_epoch_transition(&self) {
-        let swarm_size = self
-            .network_handle
-            .estimated_swarm_size()
-            .await
-            .unwrap_or(1);
+    /// Sign a receipt for `voter`'s ballot in this node's tally of `task_id`
+    /// and keep it, replacing any earlier receipt for the same voter.
+    /// Returns `None` if no ballot from `voter` has been counted.
+    pub fn issue_ballot_receipt(
+        &mut self,
+        task_id: &str,
+        voter: &AgentId,
+        epoch: u64,
+    ) -> Option<BallotReceiptParams> {
+        let (position, ballot_hash) = self.voting_engines.get(task_id)?.ballot_position(voter)?;
+        let mut receipt = BallotReceiptParams {
+            task_id: task_id.to_string(),
+            epoch,
+            voter: voter.clone(),
+            ballot_hash: ballot_hash.to_string(),
+            position,
+            recorder: self.agent_id.clone(),
+            recorder_key: String::new(),
+            recorded_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        receipt.sign(&self.identity_key);
+        self.keep_ballot_receipt(receipt.clone());
+        Some(receipt)
+    }
 
-        let mut state = self.state.write().await;
-        let stale_ttl = Duration::from_secs(self.config.hierarchy.keepalive_interval_secs.saturating_mul(3).max(30));
-        state.prune_stale_members(stale_ttl);
-        state.network_stats.total_agents = state.active_member_count(stale_ttl) as u64;
-        if let Some(action) = state.epoch_manager.tick(swarm_size) {
-            match action {
-                wws_hierarchy::epoch::EpochAction::TriggerElection {
-                    new_epoch,
-                    estimated_swarm_size,
-                } => {
-                    tracing::info!(
-                        new_epoch,
-                        swarm_size = estimated_swarm_size,
-                        "Triggering new epoch election"
-                    );
-                    // Recompute pyramid layout.
-                    if let Ok(layout) = state.pyramid.compute_layout(estimated_swarm_size) {
-                        state.network_stats.hierarchy_depth = layout.depth;
-                    }
-                    // Initialize election for new epoch.
-                    let election_config = wws_hierarchy::elections::ElectionConfig::default();
-                    state.election = Some(ElectionManager::new(election_config, new_epoch));
-                    state.status = ConnectorStatus::InElection;
-                    state.push_log(
-                        LogCategory::Epoch,
-                        format!("Epoch {} election triggered (swarm size: {})", new_epoch, estimated_swarm_size),
-                    );
-                }
-                wws_hierarchy::epoch::EpochAction::FinalizeTransition { epoch } => {
-                    tracing::info!(epoch, "Finalizing epoch transition");
-                    // In production, this would tally votes and advance the epoch.
-                    state.status = ConnectorStatus::Running;
-                    state.push_log(
-                        LogCategory::Epoch,
-                        format!("Epoch {} transition finalized", epoch),
-                    );
-                }
-            }
+    /// Keep a receipt another recorder issued for one of this node's local
+    /// agents. Returns whether it was kept; receipts for other voters are
+    /// ignored, and a receipt not signed by its recorder's key is an error.
+    pub fn accept_ballot_receipt(&mut self, receipt: BallotReceiptParams) -> Result<bool, String> {
+        let bound = receipt
+            .recorder_verifying_key()
+            .is_some_and(|key| crate::did::binds_key(receipt.recorder.as_str(), &key));
+        if !bound {
+            return Err(format!(
+                "receipt key does not belong to recorder {}",
+                receipt.recorder
+            ));
         }
+        if receipt.recorder == self.agent_id || !self.is_local_agent(&receipt.voter) {
+            return Ok(false);
+        }
+        self.keep_ballot_receipt(receipt);
+        Ok(true)
     }
 
-    /// Check if any voting engines have reached quorum and run IRV.
-    async fn check_voting_completion(&self) {
-        let mut state = self.state.write().await;
-        let mut completed_votes = Vec::new();
-        let mut assignments_to_run: Vec<(String, String)> = Vec::new();
-
-        // Collect voting results first (to avoid borrow issues)
-        let mut results_to_process = Vec::new();
+    /// This node's own receipt for `voter`'s ballot on `task_id`, if issued.
+    pub fn issued_ballot_receipt(&self, task_id: &str, voter: &AgentId) -> Option<&BallotReceiptParams> {
+        self.ballot_receipts
+            .get(task_id)?
+            .iter()
+            .find(|r| r.recorder == self.agent_id && r.voter == *voter)
+    }
 
-        let task_ids: Vec<String> = state.voting_engines.keys().cloned().collect();
-        let mut pending_logs: Vec<String> = Vec::new();
+    fn keep_ballot_receipt(&mut self, receipt: BallotReceiptParams) {
+        let receipts = self.ballot_receipts.entry(receipt.task_id.clone()).or_default();
+        receipts.retain(|r| !(r.recorder == receipt.recorder && r.voter == receipt.voter));
+        receipts.push(receipt);
+    }
 
-        for task_id in task_ids {
-            let mut single_proposal_id: Option<String> = None;
-            if let Some(proposal_owners) = state.rfp_coordinators.get(&task_id).map(|rfp| {
-                rfp.reveals
-                    .values()
-                    .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
-                    .collect::<std::collections::HashMap<String, AgentId>>()
-            }) {
-                if proposal_owners.len() == 1 {
-                    single_proposal_id = proposal_owners.keys().next().cloned();
-                }
-                if !proposal_owners.is_empty() {
-                    if let Some(v) = state.voting_engines.get_mut(&task_id) {
-                        v.set_proposals(proposal_owners);
-                    }
-                }
-            }
+    /// Ask for `task_id`'s deliberation state when this node has seen no
+    /// commits for it. Returns `None` if commits are already known or a
+    /// request is still waiting on its snapshot.
+    pub fn note_task_state_request(&mut self, task_id: &str) -> Option<TaskStateRequestParams> {
+        if self.rfp_coordinators.get(task_id).is_some_and(|rfp| rfp.commit_count() > 0) {
+            return None;
+        }
+        let now = chrono::Utc::now();
+        if self.task_state_requests.get(task_id).is_some_and(|sent| {
+            (now - *sent).num_seconds() < TASK_STATE_REQUEST_TTL_SECS
+        }) {
+            return None;
+        }
+        self.task_state_requests.insert(task_id.to_string(), now);
+        Some(TaskStateRequestParams {
+            task_id: task_id.to_string(),
+            requester: self.agent_id.clone(),
+        })
+    }
 
-            let requirement = Self::expected_vote_requirement_for_task(&state, &task_id);
-            state
-                .task_vote_requirements
-                .insert(task_id.clone(), requirement.clone());
+    /// Answer a task state request if this node chairs the task's board and
+    /// has commits to share, at most once per `TASK_STATE_REPLY_INTERVAL_SECS`.
+    /// Ballots of a blind vote stay out of the snapshot until it is decided.
+    pub fn answer_task_state_request(
+        &mut self,
+        request: &TaskStateRequestParams,
+    ) -> Option<TaskStateSnapshotParams> {
+        if request.requester == self.agent_id {
+            return None;
+        }
+        let task_id = &request.task_id;
+        if self.active_holons.get(task_id)?.chair != self.agent_id {
+            return None;
+        }
+        let rfp = self.rfp_coordinators.get(task_id).filter(|rfp| rfp.commit_count() > 0)?;
+        let now = chrono::Utc::now();
+        if self.task_state_replies.get(task_id).is_some_and(|sent| {
+            (now - *sent).num_seconds() < TASK_STATE_REPLY_INTERVAL_SECS
+        }) {
+            return None;
+        }
 
-            let (ballot_count, proposal_count) = match state.voting_engines.get(&task_id) {
-                Some(v) => (v.ballot_count(), v.proposal_count()),
-                None => continue,
-            };
-            let mut expected_votes = requirement.expected_voters.max(1);
-            let mut expected_proposals = requirement.expected_proposers.max(1);
+        let mut reveals: Vec<ProposalRevealParams> = rfp
+            .reveals
+            .values()
+            .map(|r| ProposalRevealParams {
+                task_id: task_id.clone(),
+                plan: r.plan.clone(),
+            })
+            .collect();
+        reveals.sort_by(|a, b| a.plan.proposer.as_str().cmp(b.plan.proposer.as_str()));
+        let ballots = self
+            .voting_engines
+            .get(task_id)
+            .filter(|voting| !voting.is_blind() || voting.is_finalized())
+            .map(|voting| {
+                voting
+                    .ballots
+                    .iter()
+                    .map(|b| ConsensusVoteParams {
+                        task_id: task_id.clone(),
+                        epoch: voting.epoch(),
+                        voter: b.voter.clone(),
+                        rankings: b.original_rankings.clone(),
+                        critic_scores: b.critic_scores.clone(),
+                        abstain: b.abstain,
+                        selection: None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let snapshot = TaskStateSnapshotParams {
+            task_id: task_id.clone(),
+            epoch: rfp.epoch(),
+            responder: self.agent_id.clone(),
+            phase: format!("{:?}", rfp.phase()),
+            commits: rfp.commit_params(),
+            reveals,
+            ballots,
+        };
+        self.task_state_replies.insert(task_id.clone(), now);
+        Some(snapshot)
+    }
 
-            if let Some(task) = state.task_details.get(&task_id) {
-                let age_secs = chrono::Utc::now()
-                    .signed_duration_since(task.created_at)
-                    .num_seconds();
-                if age_secs >= PROPOSAL_STAGE_TIMEOUT_SECS {
-                    // Force-advance RFP from CommitPhase if P2P commits didn't arrive in time.
-                    // This ensures the local proposal can proceed to voting even without full quorum.
-                    let pending_reveals = state.pending_plan_reveals.remove(&task_id).unwrap_or_default();
-                    if let Some(rfp) = state.rfp_coordinators.get_mut(&task_id) {
-                        if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::CommitPhase) {
-                            if rfp.commit_count() > 0 {
-                                let _ = rfp.transition_to_reveal();
-                                // Flush any pending reveals that arrived while in CommitPhase
-                                let mut pending_sorted: Vec<(String, wws_protocol::Plan)> =
-                                    pending_reveals.into_iter().collect();
-                                pending_sorted.sort_by(|a, b| a.0.cmp(&b.0));
-                                for (_, plan) in pending_sorted {
-                                    let reveal = wws_protocol::messages::ProposalRevealParams {
-                                        task_id: task_id.clone(),
-                                        plan,
-                                    };
-                                    let _ = rfp.record_reveal(&reveal);
-                                }
-                            }
-                        }
-                    }
-                    // Re-sync voting engine with proposals now that reveals may have been processed
-                    if let Some(proposal_owners) = state.rfp_coordinators.get(&task_id).map(|rfp| {
-                        rfp.reveals
-                            .values()
-                            .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
-                            .collect::<std::collections::HashMap<String, AgentId>>()
-                    }) {
-                        if !proposal_owners.is_empty() {
-                            if let Some(v) = state.voting_engines.get_mut(&task_id) {
-                                v.set_proposals(proposal_owners);
-                            }
-                        }
-                    }
-                    // Recalculate proposal_count after potential forced reveal
-                    let proposal_count_now = state.voting_engines.get(&task_id).map(|v| v.proposal_count()).unwrap_or(0);
-                    expected_proposals = expected_proposals.min(proposal_count_now.max(1));
-                }
-                if age_secs >= VOTING_STAGE_TIMEOUT_SECS {
-                    expected_votes = expected_votes.min(ballot_count.max(1));
-                }
-            }
+    /// Replay a snapshot answering this node's own task state request into
+    /// its RFP coordinator and voting engine. Entries already known, or that
+    /// fail verification (a reveal not matching its commit), are skipped.
+    /// Returns the number of commits, reveals and ballots applied, or `None`
+    /// if no request for the task is outstanding.
+    pub fn apply_task_state_snapshot(
+        &mut self,
+        snapshot: &TaskStateSnapshotParams,
+        requirement: &TaskVoteRequirement,
+    ) -> Option<(usize, usize, usize)> {
+        if snapshot.responder == self.agent_id {
+            return None;
+        }
+        self.task_state_requests.remove(&snapshot.task_id)?;
+        let task_id = snapshot.task_id.clone();
+        self.task_vote_requirements.insert(task_id.clone(), requirement.clone());
+        let task = self.task_details.get(&task_id).cloned().unwrap_or(Task {
+            task_id: task_id.clone(),
+            epoch: snapshot.epoch,
+            status: TaskStatus::Pending,
+            description: "Backfilled task state".to_string(),
+            tier_level: requirement.tier_level,
+            created_at: chrono::Utc::now(),
+            ..Default::default()
+        });
 
-            // Recalculate proposal_count (may have changed due to forced reveal above)
-            let (ballot_count, proposal_count) = match state.voting_engines.get(&task_id) {
-                Some(v) => (v.ballot_count(), v.proposal_count()),
-                None => continue,
-            };
+        let mut pending_reveals: Vec<ProposalRevealParams> = self
+            .pending_plan_reveals
+            .remove(&task_id)
+            .unwrap_or_default()
+            .into_values()
+            .map(|plan| ProposalRevealParams {
+                task_id: task_id.clone(),
+                plan,
+            })
+            .collect();
+        pending_reveals.sort_by(|a, b| a.plan.proposer.as_str().cmp(b.plan.proposer.as_str()));
 
-            // Strict participation gate: all expected tier members must propose and vote.
-            let task_age_secs = state
-                .task_details
-                .get(&task_id)
-                .map(|task| chrono::Utc::now().signed_duration_since(task.created_at).num_seconds())
-                .unwrap_or(0);
+        let rfp = self.rfp_coordinators.entry(task_id.clone()).or_insert_with(|| {
+            RfpCoordinator::new(task_id.clone(), snapshot.epoch, requirement.expected_proposers)
+        });
+        if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::Idle) {
+            let _ = rfp.inject_task(&task);
+        }
+        let accepted_commits: Vec<&ProposalCommitParams> = snapshot
+            .commits
+            .iter()
+            .filter(|commit| rfp.record_commit(commit).is_ok())
+            .collect();
+        let commits = accepted_commits.len();
+        if !snapshot.reveals.is_empty() && matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::CommitPhase) {
+            let _ = rfp.transition_to_reveal();
+        }
+        let mut revealed_proposers = Vec::new();
+        for reveal in snapshot.reveals.iter().chain(&pending_reveals) {
+            if rfp.reveals.contains_key(&reveal.plan.proposer) {
+                continue;
+            }
+            match rfp.record_reveal(reveal) {
+                Ok(()) => revealed_proposers.push(reveal.plan.proposer.to_string()),
+                Err(e) => tracing::debug!(error = %e, "Skipping backfilled proposal reveal"),
+            }
+        }
+        let proposal_owners: std::collections::HashMap<String, AgentId> = rfp
+            .reveals
+            .values()
+            .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
+            .collect();
+        let reveals = revealed_proposers.len();
+        for proposer in revealed_proposers {
+            self.bump_plans_revealed(&proposer);
+        }
+        for commit in accepted_commits {
+            self.hold_proposal_bond(commit);
+        }
 
-            if ballot_count == 0
-                && proposal_count == 1
-                && task_age_secs >= VOTING_STAGE_TIMEOUT_SECS
-            {
-                if let Some(winner) = single_proposal_id {
-                    state.push_log(
-                        LogCategory::Vote,
-                        format!(
-                            "Voting timeout for task {}; selecting sole proposal {}",
-                            task_id, winner
-                        ),
-                    );
-                    state.push_task_timeline_event(
-                        &task_id,
-                        "plan_selected",
-                        format!("Sole proposal {} selected after voting timeout", winner),
-                        None,
-                    );
-                    if let Some(task) = state.task_details.get_mut(&task_id) {
-                        task.status = TaskStatus::InProgress;
-                    }
-                    assignments_to_run.push((task_id.clone(), winner));
-                    completed_votes.push(task_id.clone());
+        let mut ballots = 0;
+        if !proposal_owners.is_empty() {
+            let voting_config = self.voting_config.clone();
+            let voting = self.voting_engines.entry(task_id.clone()).or_insert_with(|| {
+                VotingEngine::new(voting_config, task_id.clone(), snapshot.epoch)
+            });
+            voting.set_proposals(proposal_owners);
+            for ballot in &snapshot.ballots {
+                if voting.ballot_position(&ballot.voter).is_some() {
                     continue;
                 }
+                let vote = RankedVote {
+                    voter: ballot.voter.clone(),
+                    task_id: task_id.clone(),
+                    epoch: ballot.epoch,
+                    rankings: ballot.rankings.clone(),
+                    critic_scores: ballot.critic_scores.clone(),
+                    abstain: ballot.abstain,
+                };
+                if voting.record_vote(vote).is_ok() {
+                    ballots += 1;
+                }
             }
+            if let Some(task) = self.task_details.get_mut(&task_id) {
+                if matches!(task.status, TaskStatus::Pending | TaskStatus::ProposalPhase) {
+                    task.status = TaskStatus::VotingPhase;
+                }
+            }
+        }
 
-            if proposal_count >= expected_proposals && ballot_count >= expected_votes {
-                tracing::info!(
-                    task_id = %task_id,
-                    ballot_count,
-                    proposal_count,
-                    expected_votes,
-                    expected_proposals,
-                    "Voting quorum reached, running IRV"
-                );
+        self.push_task_timeline_event(
+            &task_id,
+            "state_backfill",
+            format!(
+                "{} commits, {} reveals, {} ballots from chair ({})",
+                commits, reveals, ballots, snapshot.phase
+            ),
+            Some(snapshot.responder.to_string()),
+        );
+        Some((commits, reveals, ballots))
+    }
 
-                // Run Instant Runoff Voting to select winner
-                let irv_result = {
-                    let voting_engine = match state.voting_engines.get_mut(&task_id) {
-                        Some(v) => v,
-                        None => continue,
-                    };
-                    let result = voting_engine.run_irv();
-                    // Persist IRV rounds for API visibility
-                    let rounds = voting_engine.irv_rounds().to_vec();
-                    (result, rounds)
-                };
+    /// Count an acceptance towards a board this connector chairs. Returns
+    /// the `board.ready` announcement once at least `min_size` agents have
+    /// accepted and either the quorum policy is met or every seat is taken.
+    pub fn record_board_accept(
+        &mut self,
+        params: &BoardAcceptParams,
+        eligible: &[String],
+    ) -> Option<BoardReadyParams> {
+        let formation = self.board_formations.get_mut(&params.task_id)?;
+        if !formation.record_accept(params.clone()) {
+            return None;
+        }
+        let accepted = formation.accepted.len();
+        let full = formation.is_full();
+        if accepted < self.board_config.min_size.max(1) {
+            return None;
+        }
+        if full || self.board_quorum_reached(&params.task_id, eligible) {
+            return self.finish_board_formation(&params.task_id);
+        }
+        None
+    }
 
-                let (irv_result, irv_rounds) = irv_result;
-                // Persist IRV rounds to state
-                if !irv_rounds.is_empty() {
-                    state.irv_rounds.insert(task_id.clone(), irv_rounds);
-                }
+    /// Count a `board.decline` towards a board this connector chairs.
+    pub fn record_board_decline(&mut self, params: &BoardDeclineParams) {
+        if let Some(formation) = self.board_formations.get_mut(&params.task_id) {
+            formation.record_decline(params.agent_id.clone());
+        }
+        if let Some(acceptances) = self.board_acceptances.get_mut(&params.task_id) {
+            acceptances.retain(|a| a.agent_id != params.agent_id);
+        }
+        if let Some(holon) = self.active_holons.get_mut(&params.task_id) {
+            holon.members.retain(|m| m != &params.agent_id);
+        }
+    }
 
-                match irv_result {
-                    Ok(result) => {
-                        results_to_process.push((task_id.clone(), Ok(result)));
-                        completed_votes.push(task_id.clone());
-                    }
-                    Err(e) => {
-                        results_to_process.push((task_id.clone(), Err(e)));
-                    }
-                }
-            } else {
-                pending_logs.push(format!(
-                    "Voting pending for task {}: proposals {}/{} votes {}/{}",
-                    task_id, proposal_count, expected_proposals, ballot_count, expected_votes
-                ));
+    /// Close formations whose acceptance window has ended: those with at
+    /// least `min_size` acceptances form with what they have, the rest are
+    /// dissolved. Returns the announcements and the dissolved task IDs.
+    pub fn expire_board_formations(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> (Vec<BoardReadyParams>, Vec<String>) {
+        let mut expired: Vec<String> = self
+            .board_formations
+            .iter()
+            .filter(|(_, f)| f.is_expired(now))
+            .map(|(task_id, _)| task_id.clone())
+            .collect();
+        expired.sort();
+
+        let min_size = self.board_config.min_size.max(1);
+        let mut ready = Vec::new();
+        let mut dissolved = Vec::new();
+        for task_id in expired {
+            let accepted = self.board_formations[&task_id].accepted.len();
+            if accepted >= min_size {
+                ready.extend(self.finish_board_formation(&task_id));
+                continue;
+            }
+            self.board_formations.remove(&task_id);
+            if let Some(holon) = self.active_holons.get_mut(&task_id) {
+                holon.status = HolonStatus::Done;
             }
+            self.push_task_timeline_event(
+                &task_id,
+                "board_failed",
+                format!(
+                    "Only {} of {} required members accepted; chair proceeds alone",
+                    accepted, min_size
+                ),
+                None,
+            );
+            self.push_log(
+                LogCategory::Task,
+                format!("Board for task {} not formed ({} accepted)", task_id, accepted),
+            );
+            dissolved.push(task_id);
         }
+        (ready, dissolved)
+    }
 
-        for msg in pending_logs {
-            state.push_log(LogCategory::Vote, msg);
+    fn finish_board_formation(&mut self, task_id: &str) -> Option<BoardReadyParams> {
+        let formation = self.board_formations.remove(task_id)?;
+        let mut ready = formation.announce(formation.select_members());
+        if formation.invite.private {
+            let group_key = GroupKey::generate();
+            formation.seal_group_key(&mut ready, &group_key);
+            self.board_channels.entry(task_id.to_string()).or_default().group_key = Some(group_key);
         }
+        if let Some(holon) = self.active_holons.get_mut(task_id) {
+            holon.members = ready.members.clone();
+            holon.adversarial_critic = ready.adversarial_critic.clone();
+            holon.status = HolonStatus::Deliberating;
+        }
+        self.push_task_timeline_event(
+            task_id,
+            "board_ready",
+            format!(
+                "Board formed with {} of {} accepted (critic: {})",
+                ready.members.len(),
+                formation.accepted.len(),
+                ready
+                    .adversarial_critic
+                    .as_ref()
+                    .map(|c| c.as_str())
+                    .unwrap_or("none")
+            ),
+            Some(ready.chair_id.to_string()),
+        );
+        self.push_log(
+            LogCategory::Task,
+            format!("Board ready for task {} ({} members)", task_id, ready.members.len()),
+        );
+        Some(ready)
+    }
 
-        // Process results (now we can mutably borrow state again)
-        for (task_id, result) in results_to_process {
-            match result {
-                Ok(voting_result) => {
-                    state.push_log(
-                        LogCategory::Vote,
-                        format!(
-                            "Voting complete for task {}: winner = {} ({} rounds, {} votes)",
-                            task_id, voting_result.winner, voting_result.rounds, voting_result.total_votes
-                        ),
-                    );
-
-                    state.push_task_timeline_event(
-                        &task_id,
-                        "plan_selected",
-                        format!("Plan {} selected by IRV after {} rounds", voting_result.winner, voting_result.rounds),
-                        None,
-                    );
+    /// Wrap a deliberation message for a private board in `board.sealed`,
+    /// encrypted under the board's group key. Messages for tasks without a
+    /// group key are returned unchanged.
+    pub fn seal_board_message(
+        &self,
+        task_id: &str,
+        message: SwarmMessage,
+    ) -> Result<SwarmMessage, ProtocolError> {
+        let Some(group_key) = self
+            .board_channels
+            .get(task_id)
+            .and_then(|channel| channel.group_key.as_ref())
+        else {
+            return Ok(message);
+        };
+        let sealed = group_key.encrypt(task_id, &serde_json::to_vec(&message)?)?;
+        Ok(SwarmMessage::new(
+            ProtocolMethod::BoardSealed.as_str(),
+            serde_json::to_value(&sealed)?,
+            String::new(),
+        ))
+    }
 
-                    // Update task status to InProgress
-                    if let Some(task) = state.task_details.get_mut(&task_id) {
-                        task.status = TaskStatus::InProgress;
-                    }
+    /// Open the synthesis subtask of a `ChairSynthesis` parent whose other
+    /// subtasks have all completed, assigned to the parent's board chair.
+    /// Returns the assignment to publish, or `None` if it is already open
+    /// or a child holon of the parent is still working.
+    pub fn open_synthesis_subtask(&mut self, parent_task_id: &str) -> Option<TaskAssignmentParams> {
+        let synthesis_id = crate::aggregation::synthesis_task_id(parent_task_id);
+        let parent = self.task_details.get(parent_task_id)?;
+        if parent.subtasks.contains(&synthesis_id) || !self.child_holons_done(parent_task_id) {
+            return None;
+        }
+        let chair = self
+            .active_holons
+            .get(parent_task_id)
+            .map(|h| h.chair.clone())
+            .or_else(|| parent.assigned_to.clone())
+            .unwrap_or_else(|| self.agent_id.clone());
+        let inputs: Vec<String> = parent
+            .subtasks
+            .iter()
+            .map(|id| {
+                // Sealed results stay out of the assignment, which every
+                // member of the tier sees; the chair reads them itself.
+                let sealed = self.task_results.get(id).is_some_and(|a| a.sealed.is_some());
+                let result = if sealed {
+                    "(sealed; read it with swarm.get_result)".to_string()
+                } else {
+                    self.task_result_text
+                        .get(id)
+                        .cloned()
+                        .or_else(|| self.task_results.get(id).map(|a| format!("cid:{}", a.content_cid)))
+                        .unwrap_or_default()
+                };
+                format!("- {}: {}", id, result)
+            })
+            .collect();
 
-                    // Update holon status to Executing
-                    if let Some(holon) = state.active_holons.get_mut(&task_id) {
-                        holon.status = HolonStatus::Executing;
-                    }
+        let mut task = Task::new(
+            format!(
+                "Synthesize the subtask results into one result for: {}\n\nSubtask results:\n{}",
+                parent.description,
+                inputs.join("\n")
+            ),
+            parent.tier_level,
+            self.epoch_manager.current_epoch(),
+        );
+        task.task_id = synthesis_id.clone();
+        task.parent_task_id = Some(parent_task_id.to_string());
+        task.assigned_to = Some(chair.clone());
+        task.status = TaskStatus::InProgress;
+        task.result_schema = parent.result_schema.clone();
+        task.visibility = parent.visibility;
+
+        if let Some(parent) = self.task_details.get_mut(parent_task_id) {
+            parent.subtasks.push(synthesis_id.clone());
+        }
+        if let Some(holon) = self.active_holons.get_mut(parent_task_id) {
+            holon.status = HolonStatus::Synthesizing;
+        }
+        self.task_details.insert(synthesis_id.clone(), task.clone());
+        if self.is_local_agent(&chair) {
+            self.task_set.add(synthesis_id.clone());
+        }
+        self.push_task_timeline_event(
+            parent_task_id,
+            "synthesis_assigned",
+            format!("Synthesis subtask {} assigned to chair", synthesis_id),
+            Some(chair.to_string()),
+        );
+        self.push_log(
+            LogCategory::Task,
+            format!("Synthesis of task {} assigned to chair {}", parent_task_id, chair),
+        );
+        Some(self.signed_assignment(
+            task,
+            chair,
+            parent_task_id.to_string(),
+            crate::aggregation::SYNTHESIS_PLAN_ID,
+        ))
+    }
 
-                    tracing::info!(
-                        task_id = %task_id,
-                        winner = %voting_result.winner,
-                        rounds = voting_result.rounds,
-                        "Voting completed successfully"
-                    );
+    /// Whether every child holon spawned for `parent_task_id` is `Done`.
+    /// A child whose board is not tracked here counts by its task status.
+    pub fn child_holons_done(&self, parent_task_id: &str) -> bool {
+        let Some(parent) = self.active_holons.get(parent_task_id) else {
+            return true;
+        };
+        parent.child_holons.iter().all(|child| match self.active_holons.get(child) {
+            Some(holon) => holon.status == HolonStatus::Done,
+            None => self
+                .task_details
+                .get(child)
+                .is_some_and(|t| t.status == TaskStatus::Completed),
+        })
+    }
 
-                    assignments_to_run.push((task_id.clone(), voting_result.winner.clone()));
-                }
-                Err(e) => {
-                    tracing::error!(
-                        error = %e,
-                        task_id = %task_id,
-                        "IRV execution failed"
-                    );
-                    state.push_log(
-                        LogCategory::Vote,
-                        format!("Voting failed for task {}: {}", task_id, e),
-                    );
-                }
-            }
+    /// Whether `agent` coordinates `task_id`: the task is assigned to it, or
+    /// unassigned and `agent` is a coordinator at the task's tier.
+    pub fn coordinates(&self, agent: &AgentId, task_id: &str) -> bool {
+        let Some(task) = self.task_details.get(task_id) else {
+            return false;
+        };
+        if let Some(assignee) = &task.assigned_to {
+            return assignee == agent;
         }
+        let tier = if *agent == self.agent_id {
+            self.tier_in(&self.swarm_for_task(task_id))
+        } else {
+            self.agent_tiers.get(agent.as_str()).copied().unwrap_or(Tier::Executor)
+        };
+        tier != Tier::Executor && tier.depth() == task.tier_level
+    }
 
-        // Remove completed voting engines
-        for task_id in completed_votes {
-            state.voting_engines.remove(&task_id);
-            state.task_vote_requirements.remove(&task_id);
+    /// The parent of a remote node that just completed, when it is
+    /// coordinated here and every other subtask and child holon is done, so
+    /// the caller can synthesize or aggregate it.
+    pub fn delegated_parent_ready(&self, task_id: &str) -> Option<String> {
+        let task = self.task_details.get(task_id).filter(|t| t.remote.is_some())?;
+        let parent_id = task.parent_task_id.as_ref()?;
+        let parent = self.task_details.get(parent_id)?;
+        let ready = parent.status != TaskStatus::Completed
+            && self.coordinates(&self.agent_id, parent_id)
+            && parent.subtasks.iter().all(|id| {
+                self.task_details
+                    .get(id)
+                    .is_some_and(|t| t.status == TaskStatus::Completed)
+            })
+            && self.child_holons_done(parent_id);
+        ready.then(|| parent_id.clone())
+    }
+
+    /// Mark the holon of a task whose result was just accepted `Done`. For
+    /// a child holon, record the result's producer and artifact CID with
+    /// the parent holon. Returns the parent task once all of its subtasks
+    /// have completed and all of its child holons are done, so the caller
+    /// can synthesize or aggregate it.
+    pub fn complete_holon(&mut self, task_id: &str, artifact: &Artifact) -> Option<String> {
+        let holon = self.active_holons.get_mut(task_id)?;
+        holon.status = HolonStatus::Done;
+        let parent_id = holon.parent_holon.clone()?;
+        let parent = self.active_holons.get_mut(&parent_id)?;
+        if !parent.child_holons.iter().any(|c| c == task_id) {
+            return None;
         }
+        parent
+            .subtask_assignments
+            .insert(task_id.to_string(), artifact.producer.clone());
+        parent
+            .child_artifacts
+            .insert(task_id.to_string(), artifact.content_cid.clone());
+        let (finished, total) = (
+            parent
+                .child_holons
+                .iter()
+                .filter(|c| parent.child_artifacts.contains_key(*c))
+                .count(),
+            parent.child_holons.len(),
+        );
+        self.push_task_timeline_event(
+            &parent_id,
+            "child_holon_done",
+            format!(
+                "Sub-holon {} finished with artifact cid:{} ({} of {} child holons done)",
+                task_id, artifact.content_cid, finished, total
+            ),
+            Some(artifact.producer.to_string()),
+        );
+        let parent_task = self.task_details.get(&parent_id)?;
+        let subtasks_done = !parent_task.subtasks.is_empty()
+            && parent_task.subtasks.iter().all(|id| {
+                self.task_details
+                    .get(id)
+                    .is_some_and(|t| t.status == TaskStatus::Completed)
+            });
+        (parent_task.status != TaskStatus::Completed
+            && subtasks_done
+            && self.child_holons_done(&parent_id))
+        .then_some(parent_id)
+    }
 
-        drop(state);
+    /// Whether `agent_id` is this connector's own agent or one of its local
+    /// agents.
+    pub fn is_local_agent(&self, agent_id: &AgentId) -> bool {
+        *agent_id == self.agent_id || self.local_agents.contains(agent_id.as_str())
+    }
 
-        for (task_id, winner_plan_id) in assignments_to_run {
-            if let Err(e) = self.assign_subtasks_from_winner(&task_id, &winner_plan_id).await {
-                tracing::error!(
-                    task_id = %task_id,
-                    winner = %winner_plan_id,
-                    error = %e,
-                    "Failed to assign subtasks after voting"
-                );
+    /// Load of an agent served by this connector: its open tasks here,
+    /// queued until the agent fetches them.
+    pub fn local_load(&self, agent_id: &str) -> AgentLoad {
+        let mut load = AgentLoad::default();
+        let mut held: Vec<&Task> = self
+            .task_details
+            .values()
+            .filter(|t| {
+                t.assigned_to.as_ref().is_some_and(|a| a.as_str() == agent_id)
+                    && self.task_set.contains(&t.task_id)
+                    && crate::migration::is_open(&t.status)
+            })
+            .collect();
+        held.sort_by(|a, b| (a.created_at, &a.task_id).cmp(&(b.created_at, &b.task_id)));
+        for task in held {
+            if self.picked_up_tasks.contains(&task.task_id) {
+                load.in_flight += 1;
+            } else {
+                load.queue_depth += 1;
+                load.queued.push(task.task_id.clone());
             }
         }
+        if agent_id == self.agent_id.as_str() {
+            load.max_concurrency = self.max_concurrent_tasks;
+        }
+        load
     }
 
-    async fn check_execution_timeouts(&self) {
-        let now = chrono::Utc::now();
-        let mut publishes: Vec<(String, Vec<u8>, String)> = Vec::new();
+    /// Load to assume for `agent_id` when handing it work: counted here for
+    /// this connector's agents, as last reported for the others.
+    pub fn load_of(&self, agent_id: &AgentId) -> AgentLoad {
+        if self.is_local_agent(agent_id) {
+            self.local_load(agent_id.as_str())
+        } else {
+            self.agent_loads.get(agent_id.as_str()).cloned().unwrap_or_default()
+        }
+    }
 
-        {
-            let mut state = self.state.write().await;
-            let my_id = state.agent_id.to_string();
-            let swarm_id = state.current_swarm_id.as_str().to_string();
-            let poll_staleness = Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS);
-            let seen_staleness = Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS);
-            let active_members: std::collections::HashSet<String> =
-                state.active_member_ids(seen_staleness).into_iter().collect();
+    /// Record the load `agent_id` reported in a keep-alive.
+    pub fn note_load(&mut self, agent_id: &str, load: AgentLoad) {
+        self.work_stealing.note_load(agent_id, &load);
+        self.agent_loads.insert(agent_id.to_string(), load);
+    }
 
-            let timed_out_tasks: Vec<String> = state
-                .task_details
-                .iter()
-                .filter_map(|(task_id, task)| {
-                    if !matches!(task.status, TaskStatus::InProgress) {
-                        return None;
-                    }
-                    if task.parent_task_id.is_none() {
-                        return None;
-                    }
-                    match task.deadline {
-                        Some(deadline) if deadline <= now => Some(task_id.clone()),
-                        _ => None,
-                    }
-                })
-                .collect();
+    /// Record the layout role `agent_id` announced in a keep-alive; an
+    /// announcement without one clears it.
+    pub fn note_role(&mut self, agent_id: &str, role: Option<LayoutRole>) {
+        match role {
+            Some(role) => self.agent_roles.insert(agent_id.to_string(), role),
+            None => self.agent_roles.remove(agent_id),
+        };
+    }
 
-            for task_id in timed_out_tasks {
-                let Some(task_snapshot) = state.task_details.get(&task_id).cloned() else {
-                    continue;
-                };
-                let Some(parent_id) = task_snapshot.parent_task_id.clone() else {
-                    continue;
-                };
-                let old_assignee = task_snapshot.assigned_to.clone();
-                let my_subordinates = state
-                    .subordinates
-                    .get(&my_id)
-                    .cloned()
-                    .unwrap_or_default();
-                if !my_subordinates
-                    .iter()
-                    .any(|id| Some(id.as_str()) == old_assignee.as_ref().map(|a| a.as_str()))
-                {
-                    continue;
-                }
-                let expected_tier = old_assignee
-                    .as_ref()
-                    .and_then(|a| state.agent_tiers.get(a.as_str()).copied());
+    /// Local executors whose agents poll for work but hold none and may
+    /// ask again, with their parents.
+    pub fn idle_executors(&self, now: chrono::DateTime<chrono::Utc>) -> Vec<(AgentId, AgentId)> {
+        let own = (self.my_tier == Tier::Executor)
+            .then(|| self.parent_id.clone().map(|p| (self.agent_id.clone(), p)))
+            .flatten();
+        let local = self.local_agents.agents().filter_map(|a| {
+            let tier = self.agent_tiers.get(a.agent_id.as_str()).copied();
+            let parent = self.agent_parents.get(a.agent_id.as_str())?;
+            (tier.unwrap_or(Tier::Executor) == Tier::Executor)
+                .then(|| (a.agent_id.clone(), AgentId::new(parent.clone())))
+        });
+        own.into_iter()
+            .chain(local)
+            .filter(|(agent_id, _)| {
+                let polling = self.member_last_task_poll.get(agent_id.as_str()).is_some_and(|at| {
+                    now.signed_duration_since(*at).num_seconds()
+                        <= PARTICIPATION_POLL_STALENESS_SECS as i64
+                });
+                polling
+                    && self.local_load(agent_id.as_str()).held() == 0
+                    && self.work_stealing.may_request(agent_id.as_str(), now)
+            })
+            .collect()
+    }
 
-                let mut candidates = my_subordinates
-                    .into_iter()
-                    .filter(|candidate| {
-                        if Some(candidate.as_str()) == old_assignee.as_ref().map(|a| a.as_str()) {
-                            return false;
-                        }
-                        if !active_members.contains(candidate) {
-                            return false;
-                        }
-                        if !Self::member_loop_active(&state, candidate, poll_staleness) {
-                            return false;
-                        }
-                        if let Some(tier) = expected_tier {
-                            return state.agent_tiers.get(candidate).copied().unwrap_or(Tier::Executor)
-                                == tier;
-                        }
-                        true
-                    })
-                    .collect::<Vec<_>>();
+    /// As the requester's parent, move the newest queued subtask of the
+    /// sibling with the deepest queue to the idle requester. Declined while
+    /// another transfer is unsettled. Returns the assignment to publish.
+    pub fn arbitrate_steal(
+        &mut self,
+        request: &WorkStealRequestParams,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<TaskAssignmentParams, String> {
+        if request.parent_id != self.agent_id {
+            return Err(format!("{} is not the parent of {}", self.agent_id, request.agent_id));
+        }
+        let thief = request.agent_id.to_string();
+        let siblings = self.subordinates.get(self.agent_id.as_str()).cloned().unwrap_or_default();
+        if !siblings.contains(&thief) {
+            return Err(format!("{} is not a subordinate", thief));
+        }
+        if self.work_stealing.is_busy(now) {
+            return Err("another transfer is in flight".to_string());
+        }
 
-                candidates.sort();
-                let Some(new_assignee) = candidates.into_iter().next() else {
+        let mut victims: Vec<(String, AgentLoad)> = siblings
+            .into_iter()
+            .filter(|id| *id != thief)
+            .map(|id| {
+                let load = self.load_of(&AgentId::new(id.clone()));
+                (id, load)
+            })
+            .filter(|(_, load)| !load.queued.is_empty())
+            .collect();
+        victims.sort_by(|(a, la), (b, lb)| lb.queue_depth.cmp(&la.queue_depth).then(a.cmp(b)));
+        for (victim, load) in victims {
+            for task_id in load.queued.iter().rev() {
+                let queued_here = self.task_details.get(task_id).is_some_and(|t| {
+                    t.assigned_to.as_ref().is_some_and(|a| a.as_str() == victim)
+                        && !self.picked_up_tasks.contains(task_id)
+                });
+                if !queued_here {
+                    continue;
+                }
+                let Ok((assignment, _)) = self.hand_over(task_id, &thief, STEAL_PLAN_ID) else {
                     continue;
                 };
-
-                if let Some(task) = state.task_details.get_mut(&task_id) {
-                    task.assigned_to = Some(AgentId::new(new_assignee.clone()));
-                    task.status = TaskStatus::InProgress;
-                    task.deadline = Some(now + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS));
+                if let Some(load) = self.agent_loads.get_mut(&victim) {
+                    load.queued.retain(|id| id != task_id);
+                    load.queue_depth = load.queue_depth.saturating_sub(1);
                 }
-                state.bump_tasks_assigned(&new_assignee);
-                state.push_task_timeline_event(
-                    &task_id,
-                    "reassigned",
-                    format!(
-                        "Task reassigned due to timeout: {} -> {}",
-                        old_assignee
-                            .as_ref()
-                            .map(|a| a.as_str())
-                            .unwrap_or("unassigned"),
-                        new_assignee
-                    ),
-                    Some(my_id.clone()),
+                self.work_stealing.in_flight = Some(WorkSteal {
+                    task_id: task_id.clone(),
+                    from: victim.clone(),
+                    to: thief.clone(),
+                    granted_at: now,
+                });
+                let parent = self.agent_id.to_string();
+                self.push_task_timeline_event(
+                    task_id,
+                    "stolen",
+                    format!("Queued at {}; taken by idle executor {}", victim, thief),
+                    Some(parent),
                 );
-                state.push_log(
+                self.push_log(
                     LogCategory::Task,
+                    format!("Work steal: {} moved from {} to {}", task_id, victim, thief),
+                );
+                return Ok(assignment);
+            }
+        }
+        Err("no queued work to steal".to_string())
+    }
+
+    /// A message sent on behalf of `agent_id`, signed with its key when it is
+    /// a local agent.
+    pub fn message_as(
+        &self,
+        agent_id: &AgentId,
+        method: &str,
+        params: serde_json::Value,
+    ) -> SwarmMessage {
+        let signature = self
+            .local_agents
+            .get(agent_id.as_str())
+            .map(|agent| agent.sign(method, &params))
+            .unwrap_or_default();
+        SwarmMessage::new(method, params, signature)
+    }
+
+    /// Seconds before an open proposal stage is forced forward.
+    pub fn proposal_stage_timeout_secs(&self) -> i64 {
+        self.swarm_params
+            .as_ref()
+            .map(|p| p.proposal_timeout_secs as i64)
+            .unwrap_or(PROPOSAL_STAGE_TIMEOUT_SECS)
+    }
+
+    /// Seconds before an open voting stage is forced forward.
+    pub fn voting_stage_timeout_secs(&self) -> i64 {
+        self.swarm_params
+            .as_ref()
+            .map(|p| p.voting_timeout_secs as i64)
+            .unwrap_or(VOTING_STAGE_TIMEOUT_SECS)
+    }
+
+    /// Seconds blind ballots have to be revealed once the commitments close.
+    pub fn reveal_stage_timeout_secs(&self) -> i64 {
+        self.reveal_timeout_secs
+            .map(|secs| secs as i64)
+            .unwrap_or_else(|| self.voting_stage_timeout_secs())
+    }
+
+    /// Pyramid branching factor for a swarm of `swarm_size` members: the
+    /// swarm parameters' value, or one derived from the size when unset.
+    pub fn branching_factor(&self, swarm_size: u64) -> u64 {
+        match self.swarm_params.as_ref().map(|p| p.branching_factor) {
+            Some(k) if k > 0 => k as u64,
+            _ => crate::swarm_params::dynamic_branching_factor(swarm_size),
+        }
+    }
+
+    /// Whether this node issued the swarm parameters in force.
+    pub fn is_params_authority(&self) -> bool {
+        self.swarm_params
+            .as_ref()
+            .is_some_and(|p| p.is_issued_by(&self.identity_key.verifying_key()))
+    }
+
+    /// Adopt a parameter record for the current swarm if it is valid and
+    /// newer than the one in force.
+    pub fn adopt_swarm_params(&mut self, params: SwarmParameters) -> ParamsDecision {
+        if params.swarm_id != self.current_swarm_id {
+            return ParamsDecision::Ignore;
+        }
+        let decision = crate::swarm_params::decide(self.swarm_params.as_ref(), &params);
+        if decision == ParamsDecision::Adopt {
+            self.quorum_policy = params.quorum.clone();
+            self.params_proposals.retain(|_, p| p.base_version >= params.version);
+            self.push_log(
+                LogCategory::System,
+                format!(
+                    "Adopted swarm parameters v{} (branching={}, proposal_timeout={}s, voting_timeout={}s)",
+                    params.version,
+                    params.branching_factor,
+                    params.proposal_timeout_secs,
+                    params.voting_timeout_secs
+                ),
+            );
+            self.swarm_params = Some(params);
+        }
+        decision
+    }
+
+    /// Record a parameter change proposal. Returns the next parameter
+    /// version when this node is the authority and the proposal already
+    /// has a majority.
+    pub fn record_params_proposal(
+        &mut self,
+        params: ParamsChangeProposeParams,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<SwarmParameters> {
+        let current_version = self.swarm_params.as_ref()?.version;
+        if params.swarm_id != self.current_swarm_id || params.base_version != current_version {
+            return None;
+        }
+        let proposal_id = params.proposal_id.clone();
+        self.push_log(
+            LogCategory::System,
+            format!("Swarm parameter change {} proposed by {}", proposal_id, params.proposer),
+        );
+        self.params_proposals
+            .entry(proposal_id.clone())
+            .or_insert_with(|| ParamsProposal::from(params));
+        self.tally_params_proposal(&proposal_id, now)
+    }
+
+    /// Record a vote on a parameter change proposal. Returns the next
+    /// parameter version when this node is the authority and the vote
+    /// gave the proposal a majority.
+    pub fn record_params_vote(
+        &mut self,
+        vote: &ParamsChangeVoteParams,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<SwarmParameters> {
+        let proposal = self.params_proposals.get_mut(&vote.proposal_id)?;
+        proposal.votes.insert(vote.voter.to_string(), vote.approve);
+        self.tally_params_proposal(&vote.proposal_id, now)
+    }
+
+    /// Drop expired proposals and, if this node is the authority, issue the
+    /// next parameter version when `proposal_id` has a majority.
+    fn tally_params_proposal(
+        &mut self,
+        proposal_id: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<SwarmParameters> {
+        self.params_proposals.retain(|_, p| p.expires_at > now);
+        if !self.is_params_authority() {
+            return None;
+        }
+        let active = self.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+        let proposal = self.params_proposals.get(proposal_id)?;
+        if !proposal.is_approved(active) {
+            return None;
+        }
+        let mut next = self.swarm_params.as_ref()?.with_changes(&proposal.changes, now);
+        next.sign(&self.identity_key);
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT swarm.params_change proposal={} approvals={} active={} version={}",
+                proposal_id,
+                proposal.approvals(),
+                active,
+                next.version
+            ),
+        );
+        self.params_proposals.remove(proposal_id);
+        self.adopt_swarm_params(next.clone());
+        Some(next)
+    }
+
+    /// Drop the RFP coordinator and voting engine of every round opened
+    /// more than `max_round_age_secs` before `now`, with the round's
+    /// pending reveals and quorum. Rounds held for operator approval are
+    /// kept. A task still deliberating is marked `Failed` with a
+    /// `consensus_expired` timeline event; those tasks are returned with
+    /// their swarm so the caller can release their topics.
+    pub fn expire_abandoned_rounds(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<(String, String)> {
+        let max_age = i64::try_from(self.max_round_age_secs)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .unwrap_or(chrono::Duration::MAX);
+        let started_at = |task_id: &str| {
+            self.rfp_coordinators
+                .get(task_id)
+                .map(|rfp| rfp.started_at())
+                .into_iter()
+                .chain(self.voting_engines.get(task_id).map(|v| v.started_at()))
+                .min()
+        };
+        let mut expired: Vec<String> = self
+            .rfp_coordinators
+            .keys()
+            .chain(self.voting_engines.keys())
+            .filter(|task_id| !self.pending_plan_approvals.contains_key(*task_id))
+            .filter(|task_id| started_at(task_id).is_some_and(|at| now - at > max_age))
+            .cloned()
+            .collect();
+        expired.sort();
+        expired.dedup();
+
+        let mut failed = Vec::new();
+        for task_id in expired {
+            self.rfp_coordinators.remove(&task_id);
+            self.voting_engines.remove(&task_id);
+            self.pending_plan_reveals.remove(&task_id);
+            self.pending_vote_reveals.remove(&task_id);
+            self.voting_windows.remove(&task_id);
+            self.task_vote_requirements.remove(&task_id);
+            self.task_state_requests.remove(&task_id);
+            self.task_state_replies.remove(&task_id);
+            let Some(task) = self.task_details.get_mut(&task_id).filter(|t| {
+                matches!(
+                    t.status,
+                    TaskStatus::Pending | TaskStatus::ProposalPhase | TaskStatus::VotingPhase
+                )
+            }) else {
+                // The round finished and only its state was left behind.
+                continue;
+            };
+            task.status = TaskStatus::Failed;
+            self.push_task_timeline_event(
+                &task_id,
+                "consensus_expired",
+                format!(
+                    "No plan selected within {}s; consensus state discarded",
+                    self.max_round_age_secs
+                ),
+                None,
+            );
+            self.push_log(
+                LogCategory::Task,
+                format!(
+                    "Task {} failed: its consensus round was abandoned after {}s",
+                    task_id, self.max_round_age_secs
+                ),
+            );
+            let swarm_id = self.swarm_for_task(&task_id);
+            failed.push((task_id, swarm_id));
+        }
+        failed
+    }
+
+    /// Track a task just injected here until it draws a plan.
+    pub fn record_tender(&mut self, task_id: &str, now: chrono::DateTime<chrono::Utc>) {
+        self.task_tenders.entry(task_id.to_string()).or_insert(TaskTender {
+            attempt: 0,
+            tendered_at: now,
+        });
+    }
+
+    /// Whether a task is still waiting for its first plan: open, with no
+    /// voting under way and no plan revealed.
+    fn is_unproposed(&self, task_id: &str) -> bool {
+        self.task_details
+            .get(task_id)
+            .is_some_and(|t| matches!(t.status, TaskStatus::Pending | TaskStatus::ProposalPhase))
+            && !self.voting_engines.contains_key(task_id)
+            && self.rfp_coordinators.get(task_id).is_none_or(|rfp| rfp.reveal_count() == 0)
+    }
+
+    /// Fail a task that drew no plan, dropping its proposal round.
+    fn fail_unproposed_task(&mut self, failure: &TaskTenderFailedParams, actor: Option<String>) {
+        let task_id = failure.task_id.as_str();
+        self.task_tenders.remove(task_id);
+        self.rfp_coordinators.remove(task_id);
+        self.pending_plan_reveals.remove(task_id);
+        self.task_vote_requirements.remove(task_id);
+        if let Some(task) = self.task_details.get_mut(task_id) {
+            task.status = TaskStatus::Failed;
+        }
+        self.push_task_timeline_event(task_id, "tender_failed", failure.reason.clone(), actor);
+        self.push_log(LogCategory::Task, format!("Task {} failed: {}", task_id, failure.reason));
+    }
+
+    /// Apply a tender failure announced by the task's originator. A task
+    /// that drew a plan here, or is no longer open, is left alone.
+    pub fn apply_tender_failure(&mut self, failure: &TaskTenderFailedParams) -> bool {
+        if !self.is_unproposed(&failure.task_id) {
+            return false;
+        }
+        self.fail_unproposed_task(failure, Some(failure.originator.to_string()));
+        true
+    }
+
+    /// Tender again every task injected here that has no revealed plan a
+    /// proposal timeout after its last tender, moving it one tier up when
+    /// `retender_relax_tier` is set, and fail it once it has been tendered
+    /// `max_tender_attempts` times. Tasks that drew a plan or left the
+    /// proposal stage are no longer tracked.
+    pub fn take_due_retenders(&mut self, now: chrono::DateTime<chrono::Utc>) -> Vec<(String, TenderOutcome)> {
+        let timeout = self.proposal_stage_timeout_secs();
+        let mut task_ids: Vec<String> = self.task_tenders.keys().cloned().collect();
+        task_ids.sort();
+        let mut outcomes = Vec::new();
+        for task_id in task_ids {
+            if !self.is_unproposed(&task_id) {
+                self.task_tenders.remove(&task_id);
+                continue;
+            }
+            let tender = self.task_tenders[&task_id].clone();
+            if (now - tender.tendered_at).num_seconds() < timeout {
+                continue;
+            }
+            let tendered = tender.attempt + 1;
+            if tendered >= self.max_tender_attempts {
+                let failure = TaskTenderFailedParams {
+                    task_id: task_id.clone(),
+                    originator: self.agent_id.clone(),
+                    attempts: tendered,
+                    reason: format!("No plan proposed after {} tenders", tendered),
+                    timestamp: now,
+                };
+                self.fail_unproposed_task(&failure, None);
+                let swarm_id = self.swarm_for_task(&task_id);
+                outcomes.push((task_id, TenderOutcome::GaveUp { swarm_id, failure }));
+                continue;
+            }
+            let Some(task) = self.task_details.get_mut(&task_id) else {
+                continue;
+            };
+            let from_level = task.tier_level;
+            if self.retender_relax_tier {
+                task.tier_level = task.tier_level.saturating_sub(1).max(1);
+            }
+            task.status = TaskStatus::Pending;
+            let task = task.clone();
+            self.task_tenders.insert(
+                task_id.clone(),
+                TaskTender {
+                    attempt: tender.attempt + 1,
+                    tendered_at: now,
+                },
+            );
+            self.round_restarts.insert(task_id.clone(), now);
+            self.rfp_coordinators.remove(&task_id);
+            self.pending_plan_reveals.remove(&task_id);
+            self.task_vote_requirements.remove(&task_id);
+            let detail = if task.tier_level == from_level {
+                format!("No plan proposed; tendered again (attempt {})", tender.attempt + 1)
+            } else {
+                format!(
+                    "No plan proposed; tendered again to tier {} (attempt {})",
+                    task.tier_level,
+                    tender.attempt + 1
+                )
+            };
+            self.push_task_timeline_event(&task_id, "retendered", detail.clone(), None);
+            self.push_log(LogCategory::Task, format!("Task {}: {}", task_id, detail));
+            outcomes.push((task_id, TenderOutcome::Retendered(Box::new(task))));
+        }
+        outcomes
+    }
+
+    /// Sample the active membership for the partition detector.
+    pub fn observe_partition(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<crate::partition::PartitionEvent> {
+        let self_id = self.agent_id.to_string();
+        let members = self
+            .active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
+            .into_iter()
+            .filter(|id| *id != self_id)
+            .collect();
+        self.partition_detector.observe(members, now)
+    }
+
+    /// Digest of every known task, most recently decided first, for
+    /// reconciliation with a peer.
+    pub fn task_digests(&self) -> Vec<TaskDigest> {
+        let mut digests: Vec<TaskDigest> = self
+            .task_details
+            .values()
+            .map(|task| TaskDigest {
+                task_id: task.task_id.clone(),
+                status: task.status,
+                assigned_to: task.assigned_to.clone(),
+                finality: self
+                    .task_timelines
+                    .get(&task.task_id)
+                    .and_then(|timeline| timeline.iter().map(|e| e.hlc).max())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        digests.sort_by_key(|d| std::cmp::Reverse(d.finality));
+        digests.truncate(crate::partition::MAX_RECONCILE_DIGESTS);
+        digests
+    }
+
+    /// Compare a peer's task digests with ours and adopt the peer's state
+    /// where its finality stamp is later. The outcome is added to the
+    /// latest partition report; the conflicts found are returned.
+    pub fn reconcile_with_peer(
+        &mut self,
+        peer: &str,
+        remote: &[TaskDigest],
+    ) -> Vec<crate::partition::TaskConflict> {
+        let local = self.task_digests();
+        let (compared, conflicts) = crate::partition::resolve_conflicts(peer, &local, remote);
+        for conflict in &conflicts {
+            if conflict.resolution != crate::partition::Resolution::AdoptedRemote {
+                continue;
+            }
+            let theirs = &conflict.remote;
+            if let Some(task) = self.task_details.get_mut(&theirs.task_id) {
+                task.status = theirs.status;
+                task.assigned_to = theirs.assigned_to.clone();
+            }
+            if matches!(
+                theirs.status,
+                TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Rejected
+            ) {
+                self.task_set.remove(&theirs.task_id);
+            }
+            self.push_task_timeline_event(
+                &theirs.task_id,
+                "partition_reconciled",
+                format!(
+                    "Adopted {:?} (assignee {}) from {} after a partition; was {:?} (assignee {})",
+                    theirs.status,
+                    theirs.assigned_to.as_ref().map_or("none", |a| a.as_str()),
+                    peer,
+                    conflict.local.status,
+                    conflict.local.assigned_to.as_ref().map_or("none", |a| a.as_str()),
+                ),
+                Some(peer.to_string()),
+            );
+            // The event carries the adopted decision's stamp, so this node
+            // now reports the same finality as the peer.
+            if let Some(event) = self
+                .task_timelines
+                .get_mut(&theirs.task_id)
+                .and_then(|timeline| timeline.last_mut())
+            {
+                event.hlc = theirs.finality;
+            }
+        }
+        let adopted = conflicts
+            .iter()
+            .filter(|c| c.resolution == crate::partition::Resolution::AdoptedRemote)
+            .count();
+        self.push_log(
+            LogCategory::Peer,
+            format!(
+                "Reconciled {} shared tasks with {}: {} conflicts, {} resolved in the peer's favour",
+                compared,
+                peer,
+                conflicts.len(),
+                adopted
+            ),
+        );
+        self.partition_detector
+            .record_reconciliation(peer, compared, conflicts.clone());
+        conflicts
+    }
+
+    /// This node's view of its swarm for a merge handshake, with `tasks`
+    /// for the peer to replay.
+    pub fn merge_view(&self, tasks: Vec<Task>) -> SwarmMergeParams {
+        let active = self.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+        SwarmMergeParams {
+            sender: self.agent_id.clone(),
+            swarm_id: self.current_swarm_id.clone(),
+            lineage: self.merge.lineage().clone(),
+            epoch: self.epoch_manager.current_epoch(),
+            tier1_leaders: self.epoch_manager.current_leaders().to_vec(),
+            members: self.merge.members(&active, self.agent_id.as_str()),
+            digests: self.task_digests(),
+            tasks,
+        }
+    }
+
+    /// The most recent tasks, up to `MAX_MERGE_TASKS`, leaving out those
+    /// `known` has a digest for.
+    pub fn merge_tasks(&self, known: &[TaskDigest]) -> Vec<Task> {
+        let known: std::collections::HashSet<&str> = known.iter().map(|d| d.task_id.as_str()).collect();
+        let mut tasks: Vec<Task> = self
+            .task_details
+            .values()
+            .filter(|task| !known.contains(task.task_id.as_str()))
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.created_at));
+        tasks.truncate(crate::merge::MAX_MERGE_TASKS);
+        tasks
+    }
+
+    /// Merge a peer's view of the swarm into ours: replay the tasks we were
+    /// missing, reconcile the ones we share, and adopt the peer's lineage,
+    /// epoch and Tier-1 leaders if its view wins. `None` when the peer
+    /// speaks for another swarm.
+    pub fn merge_with(&mut self, remote: &SwarmMergeParams) -> Option<crate::merge::MergeReport> {
+        if remote.swarm_id != self.current_swarm_id {
+            return None;
+        }
+        let local = self.merge_view(Vec::new());
+        let peer = remote.sender.to_string();
+
+        let mut replayed = 0;
+        for task in &remote.tasks {
+            if self.task_details.contains_key(&task.task_id) {
+                continue;
+            }
+            if !matches!(
+                task.status,
+                TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Rejected
+            ) {
+                self.task_set.add(task.task_id.clone());
+            }
+            self.set_task_swarm(&task.task_id, remote.swarm_id.as_str());
+            self.task_details.insert(task.task_id.clone(), task.clone());
+            self.push_task_timeline_event(
+                &task.task_id,
+                "merge_replayed",
+                format!("Replayed from {} when merging swarm views", peer),
+                Some(peer.clone()),
+            );
+            replayed += 1;
+        }
+        let conflicts = self.reconcile_with_peer(&peer, &remote.digests).len();
+
+        let adopted = remote.lineage != local.lineage && crate::merge::remote_wins(&local, remote);
+        if adopted {
+            self.merge.adopt(remote.lineage.clone());
+            let swarm_size = (local.members.len() + remote.members.len()) as u64;
+            self.epoch_manager
+                .force_epoch(remote.epoch, remote.tier1_leaders.clone(), swarm_size);
+        }
+        // Both views' members make up the swarm from now on.
+        for member in &remote.members {
+            if *member != self.agent_id.as_str() {
+                self.mark_member_seen(member);
+            }
+        }
+        let active = self.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+        self.recompute_hierarchy(&active);
+
+        let report = crate::merge::MergeReport {
+            peer: peer.clone(),
+            remote_lineage: remote.lineage.clone(),
+            adopted,
+            epoch_before: local.epoch,
+            epoch_after: self.epoch_manager.current_epoch(),
+            local_members: local.members.len(),
+            remote_members: remote.members.len(),
+            tasks_replayed: replayed,
+            conflicts,
+            merged_at: chrono::Utc::now(),
+        };
+        self.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT swarm.merge peer={} remote_lineage={} adopted={} epoch={}->{} members={}+{} replayed={} conflicts={}",
+                peer,
+                remote.lineage.bootstrap_id,
+                adopted,
+                report.epoch_before,
+                report.epoch_after,
+                report.local_members,
+                report.remote_members,
+                replayed,
+                conflicts
+            ),
+        );
+        self.merge.record(report.clone());
+        Some(report)
+    }
+
+    /// Tasks with a proposal or voting round still open.
+    pub fn in_flight_rounds(&self) -> Vec<String> {
+        let mut task_ids: Vec<String> = self
+            .rfp_coordinators
+            .keys()
+            .chain(self.voting_engines.keys())
+            .filter(|task_id| !self.pending_plan_approvals.contains_key(*task_id))
+            .filter(|task_id| {
+                self.task_details.get(task_id).is_some_and(|t| {
+                    matches!(
+                        t.status,
+                        TaskStatus::Pending | TaskStatus::ProposalPhase | TaskStatus::VotingPhase
+                    )
+                })
+            })
+            .cloned()
+            .collect();
+        task_ids.sort();
+        task_ids.dedup();
+        task_ids
+    }
+
+    /// Carry in-flight consensus rounds over to a new epoch's layout.
+    /// `eligible` gives each task's tier level and the members eligible for
+    /// it under the new layout. A round whose proposers and voters are all
+    /// still eligible keeps its commits, reveals and ballots; any other
+    /// round restarts from the commit phase.
+    pub fn hand_off_rounds(
+        &mut self,
+        epoch: u64,
+        eligible: &std::collections::HashMap<String, (u32, Vec<String>)>,
+    ) -> Vec<(String, HandoffOutcome)> {
+        let now = chrono::Utc::now();
+        let mut outcomes = Vec::new();
+        for task_id in self.in_flight_rounds() {
+            let Some((tier_level, members)) = eligible.get(&task_id) else {
+                continue;
+            };
+            let mut participants: Vec<String> = self
+                .rfp_coordinators
+                .get(&task_id)
+                .map(|rfp| rfp.commits_for_debug().into_iter().map(|(p, _)| p).collect())
+                .unwrap_or_default();
+            participants.extend(
+                self.ballot_records
+                    .get(&task_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|b| b.voter.to_string()),
+            );
+            // Blind ballots are only recorded once tallied.
+            participants.extend(
+                self.voting_engines
+                    .get(&task_id)
+                    .filter(|v| v.is_blind())
+                    .map(|v| v.voter_ids_for_debug())
+                    .unwrap_or_default(),
+            );
+            participants.sort();
+            participants.dedup();
+            let stranded: Vec<String> = participants
+                .into_iter()
+                .filter(|p| !members.contains(p))
+                .collect();
+
+            let requirement = self.quorum_requirement(&task_id, *tier_level, members);
+            let expected = requirement.expected_proposers;
+            self.task_vote_requirements.insert(task_id.clone(), requirement);
+
+            if stranded.is_empty() {
+                self.push_task_timeline_event(
+                    &task_id,
+                    "epoch_handoff",
                     format!(
-                        "Task {} reassigned due to non-response: {} -> {}",
-                        task_id,
-                        old_assignee
-                            .as_ref()
-                            .map(|a| a.as_str())
-                            .unwrap_or("unassigned"),
-                        new_assignee
+                        "Migrated to epoch {}: {} eligible, quorum {}",
+                        epoch,
+                        members.len(),
+                        expected
                     ),
+                    None,
                 );
+                outcomes.push((task_id, HandoffOutcome::Migrated));
+                continue;
+            }
 
-                let Some(mut reassigned_task) = state.task_details.get(&task_id).cloned() else {
-                    continue;
-                };
-                reassigned_task.assigned_to = Some(AgentId::new(new_assignee.clone()));
+            self.voting_engines.remove(&task_id);
+            self.pending_plan_reveals.remove(&task_id);
+            self.pending_vote_reveals.remove(&task_id);
+            self.voting_windows.remove(&task_id);
+            self.ballot_records.remove(&task_id);
+            self.ballot_receipts.remove(&task_id);
+            self.irv_rounds.remove(&task_id);
+            if self.rfp_coordinators.remove(&task_id).is_some() {
+                if let Some(task) = self.task_details.get(&task_id) {
+                    let mut rfp = RfpCoordinator::new(task_id.clone(), task.epoch, expected);
+                    match rfp.inject_task(task) {
+                        Ok(()) => {
+                            self.rfp_coordinators.insert(task_id.clone(), rfp);
+                        }
+                        Err(e) => {
+                            tracing::warn!(task_id = %task_id, error = %e, "Failed to restart RFP after epoch handoff");
+                        }
+                    }
+                }
+            }
+            if let Some(task) = self.task_details.get_mut(&task_id) {
+                task.status = TaskStatus::ProposalPhase;
+            }
+            self.round_restarts.insert(task_id.clone(), now);
+            self.push_task_timeline_event(
+                &task_id,
+                "epoch_handoff",
+                format!(
+                    "Restarted under epoch {}: {} no longer eligible",
+                    epoch,
+                    stranded.join(", ")
+                ),
+                None,
+            );
+            self.push_log(
+                LogCategory::Epoch,
+                format!(
+                    "Consensus round for task {} restarted at epoch {} ({} changed tier)",
+                    task_id,
+                    epoch,
+                    stranded.join(", ")
+                ),
+            );
+            outcomes.push((task_id, HandoffOutcome::Restarted));
+        }
+        outcomes
+    }
+
+    /// Seconds since a task's current consensus round began: its creation,
+    /// or its latest restart by an epoch handoff.
+    pub fn round_age_secs(&self, task_id: &str, now: chrono::DateTime<chrono::Utc>) -> Option<i64> {
+        let created_at = self.task_details.get(task_id)?.created_at;
+        let started_at = self
+            .round_restarts
+            .get(task_id)
+            .map_or(created_at, |restarted| created_at.max(*restarted));
+        Some(now.signed_duration_since(started_at).num_seconds())
+    }
+
+    fn close_rejected_task(
+        &mut self,
+        task_id: &str,
+        stage: &str,
+        detail: String,
+        actor: Option<String>,
+    ) {
+        if let Some(task) = self.task_details.get_mut(task_id) {
+            task.status = TaskStatus::Rejected;
+        }
+        if let Some(holon) = self.active_holons.get_mut(task_id) {
+            holon.status = HolonStatus::Done;
+        }
+        self.task_set.remove(task_id);
+        self.push_log(LogCategory::Task, format!("Task {}: {}", task_id, detail));
+        self.push_task_timeline_event(task_id, stage, detail, actor);
+    }
+
+    pub fn mark_member_seen(&mut self, agent_id: &str) {
+        self.mark_member_seen_with_name(agent_id, None);
+    }
+
+    pub fn mark_member_seen_with_name(&mut self, agent_id: &str, name: Option<&str>) {
+        if agent_id.trim().is_empty() {
+            return;
+        }
+        self.member_set.add(agent_id.to_string());
+        let joined = self
+            .member_last_seen
+            .insert(agent_id.to_string(), chrono::Utc::now())
+            .is_none();
+        if joined && agent_id != self.agent_id.as_str() {
+            self.epoch_manager.record_membership_change();
+        }
+        if let Some(n) = name.map(str::trim).filter(|n| !n.is_empty()) {
+            self.agent_names.insert(agent_id.to_string(), n.to_string());
+        }
+    }
+
+    pub fn mark_member_polled_tasks(&mut self, agent_id: &str) {
+        self.member_last_task_poll
+            .insert(agent_id.to_string(), chrono::Utc::now());
+    }
+
+    pub fn mark_member_submitted_result(&mut self, agent_id: &str) {
+        self.member_last_result
+            .insert(agent_id.to_string(), chrono::Utc::now());
+    }
+
+    fn activity_mut(&mut self, agent_id: &str) -> &mut AgentActivity {
+        self.agent_activity
+            .entry(agent_id.to_string())
+            .or_default()
+    }
+
+    /// Our candidacy for the running election, registered locally, if we
+    /// stand and meet its requirements.
+    pub fn election_candidacy(
+        &mut self,
+        config: &ElectionConfig,
+        capabilities: &[String],
+        epoch_duration_secs: u64,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<CandidacyParams> {
+        if !config.stand_for_election
+            || self.agent_roles.get(self.agent_id.as_str()) == Some(&LayoutRole::Executor)
+        {
+            return None;
+        }
+        let score = crate::election::own_score(
+            &self.agent_id,
+            config,
+            self.agent_activity.get(self.agent_id.as_str()),
+            (now - self.start_time).num_seconds(),
+            epoch_duration_secs,
+        );
+        let election = self.election.as_mut()?;
+        if !election.is_eligible(&score) {
+            return None;
+        }
+        let params = CandidacyParams {
+            agent_id: self.agent_id.clone(),
+            epoch: election.epoch(),
+            score,
+            location_vector: VivaldiCoordinates::origin(),
+            capabilities: capabilities.to_vec(),
+        };
+        election.register_candidate(&params).ok()?;
+        Some(params)
+    }
+
+    /// Our vote for the running election once `election_vote_due` has
+    /// passed, recorded locally. `None` before then, after voting, or when
+    /// there are no candidates.
+    pub fn due_election_vote(
+        &mut self,
+        config: &ElectionConfig,
+        capabilities: &[String],
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<ElectionVoteParams> {
+        if self.election_vote_due.is_none_or(|due| due > now) {
+            return None;
+        }
+        self.election_vote_due = None;
+        let election = self.election.as_mut()?;
+        if election.has_voted(&self.agent_id) {
+            return None;
+        }
+        let candidate_rankings = crate::election::rank_candidates(
+            config.vote_strategy,
+            election.candidates(),
+            capabilities,
+        );
+        if candidate_rankings.is_empty() {
+            return None;
+        }
+        let vote = ElectionVoteParams {
+            voter: self.agent_id.clone(),
+            epoch: election.epoch(),
+            candidate_rankings,
+        };
+        election.record_vote(vote.clone()).ok()?;
+        Some(vote)
+    }
+
+    /// Whether this connector is a member of `swarm_id`.
+    pub fn is_member_of(&self, swarm_id: &str) -> bool {
+        swarm_id == self.current_swarm_id.as_str() || self.memberships.contains_key(swarm_id)
+    }
+
+    /// Err with a message naming the joined swarms unless this connector
+    /// is a member of `swarm_id`.
+    pub fn require_membership(&self, swarm_id: &str) -> Result<(), String> {
+        if self.is_member_of(swarm_id) {
+            return Ok(());
+        }
+        Err(format!(
+            "Not a member of swarm: {} (joined: {}); join it with swarm.join_swarm first",
+            swarm_id,
+            self.joined_swarm_ids().join(", ")
+        ))
+    }
+
+    /// IDs of every joined swarm, the primary swarm first.
+    pub fn joined_swarm_ids(&self) -> Vec<String> {
+        let primary = self.current_swarm_id.as_str();
+        let mut others: Vec<String> = self
+            .memberships
+            .keys()
+            .filter(|id| id.as_str() != primary)
+            .cloned()
+            .collect();
+        others.sort();
+        std::iter::once(primary.to_string()).chain(others).collect()
+    }
+
+    /// Topics this connector must stay subscribed to: the core topics,
+    /// every joined swarm's member topics, and the flow topics (proposals,
+    /// voting, results, board) of each live task and its open subtasks.
+    /// Imported tasks are not live and need no topics.
+    pub fn required_topics(&self) -> std::collections::BTreeSet<String> {
+        let mut topics: std::collections::BTreeSet<String> =
+            wws_network::TopicManager::core_topics().into_iter().collect();
+        for swarm_id in self.joined_swarm_ids() {
+            if swarm_id == wws_protocol::DEFAULT_SWARM_ID {
+                // The core topics already cover the public swarm's own topics.
+                topics.insert(SwarmTopics::messages_for(&swarm_id));
+            } else {
+                topics.extend(crate::membership::base_topics(&swarm_id));
+            }
+            topics.extend(crate::membership::tier_topics(&swarm_id, self.tier_in(&swarm_id)));
+        }
+
+        let is_open = |task: &Task| {
+            !matches!(
+                task.status,
+                TaskStatus::Completed | TaskStatus::Rejected | TaskStatus::Failed
+            )
+        };
+        for task_id in self.task_set.elements() {
+            let Some(task) = self.task_details.get(&task_id).filter(|t| is_open(t)) else {
+                continue;
+            };
+            let open_subtasks = task
+                .subtasks
+                .iter()
+                .filter(|id| self.task_details.get(id).is_some_and(is_open));
+            for id in std::iter::once(&task_id).chain(open_subtasks) {
+                let swarm_id = self.swarm_for_task(id);
+                topics.insert(SwarmTopics::proposals_for(&swarm_id, id));
+                topics.insert(SwarmTopics::voting_for(&swarm_id, id));
+                topics.insert(SwarmTopics::results_for(&swarm_id, id));
+                topics.insert(SwarmTopics::board_for(&swarm_id, id));
+            }
+        }
+        topics
+    }
+
+    /// Join `swarm_id` alongside the swarms already joined. Returns false
+    /// if this connector was already a member.
+    pub fn join_swarm(&mut self, swarm_id: &str) -> bool {
+        if self.is_member_of(swarm_id) {
+            return false;
+        }
+        self.memberships.insert(
+            swarm_id.to_string(),
+            SwarmMembership::new(SwarmId::new(swarm_id.to_string())),
+        );
+        if let Some(record) = self.known_swarms.get_mut(swarm_id) {
+            record.joined = true;
+        }
+        true
+    }
+
+    /// Leave a swarm joined with [`Self::join_swarm`]. The primary swarm
+    /// cannot be left.
+    pub fn leave_swarm(&mut self, swarm_id: &str) -> Result<SwarmMembership, String> {
+        if swarm_id == self.current_swarm_id.as_str() {
+            return Err(format!("Cannot leave the primary swarm {}", swarm_id));
+        }
+        let membership = self
+            .memberships
+            .remove(swarm_id)
+            .ok_or_else(|| format!("Not a member of swarm {}", swarm_id))?;
+        self.task_swarms.retain(|_, swarm| swarm != swarm_id);
+        if let Some(record) = self.known_swarms.get_mut(swarm_id) {
+            record.joined = false;
+        }
+        Ok(membership)
+    }
+
+    /// This agent's tier in `swarm_id`. The primary swarm's tier is
+    /// `my_tier`.
+    pub fn tier_in(&self, swarm_id: &str) -> Tier {
+        if swarm_id == self.current_swarm_id.as_str() {
+            return self.my_tier;
+        }
+        self.memberships
+            .get(swarm_id)
+            .map(|m| m.tier)
+            .unwrap_or(self.my_tier)
+    }
+
+    /// Record this node's current tier in each joined swarm and return the
+    /// task topics to subscribe to and unsubscribe from for the tiers that
+    /// changed since the last call.
+    pub fn update_tier_subscriptions(&mut self) -> (Vec<String>, Vec<String>) {
+        let joined = self.joined_swarm_ids();
+        self.tier_subscriptions.retain(|swarm_id, _| joined.contains(swarm_id));
+        let (mut subscribe, mut unsubscribe) = (Vec::new(), Vec::new());
+        for swarm_id in joined {
+            let tier = self.tier_in(&swarm_id);
+            let previous = self.tier_subscriptions.insert(swarm_id.clone(), tier);
+            if previous == Some(tier) {
+                continue;
+            }
+            // Until its first update a swarm is subscribed at every level.
+            let old = crate::membership::tier_topics(&swarm_id, previous.unwrap_or(Tier::Executor));
+            let new = crate::membership::tier_topics(&swarm_id, tier);
+            unsubscribe.extend(old.iter().filter(|t| !new.contains(t)).cloned());
+            subscribe.extend(new.iter().filter(|t| !old.contains(t)).cloned());
+            if previous.is_some() {
+                self.push_log(
+                    LogCategory::Swarm,
+                    format!(
+                        "Tier in {} changed to {:?}: listening on task levels {:?}",
+                        swarm_id,
+                        tier,
+                        crate::membership::tier_levels(tier)
+                    ),
+                );
+            }
+        }
+        (subscribe, unsubscribe)
+    }
+
+    /// A task assignment issued by this node and signed with its key.
+    pub fn signed_assignment(
+        &self,
+        task: Task,
+        assignee: AgentId,
+        parent_task_id: String,
+        winning_plan_id: &str,
+    ) -> TaskAssignmentParams {
+        let mut assignment = TaskAssignmentParams {
+            task,
+            assignee,
+            parent_task_id,
+            winning_plan_id: winning_plan_id.to_string(),
+            issuer: self.agent_id.clone(),
+            issuer_key: String::new(),
+            signature: String::new(),
+        };
+        assignment.sign(&self.identity_key);
+        assignment
+    }
+
+    /// A tier assignment issued by this node and signed with its key.
+    pub fn signed_tier_assignment(
+        &self,
+        assigned_agent: AgentId,
+        tier: Tier,
+        parent_id: AgentId,
+        epoch: u64,
+        branch_size: u64,
+    ) -> TierAssignmentParams {
+        let mut assignment = TierAssignmentParams {
+            assigned_agent,
+            tier,
+            parent_id,
+            epoch,
+            branch_size,
+            issuer: self.agent_id.clone(),
+            issuer_key: String::new(),
+            signature: String::new(),
+        };
+        assignment.sign(&self.identity_key);
+        assignment
+    }
+
+    /// Whether a tier assignment may be applied: it must be signed by its
+    /// issuer, and only Tier-1 agents assign tiers. Returns the violation
+    /// otherwise.
+    pub fn authorize_tier_assignment(&self, assignment: &TierAssignmentParams) -> Result<(), String> {
+        Self::check_assignment_issuer(
+            &assignment.issuer,
+            assignment.issuer_verifying_key(),
+            &assignment.signature,
+        )?;
+        let issuer = assignment.issuer.as_str();
+        let tier = if issuer == self.agent_id.as_str() {
+            Some(self.my_tier)
+        } else {
+            self.agent_tiers.get(issuer).copied()
+        };
+        match tier {
+            Some(Tier::Tier1) => Ok(()),
+            Some(tier) => Err(format!("{} is {:?} and may not assign tiers", issuer, tier)),
+            None => Err(format!("{} is not in the hierarchy and may not assign tiers", issuer)),
+        }
+    }
+
+    /// Whether a task assignment may be applied: it must be signed by its
+    /// issuer, and the issuer must be above the assignee, as its parent or
+    /// further up the chain of parents. Returns the violation otherwise.
+    pub fn authorize_task_assignment(&self, assignment: &TaskAssignmentParams) -> Result<(), String> {
+        Self::check_assignment_issuer(
+            &assignment.issuer,
+            assignment.issuer_verifying_key(),
+            &assignment.signature,
+        )?;
+        // A remote node goes to its bridge, from the parent's coordinator.
+        let remote_bridge = assignment.task.remote.as_ref().map(|remote| &remote.bridge);
+        if remote_bridge == Some(&assignment.assignee)
+            && self.coordinates(&assignment.issuer, &assignment.parent_task_id)
+        {
+            return Ok(());
+        }
+        let mut agent = assignment.assignee.as_str();
+        for _ in 0..wws_protocol::MAX_HIERARCHY_DEPTH {
+            let Some(parent) = self.agent_parents.get(agent) else {
+                break;
+            };
+            if parent == assignment.issuer.as_str() {
+                return Ok(());
+            }
+            agent = parent;
+        }
+        Err(format!(
+            "{} is not above assignee {} in the hierarchy",
+            assignment.issuer, assignment.assignee
+        ))
+    }
+
+    fn check_assignment_issuer(
+        issuer: &AgentId,
+        key: Option<ed25519_dalek::VerifyingKey>,
+        signature: &str,
+    ) -> Result<(), String> {
+        if signature.is_empty() {
+            return Err(format!("assignment from {} is unsigned", issuer));
+        }
+        if !key.is_some_and(|key| crate::did::binds_key(issuer.as_str(), &key)) {
+            return Err(format!("assignment not signed by the key of {}", issuer));
+        }
+        Ok(())
+    }
+
+    /// Place `members` in the pyramid hierarchy, applying and logging only
+    /// what changed since the last layout.
+    pub fn recompute_hierarchy(&mut self, members: &[String]) {
+        if members.is_empty() {
+            return;
+        }
+
+        let swarm_size = members.len() as u64;
+        let k = self.branching_factor(swarm_size) as usize;
+        let roles: std::collections::BTreeMap<String, LayoutRole> = members
+            .iter()
+            .filter_map(|id| Some((id.clone(), *self.agent_roles.get(id)?)))
+            .collect();
+        let count = |role| roles.values().filter(|r| **r == role).count() as u64;
+        let distribution = PyramidAllocator::distribute_with_roles(
+            swarm_size,
+            k as u64,
+            count(LayoutRole::Coordinator),
+            count(LayoutRole::Executor),
+        );
+        let tier_sizes: Vec<usize> = distribution.tiers.iter().map(|n| *n as usize).collect();
+        let levels = tier_sizes.len().max(1);
+
+        // Rendezvous placement keeps most agents where they were, so only
+        // the differences are applied and reported.
+        let next = wws_hierarchy::rendezvous::assign_with_roles(members, &tier_sizes, &roles);
+        let previous = HierarchyAssignment {
+            tiers: self.agent_tiers.iter().map(|(id, t)| (id.clone(), *t)).collect(),
+            parents: self.agent_parents.clone().into_iter().collect(),
+        };
+        let changes = next.diff(&previous);
+        for change in &changes {
+            tracing::debug!(change = %change, "Hierarchy change");
+            match change {
+                HierarchyChange::Joined { agent_id, tier, parent } => {
+                    self.agent_tiers.insert(agent_id.clone(), *tier);
+                    if let Some(parent) = parent {
+                        self.agent_parents.insert(agent_id.clone(), parent.clone());
+                    }
+                }
+                HierarchyChange::Left { agent_id } => {
+                    self.agent_tiers.remove(agent_id);
+                    self.agent_parents.remove(agent_id);
+                }
+                HierarchyChange::TierChanged { agent_id, to, .. } => {
+                    self.agent_tiers.insert(agent_id.clone(), *to);
+                }
+                HierarchyChange::ParentChanged { agent_id, to, .. } => match to {
+                    Some(parent) => {
+                        self.agent_parents.insert(agent_id.clone(), parent.clone());
+                    }
+                    None => {
+                        self.agent_parents.remove(agent_id);
+                    }
+                },
+            }
+        }
+        if !changes.is_empty() {
+            self.subordinates.clear();
+            for (child, parent) in &next.parents {
+                self.subordinates.entry(parent.clone()).or_default().push(child.clone());
+            }
+            let summary = if changes.len() <= 3 {
+                changes.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("; ")
+            } else {
+                let count = |f: fn(&HierarchyChange) -> bool| changes.iter().filter(|c| f(c)).count();
+                format!(
+                    "{} joined, {} left, {} changed tier, {} changed parent",
+                    count(|c| matches!(c, HierarchyChange::Joined { .. })),
+                    count(|c| matches!(c, HierarchyChange::Left { .. })),
+                    count(|c| matches!(c, HierarchyChange::TierChanged { .. })),
+                    count(|c| matches!(c, HierarchyChange::ParentChanged { .. })),
+                )
+            };
+            self.push_log(LogCategory::Swarm, format!("Hierarchy changed: {}", summary));
+        }
+
+        self.network_stats.hierarchy_depth = levels as u32;
+        self.network_stats.total_agents = swarm_size;
+        self.current_layout = PyramidAllocator::new(PyramidConfig {
+            branching_factor: k as u32,
+            max_depth: wws_protocol::MAX_HIERARCHY_DEPTH,
+        })
+        .compute_layout(swarm_size)
+        .ok();
+
+        let my_id = self.agent_id.as_str().to_string();
+        if let Some(my_tier) = self.agent_tiers.get(&my_id).copied() {
+            self.my_tier = my_tier;
+            self.network_stats.my_tier = my_tier;
+            self.parent_id = self.agent_parents.get(&my_id).cloned().map(AgentId::new);
+            self.network_stats.parent_id = self.parent_id.clone();
+            self.network_stats.subordinate_count = self
+                .subordinates
+                .get(&my_id)
+                .map(|s| s.len() as u32)
+                .unwrap_or(0);
+        }
+    }
+
+    /// Record that `task_id` belongs to `swarm_id`.
+    pub fn set_task_swarm(&mut self, task_id: &str, swarm_id: &str) {
+        if let Some(membership) = self.memberships.get_mut(swarm_id) {
+            membership.task_ids.insert(task_id.to_string());
+        }
+        if swarm_id != self.current_swarm_id.as_str() {
+            self.task_swarms.insert(task_id.to_string(), swarm_id.to_string());
+        }
+    }
+
+    /// The swarm whose topics carry `task_id`'s traffic: the swarm it was
+    /// tagged with, else that of its nearest tagged ancestor, else the
+    /// primary swarm.
+    pub fn swarm_for_task(&self, task_id: &str) -> String {
+        let mut current = task_id;
+        for _ in 0..=wws_protocol::MAX_HIERARCHY_DEPTH {
+            if let Some(swarm_id) = self.task_swarms.get(current) {
+                return swarm_id.clone();
+            }
+            match self
+                .task_details
+                .get(current)
+                .and_then(|t| t.parent_task_id.as_deref())
+            {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+        self.current_swarm_id.as_str().to_string()
+    }
+
+    /// Open root tasks of the primary swarm that this connector injected.
+    fn open_tasks_injected_here(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .task_details
+            .values()
+            .filter(|task| task.parent_task_id.is_none() && crate::migration::is_open(&task.status))
+            .filter(|task| self.swarm_for_task(&task.task_id) == self.current_swarm_id.as_str())
+            .filter(|task| {
+                self.task_timelines.get(&task.task_id).is_some_and(|events| {
+                    events.iter().any(|e| {
+                        e.stage == "injected"
+                            && e.actor
+                                .as_deref()
+                                .is_some_and(|a| self.is_local_agent(&AgentId::new(a.to_string())))
+                    })
+                })
+            })
+            .collect();
+        tasks.sort_by_key(|t| t.created_at);
+        tasks
+    }
+
+    /// What migrating the primary swarm into `target_swarm_id` would move.
+    pub fn migration_report(&self, target_swarm_id: &str) -> Result<MigrationReport, String> {
+        if target_swarm_id == self.current_swarm_id.as_str() {
+            return Err("The target swarm is this connector's primary swarm".to_string());
+        }
+        let target = self
+            .known_swarms
+            .get(target_swarm_id)
+            .ok_or_else(|| format!("Unknown swarm: {}", target_swarm_id))?;
+        Ok(MigrationReport {
+            source_swarm_id: self.current_swarm_id.as_str().to_string(),
+            target_swarm_id: target_swarm_id.to_string(),
+            authorized: self.is_params_authority(),
+            target_public: target.is_public,
+            members: self.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)),
+            transferred_tasks: self
+                .open_tasks_injected_here()
+                .into_iter()
+                .map(|t| t.task_id.clone())
+                .collect(),
+            held_tasks: self.held_tasks.len(),
+        })
+    }
+
+    /// Make `target` the primary swarm and leave the current one. Open root
+    /// tasks injected here are returned, reset to `Pending` and marked with
+    /// a `stage` timeline event, for re-injection into the target.
+    fn move_primary_swarm(&mut self, target: &SwarmId, is_public: bool, stage: &str) -> Vec<Task> {
+        let source = self.current_swarm_id.as_str().to_string();
+        let target_id = target.as_str().to_string();
+        let tasks: Vec<Task> = self
+            .open_tasks_injected_here()
+            .into_iter()
+            .map(|task| Task {
+                status: TaskStatus::Pending,
+                assigned_to: None,
+                subtasks: Vec::new(),
+                ..task.clone()
+            })
+            .collect();
+
+        self.known_swarms
+            .entry(target_id.clone())
+            .or_insert_with(|| SwarmRecord {
+                swarm_id: target.clone(),
+                name: target_id.clone(),
+                is_public,
+                agent_count: 0,
+                joined: false,
+                last_seen: chrono::Utc::now(),
+            });
+        self.join_swarm(&target_id);
+        self.memberships.remove(&source);
+        if let Some(record) = self.known_swarms.get_mut(&source) {
+            record.joined = false;
+        }
+        self.current_swarm_id = target.clone();
+        // Tasks of the new primary swarm need no tag.
+        self.task_swarms.retain(|_, swarm| *swarm != target_id);
+        // The target swarm's own parameter record is adopted when it arrives.
+        self.swarm_params = None;
+        self.params_proposals.clear();
+        if let Some(membership) = self.memberships.get(&target_id) {
+            self.my_tier = membership.tier;
+            self.parent_id = membership.parent_id.clone();
+            self.network_stats.my_tier = membership.tier;
+        }
+        for task in &tasks {
+            self.push_task_timeline_event(
+                &task.task_id,
+                stage,
+                format!("Moved from swarm {} to {}", source, target_id),
+                None,
+            );
+        }
+        tasks
+    }
+
+    /// Make the migration's target swarm this connector's primary swarm if
+    /// the migration comes from the primary swarm's parameter authority.
+    /// The source swarm is left; its open tasks injected here are returned,
+    /// reset to `Pending`, for re-injection into the target.
+    pub fn apply_migration(&mut self, migration: SwarmMigrateParams) -> Result<MigrationOutcome, String> {
+        let source = migration.source_swarm_id.as_str().to_string();
+        let target = migration.target_swarm_id.as_str().to_string();
+        if source != self.current_swarm_id.as_str() {
+            return Err(format!("Migration {} is not for our primary swarm", migration.migration_id));
+        }
+        if target == source {
+            return Err("Migration source and target are the same swarm".to_string());
+        }
+        let authority_signed = self
+            .swarm_params
+            .as_ref()
+            .is_some_and(|p| p.creator_key == migration.creator_key);
+        if !authority_signed || !migration.verify() {
+            return Err(format!(
+                "Migration {} is not signed by the parameter authority of {}",
+                migration.migration_id, source
+            ));
+        }
+
+        let tasks = self.move_primary_swarm(
+            &migration.target_swarm_id,
+            migration.target_token.is_none(),
+            "migrated",
+        );
+        self.push_log(
+            LogCategory::Swarm,
+            format!(
+                "Migrating from swarm {} to {} ({} open tasks)",
+                source,
+                target,
+                tasks.len()
+            ),
+        );
+        Ok(MigrationOutcome { migration, tasks })
+    }
+
+    /// Active Tier-1 leaders of the current epoch, sorted by DID.
+    fn active_tier1_leaders(&self) -> Vec<String> {
+        let active = self.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+        let mut leaders: Vec<String> = self
+            .epoch_manager
+            .current_info()
+            .tier1_leaders
+            .iter()
+            .map(|leader| leader.to_string())
+            .filter(|leader| active.contains(leader))
+            .collect();
+        leaders.sort();
+        leaders
+    }
+
+    /// The Tier-1 leader expected to split the primary swarm: the first
+    /// active one by DID.
+    pub fn shard_issuer(&self) -> Option<String> {
+        self.active_tier1_leaders().into_iter().next()
+    }
+
+    /// The Tier-1 leader at the top of this node's branch, found through
+    /// its parent chain.
+    fn branch_leader(&self) -> Option<String> {
+        let mut current = self.parent_id.as_ref()?.to_string();
+        for _ in 0..=self.agent_parents.len() {
+            if self.is_tier1_leader(&current) {
+                return Some(current);
+            }
+            current = self.agent_parents.get(&current)?.clone();
+        }
+        None
+    }
+
+    /// A signed split of the primary swarm, when it has more active members
+    /// than the shard threshold, this node is its shard issuer and the
+    /// swarm was not split this epoch. The issuer applies it like any
+    /// member with [`Self::apply_shard`].
+    pub fn plan_shard(&mut self) -> Option<SwarmShardParams> {
+        let active = self.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+        if !self.shards.is_oversized(active) || self.shard_issuer()? != self.agent_id.as_str() {
+            return None;
+        }
+        let parent = self.current_swarm_id.as_str().to_string();
+        let epoch = self.epoch_manager.current_epoch();
+        let shard_id = crate::shard::shard_id(&parent, epoch);
+        if self.shards.has_seen(&shard_id) {
+            return None;
+        }
+        let leaders = self.active_tier1_leaders();
+        let strategy = self.shards.strategy();
+        let mut count = crate::shard::child_count(active, self.shards.threshold());
+        let mut branches = std::collections::BTreeMap::new();
+        if strategy == ShardStrategy::Geo {
+            count = count.min(leaders.len().max(2));
+            for (index, leader) in leaders.iter().enumerate() {
+                branches.insert(leader.clone(), index % count);
+            }
+        }
+        let is_public = self
+            .known_swarms
+            .get(&parent)
+            .map(|record| record.is_public)
+            .unwrap_or(self.swarm_token.is_none());
+        let mut shard = SwarmShardParams {
+            shard_id,
+            parent_swarm_id: self.current_swarm_id.clone(),
+            epoch,
+            strategy,
+            children: (0..count)
+                .map(|index| SwarmId::new(crate::shard::child_swarm_id(&parent, epoch, index)))
+                .collect(),
+            gateways: leaders.into_iter().map(AgentId::new).collect(),
+            branches,
+            child_token: if is_public { None } else { self.swarm_token.clone() },
+            issuer: self.agent_id.clone(),
+            issuer_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        shard.sign(&self.identity_key);
+        self.push_log(
+            LogCategory::Swarm,
+            format!(
+                "Splitting swarm {} of {} active members into {} children",
+                parent, active, count
+            ),
+        );
+        Some(shard)
+    }
+
+    /// Apply a split of the primary swarm issued by one of its Tier-1
+    /// leaders. A gateway joins every child; any other member moves into
+    /// its child. `None` when the split was already applied.
+    pub fn apply_shard(&mut self, shard: SwarmShardParams) -> Result<Option<ShardOutcome>, String> {
+        let parent = shard.parent_swarm_id.as_str().to_string();
+        if self.shards.has_seen(&shard.shard_id) {
+            return Ok(None);
+        }
+        if parent != self.current_swarm_id.as_str() {
+            return Err(format!("Split {} is not for our primary swarm", shard.shard_id));
+        }
+        if shard.children.is_empty() || shard.children.iter().any(|child| child.as_str() == parent) {
+            return Err(format!("Split {} names no valid children", shard.shard_id));
+        }
+        let bound = shard
+            .issuer_verifying_key()
+            .is_some_and(|key| crate::did::binds_key(shard.issuer.as_str(), &key));
+        if !bound || !shard.verify() {
+            return Err(format!("Split {} not signed by the key of {}", shard.shard_id, shard.issuer));
+        }
+        if !self.is_tier1_leader(shard.issuer.as_str()) {
+            return Err(format!("{} is not a Tier-1 leader of {}", shard.issuer, parent));
+        }
+        self.shards.mark_seen(&shard.shard_id);
+
+        let now = chrono::Utc::now();
+        let children: Vec<String> = shard.children.iter().map(|child| child.to_string()).collect();
+        let is_public = shard.child_token.is_none();
+        for child in &shard.children {
+            self.known_swarms
+                .entry(child.to_string())
+                .or_insert_with(|| SwarmRecord {
+                    swarm_id: child.clone(),
+                    name: child.to_string(),
+                    is_public,
+                    agent_count: 0,
+                    joined: false,
+                    last_seen: now,
+                });
+        }
+        let link = |swarm_id: &str, relation| crate::shard::FederationLink {
+            swarm_id: swarm_id.to_string(),
+            relation,
+            shard_id: shard.shard_id.clone(),
+            gateways: shard.gateways.clone(),
+            established_at: now,
+        };
+
+        let (joined, moved) = if shard.gateways.contains(&self.agent_id) {
+            let joined: Vec<String> = children
+                .iter()
+                .filter(|child| self.join_swarm(child))
+                .cloned()
+                .collect();
+            for child in &children {
+                self.shards.add_link(link(child, FederationRelation::Child));
+            }
+            (joined, None)
+        } else {
+            let branch_leader = self.branch_leader();
+            let target = shard
+                .child_for(self.agent_id.as_str(), branch_leader.as_deref())
+                .cloned()
+                .ok_or_else(|| format!("Split {} names no children", shard.shard_id))?;
+            let tasks = self.move_primary_swarm(&target, is_public, "sharded");
+            self.shards.add_link(link(&parent, FederationRelation::Parent));
+            for child in children.iter().filter(|child| child.as_str() != target.as_str()) {
+                self.shards.add_link(link(child, FederationRelation::Sibling));
+            }
+            let moved = crate::migration::SwarmMove {
+                source: shard.parent_swarm_id.clone(),
+                target,
+                token: shard.child_token.clone(),
+                tasks,
+            };
+            (Vec::new(), Some(moved))
+        };
+
+        self.shards.record(crate::shard::ShardReport {
+            shard_id: shard.shard_id.clone(),
+            parent_swarm_id: parent.clone(),
+            strategy: shard.strategy,
+            children: children.clone(),
+            issuer: shard.issuer.to_string(),
+            moved_to: moved.as_ref().map(|moved| moved.target.to_string()),
+            tasks_moved: moved.as_ref().map_or(0, |moved| moved.tasks.len()),
+            applied_at: now,
+        });
+        self.push_log(
+            LogCategory::Swarm,
+            match &moved {
+                Some(moved) => format!(
+                    "Swarm {} split into {} children by {}: moving to {} ({} open tasks)",
+                    parent,
+                    children.len(),
+                    shard.issuer,
+                    moved.target,
+                    moved.tasks.len()
+                ),
+                None => format!(
+                    "Swarm {} split into {} children by {}: staying as gateway",
+                    parent,
+                    children.len(),
+                    shard.issuer
+                ),
+            },
+        );
+        Ok(Some(ShardOutcome { shard, joined, moved }))
+    }
+
+    /// Record a wws:// name claim and refresh who holds the name. Returns
+    /// whether the claim changed the directory.
+    pub fn record_name_claim(&mut self, claim: NameClaimParams) -> Result<bool, String> {
+        let name = claim.name.to_lowercase();
+        let changed = self.name_directory.record_claim(claim, crate::names::unix_now())?;
+        if changed {
+            self.refresh_name_holder(&name);
+        }
+        Ok(changed)
+    }
+
+    /// Record a guardian's vote on a disputed name and refresh who holds it.
+    pub fn record_name_vote(&mut self, vote: NameDisputeVoteParams) -> Result<bool, String> {
+        let name = vote.name.to_lowercase();
+        let changed = self.name_directory.record_vote(vote)?;
+        if changed {
+            self.refresh_name_holder(&name);
+        }
+        Ok(changed)
+    }
+
+    fn refresh_name_holder(&mut self, name: &str) {
+        let now = crate::names::unix_now();
+        let holder = self
+            .name_directory
+            .holder(name, now)
+            .map(wws_network::name_registry::NameRecord::from_claim);
+        let previous = match holder {
+            Some(record) => self.registered_names.insert(name.to_string(), record),
+            None => self.registered_names.remove(name),
+        };
+        let current = self.registered_names.get(name).map(|r| r.did.clone());
+        if let Some(did) = current.filter(|did| previous.map(|r| r.did).as_ref() != Some(did)) {
+            self.push_log(LogCategory::Swarm, format!("wws://{} is held by {}", name, did));
+        }
+        if self.name_directory.is_disputed(name, now) {
+            let claimants = self.name_directory.contenders(name, now).len();
+            self.push_log(
+                LogCategory::Swarm,
+                format!("wws://{} is disputed by {} claimants", name, claimants),
+            );
+        }
+    }
+
+    /// How an agent is shown to the operator: the wws:// name it holds, else
+    /// the name it announces, else its DID. A label another DID also holds,
+    /// announces or claims gets a DID suffix.
+    pub fn display_name(&self, did: &str) -> String {
+        let held = self
+            .registered_names
+            .values()
+            .find(|r| r.did == did && !r.is_expired())
+            .map(|r| r.name.clone());
+        let Some(label) = held.or_else(|| self.agent_names.get(did).cloned()) else {
+            return did.to_string();
+        };
+        let lower = label.to_lowercase();
+        let shared = self
+            .name_directory
+            .contenders(&lower, crate::names::unix_now())
+            .iter()
+            .any(|claim| claim.did != did)
+            || self
+                .agent_names
+                .iter()
+                .any(|(other, name)| other != did && name.to_lowercase() == lower);
+        if shared {
+            crate::names::disambiguate(&label, did)
+        } else {
+            label
+        }
+    }
+
+    /// Count a result against its task's deadline for the submitting
+    /// agent's SLA metrics. Tasks without a deadline are not counted.
+    pub fn record_result_timeliness(
+        &mut self,
+        task_id: &str,
+        agent_id: &str,
+        submitted_at: chrono::DateTime<chrono::Utc>,
+    ) {
+        let Some(deadline) = self.task_details.get(task_id).and_then(|t| t.deadline) else {
+            return;
+        };
+        let activity = self.activity_mut(agent_id);
+        let outcome = if submitted_at <= deadline {
+            activity.results_on_time_count += 1;
+            AttestationOutcome::OnTime
+        } else {
+            activity.results_late_count += 1;
+            activity.total_lateness_secs +=
+                (submitted_at - deadline).num_milliseconds() as f64 / 1000.0;
+            AttestationOutcome::Late
+        };
+        crate::reputation::attest(self, agent_id, task_id, outcome);
+    }
+
+    /// Deadline for a new subtask of `parent_task_id`: a share of the
+    /// parent's remaining time, or the fixed execution timeout when the
+    /// parent has no deadline.
+    pub fn subtask_deadline(
+        &self,
+        parent_task_id: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::DateTime<chrono::Utc> {
+        match self.task_details.get(parent_task_id).and_then(|t| t.deadline) {
+            Some(parent_deadline) => {
+                let remaining_ms = (parent_deadline - now).num_milliseconds().max(0) as f64;
+                let share_ms = (remaining_ms * SUBTASK_DEADLINE_SHARE) as i64;
+                now + chrono::Duration::milliseconds(share_ms)
+                    .max(chrono::Duration::seconds(MIN_SUBTASK_DEADLINE_SECS))
+            }
+            None => now + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS),
+        }
+    }
+
+    /// Mark in-progress tasks whose deadline has passed as `Overdue` and
+    /// return their IDs.
+    pub fn mark_overdue_tasks(&mut self, now: chrono::DateTime<chrono::Utc>) -> Vec<String> {
+        let overdue: Vec<String> = self
+            .task_details
+            .values()
+            .filter(|t| t.status == TaskStatus::InProgress)
+            .filter(|t| t.deadline.is_some_and(|deadline| deadline <= now))
+            .map(|t| t.task_id.clone())
+            .collect();
+        for task_id in &overdue {
+            let Some(task) = self.task_details.get_mut(task_id) else {
+                continue;
+            };
+            task.status = TaskStatus::Overdue;
+            let assignee = task.assigned_to.as_ref().map(|a| a.to_string());
+            self.push_task_timeline_event(
+                task_id,
+                "overdue",
+                format!(
+                    "Deadline passed without a result from {}",
+                    assignee.as_deref().unwrap_or("unassigned")
+                ),
+                assignee.clone(),
+            );
+        }
+        overdue
+    }
+
+    /// Record a progress report from a task's assignee and add it to the
+    /// task's timeline. Reports from anyone else, for tasks no longer being
+    /// worked on, or older than the latest one are refused. The report's
+    /// time is capped at `now`.
+    pub fn record_task_progress(
+        &mut self,
+        mut progress: TaskProgressParams,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), String> {
+        let task = self
+            .task_details
+            .get(&progress.task_id)
+            .ok_or_else(|| format!("Task not found: {}", progress.task_id))?;
+        if task.assigned_to.as_ref() != Some(&progress.agent_id) {
+            return Err(format!(
+                "Task {} is not assigned to {}",
+                progress.task_id, progress.agent_id
+            ));
+        }
+        if !matches!(task.status, TaskStatus::InProgress | TaskStatus::Overdue) {
+            return Err(format!(
+                "Task {} is not in progress ({:?})",
+                progress.task_id, task.status
+            ));
+        }
+        if progress.percent > 100 {
+            return Err(format!("Progress of {}% is above 100", progress.percent));
+        }
+        progress.timestamp = progress.timestamp.min(now);
+        if self
+            .task_progress
+            .get(&progress.task_id)
+            .is_some_and(|latest| latest.timestamp > progress.timestamp)
+        {
+            return Err(format!("Stale progress report for {}", progress.task_id));
+        }
+
+        let mut message = format!("{}%", progress.percent);
+        if !progress.note.trim().is_empty() {
+            message.push_str(&format!(": {}", progress.note.trim()));
+        }
+        if let Some(cid) = &progress.intermediate_cid {
+            message.push_str(&format!(" (intermediate {})", cid));
+        }
+        let agent = progress.agent_id.to_string();
+        self.mark_member_seen(&agent);
+        self.push_task_timeline_event(&progress.task_id, "progress", message, Some(agent));
+        self.task_progress.insert(progress.task_id.clone(), progress);
+        Ok(())
+    }
+
+    /// Whether the assignee of `task_id` reported progress within the
+    /// execution timeout, so the task must not be taken away from it.
+    pub fn progress_is_recent(&self, task_id: &str, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let assignee = self.task_details.get(task_id).and_then(|t| t.assigned_to.as_ref());
+        self.task_progress.get(task_id).is_some_and(|p| {
+            Some(&p.agent_id) == assignee
+                && now - p.timestamp
+                    < chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS)
+        })
+    }
+
+    pub fn bump_tasks_assigned(&mut self, agent_id: &str) {
+        self.activity_mut(agent_id).tasks_assigned_count += 1;
+    }
+
+    pub fn bump_tasks_processed(&mut self, agent_id: &str) {
+        self.activity_mut(agent_id).tasks_processed_count += 1;
+    }
+
+    pub fn bump_plans_proposed(&mut self, agent_id: &str) {
+        self.activity_mut(agent_id).plans_proposed_count += 1;
+    }
+
+    pub fn bump_plans_revealed(&mut self, agent_id: &str) {
+        self.activity_mut(agent_id).plans_revealed_count += 1;
+    }
+
+    pub fn bump_votes_cast(&mut self, agent_id: &str) {
+        self.activity_mut(agent_id).votes_cast_count += 1;
+    }
+
+    pub fn active_member_ids(&self, max_staleness: Duration) -> Vec<String> {
+        let now = chrono::Utc::now();
+        let mut ids: Vec<String> = self
+            .member_last_seen
+            .iter()
+            .filter_map(|(agent_id, seen)| {
+                now.signed_duration_since(*seen)
+                    .to_std()
+                    .ok()
+                    .filter(|age| *age <= max_staleness)
+                    .map(|_| agent_id.clone())
+            })
+            .collect();
+
+        let self_id = self.agent_id.to_string();
+        if !ids.iter().any(|id| id == &self_id) {
+            ids.push(self_id);
+        }
+
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    pub fn active_member_count(&self, max_staleness: Duration) -> usize {
+        self.active_member_ids(max_staleness).len()
+    }
+
+    pub fn prune_stale_members(&mut self, max_staleness: Duration) {
+        let now = chrono::Utc::now();
+        let stale_ids: Vec<String> = self
+            .member_last_seen
+            .iter()
+            .filter_map(|(agent_id, seen)| {
+                let age = now.signed_duration_since(*seen).to_std().ok()?;
+                if age > max_staleness {
+                    Some(agent_id.clone())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        for stale in stale_ids {
+            if stale != self.agent_id.to_string() {
+                self.epoch_manager.record_membership_change();
+                self.member_set.remove(&stale);
+                self.member_last_seen.remove(&stale);
+                self.member_last_task_poll.remove(&stale);
+                self.member_last_result.remove(&stale);
+                self.agent_activity.remove(&stale);
+                self.agent_tiers.remove(&stale);
+                self.agent_parents.remove(&stale);
+                self.agent_loads.remove(&stale);
+                self.agent_roles.remove(&stale);
+            }
+        }
+    }
+
+    /// Id of the topic key this node holds, when the swarm's topics are
+    /// encrypted and a key was adopted.
+    pub fn topic_key_id(&self) -> Option<String> {
+        let keyring = self.topic_keys.as_ref()?.read().ok()?;
+        keyring.current().map(|current| current.key.id_hex())
+    }
+
+    /// Active members an epoch seed can be sealed to: connectors, whose
+    /// DIDs carry their identity key, and this node. Local agents read the
+    /// swarm's topics through their connector.
+    fn rekey_recipients(&self) -> Vec<(String, ed25519_dalek::VerifyingKey)> {
+        let self_id = self.agent_id.to_string();
+        self.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
+            .into_iter()
+            .filter_map(|member| {
+                let key = if member == self_id {
+                    self.identity_key.verifying_key()
+                } else {
+                    crate::did::key_of(&member)?
+                };
+                Some((member, key))
+            })
+            .collect()
+    }
+
+    /// The member expected to draw and hand out the swarm's epoch seed:
+    /// the first active Tier-1 leader of the current epoch, or without one
+    /// the first active connector.
+    pub fn rekey_issuer(&self) -> Option<String> {
+        let recipients: Vec<String> = self
+            .rekey_recipients()
+            .into_iter()
+            .map(|(member, _)| member)
+            .collect();
+        let mut leaders: Vec<&str> = self
+            .epoch_manager
+            .current_info()
+            .tier1_leaders
+            .iter()
+            .map(|leader| leader.as_str())
+            .filter(|leader| recipients.iter().any(|member| member == leader))
+            .collect();
+        leaders.sort();
+        leaders
+            .first()
+            .map(|leader| leader.to_string())
+            .or_else(|| recipients.first().cloned())
+    }
+
+    /// Whether `issuer` may replace a topic key this node holds: a Tier-1
+    /// leader of the current or just elected epoch, or the expected issuer.
+    fn may_rekey(&self, issuer: &str) -> bool {
+        self.is_tier1_leader(issuer) || self.rekey_issuer().as_deref() == Some(issuer)
+    }
+
+    /// Whether `agent` is a Tier-1 leader of the current or just elected
+    /// epoch.
+    fn is_tier1_leader(&self, agent: &str) -> bool {
+        let elected = self
+            .election
+            .as_ref()
+            .and_then(|election| election.result())
+            .map(|result| result.leaders.as_slice())
+            .unwrap_or_default();
+        self.epoch_manager
+            .current_info()
+            .tier1_leaders
+            .iter()
+            .chain(elected)
+            .any(|leader| leader.as_str() == agent)
+    }
+
+    fn signed_rekey(
+        &self,
+        swarm_id: &str,
+        epoch: u64,
+        key_id: String,
+        seeds: Vec<SealedEpochSeed>,
+    ) -> EpochRekeyParams {
+        let mut rekey = EpochRekeyParams {
+            swarm_id: SwarmId::new(swarm_id.to_string()),
+            epoch,
+            issuer: self.agent_id.clone(),
+            issuer_key: String::new(),
+            key_id,
+            seeds,
+            timestamp: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        rekey.sign(&self.identity_key);
+        rekey
+    }
+
+    /// Draw a seed for the current epoch, seal it to every active member
+    /// and adopt it. `None` when the swarm's topics are not encrypted or a
+    /// key for a later epoch is already held.
+    pub fn issue_rekey(&mut self) -> Option<EpochRekeyParams> {
+        let keyring = self.topic_keys.clone()?;
+        let epoch = self.epoch_manager.current_epoch();
+        let seed = EpochSeed::generate();
+        let seeds: Vec<SealedEpochSeed> = self
+            .rekey_recipients()
+            .iter()
+            .filter_map(|(member, key)| seed.seal_for(member, key).ok())
+            .collect();
+        let mut keyring = keyring.write().ok()?;
+        let key_id = keyring.key_for(epoch, &seed).id_hex();
+        if !keyring.adopt(epoch, self.agent_id.as_str(), seed) {
+            return None;
+        }
+        let swarm_id = keyring.swarm_id().to_string();
+        drop(keyring);
+        self.push_log(
+            LogCategory::Epoch,
+            format!(
+                "Rekeyed swarm topics for epoch {}: key {} sealed to {} members",
+                epoch,
+                key_id,
+                seeds.len()
+            ),
+        );
+        Some(self.signed_rekey(&swarm_id, epoch, key_id, seeds))
+    }
+
+    /// Seal the current seed to `member` when it announced another key and
+    /// this node is the expected issuer, so members that joined after the
+    /// rekey or missed it catch up.
+    pub fn reseal_topic_key(&self, member: &str, announced: Option<&str>) -> Option<EpochRekeyParams> {
+        let keyring = self.topic_keys.as_ref()?.read().ok()?;
+        let current = keyring.current()?;
+        let key_id = current.key.id_hex();
+        if announced == Some(key_id.as_str())
+            || member == self.agent_id.as_str()
+            || self.rekey_issuer().as_deref() != Some(self.agent_id.as_str())
+        {
+            return None;
+        }
+        let sealed = current.seed.seal_for(member, &crate::did::key_of(member)?).ok()?;
+        Some(self.signed_rekey(keyring.swarm_id(), current.epoch, key_id, vec![sealed]))
+    }
+
+    /// Adopt the seed sealed to this node in `rekey`. Returns the epoch of
+    /// the adopted key, or `None` when the rekey is for another swarm, not
+    /// for this node, or no newer than the held key. Once a key is held,
+    /// only [`Self::may_rekey`] issuers can replace it, so a former member
+    /// that kept the token cannot hand out a seed it knows.
+    pub fn accept_rekey(&mut self, rekey: &EpochRekeyParams) -> Result<Option<u64>, String> {
+        let Some(keyring) = self.topic_keys.clone() else {
+            return Ok(None);
+        };
+        let bound = rekey
+            .issuer_verifying_key()
+            .is_some_and(|key| crate::did::binds_key(rekey.issuer.as_str(), &key));
+        if !bound {
+            return Err(format!("rekey not signed by the key of {}", rekey.issuer));
+        }
+        let held = {
+            let keyring = keyring.read().map_err(|_| "topic keyring poisoned")?;
+            if keyring.swarm_id() != rekey.swarm_id.as_str() {
+                return Ok(None);
+            }
+            keyring.current().map(|current| current.key.id_hex())
+        };
+        if held.as_deref() == Some(rekey.key_id.as_str()) {
+            return Ok(None);
+        }
+        let Some(sealed) = rekey.seeds.iter().find(|s| s.member == self.agent_id.as_str()) else {
+            return Ok(None);
+        };
+        if held.is_some() && !self.may_rekey(rekey.issuer.as_str()) {
+            return Err(format!("{} may not rekey the swarm", rekey.issuer));
+        }
+        let seed = EpochSeed::open(sealed, &self.identity_key).map_err(|e| e.to_string())?;
+        let mut keyring = keyring.write().map_err(|_| "topic keyring poisoned")?;
+        if keyring.key_for(rekey.epoch, &seed).id_hex() != rekey.key_id {
+            return Err("seed does not derive the announced key".into());
+        }
+        Ok(keyring
+            .adopt(rekey.epoch, rekey.issuer.as_str(), seed)
+            .then_some(rekey.epoch))
+    }
+}
+
+/// The main WWS.Connector that orchestrates all subsystems.
+///
+/// Created from a configuration, it initializes the network, hierarchy,
+/// consensus, and state modules, then runs the event loop that ties
+/// them together.
+pub struct WwsConnector {
+    /// Shared mutable state.
+    pub state: Arc<RwLock<ConnectorState>>,
+    /// Network handle for sending commands to the swarm.
+    pub network_handle: SwarmHandle,
+    /// Channel for receiving network events.
+    event_rx: Option<mpsc::Receiver<NetworkEvent>>,
+    /// The swarm host (to be spawned).
+    swarm_host: Option<SwarmHost>,
+    /// Queue of webhook deliveries, until `run()` starts dispatching it.
+    webhook_rx: Option<mpsc::UnboundedReceiver<Delivery>>,
+    /// Configuration.
+    config: ConnectorConfig,
+}
+
+impl WwsConnector {
+    /// Create a new connector from configuration, with a fresh identity.
+    ///
+    /// Initializes all subsystems but does not start the event loop.
+    /// Call `run()` to start processing.
+    pub fn new(config: ConnectorConfig) -> Result<Self, anyhow::Error> {
+        Self::with_identity(config, wws_protocol::crypto::generate_keypair())
+    }
+
+    /// Create a new connector whose PeerId, and so its DID, is derived
+    /// from `identity_key`. The same key gives the same DID on every run.
+    pub fn with_identity(
+        config: ConnectorConfig,
+        identity_key: ed25519_dalek::SigningKey,
+    ) -> Result<Self, anyhow::Error> {
+        Self::with_stores(config, identity_key, Stores::default())
+    }
+
+    /// Create a connector that keeps tasks, artifacts and timelines in
+    /// `stores` instead of in memory.
+    pub fn with_stores(
+        config: ConnectorConfig,
+        identity_key: ed25519_dalek::SigningKey,
+        stores: Stores,
+    ) -> Result<Self, anyhow::Error> {
+        // Build network configuration.
+        let listen_addr = config.network.listen_addr.parse()
+            .map_err(|e| anyhow::anyhow!("Invalid listen address: {}", e))?;
+
+        // Parse bootstrap peer multiaddresses into (PeerId, Multiaddr) pairs.
+        let bootstrap_peers = Self::parse_bootstrap_peers(&config.network.bootstrap_peers);
+        let external_addrs = Self::parse_external_addrs(&config.network.external_addrs)?;
+
+        // Topic keys of a private swarm. The host seals and opens gossip
+        // payloads with them; epoch transitions rekey them.
+        let topic_keys = if config.swarm.encrypt_topics {
+            let token = config
+                .swarm
+                .token
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("swarm.encrypt_topics needs swarm.token"))?;
+            Some(Arc::new(std::sync::RwLock::new(TopicKeyring::new(
+                &config.swarm.swarm_id,
+                SwarmToken::new(token),
+            ))))
+        } else {
+            None
+        };
+
+        let swarm_config = SwarmHostConfig {
+            listen_addr,
+            transport: TransportConfig::default(),
+            discovery: DiscoveryConfig {
+                mdns_enabled: config.network.mdns_enabled,
+                bootstrap_peers,
+                ..Default::default()
+            },
+            keypair: Some(
+                wws_network::Keypair::ed25519_from_bytes(identity_key.to_bytes())
+                    .map_err(|e| anyhow::anyhow!("Invalid identity key: {}", e))?,
+            ),
+            topic_rate_limit: config.network.topic_rate_warn_bytes_per_sec,
+            auto_relay: config.network.enable_relay_client,
+            external_addrs,
+            cipher: topic_keys
+                .clone()
+                .map(|keys| keys as Arc<dyn wws_network::PayloadCipher>),
+            ..Default::default()
+        };
+
+        let (swarm_host, network_handle, event_rx) = SwarmHost::new(swarm_config)?;
+        let local_peer_id = network_handle.local_peer_id();
+        let agent_id = AgentId::new(format!("did:swarm:{}", local_peer_id));
+
+        // Initialize hierarchy.
+        let pyramid_config = PyramidConfig {
+            branching_factor: config.hierarchy.branching_factor,
+            ..Default::default()
+        };
+        let epoch_config = EpochConfig {
+            duration_secs: config.hierarchy.epoch_duration_secs,
+            adaptive: config.hierarchy.adaptive_epochs,
+            min_duration_secs: config.hierarchy.min_epoch_duration_secs,
+            max_duration_secs: config.hierarchy.max_epoch_duration_secs,
+            ..Default::default()
+        };
+
+        // Build swarm identity.
+        let current_swarm_id = SwarmId::new(config.swarm.swarm_id.clone());
+        let swarm_token = config.swarm.token.as_ref().map(|t| SwarmToken::new(t.clone()));
+
+        // Initialize known swarms with our own swarm.
+        let mut known_swarms = std::collections::HashMap::new();
+        known_swarms.insert(
+            current_swarm_id.as_str().to_string(),
+            SwarmRecord {
+                swarm_id: current_swarm_id.clone(),
+                name: config.swarm.name.clone(),
+                is_public: config.swarm.is_public,
+                agent_count: 1,
+                joined: true,
+                last_seen: chrono::Utc::now(),
+            },
+        );
+
+        let mut memberships = std::collections::HashMap::new();
+        memberships.insert(
+            current_swarm_id.as_str().to_string(),
+            SwarmMembership::new(current_swarm_id.clone()),
+        );
+
+        let mut content_store = match &config.storage.content_dir {
+            Some(dir) => ContentStore::open(dir)
+                .map_err(|e| anyhow::anyhow!("Failed to open content store: {}", e))?,
+            None => ContentStore::new(),
+        };
+        if let Some(max_bytes) = config.storage.max_content_bytes {
+            content_store = content_store.with_max_bytes(max_bytes);
+        }
+
+        let trace_log = match &config.trace.dir {
+            Some(dir) => Some(
+                TraceLog::open(dir, &config.trace)
+                    .map_err(|e| anyhow::anyhow!("Failed to open message trace: {}", e))?,
+            ),
+            None => None,
+        };
+
+        let log_sinks = config
+            .logging
+            .sinks
+            .iter()
+            .map(LogSink::open)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Invalid log sink: {}", e))?;
+
+        let (webhooks, webhook_rx) =
+            Webhooks::new(config.webhooks.clone(), agent_id.to_string());
+
+        let mut scheduler = Scheduler::new();
+        for (schedule_id, schedule) in &config.schedules {
+            scheduler.add(TaskSchedule::new(
+                schedule_id.clone(),
+                &schedule.cron,
+                schedule.task.clone(),
+                chrono::Utc::now(),
+            )?)?;
+        }
+
+        let state = ConnectorState {
+            agent_id: agent_id.clone(),
+            status: ConnectorStatus::Initializing,
+            epoch_manager: EpochManager::new(epoch_config),
+            pyramid: PyramidAllocator::new(pyramid_config),
+            election: None,
+            geo_cluster: GeoCluster::default(),
+            succession: SuccessionManager::new(),
+            rfp_coordinators: std::collections::HashMap::new(),
+            voting_engines: std::collections::HashMap::new(),
+            cascade: CascadeEngine::with_limits(config.consensus.cascade_limits()),
+            task_set: OrSet::new(agent_id.to_string()),
+            task_details: stores.tasks,
+            task_timelines: stores.timelines,
+            agent_set: OrSet::new(agent_id.to_string()),
+            member_set: OrSet::new(agent_id.to_string()),
+            member_last_seen: {
+                let mut m = std::collections::HashMap::new();
+                m.insert(agent_id.to_string(), chrono::Utc::now());
+                m
+            },
+            agent_names: {
+                let mut m = std::collections::HashMap::new();
+                m.insert(agent_id.to_string(), config.agent.name.clone());
+                m
+            },
+            agent_activity: {
+                let mut m = std::collections::HashMap::new();
+                m.insert(agent_id.to_string(), AgentActivity::default());
+                m
+            },
+            task_vote_requirements: std::collections::HashMap::new(),
+            member_last_task_poll: std::collections::HashMap::new(),
+            member_last_result: std::collections::HashMap::new(),
+            task_result_text: std::collections::HashMap::new(),
+            pending_plan_reveals: std::collections::HashMap::new(),
+            pending_vote_reveals: std::collections::HashMap::new(),
+            voting_windows: std::collections::HashMap::new(),
+            merkle_dag: MerkleDag::new(),
+            content_store,
+            granularity: GranularityAlgorithm::default(),
+            my_tier: Tier::Executor,
+            parent_id: None,
+            agent_tiers: std::collections::HashMap::new(),
+            agent_parents: std::collections::HashMap::new(),
+            current_layout: None,
+            subordinates: std::collections::HashMap::new(),
+            task_results: stores.artifacts,
+            network_stats: NetworkStats {
+                total_agents: 1,
+                hierarchy_depth: 1,
+                branching_factor: config.hierarchy.branching_factor,
+                current_epoch: 1,
+                my_tier: Tier::Executor,
+                subordinate_count: 0,
+                parent_id: None,
+            },
+            event_log: Vec::new(),
+            log_count: 0,
+            message_trace: Vec::new(),
+            dead_letters: Vec::new(),
+            start_time: chrono::Utc::now(),
+            current_swarm_id,
+            known_swarms,
+            swarm_token,
+            topic_keys,
+            active_holons: std::collections::HashMap::new(),
+            deliberation_messages: std::collections::HashMap::new(),
+            ballot_records: std::collections::HashMap::new(),
+            ballot_receipts: std::collections::HashMap::new(),
+            task_state_requests: std::collections::HashMap::new(),
+            task_state_replies: std::collections::HashMap::new(),
+            irv_rounds: std::collections::HashMap::new(),
+            board_acceptances: std::collections::HashMap::new(),
+            registered_names: std::collections::HashMap::new(),
+            pending_verifications: std::collections::HashMap::new(),
+            verified_agents: std::collections::HashSet::new(),
+            direct_messages: Vec::new(),
+            plan_approval_timeout_secs: config
+                .consensus
+                .plan_approval_timeout_secs
+                .unwrap_or(DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS),
+            pending_plan_approvals: std::collections::HashMap::new(),
+            task_templates: config.templates.clone(),
+            scheduler,
+            task_costs: std::collections::HashMap::new(),
+            voting_config: config.consensus.voting_config(),
+            reveal_timeout_secs: config.consensus.reveal_timeout_secs,
+            max_round_age_secs: config
+                .consensus
+                .max_round_age_secs
+                .unwrap_or(DEFAULT_MAX_ROUND_AGE_SECS),
+            max_tender_attempts: config
+                .consensus
+                .max_tender_attempts
+                .unwrap_or(DEFAULT_MAX_TENDER_ATTEMPTS),
+            retender_relax_tier: config.consensus.retender_relax_tier,
+            task_tenders: std::collections::HashMap::new(),
+            partition_detector: crate::partition::PartitionDetector::new(
+                config.hierarchy.partition_threshold,
+            ),
+            merge: crate::merge::MergeTracker::default(),
+            shards: crate::shard::ShardTracker::new(
+                config.swarm.shard_threshold,
+                config.swarm.shard_strategy,
+            ),
+            relays: crate::delegation::RelayTracker::default(),
+            reputation: crate::reputation::ReputationLedger::new(stores.attestations),
+            quorum_policy: config.consensus.quorum.clone(),
+            assignment_strategy: config.consensus.assignment,
+            assignment_seed: config.consensus.assignment_seed,
+            committee_size: config.consensus.committee_size,
+            held_tasks: std::collections::HashMap::new(),
+            verification_rate: config.consensus.verification_rate.clamp(0.0, 1.0),
+            proposal_bond: config.consensus.proposal_bond,
+            proposal_bonds: ProposalBonds::default(),
+            verifications: std::collections::HashMap::new(),
+            round_restarts: std::collections::HashMap::new(),
+            board_config: config.board.clone(),
+            board_formations: std::collections::HashMap::new(),
+            local_agents: AgentCluster::new(
+                config.cluster.max_local_agents,
+                Some(config.cluster.key_dir.clone()),
+            ),
+            identity_key,
+            did_documents: DidDirectory::default(),
+            swarm_params: None,
+            params_proposals: std::collections::HashMap::new(),
+            result_schema_violations: std::collections::HashMap::new(),
+            task_progress: std::collections::HashMap::new(),
+            blocked_tasks: std::collections::HashMap::new(),
+            plan_policy: config.plan_policy.clone(),
+            board_channels: std::collections::HashMap::new(),
+            election_vote_due: None,
+            memberships,
+            task_swarms: std::collections::HashMap::new(),
+            name_directory: NameDirectory::new(&config.names),
+            agent_capabilities: {
+                let mut m = std::collections::HashMap::new();
+                m.insert(agent_id.to_string(), config.agent.capabilities.clone());
+                m
+            },
+            capabilities: CapabilityRegistry::new(&config.capabilities),
+            task_winning_plans: std::collections::HashMap::new(),
+            agent_services: std::collections::HashMap::new(),
+            did_republish_due: false,
+            agent_loads: std::collections::HashMap::new(),
+            max_concurrent_tasks: config.agent.max_concurrent_tasks,
+            picked_up_tasks: std::collections::HashSet::new(),
+            task_deliveries: TaskDeliveries::default(),
+            restarts: 0,
+            tier_subscriptions: std::collections::HashMap::new(),
+            config_reload: ConfigReload::new(config.clone()),
+            work_stealing: WorkStealing::default(),
+            agent_roles: config
+                .agent
+                .role
+                .map(|role| (agent_id.to_string(), role))
+                .into_iter()
+                .collect(),
+            trace_log,
+            log_sinks,
+            webhooks,
+            peer_rtt: std::collections::HashMap::new(),
+        };
+
+        Ok(Self {
+            state: Arc::new(RwLock::new(state)),
+            network_handle,
+            event_rx: Some(event_rx),
+            swarm_host: Some(swarm_host),
+            webhook_rx,
+            config,
+        })
+    }
+
+    /// Start the connector, running the swarm and event loop.
+    ///
+    /// This spawns the swarm host as a background task and runs
+    /// the main event processing loop. Returns an error if the swarm host
+    /// stops, fails or panics, since the connector is cut off without it.
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        // Take and spawn the swarm host.
+        let swarm_host = self
+            .swarm_host
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("SwarmHost already consumed"))?;
+
+        // The swarm host is aborted with this future, so a supervisor that
+        // cancels the run can start a new connector on the same ports.
+        let mut swarm_task = tokio::spawn(swarm_host.run());
+        let _swarm_guard = crate::daemon::TaskGuard::new(vec![swarm_task.abort_handle()]);
+
+        if let Some(webhook_rx) = self.webhook_rx.take() {
+            tokio::spawn(crate::webhooks::run_dispatcher(webhook_rx));
+        }
+
+        // Subscribe to core topics.
+        self.network_handle.subscribe_core_topics().await?;
+
+        // Subscribe to our swarm's topics (if not the default public swarm,
+        // since core topics already include the public swarm).
+        let swarm_id_str = self.config.swarm.swarm_id.clone();
+        if swarm_id_str != wws_protocol::DEFAULT_SWARM_ID {
+            self.network_handle
+                .subscribe_swarm_topics(&swarm_id_str)
+                .await?;
+        }
+
+        self.subscribe_task_assignment_topics(&swarm_id_str).await;
+
+        // Subscribe to the direct messages topic for this swarm.
+        let messages_topic = SwarmTopics::messages_for(&swarm_id_str);
+        if let Err(e) = self.network_handle.subscribe(&messages_topic).await {
+            tracing::debug!(error = %e, topic = %messages_topic, "Failed to subscribe messages topic");
+        }
+
+        // Connect to bootstrap peers to join the swarm network immediately.
+        self.connect_to_bootstrap_peers().await;
+
+        // Initiate Kademlia bootstrap to populate the DHT routing table.
+        if !self.config.network.bootstrap_peers.is_empty() {
+            if let Err(e) = self.network_handle.bootstrap().await {
+                tracing::warn!(error = %e, "Kademlia bootstrap initiation failed");
+            }
+        }
+
+        // Update status.
+        {
+            let mut state = self.state.write().await;
+            state.status = ConnectorStatus::Running;
+            state.push_log(
+                LogCategory::System,
+                format!(
+                    "WWS.Connector started (swarm: {} [{}])",
+                    self.config.swarm.name, swarm_id_str
+                ),
+            );
+        }
+
+        tracing::info!("WWS.Connector is running");
+
+        // Take the event receiver out of self so we can use both in the loop.
+        let mut event_rx = self
+            .event_rx
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Event receiver already consumed"))?;
+        let keepalive_secs = self.config.hierarchy.keepalive_interval_secs;
+        let mut keepalive_interval =
+            tokio::time::interval(Duration::from_secs(keepalive_secs));
+        let mut epoch_tick = tokio::time::interval(Duration::from_secs(1));
+        let announce_secs = self.config.swarm.announce_interval_secs;
+        let mut swarm_announce_interval =
+            tokio::time::interval(Duration::from_secs(announce_secs));
+        let mut bootstrap_retry_interval = tokio::time::interval(Duration::from_secs(20));
+        // Voting completion check every 5 seconds
+        let mut voting_check_interval = tokio::time::interval(Duration::from_secs(5));
+        let mut execution_timeout_interval = tokio::time::interval(Duration::from_secs(10));
+        let mut schedule_interval = tokio::time::interval(Duration::from_secs(1));
+        let mut content_gc_interval =
+            tokio::time::interval(Duration::from_secs(CONTENT_GC_INTERVAL_SECS));
+        let mut did_publish_interval =
+            tokio::time::interval(Duration::from_secs(DID_REPUBLISH_INTERVAL_SECS));
+        let mut subscription_audit_interval =
+            tokio::time::interval(Duration::from_secs(SUBSCRIPTION_AUDIT_INTERVAL_SECS));
+        let mut store_flush_interval =
+            tokio::time::interval(Duration::from_secs(STORE_FLUSH_INTERVAL_SECS));
+
+        // Gossip payloads are decoded on the blocking pool and handled in
+        // arrival order.
+        let mut pending_decodes = FuturesOrdered::new();
+
+        loop {
+            tokio::select! {
+                Some(event) = event_rx.recv(), if pending_decodes.len() < MAX_PENDING_DECODES => {
+                    match event {
+                        NetworkEvent::MessageReceived { topic, data, source, .. } => {
+                            pending_decodes.push_back(crate::inbound::spawn_decode(topic, data, source, self.strict_limits()));
+                        }
+                        event => self.handle_network_event(event).await,
+                    }
+                }
+                Some(decoded) = pending_decodes.next() => {
+                    match decoded {
+                        Ok(inbound) => self.handle_message(inbound).await,
+                        Err(e) => tracing::error!(error = %e, "Message decode worker failed"),
+                    }
+                }
+                _ = keepalive_interval.tick() => {
+                    self.send_keepalive().await;
+                    if self.state.read().await.did_republish_due {
+                        self.publish_did_documents().await;
+                    }
+                    self.request_work_for_idle_executors().await;
+                    self.check_partition().await;
+                    self.check_shard().await;
+                    crate::delegation::relay_results(&self.state, &self.network_handle).await;
+                    crate::reputation::publish_attestations(&self.state, &self.network_handle).await;
+                }
+                _ = epoch_tick.tick() => {
+                    self.check_epoch_transition().await;
+                }
+                _ = swarm_announce_interval.tick() => {
+                    self.announce_swarm().await;
+                }
+                _ = did_publish_interval.tick() => {
+                    self.publish_did_documents().await;
+                }
+                _ = bootstrap_retry_interval.tick() => {
+                    self.connect_to_bootstrap_peers().await;
+                    if !self.config.network.bootstrap_peers.is_empty() {
+                        let _ = self.network_handle.bootstrap().await;
+                    }
+                }
+                _ = voting_check_interval.tick() => {
+                    self.check_voting_completion().await;
+                    crate::voting_window::publish_voting_windows(&self.state, &self.network_handle).await;
+                    self.retender_unproposed_tasks().await;
+                }
+                _ = execution_timeout_interval.tick() => {
+                    self.check_execution_timeouts().await;
+                }
+                _ = schedule_interval.tick() => {
+                    let reloaded = self.state.write().await.config_reload.take_pending();
+                    if let Some(config) = reloaded {
+                        let keepalive_secs = config.hierarchy.keepalive_interval_secs;
+                        if keepalive_secs != self.config.hierarchy.keepalive_interval_secs {
+                            keepalive_interval =
+                                tokio::time::interval(Duration::from_secs(keepalive_secs));
+                        }
+                        let announce_secs = config.swarm.announce_interval_secs;
+                        if announce_secs != self.config.swarm.announce_interval_secs {
+                            swarm_announce_interval =
+                                tokio::time::interval(Duration::from_secs(announce_secs));
+                        }
+                        let new_peers =
+                            config.network.bootstrap_peers != self.config.network.bootstrap_peers;
+                        self.config = config;
+                        if new_peers {
+                            self.connect_to_bootstrap_peers().await;
+                        }
+                    }
+                    // Catches tier changes made outside message handling,
+                    // such as joining or migrating swarms over RPC.
+                    self.sync_tier_subscriptions().await;
+                    self.run_due_schedules().await;
+                    self.release_held_tasks().await;
+                    self.check_board_formations().await;
+                }
+                _ = subscription_audit_interval.tick() => {
+                    self.reconcile_subscriptions().await;
+                }
+                _ = store_flush_interval.tick() => {
+                    let mut state = self.state.write().await;
+                    if let Err(e) = state.flush_stores() {
+                        state.push_log(
+                            LogCategory::System,
+                            format!("Failed to flush task storage: {}", e),
+                        );
+                    }
+                }
+                stopped = &mut swarm_task => {
+                    let mut state = self.state.write().await;
+                    if let Err(e) = state.flush_stores() {
+                        tracing::warn!(error = %e, "Failed to flush task storage");
+                    }
+                    state.status = ConnectorStatus::ShuttingDown;
+                    return Err(match stopped {
+                        Ok(Ok(())) => anyhow::anyhow!("Swarm host stopped"),
+                        Ok(Err(e)) => anyhow::anyhow!("Swarm host failed: {}", e),
+                        Err(e) => anyhow::anyhow!("Swarm host panicked: {}", e),
+                    });
+                }
+                _ = content_gc_interval.tick() => {
+                    self.reap_abandoned_rounds().await;
+                    let mut state = self.state.write().await;
+                    let evicted = state.collect_content_garbage();
+                    if evicted > 0 {
+                        state.push_log(
+                            LogCategory::System,
+                            format!("Evicted {} unpinned items from the content store", evicted),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Feed recorded inbound messages through the message handler in
+    /// order, against this connector's fresh state, and return that state.
+    ///
+    /// Only the messages move the state: no timers fire and the swarm host
+    /// has no peers, so what this connector publishes in response goes
+    /// nowhere. A sender whose peer ID does not parse is attributed to this
+    /// connector.
+    pub async fn replay(
+        mut self,
+        messages: Vec<RecordedMessage>,
+    ) -> Result<Arc<RwLock<ConnectorState>>, anyhow::Error> {
+        let swarm_host = self
+            .swarm_host
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("SwarmHost already consumed"))?;
+        tokio::spawn(async move {
+            if let Err(e) = swarm_host.run().await {
+                tracing::error!(error = %e, "Swarm host error");
+            }
+        });
+
+        let local_peer_id = self.network_handle.local_peer_id();
+        for message in messages {
+            let source = message
+                .peer
+                .as_deref()
+                .and_then(|peer| peer.parse().ok())
+                .unwrap_or(local_peer_id);
+            let inbound = crate::inbound::decode(
+                message.topic,
+                message.payload.into_bytes(),
+                source,
+                self.strict_limits(),
+            );
+            self.handle_message(inbound).await;
+        }
+        Ok(self.shared_state())
+    }
+
+    /// Handle a network event from the swarm.
+    async fn handle_network_event(&self, event: NetworkEvent) {
+        match event {
+            NetworkEvent::MessageReceived { topic, data, source, .. } => {
+                self.handle_message(crate::inbound::decode(topic, data, source, self.strict_limits())).await;
+            }
+            NetworkEvent::DirectRequest { peer, request_id, message } => {
+                // Direct requests go through the same decode and dispatch
+                // path as gossip; the requester gets an ack once handled.
+                let data = serde_json::to_vec(&message).unwrap_or_default();
+                let inbound = crate::inbound::decode(
+                    wws_network::DIRECT_PROTOCOL.to_string(),
+                    data,
+                    peer,
+                    self.strict_limits(),
+                );
+                let ack = match &inbound.decoded {
+                    Ok(_) => serde_json::json!({ "accepted": true }),
+                    Err(rejection) => serde_json::json!({
+                        "accepted": false,
+                        "code": rejection.error_code().code(),
+                        "reason": rejection.reason(),
+                    }),
+                };
+                // A reconciliation request is answered with our digests,
+                // taken after the requester's have been applied.
+                let reconcile = matches!(
+                    &inbound.decoded,
+                    Ok(DecodedMessage { params: InboundParams::StateReconcile(params), .. })
+                        if Self::is_direct_sender(&peer, &params.sender)
+                );
+                // A merge request is answered with our view after the merge
+                // and the tasks the requester's digests lack.
+                let merge_digests = match &inbound.decoded {
+                    Ok(DecodedMessage { params: InboundParams::SwarmMerge(params), .. })
+                        if Self::is_direct_sender(&peer, &params.sender) =>
+                    {
+                        Some(params.digests.clone())
+                    }
+                    _ => None,
+                };
+                // A delegated task is injected by this gateway, which
+                // answers with the injection's response.
+                let delegation = match &inbound.decoded {
+                    Ok(DecodedMessage { params: InboundParams::TaskDelegation(params), .. })
+                        if Self::is_direct_sender(&peer, &params.sender) =>
+                    {
+                        Some(params.clone())
+                    }
+                    _ => None,
+                };
+                self.handle_message(inbound).await;
+                let reply = if let Some(request) = delegation {
+                    let response =
+                        crate::shard::accept_delegation(request, &self.state, &self.network_handle).await;
+                    SwarmMessage::new(
+                        ProtocolMethod::TaskDelegation.as_str(),
+                        serde_json::to_value(&response).unwrap_or_default(),
+                        String::new(),
+                    )
+                } else if reconcile {
+                    let state = self.state.read().await;
+                    let params = StateReconcileParams {
+                        sender: state.agent_id.clone(),
+                        digests: state.task_digests(),
+                    };
+                    SwarmMessage::new(
+                        ProtocolMethod::StateReconcile.as_str(),
+                        serde_json::to_value(&params).unwrap_or_default(),
+                        String::new(),
+                    )
+                } else if let Some(digests) = merge_digests {
+                    let state = self.state.read().await;
+                    let params = state.merge_view(state.merge_tasks(&digests));
+                    SwarmMessage::new(
+                        ProtocolMethod::SwarmMerge.as_str(),
+                        serde_json::to_value(&params).unwrap_or_default(),
+                        String::new(),
+                    )
+                } else {
+                    SwarmMessage::new(DIRECT_ACK_METHOD, ack, String::new())
+                };
+                if let Err(e) = self.network_handle.respond(request_id, reply).await {
+                    tracing::debug!(peer = %peer, error = %e, "Failed to answer direct request");
+                }
+            }
+            NetworkEvent::PeerConnected(peer) => {
+                tracing::debug!(peer = %peer, "Peer connected");
+                let mut state = self.state.write().await;
+                state.agent_set.add(peer.to_string());
+                state.mark_member_seen(&format!("did:swarm:{}", peer));
+                state.push_log(
+                    LogCategory::Peer,
+                    format!("Connected: {}", peer),
+                );
+                state.network_stats.total_agents = state
+                    .active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
+                    as u64;
+            }
+            NetworkEvent::PeerDisconnected(peer) => {
+                tracing::debug!(peer = %peer, "Peer disconnected");
+                let mut state = self.state.write().await;
+                state.agent_set.remove(&peer.to_string());
+                state.peer_rtt.remove(&format!("did:swarm:{}", peer));
+                state.push_log(
+                    LogCategory::Peer,
+                    format!("Disconnected: {}", peer),
+                );
+            }
+            NetworkEvent::PeerBanned { peer, duration, reason } => {
+                let mut state = self.state.write().await;
+                state.agent_set.remove(&peer.to_string());
+                state.push_log(
+                    LogCategory::Peer,
+                    format!("Banned {} for {}s: {}", peer, duration.as_secs(), reason),
+                );
+            }
+            NetworkEvent::TopicRateExceeded { topic, bytes_per_sec, limit } => {
+                let mut state = self.state.write().await;
+                state.push_log(
+                    LogCategory::System,
+                    format!(
+                        "Topic {} is carrying {:.0} B/s, over the {} B/s limit",
+                        topic, bytes_per_sec, limit
+                    ),
+                );
+            }
+            NetworkEvent::PingRtt { peer, rtt } => {
+                tracing::trace!(peer = %peer, rtt_ms = rtt.as_millis(), "Ping RTT");
+                let mut state = self.state.write().await;
+                state.peer_rtt.insert(format!("did:swarm:{}", peer), rtt);
+            }
+            NetworkEvent::ReachabilityChanged { status, public_address } => {
+                let mut state = self.state.write().await;
+                let message = match public_address {
+                    Some(addr) => format!("Reachability: {:?} at {}", status, addr),
+                    None => format!("Reachability: {:?}", status),
+                };
+                state.push_log(LogCategory::Peer, message);
+            }
+            NetworkEvent::DhtRecordFound { key, value } => {
+                if !key.starts_with(wws_protocol::DID_DOCUMENT_PREFIX.as_bytes()) {
+                    return;
+                }
+                let Ok(document) = serde_json::from_slice::<DidDocument>(&value) else {
+                    return;
+                };
+                if DidDocument::dht_key(&document.id) != key {
+                    return;
+                }
+                let did = document.id.clone();
+                let mut state = self.state.write().await;
+                if state.did_documents.insert(document) {
+                    state.push_log(LogCategory::Peer, format!("Resolved DID document of {}", did));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Drop a message that failed decoding, dead-lettering it and reporting
+    /// the peer that propagated it.
+    async fn reject_inbound(
+        &self,
+        topic: &str,
+        data: &[u8],
+        source: wws_network::PeerId,
+        rejection: InboundRejection,
+    ) {
+        tracing::debug!(
+            method = ?rejection.method(),
+            topic = %topic,
+            reason = %rejection.reason(),
+            "Rejected inbound message"
+        );
+        let mut state = self.state.write().await;
+        if let InboundRejection::Unauthorized { method, what } = &rejection {
+            state.push_log(
+                LogCategory::Error,
+                format!("Dropped unauthorized {} relayed by {}: {}", method, source, what),
+            );
+        }
+        if let InboundRejection::ParseError(e) = &rejection {
+            state.push_log(
+                LogCategory::Error,
+                format!("Failed to parse message on {}: {}", topic, e),
+            );
+            state.push_message_trace(MessageTraceEvent {
+                timestamp: chrono::Utc::now(),
+                direction: "inbound".to_string(),
+                peer: Some(source.to_string()),
+                topic: topic.to_string(),
+                method: None,
+                task_id: None,
+                size_bytes: data.len(),
+                outcome: "parse_error".to_string(),
+                payload: None,
+            });
+        }
+        state.push_dead_letter(
+            topic,
+            &source.to_string(),
+            rejection.method().map(str::to_string),
+            rejection.reason(),
+            data,
+        );
+        if let Some(letter) = state.dead_letters.last_mut() {
+            letter.field_errors = rejection.field_errors().to_vec();
+        }
+        drop(state);
+        if let Some(offense) = rejection.offense() {
+            let _ = self.network_handle.report_peer(source, offense).await;
+        }
+    }
+
+    /// Handle a protocol message received on a topic, once decoded.
+    async fn handle_message(&self, inbound: InboundMessage) {
+        {
+            let mut state = self.state.write().await;
+            let payload = state
+                .trace_log
+                .as_ref()
+                .filter(|log| log.records_payloads())
+                .map(|_| String::from_utf8_lossy(&inbound.data).into_owned());
+            state.push_message_trace(MessageTraceEvent {
+                timestamp: chrono::Utc::now(),
+                direction: "inbound".to_string(),
+                peer: Some(inbound.source.to_string()),
+                topic: inbound.topic.clone(),
+                method: inbound.method().map(str::to_string),
+                task_id: inbound.task_id().map(str::to_string),
+                size_bytes: inbound.data.len(),
+                outcome: "received".to_string(),
+                payload,
+            });
+            state.push_log(
+                LogCategory::Message,
+                format!("Message received on {} from {}", inbound.topic, inbound.source),
+            );
+        }
+
+        let InboundMessage { topic, source, data, decoded } = inbound;
+        let (topic, data) = (topic.as_str(), data.as_slice());
+        let DecodedMessage { message, params } = match decoded {
+            Ok(decoded) => decoded,
+            Err(rejection) => {
+                self.reject_inbound(topic, data, source, rejection).await;
+                return;
+            }
+        };
+        if let Some(remote) = message.hlc {
+            hlc::global().observe(remote);
+        }
+
+        // Envelope signatures are checked against the sender's DID
+        // document once it has been resolved.
+        if !message.signature.is_empty() {
+            if let Some(did) = crate::did::sender(&message) {
+                let (verdict, lookup) = {
+                    let mut state = self.state.write().await;
+                    let verdict = state.did_documents.check_signature(did, &message);
+                    let lookup = verdict.is_none()
+                        && state.did_documents.start_lookup(did, std::time::Instant::now());
+                    (verdict, lookup)
+                };
+                if verdict == Some(false) {
+                    let rejection = InboundRejection::InvalidSignature {
+                        method: message.method.clone(),
+                        what: "sender DID document".to_string(),
+                    };
+                    self.reject_inbound(topic, data, source, rejection).await;
+                    return;
+                }
+                if lookup {
+                    crate::did::lookup(&self.network_handle, did).await;
+                }
+            }
+        }
+
+        match params {
+            InboundParams::KeepAlive(params) => {
+                let mut state = self.state.write().await;
+                state.succession.record_keepalive(&params.agent_id);
+                state.mark_member_seen_with_name(
+                    params.agent_id.as_str(),
+                    params.agent_name.as_deref(),
+                );
+                if let Some(ts) = params.last_task_poll_at {
+                    state
+                        .member_last_task_poll
+                        .insert(params.agent_id.to_string(), ts);
+                }
+                if let Some(ts) = params.last_result_at {
+                    state.member_last_result.insert(params.agent_id.to_string(), ts);
+                }
+                if let Some(load) = params.load.clone() {
+                    state.note_load(params.agent_id.as_str(), load);
+                }
+                state.note_role(params.agent_id.as_str(), params.role);
+                state.note_services(params.agent_id.as_str(), params.services.as_deref());
+                // A connector of another lineage bootstrapped this swarm
+                // apart from us: start a merge handshake with it.
+                let merge_request = params
+                    .lineage
+                    .as_ref()
+                    .filter(|_| topic == SwarmTopics::keepalive_for(state.current_swarm_id.as_str()))
+                    .filter(|lineage| {
+                        state
+                            .merge
+                            .observe(params.agent_id.as_str(), lineage, chrono::Utc::now())
+                    })
+                    .map(|_| state.merge_view(state.merge_tasks(&[])));
+                let active_members =
+                    state.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+                state.recompute_hierarchy(&active_members);
+                state.push_log(
+                    LogCategory::Message,
+                    format!(
+                        "KeepAlive from {}",
+                        params
+                            .agent_name
+                            .clone()
+                            .unwrap_or_else(|| params.agent_id.to_string())
+                    ),
+                );
+                let reseal = state.reseal_topic_key(params.agent_id.as_str(), params.topic_key.as_deref());
+                drop(state);
+                self.sync_tier_subscriptions().await;
+                if let Some(rekey) = reseal {
+                    self.publish_rekey(&rekey).await;
+                }
+                if let Some(request) = merge_request {
+                    self.merge_with_peer(params.agent_id.to_string(), request);
+                }
+            }
+            InboundParams::AgentKeepAlive(params) => {
+                let mut state = self.state.write().await;
+                state.mark_member_seen_with_name(
+                    params.agent_id.as_str(),
+                    params.agent_name.as_deref(),
+                );
+                if let Some(ts) = params.last_task_poll_at {
+                    state
+                        .member_last_task_poll
+                        .insert(params.agent_id.to_string(), ts);
+                }
+                if let Some(ts) = params.last_result_at {
+                    state.member_last_result.insert(params.agent_id.to_string(), ts);
+                }
+                if let Some(load) = params.load.clone() {
+                    state.note_load(params.agent_id.as_str(), load);
+                }
+                state.note_role(params.agent_id.as_str(), params.role);
+                state.note_services(params.agent_id.as_str(), params.services.as_deref());
+                let active_members =
+                    state.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+                state.recompute_hierarchy(&active_members);
+                state.push_log(
+                    LogCategory::System,
+                    format!(
+                        "Agent heartbeat: {}",
+                        params
+                            .agent_name
+                            .clone()
+                            .unwrap_or_else(|| params.agent_id.to_string())
+                    ),
+                );
+                drop(state);
+                self.sync_tier_subscriptions().await;
+            }
+            InboundParams::WorkStealRequest(params) => {
+                let mut state = self.state.write().await;
+                if params.parent_id != state.agent_id {
+                    return;
+                }
+                let granted = state.arbitrate_steal(&params, chrono::Utc::now());
+                let assignment = match granted {
+                    Ok(assignment) => assignment,
+                    Err(reason) => {
+                        tracing::debug!(agent = %params.agent_id, reason = %reason, "Work steal declined");
+                        return;
+                    }
+                };
+                let swarm_id = state.swarm_for_task(&assignment.task.task_id);
+                drop(state);
+                let topic = SwarmTopics::tasks_for(&swarm_id, assignment.task.tier_level);
+                let msg = SwarmMessage::new(
+                    ProtocolMethod::TaskAssignment.as_str(),
+                    serde_json::to_value(&assignment).unwrap_or_default(),
+                    String::new(),
+                );
+                if let Ok(data) = serde_json::to_vec(&msg) {
+                    if let Err(e) = self.network_handle.publish(&topic, data).await {
+                        tracing::debug!(error = %e, "Failed to publish stolen task assignment");
+                    }
+                }
+            }
+            InboundParams::Candidacy(params) => {
+                let mut state = self.state.write().await;
+                state.note_capabilities(params.agent_id.as_str(), &params.capabilities);
+                if let Some(ref mut election) = state.election {
+                    if let Err(e) = election.register_candidate(&params) {
+                        tracing::warn!(error = %e, "Failed to register candidate");
+                    }
+                }
+            }
+            InboundParams::ElectionVote(params) => {
+                let mut state = self.state.write().await;
+                if let Some(ref mut election) = state.election {
+                    if let Err(e) = election.record_vote(params) {
+                        tracing::warn!(error = %e, "Failed to record election vote");
+                    }
+                }
+            }
+            InboundParams::TierAssignment(params) => {
+                let authorized = self.state.read().await.authorize_tier_assignment(&params);
+                if let Err(what) = authorized {
+                    let rejection = InboundRejection::Unauthorized { method: message.method.clone(), what };
+                    self.reject_inbound(topic, data, source, rejection).await;
+                    return;
+                }
+                let mut state = self.state.write().await;
+                let swarm_id = SwarmTopics::swarm_of(topic)
+                    .filter(|swarm_id| state.memberships.contains_key(*swarm_id))
+                    .unwrap_or(state.current_swarm_id.as_str())
+                    .to_string();
+                if params.assigned_agent == state.agent_id {
+                    if let Some(membership) = state.memberships.get_mut(&swarm_id) {
+                        membership.assign(params.tier, params.parent_id.clone());
+                    }
+                    if swarm_id == state.current_swarm_id.as_str() {
+                        state.my_tier = params.tier;
+                        state.parent_id = Some(params.parent_id);
+                        state.network_stats.my_tier = params.tier;
+                    }
+                    tracing::info!(tier = ?params.tier, swarm_id = %swarm_id, "Tier assignment received");
+                }
+                drop(state);
+                self.sync_tier_subscriptions().await;
+            }
+            InboundParams::TaskInjection(params) => {
+                let mut state = self.state.write().await;
+                let swarm_id = SwarmTopics::swarm_of(topic)
+                    .filter(|swarm_id| state.is_member_of(swarm_id))
+                    .unwrap_or(state.current_swarm_id.as_str())
+                    .to_string();
+
+                // Every peer keeps inline attachments, whatever its tier, so
+                // the eventual assignee can serve them to its agent.
+                let dropped = state.store_attachment_blobs(&params.task, &params.attachments);
+                if dropped > 0 {
+                    state.push_log(
+                        LogCategory::Error,
+                        format!(
+                            "Dropped {} attachment blob(s) of task {} from {}: content does not match an attached CID",
+                            dropped, params.task.task_id, source
+                        ),
+                    );
+                }
+
+                // Tier-filtered task reception: only process tasks for our tier level
+                let my_tier = state.tier_in(&swarm_id);
+                let task_tier_level = params.task.tier_level;
+
+                // Each tier processes tasks at its level:
+                // - Tier1 processes tier_level 1
+                // - Tier2 processes tier_level 2
+                // - TierN(n) processes tier_level n
+                // - Executor processes any tier_level (leaf workers)
+                let my_tier_level = my_tier.depth();
+                let should_process = match my_tier {
+                    Tier::Executor => true, // Executors handle any level (leaf work)
+                    _ => my_tier_level == task_tier_level, // Coordinators only handle their level
+                };
+
+                if !should_process {
+                    if params.attempt > 0 {
+                        state.rfp_coordinators.remove(&params.task.task_id);
+                    }
+                    tracing::debug!(
+                        task_id = %params.task.task_id,
+                        my_tier = ?my_tier,
+                        task_tier = task_tier_level,
+                        "Ignoring task for different tier"
+                    );
+                    drop(state);
+                    return;
+                }
+
+                let task_id = params.task.task_id.as_str();
+                let proposed_here = state.voting_engines.contains_key(task_id)
+                    || state.rfp_coordinators.get(task_id).is_some_and(|rfp| rfp.reveal_count() > 0);
+                if params.attempt > 0 && proposed_here {
+                    state.push_log(
+                        LogCategory::Task,
+                        format!(
+                            "Ignoring re-tender {} of task {}: plans already revealed here",
+                            params.attempt, task_id
+                        ),
+                    );
+                    return;
+                }
+                if params.attempt > 0 {
+                    // A re-tender restarts the round; a relaxed one may have
+                    // left this tier.
+                    state.round_restarts.insert(params.task.task_id.clone(), chrono::Utc::now());
+                    state.pending_plan_reveals.remove(&params.task.task_id);
+                }
+                state.task_set.add(params.task.task_id.clone());
+                state.set_task_swarm(&params.task.task_id, &swarm_id);
+                let injected_id = params.task.task_id.clone();
+                let injected_parent_id = params.task.parent_task_id.clone();
+                state
+                    .task_details
+                    .insert(injected_id.clone(), params.task.clone());
+                // Update parent's subtasks list when a sub-holon task arrives
+                if let Some(parent_id) = &injected_parent_id {
+                    state.cascade.record_spawn(parent_id, &injected_id);
+                    if let Some(parent) = state.task_details.get_mut(parent_id) {
+                        if !parent.subtasks.iter().any(|id| id == &injected_id) {
+                            parent.subtasks.push(injected_id.clone());
+                        }
+                    }
+                }
+                if params.attempt > 0 {
+                    state.push_task_timeline_event(
+                        &params.task.task_id,
+                        "retendered",
+                        format!("Task tendered again (attempt {})", params.attempt),
+                        Some(params.originator.to_string()),
+                    );
+                } else {
+                    state.push_task_timeline_event(
+                        &params.task.task_id,
+                        "injected",
+                        format!("Task injected: {}", params.task.description),
+                        None,
+                    );
+                }
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Task injected at my tier: {} ({})",
+                        params.task.task_id, params.task.description
+                    ),
+                );
+
+                // All coordinator tiers initialize RFP for competitive planning
+                let is_coordinator = my_tier != Tier::Executor;
+                let task_id = params.task.task_id.clone();
+                let epoch = params.task.epoch;
+
+                if is_coordinator {
+                    // Agents at my tier level form the quorum pool
+                    let my_tier_agents: Vec<String> = state.agent_tiers.iter()
+                        .filter(|(_, t)| **t == my_tier)
+                        .map(|(id, _)| id.clone())
+                        .collect();
+
+                    if !my_tier_agents.is_empty() {
+                        let expected_proposers = state
+                            .quorum_requirement(&task_id, task_tier_level, &my_tier_agents)
+                            .expected_proposers;
+                        let mut rfp = RfpCoordinator::new(
+                            task_id.clone(),
+                            epoch,
+                            expected_proposers,
+                        );
+
+                        if let Err(e) = rfp.inject_task(&params.task) {
+                            tracing::error!(error = %e, "Failed to initialize RFP");
+                        } else {
+                            state.rfp_coordinators.insert(task_id.clone(), rfp);
+                            state.push_log(
+                                LogCategory::Task,
+                                format!("RFP initialized for task {} with {} {:?} agents", task_id, my_tier_agents.len(), my_tier),
+                            );
+                        }
+                    }
+                }
+
+                // Create holon record for this task in Forming status
+                let my_agent_id = state.agent_id.clone();
+                let task_tier = params.task.tier_level;
+                let parent_task_id = params.task.parent_task_id.clone();
+                state.active_holons.entry(task_id.clone()).or_insert_with(|| HolonState {
+                    task_id: task_id.clone(),
+                    chair: my_agent_id,
+                    members: Vec::new(),
+                    adversarial_critic: None,
+                    depth: task_tier,
+                    parent_holon: parent_task_id,
+                    child_holons: Vec::new(),
+                    subtask_assignments: std::collections::HashMap::new(),
+                    child_artifacts: std::collections::HashMap::new(),
+                    status: HolonStatus::Forming,
+                    created_at: chrono::Utc::now(),
+                });
+
+                tracing::info!(
+                    task_id = %params.task.task_id,
+                    my_tier = ?my_tier,
+                    is_coordinator,
+                    "Task received and accepted"
+                );
+
+                drop(state);
+
+                self.subscribe_task_flow_topics(&swarm_id, &task_id).await;
+            }
+            InboundParams::TaskAssignment(params) => {
+                let authorized = self.state.read().await.authorize_task_assignment(&params);
+                if let Err(what) = authorized {
+                    let rejection = InboundRejection::Unauthorized { method: message.method.clone(), what };
+                    self.reject_inbound(topic, data, source, rejection).await;
+                    return;
+                }
+                let mut state = self.state.write().await;
+                // Observers holding the original result track the re-execution;
+                // anyone else treats it as an ordinary assignment.
+                if params.winning_plan_id == VERIFICATION_PLAN_ID
+                    && state
+                        .begin_verification(&params.task.task_id, params.assignee.as_str())
+                        .is_some()
+                {
+                    state.mark_member_seen(params.assignee.as_str());
+                    return;
+                }
+                let mut task = params.task.clone();
+                task.assigned_to = Some(params.assignee.clone());
+                task.status = TaskStatus::InProgress;
+                if task.deadline.is_none() {
+                    task.deadline = Some(
+                        chrono::Utc::now()
+                            + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS),
+                    );
+                }
+
+                let task_id = task.task_id.clone();
+                let parent_task_id = params.parent_task_id.clone();
+                // A bridge relays a remote node's result; its agent never
+                // works on it.
+                let assigned_here = state.is_local_agent(&params.assignee) && task.remote.is_none();
+                let mut moved_away = false;
+
+                if let Some(existing) = state.task_details.get(&task_id) {
+                    if matches!(existing.status, TaskStatus::Completed) {
+                        task.status = TaskStatus::Completed;
+                    }
+                    moved_away = existing
+                        .assigned_to
+                        .as_ref()
+                        .is_some_and(|prev| *prev != params.assignee && state.is_local_agent(prev));
+                }
+
+                state.task_details.insert(task_id.clone(), task);
+                // Reassignments carry a placeholder plan; keep the one that
+                // split the parent.
+                let placeholders = [
+                    STEAL_PLAN_ID,
+                    VERIFICATION_PLAN_ID,
+                    crate::aggregation::SYNTHESIS_PLAN_ID,
+                    "retry-operator",
+                ];
+                if !placeholders.contains(&params.winning_plan_id.as_str()) {
+                    state
+                        .task_winning_plans
+                        .entry(parent_task_id.clone())
+                        .or_insert_with(|| params.winning_plan_id.clone());
+                }
+                if let Some(parent) = state.task_details.get_mut(&parent_task_id) {
+                    if !parent.subtasks.iter().any(|id| id == &task_id) {
+                        parent.subtasks.push(task_id.clone());
+                    }
+                }
+                if assigned_here {
+                    state.task_set.add(task_id.clone());
+                } else if moved_away {
+                    // Stolen or reassigned from one of this connector's agents.
+                    state.task_set.remove(&task_id);
+                    state.picked_up_tasks.remove(&task_id);
+                    state.task_deliveries.forget(&task_id);
+                }
+
+                state.mark_member_seen(params.assignee.as_str());
+                state.bump_tasks_assigned(params.assignee.as_str());
+                state.push_task_timeline_event(
+                    &task_id,
+                    if assigned_here { "assigned" } else { "assignment_observed" },
+                    format!(
+                        "Assigned by plan {} under parent {}",
+                        params.winning_plan_id, params.parent_task_id
+                    ),
+                    Some(params.assignee.to_string()),
+                );
+                state.push_log(
+                    LogCategory::Task,
+                    if assigned_here {
+                        format!(
+                            "Task assigned: {} to {} (plan={}, parent={})",
+                            task_id, params.assignee, params.winning_plan_id, params.parent_task_id
+                        )
+                    } else {
+                        format!(
+                            "Task assignment observed: {} -> {} (plan={}, parent={})",
+                            task_id, params.assignee, params.winning_plan_id, params.parent_task_id
+                        )
+                    },
+                );
+                state.push_log(
+                    LogCategory::System,
+                    format!(
+                        "AUDIT assignment.observe task_id={} assignee={} parent={} local_assignee={}",
+                        task_id,
+                        params.assignee,
+                        params.parent_task_id,
+                        assigned_here
+                    ),
+                );
+
+                if let Some(swarm_id) = SwarmTopics::swarm_of(topic).filter(|s| state.is_member_of(s)) {
+                    let swarm_id = swarm_id.to_string();
+                    state.set_task_swarm(&task_id, &swarm_id);
+                }
+                let swarm_id = state.swarm_for_task(&task_id);
+                drop(state);
+                self.subscribe_task_flow_topics(&swarm_id, &task_id).await;
+            }
+            InboundParams::ProposalCommit(params) => {
+                let mut state = self.state.write().await;
+                // A ProposalCommit is proof of activity — mark proposer as active before
+                // the participation check to avoid KeepAlive propagation race conditions.
+                state.mark_member_seen(params.proposer.as_str());
+                state.mark_member_polled_tasks(params.proposer.as_str());
+                if !Self::is_participating_member_for_task(
+                    &state,
+                    &params.task_id,
+                    params.proposer.as_str(),
+                    Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
+                ) {
+                    state.push_log(
+                        LogCategory::Task,
+                        format!(
+                            "Ignoring proposal commit from non-responding proposer {} for task {}",
+                            params.proposer, params.task_id
+                        ),
+                    );
+                    state.push_dead_letter(
+                        topic,
+                        &source.to_string(),
+                        Some(message.method.clone()),
+                        format!("participation_gate: {} is not participating", params.proposer),
+                        data,
+                    );
+                    return;
+                }
+                let (_, tier_members) = Self::tier_members_for_task(&state, &params.task_id);
+                if let Err(reason) =
+                    state.check_committee_seat(&params.task_id, &tier_members, params.proposer.as_str(), params.selection.as_ref())
+                {
+                    state.push_log(
+                        LogCategory::Task,
+                        format!("Ignoring proposal commit for task {}: {}", params.task_id, reason),
+                    );
+                    state.push_dead_letter(
+                        topic,
+                        &source.to_string(),
+                        Some(message.method.clone()),
+                        format!("committee: {}", reason),
+                        data,
+                    );
+                    return;
+                }
+                if let Some(task) = state.task_details.get_mut(&params.task_id) {
+                    if matches!(task.status, TaskStatus::Pending | TaskStatus::ProposalPhase) {
+                        task.status = TaskStatus::ProposalPhase;
+                    }
+                }
+
+                let requirement = Self::expected_vote_requirement_for_task(&state, &params.task_id);
+                state
+                    .task_vote_requirements
+                    .insert(params.task_id.clone(), requirement.clone());
+                let injected_task = state
+                    .task_details
+                    .get(&params.task_id)
+                    .cloned()
+                    .unwrap_or(Task {
+                        task_id: params.task_id.clone(),
+                        parent_task_id: None,
+                        epoch: params.epoch,
+                        status: TaskStatus::Pending,
+                        description: "Observed proposal commit".to_string(),
+                        assigned_to: None,
+                        tier_level: requirement.tier_level,
+                        subtasks: Vec::new(),
+                        created_at: chrono::Utc::now(),
+                        deadline: None,
+                        ..Default::default()
+                    });
+                {
+                    let rfp = state
+                        .rfp_coordinators
+                        .entry(params.task_id.clone())
+                        .or_insert_with(|| {
+                            RfpCoordinator::new(
+                                params.task_id.clone(),
+                                params.epoch,
+                                requirement.expected_proposers,
+                            )
+                        });
+                    if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::Idle) {
+                        let _ = rfp.inject_task(&injected_task);
+                    }
+                    if let Err(e) = rfp.record_commit(&params) {
+                        tracing::warn!(error = %e, "Failed to record proposal commit");
+                    } else {
+                        if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::CommitPhase) {
+                            let _ = rfp.transition_to_reveal();
+                        }
+                        state.hold_proposal_bond(&params);
+                    }
+                }
+
+                let flush_pending = state
+                    .rfp_coordinators
+                    .get(&params.task_id)
+                    .map(|rfp| matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::RevealPhase))
+                    .unwrap_or(false);
+
+                if flush_pending {
+                    let mut pending_reveals = state
+                        .pending_plan_reveals
+                        .remove(&params.task_id)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect::<Vec<(String, Plan)>>();
+                    pending_reveals.sort_by(|a, b| a.0.cmp(&b.0));
+                    let mut revealed_proposers = Vec::new();
+                    let mut mismatched_proposers = Vec::new();
+                    if let Some(rfp) = state.rfp_coordinators.get_mut(&params.task_id) {
+                        for (_, pending_plan) in pending_reveals {
+                            let reveal = ProposalRevealParams {
+                                task_id: params.task_id.clone(),
+                                plan: pending_plan,
+                            };
+                            if let Err(e) = rfp.record_reveal(&reveal) {
+                                tracing::warn!(error = %e, "Failed to record deferred proposal reveal");
+                                if matches!(e, ConsensusError::HashMismatch { .. }) {
+                                    mismatched_proposers.push(reveal.plan.proposer.to_string());
+                                }
+                            } else {
+                                revealed_proposers.push(reveal.plan.proposer.to_string());
+                            }
+                        }
+                    }
+                    for proposer in revealed_proposers {
+                        state.bump_plans_revealed(&proposer);
+                    }
+                    for proposer in mismatched_proposers {
+                        state.slash_proposal_bond(&params.task_id, &proposer, SlashReason::HashMismatch);
+                    }
+                }
+
+                let proposal_owners = state
+                    .rfp_coordinators
+                    .get(&params.task_id)
+                    .map(|rfp| {
+                        rfp.reveals
+                            .values()
+                            .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
+                            .collect::<std::collections::HashMap<String, AgentId>>()
+                    })
+                    .unwrap_or_default();
+                if !proposal_owners.is_empty() {
+                    let voting_config = state.voting_config.clone();
+                    let voting = state.voting_engines.entry(params.task_id.clone()).or_insert_with(|| {
+                        VotingEngine::new(
+                            voting_config,
+                            params.task_id.clone(),
+                            params.epoch,
+                        )
+                    });
+                    voting.set_proposals(proposal_owners);
+                }
+
+                state.push_task_timeline_event(
+                    &params.task_id,
+                    "proposal_commit",
+                    format!("Commit hash {}", params.plan_hash),
+                    Some(params.proposer.to_string()),
+                );
+                state.bump_plans_proposed(params.proposer.as_str());
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Plan commit for task {} from {} (hash={})",
+                        params.task_id,
+                        params.proposer,
+                        params.plan_hash
+                    ),
+                );
+                state.push_log(
+                    LogCategory::System,
+                    format!(
+                        "AUDIT proposal.commit task_id={} proposer={} hash={}",
+                        params.task_id, params.proposer, params.plan_hash
+                    ),
+                );
+            }
+            InboundParams::ProposalReveal(params) => {
+                let mut state = self.state.write().await;
+                // A ProposalReveal is proof of activity — mark proposer as active before
+                // the participation check to avoid KeepAlive propagation race conditions.
+                state.mark_member_seen(params.plan.proposer.as_str());
+                state.mark_member_polled_tasks(params.plan.proposer.as_str());
+                if !Self::is_participating_member_for_task(
+                    &state,
+                    &params.task_id,
+                    params.plan.proposer.as_str(),
+                    Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
+                ) {
+                    state.push_log(
+                        LogCategory::Task,
+                        format!(
+                            "Ignoring proposal reveal from non-responding proposer {} for task {}",
+                            params.plan.proposer, params.task_id
+                        ),
+                    );
+                    state.push_dead_letter(
+                        topic,
+                        &source.to_string(),
+                        Some(message.method.clone()),
+                        format!("participation_gate: {} is not participating", params.plan.proposer),
+                        data,
+                    );
+                    return;
+                }
+                if let Some(task) = state.task_details.get_mut(&params.task_id) {
+                    if matches!(task.status, TaskStatus::Pending | TaskStatus::ProposalPhase) {
+                        task.status = TaskStatus::VotingPhase;
+                    }
+                }
+
+                let requirement = Self::expected_vote_requirement_for_task(&state, &params.task_id);
+                state
+                    .task_vote_requirements
+                    .insert(params.task_id.clone(), requirement.clone());
+
+                let injected_task = state
+                    .task_details
+                    .get(&params.task_id)
+                    .cloned()
+                    .unwrap_or(Task {
+                        task_id: params.task_id.clone(),
+                        parent_task_id: None,
+                        epoch: params.plan.epoch,
+                        status: TaskStatus::Pending,
+                        description: "Observed proposal reveal".to_string(),
+                        assigned_to: None,
+                        tier_level: requirement.tier_level,
+                        subtasks: Vec::new(),
+                        created_at: chrono::Utc::now(),
+                        deadline: None,
+                        ..Default::default()
+                    });
+
+                let mut hash_mismatch = false;
+                let should_queue_reveal = {
+                    let rfp = state
+                        .rfp_coordinators
+                        .entry(params.task_id.clone())
+                        .or_insert_with(|| {
+                            RfpCoordinator::new(
+                                params.task_id.clone(),
+                                params.plan.epoch,
+                                requirement.expected_proposers,
+                            )
+                        });
+                    if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::Idle) {
+                        let _ = rfp.inject_task(&injected_task);
+                    }
+                    if matches!(
+                        rfp.phase(),
+                        wws_consensus::rfp::RfpPhase::RevealPhase
+                            | wws_consensus::rfp::RfpPhase::ReadyForVoting
+                    ) {
+                        if let Err(e) = rfp.record_reveal(&params) {
+                            tracing::warn!(error = %e, "Failed to record proposal reveal");
+                            hash_mismatch = matches!(e, ConsensusError::HashMismatch { .. });
+                        }
+                        false
+                    } else {
+                        true
+                    }
+                };
+                if hash_mismatch {
+                    state.slash_proposal_bond(
+                        &params.task_id,
+                        params.plan.proposer.as_str(),
+                        SlashReason::HashMismatch,
+                    );
+                }
+
+                if should_queue_reveal {
+                    state
+                        .pending_plan_reveals
+                        .entry(params.task_id.clone())
+                        .or_default()
+                        .insert(params.plan.proposer.to_string(), params.plan.clone());
+                }
+
+                let proposal_owners = state
+                    .rfp_coordinators
+                    .get(&params.task_id)
+                    .map(|rfp| {
+                        rfp.reveals
+                            .values()
+                            .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
+                            .collect::<std::collections::HashMap<String, AgentId>>()
+                    })
+                    .unwrap_or_default();
+
+                let voting_config = state.voting_config.clone();
+                let voting = state.voting_engines.entry(params.task_id.clone()).or_insert_with(|| {
+                    VotingEngine::new(
+                        voting_config,
+                        params.task_id.clone(),
+                        params.plan.epoch,
+                    )
+                });
+                voting.set_proposals(proposal_owners);
+
+                state.push_task_timeline_event(
+                    &params.task_id,
+                    "proposal_reveal",
+                    format!("{} subtasks revealed", params.plan.subtasks.len()),
+                    Some(params.plan.proposer.to_string()),
+                );
+                state.bump_plans_revealed(params.plan.proposer.as_str());
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Plan reveal for task {} by {} ({} subtasks): {}",
+                        params.task_id,
+                        params.plan.proposer,
+                        params.plan.subtasks.len(),
+                        params
+                            .plan
+                            .subtasks
+                            .iter()
+                            .map(|s| format!("{}:{}", s.index, s.description))
+                            .collect::<Vec<_>>()
+                            .join(" | ")
+                    ),
+                );
+                state.push_log(
+                    LogCategory::System,
+                    format!(
+                        "AUDIT proposal.reveal task_id={} proposer={} subtasks={}",
+                        params.task_id,
+                        params.plan.proposer,
+                        params.plan.subtasks.len()
+                    ),
+                );
+            }
+            InboundParams::ConsensusVote(params) => {
+                let task_id = params.task_id.clone();
+                let voter = params.voter.clone();
+                let rankings_preview = if params.abstain {
+                    "abstained".to_string()
+                } else {
+                    params.rankings.join(" > ")
+                };
+                let mut state = self.state.write().await;
+                if !Self::is_participating_member_for_task(
+                    &state,
+                    &task_id,
+                    voter.as_str(),
+                    Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
+                ) {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!(
+                            "Ignoring vote from non-responding voter {} for task {}",
+                            voter, task_id
+                        ),
+                    );
+                    state.push_dead_letter(
+                        topic,
+                        &source.to_string(),
+                        Some(message.method.clone()),
+                        format!("participation_gate: {} is not participating", voter),
+                        data,
+                    );
+                    return;
+                }
+                let (_, tier_members) = Self::tier_members_for_task(&state, &task_id);
+                if let Err(reason) =
+                    state.check_committee_seat(&task_id, &tier_members, voter.as_str(), params.selection.as_ref())
+                {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!("Ignoring vote from {} for task {}: {}", voter, task_id, reason),
+                    );
+                    state.push_dead_letter(
+                        topic,
+                        &source.to_string(),
+                        Some(message.method.clone()),
+                        format!("committee: {}", reason),
+                        data,
+                    );
+                    return;
+                }
+                state.mark_member_seen(voter.as_str());
+                if let Some(task) = state.task_details.get_mut(&task_id) {
+                    // Only advance to VotingPhase from pre-voting states.
+                    // Never overwrite InProgress/Completed/Failed tasks — a stale
+                    // ConsensusVote arriving after TaskAssignment must not revert the task.
+                    if matches!(
+                        task.status,
+                        TaskStatus::Pending | TaskStatus::ProposalPhase | TaskStatus::VotingPhase
+                    ) {
+                        task.status = TaskStatus::VotingPhase;
+                    }
+                }
+                let mut counted = false;
+                if let Some(voting) = state.voting_engines.get_mut(&task_id) {
+                    let ranked_vote = RankedVote {
+                        voter: voter.clone(),
+                        task_id: params.task_id.clone(),
+                        epoch: params.epoch,
+                        rankings: params.rankings.clone(),
+                        critic_scores: params.critic_scores.clone(),
+                        abstain: params.abstain,
+                    };
+                    match voting.record_vote(ranked_vote) {
+                        Ok(()) => counted = true,
+                        Err(e) => tracing::warn!(error = %e, "Failed to record consensus vote"),
+                    }
+                }
+                let receipt = if counted {
+                    state.issue_ballot_receipt(&task_id, &voter, params.epoch)
+                } else {
+                    None
+                };
+                // Record ballot for deliberation visibility
+                state.ballot_records.entry(task_id.clone()).or_default().push(BallotRecord {
+                    task_id: task_id.clone(),
+                    voter: voter.clone(),
+                    rankings: params.rankings,
+                    critic_scores: params.critic_scores,
+                    timestamp: chrono::Utc::now(),
+                    irv_round_when_eliminated: None,
+                    abstain: params.abstain,
+                });
+                // Also record as a deliberation message (proposal score phase)
+                {
+                    let rankings_str = format!("Rankings: {}", rankings_preview);
+                    state.deliberation_messages.entry(task_id.clone()).or_default().push(DeliberationMessage {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        task_id: task_id.clone(),
+                        timestamp: chrono::Utc::now(),
+                        speaker: voter.clone(),
+                        round: 2,
+                        message_type: DeliberationType::CritiqueFeedback,
+                        content: rankings_str,
+                        referenced_plan_id: None,
+                        critic_scores: None,
+                        hlc: Some(hlc::global().now()),
+                    });
+                }
+                state.push_task_timeline_event(
+                    &task_id,
+                    "vote_recorded",
+                    format!("Rankings: {}", rankings_preview),
+                    Some(voter.to_string()),
+                );
+                state.bump_votes_cast(voter.as_str());
+                state.push_log(
+                    LogCategory::Vote,
+                    format!(
+                        "Vote for task {} from {}: {}",
+                        task_id,
+                        voter,
+                        rankings_preview
+                    ),
+                );
+                if let Some(receipt) = receipt {
+                    let swarm_id = state.swarm_for_task(&task_id);
+                    drop(state);
+                    self.publish_ballot_receipt(&swarm_id, &receipt).await;
+                }
+            }
+            InboundParams::ConsensusVoteCommit(params) => {
+                let task_id = params.task_id.clone();
+                let voter = params.voter.clone();
+                let mut state = self.state.write().await;
+                if !Self::is_participating_member_for_task(
+                    &state,
+                    &task_id,
+                    voter.as_str(),
+                    Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
+                ) {
+                    state.push_dead_letter(
+                        topic,
+                        &source.to_string(),
+                        Some(message.method.clone()),
+                        format!("participation_gate: {} is not participating", voter),
+                        data,
+                    );
+                    return;
+                }
+                let (_, tier_members) = Self::tier_members_for_task(&state, &task_id);
+                if let Err(reason) =
+                    state.check_committee_seat(&task_id, &tier_members, voter.as_str(), None)
+                {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!("Ignoring ballot commitment from {} for task {}: {}", voter, task_id, reason),
+                    );
+                    state.push_dead_letter(
+                        topic,
+                        &source.to_string(),
+                        Some(message.method.clone()),
+                        format!("committee: {}", reason),
+                        data,
+                    );
+                    return;
+                }
+                state.mark_member_seen(voter.as_str());
+                if let Some(task) = state.task_details.get_mut(&task_id) {
+                    if matches!(
+                        task.status,
+                        TaskStatus::Pending | TaskStatus::ProposalPhase | TaskStatus::VotingPhase
+                    ) {
+                        task.status = TaskStatus::VotingPhase;
+                    }
+                }
+                let Some(voting) = state.voting_engines.get_mut(&task_id) else {
+                    return;
+                };
+                if let Err(e) = voting.record_commitment(voter.clone(), params.commitment) {
+                    tracing::warn!(error = %e, "Failed to record ballot commitment");
+                    return;
+                }
+                state.push_task_timeline_event(
+                    &task_id,
+                    "vote_committed",
+                    "Blind ballot committed".to_string(),
+                    Some(voter.to_string()),
+                );
+                state.bump_votes_cast(voter.as_str());
+                state.push_log(
+                    LogCategory::Vote,
+                    format!("Blind ballot for task {} committed by {}", task_id, voter),
+                );
+            }
+            InboundParams::ConsensusVoteReveal(params) => {
+                let task_id = params.task_id.clone();
+                let voter = params.voter.clone();
+                let mut state = self.state.write().await;
+                let Some(voting) = state.voting_engines.get_mut(&task_id) else {
+                    return;
+                };
+                let ranked_vote = RankedVote {
+                    voter: voter.clone(),
+                    task_id: params.task_id,
+                    epoch: params.epoch,
+                    rankings: params.rankings,
+                    critic_scores: params.critic_scores,
+                    abstain: params.abstain,
+                };
+                // Ballots are recorded for deliberation once tallied.
+                match voting.record_reveal(ranked_vote, &params.salt) {
+                    Ok(()) => {
+                        state.push_log(
+                            LogCategory::Vote,
+                            format!("Blind ballot for task {} revealed by {}", task_id, voter),
+                        );
+                        if let Some(receipt) = state.issue_ballot_receipt(&task_id, &voter, params.epoch) {
+                            let swarm_id = state.swarm_for_task(&task_id);
+                            drop(state);
+                            self.publish_ballot_receipt(&swarm_id, &receipt).await;
+                        }
+                    }
+                    Err(e) => state.push_log(
+                        LogCategory::Vote,
+                        format!(
+                            "Rejected blind ballot reveal for task {} from {}: {}",
+                            task_id, voter, e
+                        ),
+                    ),
+                }
+            }
+            InboundParams::VotingOpen(params) => {
+                let mut state = self.state.write().await;
+                let (task_id, announced_by) = (params.task_id.clone(), params.announced_by.clone());
+                if let Err(reason) = crate::voting_window::accept_voting_open(&mut state, params) {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!("Ignoring voting announcement for task {} from {}: {}", task_id, announced_by, reason),
+                    );
+                }
+            }
+            InboundParams::VoteReminder(params) => {
+                let mut state = self.state.write().await;
+                crate::voting_window::deliver_reminder(&mut state, &params);
+            }
+            InboundParams::BallotReceipt(receipt) => {
+                let mut state = self.state.write().await;
+                let (task_id, voter, position, recorder) = (
+                    receipt.task_id.clone(),
+                    receipt.voter.clone(),
+                    receipt.position,
+                    receipt.recorder.clone(),
+                );
+                match state.accept_ballot_receipt(receipt) {
+                    Ok(true) => state.push_log(
+                        LogCategory::Vote,
+                        format!(
+                            "Ballot of {} for task {} counted at position {} by {}",
+                            voter, task_id, position, recorder
+                        ),
+                    ),
+                    Ok(false) => {}
+                    Err(e) => state.push_log(
+                        LogCategory::Vote,
+                        format!("Rejected ballot receipt for task {}: {}", task_id, e),
+                    ),
+                }
+            }
+            InboundParams::TaskStateRequest(request) => {
+                let mut state = self.state.write().await;
+                let Some(snapshot) = state.answer_task_state_request(&request) else {
+                    return;
+                };
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Sent state of task {} to late subscriber {} ({} commits, {} reveals, {} ballots)",
+                        snapshot.task_id,
+                        request.requester,
+                        snapshot.commits.len(),
+                        snapshot.reveals.len(),
+                        snapshot.ballots.len()
+                    ),
+                );
+                let swarm_id = state.swarm_for_task(&snapshot.task_id);
+                drop(state);
+                self.publish_task_state_message(
+                    &swarm_id,
+                    &snapshot.task_id,
+                    ProtocolMethod::TaskStateSnapshot,
+                    serde_json::to_value(&snapshot).unwrap_or_default(),
+                )
+                .await;
+            }
+            InboundParams::StateReconcile(params) => {
+                // Only accepted over the direct protocol, from the peer
+                // whose identity signed the request.
+                if topic != wws_network::DIRECT_PROTOCOL || !Self::is_direct_sender(&source, &params.sender) {
+                    return;
+                }
+                let mut state = self.state.write().await;
+                state.mark_member_seen(params.sender.as_str());
+                state.reconcile_with_peer(params.sender.as_str(), &params.digests);
+            }
+            InboundParams::SwarmMerge(params) => {
+                // Only accepted over the direct protocol, from the peer
+                // whose identity signed the request.
+                if topic != wws_network::DIRECT_PROTOCOL || !Self::is_direct_sender(&source, &params.sender) {
+                    return;
+                }
+                let mut state = self.state.write().await;
+                state.mark_member_seen(params.sender.as_str());
+                state.merge_with(&params);
+                drop(state);
+                self.sync_tier_subscriptions().await;
+            }
+            InboundParams::TaskStateSnapshot(snapshot) => {
+                let mut state = self.state.write().await;
+                state.mark_member_seen(snapshot.responder.as_str());
+                let requirement = Self::expected_vote_requirement_for_task(&state, &snapshot.task_id);
+                if let Some((commits, reveals, ballots)) =
+                    state.apply_task_state_snapshot(&snapshot, &requirement)
+                {
+                    state.push_log(
+                        LogCategory::Task,
+                        format!(
+                            "Caught up on task {} from {} in {}: {} commits, {} reveals, {} ballots",
+                            snapshot.task_id, snapshot.responder, snapshot.phase, commits, reveals, ballots
+                        ),
+                    );
+                }
+            }
+            InboundParams::ResultSubmission(params) => {
+                let mut state = self.state.write().await;
+                if let Some(task) = state.task_details.get(&params.task_id) {
+                    // Only reject if explicitly assigned to a different agent.
+                    // If assigned_to is None (coordinator/synthesis tasks), allow through.
+                    if task.assigned_to.is_some()
+                        && task.assigned_to.as_ref() != Some(&params.agent_id)
+                    {
+                        state.push_log(
+                            LogCategory::Task,
+                            format!(
+                                "Ignoring late result for task {} from replaced assignee {}",
+                                params.task_id, params.agent_id
+                            ),
+                        );
+                        return;
+                    }
+                }
+                state.record_result_timeliness(
+                    &params.task_id,
+                    params.agent_id.as_str(),
+                    chrono::Utc::now(),
+                );
+                if let Some(task) = state.task_details.get_mut(&params.task_id) {
+                    task.status = TaskStatus::Completed;
+                    task.assigned_to = Some(params.agent_id.clone());
+                }
+                state.task_set.remove(&params.task_id);
+                if let Some(cost) = params.cost {
+                    state.record_task_cost(&params.task_id, cost);
+                }
+                state.mark_member_submitted_result(params.agent_id.as_str());
+                state.bump_tasks_processed(params.agent_id.as_str());
+                state.mark_member_seen(params.agent_id.as_str());
+                // A sealed result is read here only if it is sealed to this
+                // node's agent or one of its local agents.
+                let mut artifact = params.artifact.clone();
+                let readable = match &artifact.sealed {
+                    Some(sealed) => match state.open_result(&params.task_id, sealed) {
+                        Some(content) => {
+                            artifact.content = content;
+                            true
+                        }
+                        None => {
+                            artifact.content.clear();
+                            false
+                        }
+                    },
+                    None => true,
+                };
+                if !readable {
+                    let members = artifact.sealed.as_ref().map_or(0, |s| s.keys.len());
+                    state.push_log(
+                        LogCategory::Task,
+                        format!(
+                            "Result for task {} is sealed to {} member(s); its content is not readable here",
+                            params.task_id, members
+                        ),
+                    );
+                }
+                crate::result_chain::record(&mut state, &mut artifact);
+                // Store the artifact in task_results so /api/tasks returns result_artifact
+                state.task_results.insert(params.task_id.clone(), artifact.clone());
+                // The task's holon is done; a finished child holon may let
+                // its parent, coordinated here, synthesize.
+                let ready_parent = state
+                    .complete_holon(&params.task_id, &artifact)
+                    .filter(|parent_id| {
+                        state
+                            .active_holons
+                            .get(parent_id)
+                            .is_some_and(|parent| state.is_local_agent(&parent.chair))
+                    })
+                    .or_else(|| state.delegated_parent_ready(&params.task_id));
+
+                // Store content text for API and synthesis messages
+                let content_text = if !artifact.content.is_empty() {
+                    artifact.content.clone()
+                } else if !readable {
+                    String::new()
+                } else if let Some(c) = message.params.get("content").and_then(|v| v.as_str()) {
+                    c.to_string()
+                } else {
+                    String::new()
+                };
+                if !content_text.is_empty() {
+                    state.task_result_text.insert(params.task_id.clone(), content_text.clone());
+                    state.store_result_content(&content_text, params.agent_id.as_str());
+                }
+                let verification = state.settle_verification(
+                    &params.task_id,
+                    params.agent_id.as_str(),
+                    &artifact,
+                    &content_text,
+                );
+
+                // Record synthesis result as deliberation message if this is a synthesis
+                let synth_text = if !content_text.is_empty() {
+                    content_text.clone()
+                } else {
+                    state.task_result_text.get(&params.task_id).cloned().unwrap_or_default()
+                };
+                if params.is_synthesis && !synth_text.is_empty() {
+                    state.deliberation_messages.entry(params.task_id.clone()).or_default().push(DeliberationMessage {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        task_id: params.task_id.clone(),
+                        timestamp: chrono::Utc::now(),
+                        speaker: params.agent_id.clone(),
+                        round: 3,
+                        message_type: DeliberationType::SynthesisResult,
+                        content: synth_text,
+                        referenced_plan_id: None,
+                        critic_scores: None,
+                        hlc: Some(hlc::global().now()),
+                    });
+                }
+                let dag_nodes = state.merkle_dag.node_count();
+                state.push_task_timeline_event(
+                    &params.task_id,
+                    "result_submitted",
+                    format!("Artifact {} (dag_nodes={})", params.artifact.artifact_id, dag_nodes),
+                    Some(params.agent_id.to_string()),
+                );
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Result received for task {} from {} (artifact={}, dag_nodes={})",
+                        params.task_id,
+                        params.agent_id,
+                        params.artifact.artifact_id,
+                        dag_nodes
+                    ),
+                );
+                state.push_log(
+                    LogCategory::System,
+                    format!(
+                        "AUDIT result.observe task_id={} agent={} artifact={}",
+                        params.task_id, params.agent_id, params.artifact.artifact_id
+                    ),
+                );
+
+                let verify = if verification.is_none() && !params.is_synthesis {
+                    Self::select_result_verification(
+                        &mut state,
+                        &params.task_id,
+                        params.agent_id.as_str(),
+                    )
+                } else {
+                    None
+                };
+                drop(state);
+                if let Some((topic, data)) = verify {
+                    if let Err(e) = self.network_handle.publish(&topic, data).await {
+                        tracing::error!(task_id = %params.task_id, topic = %topic, error = %e, "Failed to publish verification assignment");
+                    }
+                }
+                if let Some(parent_id) = ready_parent {
+                    crate::rpc_server::finish_parent_task(&parent_id, &self.state, &self.network_handle)
+                        .await;
+                }
+            }
+            InboundParams::TaskProgress(params) => {
+                let mut state = self.state.write().await;
+                let task_id = params.task_id.clone();
+                if let Err(e) = state.record_task_progress(params, chrono::Utc::now()) {
+                    tracing::debug!(task_id = %task_id, error = %e, "Ignoring progress report");
+                }
+            }
+            InboundParams::TaskEscalation(params) => {
+                let mut state = self.state.write().await;
+                state.mark_member_seen(params.coordinator.as_str());
+                state.block_task(params);
+            }
+            InboundParams::TaskTenderFailed(params) => {
+                let mut state = self.state.write().await;
+                state.mark_member_seen(params.originator.as_str());
+                if !state.apply_tender_failure(&params) {
+                    return;
+                }
+                let swarm_id = state.swarm_for_task(&params.task_id);
+                drop(state);
+                self.leave_task_flow_topics(&swarm_id, &params.task_id).await;
+            }
+            InboundParams::DisputeVote(params) => {
+                let mut state = self.state.write().await;
+                state.mark_member_seen(params.voter.as_str());
+                if let Err(e) = state.record_dispute_vote(&params) {
+                    tracing::debug!(task_id = %params.task_id, error = %e, "Ignoring dispute vote");
+                }
+            }
+            InboundParams::Succession(params) => {
+                tracing::info!(
+                    failed = %params.failed_leader,
+                    new = %params.new_leader,
+                    "Succession notification received"
+                );
+                let mut state = self.state.write().await;
+                state.epoch_manager.record_leader_failure(&params.failed_leader);
+            }
+            InboundParams::SwarmAnnounce(params) => {
+                // Nodes behind a forwarded port announce where to dial them.
+                for (peer_id, addr) in Self::announced_addrs(&params) {
+                    if let Err(e) = self.network_handle.add_peer_address(peer_id, addr).await {
+                        tracing::debug!(error = %e, "Failed to add announced address");
+                    }
+                }
+                let mut state = self.state.write().await;
+                let swarm_key = params.swarm_id.as_str().to_string();
+                let is_new = !state.known_swarms.contains_key(&swarm_key);
+
+                let record = state
+                    .known_swarms
+                    .entry(swarm_key.clone())
+                    .or_insert_with(|| SwarmRecord {
+                        swarm_id: params.swarm_id.clone(),
+                        name: params.name.clone(),
+                        is_public: params.is_public,
+                        agent_count: params.agent_count,
+                        joined: false,
+                        last_seen: chrono::Utc::now(),
+                    });
+
+                record.agent_count = params.agent_count;
+                record.last_seen = chrono::Utc::now();
+                record.name = params.name.clone();
+
+                if is_new {
+                    state.push_log(
+                        LogCategory::System,
+                        format!(
+                            "Discovered swarm: {} ({}, {} agents)",
+                            params.name,
+                            if params.is_public { "public" } else { "private" },
+                            params.agent_count
+                        ),
+                    );
+                    tracing::info!(
+                        swarm_id = %params.swarm_id,
+                        name = %params.name,
+                        public = params.is_public,
+                        agents = params.agent_count,
+                        "Discovered new swarm"
+                    );
+                }
+            }
+            InboundParams::SwarmJoin(params) => {
+                let state = self.state.read().await;
+                // Only process join requests for our swarm.
+                if params.swarm_id == state.current_swarm_id {
+                    tracing::info!(
+                        agent = %params.agent_id,
+                        swarm = %params.swarm_id,
+                        "Join request for our swarm"
+                    );
+                }
+            }
+            InboundParams::SwarmLeave(params) => {
+                let mut state = self.state.write().await;
+                if let Some(record) = state.known_swarms.get_mut(params.swarm_id.as_str()) {
+                    record.agent_count = record.agent_count.saturating_sub(1);
+                }
+                state.push_log(
+                    LogCategory::Peer,
+                    format!("{} left swarm {}", params.agent_id, params.swarm_id),
+                );
+            }
+            InboundParams::SwarmParams(params) => {
+                // The record's signature was checked when it was decoded.
+                self.state.write().await.adopt_swarm_params(params);
+            }
+            InboundParams::ParamsChangePropose(params) => {
+                let issued = self
+                    .state
+                    .write()
+                    .await
+                    .record_params_proposal(params, chrono::Utc::now());
+                if let Some(issued) = issued {
+                    crate::swarm_params::publish_swarm_params(&self.network_handle, &issued).await;
+                }
+            }
+            InboundParams::ParamsChangeVote(params) => {
+                let issued = self
+                    .state
+                    .write()
+                    .await
+                    .record_params_vote(&params, chrono::Utc::now());
+                if let Some(issued) = issued {
+                    crate::swarm_params::publish_swarm_params(&self.network_handle, &issued).await;
+                }
+            }
+            InboundParams::SwarmMigrate(params) => {
+                // The signature was checked when the message was decoded;
+                // the signer is checked against our swarm's authority here.
+                let applied = self.state.write().await.apply_migration(params);
+                match applied {
+                    Ok(outcome) => {
+                        crate::migration::complete_migration(outcome, &self.state, &self.network_handle)
+                            .await;
+                    }
+                    Err(reason) => {
+                        tracing::warn!(reason = %reason, "Ignoring swarm migration");
+                        self.state.write().await.push_log(LogCategory::Swarm, reason);
+                    }
+                }
+            }
+            InboundParams::SwarmShard(params) => {
+                // The signature was checked when the message was decoded;
+                // the issuer is checked against our Tier-1 leaders here.
+                let applied = self.state.write().await.apply_shard(params);
+                match applied {
+                    Ok(Some(outcome)) => {
+                        crate::shard::complete_shard(outcome, &self.state, &self.network_handle).await;
+                    }
+                    Ok(None) => {}
+                    Err(reason) => {
+                        tracing::warn!(reason = %reason, "Ignoring swarm shard");
+                        self.state.write().await.push_log(LogCategory::Swarm, reason);
+                    }
+                }
+            }
+            // Only acted on as a direct request, answered with the
+            // injection's response.
+            InboundParams::TaskDelegation(_) => {}
+            InboundParams::ReputationAttest(params) => {
+                // Signatures were checked when the message was decoded;
+                // issuers are checked against the tasks they attest here.
+                let mut state = self.state.write().await;
+                let received = params.attestations.len();
+                let admitted = crate::reputation::accept_attestations(&mut state, params);
+                if admitted > 0 {
+                    state.push_log(
+                        LogCategory::Swarm,
+                        format!("Admitted {} of {} reputation attestations", admitted, received),
+                    );
+                }
+            }
+            InboundParams::EpochRekey(params) => {
+                // Only taken from the keep-alive topic, where it travels
+                // under the swarm's admission key.
+                if topic != SwarmTopics::keepalive_for(params.swarm_id.as_str()) {
+                    return;
+                }
+                let mut state = self.state.write().await;
+                match state.accept_rekey(&params) {
+                    Ok(Some(epoch)) => state.push_log(
+                        LogCategory::Epoch,
+                        format!(
+                            "Adopted topic key {} for epoch {} from {}",
+                            params.key_id, epoch, params.issuer
+                        ),
+                    ),
+                    Ok(None) => {}
+                    Err(reason) => {
+                        tracing::warn!(issuer = %params.issuer, reason = %reason, "Ignoring rekey");
+                        state.push_log(
+                            LogCategory::Error,
+                            format!("Ignored rekey from {}: {}", params.issuer, reason),
+                        );
+                    }
+                }
+            }
+            InboundParams::BoardInvite(params) => {
+                let mut state = self.state.write().await;
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Board invite for task {} (depth={}, chair={}, complexity={:.2})",
+                        params.task_id, params.depth, params.chair, params.complexity_estimate
+                    ),
+                );
+                if params.chair == state.agent_id {
+                    if !state.board_formations.contains_key(&params.task_id) {
+                        state.open_board_formation(params);
+                    }
+                    return;
+                }
+                if params.private {
+                    state.board_channels.entry(params.task_id.clone()).or_default();
+                }
+                // Create or update holon in Forming state
+                let holon = state.active_holons.entry(params.task_id.clone()).or_insert_with(|| {
+                    HolonState {
+                        task_id: params.task_id.clone(),
+                        chair: params.chair.clone(),
+                        members: Vec::new(),
+                        adversarial_critic: None,
+                        depth: params.depth,
+                        parent_holon: None,
+                        child_holons: Vec::new(),
+                        subtask_assignments: std::collections::HashMap::new(),
+                        child_artifacts: std::collections::HashMap::new(),
+                        status: HolonStatus::Forming,
+                        created_at: chrono::Utc::now(),
+                    }
+                });
+                holon.status = HolonStatus::Forming;
+            }
+            InboundParams::BoardAccept(params) => {
+                let mut state = self.state.write().await;
+                state.note_board_accept(&params);
+                state.push_log(
+                    LogCategory::Task,
+                    format!("Board accept: {} for task {}", params.agent_id, params.task_id),
+                );
+                let forming = state
+                    .active_holons
+                    .get(&params.task_id)
+                    .is_some_and(|h| h.status == HolonStatus::Forming);
+                let (_, eligible) = Self::quorum_eligible_for_task(&state, &params.task_id);
+                let already_reached = state
+                    .task_timelines
+                    .get(&params.task_id)
+                    .is_some_and(|events| events.iter().any(|e| e.stage == "board_quorum"));
+                if forming && !already_reached && state.board_quorum_reached(&params.task_id, &eligible) {
+                    let accepted = state.board_members_for(&params.task_id).len();
+                    state.push_task_timeline_event(
+                        &params.task_id,
+                        "board_quorum",
+                        format!("Board quorum reached with {} acceptances", accepted),
+                        None,
+                    );
+                    state.push_log(
+                        LogCategory::Task,
+                        format!("Board quorum reached for task {} ({} accepted)", params.task_id, accepted),
+                    );
+                }
+                let ready = state.record_board_accept(&params, &eligible);
+                let swarm_id = state.swarm_for_task(&params.task_id);
+                drop(state);
+                if let Some(ready) = ready {
+                    self.publish_board_message(
+                        &swarm_id,
+                        &ready.task_id,
+                        ProtocolMethod::BoardReady,
+                        serde_json::to_value(&ready).unwrap_or_default(),
+                    )
+                    .await;
+                }
+            }
+            InboundParams::BoardDecline(params) => {
+                let mut state = self.state.write().await;
+                state.record_board_decline(&params);
+                state.push_log(
+                    LogCategory::Task,
+                    format!("Board decline: {} for task {}", params.agent_id, params.task_id),
+                );
+            }
+            InboundParams::BoardReady(params) => {
+                let mut state = self.state.write().await;
+                let holon = state.active_holons.entry(params.task_id.clone()).or_insert_with(|| {
+                    HolonState {
+                        task_id: params.task_id.clone(),
+                        chair: params.chair_id.clone(),
+                        members: params.members.clone(),
+                        adversarial_critic: params.adversarial_critic.clone(),
+                        depth: 0,
+                        parent_holon: None,
+                        child_holons: Vec::new(),
+                        subtask_assignments: std::collections::HashMap::new(),
+                        child_artifacts: std::collections::HashMap::new(),
+                        status: HolonStatus::Deliberating,
+                        created_at: chrono::Utc::now(),
+                    }
+                });
+                holon.chair = params.chair_id.clone();
+                holon.members = params.members.clone();
+                holon.adversarial_critic = params.adversarial_critic.clone();
+                holon.status = HolonStatus::Deliberating;
+                let agent_id = state.agent_id.to_string();
+                if let (Some(sealed), Some(channel)) = (
+                    params.sealed_keys.get(&agent_id),
+                    state.board_channels.get_mut(&params.task_id),
+                ) {
+                    match channel.keypair.as_ref().map(|keypair| GroupKey::open(sealed, keypair)) {
+                        Some(Ok(group_key)) => channel.group_key = Some(group_key),
+                        Some(Err(e)) => {
+                            tracing::warn!(task_id = %params.task_id, error = %e, "Cannot open board key");
+                        }
+                        None => {}
+                    }
+                }
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Board ready for task {} ({} members, chair={})",
+                        params.task_id, params.members.len(), params.chair_id
+                    ),
+                );
+            }
+            InboundParams::BoardDissolve(params) => {
+                let mut state = self.state.write().await;
+                if let Some(holon) = state.active_holons.get_mut(&params.task_id) {
+                    holon.status = HolonStatus::Done;
+                }
+                state.board_channels.remove(&params.task_id);
+                state.push_log(
+                    LogCategory::Task,
+                    format!("Board dissolved for task {}", params.task_id),
+                );
+            }
+            InboundParams::BoardSealed(params) => {
+                let opened = self
+                    .state
+                    .read()
+                    .await
+                    .board_channels
+                    .get(&params.task_id)
+                    .and_then(|channel| channel.group_key.as_ref())
+                    .map(|group_key| group_key.decrypt(&params));
+                match opened {
+                    // Not on this board: the message is not for us.
+                    None => {
+                        tracing::trace!(task_id = %params.task_id, "Sealed message for another board");
+                    }
+                    Some(Ok(plaintext)) => {
+                        let inner = crate::inbound::decode(topic.to_string(), plaintext, source, self.strict_limits());
+                        let nested = matches!(
+                            &inner.decoded,
+                            Ok(DecodedMessage { params: InboundParams::BoardSealed(_), .. })
+                        );
+                        if nested {
+                            self.state.write().await.push_dead_letter(
+                                topic,
+                                &source.to_string(),
+                                Some(message.method.clone()),
+                                "nested_sealed_message",
+                                data,
+                            );
+                        } else {
+                            Box::pin(self.handle_message(inner)).await;
+                        }
+                    }
+                    Some(Err(e)) => {
+                        self.state.write().await.push_dead_letter(
+                            topic,
+                            &source.to_string(),
+                            Some(message.method.clone()),
+                            format!("undecryptable: {}", e),
+                            data,
+                        );
+                        let _ = self
+                            .network_handle
+                            .report_peer(source, wws_network::PeerOffense::ProtocolViolation)
+                            .await;
+                    }
+                }
+            }
+            InboundParams::DiscussionCritique(params) => {
+                let mut state = self.state.write().await;
+                // Store as deliberation message
+                let msg = DeliberationMessage {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    task_id: params.task_id.clone(),
+                    timestamp: chrono::Utc::now(),
+                    speaker: params.voter_id.clone(),
+                    round: params.round,
+                    message_type: DeliberationType::CritiqueFeedback,
+                    content: params.content.clone(),
+                    referenced_plan_id: None,
+                    critic_scores: Some(params.plan_scores.clone()),
+                    hlc: Some(hlc::global().now()),
+                };
+                state.deliberation_messages
+                    .entry(params.task_id.clone())
+                    .or_default()
+                    .push(msg);
+                // Also record in the rfp coordinator
+                if let Some(rfp) = state.rfp_coordinators.get_mut(&params.task_id) {
+                    let _ = rfp.record_critique(
+                        params.voter_id.clone(),
+                        params.plan_scores.clone(),
+                        params.content.clone(),
+                    );
+                }
+                state.apply_malicious_flags(&params.task_id, params.voter_id.as_str(), &params.flagged_malicious);
+                // Update holon status to Voting after critique
+                if let Some(holon) = state.active_holons.get_mut(&params.task_id) {
+                    if matches!(holon.status, HolonStatus::Deliberating) {
+                        holon.status = HolonStatus::Voting;
+                    }
+                }
+                state.push_log(
+                    LogCategory::Vote,
+                    format!(
+                        "Critique from {} for task {} (round {}, {} plan scores)",
+                        params.voter_id, params.task_id, params.round, params.plan_scores.len()
+                    ),
+                );
+            }
+            InboundParams::ProposalRevision(params) => {
+                let mut state = self.state.write().await;
+                state.mark_member_seen(params.plan.proposer.as_str());
+                if let Err(e) = state.apply_plan_revision(&params) {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!(
+                            "Ignoring plan revision for task {} from {}: {}",
+                            params.task_id, params.plan.proposer, e
+                        ),
+                    );
+                }
+            }
+            InboundParams::ProposalWithdraw(params) => {
+                let mut state = self.state.write().await;
+                state.mark_member_seen(params.proposer.as_str());
+                if let Err(e) = state.apply_plan_withdrawal(&params) {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!(
+                            "Ignoring plan withdrawal for task {} from {}: {}",
+                            params.task_id, params.proposer, e
+                        ),
+                    );
+                }
+            }
+            InboundParams::AgentDirectMessage(params) => {
+                // recipient_did is advisory — all nodes store all messages on this topic;
+                // agents filter by recipient_did when reading /api/messages.
+                let dm = DirectMessage {
+                    id: params.message_id.clone(),
+                    sender_did: params.sender_did.clone(),
+                    recipient_did: params.recipient_did.clone(),
+                    content: params.content.clone(),
+                    message_type: MessageType::from(params.message_type.as_str()),
+                    timestamp: chrono::Utc::now(),
+                };
+                let mut state = self.state.write().await;
+                state.push_log(
+                    LogCategory::Message,
+                    format!(
+                        "Direct message from {}: {}",
+                        params.sender_did, params.content
+                    ),
+                );
+                state.push_direct_message(dm);
+            }
+            InboundParams::NameClaim(params) => {
+                let mut state = self.state.write().await;
+                let my_did = state.agent_id.to_string();
+                let rival = params.did != my_did;
+                let name = params.name.clone();
+                match state.record_name_claim(params) {
+                    // Answer a new rival with our own claim, so it learns
+                    // the name is disputed.
+                    Ok(true) if rival => {
+                        let own = state.name_directory.claim_of(&name, &my_did).cloned();
+                        drop(state);
+                        if let Some(own) = own.filter(|c| c.expires_at > crate::names::unix_now()) {
+                            crate::names::publish_claim(&self.network_handle, &own).await;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(reason) => tracing::debug!(reason = %reason, "Ignoring name claim"),
+                }
+            }
+            InboundParams::NameDisputeVote(params) => {
+                if let Err(reason) = self.state.write().await.record_name_vote(params) {
+                    tracing::debug!(reason = %reason, "Ignoring name dispute vote");
+                }
+            }
+            InboundParams::Unhandled(_) => {
+                tracing::debug!(
+                    method = %message.method,
+                    topic = %topic,
+                    "Unhandled protocol message"
+                );
+            }
+        }
+    }
+
+    /// Issue fresh DID documents for this node and its local agents, keep
+    /// them in the directory and publish them to the DHT.
+    async fn publish_did_documents(&self) {
+        let documents = {
+            let mut state = self.state.write().await;
+            state.did_republish_due = false;
+            let services = state
+                .agent_services
+                .get(state.agent_id.as_str())
+                .map(|a| a.services.clone())
+                .unwrap_or_default();
+            let mut documents = vec![crate::did::own_document(
+                state.agent_id.as_str(),
+                &state.identity_key,
+                &self.network_handle.local_peer_id(),
+                self.config.identity.wws_name.as_deref(),
+                &services,
+                self.config.agent.capabilities.clone(),
+            )];
+            documents.extend(state.local_agents.agents().map(|agent| agent.did_document(Vec::new())));
+            for document in &documents {
+                state.did_documents.insert(document.clone());
+            }
+            documents
+        };
+        for document in &documents {
+            crate::did::publish(&self.network_handle, document).await;
+        }
+    }
+
+    /// Announce this node's swarm to the network via GossipSub.
+    ///
+    /// Periodically broadcasts a SwarmAnnounce message on the global
+    /// swarm discovery topic and the swarm-specific announcement topic.
+    /// Also publishes the swarm info to the Kademlia DHT for internet-wide
+    /// discovery.
+    async fn announce_swarm(&self) {
+        let state = self.state.read().await;
+        let staleness = Duration::from_secs(self.config.hierarchy.keepalive_interval_secs.saturating_mul(3).max(30));
+        let agent_count = state.active_member_count(staleness) as u64;
+        let params = SwarmAnnounceParams {
+            swarm_id: state.current_swarm_id.clone(),
+            name: self.config.swarm.name.clone(),
+            is_public: self.config.swarm.is_public,
+            agent_id: state.agent_id.clone(),
+            agent_count,
+            description: String::new(),
+            timestamp: chrono::Utc::now(),
+            addrs: self.dialable_external_addrs(),
+        };
+        drop(state);
+
+        let msg = SwarmMessage::new(
+            ProtocolMethod::SwarmAnnounce.as_str(),
+            serde_json::to_value(&params).unwrap_or_default(),
+            String::new(),
+        );
+
+        if let Ok(data) = serde_json::to_vec(&msg) {
+            // Publish to the global discovery topic.
+            let discovery_topic = SwarmTopics::swarm_discovery();
+            if let Err(e) = self.network_handle.publish(&discovery_topic, data.clone()).await {
+                tracing::debug!(error = %e, "Failed to publish swarm announcement to discovery topic");
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic: discovery_topic.clone(),
+                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
+                    task_id: None,
+                    size_bytes: data.len(),
+                    outcome: format!("error: {}", e),
+                    payload: None,
+                });
+            } else {
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic: discovery_topic.clone(),
+                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
+                    task_id: None,
+                    size_bytes: data.len(),
+                    outcome: "published".to_string(),
+                    payload: None,
+                });
+            }
+
+            // Publish to the swarm-specific announcement topic.
+            let announce_topic = SwarmTopics::swarm_announce(params.swarm_id.as_str());
+            if let Err(e) = self.network_handle.publish(&announce_topic, data).await {
+                tracing::debug!(error = %e, "Failed to publish swarm announcement to swarm topic");
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic: announce_topic,
+                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
+                    task_id: None,
+                    size_bytes: 0,
+                    outcome: format!("error: {}", e),
+                    payload: None,
+                });
+            } else {
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic: announce_topic,
+                    method: Some(ProtocolMethod::SwarmAnnounce.as_str().to_string()),
+                    task_id: None,
+                    size_bytes: 0,
+                    outcome: "published".to_string(),
+                    payload: None,
+                });
+            }
+        }
+
+        // Also register in DHT for internet-wide discovery.
+        let dht_key = format!(
+            "{}{}",
+            wws_protocol::SWARM_REGISTRY_PREFIX,
+            params.swarm_id
+        );
+        let dht_value = serde_json::json!({
+            "swarm_id": params.swarm_id.as_str(),
+            "name": params.name,
+            "is_public": params.is_public,
+            "agent_count": params.agent_count,
+            "timestamp": params.timestamp.to_rfc3339(),
+            "addrs": params.addrs,
+        });
+        if let Ok(value_bytes) = serde_json::to_vec(&dht_value) {
+            if let Err(e) = self
+                .network_handle
+                .put_dht_record(dht_key.into_bytes(), value_bytes)
+                .await
+            {
+                tracing::debug!(error = %e, "Failed to publish swarm info to DHT");
+            }
+        }
+
+        // The parameter authority republishes the record so members that
+        // joined since the last change pick it up.
+        let swarm_params = {
+            let state = self.state.read().await;
+            state
+                .swarm_params
+                .clone()
+                .filter(|_| state.is_params_authority())
+        };
+        if let Some(swarm_params) = swarm_params {
+            crate::swarm_params::publish_swarm_params(&self.network_handle, &swarm_params).await;
+        }
+    }
+
+    /// Publish a `swarm.rekey` on the swarm's keep-alive topic.
+    async fn publish_rekey(&self, rekey: &EpochRekeyParams) {
+        let msg = SwarmMessage::new(
+            ProtocolMethod::EpochRekey.as_str(),
+            serde_json::to_value(rekey).unwrap_or_default(),
+            String::new(),
+        );
+        let Ok(data) = serde_json::to_vec(&msg) else {
+            return;
+        };
+        let topic = SwarmTopics::keepalive_for(rekey.swarm_id.as_str());
+        if let Err(e) = self.network_handle.publish(&topic, data).await {
+            tracing::warn!(error = %e, epoch = rekey.epoch, "Failed to publish rekey");
+        }
+    }
+
+    /// Send a keep-alive message to every joined swarm.
+    async fn send_keepalive(&self) {
+        let state = self.state.read().await;
+        let swarm_ids = state.joined_swarm_ids();
+        let self_id = state.agent_id.to_string();
+        let (cpu_headroom, mem_headroom) = crate::load::host_headroom();
+        let params = KeepAliveParams {
+            agent_id: state.agent_id.clone(),
+            agent_name: Some(self.config.agent.name.clone()),
+            last_task_poll_at: state.member_last_task_poll.get(&self_id).cloned(),
+            last_result_at: state.member_last_result.get(&self_id).cloned(),
+            load: Some(AgentLoad {
+                cpu_headroom,
+                mem_headroom,
+                ..state.local_load(&self_id)
+            }),
+            role: state.agent_roles.get(&self_id).copied(),
+            epoch: state.epoch_manager.current_epoch(),
+            epoch_duration_secs: Some(state.epoch_manager.epoch_duration_secs()),
+            topic_key: state.topic_key_id(),
+            lineage: Some(state.merge.lineage().clone()),
+            services: state.agent_services.get(&self_id).map(|a| a.services.clone()),
+            timestamp: chrono::Utc::now(),
+        };
+        drop(state);
+
+        let msg = SwarmMessage::new(
+            ProtocolMethod::KeepAlive.as_str(),
+            serde_json::to_value(&params).unwrap_or_default(),
+            String::new(), // Signature would be computed in production.
+        );
+
+        let Ok(data) = serde_json::to_vec(&msg) else {
+            return;
+        };
+        for swarm_id in swarm_ids {
+            let topic = SwarmTopics::keepalive_for(&swarm_id);
+            if let Err(e) = self.network_handle.publish(&topic, data.clone()).await {
+                tracing::debug!(error = %e, "Failed to send keepalive");
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic,
+                    method: Some(ProtocolMethod::KeepAlive.as_str().to_string()),
+                    task_id: None,
+                    size_bytes: 0,
+                    outcome: format!("error: {}", e),
+                    payload: None,
+                });
+            } else {
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic,
+                    method: Some(ProtocolMethod::KeepAlive.as_str().to_string()),
+                    task_id: None,
+                    size_bytes: 0,
+                    outcome: "published".to_string(),
+                    payload: None,
+                });
+            }
+        }
+
+        self.send_local_agent_keepalives().await;
+    }
+
+    /// Ask the parent of each idle local executor for a task queued at a
+    /// sibling.
+    async fn request_work_for_idle_executors(&self) {
+        let (swarm_id, messages) = {
+            let mut state = self.state.write().await;
+            let now = chrono::Utc::now();
+            let idle = state.idle_executors(now);
+            let mut messages = Vec::with_capacity(idle.len());
+            for (agent_id, parent_id) in idle {
+                state.work_stealing.last_request.insert(agent_id.to_string(), now);
+                let params = WorkStealRequestParams {
+                    agent_id: agent_id.clone(),
+                    parent_id,
+                    timestamp: now,
+                };
+                messages.push(state.message_as(
+                    &agent_id,
+                    ProtocolMethod::WorkStealRequest.as_str(),
+                    serde_json::to_value(&params).unwrap_or_default(),
+                ));
+            }
+            (state.current_swarm_id.as_str().to_string(), messages)
+        };
+
+        let topic = SwarmTopics::hierarchy_for(&swarm_id);
+        for msg in messages {
+            if let Ok(data) = serde_json::to_vec(&msg) {
+                if let Err(e) = self.network_handle.publish(&topic, data).await {
+                    tracing::debug!(error = %e, "Failed to send work steal request");
+                }
+            }
+        }
+    }
+
+    /// Send a signed `agent.keepalive` for every local agent, so each one
+    /// counts as a swarm member of its own.
+    async fn send_local_agent_keepalives(&self) {
+        let (swarm_id, messages) = {
+            let mut state = self.state.write().await;
+            if state.local_agents.is_empty() {
+                return;
+            }
+            let epoch = state.epoch_manager.current_epoch();
+            let (cpu_headroom, mem_headroom) = crate::load::host_headroom();
+            let agents: Vec<(AgentId, String)> = state
+                .local_agents
+                .agents()
+                .map(|a| (a.agent_id.clone(), a.name.clone()))
+                .collect();
+            let mut messages = Vec::with_capacity(agents.len());
+            for (agent_id, name) in agents {
+                state.mark_member_seen_with_name(agent_id.as_str(), Some(&name));
+                let params = KeepAliveParams {
+                    last_task_poll_at: state.member_last_task_poll.get(agent_id.as_str()).cloned(),
+                    last_result_at: state.member_last_result.get(agent_id.as_str()).cloned(),
+                    load: Some(AgentLoad {
+                        cpu_headroom,
+                        mem_headroom,
+                        ..state.local_load(agent_id.as_str())
+                    }),
+                    role: state.agent_roles.get(agent_id.as_str()).copied(),
+                    agent_id: agent_id.clone(),
+                    agent_name: Some(name),
+                    epoch,
+                    epoch_duration_secs: None,
+                    topic_key: None,
+                    lineage: None,
+                    services: None,
+                    timestamp: chrono::Utc::now(),
+                };
+                messages.push(state.message_as(
+                    &agent_id,
+                    ProtocolMethod::AgentKeepAlive.as_str(),
+                    serde_json::to_value(&params).unwrap_or_default(),
+                ));
+            }
+            (state.current_swarm_id.as_str().to_string(), messages)
+        };
+
+        let topic = SwarmTopics::keepalive_for(&swarm_id);
+        for msg in messages {
+            if let Ok(data) = serde_json::to_vec(&msg) {
+                if let Err(e) = self.network_handle.publish(&topic, data).await {
+                    tracing::debug!(error = %e, "Failed to send local agent keepalive");
+                }
+            }
+        }
+    }
+
+    /// Check for epoch transitions and trigger elections if needed.
+    async fn check_epoch_transition(&self) {
+        let swarm_size = self
+            .network_handle
+            .estimated_swarm_size()
+            .await
+            .unwrap_or(1);
+
+        let now = chrono::Utc::now();
+        let mut outbound: Vec<(ProtocolMethod, serde_json::Value)> = Vec::new();
+        let mut rekey: Option<EpochRekeyParams> = None;
+        let mut state = self.state.write().await;
+        let stale_ttl = Duration::from_secs(self.config.hierarchy.keepalive_interval_secs.saturating_mul(3).max(30));
+        state.prune_stale_members(stale_ttl);
+        state.network_stats.total_agents = state.active_member_count(stale_ttl) as u64;
+        if let Some(action) = state.epoch_manager.tick(swarm_size) {
+            match action {
+                wws_hierarchy::epoch::EpochAction::TriggerElection {
+                    new_epoch,
+                    estimated_swarm_size,
+                } => {
+                    tracing::info!(
+                        new_epoch,
+                        swarm_size = estimated_swarm_size,
+                        "Triggering new epoch election"
+                    );
+                    // Recompute pyramid layout.
+                    if let Ok(layout) = state.pyramid.compute_layout(estimated_swarm_size) {
+                        state.network_stats.hierarchy_depth = layout.depth;
+                    }
+                    // Initialize election for new epoch.
+                    let election_config = wws_hierarchy::elections::ElectionConfig::default();
+                    state.election = Some(ElectionManager::new(election_config, new_epoch));
+                    state.status = ConnectorStatus::InElection;
+                    state.election_vote_due =
+                        deadline_after(now, self.config.election.vote_delay_secs);
+                    let epoch_duration_secs = state.epoch_manager.epoch_duration_secs();
+                    if let Some(candidacy) = state.election_candidacy(
+                        &self.config.election,
+                        &self.config.agent.capabilities,
+                        epoch_duration_secs,
+                        now,
+                    ) {
+                        state.push_log(
+                            LogCategory::Epoch,
+                            format!(
+                                "Standing for Tier-1 in epoch {} (score {:.2})",
+                                new_epoch,
+                                candidacy.score.composite_score()
+                            ),
+                        );
+                        outbound.push((
+                            ProtocolMethod::Candidacy,
+                            serde_json::to_value(&candidacy).unwrap_or_default(),
+                        ));
+                    }
+                    state.push_log(
+                        LogCategory::Epoch,
+                        format!("Epoch {} election triggered (swarm size: {})", new_epoch, estimated_swarm_size),
+                    );
+                    state.webhooks.notify(
+                        WebhookEvent::EpochChanged,
+                        serde_json::json!({
+                            "epoch": new_epoch,
+                            "swarm_size": estimated_swarm_size,
+                            "duration_secs": state.epoch_manager.next_duration_secs(estimated_swarm_size),
+                        }),
+                    );
+                }
+                wws_hierarchy::epoch::EpochAction::FinalizeTransition { epoch: previous } => {
+                    tracing::info!(epoch = previous, "Finalizing epoch transition");
+                    let leaders = state
+                        .election
+                        .as_mut()
+                        .and_then(|election| match election.result() {
+                            Some(result) => Some(result.leaders.clone()),
+                            None => election.tally_and_elect().ok().map(|r| r.leaders),
+                        })
+                        .unwrap_or_default();
+                    let churn = state.epoch_manager.churn_rate(swarm_size);
+                    let leader_failures = state.epoch_manager.current_info().leader_failures;
+                    state.epoch_manager.advance_epoch(leaders, swarm_size);
+                    let epoch = state.epoch_manager.current_epoch();
+                    state.status = ConnectorStatus::Running;
+                    if state.rekey_issuer().as_deref() == Some(state.agent_id.as_str()) {
+                        rekey = state.issue_rekey();
+                    }
+                    let eligible: std::collections::HashMap<String, (u32, Vec<String>)> = state
+                        .in_flight_rounds()
+                        .into_iter()
+                        .map(|task_id| {
+                            let tier_and_members = Self::quorum_eligible_for_task(&state, &task_id);
+                            (task_id, tier_and_members)
+                        })
+                        .collect();
+                    let outcomes = state.hand_off_rounds(epoch, &eligible);
+                    if !outcomes.is_empty() {
+                        let restarted = outcomes
+                            .iter()
+                            .filter(|(_, o)| *o == HandoffOutcome::Restarted)
+                            .count();
+                        state.push_log(
+                            LogCategory::Epoch,
+                            format!(
+                                "Epoch {} handoff: {} rounds migrated, {} restarted",
+                                epoch,
+                                outcomes.len() - restarted,
+                                restarted
+                            ),
+                        );
+                    }
+                    let compacted = state.compact_tombstones();
+                    if compacted > 0 {
+                        state.push_log(
+                            LogCategory::System,
+                            format!("Compacted {} CRDT tombstones at epoch {}", compacted, epoch),
+                        );
+                    }
+                    let duration_secs = state.epoch_manager.epoch_duration_secs();
+                    state.push_log(
+                        LogCategory::Epoch,
+                        format!(
+                            "Epoch {} transition finalized: {}s long (churn {:.0}%, {} leader failures in epoch {})",
+                            epoch,
+                            duration_secs,
+                            churn * 100.0,
+                            leader_failures,
+                            previous
+                        ),
+                    );
+                }
+            }
+        }
+        if let Some(vote) =
+            state.due_election_vote(&self.config.election, &self.config.agent.capabilities, now)
+        {
+            state.push_log(
+                LogCategory::Epoch,
+                format!(
+                    "Voted in epoch {} election: {}",
+                    vote.epoch,
+                    vote.candidate_rankings
+                        .iter()
+                        .map(|a| a.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" > ")
+                ),
+            );
+            outbound.push((
+                ProtocolMethod::ElectionVote,
+                serde_json::to_value(&vote).unwrap_or_default(),
+            ));
+        }
+        // A private swarm with no key yet, e.g. on first start, gets one
+        // from its expected issuer once members had time to announce
+        // themselves.
+        let settle = chrono::Duration::seconds(
+            self.config.hierarchy.keepalive_interval_secs.saturating_mul(2) as i64,
+        );
+        if rekey.is_none()
+            && state.topic_keys.is_some()
+            && state.topic_key_id().is_none()
+            && now - state.start_time >= settle
+            && state.rekey_issuer().as_deref() == Some(state.agent_id.as_str())
+        {
+            rekey = state.issue_rekey();
+        }
+        let topic = SwarmTopics::election_tier1_for(state.current_swarm_id.as_str());
+        drop(state);
+
+        if let Some(rekey) = rekey {
+            self.publish_rekey(&rekey).await;
+        }
+        for (method, params) in outbound {
+            let msg = SwarmMessage::new(method.as_str(), params, String::new());
+            if let Ok(data) = serde_json::to_vec(&msg) {
+                if let Err(e) = self.network_handle.publish(&topic, data).await {
+                    tracing::debug!(error = %e, method = method.as_str(), "Failed to publish election message");
+                }
+            }
+        }
+    }
+
+    /// Check if any voting engines have reached quorum and run IRV.
+    async fn check_voting_completion(&self) {
+        let mut state = self.state.write().await;
+        // Rounds are frozen while an epoch transition is in progress; the
+        // handoff at finalization migrates or restarts them.
+        if state.epoch_manager.is_transitioning() {
+            return;
+        }
+        let mut completed_votes = Vec::new();
+        let mut assignments_to_run: Vec<(String, String)> = Vec::new();
+
+        // Collect voting results first (to avoid borrow issues)
+        let mut results_to_process = Vec::new();
+
+        let task_ids: Vec<String> = state.voting_engines.keys().cloned().collect();
+        let mut pending_logs: Vec<String> = Vec::new();
+        // Reveals of blind ballots cast here, published once commitments close.
+        let mut reveals_to_publish: Vec<(String, Vec<u8>)> = Vec::new();
+
+        for task_id in task_ids {
+            let mut single_proposal_id: Option<String> = None;
+            if let Some(proposal_owners) = state.rfp_coordinators.get(&task_id).map(|rfp| {
+                rfp.reveals
+                    .values()
+                    .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
+                    .collect::<std::collections::HashMap<String, AgentId>>()
+            }) {
+                if proposal_owners.len() == 1 {
+                    single_proposal_id = proposal_owners.keys().next().cloned();
+                }
+                if !proposal_owners.is_empty() {
+                    if let Some(v) = state.voting_engines.get_mut(&task_id) {
+                        v.set_proposals(proposal_owners);
+                    }
+                }
+            }
+
+            let (tier_level, eligible) = Self::quorum_eligible_for_task(&state, &task_id);
+            let requirement = state.quorum_requirement(&task_id, tier_level, &eligible);
+            state
+                .task_vote_requirements
+                .insert(task_id.clone(), requirement.clone());
+
+            let (ballot_count, proposal_count) = match state.voting_engines.get(&task_id) {
+                Some(v) => (v.ballot_count(), v.proposal_count()),
+                None => continue,
+            };
+            let mut expected_votes = requirement.expected_voters.max(1);
+            let mut expected_proposals = requirement.expected_proposers.max(1);
+            let mut votes_timed_out = false;
+
+            if let Some(age_secs) = state.round_age_secs(&task_id, chrono::Utc::now()) {
+                if age_secs >= state.proposal_stage_timeout_secs() {
+                    // Force-advance RFP from CommitPhase if P2P commits didn't arrive in time.
+                    // This ensures the local proposal can proceed to voting even without full quorum.
+                    let pending_reveals = state.pending_plan_reveals.remove(&task_id).unwrap_or_default();
+                    let mut mismatched_proposers = Vec::new();
+                    if let Some(rfp) = state.rfp_coordinators.get_mut(&task_id) {
+                        if matches!(rfp.phase(), wws_consensus::rfp::RfpPhase::CommitPhase) {
+                            if rfp.commit_count() > 0 {
+                                let _ = rfp.transition_to_reveal();
+                                // Flush any pending reveals that arrived while in CommitPhase
+                                let mut pending_sorted: Vec<(String, wws_protocol::Plan)> =
+                                    pending_reveals.into_iter().collect();
+                                pending_sorted.sort_by(|a, b| a.0.cmp(&b.0));
+                                for (_, plan) in pending_sorted {
+                                    let reveal = wws_protocol::messages::ProposalRevealParams {
+                                        task_id: task_id.clone(),
+                                        plan,
+                                    };
+                                    if let Err(ConsensusError::HashMismatch { .. }) = rfp.record_reveal(&reveal) {
+                                        mismatched_proposers.push(reveal.plan.proposer.to_string());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    for proposer in mismatched_proposers {
+                        state.slash_proposal_bond(&task_id, &proposer, SlashReason::HashMismatch);
+                    }
+                    // Re-sync voting engine with proposals now that reveals may have been processed
+                    if let Some(proposal_owners) = state.rfp_coordinators.get(&task_id).map(|rfp| {
+                        rfp.reveals
+                            .values()
+                            .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
+                            .collect::<std::collections::HashMap<String, AgentId>>()
+                    }) {
+                        if !proposal_owners.is_empty() {
+                            if let Some(v) = state.voting_engines.get_mut(&task_id) {
+                                v.set_proposals(proposal_owners);
+                            }
+                        }
+                    }
+                    // Recalculate proposal_count after potential forced reveal
+                    let proposal_count_now = state.voting_engines.get(&task_id).map(|v| v.proposal_count()).unwrap_or(0);
+                    expected_proposals = expected_proposals.min(proposal_count_now.max(1));
+                }
+                if age_secs >= state.voting_stage_timeout_secs() {
+                    expected_votes = expected_votes.min(ballot_count.max(1));
+                    votes_timed_out = true;
+                }
+            }
+
+            // Recalculate proposal_count (may have changed due to forced reveal above)
+            let (ballot_count, proposal_count) = match state.voting_engines.get(&task_id) {
+                Some(v) => (v.ballot_count(), v.proposal_count()),
+                None => continue,
+            };
+
+            // Strict participation gate: all expected tier members must propose and vote.
+            let task_age_secs = state
+                .round_age_secs(&task_id, chrono::Utc::now())
+                .unwrap_or(0);
+
+            if ballot_count == 0
+                && proposal_count == 1
+                && task_age_secs >= state.voting_stage_timeout_secs()
+            {
+                if let Some(winner) = single_proposal_id {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!(
+                            "Voting timeout for task {}; selecting sole proposal {}",
+                            task_id, winner
+                        ),
+                    );
+                    state.push_task_timeline_event(
+                        &task_id,
+                        "plan_selected",
+                        format!("Sole proposal {} selected after voting timeout", winner),
+                        None,
+                    );
+                    if let Some(task) = state.task_details.get_mut(&task_id) {
+                        task.status = TaskStatus::InProgress;
+                    }
+                    assignments_to_run.push((task_id.clone(), winner));
+                    completed_votes.push(task_id.clone());
+                    continue;
+                }
+            }
+
+            // Weighted policies care about who voted, not just how many.
+            let voters: Vec<String> = state
+                .voting_engines
+                .get(&task_id)
+                .map(|v| v.ballots.iter().map(|b| b.voter.to_string()).collect())
+                .unwrap_or_default();
+            let voter_quorum_met = votes_timed_out || state.quorum_met(&task_id, &eligible, &voters);
+
+            // Blind ballots: commitments close once they reach quorum (or the
+            // voting timeout), then the tally waits for every reveal up to the
+            // reveal deadline. Ballots not revealed by then are not counted.
+            let mut blind_tally_due = false;
+            let blind = state
+                .voting_engines
+                .get(&task_id)
+                .filter(|v| v.is_blind())
+                .map(|v| (v.voter_ids_for_debug(), v.commitments_closed_at(), v.unrevealed_voters()));
+            if let Some((committed, closed_at, unrevealed)) = blind {
+                let now = chrono::Utc::now();
+                match closed_at {
+                    None => {
+                        let commitment_quorum_met =
+                            votes_timed_out || state.quorum_met(&task_id, &eligible, &committed);
+                        if proposal_count < expected_proposals
+                            || committed.len() < expected_votes
+                            || !commitment_quorum_met
+                        {
+                            pending_logs.push(format!(
+                                "Voting pending for task {}: proposals {}/{} blind ballots {}/{}",
+                                task_id, proposal_count, expected_proposals, committed.len(), expected_votes
+                            ));
+                            continue;
+                        }
+                        let own_reveals = state.pending_vote_reveals.remove(&task_id).unwrap_or_default();
+                        if let Some(v) = state.voting_engines.get_mut(&task_id) {
+                            v.close_commitments(now);
+                            for reveal in &own_reveals {
+                                let vote = RankedVote {
+                                    voter: reveal.voter.clone(),
+                                    task_id: reveal.task_id.clone(),
+                                    epoch: reveal.epoch,
+                                    rankings: reveal.rankings.clone(),
+                                    critic_scores: reveal.critic_scores.clone(),
+                                    abstain: reveal.abstain,
+                                };
+                                if let Err(e) = v.record_reveal(vote, &reveal.salt) {
+                                    tracing::warn!(error = %e, task_id = %task_id, "Failed to record own ballot reveal");
+                                }
+                            }
+                        }
+                        let topic = SwarmTopics::voting_for(&state.swarm_for_task(&task_id), &task_id);
+                        for reveal in own_reveals {
+                            let msg = state.message_as(
+                                &reveal.voter,
+                                ProtocolMethod::ConsensusVoteReveal.as_str(),
+                                serde_json::to_value(&reveal).unwrap_or_default(),
+                            );
+                            if let Ok(data) = serde_json::to_vec(&msg) {
+                                reveals_to_publish.push((topic.clone(), data));
+                            }
+                            if let Some(receipt) = state.issue_ballot_receipt(&task_id, &reveal.voter, reveal.epoch) {
+                                let msg = SwarmMessage::new(
+                                    ProtocolMethod::BallotReceipt.as_str(),
+                                    serde_json::to_value(&receipt).unwrap_or_default(),
+                                    String::new(),
+                                );
+                                if let Ok(data) = serde_json::to_vec(&msg) {
+                                    reveals_to_publish.push((topic.clone(), data));
+                                }
+                            }
+                        }
+                        state.push_task_timeline_event(
+                            &task_id,
+                            "votes_closed",
+                            format!("{} blind ballots committed; revealing", committed.len()),
+                            None,
+                        );
+                        state.push_log(
+                            LogCategory::Vote,
+                            format!(
+                                "Blind ballots closed for task {} with {} commitments; revealing",
+                                task_id,
+                                committed.len()
+                            ),
+                        );
+                        continue;
+                    }
+                    Some(closed_at) => {
+                        if !unrevealed.is_empty() {
+                            if (now - closed_at).num_seconds() < state.reveal_stage_timeout_secs() {
+                                pending_logs.push(format!(
+                                    "Voting pending for task {}: blind ballots revealed {}/{}",
+                                    task_id,
+                                    committed.len() - unrevealed.len(),
+                                    committed.len()
+                                ));
+                                continue;
+                            }
+                            state.push_log(
+                                LogCategory::Vote,
+                                format!(
+                                    "Reveal deadline passed for task {}; not counting ballots of {}",
+                                    task_id,
+                                    unrevealed.iter().map(|v| v.as_str()).collect::<Vec<_>>().join(", ")
+                                ),
+                            );
+                        }
+                        blind_tally_due = true;
+                    }
+                }
+            }
+
+            if blind_tally_due
+                || (proposal_count >= expected_proposals && ballot_count >= expected_votes && voter_quorum_met)
+            {
+                tracing::info!(
+                    task_id = %task_id,
+                    ballot_count,
+                    proposal_count,
+                    expected_votes,
+                    expected_proposals,
+                    "Voting quorum reached, running IRV"
+                );
+
+                // Run Instant Runoff Voting to select winner
+                let irv_result = {
+                    let chair = state.active_holons.get(&task_id).map(|h| h.chair.clone());
+                    let voting_engine = match state.voting_engines.get_mut(&task_id) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    if let Some(chair) = chair {
+                        voting_engine.set_chair(chair);
+                    }
+                    let result = voting_engine.run_irv();
+                    // Persist IRV rounds for API visibility
+                    let rounds = voting_engine.irv_rounds().to_vec();
+                    // Blind ballots become visible once tallied.
+                    let revealed = if voting_engine.is_blind() && result.is_ok() {
+                        voting_engine.ballots.clone()
+                    } else {
+                        Vec::new()
+                    };
+                    (result, rounds, revealed)
+                };
+
+                let (irv_result, irv_rounds, revealed) = irv_result;
+                // Persist IRV rounds to state
+                if !irv_rounds.is_empty() {
+                    state.irv_rounds.insert(task_id.clone(), irv_rounds);
+                }
+                let now = chrono::Utc::now();
+                for ballot in revealed {
+                    state.ballot_records.entry(task_id.clone()).or_default().push(BallotRecord {
+                        task_id: task_id.clone(),
+                        voter: ballot.voter,
+                        rankings: ballot.original_rankings,
+                        critic_scores: ballot.critic_scores,
+                        timestamp: now,
+                        irv_round_when_eliminated: None,
+                        abstain: ballot.abstain,
+                    });
+                }
+
+                match irv_result {
+                    Ok(result) => {
+                        results_to_process.push((task_id.clone(), Ok(result)));
+                        completed_votes.push(task_id.clone());
+                    }
+                    Err(e) => {
+                        results_to_process.push((task_id.clone(), Err(e)));
+                    }
+                }
+            } else {
+                pending_logs.push(format!(
+                    "Voting pending for task {}: proposals {}/{} votes {}/{}",
+                    task_id, proposal_count, expected_proposals, ballot_count, expected_votes
+                ));
+            }
+        }
+
+        for msg in pending_logs {
+            state.push_log(LogCategory::Vote, msg);
+        }
+
+        // Process results (now we can mutably borrow state again)
+        for (task_id, result) in results_to_process {
+            match result {
+                Ok(voting_result) => {
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!(
+                            "Voting complete for task {}: winner = {} ({} rounds, {} votes)",
+                            task_id, voting_result.winner, voting_result.rounds, voting_result.total_votes
+                        ),
+                    );
+
+                    state.push_task_timeline_event(
+                        &task_id,
+                        "plan_selected",
+                        format!("Plan {} selected by IRV after {} rounds", voting_result.winner, voting_result.rounds),
+                        None,
+                    );
+
+                    // Update task status to InProgress
+                    if let Some(task) = state.task_details.get_mut(&task_id) {
+                        task.status = TaskStatus::InProgress;
+                    }
+
+                    // Update holon status to Executing
+                    if let Some(holon) = state.active_holons.get_mut(&task_id) {
+                        holon.status = HolonStatus::Executing;
+                    }
+
+                    tracing::info!(
+                        task_id = %task_id,
+                        winner = %voting_result.winner,
+                        rounds = voting_result.rounds,
+                        "Voting completed successfully"
+                    );
+
+                    assignments_to_run.push((task_id.clone(), voting_result.winner.clone()));
+                }
+                Err(e) => {
+                    tracing::error!(
+                        error = %e,
+                        task_id = %task_id,
+                        "IRV execution failed"
+                    );
+                    state.push_log(
+                        LogCategory::Vote,
+                        format!("Voting failed for task {}: {}", task_id, e),
+                    );
+                }
+            }
+        }
+
+        // Remove completed voting engines
+        for task_id in completed_votes {
+            state.voting_engines.remove(&task_id);
+            state.proposal_bonds.release(&task_id);
+            state.task_vote_requirements.remove(&task_id);
+            state.pending_vote_reveals.remove(&task_id);
+            state.voting_windows.remove(&task_id);
+        }
+
+        // Tasks flagged for human sign-off wait here instead of being assigned.
+        let mut ready_assignments = Vec::new();
+        for (task_id, winner_plan_id) in assignments_to_run {
+            let requires_approval = state
+                .task_details
+                .get(&task_id)
+                .map(|t| t.requires_approval)
+                .unwrap_or(false);
+            if requires_approval {
+                state.hold_plan_for_approval(&task_id, &winner_plan_id);
+            } else {
+                ready_assignments.push((task_id, winner_plan_id));
+            }
+        }
+        ready_assignments.extend(state.take_approved_plans(chrono::Utc::now()));
+
+        drop(state);
+
+        for (topic, data) in reveals_to_publish {
+            if let Err(e) = self.network_handle.publish(&topic, data).await {
+                tracing::warn!(error = %e, "Failed to publish ballot reveal");
+            }
+        }
+
+        for (task_id, winner_plan_id) in ready_assignments {
+            if let Err(e) = self.assign_subtasks_from_winner(&task_id, &winner_plan_id).await {
+                tracing::error!(
+                    task_id = %task_id,
+                    winner = %winner_plan_id,
+                    error = %e,
+                    "Failed to assign subtasks after voting"
+                );
+            }
+        }
+    }
+
+    /// Inject a task for every schedule whose fire time has passed.
+    async fn run_due_schedules(&self) {
+        let due = {
+            let mut state = self.state.write().await;
+            if state.scheduler.is_empty() {
+                return;
+            }
+            state.scheduler.take_due(chrono::Utc::now())
+        };
+
+        for (schedule_id, template) in due {
+            let response = crate::rpc_server::handle_inject_task(
+                None,
+                &template.to_inject_params(),
+                &self.state,
+                &self.network_handle,
+            )
+            .await;
+
+            let mut state = self.state.write().await;
+            match response
+                .result
+                .as_ref()
+                .and_then(|r| r.get("task_id"))
+                .and_then(|v| v.as_str())
+            {
+                Some(task_id) => {
+                    state.push_log(
+                        LogCategory::Task,
+                        format!("Schedule {} injected task {}", schedule_id, task_id),
+                    );
+                    state.scheduler.record_injection(&schedule_id, task_id.to_string());
+                }
+                None => {
+                    let error = response
+                        .error
+                        .map(|e| e.message)
+                        .unwrap_or_else(|| "unknown error".to_string());
+                    state.push_log(
+                        LogCategory::Error,
+                        format!("Schedule {} failed to inject task: {}", schedule_id, error),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Dispatch held tasks whose prerequisites have completed.
+    async fn release_held_tasks(&self) {
+        let ready = {
+            let mut state = self.state.write().await;
+            if state.held_tasks.is_empty() {
+                return;
+            }
+            state.take_ready_dependents()
+        };
+        for task in ready {
+            crate::rpc_server::dispatch_task(task, &self.state, &self.network_handle).await;
+        }
+    }
+
+    async fn check_execution_timeouts(&self) {
+        let now = chrono::Utc::now();
+        let mut publishes: Vec<(String, Vec<u8>, String)> = Vec::new();
+        let mut escalations: Vec<(Vec<String>, TaskEscalationParams)> = Vec::new();
+
+        {
+            let mut state = self.state.write().await;
+            let my_id = state.agent_id.to_string();
+            let poll_staleness = Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS);
+            let seen_staleness = Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS);
+            let active_members: std::collections::HashSet<String> =
+                state.active_member_ids(seen_staleness).into_iter().collect();
+            // Verification re-executions are not reassigned; the original
+            // result stands when they run out of time.
+            state.expire_verifications(now);
+            for task_id in state.mark_overdue_tasks(now) {
+                state.push_log(LogCategory::Task, format!("Task {} is overdue", task_id));
+            }
+
+            // Overdue subtasks of our subordinates are reassigned unless their
+            // assignee recently reported progress; the rest stay overdue until
+            // a late result arrives. Blocked subtasks are retried on every
+            // check until a capable agent appears.
+            let timed_out_tasks: Vec<String> = state
+                .task_details
+                .iter()
+                .filter(|(_, task)| matches!(task.status, TaskStatus::Overdue | TaskStatus::Blocked))
+                .filter(|(_, task)| task.parent_task_id.is_some() && task.remote.is_none())
+                .filter(|(task_id, _)| !state.progress_is_recent(task_id, now))
+                .map(|(task_id, _)| task_id.clone())
+                .collect();
+
+            for task_id in timed_out_tasks {
+                let Some(task_snapshot) = state.task_details.get(&task_id).cloned() else {
+                    continue;
+                };
+                let Some(parent_id) = task_snapshot.parent_task_id.clone() else {
+                    continue;
+                };
+                let old_assignee = task_snapshot.assigned_to.clone();
+                let my_subordinates = state
+                    .subordinates
+                    .get(&my_id)
+                    .cloned()
+                    .unwrap_or_default();
+                if !my_subordinates
+                    .iter()
+                    .any(|id| Some(id.as_str()) == old_assignee.as_ref().map(|a| a.as_str()))
+                {
+                    continue;
+                }
+                let expected_tier = old_assignee
+                    .as_ref()
+                    .and_then(|a| state.agent_tiers.get(a.as_str()).copied());
+
+                let mut candidates = my_subordinates
+                    .into_iter()
+                    .filter(|candidate| {
+                        if Some(candidate.as_str()) == old_assignee.as_ref().map(|a| a.as_str()) {
+                            return false;
+                        }
+                        if !active_members.contains(candidate) {
+                            return false;
+                        }
+                        if !Self::member_loop_active(&state, candidate, poll_staleness) {
+                            return false;
+                        }
+                        if let Some(capabilities) = state.agent_capabilities.get(candidate) {
+                            if !state
+                                .capabilities
+                                .covers(capabilities, &task_snapshot.capabilities_required)
+                            {
+                                return false;
+                            }
+                        }
+                        if let Some(tier) = expected_tier {
+                            return state.agent_tiers.get(candidate).copied().unwrap_or(Tier::Executor)
+                                == tier;
+                        }
+                        true
+                    })
+                    .collect::<Vec<_>>();
+
+                candidates.sort();
+                let was_blocked = task_snapshot.status == TaskStatus::Blocked;
+                let Some(new_assignee) = candidates.into_iter().next() else {
+                    // Nobody can take over: escalate once to the parent's
+                    // coordinator and the originator, then keep retrying.
+                    if !was_blocked {
+                        let escalation = TaskEscalationParams {
+                            task_id: task_id.clone(),
+                            parent_task_id: parent_id.clone(),
+                            coordinator: state.agent_id.clone(),
+                            previous_assignee: old_assignee.clone(),
+                            reason: format!(
+                                "No active subordinate can take over from {}",
+                                old_assignee
+                                    .as_ref()
+                                    .map(|a| a.as_str())
+                                    .unwrap_or("unassigned")
+                            ),
+                            timestamp: now,
+                        };
+                        if state.block_task(escalation.clone()) {
+                            let swarm_id = state.swarm_for_task(&task_id);
+                            let mut topics = vec![SwarmTopics::results_for(&swarm_id, &parent_id)];
+                            let root_id = state.root_task_id(&parent_id);
+                            if root_id != parent_id {
+                                topics.push(SwarmTopics::results_for(&swarm_id, &root_id));
+                            }
+                            escalations.push((topics, escalation));
+                        }
+                    }
+                    continue;
+                };
+
+                let deadline = state.subtask_deadline(&parent_id, now);
+                if let Some(task) = state.task_details.get_mut(&task_id) {
+                    task.assigned_to = Some(AgentId::new(new_assignee.clone()));
+                    task.status = TaskStatus::InProgress;
+                    task.deadline = Some(deadline);
+                }
+                state.bump_tasks_assigned(&new_assignee);
+                state.blocked_tasks.remove(&task_id);
+                state.push_task_timeline_event(
+                    &task_id,
+                    "reassigned",
+                    format!(
+                        "{}: {} -> {}",
+                        if was_blocked {
+                            "Blocked task reassigned to a newly available agent"
+                        } else {
+                            "Task reassigned due to timeout"
+                        },
+                        old_assignee
+                            .as_ref()
+                            .map(|a| a.as_str())
+                            .unwrap_or("unassigned"),
+                        new_assignee
+                    ),
+                    Some(my_id.clone()),
+                );
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Task {} reassigned due to non-response: {} -> {}",
+                        task_id,
+                        old_assignee
+                            .as_ref()
+                            .map(|a| a.as_str())
+                            .unwrap_or("unassigned"),
+                        new_assignee
+                    ),
+                );
+
+                let Some(mut reassigned_task) = state.task_details.get(&task_id).cloned() else {
+                    continue;
+                };
+                reassigned_task.assigned_to = Some(AgentId::new(new_assignee.clone()));
+
+                let assign_params = state.signed_assignment(
+                    reassigned_task,
+                    AgentId::new(new_assignee),
+                    parent_id,
+                    "reassign-timeout",
+                );
+                let assign_msg = SwarmMessage::new(
+                    ProtocolMethod::TaskAssignment.as_str(),
+                    serde_json::to_value(&assign_params).unwrap_or_default(),
+                    String::new(),
+                );
+                if let Ok(data) = serde_json::to_vec(&assign_msg) {
+                    let swarm_id = state.swarm_for_task(&task_id);
+                    let topic = SwarmTopics::tasks_for(&swarm_id, task_snapshot.tier_level);
+                    publishes.push((topic, data, task_id.clone()));
+                }
+            }
+        }
+
+        for (topic, data, task_id) in publishes {
+            if let Err(e) = self.network_handle.publish(&topic, data).await {
+                tracing::error!(task_id = %task_id, topic = %topic, error = %e, "Failed to publish reassignment");
+            }
+        }
+        for (topics, escalation) in escalations {
+            let msg = SwarmMessage::new(
+                ProtocolMethod::TaskEscalation.as_str(),
+                serde_json::to_value(&escalation).unwrap_or_default(),
+                String::new(),
+            );
+            let Ok(data) = serde_json::to_vec(&msg) else {
+                continue;
+            };
+            for topic in topics {
+                if let Err(e) = self.network_handle.publish(&topic, data.clone()).await {
+                    tracing::warn!(task_id = %escalation.task_id, topic = %topic, error = %e, "Failed to publish escalation");
+                }
+            }
+        }
+    }
+
+    /// Close board formations whose acceptance window has ended and announce
+    /// the outcome on each board's topic.
+    async fn check_board_formations(&self) {
+        let (ready, dissolved) = {
+            let mut state = self.state.write().await;
+            if state.board_formations.is_empty() {
+                return;
+            }
+            let (ready, dissolved) = state.expire_board_formations(chrono::Utc::now());
+            let ready: Vec<_> = ready
+                .into_iter()
+                .map(|params| (state.swarm_for_task(&params.task_id), params))
+                .collect();
+            let dissolved: Vec<_> = dissolved
+                .into_iter()
+                .map(|task_id| (state.swarm_for_task(&task_id), task_id))
+                .collect();
+            (ready, dissolved)
+        };
+        for (swarm_id, params) in ready {
+            self.publish_board_message(
+                &swarm_id,
+                &params.task_id,
+                ProtocolMethod::BoardReady,
+                serde_json::to_value(&params).unwrap_or_default(),
+            )
+            .await;
+        }
+        for (swarm_id, task_id) in dissolved {
+            let params = BoardDissolveParams { task_id: task_id.clone() };
+            self.publish_board_message(
+                &swarm_id,
+                &task_id,
+                ProtocolMethod::BoardDissolve,
+                serde_json::to_value(&params).unwrap_or_default(),
+            )
+            .await;
+        }
+    }
+
+    async fn publish_ballot_receipt(&self, swarm_id: &str, receipt: &BallotReceiptParams) {
+        let msg = SwarmMessage::new(
+            ProtocolMethod::BallotReceipt.as_str(),
+            serde_json::to_value(receipt).unwrap_or_default(),
+            String::new(),
+        );
+        if let Ok(data) = serde_json::to_vec(&msg) {
+            let topic = SwarmTopics::voting_for(swarm_id, &receipt.task_id);
+            if let Err(e) = self.network_handle.publish(&topic, data).await {
+                tracing::debug!(task_id = %receipt.task_id, error = %e, "Failed to publish ballot receipt");
+            }
+        }
+    }
+
+    async fn publish_board_message(
+        &self,
+        swarm_id: &str,
+        task_id: &str,
+        method: ProtocolMethod,
+        params: serde_json::Value,
+    ) {
+        let msg = SwarmMessage::new(method.as_str(), params, String::new());
+        if let Ok(data) = serde_json::to_vec(&msg) {
+            let topic = SwarmTopics::board_for(swarm_id, task_id);
+            if let Err(e) = self.network_handle.publish(&topic, data).await {
+                tracing::error!(task_id = %task_id, topic = %topic, error = %e, "Failed to publish board message");
+            }
+        }
+    }
+
+    /// Assign subtasks from the winning plan to subordinate agents.
+    async fn assign_subtasks_from_winner(
+        &self,
+        task_id: &str,
+        winner_plan_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut state = self.state.write().await;
+
+        // Get the winning plan from RFP coordinator
+        let winning_plan = {
+            let rfp = state.rfp_coordinators.get(task_id)
+                .ok_or("RFP coordinator not found")?;
+
+            // Get the revealed proposal matching the winner
+            let revealed_proposal = rfp.reveals
+                .values()
+                .find(|p| p.plan.plan_id == winner_plan_id)
+                .ok_or("Winning plan not found in reveals")?;
+
+            revealed_proposal.plan.clone()
+        };
+        state
+            .task_winning_plans
+            .insert(task_id.to_string(), winner_plan_id.to_string());
+
+        // Get my subordinates for assignment
+        let raw_subordinates: Vec<AgentId> = state.subordinates
+            .get(state.agent_id.as_str())
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .map(AgentId::new)
+            .collect();
+
+        // In single-node mode (swarm_size==1), self-assign so the sole connector executes.
+        // In multi-node mode, Tier1 nodes without subordinates are NOT the designated
+        // coordinator — skip assignment to avoid duplicate assignments from multiple Tier1s.
+        let swarm_size = state.active_member_count(
+            std::time::Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)
+        ) as usize;
+        let subordinates: Vec<AgentId> = if raw_subordinates.is_empty() {
+            if swarm_size <= 1 {
+                tracing::info!(
+                    task_id = %task_id,
+                    agent_id = %state.agent_id,
+                    "No subordinates (single-node mode): self-assigning subtasks"
+                );
+                vec![state.agent_id.clone()]
+            } else {
+                tracing::info!(
+                    task_id = %task_id,
+                    agent_id = %state.agent_id,
+                    swarm_size,
+                    "No subordinates in multi-node swarm: skipping assignment (not the designated coordinator)"
+                );
+                return Ok(());
+            }
+        } else {
+            raw_subordinates
+        };
+
+        // Idempotency: if subtasks already exist for this task, another coordinator already
+        // assigned them. Skip to avoid competing assignments from multiple Tier1 nodes.
+        let first_subtask_id = format!("{}-st-1", task_id);
+        if state.task_details.contains_key(&first_subtask_id) {
+            tracing::info!(
+                task_id = %task_id,
+                agent_id = %state.agent_id,
+                "Subtasks already assigned by another coordinator — skipping duplicate assignment"
+            );
+            return Ok(());
+        }
+
+        if state.budget_exhausted(task_id) {
+            let spent = state.task_spend(task_id);
+            state.push_task_timeline_event(
+                task_id,
+                "budget_exhausted",
+                format!("Assignment refused: budget exhausted (spent {:.2})", spent),
+                None,
+            );
+            state.push_log(
+                LogCategory::Task,
+                format!(
+                    "Not assigning subtasks for {}: budget exhausted (spent {:.2})",
+                    task_id, spent
+                ),
+            );
+            return Ok(());
+        }
+
+        let parent_tier = state.task_details
+            .get(task_id)
+            .map(|t| t.tier_level)
+            .unwrap_or(1);
+        let parent_attachments = state
+            .task_details
+            .get(task_id)
+            .map(|t| t.attachments.clone())
+            .unwrap_or_default();
+        let parent_assignment = state.task_details.get(task_id).and_then(|t| t.assignment);
+        let parent_visibility = state
+            .task_details
+            .get(task_id)
+            .map(|t| t.visibility)
+            .unwrap_or_default();
+
+        let swarm_id = state.swarm_for_task(task_id);
+        let mut subtask_ids = Vec::new();
+        let mut assignment_messages = Vec::new();
+        let mut candidates: Vec<crate::assignment::Candidate> = subordinates
+            .iter()
+            .map(|a| state.assignment_candidate(a))
+            .collect();
+        let strategy = state.assignment_strategy_for(task_id);
+        let mut assigner = crate::assignment::assigner(
+            strategy,
+            crate::assignment::task_seed(state.assignment_seed, task_id),
+        );
+
+        let load = if candidates.is_empty() {
+            0.0
+        } else {
+            candidates
+                .iter()
+                .map(|c| c.load.utilization().min(1.0))
+                .sum::<f64>()
+                / candidates.len() as f64
+        };
+        let recursion = state.granularity.recursion_threshold(RecursionInputs {
+            swarm_size: swarm_size as u64,
+            branching_factor: state.branching_factor(swarm_size as u64) as u32,
+            depth: state.cascade.depth_of(task_id),
+            load,
+        });
+        if !winning_plan.subtasks.is_empty() {
+            state.push_task_timeline_event(
+                task_id,
+                "granularity",
+                format!("Decomposition: {}", recursion),
+                None,
+            );
+        }
+
+        // Create subtasks and assignment messages
+        for (idx, subtask_spec) in winning_plan.subtasks.iter().enumerate() {
+            let subtask_id = format!("{}-st-{}", task_id, idx + 1);
+            let is_complex = recursion.should_recurse(subtask_spec.estimated_complexity);
+
+            // Complex subtasks fall back to direct assignment once the root
+            // task's cascade would grow past its depth, fan-out or size limits.
+            let spawn_sub_holon = if !is_complex {
+                false
+            } else if let Some(limit) = state.cascade.spawn_limit(task_id) {
+                state.push_task_timeline_event(
+                    task_id,
+                    "cascade_limit",
+                    format!(
+                        "Complex subtask {} assigned directly: {}",
+                        subtask_id, limit
+                    ),
+                    None,
+                );
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Not spawning sub-holon for {}: {}",
+                        subtask_id, limit
+                    ),
+                );
+                false
+            } else {
+                true
+            };
+
+            if spawn_sub_holon {
+                state.cascade.record_spawn(task_id, &subtask_id);
+
+                // High-complexity subtask: spawn a sub-holon via TaskInjection so any
+                // available coordinator forms a new deliberation board for it.
+                let subtask = Task {
+                    task_id: subtask_id.clone(),
+                    parent_task_id: Some(task_id.to_string()),
+                    epoch: winning_plan.epoch,
+                    status: TaskStatus::Pending,
+                    description: subtask_spec.description.clone(),
+                    assigned_to: None,
+                    // Keep at parent tier so coordinator-tier agents pick it up
+                    tier_level: parent_tier,
+                    subtasks: Vec::new(),
+                    created_at: chrono::Utc::now(),
+                    deadline: None,
+                    capabilities_required: subtask_spec.required_capabilities.clone(),
+                    budget: subtask_spec.budget,
+                    attachments: parent_attachments.clone(),
+                    visibility: parent_visibility,
+                    // The sub-holon spreads its own subtasks the same way
+                    assignment: parent_assignment,
+                    ..Default::default()
+                };
+
+                state.task_details.insert(subtask_id.clone(), subtask.clone());
+                subtask_ids.push(subtask_id.clone());
+
+                // Create a child HolonState for this sub-holon
+                let my_agent_id = state.agent_id.clone();
+                state.active_holons.entry(subtask_id.clone()).or_insert_with(|| HolonState {
+                    task_id: subtask_id.clone(),
+                    chair: my_agent_id,
+                    members: Vec::new(),
+                    adversarial_critic: None,
+                    depth: parent_tier + 1,
+                    parent_holon: Some(task_id.to_string()),
+                    child_holons: Vec::new(),
+                    subtask_assignments: std::collections::HashMap::new(),
+                    child_artifacts: std::collections::HashMap::new(),
+                    status: HolonStatus::Forming,
+                    created_at: chrono::Utc::now(),
+                });
+                // Register sub-holon in parent's child_holons list
+                if let Some(parent_holon) = state.active_holons.get_mut(task_id) {
+                    if !parent_holon.child_holons.contains(&subtask_id) {
+                        parent_holon.child_holons.push(subtask_id.clone());
+                    }
+                }
+
+                state.push_task_timeline_event(
+                    task_id,
+                    "sub_holon_forming",
+                    format!(
+                        "Sub-holon forming for complex subtask {} (complexity={:.2} > threshold {:.2})",
+                        subtask_id, subtask_spec.estimated_complexity, recursion.threshold
+                    ),
+                    None,
+                );
+
+                state.record_tender(&subtask_id, chrono::Utc::now());
+                let inject_params = TaskInjectionParams {
+                    attachments: state.attachment_blobs(&subtask),
+                    task: subtask,
+                    originator: state.agent_id.clone(),
+                    attempt: 0,
+                };
+                let inject_msg = SwarmMessage::new(
+                    ProtocolMethod::TaskInjection.as_str(),
+                    serde_json::to_value(&inject_params).unwrap_or_default(),
+                    String::new(),
+                );
+                if let Ok(data) = serde_json::to_vec(&inject_msg) {
+                    let topic = SwarmTopics::tasks_for(swarm_id.as_str(), parent_tier);
+                    assignment_messages.push((topic, data));
+                }
+
+                tracing::info!(
+                    task_id = %task_id,
+                    subtask_id = %subtask_id,
+                    complexity = subtask_spec.estimated_complexity,
+                    "Complex subtask spawning sub-holon via TaskInjection"
+                );
+            } else {
+                // Low-complexity subtask: direct assignment to the subordinate
+                // executor the task's assignment strategy picks
+                let required = state
+                    .capabilities
+                    .canonicalize(&subtask_spec.required_capabilities);
+                let pick = assigner.pick(&candidates, &required, idx);
+                let assignee = subordinates[pick].clone();
+                candidates[pick].load.queue_depth += 1;
+
+                let subtask = Task {
+                    task_id: subtask_id.clone(),
+                    parent_task_id: Some(task_id.to_string()),
+                    epoch: winning_plan.epoch,
+                    status: TaskStatus::InProgress,
+                    description: subtask_spec.description.clone(),
+                    assigned_to: Some(assignee.clone()),
+                    tier_level: (parent_tier + 1).min(wws_protocol::MAX_HIERARCHY_DEPTH),
+                    subtasks: Vec::new(),
+                    created_at: chrono::Utc::now(),
+                    deadline: Some(state.subtask_deadline(task_id, chrono::Utc::now())),
+                    capabilities_required: subtask_spec.required_capabilities.clone(),
+                    budget: subtask_spec.budget,
+                    attachments: parent_attachments.clone(),
+                    visibility: parent_visibility,
+                    ..Default::default()
+                };
+
+                state.task_details.insert(subtask_id.clone(), subtask.clone());
+                state.bump_tasks_assigned(assignee.as_str());
+                subtask_ids.push(subtask_id.clone());
+
+                state.push_task_timeline_event(
+                    task_id,
+                    "subtask_assigned",
+                    format!("Subtask {} assigned to {}", subtask_id, assignee),
+                    Some(assignee.to_string()),
+                );
+
+                let assign_params = state.signed_assignment(
+                    subtask,
+                    assignee.clone(),
+                    task_id.to_string(),
+                    winner_plan_id,
+                );
+                let assign_msg = SwarmMessage::new(
+                    ProtocolMethod::TaskAssignment.as_str(),
+                    serde_json::to_value(&assign_params).unwrap_or_default(),
+                    String::new(),
+                );
+                if let Ok(data) = serde_json::to_vec(&assign_msg) {
+                    let topic =
+                        SwarmTopics::tasks_for(swarm_id.as_str(), assign_params.task.tier_level);
+                    assignment_messages.push((topic, data));
+                }
+
+                tracing::info!(
+                    task_id = %task_id,
+                    subtask_id = %subtask_id,
+                    assignee = %assignee,
+                    strategy = ?strategy,
+                    "Subtask assigned to subordinate"
+                );
+            }
+        }
+
+        // Update parent task with subtask IDs
+        if let Some(parent_task) = state.task_details.get_mut(task_id) {
+            parent_task.subtasks = subtask_ids.clone();
+        }
+
+        state.push_log(
+            LogCategory::Task,
+            format!(
+                "Assigned {} subtasks from winning plan {} to {} subordinates",
+                winning_plan.subtasks.len(),
+                winner_plan_id,
+                subordinates.len()
+            ),
+        );
+
+        // Drop the write lock before publishing
+        drop(state);
+
+        // Subscribe coordinator to each subtask's result topic so we receive completion updates
+        for st_id in &subtask_ids {
+            self.subscribe_task_flow_topics(swarm_id.as_str(), st_id).await;
+        }
+
+        // Publish all assignment messages
+        for (topic, data) in assignment_messages {
+            if let Err(e) = self.network_handle.publish(&topic, data).await {
+                tracing::error!(
+                    topic = %topic,
+                    error = %e,
+                    "Failed to publish task assignment"
+                );
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic,
+                    method: Some(ProtocolMethod::TaskAssignment.as_str().to_string()),
+                    task_id: Some(task_id.to_string()),
+                    size_bytes: 0,
+                    outcome: format!("error: {}", e),
+                    payload: None,
+                });
+            } else {
+                let mut state = self.state.write().await;
+                state.push_message_trace(MessageTraceEvent {
+                    timestamp: chrono::Utc::now(),
+                    direction: "outbound".to_string(),
+                    peer: None,
+                    topic,
+                    method: Some(ProtocolMethod::TaskAssignment.as_str().to_string()),
+                    task_id: Some(task_id.to_string()),
+                    size_bytes: 0,
+                    outcome: "published".to_string(),
+                    payload: None,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse `network.external_addrs`. A trailing `/p2p/<peer id>` is
+    /// dropped; the host advertises the addresses under its own peer ID.
+    fn parse_external_addrs(addrs: &[String]) -> Result<Vec<Multiaddr>, anyhow::Error> {
+        addrs
+            .iter()
+            .map(|a| a.trim())
+            .filter(|a| !a.is_empty())
+            .map(|a| {
+                let addr: Multiaddr = a
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("Invalid external address {}: {}", a, e))?;
+                Ok(addr.iter().take_while(|p| p.tag() != "p2p").collect())
+            })
+            .collect()
+    }
+
+    /// The configured external addresses as peers dial them, ending in
+    /// this node's `/p2p/<peer id>`.
+    fn dialable_external_addrs(&self) -> Vec<String> {
+        let peer_id = self.network_handle.local_peer_id();
+        Self::parse_external_addrs(&self.config.network.external_addrs)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|addr| format!("{}/p2p/{}", addr, peer_id))
+            .collect()
+    }
+
+    /// Addresses a swarm announcement gives for the node that sent it.
+    /// Addresses naming any other peer are ignored.
+    fn announced_addrs(params: &SwarmAnnounceParams) -> Vec<(PeerId, Multiaddr)> {
+        params
+            .addrs
+            .iter()
+            .take(MAX_ANNOUNCED_ADDRS)
+            .filter_map(|a| {
+                let addr = a.parse::<Multiaddr>().ok()?;
+                let peer_id = Self::extract_peer_id_from_addr(a)?;
+                (params.agent_id.as_str() == format!("did:swarm:{}", peer_id))
+                    .then_some((peer_id, addr))
+            })
+            .collect()
+    }
+
+    /// Parse bootstrap peer multiaddresses (e.g. "/ip4/1.2.3.4/tcp/9000/p2p/12D3...")
+    /// into (PeerId, Multiaddr) pairs for the discovery layer.
+    fn parse_bootstrap_peers(addrs: &[String]) -> Vec<(PeerId, Multiaddr)> {
+        let mut peers = Vec::new();
+        for addr_str in addrs {
+            let addr_str = addr_str.trim();
+            if addr_str.is_empty() {
+                continue;
+            }
+            match addr_str.parse::<Multiaddr>() {
+                Ok(addr) => {
+                    // Extract the PeerId from the /p2p/<peer_id> component of the multiaddr string.
+                    if let Some(peer_id) = Self::extract_peer_id_from_addr(addr_str) {
+                        peers.push((peer_id, addr));
+                    } else {
+                        tracing::warn!(
+                            addr = %addr_str,
+                            "Bootstrap address missing /p2p/<peer_id> component, skipping"
+                        );
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        addr = %addr_str,
+                        error = %e,
+                        "Failed to parse bootstrap multiaddress, skipping"
+                    );
+                }
+            }
+        }
+        peers
+    }
+
+    /// Extract a PeerId from a multiaddress string by finding the /p2p/<id> segment.
+    fn extract_peer_id_from_addr(addr: &str) -> Option<PeerId> {
+        let parts: Vec<&str> = addr.split('/').collect();
+        // Find the "p2p" component and take the next element as the peer ID.
+        for (i, part) in parts.iter().enumerate() {
+            if *part == "p2p" {
+                if let Some(id_str) = parts.get(i + 1) {
+                    return id_str.parse::<PeerId>().ok();
+                }
+            }
+        }
+        None
+    }
+
+    /// Dial bootstrap peers to establish connections immediately on startup.
+    async fn connect_to_bootstrap_peers(&self) {
+        for addr_str in &self.config.network.bootstrap_peers {
+            let addr_str = addr_str.trim();
+            if addr_str.is_empty() {
+                continue;
+            }
+            if let Ok(addr) = addr_str.parse::<Multiaddr>() {
+                match self.network_handle.dial(addr.clone()).await {
+                    Ok(()) => {
+                        tracing::info!(addr = %addr, "Dialing bootstrap peer");
+                        let mut state = self.state.write().await;
+                        state.push_log(
+                            LogCategory::System,
+                            format!("Dialing bootstrap peer: {}", addr),
+                        );
+                    }
+                    Err(e) => {
+                        tracing::warn!(addr = %addr, error = %e, "Failed to dial bootstrap peer");
+                    }
+                }
+            }
+        }
+    }
+
+    async fn subscribe_task_assignment_topics(&self, swarm_id: &str) {
+        for tier in 1..=wws_protocol::MAX_HIERARCHY_DEPTH {
+            let topic = SwarmTopics::tasks_for(swarm_id, tier);
+            if let Err(e) = self.network_handle.subscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to subscribe task assignment topic");
+            }
+        }
+    }
+
+    async fn subscribe_task_flow_topics(&self, swarm_id: &str, task_id: &str) {
+        let proposals_topic = SwarmTopics::proposals_for(swarm_id, task_id);
+        let voting_topic = SwarmTopics::voting_for(swarm_id, task_id);
+        let results_topic = SwarmTopics::results_for(swarm_id, task_id);
+        let board_topic = SwarmTopics::board_for(swarm_id, task_id);
+
+        if let Err(e) = self.network_handle.subscribe(&proposals_topic).await {
+            tracing::debug!(error = %e, topic = %proposals_topic, "Failed to subscribe proposals topic");
+        }
+        if let Err(e) = self.network_handle.subscribe(&voting_topic).await {
+            tracing::debug!(error = %e, topic = %voting_topic, "Failed to subscribe voting topic");
+        }
+        if let Err(e) = self.network_handle.subscribe(&results_topic).await {
+            tracing::debug!(error = %e, topic = %results_topic, "Failed to subscribe results topic");
+        }
+        if let Err(e) = self.network_handle.subscribe(&board_topic).await {
+            tracing::debug!(error = %e, topic = %board_topic, "Failed to subscribe board topic");
+        }
+
+        // Deliberation may be under way already; ask the chair for what was missed.
+        let request = self.state.write().await.note_task_state_request(task_id);
+        if let Some(request) = request {
+            self.publish_task_state_message(
+                swarm_id,
+                task_id,
+                ProtocolMethod::TaskStateRequest,
+                serde_json::to_value(&request).unwrap_or_default(),
+            )
+            .await;
+        }
+    }
+
+    /// Publish a task state request or snapshot on the task's proposals topic.
+    async fn publish_task_state_message(
+        &self,
+        swarm_id: &str,
+        task_id: &str,
+        method: ProtocolMethod,
+        params: serde_json::Value,
+    ) {
+        let msg = SwarmMessage::new(method.as_str(), params, String::new());
+        if let Ok(data) = serde_json::to_vec(&msg) {
+            let topic = SwarmTopics::proposals_for(swarm_id, task_id);
+            if let Err(e) = self.network_handle.publish(&topic, data).await {
+                tracing::debug!(task_id = %task_id, error = %e, "Failed to publish task state message");
+            }
+        }
+    }
+
+    /// Expire abandoned consensus rounds and leave the flow topics of the
+    /// tasks they failed.
+    async fn reap_abandoned_rounds(&self) {
+        let failed = self.state.write().await.expire_abandoned_rounds(chrono::Utc::now());
+        for (task_id, swarm_id) in failed {
+            self.leave_task_flow_topics(&swarm_id, &task_id).await;
+        }
+    }
+
+    async fn leave_task_flow_topics(&self, swarm_id: &str, task_id: &str) {
+        for topic in [
+            SwarmTopics::proposals_for(swarm_id, task_id),
+            SwarmTopics::voting_for(swarm_id, task_id),
+            SwarmTopics::results_for(swarm_id, task_id),
+            SwarmTopics::board_for(swarm_id, task_id),
+        ] {
+            if let Err(e) = self.network_handle.unsubscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to unsubscribe task topic");
+            }
+        }
+    }
+
+    /// Publish a fresh injection of every task injected here that drew no
+    /// plan in time, and leave the topics of those that ran out of attempts.
+    async fn retender_unproposed_tasks(&self) {
+        let mut state = self.state.write().await;
+        let outcomes = state.take_due_retenders(chrono::Utc::now());
+        let mut injections = Vec::new();
+        let mut given_up = Vec::new();
+        for (task_id, outcome) in outcomes {
+            match outcome {
+                TenderOutcome::Retendered(task) => {
+                    crate::rpc_server::init_local_rfp(&mut state, &task);
+                    let attempt = state.task_tenders.get(&task_id).map_or(0, |t| t.attempt);
+                    let params = TaskInjectionParams {
+                        attachments: state.attachment_blobs(&task),
+                        originator: state.agent_id.clone(),
+                        attempt,
+                        task: *task,
+                    };
+                    let msg = SwarmMessage::new(
+                        ProtocolMethod::TaskInjection.as_str(),
+                        serde_json::to_value(&params).unwrap_or_default(),
+                        String::new(),
+                    );
+                    let topic = SwarmTopics::tasks_for(&state.swarm_for_task(&task_id), params.task.tier_level);
+                    if let Ok(data) = serde_json::to_vec(&msg) {
+                        injections.push((topic, data));
+                    }
+                }
+                TenderOutcome::GaveUp { swarm_id, failure } => {
+                    let tier_level = state.task_details.get(&task_id).map_or(1, |t| t.tier_level);
+                    let msg = SwarmMessage::new(
+                        ProtocolMethod::TaskTenderFailed.as_str(),
+                        serde_json::to_value(&failure).unwrap_or_default(),
+                        String::new(),
+                    );
+                    let topic = SwarmTopics::tasks_for(&swarm_id, tier_level);
+                    given_up.push((task_id, swarm_id, topic, serde_json::to_vec(&msg).ok()));
+                }
+            }
+        }
+        drop(state);
+
+        for (topic, data) in injections {
+            if let Err(e) = self.network_handle.publish(&topic, data).await {
+                tracing::debug!(error = %e, "Failed to publish task re-tender");
+            }
+        }
+        for (task_id, swarm_id, topic, data) in given_up {
+            if let Some(data) = data {
+                if let Err(e) = self.network_handle.publish(&topic, data).await {
+                    tracing::debug!(error = %e, task_id = %task_id, "Failed to publish tender failure");
+                }
+            }
+            self.leave_task_flow_topics(&swarm_id, &task_id).await;
+        }
+    }
+
+    /// Sample membership for the partition detector. When a partition
+    /// heals, exchange task digests with a few of the returning peers.
+    async fn check_partition(&self) {
+        let event = self.state.write().await.observe_partition(chrono::Utc::now());
+        let Some(event) = event else {
+            return;
+        };
+        let mut state = self.state.write().await;
+        match event {
+            crate::partition::PartitionEvent::Detected(report) => {
+                tracing::warn!(lost = report.lost.len(), members = report.members_before, "Network partition suspected");
+                state.push_log(
+                    LogCategory::Peer,
+                    format!(
+                        "Partition {} suspected: lost {} of {} members at once",
+                        report.partition_id,
+                        report.lost.len(),
+                        report.members_before
+                    ),
+                );
+            }
+            crate::partition::PartitionEvent::Expired(report) => {
+                state.push_log(
+                    LogCategory::Peer,
+                    format!(
+                        "Partition {} closed: {} of {} lost members never returned",
+                        report.partition_id,
+                        report.lost.len() - report.returned.len(),
+                        report.lost.len()
+                    ),
+                );
+            }
+            crate::partition::PartitionEvent::Healed(report) => {
+                tracing::info!(returned = report.returned.len(), "Network partition healed");
+                state.push_log(
+                    LogCategory::Peer,
+                    format!(
+                        "Partition {} healed: {} of {} lost members returned; reconciling task state",
+                        report.partition_id,
+                        report.returned.len(),
+                        report.lost.len()
+                    ),
+                );
+                let request = StateReconcileParams {
+                    sender: state.agent_id.clone(),
+                    digests: state.task_digests(),
+                };
+                drop(state);
+                self.reconcile_after_partition(&report.returned, request);
+            }
+        }
+    }
+
+    /// As the shard issuer of a swarm grown past its threshold, split it
+    /// into child swarms and stay on as a gateway.
+    async fn check_shard(&self) {
+        let Some(shard) = self.state.write().await.plan_shard() else {
+            return;
+        };
+        crate::shard::publish_shard(&self.network_handle, &shard).await;
+        let applied = self.state.write().await.apply_shard(shard);
+        match applied {
+            Ok(Some(outcome)) => {
+                crate::shard::complete_shard(outcome, &self.state, &self.network_handle).await;
+            }
+            Ok(None) => {}
+            Err(reason) => {
+                tracing::warn!(reason = %reason, "Could not apply own swarm shard");
+                self.state.write().await.push_log(LogCategory::Swarm, reason);
+            }
+        }
+    }
+
+    /// Send our task digests to up to `MAX_RECONCILE_PEERS` returning
+    /// peers and reconcile with each answer. Requests run in the
+    /// background so a slow peer does not hold up the event loop.
+    fn reconcile_after_partition(&self, returned: &[String], request: StateReconcileParams) {
+        let message = SwarmMessage::new(
+            ProtocolMethod::StateReconcile.as_str(),
+            serde_json::to_value(&request).unwrap_or_default(),
+            String::new(),
+        );
+        let peers = returned
+            .iter()
+            .filter_map(|agent| Some((agent.clone(), agent.strip_prefix("did:swarm:")?.parse::<PeerId>().ok()?)))
+            .take(crate::partition::MAX_RECONCILE_PEERS);
+        for (agent, peer) in peers {
+            let state = Arc::clone(&self.state);
+            let network_handle = self.network_handle.clone();
+            let message = message.clone();
+            tokio::spawn(async move {
+                let reply = network_handle.request(peer, message).await;
+                let mut state = state.write().await;
+                let digests = match reply {
+                    Ok(reply) if reply.method == ProtocolMethod::StateReconcile.as_str() => {
+                        serde_json::from_value::<StateReconcileParams>(reply.params)
+                            .map(|params| params.digests)
+                            .map_err(|e| e.to_string())
+                    }
+                    Ok(reply) => Err(format!("answered with {}", reply.method)),
+                    Err(e) => Err(e.to_string()),
+                };
+                match digests {
+                    Ok(digests) => {
+                        state.reconcile_with_peer(&agent, &digests);
+                    }
+                    Err(e) => state.push_log(
+                        LogCategory::Peer,
+                        format!("Could not reconcile task state with {}: {}", agent, e),
+                    ),
+                }
+            });
+        }
+    }
+
+    /// Send a merge handshake to `agent`, a connector of another lineage,
+    /// and merge its answer. Runs in the background like reconciliation.
+    fn merge_with_peer(&self, agent: String, request: SwarmMergeParams) {
+        let Some(peer) = agent
+            .strip_prefix("did:swarm:")
+            .and_then(|id| id.parse::<PeerId>().ok())
+        else {
+            return;
+        };
+        let message = SwarmMessage::new(
+            ProtocolMethod::SwarmMerge.as_str(),
+            serde_json::to_value(&request).unwrap_or_default(),
+            String::new(),
+        );
+        let state = Arc::clone(&self.state);
+        let network_handle = self.network_handle.clone();
+        tokio::spawn(async move {
+            let reply = network_handle.request(peer, message).await;
+            let mut state = state.write().await;
+            let view = match reply {
+                Ok(reply) if reply.method == ProtocolMethod::SwarmMerge.as_str() => {
+                    serde_json::from_value::<SwarmMergeParams>(reply.params).map_err(|e| e.to_string())
+                }
+                Ok(reply) => Err(format!("answered with {}", reply.method)),
+                Err(e) => Err(e.to_string()),
+            };
+            match view {
+                Ok(view) if view.sender.as_str() == agent => {
+                    state.merge_with(&view);
+                }
+                Ok(view) => state.push_log(
+                    LogCategory::Peer,
+                    format!("Merge answer from {} was sent as {}", agent, view.sender),
+                ),
+                Err(e) => state.push_log(
+                    LogCategory::Peer,
+                    format!("Could not merge swarm views with {}: {}", agent, e),
+                ),
+            }
+        });
+    }
+
+    /// Bounds for decoding params received from peers.
+    fn strict_limits(&self) -> StrictLimits {
+        StrictLimits {
+            deny_unknown_fields: self.config.network.deny_unknown_params,
+            ..StrictLimits::default()
+        }
+    }
+
+    /// Whether `sender` is the DID of the peer that signed a direct request.
+    fn is_direct_sender(peer: &PeerId, sender: &AgentId) -> bool {
+        sender.as_str() == format!("did:swarm:{}", peer)
+    }
+
+    /// Move each joined swarm's task-topic subscriptions to this node's
+    /// current tier there, so a demoted coordinator stops receiving, and
+    /// acting on, work for a tier it no longer holds.
+    async fn sync_tier_subscriptions(&self) {
+        let (subscribe, unsubscribe) = self.state.write().await.update_tier_subscriptions();
+        for topic in unsubscribe {
+            if let Err(e) = self.network_handle.unsubscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to unsubscribe tier topic");
+            }
+        }
+        for topic in subscribe {
+            if let Err(e) = self.network_handle.subscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to subscribe tier topic");
+            }
+        }
+    }
+
+    /// Re-subscribe to every required topic the network layer is not
+    /// subscribed to, so a subscription lost or never made (a failed
+    /// subscribe, a reconnect) cannot silently drop proposals or votes.
+    async fn reconcile_subscriptions(&self) {
+        let required = self.state.read().await.required_topics();
+        let actual: std::collections::HashSet<String> =
+            match self.network_handle.subscribed_topics().await {
+                Ok(topics) => topics.into_iter().collect(),
+                Err(e) => {
+                    tracing::debug!(error = %e, "Failed to list subscribed topics");
+                    return;
+                }
+            };
+
+        for topic in required.into_iter().filter(|t| !actual.contains(t)) {
+            let outcome = self.network_handle.subscribe(&topic).await;
+            let mut state = self.state.write().await;
+            match outcome {
+                Ok(()) => {
+                    tracing::warn!(topic = %topic, "Re-subscribed to missing topic");
+                    state.push_log(
+                        LogCategory::System,
+                        format!("Subscription repaired: re-subscribed to {}", topic),
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(topic = %topic, error = %e, "Failed to repair subscription");
+                    state.push_log(
+                        LogCategory::System,
+                        format!("Subscription repair failed for {}: {}", topic, e),
+                    );
+                }
+            }
+        }
+    }
+
+    fn level_to_tier(level: u32) -> Tier {
+        match level {
+            1 => Tier::Tier1,
+            2 => Tier::Tier2,
+            n => Tier::TierN(n),
+        }
+    }
+
+    fn member_loop_active(state: &ConnectorState, agent_id: &str, max_staleness: Duration) -> bool {
+        let now = chrono::Utc::now();
+        state
+            .member_last_task_poll
+            .get(agent_id)
+            .and_then(|ts| now.signed_duration_since(*ts).to_std().ok())
+            .map(|age| age <= max_staleness)
+            .unwrap_or(false)
+    }
+
+    fn active_participating_members_in_tier(
+        state: &ConnectorState,
+        tier: Tier,
+        member_staleness: Duration,
+        poll_staleness: Duration,
+    ) -> Vec<String> {
+        state
+            .active_member_ids(member_staleness)
+            .into_iter()
+            .filter(|id| state.agent_tiers.get(id).copied().unwrap_or(Tier::Executor) == tier)
+            .filter(|id| Self::member_loop_active(state, id, poll_staleness))
+            .collect()
+    }
+
+    fn is_participating_member_for_task(
+        state: &ConnectorState,
+        task_id: &str,
+        agent_id: &str,
+        poll_staleness: Duration,
+    ) -> bool {
+        let tier_level = state
+            .task_details
+            .get(task_id)
+            .map(|t| t.tier_level)
+            .or_else(|| state.task_vote_requirements.get(task_id).map(|r| r.tier_level))
+            .unwrap_or(1);
+        let tier = Self::level_to_tier(tier_level);
+        state.agent_tiers.get(agent_id).copied().unwrap_or(Tier::Executor) == tier
+            && Self::member_loop_active(state, agent_id, poll_staleness)
+    }
+
+    /// As the producer's coordinator, hand a freshly completed subtask to
+    /// another active subordinate of the same tier with probability
+    /// `verification_rate`. Returns the TaskAssignment topic and payload.
+    fn select_result_verification(
+        state: &mut ConnectorState,
+        task_id: &str,
+        producer: &str,
+    ) -> Option<(String, Vec<u8>)> {
+        if state.verification_rate <= 0.0 || state.verifications.contains_key(task_id) {
+            return None;
+        }
+        let task = state.task_details.get(task_id)?;
+        let parent_id = task.parent_task_id.clone()?;
+        if task.status != TaskStatus::Completed || !task.subtasks.is_empty() {
+            return None;
+        }
+        let my_id = state.agent_id.to_string();
+        let subordinates = state.subordinates.get(&my_id).cloned().unwrap_or_default();
+        if !subordinates.iter().any(|id| id == producer) {
+            return None;
+        }
+        if rand::random::<f64>() >= state.verification_rate {
+            return None;
+        }
+
+        let tier = state.agent_tiers.get(producer).copied().unwrap_or(Tier::Executor);
+        let active: std::collections::HashSet<String> = state
+            .active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
+            .into_iter()
+            .collect();
+        let poll_staleness = Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS);
+        let candidates: Vec<String> = subordinates
+            .into_iter()
+            .filter(|id| id != producer && active.contains(id))
+            .filter(|id| state.agent_tiers.get(id).copied().unwrap_or(Tier::Executor) == tier)
+            .filter(|id| Self::member_loop_active(state, id, poll_staleness))
+            .collect();
+        let verifier = {
+            use rand::seq::SliceRandom;
+            candidates.choose(&mut rand::thread_rng())?.clone()
+        };
+
+        let task = state.begin_verification(task_id, &verifier)?;
+        let topic = SwarmTopics::tasks_for(&state.swarm_for_task(task_id), task.tier_level);
+        let assign_params = state.signed_assignment(
+            task,
+            AgentId::new(verifier),
+            parent_id,
+            VERIFICATION_PLAN_ID,
+        );
+        let assign_msg = SwarmMessage::new(
+            ProtocolMethod::TaskAssignment.as_str(),
+            serde_json::to_value(&assign_params).unwrap_or_default(),
+            String::new(),
+        );
+        let data = serde_json::to_vec(&assign_msg).ok()?;
+        Some((topic, data))
+    }
+
+    /// Tier level of a task and the members of that tier eligible to take
+    /// part in it: its committee when it draws one, else every active
+    /// member polling for tasks.
+    pub(crate) fn quorum_eligible_for_task(state: &ConnectorState, task_id: &str) -> (u32, Vec<String>) {
+        let (tier_level, tier_members) = Self::tier_members_for_task(state, task_id);
+        match state.committee_for(task_id, &tier_members) {
+            Some(committee) => (tier_level, committee.members()),
+            None => (tier_level, tier_members),
+        }
+    }
+
+    /// Tier level of a task and the active members of that tier polling for tasks.
+    fn tier_members_for_task(state: &ConnectorState, task_id: &str) -> (u32, Vec<String>) {
+        let tier_level = state
+            .task_details
+            .get(task_id)
+            .map(|t| t.tier_level)
+            .or_else(|| state.task_vote_requirements.get(task_id).map(|r| r.tier_level))
+            .unwrap_or(1);
+        let tier = Self::level_to_tier(tier_level);
+        let eligible = Self::active_participating_members_in_tier(
+            state,
+            tier,
+            Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS),
+            Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
+        );
+        (tier_level, eligible)
+    }
+
+    fn expected_vote_requirement_for_task(state: &ConnectorState, task_id: &str) -> TaskVoteRequirement {
+        let (tier_level, eligible) = Self::quorum_eligible_for_task(state, task_id);
+        state.quorum_requirement(task_id, tier_level, &eligible)
+    }
+
+    /// Get the current network statistics.
+    pub async fn get_network_stats(&self) -> NetworkStats {
+        let state = self.state.read().await;
+        state.network_stats.clone()
+    }
+
+    /// Get the shared state for use by the RPC server.
+    pub fn shared_state(&self) -> Arc<RwLock<ConnectorState>> {
+        Arc::clone(&self.state)
+    }
+
+    /// Get the network handle for use by the RPC server.
+    pub fn network_handle(&self) -> SwarmHandle {
+        self.network_handle.clone()
+    }
+
+}
+
+impl Clone for WwsConnector {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+            network_handle: self.network_handle.clone(),
+            event_rx: None, // Don't clone the event receiver (consumed by run())
+            swarm_host: None, // Don't clone the swarm host (consumed by run())
+            webhook_rx: None, // Don't clone the webhook queue (consumed by run())
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// Cut `text` to at most `max` characters, marking the cut with an ellipsis.
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use wws_protocol::board_channel::ChannelKeypair;
+
+    #[test]
+    fn parse_bootstrap_peers_valid_multiaddr_with_peer_id() {
+        // Use a valid Ed25519 peer ID (base58btc encoded).
+        let peer_id = PeerId::random();
+        let addr = format!("/ip4/192.168.1.1/tcp/9000/p2p/{}", peer_id);
+        let result = WwsConnector::parse_bootstrap_peers(&[addr]);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0, peer_id);
+    }
+
+    #[test]
+    fn parse_bootstrap_peers_missing_peer_id() {
+        let addr = "/ip4/192.168.1.1/tcp/9000".to_string();
+        let result = WwsConnector::parse_bootstrap_peers(&[addr]);
+        assert!(result.is_empty(), "Should skip addrs without /p2p/ component");
+    }
+
+    #[test]
+    fn parse_bootstrap_peers_invalid_multiaddr() {
+        let addr = "not-a-valid-multiaddr".to_string();
+        let result = WwsConnector::parse_bootstrap_peers(&[addr]);
+        assert!(result.is_empty(), "Should skip unparseable addrs");
+    }
+
+    #[test]
+    fn parse_bootstrap_peers_empty_and_whitespace_skipped() {
+        let addrs = vec![
+            "".to_string(),
+            "   ".to_string(),
+        ];
+        let result = WwsConnector::parse_bootstrap_peers(&addrs);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn parse_bootstrap_peers_multiple_valid() {
+        let peer1 = PeerId::random();
+        let peer2 = PeerId::random();
+        let addrs = vec![
+            format!("/ip4/10.0.0.1/tcp/4001/p2p/{}", peer1),
+            format!("/ip4/10.0.0.2/tcp/4001/p2p/{}", peer2),
+        ];
+        let result = WwsConnector::parse_bootstrap_peers(&addrs);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, peer1);
+        assert_eq!(result[1].0, peer2);
+    }
+
+    #[test]
+    fn parse_bootstrap_peers_mixed_valid_and_invalid() {
+        let peer1 = PeerId::random();
+        let addrs = vec![
+            format!("/ip4/10.0.0.1/tcp/4001/p2p/{}", peer1),
+            "/ip4/10.0.0.2/tcp/4001".to_string(), // no peer id
+            "garbage".to_string(),                  // unparseable
+        ];
+        let result = WwsConnector::parse_bootstrap_peers(&addrs);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0, peer1);
+    }
+
+    #[test]
+    fn parse_external_addrs_drops_peer_id_and_rejects_garbage() {
+        let peer_id = PeerId::random();
+        let addrs = vec![
+            format!("/ip4/203.0.113.5/tcp/9000/p2p/{}", peer_id),
+            "/dns4/node.example.org/udp/9000/quic-v1".to_string(),
+            " ".to_string(),
+        ];
+        let parsed = WwsConnector::parse_external_addrs(&addrs).unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                "/ip4/203.0.113.5/tcp/9000".parse::<Multiaddr>().unwrap(),
+                "/dns4/node.example.org/udp/9000/quic-v1".parse().unwrap(),
+            ]
+        );
+        assert!(WwsConnector::parse_external_addrs(&["garbage".to_string()]).is_err());
+    }
+
+    #[test]
+    fn announced_addrs_only_name_the_announcing_peer() {
+        let (sender, other) = (PeerId::random(), PeerId::random());
+        let params = SwarmAnnounceParams {
+            swarm_id: SwarmId::new("public".to_string()),
+            name: "Public".to_string(),
+            is_public: true,
+            agent_id: AgentId::new(format!("did:swarm:{}", sender)),
+            agent_count: 1,
+            description: String::new(),
+            timestamp: chrono::Utc::now(),
+            addrs: vec![
+                format!("/ip4/203.0.113.5/tcp/9000/p2p/{}", sender),
+                format!("/ip4/203.0.113.6/tcp/9000/p2p/{}", other),
+                "/ip4/203.0.113.7/tcp/9000".to_string(),
+            ],
+        };
+        let addrs = WwsConnector::announced_addrs(&params);
+        assert_eq!(addrs.len(), 1);
+        assert_eq!(addrs[0].0, sender);
+        assert_eq!(addrs[0].1.to_string(), params.addrs[0]);
+    }
+
+    #[test]
+    fn extract_peer_id_from_valid_addr() {
+        let peer_id = PeerId::random();
+        let addr = format!("/ip4/127.0.0.1/tcp/8080/p2p/{}", peer_id);
+        let extracted = WwsConnector::extract_peer_id_from_addr(&addr);
+        assert_eq!(extracted, Some(peer_id));
+    }
+
+    #[test]
+    fn extract_peer_id_from_addr_without_p2p() {
+        let addr = "/ip4/127.0.0.1/tcp/8080";
+        let extracted = WwsConnector::extract_peer_id_from_addr(addr);
+        assert!(extracted.is_none());
+    }
+
+    pub(crate) fn test_state() -> ConnectorState {
+        let agent_id = AgentId::new("did:swarm:connector-test".to_string());
+        ConnectorState {
+            agent_id: agent_id.clone(),
+            status: ConnectorStatus::Running,
+            epoch_manager: EpochManager::default(),
+            pyramid: PyramidAllocator::default(),
+            election: None,
+            geo_cluster: GeoCluster::default(),
+            succession: SuccessionManager::new(),
+            rfp_coordinators: std::collections::HashMap::new(),
+            voting_engines: std::collections::HashMap::new(),
+            cascade: CascadeEngine::new(),
+            task_set: OrSet::new(agent_id.to_string()),
+            task_details: Box::new(crate::storage::InMemoryTaskStore::new()),
+            task_timelines: Box::new(crate::storage::InMemoryTimelineStore::new()),
+            agent_set: OrSet::new(agent_id.to_string()),
+            member_set: OrSet::new(agent_id.to_string()),
+            member_last_seen: std::collections::HashMap::new(),
+            agent_names: std::collections::HashMap::new(),
+            agent_activity: std::collections::HashMap::new(),
+            task_vote_requirements: std::collections::HashMap::new(),
+            member_last_task_poll: std::collections::HashMap::new(),
+            member_last_result: std::collections::HashMap::new(),
+            task_result_text: std::collections::HashMap::new(),
+            pending_plan_reveals: std::collections::HashMap::new(),
+            pending_vote_reveals: std::collections::HashMap::new(),
+            voting_windows: std::collections::HashMap::new(),
+            merkle_dag: MerkleDag::new(),
+            content_store: ContentStore::new(),
+            granularity: GranularityAlgorithm::default(),
+            my_tier: Tier::Tier1,
+            parent_id: None,
+            agent_tiers: std::collections::HashMap::new(),
+            agent_parents: std::collections::HashMap::new(),
+            current_layout: None,
+            subordinates: std::collections::HashMap::new(),
+            task_results: Box::new(crate::storage::InMemoryArtifactStore::new()),
+            network_stats: NetworkStats {
+                total_agents: 1,
+                hierarchy_depth: 1,
+                branching_factor: 10,
+                current_epoch: 1,
+                my_tier: Tier::Tier1,
+                subordinate_count: 0,
+                parent_id: None,
+            },
+            event_log: Vec::new(),
+            log_count: 0,
+            message_trace: Vec::new(),
+            dead_letters: Vec::new(),
+            start_time: chrono::Utc::now(),
+            current_swarm_id: SwarmId::new("public".to_string()),
+            known_swarms: std::collections::HashMap::new(),
+            swarm_token: None,
+            active_holons: std::collections::HashMap::new(),
+            deliberation_messages: std::collections::HashMap::new(),
+            ballot_records: std::collections::HashMap::new(),
+            ballot_receipts: std::collections::HashMap::new(),
+            task_state_requests: std::collections::HashMap::new(),
+            task_state_replies: std::collections::HashMap::new(),
+            irv_rounds: std::collections::HashMap::new(),
+            board_acceptances: std::collections::HashMap::new(),
+            registered_names: std::collections::HashMap::new(),
+            pending_verifications: std::collections::HashMap::new(),
+            verified_agents: std::collections::HashSet::new(),
+            direct_messages: Vec::new(),
+            plan_approval_timeout_secs: DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS,
+            pending_plan_approvals: std::collections::HashMap::new(),
+            task_templates: std::collections::HashMap::new(),
+            scheduler: Scheduler::new(),
+            task_costs: std::collections::HashMap::new(),
+            voting_config: wws_consensus::voting::VotingConfig::default(),
+            reveal_timeout_secs: None,
+            max_round_age_secs: DEFAULT_MAX_ROUND_AGE_SECS,
+            max_tender_attempts: DEFAULT_MAX_TENDER_ATTEMPTS,
+            retender_relax_tier: false,
+            task_tenders: std::collections::HashMap::new(),
+            partition_detector: crate::partition::PartitionDetector::new(
+                crate::partition::DEFAULT_PARTITION_THRESHOLD,
+            ),
+            merge: crate::merge::MergeTracker::default(),
+            shards: crate::shard::ShardTracker::default(),
+            relays: crate::delegation::RelayTracker::default(),
+            reputation: crate::reputation::ReputationLedger::new(Box::new(
+                crate::storage::InMemoryAttestationStore::new(),
+            )),
+            quorum_policy: QuorumPolicy::default(),
+            assignment_strategy: AssignmentStrategy::default(),
+            assignment_seed: None,
+            committee_size: 0,
+            held_tasks: std::collections::HashMap::new(),
+            verification_rate: 0.0,
+            proposal_bond: 0,
+            proposal_bonds: ProposalBonds::default(),
+            verifications: std::collections::HashMap::new(),
+            round_restarts: std::collections::HashMap::new(),
+            board_config: BoardConfig::default(),
+            board_formations: std::collections::HashMap::new(),
+            local_agents: AgentCluster::default(),
+            identity_key: wws_protocol::crypto::generate_keypair(),
+            did_documents: DidDirectory::default(),
+            swarm_params: None,
+            params_proposals: std::collections::HashMap::new(),
+            result_schema_violations: std::collections::HashMap::new(),
+            task_progress: std::collections::HashMap::new(),
+            blocked_tasks: std::collections::HashMap::new(),
+            plan_policy: PlanPolicyConfig::default(),
+            board_channels: std::collections::HashMap::new(),
+            election_vote_due: None,
+            memberships: std::collections::HashMap::new(),
+            task_swarms: std::collections::HashMap::new(),
+            name_directory: NameDirectory::default(),
+            agent_capabilities: std::collections::HashMap::new(),
+            capabilities: Default::default(),
+            task_winning_plans: std::collections::HashMap::new(),
+            agent_services: std::collections::HashMap::new(),
+            did_republish_due: false,
+            agent_loads: std::collections::HashMap::new(),
+            max_concurrent_tasks: None,
+            picked_up_tasks: std::collections::HashSet::new(),
+            task_deliveries: TaskDeliveries::default(),
+            restarts: 0,
+            tier_subscriptions: std::collections::HashMap::new(),
+            config_reload: ConfigReload::default(),
+            work_stealing: WorkStealing::default(),
+            agent_roles: std::collections::HashMap::new(),
+            trace_log: None,
+            topic_keys: None,
+            log_sinks: Vec::new(),
+            webhooks: Webhooks::default(),
+            peer_rtt: std::collections::HashMap::new(),
+        }
+    }
+
+    fn insert_gated_task(state: &mut ConnectorState) -> String {
+        let mut task = Task::new("gated task".to_string(), 1, 1);
+        task.requires_approval = true;
+        let task_id = task.task_id.clone();
+        state.task_set.add(task_id.clone());
+        state.task_details.insert(task_id.clone(), task);
+        task_id
+    }
+
+    #[test]
+    fn approved_plan_is_released_for_assignment() {
+        let mut state = test_state();
+        let task_id = insert_gated_task(&mut state);
+
+        state.hold_plan_for_approval(&task_id, "plan-a");
+        assert!(state.take_approved_plans(chrono::Utc::now()).is_empty());
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::VotingPhase);
+
+        assert_eq!(state.approve_plan(&task_id, "operator"), Some("plan-a".to_string()));
+        let released = state.take_approved_plans(chrono::Utc::now());
+        assert_eq!(released, vec![(task_id.clone(), "plan-a".to_string())]);
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::InProgress);
+        assert!(state.pending_plan_approvals.is_empty());
+    }
 
-                let assign_params = TaskAssignmentParams {
-                    task: reassigned_task,
-                    assignee: AgentId::new(new_assignee),
-                    parent_task_id: parent_id,
-                    winning_plan_id: "reassign-timeout".to_string(),
-                };
-                let assign_msg = SwarmMessage::new(
-                    ProtocolMethod::TaskAssignment.as_str(),
-                    serde_json::to_value(&assign_params).unwrap_or_default(),
-                    String::new(),
-                );
-                if let Ok(data) = serde_json::to_vec(&assign_msg) {
-                    let topic = SwarmTopics::tasks_for(swarm_id.as_str(), task_snapshot.tier_level);
-                    publishes.push((topic, data, task_id.clone()));
-                }
+    #[test]
+    fn rejected_plan_closes_task() {
+        let mut state = test_state();
+        let task_id = insert_gated_task(&mut state);
+
+        state.hold_plan_for_approval(&task_id, "plan-a");
+        assert_eq!(
+            state.reject_plan(&task_id, "operator", "too risky"),
+            Some("plan-a".to_string())
+        );
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::Rejected);
+        assert!(!state.task_set.contains(&task_id));
+        assert!(state.approve_plan(&task_id, "operator").is_none());
+    }
+
+    #[test]
+    fn progress_from_the_assignee_holds_off_reassignment() {
+        let mut state = test_state();
+        let now = chrono::Utc::now();
+        let worker = AgentId::new("did:swarm:worker".to_string());
+        let mut task = Task::new("long crawl".to_string(), 2, 1);
+        task.status = TaskStatus::InProgress;
+        task.assigned_to = Some(worker.clone());
+        task.deadline = Some(now - chrono::Duration::seconds(1));
+        let task_id = task.task_id.clone();
+        state.task_details.insert(task_id.clone(), task);
+
+        let report = |agent: &AgentId, percent: u8, at: chrono::DateTime<chrono::Utc>| {
+            TaskProgressParams {
+                task_id: task_id.clone(),
+                agent_id: agent.clone(),
+                percent,
+                note: "fetched 40 of 100 pages".to_string(),
+                intermediate_cid: Some("cid-partial".to_string()),
+                timestamp: at,
             }
+        };
+        let stranger = AgentId::new("did:swarm:stranger".to_string());
+        assert!(state.record_task_progress(report(&stranger, 40, now), now).is_err());
+        assert!(state.record_task_progress(report(&worker, 101, now), now).is_err());
+        assert!(!state.progress_is_recent(&task_id, now));
+
+        state.record_task_progress(report(&worker, 40, now), now).unwrap();
+        assert!(state.progress_is_recent(&task_id, now));
+        let event = state.task_timelines.get(&task_id).unwrap().last().unwrap();
+        assert_eq!(event.stage, "progress");
+        assert_eq!(event.detail, "40%: fetched 40 of 100 pages (intermediate cid-partial)");
+        // An older report does not replace the latest one.
+        let earlier = now - chrono::Duration::seconds(5);
+        assert!(state.record_task_progress(report(&worker, 10, earlier), now).is_err());
+        assert_eq!(state.task_progress[&task_id].percent, 40);
+
+        state.mark_overdue_tasks(now);
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::Overdue);
+        let later = now + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS);
+        assert!(!state.progress_is_recent(&task_id, later));
+    }
+
+    #[test]
+    fn exhausted_reassignment_blocks_the_task_until_retried() {
+        let mut state = test_state();
+        let worker = AgentId::new("did:swarm:worker".to_string());
+        let root = Task::new("root".to_string(), 1, 1);
+        let mut task = Task::new("crawl".to_string(), 2, 1);
+        task.parent_task_id = Some(root.task_id.clone());
+        task.status = TaskStatus::InProgress;
+        task.assigned_to = Some(worker.clone());
+        let task_id = task.task_id.clone();
+        state.task_details.insert(root.task_id.clone(), root.clone());
+        state.task_details.insert(task_id.clone(), task);
+
+        let escalation = TaskEscalationParams {
+            task_id: task_id.clone(),
+            parent_task_id: root.task_id.clone(),
+            coordinator: AgentId::new("did:swarm:coord".to_string()),
+            previous_assignee: Some(worker.clone()),
+            reason: "no capable agent left".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert!(state.retry_blocked_task(&task_id, "op").is_err());
+        assert!(state.block_task(escalation.clone()));
+        assert!(!state.block_task(escalation));
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::Blocked);
+        assert_eq!(state.open_escalations().len(), 1);
+        let event = state.task_timelines.get(&task_id).unwrap().last().unwrap();
+        assert_eq!(event.stage, "blocked");
+
+        let assignment = state.retry_blocked_task(&task_id, "op").unwrap();
+        assert_eq!(assignment.assignee, worker);
+        assert_eq!(assignment.winning_plan_id, "retry-operator");
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::InProgress);
+        assert!(state.task_details[&task_id].deadline.is_some());
+        assert!(state.open_escalations().is_empty());
+        assert!(state.retry_blocked_task(&task_id, "op").is_err());
+    }
+
+    #[test]
+    fn task_assignment_strategy_overrides_the_swarm_default() {
+        let mut state = test_state();
+        state.assignment_strategy = AssignmentStrategy::RoundRobin;
+        let plain = Task::new("plain".to_string(), 1, 1);
+        let mut pinned = Task::new("pinned".to_string(), 1, 1);
+        pinned.assignment = Some(AssignmentStrategy::CapabilityScore);
+        let (plain_id, pinned_id) = (plain.task_id.clone(), pinned.task_id.clone());
+        state.task_details.insert(plain_id.clone(), plain);
+        state.task_details.insert(pinned_id.clone(), pinned);
+
+        assert_eq!(state.assignment_strategy_for(&plain_id), AssignmentStrategy::RoundRobin);
+        assert_eq!(
+            state.assignment_strategy_for(&pinned_id),
+            AssignmentStrategy::CapabilityScore
+        );
+
+        let agent = AgentId::new("did:swarm:worker".to_string());
+        state
+            .agent_capabilities
+            .insert(agent.to_string(), vec!["web_search".to_string()]);
+        let candidate = state.assignment_candidate(&agent);
+        assert_eq!(candidate.capabilities, vec!["web_search"]);
+        assert_eq!(candidate.reputation, 10);
+    }
+
+    #[test]
+    fn spend_aggregates_up_the_task_tree() {
+        let mut state = test_state();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        root.budget = Some(10.0);
+        let root_id = root.task_id.clone();
+        for i in 1..=2 {
+            let sub_id = format!("{}-st-{}", root_id, i);
+            root.subtasks.push(sub_id.clone());
+            let mut sub = Task::new(format!("sub {}", i), 2, 1);
+            sub.task_id = sub_id;
+            sub.parent_task_id = Some(root_id.clone());
+            state.task_details.insert(sub.task_id.clone(), sub);
         }
+        state.task_details.insert(root_id.clone(), root);
+
+        state.record_task_cost(&format!("{}-st-1", root_id), 4.0);
+        state.record_task_cost(&format!("{}-st-2", root_id), 3.0);
+        // A resubmission replaces the earlier figure.
+        state.record_task_cost(&format!("{}-st-2", root_id), 5.0);
+        assert_eq!(state.task_spend(&root_id), 9.0);
+        assert_eq!(state.budget_remaining(&format!("{}-st-1", root_id)), Some(1.0));
+        assert!(!state.budget_exhausted(&root_id));
+
+        state.record_task_cost(&root_id, 1.5);
+        assert!(state.budget_exhausted(&root_id));
+        assert!(state.budget_exhausted(&format!("{}-st-2", root_id)));
+    }
 
-        for (topic, data, task_id) in publishes {
-            if let Err(e) = self.network_handle.publish(&topic, data).await {
-                tracing::error!(task_id = %task_id, topic = %topic, error = %e, "Failed to publish reassignment");
-            }
+    #[test]
+    fn spend_of_cyclic_subtasks_is_counted_once() {
+        let mut state = test_state();
+        for (id, sub) in [("a", "b"), ("b", "a")] {
+            let mut task = Task::new(id.to_string(), 1, 1);
+            task.task_id = id.to_string();
+            task.subtasks = vec![sub.to_string()];
+            task.parent_task_id = Some(sub.to_string());
+            task.budget = Some(10.0);
+            state.task_details.insert(id.to_string(), task);
         }
+        state.record_task_cost("a", 2.0);
+        state.record_task_cost("b", 3.0);
+        assert_eq!(state.task_spend("a"), 5.0);
+        assert_eq!(state.budget_remaining("b"), Some(5.0));
     }
 
-    /// Assign subtasks from the winning plan to subordinate agents.
-    async fn assign_subtasks_from_winner(
-        &self,
-        task_id: &str,
-        winner_plan_id: &str,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut state = self.state.write().await;
+    #[test]
+    fn epoch_compaction_bounds_tombstones() {
+        let mut state = test_state();
+        for i in 0..5 {
+            let task_id = format!("task-{}", i);
+            state.task_set.add(task_id.clone());
+            state.task_set.remove(&task_id);
+        }
+        state.task_set.add("task-live".to_string());
+        state.member_set.add("did:swarm:gone".to_string());
+        state.member_set.remove("did:swarm:gone");
+
+        assert_eq!(state.compact_tombstones(), 6);
+        assert_eq!(state.task_set.tombstone_count(), 0);
+        assert_eq!(state.member_set.tombstone_count(), 0);
+        assert_eq!(state.task_set.elements(), vec!["task-live".to_string()]);
+    }
 
-        // Get the winning plan from RFP coordinator
-        let winning_plan = {
-            let rfp = state.rfp_coordinators.get(task_id)
-                .ok_or("RFP coordinator not found")?;
+    #[test]
+    fn hierarchy_changes_are_applied_and_logged_as_a_diff() {
+        let mut state = test_state();
+        let mut members: Vec<String> = (0..30).map(|i| format!("did:swarm:m{:02}", i)).collect();
+        members.push(state.agent_id.to_string());
+        state.recompute_hierarchy(&members);
+        let parents = state.agent_parents.clone();
+        assert_eq!(state.agent_tiers.len(), members.len());
+        let logged = state.event_log.len();
+
+        state.recompute_hierarchy(&members);
+        assert_eq!(state.event_log.len(), logged);
+
+        members.push("did:swarm:late".to_string());
+        state.recompute_hierarchy(&members);
+        let kept = parents
+            .iter()
+            .filter(|(child, parent)| state.agent_parents.get(*child) == Some(*parent))
+            .count();
+        assert!(kept * 10 >= parents.len() * 8, "only {} of {} kept their parent", kept, parents.len());
+        let last = &state.event_log.last().unwrap().message;
+        assert!(last.starts_with("Hierarchy changed:"));
+        let children: usize = state.subordinates.values().map(Vec::len).sum();
+        assert_eq!(children, state.agent_parents.len());
+    }
 
-            // Get the revealed proposal matching the winner
-            let revealed_proposal = rfp.reveals
-                .values()
-                .find(|p| p.plan.plan_id == winner_plan_id)
-                .ok_or("Winning plan not found in reveals")?;
+    #[test]
+    fn announced_layout_roles_constrain_the_hierarchy() {
+        let mut state = test_state();
+        let mut members: Vec<String> = (0..30).map(|i| format!("did:swarm:m{:02}", i)).collect();
+        members.push(state.agent_id.to_string());
+        state.recompute_hierarchy(&members);
+        let pinned = members
+            .iter()
+            .find(|id| state.agent_tiers[*id] == Tier::Executor)
+            .unwrap()
+            .clone();
+        let exempt = members
+            .iter()
+            .find(|id| state.agent_tiers[*id] == Tier::Tier1)
+            .unwrap()
+            .clone();
+
+        state.note_role(&pinned, Some(LayoutRole::Coordinator));
+        state.note_role(&exempt, Some(LayoutRole::Executor));
+        state.recompute_hierarchy(&members);
+        assert_eq!(state.agent_tiers[&pinned], Tier::Tier1);
+        assert_eq!(state.agent_tiers[&exempt], Tier::Executor);
+        assert!(!state.subordinates.contains_key(&exempt));
+
+        // A keep-alive without a role lifts the constraint.
+        state.note_role(&pinned, None);
+        assert!(!state.agent_roles.contains_key(&pinned));
+    }
 
-            revealed_proposal.plan.clone()
+    #[test]
+    fn idle_executors_steal_queued_work_one_transfer_at_a_time() {
+        let mut state = test_state();
+        let me = state.agent_id.to_string();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        root.status = TaskStatus::InProgress;
+        state.task_details.insert(root.task_id.clone(), root.clone());
+        let mut queued = Vec::new();
+        for n in 1..=2 {
+            let mut task = Task::new(format!("part {}", n), 2, 1);
+            task.task_id = format!("{}-st-{}", root.task_id, n);
+            task.parent_task_id = Some(root.task_id.clone());
+            task.status = TaskStatus::InProgress;
+            task.assigned_to = Some(AgentId::new("did:swarm:busy".to_string()));
+            queued.push(task.task_id.clone());
+            state.task_details.insert(task.task_id.clone(), task);
+        }
+        let siblings = ["did:swarm:busy", "did:swarm:idle", "did:swarm:idle2"];
+        state.subordinates.insert(me.clone(), siblings.iter().map(|s| s.to_string()).collect());
+        for sibling in siblings {
+            state.member_set.add(sibling.to_string());
+        }
+        state.note_load(
+            "did:swarm:busy",
+            AgentLoad {
+                queue_depth: 2,
+                queued: queued.clone(),
+                ..AgentLoad::default()
+            },
+        );
+        let request = |agent: &str, parent: &str| WorkStealRequestParams {
+            agent_id: AgentId::new(agent.to_string()),
+            parent_id: AgentId::new(parent.to_string()),
+            timestamp: chrono::Utc::now(),
         };
+        let now = chrono::Utc::now();
 
-        // Get my subordinates for assignment
-        let raw_subordinates: Vec<AgentId> = state.subordinates
-            .get(state.agent_id.as_str())
-            .cloned()
-            .unwrap_or_default()
-            .into_iter()
-            .map(AgentId::new)
-            .collect();
-
-        // In single-node mode (swarm_size==1), self-assign so the sole connector executes.
-        // In multi-node mode, Tier1 nodes without subordinates are NOT the designated
-        // coordinator — skip assignment to avoid duplicate assignments from multiple Tier1s.
-        let swarm_size = state.active_member_count(
-            std::time::Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)
-        ) as usize;
-        let subordinates: Vec<AgentId> = if raw_subordinates.is_empty() {
-            if swarm_size <= 1 {
-                tracing::info!(
-                    task_id = %task_id,
-                    agent_id = %state.agent_id,
-                    "No subordinates (single-node mode): self-assigning subtasks"
-                );
-                vec![state.agent_id.clone()]
-            } else {
-                tracing::info!(
-                    task_id = %task_id,
-                    agent_id = %state.agent_id,
-                    swarm_size,
-                    "No subordinates in multi-node swarm: skipping assignment (not the designated coordinator)"
-                );
-                return Ok(());
-            }
-        } else {
-            raw_subordinates
+        assert!(state.arbitrate_steal(&request("did:swarm:idle", "did:swarm:other"), now).is_err());
+        assert!(state.arbitrate_steal(&request("did:swarm:stranger", &me), now).is_err());
+        let stolen = state.arbitrate_steal(&request("did:swarm:idle", &me), now).unwrap();
+        assert_eq!(stolen.task.task_id, queued[1]);
+        assert_eq!(stolen.assignee.as_str(), "did:swarm:idle");
+        assert_eq!(stolen.winning_plan_id, STEAL_PLAN_ID);
+        assert!(state.task_timelines[&queued[1]].iter().any(|e| e.stage == "stolen"));
+
+        // The first transfer settles before another is granted.
+        assert!(state.arbitrate_steal(&request("did:swarm:idle2", &me), now).is_err());
+        let holding = AgentLoad {
+            queue_depth: 1,
+            ..AgentLoad::default()
         };
+        state.note_load("did:swarm:idle", holding);
+        let second = state.arbitrate_steal(&request("did:swarm:idle2", &me), now).unwrap();
+        assert_eq!(second.task.task_id, queued[0]);
+        state.note_load("did:swarm:idle2", AgentLoad { in_flight: 1, ..AgentLoad::default() });
+        assert!(state.arbitrate_steal(&request("did:swarm:idle", &me), now).is_err());
+    }
 
-        // Idempotency: if subtasks already exist for this task, another coordinator already
-        // assigned them. Skip to avoid competing assignments from multiple Tier1 nodes.
-        let first_subtask_id = format!("{}-st-1", task_id);
-        if state.task_details.contains_key(&first_subtask_id) {
-            tracing::info!(
-                task_id = %task_id,
-                agent_id = %state.agent_id,
-                "Subtasks already assigned by another coordinator — skipping duplicate assignment"
-            );
-            return Ok(());
+    #[test]
+    fn exported_runs_import_into_another_connector_without_executing() {
+        let mut state = test_state();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        root.status = TaskStatus::Completed;
+        let mut child = Task::new("child".to_string(), 2, 1);
+        child.parent_task_id = Some(root.task_id.clone());
+        child.status = TaskStatus::Completed;
+        root.subtasks = vec![child.task_id.clone()];
+        let other = Task::new("unrelated".to_string(), 1, 1);
+        for task in [&root, &child, &other] {
+            state.task_details.insert(task.task_id.clone(), task.clone());
         }
+        state.push_task_timeline_event(&root.task_id, "completed", "done".to_string(), None);
+        state.ballot_records.insert(
+            root.task_id.clone(),
+            vec![BallotRecord {
+                task_id: root.task_id.clone(),
+                voter: AgentId::new("did:swarm:voter".to_string()),
+                rankings: vec!["plan-a".to_string()],
+                critic_scores: std::collections::HashMap::new(),
+                timestamp: chrono::Utc::now(),
+                irv_round_when_eliminated: None,
+                abstain: false,
+            }],
+        );
+        state.task_result_text.insert(child.task_id.clone(), "42".to_string());
+
+        let transcript = state.export_run(&root.task_id).unwrap();
+        assert_eq!(transcript.tasks.len(), 2);
+        assert_eq!(transcript.tasks[0].task_id, root.task_id);
+        assert!(state.export_run("missing").is_err());
+        let json = serde_json::to_string(&transcript).unwrap();
+
+        let mut replay = test_state();
+        replay.task_details.insert(child.task_id.clone(), Task::new("live".to_string(), 2, 1));
+        let imported = replay.import_run(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(imported, vec![root.task_id.clone()]);
+        assert_eq!(replay.ballot_records[&root.task_id].len(), 1);
+        assert_eq!(replay.task_timelines[&root.task_id].len(), 1);
+        assert_eq!(replay.task_details[&child.task_id].description, "live");
+        assert!(!replay.task_result_text.contains_key(&child.task_id));
+        assert!(!replay.task_set.contains(&root.task_id));
+
+        let mut future = transcript;
+        future.format_version += 1;
+        assert!(test_state().import_run(future).is_err());
+    }
 
-        let parent_tier = state.task_details
-            .get(task_id)
-            .map(|t| t.tier_level)
-            .unwrap_or(1);
+    #[test]
+    fn operator_cancels_a_task_tree_and_reassigns_open_subtasks() {
+        let mut state = test_state();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        root.status = TaskStatus::InProgress;
+        let mut open = Task::new("open".to_string(), 2, 1);
+        open.parent_task_id = Some(root.task_id.clone());
+        open.status = TaskStatus::InProgress;
+        open.assigned_to = Some(AgentId::new("did:swarm:slow".to_string()));
+        let mut done = Task::new("done".to_string(), 2, 1);
+        done.parent_task_id = Some(root.task_id.clone());
+        done.status = TaskStatus::Completed;
+        root.subtasks = vec![open.task_id.clone(), done.task_id.clone()];
+        for task in [&root, &open, &done] {
+            state.task_details.insert(task.task_id.clone(), task.clone());
+        }
 
-        let swarm_id = state.current_swarm_id.clone();
-        let mut subtask_ids = Vec::new();
-        let mut assignment_messages = Vec::new();
+        state.member_set.add("did:swarm:fast".to_string());
+        assert!(state.reassign_task(&root.task_id, "did:swarm:fast", "op").is_err());
+        let assignment = state.reassign_task(&open.task_id, "did:swarm:fast", "op").unwrap();
+        assert_eq!(assignment.assignee.as_str(), "did:swarm:fast");
+        assert_eq!(assignment.parent_task_id, root.task_id);
+        assert!(state.reassign_task(&open.task_id, "did:swarm:fast", "op").is_err());
+
+        let closed = state.cancel_task(&root.task_id, "op").unwrap();
+        assert_eq!(closed.len(), 2);
+        assert_eq!(state.task_details[&open.task_id].status, TaskStatus::Rejected);
+        assert_eq!(state.task_details[&done.task_id].status, TaskStatus::Completed);
+        assert!(state.cancel_task(&root.task_id, "op").is_err());
+    }
 
-        const COMPLEXITY_RECURSE_THRESHOLD: f64 = 0.4;
+    #[test]
+    fn manual_reassignment_checks_membership_tier_and_capabilities() {
+        let mut state = test_state();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        root.status = TaskStatus::InProgress;
+        let mut task = Task::new("scrape".to_string(), 2, 1);
+        task.parent_task_id = Some(root.task_id.clone());
+        task.status = TaskStatus::InProgress;
+        task.assigned_to = Some(AgentId::new("did:swarm:slow".to_string()));
+        task.capabilities_required = vec!["research.web_search".to_string()];
+        state.capabilities = CapabilityRegistry::new(&crate::capabilities::CapabilityConfig {
+            known: Vec::new(),
+            aliases: [("web_search".to_string(), "research.web_search".to_string())]
+                .into_iter()
+                .collect(),
+        });
+        for t in [&root, &task] {
+            state.task_details.insert(t.task_id.clone(), t.clone());
+        }
+        state.agent_tiers.insert("did:swarm:slow".to_string(), Tier::Executor);
+        for (agent, tier, caps) in [
+            ("did:swarm:chair", Tier::Tier2, vec!["web_search"]),
+            // Only the namespace falls short; the alias is as good as the name.
+            ("did:swarm:offline", Tier::Executor, vec!["code", "research"]),
+            ("did:swarm:crawler", Tier::Executor, vec!["web_search", "code"]),
+        ] {
+            state.member_set.add(agent.to_string());
+            state.agent_tiers.insert(agent.to_string(), tier);
+            let caps: Vec<String> = caps.into_iter().map(String::from).collect();
+            state.note_capabilities(agent, &caps);
+        }
 
-        // Create subtasks and assignment messages
-        for (idx, subtask_spec) in winning_plan.subtasks.iter().enumerate() {
-            let subtask_id = format!("{}-st-{}", task_id, idx + 1);
-            let is_complex = subtask_spec.estimated_complexity > COMPLEXITY_RECURSE_THRESHOLD;
+        let unknown = state.reassign_task(&task.task_id, "did:swarm:nobody", "op");
+        assert!(unknown.unwrap_err().contains("unknown agent"));
+        let wrong_tier = state.reassign_task(&task.task_id, "did:swarm:chair", "op");
+        assert!(wrong_tier.is_err());
+        let missing = state.reassign_task(&task.task_id, "did:swarm:offline", "op");
+        assert!(missing.unwrap_err().contains("web_search"));
+        assert_eq!(
+            state.task_details[&task.task_id].assigned_to.as_ref().map(|a| a.as_str()),
+            Some("did:swarm:slow")
+        );
 
-            if is_complex {
-                // High-complexity subtask: spawn a sub-holon via TaskInjection so any
-                // available coordinator forms a new deliberation board for it.
-                let subtask = Task {
-                    task_id: subtask_id.clone(),
-                    parent_task_id: Some(task_id.to_string()),
-                    epoch: winning_plan.epoch,
-                    status: TaskStatus::Pending,
-                    description: subtask_spec.description.clone(),
-                    assigned_to: None,
-                    // Keep at parent tier so coordinator-tier agents pick it up
-                    tier_level: parent_tier,
-                    subtasks: Vec::new(),
-                    created_at: chrono::Utc::now(),
-                    deadline: None,
-                    capabilities_required: subtask_spec.required_capabilities.clone(),
-                    ..Default::default()
-                };
+        let assignment = state.reassign_task(&task.task_id, "did:swarm:crawler", "op").unwrap();
+        assert_eq!(assignment.assignee.as_str(), "did:swarm:crawler");
+        let audit = state.event_log.last().map(|e| e.message.as_str()).unwrap_or_default();
+        assert!(audit.starts_with("AUDIT task.reassign"));
+        assert!(audit.ends_with("to=did:swarm:crawler"));
+    }
 
-                state.task_details.insert(subtask_id.clone(), subtask.clone());
-                subtask_ids.push(subtask_id.clone());
+    fn node_identity() -> (String, ed25519_dalek::SigningKey) {
+        let key = wws_protocol::crypto::generate_keypair();
+        let peer_id = wws_network::Keypair::ed25519_from_bytes(key.to_bytes())
+            .unwrap()
+            .public()
+            .to_peer_id();
+        (format!("did:swarm:{}", peer_id), key)
+    }
 
-                // Create a child HolonState for this sub-holon
-                let my_agent_id = state.agent_id.clone();
-                state.active_holons.entry(subtask_id.clone()).or_insert_with(|| HolonState {
-                    task_id: subtask_id.clone(),
-                    chair: my_agent_id,
-                    members: Vec::new(),
-                    adversarial_critic: None,
-                    depth: parent_tier + 1,
-                    parent_holon: Some(task_id.to_string()),
-                    child_holons: Vec::new(),
-                    subtask_assignments: std::collections::HashMap::new(),
-                    status: HolonStatus::Forming,
-                    created_at: chrono::Utc::now(),
-                });
-                // Register sub-holon in parent's child_holons list
-                if let Some(parent_holon) = state.active_holons.get_mut(task_id) {
-                    if !parent_holon.child_holons.contains(&subtask_id) {
-                        parent_holon.child_holons.push(subtask_id.clone());
-                    }
-                }
+    #[test]
+    fn results_are_sealed_to_the_members_allowed_to_read_them() {
+        let mut state = test_state();
+        let (producer, _) = node_identity();
+        let (outsider, outsider_key) = node_identity();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        root.visibility = ResultVisibility::BoardOnly;
+        let mut task = Task::new("secret work".to_string(), 2, 1);
+        task.parent_task_id = Some(root.task_id.clone());
+        task.visibility = ResultVisibility::BoardOnly;
+        for t in [&root, &task] {
+            state.task_details.insert(t.task_id.clone(), t.clone());
+        }
+        state.active_holons.insert(
+            root.task_id.clone(),
+            HolonState {
+                task_id: root.task_id.clone(),
+                chair: state.agent_id.clone(),
+                members: vec![AgentId::new(producer.clone())],
+                adversarial_critic: None,
+                depth: 0,
+                parent_holon: None,
+                child_holons: Vec::new(),
+                subtask_assignments: std::collections::HashMap::new(),
+                child_artifacts: std::collections::HashMap::new(),
+                status: HolonStatus::Executing,
+                created_at: chrono::Utc::now(),
+            },
+        );
 
-                state.push_task_timeline_event(
-                    task_id,
-                    "sub_holon_forming",
-                    format!(
-                        "Sub-holon forming for complex subtask {} (complexity={:.2})",
-                        subtask_id, subtask_spec.estimated_complexity
-                    ),
-                    None,
-                );
+        // The board of the nearest ancestor decides who reads the result.
+        let audience = state.result_audience(&task.task_id, &producer).unwrap();
+        assert_eq!(audience.len(), 2);
+        assert!(audience.contains(&producer) && !audience.contains(&outsider));
+
+        let mut artifact: Artifact = serde_json::from_value(serde_json::json!({})).unwrap();
+        state.seal_result(&task.task_id, &producer, &mut artifact, "the answer").unwrap();
+        let sealed = artifact.sealed.clone().unwrap();
+        assert_eq!(state.open_result(&task.task_id, &sealed).as_deref(), Some("the answer"));
+        assert!(sealed.open(&task.task_id, &outsider, &outsider_key).is_err());
+
+        state.task_results.insert(task.task_id.clone(), artifact);
+        assert!(state.may_view_result(&task.task_id, &producer));
+        assert!(!state.may_view_result(&task.task_id, &outsider));
+
+        // Tier-and-above reaches every member at the task's tier or higher.
+        let (executor, _) = node_identity();
+        state.agent_tiers.insert(outsider.clone(), Tier::Tier2);
+        state.agent_tiers.insert(executor.clone(), Tier::Executor);
+        state.task_details.get_mut(&task.task_id).unwrap().visibility = ResultVisibility::TierAndAbove;
+        let audience = state.result_audience(&task.task_id, &producer).unwrap();
+        assert!(audience.contains(&outsider) && !audience.contains(&executor));
+
+        // Public results are not sealed.
+        state.task_details.get_mut(&task.task_id).unwrap().visibility = ResultVisibility::Public;
+        let mut public: Artifact = serde_json::from_value(serde_json::json!({})).unwrap();
+        state.seal_result(&task.task_id, &producer, &mut public, "open").unwrap();
+        assert!(public.sealed.is_none());
+    }
 
-                let inject_params = TaskInjectionParams {
-                    task: subtask,
-                    originator: state.agent_id.clone(),
-                };
-                let inject_msg = SwarmMessage::new(
-                    ProtocolMethod::TaskInjection.as_str(),
-                    serde_json::to_value(&inject_params).unwrap_or_default(),
-                    String::new(),
-                );
-                if let Ok(data) = serde_json::to_vec(&inject_msg) {
-                    let topic = SwarmTopics::tasks_for(swarm_id.as_str(), parent_tier);
-                    assignment_messages.push((topic, data));
-                }
+    #[test]
+    fn task_context_gathers_lineage_plan_siblings_and_attachments() {
+        let mut state = test_state();
+        let mut root = Task::new("Write a market report".to_string(), 1, 1);
+        root.task_id = "root".to_string();
+        let cid = state.store_attachment("Q3 sales were up 12%.");
+        root.attachments.push(TaskAttachment {
+            name: "sales.txt".to_string(),
+            cid: cid.clone(),
+            size_bytes: 21,
+            content_type: Some("text/plain".to_string()),
+        });
+        let mut mine = Task::new("Summarize the sales figures".to_string(), 2, 1);
+        mine.task_id = "root-st-1".to_string();
+        mine.parent_task_id = Some(root.task_id.clone());
+        mine.attachments = root.attachments.clone();
+        let mut sibling = Task::new("Collect competitor prices".to_string(), 2, 1);
+        sibling.task_id = "root-st-2".to_string();
+        sibling.parent_task_id = Some(root.task_id.clone());
+        sibling.status = TaskStatus::Completed;
+        root.subtasks = vec![mine.task_id.clone(), sibling.task_id.clone()];
+        for t in [&root, &mine, &sibling] {
+            state.task_details.insert(t.task_id.clone(), t.clone());
+        }
+        state
+            .task_result_text
+            .insert(sibling.task_id.clone(), "Prices fell 3% on average.".to_string());
+
+        let mut plan = Plan::new(root.task_id.clone(), AgentId::new("did:swarm:planner".to_string()), 1);
+        plan.plan_id = "plan-a".to_string();
+        plan.rationale = "Figures and prices are independent.".to_string();
+        let mut rfp = RfpCoordinator::new(root.task_id.clone(), 1, 1);
+        rfp.reveals.insert(
+            plan.proposer.clone(),
+            wws_consensus::rfp::RevealedProposal {
+                proposer: plan.proposer.clone(),
+                plan,
+                plan_hash: String::new(),
+            },
+        );
+        state.rfp_coordinators.insert(root.task_id.clone(), rfp);
+        state.task_winning_plans.insert(root.task_id.clone(), "plan-a".to_string());
+
+        assert!(state.task_context("missing", "did:swarm:me", ContextLimits::default()).is_none());
+        let context = state.task_context(&mine.task_id, "did:swarm:me", ContextLimits::default()).unwrap();
+        assert_eq!(context.description.text.as_deref(), Some("Summarize the sales figures"));
+        assert_eq!(context.ancestors.len(), 1);
+        assert_eq!(context.ancestors[0].task_id, "root");
+        let plan = context.plan.as_ref().unwrap();
+        assert_eq!(plan.plan_id, "plan-a");
+        assert_eq!(plan.subtask_index, Some(1));
+        assert_eq!(
+            plan.rationale.as_ref().and_then(|r| r.text.as_deref()),
+            Some("Figures and prices are independent.")
+        );
+        assert_eq!(context.siblings.len(), 1);
+        assert_eq!(
+            context.siblings[0].result.as_ref().and_then(|r| r.text.as_deref()),
+            Some("Prices fell 3% on average.")
+        );
+        // The parent's attachment is listed once, under the task itself.
+        assert_eq!(context.attachments.len(), 1);
+        assert_eq!(context.attachments[0].task_id, mine.task_id);
+        assert!(!context.truncated);
+
+        // A tight budget keeps the task's own description and cuts the rest.
+        let tight = state.task_context(&mine.task_id, "did:swarm:me", ContextLimits::with_max_bytes(120)).unwrap();
+        assert_eq!(tight.description.text.as_deref(), Some("Summarize the sales figures"));
+        assert!(tight.truncated);
+        assert_eq!(tight.used_bytes, 120);
+        assert!(tight.attachments[0].content.as_ref().is_some_and(|c| c.text.is_none()));
+    }
 
-                tracing::info!(
-                    task_id = %task_id,
-                    subtask_id = %subtask_id,
-                    complexity = subtask_spec.estimated_complexity,
-                    "Complex subtask spawning sub-holon via TaskInjection"
-                );
-            } else {
-                // Low-complexity subtask: direct assignment to a subordinate executor
-                let assignee = subordinates[idx % subordinates.len()].clone();
+    #[test]
+    fn agent_services_come_from_announcements_then_did_documents() {
+        let mut state = test_state();
+        let own_id = state.agent_id.to_string();
+        let service = |did: &str, name: &str| ServiceEndpoint {
+            id: format!("{}#{}", did, name),
+            service_type: "ReviewApi".to_string(),
+            service_endpoint: format!("https://{}.example.org", name),
+        };
 
-                let subtask = Task {
-                    task_id: subtask_id.clone(),
-                    parent_task_id: Some(task_id.to_string()),
-                    epoch: winning_plan.epoch,
-                    status: TaskStatus::InProgress,
-                    description: subtask_spec.description.clone(),
-                    assigned_to: Some(assignee.clone()),
-                    tier_level: (parent_tier + 1).min(wws_protocol::MAX_HIERARCHY_DEPTH),
-                    subtasks: Vec::new(),
-                    created_at: chrono::Utc::now(),
-                    deadline: Some(
-                        chrono::Utc::now()
-                            + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS),
-                    ),
-                    capabilities_required: subtask_spec.required_capabilities.clone(),
-                    ..Default::default()
-                };
+        state.register_services(vec![service(&own_id, "review")]);
+        assert!(state.did_republish_due);
+        // Nobody else may announce services for this node's agent.
+        state.note_services(&own_id, Some(&[]));
+        assert_eq!(state.services_of(&own_id).unwrap().0.len(), 1);
+
+        // Announcements keep only the sender's own, well-formed services.
+        let peer = "did:swarm:peer";
+        state.note_services(
+            peer,
+            Some(&[service(peer, "review"), service(peer, "p2p"), service("did:swarm:other", "review")]),
+        );
+        let (services, source) = state.services_of(peer).unwrap();
+        assert_eq!((services.len(), source), (1, "announced"));
+        state.note_services(peer, None);
+        assert_eq!(state.services_of(peer).unwrap().0.len(), 1);
+        state.note_services(peer, Some(&[]));
+        assert!(state.services_of(peer).unwrap().0.is_empty());
+
+        // Without announcements, the agent's DID document answers.
+        let key = wws_protocol::crypto::generate_keypair();
+        let did = wws_protocol::crypto::derive_agent_id(&key.verifying_key());
+        assert!(state.services_of(&did).is_none());
+        state
+            .did_documents
+            .insert(DidDocument::new(&did, &key, vec![service(&did, "search")], Vec::new()));
+        let (services, source) = state.services_of(&did).unwrap();
+        assert_eq!(source, "did_document");
+        assert_eq!(services[0].service_endpoint, "https://search.example.org");
+    }
+
+    #[test]
+    fn content_gc_keeps_results_of_incomplete_tasks() {
+        let mut state = test_state();
+        state.content_store = ContentStore::new().with_max_bytes(12);
+        let mut parent = Task::new("parent".to_string(), 1, 1);
+        parent.status = TaskStatus::InProgress;
+        let mut child = Task::new("child".to_string(), 2, 1);
+        child.status = TaskStatus::Completed;
+        child.parent_task_id = Some(parent.task_id.clone());
+        let mut done = Task::new("done".to_string(), 1, 1);
+        done.status = TaskStatus::Completed;
+        state.task_details.insert(parent.task_id.clone(), parent);
+
+        for (task, text) in [(&child, "child result"), (&done, "done result")] {
+            state.task_details.insert(task.task_id.clone(), task.clone());
+            let artifact: Artifact = serde_json::from_value(serde_json::json!({})).unwrap();
+            state.task_results.insert(task.task_id.clone(), artifact);
+            state.task_result_text.insert(task.task_id.clone(), text.to_string());
+            state.store_result_content(text, "did:swarm:worker");
+        }
+
+        assert_eq!(state.collect_content_garbage(), 1);
+        assert!(state.content_store.exists(&ContentStore::compute_cid(b"child result")));
+        assert!(!state.content_store.exists(&ContentStore::compute_cid(b"done result")));
+    }
+
+    #[test]
+    fn attachment_blobs_are_verified_stored_and_pinned() {
+        let mut state = test_state();
+        state.content_store = ContentStore::new().with_max_bytes(16);
+        let brief = "short brief";
+        let mut task = Task::new("task".to_string(), 1, 1);
+        task.attachments.push(TaskAttachment {
+            name: "brief.txt".to_string(),
+            cid: ContentStore::compute_cid(brief.as_bytes()),
+            size_bytes: brief.len() as u64,
+            content_type: None,
+        });
+        let blobs = vec![
+            AttachmentBlob {
+                cid: task.attachments[0].cid.clone(),
+                content: brief.to_string(),
+            },
+            AttachmentBlob {
+                cid: task.attachments[0].cid.clone(),
+                content: "tampered".to_string(),
+            },
+            AttachmentBlob {
+                cid: ContentStore::compute_cid(b"stray"),
+                content: "stray".to_string(),
+            },
+        ];
+
+        assert_eq!(state.store_attachment_blobs(&task, &blobs), 2);
+        assert!(!state.content_store.exists(&ContentStore::compute_cid(b"stray")));
+        assert_eq!(state.attachment_blobs(&task), blobs[..1]);
+
+        state.task_details.insert(task.task_id.clone(), task.clone());
+        state.store_result_content("other result", "did:swarm:worker");
+        assert_eq!(state.collect_content_garbage(), 1);
+        assert!(state.content_store.exists(&task.attachments[0].cid));
+    }
+
+    #[test]
+    fn held_task_is_released_with_prerequisite_results() {
+        let mut state = test_state();
+        let first = Task::new("collect data".to_string(), 1, 1);
+        let second = Task::new("clean data".to_string(), 1, 1);
+        for task in [&first, &second] {
+            state.task_details.insert(task.task_id.clone(), task.clone());
+        }
+        let mut report = Task::new("write report".to_string(), 1, 1);
+        report.depends_on = vec![first.task_id.clone(), second.task_id.clone()];
+        state.hold_task(report.clone());
+        assert_eq!(state.unfinished_prerequisites(&report).len(), 2);
+
+        state.task_details.get_mut(&first.task_id).unwrap().status = TaskStatus::Completed;
+        state.task_result_text.insert(first.task_id.clone(), "42 rows".to_string());
+        assert!(state.take_ready_dependents().is_empty());
+
+        state.task_details.get_mut(&second.task_id).unwrap().status = TaskStatus::Completed;
+        let ready = state.take_ready_dependents();
+        assert_eq!(ready.len(), 1);
+        assert!(ready[0].description.starts_with("write report"));
+        assert!(ready[0].description.contains("42 rows"));
+        assert!(state.held_tasks.is_empty());
+    }
 
-                state.task_details.insert(subtask_id.clone(), subtask.clone());
-                state.bump_tasks_assigned(assignee.as_str());
-                subtask_ids.push(subtask_id.clone());
+    #[test]
+    fn held_task_is_rejected_with_its_prerequisite() {
+        let mut state = test_state();
+        let mut first = Task::new("collect data".to_string(), 1, 1);
+        first.status = TaskStatus::Rejected;
+        state.task_details.insert(first.task_id.clone(), first.clone());
+        let mut report = Task::new("write report".to_string(), 1, 1);
+        report.depends_on = vec![first.task_id.clone()];
+        state.hold_task(report.clone());
+
+        assert!(state.take_ready_dependents().is_empty());
+        assert!(state.held_tasks.is_empty());
+        assert_eq!(state.task_details[&report.task_id].status, TaskStatus::Rejected);
+    }
 
-                state.push_task_timeline_event(
-                    task_id,
-                    "subtask_assigned",
-                    format!("Subtask {} assigned to {}", subtask_id, assignee),
-                    Some(assignee.to_string()),
-                );
+    #[test]
+    fn diverging_reexecution_is_settled_by_board_vote() {
+        let mut state = test_state();
+        let parent = Task::new("survey".to_string(), 1, 1);
+        let mut task = Task::new("count rows".to_string(), 2, 1);
+        task.parent_task_id = Some(parent.task_id.clone());
+        task.status = TaskStatus::Completed;
+        task.assigned_to = Some(AgentId::new("alice".to_string()));
+        let task_id = task.task_id.clone();
+        state.task_details.insert(parent.task_id.clone(), parent.clone());
+        state.task_details.insert(task_id.clone(), task);
+        let artifact: Artifact = serde_json::from_value(serde_json::json!({})).unwrap();
+        state.task_results.insert(task_id.clone(), artifact.clone());
+        state.task_result_text.insert(task_id.clone(), "42".to_string());
+        state.board_acceptances.insert(
+            parent.task_id.clone(),
+            ["carol", "dave"]
+                .iter()
+                .map(|id| BoardAcceptParams {
+                    task_id: parent.task_id.clone(),
+                    agent_id: AgentId::new(id.to_string()),
+                    active_tasks: 0,
+                    capabilities: Vec::new(),
+                    affinity_scores: Default::default(),
+                    channel_key: None,
+                })
+                .collect(),
+        );
 
-                let assign_params = TaskAssignmentParams {
-                    task: subtask,
-                    assignee: assignee.clone(),
-                    parent_task_id: task_id.to_string(),
-                    winning_plan_id: winner_plan_id.to_string(),
-                };
-                let assign_msg = SwarmMessage::new(
-                    ProtocolMethod::TaskAssignment.as_str(),
-                    serde_json::to_value(&assign_params).unwrap_or_default(),
-                    String::new(),
-                );
-                if let Ok(data) = serde_json::to_vec(&assign_msg) {
-                    let topic =
-                        SwarmTopics::tasks_for(swarm_id.as_str(), assign_params.task.tier_level);
-                    assignment_messages.push((topic, data));
-                }
+        assert!(state.begin_verification(&task_id, "alice").is_none());
+        let reassigned = state.begin_verification(&task_id, "bob").unwrap();
+        assert_eq!(reassigned.status, TaskStatus::InProgress);
 
-                tracing::info!(
-                    task_id = %task_id,
-                    subtask_id = %subtask_id,
-                    assignee = %assignee,
-                    "Subtask assigned to subordinate"
-                );
-            }
+        let status = state.settle_verification(&task_id, "bob", &artifact, "41").unwrap();
+        assert_eq!(status, VerificationStatus::Disputed);
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::Disputed);
+        assert_eq!(state.task_result_text[&task_id], "42");
+
+        for voter in ["carol", "dave"] {
+            let vote = DisputeVoteParams {
+                task_id: task_id.clone(),
+                voter: AgentId::new(voter.to_string()),
+                accept_agent: AgentId::new("bob".to_string()),
+            };
+            state.record_dispute_vote(&vote).unwrap();
         }
 
-        // Update parent task with subtask IDs
-        if let Some(parent_task) = state.task_details.get_mut(task_id) {
-            parent_task.subtasks = subtask_ids.clone();
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::Completed);
+        assert_eq!(state.task_result_text[&task_id], "41");
+        assert_eq!(state.agent_activity["bob"].verifications_passed_count, 1);
+        assert_eq!(state.agent_activity["alice"].verifications_failed_count, 1);
+    }
+
+    #[test]
+    fn flow_counts_respect_window() {
+        let mut state = test_state();
+        state.push_task_timeline_event("t1", "injected", "", None);
+        state.push_task_timeline_event("t1", "assigned", "", None);
+        state.push_task_timeline_event("t1", "result_submitted", "", None);
+        state.task_timelines.get_mut("t1").unwrap()[0].timestamp -= chrono::Duration::hours(1);
+
+        let totals = state.flow_counts(None);
+        assert_eq!((totals.injected, totals.assigned, totals.results), (1, 1, 1));
+        let recent = state.flow_counts(Some(chrono::Utc::now() - chrono::Duration::minutes(5)));
+        assert_eq!((recent.injected, recent.assigned, recent.results), (0, 1, 1));
+    }
+
+    #[test]
+    fn epoch_handoff_restarts_rounds_with_stranded_proposers() {
+        let mut state = test_state();
+        let mut task_ids = Vec::new();
+        for description in ["steady", "reshuffled"] {
+            let mut task = Task::new(description.to_string(), 1, 1);
+            task.status = TaskStatus::ProposalPhase;
+            let mut rfp = RfpCoordinator::new(task.task_id.clone(), task.epoch, 2);
+            rfp.inject_task(&task).unwrap();
+            rfp.record_commit(&ProposalCommitParams {
+                task_id: task.task_id.clone(),
+                proposer: AgentId::new("alice".to_string()),
+                epoch: task.epoch,
+                plan_hash: "h".to_string(),
+                bond: 0,
+                selection: None,
+            })
+            .unwrap();
+            state.rfp_coordinators.insert(task.task_id.clone(), rfp);
+            state.task_details.insert(task.task_id.clone(), task.clone());
+            task_ids.push(task.task_id);
         }
+        let mut eligible = std::collections::HashMap::new();
+        eligible.insert(task_ids[0].clone(), (1, vec!["alice".to_string(), "bob".to_string()]));
+        // alice left the tier of the second task in the new layout.
+        eligible.insert(task_ids[1].clone(), (1, vec!["bob".to_string(), "carol".to_string()]));
+
+        let outcomes: std::collections::HashMap<String, HandoffOutcome> =
+            state.hand_off_rounds(2, &eligible).into_iter().collect();
+        assert_eq!(outcomes[&task_ids[0]], HandoffOutcome::Migrated);
+        assert_eq!(outcomes[&task_ids[1]], HandoffOutcome::Restarted);
+        assert_eq!(state.rfp_coordinators[&task_ids[0]].commit_count(), 1);
+        assert_eq!(state.rfp_coordinators[&task_ids[1]].commit_count(), 0);
+        assert!(state.round_restarts.contains_key(&task_ids[1]));
+        assert!(state.task_timelines[&task_ids[1]]
+            .iter()
+            .any(|e| e.stage == "epoch_handoff"));
+    }
 
-        state.push_log(
-            LogCategory::Task,
-            format!(
-                "Assigned {} subtasks from winning plan {} to {} subordinates",
-                winning_plan.subtasks.len(),
-                winner_plan_id,
-                subordinates.len()
-            ),
+    #[test]
+    fn local_agents_get_their_own_identity_and_signatures() {
+        let mut state = test_state();
+        state.local_agents = AgentCluster::new(1, None);
+        let worker = state
+            .local_agents
+            .register("worker", chrono::Utc::now())
+            .unwrap()
+            .agent_id
+            .clone();
+        assert_ne!(worker, state.agent_id);
+        assert!(state.is_local_agent(&worker));
+        assert!(state.is_local_agent(&state.agent_id.clone()));
+        assert!(!state.is_local_agent(&AgentId::new("did:swarm:remote".to_string())));
+
+        let params = serde_json::json!({"task_id": "t1"});
+        let signed = state.message_as(&worker, "result.submit", params.clone());
+        assert_eq!(signed.signature.len(), 128);
+        let own = state.agent_id.clone();
+        assert!(state.message_as(&own, "result.submit", params).signature.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rpc_clients_are_held_to_their_role_and_local_agent() {
+        use crate::auth::{RpcAuth, RpcClientConfig, RpcRole};
+        use wws_protocol::ErrorCode;
+
+        let mut state = test_state();
+        state.local_agents = AgentCluster::new(2, None);
+        let worker = state
+            .local_agents
+            .register("worker", chrono::Utc::now())
+            .unwrap()
+            .agent_id
+            .to_string();
+        let state = Arc::new(RwLock::new(state));
+        let client = |name: &str, role, local_agent: Option<&str>| RpcClientConfig {
+            name: name.to_string(),
+            token: format!("{}-token", name),
+            role,
+            local_agent: local_agent.map(str::to_string),
+        };
+        let auth = RpcAuth::new(vec![
+            client("ops", RpcRole::Operator, None),
+            client("bot", RpcRole::Agent, Some("worker")),
+        ]);
+        let peer = "127.0.0.1:40000".parse().unwrap();
+        let call = |method: &str, token: &str, params: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0", "id": "1", "method": method,
+                "params": params, "signature": "", "auth": token,
+            })
+            .to_string()
+        };
+        let code = |denied: wws_protocol::SwarmResponse| denied.error.unwrap().error_code().unwrap();
+
+        let unknown = call("swarm.get_status", "guess", serde_json::json!({}));
+        let denied = crate::rpc_server::authorize(&unknown, &auth, &state, peer).await;
+        assert_eq!(code(denied.unwrap_err()), ErrorCode::Unauthenticated);
+
+        let inject = call("swarm.inject_task", "bot-token", serde_json::json!({}));
+        let denied = crate::rpc_server::authorize(&inject, &auth, &state, peer).await;
+        assert_eq!(code(denied.unwrap_err()), ErrorCode::Forbidden);
+        let inject = call("swarm.inject_task", "ops-token", serde_json::json!({}));
+        assert_eq!(crate::rpc_server::authorize(&inject, &auth, &state, peer).await.unwrap(), None);
+
+        // The bound agent acts as its local agent, and only as it.
+        let vote = call("swarm.submit_vote", "bot-token", serde_json::json!({"task_id": "t1"}));
+        let rewritten = crate::rpc_server::authorize(&vote, &auth, &state, peer).await.unwrap().unwrap();
+        let rewritten: serde_json::Value = serde_json::from_str(&rewritten).unwrap();
+        assert_eq!(rewritten["params"]["local_agent"], worker.as_str());
+        let other = call(
+            "swarm.submit_vote",
+            "bot-token",
+            serde_json::json!({"task_id": "t1", "local_agent": "did:swarm:other"}),
         );
+        let denied = crate::rpc_server::authorize(&other, &auth, &state, peer).await;
+        assert_eq!(code(denied.unwrap_err()), ErrorCode::Forbidden);
 
-        // Drop the write lock before publishing
-        drop(state);
+        let audit: Vec<String> = state
+            .read()
+            .await
+            .event_log
+            .iter()
+            .filter(|e| e.message.starts_with("AUDIT rpc.call"))
+            .map(|e| e.message.clone())
+            .collect();
+        assert_eq!(audit.len(), 5);
+        assert!(audit[0].contains("client=- ") && audit[0].ends_with("outcome=unauthenticated"));
+        assert!(audit[2].contains("client=ops method=swarm.inject_task"));
+    }
 
-        // Subscribe coordinator to each subtask's result topic so we receive completion updates
-        for st_id in &subtask_ids {
-            self.subscribe_task_flow_topics(swarm_id.as_str(), st_id).await;
+    #[test]
+    fn authority_issues_parameter_change_once_majority_approves() {
+        let mut state = test_state();
+        for member in ["did:swarm:connector-test", "did:swarm:b", "did:swarm:c"] {
+            state.mark_member_seen(member);
         }
+        let mut v1 = SwarmParameters {
+            swarm_id: state.current_swarm_id.clone(),
+            version: 1,
+            branching_factor: 0,
+            proposal_timeout_secs: 30,
+            voting_timeout_secs: 30,
+            quorum: QuorumPolicy::default(),
+            creator_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        v1.sign(&state.identity_key);
+        assert_eq!(state.adopt_swarm_params(v1), ParamsDecision::Adopt);
+        assert!(state.is_params_authority());
+        assert_eq!(state.branching_factor(100), 10);
 
-        // Publish all assignment messages
-        for (topic, data) in assignment_messages {
-            if let Err(e) = self.network_handle.publish(&topic, data).await {
-                tracing::error!(
-                    topic = %topic,
-                    error = %e,
-                    "Failed to publish task assignment"
-                );
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic,
-                    method: Some(ProtocolMethod::TaskAssignment.as_str().to_string()),
-                    task_id: Some(task_id.to_string()),
-                    size_bytes: 0,
-                    outcome: format!("error: {}", e),
-                });
-            } else {
-                let mut state = self.state.write().await;
-                state.push_message_trace(MessageTraceEvent {
-                    timestamp: chrono::Utc::now(),
-                    direction: "outbound".to_string(),
-                    peer: None,
-                    topic,
-                    method: Some(ProtocolMethod::TaskAssignment.as_str().to_string()),
-                    task_id: Some(task_id.to_string()),
-                    size_bytes: 0,
-                    outcome: "published".to_string(),
-                });
-            }
-        }
+        let now = chrono::Utc::now();
+        let proposal = ParamsChangeProposeParams {
+            swarm_id: state.current_swarm_id.clone(),
+            proposal_id: "p1".to_string(),
+            base_version: 1,
+            changes: SwarmParameterChanges {
+                branching_factor: Some(4),
+                voting_timeout_secs: Some(90),
+                quorum: Some(QuorumPolicy::TierFraction { fraction: 0.5 }),
+                ..Default::default()
+            },
+            proposer: AgentId::new("did:swarm:b".to_string()),
+            expires_at: now + chrono::Duration::seconds(60),
+        };
+        assert!(state.record_params_proposal(proposal, now).is_none());
 
-        Ok(())
+        let vote = |voter: &str, approve| ParamsChangeVoteParams {
+            swarm_id: SwarmId::new("public".to_string()),
+            proposal_id: "p1".to_string(),
+            voter: AgentId::new(voter.to_string()),
+            approve,
+        };
+        assert!(state.record_params_vote(&vote("did:swarm:c", false), now).is_none());
+        let issued = state.record_params_vote(&vote("did:swarm:connector-test", true), now).unwrap();
+        assert_eq!(issued.version, 2);
+        assert!(issued.verify());
+        assert_eq!(state.swarm_params.as_ref().map(|p| p.version), Some(2));
+        assert_eq!(state.branching_factor(100), 4);
+        assert_eq!(state.voting_stage_timeout_secs(), 90);
+        assert_eq!(state.quorum_policy, QuorumPolicy::TierFraction { fraction: 0.5 });
+        assert!(state.params_proposals.is_empty());
     }
 
-    /// Parse bootstrap peer multiaddresses (e.g. "/ip4/1.2.3.4/tcp/9000/p2p/12D3...")
-    /// into (PeerId, Multiaddr) pairs for the discovery layer.
-    fn parse_bootstrap_peers(addrs: &[String]) -> Vec<(PeerId, Multiaddr)> {
-        let mut peers = Vec::new();
-        for addr_str in addrs {
-            let addr_str = addr_str.trim();
-            if addr_str.is_empty() {
-                continue;
-            }
-            match addr_str.parse::<Multiaddr>() {
-                Ok(addr) => {
-                    // Extract the PeerId from the /p2p/<peer_id> component of the multiaddr string.
-                    if let Some(peer_id) = Self::extract_peer_id_from_addr(addr_str) {
-                        peers.push((peer_id, addr));
-                    } else {
-                        tracing::warn!(
-                            addr = %addr_str,
-                            "Bootstrap address missing /p2p/<peer_id> component, skipping"
-                        );
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        addr = %addr_str,
-                        error = %e,
-                        "Failed to parse bootstrap multiaddress, skipping"
-                    );
-                }
-            }
+    #[test]
+    fn dead_letters_are_bounded_and_truncated() {
+        let mut state = test_state();
+        let oversized = vec![b'x'; DEAD_LETTER_PAYLOAD_MAX_BYTES + 10];
+        state.push_dead_letter("t", "peer", None, "parse_error: eof", &oversized);
+        let letter = &state.dead_letters[0];
+        assert!(letter.truncated);
+        assert_eq!(letter.size_bytes, DEAD_LETTER_PAYLOAD_MAX_BYTES + 10);
+        assert_eq!(letter.payload.len(), DEAD_LETTER_PAYLOAD_MAX_BYTES);
+
+        for i in 0..DEAD_LETTER_CAPACITY {
+            state.push_dead_letter("t", "peer", None, format!("unknown_method {}", i), b"{}");
         }
-        peers
+        assert_eq!(state.dead_letters.len(), DEAD_LETTER_CAPACITY);
+        assert_eq!(state.dead_letters[0].reason, "unknown_method 0");
     }
 
-    /// Extract a PeerId from a multiaddress string by finding the /p2p/<id> segment.
-    fn extract_peer_id_from_addr(addr: &str) -> Option<PeerId> {
-        let parts: Vec<&str> = addr.split('/').collect();
-        // Find the "p2p" component and take the next element as the peer ID.
-        for (i, part) in parts.iter().enumerate() {
-            if *part == "p2p" {
-                if let Some(id_str) = parts.get(i + 1) {
-                    return id_str.parse::<PeerId>().ok();
-                }
-            }
+    fn board_invite(state: &ConnectorState, task_id: &str) -> BoardInviteParams {
+        BoardInviteParams {
+            task_id: task_id.to_string(),
+            task_digest: "Plan the launch".to_string(),
+            complexity_estimate: 0.7,
+            depth: 0,
+            required_capabilities: Vec::new(),
+            capacity: 5,
+            chair: state.agent_id.clone(),
+            private: false,
         }
-        None
     }
 
-    /// Dial bootstrap peers to establish connections immediately on startup.
-    async fn connect_to_bootstrap_peers(&self) {
-        for addr_str in &self.config.network.bootstrap_peers {
-            let addr_str = addr_str.trim();
-            if addr_str.is_empty() {
-                continue;
-            }
-            if let Ok(addr) = addr_str.parse::<Multiaddr>() {
-                match self.network_handle.dial(addr.clone()).await {
-                    Ok(()) => {
-                        tracing::info!(addr = %addr, "Dialing bootstrap peer");
-                        let mut state = self.state.write().await;
-                        state.push_log(
-                            LogCategory::System,
-                            format!("Dialing bootstrap peer: {}", addr),
-                        );
-                    }
-                    Err(e) => {
-                        tracing::warn!(addr = %addr, error = %e, "Failed to dial bootstrap peer");
-                    }
-                }
-            }
+    fn board_accept(task_id: &str, agent: &str) -> BoardAcceptParams {
+        BoardAcceptParams {
+            task_id: task_id.to_string(),
+            agent_id: AgentId::new(agent.to_string()),
+            active_tasks: 0,
+            capabilities: Vec::new(),
+            affinity_scores: Default::default(),
+            channel_key: None,
         }
     }
 
-    async fn subscribe_task_assignment_topics(&self, swarm_id: &str) {
-        for tier in 1..=wws_protocol::MAX_HIERARCHY_DEPTH {
-            let topic = SwarmTopics::tasks_for(swarm_id, tier);
-            if let Err(e) = self.network_handle.subscribe(&topic).await {
-                tracing::debug!(error = %e, topic = %topic, "Failed to subscribe task assignment topic");
-            }
+    #[test]
+    fn chair_synthesis_assigns_one_synthesis_subtask_to_the_chair() {
+        let mut state = test_state();
+        let mut parent = Task::new("Write a report".to_string(), 1, 1);
+        parent.task_id = "parent".to_string();
+        parent.aggregation = AggregationStrategy::ChairSynthesis;
+        parent.subtasks = vec!["s1".to_string(), "s2".to_string()];
+        state.task_details.insert("parent".to_string(), parent);
+        for id in ["s1", "s2"] {
+            state.task_result_text.insert(id.to_string(), format!("findings of {}", id));
         }
-    }
+        let chair = AgentId::new("did:swarm:chair".to_string());
+        state.active_holons.insert(
+            "parent".to_string(),
+            HolonState {
+                task_id: "parent".to_string(),
+                chair: chair.clone(),
+                members: vec![chair.clone()],
+                adversarial_critic: None,
+                depth: 1,
+                parent_holon: None,
+                child_holons: Vec::new(),
+                subtask_assignments: std::collections::HashMap::new(),
+                child_artifacts: std::collections::HashMap::new(),
+                status: HolonStatus::Executing,
+                created_at: chrono::Utc::now(),
+            },
+        );
 
-    async fn subscribe_task_flow_topics(&self, swarm_id: &str, task_id: &str) {
-        let proposals_topic = SwarmTopics::proposals_for(swarm_id, task_id);
-        let voting_topic = SwarmTopics::voting_for(swarm_id, task_id);
-        let results_topic = SwarmTopics::results_for(swarm_id, task_id);
+        let assignment = state.open_synthesis_subtask("parent").unwrap();
+        assert_eq!(state.active_holons["parent"].status, HolonStatus::Synthesizing);
+        assert_eq!(assignment.assignee, chair);
+        assert_eq!(assignment.task.task_id, "parent-synthesis");
+        assert!(assignment.task.description.contains("- s2: findings of s2"));
+        assert_eq!(state.task_details["parent"].subtasks.len(), 3);
+        // The chair is remote, so the task is not queued here.
+        assert!(!state.task_set.contains(&"parent-synthesis".to_string()));
+        assert!(state.open_synthesis_subtask("parent").is_none());
+    }
 
-        if let Err(e) = self.network_handle.subscribe(&proposals_topic).await {
-            tracing::debug!(error = %e, topic = %proposals_topic, "Failed to subscribe proposals topic");
+    #[test]
+    fn finished_child_holon_rolls_up_and_unblocks_parent_synthesis() {
+        let mut state = test_state();
+        let mut parent = Task::new("Write a report".to_string(), 1, 1);
+        parent.task_id = "parent".to_string();
+        parent.aggregation = AggregationStrategy::ChairSynthesis;
+        parent.subtasks = vec!["s1".to_string(), "s2".to_string()];
+        state.task_details.insert("parent".to_string(), parent);
+        for id in ["s1", "s2"] {
+            let mut task = Task::new(format!("part {}", id), 2, 1);
+            task.task_id = id.to_string();
+            task.parent_task_id = Some("parent".to_string());
+            task.status = TaskStatus::Completed;
+            state.task_details.insert(id.to_string(), task);
         }
-        if let Err(e) = self.network_handle.subscribe(&voting_topic).await {
-            tracing::debug!(error = %e, topic = %voting_topic, "Failed to subscribe voting topic");
+        let chair = AgentId::new("did:swarm:chair".to_string());
+        let holon = |task_id: &str, parent_holon: Option<&str>, child_holons: Vec<String>| HolonState {
+            task_id: task_id.to_string(),
+            chair: chair.clone(),
+            members: vec![chair.clone()],
+            adversarial_critic: None,
+            depth: if parent_holon.is_some() { 2 } else { 1 },
+            parent_holon: parent_holon.map(str::to_string),
+            child_holons,
+            subtask_assignments: std::collections::HashMap::new(),
+            child_artifacts: std::collections::HashMap::new(),
+            status: HolonStatus::Executing,
+            created_at: chrono::Utc::now(),
+        };
+        state
+            .active_holons
+            .insert("parent".to_string(), holon("parent", None, vec!["s2".to_string()]));
+        state
+            .active_holons
+            .insert("s2".to_string(), holon("s2", Some("parent"), Vec::new()));
+
+        // Both subtasks have results, but the s2 board has not finished.
+        assert!(!state.child_holons_done("parent"));
+        assert!(state.open_synthesis_subtask("parent").is_none());
+
+        let artifact: Artifact = serde_json::from_value(serde_json::json!({
+            "task_id": "s2",
+            "producer": "did:swarm:sub-chair",
+            "content_cid": "cid-s2",
+        }))
+        .unwrap();
+        assert_eq!(state.complete_holon("s2", &artifact).as_deref(), Some("parent"));
+        assert_eq!(state.active_holons["s2"].status, HolonStatus::Done);
+        let parent = &state.active_holons["parent"];
+        assert_eq!(parent.subtask_assignments["s2"].as_str(), "did:swarm:sub-chair");
+        assert_eq!(parent.child_artifacts["s2"], "cid-s2");
+        assert!(state.task_timelines["parent"]
+            .iter()
+            .any(|e| e.stage == "child_holon_done"));
+        assert!(state.open_synthesis_subtask("parent").is_some());
+    }
+
+    #[test]
+    fn board_forms_once_quorum_of_minimum_size_accepts() {
+        let mut state = test_state();
+        let invite = board_invite(&state, "t-board");
+        state.open_board_formation(invite);
+        let eligible: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+
+        for agent in ["a", "b"] {
+            let accept = board_accept("t-board", agent);
+            state.note_board_accept(&accept);
+            assert!(state.record_board_accept(&accept, &eligible).is_none());
         }
-        if let Err(e) = self.network_handle.subscribe(&results_topic).await {
-            tracing::debug!(error = %e, topic = %results_topic, "Failed to subscribe results topic");
+        let accept = board_accept("t-board", "c");
+        state.note_board_accept(&accept);
+        let ready = state.record_board_accept(&accept, &eligible).unwrap();
+
+        assert_eq!(ready.members.len(), 3);
+        assert!(ready.members.contains(ready.adversarial_critic.as_ref().unwrap()));
+        assert!(state.board_formations.is_empty());
+        assert_eq!(state.active_holons["t-board"].status, HolonStatus::Deliberating);
+    }
+
+    #[test]
+    fn private_board_seals_deliberation_for_its_members() {
+        let mut state = test_state();
+        let invite = BoardInviteParams {
+            private: true,
+            ..board_invite(&state, "t-private")
+        };
+        state.open_board_formation(invite);
+        let member = ChannelKeypair::generate();
+        let eligible: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let mut ready = None;
+        for agent in ["a", "b", "c"] {
+            let accept = BoardAcceptParams {
+                channel_key: Some(member.public_key_hex()),
+                ..board_accept("t-private", agent)
+            };
+            state.note_board_accept(&accept);
+            ready = state.record_board_accept(&accept, &eligible);
         }
+        let ready = ready.unwrap();
+        assert_eq!(ready.sealed_keys.len(), 3);
+        let group_key = GroupKey::open(&ready.sealed_keys["b"], &member).unwrap();
+
+        let critique = SwarmMessage::new(
+            ProtocolMethod::DiscussionCritique.as_str(),
+            serde_json::json!({"task_id": "t-private"}),
+            String::new(),
+        );
+        let sealed = state.seal_board_message("t-private", critique.clone()).unwrap();
+        assert_eq!(sealed.method, ProtocolMethod::BoardSealed.as_str());
+        let params: SealedBoardMessageParams = serde_json::from_value(sealed.params).unwrap();
+        let opened: SwarmMessage =
+            serde_json::from_slice(&group_key.decrypt(&params).unwrap()).unwrap();
+        assert_eq!(opened.params, critique.params);
+
+        // Public boards publish in the clear.
+        let public = state.seal_board_message("t-other", critique.clone()).unwrap();
+        assert_eq!(public.method, critique.method);
     }
 
-    fn recompute_hierarchy_from_members(state: &mut ConnectorState, members: &[String]) {
-        if members.is_empty() {
-            return;
+    #[test]
+    fn subtasks_share_the_parent_deadline_and_late_results_count_against_sla() {
+        let mut state = test_state();
+        let now = chrono::Utc::now();
+        let mut parent = Task::new("Ship the release".to_string(), 1, 1);
+        parent.task_id = "parent".to_string();
+        parent.deadline = Some(now + chrono::Duration::seconds(100));
+        state.task_details.insert("parent".to_string(), parent);
+        assert_eq!(
+            state.subtask_deadline("parent", now),
+            now + chrono::Duration::seconds(80)
+        );
+        assert_eq!(
+            state.subtask_deadline("unknown", now),
+            now + chrono::Duration::seconds(EXECUTION_ASSIGNMENT_TIMEOUT_SECS)
+        );
+
+        let mut child = Task::new("Write notes".to_string(), 2, 1);
+        child.task_id = "child".to_string();
+        child.parent_task_id = Some("parent".to_string());
+        child.status = TaskStatus::InProgress;
+        child.assigned_to = Some(AgentId::new("bob".to_string()));
+        child.deadline = Some(state.subtask_deadline("parent", now));
+        state.task_details.insert("child".to_string(), child);
+
+        let later = now + chrono::Duration::seconds(90);
+        assert_eq!(state.mark_overdue_tasks(later), vec!["child".to_string()]);
+        assert_eq!(state.task_details["child"].status, TaskStatus::Overdue);
+        assert!(state.mark_overdue_tasks(later).is_empty());
+
+        state.record_result_timeliness("child", "bob", later);
+        state.record_result_timeliness("parent", "bob", later);
+        let bob = &state.agent_activity["bob"];
+        assert_eq!(bob.on_time_pct(), Some(50.0));
+        assert_eq!(bob.average_lateness_secs(), Some(10.0));
+    }
+
+    #[test]
+    fn coordinators_attest_outcomes_that_members_score_alike() {
+        let mut coordinator = test_state();
+        coordinator.agent_id = AgentId::new(wws_protocol::crypto::derive_agent_id(
+            &coordinator.identity_key.verifying_key(),
+        ));
+        let now = chrono::Utc::now();
+        let mut parent = Task::new("Plan the launch".to_string(), 1, 1);
+        parent.task_id = "parent".to_string();
+        parent.assigned_to = Some(coordinator.agent_id.clone());
+        parent.subtasks = vec!["child".to_string()];
+        let mut child = Task::new("Write notes".to_string(), 2, 1);
+        child.task_id = "child".to_string();
+        child.parent_task_id = Some("parent".to_string());
+        child.assigned_to = Some(AgentId::new("did:swarm:bob".to_string()));
+        child.deadline = Some(now + chrono::Duration::seconds(60));
+        let mut member = test_state();
+        for state in [&mut coordinator, &mut member] {
+            state.task_details.insert("parent".to_string(), parent.clone());
+            state.task_details.insert("child".to_string(), child.clone());
         }
 
-        let swarm_size = members.len() as u64;
-        let mut sorted_agents = members.to_vec();
-        sorted_agents.sort();
+        coordinator.record_result_timeliness("child", "did:swarm:bob", now);
+        coordinator.record_result_timeliness("parent", "did:swarm:bob", now);
+        let issuer = coordinator.agent_id.clone();
+        let batch = ReputationAttestParams {
+            attestations: coordinator.reputation.take_outgoing(&issuer, now),
+        };
+        assert_eq!(batch.attestations.len(), 1);
+        assert_eq!(batch.attestations[0].outcome, AttestationOutcome::OnTime);
+        assert!(batch.attestations[0].verify());
+
+        assert_eq!(crate::reputation::accept_attestations(&mut member, batch.clone()), 1);
+        assert_eq!(crate::reputation::accept_attestations(&mut member, batch.clone()), 0);
+        let expected = crate::reputation::BASE_REPUTATION + crate::reputation::ON_TIME_POINTS;
+        assert_eq!(member.reputation_of("did:swarm:bob"), expected as u64);
+        assert_eq!(member.reputation_of("did:swarm:bob"), coordinator.reputation_of("did:swarm:bob"));
+
+        // Only the coordinator of the parent may attest its subtasks.
+        let mut forged = batch.attestations[0].clone();
+        let key = wws_protocol::crypto::generate_keypair();
+        forged.issuer = AgentId::new(wws_protocol::crypto::derive_agent_id(&key.verifying_key()));
+        forged.outcome = AttestationOutcome::Late;
+        forged.sign(&key);
+        let forged = ReputationAttestParams { attestations: vec![forged] };
+        assert_eq!(crate::reputation::accept_attestations(&mut member, forged), 0);
+        assert_eq!(member.reputation_of("did:swarm:bob"), expected as u64);
+    }
 
-        state.agent_tiers.clear();
-        state.agent_parents.clear();
-        state.subordinates.clear();
+    #[test]
+    fn connector_stands_for_election_and_votes_once_the_delay_passes() {
+        let mut state = test_state();
+        let now = chrono::Utc::now();
+        let config = ElectionConfig::default();
+        assert!(state.election_candidacy(&config, &[], 3_600, now).is_none());
+
+        state.election = Some(ElectionManager::new(
+            wws_hierarchy::elections::ElectionConfig::default(),
+            2,
+        ));
+        state.election_vote_due = Some(now + chrono::Duration::seconds(5));
+        let caps = vec!["web_search".to_string()];
+        // Up for the whole epoch with a newcomer's reputation.
+        let candidacy = state
+            .election_candidacy(&config, &caps, 0, now)
+            .expect("eligible");
+        assert_eq!(candidacy.epoch, 2);
+        assert_eq!(candidacy.capabilities, caps);
+
+        assert!(state.due_election_vote(&config, &caps, now).is_none());
+        let later = now + chrono::Duration::seconds(5);
+        let vote = state.due_election_vote(&config, &caps, later).unwrap();
+        assert_eq!(vote.candidate_rankings, vec![state.agent_id.clone()]);
+        assert_eq!(state.election.as_ref().unwrap().vote_count(), 1);
+        assert!(state.due_election_vote(&config, &caps, later).is_none());
+    }
 
-        let k = Self::dynamic_branching_factor(swarm_size) as usize;
-        let distribution = wws_hierarchy::PyramidAllocator::distribute(swarm_size, k as u64);
-        let tier_sizes: Vec<usize> = distribution.tiers.iter().map(|n| *n as usize).collect();
-        let levels = tier_sizes.len().max(1);
+    #[test]
+    fn tasks_travel_in_the_swarm_they_were_injected_into() {
+        let mut state = test_state();
+        state.my_tier = Tier::Tier1;
+        assert!(state.join_swarm("research"));
+        assert!(!state.join_swarm("research"));
+        assert!(!state.join_swarm("public"));
+        assert_eq!(state.joined_swarm_ids(), ["public", "research"]);
+        assert!(state.require_membership("research").is_ok());
+        let refused = state.require_membership("finance").unwrap_err();
+        assert!(refused.contains("finance") && refused.contains("joined: public, research"));
+
+        // Tiers are held per swarm; the primary swarm's is `my_tier`.
+        assert_eq!(state.tier_in("research"), Tier::Executor);
+        state
+            .memberships
+            .get_mut("research")
+            .unwrap()
+            .assign(Tier::Tier2, AgentId::new("did:swarm:chair".to_string()));
+        assert_eq!(state.tier_in("research"), Tier::Tier2);
+        assert_eq!(state.tier_in("public"), Tier::Tier1);
+
+        let root = Task::new("root".to_string(), 2, 1);
+        let mut subtask = Task::new("sub".to_string(), 3, 1);
+        subtask.parent_task_id = Some(root.task_id.clone());
+        let (root_id, subtask_id) = (root.task_id.clone(), subtask.task_id.clone());
+        state.task_details.insert(root_id.clone(), root);
+        state.task_details.insert(subtask_id.clone(), subtask);
+        assert_eq!(state.swarm_for_task(&subtask_id), "public");
+
+        state.set_task_swarm(&root_id, "research");
+        assert_eq!(state.swarm_for_task(&root_id), "research");
+        assert_eq!(state.swarm_for_task(&subtask_id), "research");
+        assert_eq!(state.swarm_for_task("unknown"), "public");
+
+        assert!(state.leave_swarm("public").is_err());
+        let left = state.leave_swarm("research").unwrap();
+        assert!(left.task_ids.contains(&root_id));
+        assert_eq!(state.swarm_for_task(&subtask_id), "public");
+        assert!(!state.is_member_of("research"));
+    }
+
+    #[test]
+    fn agents_sharing_a_name_are_shown_with_a_did_suffix() {
+        let mut state = test_state();
+        state.agent_names.insert("did:swarm:peer-a1f2".into(), "atlas".into());
+        assert_eq!(state.display_name("did:swarm:peer-a1f2"), "atlas");
+
+        let now = crate::names::unix_now();
+        let key = wws_protocol::crypto::generate_keypair();
+        let mut claim = NameClaimParams {
+            name: "atlas".into(),
+            did: "did:swarm:peer-b7c9".into(),
+            peer_id: String::new(),
+            registered_at: now,
+            expires_at: now + 60,
+            pow_nonce: 0,
+            public_key: String::new(),
+            signature: String::new(),
+        };
+        claim.sign(&key);
+        assert_eq!(state.record_name_claim(claim), Ok(true));
+        assert_eq!(
+            state.registered_names.get("atlas").map(|r| r.did.as_str()),
+            Some("did:swarm:peer-b7c9")
+        );
+        assert_eq!(state.display_name("did:swarm:peer-a1f2"), "atlas#a1f2");
+        assert_eq!(state.display_name("did:swarm:peer-b7c9"), "atlas#b7c9");
+    }
+
+    #[test]
+    fn members_follow_a_migration_signed_by_their_swarm_authority() {
+        let mut state = test_state();
+        let authority = wws_protocol::crypto::generate_keypair();
+        let mut record = SwarmParameters {
+            swarm_id: state.current_swarm_id.clone(),
+            version: 1,
+            branching_factor: 0,
+            proposal_timeout_secs: 30,
+            voting_timeout_secs: 30,
+            quorum: QuorumPolicy::default(),
+            creator_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        record.sign(&authority);
+        state.swarm_params = Some(record);
+
+        let mut mine = Task::new("mine".to_string(), 1, 1);
+        mine.status = TaskStatus::InProgress;
+        let theirs = Task::new("theirs".to_string(), 1, 1);
+        let (mine_id, theirs_id) = (mine.task_id.clone(), theirs.task_id.clone());
+        state.task_details.insert(mine_id.clone(), mine);
+        state.task_details.insert(theirs_id.clone(), theirs);
+        let me = state.agent_id.to_string();
+        state.push_task_timeline_event(&mine_id, "injected", "via RPC", Some(me));
+        state.push_task_timeline_event(&theirs_id, "injected", "from peer", None);
+
+        assert!(state.migration_report("lab").is_err());
+        state.known_swarms.insert(
+            "lab".to_string(),
+            SwarmRecord {
+                swarm_id: SwarmId::new("lab".to_string()),
+                name: "Lab".to_string(),
+                is_public: true,
+                agent_count: 3,
+                joined: false,
+                last_seen: chrono::Utc::now(),
+            },
+        );
+        let report = state.migration_report("lab").unwrap();
+        assert!(!report.authorized);
+        assert_eq!(report.transferred_tasks, vec![mine_id.clone()]);
+
+        let migration = |key: &ed25519_dalek::SigningKey| {
+            let mut migration = SwarmMigrateParams {
+                migration_id: "m1".to_string(),
+                source_swarm_id: SwarmId::new("public".to_string()),
+                target_swarm_id: SwarmId::new("lab".to_string()),
+                target_token: None,
+                creator_key: String::new(),
+                issued_at: chrono::Utc::now(),
+                signature: String::new(),
+            };
+            migration.sign(key);
+            migration
+        };
+        let impostor = wws_protocol::crypto::generate_keypair();
+        assert!(state.apply_migration(migration(&impostor)).is_err());
+        assert_eq!(state.current_swarm_id.as_str(), "public");
+
+        let outcome = state.apply_migration(migration(&authority)).unwrap();
+        assert_eq!(outcome.tasks.len(), 1);
+        assert_eq!(outcome.tasks[0].task_id, mine_id);
+        assert_eq!(outcome.tasks[0].status, TaskStatus::Pending);
+        assert_eq!(state.current_swarm_id.as_str(), "lab");
+        assert_eq!(state.swarm_for_task(&mine_id), "lab");
+        assert!(state.known_swarms["lab"].joined);
+        assert!(state.swarm_params.is_none());
+        // A replay finds the connector already moved.
+        assert!(state.apply_migration(migration(&authority)).is_err());
+    }
 
-        let mut offsets = Vec::with_capacity(levels + 1);
-        offsets.push(0usize);
-        for size in &tier_sizes {
-            let prev = *offsets.last().unwrap_or(&0);
-            offsets.push(prev + *size);
-        }
-
-        for level in 0..levels {
-            let start = offsets[level];
-            let end = *offsets.get(level + 1).unwrap_or(&start);
-            for idx in start..end.min(sorted_agents.len()) {
-                let member_id = sorted_agents[idx].clone();
-                let tier = if levels == 1 {
-                    Tier::Executor
-                } else if level == (levels - 1) {
-                    Tier::Executor
-                } else if level == 0 {
-                    Tier::Tier1
-                } else if level == 1 {
-                    Tier::Tier2
-                } else {
-                    Tier::TierN((level + 1) as u32)
-                };
-                state.agent_tiers.insert(member_id, tier);
+    #[test]
+    fn oversized_swarms_split_with_their_leaders_staying_as_gateways() {
+        let mut leader = keyed_node();
+        let mut member = keyed_node();
+        let mut rogue = keyed_node();
+        let (leader_id, member_id) = (leader.agent_id.clone(), member.agent_id.clone());
+        let others: Vec<String> = (0..4).map(|i| format!("did:swarm:m{}", i)).collect();
+        for node in [&mut leader, &mut member, &mut rogue] {
+            node.shards = crate::shard::ShardTracker::new(4, ShardStrategy::Hash);
+            node.epoch_manager.force_epoch(3, vec![leader_id.clone()], 7);
+            for id in others.iter().chain([leader_id.to_string(), member_id.to_string()].iter()) {
+                node.mark_member_seen(id);
             }
         }
 
-        for level in 1..levels {
-            let child_start = offsets[level];
-            let child_end = *offsets.get(level + 1).unwrap_or(&child_start);
-            let parent_start = offsets[level - 1];
-            let parent_end = *offsets.get(level).unwrap_or(&parent_start);
-            let parent_count = parent_end.saturating_sub(parent_start);
-
-            if parent_count == 0 {
-                continue;
-            }
+        let mut mine = Task::new("mine".to_string(), 1, 1);
+        mine.status = TaskStatus::InProgress;
+        let mine_id = mine.task_id.clone();
+        member.task_details.insert(mine_id.clone(), mine);
+        member.push_task_timeline_event(&mine_id, "injected", "via RPC", Some(member_id.to_string()));
+
+        assert_eq!(member.shard_issuer(), Some(leader_id.to_string()));
+        assert!(member.plan_shard().is_none());
+        let shard = leader.plan_shard().unwrap();
+        assert_eq!(shard.shard_id, "public@3");
+        assert_eq!(shard.children.len(), 3);
+        assert_eq!(shard.gateways, vec![leader_id.clone()]);
+
+        let outcome = member.apply_shard(shard.clone()).unwrap().unwrap();
+        let moved = outcome.moved.unwrap();
+        let child = shard.child_for(member_id.as_str(), None).unwrap();
+        assert_eq!(&moved.target, child);
+        assert_eq!(&member.current_swarm_id, child);
+        assert_eq!(moved.tasks.len(), 1);
+        assert_eq!(moved.tasks[0].status, TaskStatus::Pending);
+        assert_eq!(member.shards.link("public").unwrap().relation, FederationRelation::Parent);
+        assert_eq!(member.shards.links().count(), 3);
+        assert!(member.shards.link(child.as_str()).is_none());
+        assert!(member.apply_shard(shard.clone()).unwrap().is_none());
+
+        let outcome = leader.apply_shard(shard.clone()).unwrap().unwrap();
+        assert!(outcome.moved.is_none());
+        assert_eq!(outcome.joined.len(), 3);
+        assert_eq!(leader.current_swarm_id.as_str(), "public");
+        assert!(shard.children.iter().all(|c| leader.is_member_of(c.as_str())));
+        assert_eq!(leader.shards.link(child.as_str()).unwrap().relation, FederationRelation::Child);
+        assert!(leader.plan_shard().is_none());
+
+        // Only a Tier-1 leader may split the swarm.
+        rogue.epoch_manager.force_epoch(3, vec![rogue.agent_id.clone()], 7);
+        let rogue_id = rogue.agent_id.to_string();
+        rogue.mark_member_seen(&rogue_id);
+        let forged = rogue.plan_shard().unwrap();
+        let mut bystander = keyed_node();
+        bystander.epoch_manager.force_epoch(3, vec![leader_id.clone()], 7);
+        let refused = bystander.apply_shard(forged).unwrap_err();
+        assert!(refused.contains("not a Tier-1 leader"), "{}", refused);
+        assert_eq!(bystander.current_swarm_id.as_str(), "public");
+    }
 
-            for child_idx in child_start..child_end.min(sorted_agents.len()) {
-                let local_child_idx = child_idx.saturating_sub(child_start);
-                let mut parent_local_idx = local_child_idx / k.max(1);
-                if parent_local_idx >= parent_count {
-                    parent_local_idx = parent_count - 1;
-                }
-                let parent_idx = parent_start + parent_local_idx;
-                if let (Some(child_id), Some(parent_id)) =
-                    (sorted_agents.get(child_idx), sorted_agents.get(parent_idx))
-                {
-                    state
-                        .agent_parents
-                        .insert(child_id.clone(), parent_id.clone());
-                    state
-                        .subordinates
-                        .entry(parent_id.clone())
-                        .or_default()
-                        .push(child_id.clone());
-                }
+    #[test]
+    fn remote_nodes_complete_a_parent_their_coordinator_aggregates() {
+        let mut coordinator = keyed_node();
+        let mut observer = test_state();
+        let bridge = AgentId::new("did:swarm:bridge".to_string());
+        let coordinator_id = coordinator.agent_id.clone();
+
+        let mut parent = Task::new("parent".to_string(), 2, 1);
+        parent.assigned_to = Some(coordinator_id.clone());
+        parent.status = TaskStatus::InProgress;
+        let parent_id = parent.task_id.clone();
+        let mut local = Task::new("local".to_string(), 3, 1);
+        local.parent_task_id = Some(parent_id.clone());
+        local.status = TaskStatus::InProgress;
+        let mut node = Task::new("remote".to_string(), 3, 1);
+        node.parent_task_id = Some(parent_id.clone());
+        node.status = TaskStatus::InProgress;
+        node.assigned_to = Some(bridge.clone());
+        node.remote = Some(RemoteExecution {
+            swarm_id: SwarmId::new("other".to_string()),
+            bridge: bridge.clone(),
+            task_id: "remote-task".to_string(),
+        });
+        parent.subtasks = vec![local.task_id.clone(), node.task_id.clone()];
+        for state in [&mut coordinator, &mut observer] {
+            for task in [&parent, &local, &node] {
+                state.task_details.insert(task.task_id.clone(), task.clone());
             }
         }
 
-        state.network_stats.hierarchy_depth = levels as u32;
-        state.network_stats.total_agents = swarm_size;
-        state.current_layout = wws_hierarchy::PyramidAllocator::new(PyramidConfig {
-            branching_factor: k as u32,
-            max_depth: wws_protocol::MAX_HIERARCHY_DEPTH,
-        })
-        .compute_layout(swarm_size)
-        .ok();
+        // Observers accept the remote node from the parent's coordinator,
+        // assigned to its bridge only.
+        assert!(coordinator.coordinates(&coordinator_id, &parent_id));
+        assert!(!observer.coordinates(&bridge, &parent_id));
+        let assignment = coordinator.signed_assignment(
+            node.clone(),
+            bridge.clone(),
+            parent_id.clone(),
+            crate::delegation::REMOTE_PLAN_ID,
+        );
+        assert!(observer.authorize_task_assignment(&assignment).is_ok());
+        let elsewhere = coordinator.signed_assignment(
+            node.clone(),
+            AgentId::new("did:swarm:other".to_string()),
+            parent_id.clone(),
+            crate::delegation::REMOTE_PLAN_ID,
+        );
+        assert!(observer.authorize_task_assignment(&elsewhere).is_err());
+        let forged = keyed_node().signed_assignment(
+            node.clone(),
+            bridge.clone(),
+            parent_id.clone(),
+            crate::delegation::REMOTE_PLAN_ID,
+        );
+        assert!(observer.authorize_task_assignment(&forged).is_err());
+
+        // The parent is ready once the relayed result completes the last
+        // subtask, and only where it is coordinated.
+        let node_id = node.task_id.clone();
+        coordinator.task_details.get_mut(&node_id).unwrap().status = TaskStatus::Completed;
+        assert_eq!(coordinator.delegated_parent_ready(&node_id), None);
+        coordinator.task_details.get_mut(&local.task_id).unwrap().status = TaskStatus::Completed;
+        assert_eq!(coordinator.delegated_parent_ready(&node_id), Some(parent_id.clone()));
+        assert_eq!(coordinator.delegated_parent_ready(&local.task_id), None);
+        observer.task_details.get_mut(&node_id).unwrap().status = TaskStatus::Completed;
+        observer.task_details.get_mut(&local.task_id).unwrap().status = TaskStatus::Completed;
+        assert_eq!(observer.delegated_parent_ready(&node_id), None);
+    }
 
-        let my_id = state.agent_id.as_str().to_string();
-        if let Some(my_tier) = state.agent_tiers.get(&my_id).copied() {
-            state.my_tier = my_tier;
-            state.network_stats.my_tier = my_tier;
-            state.parent_id = state.agent_parents.get(&my_id).cloned().map(AgentId::new);
-            state.network_stats.parent_id = state.parent_id.clone();
-            state.network_stats.subordinate_count = state
-                .subordinates
-                .get(&my_id)
-                .map(|s| s.len() as u32)
-                .unwrap_or(0);
-        }
+    #[test]
+    fn undersized_board_is_dissolved_at_deadline() {
+        let mut state = test_state();
+        let invite = board_invite(&state, "t-small");
+        state.open_board_formation(invite);
+        let accept = board_accept("t-small", "a");
+        state.note_board_accept(&accept);
+        assert!(state.record_board_accept(&accept, &["a".to_string()]).is_none());
+
+        let (ready, dissolved) =
+            state.expire_board_formations(chrono::Utc::now() + chrono::Duration::seconds(60));
+        assert!(ready.is_empty());
+        assert_eq!(dissolved, vec!["t-small".to_string()]);
+        assert_eq!(state.active_holons["t-small"].status, HolonStatus::Done);
     }
 
-    fn tier_to_level(tier: Tier) -> Option<u32> {
-        match tier {
-            Tier::Tier1 => Some(1),
-            Tier::Tier2 => Some(2),
-            Tier::TierN(n) => Some(n),
-            Tier::Executor => None,
+    #[test]
+    fn task_quorum_overrides_swarm_default() {
+        let mut state = test_state();
+        state.quorum_policy = QuorumPolicy::TierFraction { fraction: 0.5 };
+        let tier: Vec<String> = (0..4).map(|i| format!("agent-{}", i)).collect();
+
+        let mut task = Task::new("quorum".to_string(), 1, 1);
+        let task_id = task.task_id.clone();
+        state.task_details.insert(task_id.clone(), task.clone());
+        assert_eq!(state.quorum_requirement(&task_id, 1, &tier).expected_voters, 2);
+
+        task.quorum = Some(QuorumPolicy::BoardFraction { fraction: 1.0 });
+        state.task_details.insert(task_id.clone(), task);
+        assert_eq!(state.quorum_requirement(&task_id, 1, &tier).expected_voters, 4);
+        assert!(!state.board_quorum_reached(&task_id, &tier));
+
+        for agent in &tier[..3] {
+            state.board_acceptances.entry(task_id.clone()).or_default().push(BoardAcceptParams {
+                task_id: task_id.clone(),
+                agent_id: AgentId::new(agent.clone()),
+                active_tasks: 0,
+                capabilities: Vec::new(),
+                affinity_scores: std::collections::HashMap::new(),
+                channel_key: None,
+            });
         }
+        // Once a board exists, the fraction applies to its members.
+        assert_eq!(state.quorum_requirement(&task_id, 1, &tier).expected_voters, 3);
+        assert!(!state.board_quorum_reached(&task_id, &tier));
+        assert!(state.quorum_met(&task_id, &tier, &tier[..3]));
     }
 
-    fn level_to_tier(level: u32) -> Tier {
-        match level {
-            1 => Tier::Tier1,
-            2 => Tier::Tier2,
-            n => Tier::TierN(n),
-        }
+    #[test]
+    fn unapproved_plan_is_rejected_after_deadline() {
+        let mut state = test_state();
+        let task_id = insert_gated_task(&mut state);
+
+        state.hold_plan_for_approval(&task_id, "plan-a");
+        let later = chrono::Utc::now() + chrono::Duration::seconds(DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS as i64 + 1);
+        assert!(state.take_approved_plans(later).is_empty());
+        assert_eq!(state.task_details[&task_id].status, TaskStatus::Rejected);
+        assert!(state.task_timelines[&task_id]
+            .iter()
+            .any(|e| e.stage == "approval_timeout"));
     }
 
-    fn member_loop_active(state: &ConnectorState, agent_id: &str, max_staleness: Duration) -> bool {
+    #[test]
+    fn deadline_after_stops_at_the_timestamp_range() {
         let now = chrono::Utc::now();
-        state
-            .member_last_task_poll
-            .get(agent_id)
-            .and_then(|ts| now.signed_duration_since(*ts).to_std().ok())
-            .map(|age| age <= max_staleness)
-            .unwrap_or(false)
+        assert_eq!(deadline_after(now, 60), Some(now + chrono::Duration::seconds(60)));
+        assert_eq!(deadline_after(now, 10_000_000_000_000), None);
+        assert_eq!(deadline_after(now, u64::MAX), None);
     }
 
-    fn active_participating_members_in_tier(
-        state: &ConnectorState,
-        tier: Tier,
-        member_staleness: Duration,
-        poll_staleness: Duration,
-    ) -> Vec<String> {
-        state
-            .active_member_ids(member_staleness)
-            .into_iter()
-            .filter(|id| state.agent_tiers.get(id).copied().unwrap_or(Tier::Executor) == tier)
-            .filter(|id| Self::member_loop_active(state, id, poll_staleness))
-            .collect()
+    #[test]
+    fn out_of_range_approval_timeout_never_expires() {
+        let mut state = test_state();
+        state.plan_approval_timeout_secs = u64::MAX;
+        let task_id = insert_gated_task(&mut state);
+
+        state.hold_plan_for_approval(&task_id, "plan-a");
+        let later = chrono::Utc::now() + chrono::Duration::days(365);
+        assert!(state.take_approved_plans(later).is_empty());
+        assert!(state.pending_plan_approvals.contains_key(&task_id));
     }
 
-    fn is_participating_member_for_task(
-        state: &ConnectorState,
-        task_id: &str,
-        agent_id: &str,
-        poll_staleness: Duration,
-    ) -> bool {
-        let tier_level = state
-            .task_details
-            .get(task_id)
-            .map(|t| t.tier_level)
-            .or_else(|| state.task_vote_requirements.get(task_id).map(|r| r.tier_level))
-            .unwrap_or(1);
-        let tier = Self::level_to_tier(tier_level);
-        state.agent_tiers.get(agent_id).copied().unwrap_or(Tier::Executor) == tier
-            && Self::member_loop_active(state, agent_id, poll_staleness)
+    #[test]
+    fn required_topics_cover_live_tasks_and_open_subtasks() {
+        let mut state = test_state();
+        let swarm = state.current_swarm_id.as_str().to_string();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        let open = Task::new("open".to_string(), 2, 1);
+        let mut done = Task::new("done".to_string(), 2, 1);
+        done.status = TaskStatus::Completed;
+        let imported = Task::new("imported".to_string(), 1, 1);
+        root.subtasks = vec![open.task_id.clone(), done.task_id.clone()];
+        state.task_set.add(root.task_id.clone());
+        for task in [&root, &open, &done, &imported] {
+            state.task_details.insert(task.task_id.clone(), task.clone());
+        }
+
+        let topics = state.required_topics();
+        assert!(topics.contains(&SwarmTopics::keepalive()));
+        assert!(topics.contains(&SwarmTopics::tasks_for(&swarm, 1)));
+        assert!(topics.contains(&SwarmTopics::voting_for(&swarm, &root.task_id)));
+        assert!(topics.contains(&SwarmTopics::board_for(&swarm, &open.task_id)));
+        assert!(!topics.contains(&SwarmTopics::voting_for(&swarm, &done.task_id)));
+        assert!(!topics.contains(&SwarmTopics::voting_for(&swarm, &imported.task_id)));
+
+        state.join_swarm("research");
+        assert!(state
+            .required_topics()
+            .contains(&SwarmTopics::keepalive_for("research")));
     }
 
-    fn expected_vote_requirement_for_task(state: &ConnectorState, task_id: &str) -> TaskVoteRequirement {
-        let tier_level = state
-            .task_details
-            .get(task_id)
-            .map(|t| t.tier_level)
-            .or_else(|| state.task_vote_requirements.get(task_id).map(|r| r.tier_level))
-            .unwrap_or(1);
-        let tier = Self::level_to_tier(tier_level);
-        let expected = Self::active_participating_members_in_tier(
-            state,
-            tier,
-            Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS),
-            Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS),
-        )
-            .len()
-            .max(1);
+    #[test]
+    fn tier_changes_move_task_topic_subscriptions() {
+        let mut state = test_state();
+        let swarm = state.current_swarm_id.as_str().to_string();
+        let level = |n| SwarmTopics::tasks_for(&swarm, n);
+
+        // Startup subscribes every level; a coordinator keeps only its own.
+        state.my_tier = Tier::Tier1;
+        let (subscribe, unsubscribe) = state.update_tier_subscriptions();
+        assert!(subscribe.is_empty());
+        assert_eq!(unsubscribe.len(), wws_protocol::MAX_HIERARCHY_DEPTH as usize - 1);
+        assert!(!unsubscribe.contains(&level(1)));
+        assert_eq!(state.update_tier_subscriptions(), (Vec::new(), Vec::new()));
+
+        state.my_tier = Tier::Tier2;
+        assert_eq!(state.update_tier_subscriptions(), (vec![level(2)], vec![level(1)]));
+        let required = state.required_topics();
+        assert!(required.contains(&level(2)) && !required.contains(&level(1)));
+
+        state.my_tier = Tier::Executor;
+        let (subscribe, unsubscribe) = state.update_tier_subscriptions();
+        assert!(unsubscribe.is_empty() && subscribe.contains(&level(1)) && !subscribe.contains(&level(2)));
+    }
 
-        TaskVoteRequirement {
-            expected_proposers: expected,
-            expected_voters: expected,
-            tier_level,
+    #[test]
+    fn sortition_committee_narrows_participants_and_gates_seats() {
+        let mut state = test_state();
+        state.committee_size = 3;
+        for i in 0..10 {
+            let member = format!("did:swarm:tier1-{}", i);
+            state.mark_member_seen(&member);
+            state.mark_member_polled_tasks(&member);
+            state.agent_tiers.insert(member, Tier::Tier1);
         }
+        let (_, tier) = WwsConnector::tier_members_for_task(&state, "task-big");
+        let (_, committee) = WwsConnector::quorum_eligible_for_task(&state, "task-big");
+        assert!(tier.len() >= 10);
+        assert_eq!(committee.len(), 3);
+
+        let member = committee[0].clone();
+        let proof = state.committee_proof("task-big", &member).unwrap().unwrap();
+        assert!(state.check_committee_seat("task-big", &tier, &member, Some(&proof)).is_ok());
+
+        let outsider = tier.iter().find(|id| !committee.contains(id)).unwrap().clone();
+        assert!(state.committee_proof("task-big", &outsider).is_err());
+        assert!(state.check_committee_seat("task-big", &tier, &outsider, None).is_err());
+        assert!(state.check_committee_seat("task-big", &tier, &outsider, Some(&proof)).is_err());
+
+        state.committee_size = 0;
+        assert_eq!(WwsConnector::quorum_eligible_for_task(&state, "task-big").1, tier);
+        assert_eq!(state.committee_proof("task-big", &outsider), Ok(None));
     }
 
-    fn dynamic_branching_factor(swarm_size: u64) -> u64 {
-        let approx = (swarm_size as f64).sqrt().round() as u64;
-        approx.clamp(3, 10)
+    #[test]
+    fn joins_and_departures_count_as_epoch_churn() {
+        let mut state = test_state();
+        state.mark_member_seen(state.agent_id.clone().as_str());
+        state.mark_member_seen("did:swarm:peer-a");
+        state.mark_member_seen("did:swarm:peer-a");
+        state.mark_member_seen("did:swarm:peer-b");
+        assert_eq!(state.epoch_manager.current_info().membership_changes, 2);
+
+        state
+            .member_last_seen
+            .insert("did:swarm:peer-b".to_string(), chrono::Utc::now() - chrono::Duration::hours(1));
+        state.prune_stale_members(Duration::from_secs(60));
+        assert_eq!(state.epoch_manager.current_info().membership_changes, 3);
     }
 
-    /// Get the current network statistics.
-    pub async fn get_network_stats(&self) -> NetworkStats {
-        let state = self.state.read().await;
-        state.network_stats.clone()
+    /// Artifact store whose backend is unreachable.
+    struct UnreachableArtifacts(crate::storage::InMemoryArtifactStore);
+
+    impl crate::storage::KeyedStore<Artifact> for UnreachableArtifacts {
+        fn get(&self, task_id: &str) -> Option<&Artifact> {
+            self.0.get(task_id)
+        }
+        fn get_mut(&mut self, task_id: &str) -> Option<&mut Artifact> {
+            self.0.get_mut(task_id)
+        }
+        fn insert(&mut self, task_id: String, value: Artifact) -> Option<Artifact> {
+            self.0.insert(task_id, value)
+        }
+        fn remove(&mut self, task_id: &str) -> Option<Artifact> {
+            self.0.remove(task_id)
+        }
+        fn iter(&self) -> Box<dyn Iterator<Item = (&String, &Artifact)> + '_> {
+            Box::new(self.0.iter())
+        }
+        fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&String, &mut Artifact)> + '_> {
+            Box::new(self.0.iter_mut())
+        }
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+        fn flush(&mut self) -> anyhow::Result<()> {
+            anyhow::bail!("connection refused")
+        }
     }
 
-    /// Get the shared state for use by the RPC server.
-    pub fn shared_state(&self) -> Arc<RwLock<ConnectorState>> {
-        Arc::clone(&self.state)
+    impl ArtifactStore for UnreachableArtifacts {}
+
+    #[test]
+    fn flush_reports_the_failing_store() {
+        let mut state = test_state();
+        assert!(state.flush_stores().is_ok());
+
+        state.task_results = Box::new(UnreachableArtifacts(Default::default()));
+        state.push_task_timeline_event("t1", "injected", "queued".to_string(), None);
+        assert_eq!(state.task_timelines["t1"].len(), 1);
+        assert_eq!(
+            state.flush_stores(),
+            Err("artifacts: connection refused".to_string())
+        );
     }
 
-    /// Get the network handle for use by the RPC server.
-    pub fn network_handle(&self) -> SwarmHandle {
-        self.network_handle.clone()
+    #[test]
+    fn recorded_ballots_get_receipts_their_voter_accepts() {
+        let mut recorder = test_state();
+        recorder.agent_id = AgentId::new(wws_protocol::crypto::derive_agent_id(
+            &recorder.identity_key.verifying_key(),
+        ));
+        let voter_node = test_state();
+        let voter = voter_node.agent_id.clone();
+
+        let mut engine = VotingEngine::new(wws_consensus::voting::VotingConfig::default(), "t1".to_string(), 1);
+        engine.set_proposals(
+            [("plan-a".to_string(), AgentId::new("did:swarm:proposer".to_string()))].into(),
+        );
+        let vote = RankedVote {
+            voter: voter.clone(),
+            task_id: "t1".to_string(),
+            epoch: 1,
+            rankings: vec!["plan-a".to_string()],
+            critic_scores: std::collections::HashMap::new(),
+            abstain: false,
+        };
+        engine.record_vote(vote.clone()).unwrap();
+        recorder.voting_engines.insert("t1".to_string(), engine);
+
+        let receipt = recorder.issue_ballot_receipt("t1", &voter, 1).unwrap();
+        assert_eq!(receipt.position, 1);
+        assert_eq!(receipt.ballot_hash, wws_consensus::voting::ballot_hash(&vote));
+        assert!(receipt.verify());
+        assert_eq!(recorder.issued_ballot_receipt("t1", &voter), Some(&receipt));
+        assert!(recorder
+            .issue_ballot_receipt("t1", &AgentId::new("did:swarm:nobody".to_string()), 1)
+            .is_none());
+
+        let mut voter_node = voter_node;
+        assert_eq!(voter_node.accept_ballot_receipt(receipt.clone()), Ok(true));
+        assert_eq!(voter_node.ballot_receipts["t1"].len(), 1);
+
+        // A receipt whose key does not belong to the named recorder is refused.
+        let mut forged = BallotReceiptParams {
+            recorder: AgentId::new("did:swarm:someone-else".to_string()),
+            ..receipt.clone()
+        };
+        forged.sign(&voter_node.identity_key);
+        assert!(voter_node.accept_ballot_receipt(forged).is_err());
+
+        // Receipts for other nodes' voters are not kept.
+        let mut bystander = test_state();
+        bystander.agent_id = AgentId::new("did:swarm:bystander".to_string());
+        assert_eq!(bystander.accept_ballot_receipt(receipt), Ok(false));
+        assert!(bystander.ballot_receipts.is_empty());
     }
 
-}
+    /// A node whose DID is derived from `identity_key`.
+    fn node_with_key(identity_key: ed25519_dalek::SigningKey) -> ConnectorState {
+        let mut state = test_state();
+        state.identity_key = identity_key;
+        let peer_id = wws_network::Keypair::ed25519_from_bytes(state.identity_key.to_bytes())
+            .unwrap()
+            .public()
+            .to_peer_id();
+        state.agent_id = AgentId::new(format!("did:swarm:{}", peer_id));
+        state
+    }
 
-impl Clone for WwsConnector {
-    fn clone(&self) -> Self {
-        Self {
-            state: Arc::clone(&self.state),
-            network_handle: self.network_handle.clone(),
-            event_rx: None, // Don't clone the event receiver (consumed by run())
-            swarm_host: None, // Don't clone the swarm host (consumed by run())
-            config: self.config.clone(),
-        }
+    /// A node whose DID is derived from a fresh identity key.
+    fn keyed_node() -> ConnectorState {
+        node_with_key(wws_protocol::crypto::generate_keypair())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn private_swarm_node(token: &SwarmToken, seed: u8) -> ConnectorState {
+        let mut state = node_with_key(ed25519_dalek::SigningKey::from_bytes(&[seed; 32]));
+        state.topic_keys = Some(Arc::new(std::sync::RwLock::new(TopicKeyring::new(
+            "lab",
+            token.clone(),
+        ))));
+        state
+    }
 
     #[test]
-    fn parse_bootstrap_peers_valid_multiaddr_with_peer_id() {
-        // Use a valid Ed25519 peer ID (base58btc encoded).
-        let peer_id = PeerId::random();
-        let addr = format!("/ip4/192.168.1.1/tcp/9000/p2p/{}", peer_id);
-        let result = WwsConnector::parse_bootstrap_peers(&[addr]);
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].0, peer_id);
+    fn assignments_are_applied_only_from_entitled_issuers() {
+        let leader = keyed_node();
+        let coordinator = keyed_node();
+        let (leader_id, coordinator_id) = (leader.agent_id.to_string(), coordinator.agent_id.to_string());
+        let executor = AgentId::new("did:swarm:executor".to_string());
+        let mut observer = test_state();
+        observer.agent_tiers.insert(leader_id.clone(), Tier::Tier1);
+        observer.agent_tiers.insert(coordinator_id.clone(), Tier::Tier2);
+        observer.agent_parents.insert(coordinator_id.clone(), leader_id.clone());
+        observer.agent_parents.insert(executor.to_string(), coordinator_id.clone());
+
+        // Only Tier-1 agents assign tiers.
+        let tier = |node: &ConnectorState| {
+            node.signed_tier_assignment(
+                executor.clone(),
+                Tier::Executor,
+                AgentId::new(coordinator_id.clone()),
+                1,
+                4,
+            )
+        };
+        assert!(observer.authorize_tier_assignment(&tier(&leader)).is_ok());
+        let refused = observer.authorize_tier_assignment(&tier(&coordinator)).unwrap_err();
+        assert!(refused.contains("Tier2"), "{}", refused);
+
+        // Tasks are assigned from above: the parent or further up.
+        let task = |node: &ConnectorState, assignee: &AgentId| {
+            node.signed_assignment(
+                Task::new("Collect sources".to_string(), 2, 1),
+                assignee.clone(),
+                "root".to_string(),
+                "plan-1",
+            )
+        };
+        assert!(observer.authorize_task_assignment(&task(&coordinator, &executor)).is_ok());
+        assert!(observer.authorize_task_assignment(&task(&leader, &executor)).is_ok());
+        let upward = task(&coordinator, &AgentId::new(leader_id.clone()));
+        assert!(observer.authorize_task_assignment(&upward).is_err());
+
+        // The issuer must hold the key its DID names, and must sign.
+        let mut forged = task(&coordinator, &executor);
+        forged.issuer = leader.agent_id.clone();
+        let refused = observer.authorize_task_assignment(&forged).unwrap_err();
+        assert!(refused.contains("not signed by the key"), "{}", refused);
+        let mut unsigned = task(&leader, &executor);
+        unsigned.signature.clear();
+        let refused = observer.authorize_task_assignment(&unsigned).unwrap_err();
+        assert!(refused.contains("unsigned"), "{}", refused);
     }
 
     #[test]
-    fn parse_bootstrap_peers_missing_peer_id() {
-        let addr = "/ip4/192.168.1.1/tcp/9000".to_string();
-        let result = WwsConnector::parse_bootstrap_peers(&[addr]);
-        assert!(result.is_empty(), "Should skip addrs without /p2p/ component");
+    fn epoch_rekeys_lock_out_members_that_left() {
+        let token = SwarmToken::generate(&SwarmId::new("lab".to_string()), "secret");
+        // Fixed keys keep the DID order, and so the rekey issuer, stable; the
+        // newcomer joining later sorts last.
+        let mut nodes: Vec<ConnectorState> = (1..=4).map(|seed| private_swarm_node(&token, seed)).collect();
+        nodes.sort_by(|a, b| a.agent_id.as_str().cmp(b.agent_id.as_str()));
+        let mut newcomer = nodes.pop().unwrap();
+        let ids: Vec<String> = nodes.iter().map(|n| n.agent_id.to_string()).collect();
+        for node in &mut nodes {
+            for id in &ids {
+                node.mark_member_seen(id);
+            }
+        }
+        let [mut issuer, mut member, mut leaver]: [ConnectorState; 3] = nodes.try_into().ok().unwrap();
+        assert_eq!(member.rekey_issuer(), Some(ids[0].clone()));
+
+        let first = issuer.issue_rekey().unwrap();
+        assert_eq!(first.seeds.len(), 3);
+        let epoch = first.epoch;
+        assert_eq!(member.accept_rekey(&first), Ok(Some(epoch)));
+        assert_eq!(leaver.accept_rekey(&first), Ok(Some(epoch)));
+        assert_eq!(member.accept_rekey(&first), Ok(None));
+        assert_eq!(leaver.topic_key_id(), issuer.topic_key_id());
+
+        // The leaver drops out before the next transition.
+        for node in [&mut issuer, &mut member] {
+            node.member_last_seen.remove(&ids[2]);
+            node.epoch_manager.advance_epoch(vec![], 2);
+        }
+        let second = issuer.issue_rekey().unwrap();
+        assert_eq!(second.seeds.len(), 2);
+        assert_eq!(leaver.accept_rekey(&second), Ok(None));
+        assert_eq!(member.accept_rekey(&second), Ok(Some(epoch + 1)));
+
+        let topic = SwarmTopics::tasks_for("lab", 1);
+        let sealed = member
+            .topic_keys
+            .as_ref()
+            .unwrap()
+            .read()
+            .unwrap()
+            .seal(&topic, b"new work".to_vec())
+            .unwrap();
+        let open = |node: &ConnectorState| {
+            node.topic_keys.as_ref().unwrap().read().unwrap().open(&topic, sealed.clone())
+        };
+        assert_eq!(open(&issuer).unwrap(), b"new work");
+        assert!(open(&leaver).is_err());
+
+        // Still holding the token, the leaver cannot hand out a seed of its own.
+        leaver.epoch_manager.advance_epoch(vec![], 3);
+        leaver.epoch_manager.advance_epoch(vec![], 3);
+        let hijack = leaver.issue_rekey().unwrap();
+        assert!(member.accept_rekey(&hijack).is_err());
+
+        // A newcomer gets the current seed once it announces no key.
+        issuer.mark_member_seen(newcomer.agent_id.as_str());
+        assert!(member.reseal_topic_key(newcomer.agent_id.as_str(), None).is_none());
+        let held = issuer.topic_key_id();
+        assert!(issuer
+            .reseal_topic_key(newcomer.agent_id.as_str(), held.as_deref())
+            .is_none());
+        let reseal = issuer.reseal_topic_key(newcomer.agent_id.as_str(), None).unwrap();
+        assert_eq!(newcomer.accept_rekey(&reseal), Ok(Some(epoch + 1)));
+        assert_eq!(newcomer.topic_key_id(), held);
     }
 
     #[test]
-    fn parse_bootstrap_peers_invalid_multiaddr() {
-        let addr = "not-a-valid-multiaddr".to_string();
-        let result = WwsConnector::parse_bootstrap_peers(&[addr]);
-        assert!(result.is_empty(), "Should skip unparseable addrs");
+    fn abandoned_rounds_expire_and_fail_their_task() {
+        let mut state = test_state();
+        let mut stalled = Task::new("never decided".to_string(), 1, 1);
+        stalled.status = TaskStatus::ProposalPhase;
+        let mut running = Task::new("already executing".to_string(), 1, 1);
+        running.status = TaskStatus::InProgress;
+        let (stalled_id, running_id) = (stalled.task_id.clone(), running.task_id.clone());
+        state.task_details.insert(stalled_id.clone(), stalled);
+        state.task_details.insert(running_id.clone(), running);
+        for id in [&stalled_id, &running_id, &"awaiting-approval".to_string()] {
+            state.rfp_coordinators.insert(id.clone(), RfpCoordinator::new(id.clone(), 1, 1));
+            state.voting_engines.insert(
+                id.clone(),
+                VotingEngine::new(state.voting_config.clone(), id.clone(), 1),
+            );
+        }
+        state.task_set.add(stalled_id.clone());
+        let now = chrono::Utc::now();
+        state.pending_plan_approvals.insert(
+            "awaiting-approval".to_string(),
+            PendingPlanApproval {
+                task_id: "awaiting-approval".to_string(),
+                winner_plan_id: "plan-a".to_string(),
+                selected_at: now,
+                deadline: now + chrono::Duration::seconds(DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS as i64),
+                approved_by: None,
+            },
+        );
+
+        assert!(state.expire_abandoned_rounds(chrono::Utc::now()).is_empty());
+        assert_eq!(state.rfp_coordinators.len(), 3);
+
+        let later = chrono::Utc::now()
+            + chrono::Duration::seconds(DEFAULT_MAX_ROUND_AGE_SECS as i64 + 1);
+        let failed = state.expire_abandoned_rounds(later);
+        assert_eq!(failed, vec![(stalled_id.clone(), "public".to_string())]);
+        assert_eq!(state.task_details[&stalled_id].status, TaskStatus::Failed);
+        assert_eq!(state.task_timelines[&stalled_id].last().unwrap().stage, "consensus_expired");
+        // A round that finished only leaves its state behind.
+        assert_eq!(state.task_details[&running_id].status, TaskStatus::InProgress);
+        assert!(!state.voting_engines.contains_key(&running_id));
+        // Rounds held for operator approval are not abandoned.
+        assert_eq!(state.rfp_coordinators.keys().collect::<Vec<_>>(), ["awaiting-approval"]);
+        assert!(!state
+            .required_topics()
+            .contains(&SwarmTopics::proposals_for("public", &stalled_id)));
     }
 
     #[test]
-    fn parse_bootstrap_peers_empty_and_whitespace_skipped() {
-        let addrs = vec![
-            "".to_string(),
-            "   ".to_string(),
-        ];
-        let result = WwsConnector::parse_bootstrap_peers(&addrs);
-        assert!(result.is_empty());
+    fn unproposed_tasks_are_retendered_until_they_fail() {
+        let mut state = test_state();
+        state.retender_relax_tier = true;
+        let timeout = chrono::Duration::seconds(state.proposal_stage_timeout_secs() + 1);
+        let stalled = Task::new("nobody proposes".to_string(), 2, 1);
+        let planned = Task::new("drew a plan".to_string(), 1, 1);
+        let (stalled_id, planned_id) = (stalled.task_id.clone(), planned.task_id.clone());
+        state.task_details.insert(stalled_id.clone(), stalled);
+        state.task_details.insert(planned_id.clone(), planned);
+        let now = chrono::Utc::now();
+        state.record_tender(&stalled_id, now);
+        state.record_tender(&planned_id, now);
+        assert!(state.take_due_retenders(now).is_empty());
+
+        state.voting_engines.insert(
+            planned_id.clone(),
+            VotingEngine::new(state.voting_config.clone(), planned_id.clone(), 1),
+        );
+        let later = now + timeout;
+        let outcomes = state.take_due_retenders(later);
+        assert_eq!(outcomes.len(), 1);
+        let TenderOutcome::Retendered(task) = &outcomes[0].1 else {
+            panic!("expected a re-tender");
+        };
+        assert_eq!(task.tier_level, 1, "relaxed one tier up");
+        assert_eq!(state.task_tenders[&stalled_id].attempt, 1);
+        assert!(!state.task_tenders.contains_key(&planned_id), "a plan was revealed");
+        assert_eq!(state.round_restarts[&stalled_id], later);
+        assert_eq!(state.task_timelines[&stalled_id].last().unwrap().stage, "retendered");
+
+        let outcomes = state.take_due_retenders(later + timeout);
+        assert!(matches!(outcomes[0].1, TenderOutcome::Retendered(_)));
+        let outcomes = state.take_due_retenders(later + timeout + timeout);
+        let TenderOutcome::GaveUp { swarm_id, failure } = &outcomes[0].1 else {
+            panic!("expected the task to be given up");
+        };
+        assert_eq!(swarm_id, "public");
+        assert_eq!(failure.attempts, 3);
+        assert_eq!(state.task_details[&stalled_id].status, TaskStatus::Failed);
+        assert_eq!(state.task_timelines[&stalled_id].last().unwrap().stage, "tender_failed");
+        assert!(state.task_tenders.is_empty());
+
+        // Other members fail the task on the announcement, unless a plan
+        // was revealed to them meanwhile.
+        let mut member = test_state();
+        let mut copy = state.task_details[&stalled_id].clone();
+        copy.status = TaskStatus::Pending;
+        member.task_details.insert(stalled_id.clone(), copy);
+        member.task_details.insert(planned_id.clone(), state.task_details[&planned_id].clone());
+        member.voting_engines.insert(
+            planned_id.clone(),
+            VotingEngine::new(member.voting_config.clone(), planned_id.clone(), 1),
+        );
+        assert!(member.apply_tender_failure(failure));
+        assert_eq!(member.task_details[&stalled_id].status, TaskStatus::Failed);
+        assert_eq!(
+            member.task_timelines[&stalled_id].last().unwrap().detail,
+            "No plan proposed after 3 tenders"
+        );
+        assert!(!member.apply_tender_failure(failure), "already failed");
+        let planned_failure = TaskTenderFailedParams {
+            task_id: planned_id.clone(),
+            ..failure.clone()
+        };
+        assert!(!member.apply_tender_failure(&planned_failure));
+        assert_ne!(member.task_details[&planned_id].status, TaskStatus::Failed);
     }
 
     #[test]
-    fn parse_bootstrap_peers_multiple_valid() {
-        let peer1 = PeerId::random();
-        let peer2 = PeerId::random();
-        let addrs = vec![
-            format!("/ip4/10.0.0.1/tcp/4001/p2p/{}", peer1),
-            format!("/ip4/10.0.0.2/tcp/4001/p2p/{}", peer2),
-        ];
-        let result = WwsConnector::parse_bootstrap_peers(&addrs);
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0].0, peer1);
-        assert_eq!(result[1].0, peer2);
+    fn reconciliation_converges_on_the_later_decision() {
+        let mut a = test_state();
+        let mut b = test_state();
+        for (state, assignee) in [(&mut a, "did:swarm:x"), (&mut b, "did:swarm:y")] {
+            for id in ["t1", "t2"] {
+                let mut task = Task::new(format!("task {}", id), 1, 1);
+                task.task_id = id.to_string();
+                task.status = TaskStatus::InProgress;
+                task.assigned_to = Some(AgentId::new(assignee.to_string()));
+                state.task_details.insert(id.to_string(), task);
+            }
+        }
+        // Each side decided one of the tasks last while split.
+        a.push_task_timeline_event("t1", "assigned", "to x", None);
+        b.push_task_timeline_event("t2", "assigned", "to y", None);
+        b.push_task_timeline_event("t1", "assigned", "to y", None);
+        a.push_task_timeline_event("t2", "completed", "by x", None);
+        a.task_details.get_mut("t2").unwrap().status = TaskStatus::Completed;
+
+        let conflicts = a.reconcile_with_peer("did:swarm:b", &b.task_digests());
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(
+            a.task_details["t1"].assigned_to,
+            Some(AgentId::new("did:swarm:y".to_string()))
+        );
+        assert_eq!(a.task_details["t2"].status, TaskStatus::Completed);
+        assert_eq!(a.task_timelines["t1"].last().unwrap().stage, "partition_reconciled");
+
+        b.reconcile_with_peer("did:swarm:a", &a.task_digests());
+        assert_eq!(b.task_details["t2"].status, TaskStatus::Completed);
+        assert_eq!(
+            b.task_details["t2"].assigned_to,
+            Some(AgentId::new("did:swarm:x".to_string()))
+        );
+        // Both sides now agree, down to the finality stamps.
+        let digests = |state: &ConnectorState| {
+            let mut d = state.task_digests();
+            d.sort_by(|x, y| x.task_id.cmp(&y.task_id));
+            d
+        };
+        assert_eq!(digests(&a), digests(&b));
+        assert!(a.reconcile_with_peer("did:swarm:b", &b.task_digests()).is_empty());
     }
 
     #[test]
-    fn parse_bootstrap_peers_mixed_valid_and_invalid() {
-        let peer1 = PeerId::random();
-        let addrs = vec![
-            format!("/ip4/10.0.0.1/tcp/4001/p2p/{}", peer1),
-            "/ip4/10.0.0.2/tcp/4001".to_string(), // no peer id
-            "garbage".to_string(),                  // unparseable
-        ];
-        let result = WwsConnector::parse_bootstrap_peers(&addrs);
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].0, peer1);
+    fn merging_views_adopts_the_larger_view_and_replays_missing_tasks() {
+        let mut small = test_state();
+        let mut large = test_state();
+        let now = chrono::Utc::now();
+        for member in ["did:swarm:b1", "did:swarm:b2"] {
+            let lineage = large.merge.lineage().clone();
+            large.mark_member_seen(member);
+            large.merge.observe(member, &lineage, now);
+        }
+        large.epoch_manager.force_epoch(7, Vec::new(), 3);
+        for (state, id) in [(&mut small, "t-small"), (&mut large, "t-large")] {
+            let mut task = Task::new(format!("task {}", id), 1, 1);
+            task.task_id = id.to_string();
+            state.task_details.insert(id.to_string(), task);
+        }
+
+        // The larger view keeps its lineage and epoch but takes the task
+        // it was missing, and answers with only the task the other lacks.
+        let request = small.merge_view(small.merge_tasks(&[]));
+        let report = large.merge_with(&request).unwrap();
+        assert!(!report.adopted);
+        assert_eq!((report.tasks_replayed, report.epoch_after), (1, 7));
+        assert_eq!(large.task_timelines["t-small"].last().unwrap().stage, "merge_replayed");
+        let reply = large.merge_view(large.merge_tasks(&request.digests));
+        assert_eq!(reply.tasks.len(), 1);
+        assert_eq!(reply.members.len(), 3);
+
+        let report = small.merge_with(&reply).unwrap();
+        assert!(report.adopted);
+        assert_eq!((report.epoch_before, report.epoch_after), (request.epoch, 7));
+        assert_eq!(small.merge.lineage(), large.merge.lineage());
+        assert!(small.task_details.contains_key("t-large"));
+        assert!(small.active_member_ids(Duration::from_secs(60)).contains(&"did:swarm:b1".to_string()));
+        assert!(small
+            .event_log
+            .iter()
+            .any(|e| e.message.starts_with("AUDIT swarm.merge")));
     }
 
     #[test]
-    fn extract_peer_id_from_valid_addr() {
-        let peer_id = PeerId::random();
-        let addr = format!("/ip4/127.0.0.1/tcp/8080/p2p/{}", peer_id);
-        let extracted = WwsConnector::extract_peer_id_from_addr(&addr);
-        assert_eq!(extracted, Some(peer_id));
+    fn bonds_are_slashed_for_late_withdrawals_and_the_adversarys_flags() {
+        let mut state = test_state();
+        let mut task = Task::new("Plan the launch".to_string(), 1, 1);
+        task.task_id = "t1".to_string();
+        let mut rfp = RfpCoordinator::new("t1".to_string(), 1, 3);
+        rfp.inject_task(&task).unwrap();
+        let plans: Vec<Plan> = ["did:swarm:p1", "did:swarm:p2", "did:swarm:p3"]
+            .into_iter()
+            .map(|p| Plan::new("t1".to_string(), AgentId::new(p.to_string()), 1))
+            .collect();
+        for plan in &plans {
+            let commit = ProposalCommitParams {
+                task_id: "t1".to_string(),
+                proposer: plan.proposer.clone(),
+                epoch: 1,
+                plan_hash: RfpCoordinator::compute_plan_hash(plan).unwrap(),
+                bond: 8,
+                selection: None,
+            };
+            rfp.record_commit(&commit).unwrap();
+            state.hold_proposal_bond(&commit);
+        }
+        for plan in &plans {
+            rfp.record_reveal(&ProposalRevealParams {
+                task_id: "t1".to_string(),
+                plan: plan.clone(),
+            })
+            .unwrap();
+        }
+        let mut engine = VotingEngine::new(wws_consensus::voting::VotingConfig::default(), "t1".to_string(), 1);
+        engine.set_proposals(
+            plans.iter().map(|p| (p.plan_id.clone(), p.proposer.clone())).collect(),
+        );
+        state.rfp_coordinators.insert("t1".to_string(), rfp);
+        state.voting_engines.insert("t1".to_string(), engine);
+        state.active_holons.insert(
+            "t1".to_string(),
+            HolonState {
+                task_id: "t1".to_string(),
+                chair: state.agent_id.clone(),
+                members: Vec::new(),
+                adversarial_critic: Some(AgentId::new("did:swarm:critic".to_string())),
+                depth: 1,
+                parent_holon: None,
+                child_holons: Vec::new(),
+                subtask_assignments: std::collections::HashMap::new(),
+                child_artifacts: std::collections::HashMap::new(),
+                status: HolonStatus::Voting,
+                created_at: chrono::Utc::now(),
+            },
+        );
+        let reputation = |state: &ConnectorState, agent: &str| {
+            AgentActivity::reputation_of(state.agent_activity.get(agent))
+        };
+        let withdraw = |plan: &Plan| ProposalWithdrawParams {
+            task_id: "t1".to_string(),
+            plan_id: plan.plan_id.clone(),
+            proposer: plan.proposer.clone(),
+            reason: String::new(),
+        };
+
+        // Withdrawing before anyone voted costs nothing.
+        state.apply_plan_withdrawal(&withdraw(&plans[2])).unwrap();
+        assert_eq!(reputation(&state, "did:swarm:p3"), 10);
+
+        // Only the adversarial critic's flags slash.
+        let flagged = [plans[1].plan_id.clone()];
+        state.apply_malicious_flags("t1", "did:swarm:p1", &flagged);
+        assert_eq!(state.proposal_bonds.held("t1", "did:swarm:p2"), 8);
+        state.apply_malicious_flags("t1", "did:swarm:critic", &flagged);
+        assert_eq!(state.proposal_bonds.held("t1", "did:swarm:p2"), 0);
+        assert_eq!(reputation(&state, "did:swarm:p2"), 2);
+
+        // Once ballots are in, a withdrawal forfeits the bond.
+        state
+            .voting_engines
+            .get_mut("t1")
+            .unwrap()
+            .record_vote(RankedVote {
+                voter: AgentId::new("did:swarm:voter".to_string()),
+                task_id: "t1".to_string(),
+                epoch: 1,
+                rankings: vec![plans[0].plan_id.clone()],
+                critic_scores: std::collections::HashMap::new(),
+                abstain: false,
+            })
+            .unwrap();
+        state.apply_plan_withdrawal(&withdraw(&plans[0])).unwrap();
+        assert_eq!(reputation(&state, "did:swarm:p1"), 2);
+        assert_eq!(state.agent_activity["did:swarm:p1"].bonds_slashed_count, 1);
     }
 
     #[test]
-    fn extract_peer_id_from_addr_without_p2p() {
-        let addr = "/ip4/127.0.0.1/tcp/8080";
-        let extracted = WwsConnector::extract_peer_id_from_addr(addr);
-        assert!(extracted.is_none());
+    fn late_subscriber_catches_up_from_the_chairs_snapshot() {
+        let mut chair = test_state();
+        chair.agent_id = AgentId::new("did:swarm:chair".to_string());
+        let mut joiner = test_state();
+        joiner.agent_id = AgentId::new("did:swarm:joiner".to_string());
+
+        let mut task = Task::new("Plan the launch".to_string(), 1, 1);
+        task.task_id = "t1".to_string();
+        let mut rfp = RfpCoordinator::new("t1".to_string(), 1, 2);
+        rfp.inject_task(&task).unwrap();
+        let plans: Vec<Plan> = ["did:swarm:p1", "did:swarm:p2"]
+            .into_iter()
+            .map(|p| Plan::new("t1".to_string(), AgentId::new(p.to_string()), 1))
+            .collect();
+        for plan in &plans {
+            rfp.record_commit(&ProposalCommitParams {
+                task_id: "t1".to_string(),
+                proposer: plan.proposer.clone(),
+                epoch: 1,
+                plan_hash: RfpCoordinator::compute_plan_hash(plan).unwrap(),
+                bond: 0,
+                selection: None,
+            })
+            .unwrap();
+        }
+        for plan in &plans {
+            rfp.record_reveal(&ProposalRevealParams {
+                task_id: "t1".to_string(),
+                plan: plan.clone(),
+            })
+            .unwrap();
+        }
+        let mut engine = VotingEngine::new(wws_consensus::voting::VotingConfig::default(), "t1".to_string(), 1);
+        engine.set_proposals(
+            plans.iter().map(|p| (p.plan_id.clone(), p.proposer.clone())).collect(),
+        );
+        engine
+            .record_vote(RankedVote {
+                voter: AgentId::new("did:swarm:voter".to_string()),
+                task_id: "t1".to_string(),
+                epoch: 1,
+                rankings: vec![plans[0].plan_id.clone(), plans[1].plan_id.clone()],
+                critic_scores: std::collections::HashMap::new(),
+                abstain: false,
+            })
+            .unwrap();
+        chair.rfp_coordinators.insert("t1".to_string(), rfp);
+        chair.voting_engines.insert("t1".to_string(), engine);
+        chair.active_holons.insert(
+            "t1".to_string(),
+            HolonState {
+                task_id: "t1".to_string(),
+                chair: chair.agent_id.clone(),
+                members: Vec::new(),
+                adversarial_critic: None,
+                depth: 1,
+                parent_holon: None,
+                child_holons: Vec::new(),
+                subtask_assignments: std::collections::HashMap::new(),
+                child_artifacts: std::collections::HashMap::new(),
+                status: HolonStatus::Deliberating,
+                created_at: chrono::Utc::now(),
+            },
+        );
+
+        let request = joiner.note_task_state_request("t1").unwrap();
+        assert!(joiner.note_task_state_request("t1").is_none(), "request still pending");
+        assert!(chair.note_task_state_request("t1").is_none(), "chair already has commits");
+
+        let snapshot = chair.answer_task_state_request(&request).unwrap();
+        assert_eq!(snapshot.phase, "ReadyForVoting");
+        assert_eq!((snapshot.commits.len(), snapshot.reveals.len(), snapshot.ballots.len()), (2, 2, 1));
+        assert!(chair.answer_task_state_request(&request).is_none(), "one snapshot per interval");
+
+        let requirement = TaskVoteRequirement {
+            expected_proposers: 2,
+            expected_voters: 2,
+            tier_level: 1,
+        };
+        // Only a node that asked applies a snapshot, and only once.
+        let mut bystander = test_state();
+        bystander.agent_id = AgentId::new("did:swarm:bystander".to_string());
+        assert!(bystander.apply_task_state_snapshot(&snapshot, &requirement).is_none());
+        assert!(bystander.rfp_coordinators.is_empty());
+
+        assert_eq!(joiner.apply_task_state_snapshot(&snapshot, &requirement), Some((2, 2, 1)));
+        assert_eq!(
+            joiner.rfp_coordinators["t1"].phase(),
+            &wws_consensus::rfp::RfpPhase::ReadyForVoting
+        );
+        assert_eq!(joiner.voting_engines["t1"].ballot_count(), 1);
+        assert_eq!(joiner.voting_engines["t1"].proposal_count(), 2);
+        assert!(joiner.apply_task_state_snapshot(&snapshot, &requirement).is_none());
     }
 
     #[tokio::test]
@@ -0,0 +1,238 @@
+//! Supervised daemon mode.
+//!
+//! `wws-connector --daemon` runs without a terminal UI under a service
+//! manager (systemd, launchd, or a Windows service wrapper). It writes a
+//! pid file, and [`supervise`] restarts the whole connector — swarm host,
+//! RPC and file servers — with exponential backoff whenever a run fails,
+//! which includes the swarm host task stopping or panicking. SIGHUP
+//! reloads the configuration by restarting with a freshly loaded config;
+//! SIGTERM or Ctrl-C stops the daemon cleanly. Each run learns how many
+//! restarts preceded it, and `swarm.health` reports them.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tokio::task::AbortHandle;
+
+/// A run lasting at least this long resets the restart backoff.
+pub const STABLE_RUN_SECS: u64 = 60;
+
+/// A pid file that is removed again when dropped.
+#[derive(Debug)]
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    /// Write this process's pid to `path`. Fails if the file names a
+    /// process that is still running; a stale file is replaced.
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        if let Ok(existing) = std::fs::read_to_string(path) {
+            if let Ok(pid) = existing.trim().parse::<u32>() {
+                if pid != std::process::id() && process_is_running(pid) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::AlreadyExists,
+                        format!("{} names running process {}", path.display(), pid),
+                    ));
+                }
+            }
+        }
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, format!("{}\n", std::process::id()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Whether `pid` names a live process. Only Linux can tell; elsewhere an
+/// existing pid file is assumed stale.
+fn process_is_running(pid: u32) -> bool {
+    cfg!(target_os = "linux") && Path::new(&format!("/proc/{}", pid)).exists()
+}
+
+/// Delay before each restart: doubling from `base` up to `max`, and back
+/// to `base` after a run that stayed up for [`STABLE_RUN_SECS`].
+#[derive(Debug, Clone)]
+pub struct RestartBackoff {
+    base: Duration,
+    max: Duration,
+    next: Duration,
+}
+
+impl RestartBackoff {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        let base = base.max(Duration::from_millis(100));
+        Self {
+            base,
+            max: max.max(base),
+            next: base,
+        }
+    }
+
+    /// Delay before restarting a run that lasted `ran_for`.
+    pub fn delay_after(&mut self, ran_for: Duration) -> Duration {
+        if ran_for >= Duration::from_secs(STABLE_RUN_SECS) {
+            self.next = self.base;
+        }
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+
+    /// Pause between runs that are not failures, such as a reload.
+    pub fn base(&self) -> Duration {
+        self.base
+    }
+}
+
+/// Tasks aborted when the guard is dropped, so a run that ends or is
+/// cancelled does not leave its servers holding their ports.
+#[derive(Debug, Default)]
+pub struct TaskGuard(Vec<AbortHandle>);
+
+impl TaskGuard {
+    pub fn new(handles: Vec<AbortHandle>) -> Self {
+        Self(handles)
+    }
+
+    pub fn push(&mut self, handle: AbortHandle) {
+        self.0.push(handle);
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        for handle in &self.0 {
+            handle.abort();
+        }
+    }
+}
+
+/// Run `start` until the daemon is told to stop. `start` receives the
+/// number of restarts so far and runs one connector to completion: a
+/// clean return ends the daemon, an error restarts it after the backoff.
+/// On SIGHUP the current run is cancelled and started again, so it picks
+/// up a reloaded configuration.
+pub async fn supervise<S, F>(mut start: S, mut backoff: RestartBackoff) -> anyhow::Result<()>
+where
+    S: FnMut(u32) -> F,
+    F: Future<Output = anyhow::Result<()>>,
+{
+    let mut hangup = Hangup::new()?;
+    let mut terminate = Terminate::new()?;
+    let mut restarts = 0;
+    loop {
+        let started = Instant::now();
+        let delay = tokio::select! {
+            result = start(restarts) => match result {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    let delay = backoff.delay_after(started.elapsed());
+                    tracing::error!(error = %e, restarts, "Connector stopped; restarting in {:?}", delay);
+                    delay
+                }
+            },
+            _ = hangup.recv() => {
+                tracing::info!("SIGHUP received; reloading configuration");
+                backoff.base()
+            }
+            _ = terminate.recv() => {
+                tracing::info!("Shutdown requested; stopping daemon");
+                return Ok(());
+            }
+        };
+        restarts += 1;
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = terminate.recv() => return Ok(()),
+        }
+    }
+}
+
+/// SIGHUP, which asks the daemon to reload. There is no SIGHUP outside
+/// Unix; restart the service there instead.
+struct Hangup(#[cfg(unix)] tokio::signal::unix::Signal);
+
+impl Hangup {
+    fn new() -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            Ok(Self(signal(SignalKind::hangup())?))
+        }
+        #[cfg(not(unix))]
+        Ok(Self())
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        self.0.recv().await;
+        #[cfg(not(unix))]
+        std::future::pending::<()>().await
+    }
+}
+
+/// SIGTERM or Ctrl-C, which stop the daemon.
+struct Terminate(#[cfg(unix)] tokio::signal::unix::Signal);
+
+impl Terminate {
+    fn new() -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            Ok(Self(signal(SignalKind::terminate())?))
+        }
+        #[cfg(not(unix))]
+        Ok(Self())
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        tokio::select! {
+            _ = self.0.recv() => {}
+            _ = tokio::signal::ctrl_c() => {}
+        }
+        #[cfg(not(unix))]
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_resets_after_a_stable_run() {
+        let mut backoff = RestartBackoff::new(Duration::from_secs(1), Duration::from_secs(5));
+        let quick = Duration::from_secs(2);
+        let delays: Vec<u64> = (0..4).map(|_| backoff.delay_after(quick).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 5]);
+        let stable = Duration::from_secs(STABLE_RUN_SECS);
+        assert_eq!(backoff.delay_after(stable).as_secs(), 1);
+    }
+
+    #[test]
+    fn pid_file_is_written_and_removed() {
+        let path = std::env::temp_dir()
+            .join(format!("wws-pid-{}", uuid::Uuid::new_v4()))
+            .join("connector.pid");
+        {
+            let _pid = PidFile::create(&path).unwrap();
+            let written = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(written.trim(), std::process::id().to_string());
+            // Our own pid is not another running instance.
+            drop(PidFile::create(&path).unwrap());
+        }
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,196 @@
+//! Delegating subtasks to other swarms.
+//!
+//! A coordinator in one swarm hands a subtask to another swarm through a
+//! bridge: a connector that is a member of both. The bridge injects the
+//! task into the other swarm, and the coordinator adds a remote node to
+//! its task tree in its place: a subtask assigned to the bridge whose
+//! `remote` names the other swarm and the task there. The bridge tracks
+//! the task, and once it completes submits its result, readable or not, as
+//! the remote node's. The coordinator then aggregates the parent as it
+//! would after any other subtask.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use wws_protocol::{AgentId, Artifact, ResultSubmissionParams, TaskStatus};
+
+use crate::connector::ConnectorState;
+use crate::tui::LogCategory;
+
+/// Placeholder plan of the assignment that adds a remote node to the
+/// delegating swarm's task tree.
+pub const REMOTE_PLAN_ID: &str = "remote-delegation";
+
+/// A task this node injected as a bridge, with the remote node its result
+/// goes to.
+#[derive(Debug, Clone, Serialize)]
+pub struct Relay {
+    pub remote_task_id: String,
+    pub remote_swarm_id: String,
+    pub origin_swarm_id: String,
+    pub origin_task_id: String,
+    pub requested_by: AgentId,
+    pub since: DateTime<Utc>,
+}
+
+/// The relays this node holds as a bridge, by remote task ID.
+#[derive(Debug, Clone, Default)]
+pub struct RelayTracker {
+    relays: BTreeMap<String, Relay>,
+}
+
+impl RelayTracker {
+    pub fn track(&mut self, relay: Relay) {
+        self.relays.insert(relay.remote_task_id.clone(), relay);
+    }
+
+    pub fn get(&self, remote_task_id: &str) -> Option<&Relay> {
+        self.relays.get(remote_task_id)
+    }
+
+    pub fn relays(&self) -> impl Iterator<Item = &Relay> {
+        self.relays.values()
+    }
+
+    pub fn finish(&mut self, remote_task_id: &str) -> Option<Relay> {
+        self.relays.remove(remote_task_id)
+    }
+}
+
+/// Start relaying the result of `remote_task_id`, just injected into
+/// `remote_swarm_id`, to `origin_task_id` in `origin_swarm_id`.
+pub fn track_relay(
+    state: &mut ConnectorState,
+    remote_task_id: &str,
+    remote_swarm_id: &str,
+    origin_swarm_id: &str,
+    origin_task_id: &str,
+    requested_by: AgentId,
+) {
+    state.set_task_swarm(origin_task_id, origin_swarm_id);
+    state.push_task_timeline_event(
+        remote_task_id,
+        "delegated_in",
+        format!(
+            "Delegated from swarm {} by {}; result relays to {}",
+            origin_swarm_id, requested_by, origin_task_id
+        ),
+        Some(state.agent_id.to_string()),
+    );
+    state.relays.track(Relay {
+        remote_task_id: remote_task_id.to_string(),
+        remote_swarm_id: remote_swarm_id.to_string(),
+        origin_swarm_id: origin_swarm_id.to_string(),
+        origin_task_id: origin_task_id.to_string(),
+        requested_by,
+        since: Utc::now(),
+    });
+}
+
+/// The result of a relayed task, as the submission of its remote node.
+fn relayed_submission(state: &ConnectorState, relay: &Relay) -> Option<ResultSubmissionParams> {
+    let task = state.task_details.get(&relay.remote_task_id)?;
+    if task.status != TaskStatus::Completed {
+        return None;
+    }
+    let result = state.task_results.get(&relay.remote_task_id)?;
+    let content = state
+        .task_result_text
+        .get(&relay.remote_task_id)
+        .cloned()
+        .unwrap_or_else(|| result.content.clone());
+    let spend = state.task_spend(&relay.remote_task_id);
+    Some(ResultSubmissionParams {
+        task_id: relay.origin_task_id.clone(),
+        agent_id: state.agent_id.clone(),
+        artifact: Artifact {
+            task_id: relay.origin_task_id.clone(),
+            producer: state.agent_id.clone(),
+            content,
+            sealed: None,
+            ..result.clone()
+        },
+        merkle_proof: Vec::new(),
+        is_synthesis: false,
+        cost: (spend > 0.0).then_some(spend),
+    })
+}
+
+/// Submit the result of every relayed task that completed as its remote
+/// node's. A relay whose remote node refuses the result is dropped.
+pub async fn relay_results(state: &Arc<RwLock<ConnectorState>>, network_handle: &wws_network::SwarmHandle) {
+    let ready: Vec<(Relay, ResultSubmissionParams)> = {
+        let state = state.read().await;
+        state
+            .relays
+            .relays()
+            .filter_map(|relay| Some((relay.clone(), relayed_submission(&state, relay)?)))
+            .collect()
+    };
+    for (relay, submission) in ready {
+        let mut params = serde_json::to_value(&submission).unwrap_or_default();
+        params["content"] = serde_json::json!(submission.artifact.content);
+        let response = crate::rpc_server::handle_submit_result(None, &params, state, network_handle).await;
+        let mut state = state.write().await;
+        state.relays.finish(&relay.remote_task_id);
+        let agent = Some(state.agent_id.to_string());
+        match response.error {
+            None => {
+                state.push_task_timeline_event(
+                    &relay.remote_task_id,
+                    "relayed",
+                    format!("Result relayed to {} in swarm {}", relay.origin_task_id, relay.origin_swarm_id),
+                    agent,
+                );
+                state.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Relayed result of {} from swarm {} to {} in swarm {}",
+                        relay.remote_task_id, relay.remote_swarm_id, relay.origin_task_id, relay.origin_swarm_id
+                    ),
+                );
+            }
+            Some(error) => {
+                state.push_task_timeline_event(
+                    &relay.remote_task_id,
+                    "relay_refused",
+                    format!("{} refused the result: {}", relay.origin_task_id, error.message),
+                    agent,
+                );
+                state.push_log(
+                    LogCategory::Error,
+                    format!(
+                        "Could not relay result of {} to {}: {}",
+                        relay.remote_task_id, relay.origin_task_id, error.message
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relays_are_tracked_until_finished() {
+        let mut tracker = RelayTracker::default();
+        tracker.track(Relay {
+            remote_task_id: "remote".into(),
+            remote_swarm_id: "b".into(),
+            origin_swarm_id: "a".into(),
+            origin_task_id: "node".into(),
+            requested_by: AgentId::new("did:swarm:coordinator".into()),
+            since: Utc::now(),
+        });
+        assert_eq!(tracker.get("remote").unwrap().origin_task_id, "node");
+        assert_eq!(tracker.relays().count(), 1);
+        assert!(tracker.finish("remote").is_some());
+        assert!(tracker.get("remote").is_none());
+        assert!(tracker.finish("remote").is_none());
+    }
+}
@@ -0,0 +1,163 @@
+//! Acknowledged task delivery for `swarm.receive_task`.
+//!
+//! An agent that passes a `cursor` to `swarm.receive_task` opts into
+//! at-least-once delivery: each task handed to it gets a sequence number
+//! and stays delivered-unacked until the agent calls `swarm.ack_task`. A
+//! poll returns the tasks delivered after its cursor — so a response lost
+//! in transit, or an agent restarted from cursor 0, sees them again — and
+//! redelivers tasks left unacked for [`ACK_TIMEOUT_SECS`]. Until acked, a
+//! task counts as queued rather than in flight, so an agent that crashed
+//! before starting work does not hold it until the assignment times out.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Seconds a delivered task may stay unacknowledged before it is redelivered.
+pub const ACK_TIMEOUT_SECS: i64 = 30;
+
+/// Longest `wait_ms` a `swarm.receive_task` long poll may ask for.
+pub const MAX_RECEIVE_WAIT_MS: u64 = 30_000;
+
+/// A task handed to an agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct Delivery {
+    pub agent_id: String,
+    /// Position in this connector's delivery sequence; the cursor an agent
+    /// passes back is the highest sequence it has seen.
+    pub seq: u64,
+    pub delivered_at: DateTime<Utc>,
+    /// How many times the task has been handed out to this agent.
+    pub attempts: u32,
+    pub acked_at: Option<DateTime<Utc>>,
+}
+
+/// What one poll hands to an agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryBatch {
+    /// Task IDs in delivery order.
+    pub tasks: Vec<String>,
+    /// Those among `tasks` handed out again after going unacked.
+    pub redelivered: Vec<String>,
+    /// Cursor to pass on the next poll.
+    pub next_cursor: u64,
+}
+
+/// Deliveries made by this connector, keyed by task ID.
+#[derive(Debug, Default)]
+pub struct TaskDeliveries {
+    last_seq: u64,
+    by_task: HashMap<String, Delivery>,
+}
+
+impl TaskDeliveries {
+    /// Hand `agent_id` its share of `eligible` — the open tasks it may work
+    /// on, oldest first — on a poll at `now` with `cursor`. Records new
+    /// deliveries and redeliveries; records of this agent's tasks that are
+    /// no longer eligible are dropped.
+    pub fn deliver(
+        &mut self,
+        agent_id: &str,
+        eligible: &[String],
+        cursor: u64,
+        now: DateTime<Utc>,
+    ) -> DeliveryBatch {
+        self.by_task
+            .retain(|task_id, d| d.agent_id != agent_id || eligible.contains(task_id));
+        let mut batch = DeliveryBatch {
+            next_cursor: cursor,
+            ..Default::default()
+        };
+        for task_id in eligible {
+            let (fresh, overdue) = match self.by_task.get(task_id) {
+                Some(d) if d.agent_id == agent_id => (
+                    false,
+                    d.acked_at.is_none()
+                        && (now - d.delivered_at).num_seconds() >= ACK_TIMEOUT_SECS,
+                ),
+                _ => (true, false),
+            };
+            if fresh || overdue {
+                self.last_seq += 1;
+                let attempts = if overdue { self.by_task[task_id].attempts + 1 } else { 1 };
+                self.by_task.insert(
+                    task_id.clone(),
+                    Delivery {
+                        agent_id: agent_id.to_string(),
+                        seq: self.last_seq,
+                        delivered_at: now,
+                        attempts,
+                        acked_at: None,
+                    },
+                );
+                if overdue {
+                    batch.redelivered.push(task_id.clone());
+                }
+            }
+            let seq = self.by_task[task_id].seq;
+            if seq > cursor {
+                batch.tasks.push(task_id.clone());
+                batch.next_cursor = batch.next_cursor.max(seq);
+            }
+        }
+        batch
+    }
+
+    /// Record that `agent_id` started work on `task_id`.
+    pub fn ack(&mut self, task_id: &str, agent_id: &str, now: DateTime<Utc>) -> Result<(), String> {
+        match self.by_task.get_mut(task_id) {
+            Some(d) if d.agent_id == agent_id => {
+                d.acked_at.get_or_insert(now);
+                Ok(())
+            }
+            _ => Err(format!("Task {} was not delivered to {}", task_id, agent_id)),
+        }
+    }
+
+    /// The delivery of `task_id`, if it was handed out.
+    pub fn get(&self, task_id: &str) -> Option<&Delivery> {
+        self.by_task.get(task_id)
+    }
+
+    /// Drop the delivery of a task that moved to another agent.
+    pub fn forget(&mut self, task_id: &str) {
+        self.by_task.remove(task_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn polls_return_new_tasks_and_redeliver_unacked_ones() {
+        let mut deliveries = TaskDeliveries::default();
+        let t0 = Utc::now();
+        let first = deliveries.deliver("a", &ids(&["t1", "t2"]), 0, t0);
+        assert_eq!(first.tasks, ids(&["t1", "t2"]));
+        assert_eq!(first.next_cursor, 2);
+
+        // Nothing new past the cursor; an agent restarting from 0 sees both.
+        assert!(deliveries.deliver("a", &ids(&["t1", "t2"]), 2, t0).tasks.is_empty());
+        assert_eq!(deliveries.deliver("a", &ids(&["t1", "t2"]), 0, t0).tasks.len(), 2);
+
+        deliveries.ack("t1", "a", t0).unwrap();
+        assert!(deliveries.ack("t2", "b", t0).is_err());
+
+        let later = t0 + chrono::Duration::seconds(ACK_TIMEOUT_SECS);
+        let again = deliveries.deliver("a", &ids(&["t1", "t2", "t3"]), 2, later);
+        assert_eq!(again.tasks, ids(&["t2", "t3"]));
+        assert_eq!(again.redelivered, ids(&["t2"]));
+        assert_eq!(deliveries.get("t2").unwrap().attempts, 2);
+        assert_eq!(again.next_cursor, 4);
+
+        // Finished tasks drop out.
+        deliveries.deliver("a", &ids(&["t3"]), 4, later);
+        assert!(deliveries.get("t1").is_none());
+    }
+}
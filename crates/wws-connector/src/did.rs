@@ -0,0 +1,359 @@
+//! DID documents.
+//!
+//! A `did:swarm` DID is derived from an Ed25519 key: `did:swarm:<PeerId>`
+//! for a connector's own identity, `did:swarm:<sha256 of the key>` for its
+//! local agents. A [`DidDocument`] is only accepted when it is signed by
+//! the key its DID was derived from, so whoever serves a document from the
+//! DHT cannot substitute keys of their own.
+//!
+//! The connector publishes a document for itself and for each local agent
+//! to the DHT every hour. Documents fetched for `swarm.resolve_did`, or for
+//! the senders of signed messages, are kept in a [`DidDirectory`]; a
+//! message whose envelope signature does not verify against its sender's
+//! document is rejected.
+//!
+//! Agents may offer services of their own, such as a review API, by passing
+//! [`ServiceDescriptor`]s to `swarm.register_agent`. They are listed in the
+//! agent's document next to its peer and name entries and announced in its
+//! keep-alives, and other agents look them up with
+//! `swarm.get_agent_endpoints`.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use wws_protocol::{DidDocument, ServiceEndpoint, SwarmMessage};
+
+/// How often this node's documents are published again.
+pub const DID_REPUBLISH_INTERVAL_SECS: u64 = 3600;
+/// Least time between DHT lookups of the same DID.
+pub const DID_LOOKUP_BACKOFF_SECS: u64 = 60;
+/// Most services one agent may offer.
+pub const MAX_AGENT_SERVICES: usize = 16;
+/// Longest service endpoint, in bytes.
+pub const MAX_SERVICE_ENDPOINT_LEN: usize = 512;
+/// Fragments of the entries the connector lists itself.
+const RESERVED_SERVICE_NAMES: [&str; 2] = ["p2p", "name"];
+
+/// Whether `did` is derived from `key`, in either `did:swarm` form.
+pub fn binds_key(did: &str, key: &VerifyingKey) -> bool {
+    if did == wws_protocol::crypto::derive_agent_id(key) {
+        return true;
+    }
+    wws_network::identity::ed25519::PublicKey::try_from_bytes(key.as_bytes())
+        .map(|public| wws_network::identity::PublicKey::from(public).to_peer_id())
+        .is_ok_and(|peer_id| did == format!("did:swarm:{}", peer_id))
+}
+
+/// The Ed25519 key a `did:swarm:<PeerId>` DID embeds. `None` for the
+/// hashed form, whose key is only known from its DID document.
+pub fn key_of(did: &str) -> Option<VerifyingKey> {
+    let peer_id: wws_network::PeerId = did.strip_prefix("did:swarm:")?.parse().ok()?;
+    let multihash = peer_id.as_ref();
+    if multihash.code() != 0 {
+        return None;
+    }
+    let public = wws_network::identity::PublicKey::try_decode_protobuf(multihash.digest()).ok()?;
+    VerifyingKey::from_bytes(&public.try_into_ed25519().ok()?.to_bytes()).ok()
+}
+
+/// Whether `document` is signed by the key its DID was derived from.
+pub fn is_authentic(document: &DidDocument) -> bool {
+    document.verify()
+        && document
+            .public_keys()
+            .next()
+            .is_some_and(|key| binds_key(&document.id, &key))
+}
+
+/// DID a message claims to come from: its `agent_id` param.
+pub fn sender(message: &SwarmMessage) -> Option<&str> {
+    message.params.get("agent_id").and_then(|v| v.as_str())
+}
+
+/// A service an agent offers, as passed to `swarm.register_agent`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceDescriptor {
+    /// Unique among the agent's services; becomes `<did>#<name>`.
+    pub name: String,
+    /// What the service is, e.g. `ReviewApi`.
+    #[serde(rename = "type")]
+    pub service_type: String,
+    /// Where to call it, e.g. `https://review.example.org/v1`.
+    pub endpoint: String,
+}
+
+/// The service entries of `did` for `descriptors`, or why they are invalid.
+pub fn agent_services(
+    did: &str,
+    descriptors: &[ServiceDescriptor],
+) -> Result<Vec<ServiceEndpoint>, String> {
+    if descriptors.len() > MAX_AGENT_SERVICES {
+        return Err(format!("At most {} services may be registered", MAX_AGENT_SERVICES));
+    }
+    let mut services: Vec<ServiceEndpoint> = Vec::with_capacity(descriptors.len());
+    for descriptor in descriptors {
+        let name = descriptor.name.trim();
+        let valid_name = !name.is_empty()
+            && name.len() <= 64
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_name || RESERVED_SERVICE_NAMES.contains(&name) {
+            return Err(format!("Invalid service name {:?}", descriptor.name));
+        }
+        if descriptor.service_type.trim().is_empty() {
+            return Err(format!("Service {:?} needs a type", name));
+        }
+        let endpoint = descriptor.endpoint.trim();
+        if endpoint.is_empty() || endpoint.len() > MAX_SERVICE_ENDPOINT_LEN {
+            return Err(format!(
+                "Service {:?} needs an endpoint of at most {} bytes",
+                name, MAX_SERVICE_ENDPOINT_LEN
+            ));
+        }
+        let id = format!("{}#{}", did, name);
+        if services.iter().any(|s| s.id == id) {
+            return Err(format!("Service {:?} is listed twice", name));
+        }
+        services.push(ServiceEndpoint {
+            id,
+            service_type: descriptor.service_type.trim().to_string(),
+            service_endpoint: endpoint.to_string(),
+        });
+    }
+    Ok(services)
+}
+
+/// Whether `service` is one `did` offers itself, rather than an entry the
+/// connector lists, and is well formed.
+pub fn is_agent_service(did: &str, service: &ServiceEndpoint) -> bool {
+    service
+        .id
+        .strip_prefix(did)
+        .and_then(|rest| rest.strip_prefix('#'))
+        .is_some_and(|name| !name.is_empty() && !RESERVED_SERVICE_NAMES.contains(&name))
+        && !service.service_endpoint.is_empty()
+        && service.service_endpoint.len() <= MAX_SERVICE_ENDPOINT_LEN
+}
+
+/// The services an agent last announced.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnouncedServices {
+    pub services: Vec<ServiceEndpoint>,
+    pub announced_at: DateTime<Utc>,
+}
+
+impl AnnouncedServices {
+    /// The well-formed services of `did` among `services`, up to the limit.
+    pub fn from_announcement(did: &str, services: &[ServiceEndpoint], now: DateTime<Utc>) -> Self {
+        Self {
+            services: services
+                .iter()
+                .filter(|s| is_agent_service(did, s))
+                .take(MAX_AGENT_SERVICES)
+                .cloned()
+                .collect(),
+            announced_at: now,
+        }
+    }
+}
+
+/// This connector's document, listing its peer, its wws:// name if it has
+/// one, the services its agent offers, and its capabilities.
+pub fn own_document(
+    did: &str,
+    key: &ed25519_dalek::SigningKey,
+    peer_id: &wws_network::PeerId,
+    wws_name: Option<&str>,
+    services: &[ServiceEndpoint],
+    capabilities: Vec<String>,
+) -> DidDocument {
+    let mut service = vec![ServiceEndpoint {
+        id: format!("{}#p2p", did),
+        service_type: "WwsPeer".to_string(),
+        service_endpoint: format!("/p2p/{}", peer_id),
+    }];
+    if let Some(name) = wws_name {
+        service.push(ServiceEndpoint {
+            id: format!("{}#name", did),
+            service_type: "WwsName".to_string(),
+            service_endpoint: format!("wws:{}", name),
+        });
+    }
+    service.extend(services.iter().cloned());
+    DidDocument::new(did, key, service, capabilities)
+}
+
+/// Resolved DID documents.
+#[derive(Debug, Clone, Default)]
+pub struct DidDirectory {
+    documents: HashMap<String, DidDocument>,
+    /// When each DID was last looked up in the DHT.
+    lookups: HashMap<String, Instant>,
+}
+
+impl DidDirectory {
+    /// Keep `document` if it is authentic and newer than the one held.
+    /// Returns whether it was kept.
+    pub fn insert(&mut self, document: DidDocument) -> bool {
+        if !is_authentic(&document) {
+            return false;
+        }
+        if self
+            .documents
+            .get(&document.id)
+            .is_some_and(|held| held.updated >= document.updated)
+        {
+            return false;
+        }
+        self.documents.insert(document.id.clone(), document);
+        true
+    }
+
+    pub fn get(&self, did: &str) -> Option<&DidDocument> {
+        self.documents.get(did)
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Whether a DHT lookup of `did` is due, noting one as started if so.
+    pub fn start_lookup(&mut self, did: &str, now: Instant) -> bool {
+        let backoff = Duration::from_secs(DID_LOOKUP_BACKOFF_SECS);
+        if self
+            .lookups
+            .get(did)
+            .is_some_and(|last| now.duration_since(*last) < backoff)
+        {
+            return false;
+        }
+        self.lookups.insert(did.to_string(), now);
+        true
+    }
+
+    /// The key `did` signs with, from its document, when that key is the
+    /// one the DID is derived from.
+    pub fn key(&self, did: &str) -> Option<VerifyingKey> {
+        let document = self.documents.get(did)?;
+        let method = document.verification_method.first()?;
+        let bytes: [u8; 32] = hex::decode(&method.public_key_hex).ok()?.try_into().ok()?;
+        VerifyingKey::from_bytes(&bytes).ok().filter(|key| binds_key(did, key))
+    }
+
+    /// Whether the envelope signature of `message` verifies against the
+    /// document of `did`, or `None` while that document is unknown.
+    pub fn check_signature(&self, did: &str, message: &SwarmMessage) -> Option<bool> {
+        let document = self.documents.get(did)?;
+        let payload = SwarmMessage::signing_payload(&message.method, &message.params);
+        Some(document.verify_signature(&payload, &message.signature))
+    }
+}
+
+/// Publish `document` to the DHT under its DID's key.
+pub async fn publish(network_handle: &wws_network::SwarmHandle, document: &DidDocument) {
+    if let Ok(value) = serde_json::to_vec(document) {
+        if let Err(e) = network_handle
+            .put_dht_record(DidDocument::dht_key(&document.id), value)
+            .await
+        {
+            tracing::debug!(error = %e, did = %document.id, "Failed to publish DID document");
+        }
+    }
+}
+
+/// Start a DHT lookup of the document of `did`; it arrives as a
+/// `NetworkEvent::DhtRecordFound`.
+pub async fn lookup(network_handle: &wws_network::SwarmHandle, did: &str) {
+    if let Err(e) = network_handle.get_dht_record(DidDocument::dht_key(did)).await {
+        tracing::debug!(error = %e, did = %did, "Failed to start DID document lookup");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_are_accepted_only_from_the_key_behind_the_did() {
+        let node_key = wws_protocol::crypto::generate_keypair();
+        let peer_id = wws_network::Keypair::ed25519_from_bytes(node_key.to_bytes())
+            .unwrap()
+            .public()
+            .to_peer_id();
+        let node_did = format!("did:swarm:{}", peer_id);
+        let agent_key = wws_protocol::crypto::generate_keypair();
+        let agent_did = wws_protocol::crypto::derive_agent_id(&agent_key.verifying_key());
+        assert_eq!(key_of(&node_did), Some(node_key.verifying_key()));
+        assert_eq!(key_of(&agent_did), None);
+
+        let mut directory = DidDirectory::default();
+        let services = agent_services(
+            &node_did,
+            &[ServiceDescriptor {
+                name: "review".into(),
+                service_type: "ReviewApi".into(),
+                endpoint: " https://review.example.org/v1 ".into(),
+            }],
+        )
+        .unwrap();
+        let node_doc = own_document(&node_did, &node_key, &peer_id, Some("atlas"), &services, vec![]);
+        assert_eq!(node_doc.service[1].service_endpoint, "wws:atlas");
+        assert_eq!(node_doc.service[2].id, format!("{}#review", node_did));
+        assert_eq!(node_doc.service[2].service_endpoint, "https://review.example.org/v1");
+        assert!(!is_agent_service(&node_did, &node_doc.service[0]));
+        assert!(is_agent_service(&node_did, &node_doc.service[2]));
+        assert!(!is_agent_service(&agent_did, &node_doc.service[2]));
+        for bad in [("p2p", "Peer", "/p2p/x"), ("bad name", "Api", "https://x"), ("api", "", "https://x"), ("api", "Api", "")] {
+            let descriptor = ServiceDescriptor {
+                name: bad.0.into(),
+                service_type: bad.1.into(),
+                endpoint: bad.2.into(),
+            };
+            assert!(agent_services(&node_did, &[descriptor]).is_err(), "{:?} should be rejected", bad);
+        }
+        assert!(directory.insert(node_doc.clone()));
+        assert!(directory.insert(DidDocument::new(&agent_did, &agent_key, vec![], vec![])));
+
+        // Self-signed, but by a key the DID does not come from.
+        let impostor = DidDocument::new(&agent_did, &node_key, vec![], vec!["admin".into()]);
+        assert!(impostor.verify());
+        assert!(!directory.insert(impostor));
+        assert!(directory.get(&agent_did).unwrap().capabilities.is_empty());
+
+        // An older copy served late does not replace the current one.
+        let mut stale = node_doc.clone();
+        stale.updated -= chrono::Duration::hours(1);
+        stale.sign(&node_key);
+        assert!(!directory.insert(stale));
+        assert_eq!(directory.len(), 2);
+
+        let params = serde_json::json!({"task_id": "t1", "agent_id": agent_did});
+        let payload = SwarmMessage::signing_payload("task.submit_result", &params);
+        let signed = |key: &ed25519_dalek::SigningKey| {
+            let signature = wws_protocol::crypto::sign_message(key, &payload);
+            let signature_hex = signature.to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+            SwarmMessage::new("task.submit_result", params.clone(), signature_hex)
+        };
+        let genuine = signed(&agent_key);
+        assert_eq!(sender(&genuine), Some(agent_did.as_str()));
+        assert_eq!(directory.check_signature(&agent_did, &genuine), Some(true));
+        assert_eq!(directory.check_signature(&agent_did, &signed(&node_key)), Some(false));
+        assert_eq!(directory.check_signature("did:swarm:unknown", &genuine), None);
+
+        let now = Instant::now();
+        assert!(directory.start_lookup("did:swarm:unknown", now));
+        assert!(!directory.start_lookup("did:swarm:unknown", now + Duration::from_secs(5)));
+        assert!(directory.start_lookup(
+            "did:swarm:unknown",
+            now + Duration::from_secs(DID_LOOKUP_BACKOFF_SECS)
+        ));
+    }
+}
@@ -0,0 +1,385 @@
+//! Built-in echo agent for watching the consensus pipeline without an LLM.
+//!
+//! With `[echo_agent] enabled` (or `--echo-agent`), the connector drives its
+//! own agent, plus `local_agents` extra local agents, through the same
+//! JSON-RPC methods an external agent calls: it polls for tasks, proposes a
+//! trivial two-part plan for every task it may decompose, votes once the
+//! plans are revealed, and answers every subtask it is assigned with a
+//! canned `echo:` result. A single connector started this way takes an
+//! injected task from proposal to a completed, aggregated result.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::RwLock;
+
+use wws_protocol::{ErrorCode, SwarmResponse, Task};
+
+use crate::config::EchoAgentConfig;
+use crate::connector::ConnectorState;
+use crate::rpc_server::process_request;
+use crate::tui::LogCategory;
+
+/// What an echo agent does next with a task it was offered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EchoAction {
+    /// Propose a plan splitting the task in two.
+    Propose,
+    /// Rank the revealed plans once voting opens.
+    Vote,
+    /// Submit the canned result.
+    Submit,
+    /// Nothing to do until the task moves on.
+    Wait,
+}
+
+/// Which steps an echo agent has already taken, by task ID.
+#[derive(Debug, Default)]
+pub struct EchoProgress {
+    proposed: HashSet<String>,
+    voted: HashSet<String>,
+    submitted: HashSet<String>,
+}
+
+/// Choose the next step for `task` as seen by agent `me`.
+///
+/// Tasks without subtasks are decomposed when they are roots or still
+/// unassigned, and executed when they are subtasks assigned to `me`. Tasks
+/// that already have subtasks are aggregated by the connector itself.
+pub fn next_action(task: &Task, me: &str, progress: &EchoProgress) -> EchoAction {
+    if !task.subtasks.is_empty() {
+        return EchoAction::Wait;
+    }
+    let assigned_to_me = task.assigned_to.as_ref().is_some_and(|a| a.as_str() == me);
+    let decomposable = task.parent_task_id.is_none() || task.assigned_to.is_none();
+    if decomposable {
+        if !progress.proposed.contains(&task.task_id) {
+            EchoAction::Propose
+        } else if !progress.voted.contains(&task.task_id) {
+            EchoAction::Vote
+        } else {
+            EchoAction::Wait
+        }
+    } else if assigned_to_me && !progress.submitted.contains(&task.task_id) {
+        EchoAction::Submit
+    } else {
+        EchoAction::Wait
+    }
+}
+
+/// `swarm.propose_plan` params splitting `task` into two parts.
+pub fn echo_plan(task: &Task, proposer: &str) -> serde_json::Value {
+    let subtasks: Vec<serde_json::Value> = (1..=2)
+        .map(|index| {
+            serde_json::json!({
+                "index": index,
+                "description": format!("Part {} of: {}", index, task.description),
+                "required_capabilities": [],
+                "estimated_complexity": 0.1,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "plan_id": uuid::Uuid::new_v4().to_string(),
+        "task_id": task.task_id,
+        "proposer": proposer,
+        "epoch": task.epoch,
+        "subtasks": subtasks,
+        "rationale": "Echo agent: split the task in two halves.",
+        "estimated_parallelism": 2.0,
+    })
+}
+
+/// `swarm.submit_result` params answering `task` with its own description.
+pub fn echo_result(task: &Task) -> serde_json::Value {
+    let content = format!("echo: {}", task.description);
+    serde_json::json!({
+        "task_id": task.task_id,
+        "artifact": {
+            "task_id": task.task_id,
+            "content_type": "text/plain",
+            "size_bytes": content.len(),
+            "content": content,
+        },
+    })
+}
+
+/// One agent the echo loop acts for.
+struct EchoActor {
+    /// DID passed as `local_agent`; `None` for the connector's own agent.
+    local_agent: Option<String>,
+    agent_id: String,
+    progress: EchoProgress,
+}
+
+/// Runs the echo loop against a connector's shared state.
+pub struct EchoAgent {
+    config: EchoAgentConfig,
+    state: Arc<RwLock<ConnectorState>>,
+    network_handle: wws_network::SwarmHandle,
+    next_request_id: u64,
+}
+
+impl EchoAgent {
+    pub fn new(
+        config: EchoAgentConfig,
+        state: Arc<RwLock<ConnectorState>>,
+        network_handle: wws_network::SwarmHandle,
+    ) -> Self {
+        Self {
+            config,
+            state,
+            network_handle,
+            next_request_id: 0,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let own_id = self.state.read().await.agent_id.to_string();
+        let mut actors = vec![EchoActor {
+            local_agent: None,
+            agent_id: own_id,
+            progress: EchoProgress::default(),
+        }];
+        for n in 1..=self.config.local_agents {
+            let name = format!("echo-{}", n);
+            let result = self
+                .call("swarm.register_local_agent", serde_json::json!({ "name": name }))
+                .await
+                .map_err(|e| anyhow::anyhow!("Echo agent {} could not register: {}", name, e.1))?;
+            let agent_id = result
+                .get("agent_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            actors.push(EchoActor {
+                local_agent: Some(agent_id.clone()),
+                agent_id,
+                progress: EchoProgress::default(),
+            });
+        }
+        self.log(
+            LogCategory::System,
+            format!("Echo agent started for {} agent(s)", actors.len()),
+        )
+        .await;
+
+        let mut poll = tokio::time::interval(Duration::from_secs(
+            self.config.poll_interval_secs.max(1),
+        ));
+        loop {
+            poll.tick().await;
+            for actor in actors.iter_mut() {
+                self.step(actor).await;
+            }
+        }
+    }
+
+    /// Poll once for `actor` and take the next step on each pending task.
+    async fn step(&mut self, actor: &mut EchoActor) {
+        let pending = match self
+            .call_as(actor, "swarm.receive_task", serde_json::json!({}))
+            .await
+        {
+            Ok(result) => result
+                .get("pending_tasks")
+                .and_then(|v| v.as_array())
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(|id| id.as_str().map(str::to_string))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default(),
+            Err((_, message)) => {
+                tracing::debug!(agent = %actor.agent_id, error = %message, "Echo agent poll failed");
+                return;
+            }
+        };
+
+        for task_id in pending {
+            let task: Task = match self
+                .call_as(actor, "swarm.get_task", serde_json::json!({ "task_id": task_id }))
+                .await
+                .ok()
+                .and_then(|result| result.get("task").cloned())
+                .and_then(|task| serde_json::from_value(task).ok())
+            {
+                Some(task) => task,
+                None => continue,
+            };
+
+            match next_action(&task, &actor.agent_id, &actor.progress) {
+                EchoAction::Propose => {
+                    let plan = echo_plan(&task, &actor.agent_id);
+                    match self.call_as(actor, "swarm.propose_plan", plan).await {
+                        // Frozen for an epoch transition; retried on the next poll.
+                        Err((code, _)) if code == ErrorCode::ProposalsFrozen.code() => continue,
+                        Err((_, message)) => {
+                            self.log(
+                                LogCategory::Error,
+                                format!("Echo agent could not propose for {}: {}", task_id, message),
+                            )
+                            .await;
+                        }
+                        Ok(_) => {}
+                    }
+                    actor.progress.proposed.insert(task_id);
+                }
+                EchoAction::Vote => {
+                    let Some(plan_ids) = self.votable_plans(actor, &task_id).await else {
+                        continue;
+                    };
+                    let params = serde_json::json!({ "task_id": task_id, "rankings": plan_ids });
+                    if let Err((_, message)) = self.call_as(actor, "swarm.submit_vote", params).await {
+                        self.log(
+                            LogCategory::Error,
+                            format!("Echo agent could not vote on {}: {}", task_id, message),
+                        )
+                        .await;
+                    }
+                    actor.progress.voted.insert(task_id);
+                }
+                EchoAction::Submit => {
+                    let params = echo_result(&task);
+                    if let Err((_, message)) = self.call_as(actor, "swarm.submit_result", params).await {
+                        self.log(
+                            LogCategory::Error,
+                            format!("Echo agent could not submit {}: {}", task_id, message),
+                        )
+                        .await;
+                    }
+                    actor.progress.submitted.insert(task_id);
+                }
+                EchoAction::Wait => {}
+            }
+        }
+    }
+
+    /// Plan IDs to rank for `task_id`, once its RFP is ready for voting.
+    async fn votable_plans(&mut self, actor: &EchoActor, task_id: &str) -> Option<Vec<String>> {
+        let result = self
+            .call_as(actor, "swarm.get_voting_state", serde_json::json!({ "task_id": task_id }))
+            .await
+            .ok()?;
+        let rfp = result
+            .get("rfp_coordinators")?
+            .as_array()?
+            .iter()
+            .find(|rfp| rfp.get("task_id").and_then(|v| v.as_str()) == Some(task_id))?;
+        if rfp.get("phase").and_then(|v| v.as_str()) != Some("ReadyForVoting") {
+            return None;
+        }
+        let mut plan_ids: Vec<String> = rfp
+            .get("plan_ids")?
+            .as_array()?
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect();
+        plan_ids.sort();
+        (!plan_ids.is_empty()).then_some(plan_ids)
+    }
+
+    async fn call_as(
+        &mut self,
+        actor: &EchoActor,
+        method: &str,
+        mut params: serde_json::Value,
+    ) -> Result<serde_json::Value, (i32, String)> {
+        if let (Some(did), Some(fields)) = (&actor.local_agent, params.as_object_mut()) {
+            fields.insert("local_agent".to_string(), serde_json::json!(did));
+        }
+        self.call(method, params).await
+    }
+
+    /// Send one JSON-RPC request through the connector's own dispatcher.
+    async fn call(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, (i32, String)> {
+        self.next_request_id += 1;
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": format!("echo-{}", self.next_request_id),
+            "method": method,
+            "params": params,
+            "signature": "",
+        });
+        let response: SwarmResponse =
+            process_request(&request.to_string(), &self.state, &self.network_handle).await;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err((error.code, error.message)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Ok(serde_json::Value::Null),
+        }
+    }
+
+    async fn log(&self, category: LogCategory, message: String) {
+        self.state.write().await.push_log(category, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::AgentId;
+
+    const ME: &str = "did:swarm:echo";
+
+    fn task(parent: Option<&str>, assigned_to: Option<&str>) -> Task {
+        let mut task = Task::new("Summarize the report".to_string(), 1, 3);
+        task.parent_task_id = parent.map(str::to_string);
+        task.assigned_to = assigned_to.map(|a| AgentId::new(a.to_string()));
+        task
+    }
+
+    #[test]
+    fn decomposes_roots_then_votes_once() {
+        let root = task(None, None);
+        let mut progress = EchoProgress::default();
+        assert_eq!(next_action(&root, ME, &progress), EchoAction::Propose);
+
+        progress.proposed.insert(root.task_id.clone());
+        assert_eq!(next_action(&root, ME, &progress), EchoAction::Vote);
+
+        progress.voted.insert(root.task_id.clone());
+        assert_eq!(next_action(&root, ME, &progress), EchoAction::Wait);
+
+        let mut decomposed = root.clone();
+        decomposed.subtasks.push("sub-1".to_string());
+        assert_eq!(
+            next_action(&decomposed, ME, &EchoProgress::default()),
+            EchoAction::Wait
+        );
+    }
+
+    #[test]
+    fn executes_only_its_own_subtasks_once() {
+        let mine = task(Some("root"), Some(ME));
+        let mut progress = EchoProgress::default();
+        assert_eq!(next_action(&mine, ME, &progress), EchoAction::Submit);
+        progress.submitted.insert(mine.task_id.clone());
+        assert_eq!(next_action(&mine, ME, &progress), EchoAction::Wait);
+
+        let theirs = task(Some("root"), Some("did:swarm:other"));
+        assert_eq!(
+            next_action(&theirs, ME, &EchoProgress::default()),
+            EchoAction::Wait
+        );
+    }
+
+    #[test]
+    fn canned_plan_and_result_are_valid_submissions() {
+        let root = task(None, None);
+        let plan: wws_protocol::Plan = serde_json::from_value(echo_plan(&root, ME)).unwrap();
+        assert_eq!(plan.task_id, root.task_id);
+        assert_eq!(plan.subtasks.len(), 2);
+        assert_eq!(plan.subtasks[1].description, "Part 2 of: Summarize the report");
+
+        let result: wws_protocol::messages::ResultSubmissionParams =
+            serde_json::from_value(echo_result(&root)).unwrap();
+        assert_eq!(result.artifact.content, "echo: Summarize the report");
+        assert_eq!(result.artifact.size_bytes, result.artifact.content.len() as u64);
+    }
+}
@@ -0,0 +1,198 @@
+//! This connector's part in Tier-1 elections.
+//!
+//! When an epoch election starts, the connector scores itself — reputation
+//! from its own task record, uptime within the epoch and a configured
+//! proof-of-compute figure — and, if that meets the election's candidacy
+//! requirements, broadcasts `election.candidacy` with its capabilities as
+//! its platform. After the candidacy window it ranks the candidates it has
+//! heard from according to its [`ElectionVoteStrategy`] and broadcasts
+//! `election.vote`.
+
+use serde::{Deserialize, Serialize};
+use wws_hierarchy::elections::Candidate;
+use wws_protocol::{AgentId, NodeScore};
+
+use crate::connector::AgentActivity;
+
+/// How this connector ranks Tier-1 candidates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElectionVoteStrategy {
+    /// Highest composite score first.
+    #[default]
+    Score,
+    /// Highest reputation first, then uptime.
+    Reputation,
+    /// Most capabilities in common with this agent first, then score.
+    CapabilityMatch,
+}
+
+/// Election participation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElectionConfig {
+    /// Announce candidacy when eligible.
+    #[serde(default = "default_stand_for_election")]
+    pub stand_for_election: bool,
+    /// Self-reported proof-of-compute benchmark score, in `[0, 1]`.
+    #[serde(default = "default_proof_of_compute")]
+    pub proof_of_compute: f64,
+    /// Seconds after the election starts before this connector votes, so
+    /// candidacies can arrive first.
+    #[serde(default = "default_vote_delay_secs")]
+    pub vote_delay_secs: u64,
+    #[serde(default)]
+    pub vote_strategy: ElectionVoteStrategy,
+}
+
+impl Default for ElectionConfig {
+    fn default() -> Self {
+        Self {
+            stand_for_election: default_stand_for_election(),
+            proof_of_compute: default_proof_of_compute(),
+            vote_delay_secs: default_vote_delay_secs(),
+            vote_strategy: ElectionVoteStrategy::default(),
+        }
+    }
+}
+
+fn default_stand_for_election() -> bool {
+    true
+}
+fn default_proof_of_compute() -> f64 {
+    0.5
+}
+fn default_vote_delay_secs() -> u64 {
+    5
+}
+
+/// Success rate of an agent's results: completed tasks and passed
+/// verifications against failed verifications and late results, starting
+/// from 0.5 for an agent without a record.
+pub fn reputation(activity: Option<&AgentActivity>) -> f64 {
+    let Some(a) = activity else {
+        return 0.5;
+    };
+    let good = (a.tasks_processed_count + a.verifications_passed_count) as f64;
+    let bad = (a.verifications_failed_count + a.results_late_count) as f64;
+    (good + 1.0) / (good + bad + 2.0)
+}
+
+/// This node's election score.
+pub fn own_score(
+    agent_id: &AgentId,
+    config: &ElectionConfig,
+    activity: Option<&AgentActivity>,
+    up_secs: i64,
+    epoch_duration_secs: u64,
+) -> NodeScore {
+    let uptime = if epoch_duration_secs == 0 {
+        1.0
+    } else {
+        (up_secs.max(0) as f64 / epoch_duration_secs as f64).min(1.0)
+    };
+    NodeScore {
+        agent_id: agent_id.clone(),
+        proof_of_compute: config.proof_of_compute.clamp(0.0, 1.0),
+        reputation: reputation(activity),
+        uptime,
+        stake: None,
+    }
+}
+
+/// Candidates in the order this connector votes for them. Ties are broken
+/// by agent ID so that voters using the same strategy agree.
+pub fn rank_candidates<'a>(
+    strategy: ElectionVoteStrategy,
+    candidates: impl IntoIterator<Item = &'a Candidate>,
+    own_capabilities: &[String],
+) -> Vec<AgentId> {
+    let shared = |c: &Candidate| {
+        c.capabilities
+            .iter()
+            .filter(|cap| own_capabilities.contains(cap))
+            .count()
+    };
+    let mut ranked: Vec<&Candidate> = candidates.into_iter().collect();
+    ranked.sort_by(|a, b| {
+        let order = match strategy {
+            ElectionVoteStrategy::Score => b.composite.total_cmp(&a.composite),
+            ElectionVoteStrategy::Reputation => b
+                .score
+                .reputation
+                .total_cmp(&a.score.reputation)
+                .then(b.score.uptime.total_cmp(&a.score.uptime)),
+            ElectionVoteStrategy::CapabilityMatch => shared(b)
+                .cmp(&shared(a))
+                .then(b.composite.total_cmp(&a.composite)),
+        };
+        order.then_with(|| a.agent_id.as_str().cmp(b.agent_id.as_str()))
+    });
+    ranked.into_iter().map(|c| c.agent_id.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, reputation: f64, uptime: f64, capabilities: &[&str]) -> Candidate {
+        let score = NodeScore {
+            agent_id: AgentId::new(id.to_string()),
+            proof_of_compute: 0.5,
+            reputation,
+            uptime,
+            stake: None,
+        };
+        Candidate {
+            agent_id: score.agent_id.clone(),
+            composite: score.composite_score(),
+            score,
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    fn ids(ranked: Vec<AgentId>) -> Vec<String> {
+        ranked.into_iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn candidates_are_ranked_by_the_configured_strategy() {
+        let candidates = [
+            candidate("a", 0.6, 1.0, &["web_search"]),
+            candidate("b", 0.9, 0.2, &[]),
+            candidate("c", 0.6, 1.0, &["web_search", "gpt-4"]),
+        ];
+        let own = vec!["gpt-4".to_string(), "web_search".to_string()];
+        assert_eq!(
+            ids(rank_candidates(ElectionVoteStrategy::Score, &candidates, &own)),
+            ["a", "c", "b"]
+        );
+        assert_eq!(
+            ids(rank_candidates(ElectionVoteStrategy::Reputation, &candidates, &own)),
+            ["b", "a", "c"]
+        );
+        assert_eq!(
+            ids(rank_candidates(ElectionVoteStrategy::CapabilityMatch, &candidates, &own)),
+            ["c", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn newcomers_start_from_an_even_reputation() {
+        assert_eq!(reputation(None), 0.5);
+        let activity = AgentActivity {
+            tasks_processed_count: 7,
+            verifications_failed_count: 1,
+            ..AgentActivity::default()
+        };
+        assert_eq!(reputation(Some(&activity)), 0.8);
+
+        let score = own_score(
+            &AgentId::new("me".to_string()),
+            &ElectionConfig::default(),
+            None,
+            1_800,
+            3_600,
+        );
+        assert_eq!(score.uptime, 0.5);
+    }
+}
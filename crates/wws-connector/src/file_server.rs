@@ -80,6 +80,7 @@ impl FileServer {
             .route("/MESSAGING.md", get(messaging_md))
             .route("/agent-onboarding.json", get(onboarding))
             .route("/api/health", get(api_health))
+            .route("/api/health/swarm", get(api_swarm_health))
             .route("/api/auth-status", get(api_auth_status))
             .route("/api/identity", get(api_identity))
             .route("/api/reputation", get(api_reputation))
@@ -88,6 +89,7 @@ impl FileServer {
             .route("/api/names/:name/renew", put(api_names_renew))
             .route("/api/names/:name", delete(api_names_release))
             .route("/api/network", get(api_network))
+            .route("/api/metrics/bandwidth", get(api_bandwidth))
             .route("/api/peers", get(api_peers))
             .route("/api/directory", get(api_directory))
             .route("/api/keys", get(api_keys))
@@ -181,6 +183,7 @@ async fn onboarding() -> Json<serde_json::Value> {
             "swarm.get_status",
             "swarm.receive_task",
             "swarm.get_task",
+            "swarm.ack_task",
             "swarm.get_task_timeline",
             "swarm.register_agent",
             "swarm.propose_plan",
@@ -202,6 +205,26 @@ async fn api_health() -> Json<serde_json::Value> {
     Json(serde_json::json!({"ok": true, "service": "wws-web"}))
 }
 
+/// Liveness probe: the `swarm.health` report, with 503 when the node is
+/// isolated or the network layer does not answer.
+async fn api_swarm_health(State(web): State<WebState>) -> Response {
+    match crate::health::check(&web.state, &web.network_handle).await {
+        Ok(report) => {
+            let code = if report.status == crate::health::HealthStatus::Isolated {
+                StatusCode::SERVICE_UNAVAILABLE
+            } else {
+                StatusCode::OK
+            };
+            (code, Json(report)).into_response()
+        }
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"status": "unavailable", "error": e})),
+        )
+            .into_response(),
+    }
+}
+
 async fn api_auth_status() -> Json<serde_json::Value> {
     let token_required = std::env::var("WWS_WEB_TOKEN")
         .ok()
@@ -584,7 +607,7 @@ async fn api_task_timeline(
         .cloned()
         .collect::<Vec<_>>();
 
-    let descendants = collect_task_descendants(&task_id, &s.task_details)
+    let descendants = collect_task_descendants(&task_id, s.task_details.as_ref())
         .into_iter()
         .map(|t| {
             let result = s.task_results.get(&t.task_id).cloned();
@@ -662,6 +685,8 @@ async fn api_agents(State(web): State<WebState>) -> Json<serde_json::Value> {
                 "plans_proposed_count": s.agent_activity.get(&id).map(|a| a.plans_proposed_count).unwrap_or(0),
                 "plans_revealed_count": s.agent_activity.get(&id).map(|a| a.plans_revealed_count).unwrap_or(0),
                 "votes_cast_count": s.agent_activity.get(&id).map(|a| a.votes_cast_count).unwrap_or(0),
+                "on_time_pct": s.agent_activity.get(&id).and_then(|a| a.on_time_pct()),
+                "avg_lateness_secs": s.agent_activity.get(&id).and_then(|a| a.average_lateness_secs()),
                 "is_self": id == s.agent_id.to_string(),
                 "connected": seen_secs.map(|v| v <= 60).unwrap_or(false),
                 "loop_active": last_task_poll_secs.map(|v| v <= 120).unwrap_or(false),
@@ -675,7 +700,7 @@ async fn api_agents(State(web): State<WebState>) -> Json<serde_json::Value> {
 
 fn collect_task_descendants(
     root: &str,
-    details: &HashMap<String, wws_protocol::Task>,
+    details: &dyn crate::storage::TaskStore,
 ) -> Vec<wws_protocol::Task> {
     let mut out = Vec::new();
     let mut frontier = vec![root.to_string()];
@@ -923,6 +948,7 @@ async fn api_holon_detail(
             "subtask_assignments": h.subtask_assignments.iter()
                 .map(|(k, v)| (k.clone(), v.to_string()))
                 .collect::<std::collections::HashMap<_, _>>(),
+            "child_artifacts": h.child_artifacts,
             "status": format!("{:?}", h.status),
             "created_at": h.created_at,
         })).into_response(),
@@ -937,10 +963,16 @@ async fn api_task_deliberation(
     let state = s.state.read().await;
     let messages: Vec<serde_json::Value> = state.deliberation_messages
         .get(&task_id)
-        .map(|msgs| msgs.iter().map(|m| serde_json::json!({
+        .map(|msgs| {
+            let mut msgs: Vec<_> = msgs.iter().collect();
+            msgs.sort_by_key(|m| m.hlc);
+            msgs
+        })
+        .map(|msgs| msgs.into_iter().map(|m| serde_json::json!({
             "id": m.id,
             "task_id": m.task_id,
             "timestamp": m.timestamp,
+            "hlc": m.hlc,
             "speaker": m.speaker.to_string(),
             "round": m.round,
             "message_type": format!("{:?}", m.message_type),
@@ -1016,14 +1048,16 @@ async fn api_reputation(State(web): State<WebState>) -> Json<serde_json::Value>
     let my_id = s.agent_id.to_string();
     let activity = s.agent_activity.get(&my_id);
     let tasks_done = activity.map(|a| a.tasks_processed_count).unwrap_or(0);
-    let score = 10u64 + tasks_done * 5;
+    let verified = activity.map(|a| a.verifications_passed_count).unwrap_or(0);
+    let disputes_lost = activity.map(|a| a.verifications_failed_count).unwrap_or(0);
+    let score = s.reputation_of(&my_id);
     let rep_tier = rep_tier_for_score(score);
     Json(serde_json::json!({
         "score": score,
         "tier": rep_tier,
         "next_tier_at": rep_next_threshold(score),
-        "positive_total": tasks_done,
-        "negative_total": 0,
+        "positive_total": tasks_done + verified,
+        "negative_total": disputes_lost,
         "decay": 0.0,
     }))
 }
@@ -1155,6 +1189,18 @@ async fn api_network(State(web): State<WebState>) -> Json<serde_json::Value> {
     }))
 }
 
+/// Bytes and messages sent and received per topic and per peer.
+async fn api_bandwidth(State(web): State<WebState>) -> Response {
+    match web.network_handle.bandwidth().await {
+        Ok(bandwidth) => Json(bandwidth).into_response(),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
 async fn api_peers(State(web): State<WebState>) -> Json<serde_json::Value> {
     let s = web.state.read().await;
     let now = chrono::Utc::now();
@@ -1203,7 +1249,7 @@ async fn api_directory(
             let online = seen_secs.map(|v| v <= 60).unwrap_or(id == my_id);
             let activity = s.agent_activity.get(&id);
             let tasks_done = activity.map(|a| a.tasks_processed_count).unwrap_or(0);
-            let score = 10u64 + tasks_done * 5;
+            let score = s.reputation_of(&id);
             serde_json::json!({
                 "did": id,
                 "name": name,
@@ -0,0 +1,280 @@
+//! gRPC interface to the connector API.
+//!
+//! An optional alternative to the JSON-RPC server for deployments that
+//! standardize on gRPC. The service is defined in
+//! `proto/wws/connector/v1/connector.proto` (package `wws.connector.v1`):
+//! one rpc per JSON-RPC method, named after it in PascalCase, plus `Call`
+//! for any method by its JSON-RPC name. Params and results are the
+//! JSON-RPC method's, as JSON text.
+//!
+//! Every call goes through the same client check and dispatch as a
+//! JSON-RPC request, so both interfaces answer alike. Clients authenticate
+//! with `authorization: Bearer <token>` metadata. `swarm.get_logs` returns
+//! its entries but does not follow the log.
+
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+use tonic::{Request, Response, Status};
+use wws_protocol::{SwarmResponse, JSONRPC_VERSION};
+
+use crate::auth::{RpcAuth, RpcClientConfig};
+use crate::connector::ConnectorState;
+use crate::rpc_server::{authorize, process_request};
+
+/// Code generated from the connector's protobuf definitions.
+pub mod proto {
+    tonic::include_proto!("wws.connector.v1");
+}
+
+use proto::connector_server::{Connector, ConnectorServer};
+
+/// The gRPC server.
+pub struct GrpcServer {
+    bind_addr: String,
+    service: GrpcService,
+}
+
+impl GrpcServer {
+    /// Create a new gRPC server.
+    pub fn new(
+        bind_addr: String,
+        state: Arc<RwLock<ConnectorState>>,
+        network_handle: wws_network::SwarmHandle,
+    ) -> Self {
+        Self {
+            bind_addr,
+            service: GrpcService {
+                state,
+                network_handle,
+                auth: Arc::new(RpcAuth::default()),
+            },
+        }
+    }
+
+    /// Require calls to authenticate as one of `clients`.
+    pub fn with_clients(mut self, clients: Vec<RpcClientConfig>) -> Self {
+        self.service.auth = Arc::new(RpcAuth::new(clients));
+        self
+    }
+
+    /// Start the gRPC server.
+    pub async fn run(self) -> Result<(), anyhow::Error> {
+        let addr = self.bind_addr.parse()?;
+        tracing::info!(addr = %self.bind_addr, "gRPC server listening");
+        tonic::transport::Server::builder()
+            .add_service(ConnectorServer::new(self.service))
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+}
+
+/// The `Connector` service, answering through the JSON-RPC handlers.
+#[derive(Clone)]
+pub struct GrpcService {
+    state: Arc<RwLock<ConnectorState>>,
+    network_handle: wws_network::SwarmHandle,
+    auth: Arc<RpcAuth>,
+}
+
+impl GrpcService {
+    /// Answer `method` with `params` as the JSON-RPC server would.
+    async fn dispatch<T>(
+        &self,
+        method: &str,
+        params: &str,
+        request: &Request<T>,
+    ) -> Result<Response<proto::Response>, Status> {
+        let params: serde_json::Value = if params.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(params)
+                .map_err(|e| Status::invalid_argument(format!("params are not JSON: {}", e)))?
+        };
+        let mut line = serde_json::json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "method": method,
+            "params": params,
+            "signature": "",
+        });
+        if let Some(token) = bearer_token(request) {
+            line["auth"] = serde_json::json!(token);
+        }
+        let line = line.to_string();
+        let peer = request
+            .remote_addr()
+            .unwrap_or_else(|| std::net::SocketAddr::from(([0, 0, 0, 0], 0)));
+        let response = match authorize(&line, &self.auth, &self.state, peer).await {
+            Ok(Some(rewritten)) => process_request(&rewritten, &self.state, &self.network_handle).await,
+            Ok(None) => process_request(&line, &self.state, &self.network_handle).await,
+            Err(denied) => denied,
+        };
+        Ok(Response::new(to_proto(response)))
+    }
+}
+
+/// The token of `authorization: Bearer <token>` metadata.
+fn bearer_token<T>(request: &Request<T>) -> Option<&str> {
+    request
+        .metadata()
+        .get("authorization")?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+fn to_proto(response: SwarmResponse) -> proto::Response {
+    proto::Response {
+        result: response.result.map(|r| r.to_string()).unwrap_or_default(),
+        error: response.error.map(|e| proto::Error {
+            code: e.code,
+            message: e.message,
+            data: e.data.map(|d| d.to_string()).unwrap_or_default(),
+        }),
+    }
+}
+
+/// Implement an rpc for each listed JSON-RPC method, named after it without
+/// the `swarm.` prefix.
+macro_rules! json_rpc_methods {
+    ($($rpc:ident),* $(,)?) => {
+        /// The JSON-RPC methods with an rpc of their own.
+        pub const METHODS: &[&str] = &[$(concat!("swarm.", stringify!($rpc))),*];
+
+        #[tonic::async_trait]
+        impl Connector for GrpcService {
+            async fn call(
+                &self,
+                request: Request<proto::CallRequest>,
+            ) -> Result<Response<proto::Response>, Status> {
+                let proto::CallRequest { method, params } = request.get_ref();
+                self.dispatch(method, params, &request).await
+            }
+
+            $(
+                async fn $rpc(
+                    &self,
+                    request: Request<proto::Request>,
+                ) -> Result<Response<proto::Response>, Status> {
+                    self.dispatch(concat!("swarm.", stringify!($rpc)), &request.get_ref().params, &request)
+                        .await
+                }
+            )*
+        }
+    };
+}
+
+json_rpc_methods!(
+    connect,
+    get_network_stats,
+    propose_plan,
+    revise_plan,
+    withdraw_plan,
+    submit_vote,
+    submit_critique,
+    get_voting_state,
+    submit_result,
+    report_progress,
+    receive_task,
+    ack_task,
+    get_task,
+    search_tasks,
+    get_attachment,
+    get_task_context,
+    get_result,
+    get_task_timeline,
+    get_logs,
+    get_task_costs,
+    get_flow_stats,
+    get_sla_stats,
+    get_dead_letters,
+    get_peer_scores,
+    health,
+    describe_errors,
+    reload_config,
+    get_partition_report,
+    ban_peer,
+    unban_peer,
+    init_swarm_params,
+    get_swarm_params,
+    propose_params_change,
+    vote_params_change,
+    get_status,
+    register_agent,
+    register_local_agent,
+    list_local_agents,
+    get_agent_endpoints,
+    get_reputation,
+    list_capabilities,
+    list_swarms,
+    create_swarm,
+    join_swarm,
+    leave_swarm,
+    migrate_swarm,
+    get_shard_status,
+    delegate_task,
+    delegate_subtask,
+    inject_task,
+    get_hierarchy,
+    get_board_status,
+    form_board,
+    answer_board_invite,
+    get_deliberation,
+    get_ballots,
+    get_irv_rounds,
+    register_name,
+    resolve_name,
+    resolve_did,
+    renew_name,
+    vote_name_dispute,
+    my_names,
+    verify_agent,
+    send_message,
+    approve_plan,
+    reassign_task,
+    export_run,
+    import_run,
+    save_task_template,
+    inject_from_template,
+    schedule_task,
+    list_schedules,
+    cancel_schedule,
+    vote_dispute,
+    get_verification,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_agent_method_has_an_rpc() {
+        for method in crate::auth::AGENT_METHODS {
+            assert!(METHODS.contains(method), "{} has no rpc", method);
+        }
+    }
+
+    #[test]
+    fn responses_carry_json_text_and_tokens_come_from_metadata() {
+        let ok = to_proto(SwarmResponse::success(None, serde_json::json!({"ok": true})));
+        assert_eq!(ok.result, r#"{"ok":true}"#);
+        assert!(ok.error.is_none());
+
+        let denied = to_proto(SwarmResponse::error(
+            None,
+            wws_protocol::ErrorCode::Forbidden,
+            "no".to_string(),
+        ));
+        let error = denied.error.unwrap();
+        assert_eq!(error.code, wws_protocol::ErrorCode::Forbidden.code());
+        assert!(denied.result.is_empty());
+
+        let mut request = Request::new(());
+        assert_eq!(bearer_token(&request), None);
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer change-me".parse().unwrap());
+        assert_eq!(bearer_token(&request), Some("change-me"));
+    }
+}
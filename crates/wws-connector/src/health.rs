@@ -0,0 +1,302 @@
+//! Self-diagnostics behind `swarm.health`.
+//!
+//! The network layer reports mesh membership per subscribed topic, the
+//! Kademlia routing table size, bootstrap reachability, event-loop lag and
+//! channel backlogs; the connector adds how long it waited for its own
+//! state lock. Each input becomes a named check that passes, warns or
+//! fails, and the checks roll up into one status: `isolated` when the node
+//! has no peers at all, `degraded` when any check warns, else `healthy`.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+use wws_network::{NetworkHealth, SwarmHandle};
+
+use crate::connector::ConnectorState;
+
+/// Event-loop lag at or above which the node is degraded.
+pub const LAG_WARN_MS: u64 = 500;
+/// Channel fill ratio at or above which the node is degraded.
+pub const BACKLOG_WARN_RATIO: f64 = 0.8;
+/// State-lock wait at or above which the node is degraded.
+pub const LOCK_WAIT_WARN_MS: u64 = 250;
+/// How long to wait for the network layer's health snapshot.
+const NETWORK_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Overall health of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Isolated,
+}
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One named diagnostic.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+/// How contended the connector's state lock was when probed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LockProbe {
+    /// Whether the lock was held by a writer when the probe started.
+    pub contended: bool,
+    /// Time spent waiting for a read lock, in milliseconds.
+    pub read_wait_ms: u64,
+}
+
+impl LockProbe {
+    /// Wait for a read lock on the connector state and record how long it took.
+    pub async fn measure(state: &Arc<RwLock<ConnectorState>>) -> Self {
+        let contended = state.try_read().is_err();
+        let started = Instant::now();
+        drop(state.read().await);
+        Self {
+            contended,
+            read_wait_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// Result of `swarm.health`.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checks: Vec<HealthCheck>,
+    pub network: NetworkHealth,
+    pub lock: LockProbe,
+    /// Times the daemon supervisor restarted the connector; 0 outside
+    /// daemon mode.
+    pub restarts: u32,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Probe the network layer and the state lock and assess the node.
+pub async fn check(
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &SwarmHandle,
+) -> Result<HealthReport, String> {
+    let lock = LockProbe::measure(state).await;
+    let restarts = state.read().await.restarts;
+    let network = tokio::time::timeout(NETWORK_PROBE_TIMEOUT, network_handle.health())
+        .await
+        .map_err(|_| "network layer did not answer the health probe".to_string())?
+        .map_err(|e| e.to_string())?;
+    let (status, checks) = assess(&network, &lock);
+    Ok(HealthReport {
+        status,
+        checks,
+        network,
+        lock,
+        restarts,
+        checked_at: chrono::Utc::now(),
+    })
+}
+
+/// Turn raw diagnostics into checks and an overall status.
+pub fn assess(network: &NetworkHealth, lock: &LockProbe) -> (HealthStatus, Vec<HealthCheck>) {
+    let mut checks = Vec::new();
+    let mut push = |name, status, detail: String| {
+        checks.push(HealthCheck {
+            name,
+            status,
+            detail,
+        })
+    };
+
+    if network.connected_peers == 0 {
+        push("peers", CheckStatus::Fail, "no connected peers".into());
+    } else {
+        push(
+            "peers",
+            CheckStatus::Pass,
+            format!("{} connected", network.connected_peers),
+        );
+    }
+
+    let empty_meshes: Vec<&str> = network
+        .topics
+        .iter()
+        .filter(|t| t.subscribed_peers > 0 && t.mesh_peers == 0)
+        .map(|t| t.topic.as_str())
+        .collect();
+    if empty_meshes.is_empty() {
+        push(
+            "gossipsub_mesh",
+            CheckStatus::Pass,
+            format!("{} topics subscribed", network.topics.len()),
+        );
+    } else {
+        push(
+            "gossipsub_mesh",
+            CheckStatus::Warn,
+            format!("no mesh peers on {}", empty_meshes.join(", ")),
+        );
+    }
+
+    if network.routing_table_size == 0 {
+        push("dht", CheckStatus::Warn, "routing table is empty".into());
+    } else {
+        push(
+            "dht",
+            CheckStatus::Pass,
+            format!("{} peers in routing table", network.routing_table_size),
+        );
+    }
+
+    if network.bootstrap_peers == 0 {
+        push("bootstrap", CheckStatus::Pass, "no bootstrap peers configured".into());
+    } else if network.bootstrap_connected == 0 {
+        push(
+            "bootstrap",
+            CheckStatus::Warn,
+            format!("none of {} bootstrap peers reachable", network.bootstrap_peers),
+        );
+    } else {
+        push(
+            "bootstrap",
+            CheckStatus::Pass,
+            format!(
+                "{} of {} bootstrap peers connected",
+                network.bootstrap_connected, network.bootstrap_peers
+            ),
+        );
+    }
+
+    let lag = network.event_loop_lag_ms.max(network.event_loop_lag_peak_ms);
+    push(
+        "event_loop",
+        if lag >= LAG_WARN_MS { CheckStatus::Warn } else { CheckStatus::Pass },
+        format!(
+            "lag {}ms (peak {}ms)",
+            network.event_loop_lag_ms, network.event_loop_lag_peak_ms
+        ),
+    );
+
+    let backed_up: Vec<String> = network
+        .channels
+        .iter()
+        .filter(|c| c.fill_ratio() >= BACKLOG_WARN_RATIO)
+        .map(|c| format!("{} {}/{}", c.name, c.queued, c.capacity))
+        .collect();
+    if backed_up.is_empty() {
+        push("channels", CheckStatus::Pass, "no backlog".into());
+    } else {
+        push(
+            "channels",
+            CheckStatus::Warn,
+            format!("backlogged: {}", backed_up.join(", ")),
+        );
+    }
+
+    push(
+        "state_lock",
+        if lock.read_wait_ms >= LOCK_WAIT_WARN_MS {
+            CheckStatus::Warn
+        } else {
+            CheckStatus::Pass
+        },
+        format!(
+            "read wait {}ms{}",
+            lock.read_wait_ms,
+            if lock.contended { " (contended)" } else { "" }
+        ),
+    );
+
+    let status = if checks.iter().any(|c| c.status == CheckStatus::Fail) {
+        HealthStatus::Isolated
+    } else if checks.iter().any(|c| c.status == CheckStatus::Warn) {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Healthy
+    };
+    (status, checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_network::{ChannelBacklog, TopicMeshHealth};
+
+    fn network() -> NetworkHealth {
+        NetworkHealth {
+            connected_peers: 3,
+            topics: vec![TopicMeshHealth {
+                topic: "/wws/1.0.0/keepalive".into(),
+                mesh_peers: 3,
+                subscribed_peers: 3,
+            }],
+            routing_table_size: 3,
+            bootstrap_peers: 1,
+            bootstrap_connected: 1,
+            bootstrap_complete: true,
+            event_loop_lag_ms: 2,
+            event_loop_lag_peak_ms: 10,
+            channels: vec![ChannelBacklog {
+                name: "events".into(),
+                queued: 0,
+                capacity: 256,
+            }],
+        }
+    }
+
+    fn status_of(checks: &[HealthCheck], name: &str) -> CheckStatus {
+        checks.iter().find(|c| c.name == name).unwrap().status
+    }
+
+    #[test]
+    fn connected_node_is_healthy() {
+        let (status, checks) = assess(&network(), &LockProbe::default());
+        assert_eq!(status, HealthStatus::Healthy);
+        assert!(checks.iter().all(|c| c.status == CheckStatus::Pass));
+    }
+
+    #[test]
+    fn empty_mesh_backlog_and_lag_degrade() {
+        let mut net = network();
+        net.topics[0].mesh_peers = 0;
+        let (status, checks) = assess(&net, &LockProbe::default());
+        assert_eq!(status, HealthStatus::Degraded);
+        assert_eq!(status_of(&checks, "gossipsub_mesh"), CheckStatus::Warn);
+
+        let mut net = network();
+        net.channels[0].queued = 250;
+        net.event_loop_lag_peak_ms = LAG_WARN_MS;
+        let (status, checks) = assess(&net, &LockProbe::default());
+        assert_eq!(status, HealthStatus::Degraded);
+        assert_eq!(status_of(&checks, "channels"), CheckStatus::Warn);
+        assert_eq!(status_of(&checks, "event_loop"), CheckStatus::Warn);
+
+        let slow_lock = LockProbe {
+            contended: true,
+            read_wait_ms: LOCK_WAIT_WARN_MS,
+        };
+        let (status, _) = assess(&network(), &slow_lock);
+        assert_eq!(status, HealthStatus::Degraded);
+    }
+
+    #[test]
+    fn node_without_peers_is_isolated() {
+        let mut net = network();
+        net.connected_peers = 0;
+        net.bootstrap_connected = 0;
+        let (status, checks) = assess(&net, &LockProbe::default());
+        assert_eq!(status, HealthStatus::Isolated);
+        assert_eq!(status_of(&checks, "bootstrap"), CheckStatus::Warn);
+    }
+}
@@ -0,0 +1,389 @@
+//! Decoding and validation of inbound gossip messages.
+//!
+//! Each received payload is parsed, its method resolved and its params
+//! decoded into the method's type on the blocking worker pool — strictly,
+//! within [`StrictLimits`], so a hostile payload cannot blow up memory —
+//! together with
+//! any signature check the params carry (swarm parameter records,
+//! migrations, splits, name claims, guardian votes and reputation
+//! attestations). The connector's event loop only receives the finished
+//! [`InboundMessage`]s, in arrival order, so a burst of large or malformed
+//! messages does not stall keep-alives, timers or RPC-driven state changes.
+
+use serde::de::DeserializeOwned;
+use wws_network::PeerId;
+use wws_protocol::*;
+
+/// Most messages decoded ahead of the event loop; the loop stops taking
+/// network events while this many are in flight.
+pub const MAX_PENDING_DECODES: usize = 256;
+
+/// A received gossip message after decoding.
+#[derive(Debug)]
+pub struct InboundMessage {
+    pub topic: String,
+    pub source: PeerId,
+    /// The raw payload, kept for dead letters.
+    pub data: Vec<u8>,
+    pub decoded: Result<DecodedMessage, InboundRejection>,
+}
+
+impl InboundMessage {
+    /// Method named by the message, if it could be read.
+    pub fn method(&self) -> Option<&str> {
+        match &self.decoded {
+            Ok(decoded) => Some(decoded.message.method.as_str()),
+            Err(rejection) => rejection.method(),
+        }
+    }
+
+    /// `task_id` param of the message, if it has one.
+    pub fn task_id(&self) -> Option<&str> {
+        self.decoded
+            .as_ref()
+            .ok()
+            .and_then(|d| d.message.params.get("task_id"))
+            .and_then(|v| v.as_str())
+    }
+}
+
+/// A message whose params decoded into its method's type.
+#[derive(Debug)]
+pub struct DecodedMessage {
+    pub message: SwarmMessage,
+    pub params: InboundParams,
+}
+
+/// Why an inbound message was dropped before handling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboundRejection {
+    /// The payload is not a protocol message.
+    ParseError(String),
+    /// The method is not part of the protocol.
+    UnknownMethod(String),
+    /// The params do not fit the method's schema or exceed the strict
+    /// decoding limits; `fields` names each offending field.
+    InvalidParams {
+        method: String,
+        error: String,
+        fields: Vec<FieldError>,
+    },
+    /// A signature carried in the params does not verify.
+    InvalidSignature { method: String, what: String },
+    /// The sender is not entitled to issue the message, e.g. an assignment
+    /// from an agent that is not above the assignee.
+    Unauthorized { method: String, what: String },
+}
+
+impl InboundRejection {
+    pub fn method(&self) -> Option<&str> {
+        match self {
+            InboundRejection::ParseError(_) => None,
+            InboundRejection::UnknownMethod(method)
+            | InboundRejection::InvalidParams { method, .. }
+            | InboundRejection::InvalidSignature { method, .. }
+            | InboundRejection::Unauthorized { method, .. } => Some(method),
+        }
+    }
+
+    /// Code sent back to a direct request's sender.
+    pub fn error_code(&self) -> wws_protocol::ErrorCode {
+        use wws_protocol::ErrorCode;
+        match self {
+            InboundRejection::ParseError(_) => ErrorCode::ParseError,
+            InboundRejection::UnknownMethod(_) => ErrorCode::MethodNotFound,
+            InboundRejection::InvalidParams { .. } => ErrorCode::InvalidParams,
+            InboundRejection::InvalidSignature { .. } => ErrorCode::InvalidSignature,
+            InboundRejection::Unauthorized { .. } => ErrorCode::UnauthorizedSender,
+        }
+    }
+
+    /// Dead-letter reason, prefixed with the rejection kind.
+    pub fn reason(&self) -> String {
+        match self {
+            InboundRejection::ParseError(e) => format!("parse_error: {}", e),
+            InboundRejection::UnknownMethod(_) => "unknown_method".to_string(),
+            InboundRejection::InvalidParams { error, .. } => format!("invalid_params: {}", error),
+            InboundRejection::InvalidSignature { what, .. } => format!("invalid_signature: {}", what),
+            InboundRejection::Unauthorized { what, .. } => format!("unauthorized: {}", what),
+        }
+    }
+
+    /// Problems found per field, for params that failed strict decoding.
+    pub fn field_errors(&self) -> &[FieldError] {
+        match self {
+            InboundRejection::InvalidParams { fields, .. } => fields,
+            _ => &[],
+        }
+    }
+
+    /// The offense to report against the propagating peer, if any.
+    pub fn offense(&self) -> Option<wws_network::PeerOffense> {
+        match self {
+            InboundRejection::ParseError(_) => Some(wws_network::PeerOffense::ParseError),
+            InboundRejection::UnknownMethod(_) => None,
+            InboundRejection::InvalidParams { .. } => Some(wws_network::PeerOffense::ProtocolViolation),
+            InboundRejection::InvalidSignature { .. } => Some(wws_network::PeerOffense::InvalidSignature),
+            // Peers' views of the hierarchy lag each other, so an honest
+            // relay may pass on an assignment this node does not accept.
+            InboundRejection::Unauthorized { .. } => None,
+        }
+    }
+}
+
+/// Typed params of every method the connector handles.
+#[derive(Debug)]
+pub enum InboundParams {
+    KeepAlive(KeepAliveParams),
+    AgentKeepAlive(KeepAliveParams),
+    WorkStealRequest(WorkStealRequestParams),
+    Candidacy(CandidacyParams),
+    ElectionVote(ElectionVoteParams),
+    TierAssignment(TierAssignmentParams),
+    TaskInjection(TaskInjectionParams),
+    TaskAssignment(TaskAssignmentParams),
+    ProposalCommit(ProposalCommitParams),
+    ProposalReveal(ProposalRevealParams),
+    ConsensusVote(ConsensusVoteParams),
+    ConsensusVoteCommit(ConsensusVoteCommitParams),
+    ConsensusVoteReveal(ConsensusVoteRevealParams),
+    VotingOpen(VotingOpenParams),
+    VoteReminder(VoteReminderParams),
+    ResultSubmission(ResultSubmissionParams),
+    TaskProgress(TaskProgressParams),
+    TaskEscalation(TaskEscalationParams),
+    TaskTenderFailed(TaskTenderFailedParams),
+    DisputeVote(DisputeVoteParams),
+    Succession(SuccessionParams),
+    SwarmAnnounce(SwarmAnnounceParams),
+    SwarmJoin(SwarmJoinParams),
+    SwarmLeave(SwarmLeaveParams),
+    SwarmParams(SwarmParameters),
+    ParamsChangePropose(ParamsChangeProposeParams),
+    ParamsChangeVote(ParamsChangeVoteParams),
+    SwarmMigrate(SwarmMigrateParams),
+    SwarmShard(SwarmShardParams),
+    TaskDelegation(TaskDelegationParams),
+    EpochRekey(EpochRekeyParams),
+    ReputationAttest(ReputationAttestParams),
+    BoardInvite(BoardInviteParams),
+    BoardAccept(BoardAcceptParams),
+    BoardDecline(BoardDeclineParams),
+    BoardReady(BoardReadyParams),
+    BoardDissolve(BoardDissolveParams),
+    BoardSealed(SealedBoardMessageParams),
+    DiscussionCritique(DiscussionCritiqueParams),
+    ProposalRevision(ProposalRevisionParams),
+    ProposalWithdraw(ProposalWithdrawParams),
+    BallotReceipt(BallotReceiptParams),
+    TaskStateRequest(TaskStateRequestParams),
+    TaskStateSnapshot(TaskStateSnapshotParams),
+    StateReconcile(StateReconcileParams),
+    SwarmMerge(SwarmMergeParams),
+    AgentDirectMessage(DirectMessageParams),
+    NameClaim(NameClaimParams),
+    NameDisputeVote(NameDisputeVoteParams),
+    /// A protocol method the connector does not act on.
+    Unhandled(ProtocolMethod),
+}
+
+impl InboundParams {
+    fn decode(
+        method: ProtocolMethod,
+        params: &serde_json::Value,
+        limits: &StrictLimits,
+    ) -> Result<Self, StrictError> {
+        fn typed<T: DeserializeOwned>(
+            params: &serde_json::Value,
+            limits: &StrictLimits,
+        ) -> Result<T, StrictError> {
+            decode_strict(params, limits)
+        }
+        use ProtocolMethod as M;
+        Ok(match method {
+            M::KeepAlive => Self::KeepAlive(typed(params, limits)?),
+            M::AgentKeepAlive => Self::AgentKeepAlive(typed(params, limits)?),
+            M::WorkStealRequest => Self::WorkStealRequest(typed(params, limits)?),
+            M::Candidacy => Self::Candidacy(typed(params, limits)?),
+            M::ElectionVote => Self::ElectionVote(typed(params, limits)?),
+            M::TierAssignment => Self::TierAssignment(typed(params, limits)?),
+            M::TaskInjection => Self::TaskInjection(typed(params, limits)?),
+            M::TaskAssignment => Self::TaskAssignment(typed(params, limits)?),
+            M::ProposalCommit => Self::ProposalCommit(typed(params, limits)?),
+            M::ProposalReveal => Self::ProposalReveal(typed(params, limits)?),
+            M::ConsensusVote => Self::ConsensusVote(typed(params, limits)?),
+            M::ConsensusVoteCommit => Self::ConsensusVoteCommit(typed(params, limits)?),
+            M::ConsensusVoteReveal => Self::ConsensusVoteReveal(typed(params, limits)?),
+            M::VotingOpen => Self::VotingOpen(typed(params, limits)?),
+            M::VoteReminder => Self::VoteReminder(typed(params, limits)?),
+            M::ResultSubmission => Self::ResultSubmission(typed(params, limits)?),
+            M::TaskProgress => Self::TaskProgress(typed(params, limits)?),
+            M::TaskEscalation => Self::TaskEscalation(typed(params, limits)?),
+            M::TaskTenderFailed => Self::TaskTenderFailed(typed(params, limits)?),
+            M::DisputeVote => Self::DisputeVote(typed(params, limits)?),
+            M::Succession => Self::Succession(typed(params, limits)?),
+            M::SwarmAnnounce => Self::SwarmAnnounce(typed(params, limits)?),
+            M::SwarmJoin => Self::SwarmJoin(typed(params, limits)?),
+            M::SwarmLeave => Self::SwarmLeave(typed(params, limits)?),
+            M::SwarmParams => Self::SwarmParams(typed(params, limits)?),
+            M::ParamsChangePropose => Self::ParamsChangePropose(typed(params, limits)?),
+            M::ParamsChangeVote => Self::ParamsChangeVote(typed(params, limits)?),
+            M::SwarmMigrate => Self::SwarmMigrate(typed(params, limits)?),
+            M::SwarmShard => Self::SwarmShard(typed(params, limits)?),
+            M::TaskDelegation => Self::TaskDelegation(typed(params, limits)?),
+            M::EpochRekey => Self::EpochRekey(typed(params, limits)?),
+            M::ReputationAttest => Self::ReputationAttest(typed(params, limits)?),
+            M::BoardInvite => Self::BoardInvite(typed(params, limits)?),
+            M::BoardAccept => Self::BoardAccept(typed(params, limits)?),
+            M::BoardDecline => Self::BoardDecline(typed(params, limits)?),
+            M::BoardReady => Self::BoardReady(typed(params, limits)?),
+            M::BoardDissolve => Self::BoardDissolve(typed(params, limits)?),
+            M::BoardSealed => Self::BoardSealed(typed(params, limits)?),
+            M::DiscussionCritique => Self::DiscussionCritique(typed(params, limits)?),
+            M::ProposalRevision => Self::ProposalRevision(typed(params, limits)?),
+            M::ProposalWithdraw => Self::ProposalWithdraw(typed(params, limits)?),
+            M::BallotReceipt => Self::BallotReceipt(typed(params, limits)?),
+            M::TaskStateRequest => Self::TaskStateRequest(typed(params, limits)?),
+            M::TaskStateSnapshot => Self::TaskStateSnapshot(typed(params, limits)?),
+            M::StateReconcile => Self::StateReconcile(typed(params, limits)?),
+            M::SwarmMerge => Self::SwarmMerge(typed(params, limits)?),
+            M::AgentDirectMessage => Self::AgentDirectMessage(typed(params, limits)?),
+            M::NameClaim => Self::NameClaim(typed(params, limits)?),
+            M::NameDisputeVote => Self::NameDisputeVote(typed(params, limits)?),
+            other => Self::Unhandled(other),
+        })
+    }
+}
+
+/// Decode and validate one payload.
+pub fn decode(topic: String, data: Vec<u8>, source: PeerId, limits: StrictLimits) -> InboundMessage {
+    let decoded = decode_payload(&data, &limits);
+    InboundMessage {
+        topic,
+        source,
+        data,
+        decoded,
+    }
+}
+
+/// Decode a payload on the blocking worker pool.
+pub fn spawn_decode(
+    topic: String,
+    data: Vec<u8>,
+    source: PeerId,
+    limits: StrictLimits,
+) -> tokio::task::JoinHandle<InboundMessage> {
+    tokio::task::spawn_blocking(move || decode(topic, data, source, limits))
+}
+
+fn decode_payload(data: &[u8], limits: &StrictLimits) -> Result<DecodedMessage, InboundRejection> {
+    let message: SwarmMessage =
+        serde_json::from_slice(data).map_err(|e| InboundRejection::ParseError(e.to_string()))?;
+    let Some(method) = ProtocolMethod::from_str(&message.method) else {
+        return Err(InboundRejection::UnknownMethod(message.method));
+    };
+    let params = InboundParams::decode(method, &message.params, limits).map_err(|e| {
+        InboundRejection::InvalidParams {
+            method: message.method.clone(),
+            error: e.to_string(),
+            fields: e.0,
+        }
+    })?;
+    let signature_ok = match &params {
+        InboundParams::SwarmParams(record) => record.verify().then_some(()).ok_or("swarm parameters"),
+        InboundParams::SwarmMigrate(migration) => migration.verify().then_some(()).ok_or("swarm migration"),
+        InboundParams::SwarmShard(shard) => shard.verify().then_some(()).ok_or("swarm shard"),
+        InboundParams::EpochRekey(rekey) => rekey.verify().then_some(()).ok_or("epoch rekey"),
+        InboundParams::ReputationAttest(batch) => batch
+            .attestations
+            .iter()
+            .all(ReputationAttestation::verify)
+            .then_some(())
+            .ok_or("reputation attestation"),
+        InboundParams::NameClaim(claim) => claim.verify().then_some(()).ok_or("name claim"),
+        InboundParams::NameDisputeVote(vote) => vote.verify().then_some(()).ok_or("name dispute vote"),
+        InboundParams::BallotReceipt(receipt) => receipt.verify().then_some(()).ok_or("ballot receipt"),
+        // Unsigned assignments decode; the connector refuses them as unauthorized.
+        InboundParams::TierAssignment(assignment) if !assignment.signature.is_empty() => {
+            assignment.verify().then_some(()).ok_or("tier assignment")
+        }
+        InboundParams::TaskAssignment(assignment) if !assignment.signature.is_empty() => {
+            assignment.verify().then_some(()).ok_or("task assignment")
+        }
+        _ => Ok(()),
+    };
+    if let Err(what) = signature_ok {
+        return Err(InboundRejection::InvalidSignature {
+            method: message.method.clone(),
+            what: what.to_string(),
+        });
+    }
+    Ok(DecodedMessage { message, params })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_json(value: serde_json::Value) -> InboundMessage {
+        decode("t".to_string(), serde_json::to_vec(&value).unwrap(), PeerId::random(), StrictLimits::default())
+    }
+
+    #[test]
+    fn payloads_decode_into_typed_params_or_a_rejection() {
+        let ok = decode_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "method": "swarm.leave",
+            "params": {"swarm_id": "s1", "agent_id": "did:swarm:a", "timestamp": "2026-01-01T00:00:00Z"},
+            "signature": ""
+        }));
+        assert!(matches!(
+            ok.decoded.as_ref().map(|d| &d.params),
+            Ok(InboundParams::SwarmLeave(_))
+        ));
+        assert_eq!(ok.method(), Some("swarm.leave"));
+
+        let bad_params = decode_json(serde_json::json!({
+            "jsonrpc": "2.0", "id": "2", "method": "swarm.leave", "params": {"swarm_id": 7}, "signature": ""
+        }));
+        let rejection = bad_params.decoded.unwrap_err();
+        assert!(rejection.reason().starts_with("invalid_params: "));
+        assert_eq!(rejection.offense(), Some(wws_network::PeerOffense::ProtocolViolation));
+
+        let unknown = decode_json(serde_json::json!({
+            "jsonrpc": "2.0", "id": "3", "method": "swarm.nope", "params": {}, "signature": ""
+        }));
+        assert_eq!(unknown.decoded.unwrap_err(), InboundRejection::UnknownMethod("swarm.nope".into()));
+
+        let garbage = decode("t".to_string(), b"not json".to_vec(), PeerId::random(), StrictLimits::default());
+        assert_eq!(garbage.method(), None);
+        assert!(garbage.decoded.unwrap_err().reason().starts_with("parse_error: "));
+    }
+
+    #[test]
+    fn hostile_params_are_rejected_with_per_field_errors() {
+        let oversized = decode_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "4",
+            "method": "swarm.leave",
+            "params": {
+                "swarm_id": "s".repeat(StrictLimits::default().max_string_bytes + 1),
+                "agent_id": "did:swarm:a",
+                "timestamp": "2026-01-01T00:00:00Z"
+            },
+            "signature": ""
+        }));
+        let rejection = oversized.decoded.unwrap_err();
+        assert!(rejection.reason().starts_with("invalid_params: swarm_id: string of"));
+        assert_eq!(rejection.field_errors().len(), 1);
+        assert_eq!(rejection.field_errors()[0].path, "swarm_id");
+
+        let mistyped = decode_json(serde_json::json!({
+            "jsonrpc": "2.0", "id": "5", "method": "swarm.leave",
+            "params": {"swarm_id": "s1", "agent_id": 7, "timestamp": "2026-01-01T00:00:00Z"},
+            "signature": ""
+        }));
+        assert_eq!(mistyped.decoded.unwrap_err().field_errors()[0].path, "agent_id");
+    }
+}
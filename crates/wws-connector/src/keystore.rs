@@ -0,0 +1,422 @@
+//! Encrypted keystore for node secrets.
+//!
+//! The keystore is a JSON file of named entries, each sealed with
+//! ChaCha20-Poly1305 under a key derived from a passphrase with scrypt; the
+//! entry name is bound in as associated data, so entries cannot be swapped.
+//! It holds the node's Ed25519 identity key under [`IDENTITY_ENTRY`] — the
+//! libp2p keypair, and so the PeerId and DID, derive from it — and swarm
+//! tokens under [`swarm_token_entry`]. With `[keystore] path` set, the
+//! connector reads its identity and any swarm token missing from the
+//! configuration from the keystore; `wws-connector keystore` manages the
+//! entries.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::SigningKey;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Entry holding the node's Ed25519 identity key.
+pub const IDENTITY_ENTRY: &str = "identity";
+
+/// Environment variable read for the passphrase before `passphrase_file`.
+pub const PASSPHRASE_ENV: &str = "WWS_KEYSTORE_PASSPHRASE";
+
+const FORMAT_VERSION: u32 = 1;
+/// Associated data of the passphrase check value.
+const CHECK_ENTRY: &str = "\0check";
+
+/// Entry holding the token of `swarm_id`.
+pub fn swarm_token_entry(swarm_id: &str) -> String {
+    format!("swarm-token/{}", swarm_id)
+}
+
+/// Errors from opening or using a keystore.
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error("Keystore I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid keystore file: {0}")]
+    Format(String),
+
+    #[error("Wrong keystore passphrase")]
+    WrongPassphrase,
+
+    #[error("Keystore entry {0:?} is corrupt")]
+    Corrupt(String),
+
+    #[error("Keystore already exists at {0}")]
+    Exists(PathBuf),
+}
+
+/// scrypt cost parameters, stored with the keystore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    /// About 32 MiB and a fraction of a second per unlock.
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sealed {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeystoreFile {
+    version: u32,
+    kdf: KdfParams,
+    salt: String,
+    check: Sealed,
+    entries: BTreeMap<String, Sealed>,
+}
+
+/// An unlocked keystore. Changes are written by [`Keystore::save`].
+pub struct Keystore {
+    path: PathBuf,
+    file: KeystoreFile,
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl Keystore {
+    /// Create an empty keystore at `path`, which must not exist yet.
+    pub fn create(path: &Path, passphrase: &str, kdf: KdfParams) -> Result<Self, KeystoreError> {
+        if path.exists() {
+            return Err(KeystoreError::Exists(path.to_path_buf()));
+        }
+        let mut salt = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let key = derive_key(passphrase, &salt, kdf)?;
+        let check = seal(&key, CHECK_ENTRY, CHECK_ENTRY.as_bytes())?;
+        let keystore = Self {
+            path: path.to_path_buf(),
+            file: KeystoreFile {
+                version: FORMAT_VERSION,
+                kdf,
+                salt: hex::encode(salt),
+                check,
+                entries: BTreeMap::new(),
+            },
+            key,
+        };
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Open and unlock the keystore at `path`.
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self, KeystoreError> {
+        let file: KeystoreFile = serde_json::from_slice(&std::fs::read(path)?)
+            .map_err(|e| KeystoreError::Format(e.to_string()))?;
+        if file.version != FORMAT_VERSION {
+            return Err(KeystoreError::Format(format!(
+                "unsupported version {}",
+                file.version
+            )));
+        }
+        let salt = hex::decode(&file.salt).map_err(|e| KeystoreError::Format(e.to_string()))?;
+        let key = derive_key(passphrase, &salt, file.kdf)?;
+        open_sealed(&key, CHECK_ENTRY, &file.check).map_err(|_| KeystoreError::WrongPassphrase)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            key,
+        })
+    }
+
+    /// Names of the entries, sorted.
+    pub fn names(&self) -> Vec<String> {
+        self.file.entries.keys().cloned().collect()
+    }
+
+    /// The decrypted value of `name`.
+    pub fn get(&self, name: &str) -> Result<Option<Zeroizing<Vec<u8>>>, KeystoreError> {
+        self.file
+            .entries
+            .get(name)
+            .map(|sealed| open_sealed(&self.key, name, sealed))
+            .transpose()
+    }
+
+    /// Store `value` under `name`, replacing any previous value.
+    pub fn set(&mut self, name: &str, value: &[u8]) -> Result<(), KeystoreError> {
+        let sealed = seal(&self.key, name, value)?;
+        self.file.entries.insert(name.to_string(), sealed);
+        Ok(())
+    }
+
+    /// Move the value of `from` to `to`; false if `from` does not exist.
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<bool, KeystoreError> {
+        match self.get(from)? {
+            Some(value) => {
+                self.set(to, &value)?;
+                self.file.entries.remove(from);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Delete `name`; false if it did not exist.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.file.entries.remove(name).is_some()
+    }
+
+    /// Re-encrypt every entry under a new passphrase.
+    pub fn change_passphrase(&mut self, passphrase: &str) -> Result<(), KeystoreError> {
+        let values = self
+            .names()
+            .into_iter()
+            .map(|name| Ok((self.get(&name)?.unwrap_or_default(), name)))
+            .collect::<Result<Vec<_>, KeystoreError>>()?;
+        let mut salt = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        self.key = derive_key(passphrase, &salt, self.file.kdf)?;
+        self.file.salt = hex::encode(salt);
+        self.file.check = seal(&self.key, CHECK_ENTRY, CHECK_ENTRY.as_bytes())?;
+        for (value, name) in values {
+            self.set(&name, &value)?;
+        }
+        Ok(())
+    }
+
+    /// The identity key, if the keystore holds one.
+    pub fn identity(&self) -> Result<Option<SigningKey>, KeystoreError> {
+        let Some(seed) = self.get(IDENTITY_ENTRY)? else {
+            return Ok(None);
+        };
+        let seed: [u8; 32] = seed
+            .as_slice()
+            .try_into()
+            .map_err(|_| KeystoreError::Corrupt(IDENTITY_ENTRY.to_string()))?;
+        Ok(Some(SigningKey::from_bytes(&seed)))
+    }
+
+    /// Store `key` as the identity key.
+    pub fn set_identity(&mut self, key: &SigningKey) -> Result<(), KeystoreError> {
+        self.set(IDENTITY_ENTRY, &Zeroizing::new(key.to_bytes())[..])
+    }
+
+    /// Store a fresh identity key. A previous one is kept as
+    /// `identity.old.<unix time>`, whose name is returned with the new key.
+    pub fn rotate_identity(&mut self) -> Result<(SigningKey, Option<String>), KeystoreError> {
+        let old = format!("{}.old.{}", IDENTITY_ENTRY, chrono::Utc::now().timestamp());
+        let old = self.rename(IDENTITY_ENTRY, &old)?.then_some(old);
+        let key = wws_protocol::crypto::generate_keypair();
+        self.set_identity(&key)?;
+        Ok((key, old))
+    }
+
+    /// The token of `swarm_id`, if the keystore holds one.
+    pub fn swarm_token(&self, swarm_id: &str) -> Result<Option<String>, KeystoreError> {
+        let name = swarm_token_entry(swarm_id);
+        self.get(&name)?
+            .map(|token| {
+                String::from_utf8(token.to_vec()).map_err(|_| KeystoreError::Corrupt(name))
+            })
+            .transpose()
+    }
+
+    /// Write the keystore to its file, readable only by its owner.
+    pub fn save(&self) -> Result<(), KeystoreError> {
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_vec_pretty(&self.file)
+            .map_err(|e| KeystoreError::Format(e.to_string()))?;
+        let mut tmp = self.path.as_os_str().to_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        std::fs::write(&tmp, json)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Read the passphrase from [`PASSPHRASE_ENV`], then `passphrase_file`,
+/// then the terminal.
+pub fn read_passphrase(
+    passphrase_file: Option<&Path>,
+    new: bool,
+) -> anyhow::Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    match passphrase_file {
+        Some(path) => passphrase_from_file(path),
+        None => prompt_passphrase(new),
+    }
+}
+
+/// The first line of `path`.
+pub fn passphrase_from_file(path: &Path) -> anyhow::Result<Zeroizing<String>> {
+    let contents = Zeroizing::new(
+        std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?,
+    );
+    Ok(Zeroizing::new(
+        contents.lines().next().unwrap_or_default().to_string(),
+    ))
+}
+
+/// Ask for the passphrase on the terminal; a new one is asked for twice.
+pub fn prompt_passphrase(new: bool) -> anyhow::Result<Zeroizing<String>> {
+    let prompt = if new {
+        "New keystore passphrase: "
+    } else {
+        "Keystore passphrase: "
+    };
+    let passphrase = Zeroizing::new(rpassword::prompt_password(prompt).map_err(|e| {
+        anyhow::anyhow!(
+            "No keystore passphrase: set {} or [keystore] passphrase_file ({})",
+            PASSPHRASE_ENV,
+            e
+        )
+    })?);
+    if new {
+        if passphrase.is_empty() {
+            anyhow::bail!("Empty keystore passphrase");
+        }
+        let again = Zeroizing::new(rpassword::prompt_password("Repeat passphrase: ")?);
+        if *again != *passphrase {
+            anyhow::bail!("Passphrases do not match");
+        }
+    }
+    Ok(passphrase)
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    kdf: KdfParams,
+) -> Result<Zeroizing<[u8; 32]>, KeystoreError> {
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, 32)
+        .map_err(|e| KeystoreError::Format(format!("scrypt parameters: {}", e)))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key[..])
+        .map_err(|e| KeystoreError::Format(format!("scrypt: {}", e)))?;
+    Ok(key)
+}
+
+fn seal(key: &[u8; 32], name: &str, value: &[u8]) -> Result<Sealed, KeystoreError> {
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: value,
+                aad: name.as_bytes(),
+            },
+        )
+        .map_err(|_| KeystoreError::Corrupt(name.to_string()))?;
+    Ok(Sealed {
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn open_sealed(
+    key: &[u8; 32],
+    name: &str,
+    sealed: &Sealed,
+) -> Result<Zeroizing<Vec<u8>>, KeystoreError> {
+    let corrupt = || KeystoreError::Corrupt(name.to_string());
+    let nonce = hex::decode(&sealed.nonce).map_err(|_| corrupt())?;
+    let ciphertext = hex::decode(&sealed.ciphertext).map_err(|_| corrupt())?;
+    if nonce.len() != 12 {
+        return Err(corrupt());
+    }
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: name.as_bytes(),
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| corrupt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn entries_round_trip_and_need_the_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+        let identity = wws_protocol::crypto::generate_keypair();
+        {
+            let mut keystore = Keystore::create(&path, "hunter2", FAST).unwrap();
+            keystore.set_identity(&identity).unwrap();
+            keystore.set(&swarm_token_entry("s1"), b"tok").unwrap();
+            keystore.save().unwrap();
+        }
+        assert!(matches!(
+            Keystore::create(&path, "x", FAST),
+            Err(KeystoreError::Exists(_))
+        ));
+        assert!(matches!(
+            Keystore::open(&path, "wrong"),
+            Err(KeystoreError::WrongPassphrase)
+        ));
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains(&hex::encode(identity.to_bytes())));
+
+        let mut keystore = Keystore::open(&path, "hunter2").unwrap();
+        assert_eq!(
+            keystore.identity().unwrap().unwrap().to_bytes(),
+            identity.to_bytes()
+        );
+        assert_eq!(keystore.swarm_token("s1").unwrap().as_deref(), Some("tok"));
+        assert_eq!(keystore.swarm_token("s2").unwrap(), None);
+
+        keystore.change_passphrase("correct horse").unwrap();
+        assert!(keystore.rename(IDENTITY_ENTRY, "identity.old").unwrap());
+        keystore.save().unwrap();
+        let keystore = Keystore::open(&path, "correct horse").unwrap();
+        assert_eq!(keystore.names(), ["identity.old", "swarm-token/s1"]);
+        assert!(keystore.identity().unwrap().is_none());
+
+        // An entry moved under another name no longer decrypts.
+        let mut file: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let entry = file["entries"]["swarm-token/s1"].clone();
+        file["entries"]["swarm-token/s2"] = entry;
+        std::fs::write(&path, file.to_string()).unwrap();
+        let keystore = Keystore::open(&path, "correct horse").unwrap();
+        assert!(matches!(
+            keystore.swarm_token("s2"),
+            Err(KeystoreError::Corrupt(_))
+        ));
+    }
+}
@@ -11,15 +11,62 @@
 //! to interact with the swarm.
 
 pub mod agent_bridge;
+pub mod aggregation;
+pub mod assignment;
 pub mod auth;
+pub mod board;
+pub mod bonds;
+pub mod capabilities;
+pub mod cluster;
 pub mod config;
 pub mod connector;
+pub mod daemon;
+pub mod delegation;
+pub mod delivery;
+pub mod did;
+pub mod echo_agent;
+pub mod election;
 pub mod file_server;
+#[cfg(feature = "grpc")]
+pub mod grpc_server;
+pub mod health;
+pub mod inbound;
+pub mod keystore;
+pub mod load;
+pub mod load_test;
+pub mod log_sinks;
+pub mod membership;
+pub mod merge;
+pub mod migration;
+#[cfg(feature = "mqtt")]
+pub mod mqtt_bridge;
+pub mod names;
 pub mod operator_console;
+pub mod partition;
+pub mod plan_policy;
+pub mod reload;
+pub mod replay;
+pub mod reputation;
+pub mod result_chain;
 pub mod rpc_server;
+pub mod scheduler;
+pub mod shard;
+pub mod storage;
+pub mod swarm_params;
+pub mod task_context;
+pub mod task_index;
+pub mod telemetry;
+pub mod trace_log;
+pub mod transcript;
 pub mod tui;
+pub mod verification;
+pub mod voting_window;
+pub mod webhooks;
+pub mod work_steal;
 
 pub use config::ConnectorConfig;
 pub use connector::WwsConnector;
 pub use file_server::FileServer;
+#[cfg(feature = "grpc")]
+pub use grpc_server::GrpcServer;
 pub use rpc_server::RpcServer;
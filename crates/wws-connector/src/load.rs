@@ -0,0 +1,82 @@
+//! Load-aware subtask assignment.
+//!
+//! Keep-alives carry the sender's [`AgentLoad`]: the tasks it holds, the
+//! spare CPU and memory of its host and the concurrency it declares. A
+//! coordinator hands each directly assigned subtask to the subordinate with
+//! the most room, passing over saturated subordinates while any other has
+//! room. Equally loaded subordinates take turns, as in plain round-robin.
+
+use wws_protocol::AgentLoad;
+
+/// Spare CPU and available memory of this host, as fractions, where the
+/// platform exposes them.
+pub fn host_headroom() -> (Option<f64>, Option<f64>) {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get() as f64)
+        .unwrap_or(1.0);
+    let cpu = std::fs::read_to_string("/proc/loadavg")
+        .ok()
+        .and_then(|s| s.split_whitespace().next()?.parse::<f64>().ok())
+        .map(|load| (1.0 - load / cores).clamp(0.0, 1.0));
+    let mem = std::fs::read_to_string("/proc/meminfo").ok().and_then(|s| {
+        let field = |name: &str| {
+            s.lines()
+                .find_map(|l| l.strip_prefix(name)?.split_whitespace().next()?.parse::<f64>().ok())
+        };
+        let total = field("MemTotal:")?;
+        let available = field("MemAvailable:")?;
+        (total > 0.0).then(|| (available / total).clamp(0.0, 1.0))
+    });
+    (cpu, mem)
+}
+
+/// Index of the agent with the most room in `loads`, scanning from `start`
+/// so that ties go to the next agent in turn.
+pub fn least_loaded(loads: &[AgentLoad], start: usize) -> usize {
+    let n = loads.len().max(1);
+    (0..loads.len())
+        .map(|i| (start + i) % n)
+        .min_by(|&a, &b| {
+            let (a, b) = (&loads[a], &loads[b]);
+            a.is_saturated()
+                .cmp(&b.is_saturated())
+                .then(a.utilization().total_cmp(&b.utilization()))
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn busy_subordinates_are_passed_over() {
+        let idle = AgentLoad::default();
+        let busy = AgentLoad {
+            in_flight: 2,
+            max_concurrency: Some(2),
+            ..AgentLoad::default()
+        };
+        let roomy = AgentLoad {
+            in_flight: 2,
+            max_concurrency: Some(8),
+            ..AgentLoad::default()
+        };
+        let starved = AgentLoad {
+            mem_headroom: Some(0.01),
+            ..AgentLoad::default()
+        };
+
+        // Equal loads take turns.
+        assert_eq!(least_loaded(&[idle.clone(), idle.clone()], 0), 0);
+        assert_eq!(least_loaded(&[idle.clone(), idle.clone()], 1), 1);
+        assert_eq!(least_loaded(&[busy.clone(), roomy.clone()], 0), 1);
+        assert_eq!(least_loaded(&[starved.clone(), roomy], 0), 1);
+        // When every agent is saturated the least utilized still gets work.
+        let overloaded = AgentLoad {
+            queue_depth: 3,
+            ..busy.clone()
+        };
+        assert_eq!(least_loaded(&[overloaded, busy], 0), 1);
+    }
+}
@@ -0,0 +1,303 @@
+//! Synthetic vote load against a running connector.
+//!
+//! `wws-connector load-test` casts ballots on synthetic tasks through a
+//! connector's JSON-RPC API from several connections at once. Each ballot
+//! passes through the voting engine and is published on the swarm's voting
+//! topic as an agent's would, so raising the load until rejections or
+//! latency climb shows where the connector and its swarm top out.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use wws_protocol::{ErrorCode, SwarmResponse};
+
+/// Prefix of the local agents registered as extra voters.
+pub const VOTER_PREFIX: &str = "load-voter-";
+
+/// What a load test sends.
+#[derive(Debug, Clone)]
+pub struct LoadTestConfig {
+    /// Connector RPC address.
+    pub rpc_addr: String,
+    /// `auth` token, when the connector requires one.
+    pub auth: Option<String>,
+    /// Synthetic tasks to vote on.
+    pub tasks: usize,
+    /// Ballots per task. The first is cast by the connector's own identity,
+    /// the rest by local agents registered as `load-voter-<n>`.
+    pub voters: usize,
+    /// Plans ranked on each ballot.
+    pub plans: usize,
+    /// Concurrent RPC connections.
+    pub connections: usize,
+}
+
+/// Latency percentiles of accepted and rejected calls alike, in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl LatencySummary {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort();
+        let at = |q: f64| {
+            let index = ((samples.len() as f64 * q).ceil() as usize).clamp(1, samples.len()) - 1;
+            samples[index].as_secs_f64() * 1000.0
+        };
+        Self {
+            p50: at(0.50),
+            p95: at(0.95),
+            p99: at(0.99),
+            max: at(1.0),
+        }
+    }
+}
+
+/// Outcome of a load test.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadReport {
+    pub sent: usize,
+    pub accepted: usize,
+    /// Rejected ballots by error name, or `connection` for calls that got
+    /// no response.
+    pub rejected: BTreeMap<String, usize>,
+    pub elapsed_secs: f64,
+    /// Accepted ballots per second.
+    pub votes_per_sec: f64,
+    pub latency_ms: LatencySummary,
+}
+
+impl std::fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Sent {} ballots in {:.2}s: {} accepted ({:.0} votes/s)",
+            self.sent, self.elapsed_secs, self.accepted, self.votes_per_sec
+        )?;
+        for (reason, count) in &self.rejected {
+            writeln!(f, "  rejected {:<24} {}", reason, count)?;
+        }
+        write!(
+            f,
+            "Latency ms: p50 {:.2}  p95 {:.2}  p99 {:.2}  max {:.2}",
+            self.latency_ms.p50, self.latency_ms.p95, self.latency_ms.p99, self.latency_ms.max
+        )
+    }
+}
+
+/// One newline-delimited JSON-RPC connection.
+struct RpcConnection {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    auth: Option<String>,
+    next_id: u64,
+}
+
+impl RpcConnection {
+    async fn connect(addr: &str, auth: Option<String>) -> anyhow::Result<Self> {
+        let stream = TcpStream::connect(addr)
+            .await
+            .map_err(|e| anyhow::anyhow!("Cannot reach the connector RPC at {}: {}", addr, e))?;
+        stream.set_nodelay(true)?;
+        let (reader, writer) = stream.into_split();
+        Ok(Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+            auth,
+            next_id: 0,
+        })
+    }
+
+    async fn call(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<SwarmResponse> {
+        self.next_id += 1;
+        let mut request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.to_string(),
+            "method": method,
+            "params": params,
+            "signature": "",
+        });
+        if let Some(auth) = &self.auth {
+            request["auth"] = serde_json::json!(auth);
+        }
+        let mut line = request.to_string();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await?;
+        let reply = self
+            .lines
+            .next_line()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Connector closed the RPC connection"))?;
+        Ok(serde_json::from_str(&reply)?)
+    }
+}
+
+/// The rankings `voter` casts: every plan, starting at a different one per
+/// voter so that the instant runoff has rounds to run.
+pub fn ballot(voter: usize, plans: usize) -> Vec<String> {
+    let plans = plans.max(1);
+    (0..plans)
+        .map(|k| format!("load-plan-{}", (voter + k) % plans))
+        .collect()
+}
+
+/// Register the extra voters and return the `local_agent` each ballot is
+/// cast as, `None` for the connector's own identity.
+async fn register_voters(
+    connection: &mut RpcConnection,
+    voters: usize,
+) -> anyhow::Result<Vec<Option<String>>> {
+    let mut agents = vec![None];
+    for n in 1..voters {
+        let response = connection
+            .call(
+                "swarm.register_local_agent",
+                serde_json::json!({ "name": format!("{}{}", VOTER_PREFIX, n) }),
+            )
+            .await?;
+        if let Some(error) = response.error {
+            anyhow::bail!(
+                "Cannot register voter {}: {} ([cluster] max_local_agents must be at least {})",
+                n,
+                error.message,
+                voters - 1
+            );
+        }
+        let did = response
+            .result
+            .as_ref()
+            .and_then(|r| r.get("agent_id"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("swarm.register_local_agent returned no agent_id"))?;
+        agents.push(Some(did.to_string()));
+    }
+    Ok(agents)
+}
+
+/// Cast `config.tasks * config.voters` ballots as fast as the connector
+/// answers and report throughput and latency.
+pub async fn run(config: &LoadTestConfig) -> anyhow::Result<LoadReport> {
+    let mut setup = RpcConnection::connect(&config.rpc_addr, config.auth.clone()).await?;
+    let voters = Arc::new(register_voters(&mut setup, config.voters.max(1)).await?);
+    drop(setup);
+
+    let run_id = uuid::Uuid::new_v4().simple().to_string();
+    let total = config.tasks * voters.len();
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+
+    let mut workers = Vec::new();
+    for _ in 0..config.connections.max(1).min(total.max(1)) {
+        let mut connection = RpcConnection::connect(&config.rpc_addr, config.auth.clone()).await?;
+        let (voters, next, run_id, plans) = (
+            voters.clone(),
+            next.clone(),
+            run_id[..8].to_string(),
+            config.plans,
+        );
+        workers.push(tokio::spawn(async move {
+            let mut latencies = Vec::new();
+            let mut outcomes: BTreeMap<String, usize> = BTreeMap::new();
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= total {
+                    break;
+                }
+                let (task, voter) = (i / voters.len(), i % voters.len());
+                let mut params = serde_json::json!({
+                    "task_id": format!("load-{}-{}", run_id, task),
+                    "rankings": ballot(voter, plans),
+                });
+                if let Some(did) = &voters[voter] {
+                    params["local_agent"] = serde_json::json!(did);
+                }
+                let sent = Instant::now();
+                let outcome = match connection.call("swarm.submit_vote", params).await {
+                    Ok(SwarmResponse { error: None, .. }) => "accepted".to_string(),
+                    Ok(SwarmResponse {
+                        error: Some(error), ..
+                    }) => ErrorCode::from_code(error.code)
+                        .map(|code| code.name().to_string())
+                        .unwrap_or_else(|| error.code.to_string()),
+                    Err(_) => {
+                        *outcomes.entry("connection".to_string()).or_default() += 1;
+                        break;
+                    }
+                };
+                latencies.push(sent.elapsed());
+                *outcomes.entry(outcome).or_default() += 1;
+            }
+            (latencies, outcomes)
+        }));
+    }
+
+    let mut latencies = Vec::with_capacity(total);
+    let mut rejected: BTreeMap<String, usize> = BTreeMap::new();
+    for worker in workers {
+        let (samples, outcomes) = worker.await?;
+        latencies.extend(samples);
+        for (outcome, count) in outcomes {
+            *rejected.entry(outcome).or_default() += count;
+        }
+    }
+    let elapsed = started.elapsed().as_secs_f64();
+    // Workers count accepted ballots alongside the rejections.
+    let accepted = rejected.remove("accepted").unwrap_or(0);
+    Ok(LoadReport {
+        sent: accepted + rejected.values().sum::<usize>(),
+        accepted,
+        rejected,
+        elapsed_secs: elapsed,
+        votes_per_sec: if elapsed > 0.0 {
+            accepted as f64 / elapsed
+        } else {
+            0.0
+        },
+        latency_ms: LatencySummary::from_samples(latencies),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ballots_rotate_first_choices_and_percentiles_are_nearest_rank() {
+        assert_eq!(
+            ballot(0, 3),
+            vec!["load-plan-0", "load-plan-1", "load-plan-2"]
+        );
+        assert_eq!(
+            ballot(4, 3),
+            vec!["load-plan-1", "load-plan-2", "load-plan-0"]
+        );
+
+        let samples = (1..=100).map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_samples(samples);
+        assert_eq!(
+            (summary.p50, summary.p95, summary.p99, summary.max),
+            (50.0, 95.0, 99.0, 100.0)
+        );
+        assert_eq!(
+            LatencySummary::from_samples(Vec::new()),
+            LatencySummary::default()
+        );
+    }
+}
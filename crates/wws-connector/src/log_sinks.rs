@@ -0,0 +1,229 @@
+//! Durable, machine-parseable copies of the event log.
+//!
+//! `push_log` keeps the last 1000 entries in memory for the TUI and the
+//! console. Each `[[logging.sinks]]` entry also receives every entry in the
+//! categories it selects, as one JSON object per entry:
+//!
+//! - `jsonl`: appended to rotating files in `dir`, with the same size and
+//!   age caps as the message trace.
+//! - `syslog`: an RFC 5424 datagram to `address` over UDP, with the JSON
+//!   as the message. Errors are sent with severity `err`, the rest `info`.
+//! - `stdout`: printed as one line. Not available with `--tui` or
+//!   `--console`, which own the terminal.
+
+use std::net::UdpSocket;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::TraceConfig;
+use crate::trace_log::TraceLog;
+use crate::tui::{LogCategory, LogEntry};
+
+/// File name prefix of the JSONL sink's files.
+const EVENT_FILE_PREFIX: &str = "events-";
+
+/// Where a sink writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogSinkKind {
+    Jsonl,
+    Syslog,
+    Stdout,
+}
+
+/// One `[[logging.sinks]]` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogSinkConfig {
+    pub kind: LogSinkKind,
+    /// Categories written, by label (`"ERR"`) or name (`"Error"`); all of
+    /// them when empty.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Syslog receiver as `host:port`.
+    #[serde(default = "default_syslog_address")]
+    pub address: String,
+    /// Directory and rotation of the `jsonl` sink's files.
+    #[serde(flatten)]
+    pub file: TraceConfig,
+}
+
+fn default_syslog_address() -> String {
+    "127.0.0.1:514".to_string()
+}
+
+/// An open sink and the categories it takes.
+#[derive(Debug)]
+pub struct LogSink {
+    kind: LogSinkKind,
+    /// Categories written; all of them when `None`.
+    categories: Option<Vec<LogCategory>>,
+    output: SinkOutput,
+}
+
+#[derive(Debug)]
+enum SinkOutput {
+    File(TraceLog),
+    Syslog { socket: UdpSocket, hostname: String },
+    Stdout,
+}
+
+impl LogSink {
+    /// Open the sink described by `config`. Unknown categories and a
+    /// `jsonl` sink without `dir` are errors.
+    pub fn open(config: &LogSinkConfig) -> Result<Self, String> {
+        let categories = if config.categories.is_empty() {
+            None
+        } else {
+            Some(
+                config
+                    .categories
+                    .iter()
+                    .map(|name| {
+                        LogCategory::parse(name)
+                            .ok_or_else(|| format!("unknown log category '{}'", name))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        };
+        let output = match config.kind {
+            LogSinkKind::Jsonl => {
+                let dir = config
+                    .file
+                    .dir
+                    .as_ref()
+                    .ok_or_else(|| "a jsonl log sink needs `dir`".to_string())?;
+                let log = TraceLog::open_with_prefix(dir, EVENT_FILE_PREFIX, &config.file)
+                    .map_err(|e| format!("cannot open {}: {}", dir.display(), e))?;
+                SinkOutput::File(log)
+            }
+            LogSinkKind::Syslog => {
+                let socket = UdpSocket::bind("0.0.0.0:0")
+                    .and_then(|socket| socket.connect(&config.address).map(|_| socket))
+                    .map_err(|e| format!("cannot reach syslog at {}: {}", config.address, e))?;
+                SinkOutput::Syslog {
+                    socket,
+                    hostname: std::env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string()),
+                }
+            }
+            LogSinkKind::Stdout => SinkOutput::Stdout,
+        };
+        Ok(Self {
+            kind: config.kind,
+            categories,
+            output,
+        })
+    }
+
+    pub fn kind(&self) -> LogSinkKind {
+        self.kind
+    }
+
+    /// Whether this sink takes entries of `category`.
+    pub fn wants(&self, category: LogCategory) -> bool {
+        self.categories
+            .as_ref()
+            .is_none_or(|categories| categories.contains(&category))
+    }
+
+    /// Write `entry`, if this sink takes its category.
+    pub fn write(&mut self, entry: &LogEntry) -> std::io::Result<()> {
+        if !self.wants(entry.category) {
+            return Ok(());
+        }
+        match &mut self.output {
+            SinkOutput::File(log) => log.append(entry),
+            SinkOutput::Syslog { socket, hostname } => {
+                socket.send(syslog_line(hostname, entry)?.as_bytes())?;
+                Ok(())
+            }
+            SinkOutput::Stdout => {
+                let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+                println!("{}", line);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `entry` as an RFC 5424 message from facility `user`.
+fn syslog_line(hostname: &str, entry: &LogEntry) -> std::io::Result<String> {
+    const FACILITY_USER: u8 = 1;
+    let severity = match entry.category {
+        LogCategory::Error => 3,
+        _ => 6,
+    };
+    let json = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    Ok(format!(
+        "<{}>1 {} {} wws-connector {} {} - {}",
+        FACILITY_USER * 8 + severity,
+        entry.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        hostname,
+        std::process::id(),
+        entry.category.label(),
+        json
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(category: LogCategory, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::Utc::now(),
+            category,
+            message: message.to_string(),
+        }
+    }
+
+    fn config(kind: LogSinkKind, categories: &[&str]) -> LogSinkConfig {
+        LogSinkConfig {
+            kind,
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+            address: default_syslog_address(),
+            file: TraceConfig::default(),
+        }
+    }
+
+    #[test]
+    fn jsonl_sink_keeps_only_its_categories() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = config(LogSinkKind::Jsonl, &["ERR", "task"]);
+        config.file.dir = Some(dir.path().to_path_buf());
+        let mut sink = LogSink::open(&config).unwrap();
+
+        sink.write(&entry(LogCategory::Task, "Task t1 is blocked")).unwrap();
+        sink.write(&entry(LogCategory::Peer, "Connected: 12D3KooW")).unwrap();
+        sink.write(&entry(LogCategory::Error, "Publish failed")).unwrap();
+
+        let files: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        let text = std::fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+        let entries: Vec<LogEntry> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "Task t1 is blocked");
+        assert_eq!(entries[1].category, LogCategory::Error);
+    }
+
+    #[test]
+    fn sink_config_is_checked_when_opened() {
+        assert!(LogSink::open(&config(LogSinkKind::Jsonl, &[])).is_err());
+        assert!(LogSink::open(&config(LogSinkKind::Stdout, &["NOISE"])).is_err());
+        let sink = LogSink::open(&config(LogSinkKind::Stdout, &[])).unwrap();
+        assert!(sink.wants(LogCategory::Vote));
+    }
+
+    #[test]
+    fn syslog_lines_follow_rfc_5424() {
+        let line = syslog_line("node-1", &entry(LogCategory::Error, "boom")).unwrap();
+        assert!(line.starts_with("<11>1 "));
+        assert!(line.contains(" node-1 wws-connector "));
+        assert!(line.contains(" ERR - {"));
+        assert!(line.ends_with("\"message\":\"boom\"}"));
+        let info = syslog_line("-", &entry(LogCategory::Task, "ok")).unwrap();
+        assert!(info.starts_with("<14>1 "));
+    }
+}
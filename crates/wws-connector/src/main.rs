@@ -7,20 +7,44 @@
 //!   -c, --config <FILE>    Path to configuration TOML file
 //!   -l, --listen <ADDR>    P2P listen address (overrides config)
 //!   -r, --rpc <ADDR>       RPC bind address (overrides config)
+//!   --grpc <ADDR>          Serve the gRPC API on this address
 //!   -b, --bootstrap <ADDR> Bootstrap peer multiaddress (repeatable)
 //!   -v, --verbose          Increase logging verbosity
 //!   --agent-name <NAME>    Set the agent name
 //!   --tui                  Launch the TUI monitoring dashboard
 //!   --console              Launch the operator console (interactive task injection + hierarchy)
+//!   --script <FILE>        Run console commands from FILE (`-` for stdin), then exit
+//!   --transcript <FILE>    Write the script transcript here instead of stdout
+//!   --echo-agent           Answer tasks with the built-in echo agent
+//!   --daemon               Run headless under a service manager, restarting on failure
+//!   --pid-file <FILE>      Pid file written in daemon mode
+//!
+//! Commands:
+//!   trace                  Query the persisted message trace offline
+//!   replay                 Re-run recorded inbound messages against a fresh state
+//!   keystore               Manage the encrypted keystore of node secrets
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 
 use wws_connector::config::ConnectorConfig;
 use wws_connector::connector::WwsConnector;
+use wws_connector::daemon::{PidFile, RestartBackoff, TaskGuard};
+use wws_connector::keystore::{KdfParams, Keystore};
+use wws_connector::load_test::{self, LoadTestConfig};
+use wws_connector::echo_agent::EchoAgent;
 use wws_connector::file_server::FileServer;
+#[cfg(feature = "grpc")]
+use wws_connector::grpc_server::GrpcServer;
+#[cfg(feature = "mqtt")]
+use wws_connector::mqtt_bridge::MqttBridge;
+use wws_connector::reload::ConfigLoader;
+use wws_connector::replay::{diff_runs, recorded_messages};
 use wws_connector::rpc_server::RpcServer;
+use wws_connector::trace_log::{read_trace, TraceQuery};
+use wws_connector::transcript::RunTranscript;
 
 /// WWS.Connector - Sidecar process connecting AI agents to the swarm.
 #[derive(Parser, Debug)]
@@ -72,6 +96,15 @@ struct Cli {
     #[arg(long)]
     console: bool,
 
+    /// Run the operator console commands in this file (`-` for stdin)
+    /// without the TUI, writing a transcript, and exit when done.
+    #[arg(long, value_name = "FILE", conflicts_with = "tui")]
+    script: Option<PathBuf>,
+
+    /// Write the `--script` transcript to this file instead of stdout.
+    #[arg(long, value_name = "FILE")]
+    transcript: Option<PathBuf>,
+
     /// HTTP file server bind address for serving agent onboarding docs.
     #[arg(long, value_name = "ADDR")]
     files_addr: Option<String>,
@@ -80,70 +113,288 @@ struct Cli {
     #[arg(long)]
     no_files: bool,
 
+    /// Serve the gRPC API on this address (enables `[grpc]`).
+    #[arg(long, value_name = "ADDR")]
+    grpc: Option<String>,
+
     /// Register a wws:// name for this agent (e.g. 'alice' → wws:alice).
     #[arg(long, value_name = "NAME")]
     wws_name: Option<String>,
 
     /// Path to the identity key file (default: ~/.wws/identity.key).
-    #[arg(long, value_name = "PATH")]
+    /// The agent's DID is derived from this key, so it survives restarts.
+    #[arg(long, visible_alias = "key-file", value_name = "PATH")]
     identity_path: Option<String>,
 
+    /// Start under a new identity: the key file is replaced by a fresh key
+    /// (the old one is kept beside it as `<file>.<unix time>.old`).
+    #[arg(long)]
+    new_identity: bool,
+
     /// Run as a public bootstrap node (no agent bridge, high capacity).
     #[arg(long)]
     bootstrap_mode: bool,
+
+    /// Answer tasks with the built-in echo agent (canned plans, votes and
+    /// results), to watch the consensus pipeline without a real agent.
+    #[arg(long)]
+    echo_agent: bool,
+
+    /// Run headless under a service manager (systemd, launchd, a Windows
+    /// service wrapper): restart the connector when it fails and reload
+    /// the configuration on SIGHUP.
+    #[arg(long, conflicts_with_all = ["tui", "console", "script"])]
+    daemon: bool,
+
+    /// Pid file written while running as a daemon (overrides `[daemon] pid_file`).
+    #[arg(long, value_name = "FILE", requires = "daemon")]
+    pid_file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+/// Tools that run without starting the connector.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Query the message trace persisted under `[trace] dir`.
+    Trace(TraceArgs),
+    /// Feed the inbound messages recorded with `[trace] record_payloads`
+    /// through a fresh connector and report the tasks it ends up with.
+    Replay(ReplayArgs),
+    /// Manage the encrypted keystore under `[keystore] path`.
+    Keystore(KeystoreArgs),
+    /// Flood a running connector with synthetic votes and report the
+    /// throughput and latency it sustains.
+    LoadTest(LoadTestArgs),
+}
 
-    // Load configuration.
-    let mut config = ConnectorConfig::load(cli.config.as_deref())?;
+#[derive(Args, Debug)]
+struct LoadTestArgs {
+    /// Connector RPC address (default: `[rpc] bind_addr` from the configuration).
+    #[arg(long, value_name = "ADDR")]
+    rpc: Option<String>,
+
+    /// RPC client token, when the connector has `[[rpc.clients]]`.
+    #[arg(long, value_name = "TOKEN")]
+    auth: Option<String>,
+
+    /// Synthetic tasks to vote on.
+    #[arg(long, default_value_t = 100)]
+    tasks: usize,
+
+    /// Ballots per task. Voters past the first are local agents, so
+    /// `[cluster] max_local_agents` must allow VOTERS - 1.
+    #[arg(long, default_value_t = 1)]
+    voters: usize,
+
+    /// Plans ranked on each ballot.
+    #[arg(long, default_value_t = 3)]
+    plans: usize,
 
-    // Apply CLI overrides.
-    if let Some(listen) = cli.listen {
-        config.network.listen_addr = listen;
+    /// Concurrent RPC connections.
+    #[arg(long, default_value_t = 8)]
+    connections: usize,
+
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct KeystoreArgs {
+    /// Keystore file (default: `[keystore] path` from the configuration).
+    #[arg(long, value_name = "FILE")]
+    path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    action: KeystoreAction,
+}
+
+#[derive(Subcommand, Debug)]
+enum KeystoreAction {
+    /// Create an empty keystore.
+    Init,
+    /// List the entry names; values are never printed.
+    List,
+    /// Move the identity key file (`[identity] path`) into the keystore.
+    ImportIdentity {
+        /// Delete the plaintext key file once the keystore is written.
+        #[arg(long)]
+        remove_plaintext: bool,
+    },
+    /// Replace the identity with a fresh key, keeping the old one as
+    /// `identity.old.<unix time>`. The node's DID and PeerId change.
+    RotateIdentity,
+    /// Store or replace the token of a private swarm, read from the
+    /// terminal or with `--stdin` from standard input.
+    SetToken {
+        swarm_id: String,
+        /// Read the token from the first line of standard input.
+        #[arg(long)]
+        stdin: bool,
+    },
+    /// Delete an entry.
+    Remove { name: String },
+    /// Re-encrypt the keystore under a new passphrase.
+    ChangePassphrase {
+        /// Read the new passphrase from this file instead of the terminal.
+        #[arg(long, value_name = "FILE")]
+        new_passphrase_file: Option<PathBuf>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct TraceArgs {
+    /// Trace directory (default: `[trace] dir` from the configuration).
+    #[arg(long, value_name = "DIR")]
+    dir: Option<PathBuf>,
+
+    /// Only events at or after this time: RFC 3339, or an age such as 30m, 2h or 1d.
+    #[arg(long, value_name = "TIME")]
+    since: Option<String>,
+
+    /// Only events at or before this time: RFC 3339, or an age such as 30m, 2h or 1d.
+    #[arg(long, value_name = "TIME")]
+    until: Option<String>,
+
+    /// Only messages with this protocol method (e.g. task.assign).
+    #[arg(long)]
+    method: Option<String>,
+
+    /// Only messages about this task.
+    #[arg(long, value_name = "TASK_ID")]
+    task: Option<String>,
+
+    /// Only messages from or to this peer.
+    #[arg(long)]
+    peer: Option<String>,
+
+    /// Only inbound or outbound messages.
+    #[arg(long)]
+    direction: Option<String>,
+
+    /// Print only the last N matching events.
+    #[arg(short = 'n', long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Print events as JSON lines.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct ReplayArgs {
+    /// Trace directory (default: `[trace] dir` from the configuration).
+    #[arg(long, value_name = "DIR")]
+    dir: Option<PathBuf>,
+
+    /// Only messages received at or after this time: RFC 3339, or an age such as 30m, 2h or 1d.
+    #[arg(long, value_name = "TIME")]
+    since: Option<String>,
+
+    /// Only messages received at or before this time: RFC 3339, or an age such as 30m, 2h or 1d.
+    #[arg(long, value_name = "TIME")]
+    until: Option<String>,
+
+    /// Stop after the first N messages, to narrow down where a run diverged.
+    #[arg(long, value_name = "N")]
+    stop_after: Option<usize>,
+
+    /// Transcript written by `swarm.export_run` on the original node; the
+    /// replayed task tree is compared with it.
+    #[arg(long, value_name = "FILE")]
+    expect: Option<PathBuf>,
+
+    /// Print the result as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Parse `--since`/`--until`: an RFC 3339 time, or an age before now.
+fn parse_time(value: &str) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&chrono::Utc));
     }
-    if let Some(rpc) = cli.rpc {
-        config.rpc.bind_addr = rpc;
+    let (amount, unit) = value.split_at(value.len().saturating_sub(1));
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid time '{}': use RFC 3339 or an age like 2h", value))?;
+    let age = match unit {
+        "s" => chrono::Duration::seconds(amount),
+        "m" => chrono::Duration::minutes(amount),
+        "h" => chrono::Duration::hours(amount),
+        "d" => chrono::Duration::days(amount),
+        _ => anyhow::bail!("Invalid time '{}': use RFC 3339 or an age like 2h", value),
+    };
+    Ok(chrono::Utc::now() - age)
+}
+
+/// Apply the command-line overrides to a freshly loaded configuration.
+fn apply_overrides(cli: &Cli, config: &mut ConnectorConfig) {
+    if let Some(listen) = &cli.listen {
+        config.network.listen_addr = listen.clone();
+    }
+    if let Some(rpc) = &cli.rpc {
+        config.rpc.bind_addr = rpc.clone();
     }
     if !cli.bootstrap.is_empty() {
-        config.network.bootstrap_peers = cli.bootstrap;
+        config.network.bootstrap_peers = cli.bootstrap.clone();
     }
-    if let Some(name) = cli.agent_name {
-        config.agent.name = name;
+    if let Some(name) = &cli.agent_name {
+        config.agent.name = name.clone();
     }
-    if let Some(swarm_id) = cli.swarm_id {
-        config.swarm.swarm_id = swarm_id;
+    if let Some(swarm_id) = &cli.swarm_id {
+        config.swarm.swarm_id = swarm_id.clone();
     }
-    if let Some(token) = cli.swarm_token {
-        config.swarm.token = Some(token);
+    if let Some(token) = &cli.swarm_token {
+        config.swarm.token = Some(token.clone());
     }
-    if let Some(name) = cli.create_swarm {
+    if let Some(name) = &cli.create_swarm {
         // When creating a new swarm, generate a new swarm ID and mark it as private.
         config.swarm.swarm_id = uuid::Uuid::new_v4().to_string();
-        config.swarm.name = name;
+        config.swarm.name = name.clone();
         config.swarm.is_public = false;
     }
-    if let Some(addr) = cli.files_addr {
-        config.file_server.bind_addr = addr;
+    if let Some(addr) = &cli.files_addr {
+        config.file_server.bind_addr = addr.clone();
     }
     if cli.no_files {
         config.file_server.enabled = false;
     }
+    if let Some(addr) = &cli.grpc {
+        config.grpc.bind_addr = addr.clone();
+        config.grpc.enabled = true;
+    }
+    if cli.echo_agent {
+        config.echo_agent.enabled = true;
+    }
+    #[cfg(feature = "mqtt")]
+    if config.mqtt.enabled {
+        // So are the agents the MQTT bridge acts for.
+        config.cluster.max_local_agents = config
+            .cluster
+            .max_local_agents
+            .max(config.mqtt.agents.len());
+    }
+    if config.echo_agent.enabled {
+        // The echo agent's extra agents are ordinary local agents.
+        config.cluster.max_local_agents = config
+            .cluster
+            .max_local_agents
+            .max(config.echo_agent.local_agents);
+    }
     if cli.bootstrap_mode {
         config.network.bootstrap_mode = true;
         config.network.enable_relay_server = true;
-        tracing::info!("Starting in BOOTSTRAP MODE");
-        eprintln!("=== Bootstrap Node Mode ===");
     }
 
     // Apply identity CLI overrides.
-    if let Some(path) = cli.identity_path {
+    if let Some(path) = &cli.identity_path {
         config.identity.path = std::path::PathBuf::from(path);
     }
-    if let Some(name) = cli.wws_name {
-        config.identity.wws_name = Some(name);
+    if let Some(name) = &cli.wws_name {
+        config.identity.wws_name = Some(name.clone());
     }
     // If no explicit identity path was set but --agent-name was given,
     // use ~/.wws/<agent-name>.key as the identity file.
@@ -153,12 +404,564 @@ async fn main() -> anyhow::Result<()> {
                 wws_connector::config::default_identity_dir().join(format!("{}.key", config.agent.name));
         }
     }
+}
+
+/// Start the RPC and gRPC servers, the MQTT bridge, the HTTP file server
+/// and the echo agent for `connector`. They stop when the returned guard
+/// is dropped.
+fn start_services(config: &ConnectorConfig, connector: &WwsConnector) -> TaskGuard {
+    let mut services = TaskGuard::default();
+    let state = connector.shared_state();
+
+    // Start the RPC server in a background task.
+    let rpc_server = RpcServer::new(
+        config.rpc.bind_addr.clone(),
+        state.clone(),
+        connector.network_handle(),
+        config.rpc.max_connections,
+    )
+    .with_clients(config.rpc.clients.clone());
+    let handle = tokio::spawn(async move {
+        if let Err(e) = rpc_server.run().await {
+            tracing::error!(error = %e, "RPC server error");
+        }
+    });
+    services.push(handle.abort_handle());
+
+    // Start the gRPC server if enabled.
+    #[cfg(feature = "grpc")]
+    if config.grpc.enabled {
+        let grpc_server = GrpcServer::new(
+            config.grpc.bind_addr.clone(),
+            state.clone(),
+            connector.network_handle(),
+        )
+        .with_clients(config.rpc.clients.clone());
+        let handle = tokio::spawn(async move {
+            if let Err(e) = grpc_server.run().await {
+                tracing::error!(error = %e, "gRPC server error");
+            }
+        });
+        services.push(handle.abort_handle());
+    }
+    #[cfg(not(feature = "grpc"))]
+    if config.grpc.enabled {
+        tracing::warn!("[grpc] is enabled, but this build has no gRPC server; rebuild with --features grpc");
+    }
+
+    // Start the MQTT bridge if enabled.
+    #[cfg(feature = "mqtt")]
+    if config.mqtt.enabled {
+        let mqtt_bridge = MqttBridge::new(config.mqtt.clone(), state.clone(), connector.network_handle())
+            .with_clients(config.rpc.clients.clone());
+        let handle = tokio::spawn(async move {
+            if let Err(e) = mqtt_bridge.run().await {
+                tracing::error!(error = %e, "MQTT bridge error");
+            }
+        });
+        services.push(handle.abort_handle());
+    }
+    #[cfg(not(feature = "mqtt"))]
+    if config.mqtt.enabled {
+        tracing::warn!("[mqtt] is enabled, but this build has no MQTT bridge; rebuild with --features mqtt");
+    }
+
+    // Start the HTTP file server if enabled.
+    if config.file_server.enabled {
+        let file_server = FileServer::new(
+            config.file_server.bind_addr.clone(),
+            state.clone(),
+            connector.network_handle(),
+        );
+        let handle = tokio::spawn(async move {
+            if let Err(e) = file_server.run().await {
+                tracing::error!(error = %e, "HTTP file server error");
+            }
+        });
+        services.push(handle.abort_handle());
+    }
+
+    // Start the built-in echo agent if enabled.
+    if config.echo_agent.enabled {
+        let echo_agent = EchoAgent::new(config.echo_agent.clone(), state, connector.network_handle());
+        let handle = tokio::spawn(async move {
+            if let Err(e) = echo_agent.run().await {
+                tracing::error!(error = %e, "Echo agent error");
+            }
+        });
+        services.push(handle.abort_handle());
+    }
+    services
+}
+
+/// `--daemon`: run the connector under [`wws_connector::daemon::supervise`].
+/// Every restart after the first reloads the configuration file, keeping
+/// the previous configuration if the file no longer loads. With a keystore
+/// the identity unlocked at startup is kept, so restarts need no passphrase.
+async fn run_daemon(
+    cli: &Arc<Cli>,
+    config: ConnectorConfig,
+    keypair: ed25519_dalek::SigningKey,
+    keystore: Option<Arc<Keystore>>,
+) -> anyhow::Result<()> {
+    let pid_file = cli.pid_file.clone().or_else(|| config.daemon.pid_file.clone());
+    let _pid_file = pid_file
+        .as_deref()
+        .map(PidFile::create)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Failed to write pid file: {e}"))?;
+    let backoff = RestartBackoff::new(
+        std::time::Duration::from_secs(config.daemon.restart_backoff_secs),
+        std::time::Duration::from_secs(config.daemon.max_restart_backoff_secs),
+    );
+
+    let mut current = config;
+    let mut first_keypair = Some(keypair.clone());
+    wws_connector::daemon::supervise(
+        |restarts| {
+            let keypair = match first_keypair.take() {
+                Some(keypair) => Ok(keypair),
+                None => {
+                    match reload_config(cli, &current.swarm.swarm_id, keystore.as_deref()) {
+                        Ok(reloaded) => current = reloaded,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Failed to reload configuration; keeping the previous one");
+                        }
+                    }
+                    if keystore.is_some() {
+                        Ok(keypair.clone())
+                    } else {
+                        wws_protocol::crypto::load_or_create_keypair(&current.identity.path)
+                            .map_err(|e| anyhow::anyhow!("Failed to load identity keypair: {e}"))
+                    }
+                }
+            };
+            let config = current.clone();
+            let loader = config_loader(cli, &config, keystore.as_ref());
+            async move {
+                let connector = WwsConnector::with_identity(config.clone(), keypair?)?;
+                {
+                    let state = connector.shared_state();
+                    let mut state = state.write().await;
+                    state.restarts = restarts;
+                    state.config_reload.set_loader(loader);
+                }
+                let _services = start_services(&config, &connector);
+                connector.run().await
+            }
+        },
+        backoff,
+    )
+    .await
+}
+
+/// Re-read the configuration the way `main` loaded it. A swarm created
+/// with `--create-swarm` keeps the ID it was given at startup, and the
+/// swarm token is looked up in the keystore unlocked at startup.
+fn reload_config(
+    cli: &Cli,
+    swarm_id: &str,
+    keystore: Option<&Keystore>,
+) -> anyhow::Result<ConnectorConfig> {
+    let mut config = ConnectorConfig::load(cli.config.as_deref())?;
+    apply_overrides(cli, &mut config);
+    if cli.create_swarm.is_some() {
+        config.swarm.swarm_id = swarm_id.to_string();
+    }
+    fill_swarm_token(&mut config, keystore)?;
+    Ok(config)
+}
+
+/// The loader `swarm.reload_config` uses for a connector running `config`.
+fn config_loader(
+    cli: &Arc<Cli>,
+    config: &ConnectorConfig,
+    keystore: Option<&Arc<Keystore>>,
+) -> ConfigLoader {
+    let cli = Arc::clone(cli);
+    let swarm_id = config.swarm.swarm_id.clone();
+    let keystore = keystore.cloned();
+    Arc::new(move || reload_config(&cli, &swarm_id, keystore.as_deref()))
+}
+
+/// Unlock the keystore named by `[keystore] path`, if any.
+fn open_keystore(config: &ConnectorConfig) -> anyhow::Result<Option<Keystore>> {
+    let Some(path) = &config.keystore.path else {
+        return Ok(None);
+    };
+    let passphrase = wws_connector::keystore::read_passphrase(
+        config.keystore.passphrase_file.as_deref(),
+        false,
+    )?;
+    let keystore = Keystore::open(path, &passphrase)
+        .map_err(|e| anyhow::anyhow!("Failed to open keystore {}: {e}", path.display()))?;
+    Ok(Some(keystore))
+}
+
+/// Take the swarm token from the keystore when neither the configuration,
+/// the environment nor `--swarm-token` gave one.
+fn fill_swarm_token(config: &mut ConnectorConfig, keystore: Option<&Keystore>) -> anyhow::Result<()> {
+    if let (None, Some(keystore)) = (&config.swarm.token, keystore) {
+        config.swarm.token = keystore.swarm_token(&config.swarm.swarm_id)?;
+    }
+    Ok(())
+}
+
+/// Load (or create) the persistent Ed25519 identity keypair: from the
+/// keystore when one is configured, otherwise from `[identity] path`.
+/// `--new-identity` replaces it and keeps the old key.
+fn load_identity(
+    cli: &Cli,
+    config: &ConnectorConfig,
+    keystore: Option<&mut Keystore>,
+) -> anyhow::Result<ed25519_dalek::SigningKey> {
+    let Some(keystore) = keystore else {
+        tracing::info!(path = %config.identity.path.display(), "Loading agent identity");
+        eprintln!("Identity: {}", config.identity.path.display());
+        if cli.new_identity {
+            let (keypair, backup) = wws_protocol::crypto::replace_keypair(&config.identity.path)
+                .map_err(|e| anyhow::anyhow!("Failed to create new identity keypair: {e}"))?;
+            if let Some(backup) = backup {
+                eprintln!("New identity; previous key moved to {}", backup.display());
+            }
+            return Ok(keypair);
+        }
+        return wws_protocol::crypto::load_or_create_keypair(&config.identity.path)
+            .map_err(|e| anyhow::anyhow!("Failed to load identity keypair: {e}"));
+    };
+
+    if let Some(path) = &config.keystore.path {
+        eprintln!("Identity: keystore {}", path.display());
+    }
+    if !cli.new_identity {
+        if let Some(keypair) = keystore.identity()? {
+            return Ok(keypair);
+        }
+        if config.identity.path.exists() {
+            eprintln!(
+                "The keystore holds no identity; using {} (move it in with `wws-connector keystore import-identity`)",
+                config.identity.path.display()
+            );
+            return wws_protocol::crypto::load_or_create_keypair(&config.identity.path)
+                .map_err(|e| anyhow::anyhow!("Failed to load identity keypair: {e}"));
+        }
+    }
+    let (keypair, old) = keystore.rotate_identity()?;
+    keystore.save()?;
+    if let Some(old) = old {
+        eprintln!("New identity; previous key kept in the keystore as {}", old);
+    }
+    Ok(keypair)
+}
+
+/// `wws-connector keystore`: create the keystore and manage its entries.
+fn run_keystore(args: KeystoreArgs, config: &ConnectorConfig) -> anyhow::Result<()> {
+    let path = args
+        .path
+        .or_else(|| config.keystore.path.clone())
+        .ok_or_else(|| anyhow::anyhow!("No keystore: set [keystore] path or pass --path"))?;
+    let passphrase_file = config.keystore.passphrase_file.as_deref();
+    if let KeystoreAction::Init = args.action {
+        let passphrase = wws_connector::keystore::read_passphrase(passphrase_file, true)?;
+        Keystore::create(&path, &passphrase, KdfParams::default())?;
+        println!("Created keystore {}", path.display());
+        return Ok(());
+    }
+    let passphrase = wws_connector::keystore::read_passphrase(passphrase_file, false)?;
+    let mut keystore = Keystore::open(&path, &passphrase)?;
+    match args.action {
+        KeystoreAction::Init => unreachable!("handled above"),
+        KeystoreAction::List => {
+            for name in keystore.names() {
+                println!("{}", name);
+            }
+            return Ok(());
+        }
+        KeystoreAction::ImportIdentity { remove_plaintext } => {
+            let key_file = &config.identity.path;
+            if keystore.identity()?.is_some() {
+                anyhow::bail!("The keystore already holds an identity; use rotate-identity or remove it first");
+            }
+            if !key_file.exists() {
+                anyhow::bail!("No identity key file at {}", key_file.display());
+            }
+            let keypair = wws_protocol::crypto::load_or_create_keypair(key_file)
+                .map_err(|e| anyhow::anyhow!("Failed to load identity keypair: {e}"))?;
+            keystore.set_identity(&keypair)?;
+            keystore.save()?;
+            if remove_plaintext {
+                std::fs::remove_file(key_file)?;
+            }
+            println!(
+                "Imported {} ({})",
+                key_file.display(),
+                wws_protocol::crypto::derive_agent_id(&keypair.verifying_key())
+            );
+        }
+        KeystoreAction::RotateIdentity => {
+            let (keypair, old) = keystore.rotate_identity()?;
+            keystore.save()?;
+            println!(
+                "New identity {}",
+                wws_protocol::crypto::derive_agent_id(&keypair.verifying_key())
+            );
+            if let Some(old) = old {
+                println!("Previous key kept as {}", old);
+            }
+        }
+        KeystoreAction::SetToken { swarm_id, stdin } => {
+            let token = if stdin {
+                let mut line = zeroize::Zeroizing::new(String::new());
+                std::io::stdin().read_line(&mut line)?;
+                zeroize::Zeroizing::new(line.trim_end_matches(['\r', '\n']).to_string())
+            } else {
+                zeroize::Zeroizing::new(rpassword::prompt_password("Swarm token: ")?)
+            };
+            if token.is_empty() {
+                anyhow::bail!("Empty swarm token");
+            }
+            let name = wws_connector::keystore::swarm_token_entry(&swarm_id);
+            keystore.set(&name, token.as_bytes())?;
+            keystore.save()?;
+            println!("Stored {}", name);
+        }
+        KeystoreAction::Remove { name } => {
+            if !keystore.remove(&name) {
+                anyhow::bail!("No entry named {}", name);
+            }
+            keystore.save()?;
+            println!("Removed {}", name);
+        }
+        KeystoreAction::ChangePassphrase {
+            new_passphrase_file,
+        } => {
+            let passphrase = match new_passphrase_file {
+                Some(file) => wws_connector::keystore::passphrase_from_file(&file)?,
+                None => wws_connector::keystore::prompt_passphrase(true)?,
+            };
+            keystore.change_passphrase(&passphrase)?;
+            keystore.save()?;
+            println!("Changed the passphrase of {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Let `swarm.reload_config` change the log level through `handle`.
+fn install_log_filter_hook<S: 'static>(
+    handle: tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, S>,
+) {
+    wws_connector::reload::install_log_filter_hook(move |level| {
+        let filter = tracing_subscriber::EnvFilter::try_new(level).map_err(|e| e.to_string())?;
+        handle.reload(filter).map_err(|e| e.to_string())
+    });
+}
+
+/// `wws-connector trace`: print past traffic from the trace files.
+fn run_trace(args: TraceArgs, config: &ConnectorConfig) -> anyhow::Result<()> {
+    let dir = args
+        .dir
+        .or_else(|| config.trace.dir.clone())
+        .ok_or_else(|| anyhow::anyhow!("No trace directory: pass --dir or set [trace] dir"))?;
+    let query = TraceQuery {
+        since: args.since.as_deref().map(parse_time).transpose()?,
+        until: args.until.as_deref().map(parse_time).transpose()?,
+        method: args.method,
+        task_id: args.task,
+        peer: args.peer,
+        direction: args.direction,
+        limit: args.limit,
+    };
+    let events = read_trace(&dir, &query)
+        .map_err(|e| anyhow::anyhow!("Failed to read trace in {}: {}", dir.display(), e))?;
+    for event in events {
+        if args.json {
+            println!("{}", serde_json::to_string(&event)?);
+        } else {
+            println!(
+                "{} {:<8} {:<24} task={} peer={} {}B {} {}",
+                event.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                event.direction,
+                event.method.as_deref().unwrap_or("-"),
+                event.task_id.as_deref().unwrap_or("-"),
+                event.peer.as_deref().unwrap_or("-"),
+                event.size_bytes,
+                event.outcome,
+                event.topic,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `wws-connector replay`: run recorded inbound traffic through a fresh
+/// connector with this node's identity, print the tasks it ends up with and
+/// fail if they differ from `--expect`.
+async fn run_replay(args: ReplayArgs, mut config: ConnectorConfig) -> anyhow::Result<()> {
+    let dir = args
+        .dir
+        .or_else(|| config.trace.dir.clone())
+        .ok_or_else(|| anyhow::anyhow!("No trace directory: pass --dir or set [trace] dir"))?;
+    let query = TraceQuery {
+        since: args.since.as_deref().map(parse_time).transpose()?,
+        until: args.until.as_deref().map(parse_time).transpose()?,
+        direction: Some("inbound".to_string()),
+        ..TraceQuery::default()
+    };
+    let events = read_trace(&dir, &query)
+        .map_err(|e| anyhow::anyhow!("Failed to read trace in {}: {}", dir.display(), e))?;
+    let mut messages = recorded_messages(events);
+    if let Some(n) = args.stop_after {
+        messages.truncate(n);
+    }
+    if messages.is_empty() {
+        anyhow::bail!(
+            "No recorded messages in {}: the node must run with [trace] record_payloads",
+            dir.display()
+        );
+    }
+    let expected = match &args.expect {
+        Some(path) => {
+            let transcript: RunTranscript = serde_json::from_slice(&std::fs::read(path)?)
+                .map_err(|e| anyhow::anyhow!("Invalid transcript {}: {}", path.display(), e))?;
+            transcript.validate().map_err(|e| anyhow::anyhow!(e))?;
+            Some(transcript)
+        }
+        None => None,
+    };
+
+    // The replaying connector has no peers and writes nothing to disk.
+    config.network.listen_addr = "/ip4/127.0.0.1/tcp/0".to_string();
+    config.network.bootstrap_peers.clear();
+    config.network.mdns_enabled = false;
+    config.network.enable_relay_server = false;
+    config.trace.dir = None;
+    config.storage.content_dir = None;
+    config.logging.sinks.clear();
+    config.webhooks.clear();
+    config.telemetry.otlp_endpoint = None;
+    let stored = match open_keystore(&config)? {
+        Some(keystore) => keystore.identity()?,
+        None => None,
+    };
+    let keypair = if let Some(keypair) = stored {
+        keypair
+    } else if config.identity.path.exists() {
+        wws_protocol::crypto::load_or_create_keypair(&config.identity.path)
+            .map_err(|e| anyhow::anyhow!("Failed to load identity keypair: {e}"))?
+    } else {
+        wws_protocol::crypto::generate_keypair()
+    };
+
+    let replayed = messages.len();
+    let state = WwsConnector::with_identity(config, keypair)?
+        .replay(messages)
+        .await?;
+    let state = state.read().await;
+    let mut tasks: Vec<_> = state.task_details.values().cloned().collect();
+    tasks.sort_by_key(|t| t.created_at);
+    let differences = expected.as_ref().map(|expected| {
+        let run = state
+            .export_run(&expected.root_task_id)
+            .unwrap_or_else(|_| RunTranscript {
+                tasks: Vec::new(),
+                ..expected.clone()
+            });
+        diff_runs(expected, &run)
+    });
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "replayed": replayed,
+                "dropped": state.dead_letters.len(),
+                "tasks": tasks,
+                "differences": differences,
+            })
+        );
+    } else {
+        println!(
+            "Replayed {} messages ({} dropped): {} tasks",
+            replayed,
+            state.dead_letters.len(),
+            tasks.len()
+        );
+        for task in &tasks {
+            println!(
+                "{} {:<14} assigned={} {}",
+                task.task_id,
+                format!("{:?}", task.status),
+                task.assigned_to.as_ref().map_or("-", |a| a.as_str()),
+                task.description,
+            );
+        }
+        for difference in differences.iter().flatten() {
+            println!(
+                "DIFF {} {}: original {} replayed {}",
+                difference.task_id, difference.field, difference.original, difference.replayed
+            );
+        }
+    }
+    match differences {
+        Some(differences) if !differences.is_empty() => anyhow::bail!(
+            "Replay diverged from the original run in {} places",
+            differences.len()
+        ),
+        _ => Ok(()),
+    }
+}
+
+async fn run_load_test(args: LoadTestArgs, config: &ConnectorConfig) -> anyhow::Result<()> {
+    let report = load_test::run(&LoadTestConfig {
+        rpc_addr: args.rpc.unwrap_or_else(|| config.rpc.bind_addr.clone()),
+        auth: args.auth,
+        tasks: args.tasks,
+        voters: args.voters,
+        plans: args.plans,
+        connections: args.connections,
+    })
+    .await?;
+    if args.json {
+        println!("{}", serde_json::to_string(&report)?);
+    } else {
+        println!("{}", report);
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut cli = Cli::parse();
+
+    // Load configuration.
+    let mut config = ConnectorConfig::load(cli.config.as_deref())?;
+    let (replay, keystore_args) = match cli.command.take() {
+        Some(Command::Trace(args)) => return run_trace(args, &config),
+        Some(Command::Replay(args)) => (Some(args), None),
+        Some(Command::Keystore(args)) => (None, Some(args)),
+        Some(Command::LoadTest(args)) => return run_load_test(args, &config).await,
+        None => (None, None),
+    };
+
+    apply_overrides(&cli, &mut config);
+    if let Some(args) = keystore_args {
+        return run_keystore(args, &config);
+    }
+    if cli.bootstrap_mode {
+        tracing::info!("Starting in BOOTSTRAP MODE");
+        eprintln!("=== Bootstrap Node Mode ===");
+    }
+
+    if let Some(args) = replay {
+        return run_replay(args, config).await;
+    }
 
-    // Load (or create) the persistent Ed25519 identity keypair.
-    tracing::info!(path = %config.identity.path.display(), "Loading agent identity");
-    eprintln!("Identity: {}", config.identity.path.display());
-    let _keypair = wws_protocol::crypto::load_or_create_keypair(&config.identity.path)
-        .map_err(|e| anyhow::anyhow!("Failed to load identity keypair: {e}"))?;
+    // Unlock the keystore, then load the identity and swarm token.
+    let mut keystore = open_keystore(&config)?;
+    let keypair = load_identity(&cli, &config, keystore.as_mut())?;
+    let keystore = keystore.map(Arc::new);
+    fill_swarm_token(&mut config, keystore.as_deref())?;
 
     // Adjust log level based on verbosity.
     let log_level = match cli.verbose {
@@ -168,12 +971,33 @@ async fn main() -> anyhow::Result<()> {
         _ => "trace",
     };
 
+    // A script, or the console on a stdin that is not a terminal, runs
+    // without the TUI.
+    let script = cli.script.clone().or_else(|| {
+        use std::io::IsTerminal;
+        (cli.console && !std::io::stdin().is_terminal()).then(|| PathBuf::from("-"))
+    });
+
     // Initialize logging.
     // When TUI/console mode is enabled, redirect logs to a file.
     let filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(log_level));
+    // Only a level taken from `[logging] level` follows configuration reloads.
+    let level_from_config = cli.verbose == 0 && std::env::var_os("RUST_LOG").is_none();
+
+    if cli.tui || cli.console || script.is_some() {
+        // The TUI and the console own the terminal, and a script's
+        // transcript may go to stdout; stdout log sinks would scribble
+        // over them.
+        let sinks = config.logging.sinks.len();
+        config
+            .logging
+            .sinks
+            .retain(|sink| sink.kind != wws_connector::log_sinks::LogSinkKind::Stdout);
+        if config.logging.sinks.len() < sinks {
+            eprintln!("Ignoring stdout log sinks in TUI/console mode");
+        }
 
-    if cli.tui || cli.console {
         // In TUI/console mode, write logs to a file instead of stdout/stderr.
         let log_dir = std::env::temp_dir().join("wws-logs");
         std::fs::create_dir_all(&log_dir)?;
@@ -183,21 +1007,34 @@ async fn main() -> anyhow::Result<()> {
             .append(true)
             .open(&log_file)?;
 
-        tracing_subscriber::fmt()
+        let subscriber = tracing_subscriber::fmt()
             .with_env_filter(filter)
             .with_target(true)
             .with_ansi(false)
             .with_writer(std::sync::Mutex::new(file))
-            .init();
+            .with_filter_reloading();
+        if level_from_config {
+            install_log_filter_hook(subscriber.reload_handle());
+        }
+        subscriber.init();
 
         eprintln!("Logs: {}", log_file.display());
         eprintln!("  tail -f {}", log_file.display());
         eprintln!();
     } else {
-        tracing_subscriber::fmt()
+        let subscriber = tracing_subscriber::fmt()
             .with_env_filter(filter)
             .with_target(true)
-            .init();
+            .with_filter_reloading();
+        if level_from_config {
+            install_log_filter_hook(subscriber.reload_handle());
+        }
+        subscriber.init();
+    }
+
+    let tracer_provider = wws_connector::telemetry::init(&config.telemetry, &config.agent.name)?;
+    if let Some(endpoint) = &config.telemetry.otlp_endpoint {
+        tracing::info!(endpoint = %endpoint, "Exporting task spans over OTLP");
     }
 
     tracing::info!(
@@ -212,42 +1049,52 @@ async fn main() -> anyhow::Result<()> {
         "Starting WWS.Connector"
     );
 
+    let cli = Arc::new(cli);
+    if cli.daemon {
+        run_daemon(&cli, config, keypair, keystore).await?;
+        if let Some(provider) = tracer_provider {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!(error = %e, "Failed to flush OpenTelemetry spans");
+            }
+        }
+        return Ok(());
+    }
+
     // Create the connector.
-    let connector = WwsConnector::new(config.clone())?;
+    let connector = WwsConnector::with_identity(config.clone(), keypair)?;
 
-    // Get handles for the RPC server.
     let state = connector.shared_state();
-    let network_handle = connector.network_handle();
+    state
+        .write()
+        .await
+        .config_reload
+        .set_loader(config_loader(&cli, &config, keystore.as_ref()));
+    let _services = start_services(&config, &connector);
 
-    // Start the RPC server in a background task.
-    let rpc_server = RpcServer::new(
-        config.rpc.bind_addr.clone(),
-        state.clone(),
-        network_handle,
-        config.rpc.max_connections,
-    );
-
-    tokio::spawn(async move {
-        if let Err(e) = rpc_server.run().await {
-            tracing::error!(error = %e, "RPC server error");
-        }
-    });
-
-    // Start the HTTP file server if enabled.
-    if config.file_server.enabled {
-        let file_server = FileServer::new(
-            config.file_server.bind_addr.clone(),
-            state.clone(),
-            connector.network_handle(),
-        );
-        tokio::spawn(async move {
-            if let Err(e) = file_server.run().await {
-                tracing::error!(error = %e, "HTTP file server error");
-            }
+    if let Some(script) = script {
+        // Run the console script, then shut down.
+        let script_state = state.clone();
+        let script_network_handle = connector.network_handle();
+        let transcript = cli.transcript.clone();
+        let script_handle = tokio::spawn(async move {
+            wws_connector::operator_console::run_console_script(
+                script_state,
+                script_network_handle,
+                &script,
+                transcript.as_deref(),
+            )
+            .await
         });
-    }
 
-    if cli.console {
+        tokio::select! {
+            result = connector.run() => {
+                result?;
+            }
+            result = script_handle => {
+                result??;
+            }
+        }
+    } else if cli.console {
         // Launch the operator console.
         let console_state = state.clone();
         let console_network_handle = connector.network_handle();
@@ -309,5 +1156,12 @@ async fn main() -> anyhow::Result<()> {
         connector.run().await?;
     }
 
+    // Flush spans still waiting to be exported.
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            tracing::warn!(error = %e, "Failed to flush OpenTelemetry spans");
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,107 @@
+//! Membership in several swarms at once.
+//!
+//! A connector starts in the swarm named by its configuration, its primary
+//! swarm, and may join further swarms with `swarm.join_swarm`. Each joined
+//! swarm keeps its own [`SwarmMembership`]: this agent's tier and parent in
+//! that swarm's hierarchy and the tasks that belong to it. Task traffic is
+//! published on the topics of the swarm its task belongs to, so a
+//! proposal for a task injected into `research` never reaches `public`.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use wws_protocol::{AgentId, SwarmId, SwarmTopics, Tier};
+
+/// This agent's place in one joined swarm.
+#[derive(Debug, Clone, Serialize)]
+pub struct SwarmMembership {
+    pub swarm_id: SwarmId,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
+    /// Tier assigned to this agent in the swarm's hierarchy.
+    pub tier: Tier,
+    /// Parent in the swarm's hierarchy (None if Tier-1 or not yet assigned).
+    pub parent_id: Option<AgentId>,
+    /// Tasks injected into or assigned within this swarm.
+    pub task_ids: HashSet<String>,
+}
+
+impl SwarmMembership {
+    pub fn new(swarm_id: SwarmId) -> Self {
+        Self {
+            swarm_id,
+            joined_at: chrono::Utc::now(),
+            tier: Tier::Executor,
+            parent_id: None,
+            task_ids: HashSet::new(),
+        }
+    }
+
+    /// Record a tier assignment received on this swarm's hierarchy topic.
+    pub fn assign(&mut self, tier: Tier, parent_id: AgentId) {
+        self.tier = tier;
+        self.parent_id = Some(parent_id);
+    }
+}
+
+/// Topics a member of `swarm_id` listens on, apart from per-task topics,
+/// as a new member does: with the task topics of every tier.
+pub fn member_topics(swarm_id: &str) -> Vec<String> {
+    let mut topics = base_topics(swarm_id);
+    topics.extend(tier_topics(swarm_id, Tier::Executor));
+    topics
+}
+
+/// Topics a member of `swarm_id` listens on whatever its tier.
+pub fn base_topics(swarm_id: &str) -> Vec<String> {
+    vec![
+        SwarmTopics::swarm_announce(swarm_id),
+        SwarmTopics::election_tier1_for(swarm_id),
+        SwarmTopics::keepalive_for(swarm_id),
+        SwarmTopics::hierarchy_for(swarm_id),
+        SwarmTopics::params_for(swarm_id),
+        SwarmTopics::messages_for(swarm_id),
+    ]
+}
+
+/// Task-topic levels a member at `tier` listens on: a coordinator only
+/// its own level, an executor every level, since leaf work reaches it from
+/// coordinators at any depth.
+pub fn tier_levels(tier: Tier) -> Vec<u32> {
+    match tier {
+        Tier::Executor => (1..=wws_protocol::MAX_HIERARCHY_DEPTH).collect(),
+        coordinator => vec![coordinator.depth().min(wws_protocol::MAX_HIERARCHY_DEPTH)],
+    }
+}
+
+/// Task topics of `swarm_id` a member at `tier` listens on.
+pub fn tier_topics(swarm_id: &str, tier: Tier) -> Vec<String> {
+    tier_levels(tier)
+        .into_iter()
+        .map(|level| SwarmTopics::tasks_for(swarm_id, level))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinators_listen_only_on_their_own_tier() {
+        assert_eq!(tier_levels(Tier::Tier2), [2]);
+        assert_eq!(tier_levels(Tier::TierN(40)), [wws_protocol::MAX_HIERARCHY_DEPTH]);
+        assert_eq!(tier_levels(Tier::Executor).len(), wws_protocol::MAX_HIERARCHY_DEPTH as usize);
+        assert_eq!(tier_topics("lab", Tier::Tier1), [SwarmTopics::tasks_for("lab", 1)]);
+        assert!(member_topics("lab").contains(&SwarmTopics::tasks_for("lab", 3)));
+    }
+
+    #[test]
+    fn new_members_start_as_executors_until_assigned() {
+        let mut membership = SwarmMembership::new(SwarmId::new("research".to_string()));
+        assert_eq!(membership.tier, Tier::Executor);
+        assert!(membership.parent_id.is_none());
+
+        membership.assign(Tier::Tier2, AgentId::new("did:swarm:chair".to_string()));
+        assert_eq!(membership.tier, Tier::Tier2);
+        assert_eq!(membership.parent_id.as_ref().map(|p| p.as_str()), Some("did:swarm:chair"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Merging independently bootstrapped views of a swarm.
+//!
+//! A connector starts its view of a swarm from scratch: its own epoch count
+//! and pyramid layout, under a fresh [`SwarmLineage`]. Two groups that
+//! bootstrapped the same swarm ID apart, say on networks later joined by
+//! DNS-based discovery, run separate epochs and layouts, and their
+//! hierarchies conflict once they hear each other.
+//!
+//! Connectors announce their lineage in keep-alives. On hearing one of
+//! another lineage, a connector sends the sender a `swarm.merge` handshake
+//! over the direct protocol carrying its epoch, members, Tier-1 leaders and
+//! tasks, and the peer merges and answers with its own. Both sides pick the
+//! same winner: the view with more members, then the later epoch, then the
+//! earlier bootstrap. The other side adopts the winner's lineage, epoch and
+//! Tier-1 leaders and lays the pyramid out over both views' members. Either
+//! way, each side replays the tasks it was missing and reconciles the tasks
+//! both know as it would after a partition. A connector joining an
+//! established swarm goes through the same handshake and adopts the swarm's
+//! lineage.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use wws_protocol::{SwarmLineage, SwarmMergeParams};
+
+/// Seconds before another handshake is tried with a lineage that did not
+/// merge.
+pub const MERGE_RETRY_SECS: i64 = 60;
+/// Most tasks sent for replay in one handshake, most recent first, to stay
+/// well inside the direct protocol's request size limit.
+pub const MAX_MERGE_TASKS: usize = 200;
+/// Merge reports kept for `swarm.get_partition_report`.
+pub const MAX_MERGE_REPORTS: usize = 16;
+
+/// One merge handshake, from this node's side.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeReport {
+    pub peer: String,
+    pub remote_lineage: SwarmLineage,
+    /// Whether this node adopted the peer's lineage, epoch and leaders.
+    pub adopted: bool,
+    pub epoch_before: u64,
+    pub epoch_after: u64,
+    pub local_members: usize,
+    pub remote_members: usize,
+    /// Tasks this node was missing and took from the peer.
+    pub tasks_replayed: usize,
+    /// Tasks both sides knew in a different state.
+    pub conflicts: usize,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// This node's lineage and what it has heard of others.
+#[derive(Debug, Clone)]
+pub struct MergeTracker {
+    lineage: SwarmLineage,
+    /// Lineage each connector last announced.
+    peers: HashMap<String, String>,
+    /// When a handshake was last started with each foreign lineage.
+    attempts: HashMap<String, DateTime<Utc>>,
+    reports: VecDeque<MergeReport>,
+}
+
+impl Default for MergeTracker {
+    /// A freshly bootstrapped view.
+    fn default() -> Self {
+        Self {
+            lineage: SwarmLineage {
+                bootstrap_id: uuid::Uuid::new_v4().simple().to_string(),
+                bootstrapped_at: Utc::now(),
+            },
+            peers: HashMap::new(),
+            attempts: HashMap::new(),
+            reports: VecDeque::new(),
+        }
+    }
+}
+
+impl MergeTracker {
+    pub fn lineage(&self) -> &SwarmLineage {
+        &self.lineage
+    }
+
+    pub fn reports(&self) -> impl Iterator<Item = &MergeReport> {
+        self.reports.iter()
+    }
+
+    /// Note the lineage `agent_id` announced. True when it is another
+    /// lineage than ours and no handshake with it was started in the last
+    /// `MERGE_RETRY_SECS`; the attempt is then recorded.
+    pub fn observe(&mut self, agent_id: &str, lineage: &SwarmLineage, now: DateTime<Utc>) -> bool {
+        self.peers
+            .insert(agent_id.to_string(), lineage.bootstrap_id.clone());
+        if lineage.bootstrap_id == self.lineage.bootstrap_id {
+            return false;
+        }
+        let due = self
+            .attempts
+            .get(&lineage.bootstrap_id)
+            .is_none_or(|at| now.signed_duration_since(*at).num_seconds() >= MERGE_RETRY_SECS);
+        if due {
+            self.attempts.insert(lineage.bootstrap_id.clone(), now);
+        }
+        due
+    }
+
+    /// The members of `active` that announced our lineage, with `self_id`.
+    pub fn members(&self, active: &[String], self_id: &str) -> Vec<String> {
+        let mut members: Vec<String> = active
+            .iter()
+            .filter(|id| {
+                id.as_str() == self_id
+                    || self.peers.get(id.as_str()) == Some(&self.lineage.bootstrap_id)
+            })
+            .cloned()
+            .collect();
+        if !members.iter().any(|id| id == self_id) {
+            members.push(self_id.to_string());
+        }
+        members.sort();
+        members
+    }
+
+    /// Take on another lineage after losing a merge.
+    pub fn adopt(&mut self, lineage: SwarmLineage) {
+        self.attempts.remove(&lineage.bootstrap_id);
+        self.lineage = lineage;
+    }
+
+    pub fn record(&mut self, report: MergeReport) {
+        self.reports.push_back(report);
+        while self.reports.len() > MAX_MERGE_REPORTS {
+            self.reports.pop_front();
+        }
+    }
+}
+
+/// Whether the remote view wins a merge: more members, then the later
+/// epoch, then the earlier bootstrap, then the smaller bootstrap ID, so
+/// both sides agree.
+pub fn remote_wins(local: &SwarmMergeParams, remote: &SwarmMergeParams) -> bool {
+    let rank = |view: &SwarmMergeParams| {
+        (
+            view.members.len(),
+            view.epoch,
+            Reverse(view.lineage.bootstrapped_at),
+            Reverse(view.lineage.bootstrap_id.clone()),
+        )
+    };
+    rank(remote) > rank(local)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::AgentId;
+
+    fn view(members: usize, epoch: u64, bootstrapped_secs_ago: i64, id: &str) -> SwarmMergeParams {
+        SwarmMergeParams {
+            sender: AgentId::new(format!("did:swarm:{}", id)),
+            swarm_id: wws_protocol::SwarmId::default_public(),
+            lineage: SwarmLineage {
+                bootstrap_id: id.to_string(),
+                bootstrapped_at: Utc::now() - chrono::Duration::seconds(bootstrapped_secs_ago),
+            },
+            epoch,
+            tier1_leaders: Vec::new(),
+            members: (0..members).map(|i| format!("did:swarm:{}-{}", id, i)).collect(),
+            digests: Vec::new(),
+            tasks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn both_sides_pick_the_larger_then_later_then_older_view() {
+        let small = view(3, 40, 600, "a");
+        let large = view(5, 2, 60, "b");
+        assert!(remote_wins(&small, &large) && !remote_wins(&large, &small));
+
+        let behind = view(3, 4, 600, "a");
+        let ahead = view(3, 9, 60, "b");
+        assert!(remote_wins(&behind, &ahead) && !remote_wins(&ahead, &behind));
+
+        let younger = view(3, 4, 60, "a");
+        let older = view(3, 4, 600, "b");
+        assert!(remote_wins(&younger, &older) && !remote_wins(&older, &younger));
+        assert!(!remote_wins(&older, &older));
+    }
+
+    #[test]
+    fn foreign_lineages_are_tried_once_per_retry_window() {
+        let mut tracker = MergeTracker::default();
+        let ours = tracker.lineage().clone();
+        let theirs = MergeTracker::default().lineage().clone();
+        let now = Utc::now();
+
+        assert!(!tracker.observe("did:swarm:b", &ours, now));
+        assert!(tracker.observe("did:swarm:c", &theirs, now));
+        assert!(!tracker.observe("did:swarm:d", &theirs, now + chrono::Duration::seconds(5)));
+        assert!(tracker.observe(
+            "did:swarm:d",
+            &theirs,
+            now + chrono::Duration::seconds(MERGE_RETRY_SECS)
+        ));
+
+        let active = ["did:swarm:a", "did:swarm:b", "did:swarm:c", "did:swarm:d"].map(String::from);
+        assert_eq!(tracker.members(&active, "did:swarm:a"), ["did:swarm:a", "did:swarm:b"]);
+        tracker.adopt(theirs.clone());
+        assert_eq!(tracker.lineage(), &theirs);
+        assert_eq!(
+            tracker.members(&active, "did:swarm:a"),
+            ["did:swarm:a", "did:swarm:c", "did:swarm:d"]
+        );
+    }
+}
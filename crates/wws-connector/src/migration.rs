@@ -0,0 +1,148 @@
+//! Moving every member of a swarm into another swarm.
+//!
+//! The source swarm's parameter authority (its creator) signs a
+//! `swarm.migrate` message and publishes it on the swarm's params topic.
+//! Each member checks the signature against the authority of the parameter
+//! record it holds, joins the target swarm, makes it its primary swarm,
+//! announces itself there and re-injects the open tasks it originated, so
+//! their planning restarts among the migrated members. `swarm.migrate_swarm`
+//! with `dry_run` reports what a migration would move without sending it.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+use wws_protocol::{
+    ProtocolMethod, SwarmId, SwarmJoinParams, SwarmLeaveParams, SwarmMessage, SwarmMigrateParams,
+    SwarmToken, SwarmTopics, Task, TaskStatus,
+};
+
+use crate::connector::ConnectorState;
+use crate::tui::LogCategory;
+
+/// What a migration out of this connector's primary swarm would move.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub source_swarm_id: String,
+    pub target_swarm_id: String,
+    /// Whether this connector is the source swarm's parameter authority and
+    /// so may issue the migration.
+    pub authorized: bool,
+    pub target_public: bool,
+    /// Active members of the source swarm, this connector included.
+    pub members: usize,
+    /// Open root tasks this connector would re-inject into the target.
+    pub transferred_tasks: Vec<String>,
+    /// Tasks waiting on prerequisites; they are dispatched to the target
+    /// swarm once released.
+    pub held_tasks: usize,
+}
+
+/// A migration applied to the connector state, with the network side still
+/// to do.
+#[derive(Debug)]
+pub struct MigrationOutcome {
+    pub migration: SwarmMigrateParams,
+    /// Open tasks to re-inject into the target swarm, reset to `Pending`.
+    pub tasks: Vec<Task>,
+}
+
+/// Whether a task can still be worked on.
+pub fn is_open(status: &TaskStatus) -> bool {
+    !matches!(
+        status,
+        TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Rejected | TaskStatus::Disputed
+    )
+}
+
+/// A change of primary swarm applied to the connector state, with the
+/// network side still to do.
+#[derive(Debug)]
+pub struct SwarmMove {
+    pub source: SwarmId,
+    pub target: SwarmId,
+    /// Token for joining the target when it is private.
+    pub token: Option<SwarmToken>,
+    /// Open tasks to re-inject into the target, reset to `Pending`.
+    pub tasks: Vec<Task>,
+}
+
+/// Move subscriptions from the source swarm to the target, announce the
+/// move on both swarms and re-inject the transferred tasks.
+pub async fn complete_migration(
+    outcome: MigrationOutcome,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) {
+    let MigrationOutcome { migration, tasks } = outcome;
+    let target = migration.target_swarm_id.clone();
+    let moved = SwarmMove {
+        source: migration.source_swarm_id,
+        target: migration.target_swarm_id,
+        token: migration.target_token,
+        tasks,
+    };
+    move_swarm(moved, state, network_handle).await;
+
+    state.write().await.push_log(
+        LogCategory::Swarm,
+        format!("Migration {} complete: now in swarm {}", migration.migration_id, target),
+    );
+}
+
+/// The network side of a change of primary swarm: subscribe to the
+/// target's topics, announce leaving the source and joining the target,
+/// drop the source's topics and re-inject the moved tasks.
+pub async fn move_swarm(
+    moved: SwarmMove,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) {
+    let SwarmMove { source, target, token, tasks } = moved;
+
+    for topic in crate::membership::member_topics(target.as_str()) {
+        if let Err(e) = network_handle.subscribe(&topic).await {
+            tracing::debug!(error = %e, topic = %topic, "Failed to subscribe swarm topic");
+        }
+    }
+
+    let agent_id = state.read().await.agent_id.clone();
+    let now = chrono::Utc::now();
+    let leave = SwarmLeaveParams {
+        swarm_id: source.clone(),
+        agent_id: agent_id.clone(),
+        timestamp: now,
+    };
+    let join = SwarmJoinParams {
+        swarm_id: target.clone(),
+        agent_id,
+        token,
+        timestamp: now,
+    };
+    let announcements = [
+        (source.as_str(), ProtocolMethod::SwarmLeave, serde_json::to_value(&leave)),
+        (target.as_str(), ProtocolMethod::SwarmJoin, serde_json::to_value(&join)),
+    ];
+    for (swarm_id, method, params) in announcements {
+        let msg = SwarmMessage::new(method.as_str(), params.unwrap_or_default(), String::new());
+        if let Ok(data) = serde_json::to_vec(&msg) {
+            let topic = SwarmTopics::swarm_announce(swarm_id);
+            if let Err(e) = network_handle.publish(&topic, data).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to announce swarm move");
+            }
+        }
+    }
+
+    // The public swarm's topics are core topics, kept for discovery.
+    if source.as_str() != wws_protocol::DEFAULT_SWARM_ID {
+        for topic in crate::membership::member_topics(source.as_str()) {
+            if let Err(e) = network_handle.unsubscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to unsubscribe swarm topic");
+            }
+        }
+    }
+
+    for task in tasks {
+        crate::rpc_server::dispatch_task(task, state, network_handle).await;
+    }
+}
@@ -0,0 +1,360 @@
+//! MQTT bridge for agents that do not speak libp2p.
+//!
+//! With `[mqtt] enabled`, the connector connects to an MQTT broker and acts
+//! for each agent named in `agents`, which it registers as a local agent.
+//! Constrained devices, or fleets already built around a broker, then take
+//! part in the swarm through topics under `topic_prefix` (`wws` by default):
+//!
+//! | Topic | Direction | Carries |
+//! |-------|-----------|---------|
+//! | `wws/agents/<name>/tasks` | out | Each task assigned to `<name>`, once, as `swarm.get_task` returns it |
+//! | `wws/agents/<name>/results` | in | `swarm.submit_result` |
+//! | `wws/agents/<name>/votes` | in | `swarm.submit_vote` |
+//! | `wws/agents/<name>/rpc` | in | Any method, named in `method` |
+//! | `wws/agents/<name>/replies` | out | The JSON-RPC response to every inbound message |
+//!
+//! Inbound payloads are `{"id": ..., "auth": ..., "params": {...}}`, plus
+//! `method` on the `rpc` topic. They act as the topic's agent and pass the
+//! same client check as JSON-RPC requests, so with `[[rpc.clients]]`
+//! configured each message needs a client's `auth` token, and a client
+//! bound to a `local_agent` may only publish for that agent. The bridge
+//! itself logs in to the broker with `username` and `password`.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use tokio::sync::{mpsc, RwLock};
+use wws_protocol::{ErrorCode, SwarmResponse, JSONRPC_VERSION};
+
+use crate::auth::{RpcAuth, RpcClientConfig};
+use crate::config::MqttConfig;
+use crate::connector::ConnectorState;
+use crate::daemon::TaskGuard;
+use crate::rpc_server::{authorize, process_request};
+use crate::tui::LogCategory;
+
+/// Delay before reconnecting after the broker connection fails.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// What an inbound topic asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inbound {
+    /// `swarm.submit_result`.
+    Result,
+    /// `swarm.submit_vote`.
+    Vote,
+    /// The method the payload names.
+    Rpc,
+}
+
+/// The agent name and request kind of an inbound `topic`, if it is one.
+pub fn parse_topic<'a>(prefix: &str, topic: &'a str) -> Option<(&'a str, Inbound)> {
+    let rest = topic.strip_prefix(prefix)?.strip_prefix("/agents/")?;
+    let (name, kind) = rest.split_once('/')?;
+    let kind = match kind {
+        "results" => Inbound::Result,
+        "votes" => Inbound::Vote,
+        "rpc" => Inbound::Rpc,
+        _ => return None,
+    };
+    (!name.is_empty()).then_some((name, kind))
+}
+
+/// A request that could not be built: its ID, if it had one, and why.
+type Rejection = (Option<String>, ErrorCode, String);
+
+/// The JSON-RPC request line for an inbound `payload`, acting as the local
+/// agent `did`.
+pub fn request_line(kind: Inbound, payload: &[u8], did: &str) -> Result<String, Rejection> {
+    let envelope: serde_json::Value = serde_json::from_slice(payload)
+        .map_err(|e| (None, ErrorCode::ParseError, format!("Payload is not JSON: {}", e)))?;
+    let id = envelope.get("id").and_then(|v| v.as_str()).map(str::to_string);
+    let method = match kind {
+        Inbound::Result => "swarm.submit_result",
+        Inbound::Vote => "swarm.submit_vote",
+        Inbound::Rpc => match envelope.get("method").and_then(|v| v.as_str()) {
+            Some(method) => method,
+            None => return Err((id, ErrorCode::InvalidRequest, "Missing 'method'".to_string())),
+        },
+    };
+    let mut params = envelope.get("params").cloned().unwrap_or(serde_json::json!({}));
+    let Some(fields) = params.as_object_mut() else {
+        return Err((id, ErrorCode::InvalidParams, "'params' must be an object".to_string()));
+    };
+    fields.insert("local_agent".to_string(), serde_json::json!(did));
+    let mut request = serde_json::json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "id": id,
+        "method": method,
+        "params": params,
+        "signature": "",
+    });
+    if let Some(auth) = envelope.get("auth") {
+        request["auth"] = auth.clone();
+    }
+    Ok(request.to_string())
+}
+
+/// One bridged agent.
+struct BridgedAgent {
+    did: String,
+    /// Tasks already published, pruned once they stop being pending.
+    published: HashSet<String>,
+}
+
+/// Runs the MQTT bridge against a connector's shared state.
+pub struct MqttBridge {
+    config: MqttConfig,
+    state: Arc<RwLock<ConnectorState>>,
+    network_handle: wws_network::SwarmHandle,
+    auth: RpcAuth,
+    next_request_id: u64,
+}
+
+impl MqttBridge {
+    pub fn new(
+        config: MqttConfig,
+        state: Arc<RwLock<ConnectorState>>,
+        network_handle: wws_network::SwarmHandle,
+    ) -> Self {
+        Self {
+            config,
+            state,
+            network_handle,
+            auth: RpcAuth::default(),
+            next_request_id: 0,
+        }
+    }
+
+    /// Require inbound messages to authenticate as one of `clients`.
+    pub fn with_clients(mut self, clients: Vec<RpcClientConfig>) -> Self {
+        self.auth = RpcAuth::new(clients);
+        self
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let mut agents = HashMap::new();
+        for name in self.config.agents.clone() {
+            let result = self
+                .call("swarm.register_local_agent", serde_json::json!({ "name": name }))
+                .await
+                .map_err(|e| anyhow::anyhow!("MQTT agent {} could not register: {}", name, e.1))?;
+            let did = result
+                .get("agent_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            agents.insert(
+                name,
+                BridgedAgent {
+                    did,
+                    published: HashSet::new(),
+                },
+            );
+        }
+
+        let (host, port) = self
+            .config
+            .broker
+            .rsplit_once(':')
+            .and_then(|(host, port)| Some((host.to_string(), port.parse::<u16>().ok()?)))
+            .ok_or_else(|| anyhow::anyhow!("Invalid MQTT broker address: {}", self.config.broker))?;
+        let mut options = MqttOptions::new(self.config.client_id.clone(), host, port);
+        options.set_keep_alive(Duration::from_secs(30));
+        if let Some(username) = &self.config.username {
+            options.set_credentials(username.clone(), self.config.password.clone().unwrap_or_default());
+        }
+        let (client, mut eventloop) = AsyncClient::new(options, 64);
+
+        // The event loop runs on its own so publishing never waits on it.
+        let (inbound_tx, mut inbound_rx) = mpsc::channel(64);
+        let subscriptions: Vec<String> = agents
+            .keys()
+            .map(|name| format!("{}/agents/{}/+", self.config.topic_prefix, name))
+            .collect();
+        let subscriber = client.clone();
+        let events = tokio::spawn(async move {
+            loop {
+                match eventloop.poll().await {
+                    // Subscriptions are lost with a clean session; renew them.
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        for topic in &subscriptions {
+                            let _ = subscriber.subscribe(topic.clone(), QoS::AtLeastOnce).await;
+                        }
+                    }
+                    Ok(Event::Incoming(Packet::Publish(publish))) => {
+                        if inbound_tx.send(publish).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::warn!(error = %e, "MQTT broker connection failed");
+                        tokio::time::sleep(RECONNECT_DELAY).await;
+                    }
+                }
+            }
+        });
+        let _events = TaskGuard::new(vec![events.abort_handle()]);
+
+        self.log(
+            LogCategory::System,
+            format!(
+                "MQTT bridge connecting to {} for {} agent(s)",
+                self.config.broker,
+                agents.len()
+            ),
+        )
+        .await;
+
+        let mut poll = tokio::time::interval(Duration::from_secs(self.config.poll_interval_secs.max(1)));
+        loop {
+            tokio::select! {
+                _ = poll.tick() => {
+                    for (name, agent) in agents.iter_mut() {
+                        self.publish_tasks(&client, name, agent).await;
+                    }
+                }
+                Some(publish) = inbound_rx.recv() => {
+                    let Some((name, kind)) = parse_topic(&self.config.topic_prefix, &publish.topic) else {
+                        continue;
+                    };
+                    let Some(agent) = agents.get(name) else {
+                        continue;
+                    };
+                    let response = self.answer(kind, &publish.payload, &agent.did).await;
+                    let topic = format!("{}/agents/{}/replies", self.config.topic_prefix, name);
+                    if let Ok(body) = serde_json::to_vec(&response) {
+                        let _ = client.publish(topic, QoS::AtLeastOnce, false, body).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Publish the tasks pending for `agent` that were not published yet.
+    async fn publish_tasks(&mut self, client: &AsyncClient, name: &str, agent: &mut BridgedAgent) {
+        let params = serde_json::json!({ "local_agent": agent.did });
+        let pending: HashSet<String> = match self.call("swarm.receive_task", params).await {
+            Ok(result) => result
+                .get("pending_tasks")
+                .and_then(|v| v.as_array())
+                .map(|ids| ids.iter().filter_map(|id| id.as_str().map(str::to_string)).collect())
+                .unwrap_or_default(),
+            Err((_, message)) => {
+                tracing::debug!(agent = %name, error = %message, "MQTT bridge poll failed");
+                return;
+            }
+        };
+        agent.published.retain(|task_id| pending.contains(task_id));
+
+        let topic = format!("{}/agents/{}/tasks", self.config.topic_prefix, name);
+        for task_id in pending {
+            if agent.published.contains(&task_id) {
+                continue;
+            }
+            let params = serde_json::json!({ "task_id": task_id, "local_agent": agent.did });
+            let Some(task) = self
+                .call("swarm.get_task", params)
+                .await
+                .ok()
+                .and_then(|result| result.get("task").cloned())
+            else {
+                continue;
+            };
+            let Ok(body) = serde_json::to_vec(&task) else {
+                continue;
+            };
+            if client.publish(topic.clone(), QoS::AtLeastOnce, false, body).await.is_ok() {
+                agent.published.insert(task_id);
+            }
+        }
+    }
+
+    /// Answer an inbound message as the JSON-RPC server would.
+    async fn answer(&self, kind: Inbound, payload: &[u8], did: &str) -> SwarmResponse {
+        let line = match request_line(kind, payload, did) {
+            Ok(line) => line,
+            Err((id, code, message)) => return SwarmResponse::error(id, code, message),
+        };
+        let peer = std::net::SocketAddr::from(([0, 0, 0, 0], 0));
+        match authorize(&line, &self.auth, &self.state, peer).await {
+            Ok(Some(rewritten)) => process_request(&rewritten, &self.state, &self.network_handle).await,
+            Ok(None) => process_request(&line, &self.state, &self.network_handle).await,
+            Err(denied) => denied,
+        }
+    }
+
+    /// Send one JSON-RPC request through the connector's own dispatcher.
+    async fn call(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, (i32, String)> {
+        self.next_request_id += 1;
+        let request = serde_json::json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "id": format!("mqtt-{}", self.next_request_id),
+            "method": method,
+            "params": params,
+            "signature": "",
+        });
+        let response = process_request(&request.to_string(), &self.state, &self.network_handle).await;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err((error.code, error.message)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Ok(serde_json::Value::Null),
+        }
+    }
+
+    async fn log(&self, category: LogCategory, message: String) {
+        self.state.write().await.push_log(category, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_agent_request_topics_are_inbound() {
+        assert_eq!(
+            parse_topic("wws", "wws/agents/sensor-1/results"),
+            Some(("sensor-1", Inbound::Result))
+        );
+        assert_eq!(
+            parse_topic("wws", "wws/agents/sensor-1/votes"),
+            Some(("sensor-1", Inbound::Vote))
+        );
+        assert_eq!(parse_topic("wws", "wws/agents/sensor-1/rpc"), Some(("sensor-1", Inbound::Rpc)));
+        assert_eq!(parse_topic("wws", "wws/agents/sensor-1/tasks"), None);
+        assert_eq!(parse_topic("wws", "wws/agents/sensor-1/replies"), None);
+        assert_eq!(parse_topic("wws", "other/agents/sensor-1/results"), None);
+        assert_eq!(parse_topic("wws", "wws/agents//results"), None);
+    }
+
+    #[test]
+    fn payloads_become_requests_acting_as_the_topic_agent() {
+        let payload = br#"{"id":"r1","auth":"device-token","params":{"task_id":"t1","rankings":["p1"]}}"#;
+        let line = request_line(Inbound::Vote, payload, "did:swarm:sensor").unwrap();
+        let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(request["method"], "swarm.submit_vote");
+        assert_eq!(request["id"], "r1");
+        assert_eq!(request["auth"], "device-token");
+        assert_eq!(request["params"]["local_agent"], "did:swarm:sensor");
+        assert_eq!(request["params"]["task_id"], "t1");
+
+        let line = request_line(Inbound::Rpc, br#"{"method":"swarm.get_status"}"#, "did:swarm:sensor").unwrap();
+        let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(request["method"], "swarm.get_status");
+        assert!(request.get("auth").is_none());
+
+        let (id, code, _) =
+            request_line(Inbound::Rpc, br#"{"id":"r2","params":{}}"#, "did:swarm:sensor").unwrap_err();
+        assert_eq!((id.as_deref(), code), (Some("r2"), ErrorCode::InvalidRequest));
+        let (_, code, _) = request_line(Inbound::Result, b"not json", "did:swarm:sensor").unwrap_err();
+        assert_eq!(code, ErrorCode::ParseError);
+    }
+}
@@ -0,0 +1,303 @@
+//! The wws:// name directory.
+//!
+//! Agents claim names with signed `name.claim` messages, published on the
+//! global names topic and stored in the DHT under the name's key. Claims
+//! from different DIDs on the same name are all kept: the earliest
+//! unexpired claim holds the name (ties go to the lower DID) and the rest
+//! stand as a dispute. Guardians listed in the `[names]` configuration
+//! settle disputes with signed `name.dispute_vote` messages; once
+//! `guardian_threshold` of them award the name to one claimant, that
+//! claimant holds it whatever the claim order. A DID's claims and votes must
+//! all be signed with the key of the first one seen from it.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wws_protocol::{
+    NameClaimParams, NameDisputeVoteParams, ProtocolMethod, SwarmMessage, SwarmTopics,
+};
+
+/// How far in the future a claim's `registered_at` may lie.
+pub const CLAIM_CLOCK_SKEW_SECS: u64 = 300;
+
+/// Name dispute settlement.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NamesConfig {
+    /// DIDs whose votes settle disputed names.
+    #[serde(default)]
+    pub guardians: Vec<String>,
+    /// Guardian votes needed to award a disputed name.
+    #[serde(default = "default_guardian_threshold")]
+    pub guardian_threshold: u32,
+}
+
+fn default_guardian_threshold() -> u32 {
+    2
+}
+
+/// Every claim and guardian vote seen, by lowercase name.
+#[derive(Debug, Clone, Default)]
+pub struct NameDirectory {
+    guardians: Vec<String>,
+    guardian_threshold: u32,
+    /// Latest claim from each claimant DID.
+    claims: HashMap<String, HashMap<String, NameClaimParams>>,
+    /// Guardian DID -> DID it awards the name to.
+    awards: HashMap<String, HashMap<String, String>>,
+    /// Key that signed the first claim or vote seen from each DID.
+    keys: HashMap<String, String>,
+}
+
+/// Whether `name` may be claimed: 1-64 alphanumeric or hyphen characters.
+pub fn is_valid_name(name: &str) -> bool {
+    (1..=64).contains(&name.len()) && name.chars().all(|c| c.is_alphanumeric() || c == '-')
+}
+
+/// Unix time in seconds.
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// A label told apart from other holders of the same label by the tail of
+/// its DID, e.g. `alice#9f3c`.
+pub fn disambiguate(label: &str, did: &str) -> String {
+    let tail = did.len().saturating_sub(4);
+    format!("{}#{}", label, did.get(tail..).unwrap_or(did))
+}
+
+impl NameDirectory {
+    pub fn new(config: &NamesConfig) -> Self {
+        Self {
+            guardians: config.guardians.clone(),
+            guardian_threshold: config.guardian_threshold,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_guardian(&self, did: &str) -> bool {
+        self.guardians.iter().any(|g| g == did)
+    }
+
+    /// Pin `key` to `did` on first sight; afterwards only that key is accepted.
+    fn check_key(&mut self, did: &str, key: &str) -> Result<(), String> {
+        match self.keys.get(did) {
+            Some(pinned) if pinned != key => {
+                Err(format!("{} signed with a key it has not used before", did))
+            }
+            Some(_) => Ok(()),
+            None => {
+                self.keys.insert(did.to_string(), key.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Record a claim. Returns whether it changed the directory; a copy of a
+    /// claim already held does not.
+    pub fn record_claim(&mut self, claim: NameClaimParams, now: u64) -> Result<bool, String> {
+        if !claim.verify() {
+            return Err(format!("claim on '{}' has a bad signature", claim.name));
+        }
+        if !is_valid_name(&claim.name) {
+            return Err(format!("'{}' is not a valid name", claim.name));
+        }
+        if claim.registered_at > now + CLAIM_CLOCK_SKEW_SECS {
+            return Err(format!("claim on '{}' is dated in the future", claim.name));
+        }
+        if claim.expires_at <= now {
+            return Err(format!("claim on '{}' has expired", claim.name));
+        }
+        self.check_key(&claim.did, &claim.public_key)?;
+        let name = claim.name.to_lowercase();
+        let held = self.claims.get(&name).and_then(|c| c.get(&claim.did));
+        if let Some(held) = held.filter(|held| held.expires_at > now) {
+            if held.registered_at != claim.registered_at {
+                return Err(format!("{} re-dated its claim on '{}'", claim.did, claim.name));
+            }
+            if held.expires_at >= claim.expires_at {
+                return Ok(false);
+            }
+        }
+        self.claims.entry(name).or_default().insert(claim.did.clone(), claim);
+        Ok(true)
+    }
+
+    /// Record a guardian's vote on a claimed name. Returns whether it
+    /// changed the directory.
+    pub fn record_vote(&mut self, vote: NameDisputeVoteParams) -> Result<bool, String> {
+        if !vote.verify() {
+            return Err(format!("vote on '{}' has a bad signature", vote.name));
+        }
+        if !self.is_guardian(&vote.guardian_did) {
+            return Err(format!("{} is not a name guardian", vote.guardian_did));
+        }
+        let name = vote.name.to_lowercase();
+        if !self.claims.get(&name).is_some_and(|c| c.contains_key(&vote.award_to)) {
+            return Err(format!("{} has no claim on '{}'", vote.award_to, vote.name));
+        }
+        self.check_key(&vote.guardian_did, &vote.guardian_key)?;
+        let previous = self
+            .awards
+            .entry(name)
+            .or_default()
+            .insert(vote.guardian_did, vote.award_to.clone());
+        Ok(previous.as_deref() != Some(vote.award_to.as_str()))
+    }
+
+    /// Unexpired claims on `name`, earliest first.
+    pub fn contenders(&self, name: &str, now: u64) -> Vec<&NameClaimParams> {
+        let mut contenders: Vec<&NameClaimParams> = self
+            .claims
+            .get(&name.to_lowercase())
+            .map(|c| c.values().filter(|claim| claim.expires_at > now).collect())
+            .unwrap_or_default();
+        contenders.sort_by(|a, b| {
+            a.registered_at.cmp(&b.registered_at).then_with(|| a.did.cmp(&b.did))
+        });
+        contenders
+    }
+
+    /// Guardians voting for each DID on `name`.
+    pub fn votes(&self, name: &str) -> HashMap<&str, Vec<String>> {
+        let mut votes: HashMap<&str, Vec<String>> = HashMap::new();
+        for (guardian, award_to) in self.awards.get(&name.to_lowercase()).into_iter().flatten() {
+            votes.entry(award_to.as_str()).or_default().push(guardian.clone());
+        }
+        votes
+    }
+
+    /// The contender the guardians have awarded `name` to, if their votes
+    /// reach the threshold.
+    pub fn awarded_to(&self, name: &str, now: u64) -> Option<&NameClaimParams> {
+        if self.guardians.is_empty() || self.guardian_threshold == 0 {
+            return None;
+        }
+        let guardians: Vec<&str> = self.guardians.iter().map(String::as_str).collect();
+        let votes = self.votes(name);
+        self.contenders(name, now).into_iter().find(|claim| {
+            let voters = votes.get(claim.did.as_str()).cloned().unwrap_or_default();
+            wws_protocol::verify_guardian_threshold(&voters, self.guardian_threshold, &guardians)
+                .is_ok()
+        })
+    }
+
+    /// The claim holding `name`: the guardians' award, else the earliest
+    /// unexpired claim.
+    pub fn holder(&self, name: &str, now: u64) -> Option<&NameClaimParams> {
+        self.awarded_to(name, now)
+            .or_else(|| self.contenders(name, now).into_iter().next())
+    }
+
+    /// Whether several DIDs claim `name` and the guardians have not settled it.
+    pub fn is_disputed(&self, name: &str, now: u64) -> bool {
+        self.contenders(name, now).len() > 1 && self.awarded_to(name, now).is_none()
+    }
+
+    /// `did`'s own claim on `name`, expired or not.
+    pub fn claim_of(&self, name: &str, did: &str) -> Option<&NameClaimParams> {
+        self.claims.get(&name.to_lowercase()).and_then(|c| c.get(did))
+    }
+}
+
+/// Publish a name directory message on the names topic.
+pub async fn publish(
+    network_handle: &wws_network::SwarmHandle,
+    method: ProtocolMethod,
+    params: serde_json::Value,
+) {
+    let msg = SwarmMessage::new(method.as_str(), params, String::new());
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        if let Err(e) = network_handle.publish(&SwarmTopics::names(), data).await {
+            tracing::debug!(error = %e, method = method.as_str(), "Failed to publish name message");
+        }
+    }
+}
+
+/// Publish a claim on the names topic and to the DHT under the name's key.
+pub async fn publish_claim(network_handle: &wws_network::SwarmHandle, claim: &NameClaimParams) {
+    let value = serde_json::to_value(claim).unwrap_or_default();
+    publish(network_handle, ProtocolMethod::NameClaim, value).await;
+
+    let dht_key = wws_network::name_registry::NameRecord::dht_key(&claim.name);
+    if let Ok(value) = serde_json::to_vec(claim) {
+        if let Err(e) = network_handle.put_dht_record(dht_key, value).await {
+            tracing::debug!(error = %e, "Failed to publish name claim to DHT");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(key: &ed25519_dalek::SigningKey, did: &str, registered_at: u64) -> NameClaimParams {
+        let mut claim = NameClaimParams {
+            name: "Atlas".into(),
+            did: did.into(),
+            peer_id: String::new(),
+            registered_at,
+            expires_at: registered_at + 86_400,
+            pow_nonce: 0,
+            public_key: String::new(),
+            signature: String::new(),
+        };
+        claim.sign(key);
+        claim
+    }
+
+    fn vote(
+        key: &ed25519_dalek::SigningKey,
+        guardian: &str,
+        award_to: &str,
+    ) -> NameDisputeVoteParams {
+        let mut vote = NameDisputeVoteParams {
+            name: "atlas".into(),
+            award_to: award_to.into(),
+            guardian_did: guardian.into(),
+            guardian_key: String::new(),
+            timestamp: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        vote.sign(key);
+        vote
+    }
+
+    #[test]
+    fn the_first_claim_holds_until_guardians_award_the_name() {
+        let config = NamesConfig {
+            guardians: vec!["did:swarm:g1".into(), "did:swarm:g2".into()],
+            guardian_threshold: 2,
+        };
+        let mut directory = NameDirectory::new(&config);
+        let early = ed25519_dalek::SigningKey::from_bytes(&[1; 32]);
+        let late = ed25519_dalek::SigningKey::from_bytes(&[2; 32]);
+        let now = 10_000;
+
+        assert_eq!(directory.record_claim(claim(&late, "did:swarm:late", 9_000), now), Ok(true));
+        assert_eq!(directory.record_claim(claim(&early, "did:swarm:early", 8_000), now), Ok(true));
+        assert_eq!(directory.record_claim(claim(&early, "did:swarm:early", 8_000), now), Ok(false));
+        assert_eq!(directory.holder("ATLAS", now).map(|c| c.did.as_str()), Some("did:swarm:early"));
+        assert!(directory.is_disputed("atlas", now));
+
+        // Another key cannot claim for a DID it did not first sign for.
+        assert!(directory.record_claim(claim(&late, "did:swarm:early", 8_000), now).is_err());
+
+        let g1 = ed25519_dalek::SigningKey::from_bytes(&[3; 32]);
+        let g2 = ed25519_dalek::SigningKey::from_bytes(&[4; 32]);
+        assert!(directory.record_vote(vote(&g1, "did:swarm:outsider", "did:swarm:late")).is_err());
+        assert_eq!(directory.record_vote(vote(&g1, "did:swarm:g1", "did:swarm:late")), Ok(true));
+        assert_eq!(directory.holder("atlas", now).map(|c| c.did.as_str()), Some("did:swarm:early"));
+        assert_eq!(directory.record_vote(vote(&g2, "did:swarm:g2", "did:swarm:late")), Ok(true));
+        assert_eq!(directory.holder("atlas", now).map(|c| c.did.as_str()), Some("did:swarm:late"));
+        assert!(!directory.is_disputed("atlas", now));
+    }
+
+    #[test]
+    fn colliding_labels_take_a_did_suffix() {
+        assert_eq!(disambiguate("atlas", "did:swarm:12D3KooW9f3c"), "atlas#9f3c");
+        assert_eq!(disambiguate("atlas", "abc"), "atlas#abc");
+    }
+}
@@ -1,14 +1,31 @@
 //! Human-Operator Console for the WWS.Connector.
 //!
-//! An interactive TUI that allows a human operator (or script piping stdin)
-//! to inject tasks into the swarm, view the agent hierarchy tree, monitor
-//! active tasks, and observe the event log.
+//! An interactive TUI that allows a human operator to inject tasks into the swarm, view the agent hierarchy tree, monitor
+//! active tasks, and observe the event log. Tab moves focus to the task
+//! table, where the arrow keys select a task and Enter opens its detail
+//! view: timeline, deliberation, ballots, IRV rounds and subtask tree,
+//! refreshed live, with keys to approve, cancel or reassign it.
+//!
+//! `/multi` (or Alt+Enter) switches the input to multi-line entry, where
+//! Enter adds a line and Ctrl+D submits. `/inject` walks through the task's
+//! description, priority, capabilities, deadline and approval requirement,
+//! then injects it through the same path as `swarm.inject_task`.
+//! `/inject-into <swarm_id>` does the same for another joined swarm.
 //!
 //! Launch with `wws-connector --console`.
-
-use std::io::{self, Stdout};
+//!
+//! `wws-connector --script <file>` runs the same input without the TUI:
+//! each line of the file is handled as if typed and submitted, with blank
+//! lines and `#` comments skipped, and `sleep <duration>` (`5`, `5s`, `2m`)
+//! pausing before the next line. The input and the console's replies go to
+//! a transcript, and the connector exits after the last line. With `-` as
+//! the file, or `--console` on a stdin that is not a terminal, the lines
+//! come from stdin.
+
+use std::io::{self, Stdout, Write};
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::{
     event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
@@ -23,6 +40,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Row, Table},
     Frame, Terminal,
 };
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
 use tokio::sync::RwLock;
 
 use crate::connector::{ConnectorState, ConnectorStatus};
@@ -31,6 +49,43 @@ use wws_protocol::{
     ProtocolMethod, SwarmMessage, SwarmTopics, Task, TaskInjectionParams, TaskStatus, Tier,
 };
 
+/// Most lines of multi-line input shown at once; earlier lines scroll away.
+const MAX_INPUT_LINES: usize = 8;
+/// Agents whose keep-alives stopped longer ago than this leave the
+/// hierarchy panel, unless they still hold a tier.
+const HIERARCHY_MEMBER_WINDOW: Duration = Duration::from_secs(180);
+/// How long a tier change stays highlighted in the hierarchy panel.
+const TIER_CHANGE_HIGHLIGHT: Duration = Duration::from_secs(5);
+
+/// How recently an agent's keep-alive arrived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liveness {
+    /// Within two keep-alive intervals.
+    Fresh,
+    /// Older, but within the membership window.
+    Stale,
+    /// None within the membership window.
+    Missing,
+}
+
+impl Liveness {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Fresh => "fresh",
+            Self::Stale => "stale",
+            Self::Missing => "missing",
+        }
+    }
+
+    fn badge(self) -> (&'static str, Color) {
+        match self {
+            Self::Fresh => ("●", Color::Green),
+            Self::Stale => ("◐", Color::Yellow),
+            Self::Missing => ("○", Color::Red),
+        }
+    }
+}
+
 /// A node in the hierarchy tree for display.
 #[derive(Debug, Clone)]
 pub struct HierarchyNode {
@@ -41,6 +96,42 @@ pub struct HierarchyNode {
     pub children: Vec<HierarchyNode>,
     pub task_count: usize,
     pub last_seen_secs: Option<i64>,
+    pub liveness: Liveness,
+    /// Last ping round-trip time to the agent's node.
+    pub rtt: Option<Duration>,
+    /// Whether the tier changed within the last few seconds.
+    pub tier_changed: bool,
+}
+
+/// Tiers seen in earlier snapshots, to highlight the agents whose tier
+/// changed.
+#[derive(Debug, Default)]
+struct TierChanges {
+    tiers: std::collections::HashMap<String, String>,
+    changed_at: std::collections::HashMap<String, Instant>,
+}
+
+impl TierChanges {
+    /// Record the tiers in `nodes` and flag those that changed within
+    /// [`TIER_CHANGE_HIGHLIGHT`] of `now`. An agent's first tier is not a
+    /// change.
+    fn mark(&mut self, nodes: &mut [HierarchyNode], now: Instant) {
+        self.changed_at
+            .retain(|_, at| now.duration_since(*at) < TIER_CHANGE_HIGHLIGHT);
+        self.mark_nodes(nodes, now);
+    }
+
+    fn mark_nodes(&mut self, nodes: &mut [HierarchyNode], now: Instant) {
+        for node in nodes {
+            if let Some(previous) = self.tiers.insert(node.agent_id.clone(), node.tier.clone()) {
+                if previous != node.tier {
+                    self.changed_at.insert(node.agent_id.clone(), now);
+                }
+            }
+            node.tier_changed = self.changed_at.contains_key(&node.agent_id);
+            self.mark_nodes(&mut node.children, now);
+        }
+    }
 }
 
 /// Snapshot of operator console state for rendering.
@@ -58,6 +149,8 @@ struct ConsoleSnapshot {
     event_log: Vec<LogEntry>,
     current_swarm_name: String,
     flow: FlowSnapshot,
+    /// The task open in the detail view, if any.
+    task_detail: Option<TaskDetailView>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -84,6 +177,243 @@ struct TaskView {
     subtask_count: usize,
 }
 
+/// Everything the detail view shows for one task, as display lines.
+#[derive(Debug, Clone, Default)]
+struct TaskDetailView {
+    task_id: String,
+    description: String,
+    status: String,
+    assigned_to: String,
+    swarm_id: String,
+    /// Winning plan held for operator approval, if any.
+    awaiting_approval: Option<String>,
+    /// Why the task is blocked, if no agent could take it over.
+    blocked: Option<String>,
+    timeline: Vec<String>,
+    deliberation: Vec<String>,
+    ballots: Vec<String>,
+    irv_rounds: Vec<String>,
+    subtask_tree: Vec<String>,
+}
+
+/// Which part of the console receives key presses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConsoleFocus {
+    Input,
+    Tasks,
+}
+
+/// One question of the guided `/inject` prompt, in the order asked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InjectField {
+    Description,
+    Priority,
+    Capabilities,
+    Deadline,
+    Approval,
+    Confirm,
+}
+
+impl InjectField {
+    const ALL: [InjectField; 6] = [
+        InjectField::Description,
+        InjectField::Priority,
+        InjectField::Capabilities,
+        InjectField::Deadline,
+        InjectField::Approval,
+        InjectField::Confirm,
+    ];
+
+    fn question(self) -> &'static str {
+        match self {
+            Self::Description => "Task description (Enter adds a line, Ctrl+D when done):",
+            Self::Priority => "Priority 0-255, higher is more urgent (blank = 0):",
+            Self::Capabilities => "Required capabilities, comma-separated (blank = any agent):",
+            Self::Deadline => "Deadline from now, e.g. 90s, 30m or 2h (blank = none):",
+            Self::Approval => "Hold the winning plan for operator approval? (y/N):",
+            Self::Confirm => "Inject this task? (Y/n):",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Description => "description",
+            Self::Priority => "priority",
+            Self::Capabilities => "capabilities",
+            Self::Deadline => "deadline",
+            Self::Approval => "approval",
+            Self::Confirm => "confirm",
+        }
+    }
+
+    /// 1-based position among the questions.
+    fn step(self) -> usize {
+        Self::ALL.iter().position(|f| *f == self).unwrap_or(0) + 1
+    }
+
+    fn next(self) -> Self {
+        Self::ALL.get(self.step()).copied().unwrap_or(Self::Confirm)
+    }
+}
+
+/// What an answer to the guided `/inject` prompt led to.
+#[derive(Debug, Clone, PartialEq)]
+enum PromptOutcome {
+    /// The answer was taken; ask the next question.
+    Next,
+    /// The answer was refused; ask the same question again.
+    Invalid(String),
+    /// The operator confirmed; inject with these `swarm.inject_task` params.
+    Submit(serde_json::Value),
+    /// The operator declined at confirmation.
+    Abandon,
+}
+
+/// The guided `/inject` prompt: collects `swarm.inject_task` params one
+/// question at a time.
+#[derive(Debug, Clone)]
+struct InjectPrompt {
+    field: InjectField,
+    params: serde_json::Map<String, serde_json::Value>,
+}
+
+impl InjectPrompt {
+    fn new() -> Self {
+        Self {
+            field: InjectField::Description,
+            params: serde_json::Map::new(),
+        }
+    }
+
+    /// A prompt for a task injected into `swarm_id` rather than the
+    /// connector's own swarm.
+    fn into_swarm(swarm_id: &str) -> Self {
+        let mut prompt = Self::new();
+        prompt.params.insert("swarm_id".into(), swarm_id.into());
+        prompt
+    }
+
+    /// Take the answer to the current question.
+    fn answer(&mut self, input: &str) -> PromptOutcome {
+        let input = input.trim();
+        match self.field {
+            InjectField::Description => {
+                if input.is_empty() {
+                    return PromptOutcome::Invalid("A task needs a description.".into());
+                }
+                self.params.insert("description".into(), input.into());
+            }
+            InjectField::Priority if !input.is_empty() => match input.parse::<u8>() {
+                Ok(priority) => {
+                    self.params.insert("priority".into(), priority.into());
+                }
+                Err(_) => {
+                    return PromptOutcome::Invalid("Priority must be a number from 0 to 255.".into())
+                }
+            },
+            InjectField::Capabilities => {
+                let capabilities: Vec<serde_json::Value> = input
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .map(Into::into)
+                    .collect();
+                if !capabilities.is_empty() {
+                    self.params.insert("capabilities_required".into(), capabilities.into());
+                }
+            }
+            InjectField::Deadline if !input.is_empty() => match parse_deadline_secs(input) {
+                Some(secs) => {
+                    self.params.insert("deadline_secs".into(), secs.into());
+                }
+                None => {
+                    return PromptOutcome::Invalid(
+                        "Deadline must be a positive number of seconds, or end in s, m or h.".into(),
+                    )
+                }
+            },
+            InjectField::Approval => match input.to_ascii_lowercase().as_str() {
+                "" | "n" | "no" => {
+                    self.params.insert("requires_approval".into(), false.into());
+                }
+                "y" | "yes" => {
+                    self.params.insert("requires_approval".into(), true.into());
+                }
+                _ => return PromptOutcome::Invalid("Answer y or n.".into()),
+            },
+            InjectField::Confirm => {
+                return match input.to_ascii_lowercase().as_str() {
+                    "" | "y" | "yes" => {
+                        PromptOutcome::Submit(serde_json::Value::Object(self.params.clone()))
+                    }
+                    "n" | "no" => PromptOutcome::Abandon,
+                    _ => PromptOutcome::Invalid("Answer y or n.".into()),
+                };
+            }
+            InjectField::Priority | InjectField::Deadline => {}
+        }
+        self.field = self.field.next();
+        PromptOutcome::Next
+    }
+
+    /// The collected fields, shown before the confirmation question.
+    fn summary(&self) -> Vec<String> {
+        let param = |key: &str| self.params.get(key);
+        let mut lines = vec![match param("swarm_id").and_then(|v| v.as_str()) {
+            Some(swarm_id) => format!("Task to inject into swarm {}:", swarm_id),
+            None => "Task to inject:".to_string(),
+        }];
+        let description = param("description").and_then(|v| v.as_str()).unwrap_or_default();
+        for (i, line) in description.lines().enumerate() {
+            let label = if i == 0 { "  description:" } else { "              " };
+            lines.push(format!("{} {}", label, line));
+        }
+        lines.push(format!(
+            "  priority:     {}",
+            param("priority").and_then(|v| v.as_u64()).unwrap_or(0)
+        ));
+        let capabilities = param("capabilities_required")
+            .and_then(|v| v.as_array())
+            .map(|caps| caps.iter().filter_map(|c| c.as_str()).collect::<Vec<_>>().join(", "))
+            .filter(|caps| !caps.is_empty())
+            .unwrap_or_else(|| "any".to_string());
+        lines.push(format!("  capabilities: {}", capabilities));
+        lines.push(format!(
+            "  deadline:     {}",
+            param("deadline_secs")
+                .and_then(|v| v.as_u64())
+                .map(|secs| format!("{}s from injection", secs))
+                .unwrap_or_else(|| "none".to_string())
+        ));
+        lines.push(format!(
+            "  approval:     {}",
+            if param("requires_approval").and_then(|v| v.as_bool()) == Some(true) {
+                "required"
+            } else {
+                "not required"
+            }
+        ));
+        lines
+    }
+}
+
+/// Parse a deadline such as `90`, `90s`, `30m` or `2h` into seconds.
+fn parse_deadline_secs(input: &str) -> Option<u64> {
+    let input = input.trim().to_ascii_lowercase();
+    let (number, unit) = match input.char_indices().last()? {
+        (i, 's') => (&input[..i], 1),
+        (i, 'm') => (&input[..i], 60),
+        (i, 'h') => (&input[..i], 3600),
+        _ => (input.as_str(), 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit))
+        .filter(|secs| *secs > 0)
+}
+
 /// The operator console TUI state.
 struct OperatorConsole {
     state: Arc<RwLock<ConnectorState>>,
@@ -101,8 +431,29 @@ struct OperatorConsole {
     log_scroll: u16,
     /// Scroll offset for the hierarchy panel.
     hierarchy_scroll: u16,
+    /// The hierarchy panel lists agents flat, sorted by tier, instead of
+    /// as a tree.
+    hierarchy_flat: bool,
+    tier_changes: TierChanges,
     /// Messages displayed in the console output area.
     console_messages: Vec<(chrono::DateTime<chrono::Utc>, String, Color)>,
+    focus: ConsoleFocus,
+    /// Active task IDs in table order, as of the last snapshot.
+    task_ids: Vec<String>,
+    /// Index of the selected row in the task table.
+    selected_task: usize,
+    /// Task shown in the detail view.
+    detail_task: Option<String>,
+    /// Scroll offset for the detail view.
+    detail_scroll: u16,
+    /// Command queued by a detail view key, run by the event loop.
+    pending_command: Option<String>,
+    /// Multi-line entry: Enter adds a line and Ctrl+D submits.
+    multiline: bool,
+    /// The guided `/inject` prompt, while it is asking its questions.
+    inject_prompt: Option<InjectPrompt>,
+    /// Blocked tasks the operator has already been told about.
+    announced_escalations: std::collections::HashSet<String>,
 }
 
 impl OperatorConsole {
@@ -131,7 +482,44 @@ impl OperatorConsole {
             history_pos: None,
             log_scroll: 0,
             hierarchy_scroll: 0,
+            hierarchy_flat: false,
+            tier_changes: TierChanges::default(),
             console_messages,
+            focus: ConsoleFocus::Input,
+            task_ids: Vec::new(),
+            selected_task: 0,
+            detail_task: None,
+            detail_scroll: 0,
+            pending_command: None,
+            multiline: false,
+            inject_prompt: None,
+            announced_escalations: std::collections::HashSet::new(),
+        }
+    }
+
+    /// Tell the operator about tasks that became blocked since the last
+    /// call, with the commands that resolve them.
+    async fn announce_escalations(&mut self) {
+        let escalations: Vec<(String, String)> = {
+            let state = self.state.read().await;
+            state
+                .open_escalations()
+                .into_iter()
+                .map(|e| (e.task_id.clone(), e.reason.clone()))
+                .collect()
+        };
+        self.announced_escalations
+            .retain(|task_id| escalations.iter().any(|(id, _)| id == task_id));
+        for (task_id, reason) in escalations {
+            if self.announced_escalations.insert(task_id.clone()) {
+                self.add_message(
+                    &format!(
+                        "Task {} is blocked: {}. /retry {} to hand it back, /reassign {} <agent_id> or /cancel {}",
+                        task_id, reason, task_id, task_id, task_id
+                    ),
+                    Color::LightRed,
+                );
+            }
         }
     }
 
@@ -148,6 +536,16 @@ impl OperatorConsole {
         // Build hierarchy from known agents.
         let hierarchy = build_hierarchy_tree(&state);
         let flow = summarize_flow_snapshot(&state);
+        let task_detail = self
+            .detail_task
+            .as_deref()
+            .and_then(|task_id| build_task_detail(&state, task_id));
+        let mut task_ids = state.task_set.elements();
+        // The set has no order of its own; keep the table stable between frames.
+        task_ids.sort_by_key(|task_id| {
+            let created_at = state.task_details.get(task_id).map(|t| t.created_at);
+            (created_at, task_id.clone())
+        });
 
         ConsoleSnapshot {
             agent_id: state.agent_id.to_string(),
@@ -157,9 +555,7 @@ impl OperatorConsole {
             status_color: status_color(&state.status),
             peer_count: state.member_set.len(),
             swarm_size: state.network_stats.total_agents,
-            active_tasks: state
-                .task_set
-                .elements()
+            active_tasks: task_ids
                 .iter()
                 .map(|task_id| {
                     if let Some(task) = state.task_details.get(task_id) {
@@ -189,15 +585,25 @@ impl OperatorConsole {
             event_log: state.event_log.clone(),
             current_swarm_name,
             flow,
+            task_detail,
         }
     }
 
     /// Process a command or task input from the operator.
     async fn process_input(&mut self) {
         let input = self.input.trim().to_string();
+        if let Some(prompt) = self.inject_prompt.take() {
+            // A blank answer takes the question's default.
+            self.input.clear();
+            self.cursor_pos = 0;
+            self.multiline = false;
+            self.answer_inject_prompt(prompt, &input).await;
+            return;
+        }
         if input.is_empty() {
             return;
         }
+        self.multiline = false;
 
         // Save to history.
         self.history.push(input.clone());
@@ -262,6 +668,46 @@ impl OperatorConsole {
                     "  /flow        - Show flow counters (votes/decompose/results)",
                     Color::White,
                 );
+                self.add_message(
+                    "  /template [name] - Inject a task from a template (no name lists them)",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /inject      - Guided injection: description, priority, capabilities, deadline, approval",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /inject-into <swarm_id> [text] - Inject into another joined swarm (no text: guided)",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /multi       - Multi-line input (Enter adds a line, Ctrl+D submits, Esc cancels)",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /approve <task_id> - Approve a plan awaiting sign-off",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /reject <task_id> [reason] - Reject a plan awaiting sign-off",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /open <task_id> - Open the task detail view (or Tab, arrows, Enter)",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /cancel <task_id> - Cancel a task and its open subtasks",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /reassign <task_id> <agent_id> - Hand a subtask to another agent",
+                    Color::White,
+                );
+                self.add_message(
+                    "  /retry <task_id> - Hand a blocked subtask back to its assignee",
+                    Color::White,
+                );
                 self.add_message(
                     "  /help        - Show this help message",
                     Color::White,
@@ -465,6 +911,150 @@ impl OperatorConsole {
                     Color::Cyan,
                 );
             }
+            "/template" => {
+                let name = parts.get(1).copied().unwrap_or("").trim().to_string();
+                if name.is_empty() {
+                    let mut templates = {
+                        let state = self.state.read().await;
+                        state
+                            .task_templates
+                            .iter()
+                            .map(|(name, t)| (name.clone(), t.description.clone()))
+                            .collect::<Vec<_>>()
+                    };
+                    templates.sort();
+                    if templates.is_empty() {
+                        self.add_message("No task templates defined.", Color::Yellow);
+                    } else {
+                        self.add_message("Task templates:", Color::Cyan);
+                        for (name, description) in templates {
+                            self.add_message(&format!("  {} - {}", name, description), Color::White);
+                        }
+                    }
+                } else {
+                    self.inject_template(&name).await;
+                }
+            }
+            "/inject" => {
+                self.add_message("Guided task injection (Esc cancels at any question).", Color::Cyan);
+                self.ask(InjectPrompt::new());
+            }
+            "/inject-into" => {
+                let mut args = parts.get(1).copied().unwrap_or("").trim().splitn(2, char::is_whitespace);
+                let swarm_id = args.next().unwrap_or("").to_string();
+                let description = args.next().unwrap_or("").trim().to_string();
+                if swarm_id.is_empty() {
+                    self.add_message("Usage: /inject-into <swarm_id> [description]", Color::Yellow);
+                    return;
+                }
+                let membership = self.state.read().await.require_membership(&swarm_id);
+                if let Err(reason) = membership {
+                    self.add_message(&reason, Color::Red);
+                } else if description.is_empty() {
+                    self.add_message(
+                        &format!("Guided task injection into swarm {} (Esc cancels at any question).", swarm_id),
+                        Color::Cyan,
+                    );
+                    self.ask(InjectPrompt::into_swarm(&swarm_id));
+                } else {
+                    let params = serde_json::json!({ "description": description, "swarm_id": swarm_id });
+                    self.inject_with_params(&params).await;
+                }
+            }
+            "/multi" => {
+                self.multiline = true;
+                self.add_message(
+                    "Multi-line input: Enter adds a line, Ctrl+D submits, Esc cancels.",
+                    Color::Cyan,
+                );
+            }
+            "/approve" | "/reject" => {
+                let mut args = parts.get(1).copied().unwrap_or("").trim().splitn(2, ' ');
+                let task_id = args.next().unwrap_or("").trim().to_string();
+                let reason = args.next().unwrap_or("").trim().to_string();
+                if task_id.is_empty() {
+                    self.add_message(&format!("Usage: {} <task_id>", command), Color::Yellow);
+                } else {
+                    let approve = command == "/approve";
+                    let plan_id = {
+                        let mut state = self.state.write().await;
+                        let actor = state.agent_id.to_string();
+                        if approve {
+                            state.approve_plan(&task_id, &actor)
+                        } else {
+                            state.reject_plan(&task_id, &actor, &reason)
+                        }
+                    };
+                    match plan_id {
+                        Some(plan_id) if approve => self.add_message(
+                            &format!("Plan {} approved for task {}", plan_id, task_id),
+                            Color::Green,
+                        ),
+                        Some(plan_id) => self.add_message(
+                            &format!("Plan {} rejected for task {}", plan_id, task_id),
+                            Color::Yellow,
+                        ),
+                        None => self.add_message(
+                            &format!("No plan awaiting approval for task {}", task_id),
+                            Color::Red,
+                        ),
+                    }
+                }
+            }
+            "/open" => {
+                let task_id = parts.get(1).copied().unwrap_or("").trim().to_string();
+                if task_id.is_empty() {
+                    self.add_message("Usage: /open <task_id>", Color::Yellow);
+                } else if self.state.read().await.task_details.contains_key(&task_id) {
+                    self.open_task(task_id);
+                } else {
+                    self.add_message(&format!("Unknown task {}", task_id), Color::Red);
+                }
+            }
+            "/cancel" => {
+                let task_id = parts.get(1).copied().unwrap_or("").trim().to_string();
+                if task_id.is_empty() {
+                    self.add_message("Usage: /cancel <task_id>", Color::Yellow);
+                } else {
+                    let cancelled = {
+                        let mut state = self.state.write().await;
+                        let actor = state.agent_id.to_string();
+                        state.cancel_task(&task_id, &actor)
+                    };
+                    match cancelled {
+                        Ok(closed) => self.add_message(
+                            &format!("Task {} cancelled ({} tasks closed)", task_id, closed.len()),
+                            Color::Yellow,
+                        ),
+                        Err(reason) => self.add_message(&reason, Color::Red),
+                    }
+                }
+            }
+            "/reassign" => {
+                let mut args = parts.get(1).copied().unwrap_or("").split_whitespace();
+                let (Some(task_id), Some(assignee)) = (args.next(), args.next()) else {
+                    self.add_message("Usage: /reassign <task_id> <agent_id>", Color::Yellow);
+                    return;
+                };
+                self.reassign_task(task_id, assignee).await;
+            }
+            "/retry" => {
+                let task_id = parts.get(1).copied().unwrap_or("").trim().to_string();
+                if task_id.is_empty() {
+                    self.add_message("Usage: /retry <task_id>", Color::Yellow);
+                    return;
+                }
+                let retried =
+                    crate::rpc_server::retry_task(&task_id, &self.state, &self.network_handle)
+                        .await;
+                match retried {
+                    Ok(assignment) => self.add_message(
+                        &format!("Task {} handed back to {}", task_id, assignment.assignee),
+                        Color::Green,
+                    ),
+                    Err(reason) => self.add_message(&reason, Color::Red),
+                }
+            }
             "/quit" | "/exit" | "/q" => {
                 // Handled in the event loop.
             }
@@ -477,11 +1067,126 @@ impl OperatorConsole {
         }
     }
 
+    /// Reassign a subtask and publish the new assignment to its swarm.
+    async fn reassign_task(&mut self, task_id: &str, assignee: &str) {
+        let reassigned =
+            crate::rpc_server::reassign_task(task_id, assignee, &self.state, &self.network_handle)
+                .await;
+        if let Err(reason) = reassigned {
+            self.add_message(&reason, Color::Red);
+            return;
+        }
+        self.add_message(
+            &format!("Task {} reassigned to {}", task_id, assignee),
+            Color::Green,
+        );
+    }
+
+    /// Ask the prompt's current question and wait for the answer.
+    fn ask(&mut self, prompt: InjectPrompt) {
+        self.add_message(prompt.field.question(), Color::Cyan);
+        self.multiline = prompt.field == InjectField::Description;
+        self.inject_prompt = Some(prompt);
+    }
+
+    /// Apply an answer to the guided `/inject` prompt.
+    async fn answer_inject_prompt(&mut self, mut prompt: InjectPrompt, answer: &str) {
+        match prompt.answer(answer) {
+            PromptOutcome::Next => {
+                if prompt.field == InjectField::Confirm {
+                    for line in prompt.summary() {
+                        self.add_message(&line, Color::White);
+                    }
+                }
+                self.ask(prompt);
+            }
+            PromptOutcome::Invalid(reason) => {
+                self.add_message(&reason, Color::Red);
+                self.ask(prompt);
+            }
+            PromptOutcome::Submit(params) => self.inject_with_params(&params).await,
+            PromptOutcome::Abandon => self.add_message("Task injection cancelled.", Color::Yellow),
+        }
+    }
+
+    /// Inject a task through the `swarm.inject_task` handler, so the console
+    /// validates and publishes it exactly as an RPC client's task.
+    async fn inject_with_params(&mut self, params: &serde_json::Value) {
+        let response = crate::rpc_server::handle_inject_task(
+            None,
+            params,
+            &self.state,
+            &self.network_handle,
+        )
+        .await;
+        match (response.result, response.error) {
+            (Some(result), _) => {
+                let task_id = result.get("task_id").and_then(|v| v.as_str()).unwrap_or_default();
+                if result.get("held").and_then(|v| v.as_bool()) == Some(true) {
+                    self.add_message(
+                        &format!("Task {} held until its prerequisites finish", task_id),
+                        Color::Yellow,
+                    );
+                } else {
+                    let swarm_id = result.get("swarm_id").and_then(|v| v.as_str()).unwrap_or_default();
+                    self.add_message(
+                        &format!("Task injected: {} (swarm {})", task_id, swarm_id),
+                        Color::Green,
+                    );
+                }
+            }
+            (None, Some(error)) => {
+                self.add_message(&format!("Task injection failed: {}", error.message), Color::Red)
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Drop the text being typed, leaving multi-line entry and any guided prompt.
+    fn cancel_input(&mut self) {
+        if self.inject_prompt.take().is_some() {
+            self.add_message("Task injection cancelled.", Color::Yellow);
+        }
+        self.multiline = false;
+        self.input.clear();
+        self.cursor_pos = 0;
+    }
+
+    /// Show `task_id` in the detail view.
+    fn open_task(&mut self, task_id: String) {
+        self.detail_task = Some(task_id);
+        self.detail_scroll = 0;
+        self.focus = ConsoleFocus::Tasks;
+    }
+
     /// Inject a task into the swarm.
     async fn inject_task(&mut self, description: &str) {
+        let epoch = self.state.read().await.epoch_manager.current_epoch();
+        self.publish_injection(Task::new(description.to_string(), 1, epoch)).await;
+    }
+
+    /// Inject a task built from a named template.
+    async fn inject_template(&mut self, name: &str) {
+        let task = {
+            let state = self.state.read().await;
+            state
+                .task_templates
+                .get(name)
+                .map(|t| t.instantiate(state.epoch_manager.current_epoch()))
+        };
+        match task {
+            Some(task) => self.publish_injection(task).await,
+            None => self.add_message(
+                &format!("Unknown template: {}. Type /template to list templates.", name),
+                Color::Red,
+            ),
+        }
+    }
+
+    /// Record a task locally and publish it to the swarm.
+    async fn publish_injection(&mut self, task: Task) {
         let mut state = self.state.write().await;
-        let epoch = state.epoch_manager.current_epoch();
-        let task = Task::new(description.to_string(), 1, epoch);
+        let description = task.description.clone();
         let task_id = task.task_id.clone();
         let originator = state.agent_id.clone();
         let swarm_id = state.current_swarm_id.as_str().to_string();
@@ -509,6 +1214,8 @@ impl OperatorConsole {
         let inject_params = TaskInjectionParams {
             task: task.clone(),
             originator,
+            attachments: Vec::new(),
+            attempt: 0,
         };
 
         let msg = SwarmMessage::new(
@@ -518,7 +1225,7 @@ impl OperatorConsole {
         );
 
         if let Ok(data) = serde_json::to_vec(&msg) {
-            let topic = SwarmTopics::tasks_for(&swarm_id, 1);
+            let topic = SwarmTopics::tasks_for(&swarm_id, task.tier_level);
             if let Err(e) = self.network_handle.publish(&topic, data).await {
                 tracing::debug!(error = %e, "Failed to publish console task injection");
             }
@@ -554,6 +1261,47 @@ impl OperatorConsole {
         }
     }
 
+    /// Handle each line of `script` as if typed and submitted, writing the
+    /// line and the console's replies to `transcript`.
+    async fn run_script<R: AsyncBufRead + Unpin>(
+        &mut self,
+        script: R,
+        transcript: &mut (dyn Write + Send),
+    ) -> io::Result<()> {
+        // The interactive greeting is no part of the run.
+        self.console_messages.clear();
+        let mut lines = script.lines();
+        while let Some(line) = lines.next_line().await? {
+            let line = line.trim();
+            // A blank answer to the guided `/inject` prompt takes its default.
+            if self.inject_prompt.is_none() && (line.is_empty() || line.starts_with('#')) {
+                continue;
+            }
+            writeln!(transcript, "[{}] > {}", chrono::Utc::now().format("%H:%M:%S"), line)?;
+            if matches!(line, "/quit" | "/exit" | "/q") {
+                break;
+            }
+            match line.strip_prefix("sleep ") {
+                // Same syntax as a deadline.
+                Some(duration) if self.inject_prompt.is_none() => match parse_deadline_secs(duration) {
+                    Some(secs) => tokio::time::sleep(Duration::from_secs(secs)).await,
+                    None => self.add_message(&format!("Invalid sleep duration: {}", duration), Color::Red),
+                },
+                _ => {
+                    self.input = line.to_string();
+                    self.cursor_pos = self.input.len();
+                    self.process_input().await;
+                }
+            }
+            self.announce_escalations().await;
+            for (at, message, _) in self.console_messages.drain(..) {
+                writeln!(transcript, "[{}]   {}", at.format("%H:%M:%S"), message)?;
+            }
+            transcript.flush()?;
+        }
+        Ok(())
+    }
+
     /// Render the full operator console layout.
     fn render(&self, frame: &mut Frame, snapshot: &ConsoleSnapshot) {
         let outer = Layout::default()
@@ -561,7 +1309,7 @@ impl OperatorConsole {
             .constraints([
                 Constraint::Length(3),  // Status bar
                 Constraint::Min(8),    // Main area (hierarchy + tasks + log)
-                Constraint::Length(5), // Input area
+                Constraint::Length(4 + self.input_line_count()), // Input area
             ])
             .split(frame.area());
 
@@ -602,8 +1350,14 @@ impl OperatorConsole {
         frame.render_widget(paragraph, area);
     }
 
-    /// Render the main area with hierarchy, tasks, and event log.
+    /// Render the main area with hierarchy, tasks, and event log, or the
+    /// open task's detail view.
     fn render_main_area(&self, frame: &mut Frame, area: Rect, snap: &ConsoleSnapshot) {
+        if let Some(detail) = &snap.task_detail {
+            self.render_task_detail(frame, area, detail);
+            return;
+        }
+
         let columns = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -670,8 +1424,13 @@ impl OperatorConsole {
 
     /// Render the agent hierarchy tree panel.
     fn render_hierarchy(&self, frame: &mut Frame, area: Rect, snap: &ConsoleSnapshot) {
+        let title = if self.hierarchy_flat {
+            " Known Agents (by tier) "
+        } else {
+            " Known Agents "
+        };
         let block = Block::default()
-            .title(" Known Agents ")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Yellow));
 
@@ -698,8 +1457,14 @@ impl OperatorConsole {
         }
 
         let mut lines: Vec<Line> = Vec::new();
-        for node in &snap.hierarchy {
-            render_hierarchy_lines(node, "", true, &mut lines);
+        if self.hierarchy_flat {
+            for node in &flatten_hierarchy(&snap.hierarchy) {
+                render_hierarchy_lines(node, "", true, &mut lines);
+            }
+        } else {
+            for node in &snap.hierarchy {
+                render_hierarchy_lines(node, "", true, &mut lines);
+            }
         }
 
         // Apply scroll offset.
@@ -717,10 +1482,15 @@ impl OperatorConsole {
 
     /// Render the active tasks panel.
     fn render_tasks(&self, frame: &mut Frame, area: Rect, snap: &ConsoleSnapshot) {
+        let border_color = if self.focus == ConsoleFocus::Tasks {
+            Color::Cyan
+        } else {
+            Color::White
+        };
         let block = Block::default()
             .title(format!(" Active Tasks ({}) ", snap.active_tasks.len()))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::White));
+            .border_style(Style::default().fg(border_color));
 
         if snap.active_tasks.is_empty() {
             let text = Paragraph::new(Line::from(Span::styled(
@@ -732,10 +1502,17 @@ impl OperatorConsole {
             return;
         }
 
+        // Scroll the table so the selected row stays visible.
+        let visible_rows = area.height.saturating_sub(3).max(1) as usize;
+        let selected = self.selected_task.min(snap.active_tasks.len() - 1);
+        let first_row = (selected + 1).saturating_sub(visible_rows);
+
         let rows: Vec<Row> = snap
             .active_tasks
             .iter()
-            .map(|task| {
+            .enumerate()
+            .skip(first_row)
+            .map(|(index, task)| {
                 let short_id = if task.task_id.len() > 16 {
                     format!("{}...", &task.task_id[..16])
                 } else {
@@ -753,9 +1530,10 @@ impl OperatorConsole {
                     "In Progress" => Color::Blue,
                     "Completed" => Color::Green,
                     "Failed" | "Rejected" => Color::Red,
+                    "Disputed" => Color::LightRed,
                     _ => Color::White,
                 };
-                Row::new(vec![
+                let row = Row::new(vec![
                     ratatui::widgets::Cell::from(Span::styled(
                         format!("  {}", short_id),
                         Style::default().fg(Color::White),
@@ -772,7 +1550,12 @@ impl OperatorConsole {
                         format!("{} [{} st]", desc, task.subtask_count),
                         Style::default().fg(Color::Gray),
                     )),
-                ])
+                ]);
+                if self.focus == ConsoleFocus::Tasks && index == selected {
+                    row.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    row
+                }
             })
             .collect();
 
@@ -794,6 +1577,70 @@ impl OperatorConsole {
         frame.render_widget(table, area);
     }
 
+    /// Render the detail view of one task.
+    fn render_task_detail(&self, frame: &mut Frame, area: Rect, detail: &TaskDetailView) {
+        let block = Block::default()
+            .title(format!(
+                " Task {} (Esc back, a approve, c cancel, r reassign, t retry, Up/Down scroll) ",
+                detail.task_id
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let label = |text: &str| Span::styled(format!("  {}", text), Style::default().fg(Color::Gray));
+        let heading = |text: &str, count: usize| {
+            Line::from(Span::styled(
+                format!("  {} ({})", text, count),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ))
+        };
+        let mut lines = vec![
+            Line::from(vec![
+                label("Status: "),
+                Span::styled(&detail.status, Style::default().fg(Color::Yellow)),
+                label("Assigned: "),
+                Span::styled(&detail.assigned_to, Style::default().fg(Color::White)),
+                label("Swarm: "),
+                Span::styled(&detail.swarm_id, Style::default().fg(Color::LightCyan)),
+            ]),
+            Line::from(vec![
+                label("Description: "),
+                Span::styled(&detail.description, Style::default().fg(Color::White)),
+            ]),
+        ];
+        if let Some(plan_id) = &detail.awaiting_approval {
+            lines.push(Line::from(Span::styled(
+                format!("  Plan {} awaits approval (press a)", plan_id),
+                Style::default().fg(Color::LightRed),
+            )));
+        }
+        if let Some(reason) = &detail.blocked {
+            lines.push(Line::from(Span::styled(
+                format!("  Blocked: {} (press t to retry, r to reassign, c to cancel)", reason),
+                Style::default().fg(Color::LightRed),
+            )));
+        }
+        let sections = [
+            ("Subtasks", &detail.subtask_tree),
+            ("Timeline", &detail.timeline),
+            ("Deliberation", &detail.deliberation),
+            ("Ballots", &detail.ballots),
+            ("IRV rounds", &detail.irv_rounds),
+        ];
+        for (title, entries) in sections {
+            lines.push(Line::from(""));
+            lines.push(heading(title, entries.len()));
+            lines.extend(entries.iter().map(|entry| {
+                Line::from(Span::styled(format!("    {}", entry), Style::default().fg(Color::White)))
+            }));
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .scroll((self.detail_scroll, 0));
+        frame.render_widget(paragraph, area);
+    }
+
     /// Render the console output area (mixed console messages + recent events).
     fn render_console_output(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
@@ -836,53 +1683,107 @@ impl OperatorConsole {
     }
 
     /// Render the input area at the bottom.
+    /// Lines the input area shows for the text being typed.
+    fn input_line_count(&self) -> u16 {
+        (self.input.matches('\n').count() + 1).min(MAX_INPUT_LINES) as u16
+    }
+
     fn render_input(&self, frame: &mut Frame, area: Rect) {
+        let title = match (&self.inject_prompt, self.multiline) {
+            (Some(prompt), _) => format!(
+                " /inject {}/{}: {} (Esc = cancel) ",
+                prompt.field.step(),
+                InjectField::ALL.len(),
+                prompt.field.label()
+            ),
+            (None, true) => " Multi-line Input (Enter = new line, Ctrl+D = submit, Esc = cancel) ".to_string(),
+            (None, false) => " Command Input (Enter = inject task, /help = commands, /quit = exit) ".to_string(),
+        };
         let block = Block::default()
-            .title(" Command Input (Enter = inject task, /help = commands, /quit = exit) ")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Green));
 
-        // Show the input with cursor.
-        let input_display = if self.input.is_empty() {
-            Line::from(vec![
+        // Show the input with cursor, scrolled to keep the last lines visible.
+        let mut lines = vec![Line::from("")];
+        if self.input.is_empty() {
+            let placeholder = match &self.inject_prompt {
+                Some(prompt) => prompt.field.question(),
+                None => "Type a task description or /command...",
+            };
+            lines.push(Line::from(vec![
                 Span::styled("  > ", Style::default().fg(Color::Green)),
-                Span::styled(
-                    "Type a task description or /command...",
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ])
+                Span::styled(placeholder, Style::default().fg(Color::DarkGray)),
+            ]));
         } else {
-            Line::from(vec![
-                Span::styled("  > ", Style::default().fg(Color::Green)),
-                Span::styled(&self.input, Style::default().fg(Color::White)),
-            ])
-        };
+            let input_lines: Vec<&str> = self.input.split('\n').collect();
+            let skipped = input_lines.len() - self.input_line_count() as usize;
+            for (i, text) in input_lines.iter().enumerate().skip(skipped) {
+                let prefix = if i == 0 { "  > " } else { "  | " };
+                lines.push(Line::from(vec![
+                    Span::styled(prefix, Style::default().fg(Color::Green)),
+                    Span::styled(*text, Style::default().fg(Color::White)),
+                ]));
+            }
+        }
 
-        let hint_line = Line::from(vec![
-            Span::styled(
-                "  Ctrl+C or /quit to exit  |  Up/Down for history  |  Enter to submit",
-                Style::default().fg(Color::DarkGray),
-            ),
-        ]);
+        let hint = match self.focus {
+            ConsoleFocus::Input if self.multiline => {
+                "  Enter for a new line  |  Ctrl+D to submit  |  Esc to cancel"
+            }
+            ConsoleFocus::Input if self.inject_prompt.is_some() => {
+                "  Enter to answer  |  Esc to cancel the injection"
+            }
+            ConsoleFocus::Input => {
+                "  Ctrl+C or /quit to exit  |  Up/Down for history  |  Enter to submit  |  Alt+Enter: new line  |  Tab: tasks  |  Ctrl+T: tree/flat"
+            }
+            ConsoleFocus::Tasks => {
+                "  Up/Down select  |  Enter open task  |  Esc back  |  Tab: command input"
+            }
+        };
+        lines.push(Line::from(vec![Span::styled(hint, Style::default().fg(Color::DarkGray))]));
 
-        let paragraph = Paragraph::new(vec![
-            Line::from(""),
-            input_display,
-            hint_line,
-        ])
-        .block(block);
+        let paragraph = Paragraph::new(lines).block(block);
         frame.render_widget(paragraph, area);
 
         // Position cursor.
-        let cursor_x = area.x + 4 + self.cursor_pos as u16;
-        let cursor_y = area.y + 2;
+        let before = &self.input[..self.cursor_pos];
+        let row = before.matches('\n').count();
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1);
+        let first_shown = self.input.matches('\n').count() + 1 - self.input_line_count() as usize;
+        let cursor_x = area.x + 4 + column as u16;
+        let cursor_y = area.y + 2 + row.saturating_sub(first_shown) as u16;
         frame.set_cursor_position((cursor_x, cursor_y));
     }
 
+    /// Whether a key press submits the input: Enter on a single line, or
+    /// Ctrl+D during multi-line entry.
+    fn submits(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        if self.focus != ConsoleFocus::Input {
+            return false;
+        }
+        match code {
+            KeyCode::Enter => !self.multiline && !modifiers.contains(KeyModifiers::ALT),
+            KeyCode::Char('d') => self.multiline && modifiers.contains(KeyModifiers::CONTROL),
+            _ => false,
+        }
+    }
+
     /// Handle keyboard input. Returns `true` if the console should exit.
     fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
         match (code, modifiers) {
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => return true,
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                self.hierarchy_flat = !self.hierarchy_flat;
+                self.hierarchy_scroll = 0;
+            }
+            (KeyCode::Tab, _) => {
+                self.focus = match self.focus {
+                    ConsoleFocus::Input => ConsoleFocus::Tasks,
+                    ConsoleFocus::Tasks => ConsoleFocus::Input,
+                };
+            }
+            _ if self.focus == ConsoleFocus::Tasks => self.handle_task_key(code),
             (KeyCode::Char(c), _) => {
                 self.input.insert(self.cursor_pos, c);
                 self.cursor_pos += 1;
@@ -914,7 +1815,8 @@ impl OperatorConsole {
             (KeyCode::End, _) => {
                 self.cursor_pos = self.input.len();
             }
-            (KeyCode::Up, _) => {
+            (KeyCode::Esc, _) => self.cancel_input(),
+            (KeyCode::Up, _) if !self.multiline => {
                 if !self.history.is_empty() {
                     let pos = match self.history_pos {
                         Some(p) if p > 0 => p - 1,
@@ -926,7 +1828,7 @@ impl OperatorConsole {
                     self.cursor_pos = self.input.len();
                 }
             }
-            (KeyCode::Down, _) => {
+            (KeyCode::Down, _) if !self.multiline => {
                 if let Some(pos) = self.history_pos {
                     if pos + 1 < self.history.len() {
                         let new_pos = pos + 1;
@@ -941,7 +1843,11 @@ impl OperatorConsole {
                 }
             }
             (KeyCode::Enter, _) => {
-                // Handled by caller (needs async).
+                // A plain Enter that submits is handled by the caller (needs
+                // async); any other Enter starts or continues multi-line entry.
+                self.multiline = true;
+                self.input.insert(self.cursor_pos, '\n');
+                self.cursor_pos += 1;
             }
             (KeyCode::PageUp, _) => {
                 self.hierarchy_scroll = self.hierarchy_scroll.saturating_sub(5);
@@ -953,24 +1859,88 @@ impl OperatorConsole {
         }
         false
     }
+
+    /// Keys while the task table or the detail view has focus.
+    fn handle_task_key(&mut self, code: KeyCode) {
+        if let Some(task_id) = self.detail_task.clone() {
+            match code {
+                KeyCode::Esc | KeyCode::Backspace => self.detail_task = None,
+                KeyCode::Up => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+                KeyCode::Down => self.detail_scroll += 1,
+                KeyCode::PageUp => self.detail_scroll = self.detail_scroll.saturating_sub(10),
+                KeyCode::PageDown => self.detail_scroll += 10,
+                KeyCode::Char('a') => self.pending_command = Some(format!("/approve {}", task_id)),
+                KeyCode::Char('c') => self.pending_command = Some(format!("/cancel {}", task_id)),
+                KeyCode::Char('t') => self.pending_command = Some(format!("/retry {}", task_id)),
+                KeyCode::Char('r') => {
+                    // The new assignee is typed into the command input.
+                    self.input = format!("/reassign {} ", task_id);
+                    self.cursor_pos = self.input.len();
+                    self.focus = ConsoleFocus::Input;
+                }
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Esc => self.focus = ConsoleFocus::Input,
+            KeyCode::Up => self.selected_task = self.selected_task.saturating_sub(1),
+            KeyCode::Down if self.selected_task + 1 < self.task_ids.len() => {
+                self.selected_task += 1;
+            }
+            KeyCode::Enter => {
+                if let Some(task_id) = self.task_ids.get(self.selected_task).cloned() {
+                    self.open_task(task_id);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Build a hierarchy tree from the connector state.
+///
+/// Members seen within [`HIERARCHY_MEMBER_WINDOW`] are shown, along with
+/// agents that hold a tier but have not been heard from in that window.
 fn build_hierarchy_tree(state: &ConnectorState) -> Vec<HierarchyNode> {
     let mut nodes: std::collections::HashMap<String, HierarchyNode> = std::collections::HashMap::new();
-    for agent_id in state.active_member_ids(Duration::from_secs(180)) {
+    let keepalive_secs = state
+        .config_reload
+        .running()
+        .hierarchy
+        .keepalive_interval_secs;
+    let fresh_secs = keepalive_secs.saturating_mul(2) as i64;
+    let active = state.active_member_ids(HIERARCHY_MEMBER_WINDOW);
+    let missing: Vec<String> = state
+        .agent_tiers
+        .keys()
+        .filter(|id| !active.contains(id))
+        .cloned()
+        .collect();
+    let agent_ids = active
+        .into_iter()
+        .map(|id| (id, false))
+        .chain(missing.into_iter().map(|id| (id, true)));
+    for (agent_id, is_missing) in agent_ids {
         let last_seen_secs = state.member_last_seen.get(&agent_id).map(|ts| {
             chrono::Utc::now()
                 .signed_duration_since(*ts)
                 .num_seconds()
                 .max(0)
         });
+        let is_self = agent_id == state.agent_id.to_string();
+        let liveness = match last_seen_secs {
+            _ if is_self => Liveness::Fresh,
+            _ if is_missing => Liveness::Missing,
+            Some(age) if age <= fresh_secs => Liveness::Fresh,
+            _ => Liveness::Stale,
+        };
         let tier = state
             .agent_tiers
             .get(&agent_id)
             .map(format_tier)
             .unwrap_or_else(|| {
-                if agent_id == state.agent_id.to_string() {
+                if is_self {
                     format_tier(&state.my_tier)
                 } else {
                     "Peer".to_string()
@@ -985,13 +1955,16 @@ fn build_hierarchy_tree(state: &ConnectorState) -> Vec<HierarchyNode> {
         nodes.insert(
             agent_id.clone(),
             HierarchyNode {
-                display_name: truncate_agent_id(&agent_id),
+                display_name: truncate_agent_id(&state.display_name(&agent_id)),
                 agent_id: agent_id.clone(),
                 tier,
-                is_self: agent_id == state.agent_id.to_string(),
+                is_self,
                 children: Vec::new(),
                 task_count,
                 last_seen_secs,
+                liveness,
+                rtt: state.peer_rtt.get(&agent_id).copied(),
+                tier_changed: false,
             },
         );
     }
@@ -1019,24 +1992,19 @@ fn build_hierarchy_tree(state: &ConnectorState) -> Vec<HierarchyNode> {
 }
 
 fn summarize_flow_snapshot(state: &ConnectorState) -> FlowSnapshot {
-    let mut flow = FlowSnapshot::default();
-
-    for events in state.task_timelines.values() {
-        for event in events {
-            match event.stage.as_str() {
-                "injected" => flow.injected += 1,
-                "proposed" => flow.proposed += 1,
-                "proposal_commit" => flow.commits += 1,
-                "proposal_reveal" => flow.reveals += 1,
-                "vote_recorded" => flow.votes += 1,
-                "plan_selected" => flow.selected += 1,
-                "subtask_created" => flow.subtasks += 1,
-                "subtask_assigned" | "assigned" => flow.assigned += 1,
-                "result_submitted" => flow.results += 1,
-                _ => {}
-            }
-        }
-    }
+    let counts = state.flow_counts(None);
+    let mut flow = FlowSnapshot {
+        injected: counts.injected,
+        proposed: counts.proposed,
+        commits: counts.commits,
+        reveals: counts.reveals,
+        votes: counts.votes,
+        selected: counts.selected,
+        subtasks: counts.subtasks,
+        assigned: counts.assigned,
+        results: counts.results,
+        ..FlowSnapshot::default()
+    };
 
     for entry in state.event_log.iter().rev().take(200) {
         match entry.category {
@@ -1049,6 +2017,38 @@ fn summarize_flow_snapshot(state: &ConnectorState) -> FlowSnapshot {
     flow
 }
 
+/// Every node of the tree without its children, sorted by tier from the
+/// top, then by name.
+fn flatten_hierarchy(nodes: &[HierarchyNode]) -> Vec<HierarchyNode> {
+    fn collect(nodes: &[HierarchyNode], flat: &mut Vec<HierarchyNode>) {
+        for node in nodes {
+            flat.push(HierarchyNode {
+                children: Vec::new(),
+                ..node.clone()
+            });
+            collect(&node.children, flat);
+        }
+    }
+    let mut flat = Vec::new();
+    collect(nodes, &mut flat);
+    flat.sort_by(|a, b| {
+        tier_rank(&a.tier)
+            .cmp(&tier_rank(&b.tier))
+            .then_with(|| a.display_name.cmp(&b.display_name))
+    });
+    flat
+}
+
+/// Sort key of a displayed tier: numbered tiers from the top, then
+/// executors, then peers with no tier.
+fn tier_rank(tier: &str) -> (u8, u32) {
+    match tier.strip_prefix("Tier").and_then(|n| n.parse().ok()) {
+        Some(n) => (0, n),
+        None if tier == "Executor" => (1, 0),
+        None => (2, 0),
+    }
+}
+
 /// Render hierarchy tree into display lines.
 fn render_hierarchy_lines(
     node: &HierarchyNode,
@@ -1079,18 +2079,25 @@ fn render_hierarchy_lines(
         .last_seen_secs
         .map(|s| format!(" [{}s ago]", s))
         .unwrap_or_else(|| " [never]".to_string());
+    let rtt = node
+        .rtt
+        .map(|rtt| format!(" {}ms", rtt.as_millis()))
+        .unwrap_or_default();
     let task_info = if node.task_count > 0 {
         format!(" [{}t]", node.task_count)
     } else {
         String::new()
     };
+    let (badge, badge_color) = node.liveness.badge();
+    let mut tier_style = Style::default().fg(tier_color).add_modifier(Modifier::BOLD);
+    if node.tier_changed {
+        tier_style = tier_style.add_modifier(Modifier::REVERSED);
+    }
 
     lines.push(Line::from(vec![
         Span::styled(branch, Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            format!("[{}]", node.tier),
-            Style::default().fg(tier_color).add_modifier(Modifier::BOLD),
-        ),
+        Span::styled(format!("{} ", badge), Style::default().fg(badge_color)),
+        Span::styled(format!("[{}]", node.tier), tier_style),
         Span::styled(
             format!(" {}", node.display_name),
             Style::default().fg(if node.is_self {
@@ -1104,6 +2111,7 @@ fn render_hierarchy_lines(
             Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
         ),
         Span::styled(last_seen, Style::default().fg(Color::DarkGray)),
+        Span::styled(rtt, Style::default().fg(Color::Cyan)),
         Span::styled(task_info, Style::default().fg(Color::Yellow)),
     ]));
 
@@ -1139,13 +2147,17 @@ fn print_hierarchy_node(
     let self_marker = if node.is_self { " (you)" } else { "" };
     let last_seen = node
         .last_seen_secs
-        .map(|s| format!(" [{}s ago]", s))
-        .unwrap_or_else(|| " [never]".to_string());
+        .map(|s| format!(" [{}s ago, {}]", s, node.liveness.label()))
+        .unwrap_or_else(|| format!(" [never, {}]", node.liveness.label()));
+    let rtt = node
+        .rtt
+        .map(|rtt| format!(" {}ms", rtt.as_millis()))
+        .unwrap_or_default();
     messages.push((
         chrono::Utc::now(),
         format!(
-            "  {}[{}] {}{}{}",
-            branch, node.tier, node.display_name, self_marker, last_seen
+            "  {}[{}] {}{}{}{}",
+            branch, node.tier, node.display_name, self_marker, last_seen, rtt
         ),
         if node.is_self { Color::Green } else { Color::White },
     ));
@@ -1164,6 +2176,128 @@ fn print_hierarchy_node(
     }
 }
 
+/// Collect the detail view of `task_id`, or `None` for an unknown task.
+fn build_task_detail(state: &ConnectorState, task_id: &str) -> Option<TaskDetailView> {
+    let task = state.task_details.get(task_id)?;
+    let name = |agent: &str| truncate_agent_id(&state.display_name(agent));
+
+    let timeline = state
+        .task_timelines
+        .get(task_id)
+        .into_iter()
+        .flatten()
+        .map(|event| {
+            format!(
+                "{} {:<22} {}",
+                event.timestamp.format("%H:%M:%S"),
+                event.stage,
+                event.detail
+            )
+        })
+        .collect();
+    let deliberation = state
+        .deliberation_messages
+        .get(task_id)
+        .into_iter()
+        .flatten()
+        .map(|message| {
+            format!(
+                "r{} {:?} {}: {}",
+                message.round,
+                message.message_type,
+                name(message.speaker.as_str()),
+                message.content
+            )
+        })
+        .collect();
+    let ballots = state
+        .ballot_records
+        .get(task_id)
+        .into_iter()
+        .flatten()
+        .map(|ballot| format!("{}: {}", name(ballot.voter.as_str()), ballot.rankings.join(" > ")))
+        .collect();
+    let irv_rounds = state
+        .irv_rounds
+        .get(task_id)
+        .into_iter()
+        .flatten()
+        .map(|round| {
+            let mut tallies: Vec<String> = round
+                .tallies
+                .iter()
+                .map(|(plan, votes)| format!("{}={}", plan, votes))
+                .collect();
+            tallies.sort();
+            format!(
+                "round {}: {} eliminated={}",
+                round.round_number,
+                tallies.join(" "),
+                round.eliminated.as_deref().unwrap_or("-")
+            )
+        })
+        .collect();
+
+    let mut subtask_tree = Vec::new();
+    let mut stack: Vec<(String, usize)> =
+        task.subtasks.iter().rev().map(|id| (id.clone(), 0)).collect();
+    while let Some((id, depth)) = stack.pop() {
+        let indent = "  ".repeat(depth);
+        match state.task_details.get(&id) {
+            Some(subtask) => {
+                // A remote node names the swarm and task it runs as.
+                let remote = subtask
+                    .remote
+                    .as_ref()
+                    .map(|remote| format!(" (in {} as {})", remote.swarm_id, remote.task_id))
+                    .unwrap_or_default();
+                subtask_tree.push(format!(
+                    "{}{} [{}] {} {}{}",
+                    indent,
+                    id,
+                    format_task_status(subtask.status),
+                    subtask
+                        .assigned_to
+                        .as_ref()
+                        .map(|a| name(a.as_str()))
+                        .unwrap_or_else(|| "-".to_string()),
+                    subtask.description,
+                    remote
+                ));
+                stack.extend(subtask.subtasks.iter().rev().map(|child| (child.clone(), depth + 1)));
+            }
+            None => subtask_tree.push(format!("{}{} (details unavailable)", indent, id)),
+        }
+    }
+
+    Some(TaskDetailView {
+        task_id: task_id.to_string(),
+        description: task.description.clone(),
+        status: format_task_status(task.status).to_string(),
+        assigned_to: task
+            .assigned_to
+            .as_ref()
+            .map(|a| name(a.as_str()))
+            .unwrap_or_else(|| "-".to_string()),
+        swarm_id: state.swarm_for_task(task_id),
+        awaiting_approval: state
+            .pending_plan_approvals
+            .get(task_id)
+            .filter(|pending| pending.approved_by.is_none())
+            .map(|pending| pending.winner_plan_id.clone()),
+        blocked: state
+            .blocked_tasks
+            .get(task_id)
+            .filter(|_| task.status == TaskStatus::Blocked)
+            .map(|escalation| escalation.reason.clone()),
+        timeline,
+        deliberation,
+        ballots,
+        irv_rounds,
+        subtask_tree,
+    })
+}
+
 fn truncate_agent_id(id: &str) -> String {
     if id.len() > 28 {
         format!("{}...{}", &id[..16], &id[id.len() - 8..])
@@ -1190,6 +2324,9 @@ fn format_task_status(status: TaskStatus) -> &'static str {
         TaskStatus::Completed => "Completed",
         TaskStatus::Failed => "Failed",
         TaskStatus::Rejected => "Rejected",
+        TaskStatus::Disputed => "Disputed",
+        TaskStatus::Overdue => "Overdue",
+        TaskStatus::Blocked => "Blocked",
     }
 }
 
@@ -1263,7 +2400,11 @@ pub async fn run_operator_console(
 
     loop {
         // Take a snapshot.
-        let snapshot = console.snapshot().await;
+        let mut snapshot = console.snapshot().await;
+        console.tier_changes.mark(&mut snapshot.hierarchy, Instant::now());
+        console.announce_escalations().await;
+        console.task_ids = snapshot.active_tasks.iter().map(|t| t.task_id.clone()).collect();
+        console.selected_task = console.selected_task.min(console.task_ids.len().saturating_sub(1));
 
         // Render.
         terminal.draw(|frame| {
@@ -1275,7 +2416,7 @@ pub async fn run_operator_console(
             if let Event::Key(key_event) = event::read()? {
                 if key_event.kind == KeyEventKind::Press {
                     // Check for quit commands first.
-                    if key_event.code == KeyCode::Enter {
+                    if console.submits(key_event.code, key_event.modifiers) {
                         // Check if input is a quit command.
                         let trimmed = console.input.trim().to_string();
                         if trimmed == "/quit" || trimmed == "/exit" || trimmed == "/q" {
@@ -1285,6 +2426,9 @@ pub async fn run_operator_console(
                     } else if console.handle_key(key_event.code, key_event.modifiers) {
                         break; // Ctrl+C
                     }
+                    if let Some(command) = console.pending_command.take() {
+                        console.process_command(&command).await;
+                    }
                 }
             }
         }
@@ -1294,6 +2438,37 @@ pub async fn run_operator_console(
     Ok(())
 }
 
+/// Run the console input in `script` (`-` for stdin) without the TUI and
+/// return when it is done. The transcript goes to `transcript`, or to
+/// stdout.
+pub async fn run_console_script(
+    state: Arc<RwLock<ConnectorState>>,
+    network_handle: wws_network::SwarmHandle,
+    script: &Path,
+    transcript: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let mut out: Box<dyn Write + Send> = match transcript {
+        Some(path) => Box::new(std::fs::File::create(path).map_err(|e| {
+            anyhow::anyhow!("Failed to create transcript {}: {}", path.display(), e)
+        })?),
+        None => Box::new(io::stdout()),
+    };
+    let mut console = OperatorConsole::new(state, network_handle);
+    if script == Path::new("-") {
+        console
+            .run_script(tokio::io::BufReader::new(tokio::io::stdin()), &mut out)
+            .await?;
+    } else {
+        let file = tokio::fs::File::open(script)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to open script {}: {}", script.display(), e))?;
+        console
+            .run_script(tokio::io::BufReader::new(file), &mut out)
+            .await?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1323,8 +2498,8 @@ mod tests {
             voting_engines: std::collections::HashMap::new(),
             cascade: wws_consensus::CascadeEngine::new(),
             task_set: OrSet::new("seed".to_string()),
-            task_details: std::collections::HashMap::new(),
-            task_timelines: std::collections::HashMap::new(),
+            task_details: Box::new(crate::storage::InMemoryTaskStore::new()),
+            task_timelines: Box::new(crate::storage::InMemoryTimelineStore::new()),
             agent_set: OrSet::new("seed".to_string()),
             member_set: OrSet::new("seed".to_string()),
             member_last_seen: std::collections::HashMap::new(),
@@ -1335,6 +2510,8 @@ mod tests {
             member_last_result: std::collections::HashMap::new(),
             task_result_text: std::collections::HashMap::new(),
             pending_plan_reveals: std::collections::HashMap::new(),
+            pending_vote_reveals: std::collections::HashMap::new(),
+            voting_windows: std::collections::HashMap::new(),
             merkle_dag: MerkleDag::new(),
             content_store: ContentStore::new(),
             granularity: GranularityAlgorithm::default(),
@@ -1344,7 +2521,7 @@ mod tests {
             agent_parents: std::collections::HashMap::new(),
             current_layout: None,
             subordinates: std::collections::HashMap::new(),
-            task_results: std::collections::HashMap::new(),
+            task_results: Box::new(crate::storage::InMemoryArtifactStore::new()),
             network_stats: wws_protocol::NetworkStats {
                 total_agents: 0,
                 hierarchy_depth: 1,
@@ -1355,7 +2532,9 @@ mod tests {
                 parent_id: None,
             },
             event_log: Vec::new(),
+            log_count: 0,
             message_trace: Vec::new(),
+            dead_letters: Vec::new(),
             start_time: chrono::Utc::now(),
             current_swarm_id: SwarmId::new("public".to_string()),
             known_swarms: std::collections::HashMap::new(),
@@ -1363,12 +2542,80 @@ mod tests {
             active_holons: std::collections::HashMap::new(),
             deliberation_messages: std::collections::HashMap::new(),
             ballot_records: std::collections::HashMap::new(),
+            ballot_receipts: std::collections::HashMap::new(),
+            task_state_requests: std::collections::HashMap::new(),
+            task_state_replies: std::collections::HashMap::new(),
             irv_rounds: std::collections::HashMap::new(),
             board_acceptances: std::collections::HashMap::new(),
             registered_names: std::collections::HashMap::new(),
             pending_verifications: std::collections::HashMap::new(),
             verified_agents: std::collections::HashSet::new(),
             direct_messages: Vec::new(),
+            plan_approval_timeout_secs: crate::connector::DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS,
+            pending_plan_approvals: std::collections::HashMap::new(),
+            task_templates: std::collections::HashMap::new(),
+            scheduler: crate::scheduler::Scheduler::new(),
+            task_costs: std::collections::HashMap::new(),
+            voting_config: wws_consensus::voting::VotingConfig::default(),
+            reveal_timeout_secs: None,
+            max_round_age_secs: crate::connector::DEFAULT_MAX_ROUND_AGE_SECS,
+            max_tender_attempts: crate::connector::DEFAULT_MAX_TENDER_ATTEMPTS,
+            retender_relax_tier: false,
+            task_tenders: std::collections::HashMap::new(),
+            partition_detector: crate::partition::PartitionDetector::new(
+                crate::partition::DEFAULT_PARTITION_THRESHOLD,
+            ),
+            merge: crate::merge::MergeTracker::default(),
+            shards: crate::shard::ShardTracker::default(),
+            relays: crate::delegation::RelayTracker::default(),
+            reputation: crate::reputation::ReputationLedger::new(Box::new(
+                crate::storage::InMemoryAttestationStore::new(),
+            )),
+            quorum_policy: wws_protocol::QuorumPolicy::default(),
+            assignment_strategy: wws_protocol::AssignmentStrategy::default(),
+            assignment_seed: None,
+            committee_size: 0,
+            held_tasks: std::collections::HashMap::new(),
+            verification_rate: 0.0,
+            proposal_bond: 0,
+            proposal_bonds: crate::bonds::ProposalBonds::default(),
+            verifications: std::collections::HashMap::new(),
+            round_restarts: std::collections::HashMap::new(),
+            board_config: crate::config::BoardConfig::default(),
+            board_formations: std::collections::HashMap::new(),
+            local_agents: crate::cluster::AgentCluster::default(),
+            identity_key: wws_protocol::crypto::generate_keypair(),
+            did_documents: crate::did::DidDirectory::default(),
+            swarm_params: None,
+            params_proposals: std::collections::HashMap::new(),
+            result_schema_violations: std::collections::HashMap::new(),
+            task_progress: std::collections::HashMap::new(),
+            blocked_tasks: std::collections::HashMap::new(),
+            plan_policy: Default::default(),
+            board_channels: Default::default(),
+            election_vote_due: None,
+            memberships: std::collections::HashMap::new(),
+            task_swarms: std::collections::HashMap::new(),
+            name_directory: crate::names::NameDirectory::default(),
+            agent_capabilities: std::collections::HashMap::new(),
+            capabilities: Default::default(),
+            task_winning_plans: std::collections::HashMap::new(),
+            agent_services: std::collections::HashMap::new(),
+            did_republish_due: false,
+            agent_loads: std::collections::HashMap::new(),
+            max_concurrent_tasks: None,
+            picked_up_tasks: std::collections::HashSet::new(),
+            task_deliveries: crate::delivery::TaskDeliveries::default(),
+            restarts: 0,
+            tier_subscriptions: std::collections::HashMap::new(),
+            config_reload: crate::reload::ConfigReload::default(),
+            work_stealing: crate::work_steal::WorkStealing::default(),
+            agent_roles: std::collections::HashMap::new(),
+            trace_log: None,
+            topic_keys: None,
+            log_sinks: Vec::new(),
+            webhooks: crate::webhooks::Webhooks::default(),
+            peer_rtt: std::collections::HashMap::new(),
         };
 
         state.mark_member_seen("did:swarm:agent-1");
@@ -1383,6 +2630,103 @@ mod tests {
             .all(|n| n.last_seen_secs.is_some()));
     }
 
+    #[test]
+    fn hierarchy_nodes_carry_liveness_and_rtt() {
+        let mut state = crate::connector::tests::test_state();
+        state.mark_member_seen("did:swarm:fresh");
+        state.mark_member_seen("did:swarm:stale");
+        state.member_last_seen.insert(
+            "did:swarm:stale".to_string(),
+            chrono::Utc::now() - chrono::Duration::seconds(120),
+        );
+        state.agent_tiers.insert("did:swarm:gone".to_string(), wws_protocol::Tier::Tier2);
+        state
+            .peer_rtt
+            .insert("did:swarm:fresh".to_string(), Duration::from_millis(42));
+
+        let tree = build_hierarchy_tree(&state);
+        let node = |id: &str| tree.iter().find(|n| n.agent_id == id).unwrap();
+        assert_eq!(node("did:swarm:fresh").liveness, Liveness::Fresh);
+        assert_eq!(node("did:swarm:fresh").rtt, Some(Duration::from_millis(42)));
+        assert_eq!(node("did:swarm:stale").liveness, Liveness::Stale);
+        assert_eq!(node("did:swarm:gone").liveness, Liveness::Missing);
+        assert_eq!(node("did:swarm:gone").tier, "Tier2");
+        assert_eq!(node(state.agent_id.as_str()).liveness, Liveness::Fresh);
+    }
+
+    fn node(agent_id: &str, tier: &str, children: Vec<HierarchyNode>) -> HierarchyNode {
+        HierarchyNode {
+            agent_id: agent_id.to_string(),
+            display_name: agent_id.to_string(),
+            tier: tier.to_string(),
+            is_self: false,
+            children,
+            task_count: 0,
+            last_seen_secs: Some(0),
+            liveness: Liveness::Fresh,
+            rtt: None,
+            tier_changed: false,
+        }
+    }
+
+    #[test]
+    fn tier_changes_stay_highlighted_for_a_few_seconds() {
+        let mut changes = TierChanges::default();
+        let start = Instant::now();
+        let mut tree = vec![node("a", "Tier1", vec![node("b", "Executor", vec![])])];
+        changes.mark(&mut tree, start);
+        assert!(!tree[0].tier_changed, "a first tier is not a change");
+
+        let mut tree = vec![node("a", "Tier1", vec![node("b", "Tier2", vec![])])];
+        changes.mark(&mut tree, start + Duration::from_secs(1));
+        assert!(!tree[0].tier_changed);
+        assert!(tree[0].children[0].tier_changed);
+
+        let mut tree = vec![node("a", "Tier1", vec![node("b", "Tier2", vec![])])];
+        changes.mark(&mut tree, start + Duration::from_secs(1) + TIER_CHANGE_HIGHLIGHT);
+        assert!(!tree[0].children[0].tier_changed);
+    }
+
+    #[tokio::test]
+    async fn script_runs_lines_in_order_and_writes_a_transcript() {
+        let (_host, handle, _events) = SwarmHost::new(SwarmHostConfig::default()).unwrap();
+        let state = Arc::new(RwLock::new(crate::connector::tests::test_state()));
+        let mut console = OperatorConsole::new(state, handle);
+        let script = "# demo\n\n/bogus\nsleep 1\nsleep soon\n/quit\n/tasks\n";
+
+        let started = tokio::time::Instant::now();
+        let mut transcript = Vec::new();
+        console.run_script(script.as_bytes(), &mut transcript).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(1));
+
+        let transcript = String::from_utf8(transcript).unwrap();
+        let lines: Vec<&str> = transcript.lines().map(|l| &l[11..]).collect();
+        assert_eq!(
+            lines,
+            [
+                "> /bogus",
+                "  Unknown command: /bogus. Type /help for available commands.",
+                "> sleep 1",
+                "> sleep soon",
+                "  Invalid sleep duration: soon",
+                "> /quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn flat_hierarchy_sorts_by_tier_then_name() {
+        let tree = vec![
+            node("z", "Tier1", vec![node("y", "Executor", vec![]), node("x", "Tier2", vec![])]),
+            node("a", "Peer", vec![]),
+            node("b", "Tier10", vec![]),
+        ];
+        let flat = flatten_hierarchy(&tree);
+        let order: Vec<&str> = flat.iter().map(|n| n.agent_id.as_str()).collect();
+        assert_eq!(order, ["z", "x", "b", "y", "a"]);
+        assert!(flat.iter().all(|n| n.children.is_empty()));
+    }
+
     #[test]
     fn flow_summary_counts_votes_decomposition_results() {
         use wws_hierarchy::{EpochManager, GeoCluster, PyramidAllocator, SuccessionManager};
@@ -1401,8 +2745,8 @@ mod tests {
             voting_engines: std::collections::HashMap::new(),
             cascade: wws_consensus::CascadeEngine::new(),
             task_set: OrSet::new("seed".to_string()),
-            task_details: std::collections::HashMap::new(),
-            task_timelines: std::collections::HashMap::new(),
+            task_details: Box::new(crate::storage::InMemoryTaskStore::new()),
+            task_timelines: Box::new(crate::storage::InMemoryTimelineStore::new()),
             agent_set: OrSet::new("seed".to_string()),
             member_set: OrSet::new("seed".to_string()),
             member_last_seen: std::collections::HashMap::new(),
@@ -1413,6 +2757,8 @@ mod tests {
             member_last_result: std::collections::HashMap::new(),
             task_result_text: std::collections::HashMap::new(),
             pending_plan_reveals: std::collections::HashMap::new(),
+            pending_vote_reveals: std::collections::HashMap::new(),
+            voting_windows: std::collections::HashMap::new(),
             merkle_dag: MerkleDag::new(),
             content_store: ContentStore::new(),
             granularity: GranularityAlgorithm::default(),
@@ -1422,7 +2768,7 @@ mod tests {
             agent_parents: std::collections::HashMap::new(),
             current_layout: None,
             subordinates: std::collections::HashMap::new(),
-            task_results: std::collections::HashMap::new(),
+            task_results: Box::new(crate::storage::InMemoryArtifactStore::new()),
             network_stats: wws_protocol::NetworkStats {
                 total_agents: 1,
                 hierarchy_depth: 1,
@@ -1433,7 +2779,9 @@ mod tests {
                 parent_id: None,
             },
             event_log: Vec::new(),
+            log_count: 0,
             message_trace: Vec::new(),
+            dead_letters: Vec::new(),
             start_time: chrono::Utc::now(),
             current_swarm_id: SwarmId::new("public".to_string()),
             known_swarms: std::collections::HashMap::new(),
@@ -1441,12 +2789,80 @@ mod tests {
             active_holons: std::collections::HashMap::new(),
             deliberation_messages: std::collections::HashMap::new(),
             ballot_records: std::collections::HashMap::new(),
+            ballot_receipts: std::collections::HashMap::new(),
+            task_state_requests: std::collections::HashMap::new(),
+            task_state_replies: std::collections::HashMap::new(),
             irv_rounds: std::collections::HashMap::new(),
             board_acceptances: std::collections::HashMap::new(),
             registered_names: std::collections::HashMap::new(),
             pending_verifications: std::collections::HashMap::new(),
             verified_agents: std::collections::HashSet::new(),
             direct_messages: Vec::new(),
+            plan_approval_timeout_secs: crate::connector::DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS,
+            pending_plan_approvals: std::collections::HashMap::new(),
+            task_templates: std::collections::HashMap::new(),
+            scheduler: crate::scheduler::Scheduler::new(),
+            task_costs: std::collections::HashMap::new(),
+            voting_config: wws_consensus::voting::VotingConfig::default(),
+            reveal_timeout_secs: None,
+            max_round_age_secs: crate::connector::DEFAULT_MAX_ROUND_AGE_SECS,
+            max_tender_attempts: crate::connector::DEFAULT_MAX_TENDER_ATTEMPTS,
+            retender_relax_tier: false,
+            task_tenders: std::collections::HashMap::new(),
+            partition_detector: crate::partition::PartitionDetector::new(
+                crate::partition::DEFAULT_PARTITION_THRESHOLD,
+            ),
+            merge: crate::merge::MergeTracker::default(),
+            shards: crate::shard::ShardTracker::default(),
+            relays: crate::delegation::RelayTracker::default(),
+            reputation: crate::reputation::ReputationLedger::new(Box::new(
+                crate::storage::InMemoryAttestationStore::new(),
+            )),
+            quorum_policy: wws_protocol::QuorumPolicy::default(),
+            assignment_strategy: wws_protocol::AssignmentStrategy::default(),
+            assignment_seed: None,
+            committee_size: 0,
+            held_tasks: std::collections::HashMap::new(),
+            verification_rate: 0.0,
+            proposal_bond: 0,
+            proposal_bonds: crate::bonds::ProposalBonds::default(),
+            verifications: std::collections::HashMap::new(),
+            round_restarts: std::collections::HashMap::new(),
+            board_config: crate::config::BoardConfig::default(),
+            board_formations: std::collections::HashMap::new(),
+            local_agents: crate::cluster::AgentCluster::default(),
+            identity_key: wws_protocol::crypto::generate_keypair(),
+            did_documents: crate::did::DidDirectory::default(),
+            swarm_params: None,
+            params_proposals: std::collections::HashMap::new(),
+            result_schema_violations: std::collections::HashMap::new(),
+            task_progress: std::collections::HashMap::new(),
+            blocked_tasks: std::collections::HashMap::new(),
+            plan_policy: Default::default(),
+            board_channels: Default::default(),
+            election_vote_due: None,
+            memberships: std::collections::HashMap::new(),
+            task_swarms: std::collections::HashMap::new(),
+            name_directory: crate::names::NameDirectory::default(),
+            agent_capabilities: std::collections::HashMap::new(),
+            capabilities: Default::default(),
+            task_winning_plans: std::collections::HashMap::new(),
+            agent_services: std::collections::HashMap::new(),
+            did_republish_due: false,
+            agent_loads: std::collections::HashMap::new(),
+            max_concurrent_tasks: None,
+            picked_up_tasks: std::collections::HashSet::new(),
+            task_deliveries: crate::delivery::TaskDeliveries::default(),
+            restarts: 0,
+            tier_subscriptions: std::collections::HashMap::new(),
+            config_reload: crate::reload::ConfigReload::default(),
+            work_stealing: crate::work_steal::WorkStealing::default(),
+            agent_roles: std::collections::HashMap::new(),
+            trace_log: None,
+            topic_keys: None,
+            log_sinks: Vec::new(),
+            webhooks: crate::webhooks::Webhooks::default(),
+            peer_rtt: std::collections::HashMap::new(),
         };
 
         state.push_task_timeline_event("t1", "injected", "", None);
@@ -1475,6 +2891,60 @@ mod tests {
         assert_eq!(flow.peer_events, 1);
     }
 
+    #[test]
+    fn inject_prompt_collects_inject_task_params() {
+        let mut prompt = InjectPrompt::new();
+        assert!(matches!(prompt.answer("  "), PromptOutcome::Invalid(_)));
+        assert_eq!(prompt.answer("Audit the API\nand list breaking changes"), PromptOutcome::Next);
+        assert!(matches!(prompt.answer("300"), PromptOutcome::Invalid(_)));
+        assert_eq!(prompt.answer("7"), PromptOutcome::Next);
+        assert_eq!(prompt.answer("rust, review ,"), PromptOutcome::Next);
+        assert!(matches!(prompt.answer("soon"), PromptOutcome::Invalid(_)));
+        assert_eq!(prompt.answer("30m"), PromptOutcome::Next);
+        assert_eq!(prompt.answer("y"), PromptOutcome::Next);
+        assert_eq!(prompt.field, InjectField::Confirm);
+        assert!(prompt.summary().iter().any(|l| l.contains("rust, review")));
+
+        let PromptOutcome::Submit(params) = prompt.answer("") else {
+            panic!("confirmation should submit");
+        };
+        assert_eq!(
+            params,
+            serde_json::json!({
+                "description": "Audit the API\nand list breaking changes",
+                "priority": 7,
+                "capabilities_required": ["rust", "review"],
+                "deadline_secs": 1800,
+                "requires_approval": true,
+            })
+        );
+
+        let mut prompt = InjectPrompt::into_swarm("research");
+        prompt.answer("Survey the field");
+        assert_eq!(prompt.params["swarm_id"], "research");
+        assert_eq!(prompt.summary()[0], "Task to inject into swarm research:");
+
+        // Blank answers keep the defaults, and "n" at confirmation abandons.
+        let mut prompt = InjectPrompt::new();
+        prompt.answer("Summarise the logs");
+        for _ in 0..4 {
+            assert_eq!(prompt.answer(""), PromptOutcome::Next);
+        }
+        assert_eq!(prompt.params.len(), 2, "description and requires_approval only");
+        assert_eq!(prompt.answer("n"), PromptOutcome::Abandon);
+    }
+
+    #[test]
+    fn deadlines_accept_seconds_minutes_and_hours() {
+        assert_eq!(parse_deadline_secs("90"), Some(90));
+        assert_eq!(parse_deadline_secs("90s"), Some(90));
+        assert_eq!(parse_deadline_secs("15M"), Some(900));
+        assert_eq!(parse_deadline_secs("2h"), Some(7200));
+        assert_eq!(parse_deadline_secs("0"), None);
+        assert_eq!(parse_deadline_secs("h"), None);
+        assert_eq!(parse_deadline_secs("-5m"), None);
+    }
+
     #[tokio::test]
     async fn console_inject_task_publishes_to_swarm() {
         let cfg = SwarmHostConfig {
@@ -1542,8 +3012,8 @@ mod tests {
             voting_engines: std::collections::HashMap::new(),
             cascade: wws_consensus::CascadeEngine::new(),
             task_set: OrSet::new("seed".to_string()),
-            task_details: std::collections::HashMap::new(),
-            task_timelines: std::collections::HashMap::new(),
+            task_details: Box::new(crate::storage::InMemoryTaskStore::new()),
+            task_timelines: Box::new(crate::storage::InMemoryTimelineStore::new()),
             agent_set: OrSet::new("seed".to_string()),
             member_set: OrSet::new("seed".to_string()),
             member_last_seen: std::collections::HashMap::new(),
@@ -1554,6 +3024,8 @@ mod tests {
             member_last_result: std::collections::HashMap::new(),
             task_result_text: std::collections::HashMap::new(),
             pending_plan_reveals: std::collections::HashMap::new(),
+            pending_vote_reveals: std::collections::HashMap::new(),
+            voting_windows: std::collections::HashMap::new(),
             merkle_dag: MerkleDag::new(),
             content_store: ContentStore::new(),
             granularity: GranularityAlgorithm::default(),
@@ -1563,7 +3035,7 @@ mod tests {
             agent_parents: std::collections::HashMap::new(),
             current_layout: None,
             subordinates: std::collections::HashMap::new(),
-            task_results: std::collections::HashMap::new(),
+            task_results: Box::new(crate::storage::InMemoryArtifactStore::new()),
             network_stats: wws_protocol::NetworkStats {
                 total_agents: 1,
                 hierarchy_depth: 1,
@@ -1574,7 +3046,9 @@ mod tests {
                 parent_id: None,
             },
             event_log: Vec::new(),
+            log_count: 0,
             message_trace: Vec::new(),
+            dead_letters: Vec::new(),
             start_time: chrono::Utc::now(),
             current_swarm_id: SwarmId::new("public".to_string()),
             known_swarms: std::collections::HashMap::new(),
@@ -1582,12 +3056,80 @@ mod tests {
             active_holons: std::collections::HashMap::new(),
             deliberation_messages: std::collections::HashMap::new(),
             ballot_records: std::collections::HashMap::new(),
+            ballot_receipts: std::collections::HashMap::new(),
+            task_state_requests: std::collections::HashMap::new(),
+            task_state_replies: std::collections::HashMap::new(),
             irv_rounds: std::collections::HashMap::new(),
             board_acceptances: std::collections::HashMap::new(),
             registered_names: std::collections::HashMap::new(),
             pending_verifications: std::collections::HashMap::new(),
             verified_agents: std::collections::HashSet::new(),
             direct_messages: Vec::new(),
+            plan_approval_timeout_secs: crate::connector::DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS,
+            pending_plan_approvals: std::collections::HashMap::new(),
+            task_templates: std::collections::HashMap::new(),
+            scheduler: crate::scheduler::Scheduler::new(),
+            task_costs: std::collections::HashMap::new(),
+            voting_config: wws_consensus::voting::VotingConfig::default(),
+            reveal_timeout_secs: None,
+            max_round_age_secs: crate::connector::DEFAULT_MAX_ROUND_AGE_SECS,
+            max_tender_attempts: crate::connector::DEFAULT_MAX_TENDER_ATTEMPTS,
+            retender_relax_tier: false,
+            task_tenders: std::collections::HashMap::new(),
+            partition_detector: crate::partition::PartitionDetector::new(
+                crate::partition::DEFAULT_PARTITION_THRESHOLD,
+            ),
+            merge: crate::merge::MergeTracker::default(),
+            shards: crate::shard::ShardTracker::default(),
+            relays: crate::delegation::RelayTracker::default(),
+            reputation: crate::reputation::ReputationLedger::new(Box::new(
+                crate::storage::InMemoryAttestationStore::new(),
+            )),
+            quorum_policy: wws_protocol::QuorumPolicy::default(),
+            assignment_strategy: wws_protocol::AssignmentStrategy::default(),
+            assignment_seed: None,
+            committee_size: 0,
+            held_tasks: std::collections::HashMap::new(),
+            verification_rate: 0.0,
+            proposal_bond: 0,
+            proposal_bonds: crate::bonds::ProposalBonds::default(),
+            verifications: std::collections::HashMap::new(),
+            round_restarts: std::collections::HashMap::new(),
+            board_config: crate::config::BoardConfig::default(),
+            board_formations: std::collections::HashMap::new(),
+            local_agents: crate::cluster::AgentCluster::default(),
+            identity_key: wws_protocol::crypto::generate_keypair(),
+            did_documents: crate::did::DidDirectory::default(),
+            swarm_params: None,
+            params_proposals: std::collections::HashMap::new(),
+            result_schema_violations: std::collections::HashMap::new(),
+            task_progress: std::collections::HashMap::new(),
+            blocked_tasks: std::collections::HashMap::new(),
+            plan_policy: Default::default(),
+            board_channels: Default::default(),
+            election_vote_due: None,
+            memberships: std::collections::HashMap::new(),
+            task_swarms: std::collections::HashMap::new(),
+            name_directory: crate::names::NameDirectory::default(),
+            agent_capabilities: std::collections::HashMap::new(),
+            capabilities: Default::default(),
+            task_winning_plans: std::collections::HashMap::new(),
+            agent_services: std::collections::HashMap::new(),
+            did_republish_due: false,
+            agent_loads: std::collections::HashMap::new(),
+            max_concurrent_tasks: None,
+            picked_up_tasks: std::collections::HashSet::new(),
+            task_deliveries: crate::delivery::TaskDeliveries::default(),
+            restarts: 0,
+            tier_subscriptions: std::collections::HashMap::new(),
+            config_reload: crate::reload::ConfigReload::default(),
+            work_stealing: crate::work_steal::WorkStealing::default(),
+            agent_roles: std::collections::HashMap::new(),
+            trace_log: None,
+            topic_keys: None,
+            log_sinks: Vec::new(),
+            webhooks: crate::webhooks::Webhooks::default(),
+            peer_rtt: std::collections::HashMap::new(),
         };
 
         let mut console = OperatorConsole::new(Arc::new(RwLock::new(state)), handle_a.clone());
@@ -0,0 +1,360 @@
+//! Partition detection and reconciliation after a partition heals.
+//!
+//! A network split shows up locally as a sudden loss of members: the nodes
+//! on the other side stop sending keep-alives and age out together. The
+//! detector samples the active membership on every keep-alive tick and
+//! opens a partition when more than `hierarchy.partition_threshold` of the
+//! members seen within the last `PARTITION_WINDOW_SECS` are gone at once.
+//! The partition heals when more than that fraction of the membership (or
+//! every lost member) is back.
+//!
+//! While split, both sides keep deciding tasks, so after healing the
+//! connector exchanges a digest of its tasks with a few returning peers
+//! over the direct protocol (`state.reconcile`). A task both sides know
+//! with a different status or assignee is a conflict; the digest with the
+//! later finality stamp — the HLC of the latest timeline event the node
+//! recorded for the task — wins, so both sides settle on the same state.
+//! Every partition and the conflicts resolved after it end up in a
+//! report, returned by `swarm.get_partition_report`.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use wws_protocol::TaskDigest;
+
+/// Default for `hierarchy.partition_threshold`.
+pub const DEFAULT_PARTITION_THRESHOLD: f64 = 0.3;
+/// Members, besides this node, a swarm needs before a loss can count as a
+/// partition.
+pub const MIN_PARTITION_MEMBERS: usize = 3;
+/// How far back the detector looks for the membership a loss is measured
+/// against. Slow churn spread over a longer time is not a partition.
+pub const PARTITION_WINDOW_SECS: u64 = 120;
+/// After this long, members still missing are taken to have left and the
+/// partition is closed without healing.
+pub const PARTITION_EXPIRY_SECS: u64 = 3600;
+/// Returning peers asked to reconcile after a partition heals.
+pub const MAX_RECONCILE_PEERS: usize = 3;
+/// Most task digests sent in one reconciliation, most recent first.
+pub const MAX_RECONCILE_DIGESTS: usize = 1000;
+/// Partition reports kept for `swarm.get_partition_report`.
+pub const MAX_PARTITION_REPORTS: usize = 16;
+
+/// Which side of a conflict was kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    KeptLocal,
+    AdoptedRemote,
+}
+
+/// A task whose state differed between this node and a peer.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskConflict {
+    pub task_id: String,
+    pub peer: String,
+    pub local: TaskDigest,
+    pub remote: TaskDigest,
+    pub resolution: Resolution,
+}
+
+/// One partition, from detection to healing and reconciliation.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartitionReport {
+    pub partition_id: u64,
+    pub detected_at: DateTime<Utc>,
+    /// When enough members returned; `None` while split or when the
+    /// partition expired with the members still gone.
+    pub healed_at: Option<DateTime<Utc>>,
+    /// Active members, besides this node, before the loss.
+    pub members_before: usize,
+    pub lost: Vec<String>,
+    pub returned: Vec<String>,
+    /// Peers whose task digests were compared with ours.
+    pub reconciled_with: Vec<String>,
+    pub tasks_compared: usize,
+    pub conflicts: Vec<TaskConflict>,
+}
+
+/// A change in partition state reported by [`PartitionDetector::observe`].
+#[derive(Debug, Clone)]
+pub enum PartitionEvent {
+    Detected(PartitionReport),
+    Healed(PartitionReport),
+    Expired(PartitionReport),
+}
+
+/// Tracks active membership and opens and closes partitions.
+#[derive(Debug)]
+pub struct PartitionDetector {
+    threshold: f64,
+    samples: VecDeque<(DateTime<Utc>, BTreeSet<String>)>,
+    open: Option<PartitionReport>,
+    /// Closed partitions, oldest first.
+    reports: VecDeque<PartitionReport>,
+    next_id: u64,
+}
+
+impl PartitionDetector {
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold: threshold.clamp(0.0, 1.0),
+            samples: VecDeque::new(),
+            open: None,
+            reports: VecDeque::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// The open partition, if the node is currently split from part of
+    /// the swarm.
+    pub fn current(&self) -> Option<&PartitionReport> {
+        self.open.as_ref()
+    }
+
+    /// Closed partitions, oldest first.
+    pub fn reports(&self) -> impl Iterator<Item = &PartitionReport> {
+        self.reports.iter()
+    }
+
+    /// Record the active members, other than this node, seen at `now`.
+    pub fn observe(&mut self, members: BTreeSet<String>, now: DateTime<Utc>) -> Option<PartitionEvent> {
+        if let Some(open) = self.open.as_mut() {
+            let returned: Vec<String> = open
+                .lost
+                .iter()
+                .filter(|m| members.contains(*m))
+                .cloned()
+                .collect();
+            let healed = returned.len() == open.lost.len()
+                || returned.len() as f64 > self.threshold * open.members_before as f64;
+            let expired = now.signed_duration_since(open.detected_at)
+                > chrono::Duration::seconds(PARTITION_EXPIRY_SECS as i64);
+            if !healed && !expired {
+                return None;
+            }
+            let mut report = self.open.take()?;
+            report.returned = returned;
+            if healed {
+                report.healed_at = Some(now);
+            }
+            self.samples.clear();
+            self.samples.push_back((now, members));
+            self.push_report(report.clone());
+            return Some(if healed {
+                PartitionEvent::Healed(report)
+            } else {
+                PartitionEvent::Expired(report)
+            });
+        }
+
+        let window = chrono::Duration::seconds(PARTITION_WINDOW_SECS as i64);
+        while self
+            .samples
+            .front()
+            .is_some_and(|(at, _)| now.signed_duration_since(*at) > window)
+        {
+            self.samples.pop_front();
+        }
+        let reference = self
+            .samples
+            .iter()
+            .map(|(_, seen)| seen)
+            .max_by_key(|seen| seen.len())
+            .cloned()
+            .unwrap_or_default();
+        let lost: Vec<String> = reference.difference(&members).cloned().collect();
+        self.samples.push_back((now, members));
+
+        if reference.len() < MIN_PARTITION_MEMBERS
+            || lost.len() as f64 <= self.threshold * reference.len() as f64
+        {
+            return None;
+        }
+        let report = PartitionReport {
+            partition_id: self.next_id,
+            detected_at: now,
+            healed_at: None,
+            members_before: reference.len(),
+            lost,
+            returned: Vec::new(),
+            reconciled_with: Vec::new(),
+            tasks_compared: 0,
+            conflicts: Vec::new(),
+        };
+        self.next_id += 1;
+        self.samples.clear();
+        self.open = Some(report.clone());
+        Some(PartitionEvent::Detected(report))
+    }
+
+    /// Add the outcome of a reconciliation with `peer` to the latest
+    /// partition report. Returns false when there is no report.
+    pub fn record_reconciliation(
+        &mut self,
+        peer: &str,
+        tasks_compared: usize,
+        conflicts: Vec<TaskConflict>,
+    ) -> bool {
+        let Some(report) = self.open.as_mut().or(self.reports.back_mut()) else {
+            return false;
+        };
+        if !report.reconciled_with.iter().any(|p| p == peer) {
+            report.reconciled_with.push(peer.to_string());
+        }
+        report.tasks_compared += tasks_compared;
+        report.conflicts.extend(conflicts);
+        true
+    }
+
+    fn push_report(&mut self, report: PartitionReport) {
+        self.reports.push_back(report);
+        while self.reports.len() > MAX_PARTITION_REPORTS {
+            self.reports.pop_front();
+        }
+    }
+}
+
+/// Compare our digests with a peer's. Returns how many tasks both sides
+/// know and the ones that differ, each resolved for the later finality
+/// stamp. Ties fall to the larger assignee, then status, so that both
+/// sides pick the same winner.
+pub fn resolve_conflicts(
+    peer: &str,
+    local: &[TaskDigest],
+    remote: &[TaskDigest],
+) -> (usize, Vec<TaskConflict>) {
+    let ours: std::collections::HashMap<&str, &TaskDigest> =
+        local.iter().map(|d| (d.task_id.as_str(), d)).collect();
+    let mut compared = 0;
+    let mut conflicts = Vec::new();
+    for theirs in remote {
+        let Some(mine) = ours.get(theirs.task_id.as_str()) else {
+            continue;
+        };
+        compared += 1;
+        if mine.status == theirs.status && mine.assigned_to == theirs.assigned_to {
+            continue;
+        }
+        let resolution = if finality_key(theirs) > finality_key(mine) {
+            Resolution::AdoptedRemote
+        } else {
+            Resolution::KeptLocal
+        };
+        conflicts.push(TaskConflict {
+            task_id: theirs.task_id.clone(),
+            peer: peer.to_string(),
+            local: (*mine).clone(),
+            remote: theirs.clone(),
+            resolution,
+        });
+    }
+    (compared, conflicts)
+}
+
+fn finality_key(digest: &TaskDigest) -> (wws_protocol::HlcTimestamp, Option<&str>, String) {
+    (
+        digest.finality,
+        digest.assigned_to.as_ref().map(|a| a.as_str()),
+        format!("{:?}", digest.status),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::{AgentId, HlcTimestamp, TaskStatus};
+
+    fn members(ids: &[&str]) -> BTreeSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn digest(task_id: &str, status: TaskStatus, assignee: &str, wall_ms: i64) -> TaskDigest {
+        TaskDigest {
+            task_id: task_id.to_string(),
+            status,
+            assigned_to: Some(AgentId::new(assignee.to_string())),
+            finality: HlcTimestamp { wall_ms, logical: 0 },
+        }
+    }
+
+    #[test]
+    fn sudden_loss_opens_and_return_heals_a_partition() {
+        let mut detector = PartitionDetector::new(DEFAULT_PARTITION_THRESHOLD);
+        let t0 = Utc::now();
+        let all = members(&["a", "b", "c", "d", "e"]);
+        assert!(detector.observe(all.clone(), t0).is_none());
+        // Losing one of five is churn, not a partition.
+        assert!(detector
+            .observe(members(&["a", "b", "c", "d"]), t0 + chrono::Duration::seconds(10))
+            .is_none());
+
+        let split = detector.observe(members(&["a", "b"]), t0 + chrono::Duration::seconds(20));
+        let Some(PartitionEvent::Detected(report)) = split else {
+            panic!("expected a partition, got {:?}", split);
+        };
+        assert_eq!(report.members_before, 5);
+        assert_eq!(report.lost, vec!["c", "d", "e"]);
+        assert!(detector.current().is_some());
+
+        // One of three back is not yet a heal.
+        assert!(detector
+            .observe(members(&["a", "b", "c"]), t0 + chrono::Duration::seconds(30))
+            .is_none());
+        let healed = detector.observe(all, t0 + chrono::Duration::seconds(40));
+        let Some(PartitionEvent::Healed(report)) = healed else {
+            panic!("expected a heal, got {:?}", healed);
+        };
+        assert_eq!(report.returned, vec!["c", "d", "e"]);
+        assert!(detector.current().is_none());
+
+        assert!(detector.record_reconciliation("c", 4, Vec::new()));
+        let closed = detector.reports().last().unwrap();
+        assert_eq!(closed.reconciled_with, vec!["c"]);
+        assert_eq!(closed.tasks_compared, 4);
+    }
+
+    #[test]
+    fn gradual_departures_outside_the_window_are_not_a_partition() {
+        let mut detector = PartitionDetector::new(DEFAULT_PARTITION_THRESHOLD);
+        let t0 = Utc::now();
+        let step = chrono::Duration::seconds(PARTITION_WINDOW_SECS as i64 + 1);
+        assert!(detector.observe(members(&["a", "b", "c", "d", "e"]), t0).is_none());
+        assert!(detector.observe(members(&["a", "b", "c", "d"]), t0 + step).is_none());
+        assert!(detector.observe(members(&["a", "b", "c"]), t0 + step * 2).is_none());
+        assert!(detector.observe(members(&["a", "b"]), t0 + step * 3).is_none());
+        assert!(detector.current().is_none());
+    }
+
+    #[test]
+    fn later_finality_wins_a_conflict() {
+        let local = vec![
+            digest("t1", TaskStatus::InProgress, "did:swarm:a", 100),
+            digest("t2", TaskStatus::Completed, "did:swarm:b", 300),
+            digest("t3", TaskStatus::InProgress, "did:swarm:a", 100),
+        ];
+        let remote = vec![
+            digest("t1", TaskStatus::InProgress, "did:swarm:c", 200),
+            digest("t2", TaskStatus::InProgress, "did:swarm:c", 200),
+            digest("t3", TaskStatus::InProgress, "did:swarm:a", 500),
+            digest("t4", TaskStatus::Pending, "did:swarm:c", 500),
+        ];
+        let (compared, conflicts) = resolve_conflicts("did:swarm:peer", &local, &remote);
+        assert_eq!(compared, 3);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].task_id, "t1");
+        assert_eq!(conflicts[0].resolution, Resolution::AdoptedRemote);
+        assert_eq!(conflicts[1].task_id, "t2");
+        assert_eq!(conflicts[1].resolution, Resolution::KeptLocal);
+
+        // The peer, comparing the other way round, keeps the same winners.
+        let (_, mirrored) = resolve_conflicts("did:swarm:us", &remote, &local);
+        assert_eq!(mirrored[0].resolution, Resolution::KeptLocal);
+        assert_eq!(mirrored[1].resolution, Resolution::AdoptedRemote);
+    }
+}
@@ -0,0 +1,295 @@
+//! Validation policy for proposed plans.
+//!
+//! `swarm.propose_plan` checks each plan against the policy of the
+//! connector's swarm before committing it: subtask indices must be unique
+//! and contiguous, descriptions non-empty, complexities within `[0, 1]`,
+//! the subtask count within the swarm's limit and, when the swarm keeps a
+//! capability registry, every required capability must be registered. With
+//! `min_coverage` set, some known agent must also cover each subtask's
+//! required capabilities at least that well. Each broken rule is reported
+//! as a [`PlanViolation`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use wws_protocol::Plan;
+
+use crate::capabilities::CapabilityRegistry;
+
+/// Rules a plan must satisfy to be proposed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanPolicy {
+    /// Most subtasks a plan may have.
+    #[serde(default = "default_max_subtasks")]
+    pub max_subtasks: usize,
+    /// Fewest non-whitespace characters in a subtask description.
+    #[serde(default = "default_min_description_chars")]
+    pub min_description_chars: usize,
+    /// Capability registry; required capabilities must be listed here or
+    /// in `[capabilities] known`, themselves or as a namespace of a listed
+    /// one. Empty in both accepts any capability.
+    #[serde(default)]
+    pub known_capabilities: Vec<String>,
+    /// Least coverage, from 0 to 1, of a subtask's required capabilities
+    /// by the best-matching known agent. 0 skips the check.
+    #[serde(default)]
+    pub min_coverage: f64,
+}
+
+impl Default for PlanPolicy {
+    fn default() -> Self {
+        Self {
+            max_subtasks: default_max_subtasks(),
+            min_description_chars: default_min_description_chars(),
+            known_capabilities: Vec::new(),
+            min_coverage: 0.0,
+        }
+    }
+}
+
+fn default_max_subtasks() -> usize {
+    32
+}
+fn default_min_description_chars() -> usize {
+    1
+}
+
+/// Plan policy for this connector's swarm, with per-swarm overrides.
+///
+/// ```toml
+/// [plan_policy]
+/// max_subtasks = 20
+///
+/// [plan_policy.swarms.research]
+/// max_subtasks = 50
+/// known_capabilities = ["web_search", "summarization"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlanPolicyConfig {
+    /// Policy for swarms without an override.
+    #[serde(flatten)]
+    pub default: PlanPolicy,
+    /// Policies by swarm ID.
+    #[serde(default)]
+    pub swarms: HashMap<String, PlanPolicy>,
+}
+
+impl PlanPolicyConfig {
+    pub fn for_swarm(&self, swarm_id: &str) -> &PlanPolicy {
+        self.swarms.get(swarm_id).unwrap_or(&self.default)
+    }
+}
+
+/// One broken plan rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanViolation {
+    /// Rule identifier, e.g. `duplicate_index`.
+    pub rule: &'static str,
+    /// Index of the offending subtask, for per-subtask rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtask_index: Option<u32>,
+    pub message: String,
+}
+
+impl PlanViolation {
+    fn new(rule: &'static str, subtask_index: Option<u32>, message: String) -> Self {
+        Self {
+            rule,
+            subtask_index,
+            message,
+        }
+    }
+}
+
+impl PlanPolicy {
+    /// Every rule `plan` breaks; empty when it is acceptable. Capabilities
+    /// are resolved through `capabilities`, and `agents` are the
+    /// capabilities of each known agent.
+    pub fn validate(
+        &self,
+        plan: &Plan,
+        capabilities: &CapabilityRegistry,
+        agents: &[&[String]],
+    ) -> Vec<PlanViolation> {
+        let mut violations = Vec::new();
+        if plan.subtasks.len() > self.max_subtasks {
+            violations.push(PlanViolation::new(
+                "max_subtasks",
+                None,
+                format!(
+                    "Plan has {} subtasks; at most {} are allowed",
+                    plan.subtasks.len(),
+                    self.max_subtasks
+                ),
+            ));
+        }
+
+        let mut seen: BTreeMap<u32, usize> = BTreeMap::new();
+        for subtask in &plan.subtasks {
+            *seen.entry(subtask.index).or_default() += 1;
+        }
+        for (&index, &count) in seen.iter().filter(|(_, count)| **count > 1) {
+            violations.push(PlanViolation::new(
+                "duplicate_index",
+                Some(index),
+                format!("Index {} is used by {} subtasks", index, count),
+            ));
+        }
+        // Indices run from 0 or 1 without gaps.
+        if let (Some(&first), Some(&last)) = (seen.keys().next(), seen.keys().next_back()) {
+            if first > 1 || (last - first) as usize + 1 != seen.len() {
+                let missing: Vec<String> = (first.min(1)..last)
+                    .filter(|i| !seen.contains_key(i))
+                    .map(|i| i.to_string())
+                    .collect();
+                violations.push(PlanViolation::new(
+                    "index_gap",
+                    None,
+                    format!(
+                        "Subtask indices must be contiguous from 0 or 1; missing {}",
+                        missing.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        for subtask in &plan.subtasks {
+            let index = Some(subtask.index);
+            if subtask.description.trim().chars().count() < self.min_description_chars.max(1) {
+                violations.push(PlanViolation::new(
+                    "description_too_short",
+                    index,
+                    format!(
+                        "Description must have at least {} characters",
+                        self.min_description_chars.max(1)
+                    ),
+                ));
+            }
+            let complexity = subtask.estimated_complexity;
+            if !(0.0..=1.0).contains(&complexity) {
+                violations.push(PlanViolation::new(
+                    "complexity_out_of_range",
+                    index,
+                    format!("Estimated complexity {} is outside [0, 1]", complexity),
+                ));
+            }
+            if !self.known_capabilities.is_empty() || capabilities.has_known() {
+                for capability in &subtask.required_capabilities {
+                    if !capabilities.is_registered_in(&self.known_capabilities, capability)
+                        && !capabilities.is_registered(capability)
+                    {
+                        violations.push(PlanViolation::new(
+                            "unknown_capability",
+                            index,
+                            format!("Capability {:?} is not registered in this swarm", capability),
+                        ));
+                    }
+                }
+            }
+            if self.min_coverage > 0.0 && !subtask.required_capabilities.is_empty() {
+                let best = agents
+                    .iter()
+                    .map(|held| capabilities.coverage(held, &subtask.required_capabilities))
+                    .fold(0.0, f64::max);
+                if best < self.min_coverage {
+                    violations.push(PlanViolation::new(
+                        "uncovered_capability",
+                        index,
+                        format!(
+                            "No known agent covers the required capabilities {} well enough ({:.2} < {:.2})",
+                            subtask.required_capabilities.join(", "),
+                            best,
+                            self.min_coverage
+                        ),
+                    ));
+                }
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::{AgentId, PlanSubtask};
+
+    fn subtask(index: u32, description: &str, complexity: f64, capabilities: &[&str]) -> PlanSubtask {
+        PlanSubtask {
+            index,
+            description: description.to_string(),
+            required_capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            estimated_complexity: complexity,
+            budget: None,
+        }
+    }
+
+    fn plan(subtasks: Vec<PlanSubtask>) -> Plan {
+        let mut plan = Plan::new("t1".to_string(), AgentId::new("did:swarm:p".to_string()), 1);
+        plan.subtasks = subtasks;
+        plan
+    }
+
+    #[test]
+    fn plan_violations_are_reported_per_rule_and_subtask() {
+        let policy = PlanPolicy {
+            max_subtasks: 3,
+            known_capabilities: vec!["web_search".to_string()],
+            ..PlanPolicy::default()
+        };
+        let good = plan(vec![
+            subtask(1, "Collect sources", 0.4, &["web_search"]),
+            subtask(2, "Summarize", 1.0, &[]),
+        ]);
+        assert!(policy.validate(&good, &CapabilityRegistry::default(), &[]).is_empty());
+
+        let bad = plan(vec![
+            subtask(0, "Collect sources", 0.4, &["web_search"]),
+            subtask(2, "  ", 0.5, &[]),
+            subtask(2, "Write report", 7.0, &["telepathy"]),
+            subtask(4, "Review", f64::NAN, &[]),
+        ]);
+        let rules: Vec<(&str, Option<u32>)> = policy
+            .validate(&bad, &CapabilityRegistry::default(), &[])
+            .iter()
+            .map(|v| (v.rule, v.subtask_index))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("max_subtasks", None),
+                ("duplicate_index", Some(2)),
+                ("index_gap", None),
+                ("description_too_short", Some(2)),
+                ("complexity_out_of_range", Some(2)),
+                ("unknown_capability", Some(2)),
+                ("complexity_out_of_range", Some(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn capabilities_resolve_through_the_registry_and_must_be_covered() {
+        let registry = CapabilityRegistry::new(&crate::capabilities::CapabilityConfig {
+            known: vec!["code.rust".to_string()],
+            aliases: [("rust".to_string(), "code.rust".to_string())].into_iter().collect(),
+        });
+        let policy = PlanPolicy {
+            min_coverage: 0.8,
+            ..PlanPolicy::default()
+        };
+        let specialist = vec!["code.rust".to_string()];
+        let generalist = vec!["code".to_string()];
+        let rules = |plan: &Plan, agents: &[&[String]]| -> Vec<&'static str> {
+            policy.validate(plan, &registry, agents).iter().map(|v| v.rule).collect()
+        };
+
+        let rust = plan(vec![subtask(1, "Port the parser", 0.4, &["Rust", "code"])]);
+        assert!(rules(&rust, &[&specialist]).is_empty());
+        assert_eq!(rules(&rust, &[&generalist]), ["uncovered_capability"]);
+        assert_eq!(rules(&rust, &[]), ["uncovered_capability"]);
+
+        let go = plan(vec![subtask(1, "Port the parser", 0.4, &["code.go"])]);
+        assert_eq!(rules(&go, &[&specialist]), ["unknown_capability", "uncovered_capability"]);
+    }
+}
@@ -0,0 +1,318 @@
+//! Hot configuration reload behind `swarm.reload_config`.
+//!
+//! The connector re-reads its configuration the way it was loaded at
+//! startup — file, environment, then command-line overrides — and compares
+//! it with the configuration it is running. Settings in [`LIVE_SETTINGS`]
+//! take effect immediately: consensus policy for new rounds, the agent's
+//! capabilities and concurrency limit, templates, plan policy, board
+//! formation, elections, bootstrap peers, the keepalive and announce
+//! intervals, and the log level. Any other change is reported as needing a
+//! restart and left out of the running configuration, so it is reported
+//! again on the next reload until the connector restarts.
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, OnceLock};
+
+use serde::Serialize;
+
+use crate::capabilities::CapabilityRegistry;
+use crate::config::ConnectorConfig;
+use crate::connector::ConnectorState;
+use crate::tui::LogCategory;
+
+/// Settings, as `section` or `section.field`, that a reload applies
+/// without a restart.
+pub const LIVE_SETTINGS: &[&str] = &[
+    "consensus.tie_break",
+    "consensus.tie_break_seed",
+    "consensus.ballot_mode",
+    "consensus.quorum",
+    "consensus.verification_rate",
+    "consensus.proposal_bond",
+    "consensus.reveal_timeout_secs",
+    "consensus.max_round_age_secs",
+    "consensus.max_tender_attempts",
+    "consensus.retender_relax_tier",
+    "consensus.plan_approval_timeout_secs",
+    "consensus.assignment",
+    "consensus.assignment_seed",
+    "consensus.committee_size",
+    "agent.capabilities",
+    "agent.max_concurrent_tasks",
+    "templates",
+    "plan_policy",
+    "capabilities",
+    "board",
+    "election",
+    "network.bootstrap_peers",
+    "hierarchy.keepalive_interval_secs",
+    "swarm.announce_interval_secs",
+    "logging.level",
+];
+
+/// Re-reads the configuration from where it was loaded at startup.
+pub type ConfigLoader = Arc<dyn Fn() -> anyhow::Result<ConnectorConfig> + Send + Sync>;
+
+/// Swaps the log filter for a new level; installed by the binary when the
+/// level comes from the configuration rather than `RUST_LOG`.
+type LogFilterHook = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+static LOG_FILTER_HOOK: OnceLock<LogFilterHook> = OnceLock::new();
+
+/// Let reloads change the log level through `hook`. Only the first hook
+/// installed is kept.
+pub fn install_log_filter_hook(hook: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static) {
+    let _ = LOG_FILTER_HOOK.set(Box::new(hook));
+}
+
+/// The running configuration and how to reload it.
+#[derive(Default)]
+pub struct ConfigReload {
+    running: ConnectorConfig,
+    loader: Option<ConfigLoader>,
+    /// Running configuration the event loop has not picked up yet.
+    pending: Option<ConnectorConfig>,
+}
+
+impl ConfigReload {
+    pub fn new(running: ConnectorConfig) -> Self {
+        Self {
+            running,
+            ..Self::default()
+        }
+    }
+
+    /// Set how `swarm.reload_config` re-reads the configuration.
+    pub fn set_loader(&mut self, loader: ConfigLoader) {
+        self.loader = Some(loader);
+    }
+
+    /// The configuration in force, including settings reloaded live.
+    pub fn running(&self) -> &ConnectorConfig {
+        &self.running
+    }
+
+    /// The running configuration, once after each reload, for the event
+    /// loop's own copy.
+    pub fn take_pending(&mut self) -> Option<ConnectorConfig> {
+        self.pending.take()
+    }
+}
+
+/// Outcome of a reload, as `section.field` paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReloadReport {
+    /// Changed settings now in effect.
+    pub applied: Vec<String>,
+    /// Changed settings ignored until the connector restarts.
+    pub requires_restart: Vec<String>,
+}
+
+/// Re-read the configuration with the loader set at startup and apply it.
+pub fn reload(state: &mut ConnectorState) -> Result<ReloadReport, String> {
+    let loader = state
+        .config_reload
+        .loader
+        .clone()
+        .ok_or_else(|| "This connector has no configuration source to reload".to_string())?;
+    let config = loader().map_err(|e| format!("Failed to load configuration: {}", e))?;
+    apply(state, config)
+}
+
+/// Apply the live settings of `new` to the state and report what changed.
+pub fn apply(state: &mut ConnectorState, new: ConnectorConfig) -> Result<ReloadReport, String> {
+    let old = state.config_reload.running.clone();
+    let mut running = old.clone();
+    take_live(&mut running, &new);
+    let applied = changed_settings(&old, &running);
+    let requires_restart = changed_settings(&running, &new);
+
+    let changed = |setting: &str| applied.iter().any(|s| covers(setting, s));
+    if changed("logging.level") {
+        if let Some(hook) = LOG_FILTER_HOOK.get() {
+            hook(&running.logging.level)?;
+        }
+    }
+    let consensus = &running.consensus;
+    if changed("consensus.tie_break")
+        || changed("consensus.tie_break_seed")
+        || changed("consensus.ballot_mode")
+    {
+        state.voting_config = consensus.voting_config();
+    }
+    if changed("consensus.quorum") {
+        state.quorum_policy = consensus.quorum.clone();
+    }
+    if changed("consensus.verification_rate") {
+        state.verification_rate = consensus.verification_rate.clamp(0.0, 1.0);
+    }
+    if changed("consensus.proposal_bond") {
+        state.proposal_bond = consensus.proposal_bond;
+    }
+    if changed("consensus.reveal_timeout_secs") {
+        state.reveal_timeout_secs = consensus.reveal_timeout_secs;
+    }
+    if changed("consensus.max_round_age_secs") {
+        state.max_round_age_secs = consensus
+            .max_round_age_secs
+            .unwrap_or(crate::connector::DEFAULT_MAX_ROUND_AGE_SECS);
+    }
+    if changed("consensus.max_tender_attempts") {
+        state.max_tender_attempts = consensus
+            .max_tender_attempts
+            .unwrap_or(crate::connector::DEFAULT_MAX_TENDER_ATTEMPTS);
+    }
+    if changed("consensus.retender_relax_tier") {
+        state.retender_relax_tier = consensus.retender_relax_tier;
+    }
+    if changed("consensus.plan_approval_timeout_secs") {
+        state.plan_approval_timeout_secs = consensus
+            .plan_approval_timeout_secs
+            .unwrap_or(crate::connector::DEFAULT_PLAN_APPROVAL_TIMEOUT_SECS);
+    }
+    if changed("consensus.assignment") || changed("consensus.assignment_seed") {
+        state.assignment_strategy = consensus.assignment;
+        state.assignment_seed = consensus.assignment_seed;
+    }
+    if changed("consensus.committee_size") {
+        state.committee_size = consensus.committee_size;
+    }
+    if changed("agent.capabilities") {
+        let agent_id = state.agent_id.to_string();
+        state
+            .agent_capabilities
+            .insert(agent_id, running.agent.capabilities.clone());
+    }
+    if changed("agent.max_concurrent_tasks") {
+        state.max_concurrent_tasks = running.agent.max_concurrent_tasks;
+    }
+    if changed("templates") {
+        state.task_templates = running.templates.clone();
+    }
+    if changed("plan_policy") {
+        state.plan_policy = running.plan_policy.clone();
+    }
+    if changed("capabilities") {
+        state.capabilities = CapabilityRegistry::new(&running.capabilities);
+    }
+    if changed("board") {
+        state.board_config = running.board.clone();
+    }
+
+    if !applied.is_empty() || !requires_restart.is_empty() {
+        let mut message = format!("Reloaded configuration: applied [{}]", applied.join(", "));
+        if !requires_restart.is_empty() {
+            message.push_str(&format!("; restart needed for [{}]", requires_restart.join(", ")));
+        }
+        state.push_log(LogCategory::System, message);
+    }
+    if !applied.is_empty() {
+        state.config_reload.pending = Some(running.clone());
+        state.config_reload.running = running;
+    }
+    Ok(ReloadReport {
+        applied,
+        requires_restart,
+    })
+}
+
+/// Copy the settings a reload may apply from `new` into `running`. The log
+/// level only counts when a hook can change it.
+fn take_live(running: &mut ConnectorConfig, new: &ConnectorConfig) {
+    running.consensus.tie_break = new.consensus.tie_break;
+    running.consensus.tie_break_seed = new.consensus.tie_break_seed;
+    running.consensus.ballot_mode = new.consensus.ballot_mode;
+    running.consensus.quorum = new.consensus.quorum.clone();
+    running.consensus.verification_rate = new.consensus.verification_rate;
+    running.consensus.proposal_bond = new.consensus.proposal_bond;
+    running.consensus.reveal_timeout_secs = new.consensus.reveal_timeout_secs;
+    running.consensus.max_round_age_secs = new.consensus.max_round_age_secs;
+    running.consensus.max_tender_attempts = new.consensus.max_tender_attempts;
+    running.consensus.retender_relax_tier = new.consensus.retender_relax_tier;
+    running.consensus.plan_approval_timeout_secs = new.consensus.plan_approval_timeout_secs;
+    running.consensus.assignment = new.consensus.assignment;
+    running.consensus.assignment_seed = new.consensus.assignment_seed;
+    running.consensus.committee_size = new.consensus.committee_size;
+    running.agent.capabilities = new.agent.capabilities.clone();
+    running.agent.max_concurrent_tasks = new.agent.max_concurrent_tasks;
+    running.templates = new.templates.clone();
+    running.plan_policy = new.plan_policy.clone();
+    running.capabilities = new.capabilities.clone();
+    running.board = new.board.clone();
+    running.election = new.election.clone();
+    running.network.bootstrap_peers = new.network.bootstrap_peers.clone();
+    running.hierarchy.keepalive_interval_secs = new.hierarchy.keepalive_interval_secs;
+    running.swarm.announce_interval_secs = new.swarm.announce_interval_secs;
+    if LOG_FILTER_HOOK.get().is_some() {
+        running.logging.level = new.logging.level.clone();
+    }
+}
+
+/// Whether `setting` is, or contains, the changed path `path`.
+fn covers(setting: &str, path: &str) -> bool {
+    path == setting || path.starts_with(&format!("{}.", setting))
+}
+
+/// Settings that differ between two configurations, as `section` for
+/// top-level values and `section.field` within sections, sorted.
+pub fn changed_settings(old: &ConnectorConfig, new: &ConnectorConfig) -> Vec<String> {
+    let (old, new) = match (serde_json::to_value(old), serde_json::to_value(new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        _ => return Vec::new(),
+    };
+    let null = serde_json::Value::Null;
+    let mut changed = BTreeSet::new();
+    let sections: BTreeSet<&String> = old
+        .as_object()
+        .into_iter()
+        .chain(new.as_object())
+        .flat_map(|o| o.keys())
+        .collect();
+    for section in sections {
+        let (before, after) = (old.get(section).unwrap_or(&null), new.get(section).unwrap_or(&null));
+        if before == after {
+            continue;
+        }
+        match (before.as_object(), after.as_object()) {
+            (Some(before), Some(after)) => {
+                for field in before.keys().chain(after.keys()) {
+                    if before.get(field) != after.get(field) {
+                        changed.insert(format!("{}.{}", section, field));
+                    }
+                }
+            }
+            _ => {
+                changed.insert(section.clone());
+            }
+        }
+    }
+    changed.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_settings_are_applied_and_the_rest_reported() {
+        let old = ConnectorConfig::default();
+        let mut new = old.clone();
+        new.consensus.proposal_bond = 7;
+        new.network.bootstrap_peers = vec!["/ip4/10.0.0.1/tcp/9000".to_string()];
+        new.rpc.bind_addr = "127.0.0.1:9999".to_string();
+        new.hierarchy.branching_factor += 1;
+
+        let mut running = old.clone();
+        take_live(&mut running, &new);
+        assert_eq!(
+            changed_settings(&old, &running),
+            ["consensus.proposal_bond", "network.bootstrap_peers"]
+        );
+        assert_eq!(
+            changed_settings(&running, &new),
+            ["hierarchy.branching_factor", "rpc.bind_addr"]
+        );
+        assert!(LIVE_SETTINGS.iter().any(|s| covers(s, "consensus.proposal_bond")));
+        assert!(!covers("board", "boards.x"));
+    }
+}
@@ -0,0 +1,206 @@
+//! Replay of recorded inbound traffic.
+//!
+//! With `[trace] record_payloads` set, the trace files keep the raw content
+//! of every inbound message the connector handled. `wws-connector replay`
+//! reads them back with [`recorded_messages`] and feeds them, in arrival
+//! order, through [`WwsConnector::replay`] against a fresh state, so a
+//! state-machine bug seen on a live node can be reproduced
+//! deterministically. [`diff_runs`] then compares the replayed task tree
+//! with a transcript the original node exported with `swarm.export_run`.
+//!
+//! [`WwsConnector::replay`]: crate::connector::WwsConnector::replay
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::connector::MessageTraceEvent;
+use crate::transcript::RunTranscript;
+
+/// An inbound message as it reached the message handler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedMessage {
+    pub topic: String,
+    /// Peer that propagated the message.
+    pub peer: Option<String>,
+    pub payload: String,
+}
+
+/// The handled inbound messages among `events`, in trace order. Events
+/// written without a payload are skipped.
+pub fn recorded_messages(
+    events: impl IntoIterator<Item = MessageTraceEvent>,
+) -> Vec<RecordedMessage> {
+    events
+        .into_iter()
+        .filter(|event| event.direction == "inbound" && event.outcome == "received")
+        .filter_map(|event| {
+            Some(RecordedMessage {
+                payload: event.payload?,
+                topic: event.topic,
+                peer: event.peer,
+            })
+        })
+        .collect()
+}
+
+/// One field of one task on which the replayed run ended up elsewhere than
+/// the original. A task missing from either run has `field` "task" and a
+/// null value on the side that lacks it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunDifference {
+    pub task_id: String,
+    pub field: String,
+    pub original: serde_json::Value,
+    pub replayed: serde_json::Value,
+}
+
+/// Compare the task tree of two runs: each task's status, assignee, parent
+/// and subtasks, the content ID of its result and how many ballots were
+/// cast on it. Tasks are listed in the original's order, then those only
+/// the replay has.
+pub fn diff_runs(original: &RunTranscript, replayed: &RunTranscript) -> Vec<RunDifference> {
+    let fields = |run: &RunTranscript| -> BTreeMap<String, BTreeMap<&'static str, serde_json::Value>> {
+        run.tasks
+            .iter()
+            .map(|task| {
+                let subtasks: BTreeSet<&String> = task.subtasks.iter().collect();
+                let fields = BTreeMap::from([
+                    ("status", serde_json::json!(task.status)),
+                    ("assigned_to", serde_json::json!(task.assigned_to)),
+                    ("parent_task_id", serde_json::json!(task.parent_task_id)),
+                    ("subtasks", serde_json::json!(subtasks)),
+                    (
+                        "result_cid",
+                        serde_json::json!(run.artifacts.get(&task.task_id).map(|a| &a.content_cid)),
+                    ),
+                    (
+                        "ballots",
+                        serde_json::json!(run.ballots.get(&task.task_id).map_or(0, Vec::len)),
+                    ),
+                ]);
+                (task.task_id.clone(), fields)
+            })
+            .collect()
+    };
+    let (before, after) = (fields(original), fields(replayed));
+    let task_ids = original
+        .tasks
+        .iter()
+        .chain(replayed.tasks.iter().filter(|t| !before.contains_key(&t.task_id)))
+        .map(|t| &t.task_id);
+
+    let mut differences = Vec::new();
+    for task_id in task_ids {
+        match (before.get(task_id), after.get(task_id)) {
+            (Some(before), Some(after)) => {
+                for (field, value) in before {
+                    if after[field] != *value {
+                        differences.push(RunDifference {
+                            task_id: task_id.clone(),
+                            field: field.to_string(),
+                            original: value.clone(),
+                            replayed: after[field].clone(),
+                        });
+                    }
+                }
+            }
+            (before, after) => differences.push(RunDifference {
+                task_id: task_id.clone(),
+                field: "task".to_string(),
+                original: before.map_or(serde_json::Value::Null, |f| f["status"].clone()),
+                replayed: after.map_or(serde_json::Value::Null, |f| f["status"].clone()),
+            }),
+        }
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::{AgentId, Task, TaskStatus};
+
+    fn event(direction: &str, outcome: &str, payload: Option<&str>) -> MessageTraceEvent {
+        MessageTraceEvent {
+            timestamp: chrono::Utc::now(),
+            direction: direction.to_string(),
+            peer: Some("12D3KooWpeer".to_string()),
+            topic: "/wws/1.0.0/s/public/proposals".to_string(),
+            method: Some("consensus.proposal_commit".to_string()),
+            task_id: None,
+            size_bytes: 2,
+            outcome: outcome.to_string(),
+            payload: payload.map(str::to_string),
+        }
+    }
+
+    fn run(tasks: Vec<Task>) -> RunTranscript {
+        RunTranscript {
+            format_version: crate::transcript::TRANSCRIPT_FORMAT_VERSION,
+            root_task_id: tasks[0].task_id.clone(),
+            exported_at: chrono::Utc::now(),
+            exported_by: "did:swarm:origin".to_string(),
+            tasks,
+            timelines: BTreeMap::new(),
+            deliberation: BTreeMap::new(),
+            ballots: BTreeMap::new(),
+            irv_rounds: BTreeMap::new(),
+            artifacts: BTreeMap::new(),
+            result_text: BTreeMap::new(),
+            costs: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn only_handled_inbound_messages_with_payloads_are_replayed() {
+        let events = vec![
+            event("inbound", "received", Some("{\"a\":1}")),
+            event("outbound", "published", None),
+            event("inbound", "parse_error", None),
+            event("inbound", "received", None),
+            event("inbound", "received", Some("{\"b\":2}")),
+        ];
+        let payloads: Vec<_> = recorded_messages(events)
+            .into_iter()
+            .map(|m| m.payload)
+            .collect();
+        assert_eq!(payloads, ["{\"a\":1}", "{\"b\":2}"]);
+    }
+
+    #[test]
+    fn diff_reports_diverging_fields_and_missing_tasks() {
+        let mut root = Task::new("Root".to_string(), 1, 1);
+        let mut child = Task::new("Child".to_string(), 2, 1);
+        child.parent_task_id = Some(root.task_id.clone());
+        root.subtasks = vec![child.task_id.clone()];
+        root.status = TaskStatus::Completed;
+        let original = run(vec![root.clone(), child.clone()]);
+        assert!(diff_runs(&original, &original).is_empty());
+
+        let mut stalled = root.clone();
+        stalled.status = TaskStatus::InProgress;
+        let mut reassigned = child.clone();
+        reassigned.assigned_to = Some(AgentId::new("did:swarm:other".to_string()));
+        let extra = Task::new("Extra".to_string(), 2, 1);
+        let replayed = run(vec![stalled, reassigned, extra.clone()]);
+
+        let fields: Vec<_> = diff_runs(&original, &replayed)
+            .into_iter()
+            .map(|d| (d.task_id, d.field))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                (root.task_id.clone(), "status".to_string()),
+                (child.task_id.clone(), "assigned_to".to_string()),
+                (extra.task_id.clone(), "task".to_string()),
+            ]
+        );
+
+        let lost = diff_runs(&original, &run(vec![root.clone()]));
+        assert_eq!(lost.len(), 1);
+        assert_eq!(lost[0].task_id, child.task_id);
+        assert_eq!(lost[0].replayed, serde_json::Value::Null);
+    }
+}
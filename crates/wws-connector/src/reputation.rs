@@ -0,0 +1,432 @@
+//! The reputation ledger and the attestations it is built from.
+//!
+//! Reputation counted from what each node happened to see diverges from
+//! node to node. Instead, whoever coordinates a task's parent signs a
+//! `ReputationAttestation` for each outcome it observes on the task — the
+//! result arrived on time or late, passed or failed verification — and
+//! publishes it in a `reputation.attest` batch on the swarm's keep-alive
+//! topic. Every member admits the attestations it receives into its ledger,
+//! so any node can show the signed statements behind a score.
+//!
+//! The ledger keeps one attestation per subject, task, kind of outcome and
+//! issuer; a later attestation from the same issuer supersedes its earlier
+//! one. When issuers disagree, the least favourable outcome prevails, ties
+//! going to the earlier attestation and then the smaller issuer DID, so
+//! nodes holding the same attestations agree on every score whatever order
+//! they arrived in. Issuers republish what they attested now and then, so a
+//! member that missed a batch catches up.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use wws_protocol::{
+    AgentId, AttestationOutcome, ProtocolMethod, ReputationAttestParams, ReputationAttestation, SwarmId,
+    SwarmMessage, SwarmTopics,
+};
+
+use crate::connector::ConnectorState;
+use crate::storage::AttestationStore;
+use crate::tui::LogCategory;
+use crate::verification::{VERIFICATION_FAIL_POINTS, VERIFICATION_PASS_POINTS};
+
+/// Score of an agent nothing has been attested about yet.
+pub const BASE_REPUTATION: i64 = 10;
+/// Points for a result that arrived by its deadline.
+pub const ON_TIME_POINTS: i64 = 5;
+/// Points for a result that arrived after its deadline.
+pub const LATE_POINTS: i64 = 2;
+/// How far in the future an attestation may be dated.
+pub const ATTESTATION_CLOCK_SKEW_SECS: i64 = 300;
+/// How often an issuer republishes the attestations it issued.
+pub const ATTESTATION_REPUBLISH_SECS: i64 = 600;
+/// Most attestations published in one `reputation.attest` batch.
+pub const MAX_ATTESTATION_BATCH: usize = 128;
+
+/// What an outcome adds to its subject's score.
+pub fn points(outcome: AttestationOutcome) -> i64 {
+    match outcome {
+        AttestationOutcome::OnTime => ON_TIME_POINTS,
+        AttestationOutcome::Late => LATE_POINTS,
+        AttestationOutcome::VerificationPassed => VERIFICATION_PASS_POINTS as i64,
+        AttestationOutcome::VerificationFailed => -(VERIFICATION_FAIL_POINTS as i64),
+    }
+}
+
+/// The claim an attestation makes: a kind of outcome for a subject on a
+/// task. Issuers attesting the same claim compete under the conflict rules.
+fn claim_of(attestation: &ReputationAttestation) -> String {
+    let kind = if attestation.outcome.is_timeliness() {
+        "timeliness"
+    } else {
+        "verification"
+    };
+    format!("{}|{}|{}", attestation.subject, attestation.task_id, kind)
+}
+
+/// Whether `candidate` prevails over `current` among different issuers'
+/// attestations of one claim.
+fn prevails(candidate: &ReputationAttestation, current: &ReputationAttestation) -> bool {
+    let rank = |a: &ReputationAttestation| (points(a.outcome), a.issued_at, a.issuer.to_string());
+    rank(candidate) < rank(current)
+}
+
+/// Every admitted attestation, with the scores they add up to.
+pub struct ReputationLedger {
+    store: Box<dyn AttestationStore>,
+    /// Record keys of each subject's attestations, by claim.
+    claims: HashMap<String, BTreeMap<String, BTreeSet<String>>>,
+    scores: HashMap<String, i64>,
+    /// Attestations issued here and not yet published.
+    outbox: Vec<ReputationAttestation>,
+    last_republished: Option<DateTime<Utc>>,
+}
+
+impl ReputationLedger {
+    /// A ledger over `store`, scored from whatever it already holds.
+    pub fn new(store: Box<dyn AttestationStore>) -> Self {
+        let mut ledger = Self {
+            store,
+            claims: HashMap::new(),
+            scores: HashMap::new(),
+            outbox: Vec::new(),
+            last_republished: None,
+        };
+        let records: Vec<(String, String, String)> = ledger
+            .store
+            .iter()
+            .map(|(key, a)| (key.clone(), a.subject.to_string(), claim_of(a)))
+            .collect();
+        for (key, subject, claim) in records {
+            ledger.claims.entry(subject).or_default().entry(claim).or_default().insert(key);
+        }
+        let subjects: Vec<String> = ledger.claims.keys().cloned().collect();
+        for subject in subjects {
+            ledger.rescore(&subject);
+        }
+        ledger
+    }
+
+    /// The key an attestation is stored under: its claim and issuer.
+    pub fn record_key(attestation: &ReputationAttestation) -> String {
+        format!("{}|{}", claim_of(attestation), attestation.issuer)
+    }
+
+    /// Admit a verified attestation. Returns whether it changed the ledger:
+    /// `false` when it repeats or predates what its issuer already attested.
+    pub fn admit(&mut self, attestation: ReputationAttestation, now: DateTime<Utc>) -> Result<bool, String> {
+        if attestation.subject == attestation.issuer {
+            return Err(format!("{} attested its own work", attestation.issuer));
+        }
+        if attestation.issued_at > now + chrono::Duration::seconds(ATTESTATION_CLOCK_SKEW_SECS) {
+            return Err(format!(
+                "attestation from {} is dated {}, in the future",
+                attestation.issuer, attestation.issued_at
+            ));
+        }
+        let key = Self::record_key(&attestation);
+        if self
+            .store
+            .get(&key)
+            .is_some_and(|known| known.issued_at >= attestation.issued_at)
+        {
+            return Ok(false);
+        }
+        let subject = attestation.subject.to_string();
+        self.claims
+            .entry(subject.clone())
+            .or_default()
+            .entry(claim_of(&attestation))
+            .or_default()
+            .insert(key.clone());
+        self.store.insert(key, attestation);
+        self.rescore(&subject);
+        Ok(true)
+    }
+
+    /// The prevailing attestation of each of `subject`'s claims.
+    pub fn prevailing(&self, subject: &str) -> Vec<&ReputationAttestation> {
+        let Some(claims) = self.claims.get(subject) else {
+            return Vec::new();
+        };
+        claims
+            .values()
+            .filter_map(|keys| {
+                keys.iter()
+                    .filter_map(|key| self.store.get(key))
+                    .reduce(|best, a| if prevails(a, best) { a } else { best })
+            })
+            .collect()
+    }
+
+    /// Every attestation about `subject`, including those that did not
+    /// prevail.
+    pub fn attestations_of(&self, subject: &str) -> Vec<&ReputationAttestation> {
+        self.claims
+            .get(subject)
+            .into_iter()
+            .flat_map(|claims| claims.values().flatten())
+            .filter_map(|key| self.store.get(key))
+            .collect()
+    }
+
+    /// Number of `subject`'s claims whose issuers disagree on the outcome.
+    pub fn conflicts_of(&self, subject: &str) -> usize {
+        let Some(claims) = self.claims.get(subject) else {
+            return 0;
+        };
+        claims
+            .values()
+            .filter(|keys| {
+                let outcomes: HashSet<AttestationOutcome> = keys
+                    .iter()
+                    .filter_map(|key| self.store.get(key))
+                    .map(|a| a.outcome)
+                    .collect();
+                outcomes.len() > 1
+            })
+            .count()
+    }
+
+    /// `subject`'s score from the prevailing attestations, or `None` when
+    /// nothing has been attested about it.
+    pub fn score(&self, subject: &str) -> Option<i64> {
+        self.scores.get(subject).copied()
+    }
+
+    /// Number of attestations held.
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    fn rescore(&mut self, subject: &str) {
+        let score = BASE_REPUTATION + self.prevailing(subject).iter().map(|a| points(a.outcome)).sum::<i64>();
+        self.scores.insert(subject.to_string(), score);
+    }
+
+    /// Queue an attestation issued here for the next batch.
+    pub fn queue(&mut self, attestation: ReputationAttestation) {
+        self.outbox.push(attestation);
+    }
+
+    /// The attestations to publish now: the queued ones, plus everything
+    /// `issuer` attested when a republish is due.
+    pub fn take_outgoing(&mut self, issuer: &AgentId, now: DateTime<Utc>) -> Vec<ReputationAttestation> {
+        let republish = self
+            .last_republished
+            .is_none_or(|at| (now - at).num_seconds() >= ATTESTATION_REPUBLISH_SECS);
+        if !republish {
+            return std::mem::take(&mut self.outbox);
+        }
+        self.last_republished = Some(now);
+        self.outbox.clear();
+        self.store.values().filter(|a| a.issuer == *issuer).cloned().collect()
+    }
+
+    /// Persist attestations admitted since the last flush.
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        self.store.flush()
+    }
+}
+
+/// Issue an attestation of `outcome` for `subject` on `task_id`, when this
+/// node coordinates the task's parent, and queue it for publishing.
+pub fn attest(state: &mut ConnectorState, subject: &str, task_id: &str, outcome: AttestationOutcome) {
+    if subject == state.agent_id.as_str() {
+        return;
+    }
+    let Some(parent_id) = state.task_details.get(task_id).and_then(|t| t.parent_task_id.clone()) else {
+        return;
+    };
+    if !state.coordinates(&state.agent_id, &parent_id) {
+        return;
+    }
+    let mut attestation = ReputationAttestation {
+        swarm_id: SwarmId::new(state.swarm_for_task(task_id)),
+        subject: AgentId::new(subject.to_string()),
+        task_id: task_id.to_string(),
+        outcome,
+        issuer: state.agent_id.clone(),
+        issuer_key: String::new(),
+        issued_at: Utc::now(),
+        signature: String::new(),
+    };
+    attestation.sign(&state.identity_key);
+    if let Ok(true) = state.reputation.admit(attestation.clone(), Utc::now()) {
+        state.reputation.queue(attestation);
+    }
+}
+
+/// Whether `attestation`'s issuer may attest its task: its key binds its
+/// DID and, when the task is known here, it coordinates the task's parent.
+fn check_issuer(state: &ConnectorState, attestation: &ReputationAttestation) -> Result<(), String> {
+    let bound = attestation
+        .issuer_verifying_key()
+        .is_some_and(|key| crate::did::binds_key(attestation.issuer.as_str(), &key));
+    if !bound {
+        return Err(format!("key does not belong to {}", attestation.issuer));
+    }
+    let Some(task) = state.task_details.get(&attestation.task_id) else {
+        return Ok(());
+    };
+    let authority = task.parent_task_id.as_deref().unwrap_or(&attestation.task_id);
+    if !state.coordinates(&attestation.issuer, authority) {
+        return Err(format!(
+            "{} does not coordinate task {}",
+            attestation.issuer, attestation.task_id
+        ));
+    }
+    Ok(())
+}
+
+/// Admit the attestations of a received batch whose issuer may attest them.
+/// Returns how many changed the ledger.
+pub fn accept_attestations(state: &mut ConnectorState, batch: ReputationAttestParams) -> usize {
+    let now = Utc::now();
+    let mut admitted = 0;
+    for attestation in batch.attestations {
+        let issuer = attestation.issuer.clone();
+        let result = check_issuer(state, &attestation).and_then(|()| state.reputation.admit(attestation, now));
+        match result {
+            Ok(true) => admitted += 1,
+            Ok(false) => {}
+            Err(reason) => {
+                tracing::warn!(issuer = %issuer, reason = %reason, "Ignoring reputation attestation");
+                state.push_log(
+                    LogCategory::Error,
+                    format!("Ignored reputation attestation from {}: {}", issuer, reason),
+                );
+            }
+        }
+    }
+    admitted
+}
+
+/// Publish the attestations due, batched per swarm on each swarm's
+/// keep-alive topic.
+pub async fn publish_attestations(state: &Arc<RwLock<ConnectorState>>, network_handle: &wws_network::SwarmHandle) {
+    let outgoing = {
+        let mut state = state.write().await;
+        let issuer = state.agent_id.clone();
+        state.reputation.take_outgoing(&issuer, Utc::now())
+    };
+    let mut by_swarm: BTreeMap<String, Vec<ReputationAttestation>> = BTreeMap::new();
+    for attestation in outgoing {
+        by_swarm.entry(attestation.swarm_id.to_string()).or_default().push(attestation);
+    }
+    for (swarm_id, attestations) in by_swarm {
+        let topic = SwarmTopics::keepalive_for(&swarm_id);
+        for chunk in attestations.chunks(MAX_ATTESTATION_BATCH) {
+            let batch = ReputationAttestParams {
+                attestations: chunk.to_vec(),
+            };
+            let msg = SwarmMessage::new(
+                ProtocolMethod::ReputationAttest.as_str(),
+                serde_json::to_value(&batch).unwrap_or_default(),
+                String::new(),
+            );
+            let Ok(data) = serde_json::to_vec(&msg) else {
+                continue;
+            };
+            if let Err(e) = network_handle.publish(&topic, data).await {
+                tracing::warn!(error = %e, swarm = %swarm_id, "Failed to publish reputation attestations");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryAttestationStore;
+
+    fn attestation(issuer: &str, outcome: AttestationOutcome, issued_at: DateTime<Utc>) -> ReputationAttestation {
+        let key = wws_protocol::crypto::generate_keypair();
+        let mut attestation = ReputationAttestation {
+            swarm_id: SwarmId::new("lab".into()),
+            subject: AgentId::new("did:swarm:worker".into()),
+            task_id: "t1".into(),
+            outcome,
+            issuer: AgentId::new(issuer.into()),
+            issuer_key: String::new(),
+            issued_at,
+            signature: String::new(),
+        };
+        attestation.sign(&key);
+        attestation
+    }
+
+    #[test]
+    fn the_least_favourable_outcome_prevails_whatever_the_order() {
+        let now = Utc::now();
+        let early = now - chrono::Duration::seconds(60);
+        let on_time = attestation("did:swarm:chair-a", AttestationOutcome::OnTime, early);
+        let late = attestation("did:swarm:chair-b", AttestationOutcome::Late, now);
+        let passed = attestation("did:swarm:chair-a", AttestationOutcome::VerificationPassed, early);
+
+        let mut forward = ReputationLedger::new(Box::new(InMemoryAttestationStore::new()));
+        let mut backward = ReputationLedger::new(Box::new(InMemoryAttestationStore::new()));
+        for a in [&on_time, &late, &passed] {
+            assert_eq!(forward.admit(a.clone(), now), Ok(true));
+        }
+        for a in [&passed, &late, &on_time] {
+            assert_eq!(backward.admit(a.clone(), now), Ok(true));
+        }
+        let expected = BASE_REPUTATION + LATE_POINTS + VERIFICATION_PASS_POINTS as i64;
+        assert_eq!(forward.score("did:swarm:worker"), Some(expected));
+        assert_eq!(backward.score("did:swarm:worker"), Some(expected));
+        assert_eq!(forward.conflicts_of("did:swarm:worker"), 1);
+        assert_eq!(forward.attestations_of("did:swarm:worker").len(), 3);
+        assert_eq!(forward.prevailing("did:swarm:worker").len(), 2);
+        assert_eq!(forward.score("did:swarm:other"), None);
+
+        // Its issuer's later word supersedes; an older or repeated one does not.
+        let revised = attestation("did:swarm:chair-b", AttestationOutcome::OnTime, now + chrono::Duration::seconds(1));
+        assert_eq!(forward.admit(late.clone(), now), Ok(false));
+        assert_eq!(forward.admit(revised, now), Ok(true));
+        assert_eq!(
+            forward.score("did:swarm:worker"),
+            Some(BASE_REPUTATION + ON_TIME_POINTS + VERIFICATION_PASS_POINTS as i64)
+        );
+        assert_eq!(forward.conflicts_of("did:swarm:worker"), 0);
+        assert_eq!(forward.admit(late, now), Ok(false));
+
+        let mut own = attestation("did:swarm:worker", AttestationOutcome::OnTime, now);
+        own.task_id = "t2".into();
+        assert!(forward.admit(own, now).is_err());
+        let future = attestation("did:swarm:chair-c", AttestationOutcome::OnTime, now + chrono::Duration::hours(1));
+        assert!(forward.admit(future, now).is_err());
+    }
+
+    #[test]
+    fn ledgers_rescore_from_their_store() {
+        let now = Utc::now();
+        let failed = attestation("did:swarm:chair-a", AttestationOutcome::VerificationFailed, now);
+        let mut store = InMemoryAttestationStore::new();
+        store.insert(ReputationLedger::record_key(&failed), failed);
+        let ledger = ReputationLedger::new(Box::new(store));
+        assert_eq!(
+            ledger.score("did:swarm:worker"),
+            Some(BASE_REPUTATION - VERIFICATION_FAIL_POINTS as i64)
+        );
+    }
+
+    #[test]
+    fn issuers_republish_what_they_attested_when_due() {
+        let now = Utc::now();
+        let issued = attestation("did:swarm:chair-a", AttestationOutcome::OnTime, now);
+        let issuer = issued.issuer.clone();
+        let mut ledger = ReputationLedger::new(Box::new(InMemoryAttestationStore::new()));
+        ledger.admit(issued.clone(), now).unwrap();
+        ledger.queue(issued);
+        assert_eq!(ledger.take_outgoing(&issuer, now).len(), 1);
+        assert!(ledger.take_outgoing(&issuer, now).is_empty());
+        let later = now + chrono::Duration::seconds(ATTESTATION_REPUBLISH_SECS);
+        assert_eq!(ledger.take_outgoing(&issuer, later).len(), 1);
+    }
+}
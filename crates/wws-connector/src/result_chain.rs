@@ -0,0 +1,294 @@
+//! Merkle-DAG linked result history.
+//!
+//! Every result a connector accepts becomes a node of
+//! [`ConnectorState::merkle_dag`]. An executor's artifact is a leaf hashed
+//! from its content CID; the artifact the connector aggregates for a parent
+//! task is a branch over its subtasks' hashes in subtask order. The
+//! `merkle_hash` of a root task's final artifact is therefore the root of
+//! the whole task tree, and [`verify`] recomputes the chain from the stored
+//! artifacts to check it.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wws_protocol::Artifact;
+use wws_state::MerkleDag;
+
+use crate::connector::ConnectorState;
+
+/// A root hash published in the DHT so others can check a task's result
+/// without trusting this connector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleAnchor {
+    pub task_id: String,
+    pub root: String,
+    pub anchored_by: String,
+    pub anchored_at: DateTime<Utc>,
+}
+
+impl MerkleAnchor {
+    /// DHT key of the anchor for `task_id`.
+    pub fn dht_key(task_id: &str) -> Vec<u8> {
+        format!("{}{}", wws_protocol::MERKLE_ANCHOR_PREFIX, task_id).into_bytes()
+    }
+}
+
+/// One node of a checked chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainNode {
+    pub task_id: String,
+    pub parent_task_id: Option<String>,
+    /// Hash recomputed from the stored artifacts.
+    pub expected: Option<String>,
+    /// `merkle_hash` carried by the stored artifact.
+    pub recorded: Option<String>,
+    /// Whether the recorded hash is a node of the local DAG.
+    pub in_dag: bool,
+    pub ok: bool,
+}
+
+/// Outcome of [`verify`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainReport {
+    pub task_id: String,
+    pub root: String,
+    pub valid: bool,
+    pub nodes: Vec<ChainNode>,
+}
+
+/// Whether `artifact` is the one the connector aggregated for its task,
+/// and so a branch rather than a leaf.
+fn is_branch(artifact: &Artifact) -> bool {
+    artifact.artifact_id == crate::aggregation::aggregated_artifact_id(&artifact.task_id)
+}
+
+/// Recorded hashes of the subtask results of `parent_task_id`, in subtask order.
+pub fn child_hashes(state: &ConnectorState, parent_task_id: &str) -> Vec<String> {
+    state
+        .task_details
+        .get(parent_task_id)
+        .map(|t| t.subtasks.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|id| state.task_results.get(id))
+        .map(|a| a.merkle_hash.clone())
+        .collect()
+}
+
+/// Fill in `artifact.merkle_hash` when the producer left it empty and add
+/// the artifact to the DAG. A root task's branch is noted on its timeline.
+pub fn record(state: &mut ConnectorState, artifact: &mut Artifact) {
+    let task_id = artifact.task_id.clone();
+    let node = if is_branch(artifact) {
+        let children = child_hashes(state, &task_id);
+        state.merkle_dag.add_branch(task_id.clone(), children)
+    } else {
+        state.merkle_dag.add_leaf(task_id.clone(), artifact.content_cid.as_bytes())
+    };
+    if artifact.merkle_hash.is_empty() {
+        artifact.merkle_hash = node.hash.clone();
+    }
+    let is_root = state
+        .task_details
+        .get(&task_id)
+        .is_some_and(|t| t.parent_task_id.is_none());
+    if is_root && !node.children.is_empty() {
+        state.push_task_timeline_event(
+            &task_id,
+            "merkle_root",
+            format!("Root {} over {} subtask results", artifact.merkle_hash, node.children.len()),
+            None,
+        );
+    }
+}
+
+/// Recompute the hash of `task_id`'s result, pushing every node visited
+/// onto `nodes`. Returns `None` when the task has no result.
+///
+/// Subtask lists come from peers; a task reached a second time is reported
+/// as a failed node instead of being descended into again.
+fn check(
+    state: &ConnectorState,
+    task_id: &str,
+    parent_task_id: Option<&str>,
+    visited: &mut HashSet<String>,
+    nodes: &mut Vec<ChainNode>,
+) -> Option<String> {
+    let artifact = state.task_results.get(task_id);
+    if !visited.insert(task_id.to_string()) {
+        let recorded = artifact.map(|a| a.merkle_hash.clone());
+        nodes.push(ChainNode {
+            task_id: task_id.to_string(),
+            parent_task_id: parent_task_id.map(str::to_string),
+            expected: None,
+            in_dag: false,
+            ok: false,
+            recorded: recorded.clone(),
+        });
+        return recorded;
+    }
+    let mut expected = None;
+    if let Some(artifact) = artifact {
+        expected = Some(if is_branch(artifact) {
+            let subtasks = state
+                .task_details
+                .get(task_id)
+                .map(|t| t.subtasks.clone())
+                .unwrap_or_default();
+            let children: Vec<String> = subtasks
+                .iter()
+                .filter_map(|id| check(state, id, Some(task_id), visited, nodes))
+                .collect();
+            MerkleDag::branch_hash(&children)
+        } else {
+            MerkleDag::leaf_hash(artifact.content_cid.as_bytes())
+        });
+    }
+    let recorded = artifact.map(|a| a.merkle_hash.clone());
+    nodes.push(ChainNode {
+        task_id: task_id.to_string(),
+        parent_task_id: parent_task_id.map(str::to_string),
+        ok: expected.is_some() && expected == recorded,
+        in_dag: recorded
+            .as_deref()
+            .is_some_and(|h| state.merkle_dag.get_node(h).is_some()),
+        expected,
+        recorded,
+    });
+    // A parent is checked against what its children recorded: a leaf whose
+    // content changed fails on itself, one that was also re-hashed fails on
+    // its parent.
+    nodes.last().and_then(|n| n.recorded.clone())
+}
+
+/// Recompute the chain below `task_id` from the stored artifacts and
+/// compare each node with the hash its artifact carries. Returns `None`
+/// when the task has no result yet.
+pub fn verify(state: &ConnectorState, task_id: &str) -> Option<ChainReport> {
+    let root = state.task_results.get(task_id)?.merkle_hash.clone();
+    let mut nodes = Vec::new();
+    check(state, task_id, None, &mut HashSet::new(), &mut nodes);
+    nodes.reverse();
+    Some(ChainReport {
+        task_id: task_id.to_string(),
+        valid: nodes.iter().all(|n| n.ok),
+        root,
+        nodes,
+    })
+}
+
+/// Publish the root of a verified chain in the DHT.
+pub async fn anchor(
+    network_handle: &wws_network::SwarmHandle,
+    anchor: &MerkleAnchor,
+) -> bool {
+    let Ok(value) = serde_json::to_vec(anchor) else {
+        return false;
+    };
+    match network_handle
+        .put_dht_record(MerkleAnchor::dht_key(&anchor.task_id), value)
+        .await
+    {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::debug!(error = %e, task_id = %anchor.task_id, "Failed to anchor Merkle root");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connector::tests::test_state;
+    use wws_protocol::Task;
+
+    fn artifact(task_id: &str, artifact_id: &str, cid: &str) -> Artifact {
+        serde_json::from_value(serde_json::json!({
+            "artifact_id": artifact_id,
+            "task_id": task_id,
+            "producer": "did:swarm:exec",
+            "content_cid": cid,
+            "merkle_hash": "",
+            "content_type": "text/plain",
+            "size_bytes": 0,
+            "created_at": Utc::now(),
+        }))
+        .unwrap()
+    }
+
+    fn submit(state: &mut ConnectorState, mut artifact: Artifact) {
+        record(state, &mut artifact);
+        state.task_results.insert(artifact.task_id.clone(), artifact);
+    }
+
+    #[test]
+    fn aggregated_results_chain_up_to_the_root() {
+        let mut state = test_state();
+        let mut root = Task::new("root".to_string(), 1, 1);
+        root.task_id = "root".to_string();
+        root.subtasks = vec!["a".to_string(), "b".to_string()];
+        state.task_details.insert("root".to_string(), root);
+        for id in ["a", "b"] {
+            let mut sub = Task::new(id.to_string(), 2, 1);
+            sub.task_id = id.to_string();
+            sub.parent_task_id = Some("root".to_string());
+            state.task_details.insert(id.to_string(), sub);
+        }
+
+        submit(&mut state, artifact("a", "art-a", "cid-a"));
+        submit(&mut state, artifact("b", "art-b", "cid-b"));
+        let aggregated = crate::aggregation::aggregated_artifact_id("root");
+        submit(&mut state, artifact("root", &aggregated, "cid-root"));
+
+        let leaves = [
+            MerkleDag::leaf_hash(b"cid-a"),
+            MerkleDag::leaf_hash(b"cid-b"),
+        ];
+        let root_hash = MerkleDag::branch_hash(&leaves);
+        assert_eq!(state.task_results["root"].merkle_hash, root_hash);
+        assert_eq!(state.merkle_dag.get_node(&root_hash).unwrap().children, leaves);
+
+        let report = verify(&state, "root").unwrap();
+        assert!(report.valid);
+        assert_eq!(report.root, root_hash);
+        assert_eq!(report.nodes.len(), 3);
+        assert!(report.nodes.iter().all(|n| n.in_dag));
+
+        // A subtask result swapped after aggregation breaks its leaf.
+        state.task_results.get_mut("b").unwrap().content_cid = "cid-forged".to_string();
+        let report = verify(&state, "root").unwrap();
+        assert!(!report.valid);
+        let broken: Vec<&str> = report
+            .nodes
+            .iter()
+            .filter(|n| !n.ok)
+            .map(|n| n.task_id.as_str())
+            .collect();
+        assert_eq!(broken, ["b"]);
+
+        assert!(verify(&state, "missing").is_none());
+    }
+
+    #[test]
+    fn cyclic_subtasks_are_reported_not_followed() {
+        let mut state = test_state();
+        for (id, sub) in [("a", "b"), ("b", "a")] {
+            let mut task = Task::new(id.to_string(), 1, 1);
+            task.task_id = id.to_string();
+            task.subtasks = vec![sub.to_string()];
+            state.task_details.insert(id.to_string(), task);
+            let aggregated = crate::aggregation::aggregated_artifact_id(id);
+            let mut artifact = artifact(id, &aggregated, id);
+            artifact.merkle_hash = format!("hash-{}", id);
+            state.task_results.insert(id.to_string(), artifact);
+        }
+
+        let report = verify(&state, "a").unwrap();
+        assert!(!report.valid);
+        let ids: Vec<&str> = report.nodes.iter().map(|n| n.task_id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "a"]);
+        assert!(!report.nodes[2].ok);
+    }
+}
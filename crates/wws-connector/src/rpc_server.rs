@@ -4,22 +4,71 @@
 //! - `swarm.connect()` - Connect to a peer by multiaddress
 //! - `swarm.get_network_stats()` - Get current network statistics
 //! - `swarm.propose_plan()` - Submit a task decomposition plan
+//! - `swarm.revise_plan()` - Replace your plan once after the critique round
+//! - `swarm.withdraw_plan()` - Retract your plan before voting closes
 //! - `swarm.submit_result()` - Submit a task execution result
 //! - `swarm.receive_task()` - Poll for assigned tasks
 //! - `swarm.get_task()` - Get full details for a task by ID
+//! - `swarm.search_tasks()` - Find tasks by status, assignee, tier, parent, text and creation time
+//! - `swarm.get_attachment()` - Fetch the content of a task attachment by CID
+//! - `swarm.get_result()` - Fetch a task's result, redacted when it is sealed to other members
+//! - `swarm.get_task_context()` - Assemble a task's description, ancestors, plan rationale, sibling results and attachments within a byte budget
 //! - `swarm.get_task_timeline()` - Get lifecycle timeline for a task
+//! - `swarm.get_task_costs()` - Get budget and spend totals for a task tree
+//! - `swarm.get_flow_stats()` - Get task-flow stage counters and recent rates
+//! - `swarm.get_sla_stats()` - Get per-agent on-time rate and lateness of results
+//! - `swarm.get_dead_letters()` - Get recent undecodable or rejected inbound messages
+//! - `swarm.get_logs()` - Get the event log filtered by category, text and time, or follow it
+//! - `swarm.get_peer_scores()` - Get offense counts, scores and bans per peer
+//! - `swarm.health()` - Run self-diagnostics and report healthy, degraded or isolated
+//! - `swarm.reload_config()` - Re-read the configuration, applying what can change live
+//! - `swarm.get_partition_report()` - Get detected network partitions, swarm merges and the conflicts reconciled after them
+//! - `swarm.ban_peer()` - Disconnect a peer and ban it for a while
+//! - `swarm.unban_peer()` - Lift a peer's ban
+//! - `swarm.init_swarm_params()` - Issue the first swarm parameter record, as its authority
+//! - `swarm.get_swarm_params()` - Get the swarm parameters in force and open change proposals
+//! - `swarm.propose_params_change()` - Propose a change to the swarm parameters
+//! - `swarm.vote_params_change()` - Approve or reject a swarm parameter change
+//! - `swarm.form_board()` - Invite agents to a board for a task, chaired by this agent
+//! - `swarm.answer_board_invite()` - Accept or decline a board invitation
 //! - `swarm.get_status()` - Get connector and agent status
-//! - `swarm.register_agent()` - Register an execution agent identity
+//! - `swarm.register_agent()` - Register an execution agent identity and the services it offers
+//! - `swarm.get_agent_endpoints()` - Get the service endpoints another agent offers
+//! - `swarm.get_reputation()` - Get an agent's reputation and the signed attestations behind it
+//! - `swarm.register_local_agent()` - Add a local agent with its own DID (multi-agent mode)
+//! - `swarm.list_local_agents()` - List local agents sharing this connector
+//! - `swarm.list_capabilities()` - List the capability namespaces, aliases and holders, or score agents against requirements
 //! - `swarm.list_swarms()` - List all known swarms with their info
 //! - `swarm.create_swarm()` - Create a new private swarm
-//! - `swarm.join_swarm()` - Join an existing swarm
+//! - `swarm.join_swarm()` - Join an existing swarm alongside those already joined
+//! - `swarm.leave_swarm()` - Leave a joined swarm other than the primary one
+//! - `swarm.migrate_swarm()` - Move every member of this swarm into another (or report with `dry_run`)
+//! - `swarm.get_shard_status()` - Get the sharding policy, federation links and recent splits of this swarm
+//! - `swarm.delegate_task()` - Inject a task into a federated swarm, through a gateway when not a member
+//! - `swarm.delegate_subtask()` - Run a subtask in another swarm through a bridge and relay its result back
 //! - `swarm.register_name()` - Register a wws:// name for this agent
 //! - `swarm.resolve_name()` - Resolve a wws:// name to DID + peer_id
+//! - `swarm.resolve_did()` - Resolve a DID to its verified DID document via the DHT
 //! - `swarm.renew_name()` - Renew an existing name registration (extend TTL)
 //! - `swarm.my_names()` - List all names registered by this agent
+//! - `swarm.vote_name_dispute()` - As a name guardian, award a disputed name to one claimant
+//! - `swarm.approve_plan()` - Approve or reject a winning plan held for sign-off
+//! - `swarm.reassign_task()` - Hand an open subtask to another agent
+//! - `swarm.export_run()` - Export a task tree and its decisions as a JSON transcript
+//! - `swarm.import_run()` - Load an exported transcript for offline inspection
+//! - `swarm.save_task_template()` - Save a named task template
+//! - `swarm.inject_from_template()` - Inject a task from a named template
+//! - `swarm.schedule_task()` - Inject a task on a recurring cron schedule
+//! - `swarm.list_schedules()` - List schedules with last/next run times
+//! - `swarm.cancel_schedule()` - Cancel a recurring schedule
+//! - `swarm.vote_dispute()` - Choose between diverging results of a disputed task
+//! - `swarm.get_verification()` - Get the re-execution check or dispute for a task
 //!
 //! The server listens on localhost TCP and speaks JSON-RPC 2.0.
 //! Each line received is a JSON-RPC request; each line sent is a response.
+//! The one exception is `swarm.get_logs` with `follow: true`: after its
+//! response the connection only streams `swarm.log` notifications, one per
+//! new matching log entry, until the client closes it.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -30,11 +79,30 @@ use tokio::net::TcpListener;
 use tokio::sync::RwLock;
 
 use wws_protocol::*;
+use wws_protocol::board_channel::ChannelKeypair;
 
-use crate::connector::{ConnectorState, SwarmRecord, TaskTimelineEvent, TaskVoteRequirement};
+use crate::auth::{is_read_only, permits, RpcAuth, RpcClientConfig};
+use crate::bonds::SlashReason;
+use crate::delivery::MAX_RECEIVE_WAIT_MS;
+use crate::connector::{
+    ConnectorState, DeadLetter, SwarmRecord, TaskTimelineEvent, MAX_INLINE_ATTACHMENT_BYTES,
+};
+use crate::task_index::{TaskCursor, TaskQuery, DEFAULT_SEARCH_LIMIT, MAX_SEARCH_LIMIT};
+use crate::tui::{LogCategory, LogEntry};
 
 const ACTIVE_MEMBER_STALENESS_SECS: u64 = 45;
+/// How often a `swarm.receive_task` long poll checks for new tasks.
+const RECEIVE_WAIT_INTERVAL: Duration = Duration::from_millis(200);
 const PARTICIPATION_POLL_STALENESS_SECS: u64 = 180;
+const DEFAULT_FLOW_WINDOW_SECS: u64 = 300;
+const MAX_FLOW_WINDOW_SECS: u64 = 86_400;
+const DEFAULT_PEER_BAN_SECS: u64 = 3600;
+/// How long `swarm.resolve_did` waits for a DHT lookup.
+const DID_RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How often a followed event log is checked for new entries.
+const LOG_FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+/// Content type of the CID listing built by the `Concatenate` strategy.
+const AGGREGATED_CONTENT_TYPE: &str = "application/json; aggregated";
 
 /// The JSON-RPC 2.0 server.
 pub struct RpcServer {
@@ -46,6 +114,8 @@ pub struct RpcServer {
     network_handle: wws_network::SwarmHandle,
     /// Maximum concurrent connections.
     max_connections: usize,
+    /// Clients allowed to call, when any are configured.
+    auth: Arc<RpcAuth>,
 }
 
 impl RpcServer {
@@ -61,9 +131,16 @@ impl RpcServer {
             state,
             network_handle,
             max_connections,
+            auth: Arc::new(RpcAuth::default()),
         }
     }
 
+    /// Require requests to authenticate as one of `clients`.
+    pub fn with_clients(mut self, clients: Vec<RpcClientConfig>) -> Self {
+        self.auth = Arc::new(RpcAuth::new(clients));
+        self
+    }
+
     /// Start the RPC server, listening for connections.
     pub async fn run(self) -> Result<(), anyhow::Error> {
         let listener = TcpListener::bind(&self.bind_addr).await?;
@@ -71,6 +148,9 @@ impl RpcServer {
 
         let state = Arc::clone(&self.state);
         let network_handle = self.network_handle.clone();
+        if self.auth.is_enabled() {
+            tracing::info!("RPC clients must authenticate");
+        }
         let semaphore = Arc::new(tokio::sync::Semaphore::new(self.max_connections));
 
         loop {
@@ -79,11 +159,12 @@ impl RpcServer {
 
             let state = Arc::clone(&state);
             let network_handle = network_handle.clone();
+            let auth = Arc::clone(&self.auth);
             let permit = semaphore.clone().acquire_owned().await?;
 
             tokio::spawn(async move {
                 if let Err(e) =
-                    handle_connection(stream, state, network_handle).await
+                    handle_connection(stream, peer_addr, state, network_handle, auth).await
                 {
                     tracing::warn!(
                         peer = %peer_addr,
@@ -102,25 +183,187 @@ impl RpcServer {
 /// Reads newline-delimited JSON-RPC requests and sends back responses.
 async fn handle_connection(
     stream: tokio::net::TcpStream,
+    peer_addr: std::net::SocketAddr,
     state: Arc<RwLock<ConnectorState>>,
     network_handle: wws_network::SwarmHandle,
+    auth: Arc<RpcAuth>,
 ) -> Result<(), anyhow::Error> {
     let (reader, mut writer) = stream.into_split();
     let mut lines = BufReader::new(reader).lines();
 
     while let Some(line) = lines.next_line().await? {
-        let response = process_request(&line, &state, &network_handle).await;
+        let response = match authorize(&line, &auth, &state, peer_addr).await {
+            Ok(Some(rewritten)) => process_request(&rewritten, &state, &network_handle).await,
+            Ok(None) => process_request(&line, &state, &network_handle).await,
+            Err(denied) => denied,
+        };
         let response_json = serde_json::to_string(&response)?;
         writer.write_all(response_json.as_bytes()).await?;
         writer.write_all(b"\n").await?;
         writer.flush().await?;
+
+        if let Some((query, cursor)) = log_follow_request(&line, &response) {
+            return follow_logs(lines, writer, &state, query, cursor).await;
+        }
     }
 
     Ok(())
 }
 
+/// Check the client behind a request line when clients are configured:
+/// authenticate its `auth` token, check its role allows the method and,
+/// for a client bound to a local agent, make the request act for that
+/// agent. Returns the request rewritten for the binding, if it was, or the
+/// error response. Denials and calls that change state are audited.
+pub(crate) async fn authorize(
+    line: &str,
+    auth: &RpcAuth,
+    state: &Arc<RwLock<ConnectorState>>,
+    peer_addr: std::net::SocketAddr,
+) -> Result<Option<String>, SwarmResponse> {
+    if !auth.is_enabled() {
+        return Ok(None);
+    }
+    // Unparseable requests fall through to the usual parse error.
+    let Ok(mut request) = serde_json::from_str::<serde_json::Value>(line) else {
+        return Ok(None);
+    };
+    let id = request.get("id").and_then(|v| v.as_str()).map(str::to_string);
+    let method = request
+        .get("method")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let audit = |state: &mut ConnectorState, client: &str, outcome: &str| {
+        state.push_log(
+            LogCategory::System,
+            format!(
+                "AUDIT rpc.call client={} method={} peer={} outcome={}",
+                client, method, peer_addr, outcome
+            ),
+        );
+    };
+    let Some(client) = auth.authenticate(request.get("auth").and_then(|v| v.as_str())) else {
+        audit(&mut *state.write().await, "-", "unauthenticated");
+        return Err(SwarmResponse::error(
+            id,
+            ErrorCode::Unauthenticated,
+            "Missing or invalid 'auth' token".to_string(),
+        ));
+    };
+    if !permits(client.role, &method) {
+        audit(&mut *state.write().await, &client.name, "forbidden");
+        return Err(SwarmResponse::error(
+            id,
+            ErrorCode::Forbidden,
+            format!("Client '{}' ({}) may not call {}", client.name, client.role, method),
+        ));
+    }
+
+    let mut rewritten = false;
+    if let Some(bound) = &client.local_agent {
+        let mut state = state.write().await;
+        let params = &mut request["params"];
+        let allowed = if method == "swarm.register_local_agent" {
+            params.get("name").and_then(|v| v.as_str()) == Some(bound.as_str())
+        } else {
+            let did = state
+                .local_agents
+                .agents()
+                .find(|agent| &agent.name == bound)
+                .map(|agent| agent.agent_id.to_string());
+            match (params.get("local_agent").and_then(|v| v.as_str()), did) {
+                (Some(requested), Some(did)) => requested == did,
+                (None, Some(did)) if params.is_object() || params.is_null() => {
+                    if params.is_null() {
+                        *params = serde_json::json!({});
+                    }
+                    params["local_agent"] = serde_json::json!(did);
+                    rewritten = true;
+                    true
+                }
+                _ => false,
+            }
+        };
+        if !allowed {
+            audit(&mut state, &client.name, "forbidden");
+            return Err(SwarmResponse::error(
+                id,
+                ErrorCode::Forbidden,
+                format!("Client '{}' may only act as local agent '{}'", client.name, bound),
+            ));
+        }
+    }
+    if !is_read_only(&method) {
+        audit(&mut *state.write().await, &client.name, "allowed");
+    }
+    Ok(rewritten.then(|| request.to_string()))
+}
+
+/// The filter and starting cursor when `line` was a successful
+/// `swarm.get_logs` request with `follow: true`.
+fn log_follow_request(line: &str, response: &SwarmResponse) -> Option<(LogQuery, u64)> {
+    let request: SwarmMessage = serde_json::from_str(line).ok()?;
+    if request.method != "swarm.get_logs"
+        || request.params.get("follow").and_then(|v| v.as_bool()) != Some(true)
+    {
+        return None;
+    }
+    let cursor = response.result.as_ref()?.get("cursor")?.as_u64()?;
+    Some((LogQuery::from_params(&request.params).ok()?, cursor))
+}
+
+/// Stream event log entries pushed after `cursor` that match `query` as
+/// `swarm.log` notifications, until the client closes the connection.
+async fn follow_logs(
+    mut lines: tokio::io::Lines<BufReader<tokio::net::tcp::OwnedReadHalf>>,
+    mut writer: tokio::net::tcp::OwnedWriteHalf,
+    state: &Arc<RwLock<ConnectorState>>,
+    query: LogQuery,
+    mut cursor: u64,
+) -> Result<(), anyhow::Error> {
+    let mut interval = tokio::time::interval(LOG_FOLLOW_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            line = lines.next_line() => match line {
+                // The stream is one-way; anything sent is ignored.
+                Ok(Some(_)) => continue,
+                _ => return Ok(()),
+            },
+        }
+        let entries = {
+            let state = state.read().await;
+            let entries = entries_since(&state.event_log, state.log_count, cursor)
+                .iter()
+                .filter(|e| query.matches(e))
+                .cloned()
+                .collect::<Vec<_>>();
+            cursor = state.log_count;
+            entries
+        };
+        for entry in entries {
+            let notification = serde_json::json!({
+                "jsonrpc": JSONRPC_VERSION,
+                "method": "swarm.log",
+                "params": entry,
+            });
+            writer.write_all(notification.to_string().as_bytes()).await?;
+            writer.write_all(b"\n").await?;
+        }
+        writer.flush().await?;
+    }
+}
+
+/// The entries of `log` pushed after the first `cursor` of `log_count`.
+fn entries_since(log: &[LogEntry], log_count: u64, cursor: u64) -> &[LogEntry] {
+    let new = log_count.saturating_sub(cursor).min(log.len() as u64) as usize;
+    &log[log.len() - new..]
+}
+
 /// Process a single JSON-RPC request and return a response.
-async fn process_request(
+pub(crate) async fn process_request(
     request_str: &str,
     state: &Arc<RwLock<ConnectorState>>,
     network_handle: &wws_network::SwarmHandle,
@@ -131,7 +374,7 @@ async fn process_request(
         Err(e) => {
             return SwarmResponse::error(
                 None,
-                -32700, // Parse error
+                ErrorCode::ParseError,
                 format!("Invalid JSON: {}", e),
             );
         }
@@ -141,10 +384,18 @@ async fn process_request(
 
     match request.method.as_str() {
         "swarm.connect" => handle_connect(request_id, &request.params, network_handle).await,
-        "swarm.get_network_stats" => handle_get_network_stats(request_id, state).await,
+        "swarm.get_network_stats" => {
+            handle_get_network_stats(request_id, state, network_handle).await
+        }
         "swarm.propose_plan" => {
             handle_propose_plan(request_id, &request.params, state, network_handle).await
         }
+        "swarm.revise_plan" => {
+            handle_revise_plan(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.withdraw_plan" => {
+            handle_withdraw_plan(request_id, &request.params, state, network_handle).await
+        }
         "swarm.submit_vote" => {
             handle_submit_vote(request_id, &request.params, state, network_handle).await
         }
@@ -155,27 +406,103 @@ async fn process_request(
         "swarm.submit_result" => {
             handle_submit_result(request_id, &request.params, state, network_handle).await
         }
-        "swarm.receive_task" => handle_receive_task(request_id, state).await,
+        "swarm.report_progress" => {
+            handle_report_progress(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.receive_task" => {
+            handle_receive_task(request_id, &request.params, state).await
+        }
+        "swarm.ack_task" => handle_ack_task(request_id, &request.params, state).await,
         "swarm.get_task" => handle_get_task(request_id, &request.params, state).await,
+        "swarm.search_tasks" => {
+            handle_search_tasks(request_id, &request.params, state).await
+        }
+        "swarm.get_attachment" => handle_get_attachment(request_id, &request.params, state).await,
+        "swarm.get_task_context" => handle_get_task_context(request_id, &request.params, state).await,
+        "swarm.get_result" => handle_get_result(request_id, &request.params, state).await,
         "swarm.get_task_timeline" => {
             handle_get_task_timeline(request_id, &request.params, state).await
         }
+        "swarm.get_logs" => handle_get_logs(request_id, &request.params, state).await,
+        "swarm.get_task_costs" => {
+            handle_get_task_costs(request_id, &request.params, state).await
+        }
+        "swarm.get_flow_stats" => {
+            handle_get_flow_stats(request_id, &request.params, state).await
+        }
+        "swarm.get_sla_stats" => {
+            handle_get_sla_stats(request_id, &request.params, state).await
+        }
+        "swarm.get_dead_letters" => {
+            handle_get_dead_letters(request_id, &request.params, state).await
+        }
+        "swarm.get_peer_scores" => handle_get_peer_scores(request_id, network_handle).await,
+        "swarm.health" => handle_health(request_id, state, network_handle).await,
+        "swarm.describe_errors" => handle_describe_errors(request_id),
+        "swarm.reload_config" => handle_reload_config(request_id, state).await,
+        "swarm.get_partition_report" => handle_get_partition_report(request_id, state).await,
+        "swarm.ban_peer" => {
+            handle_ban_peer(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.unban_peer" => {
+            handle_unban_peer(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.init_swarm_params" => {
+            handle_init_swarm_params(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.get_swarm_params" => handle_get_swarm_params(request_id, state).await,
+        "swarm.propose_params_change" => {
+            handle_propose_params_change(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.vote_params_change" => {
+            handle_vote_params_change(request_id, &request.params, state, network_handle).await
+        }
         "swarm.get_status" => handle_get_status(request_id, state).await,
         "swarm.register_agent" => {
             handle_register_agent(request_id, &request.params, state, network_handle).await
         }
+        "swarm.register_local_agent" => {
+            handle_register_local_agent(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.list_local_agents" => handle_list_local_agents(request_id, state).await,
+        "swarm.get_agent_endpoints" => {
+            handle_get_agent_endpoints(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.get_reputation" => handle_get_reputation(request_id, &request.params, state).await,
+        "swarm.list_capabilities" => {
+            handle_list_capabilities(request_id, &request.params, state).await
+        }
         "swarm.list_swarms" => handle_list_swarms(request_id, state).await,
         "swarm.create_swarm" => {
-            handle_create_swarm(request_id, &request.params, state).await
+            handle_create_swarm(request_id, &request.params, state, network_handle).await
         }
         "swarm.join_swarm" => {
-            handle_join_swarm(request_id, &request.params, state).await
+            handle_join_swarm(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.leave_swarm" => {
+            handle_leave_swarm(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.migrate_swarm" => {
+            handle_migrate_swarm(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.get_shard_status" => handle_get_shard_status(request_id, state).await,
+        "swarm.delegate_task" => {
+            handle_delegate_task(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.delegate_subtask" => {
+            handle_delegate_subtask(request_id, &request.params, state, network_handle).await
         }
         "swarm.inject_task" => {
             handle_inject_task(request_id, &request.params, state, network_handle).await
         }
         "swarm.get_hierarchy" => handle_get_hierarchy(request_id, state).await,
         "swarm.get_board_status" => handle_get_board_status(request_id, state).await,
+        "swarm.form_board" => {
+            handle_form_board(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.answer_board_invite" => {
+            handle_answer_board_invite(request_id, &request.params, state, network_handle).await
+        }
         "swarm.get_deliberation" => {
             handle_get_deliberation(request_id, &request.params, state).await
         }
@@ -186,13 +513,19 @@ async fn process_request(
             handle_get_irv_rounds(request_id, &request.params, state).await
         }
         "swarm.register_name" => {
-            handle_register_name(request_id, &request.params, state).await
+            handle_register_name(request_id, &request.params, state, network_handle).await
         }
         "swarm.resolve_name" => {
             handle_resolve_name(request_id, &request.params, state).await
         }
+        "swarm.resolve_did" => {
+            handle_resolve_did(request_id, &request.params, state, network_handle).await
+        }
         "swarm.renew_name" => {
-            handle_renew_name(request_id, &request.params, state).await
+            handle_renew_name(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.vote_name_dispute" => {
+            handle_vote_name_dispute(request_id, &request.params, state, network_handle).await
         }
         "swarm.my_names" => handle_my_names(request_id, state).await,
         "swarm.verify_agent" => {
@@ -201,15 +534,48 @@ async fn process_request(
         "swarm.send_message" => {
             handle_send_message(request_id, &request.params, state, network_handle).await
         }
+        "swarm.approve_plan" => {
+            handle_approve_plan(request_id, &request.params, state).await
+        }
+        "swarm.reassign_task" => {
+            handle_reassign_task(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.export_run" => handle_export_run(request_id, &request.params, state).await,
+        "swarm.import_run" => handle_import_run(request_id, &request.params, state).await,
+        "swarm.save_task_template" => {
+            handle_save_task_template(request_id, &request.params, state).await
+        }
+        "swarm.inject_from_template" => {
+            handle_inject_from_template(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.schedule_task" => {
+            handle_schedule_task(request_id, &request.params, state).await
+        }
+        "swarm.list_schedules" => handle_list_schedules(request_id, state).await,
+        "swarm.cancel_schedule" => {
+            handle_cancel_schedule(request_id, &request.params, state).await
+        }
+        "swarm.vote_dispute" => {
+            handle_vote_dispute(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.get_verification" => {
+            handle_get_verification(request_id, &request.params, state).await
+        }
+        "swarm.verify_task" => {
+            handle_verify_task(request_id, &request.params, state, network_handle).await
+        }
         _ => SwarmResponse::error(
             request_id,
-            -32601, // Method not found
+            ErrorCode::MethodNotFound,
             format!("Unknown method: {}", request.method),
         ),
     }
 }
 
 /// Handle `swarm.submit_vote` - submit a ranked vote for a task.
+///
+/// A non-blind ballot is counted at once; the response carries the signed
+/// receipt, which is also published as `consensus.ballot_receipt`.
 async fn handle_submit_vote(
     id: Option<String>,
     params: &serde_json::Value,
@@ -219,36 +585,51 @@ async fn handle_submit_vote(
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(v) if !v.is_empty() => v.to_string(),
         _ => {
-            return SwarmResponse::error(id, -32602, "Missing 'task_id' parameter".to_string());
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
         }
     };
 
-    // Accept either "rankings" or "ranked_plan_ids" as parameter name
+    // An abstaining voter counts towards quorum without ranking any plan.
+    let abstain = params.get("abstain").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    // Accept either "rankings" or "ranked_plan_ids" as parameter name.
+    // Rankings may be partial.
     let rankings: Vec<String> = match params.get("rankings").or_else(|| params.get("ranked_plan_ids")).and_then(|v| v.as_array()) {
         Some(arr) if !arr.is_empty() => arr
             .iter()
             .filter_map(|v| v.as_str().map(|s| s.to_string()))
             .collect(),
+        _ if abstain => Vec::new(),
         _ => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing or empty 'rankings' or 'ranked_plan_ids' parameter".to_string(),
             );
         }
     };
 
+    // A round keeps the epoch it started in across epoch transitions.
     let epoch = {
         let state = state.read().await;
         params
             .get("epoch")
             .and_then(|v| v.as_u64())
+            .or_else(|| state.voting_engines.get(&task_id).map(|v| v.epoch()))
             .unwrap_or_else(|| state.epoch_manager.current_epoch())
     };
 
-    let (voter, swarm_id, ballot_count, proposal_count, accepted_rankings) = {
+    let (voter, selection, swarm_id, ballot_count, proposal_count, accepted_rankings, blind_commitment, receipt) = {
         let mut state = state.write().await;
-        let voter = state.agent_id.clone();
+        let voter = match acting_agent(params, &state) {
+            Ok(agent_id) => agent_id,
+            Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+        };
+        // In a tier that draws a committee, only its members vote.
+        let selection = match state.committee_proof(&task_id, voter.as_str()) {
+            Ok(selection) => selection,
+            Err(message) => return SwarmResponse::error(id, ErrorCode::NotPermitted, message),
+        };
 
         let proposals: std::collections::HashMap<String, AgentId> = rankings
             .iter()
@@ -260,10 +641,11 @@ async fn handle_submit_vote(
             })
             .collect();
 
-        let (ballot_count, proposal_count, accepted_rankings) = {
+        let (ballot_count, proposal_count, accepted_rankings, blind_ballot) = {
+            let voting_config = state.voting_config.clone();
             let voting = state.voting_engines.entry(task_id.clone()).or_insert_with(|| {
                 let engine = wws_consensus::VotingEngine::new(
-                    wws_consensus::voting::VotingConfig::default(),
+                    voting_config,
                     task_id.clone(),
                     epoch,
                 );
@@ -273,17 +655,25 @@ async fn handle_submit_vote(
 
             let mut accepted_rankings = rankings.clone();
             let mut attempts_left = accepted_rankings.len().max(1);
-            loop {
+            let blind = voting.is_blind();
+            let ranked_vote = loop {
                 let ranked_vote = RankedVote {
                     voter: voter.clone(),
                     task_id: task_id.clone(),
                     epoch,
                     rankings: accepted_rankings.clone(),
                     critic_scores: std::collections::HashMap::new(),
+                    abstain,
                 };
 
-                match voting.record_vote(ranked_vote) {
-                    Ok(()) => break,
+                // A blind ballot is checked now and only counted once revealed.
+                let recorded = if blind {
+                    voting.validate_vote(&ranked_vote).map(|_| ())
+                } else {
+                    voting.record_vote(ranked_vote.clone())
+                };
+                match recorded {
+                    Ok(()) => break ranked_vote,
                     Err(wws_consensus::ConsensusError::SelfVoteProhibited(_))
                         if accepted_rankings.len() > 1 && attempts_left > 1 =>
                     {
@@ -293,26 +683,78 @@ async fn handle_submit_vote(
                     Err(e) => {
                         return SwarmResponse::error(
                             id,
-                            -32000,
+                            ErrorCode::OperationFailed,
                             format!("Failed to record vote: {}", e),
                         );
                     }
                 }
-            }
+            };
+
+            let blind_ballot = if blind {
+                let salt: String = rand::random::<[u8; 16]>()
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                let commitment = wws_consensus::voting::ballot_commitment(&ranked_vote, &salt);
+                if let Err(e) = voting.record_commitment(voter.clone(), commitment.clone()) {
+                    return SwarmResponse::error(
+                        id,
+                        ErrorCode::OperationFailed,
+                        format!("Failed to record vote: {}", e),
+                    );
+                }
+                Some((ranked_vote, salt, commitment))
+            } else {
+                None
+            };
 
             (
                 voting.ballot_count(),
                 voting.proposal_count(),
                 accepted_rankings,
+                blind_ballot,
             )
         };
 
-        state.push_task_timeline_event(
-            &task_id,
-            "vote_recorded",
-            format!("Vote submitted via RPC: {}", accepted_rankings.join(" > ")),
-            Some(voter.to_string()),
-        );
+        let ballot_preview = if abstain {
+            "abstained".to_string()
+        } else {
+            accepted_rankings.join(" > ")
+        };
+
+        // The ballot itself is published once the commitments close.
+        let blind_commitment = blind_ballot.map(|(vote, salt, commitment)| {
+            state
+                .pending_vote_reveals
+                .entry(task_id.clone())
+                .or_default()
+                .push(ConsensusVoteRevealParams {
+                    task_id: vote.task_id,
+                    epoch: vote.epoch,
+                    voter: vote.voter,
+                    rankings: vote.rankings,
+                    critic_scores: vote.critic_scores,
+                    abstain: vote.abstain,
+                    salt,
+                });
+            commitment
+        });
+
+        if blind_commitment.is_some() {
+            state.push_task_timeline_event(
+                &task_id,
+                "vote_committed",
+                "Blind ballot committed via RPC".to_string(),
+                Some(voter.to_string()),
+            );
+        } else {
+            state.push_task_timeline_event(
+                &task_id,
+                "vote_recorded",
+                format!("Vote submitted via RPC: {}", ballot_preview),
+                Some(voter.to_string()),
+            );
+        }
         state.bump_votes_cast(voter.as_str());
         state.push_log(
             crate::tui::LogCategory::Vote,
@@ -320,35 +762,72 @@ async fn handle_submit_vote(
                     "Vote submitted for task {} by {} ({})",
                     task_id,
                     voter,
-                    accepted_rankings.join(" > ")
+                    ballot_preview
                 ),
         );
+        // A blind ballot gets its receipt once revealed.
+        let receipt = if blind_commitment.is_none() {
+            state.issue_ballot_receipt(&task_id, &voter, epoch)
+        } else {
+            None
+        };
 
         (
             voter,
-            state.current_swarm_id.as_str().to_string(),
+            selection,
+            state.swarm_for_task(&task_id),
             ballot_count,
             proposal_count,
             accepted_rankings,
+            blind_commitment,
+            receipt,
         )
     };
 
-    let vote_msg = SwarmMessage::new(
-        ProtocolMethod::ConsensusVote.as_str(),
-        serde_json::json!({
-            "task_id": task_id,
-            "voter": voter,
-            "epoch": epoch,
-            "rankings": accepted_rankings,
-            "critic_scores": {},
-        }),
-        String::new(),
-    );
+    let vote_msg = match &blind_commitment {
+        Some(commitment) => state.read().await.message_as(
+            &voter,
+            ProtocolMethod::ConsensusVoteCommit.as_str(),
+            serde_json::json!({
+                "task_id": task_id,
+                "voter": voter,
+                "epoch": epoch,
+                "commitment": commitment,
+            }),
+        ),
+        None => {
+            let mut vote = serde_json::json!({
+                "task_id": task_id,
+                "voter": voter,
+                "epoch": epoch,
+                "rankings": accepted_rankings,
+                "critic_scores": {},
+                "abstain": abstain,
+            });
+            if let Some(selection) = &selection {
+                vote["selection"] = serde_json::to_value(selection).unwrap_or_default();
+            }
+            state
+                .read()
+                .await
+                .message_as(&voter, ProtocolMethod::ConsensusVote.as_str(), vote)
+        }
+    };
 
+    let topic = SwarmTopics::voting_for(&swarm_id, &task_id);
     if let Ok(data) = serde_json::to_vec(&vote_msg) {
-        let topic = SwarmTopics::voting_for(&swarm_id, &task_id);
         let _ = network_handle.publish(&topic, data).await;
     }
+    if let Some(receipt) = &receipt {
+        let receipt_msg = SwarmMessage::new(
+            ProtocolMethod::BallotReceipt.as_str(),
+            serde_json::to_value(receipt).unwrap_or_default(),
+            String::new(),
+        );
+        if let Ok(data) = serde_json::to_vec(&receipt_msg) {
+            let _ = network_handle.publish(&topic, data).await;
+        }
+    }
 
     SwarmResponse::success(
         id,
@@ -357,6 +836,9 @@ async fn handle_submit_vote(
             "accepted": true,
             "ballot_count": ballot_count,
             "proposal_count": proposal_count,
+            "abstain": abstain,
+            "commitment": blind_commitment,
+            "receipt": receipt,
         }),
     )
 }
@@ -365,7 +847,8 @@ async fn handle_submit_vote(
 ///
 /// Agent calls this after voting to score each proposal on feasibility/parallelism/completeness/risk.
 /// Connector records the critique, broadcasts a `discussion.critique` P2P message, and
-/// updates the holon status to Voting.
+/// updates the holon status to Voting. Plans listed in `flagged_malicious` lose their
+/// proposers' bonds when the caller is the board's adversarial critic.
 async fn handle_submit_critique(
     id: Option<String>,
     params: &serde_json::Value,
@@ -375,7 +858,7 @@ async fn handle_submit_critique(
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(v) if !v.is_empty() => v.to_string(),
         _ => {
-            return SwarmResponse::error(id, -32602, "Missing 'task_id' parameter".to_string());
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
         }
     };
 
@@ -398,12 +881,26 @@ async fn handle_submit_critique(
         None => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing or invalid 'plan_scores' parameter".to_string(),
             );
         }
     };
 
+    let flagged_malicious: Vec<String> = match params.get("flagged_malicious") {
+        None => Vec::new(),
+        Some(v) => match serde_json::from_value(v.clone()) {
+            Ok(plan_ids) => plan_ids,
+            Err(_) => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'flagged_malicious' must be a list of plan IDs".to_string(),
+                );
+            }
+        },
+    };
+
     let (voter, swarm_id) = {
         let mut state = state.write().await;
         let voter = state.agent_id.clone();
@@ -413,6 +910,7 @@ async fn handle_submit_critique(
             let _ = rfp.transition_to_critique();
             let _ = rfp.record_critique(voter.clone(), plan_scores.clone(), content.clone());
         }
+        state.apply_malicious_flags(&task_id, voter.as_str(), &flagged_malicious);
 
         // Store as a CritiqueFeedback DeliberationMessage
         let msg = DeliberationMessage {
@@ -425,6 +923,7 @@ async fn handle_submit_critique(
             content: content.clone(),
             referenced_plan_id: None,
             critic_scores: Some(plan_scores.clone()),
+            hlc: Some(hlc::global().now()),
         };
         state
             .deliberation_messages
@@ -460,7 +959,7 @@ async fn handle_submit_critique(
             ),
         );
 
-        (voter, state.current_swarm_id.as_str().to_string())
+        (voter, state.swarm_for_task(&task_id))
     };
 
     // Broadcast discussion.critique P2P message so all board members receive it
@@ -470,12 +969,19 @@ async fn handle_submit_critique(
         round,
         plan_scores,
         content,
+        flagged_malicious,
     };
     let msg = SwarmMessage::new(
         ProtocolMethod::DiscussionCritique.as_str(),
         serde_json::to_value(&critique_params).unwrap_or_default(),
         String::new(),
     );
+    let msg = match state.read().await.seal_board_message(&task_id, msg) {
+        Ok(msg) => msg,
+        Err(e) => {
+            return SwarmResponse::error(id, ErrorCode::OperationFailed, format!("Failed to seal critique: {}", e));
+        }
+    };
     if let Ok(data) = serde_json::to_vec(&msg) {
         let topic = SwarmTopics::voting_for(&swarm_id, &task_id);
         let _ = network_handle.publish(&topic, data).await;
@@ -496,17 +1002,28 @@ async fn handle_get_voting_state(
         .map(|s| s.to_string());
 
     let state = state.read().await;
+    let now = chrono::Utc::now();
 
     let voting_entries: Vec<serde_json::Value> = state
         .voting_engines
         .iter()
         .filter(|(task_id, _)| maybe_task_id.as_ref().map(|t| t == *task_id).unwrap_or(true))
         .map(|(task_id, voting)| {
+            let window = state.voting_windows.get(task_id);
             serde_json::json!({
                 "task_id": task_id,
                 "proposal_count": voting.proposal_count(),
                 "ballot_count": voting.ballot_count(),
+                "abstention_count": voting.abstention_count(),
                 "quorum_reached": voting.ballot_count() >= voting.proposal_count() && voting.ballot_count() > 0,
+                "ballot_mode": voting.ballot_mode().as_str(),
+                "commitment_count": voting.commitment_count(),
+                "commitments_closed_at": voting.commitments_closed_at(),
+                // Blind ballots stay secret until the tally.
+                "first_preferences": (!voting.is_blind() || voting.is_finalized())
+                    .then(|| voting.first_preferences()),
+                "deadline": window.map(|w| w.deadline),
+                "remaining_secs": window.map(|w| w.remaining_secs(now)),
             })
         })
         .collect();
@@ -551,7 +1068,7 @@ async fn handle_connect(
         None => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing 'addr' parameter".into(),
             );
         }
@@ -562,7 +1079,7 @@ async fn handle_connect(
         Err(e) => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 format!("Invalid multiaddress: {}", e),
             );
         }
@@ -570,24 +1087,37 @@ async fn handle_connect(
 
     match network_handle.dial(addr).await {
         Ok(()) => SwarmResponse::success(id, serde_json::json!({"connected": true})),
-        Err(e) => SwarmResponse::error(id, -32000, format!("Dial failed: {}", e)),
+        Err(e) => SwarmResponse::error(id, ErrorCode::OperationFailed, format!("Dial failed: {}", e)),
     }
 }
 
-/// Handle `swarm.get_network_stats` - return current network statistics.
+/// Handle `swarm.get_network_stats` - return current network statistics,
+/// with the bytes and messages sent and received per topic and per peer
+/// under `bandwidth` and the node's AutoNAT status, external addresses and
+/// relay use under `reachability`.
 async fn handle_get_network_stats(
     id: Option<String>,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
-    let state = state.read().await;
-    let mut stats = state.network_stats.clone();
-    stats.total_agents =
-        state.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)) as u64;
+    let mut stats = {
+        let state = state.read().await;
+        let mut stats = state.network_stats.clone();
+        stats.total_agents =
+            state.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)) as u64;
+        serde_json::to_value(&stats).unwrap_or_default()
+    };
+    let bandwidth = match network_handle.bandwidth().await {
+        Ok(bandwidth) => serde_json::to_value(&bandwidth).unwrap_or_default(),
+        Err(_) => serde_json::Value::Null,
+    };
+    stats["bandwidth"] = bandwidth;
+    stats["reachability"] = match network_handle.reachability().await {
+        Ok(reachability) => serde_json::to_value(&reachability).unwrap_or_default(),
+        Err(_) => serde_json::Value::Null,
+    };
 
-    SwarmResponse::success(
-        id,
-        serde_json::to_value(&stats).unwrap_or_default(),
-    )
+    SwarmResponse::success(id, stats)
 }
 
 /// Handle `swarm.propose_plan` - submit a task decomposition plan.
@@ -602,7 +1132,7 @@ pub(crate) async fn handle_propose_plan(
         Err(e) => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 format!("Invalid plan: {}", e),
             );
         }
@@ -610,23 +1140,104 @@ pub(crate) async fn handle_propose_plan(
 
     {
         let state = state.read().await;
-        plan.proposer = state.agent_id.clone();
+        // New proposals wait out an epoch transition; in-flight rounds are
+        // handed off to the new layout when it finalizes.
+        if state.epoch_manager.is_transitioning() {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::ProposalsFrozen,
+                "Epoch transition in progress; proposals are frozen until it finalizes".to_string(),
+            );
+        }
+        plan.proposer = match acting_agent(params, &state) {
+            Ok(agent_id) => agent_id,
+            Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+        };
     }
 
+    // In a tier that draws a committee, only its members propose.
+    let selection = match state
+        .read()
+        .await
+        .committee_proof(&plan.task_id, plan.proposer.as_str())
+    {
+        Ok(selection) => selection,
+        Err(message) => return SwarmResponse::error(id, ErrorCode::NotPermitted, message),
+    };
+
+    // Reputation staked on the plan; the connector's default unless named.
+    let bond = match params.get("bond") {
+        None => state.read().await.proposal_bond,
+        Some(v) => match v.as_u64() {
+            Some(bond) => bond,
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'bond' must be a non-negative integer".to_string(),
+                );
+            }
+        },
+    };
+
     if plan.subtasks.is_empty() {
         return SwarmResponse::error(
             id,
-            -32013,
+            ErrorCode::EmptyPlan,
             "Plan must include at least one subtask".to_string(),
         );
     }
 
+    let violations = {
+        let state = state.read().await;
+        let agents: Vec<&[String]> = state.agent_capabilities.values().map(Vec::as_slice).collect();
+        state
+            .plan_policy
+            .for_swarm(&state.swarm_for_task(&plan.task_id))
+            .validate(&plan, &state.capabilities, &agents)
+    };
+    if !violations.is_empty() {
+        return SwarmResponse::error_with_data(
+            id,
+            ErrorCode::PlanPolicyViolation,
+            format!(
+                "Plan violates the swarm's plan policy: {}",
+                violations
+                    .iter()
+                    .map(|v| v.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+            serde_json::json!({ "violations": violations }),
+        );
+    }
+
+    // A plan may not ask for more than the task has left to spend.
+    let requested_budget = plan
+        .budget
+        .unwrap_or_else(|| plan.subtasks.iter().filter_map(|s| s.budget).sum());
+    {
+        let state = state.read().await;
+        if let Some(remaining) = state.budget_remaining(&plan.task_id) {
+            if requested_budget > remaining {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    format!(
+                        "Plan budget {:.2} exceeds remaining task budget {:.2}",
+                        requested_budget, remaining
+                    ),
+                );
+            }
+        }
+    }
+
     let plan_hash = match wws_consensus::RfpCoordinator::compute_plan_hash(&plan) {
         Ok(h) => h,
         Err(e) => {
             return SwarmResponse::error(
                 id,
-                -32000,
+                ErrorCode::OperationFailed,
                 format!("Hash computation failed: {}", e),
             );
         }
@@ -659,16 +1270,19 @@ pub(crate) async fn handle_propose_plan(
             .map(|t| t.tier_level)
             .unwrap_or(1);
         let tier = tier_from_level(task_tier_level);
-        let expected_proposers =
-            active_members_in_tier(&state, tier, Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
-                .len()
-                .max(1);
+        let tier_members =
+            active_members_in_tier(&state, tier, Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+        let expected_proposers = state
+            .quorum_requirement(&plan.task_id, task_tier_level, &tier_members)
+            .expected_proposers;
 
         let commit = ProposalCommitParams {
             task_id: plan.task_id.clone(),
             proposer: plan.proposer.clone(),
             epoch: plan.epoch,
             plan_hash: plan_hash.clone(),
+            bond,
+            selection: selection.clone(),
         };
 
         let reveal_phase_ready = {
@@ -687,7 +1301,7 @@ pub(crate) async fn handle_propose_plan(
                 if let Err(e) = coordinator.inject_task(&task) {
                     return SwarmResponse::error(
                         id,
-                        -32000,
+                        ErrorCode::OperationFailed,
                         format!("Failed to initialize RFP: {}", e),
                     );
                 }
@@ -696,13 +1310,14 @@ pub(crate) async fn handle_propose_plan(
             if let Err(e) = coordinator.record_commit(&commit) {
                 return SwarmResponse::error(
                     id,
-                    -32000,
+                    ErrorCode::OperationFailed,
                     format!("Failed to record proposal commit: {}", e),
                 );
             }
 
             matches!(coordinator.phase(), RfpPhase::RevealPhase)
         };
+        state.hold_proposal_bond(&commit);
 
         state
             .pending_plan_reveals
@@ -721,6 +1336,7 @@ pub(crate) async fn handle_propose_plan(
             pending_items.sort_by(|a, b| a.0.cmp(&b.0));
 
             let mut reveal_errors = Vec::new();
+            let mut mismatched_proposers = Vec::new();
             if let Some(coordinator) = state.rfp_coordinators.get_mut(&plan.task_id) {
                 for (_, pending_plan) in pending_items {
                     let reveal = ProposalRevealParams {
@@ -728,6 +1344,9 @@ pub(crate) async fn handle_propose_plan(
                         plan: pending_plan,
                     };
                     if let Err(e) = coordinator.record_reveal(&reveal) {
+                        if matches!(e, wws_consensus::ConsensusError::HashMismatch { .. }) {
+                            mismatched_proposers.push(reveal.plan.proposer.to_string());
+                        }
                         reveal_errors.push(format!(
                             "Failed to record deferred proposal reveal for task {}: {}",
                             plan.task_id, e
@@ -740,6 +1359,9 @@ pub(crate) async fn handle_propose_plan(
             for err in reveal_errors {
                 state.push_log(crate::tui::LogCategory::Error, err);
             }
+            for proposer in mismatched_proposers {
+                state.slash_proposal_bond(&plan.task_id, &proposer, SlashReason::HashMismatch);
+            }
         }
 
         state.push_log(
@@ -775,7 +1397,7 @@ pub(crate) async fn handle_propose_plan(
         );
 
         (
-            state.current_swarm_id.as_str().to_string(),
+            state.swarm_for_task(&plan.task_id),
             state.task_details.contains_key(&plan.task_id),
             plan.subtasks.len(),
             reveals_to_publish,
@@ -815,15 +1437,8 @@ pub(crate) async fn handle_propose_plan(
             .unwrap_or(1);
         let tier = tier_from_level(task_tier_level);
         let tier_members = active_members_in_tier(&state, tier, Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
-        let expected = tier_members.len().max(1);
-        state.task_vote_requirements.insert(
-            plan.task_id.clone(),
-            crate::connector::TaskVoteRequirement {
-                expected_proposers: expected,
-                expected_voters: expected,
-                tier_level: task_tier_level,
-            },
-        );
+        let requirement = state.quorum_requirement(&plan.task_id, task_tier_level, &tier_members);
+        state.task_vote_requirements.insert(plan.task_id.clone(), requirement);
 
         let proposal_owners: std::collections::HashMap<String, AgentId> = state
             .rfp_coordinators
@@ -837,9 +1452,10 @@ pub(crate) async fn handle_propose_plan(
             .unwrap_or_default();
 
         let has_proposals = !proposal_owners.is_empty();
+        let voting_config = state.voting_config.clone();
         let voting = state.voting_engines.entry(plan.task_id.clone()).or_insert_with(|| {
             wws_consensus::VotingEngine::new(
-                wws_consensus::voting::VotingConfig::default(),
+                voting_config,
                 plan.task_id.clone(),
                 plan.epoch,
             )
@@ -874,18 +1490,25 @@ pub(crate) async fn handle_propose_plan(
         proposer: plan.proposer.clone(),
         epoch: plan.epoch,
         plan_hash: plan_hash.clone(),
+        bond,
+        selection,
     };
-    let commit_msg = SwarmMessage::new(
+    let commit_msg = state.read().await.message_as(
+        &plan.proposer,
         ProtocolMethod::ProposalCommit.as_str(),
         serde_json::to_value(&commit_params).unwrap_or_default(),
-        String::new(),
     );
-    let commit_data = match serde_json::to_vec(&commit_msg) {
+    let commit_data = match state
+        .read()
+        .await
+        .seal_board_message(&plan.task_id, commit_msg)
+        .and_then(|msg| Ok(serde_json::to_vec(&msg)?))
+    {
         Ok(data) => data,
         Err(e) => {
             return SwarmResponse::error(
                 id,
-                -32000,
+                ErrorCode::OperationFailed,
                 format!("Failed to serialize proposal commit: {}", e),
             );
         }
@@ -902,17 +1525,22 @@ pub(crate) async fn handle_propose_plan(
         task_id: plan.task_id.clone(),
         plan: plan.clone(),
     };
-    let current_reveal_msg = SwarmMessage::new(
+    let current_reveal_msg = state.read().await.message_as(
+        &plan.proposer,
         ProtocolMethod::ProposalReveal.as_str(),
         serde_json::to_value(&current_reveal).unwrap_or_default(),
-        String::new(),
     );
-    let current_reveal_data = match serde_json::to_vec(&current_reveal_msg) {
+    let current_reveal_data = match state
+        .read()
+        .await
+        .seal_board_message(&plan.task_id, current_reveal_msg)
+        .and_then(|msg| Ok(serde_json::to_vec(&msg)?))
+    {
         Ok(data) => data,
         Err(e) => {
             return SwarmResponse::error(
                 id,
-                -32000,
+                ErrorCode::OperationFailed,
                 format!("Failed to serialize proposal reveal: {}", e),
             );
         }
@@ -927,17 +1555,22 @@ pub(crate) async fn handle_propose_plan(
     };
 
     for reveal_params in reveals_to_publish {
-        let reveal_msg = SwarmMessage::new(
+        let reveal_msg = state.read().await.message_as(
+            &reveal_params.plan.proposer,
             ProtocolMethod::ProposalReveal.as_str(),
             serde_json::to_value(&reveal_params).unwrap_or_default(),
-            String::new(),
         );
-        let reveal_data = match serde_json::to_vec(&reveal_msg) {
+        let reveal_data = match state
+            .read()
+            .await
+            .seal_board_message(&reveal_params.task_id, reveal_msg)
+            .and_then(|msg| Ok(serde_json::to_vec(&msg)?))
+        {
             Ok(data) => data,
             Err(e) => {
                 return SwarmResponse::error(
                     id,
-                    -32000,
+                    ErrorCode::OperationFailed,
                     format!("Failed to serialize proposal reveal: {}", e),
                 );
             }
@@ -993,57 +1626,352 @@ pub(crate) async fn handle_propose_plan(
     )
 }
 
-/// Aggregate results from all subtasks of a parent task.
-fn aggregate_subtask_results(state: &ConnectorState, parent_task_id: &str) -> Artifact {
-    use sha2::{Digest, Sha256};
-
-    let parent_task = state.task_details.get(parent_task_id);
-    let subtask_ids = parent_task
-        .map(|t| t.subtasks.clone())
-        .unwrap_or_default();
-
-    // Collect all subtask results
-    let mut subtask_results = Vec::new();
-    for subtask_id in &subtask_ids {
-        if let Some(result) = state.task_results.get(subtask_id) {
-            subtask_results.push(result.clone());
-        }
-    }
-
-    // Create aggregated content (concatenate content CIDs)
-    let aggregated_content = subtask_results
-        .iter()
-        .map(|r| format!("subtask:{} -> cid:{}", r.task_id, r.content_cid))
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    // Compute content-addressed ID for aggregated result
-    let mut hasher = Sha256::new();
-    hasher.update(aggregated_content.as_bytes());
-    let content_cid = format!("{:x}", hasher.finalize());
-
-    // Compute Merkle hash (aggregate subtask merkle hashes)
-    let mut merkle_hasher = Sha256::new();
-    for result in &subtask_results {
-        merkle_hasher.update(result.merkle_hash.as_bytes());
-    }
-    let merkle_hash = format!("{:x}", merkle_hasher.finalize());
-
-    Artifact {
-        artifact_id: format!("{}-aggregated", parent_task_id),
-        task_id: parent_task_id.to_string(),
-        producer: state.agent_id.clone(),
-        content_cid,
-        merkle_hash,
-        content_type: "application/json; aggregated".to_string(),
-        size_bytes: aggregated_content.len() as u64,
-        created_at: chrono::Utc::now(),
-        content: aggregated_content,
-    }
-}
-
-/// Handle `swarm.submit_result` - submit a task execution result.
-pub(crate) async fn handle_submit_result(
+/// Handle `swarm.revise_plan` - submit one revised plan after the critique round.
+///
+/// Params: `{ "original_plan_id": <str>, "plan": <Plan>, "reason"?: <str> }`
+/// The revision replaces the original in voting and is broadcast as a
+/// `consensus.proposal_revision` message.
+async fn handle_revise_plan(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let original_plan_id = match params.get("original_plan_id").and_then(|v| v.as_str()) {
+        Some(v) if !v.trim().is_empty() => v.to_string(),
+        _ => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "Missing 'original_plan_id' parameter".into(),
+            );
+        }
+    };
+    let mut plan: Plan = match params
+        .get("plan")
+        .map(|v| serde_json::from_value(v.clone()))
+    {
+        Some(Ok(p)) => p,
+        Some(Err(e)) => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid plan: {}", e));
+        }
+        None => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'plan' parameter".into());
+        }
+    };
+    if plan.subtasks.is_empty() {
+        return SwarmResponse::error(
+            id,
+            ErrorCode::EmptyPlan,
+            "Plan must include at least one subtask".to_string(),
+        );
+    }
+    let reason = params
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let (revision, swarm_id) = {
+        let mut state = state.write().await;
+        plan.proposer = state.agent_id.clone();
+        if plan.epoch == 0 {
+            if let Some(original) = state
+                .rfp_coordinators
+                .get(&plan.task_id)
+                .and_then(|rfp| rfp.reveals.get(&plan.proposer))
+            {
+                plan.epoch = original.plan.epoch;
+            }
+        }
+        let revision = ProposalRevisionParams {
+            task_id: plan.task_id.clone(),
+            original_plan_id,
+            plan,
+            reason,
+        };
+        if let Err(e) = state.apply_plan_revision(&revision) {
+            return SwarmResponse::error(id, ErrorCode::OperationFailed, e);
+        }
+        let swarm_id = state.swarm_for_task(&revision.task_id);
+        (revision, swarm_id)
+    };
+
+    let msg = SwarmMessage::new(
+        ProtocolMethod::ProposalRevision.as_str(),
+        serde_json::to_value(&revision).unwrap_or_default(),
+        String::new(),
+    );
+    let sealed = state
+        .read()
+        .await
+        .seal_board_message(&revision.task_id, msg)
+        .and_then(|msg| Ok(serde_json::to_vec(&msg)?));
+    let published = match sealed {
+        Ok(data) => {
+            let topic = SwarmTopics::proposals_for(&swarm_id, &revision.task_id);
+            network_handle.publish(&topic, data).await.is_ok()
+        }
+        Err(_) => false,
+    };
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": revision.task_id,
+            "original_plan_id": revision.original_plan_id,
+            "plan_id": revision.plan.plan_id,
+            "published": published,
+        }),
+    )
+}
+
+/// Handle `swarm.withdraw_plan` - retract a revealed plan before voting closes.
+///
+/// Params: `{ "task_id": <str>, "plan_id": <str>, "reason"?: <str>, "local_agent"?: <did> }`
+/// The plan leaves the RFP and the voting candidates, and the withdrawal is
+/// broadcast as a `consensus.proposal_withdraw` message.
+async fn handle_withdraw_plan(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(v) if !v.trim().is_empty() => v.to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".into());
+        }
+    };
+    let plan_id = match params.get("plan_id").and_then(|v| v.as_str()) {
+        Some(v) if !v.trim().is_empty() => v.to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'plan_id' parameter".into());
+        }
+    };
+    let reason = params
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let (withdrawal, swarm_id) = {
+        let mut state = state.write().await;
+        let proposer = match acting_agent(params, &state) {
+            Ok(agent_id) => agent_id,
+            Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+        };
+        let withdrawal = ProposalWithdrawParams {
+            task_id,
+            plan_id,
+            proposer,
+            reason,
+        };
+        if let Err(e) = state.apply_plan_withdrawal(&withdrawal) {
+            return SwarmResponse::error(id, ErrorCode::OperationFailed, e);
+        }
+        let swarm_id = state.swarm_for_task(&withdrawal.task_id);
+        (withdrawal, swarm_id)
+    };
+
+    let msg = SwarmMessage::new(
+        ProtocolMethod::ProposalWithdraw.as_str(),
+        serde_json::to_value(&withdrawal).unwrap_or_default(),
+        String::new(),
+    );
+    let sealed = state
+        .read()
+        .await
+        .seal_board_message(&withdrawal.task_id, msg)
+        .and_then(|msg| Ok(serde_json::to_vec(&msg)?));
+    let published = match sealed {
+        Ok(data) => {
+            let topic = SwarmTopics::proposals_for(&swarm_id, &withdrawal.task_id);
+            network_handle.publish(&topic, data).await.is_ok()
+        }
+        Err(_) => false,
+    };
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": withdrawal.task_id,
+            "plan_id": withdrawal.plan_id,
+            "published": published,
+        }),
+    )
+}
+
+/// Aggregate results from all subtasks of a parent task using the parent's
+/// aggregation strategy.
+fn aggregate_subtask_results(state: &ConnectorState, parent_task_id: &str) -> Artifact {
+    use sha2::{Digest, Sha256};
+
+    let parent_task = state.task_details.get(parent_task_id);
+    let subtask_ids = parent_task
+        .map(|t| t.subtasks.clone())
+        .unwrap_or_default();
+    let strategy = parent_task.map(|t| t.aggregation).unwrap_or_default();
+
+    // Collect all subtask results
+    let mut subtask_results = Vec::new();
+    for subtask_id in &subtask_ids {
+        if let Some(result) = state.task_results.get(subtask_id) {
+            subtask_results.push(result.clone());
+        }
+    }
+    let result_text = |artifact: &Artifact| -> String {
+        state
+            .task_result_text
+            .get(&artifact.task_id)
+            .cloned()
+            .unwrap_or_else(|| artifact.content.clone())
+    };
+
+    let synthesis_id = crate::aggregation::synthesis_task_id(parent_task_id);
+    let synthesis = subtask_results.iter().find(|r| r.task_id == synthesis_id);
+    let (aggregated_content, content_type) = match (strategy, synthesis) {
+        (AggregationStrategy::ChairSynthesis, Some(synthesis)) => {
+            (result_text(synthesis), synthesis.content_type.clone())
+        }
+        (AggregationStrategy::JsonMerge, _) => {
+            let texts: Vec<(String, String)> = subtask_results
+                .iter()
+                .map(|r| (r.task_id.clone(), result_text(r)))
+                .collect();
+            let merged =
+                crate::aggregation::json_merge(texts.iter().map(|(id, text)| (id.as_str(), text.as_str())));
+            (merged.to_string(), "application/json".to_string())
+        }
+        // Concatenate content CIDs, marking results that were accepted
+        // despite breaking their result schema.
+        _ => {
+            let listing = subtask_results
+                .iter()
+                .map(|r| {
+                    let flag = if state.result_schema_violations.contains_key(&r.task_id) {
+                        " (nonconforming)"
+                    } else {
+                        ""
+                    };
+                    format!("subtask:{} -> cid:{}{}", r.task_id, r.content_cid, flag)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            (listing, AGGREGATED_CONTENT_TYPE.to_string())
+        }
+    };
+
+    // Compute content-addressed ID for aggregated result
+    let mut hasher = Sha256::new();
+    hasher.update(aggregated_content.as_bytes());
+    let content_cid = format!("{:x}", hasher.finalize());
+
+    // The aggregated artifact is the Merkle branch over its subtasks' hashes.
+    let merkle_hash =
+        wws_state::MerkleDag::branch_hash(&crate::result_chain::child_hashes(state, parent_task_id));
+
+    Artifact {
+        artifact_id: crate::aggregation::aggregated_artifact_id(parent_task_id),
+        task_id: parent_task_id.to_string(),
+        producer: state.agent_id.clone(),
+        content_cid,
+        merkle_hash,
+        content_type,
+        size_bytes: aggregated_content.len() as u64,
+        created_at: chrono::Utc::now(),
+        content: aggregated_content,
+        sealed: None,
+    }
+}
+
+/// Handle `swarm.submit_result` - submit a task execution result.
+/// Handle `swarm.report_progress` - tell the coordinator how far an
+/// assigned task has come.
+///
+/// Params: `{ "task_id": <str>, "percent": <0-100>, "note"?: <str>, "cid"?: <str>, "local_agent"?: <did> }`
+/// The report is added to the task's timeline and published on the task's
+/// results topic. A task whose assignee reported progress within the
+/// execution timeout is not reassigned, even once it is overdue.
+async fn handle_report_progress(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(v) if !v.trim().is_empty() => v.to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".into());
+        }
+    };
+    let percent = match params.get("percent").and_then(|v| v.as_u64()).filter(|p| *p <= 100) {
+        Some(p) => p as u8,
+        None => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "'percent' must be an integer from 0 to 100".into(),
+            );
+        }
+    };
+    let note = params
+        .get("note")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let intermediate_cid = params
+        .get("cid")
+        .and_then(|v| v.as_str())
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| c.to_string());
+
+    let (progress, swarm_id) = {
+        let mut state = state.write().await;
+        let agent_id = match acting_agent(params, &state) {
+            Ok(agent_id) => agent_id,
+            Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+        };
+        if !state.task_details.contains_key(&task_id) {
+            return SwarmResponse::error(id, ErrorCode::NotFound, format!("Task not found: {}", task_id));
+        }
+        let progress = TaskProgressParams {
+            task_id,
+            agent_id,
+            percent,
+            note,
+            intermediate_cid,
+            timestamp: chrono::Utc::now(),
+        };
+        if let Err(e) = state.record_task_progress(progress.clone(), progress.timestamp) {
+            return SwarmResponse::error(id, ErrorCode::NotAssignee, e);
+        }
+        let swarm_id = state.swarm_for_task(&progress.task_id);
+        (progress, swarm_id)
+    };
+
+    let topic = SwarmTopics::results_for(&swarm_id, &progress.task_id);
+    let msg = state.read().await.message_as(
+        &progress.agent_id,
+        ProtocolMethod::TaskProgress.as_str(),
+        serde_json::to_value(&progress).unwrap_or_default(),
+    );
+    let published = match serde_json::to_vec(&msg) {
+        Ok(data) => network_handle.publish(&topic, data).await.is_ok(),
+        Err(_) => false,
+    };
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": progress.task_id,
+            "percent": progress.percent,
+            "published": published,
+        }),
+    )
+}
+
+pub(crate) async fn handle_submit_result(
     id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
@@ -1054,7 +1982,7 @@ pub(crate) async fn handle_submit_result(
         Err(e) => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 format!("Invalid result submission: {}", e),
             );
         }
@@ -1062,11 +1990,19 @@ pub(crate) async fn handle_submit_result(
 
     {
         let state = state.read().await;
-        submission.agent_id = state.agent_id.clone();
-        submission.artifact.producer = state.agent_id.clone();
+        let agent_id = match acting_agent(params, &state) {
+            Ok(agent_id) => agent_id,
+            Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+        };
+        submission.agent_id = agent_id.clone();
+        submission.artifact.producer = agent_id;
+        // Only the connector seals results, after the task's visibility.
+        submission.artifact.sealed = None;
     }
 
     // Add to Merkle DAG and update task state.
+    let mut schema_violations = Vec::new();
+    let mut synthesis_assignment = None;
     let (dag_nodes, parent_propagation_info) = {
         let mut state = state.write().await;
 
@@ -1082,7 +2018,7 @@ pub(crate) async fn handle_submit_result(
             if !assignee_ok {
                 return SwarmResponse::error(
                     id,
-                    -32012,
+                    ErrorCode::NotAssignee,
                     format!(
                         "Result submission ignored for {}: assignee {} is no longer current",
                         submission.task_id, submission.agent_id
@@ -1092,7 +2028,7 @@ pub(crate) async fn handle_submit_result(
             if task.parent_task_id.is_none() && task.subtasks.is_empty() {
                 return SwarmResponse::error(
                     id,
-                    -32011,
+                    ErrorCode::RootNotDecomposed,
                     format!(
                         "Root result submission blocked for {}: no decomposed subtasks",
                         submission.task_id
@@ -1108,17 +2044,74 @@ pub(crate) async fn handle_submit_result(
                         .map(|t| t.status == TaskStatus::Completed)
                         .unwrap_or(false)
                 });
-                if !all_subtasks_done {
+                if !all_subtasks_done || !state.child_holons_done(&submission.task_id) {
                     return SwarmResponse::error(
                         id,
-                        -32010,
+                        ErrorCode::SubtasksIncomplete,
                         format!(
-                            "Cannot submit aggregated result for {} before all subtasks are completed",
+                            "Cannot submit aggregated result for {} before all subtasks and child holons are completed",
                             submission.task_id
                         ),
                     );
                 }
             }
+
+            // Artifacts the connector aggregates from subtask results are
+            // not checked against the parent's schema.
+            let aggregated = submission.artifact.artifact_id
+                == crate::aggregation::aggregated_artifact_id(&submission.task_id);
+            let schema = task.result_schema.as_ref().filter(|_| !aggregated);
+            if let Some(schema) = schema {
+                let content = if submission.artifact.content.is_empty() {
+                    params.get("content").and_then(|v| v.as_str()).unwrap_or("")
+                } else {
+                    submission.artifact.content.as_str()
+                };
+                let violations = schema.check(&submission.artifact.content_type, content);
+                if !violations.is_empty() && schema.strict {
+                    return SwarmResponse::error(
+                        id,
+                        ErrorCode::ResultSchemaMismatch,
+                        format!(
+                            "Result for {} does not match the task's result schema: {}",
+                            submission.task_id,
+                            violations.join("; ")
+                        ),
+                    );
+                }
+                schema_violations = violations;
+            }
+        }
+        let content_text = params
+            .get("content")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        if let Err(e) = state.seal_result(
+            &submission.task_id,
+            submission.agent_id.as_str(),
+            &mut submission.artifact,
+            &content_text,
+        ) {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::OperationFailed,
+                format!("Could not seal the result of {}: {}", submission.task_id, e),
+            );
+        }
+
+        if !schema_violations.is_empty() {
+            state.push_task_timeline_event(
+                &submission.task_id,
+                "result_nonconforming",
+                schema_violations.join("; "),
+                Some(submission.agent_id.to_string()),
+            );
+            state
+                .result_schema_violations
+                .insert(submission.task_id.clone(), schema_violations.clone());
+        } else {
+            state.result_schema_violations.remove(&submission.task_id);
         }
 
         let parent_task_id = state
@@ -1126,18 +2119,24 @@ pub(crate) async fn handle_submit_result(
             .get(&submission.task_id)
             .and_then(|t| t.parent_task_id.clone());
 
+        state.record_result_timeliness(
+            &submission.task_id,
+            submission.agent_id.as_str(),
+            chrono::Utc::now(),
+        );
         if let Some(task) = state.task_details.get_mut(&submission.task_id) {
             task.status = TaskStatus::Completed;
             task.assigned_to = Some(submission.agent_id.clone());
         }
         state.task_set.remove(&submission.task_id);
+        state.complete_holon(&submission.task_id, &submission.artifact);
+        if let Some(cost) = submission.cost {
+            state.record_task_cost(&submission.task_id, cost);
+        }
         state.bump_tasks_processed(submission.agent_id.as_str());
         state.mark_member_submitted_result(submission.agent_id.as_str());
         state.mark_member_seen(submission.agent_id.as_str());
-        state.merkle_dag.add_leaf(
-            submission.task_id.clone(),
-            submission.artifact.content_cid.as_bytes(),
-        );
+        crate::result_chain::record(&mut state, &mut submission.artifact);
         let nodes = state.merkle_dag.node_count();
         state.push_task_timeline_event(
             &submission.task_id,
@@ -1165,17 +2164,21 @@ pub(crate) async fn handle_submit_result(
 
         // Store the result for potential aggregation
         state.task_results.insert(submission.task_id.clone(), submission.artifact.clone());
-        let content_text = params
-            .get("content")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
         if !content_text.trim().is_empty() {
             state
                 .task_result_text
                 .insert(submission.task_id.clone(), content_text.clone());
+            let provider = submission.agent_id.to_string();
+            state.store_result_content(&content_text, &provider);
         }
 
+        state.settle_verification(
+            &submission.task_id,
+            submission.agent_id.as_str(),
+            &submission.artifact,
+            &content_text,
+        );
+
         // If is_synthesis flag is set, record a SynthesisResult deliberation message
         // so it appears in the deliberation panel alongside critiques and proposals.
         if params
@@ -1193,6 +2196,7 @@ pub(crate) async fn handle_submit_result(
                 content: content_text.clone(),
                 referenced_plan_id: None,
                 critic_scores: None,
+                hlc: Some(hlc::global().now()),
             };
             state
                 .deliberation_messages
@@ -1215,9 +2219,19 @@ pub(crate) async fn handle_submit_result(
                                 .unwrap_or(false)
                         })
                 })
-                .unwrap_or(false);
+                .unwrap_or(false)
+                && state.child_holons_done(&parent_id);
 
-            if parent_completed {
+            let needs_synthesis = parent_completed
+                && state.task_details.get(&parent_id).is_some_and(|p| {
+                    p.aggregation == AggregationStrategy::ChairSynthesis
+                        && !p.subtasks.contains(&crate::aggregation::synthesis_task_id(&parent_id))
+                });
+            if needs_synthesis {
+                // The parent completes once the chair submits the synthesis.
+                synthesis_assignment = state.open_synthesis_subtask(&parent_id);
+                None
+            } else if parent_completed {
                 // Aggregate results from all subtasks
                 let aggregated_artifact = aggregate_subtask_results(&state, &parent_id);
 
@@ -1257,16 +2271,18 @@ pub(crate) async fn handle_submit_result(
         (nodes, propagation_info)
     };
 
-    // Publish result to the results topic.
-    let swarm_id = {
-        let state = state.read().await;
-        state.current_swarm_id.as_str().to_string()
-    };
+    // Publish result to the results topic; a sealed result travels without
+    // its plaintext.
+    let swarm_id = state.read().await.swarm_for_task(&submission.task_id);
     let topic = SwarmTopics::results_for(&swarm_id, &submission.task_id);
-    let msg = SwarmMessage::new(
+    let mut published = submission.clone();
+    if published.artifact.sealed.is_some() {
+        published.artifact.content.clear();
+    }
+    let msg = state.read().await.message_as(
+        &submission.agent_id,
         ProtocolMethod::ResultSubmission.as_str(),
-        serde_json::to_value(&submission).unwrap_or_default(),
-        String::new(),
+        serde_json::to_value(&published).unwrap_or_default(),
     );
     if let Ok(data) = serde_json::to_vec(&msg) {
         if let Err(e) = network_handle.publish(&topic, data).await {
@@ -1274,6 +2290,10 @@ pub(crate) async fn handle_submit_result(
         }
     }
 
+    if let Some(assignment) = synthesis_assignment {
+        publish_synthesis_assignment(&swarm_id, &assignment, network_handle).await;
+    }
+
     // Hierarchical propagation: if parent was aggregated, submit aggregated result
     // for the parent task as a normal result event. If a grandparent exists,
     // recursive propagation will continue in the nested call.
@@ -1296,6 +2316,7 @@ pub(crate) async fn handle_submit_result(
             artifact: aggregated_artifact,
             merkle_proof: vec![], // TODO: proper merkle proof
             is_synthesis: true,
+            cost: None,
         };
 
         // Recursively call handle_submit_result for the parent task
@@ -1315,20 +2336,155 @@ pub(crate) async fn handle_submit_result(
             "artifact_id": submission.artifact.artifact_id,
             "accepted": true,
             "dag_nodes": dag_nodes,
+            "schema_violations": schema_violations,
         }),
     )
 }
 
+async fn publish_synthesis_assignment(
+    swarm_id: &str,
+    assignment: &TaskAssignmentParams,
+    network_handle: &wws_network::SwarmHandle,
+) {
+    let topic = SwarmTopics::tasks_for(swarm_id, assignment.task.tier_level);
+    let msg = SwarmMessage::new(
+        ProtocolMethod::TaskAssignment.as_str(),
+        serde_json::to_value(assignment).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            tracing::warn!(error = %e, "Failed to publish synthesis assignment");
+        }
+    }
+}
+
+/// Finish a parent task whose last child holon completed on another node:
+/// open its synthesis subtask, or aggregate the subtask results and submit
+/// them as the parent's result, as `handle_submit_result` does when the
+/// last subtask result arrives over RPC.
+pub(crate) async fn finish_parent_task(
+    parent_id: &str,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) {
+    let (swarm_id, synthesis_assignment, aggregated) = {
+        let mut state = state.write().await;
+        let Some(parent) = state.task_details.get(parent_id) else {
+            return;
+        };
+        if parent.status == TaskStatus::Completed {
+            return;
+        }
+        let swarm_id = state.swarm_for_task(parent_id);
+        if parent.aggregation == AggregationStrategy::ChairSynthesis {
+            (swarm_id, state.open_synthesis_subtask(parent_id), None)
+        } else {
+            let artifact = aggregate_subtask_results(&state, parent_id);
+            (swarm_id, None, Some((state.agent_id.clone(), artifact)))
+        }
+    };
+    if let Some(assignment) = synthesis_assignment {
+        publish_synthesis_assignment(&swarm_id, &assignment, network_handle).await;
+    }
+    if let Some((agent_id, artifact)) = aggregated {
+        tracing::info!(parent_task_id = %parent_id, "Child holons done; submitting aggregated result");
+        let submission = ResultSubmissionParams {
+            task_id: parent_id.to_string(),
+            agent_id,
+            artifact,
+            merkle_proof: vec![],
+            is_synthesis: true,
+            cost: None,
+        };
+        let _ = Box::pin(handle_submit_result(
+            None,
+            &serde_json::to_value(&submission).unwrap_or_default(),
+            state,
+            network_handle,
+        ))
+        .await;
+    }
+}
+
 /// Handle `swarm.receive_task` - poll for assigned tasks.
+///
+/// With `wait_ms` an empty poll is repeated until a task arrives or the
+/// wait ends. With `cursor` only tasks delivered after the cursor are
+/// returned, and each stays unacknowledged until `swarm.ack_task`.
 async fn handle_receive_task(
     id: Option<String>,
+    params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
 ) -> SwarmResponse {
-    let mut state = state.write().await;
-    let my_id = state.agent_id.clone();
+    let wait_ms = match params.get("wait_ms") {
+        None => 0,
+        Some(v) => match v.as_u64() {
+            Some(ms) => ms.min(MAX_RECEIVE_WAIT_MS),
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'wait_ms' must be a non-negative integer".to_string(),
+                );
+            }
+        },
+    };
+    let cursor = match params.get("cursor") {
+        None => None,
+        Some(v) => match v.as_u64() {
+            Some(cursor) => Some(cursor),
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'cursor' must be a non-negative integer".to_string(),
+                );
+            }
+        },
+    };
+
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(wait_ms);
+    loop {
+        let result = match poll_assigned_tasks(params, &mut *state.write().await, cursor) {
+            Ok(result) => result,
+            Err((code, message)) => return SwarmResponse::error(id, code, message),
+        };
+        let empty = result["pending_tasks"].as_array().is_some_and(|t| t.is_empty());
+        if !empty || tokio::time::Instant::now() >= deadline {
+            return SwarmResponse::success(id, result);
+        }
+        tokio::time::sleep(RECEIVE_WAIT_INTERVAL).await;
+    }
+}
+
+/// One `swarm.receive_task` poll: the open tasks the acting agent may work
+/// on, oldest first.
+fn poll_assigned_tasks(
+    params: &serde_json::Value,
+    state: &mut ConnectorState,
+    cursor: Option<u64>,
+) -> Result<serde_json::Value, (ErrorCode, String)> {
+    let my_id = acting_agent(params, state).map_err(|message| (ErrorCode::NotFound, message))?;
     state.mark_member_polled_tasks(my_id.as_str());
-    let my_tier = state.my_tier;
-    let my_tier_level = my_tier.depth();
+    let swarm_filter = params.get("swarm_id").and_then(|v| v.as_str());
+    if let Some(swarm_id) = swarm_filter.filter(|s| !state.is_member_of(s)) {
+        return Err((ErrorCode::NotPermitted, format!("Not a member of swarm: {}", swarm_id)));
+    }
+    let is_self = my_id == state.agent_id;
+    // This connector's own agent holds a tier per joined swarm.
+    let tier_in = |swarm_id: &str| {
+        if is_self {
+            state.tier_in(swarm_id)
+        } else {
+            state
+                .agent_tiers
+                .get(my_id.as_str())
+                .copied()
+                .unwrap_or(Tier::Executor)
+        }
+    };
+    let my_tier = tier_in(swarm_filter.unwrap_or(state.current_swarm_id.as_str()));
 
     let mut tasks: Vec<&Task> = state
         .task_details
@@ -1340,29 +2496,96 @@ async fn handle_receive_task(
 
             if matches!(
                 task.status,
-                TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Rejected
+                TaskStatus::Completed
+                    | TaskStatus::Failed
+                    | TaskStatus::Rejected
+                    | TaskStatus::Disputed
             ) {
                 return false;
             }
 
-            match my_tier {
+            let task_swarm = state.swarm_for_task(&task.task_id);
+            if swarm_filter.is_some_and(|s| s != task_swarm) {
+                return false;
+            }
+            let tier = tier_in(&task_swarm);
+            match tier {
                 Tier::Executor => task.assigned_to.as_ref() == Some(&my_id),
                 _ => {
-                    task.tier_level == my_tier_level
+                    task.tier_level == tier.depth()
                         && (task.assigned_to.is_none() || task.assigned_to.as_ref() == Some(&my_id))
                 }
             }
         })
         .collect();
     tasks.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    let picked_up: Vec<String> = tasks
+        .iter()
+        .filter(|t| t.assigned_to.as_ref() == Some(&my_id))
+        .map(|t| t.task_id.clone())
+        .collect();
     let tasks: Vec<String> = tasks.into_iter().map(|t| t.task_id.clone()).collect();
 
+    let Some(cursor) = cursor else {
+        // Fetched tasks no longer count as queued, so they are not stolen.
+        state.picked_up_tasks.extend(picked_up);
+        return Ok(serde_json::json!({
+            "pending_tasks": tasks,
+            "agent_id": my_id.to_string(),
+            "tier": format!("{:?}", my_tier),
+        }));
+    };
+    // Delivered tasks stay queued until the agent acknowledges them.
+    let batch = state
+        .task_deliveries
+        .deliver(my_id.as_str(), &tasks, cursor, chrono::Utc::now());
+    for task_id in &batch.redelivered {
+        state.push_task_timeline_event(
+            task_id,
+            "redelivered",
+            "Redelivered after going unacknowledged".to_string(),
+            Some(my_id.to_string()),
+        );
+    }
+    Ok(serde_json::json!({
+        "pending_tasks": batch.tasks,
+        "redelivered": batch.redelivered,
+        "next_cursor": batch.next_cursor,
+        "agent_id": my_id.to_string(),
+        "tier": format!("{:?}", my_tier),
+    }))
+}
+
+/// Handle `swarm.ack_task` - confirm that the acting agent started work on
+/// a task `swarm.receive_task` delivered with a cursor.
+async fn handle_ack_task(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".into());
+        }
+    };
+    let mut state = state.write().await;
+    let agent_id = match acting_agent(params, &state) {
+        Ok(agent_id) => agent_id,
+        Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+    };
+    if let Err(e) = state
+        .task_deliveries
+        .ack(&task_id, agent_id.as_str(), chrono::Utc::now())
+    {
+        return SwarmResponse::error(id, ErrorCode::NotFound, e);
+    }
+    state.picked_up_tasks.insert(task_id.clone());
     SwarmResponse::success(
         id,
         serde_json::json!({
-            "pending_tasks": tasks,
-            "agent_id": state.agent_id.to_string(),
-            "tier": format!("{:?}", state.my_tier),
+            "task_id": task_id,
+            "acknowledged": true,
         }),
     )
 }
@@ -1378,7 +2601,7 @@ async fn handle_get_task(
         _ => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing 'task_id' parameter".into(),
             );
         }
@@ -1390,7 +2613,7 @@ async fn handle_get_task(
         None => {
             return SwarmResponse::error(
                 id,
-                -32004,
+                ErrorCode::NotFound,
                 format!("Task not found: {}", task_id),
             );
         }
@@ -1401,10 +2624,267 @@ async fn handle_get_task(
         serde_json::json!({
             "task": task,
             "is_pending": state.task_set.contains(&task.task_id),
+            "pending_approval": state.pending_plan_approvals.get(task_id),
+            "result_schema_violations": state.result_schema_violations.get(task_id),
+            "progress": state.task_progress.get(task_id),
+        }),
+    )
+}
+
+/// The `swarm.search_tasks` query described by `params`.
+fn task_query_from_params(params: &serde_json::Value) -> Result<TaskQuery, String> {
+    let statuses = match params.get("status") {
+        None | Some(serde_json::Value::Null) => Vec::new(),
+        Some(status @ serde_json::Value::String(_)) => vec![status.clone()],
+        Some(serde_json::Value::Array(statuses)) => statuses.clone(),
+        Some(_) => return Err("'status' must be a string or list of strings".into()),
+    }
+    .into_iter()
+    .map(|s| {
+        serde_json::from_value::<TaskStatus>(s.clone())
+            .map_err(|_| format!("Unknown task status: {}", s))
+    })
+    .collect::<Result<_, _>>()?;
+    let text = |key: &str| -> Result<Option<String>, String> {
+        match params.get(key) {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(serde_json::Value::String(s)) if !s.is_empty() => Ok(Some(s.clone())),
+            Some(_) => Err(format!("'{}' must be a non-empty string", key)),
+        }
+    };
+    let time = |key: &str| -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+        text(key)?
+            .map(|t| {
+                chrono::DateTime::parse_from_rfc3339(&t)
+                    .map(|t| t.with_timezone(&chrono::Utc))
+                    .map_err(|e| format!("Invalid '{}' timestamp: {}", key, e))
+            })
+            .transpose()
+    };
+    let tier = match params.get("tier") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(tier) => Some(
+            tier.as_u64()
+                .and_then(|t| u32::try_from(t).ok())
+                .ok_or("'tier' must be a non-negative integer")?,
+        ),
+    };
+    let newest_first = match text("order")?.as_deref() {
+        None | Some("desc") => true,
+        Some("asc") => false,
+        Some(other) => return Err(format!("'order' must be \"asc\" or \"desc\", not {:?}", other)),
+    };
+    let after = text("cursor")?
+        .map(|c| TaskCursor::decode(&c).ok_or("Invalid 'cursor'"))
+        .transpose()?;
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize)
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    Ok(TaskQuery {
+        statuses,
+        assignee: text("assignee")?,
+        tier,
+        parent: text("parent")?,
+        contains: text("contains")?.map(|c| c.to_lowercase()),
+        created_since: time("since")?,
+        created_until: time("until")?,
+        newest_first,
+        after,
+        limit,
+    })
+}
+
+/// Handle `swarm.search_tasks` - one page of the known tasks matching the
+/// optional `status`, `assignee`, `tier`, `parent`, `contains`, `since` and
+/// `until` filters, newest first unless `order` is `"asc"`.
+///
+/// `count` is the number of matching tasks across all pages; pass
+/// `next_cursor` back as `cursor` for the next page.
+async fn handle_search_tasks(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let query = match task_query_from_params(params) {
+        Ok(query) => query,
+        Err(e) => return SwarmResponse::error(id, ErrorCode::InvalidParams, e),
+    };
+
+    // Searching brings the task index up to date, hence the write lock.
+    let mut state = state.write().await;
+    let page = state.task_details.search(&query);
+    let tasks: Vec<&Task> = page
+        .task_ids
+        .iter()
+        .filter_map(|task_id| state.task_details.get(task_id))
+        .collect();
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "tasks": tasks,
+            "count": page.total,
+            "next_cursor": page.next.map(|c| c.encode()),
+        }),
+    )
+}
+
+/// Handle `swarm.get_attachment` - fetch the content of a task attachment.
+///
+/// Params: `{ "cid": <str>, "task_id"?: <str> }`
+/// Returns: `{ "cid", "name", "content_type", "size_bytes", "content" }`.
+/// Only CIDs attached to a known task (to `task_id`, when given) are served.
+async fn handle_get_attachment(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let cid = match params.get("cid").and_then(|v| v.as_str()) {
+        Some(c) if !c.trim().is_empty() => c,
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'cid' parameter".into());
+        }
+    };
+    let task_id = params.get("task_id").and_then(|v| v.as_str());
+
+    let state = state.read().await;
+    let attachment = match task_id {
+        Some(task_id) => match state.task_details.get(task_id) {
+            Some(task) => task.attachments.iter().find(|a| a.cid == cid),
+            None => {
+                return SwarmResponse::error(id, ErrorCode::NotFound, format!("Task not found: {}", task_id));
+            }
+        },
+        None => state
+            .task_details
+            .values()
+            .flat_map(|task| task.attachments.iter())
+            .find(|a| a.cid == cid),
+    };
+    let Some(attachment) = attachment else {
+        return SwarmResponse::error(id, ErrorCode::NotFound, format!("Attachment not found: {}", cid));
+    };
+    let Some(bytes) = state.content_store.get(cid) else {
+        return SwarmResponse::error(
+            id,
+            ErrorCode::NotFound,
+            format!("Attachment content not held by this connector: {}", cid),
+        );
+    };
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "cid": cid,
+            "name": attachment.name,
+            "content_type": attachment.content_type,
+            "size_bytes": bytes.len(),
+            "content": String::from_utf8_lossy(&bytes),
+        }),
+    )
+}
+
+/// Handle `swarm.get_result` - the result of a task, as far as the acting
+/// agent may read it.
+///
+/// Params: `{ "task_id": <str>, "local_agent"?: <did> }`
+/// Returns: `{ "task_id", "visibility", "artifact", "content", "redacted",
+/// "sealed_to" }`. A result sealed to other members comes back with its
+/// artifact's metadata only.
+async fn handle_get_result(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t,
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".into());
+        }
+    };
+
+    let state = state.read().await;
+    let viewer = match acting_agent(params, &state) {
+        Ok(viewer) => viewer,
+        Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+    };
+    let Some(artifact) = state.task_results.get(task_id) else {
+        return SwarmResponse::error(id, ErrorCode::NotFound, format!("No result known for task {}", task_id));
+    };
+    let visibility = state
+        .task_details
+        .get(task_id)
+        .map(|t| t.visibility)
+        .unwrap_or_default();
+    let sealed_to: Option<Vec<&str>> = artifact.sealed.as_ref().map(|s| s.members().collect());
+    let redacted = !state.may_view_result(task_id, viewer.as_str());
+    let mut artifact = artifact.clone();
+    artifact.sealed = None;
+    let content = if redacted {
+        artifact.content.clear();
+        None
+    } else {
+        state
+            .task_result_text
+            .get(task_id)
+            .cloned()
+            .or_else(|| Some(artifact.content.clone()).filter(|c| !c.is_empty()))
+    };
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": task_id,
+            "visibility": visibility,
+            "artifact": artifact,
+            "content": content,
+            "redacted": redacted,
+            "sealed_to": sealed_to,
         }),
     )
 }
 
+/// Handle `swarm.get_task_context` - everything an agent needs to work on a
+/// task, held to a byte budget.
+///
+/// Params: `{ "task_id": <str>, "max_bytes"?: <int>, "local_agent"?: <did> }`
+/// Returns a [`crate::task_context::TaskContext`]; sibling results the
+/// acting agent may not read are left out.
+async fn handle_get_task_context(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t,
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".into());
+        }
+    };
+    let max_bytes = params
+        .get("max_bytes")
+        .and_then(|v| v.as_u64())
+        .map(|n| n as usize)
+        .unwrap_or(crate::task_context::DEFAULT_CONTEXT_BYTES);
+    let limits = crate::task_context::ContextLimits::with_max_bytes(max_bytes);
+
+    let state = state.read().await;
+    let viewer = match acting_agent(params, &state) {
+        Ok(viewer) => viewer,
+        Err(message) => return SwarmResponse::error(id, ErrorCode::NotFound, message),
+    };
+    match state.task_context(task_id, viewer.as_str(), limits) {
+        Some(context) => SwarmResponse::success(
+            id,
+            serde_json::to_value(context).unwrap_or_default(),
+        ),
+        None => SwarmResponse::error(id, ErrorCode::NotFound, format!("Task not found: {}", task_id)),
+    }
+}
+
 /// Handle `swarm.get_task_timeline` - fetch lifecycle events for a task.
 async fn handle_get_task_timeline(
     id: Option<String>,
@@ -1416,7 +2896,7 @@ async fn handle_get_task_timeline(
         _ => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing 'task_id' parameter".into(),
             );
         }
@@ -1430,11 +2910,14 @@ async fn handle_get_task_timeline(
         .min(1000);
 
     let state = state.read().await;
-    let timeline: Vec<TaskTimelineEvent> = state
+    let mut timeline: Vec<TaskTimelineEvent> = state
         .task_timelines
         .get(task_id)
         .cloned()
         .unwrap_or_default();
+    // Hybrid logical clocks order events by cause rather than by the wall
+    // clocks of the nodes involved.
+    timeline.sort_by_key(|event| event.hlc);
     let total = timeline.len();
     let start = total.saturating_sub(limit);
     let events = timeline.into_iter().skip(start).collect::<Vec<_>>();
@@ -1449,54 +2932,754 @@ async fn handle_get_task_timeline(
     )
 }
 
-/// Handle `swarm.get_status` - get connector and agent status.
-async fn handle_get_status(
+/// Handle `swarm.get_task_costs` - report budget and spend for a task tree.
+///
+/// `total_spent` includes every known descendant; `remaining` is the tightest
+/// budget across the task and its ancestors.
+async fn handle_get_task_costs(
     id: Option<String>,
+    params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
 ) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t,
+        _ => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "Missing 'task_id' parameter".into(),
+            );
+        }
+    };
+
     let state = state.read().await;
-    let known_agents = state.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+    let task = match state.task_details.get(task_id) {
+        Some(task) => task,
+        None => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::NotFound,
+                format!("Task not found: {}", task_id),
+            );
+        }
+    };
+
+    let subtasks: Vec<serde_json::Value> = task
+        .subtasks
+        .iter()
+        .filter_map(|sub_id| state.task_details.get(sub_id))
+        .map(|sub| {
+            serde_json::json!({
+                "task_id": sub.task_id,
+                "budget": sub.budget,
+                "own_cost": state.task_costs.get(&sub.task_id).copied().unwrap_or(0.0),
+                "total_spent": state.task_spend(&sub.task_id),
+            })
+        })
+        .collect();
+    let remaining = state.budget_remaining(task_id);
 
     SwarmResponse::success(
         id,
         serde_json::json!({
-            "agent_id": state.agent_id.to_string(),
-            "status": format!("{:?}", state.status),
-            "tier": format!("{:?}", state.my_tier),
-            "epoch": state.epoch_manager.current_epoch(),
-            "parent_id": state.parent_id.as_ref().map(|p| p.to_string()),
-            "active_tasks": state.task_set.len(),
-            "known_agents": known_agents,
-            "content_items": state.content_store.item_count(),
+            "task_id": task_id,
+            "budget": task.budget,
+            "own_cost": state.task_costs.get(task_id).copied().unwrap_or(0.0),
+            "total_spent": state.task_spend(task_id),
+            "remaining": remaining,
+            "exhausted": remaining.is_some_and(|r| r <= 0.0),
+            "subtasks": subtasks,
         }),
     )
 }
 
-/// Generate an obfuscated arithmetic challenge for anti-bot verification.
-/// Returns a VerificationChallenge that is NOT sent in full to the agent
-/// (expected_answer is kept server-side).
-fn generate_verification_challenge() -> VerificationChallenge {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
+/// Handle `swarm.get_sla_stats` - deadline compliance of submitted results
+/// per agent, optionally for one `agent_id`, plus the tasks currently overdue.
+async fn handle_get_sla_stats(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let agent_filter = params.get("agent_id").and_then(|v| v.as_str());
+    let state = state.read().await;
+    let mut agents: Vec<serde_json::Value> = state
+        .agent_activity
+        .iter()
+        .filter(|(agent_id, _)| agent_filter.is_none_or(|f| f == agent_id.as_str()))
+        .filter(|(_, a)| a.results_on_time_count + a.results_late_count > 0)
+        .map(|(agent_id, a)| {
+            serde_json::json!({
+                "agent_id": agent_id,
+                "results_on_time": a.results_on_time_count,
+                "results_late": a.results_late_count,
+                "on_time_pct": a.on_time_pct(),
+                "avg_lateness_secs": a.average_lateness_secs(),
+            })
+        })
+        .collect();
+    agents.sort_by(|a, b| a["agent_id"].as_str().cmp(&b["agent_id"].as_str()));
+    let mut overdue: Vec<serde_json::Value> = state
+        .task_details
+        .values()
+        .filter(|t| matches!(t.status, TaskStatus::Overdue | TaskStatus::Blocked))
+        .filter(|t| {
+            agent_filter.is_none_or(|f| t.assigned_to.as_ref().is_some_and(|a| a.as_str() == f))
+        })
+        .map(|t| {
+            serde_json::json!({
+                "task_id": t.task_id,
+                "assigned_to": t.assigned_to.as_ref().map(|a| a.to_string()),
+                "deadline": t.deadline,
+                "blocked": t.status == TaskStatus::Blocked,
+            })
+        })
+        .collect();
+    overdue.sort_by(|a, b| a["task_id"].as_str().cmp(&b["task_id"].as_str()));
 
-    let a: i64 = rng.gen_range(10..=89);
-    let b: i64 = rng.gen_range(10..=89);
-    let answer = a + b;
+    SwarmResponse::success(
+        id,
+        serde_json::json!({ "agents": agents, "overdue_tasks": overdue }),
+    )
+}
 
-    // Unique challenge code
-    let code = format!("wws_verify_{:016x}", rng.gen::<u64>());
+/// Handle `swarm.get_flow_stats` - task-flow stage counters (the console's
+/// flow panel) plus counts and per-minute rates over a sliding window.
+async fn handle_get_flow_stats(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let window_secs = match params.get("window_secs") {
+        None | Some(serde_json::Value::Null) => DEFAULT_FLOW_WINDOW_SECS,
+        Some(v) => match v.as_u64() {
+            Some(secs) if (1..=MAX_FLOW_WINDOW_SECS).contains(&secs) => secs,
+            _ => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    format!(
+                        "'window_secs' must be an integer between 1 and {}",
+                        MAX_FLOW_WINDOW_SECS
+                    ),
+                );
+            }
+        },
+    };
 
-    // Plain question
-    let plain = format!("what is {} plus {}", a, b);
+    let now = chrono::Utc::now();
+    let state = state.read().await;
+    let totals = state.flow_counts(None);
+    let window = state.flow_counts(Some(now - chrono::Duration::seconds(window_secs as i64)));
+    let minutes = window_secs as f64 / 60.0;
+    let rates: serde_json::Map<String, serde_json::Value> = match serde_json::to_value(&window) {
+        Ok(serde_json::Value::Object(counts)) => counts
+            .into_iter()
+            .map(|(stage, count)| {
+                let rate = count.as_f64().unwrap_or(0.0) / minutes;
+                (stage, serde_json::json!(rate))
+            })
+            .collect(),
+        _ => serde_json::Map::new(),
+    };
 
-    // Garble: random case, random symbol insertion, random intra-word spaces
-    let symbols: &[char] = &['^', '{', '}', '|', '~'];
-    let mut garbled = String::new();
-    for ch in plain.chars() {
-        if ch == ' ' {
-            let r = rng.gen_range(0u8..4);
-            match r {
-                0 => garbled.push(' '),
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "timestamp": now,
+            "totals": totals,
+            "window_secs": window_secs,
+            "window": window,
+            "rates_per_min": rates,
+        }),
+    )
+}
+
+/// Handle `swarm.get_dead_letters` - the most recent inbound messages that
+/// were dropped, optionally only those whose reason starts with `reason`.
+async fn handle_get_dead_letters(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize)
+        .unwrap_or(100)
+        .min(500);
+    let reason = params.get("reason").and_then(|v| v.as_str());
+
+    let state = state.read().await;
+    let matching: Vec<&DeadLetter> = state
+        .dead_letters
+        .iter()
+        .filter(|d| reason.is_none_or(|r| d.reason.starts_with(r)))
+        .collect();
+    let total = matching.len();
+    let letters = matching[total.saturating_sub(limit)..].to_vec();
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "dead_letters": letters,
+            "count": total,
+        }),
+    )
+}
+
+/// Which event log entries `swarm.get_logs` returns.
+#[derive(Debug, Clone, Default)]
+struct LogQuery {
+    /// Empty means every category.
+    categories: Vec<LogCategory>,
+    /// Case-insensitive substring of the message.
+    contains: Option<String>,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl LogQuery {
+    fn from_params(params: &serde_json::Value) -> Result<Self, String> {
+        let categories = match params.get("category") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::String(c)) => vec![c.as_str()],
+            Some(serde_json::Value::Array(cs)) => cs
+                .iter()
+                .map(|c| c.as_str().ok_or("'category' must be a string or list of strings"))
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("'category' must be a string or list of strings".into()),
+        }
+        .into_iter()
+        .map(|c| LogCategory::parse(c).ok_or_else(|| format!("Unknown log category: {}", c)))
+        .collect::<Result<_, _>>()?;
+        let time = |key: &str| -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+            params
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|t| {
+                    chrono::DateTime::parse_from_rfc3339(t)
+                        .map(|t| t.with_timezone(&chrono::Utc))
+                        .map_err(|e| format!("Invalid '{}' timestamp: {}", key, e))
+                })
+                .transpose()
+        };
+        Ok(Self {
+            categories,
+            contains: params
+                .get("contains")
+                .and_then(|v| v.as_str())
+                .filter(|c| !c.is_empty())
+                .map(str::to_lowercase),
+            since: time("since")?,
+            until: time("until")?,
+        })
+    }
+
+    fn matches(&self, entry: &LogEntry) -> bool {
+        (self.categories.is_empty() || self.categories.contains(&entry.category))
+            && self
+                .contains
+                .as_ref()
+                .is_none_or(|c| entry.message.to_lowercase().contains(c.as_str()))
+            && self.since.is_none_or(|t| entry.timestamp >= t)
+            && self.until.is_none_or(|t| entry.timestamp <= t)
+    }
+}
+
+/// Handle `swarm.get_logs` - the most recent event log entries matching
+/// the optional `category`, `contains`, `since` and `until` filters.
+///
+/// `cursor` counts every entry ever logged; with `follow: true` the
+/// connection then streams the entries logged after it.
+async fn handle_get_logs(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let query = match LogQuery::from_params(params) {
+        Ok(query) => query,
+        Err(e) => return SwarmResponse::error(id, ErrorCode::InvalidParams, e),
+    };
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize)
+        .unwrap_or(100)
+        .min(1000);
+
+    let state = state.read().await;
+    let matching: Vec<&LogEntry> = state.event_log.iter().filter(|e| query.matches(e)).collect();
+    let total = matching.len();
+    let entries = matching[total.saturating_sub(limit)..].to_vec();
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "entries": entries,
+            "count": total,
+            "cursor": state.log_count,
+        }),
+    )
+}
+
+/// Handle `swarm.get_peer_scores` - offense counts, current score and ban
+/// state for every peer that misbehaved, lowest score first.
+async fn handle_get_peer_scores(
+    id: Option<String>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    match network_handle.peer_scores().await {
+        Ok(peers) => SwarmResponse::success(
+            id,
+            serde_json::json!({
+                "banned_count": peers.iter().filter(|p| p.banned).count(),
+                "peers": peers,
+            }),
+        ),
+        Err(e) => SwarmResponse::error(id, ErrorCode::OperationFailed, format!("Failed to read peer scores: {}", e)),
+    }
+}
+
+/// Handle `swarm.health`: mesh, DHT, bootstrap, event-loop, channel and
+/// state-lock checks rolled up into `healthy`, `degraded` or `isolated`.
+/// Handle `swarm.describe_errors`: every error code this API and the
+/// direct P2P protocol return, with its name, category and meaning.
+fn handle_describe_errors(id: Option<String>) -> SwarmResponse {
+    let errors: Vec<serde_json::Value> = ErrorCode::ALL
+        .iter()
+        .map(|code| {
+            serde_json::json!({
+                "code": code.code(),
+                "name": code,
+                "category": code.category(),
+                "description": code.description(),
+            })
+        })
+        .collect();
+    SwarmResponse::success(id, serde_json::json!({ "errors": errors }))
+}
+
+async fn handle_health(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    match crate::health::check(state, network_handle).await {
+        Ok(report) => SwarmResponse::success(
+            id,
+            serde_json::to_value(&report).unwrap_or_default(),
+        ),
+        Err(e) => SwarmResponse::error(id, ErrorCode::OperationFailed, format!("Health check failed: {}", e)),
+    }
+}
+
+/// Handle `swarm.reload_config`: re-read the configuration, apply the
+/// settings that can change live and list those that need a restart.
+async fn handle_reload_config(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let mut state = state.write().await;
+    match crate::reload::reload(&mut state) {
+        Ok(report) => SwarmResponse::success(
+            id,
+            serde_json::to_value(&report).unwrap_or_default(),
+        ),
+        Err(e) => SwarmResponse::error(id, ErrorCode::OperationFailed, e),
+    }
+}
+
+/// Handle `swarm.get_partition_report`: the open partition, if any, and
+/// recent healed ones with the task conflicts resolved after each, plus
+/// this node's lineage and its merges with independently bootstrapped views.
+async fn handle_get_partition_report(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let detector = &state.partition_detector;
+    let reports: Vec<_> = detector.reports().collect();
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "partitioned": detector.current().is_some(),
+            "threshold": detector.threshold(),
+            "current": detector.current(),
+            "reports": reports,
+            "lineage": state.merge.lineage(),
+            "merges": state.merge.reports().collect::<Vec<_>>(),
+        }),
+    )
+}
+
+/// Parse the `peer_id` parameter as a libp2p peer ID.
+fn peer_id_param(params: &serde_json::Value) -> Result<wws_network::PeerId, String> {
+    let raw = params
+        .get("peer_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Missing 'peer_id' parameter".to_string())?;
+    // Accept the did:swarm:<peer_id> form used for connector identities too.
+    raw.trim_start_matches("did:swarm:")
+        .parse()
+        .map_err(|e| format!("Invalid 'peer_id': {}", e))
+}
+
+/// Handle `swarm.ban_peer` - disconnect a peer and refuse it for
+/// `duration_secs` (default one hour).
+async fn handle_ban_peer(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let peer = match peer_id_param(params) {
+        Ok(peer) => peer,
+        Err(message) => return SwarmResponse::error(id, ErrorCode::InvalidParams, message),
+    };
+    if peer == network_handle.local_peer_id() {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Cannot ban the local peer".to_string());
+    }
+    let duration_secs = match params.get("duration_secs") {
+        None | Some(serde_json::Value::Null) => DEFAULT_PEER_BAN_SECS,
+        Some(v) => match v.as_u64() {
+            Some(secs) if secs > 0 => secs,
+            _ => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'duration_secs' must be a positive integer".to_string(),
+                );
+            }
+        },
+    };
+    let reason = params
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .unwrap_or("banned by operator")
+        .to_string();
+
+    if let Err(e) = network_handle
+        .ban_peer(peer, Duration::from_secs(duration_secs), reason.clone())
+        .await
+    {
+        return SwarmResponse::error(id, ErrorCode::OperationFailed, format!("Failed to ban peer: {}", e));
+    }
+    {
+        let mut state = state.write().await;
+        let actor = state.agent_id.to_string();
+        state.push_log(
+            crate::tui::LogCategory::System,
+            format!(
+                "AUDIT peer.ban actor={} peer={} duration_secs={} reason={}",
+                actor, peer, duration_secs, reason
+            ),
+        );
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "peer_id": peer.to_string(),
+            "banned": true,
+            "duration_secs": duration_secs,
+            "reason": reason,
+        }),
+    )
+}
+
+/// Handle `swarm.unban_peer` - lift a peer's ban.
+async fn handle_unban_peer(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let peer = match peer_id_param(params) {
+        Ok(peer) => peer,
+        Err(message) => return SwarmResponse::error(id, ErrorCode::InvalidParams, message),
+    };
+    let was_banned = match network_handle.unban_peer(peer).await {
+        Ok(was_banned) => was_banned,
+        Err(e) => return SwarmResponse::error(id, ErrorCode::OperationFailed, format!("Failed to unban peer: {}", e)),
+    };
+    if !was_banned {
+        return SwarmResponse::error(id, ErrorCode::NotFound, format!("Peer {} is not banned", peer));
+    }
+    {
+        let mut state = state.write().await;
+        let actor = state.agent_id.to_string();
+        state.push_log(
+            crate::tui::LogCategory::System,
+            format!("AUDIT peer.unban actor={} peer={}", actor, peer),
+        );
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "peer_id": peer.to_string(),
+            "banned": false,
+        }),
+    )
+}
+
+/// Handle `swarm.init_swarm_params` - issue version 1 of the swarm
+/// parameters, making this node their authority. Unset fields take the
+/// connector's current settings.
+async fn handle_init_swarm_params(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let changes: SwarmParameterChanges = match serde_json::from_value(params.clone()) {
+        Ok(changes) => changes,
+        Err(e) => return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid parameters: {}", e)),
+    };
+    if let Err(message) = crate::swarm_params::validate_changes(&changes) {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, message);
+    }
+
+    let record = {
+        let mut state = state.write().await;
+        if let Some(existing) = &state.swarm_params {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::OperationFailed,
+                format!("Swarm parameters already issued (v{})", existing.version),
+            );
+        }
+        // Version 0 is the unissued baseline; applying the requested
+        // settings to it yields version 1.
+        let mut record = SwarmParameters {
+            swarm_id: state.current_swarm_id.clone(),
+            version: 0,
+            branching_factor: 0,
+            proposal_timeout_secs: state.proposal_stage_timeout_secs() as u64,
+            voting_timeout_secs: state.voting_stage_timeout_secs() as u64,
+            quorum: state.quorum_policy.clone(),
+            creator_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        }
+        .with_changes(&changes, chrono::Utc::now());
+        record.sign(&state.identity_key);
+        state.adopt_swarm_params(record.clone());
+        let actor = state.agent_id.to_string();
+        state.push_log(
+            crate::tui::LogCategory::System,
+            format!("AUDIT swarm.params_init actor={} swarm={}", actor, record.swarm_id),
+        );
+        record
+    };
+    crate::swarm_params::publish_swarm_params(network_handle, &record).await;
+
+    SwarmResponse::success(id, serde_json::json!({ "params": record }))
+}
+
+/// Handle `swarm.get_swarm_params` - the parameters in force, whether this
+/// node is their authority, and open change proposals.
+async fn handle_get_swarm_params(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let mut proposals: Vec<&crate::swarm_params::ParamsProposal> =
+        state.params_proposals.values().collect();
+    proposals.sort_by_key(|p| p.expires_at);
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "params": state.swarm_params,
+            "authority": state.is_params_authority(),
+            "proposals": proposals,
+        }),
+    )
+}
+
+/// Handle `swarm.propose_params_change` - put a parameter change to a vote
+/// of the swarm's active members.
+async fn handle_propose_params_change(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let changes: SwarmParameterChanges = match params
+        .get("changes")
+        .map(|v| serde_json::from_value(v.clone()))
+    {
+        Some(Ok(changes)) => changes,
+        Some(Err(e)) => return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid 'changes': {}", e)),
+        None => return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'changes' parameter".to_string()),
+    };
+    if changes.is_empty() {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "'changes' sets no parameters".to_string());
+    }
+    if let Err(message) = crate::swarm_params::validate_changes(&changes) {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, message);
+    }
+
+    let now = chrono::Utc::now();
+    let (proposal, issued) = {
+        let mut state = state.write().await;
+        let Some(base_version) = state.swarm_params.as_ref().map(|p| p.version) else {
+            return SwarmResponse::error(id, ErrorCode::NotFound, "No swarm parameters issued yet".to_string());
+        };
+        let proposal = ParamsChangeProposeParams {
+            swarm_id: state.current_swarm_id.clone(),
+            proposal_id: uuid::Uuid::new_v4().to_string(),
+            base_version,
+            changes,
+            proposer: state.agent_id.clone(),
+            expires_at: now
+                + chrono::Duration::seconds(crate::swarm_params::PARAMS_PROPOSAL_TTL_SECS),
+        };
+        let issued = state.record_params_proposal(proposal.clone(), now);
+        (proposal, issued)
+    };
+
+    let msg = SwarmMessage::new(
+        ProtocolMethod::ParamsChangePropose.as_str(),
+        serde_json::to_value(&proposal).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let topic = SwarmTopics::params_for(proposal.swarm_id.as_str());
+        let _ = network_handle.publish(&topic, data).await;
+    }
+    if let Some(issued) = &issued {
+        crate::swarm_params::publish_swarm_params(network_handle, issued).await;
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "proposal_id": proposal.proposal_id,
+            "base_version": proposal.base_version,
+            "expires_at": proposal.expires_at,
+            "issued_version": issued.map(|p| p.version),
+        }),
+    )
+}
+
+/// Handle `swarm.vote_params_change` - approve or reject an open parameter
+/// change proposal.
+async fn handle_vote_params_change(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let proposal_id = match params.get("proposal_id").and_then(|v| v.as_str()) {
+        Some(p) => p.to_string(),
+        None => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'proposal_id' parameter".to_string());
+        }
+    };
+    let approve = match params.get("approve").and_then(|v| v.as_bool()) {
+        Some(a) => a,
+        None => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'approve' parameter".to_string());
+        }
+    };
+
+    let (vote, issued, approvals) = {
+        let mut state = state.write().await;
+        let vote = ParamsChangeVoteParams {
+            swarm_id: state.current_swarm_id.clone(),
+            proposal_id: proposal_id.clone(),
+            voter: state.agent_id.clone(),
+            approve,
+        };
+        if !state.params_proposals.contains_key(&proposal_id) {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::NotFound,
+                format!("Unknown or expired parameter proposal: {}", proposal_id),
+            );
+        }
+        let issued = state.record_params_vote(&vote, chrono::Utc::now());
+        // Once the change is issued the proposal is closed.
+        let approvals = state.params_proposals.get(&proposal_id).map(|p| p.approvals());
+        (vote, issued, approvals)
+    };
+
+    let msg = SwarmMessage::new(
+        ProtocolMethod::ParamsChangeVote.as_str(),
+        serde_json::to_value(&vote).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let topic = SwarmTopics::params_for(vote.swarm_id.as_str());
+        let _ = network_handle.publish(&topic, data).await;
+    }
+    if let Some(issued) = &issued {
+        crate::swarm_params::publish_swarm_params(network_handle, issued).await;
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "proposal_id": proposal_id,
+            "approve": approve,
+            "approvals": approvals,
+            "issued_version": issued.map(|p| p.version),
+        }),
+    )
+}
+
+/// Handle `swarm.get_status` - get connector and agent status.
+async fn handle_get_status(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let known_agents = state.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "agent_id": state.agent_id.to_string(),
+            "status": format!("{:?}", state.status),
+            "tier": format!("{:?}", state.my_tier),
+            "epoch": state.epoch_manager.current_epoch(),
+            "epoch_duration_secs": state.epoch_manager.epoch_duration_secs(),
+            "epoch_remaining_secs": state.epoch_manager.remaining_time().num_seconds(),
+            "parent_id": state.parent_id.as_ref().map(|p| p.to_string()),
+            "active_tasks": state.task_set.len(),
+            "known_agents": known_agents,
+            "content_items": state.content_store.item_count(),
+        }),
+    )
+}
+
+/// Generate an obfuscated arithmetic challenge for anti-bot verification.
+/// Returns a VerificationChallenge that is NOT sent in full to the agent
+/// (expected_answer is kept server-side).
+fn generate_verification_challenge() -> VerificationChallenge {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+
+    let a: i64 = rng.gen_range(10..=89);
+    let b: i64 = rng.gen_range(10..=89);
+    let answer = a + b;
+
+    // Unique challenge code
+    let code = format!("wws_verify_{:016x}", rng.gen::<u64>());
+
+    // Plain question
+    let plain = format!("what is {} plus {}", a, b);
+
+    // Garble: random case, random symbol insertion, random intra-word spaces
+    let symbols: &[char] = &['^', '{', '}', '|', '~'];
+    let mut garbled = String::new();
+    for ch in plain.chars() {
+        if ch == ' ' {
+            let r = rng.gen_range(0u8..4);
+            match r {
+                0 => garbled.push(' '),
                 1 => {
                     let s = symbols[rng.gen_range(0..symbols.len())];
                     garbled.push(s);
@@ -1618,15 +3801,57 @@ async fn handle_register_agent(
         _ => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing 'agent_id' parameter".into(),
             );
         }
     };
 
-    let (known_agents, canonical_agent_id, swarm_id, epoch, hierarchy_assignments, announced_name) = {
+    let role = match params.get("role") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(v) => match v.as_str().and_then(LayoutRole::parse) {
+            Some(role) => Some(role),
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "Invalid 'role' parameter (expected \"coordinator\" or \"executor\")".into(),
+                );
+            }
+        },
+    };
+
+    let services = match params.get("services") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(value) => {
+            let descriptors: Vec<crate::did::ServiceDescriptor> =
+                match serde_json::from_value(value.clone()) {
+                    Ok(descriptors) => descriptors,
+                    Err(e) => {
+                        return SwarmResponse::error(
+                            id,
+                            ErrorCode::InvalidParams,
+                            format!("Invalid 'services' parameter: {}", e),
+                        );
+                    }
+                };
+            let agent_id = state.read().await.agent_id.to_string();
+            match crate::did::agent_services(&agent_id, &descriptors) {
+                Ok(services) => Some(services),
+                Err(e) => return SwarmResponse::error(id, ErrorCode::InvalidParams, e),
+            }
+        }
+    };
+
+    let (known_agents, canonical_agent_id, swarm_id, epoch, hierarchy_assignments, announced_name, role, services) = {
         let mut state = state.write().await;
         let canonical_agent_id = state.agent_id.to_string();
+        if let Some(role) = role {
+            state.agent_roles.insert(canonical_agent_id.clone(), role);
+        }
+        if let Some(services) = services {
+            state.register_services(services);
+        }
         let requested_name = if requested_agent_id.starts_with("did:swarm:") {
             None
         } else {
@@ -1647,74 +3872,9 @@ async fn handle_register_agent(
         let mut hierarchy_assignments = Vec::new();
 
         if swarm_size >= 2 {
+            state.recompute_hierarchy(&active_members);
             let mut sorted_agents: Vec<String> = active_members;
             sorted_agents.sort();
-
-            state.agent_tiers.clear();
-            state.agent_parents.clear();
-            state.subordinates.clear();
-
-            let k = dynamic_branching_factor(swarm_size) as usize;
-            let distribution = wws_hierarchy::PyramidAllocator::distribute(swarm_size, k as u64);
-            let tier_sizes: Vec<usize> = distribution.tiers.iter().map(|n| *n as usize).collect();
-            let levels = tier_sizes.len().max(1);
-
-            let mut offsets = Vec::with_capacity(levels + 1);
-            offsets.push(0usize);
-            for size in &tier_sizes {
-                let prev = *offsets.last().unwrap_or(&0);
-                offsets.push(prev + *size);
-            }
-
-            for level in 0..levels {
-                let start = offsets[level];
-                let end = *offsets.get(level + 1).unwrap_or(&start);
-                for idx in start..end.min(sorted_agents.len()) {
-                    let member_id = sorted_agents[idx].clone();
-                    let tier = if levels == 1 {
-                        Tier::Executor
-                    } else if level == (levels - 1) {
-                        Tier::Executor
-                    } else if level == 0 {
-                        Tier::Tier1
-                    } else if level == 1 {
-                        Tier::Tier2
-                    } else {
-                        Tier::TierN((level + 1) as u32)
-                    };
-                    state.agent_tiers.insert(member_id, tier);
-                }
-            }
-
-            for level in 1..levels {
-                let child_start = offsets[level];
-                let child_end = *offsets.get(level + 1).unwrap_or(&child_start);
-                let parent_start = offsets[level - 1];
-                let parent_end = *offsets.get(level).unwrap_or(&parent_start);
-                let parent_count = parent_end.saturating_sub(parent_start);
-                if parent_count == 0 {
-                    continue;
-                }
-                for child_idx in child_start..child_end.min(sorted_agents.len()) {
-                    let local_child_idx = child_idx.saturating_sub(child_start);
-                    let mut parent_local_idx = local_child_idx / k.max(1);
-                    if parent_local_idx >= parent_count {
-                        parent_local_idx = parent_count - 1;
-                    }
-                    let parent_idx = parent_start + parent_local_idx;
-                    if let (Some(child_id), Some(parent_id)) =
-                        (sorted_agents.get(child_idx), sorted_agents.get(parent_idx))
-                    {
-                        state.agent_parents.insert(child_id.clone(), parent_id.clone());
-                        state
-                            .subordinates
-                            .entry(parent_id.clone())
-                            .or_default()
-                            .push(child_id.clone());
-                    }
-                }
-            }
-
             for member_id in &sorted_agents {
                 let tier = state
                     .agent_tiers
@@ -1724,26 +3884,6 @@ async fn handle_register_agent(
                 let parent = state.agent_parents.get(member_id).cloned();
                 hierarchy_assignments.push((member_id.clone(), tier, parent));
             }
-
-            let my_agent_id = state.agent_id.as_str().to_string();
-            if let Some(my_tier) = state.agent_tiers.get(&my_agent_id).copied() {
-                state.my_tier = my_tier;
-                state.parent_id = state.agent_parents.get(&my_agent_id).cloned().map(AgentId::new);
-                state.network_stats.my_tier = my_tier;
-                state.network_stats.parent_id = state.parent_id.clone();
-                state.network_stats.subordinate_count = state
-                    .subordinates
-                    .get(&my_agent_id)
-                    .map(|s| s.len())
-                    .unwrap_or(0) as u32;
-            }
-            state.network_stats.hierarchy_depth = levels as u32;
-            state.current_layout = wws_hierarchy::PyramidAllocator::new(wws_hierarchy::pyramid::PyramidConfig {
-                branching_factor: k as u32,
-                max_depth: wws_protocol::MAX_HIERARCHY_DEPTH,
-            })
-            .compute_layout(swarm_size)
-            .ok();
         }
 
         let announced_name = if let Some(existing) = state.agent_names.get(&canonical_agent_id) {
@@ -1757,6 +3897,11 @@ async fn handle_register_agent(
         } else {
             Some(requested_agent_id.clone())
         };
+        let role = state.agent_roles.get(&canonical_agent_id).copied();
+        let services = state
+            .agent_services
+            .get(&canonical_agent_id)
+            .map(|a| a.services.clone());
 
         (
             state.active_member_count(staleness),
@@ -1765,6 +3910,8 @@ async fn handle_register_agent(
             state.epoch_manager.current_epoch(),
             hierarchy_assignments,
             announced_name,
+            role,
+            services,
         )
     };
 
@@ -1774,7 +3921,13 @@ async fn handle_register_agent(
         agent_name: announced_name,
         last_task_poll_at: None,
         last_result_at: None,
+        load: None,
+        role,
         epoch,
+        epoch_duration_secs: None,
+        topic_key: None,
+        lineage: None,
+        services: services.clone(),
         timestamp: chrono::Utc::now(),
     };
     let msg = SwarmMessage::new(
@@ -1787,42 +3940,341 @@ async fn handle_register_agent(
         let _ = network_handle.publish(&topic, data).await;
     }
 
-    // Broadcast tier assignments if hierarchy was recomputed
-    if !hierarchy_assignments.is_empty() {
-        let branch_size = dynamic_branching_factor(known_agents as u64);
-        for (member_id, tier, parent) in hierarchy_assignments {
-            let params = TierAssignmentParams {
-                assigned_agent: AgentId::new(member_id),
-                tier,
-                parent_id: parent.map(|p| AgentId::new(p)).unwrap_or_else(|| AgentId::new("root".to_string())),
-                epoch,
-                branch_size,
-            };
+    // Broadcast tier assignments if hierarchy was recomputed. Only Tier-1
+    // agents may issue them; peers drop anyone else's.
+    let tier_assignments: Vec<TierAssignmentParams> = {
+        let state = state.read().await;
+        if state.my_tier == Tier::Tier1 {
+            let branch_size = state.branching_factor(known_agents as u64);
+            hierarchy_assignments
+                .into_iter()
+                .map(|(member_id, tier, parent)| {
+                    state.signed_tier_assignment(
+                        AgentId::new(member_id),
+                        tier,
+                        parent.map(|p| AgentId::new(p)).unwrap_or_else(|| AgentId::new("root".to_string())),
+                        epoch,
+                        branch_size,
+                    )
+                })
+                .collect()
+        } else {
+            Vec::new()
+        }
+    };
+    for params in tier_assignments {
+        let msg = SwarmMessage::new(
+            ProtocolMethod::TierAssignment.as_str(),
+            serde_json::to_value(&params).unwrap_or_default(),
+            String::new(),
+        );
+
+        if let Ok(data) = serde_json::to_vec(&msg) {
+            let topic = SwarmTopics::hierarchy_for(&swarm_id);
+            let _ = network_handle.publish(&topic, data).await;
+        }
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "registered": true,
+            "agent_id": canonical_agent_id,
+            "requested_agent_id": requested_agent_id,
+            "known_agents": known_agents,
+            "role": role.map(|r| r.as_str()),
+            "services": services.unwrap_or_default(),
+        }),
+    )
+}
 
-            let msg = SwarmMessage::new(
-                ProtocolMethod::TierAssignment.as_str(),
-                serde_json::to_value(&params).unwrap_or_default(),
-                String::new(),
+/// Handle `swarm.get_agent_endpoints` - the services an agent offers, from
+/// its keep-alives or else its DID document, looked up in the DHT when
+/// neither is known (or with `refresh: true`). `type` keeps only services
+/// of that type.
+async fn handle_get_agent_endpoints(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let agent_id = match params.get("agent_id").and_then(|v| v.as_str()) {
+        Some(a) if a.starts_with("did:swarm:") => a,
+        _ => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "agent_id parameter required (did:swarm:...)".to_string(),
             );
+        }
+    };
+    let service_type = params.get("type").and_then(|v| v.as_str());
+    let refresh = params.get("refresh").and_then(|v| v.as_bool()).unwrap_or(false);
 
-            if let Ok(data) = serde_json::to_vec(&msg) {
-                let topic = SwarmTopics::hierarchy_for(&swarm_id);
-                let _ = network_handle.publish(&topic, data).await;
+    let held = {
+        let state = state.read().await;
+        state
+            .services_of(agent_id)
+            .map(|_| state.did_documents.get(agent_id).map(|d| d.updated))
+    };
+    if held.is_none() || refresh {
+        let held = held.flatten();
+        crate::did::lookup(network_handle, agent_id).await;
+        let deadline = tokio::time::Instant::now() + DID_RESOLVE_TIMEOUT;
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            if state.read().await.did_documents.get(agent_id).map(|d| d.updated) != held {
+                break;
             }
         }
     }
 
+    let state = state.read().await;
+    match state.services_of(agent_id) {
+        Some((services, source)) => {
+            let endpoints: Vec<ServiceEndpoint> = services
+                .into_iter()
+                .filter(|s| service_type.is_none_or(|t| s.service_type == t))
+                .collect();
+            SwarmResponse::success(
+                id,
+                serde_json::json!({
+                    "agent_id": agent_id,
+                    "endpoints": endpoints,
+                    "source": source,
+                }),
+            )
+        }
+        None => SwarmResponse::error(
+            id,
+            ErrorCode::NotFound,
+            format!("No endpoints known for {}", agent_id),
+        ),
+    }
+}
+
+/// Handle `swarm.get_reputation` - an agent's score and the signed
+/// attestations behind it. Defaults to this node's agent.
+async fn handle_get_reputation(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let agent_id = params
+        .get("agent_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| state.agent_id.to_string());
+    let attestations = state.reputation.attestations_of(&agent_id);
+    let prevailing: Vec<String> = state
+        .reputation
+        .prevailing(&agent_id)
+        .into_iter()
+        .map(|a| a.signature.clone())
+        .collect();
+    let attestations: Vec<serde_json::Value> = attestations
+        .into_iter()
+        .map(|a| {
+            let mut value = serde_json::to_value(a).unwrap_or_default();
+            value["prevailing"] = serde_json::json!(prevailing.contains(&a.signature));
+            value
+        })
+        .collect();
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "agent_id": agent_id,
+            "score": state.reputation_of(&agent_id),
+            "source": if state.reputation.score(&agent_id).is_some() { "ledger" } else { "local_activity" },
+            "conflicts": state.reputation.conflicts_of(&agent_id),
+            "attestations": attestations,
+        }),
+    )
+}
+
+/// The agent an RPC acts for: the local agent whose DID is given as
+/// `local_agent`, or the connector's own agent when it is absent.
+fn acting_agent(params: &serde_json::Value, state: &ConnectorState) -> Result<AgentId, String> {
+    match params.get("local_agent").and_then(|v| v.as_str()) {
+        None => Ok(state.agent_id.clone()),
+        Some(did) => state
+            .local_agents
+            .get(did)
+            .map(|agent| agent.agent_id.clone())
+            .ok_or_else(|| format!("Unknown local agent: {}", did)),
+    }
+}
+
+/// Handle `swarm.register_local_agent` - add a local agent with its own key
+/// and DID. Registering a name again returns the same agent.
+async fn handle_register_local_agent(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let name = match params.get("name").and_then(|v| v.as_str()) {
+        Some(n) => n.trim().to_string(),
+        None => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'name' parameter".to_string());
+        }
+    };
+
+    let (agent, swarm_id, keepalive, document) = {
+        let mut state = state.write().await;
+        let agent = match state.local_agents.register(&name, chrono::Utc::now()) {
+            Ok(agent) => agent.clone(),
+            Err(e @ crate::cluster::ClusterError::InvalidName(_)) => {
+                return SwarmResponse::error(id, ErrorCode::InvalidParams, e.to_string());
+            }
+            Err(e) => return SwarmResponse::error(id, ErrorCode::OperationFailed, e.to_string()),
+        };
+        let document = agent.did_document(Vec::new());
+        state.did_documents.insert(document.clone());
+        state.mark_member_seen_with_name(agent.agent_id.as_str(), Some(&agent.name));
+        state.push_log(
+            crate::tui::LogCategory::System,
+            format!("Local agent registered: {} ({})", agent.name, agent.agent_id),
+        );
+        let params = KeepAliveParams {
+            agent_id: agent.agent_id.clone(),
+            agent_name: Some(agent.name.clone()),
+            last_task_poll_at: None,
+            last_result_at: None,
+            load: Some(AgentLoad::default()),
+            role: state.agent_roles.get(agent.agent_id.as_str()).copied(),
+            epoch: state.epoch_manager.current_epoch(),
+            epoch_duration_secs: None,
+            topic_key: None,
+            lineage: None,
+            services: None,
+            timestamp: chrono::Utc::now(),
+        };
+        let keepalive = state.message_as(
+            &agent.agent_id,
+            ProtocolMethod::AgentKeepAlive.as_str(),
+            serde_json::to_value(&params).unwrap_or_default(),
+        );
+        (agent, state.current_swarm_id.as_str().to_string(), keepalive, document)
+    };
+
+    crate::did::publish(network_handle, &document).await;
+    if let Ok(data) = serde_json::to_vec(&keepalive) {
+        let topic = SwarmTopics::keepalive_for(&swarm_id);
+        let _ = network_handle.publish(&topic, data).await;
+    }
+
     SwarmResponse::success(
         id,
         serde_json::json!({
             "registered": true,
-            "agent_id": canonical_agent_id,
-            "requested_agent_id": requested_agent_id,
-            "known_agents": known_agents,
+            "agent_id": agent.agent_id,
+            "name": agent.name,
+        }),
+    )
+}
+
+/// Handle `swarm.list_local_agents` - local agents sharing this connector,
+/// with how many open tasks each has queued.
+async fn handle_list_local_agents(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let agents: Vec<serde_json::Value> = state
+        .local_agents
+        .agents()
+        .map(|agent| {
+            let queued = state
+                .task_details
+                .values()
+                .filter(|t| {
+                    t.assigned_to.as_ref() == Some(&agent.agent_id)
+                        && state.task_set.contains(&t.task_id)
+                        && !matches!(
+                            t.status,
+                            TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Rejected
+                        )
+                })
+                .count();
+            let tier = state
+                .agent_tiers
+                .get(agent.agent_id.as_str())
+                .copied()
+                .unwrap_or(Tier::Executor);
+            serde_json::json!({
+                "agent_id": agent.agent_id,
+                "name": agent.name,
+                "registered_at": agent.registered_at,
+                "tier": format!("{:?}", tier),
+                "queued_tasks": queued,
+                "last_task_poll_at": state.member_last_task_poll.get(agent.agent_id.as_str()),
+            })
+        })
+        .collect();
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "connector_agent_id": state.agent_id,
+            "enabled": state.local_agents.is_enabled(),
+            "agents": agents,
         }),
     )
 }
 
+/// Handle `swarm.list_capabilities` - every declared or advertised
+/// capability with its namespace, aliases and the agents holding it.
+///
+/// With `required` (a list of capabilities), also `matches`: each known
+/// agent's coverage of them, best first.
+async fn handle_list_capabilities(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let required: Option<Vec<String>> = match params.get("required") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(value) => match serde_json::from_value(value.clone()) {
+            Ok(required) => Some(required),
+            Err(_) => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'required' must be a list of strings".into(),
+                );
+            }
+        },
+    };
+
+    let state = state.read().await;
+    let mut result = serde_json::json!({
+        "capabilities": state.capabilities.entries(&state.agent_capabilities),
+    });
+    if let Some(required) = required {
+        let mut matches: Vec<(&String, f64)> = state
+            .agent_capabilities
+            .iter()
+            .map(|(agent, held)| (agent, state.capabilities.coverage(held, &required)))
+            .collect();
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        result["required"] = serde_json::json!(state.capabilities.canonicalize(&required));
+        result["matches"] = matches
+            .into_iter()
+            .map(|(agent, coverage)| {
+                serde_json::json!({
+                    "agent_id": agent,
+                    "coverage": coverage,
+                    "missing": state.capabilities.missing(&state.agent_capabilities[agent], &required),
+                })
+            })
+            .collect();
+    }
+
+    SwarmResponse::success(id, result)
+}
+
 /// Handle `swarm.verify_agent` - verify a pending anti-bot challenge.
 async fn handle_verify_agent(
     id: Option<String>,
@@ -1850,7 +4302,7 @@ async fn handle_verify_agent(
     };
 
     if agent_id.is_empty() || code.is_empty() {
-        return SwarmResponse::error(id, -32602, "missing agent_id or code".to_string());
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "missing agent_id or code".to_string());
     }
 
     let mut state_w = state.write().await;
@@ -1866,13 +4318,13 @@ async fn handle_verify_agent(
                     serde_json::json!({ "verified": true, "agent_id": agent_id }),
                 );
             }
-            return SwarmResponse::error(id, -32000, "no_pending_challenge".to_string());
+            return SwarmResponse::error(id, ErrorCode::OperationFailed, "no_pending_challenge".to_string());
         }
     };
 
     if challenge.code != code {
         drop(state_w);
-        return SwarmResponse::error(id, -32000, "invalid_code".to_string());
+        return SwarmResponse::error(id, ErrorCode::OperationFailed, "invalid_code".to_string());
     }
 
     if challenge.expected_answer != answer {
@@ -1883,7 +4335,7 @@ async fn handle_verify_agent(
             answer
         );
         drop(state_w);
-        return SwarmResponse::error(id, -32000, "invalid_answer".to_string());
+        return SwarmResponse::error(id, ErrorCode::OperationFailed, "invalid_answer".to_string());
     }
 
     // Clean up and mark verified
@@ -1903,11 +4355,6 @@ async fn handle_verify_agent(
     )
 }
 
-fn dynamic_branching_factor(swarm_size: u64) -> u64 {
-    let approx = (swarm_size as f64).sqrt().round() as u64;
-    approx.clamp(3, 10)
-}
-
 fn tier_from_level(level: u32) -> Tier {
     match level {
         1 => Tier::Tier1,
@@ -1959,11 +4406,31 @@ async fn handle_list_swarms(
         })
         .collect();
 
+    let memberships: Vec<serde_json::Value> = state
+        .joined_swarm_ids()
+        .iter()
+        .map(|swarm_id| {
+            let membership = state.memberships.get(swarm_id);
+            let parent_id = if swarm_id == state.current_swarm_id.as_str() {
+                state.parent_id.as_ref()
+            } else {
+                membership.and_then(|m| m.parent_id.as_ref())
+            };
+            serde_json::json!({
+                "swarm_id": swarm_id,
+                "tier": format!("{:?}", state.tier_in(swarm_id)),
+                "parent_id": parent_id.map(|p| p.to_string()),
+                "task_count": membership.map_or(0, |m| m.task_ids.len()),
+            })
+        })
+        .collect();
+
     SwarmResponse::success(
         id,
         serde_json::json!({
             "swarms": swarms,
             "current_swarm": state.current_swarm_id.as_str(),
+            "memberships": memberships,
         }),
     )
 }
@@ -1973,13 +4440,14 @@ async fn handle_create_swarm(
     id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(n) => n.to_string(),
         None => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing 'name' parameter".into(),
             );
         }
@@ -1990,93 +4458,633 @@ async fn handle_create_swarm(
         None => {
             return SwarmResponse::error(
                 id,
-                -32602,
-                "Missing 'secret' parameter".into(),
+                ErrorCode::InvalidParams,
+                "Missing 'secret' parameter".into(),
+            );
+        }
+    };
+
+    let swarm_id = SwarmId::generate();
+    let token = SwarmToken::generate(&swarm_id, &secret);
+
+    let record = SwarmRecord {
+        swarm_id: swarm_id.clone(),
+        name: name.clone(),
+        is_public: false,
+        agent_count: 1,
+        joined: false,
+        last_seen: chrono::Utc::now(),
+    };
+
+    {
+        let mut state = state.write().await;
+        state
+            .known_swarms
+            .insert(swarm_id.as_str().to_string(), record);
+        state.join_swarm(swarm_id.as_str());
+    }
+    for topic in crate::membership::member_topics(swarm_id.as_str()) {
+        if let Err(e) = network_handle.subscribe(&topic).await {
+            tracing::debug!(error = %e, topic = %topic, "Failed to subscribe swarm topic");
+        }
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "swarm_id": swarm_id.as_str(),
+            "token": token.as_str(),
+            "name": name,
+        }),
+    )
+}
+
+/// Handle `swarm.join_swarm` - join an existing swarm.
+async fn handle_join_swarm(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let swarm_id_str = match params.get("swarm_id").and_then(|v| v.as_str()) {
+        Some(s) => s.to_string(),
+        None => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "Missing 'swarm_id' parameter".into(),
+            );
+        }
+    };
+
+    let token = params.get("token").and_then(|v| v.as_str()).map(String::from);
+
+    let newly_joined = {
+        let mut state = state.write().await;
+
+        let record = match state.known_swarms.get(&swarm_id_str) {
+            Some(r) => r,
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::NotPermitted,
+                    format!("Unknown swarm: {}", swarm_id_str),
+                );
+            }
+        };
+
+        // Private swarms require a token.
+        if !record.is_public && token.is_none() {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "Token required for private swarm".into(),
+            );
+        }
+
+        let newly_joined = state.join_swarm(&swarm_id_str);
+        if newly_joined {
+            state.push_log(
+                crate::tui::LogCategory::Swarm,
+                format!("Joined swarm {}", swarm_id_str),
+            );
+        }
+        newly_joined
+    };
+
+    if newly_joined {
+        for topic in crate::membership::member_topics(&swarm_id_str) {
+            if let Err(e) = network_handle.subscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to subscribe swarm topic");
+            }
+        }
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "swarm_id": swarm_id_str,
+            "joined": true,
+        }),
+    )
+}
+
+/// Handle `swarm.leave_swarm` - leave a swarm joined alongside the primary one.
+async fn handle_leave_swarm(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let Some(swarm_id) = params.get("swarm_id").and_then(|v| v.as_str()) else {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'swarm_id' parameter".into());
+    };
+
+    let membership = {
+        let mut state = state.write().await;
+        match state.leave_swarm(swarm_id) {
+            Ok(membership) => {
+                state.push_log(
+                    crate::tui::LogCategory::Swarm,
+                    format!("Left swarm {}", swarm_id),
+                );
+                membership
+            }
+            Err(e) => return SwarmResponse::error(id, ErrorCode::NotPermitted, e),
+        }
+    };
+
+    // The public swarm's topics are core topics, kept for discovery.
+    if swarm_id != wws_protocol::DEFAULT_SWARM_ID {
+        for topic in crate::membership::member_topics(swarm_id) {
+            if let Err(e) = network_handle.unsubscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to unsubscribe swarm topic");
+            }
+        }
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "swarm_id": swarm_id,
+            "left": true,
+            "tasks_dropped": membership.task_ids.len(),
+        }),
+    )
+}
+
+/// Handle `swarm.migrate_swarm` - as the swarm's parameter authority, move
+/// every member into another swarm. With `dry_run` only the report of what
+/// would move is returned.
+async fn handle_migrate_swarm(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let Some(target) = params.get("target_swarm_id").and_then(|v| v.as_str()) else {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'target_swarm_id' parameter".into());
+    };
+    let token = params
+        .get("token")
+        .and_then(|v| v.as_str())
+        .map(|t| SwarmToken::new(t.to_string()));
+    let dry_run = params.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let (migration, report) = {
+        let state = state.read().await;
+        let report = match state.migration_report(target) {
+            Ok(report) => report,
+            Err(e) => return SwarmResponse::error(id, ErrorCode::NotPermitted, e),
+        };
+        if dry_run {
+            return SwarmResponse::success(
+                id,
+                serde_json::json!({ "dry_run": true, "report": report }),
+            );
+        }
+        if !report.authorized {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::OperationFailed,
+                "Only the swarm's parameter authority can migrate it".into(),
+            );
+        }
+        if !report.target_public && token.is_none() {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Token required for private swarm".into());
+        }
+        let mut migration = SwarmMigrateParams {
+            migration_id: uuid::Uuid::new_v4().to_string(),
+            source_swarm_id: state.current_swarm_id.clone(),
+            target_swarm_id: SwarmId::new(target.to_string()),
+            target_token: token,
+            creator_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        migration.sign(&state.identity_key);
+        (migration, report)
+    };
+
+    let msg = SwarmMessage::new(
+        ProtocolMethod::SwarmMigrate.as_str(),
+        serde_json::to_value(&migration).unwrap_or_default(),
+        String::new(),
+    );
+    let topic = SwarmTopics::params_for(migration.source_swarm_id.as_str());
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            return SwarmResponse::error(id, ErrorCode::OperationFailed, format!("Failed to publish migration: {}", e));
+        }
+    }
+
+    let outcome = {
+        let mut state = state.write().await;
+        let actor = state.agent_id.to_string();
+        state.push_log(
+            crate::tui::LogCategory::System,
+            format!(
+                "AUDIT swarm.migrate actor={} from={} to={} migration_id={}",
+                actor, migration.source_swarm_id, target, migration.migration_id
+            ),
+        );
+        match state.apply_migration(migration) {
+            Ok(outcome) => outcome,
+            Err(e) => return SwarmResponse::error(id, ErrorCode::OperationFailed, e),
+        }
+    };
+    let migration_id = outcome.migration.migration_id.clone();
+    crate::migration::complete_migration(outcome, state, network_handle).await;
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "dry_run": false,
+            "migration_id": migration_id,
+            "report": report,
+        }),
+    )
+}
+
+/// Handle `swarm.get_shard_status`: the sharding policy, whether the
+/// primary swarm is past its threshold, the federation links this node
+/// holds and recent splits.
+async fn handle_get_shard_status(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let active = state.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "swarm_id": state.current_swarm_id.as_str(),
+            "threshold": state.shards.threshold(),
+            "strategy": state.shards.strategy(),
+            "active_members": active,
+            "oversized": state.shards.is_oversized(active),
+            "issuer": state.shard_issuer(),
+            "links": state.shards.links().collect::<Vec<_>>(),
+            "shards": state.shards.reports().collect::<Vec<_>>(),
+        }),
+    )
+}
+
+/// Gateways of the federation link to `swarm_id` other than this node,
+/// active ones first.
+fn link_gateways(state: &ConnectorState, swarm_id: &str) -> Option<Vec<AgentId>> {
+    let link = state.shards.link(swarm_id)?;
+    let active = state.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+    let mut gateways: Vec<AgentId> = link
+        .gateways
+        .iter()
+        .filter(|gateway| **gateway != state.agent_id)
+        .cloned()
+        .collect();
+    gateways.sort_by_key(|gateway| !active.contains(&gateway.to_string()));
+    Some(gateways)
+}
+
+/// Ask `gateways` in turn to inject the task of `request` until one
+/// answers, and return it with its `swarm.inject_task` result. A gateway
+/// that refuses the task ends the search.
+async fn delegate_through_gateways(
+    gateways: Vec<AgentId>,
+    request: TaskDelegationParams,
+    network_handle: &wws_network::SwarmHandle,
+) -> Result<(AgentId, serde_json::Value), (i32, String)> {
+    let mut failures = Vec::new();
+    for gateway in gateways {
+        let reply = match crate::shard::delegate_via_gateway(&gateway, request.clone(), network_handle).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                failures.push(e);
+                continue;
+            }
+        };
+        let response = match serde_json::from_value::<SwarmResponse>(reply) {
+            Ok(response) => response,
+            Err(e) => {
+                failures.push(format!("Gateway {} answered malformed: {}", gateway, e));
+                continue;
+            }
+        };
+        if let Some(error) = response.error {
+            return Err((
+                error.code,
+                format!("Gateway {} refused the task: {}", gateway, error.message),
+            ));
+        }
+        return Ok((gateway, response.result.unwrap_or_default()));
+    }
+    Err((
+        ErrorCode::OperationFailed.code(),
+        if failures.is_empty() {
+            format!("No gateway to swarm {}", request.target_swarm_id)
+        } else {
+            failures.join("; ")
+        },
+    ))
+}
+
+/// Handle `swarm.delegate_task`: inject a task into a swarm federated with
+/// this connector's. A member of that swarm injects it itself; otherwise
+/// the link's gateways are asked in turn, active ones first, until one
+/// answers.
+async fn handle_delegate_task(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let Some(target) = params.get("swarm_id").and_then(|v| v.as_str()) else {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'swarm_id' parameter".into());
+    };
+    if params.get("description").and_then(|v| v.as_str()).is_none() {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'description' parameter".into());
+    }
+
+    let (is_member, request, gateways) = {
+        let state = state.read().await;
+        let Some(gateways) = link_gateways(&state, target) else {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::NotPermitted,
+                format!("No federation link to swarm: {}", target),
             );
-        }
-    };
-
-    let swarm_id = SwarmId::generate();
-    let token = SwarmToken::generate(&swarm_id, &secret);
-
-    let record = SwarmRecord {
-        swarm_id: swarm_id.clone(),
-        name: name.clone(),
-        is_public: false,
-        agent_count: 1,
-        joined: true,
-        last_seen: chrono::Utc::now(),
+        };
+        let request = TaskDelegationParams {
+            sender: state.agent_id.clone(),
+            source_swarm_id: state.current_swarm_id.clone(),
+            target_swarm_id: SwarmId::new(target.to_string()),
+            task: params.clone(),
+            relay_to: None,
+        };
+        (state.is_member_of(target), request, gateways)
     };
-
-    {
-        let mut state = state.write().await;
-        state
-            .known_swarms
-            .insert(swarm_id.as_str().to_string(), record);
+    if is_member {
+        return handle_inject_task(id, params, state, network_handle).await;
     }
 
-    SwarmResponse::success(
-        id,
-        serde_json::json!({
-            "swarm_id": swarm_id.as_str(),
-            "token": token.as_str(),
-            "name": name,
-        }),
-    )
+    match delegate_through_gateways(gateways, request, network_handle).await {
+        Ok((gateway, mut result)) => {
+            if let Some(map) = result.as_object_mut() {
+                map.insert("gateway".to_string(), serde_json::json!(gateway));
+            }
+            state.write().await.push_log(
+                crate::tui::LogCategory::Swarm,
+                format!("Delegated task to swarm {} through {}", target, gateway),
+            );
+            SwarmResponse::success(id, result)
+        }
+        Err((code, message)) => SwarmResponse::error(id, code, message),
+    }
 }
 
-/// Handle `swarm.join_swarm` - join an existing swarm.
-async fn handle_join_swarm(
+/// Handle `swarm.delegate_subtask`: hand a subtask of a task this
+/// connector's agent coordinates to another swarm, through a bridge that is
+/// a member of both. The bridge injects the task there. Here, a remote node
+/// assigned to the bridge joins the parent's subtasks and completes with
+/// the result the bridge relays.
+///
+/// Params: `{ "parent_task_id": <str>, "swarm_id": <str>, "description": <str>,
+///            "bridge"?: <did>, ...other swarm.inject_task params }`
+/// Without `bridge`, this connector bridges when it is a member of the
+/// other swarm, and the federation link's gateways otherwise.
+async fn handle_delegate_subtask(
     id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
-    let swarm_id_str = match params.get("swarm_id").and_then(|v| v.as_str()) {
-        Some(s) => s.to_string(),
-        None => {
+    let Some(parent_id) = params.get("parent_task_id").and_then(|v| v.as_str()) else {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'parent_task_id' parameter".into());
+    };
+    let Some(target) = params.get("swarm_id").and_then(|v| v.as_str()) else {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'swarm_id' parameter".into());
+    };
+    let Some(description) = params.get("description").and_then(|v| v.as_str()) else {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'description' parameter".into());
+    };
+    if params.get("local_agent").is_some() {
+        return SwarmResponse::error(
+            id,
+            ErrorCode::Forbidden,
+            "Only the connector's own agent delegates subtasks".into(),
+        );
+    }
+    let named_bridge = params
+        .get("bridge")
+        .and_then(|v| v.as_str())
+        .map(|bridge| AgentId::new(bridge.to_string()));
+
+    let (my_id, origin, mut node, bridges) = {
+        let state = state.read().await;
+        let Some(parent) = state.task_details.get(parent_id) else {
+            return SwarmResponse::error(id, ErrorCode::NotFound, format!("Task not found: {}", parent_id));
+        };
+        if !state.coordinates(&state.agent_id, parent_id) {
             return SwarmResponse::error(
                 id,
-                -32602,
-                "Missing 'swarm_id' parameter".into(),
+                ErrorCode::NotPermitted,
+                format!("Task {} is not coordinated by {}", parent_id, state.agent_id),
             );
         }
-    };
-
-    let token = params.get("token").and_then(|v| v.as_str()).map(String::from);
-
-    let mut state = state.write().await;
-
-    let record = match state.known_swarms.get_mut(&swarm_id_str) {
-        Some(r) => r,
-        None => {
+        if matches!(
+            parent.status,
+            TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Rejected
+        ) {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                format!("Task {} is already {:?}", parent_id, parent.status),
+            );
+        }
+        let origin = state.swarm_for_task(parent_id);
+        if origin == target {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                format!("Task {} already runs in swarm {}", parent_id, target),
+            );
+        }
+        let bridges = match named_bridge {
+            Some(bridge) => vec![bridge],
+            None if state.is_member_of(target) => vec![state.agent_id.clone()],
+            None => link_gateways(&state, target).unwrap_or_default(),
+        };
+        if bridges.is_empty() {
             return SwarmResponse::error(
                 id,
-                -32001,
-                format!("Unknown swarm: {}", swarm_id_str),
+                ErrorCode::NotPermitted,
+                format!("No bridge to swarm {}: name a member of both swarms as 'bridge'", target),
             );
         }
+        let mut node = Task::new(
+            description.to_string(),
+            parent.tier_level + 1,
+            state.epoch_manager.current_epoch(),
+        );
+        node.parent_task_id = Some(parent_id.to_string());
+        node.status = TaskStatus::InProgress;
+        node.deadline = Some(state.subtask_deadline(parent_id, chrono::Utc::now()));
+        node.visibility = parent.visibility;
+        (state.agent_id.clone(), origin, node, bridges)
     };
 
-    // Private swarms require a token.
-    if !record.is_public && token.is_none() {
+    let mut task_params = params.clone();
+    if let Some(map) = task_params.as_object_mut() {
+        map.remove("parent_task_id");
+        map.remove("bridge");
+    }
+    let (bridge, result) = if bridges[0] == my_id {
+        let response = handle_inject_task(None, &task_params, state, network_handle).await;
+        if let Some(error) = response.error {
+            return SwarmResponse::error(id, error.code, error.message);
+        }
+        (my_id.clone(), response.result.unwrap_or_default())
+    } else {
+        let request = TaskDelegationParams {
+            sender: my_id.clone(),
+            source_swarm_id: SwarmId::new(origin.clone()),
+            target_swarm_id: SwarmId::new(target.to_string()),
+            task: task_params,
+            relay_to: Some(node.task_id.clone()),
+        };
+        match delegate_through_gateways(bridges, request, network_handle).await {
+            Ok(delegated) => delegated,
+            Err((code, message)) => return SwarmResponse::error(id, code, message),
+        }
+    };
+    let Some(remote_task_id) = result.get("task_id").and_then(|v| v.as_str()).map(str::to_string) else {
         return SwarmResponse::error(
             id,
-            -32602,
-            "Token required for private swarm".into(),
+            ErrorCode::OperationFailed,
+            format!("Bridge {} returned no task ID", bridge),
         );
-    }
+    };
+
+    let node_id = node.task_id.clone();
+    node.assigned_to = Some(bridge.clone());
+    node.remote = Some(RemoteExecution {
+        swarm_id: SwarmId::new(target.to_string()),
+        bridge: bridge.clone(),
+        task_id: remote_task_id.clone(),
+    });
+    let assignment = {
+        let mut state = state.write().await;
+        if bridge == my_id {
+            crate::delegation::track_relay(&mut state, &remote_task_id, target, &origin, &node_id, my_id.clone());
+        }
+        state.set_task_swarm(&node_id, &origin);
+        state.task_details.insert(node_id.clone(), node.clone());
+        if let Some(parent) = state.task_details.get_mut(parent_id) {
+            parent.subtasks.push(node_id.clone());
+        }
+        state.push_task_timeline_event(
+            &node_id,
+            "delegated",
+            format!("Runs in swarm {} as {} through {}", target, remote_task_id, bridge),
+            Some(my_id.to_string()),
+        );
+        state.push_task_timeline_event(
+            parent_id,
+            "subtask_delegated",
+            format!("Subtask {} delegated to swarm {}", node_id, target),
+            Some(my_id.to_string()),
+        );
+        state.push_log(
+            crate::tui::LogCategory::Task,
+            format!(
+                "Delegated subtask {} of {} to swarm {} through {} (remote task {})",
+                node_id, parent_id, target, bridge, remote_task_id
+            ),
+        );
+        state.signed_assignment(
+            node.clone(),
+            bridge.clone(),
+            parent_id.to_string(),
+            crate::delegation::REMOTE_PLAN_ID,
+        )
+    };
 
-    record.joined = true;
+    let msg = SwarmMessage::new(
+        ProtocolMethod::TaskAssignment.as_str(),
+        serde_json::to_value(&assignment).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let topic = SwarmTopics::tasks_for(&origin, node.tier_level);
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            tracing::debug!(error = %e, "Failed to publish remote subtask");
+        }
+    }
+    let results_topic = SwarmTopics::results_for(&origin, &node_id);
+    if let Err(e) = network_handle.subscribe(&results_topic).await {
+        tracing::debug!(error = %e, topic = %results_topic, "Failed to subscribe results topic");
+    }
 
     SwarmResponse::success(
         id,
         serde_json::json!({
-            "swarm_id": swarm_id_str,
-            "joined": true,
+            "task_id": node_id,
+            "parent_task_id": parent_id,
+            "swarm_id": target,
+            "remote_task_id": remote_task_id,
+            "bridge": bridge,
         }),
     )
 }
 
+/// Parse the `attachments` of `swarm.inject_task`: each entry has a `name`,
+/// an optional `content_type`, and either inline `content` or the `cid` of
+/// content peers already hold. Inline content is returned alongside its
+/// attachment; its CID is filled in once stored.
+fn parse_attachments(
+    value: &serde_json::Value,
+) -> Result<Vec<(wws_protocol::TaskAttachment, Option<String>)>, String> {
+    let entries = value
+        .as_array()
+        .ok_or_else(|| "'attachments' must be a list".to_string())?;
+    let mut attachments = Vec::with_capacity(entries.len());
+    let mut inline_bytes = 0;
+    for entry in entries {
+        let name = entry
+            .get("name")
+            .and_then(|v| v.as_str())
+            .filter(|n| !n.trim().is_empty())
+            .ok_or_else(|| "Each attachment needs a non-empty 'name'".to_string())?;
+        let content = entry.get("content").and_then(|v| v.as_str());
+        let cid = entry.get("cid").and_then(|v| v.as_str()).filter(|c| !c.is_empty());
+        if content.is_some() == cid.is_some() {
+            return Err(format!(
+                "Attachment '{}' needs exactly one of 'content' or 'cid'",
+                name
+            ));
+        }
+        inline_bytes += content.map_or(0, str::len);
+        attachments.push((
+            wws_protocol::TaskAttachment {
+                name: name.to_string(),
+                cid: cid.unwrap_or_default().to_string(),
+                size_bytes: 0,
+                content_type: entry
+                    .get("content_type")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+            },
+            content.map(str::to_string),
+        ));
+    }
+    if inline_bytes > MAX_INLINE_ATTACHMENT_BYTES {
+        return Err(format!(
+            "Inline attachment content totals {} bytes; the limit is {}",
+            inline_bytes, MAX_INLINE_ATTACHMENT_BYTES
+        ));
+    }
+    Ok(attachments)
+}
+
 /// Handle `swarm.inject_task` - inject a task into the swarm from the operator/external source.
 pub(crate) async fn handle_inject_task(
     id: Option<String>,
@@ -2089,15 +5097,29 @@ pub(crate) async fn handle_inject_task(
         None => {
             return SwarmResponse::error(
                 id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "Missing 'description' parameter".into(),
             );
         }
     };
 
+    let tier_level = params
+        .get("tier_level")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32)
+        .filter(|v| *v >= 1)
+        .unwrap_or(1);
+
     let mut state_guard = state.write().await;
+    let swarm_id = match params.get("swarm_id").and_then(|v| v.as_str()) {
+        Some(s) => match state_guard.require_membership(s) {
+            Ok(()) => s.to_string(),
+            Err(e) => return SwarmResponse::error(id, ErrorCode::NotPermitted, e),
+        },
+        None => state_guard.current_swarm_id.as_str().to_string(),
+    };
     let epoch = state_guard.epoch_manager.current_epoch();
-    let mut task = wws_protocol::Task::new(description.clone(), 1, epoch);
+    let mut task = wws_protocol::Task::new(description.clone(), tier_level, epoch);
     // Accept an optional pre-specified task_id (for multi-node injection with same ID)
     if let Some(v) = params.get("task_id").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
         task.task_id = v.to_string();
@@ -2121,7 +5143,292 @@ pub(crate) async fn handle_inject_task(
     if let Some(arr) = params.get("tools_available").and_then(|v| v.as_array()) {
         task.tools_available = arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect();
     }
+    if let Some(v) = params.get("requires_approval").and_then(|v| v.as_bool()) {
+        task.requires_approval = v;
+    }
+    if let Some(v) = params.get("priority").and_then(|v| v.as_u64()) {
+        task.priority = v.min(u8::MAX as u64) as u8;
+    }
+    if let Some(v) = params
+        .get("budget")
+        .and_then(|v| v.as_f64())
+        .filter(|b| b.is_finite() && *b >= 0.0)
+    {
+        task.budget = Some(v);
+    }
+    if let Some(v) = params.get("deadline_secs").filter(|v| !v.is_null()) {
+        let deadline = v
+            .as_u64()
+            .filter(|secs| *secs > 0)
+            .and_then(|secs| crate::connector::deadline_after(task.created_at, secs));
+        match deadline {
+            Some(deadline) => task.deadline = Some(deadline),
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'deadline_secs' must be a positive integer within range".into(),
+                );
+            }
+        }
+    }
+    if let Some(v) = params.get("quorum").filter(|v| !v.is_null()) {
+        match serde_json::from_value::<wws_protocol::QuorumPolicy>(v.clone()) {
+            Ok(policy) => task.quorum = Some(policy),
+            Err(e) => {
+                return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid 'quorum' parameter: {}", e));
+            }
+        }
+    }
+    if let Some(v) = params.get("aggregation").filter(|v| !v.is_null()) {
+        match serde_json::from_value::<AggregationStrategy>(v.clone()) {
+            Ok(strategy) => task.aggregation = strategy,
+            Err(_) => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'aggregation' must be one of concatenate, json_merge, chair_synthesis".into(),
+                );
+            }
+        }
+    }
+    if let Some(v) = params.get("visibility").filter(|v| !v.is_null()) {
+        match serde_json::from_value::<ResultVisibility>(v.clone()) {
+            Ok(visibility) => task.visibility = visibility,
+            Err(_) => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'visibility' must be one of public, tier_and_above, board_only".into(),
+                );
+            }
+        }
+    }
+    if let Some(v) = params.get("assignment").filter(|v| !v.is_null()) {
+        match serde_json::from_value::<AssignmentStrategy>(v.clone()) {
+            Ok(strategy) => task.assignment = Some(strategy),
+            Err(_) => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "'assignment' must be one of round_robin, least_loaded, capability_score, reputation_weighted, random_seeded".into(),
+                );
+            }
+        }
+    }
+    if let Some(v) = params.get("result_schema").filter(|v| !v.is_null()) {
+        match serde_json::from_value::<ResultSchema>(v.clone()) {
+            Ok(schema)
+                if schema
+                    .json_schema
+                    .as_ref()
+                    .is_some_and(|s| !s.is_object() && !s.is_boolean()) =>
+            {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::InvalidParams,
+                    "Invalid 'result_schema': json_schema must be an object".into(),
+                );
+            }
+            Ok(schema) => task.result_schema = Some(schema),
+            Err(e) => {
+                return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid 'result_schema' parameter: {}", e));
+            }
+        }
+    }
+    if let Some(deps) = params.get("depends_on").filter(|v| !v.is_null()) {
+        let Some(deps) = deps.as_array() else {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "'depends_on' must be a list of task IDs".into());
+        };
+        for dep in deps {
+            match dep.as_str().filter(|d| !d.is_empty()) {
+                Some(dep) if !task.depends_on.iter().any(|d| d == dep) => {
+                    task.depends_on.push(dep.to_string());
+                }
+                Some(_) => {}
+                None => {
+                    return SwarmResponse::error(
+                        id,
+                        ErrorCode::InvalidParams,
+                        "'depends_on' must be a list of task IDs".into(),
+                    );
+                }
+            }
+        }
+    }
+    if task.depends_on.contains(&task.task_id) {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "A task cannot depend on itself".into());
+    }
+    if let Some(missing) = task
+        .depends_on
+        .iter()
+        .find(|d| !state_guard.task_details.contains_key(d))
+    {
+        return SwarmResponse::error(
+            id,
+            ErrorCode::NotFound,
+            format!("Prerequisite task not found: {}", missing),
+        );
+    }
+    let attachments = match params.get("attachments").filter(|v| !v.is_null()) {
+        Some(v) => match parse_attachments(v) {
+            Ok(attachments) => attachments,
+            Err(e) => return SwarmResponse::error(id, ErrorCode::InvalidParams, e),
+        },
+        None => Vec::new(),
+    };
+    for (mut attachment, content) in attachments {
+        match content {
+            Some(content) => {
+                attachment.cid = state_guard.store_attachment(&content);
+                attachment.size_bytes = content.len() as u64;
+            }
+            None => {
+                if let Some(bytes) = state_guard.content_store.get(&attachment.cid) {
+                    attachment.size_bytes = bytes.len() as u64;
+                }
+            }
+        }
+        task.attachments.push(attachment);
+    }
+    let task_id = task.task_id.clone();
+    state_guard.set_task_swarm(&task_id, &swarm_id);
+
+    let waiting_on = state_guard.unfinished_prerequisites(&task);
+    if !waiting_on.is_empty() {
+        state_guard.hold_task(task);
+        return SwarmResponse::success(
+            id,
+            serde_json::json!({
+                "task_id": task_id,
+                "swarm_id": swarm_id,
+                "description": description,
+                "epoch": epoch,
+                "injected": false,
+                "held": true,
+                "waiting_on": waiting_on,
+            }),
+        );
+    }
+    state_guard.attach_prerequisite_context(&mut task);
+    drop(state_guard);
+
+    dispatch_task(task, state, network_handle).await;
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": task_id,
+            "swarm_id": swarm_id,
+            "description": description,
+            "epoch": epoch,
+            "injected": true,
+        }),
+    )
+}
+
+/// Reassign an open subtask to `assignee` on behalf of this connector's
+/// operator and publish the new assignment on the task's tier topic.
+pub(crate) async fn reassign_task(
+    task_id: &str,
+    assignee: &str,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> Result<TaskAssignmentParams, String> {
+    let (swarm_id, assignment) = {
+        let mut state = state.write().await;
+        let actor = state.agent_id.to_string();
+        let assignment = state.reassign_task(task_id, assignee, &actor)?;
+        (state.swarm_for_task(task_id), assignment)
+    };
+    let topic = SwarmTopics::tasks_for(&swarm_id, assignment.task.tier_level);
+    let msg = SwarmMessage::new(
+        ProtocolMethod::TaskAssignment.as_str(),
+        serde_json::to_value(&assignment).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            tracing::debug!(error = %e, "Failed to publish reassignment");
+        }
+    }
+    Ok(assignment)
+}
+
+/// Hand a blocked task back to its assignee with a fresh deadline and
+/// publish the assignment to the task's swarm.
+pub(crate) async fn retry_task(
+    task_id: &str,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> Result<TaskAssignmentParams, String> {
+    let (swarm_id, assignment) = {
+        let mut state = state.write().await;
+        let actor = state.agent_id.to_string();
+        let assignment = state.retry_blocked_task(task_id, &actor)?;
+        (state.swarm_for_task(task_id), assignment)
+    };
+    let topic = SwarmTopics::tasks_for(&swarm_id, assignment.task.tier_level);
+    let msg = SwarmMessage::new(
+        ProtocolMethod::TaskAssignment.as_str(),
+        serde_json::to_value(&assignment).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            tracing::debug!(error = %e, "Failed to publish retried assignment");
+        }
+    }
+    Ok(assignment)
+}
+
+/// Start the RFP of `task` here when it is at this connector's tier.
+pub(crate) fn init_local_rfp(state_guard: &mut ConnectorState, task: &wws_protocol::Task) {
+    let task_id = task.task_id.clone();
+    let epoch = task.epoch;
+    let swarm_id = state_guard.swarm_for_task(&task_id);
+    let my_tier = state_guard.tier_in(&swarm_id);
+    let my_level = my_tier.depth();
+    if my_tier != Tier::Executor && my_level == task.tier_level {
+        let tier_members: Vec<String> = state_guard
+            .active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
+            .into_iter()
+            .filter(|id| state_guard.agent_tiers.get(id).copied().unwrap_or(Tier::Executor) == my_tier)
+            .collect();
+        let requirement = state_guard.quorum_requirement(&task_id, task.tier_level, &tier_members);
+        let expected_participants = requirement.expected_proposers;
+
+        let mut rfp = wws_consensus::RfpCoordinator::new(
+            task_id.clone(),
+            epoch,
+            expected_participants,
+        );
+        if let Err(e) = rfp.inject_task(task) {
+            tracing::warn!(error = %e, task_id = %task_id, "Failed to initialize local RFP on inject");
+        } else {
+            state_guard.rfp_coordinators.insert(task_id.clone(), rfp);
+            state_guard.task_vote_requirements.insert(task_id.clone(), requirement);
+            state_guard.push_log(
+                crate::tui::LogCategory::Task,
+                format!(
+                    "Local RFP initialized for injected task {} (tier {:?}, expected participants: {})",
+                    task_id, my_tier, expected_participants
+                ),
+            );
+        }
+    }
+}
+
+/// Record a fully built task locally, start its RFP when it is at this
+/// connector's tier, and publish it to the swarm.
+pub(crate) async fn dispatch_task(
+    task: wws_protocol::Task,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) {
+    let mut state_guard = state.write().await;
     let task_id = task.task_id.clone();
+    let description = task.description.clone();
 
     // Add task to the local task set (CRDT).
     state_guard.task_set.add(task_id.clone());
@@ -2148,47 +5455,16 @@ pub(crate) async fn handle_inject_task(
         ),
     );
 
-    let my_tier = state_guard.my_tier;
-    let my_level = my_tier.depth();
-    if my_tier != Tier::Executor && my_level == task.tier_level {
-        let expected_participants = state_guard
-            .active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
-            .into_iter()
-            .filter(|id| state_guard.agent_tiers.get(id).copied().unwrap_or(Tier::Executor) == my_tier)
-            .count()
-            .max(1);
-
-        let mut rfp = wws_consensus::RfpCoordinator::new(
-            task_id.clone(),
-            epoch,
-            expected_participants,
-        );
-        if let Err(e) = rfp.inject_task(&task) {
-            tracing::warn!(error = %e, task_id = %task_id, "Failed to initialize local RFP on inject");
-        } else {
-            state_guard.rfp_coordinators.insert(task_id.clone(), rfp);
-            state_guard.task_vote_requirements.insert(
-                task_id.clone(),
-                TaskVoteRequirement {
-                    expected_proposers: expected_participants,
-                    expected_voters: expected_participants,
-                    tier_level: task.tier_level,
-                },
-            );
-            state_guard.push_log(
-                crate::tui::LogCategory::Task,
-                format!(
-                    "Local RFP initialized for injected task {} (tier {:?}, expected participants: {})",
-                    task_id, my_tier, expected_participants
-                ),
-            );
-        }
-    }
+    let swarm_id = state_guard.swarm_for_task(&task_id);
+    init_local_rfp(&mut state_guard, &task);
+    state_guard.record_tender(&task_id, chrono::Utc::now());
 
     // Publish task injection to the swarm network.
     let inject_params = TaskInjectionParams {
         task: task.clone(),
         originator: state_guard.agent_id.clone(),
+        attachments: state_guard.attachment_blobs(&task),
+        attempt: 0,
     };
 
     let msg = SwarmMessage::new(
@@ -2197,11 +5473,10 @@ pub(crate) async fn handle_inject_task(
         String::new(),
     );
 
-    let swarm_id = state_guard.current_swarm_id.as_str().to_string();
     drop(state_guard);
 
     if let Ok(data) = serde_json::to_vec(&msg) {
-        let topic = SwarmTopics::tasks_for(&swarm_id, 1);
+        let topic = SwarmTopics::tasks_for(&swarm_id, task.tier_level);
         if let Err(e) = network_handle.publish(&topic, data).await {
             tracing::debug!(error = %e, "Failed to publish task injection");
         }
@@ -2221,13 +5496,207 @@ pub(crate) async fn handle_inject_task(
         }
     }
 
+}
+
+/// Handle `swarm.save_task_template` - register or replace a named task template.
+///
+/// Params: `{ "name": <str>, "description": <str>, "tier_level"?: <u32>,
+///            "capabilities_required"?: [<str>], "priority"?: <u8> }`
+/// Returns: `{ "name": <str>, "saved": true, "replaced": <bool> }`
+async fn handle_save_task_template(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let name = match params.get("name").and_then(|v| v.as_str()) {
+        Some(n) if !n.trim().is_empty() && !n.contains(char::is_whitespace) => n.to_string(),
+        _ => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "Missing or invalid 'name' parameter (must be non-empty, no whitespace)".into(),
+            );
+        }
+    };
+
+    let template: crate::config::TaskTemplate = match serde_json::from_value(params.clone()) {
+        Ok(t) => t,
+        Err(e) => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid template: {}", e));
+        }
+    };
+    if template.tier_level == 0 {
+        return SwarmResponse::error(id, ErrorCode::InvalidParams, "'tier_level' must be at least 1".into());
+    }
+
+    let mut state = state.write().await;
+    let replaced = state.task_templates.insert(name.clone(), template).is_some();
+    state.push_log(
+        crate::tui::LogCategory::System,
+        format!("Task template saved: {} (replaced: {})", name, replaced),
+    );
+
     SwarmResponse::success(
         id,
         serde_json::json!({
-            "task_id": task_id,
-            "description": description,
-            "epoch": epoch,
-            "injected": true,
+            "name": name,
+            "saved": true,
+            "replaced": replaced,
+        }),
+    )
+}
+
+/// Handle `swarm.inject_from_template` - inject a task built from a named template.
+///
+/// Any extra params (e.g. `requires_approval`, `task_id`) are passed through to
+/// `swarm.inject_task` and override the template's values.
+async fn handle_inject_from_template(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let name = match params.get("name").and_then(|v| v.as_str()) {
+        Some(n) if !n.trim().is_empty() => n.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'name' parameter".into());
+        }
+    };
+
+    let template = {
+        let state = state.read().await;
+        state.task_templates.get(&name).cloned()
+    };
+    let Some(template) = template else {
+        return SwarmResponse::error(id, ErrorCode::NotFound, format!("Task template not found: {}", name));
+    };
+
+    let mut inject_params = template.to_inject_params();
+    if let (Some(target), Some(overrides)) = (inject_params.as_object_mut(), params.as_object()) {
+        for (key, value) in overrides {
+            if key != "name" {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    handle_inject_task(id, &inject_params, state, network_handle).await
+}
+
+/// Handle `swarm.schedule_task` - inject a task on a recurring cron schedule.
+///
+/// Params: `{ "cron": <str>, "schedule_id"?: <str>, "template"?: <str> }` plus,
+/// when no template name is given, the template fields (`description`,
+/// `tier_level`, `capabilities_required`, `priority`).
+/// Returns: `{ "schedule_id": <str>, "next_run": <datetime> }`
+async fn handle_schedule_task(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let cron = match params.get("cron").and_then(|v| v.as_str()) {
+        Some(c) if !c.trim().is_empty() => c.to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'cron' parameter".into());
+        }
+    };
+    let schedule_id = params
+        .get("schedule_id")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let mut state = state.write().await;
+    let task = match params.get("template").and_then(|v| v.as_str()) {
+        Some(name) => match state.task_templates.get(name) {
+            Some(t) => t.clone(),
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::NotFound,
+                    format!("Task template not found: {}", name),
+                );
+            }
+        },
+        None => match serde_json::from_value::<crate::config::TaskTemplate>(params.clone()) {
+            Ok(t) => t,
+            Err(e) => {
+                return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid scheduled task: {}", e));
+            }
+        },
+    };
+
+    let schedule = match crate::scheduler::TaskSchedule::new(
+        schedule_id.clone(),
+        &cron,
+        task,
+        chrono::Utc::now(),
+    ) {
+        Ok(s) => s,
+        Err(e) => return SwarmResponse::error(id, ErrorCode::InvalidParams, e.to_string()),
+    };
+    let next_run = schedule.next_run;
+    if let Err(e) = state.scheduler.add(schedule) {
+        return SwarmResponse::error(id, ErrorCode::OperationFailed, e.to_string());
+    }
+    state.push_log(
+        crate::tui::LogCategory::System,
+        format!("Schedule {} created ({})", schedule_id, cron),
+    );
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "schedule_id": schedule_id,
+            "next_run": next_run,
+        }),
+    )
+}
+
+/// Handle `swarm.list_schedules` - list recurring schedules ordered by next run.
+async fn handle_list_schedules(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let schedules = state.scheduler.list();
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "schedules": schedules,
+            "count": schedules.len(),
+        }),
+    )
+}
+
+/// Handle `swarm.cancel_schedule` - stop a recurring schedule.
+async fn handle_cancel_schedule(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let schedule_id = match params.get("schedule_id").and_then(|v| v.as_str()) {
+        Some(s) if !s.trim().is_empty() => s.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'schedule_id' parameter".into());
+        }
+    };
+
+    let mut state = state.write().await;
+    if state.scheduler.cancel(&schedule_id).is_none() {
+        return SwarmResponse::error(id, ErrorCode::NotFound, format!("Schedule not found: {}", schedule_id));
+    }
+    state.push_log(
+        crate::tui::LogCategory::System,
+        format!("Schedule {} cancelled", schedule_id),
+    );
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "schedule_id": schedule_id,
+            "cancelled": true,
         }),
     )
 }
@@ -2296,6 +5765,7 @@ async fn handle_get_board_status(
             "depth": h.depth,
             "parent_holon": h.parent_holon,
             "child_holons": h.child_holons,
+            "child_artifacts": h.child_artifacts,
             "status": format!("{:?}", h.status),
             "created_at": h.created_at,
         })
@@ -2303,6 +5773,171 @@ async fn handle_get_board_status(
     SwarmResponse::success(request_id, serde_json::json!({ "holons": holons }))
 }
 
+/// Handle `swarm.form_board` - broadcast `board.invite` for a task with this
+/// agent as chair. The connector announces `board.ready` once enough agents
+/// accept, or dissolves the board when the acceptance window closes.
+async fn handle_form_board(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+
+    let (invite, swarm_id) = {
+        let mut state = state.write().await;
+        let Some(task) = state.task_details.get(&task_id) else {
+            return SwarmResponse::error(id, ErrorCode::NotFound, format!("Task not found: {}", task_id));
+        };
+        if state.board_formations.contains_key(&task_id) {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::OperationFailed,
+                format!("Board for task {} is already forming", task_id),
+            );
+        }
+        let required_capabilities = params
+            .get("required_capabilities")
+            .and_then(|v| v.as_array())
+            .map(|caps| {
+                caps.iter()
+                    .filter_map(|c| c.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_else(|| task.capabilities_required.clone());
+        let invite = BoardInviteParams {
+            task_id: task_id.clone(),
+            task_digest: task.description.chars().take(280).collect(),
+            complexity_estimate: params
+                .get("complexity_estimate")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.5),
+            depth: params.get("depth").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+            required_capabilities,
+            capacity: params
+                .get("capacity")
+                .and_then(|v| v.as_u64())
+                .map(|c| c as usize)
+                .unwrap_or(crate::board::DEFAULT_BOARD_CAPACITY),
+            chair: state.agent_id.clone(),
+            private: params.get("private").and_then(|v| v.as_bool()).unwrap_or(false),
+        };
+        state.open_board_formation(invite.clone());
+        let swarm_id = state.swarm_for_task(&invite.task_id);
+        (invite, swarm_id)
+    };
+
+    let topic = SwarmTopics::board_for(&swarm_id, &task_id);
+    let _ = network_handle.subscribe(&topic).await;
+    let msg = SwarmMessage::new(
+        ProtocolMethod::BoardInvite.as_str(),
+        serde_json::to_value(&invite).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let _ = network_handle.publish(&topic, data).await;
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": task_id,
+            "capacity": invite.capacity,
+            "required_capabilities": invite.required_capabilities,
+            "private": invite.private,
+            "invited": true,
+        }),
+    )
+}
+
+/// Handle `swarm.answer_board_invite` - accept or decline a board invitation
+/// on behalf of this agent.
+async fn handle_answer_board_invite(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+    let accept = params.get("accept").and_then(|v| v.as_bool()).unwrap_or(true);
+
+    let (method, payload, swarm_id) = {
+        let mut state = state.write().await;
+        let forming = state
+            .active_holons
+            .get(&task_id)
+            .is_some_and(|h| h.status == HolonStatus::Forming);
+        if !forming {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::NotFound,
+                format!("No board invitation open for task {}", task_id),
+            );
+        }
+        let agent_id = state.agent_id.clone();
+        let swarm_id = state.swarm_for_task(&task_id);
+        if accept {
+            let active_tasks = state
+                .task_details
+                .values()
+                .filter(|t| t.assigned_to.as_ref() == Some(&agent_id))
+                .filter(|t| !matches!(t.status, TaskStatus::Completed | TaskStatus::Rejected))
+                .count() as u32;
+            // A private board seals its group key to this key in `board.ready`.
+            let channel_key = state.board_channels.get_mut(&task_id).map(|channel| {
+                let keypair = channel.keypair.get_or_insert_with(ChannelKeypair::generate);
+                keypair.public_key_hex()
+            });
+            let accept_params = BoardAcceptParams {
+                task_id: task_id.clone(),
+                agent_id,
+                active_tasks,
+                capabilities: params
+                    .get("capabilities")
+                    .and_then(|v| serde_json::from_value(v.clone()).ok())
+                    .unwrap_or_default(),
+                affinity_scores: params
+                    .get("affinity_scores")
+                    .and_then(|v| serde_json::from_value(v.clone()).ok())
+                    .unwrap_or_default(),
+                channel_key,
+            };
+            state.note_board_accept(&accept_params);
+            (
+                ProtocolMethod::BoardAccept,
+                serde_json::to_value(&accept_params).unwrap_or_default(),
+                swarm_id,
+            )
+        } else {
+            let decline = BoardDeclineParams { task_id: task_id.clone(), agent_id };
+            state.record_board_decline(&decline);
+            (
+                ProtocolMethod::BoardDecline,
+                serde_json::to_value(&decline).unwrap_or_default(),
+                swarm_id,
+            )
+        }
+    };
+
+    let msg = SwarmMessage::new(method.as_str(), payload, String::new());
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let topic = SwarmTopics::board_for(&swarm_id, &task_id);
+        let _ = network_handle.publish(&topic, data).await;
+    }
+
+    SwarmResponse::success(id, serde_json::json!({ "task_id": task_id, "accepted": accept }))
+}
+
 /// Handle `swarm.get_deliberation` - returns deliberation messages for a task.
 async fn handle_get_deliberation(
     request_id: Option<String>,
@@ -2311,19 +5946,35 @@ async fn handle_get_deliberation(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
-        None => return SwarmResponse::error(request_id, -32602, "task_id required".to_string()),
+        None => return SwarmResponse::error(request_id, ErrorCode::InvalidParams, "task_id required".to_string()),
     };
     let state = state.read().await;
+    let viewer = match acting_agent(params, &state) {
+        Ok(viewer) => viewer,
+        Err(message) => return SwarmResponse::error(request_id, ErrorCode::NotFound, message),
+    };
+    // A synthesis is the task's result, so it follows the result's visibility.
+    let redacted = |m: &DeliberationMessage| {
+        m.message_type == DeliberationType::SynthesisResult
+            && !state.may_view_result(&m.task_id, viewer.as_str())
+    };
     let messages: Vec<serde_json::Value> = state.deliberation_messages
         .get(&task_id)
-        .map(|msgs| msgs.iter().map(|m| serde_json::json!({
+        .map(|msgs| {
+            let mut msgs: Vec<_> = msgs.iter().collect();
+            msgs.sort_by_key(|m| m.hlc);
+            msgs
+        })
+        .map(|msgs| msgs.into_iter().map(|m| serde_json::json!({
             "id": m.id,
             "task_id": m.task_id,
             "timestamp": m.timestamp,
+            "hlc": m.hlc,
             "speaker": m.speaker.to_string(),
             "round": m.round,
             "message_type": format!("{:?}", m.message_type),
-            "content": m.content,
+            "content": if redacted(m) { "" } else { m.content.as_str() },
+            "redacted": redacted(m),
             "referenced_plan_id": m.referenced_plan_id,
             "critic_scores": m.critic_scores,
         })).collect())
@@ -2331,7 +5982,9 @@ async fn handle_get_deliberation(
     SwarmResponse::success(request_id, serde_json::json!({ "task_id": task_id, "messages": messages }))
 }
 
-/// Handle `swarm.get_ballots` - returns ballot records for a task.
+/// Handle `swarm.get_ballots` - returns ballot records for a task, with the
+/// hash, position and receipt hash of each ballot this node counted, and
+/// the ballot receipts it holds.
 async fn handle_get_ballots(
     request_id: Option<String>,
     params: &serde_json::Value,
@@ -2339,21 +5992,31 @@ async fn handle_get_ballots(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
-        None => return SwarmResponse::error(request_id, -32602, "task_id required".to_string()),
+        None => return SwarmResponse::error(request_id, ErrorCode::InvalidParams, "task_id required".to_string()),
     };
     let state = state.read().await;
     let ballots: Vec<serde_json::Value> = state.ballot_records
         .get(&task_id)
-        .map(|records| records.iter().map(|b| serde_json::json!({
-            "task_id": b.task_id,
-            "voter": b.voter.to_string(),
-            "rankings": b.rankings,
-            "critic_scores": b.critic_scores,
-            "timestamp": b.timestamp,
-            "irv_round_when_eliminated": b.irv_round_when_eliminated,
-        })).collect())
+        .map(|records| records.iter().map(|b| {
+            let receipt = state.issued_ballot_receipt(&task_id, &b.voter);
+            serde_json::json!({
+                "task_id": b.task_id,
+                "voter": b.voter.to_string(),
+                "rankings": b.rankings,
+                "critic_scores": b.critic_scores,
+                "timestamp": b.timestamp,
+                "irv_round_when_eliminated": b.irv_round_when_eliminated,
+                "ballot_hash": receipt.map(|r| r.ballot_hash.clone()),
+                "position": receipt.map(|r| r.position),
+                "receipt_hash": receipt.map(|r| r.receipt_hash()),
+            })
+        }).collect())
         .unwrap_or_default();
-    SwarmResponse::success(request_id, serde_json::json!({ "task_id": task_id, "ballots": ballots }))
+    let receipts = state.ballot_receipts.get(&task_id).cloned().unwrap_or_default();
+    SwarmResponse::success(
+        request_id,
+        serde_json::json!({ "task_id": task_id, "ballots": ballots, "receipts": receipts }),
+    )
 }
 
 /// Handle `swarm.get_irv_rounds` - returns IRV round history for a task.
@@ -2364,7 +6027,7 @@ async fn handle_get_irv_rounds(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
-        None => return SwarmResponse::error(request_id, -32602, "task_id required".to_string()),
+        None => return SwarmResponse::error(request_id, ErrorCode::InvalidParams, "task_id required".to_string()),
     };
     let state = state.read().await;
     let rounds: Vec<serde_json::Value> = state.irv_rounds
@@ -2375,126 +6038,230 @@ async fn handle_get_irv_rounds(
             "tallies": r.tallies,
             "eliminated": r.eliminated,
             "continuing_candidates": r.continuing_candidates,
+            "tie_break": r.tie_break,
+            "tie_break_seed": r.tie_break_seed,
         })).collect())
         .unwrap_or_default();
     SwarmResponse::success(request_id, serde_json::json!({ "task_id": task_id, "irv_rounds": rounds }))
 }
 
-/// Handle `swarm.register_name` — register a wws:// human-readable name for this agent.
+/// Handle `swarm.register_name` — claim a wws:// human-readable name for this agent.
 ///
-/// Params: `{ "name": <str>, "pow_nonce": <u64> }`
-/// Returns: `{ "registered": true, "expires_at": <u64> }`
+/// Params: `{ "name": <str>, "pow_nonce": <u64>, "dispute"?: <bool> }`
+/// Returns: `{ "registered": <bool>, "expires_at": <u64>, "holder": <str>, "disputed": <bool> }`
 ///
-/// Name constraints: 1–64 chars, alphanumeric + hyphen only.
-/// Registration is keyed by the lowercase name and owned by the agent's DID.
+/// Name constraints: 1–64 chars, alphanumeric + hyphen only. The claim is
+/// signed, published on the names topic and stored in the DHT. A name
+/// another DID already holds is refused unless `dispute` is set, in which
+/// case the claim is published as a contender for the guardians to settle.
 async fn handle_register_name(
     request_id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
     let name = match params.get("name").and_then(|v| v.as_str()) {
-        Some(n) if !n.is_empty() => n.to_string(),
+        Some(n) if !n.is_empty() && n.len() <= 64 => n.to_string(),
         _ => {
             return SwarmResponse::error(
                 request_id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "invalid name: must be 1-64 chars".to_string(),
             )
         }
     };
 
-    if name.len() > 64 {
-        return SwarmResponse::error(
-            request_id,
-            -32602,
-            "invalid name: must be 1-64 chars".to_string(),
-        );
-    }
-
-    if !name.chars().all(|c| c.is_alphanumeric() || c == '-') {
+    if !crate::names::is_valid_name(&name) {
         return SwarmResponse::error(
             request_id,
-            -32602,
+            ErrorCode::InvalidParams,
             "invalid name: only alphanumeric and hyphen allowed".to_string(),
         );
     }
 
     let pow_nonce = params.get("pow_nonce").and_then(|v| v.as_u64()).unwrap_or(0);
+    let dispute = params.get("dispute").and_then(|v| v.as_bool()).unwrap_or(false);
 
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let expires_at = now + wws_network::name_registry::NAME_TTL_SECS;
-
+    let now = crate::names::unix_now();
     let mut state = state.write().await;
     let did = state.agent_id.to_string();
-    let peer_id = state.agent_id.to_string(); // peer_id not separately stored; use agent_id
 
-    let record = wws_network::name_registry::NameRecord {
+    if let Some(holder) = state.name_directory.holder(&name, now) {
+        if holder.did != did && !dispute {
+            return SwarmResponse::error(
+                request_id,
+                ErrorCode::NotPermitted,
+                format!("name already held by {}", holder.did),
+            );
+        }
+    }
+
+    let registered_at = state
+        .name_directory
+        .claim_of(&name, &did)
+        .filter(|c| c.expires_at > now)
+        .map(|c| c.registered_at)
+        .unwrap_or(now);
+    let mut claim = NameClaimParams {
         name: name.clone(),
         did: did.clone(),
-        peer_id,
-        registered_at: now,
-        expires_at,
+        peer_id: network_handle.local_peer_id().to_string(),
+        registered_at,
+        expires_at: now + wws_network::name_registry::NAME_TTL_SECS,
         pow_nonce,
-        signature: vec![],
+        public_key: String::new(),
+        signature: String::new(),
     };
+    claim.sign(&state.identity_key);
+    if let Err(reason) = state.record_name_claim(claim.clone()) {
+        return SwarmResponse::error(request_id, ErrorCode::OperationFailed, reason);
+    }
+    let holder = state
+        .name_directory
+        .holder(&name, now)
+        .map(|c| c.did.clone())
+        .unwrap_or_default();
+    let disputed = state.name_directory.is_disputed(&name, now);
+    drop(state);
 
-    state.registered_names.insert(name.to_lowercase(), record);
+    crate::names::publish_claim(network_handle, &claim).await;
 
     SwarmResponse::success(
         request_id,
         serde_json::json!({
-            "registered": true,
-            "expires_at": expires_at
+            "registered": holder == did,
+            "expires_at": claim.expires_at,
+            "holder": holder,
+            "disputed": disputed
         }),
     )
 }
 
-/// Handle `swarm.resolve_name` — resolve a wws:// name to its DID and peer_id.
+/// Handle `swarm.resolve_name` — resolve a wws:// name to its DID and peer_id.
+///
+/// Params: `{ "name": <str> }`
+/// Returns: `{ "name": <str>, "did": <str>, "peer_id": <str>, "expires_at": <u64>,
+///            "disputed": <bool>, "awarded_by_guardians": <bool>,
+///            "claims": [{ "did", "registered_at", "expires_at", "guardian_votes" }] }`
+///
+/// `claims` lists every unexpired claim, earliest first; more than one
+/// means the name is contested.
+async fn handle_resolve_name(
+    request_id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let name = match params.get("name").and_then(|v| v.as_str()) {
+        Some(n) if !n.is_empty() => n.to_lowercase(),
+        _ => {
+            return SwarmResponse::error(
+                request_id,
+                ErrorCode::InvalidParams,
+                "name parameter required".to_string(),
+            )
+        }
+    };
+
+    let state = state.read().await;
+    let now = crate::names::unix_now();
+    match state.registered_names.get(&name) {
+        Some(record) if !record.is_expired() => {
+            let directory = &state.name_directory;
+            let votes = directory.votes(&name);
+            let claims: Vec<serde_json::Value> = directory
+                .contenders(&name, now)
+                .into_iter()
+                .map(|c| {
+                    serde_json::json!({
+                        "did": c.did,
+                        "registered_at": c.registered_at,
+                        "expires_at": c.expires_at,
+                        "guardian_votes": votes.get(c.did.as_str()).map(Vec::len).unwrap_or(0)
+                    })
+                })
+                .collect();
+            SwarmResponse::success(
+                request_id,
+                serde_json::json!({
+                    "name": record.name,
+                    "did": record.did,
+                    "peer_id": record.peer_id,
+                    "expires_at": record.expires_at,
+                    "disputed": directory.is_disputed(&name, now),
+                    "awarded_by_guardians": directory.awarded_to(&name, now).is_some(),
+                    "claims": claims
+                }),
+            )
+        }
+        Some(_) => SwarmResponse::error(
+            request_id,
+            ErrorCode::NotPermitted,
+            "name registration expired".to_string(),
+        ),
+        None => SwarmResponse::error(request_id, ErrorCode::NotPermitted, "name not found".to_string()),
+    }
+}
+
+/// Handle `swarm.resolve_did` — the DID document of a `did:swarm` DID.
 ///
-/// Params: `{ "name": <str> }`
-/// Returns: `{ "name": <str>, "did": <str>, "peer_id": <str>, "expires_at": <u64> }`
-async fn handle_resolve_name(
+/// Params: `{ "did": <str>, "refresh": <bool, optional> }`
+/// Returns: `{ "did": <str>, "document": <DID document> }`
+///
+/// A document already resolved is returned at once unless `refresh` is
+/// set; otherwise it is looked up in the DHT, waiting up to five seconds
+/// for a copy signed by the key the DID derives from (or, on refresh, for
+/// a newer one).
+async fn handle_resolve_did(
     request_id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
-    let name = match params.get("name").and_then(|v| v.as_str()) {
-        Some(n) if !n.is_empty() => n.to_lowercase(),
+    let did = match params.get("did").and_then(|v| v.as_str()) {
+        Some(d) if d.starts_with("did:swarm:") => d,
         _ => {
             return SwarmResponse::error(
                 request_id,
-                -32602,
-                "name parameter required".to_string(),
+                ErrorCode::InvalidParams,
+                "did parameter required (did:swarm:...)".to_string(),
             )
         }
     };
+    let refresh = params.get("refresh").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let held = state.read().await.did_documents.get(did).map(|d| d.updated);
+    if held.is_none() || refresh {
+        crate::did::lookup(network_handle, did).await;
+        let deadline = tokio::time::Instant::now() + DID_RESOLVE_TIMEOUT;
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            if state.read().await.did_documents.get(did).map(|d| d.updated) != held {
+                break;
+            }
+        }
+    }
 
     let state = state.read().await;
-    match state.registered_names.get(&name) {
-        Some(record) if !record.is_expired() => SwarmResponse::success(
+    match state.did_documents.get(did) {
+        Some(document) => SwarmResponse::success(
             request_id,
             serde_json::json!({
-                "name": record.name,
-                "did": record.did,
-                "peer_id": record.peer_id,
-                "expires_at": record.expires_at
+                "did": did,
+                "document": document,
             }),
         ),
-        Some(_) => SwarmResponse::error(
+        None => SwarmResponse::error(
             request_id,
-            -32001,
-            "name registration expired".to_string(),
+            ErrorCode::NotFound,
+            format!("DID document not found: {}", did),
         ),
-        None => SwarmResponse::error(request_id, -32001, "name not found".to_string()),
     }
 }
 
 /// Handle `swarm.renew_name` — extend the TTL of an existing name registration.
 ///
-/// Only the DID that originally registered the name may renew it.
+/// Only the DID that originally registered the name may renew it. The
+/// renewed claim keeps its original registration time and is republished.
 ///
 /// Params: `{ "name": <str> }`
 /// Returns: `{ "renewed": true, "new_expires_at": <u64> }`
@@ -2502,45 +6269,118 @@ async fn handle_renew_name(
     request_id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(n) if !n.is_empty() => n.to_lowercase(),
         _ => {
             return SwarmResponse::error(
                 request_id,
-                -32602,
+                ErrorCode::InvalidParams,
                 "name parameter required".to_string(),
             )
         }
     };
 
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-
+    let now = crate::names::unix_now();
     let mut state = state.write().await;
     let caller_did = state.agent_id.to_string();
 
-    match state.registered_names.get_mut(&name) {
-        Some(record) if record.did == caller_did => {
-            record.expires_at = now + wws_network::name_registry::NAME_TTL_SECS;
-            let new_expires_at = record.expires_at;
-            SwarmResponse::success(
+    let own = state.name_directory.claim_of(&name, &caller_did).cloned();
+    let mut claim = match (state.registered_names.get(&name), own) {
+        (Some(record), Some(own)) if record.did == caller_did => own,
+        (Some(_), _) => {
+            return SwarmResponse::error(
                 request_id,
-                serde_json::json!({
-                    "renewed": true,
-                    "new_expires_at": new_expires_at
-                }),
+                ErrorCode::NotPermitted,
+                "not owner of this name".to_string(),
             )
         }
-        Some(_) => SwarmResponse::error(
+        (None, _) => {
+            return SwarmResponse::error(request_id, ErrorCode::NotPermitted, "name not found".to_string())
+        }
+    };
+    claim.expires_at = now + wws_network::name_registry::NAME_TTL_SECS;
+    claim.sign(&state.identity_key);
+    if let Err(reason) = state.record_name_claim(claim.clone()) {
+        return SwarmResponse::error(request_id, ErrorCode::OperationFailed, reason);
+    }
+    drop(state);
+
+    crate::names::publish_claim(network_handle, &claim).await;
+
+    SwarmResponse::success(
+        request_id,
+        serde_json::json!({
+            "renewed": true,
+            "new_expires_at": claim.expires_at
+        }),
+    )
+}
+
+/// Handle `swarm.vote_name_dispute` — as one of the configured name
+/// guardians, award a contested name to one of its claimants.
+///
+/// Params: `{ "name": <str>, "award_to": <did> }`
+/// Returns: `{ "recorded": true, "holder": <str>, "disputed": <bool> }`
+async fn handle_vote_name_dispute(
+    request_id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let (Some(name), Some(award_to)) = (
+        params.get("name").and_then(|v| v.as_str()),
+        params.get("award_to").and_then(|v| v.as_str()),
+    ) else {
+        return SwarmResponse::error(
             request_id,
-            -32001,
-            "not owner of this name".to_string(),
-        ),
-        None => SwarmResponse::error(request_id, -32001, "name not found".to_string()),
+            ErrorCode::InvalidParams,
+            "name and award_to parameters required".to_string(),
+        );
+    };
+
+    let mut state = state.write().await;
+    let guardian_did = state.agent_id.to_string();
+    if !state.name_directory.is_guardian(&guardian_did) {
+        return SwarmResponse::error(
+            request_id,
+            ErrorCode::OperationFailed,
+            "this agent is not a name guardian".to_string(),
+        );
+    }
+    let mut vote = NameDisputeVoteParams {
+        name: name.to_lowercase(),
+        award_to: award_to.to_string(),
+        guardian_did,
+        guardian_key: String::new(),
+        timestamp: chrono::Utc::now(),
+        signature: String::new(),
+    };
+    vote.sign(&state.identity_key);
+    if let Err(reason) = state.record_name_vote(vote.clone()) {
+        return SwarmResponse::error(request_id, ErrorCode::NotFound, reason);
     }
+    let now = crate::names::unix_now();
+    let holder = state
+        .name_directory
+        .holder(&vote.name, now)
+        .map(|c| c.did.clone())
+        .unwrap_or_default();
+    let disputed = state.name_directory.is_disputed(&vote.name, now);
+    drop(state);
+
+    let value = serde_json::to_value(&vote).unwrap_or_default();
+    crate::names::publish(network_handle, ProtocolMethod::NameDisputeVote, value).await;
+
+    SwarmResponse::success(
+        request_id,
+        serde_json::json!({
+            "recorded": true,
+            "holder": holder,
+            "disputed": disputed
+        }),
+    )
 }
 
 /// Handle `swarm.my_names` — list all non-expired names registered by this agent.
@@ -2580,7 +6420,7 @@ async fn handle_send_message(
 ) -> SwarmResponse {
     let content = match params.get("content").and_then(|v| v.as_str()) {
         Some(c) if !c.is_empty() => c.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "missing content".to_string()),
+        _ => return SwarmResponse::error(id, ErrorCode::InvalidParams, "missing content".to_string()),
     };
     let recipient_did = params.get("recipient_did").and_then(|v| v.as_str()).map(|s| s.to_string());
     let message_type = params
@@ -2632,6 +6472,291 @@ async fn handle_send_message(
     SwarmResponse::success(id, serde_json::json!({ "ok": true, "message_id": message_id }))
 }
 
+/// Handle `swarm.approve_plan` — approve or reject a winning plan held for sign-off.
+///
+/// Params: `{ "task_id": <str>, "approved"?: <bool, default true>, "reason"?: <str> }`
+/// Returns: `{ "task_id": <str>, "plan_id": <str>, "approved": <bool> }`
+async fn handle_approve_plan(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+    let approved = params
+        .get("approved")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    let reason = params
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let mut state = state.write().await;
+    let actor = state.agent_id.to_string();
+    let plan_id = if approved {
+        state.approve_plan(&task_id, &actor)
+    } else {
+        state.reject_plan(&task_id, &actor, &reason)
+    };
+
+    match plan_id {
+        Some(plan_id) => SwarmResponse::success(
+            id,
+            serde_json::json!({
+                "task_id": task_id,
+                "plan_id": plan_id,
+                "approved": approved,
+            }),
+        ),
+        None => SwarmResponse::error(
+            id,
+            ErrorCode::NotFound,
+            format!("No plan awaiting approval for task {}", task_id),
+        ),
+    }
+}
+
+/// Handle `swarm.reassign_task` - hand an open subtask to another agent in
+/// place of a stuck assignee, as an operator override.
+async fn handle_reassign_task(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+    let agent_id = match params.get("agent_id").and_then(|v| v.as_str()) {
+        Some(a) if !a.trim().is_empty() => a.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'agent_id' parameter".to_string());
+        }
+    };
+    if !state.read().await.task_details.contains_key(&task_id) {
+        return SwarmResponse::error(id, ErrorCode::NotFound, format!("Task not found: {}", task_id));
+    }
+
+    match reassign_task(&task_id, &agent_id, state, network_handle).await {
+        Ok(assignment) => SwarmResponse::success(
+            id,
+            serde_json::json!({
+                "task_id": task_id,
+                "assignee": assignment.assignee,
+                "parent_task_id": assignment.parent_task_id,
+                "deadline": assignment.task.deadline,
+            }),
+        ),
+        Err(reason) => SwarmResponse::error(id, ErrorCode::OperationFailed, reason),
+    }
+}
+
+/// Handle `swarm.export_run` - archive a task tree with its deliberation,
+/// ballots, IRV rounds, timelines and artifacts.
+async fn handle_export_run(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+    match state.read().await.export_run(&task_id) {
+        Ok(transcript) => {
+            SwarmResponse::success(id, serde_json::to_value(&transcript).unwrap_or_default())
+        }
+        Err(reason) => SwarmResponse::error(id, ErrorCode::NotFound, reason),
+    }
+}
+
+/// Handle `swarm.import_run` - load a transcript written by
+/// `swarm.export_run`.
+///
+/// Params: `{ "transcript": <object> }`
+async fn handle_import_run(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let transcript = params
+        .get("transcript")
+        .cloned()
+        .map(serde_json::from_value::<crate::transcript::RunTranscript>);
+    let transcript = match transcript {
+        Some(Ok(transcript)) => transcript,
+        Some(Err(e)) => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, format!("Invalid transcript: {}", e));
+        }
+        None => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'transcript' parameter".to_string());
+        }
+    };
+    let root_task_id = transcript.root_task_id.clone();
+    match state.write().await.import_run(transcript) {
+        Ok(imported) => SwarmResponse::success(
+            id,
+            serde_json::json!({
+                "root_task_id": root_task_id,
+                "imported": imported,
+            }),
+        ),
+        Err(reason) => SwarmResponse::error(id, ErrorCode::InvalidParams, reason),
+    }
+}
+
+/// Handle `swarm.vote_dispute` - accept one of two diverging results for a
+/// disputed task on behalf of this agent.
+async fn handle_vote_dispute(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+    let accept_agent = match params.get("accept_agent").and_then(|v| v.as_str()) {
+        Some(a) if !a.trim().is_empty() => a.trim().to_string(),
+        _ => {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::InvalidParams,
+                "Missing 'accept_agent' parameter".to_string(),
+            );
+        }
+    };
+
+    let (vote, swarm_id, resolved) = {
+        let mut state = state.write().await;
+        if !state.verifications.contains_key(&task_id) {
+            return SwarmResponse::error(
+                id,
+                ErrorCode::NotFound,
+                format!("No verification for task {}", task_id),
+            );
+        }
+        let vote = DisputeVoteParams {
+            task_id: task_id.clone(),
+            voter: state.agent_id.clone(),
+            accept_agent: AgentId::new(accept_agent),
+        };
+        let resolved = match state.record_dispute_vote(&vote) {
+            Ok(resolved) => resolved,
+            Err(e) => return SwarmResponse::error(id, ErrorCode::InvalidParams, e.to_string()),
+        };
+        let swarm_id = state.swarm_for_task(&task_id);
+        (vote, swarm_id, resolved)
+    };
+
+    let vote_msg = SwarmMessage::new(
+        ProtocolMethod::DisputeVote.as_str(),
+        serde_json::to_value(&vote).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&vote_msg) {
+        let topic = SwarmTopics::voting_for(&swarm_id, &task_id);
+        let _ = network_handle.publish(&topic, data).await;
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": task_id,
+            "accepted": true,
+            "resolved": resolved.is_some(),
+            "accepted_agent": resolved,
+        }),
+    )
+}
+
+/// Handle `swarm.get_verification` - re-execution check or dispute for a task.
+async fn handle_get_verification(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+    let state = state.read().await;
+    match state.verifications.get(task_id) {
+        Some(record) => {
+            SwarmResponse::success(id, serde_json::to_value(record).unwrap_or_default())
+        }
+        None => SwarmResponse::error(
+            id,
+            ErrorCode::NotFound,
+            format!("No verification for task {}", task_id),
+        ),
+    }
+}
+
+/// Handle `swarm.verify_task` - recompute a task's Merkle chain.
+///
+/// Params: `{ "task_id": <str>, "anchor"?: <bool> }`
+/// Every result below the task is rehashed and compared with the hash its
+/// artifact carries. With `anchor`, the root of a valid chain is also
+/// published in the DHT under `/wws/merkle-root/<task_id>`.
+async fn handle_verify_task(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t.trim(),
+        _ => {
+            return SwarmResponse::error(id, ErrorCode::InvalidParams, "Missing 'task_id' parameter".to_string());
+        }
+    };
+    let anchor = params.get("anchor").and_then(|v| v.as_bool()).unwrap_or(false);
+    let (report, agent_id) = {
+        let state = state.read().await;
+        match crate::result_chain::verify(&state, task_id) {
+            Some(report) => (report, state.agent_id.to_string()),
+            None => {
+                return SwarmResponse::error(
+                    id,
+                    ErrorCode::NotFound,
+                    format!("No result for task {}", task_id),
+                );
+            }
+        }
+    };
+    let anchored = if anchor && report.valid {
+        let record = crate::result_chain::MerkleAnchor {
+            task_id: report.task_id.clone(),
+            root: report.root.clone(),
+            anchored_by: agent_id,
+            anchored_at: chrono::Utc::now(),
+        };
+        crate::result_chain::anchor(network_handle, &record).await
+    } else {
+        false
+    };
+    let mut result = serde_json::to_value(&report).unwrap_or_default();
+    result["anchored"] = serde_json::json!(anchored);
+    SwarmResponse::success(id, result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2656,6 +6781,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn attachments_need_a_name_and_one_source() {
+        let parsed = parse_attachments(&serde_json::json!([
+            {"name": "brief.md", "content": "# Brief", "content_type": "text/markdown"},
+            {"name": "data.csv", "cid": "cid-data"},
+        ]))
+        .unwrap();
+        assert_eq!(parsed[0].1.as_deref(), Some("# Brief"));
+        assert_eq!(parsed[0].0.content_type.as_deref(), Some("text/markdown"));
+        assert_eq!((parsed[1].0.cid.as_str(), parsed[1].1.as_deref()), ("cid-data", None));
+
+        for bad in [
+            serde_json::json!({"name": "x"}),
+            serde_json::json!([{"content": "x"}]),
+            serde_json::json!([{"name": "x"}]),
+            serde_json::json!([{"name": "x", "content": "a", "cid": "b"}]),
+            serde_json::json!([{"name": "x", "content": "a".repeat(MAX_INLINE_ATTACHMENT_BYTES + 1)}]),
+        ] {
+            assert!(parse_attachments(&bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn describe_errors_lists_every_code() {
+        let response = handle_describe_errors(Some("1".into()));
+        let errors = response.result.unwrap()["errors"].as_array().unwrap().clone();
+        assert_eq!(errors.len(), ErrorCode::ALL.len());
+        let frozen = errors.iter().find(|e| e["code"] == -32014).unwrap();
+        assert_eq!(frozen["name"], "proposals_frozen");
+        assert_eq!(frozen["category"], "consensus");
+    }
+
+    #[test]
+    fn log_query_filters_by_category_text_and_time() {
+        let now = chrono::Utc::now();
+        let entry = |secs_ago, category, message: &str| LogEntry {
+            timestamp: now - chrono::Duration::seconds(secs_ago),
+            category,
+            message: message.to_string(),
+        };
+        let log = vec![
+            entry(300, LogCategory::Peer, "Peer connected: 12D3KooWa"),
+            entry(200, LogCategory::Error, "Failed to publish task"),
+            entry(100, LogCategory::Task, "Task injected: t-1"),
+            entry(10, LogCategory::Error, "Vote rejected for TASK t-1"),
+        ];
+        let select = |params: serde_json::Value| -> Vec<String> {
+            let query = LogQuery::from_params(&params).unwrap();
+            log.iter().filter(|e| query.matches(e)).map(|e| e.message.clone()).collect()
+        };
+
+        assert_eq!(select(serde_json::json!({})).len(), 4);
+        assert_eq!(
+            select(serde_json::json!({"category": "err"})),
+            ["Failed to publish task", "Vote rejected for TASK t-1"]
+        );
+        assert_eq!(
+            select(serde_json::json!({"category": ["Task", "ERROR"], "contains": "task t-1"})),
+            ["Vote rejected for TASK t-1"]
+        );
+        let since = (now - chrono::Duration::seconds(250)).to_rfc3339();
+        let until = (now - chrono::Duration::seconds(50)).to_rfc3339();
+        assert_eq!(
+            select(serde_json::json!({"since": since, "until": until})),
+            ["Failed to publish task", "Task injected: t-1"]
+        );
+        assert!(LogQuery::from_params(&serde_json::json!({"category": "debug"})).is_err());
+        assert!(LogQuery::from_params(&serde_json::json!({"since": "yesterday"})).is_err());
+
+        // A follower only sees entries pushed after its cursor, even once
+        // older ones have been dropped from the front of the log.
+        assert_eq!(entries_since(&log, 4, 4).len(), 0);
+        assert_eq!(entries_since(&log, 6, 4).len(), 2);
+        assert_eq!(entries_since(&log, 10, 2).len(), 4);
+    }
+
+    #[test]
+    fn task_query_reads_filters_order_and_cursor() {
+        let cursor = TaskCursor {
+            created_at: chrono::Utc::now(),
+            task_id: "task-7".to_string(),
+        };
+        let query = task_query_from_params(&serde_json::json!({
+            "status": ["Pending", "InProgress"],
+            "assignee": "did:swarm:alice",
+            "tier": 2,
+            "contains": "Launch",
+            "since": "2026-01-01T00:00:00Z",
+            "order": "asc",
+            "cursor": cursor.encode(),
+            "limit": 5000,
+        }))
+        .unwrap();
+        assert_eq!(query.statuses, [TaskStatus::Pending, TaskStatus::InProgress]);
+        assert_eq!(query.assignee.as_deref(), Some("did:swarm:alice"));
+        assert_eq!((query.tier, query.contains.as_deref()), (Some(2), Some("launch")));
+        assert!(query.created_since.is_some() && query.created_until.is_none());
+        assert!(!query.newest_first);
+        assert_eq!(query.after, Some(cursor));
+        assert_eq!(query.limit, MAX_SEARCH_LIMIT);
+
+        let defaults = task_query_from_params(&serde_json::json!({"status": "Completed"})).unwrap();
+        assert_eq!(defaults.statuses, [TaskStatus::Completed]);
+        assert!(defaults.newest_first);
+        assert_eq!(defaults.limit, DEFAULT_SEARCH_LIMIT);
+
+        for bad in [
+            serde_json::json!({"status": "Done"}),
+            serde_json::json!({"tier": -1}),
+            serde_json::json!({"order": "newest"}),
+            serde_json::json!({"cursor": "not-a-cursor"}),
+            serde_json::json!({"until": "tomorrow"}),
+        ] {
+            assert!(task_query_from_params(&bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
     #[test]
     fn test_verification_challenge_is_expired() {
         // Not expired for a fresh challenge with 300s TTL
@@ -0,0 +1,293 @@
+//! Cron-style scheduled task injection.
+//!
+//! Schedules pair a cron expression with a [`TaskTemplate`]. The connector
+//! polls [`Scheduler::take_due`] from its event loop and injects a fresh task
+//! for every schedule whose next fire time has passed. Missed fires (e.g.
+//! while the connector was down) are collapsed into a single injection.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::config::TaskTemplate;
+
+/// Errors returned when registering a schedule.
+#[derive(Debug, thiserror::Error)]
+pub enum SchedulerError {
+    #[error("Invalid cron expression '{0}': {1}")]
+    InvalidCron(String, String),
+
+    #[error("Schedule already exists: {0}")]
+    DuplicateId(String),
+
+    #[error("Cron expression '{0}' never fires")]
+    NeverFires(String),
+}
+
+/// A recurring task injection.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskSchedule {
+    pub schedule_id: String,
+    /// Cron expression as supplied (5, 6 or 7 fields).
+    pub cron: String,
+    pub task: TaskTemplate,
+    pub created_at: DateTime<Utc>,
+    pub last_run: Option<DateTime<Utc>>,
+    pub next_run: Option<DateTime<Utc>>,
+    pub run_count: u64,
+    /// Task ID produced by the most recent injection.
+    pub last_task_id: Option<String>,
+    #[serde(skip)]
+    schedule: cron::Schedule,
+}
+
+impl TaskSchedule {
+    /// Parse `cron` and compute the first fire time after `now`.
+    ///
+    /// Standard 5-field expressions (`min hour dom month dow`, Sunday 0 or
+    /// 7) are accepted and fire at second zero. 6 and 7-field expressions
+    /// use the `cron` crate's syntax, which numbers days 1-7 from Sunday.
+    pub fn new(
+        schedule_id: String,
+        cron: &str,
+        task: TaskTemplate,
+        now: DateTime<Utc>,
+    ) -> Result<Self, SchedulerError> {
+        let expression = cron.trim();
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let normalized = if let [minute, hour, dom, month, dow] = fields[..] {
+            let dow = crate_day_of_week(dow)
+                .map_err(|e| SchedulerError::InvalidCron(expression.to_string(), e))?;
+            format!("0 {} {} {} {} {}", minute, hour, dom, month, dow)
+        } else {
+            expression.to_string()
+        };
+        let schedule = cron::Schedule::from_str(&normalized)
+            .map_err(|e| SchedulerError::InvalidCron(expression.to_string(), e.to_string()))?;
+        let next_run = schedule.after(&now).next();
+        if next_run.is_none() {
+            return Err(SchedulerError::NeverFires(expression.to_string()));
+        }
+
+        Ok(Self {
+            schedule_id,
+            cron: expression.to_string(),
+            task,
+            created_at: now,
+            last_run: None,
+            next_run,
+            run_count: 0,
+            last_task_id: None,
+            schedule,
+        })
+    }
+}
+
+/// Rewrite a standard day-of-week field (0-7, Sunday 0 or 7) as an
+/// explicit list in the `cron` crate's numbering (1-7, Sunday 1). `*`, `?`
+/// and fields using day names are passed through unchanged.
+fn crate_day_of_week(field: &str) -> Result<String, String> {
+    if field == "*" || field == "?" || field.chars().any(|c| c.is_ascii_alphabetic()) {
+        return Ok(field.to_string());
+    }
+    let day = |value: &str| match value.parse::<u32>() {
+        Ok(day) if day <= 7 => Ok(day),
+        _ => Err(format!("invalid day of week '{}'", value)),
+    };
+    let mut days = std::collections::BTreeSet::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<usize>() {
+                Ok(step) if step > 0 => (range, Some(step)),
+                _ => return Err(format!("invalid step '{}'", step)),
+            },
+            None => (part, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (0, 6),
+            Some((start, end)) => (day(start)?, day(end)?),
+            // `n/step` runs from n to the end of the week.
+            None if step.is_some() => (day(range)?, 6),
+            None => (day(range)?, day(range)?),
+        };
+        if start > end {
+            return Err(format!("invalid day range '{}'", range));
+        }
+        for d in (start..=end).step_by(step.unwrap_or(1)) {
+            days.insert(d % 7 + 1);
+        }
+    }
+    Ok(days.iter().map(u32::to_string).collect::<Vec<_>>().join(","))
+}
+
+/// Registry of active schedules.
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    schedules: HashMap<String, TaskSchedule>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a schedule. IDs must be unique.
+    pub fn add(&mut self, schedule: TaskSchedule) -> Result<(), SchedulerError> {
+        if self.schedules.contains_key(&schedule.schedule_id) {
+            return Err(SchedulerError::DuplicateId(schedule.schedule_id));
+        }
+        self.schedules.insert(schedule.schedule_id.clone(), schedule);
+        Ok(())
+    }
+
+    /// Remove a schedule, returning it if it existed.
+    pub fn cancel(&mut self, schedule_id: &str) -> Option<TaskSchedule> {
+        self.schedules.remove(schedule_id)
+    }
+
+    pub fn get(&self, schedule_id: &str) -> Option<&TaskSchedule> {
+        self.schedules.get(schedule_id)
+    }
+
+    /// All schedules ordered by next fire time (exhausted schedules last).
+    pub fn list(&self) -> Vec<&TaskSchedule> {
+        let mut schedules: Vec<&TaskSchedule> = self.schedules.values().collect();
+        schedules.sort_by(|a, b| match (a.next_run, b.next_run) {
+            (Some(x), Some(y)) => x.cmp(&y).then_with(|| a.schedule_id.cmp(&b.schedule_id)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.schedule_id.cmp(&b.schedule_id),
+        });
+        schedules
+    }
+
+    pub fn len(&self) -> usize {
+        self.schedules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schedules.is_empty()
+    }
+
+    /// Collect schedules that are due at `now` and advance them to their
+    /// next fire time. Returns `(schedule_id, template)` pairs to inject.
+    pub fn take_due(&mut self, now: DateTime<Utc>) -> Vec<(String, TaskTemplate)> {
+        let mut due = Vec::new();
+        for schedule in self.schedules.values_mut() {
+            let Some(next_run) = schedule.next_run else {
+                continue;
+            };
+            if next_run > now {
+                continue;
+            }
+            schedule.last_run = Some(now);
+            schedule.next_run = schedule.schedule.after(&now).next();
+            schedule.run_count += 1;
+            due.push((schedule.schedule_id.clone(), schedule.task.clone()));
+        }
+        due.sort_by(|a, b| a.0.cmp(&b.0));
+        due
+    }
+
+    /// Remember which task a schedule produced on its latest run.
+    pub fn record_injection(&mut self, schedule_id: &str, task_id: String) {
+        if let Some(schedule) = self.schedules.get_mut(schedule_id) {
+            schedule.last_task_id = Some(task_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn template() -> TaskTemplate {
+        TaskTemplate {
+            description: "Nightly report".to_string(),
+            tier_level: 1,
+            capabilities_required: Vec::new(),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn five_field_expression_is_accepted() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 1, 30, 0).unwrap();
+        let schedule =
+            TaskSchedule::new("nightly".to_string(), "0 2 * * *", template(), now).unwrap();
+        assert_eq!(
+            schedule.next_run,
+            Some(Utc.with_ymd_and_hms(2026, 1, 1, 2, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn five_field_days_of_week_count_from_sunday_zero() {
+        // 2026-01-01 is a Thursday.
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let next = |cron: &str| {
+            TaskSchedule::new("weekly".to_string(), cron, template(), now)
+                .unwrap()
+                .next_run
+                .unwrap()
+        };
+        let monday = Utc.with_ymd_and_hms(2026, 1, 5, 9, 0, 0).unwrap();
+        assert_eq!(next("0 9 * * 1"), monday);
+        assert_eq!(next("0 9 * * 0"), Utc.with_ymd_and_hms(2026, 1, 4, 9, 0, 0).unwrap());
+        assert_eq!(next("0 9 * * 7"), Utc.with_ymd_and_hms(2026, 1, 4, 9, 0, 0).unwrap());
+        assert_eq!(next("0 9 * * 6"), Utc.with_ymd_and_hms(2026, 1, 3, 9, 0, 0).unwrap());
+        assert_eq!(next("0 9 * * 1-5"), Utc.with_ymd_and_hms(2026, 1, 2, 9, 0, 0).unwrap());
+        assert_eq!(next("0 9 * * Mon"), monday);
+        assert!(TaskSchedule::new("bad".to_string(), "0 9 * * 8", template(), now).is_err());
+    }
+
+    #[test]
+    fn invalid_expression_is_rejected() {
+        let result = TaskSchedule::new("bad".to_string(), "every tuesday", template(), Utc::now());
+        assert!(matches!(result, Err(SchedulerError::InvalidCron(..))));
+    }
+
+    #[test]
+    fn take_due_fires_once_and_advances() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 30).unwrap();
+        let mut scheduler = Scheduler::new();
+        scheduler
+            .add(TaskSchedule::new("minutely".to_string(), "* * * * *", template(), start).unwrap())
+            .unwrap();
+
+        assert!(scheduler.take_due(start).is_empty());
+
+        // Several missed fires collapse into one injection.
+        let later = Utc.with_ymd_and_hms(2026, 1, 1, 0, 5, 10).unwrap();
+        let due = scheduler.take_due(later);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, "minutely");
+
+        let schedule = scheduler.get("minutely").unwrap();
+        assert_eq!(schedule.run_count, 1);
+        assert_eq!(schedule.last_run, Some(later));
+        assert_eq!(
+            schedule.next_run,
+            Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 6, 0).unwrap())
+        );
+        assert!(scheduler.take_due(later).is_empty());
+    }
+
+    #[test]
+    fn duplicate_and_cancel() {
+        let now = Utc::now();
+        let mut scheduler = Scheduler::new();
+        scheduler
+            .add(TaskSchedule::new("a".to_string(), "0 * * * *", template(), now).unwrap())
+            .unwrap();
+        let dup = scheduler.add(TaskSchedule::new("a".to_string(), "0 * * * *", template(), now).unwrap());
+        assert!(matches!(dup, Err(SchedulerError::DuplicateId(_))));
+
+        assert!(scheduler.cancel("a").is_some());
+        assert!(scheduler.cancel("a").is_none());
+        assert!(scheduler.is_empty());
+    }
+}
@@ -0,0 +1,336 @@
+//! Splitting a swarm that has outgrown its topics.
+//!
+//! Gossip topics degrade beyond a few thousand members. When a swarm's
+//! active membership exceeds `swarm.shard_threshold`, its shard issuer —
+//! the first active Tier-1 leader by DID — signs a `swarm.shard` plan and
+//! publishes it on the swarm's params topic. The plan names enough child
+//! swarms for each to hold at most half the threshold, and places every
+//! member in one of them: by a hash of its DID, or by the Tier-1 branch
+//! geo-clustering put it in. Every leader planning the split in the same
+//! epoch arrives at the same children, so a plan issued twice is applied
+//! once.
+//!
+//! The Tier-1 leaders stay in the parent and join every child as federation
+//! gateways. Every other member moves into its child as in a migration,
+//! taking the open tasks it originated along. Each side records federation
+//! links to the swarms it split from or alongside, and `swarm.delegate_task`
+//! injects a task into a linked swarm: directly when this node is a member
+//! of it, otherwise through one of the link's gateways over the direct
+//! protocol (`swarm.delegate`).
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use wws_protocol::{
+    AgentId, ProtocolMethod, ShardStrategy, SwarmMessage, SwarmShardParams, SwarmTopics,
+    TaskDelegationParams,
+};
+
+use crate::connector::ConnectorState;
+use crate::migration::SwarmMove;
+use crate::tui::LogCategory;
+
+/// Default for `swarm.shard_threshold`.
+pub const DEFAULT_SHARD_THRESHOLD: usize = 4000;
+/// Shard reports kept for `swarm.get_shard_status`.
+pub const MAX_SHARD_REPORTS: usize = 16;
+
+/// How a linked swarm relates to this node's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FederationRelation {
+    /// The swarm this node's swarm was split from.
+    Parent,
+    /// A swarm split from this node's swarm.
+    Child,
+    /// Another child of the same split.
+    Sibling,
+}
+
+/// A swarm this node may delegate tasks to.
+#[derive(Debug, Clone, Serialize)]
+pub struct FederationLink {
+    pub swarm_id: String,
+    pub relation: FederationRelation,
+    pub shard_id: String,
+    /// Members of both swarms that inject tasks delegated to it.
+    pub gateways: Vec<AgentId>,
+    pub established_at: DateTime<Utc>,
+}
+
+/// One split, from this node's side.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShardReport {
+    pub shard_id: String,
+    pub parent_swarm_id: String,
+    pub strategy: ShardStrategy,
+    pub children: Vec<String>,
+    pub issuer: String,
+    /// Child this node moved into; `None` when it stayed as a gateway.
+    pub moved_to: Option<String>,
+    /// Open tasks this node took into its child.
+    pub tasks_moved: usize,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// A split applied to the connector state, with the network side still to
+/// do.
+#[derive(Debug)]
+pub struct ShardOutcome {
+    pub shard: SwarmShardParams,
+    /// Children joined as a gateway.
+    pub joined: Vec<String>,
+    /// The move into this node's child, unless it is a gateway.
+    pub moved: Option<SwarmMove>,
+}
+
+/// The sharding policy, the splits seen and the federation links they left.
+#[derive(Debug, Clone)]
+pub struct ShardTracker {
+    threshold: usize,
+    strategy: ShardStrategy,
+    /// Plans issued or applied here.
+    seen: HashSet<String>,
+    links: BTreeMap<String, FederationLink>,
+    reports: VecDeque<ShardReport>,
+}
+
+impl Default for ShardTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_SHARD_THRESHOLD, ShardStrategy::default())
+    }
+}
+
+impl ShardTracker {
+    pub fn new(threshold: usize, strategy: ShardStrategy) -> Self {
+        Self {
+            threshold,
+            strategy,
+            seen: HashSet::new(),
+            links: BTreeMap::new(),
+            reports: VecDeque::new(),
+        }
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn strategy(&self) -> ShardStrategy {
+        self.strategy
+    }
+
+    /// Whether `active` members call for a split.
+    pub fn is_oversized(&self, active: usize) -> bool {
+        self.threshold > 0 && active > self.threshold
+    }
+
+    pub fn has_seen(&self, shard_id: &str) -> bool {
+        self.seen.contains(shard_id)
+    }
+
+    /// Note a plan as issued or applied. False when it already was.
+    pub fn mark_seen(&mut self, shard_id: &str) -> bool {
+        self.seen.insert(shard_id.to_string())
+    }
+
+    pub fn link(&self, swarm_id: &str) -> Option<&FederationLink> {
+        self.links.get(swarm_id)
+    }
+
+    pub fn links(&self) -> impl Iterator<Item = &FederationLink> {
+        self.links.values()
+    }
+
+    pub fn add_link(&mut self, link: FederationLink) {
+        self.links.insert(link.swarm_id.clone(), link);
+    }
+
+    pub fn reports(&self) -> impl Iterator<Item = &ShardReport> {
+        self.reports.iter()
+    }
+
+    pub fn record(&mut self, report: ShardReport) {
+        self.reports.push_back(report);
+        while self.reports.len() > MAX_SHARD_REPORTS {
+            self.reports.pop_front();
+        }
+    }
+}
+
+/// Id of the split of `parent` planned in `epoch`.
+pub fn shard_id(parent: &str, epoch: u64) -> String {
+    format!("{}@{}", parent, epoch)
+}
+
+/// Id of the `index`-th child of the split of `parent` in `epoch`.
+pub fn child_swarm_id(parent: &str, epoch: u64, index: usize) -> String {
+    format!("{}.{}-{}", parent, epoch, index)
+}
+
+/// Children needed for each to hold at most half the threshold, at least
+/// two.
+pub fn child_count(active: usize, threshold: usize) -> usize {
+    (active * 2).div_ceil(threshold.max(1)).max(2)
+}
+
+/// Publish a split on the parent swarm's params topic.
+pub async fn publish_shard(network_handle: &wws_network::SwarmHandle, shard: &SwarmShardParams) {
+    let msg = SwarmMessage::new(
+        ProtocolMethod::SwarmShard.as_str(),
+        serde_json::to_value(shard).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let topic = SwarmTopics::params_for(shard.parent_swarm_id.as_str());
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            tracing::debug!(error = %e, "Failed to publish swarm shard");
+        }
+    }
+}
+
+/// Subscribe to the children joined as a gateway, or move into this node's
+/// child.
+pub async fn complete_shard(
+    outcome: ShardOutcome,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) {
+    let ShardOutcome { shard, joined, moved } = outcome;
+    for child in &joined {
+        for topic in crate::membership::member_topics(child) {
+            if let Err(e) = network_handle.subscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to subscribe swarm topic");
+            }
+        }
+    }
+    let message = match moved {
+        Some(moved) => {
+            let target = moved.target.as_str().to_string();
+            crate::migration::move_swarm(moved, state, network_handle).await;
+            format!("Split {} complete: now in child swarm {}", shard.shard_id, target)
+        }
+        None => format!(
+            "Split {} complete: gateway to {} child swarms",
+            shard.shard_id,
+            joined.len()
+        ),
+    };
+    state.write().await.push_log(LogCategory::Swarm, message);
+}
+
+/// Send a task to a gateway of `target_swarm_id` for injection there, and
+/// return the gateway's RPC response.
+pub async fn delegate_via_gateway(
+    gateway: &AgentId,
+    request: TaskDelegationParams,
+    network_handle: &wws_network::SwarmHandle,
+) -> Result<serde_json::Value, String> {
+    let peer = gateway
+        .as_str()
+        .strip_prefix("did:swarm:")
+        .and_then(|id| id.parse::<wws_network::PeerId>().ok())
+        .ok_or_else(|| format!("Gateway {} has no peer ID", gateway))?;
+    let message = SwarmMessage::new(
+        ProtocolMethod::TaskDelegation.as_str(),
+        serde_json::to_value(&request).unwrap_or_default(),
+        String::new(),
+    );
+    match network_handle.request(peer, message).await {
+        Ok(reply) if reply.method == ProtocolMethod::TaskDelegation.as_str() => Ok(reply.params),
+        Ok(reply) => Err(format!("Gateway {} answered with {}", gateway, reply.method)),
+        Err(e) => Err(format!("Gateway {} did not answer: {}", gateway, e)),
+    }
+}
+
+/// As a gateway, inject a task delegated from a linked swarm into another
+/// linked swarm this node is a member of. A task standing in for a subtask
+/// of the source swarm is only taken by a member of both, which then
+/// relays its result back.
+pub async fn accept_delegation(
+    request: TaskDelegationParams,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> wws_protocol::SwarmResponse {
+    let source = request.source_swarm_id.as_str();
+    let target = request.target_swarm_id.as_str();
+    let permitted = {
+        let state = state.read().await;
+        let linked = |swarm_id: &str| {
+            swarm_id == state.current_swarm_id.as_str()
+                || state.shards.link(swarm_id).is_some()
+                || state.is_member_of(swarm_id)
+        };
+        if !linked(source) || !linked(target) {
+            Err(format!("No federation link between {} and {}", source, target))
+        } else if request.relay_to.is_some() {
+            state.require_membership(source).and_then(|()| state.require_membership(target))
+        } else {
+            state.require_membership(target)
+        }
+    };
+    if let Err(e) = permitted {
+        return wws_protocol::SwarmResponse::error(None, wws_protocol::ErrorCode::NotPermitted, e);
+    }
+    let mut params = request.task.clone();
+    if let Some(map) = params.as_object_mut() {
+        map.insert("swarm_id".to_string(), serde_json::json!(target));
+    }
+    state.write().await.push_log(
+        LogCategory::Swarm,
+        format!(
+            "Injecting task delegated by {} from swarm {} into {}",
+            request.sender, source, target
+        ),
+    );
+    let response = crate::rpc_server::handle_inject_task(None, &params, state, network_handle).await;
+    let remote_task_id = response
+        .result
+        .as_ref()
+        .and_then(|result| result.get("task_id"))
+        .and_then(|v| v.as_str());
+    if let (Some(origin_task_id), Some(remote_task_id)) = (&request.relay_to, remote_task_id) {
+        crate::delegation::track_relay(
+            &mut *state.write().await,
+            remote_task_id,
+            target,
+            source,
+            origin_task_id,
+            request.sender.clone(),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn children_hold_at_most_half_the_threshold() {
+        assert_eq!(child_count(4001, 4000), 3);
+        assert_eq!(child_count(5000, 4000), 3);
+        assert_eq!(child_count(9000, 4000), 5);
+        assert_eq!(child_count(10, 4), 5);
+        assert_eq!(child_count(3, 2), 3);
+
+        let tracker = ShardTracker::new(4000, ShardStrategy::Hash);
+        assert!(!tracker.is_oversized(4000));
+        assert!(tracker.is_oversized(4001));
+        assert!(!ShardTracker::new(0, ShardStrategy::Hash).is_oversized(1_000_000));
+    }
+
+    #[test]
+    fn every_leader_plans_the_same_children() {
+        assert_eq!(shard_id("lab", 7), "lab@7");
+        assert_eq!(child_swarm_id("lab", 7, 2), "lab.7-2");
+
+        let mut tracker = ShardTracker::default();
+        assert!(tracker.mark_seen("lab@7"));
+        assert!(!tracker.mark_seen("lab@7"));
+        assert!(tracker.has_seen("lab@7"));
+    }
+}
@@ -0,0 +1,224 @@
+//! Storage backends for the connector's per-task state.
+//!
+//! Tasks, result artifacts, task timelines and the reputation attestations
+//! behind the ledger sit behind the traits here instead of bare maps, so a
+//! deployment can keep them in RocksDB, Postgres or any other backend by
+//! handing its own implementations to `WwsConnector::with_stores`. The
+//! in-memory implementation of each is a `HashMap`, and is the default; the
+//! task store's also keeps the index `swarm.search_tasks` answers from.
+//!
+//! The connector reads and updates records in place, so accessors return
+//! references. A persistent backend therefore keeps its working set in
+//! memory and writes changes through, at the latest when `flush` is called;
+//! the connector flushes every `STORE_FLUSH_INTERVAL_SECS`.
+
+use std::collections::HashMap;
+
+use wws_protocol::{Artifact, ReputationAttestation, Task};
+
+use crate::connector::TaskTimelineEvent;
+use crate::task_index::{self, TaskPage, TaskQuery};
+
+/// How often the connector asks its stores to persist pending changes.
+pub const STORE_FLUSH_INTERVAL_SECS: u64 = 5;
+
+/// Records keyed by task ID, with the map operations the connector uses.
+pub trait KeyedStore<V: 'static>: Send + Sync {
+    fn get(&self, task_id: &str) -> Option<&V>;
+
+    fn get_mut(&mut self, task_id: &str) -> Option<&mut V>;
+
+    /// Store `value` under `task_id`, returning the record it replaced.
+    fn insert(&mut self, task_id: String, value: V) -> Option<V>;
+
+    fn remove(&mut self, task_id: &str) -> Option<V>;
+
+    /// Every record, in no particular order.
+    fn iter(&self) -> Box<dyn Iterator<Item = (&String, &V)> + '_>;
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&String, &mut V)> + '_>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains_key(&self, task_id: &str) -> bool {
+        self.get(task_id).is_some()
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        Box::new(self.iter().map(|(id, _)| id))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &V> + '_> {
+        Box::new(self.iter().map(|(_, v)| v))
+    }
+
+    fn values_mut(&mut self) -> Box<dyn Iterator<Item = &mut V> + '_> {
+        Box::new(self.iter_mut().map(|(_, v)| v))
+    }
+
+    /// Persist changes made since the last flush, including records
+    /// modified through `get_mut`. In-memory stores have nothing to do.
+    fn flush(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl<V: Send + Sync + 'static> KeyedStore<V> for HashMap<String, V> {
+    fn get(&self, task_id: &str) -> Option<&V> {
+        HashMap::get(self, task_id)
+    }
+
+    fn get_mut(&mut self, task_id: &str) -> Option<&mut V> {
+        HashMap::get_mut(self, task_id)
+    }
+
+    fn insert(&mut self, task_id: String, value: V) -> Option<V> {
+        HashMap::insert(self, task_id, value)
+    }
+
+    fn remove(&mut self, task_id: &str) -> Option<V> {
+        HashMap::remove(self, task_id)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&String, &V)> + '_> {
+        Box::new(HashMap::iter(self))
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&String, &mut V)> + '_> {
+        Box::new(HashMap::iter_mut(self))
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+/// Task records: descriptions, status, assignment and the task tree.
+pub trait TaskStore: KeyedStore<Task> {
+    /// One page of the tasks matching `query`. The default visits every
+    /// task; a backend with its own indexes should answer from them.
+    fn search(&mut self, query: &TaskQuery) -> TaskPage {
+        task_index::scan(self.iter(), query)
+    }
+}
+
+/// Result artifacts submitted for tasks.
+pub trait ArtifactStore: KeyedStore<Artifact> {}
+
+/// Lifecycle events of each task, oldest first.
+pub trait TimelineStore: KeyedStore<Vec<TaskTimelineEvent>> {
+    /// Append an event to a task's timeline.
+    fn push(&mut self, task_id: &str, event: TaskTimelineEvent) {
+        match self.get_mut(task_id) {
+            Some(events) => events.push(event),
+            None => {
+                self.insert(task_id.to_string(), vec![event]);
+            }
+        }
+    }
+}
+
+/// Reputation attestations admitted to the ledger, one per subject, task,
+/// kind of outcome and issuer, keyed by `ReputationLedger::record_key`.
+pub trait AttestationStore: KeyedStore<ReputationAttestation> {}
+
+/// In-memory task store, the default. Indexed for `TaskStore::search`.
+pub type InMemoryTaskStore = task_index::IndexedTaskStore;
+/// In-memory artifact store, the default.
+pub type InMemoryArtifactStore = HashMap<String, Artifact>;
+/// In-memory timeline store, the default.
+pub type InMemoryTimelineStore = HashMap<String, Vec<TaskTimelineEvent>>;
+/// In-memory attestation store, the default.
+pub type InMemoryAttestationStore = HashMap<String, ReputationAttestation>;
+
+impl TaskStore for HashMap<String, Task> {}
+impl ArtifactStore for InMemoryArtifactStore {}
+impl TimelineStore for InMemoryTimelineStore {}
+impl AttestationStore for InMemoryAttestationStore {}
+
+macro_rules! index_by_task_id {
+    ($store:ident, $value:ty) => {
+        impl std::ops::Index<&str> for dyn $store {
+            type Output = $value;
+
+            fn index(&self, task_id: &str) -> &$value {
+                self.get(task_id).expect("no record for task")
+            }
+        }
+
+        impl std::ops::Index<&String> for dyn $store {
+            type Output = $value;
+
+            fn index(&self, task_id: &String) -> &$value {
+                &self[task_id.as_str()]
+            }
+        }
+    };
+}
+
+index_by_task_id!(TaskStore, Task);
+index_by_task_id!(ArtifactStore, Artifact);
+index_by_task_id!(TimelineStore, Vec<TaskTimelineEvent>);
+
+/// The stores a connector keeps its per-task state and reputation ledger in.
+pub struct Stores {
+    pub tasks: Box<dyn TaskStore>,
+    pub artifacts: Box<dyn ArtifactStore>,
+    pub timelines: Box<dyn TimelineStore>,
+    pub attestations: Box<dyn AttestationStore>,
+}
+
+impl Default for Stores {
+    fn default() -> Self {
+        Self {
+            tasks: Box::new(InMemoryTaskStore::new()),
+            artifacts: Box::new(InMemoryArtifactStore::new()),
+            timelines: Box::new(InMemoryTimelineStore::new()),
+            attestations: Box::new(InMemoryAttestationStore::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(stage: &str) -> TaskTimelineEvent {
+        TaskTimelineEvent {
+            timestamp: chrono::Utc::now(),
+            stage: stage.to_string(),
+            detail: String::new(),
+            actor: None,
+            hlc: Default::default(),
+        }
+    }
+
+    #[test]
+    fn in_memory_stores_behave_as_maps() {
+        let mut tasks: Box<dyn TaskStore> = Box::new(InMemoryTaskStore::new());
+        let task = Task::new("write the report".to_string(), 1, 1);
+        let id = task.task_id.clone();
+        assert!(tasks.insert(id.clone(), task).is_none());
+        assert!(tasks.contains_key(&id));
+        assert_eq!(tasks[&id].description, "write the report");
+        tasks.get_mut(&id).unwrap().tier_level = 2;
+        assert_eq!(tasks.values().map(|t| t.tier_level).collect::<Vec<_>>(), vec![2]);
+        assert!(tasks.remove(&id).is_some());
+        assert!(tasks.is_empty());
+        assert!(tasks.flush().is_ok());
+    }
+
+    #[test]
+    fn timeline_push_creates_and_appends() {
+        let mut timelines: Box<dyn TimelineStore> = Box::new(InMemoryTimelineStore::new());
+        timelines.push("t1", event("injected"));
+        timelines.push("t1", event("proposed"));
+        let stages: Vec<&str> = timelines["t1"].iter().map(|e| e.stage.as_str()).collect();
+        assert_eq!(stages, vec!["injected", "proposed"]);
+        assert_eq!(timelines.len(), 1);
+    }
+}
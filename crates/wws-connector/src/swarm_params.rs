@@ -0,0 +1,163 @@
+//! Swarm-wide shared parameters.
+//!
+//! The swarm creator acts as parameter authority: it signs a
+//! [`SwarmParameters`] record and publishes it on the swarm's params topic
+//! and to the DHT. Members adopt the first valid record they see for their
+//! swarm and afterwards only newer versions signed by the same key, so every
+//! node runs consensus with the same branching factor, stage timeouts and
+//! quorum. Any member may propose a change; the authority issues the next
+//! version once a majority of active members approve it.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use wws_protocol::messages::ParamsChangeProposeParams;
+use wws_protocol::{AgentId, ProtocolMethod, SwarmMessage, SwarmParameterChanges, SwarmParameters, SwarmTopics};
+
+/// How long a parameter change proposal stays open for votes.
+pub const PARAMS_PROPOSAL_TTL_SECS: i64 = 600;
+
+/// Branching factor derived from the swarm size, used when the swarm
+/// parameters leave it at 0.
+pub fn dynamic_branching_factor(swarm_size: u64) -> u64 {
+    let approx = (swarm_size as f64).sqrt().round() as u64;
+    approx.clamp(3, 10)
+}
+
+/// What to do with a parameter record received from the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamsDecision {
+    /// The record replaces the current one.
+    Adopt,
+    /// Valid, but not newer than the current record or issued by another
+    /// authority.
+    Ignore,
+    /// The signature does not verify against `creator_key`.
+    InvalidSignature,
+}
+
+/// Decide whether `incoming` should replace `current`.
+pub fn decide(current: Option<&SwarmParameters>, incoming: &SwarmParameters) -> ParamsDecision {
+    if !incoming.verify() {
+        return ParamsDecision::InvalidSignature;
+    }
+    match current {
+        None => ParamsDecision::Adopt,
+        Some(current)
+            if current.creator_key == incoming.creator_key && incoming.version > current.version =>
+        {
+            ParamsDecision::Adopt
+        }
+        Some(_) => ParamsDecision::Ignore,
+    }
+}
+
+/// Reject changes that would leave the swarm unable to reach consensus.
+pub fn validate_changes(changes: &SwarmParameterChanges) -> Result<(), String> {
+    if changes.branching_factor == Some(1) {
+        return Err("branching_factor must be 0 (dynamic) or at least 2".to_string());
+    }
+    if changes.proposal_timeout_secs == Some(0) || changes.voting_timeout_secs == Some(0) {
+        return Err("Stage timeouts must be at least 1 second".to_string());
+    }
+    Ok(())
+}
+
+/// A parameter change proposal and the votes cast on it so far.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamsProposal {
+    pub proposal_id: String,
+    pub base_version: u64,
+    pub changes: SwarmParameterChanges,
+    pub proposer: AgentId,
+    pub expires_at: DateTime<Utc>,
+    /// Vote per voter DID; the proposer approves implicitly.
+    pub votes: BTreeMap<String, bool>,
+}
+
+impl From<ParamsChangeProposeParams> for ParamsProposal {
+    fn from(params: ParamsChangeProposeParams) -> Self {
+        let mut votes = BTreeMap::new();
+        votes.insert(params.proposer.to_string(), true);
+        Self {
+            proposal_id: params.proposal_id,
+            base_version: params.base_version,
+            changes: params.changes,
+            proposer: params.proposer,
+            expires_at: params.expires_at,
+            votes,
+        }
+    }
+}
+
+impl ParamsProposal {
+    pub fn approvals(&self) -> usize {
+        self.votes.values().filter(|approve| **approve).count()
+    }
+
+    /// Whether more than half of the active members approve.
+    pub fn is_approved(&self, active_members: usize) -> bool {
+        self.approvals() * 2 > active_members.max(1)
+    }
+}
+
+/// Publish a parameter record on the swarm's params topic and to the DHT.
+pub async fn publish_swarm_params(network_handle: &wws_network::SwarmHandle, params: &SwarmParameters) {
+    let msg = SwarmMessage::new(
+        ProtocolMethod::SwarmParams.as_str(),
+        serde_json::to_value(params).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let topic = SwarmTopics::params_for(params.swarm_id.as_str());
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            tracing::debug!(error = %e, "Failed to publish swarm parameters");
+        }
+    }
+
+    let dht_key = format!("{}{}", wws_protocol::SWARM_PARAMS_PREFIX, params.swarm_id);
+    if let Ok(value) = serde_json::to_vec(params) {
+        if let Err(e) = network_handle.put_dht_record(dht_key.into_bytes(), value).await {
+            tracing::debug!(error = %e, "Failed to publish swarm parameters to DHT");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::{QuorumPolicy, SwarmId};
+
+    fn record(key: &ed25519_dalek::SigningKey, version: u64) -> SwarmParameters {
+        let mut params = SwarmParameters {
+            swarm_id: SwarmId::new("s1".to_string()),
+            version,
+            branching_factor: 0,
+            proposal_timeout_secs: 30,
+            voting_timeout_secs: 30,
+            quorum: QuorumPolicy::default(),
+            creator_key: String::new(),
+            issued_at: Utc::now(),
+            signature: String::new(),
+        };
+        params.sign(key);
+        params
+    }
+
+    #[test]
+    fn only_newer_records_from_the_same_authority_are_adopted() {
+        let creator = wws_protocol::crypto::generate_keypair();
+        let other = wws_protocol::crypto::generate_keypair();
+        let v1 = record(&creator, 1);
+
+        assert_eq!(decide(None, &v1), ParamsDecision::Adopt);
+        assert_eq!(decide(Some(&v1), &record(&creator, 2)), ParamsDecision::Adopt);
+        assert_eq!(decide(Some(&record(&creator, 2)), &v1), ParamsDecision::Ignore);
+        assert_eq!(decide(Some(&v1), &record(&other, 5)), ParamsDecision::Ignore);
+
+        let mut forged = record(&creator, 3);
+        forged.voting_timeout_secs = 1;
+        assert_eq!(decide(Some(&v1), &forged), ParamsDecision::InvalidSignature);
+    }
+}
@@ -0,0 +1,215 @@
+//! Context bundles for agents working on a task.
+//!
+//! An agent handed a subtask usually needs more than its description: what
+//! the parent task asked for, why the winning plan split it the way it did,
+//! what its siblings have already produced and the documents attached along
+//! the way. `swarm.get_task_context` assembles all of it into one
+//! [`TaskContext`], so agent bridges do not each gather it themselves.
+//!
+//! The bundle is held to a byte budget, filled in order of importance: the
+//! task's own description, its ancestors, the plan rationale, the siblings'
+//! descriptions, then their results and finally attachment contents. No
+//! single text takes more than a share of the budget; a text cut short is
+//! marked `truncated`, and one left out once the budget runs out has no
+//! `text` at all.
+
+use serde::Serialize;
+use wws_protocol::{AgentId, TaskStatus};
+
+/// Budget of a bundle when the caller does not give one.
+pub const DEFAULT_CONTEXT_BYTES: usize = 32 * 1024;
+/// Largest budget a caller may ask for.
+pub const MAX_CONTEXT_BYTES: usize = 256 * 1024;
+/// Most ancestors listed, nearest first.
+pub const MAX_CONTEXT_ANCESTORS: usize = 8;
+
+/// How much text a bundle may carry.
+#[derive(Debug, Clone, Copy)]
+pub struct ContextLimits {
+    /// Bytes of text in the whole bundle.
+    pub max_bytes: usize,
+    /// Bytes of any one text.
+    pub max_field_bytes: usize,
+}
+
+impl Default for ContextLimits {
+    fn default() -> Self {
+        Self::with_max_bytes(DEFAULT_CONTEXT_BYTES)
+    }
+}
+
+impl ContextLimits {
+    /// A budget of `max_bytes`, at most [`MAX_CONTEXT_BYTES`], of which one
+    /// text may take a quarter.
+    pub fn with_max_bytes(max_bytes: usize) -> Self {
+        let max_bytes = max_bytes.min(MAX_CONTEXT_BYTES);
+        Self {
+            max_bytes,
+            max_field_bytes: (max_bytes / 4).max(1),
+        }
+    }
+}
+
+/// A text as included in the bundle.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Snippet {
+    /// The text, possibly cut short; absent once the budget ran out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Length of the full text, in bytes.
+    pub bytes: usize,
+    pub truncated: bool,
+}
+
+/// What is left of a bundle's budget.
+#[derive(Debug, Clone)]
+pub struct ContextBudget {
+    limits: ContextLimits,
+    used: usize,
+    truncated: bool,
+}
+
+impl ContextBudget {
+    pub fn new(limits: ContextLimits) -> Self {
+        Self {
+            limits,
+            used: 0,
+            truncated: false,
+        }
+    }
+
+    /// Spend budget on as much of `text` as it allows.
+    pub fn take(&mut self, text: &str) -> Snippet {
+        let allowed = self
+            .limits
+            .max_field_bytes
+            .min(self.limits.max_bytes - self.used);
+        if text.len() <= allowed {
+            self.used += text.len();
+            return Snippet {
+                text: Some(text.to_string()),
+                bytes: text.len(),
+                truncated: false,
+            };
+        }
+        self.truncated = true;
+        let mut cut = allowed;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        if cut == 0 {
+            return Snippet {
+                text: None,
+                bytes: text.len(),
+                truncated: true,
+            };
+        }
+        self.used += cut;
+        Snippet {
+            text: Some(text[..cut].to_string()),
+            bytes: text.len(),
+            truncated: true,
+        }
+    }
+
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Whether any text was cut short or left out.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// A task above the one the bundle is for.
+#[derive(Debug, Clone, Serialize)]
+pub struct AncestorContext {
+    pub task_id: String,
+    pub description: Snippet,
+}
+
+/// The plan that created the task.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanContext {
+    pub plan_id: String,
+    pub proposer: Option<AgentId>,
+    /// Absent when this connector does not hold the plan.
+    pub rationale: Option<Snippet>,
+    /// The task's position among the plan's subtasks, from 1.
+    pub subtask_index: Option<u32>,
+    pub subtask_count: Option<usize>,
+}
+
+/// Another subtask of the same parent.
+#[derive(Debug, Clone, Serialize)]
+pub struct SiblingContext {
+    pub task_id: String,
+    pub status: TaskStatus,
+    pub assigned_to: Option<AgentId>,
+    pub description: Snippet,
+    /// Absent until a result is known, or when the viewer may not read it.
+    pub result: Option<Snippet>,
+}
+
+/// A document attached to the task or one of its ancestors.
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentContext {
+    /// Task the document is attached to.
+    pub task_id: String,
+    pub name: String,
+    pub cid: String,
+    pub content_type: Option<String>,
+    pub size_bytes: u64,
+    /// Absent when this connector does not hold the content.
+    pub content: Option<Snippet>,
+}
+
+/// Everything an agent needs to know to work on one task.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskContext {
+    pub task_id: String,
+    pub status: TaskStatus,
+    pub tier_level: u32,
+    pub description: Snippet,
+    pub capabilities_required: Vec<String>,
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+    /// Nearest first.
+    pub ancestors: Vec<AncestorContext>,
+    pub plan: Option<PlanContext>,
+    pub siblings: Vec<SiblingContext>,
+    pub attachments: Vec<AttachmentContext>,
+    pub max_bytes: usize,
+    pub used_bytes: usize,
+    pub truncated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_caps_each_text_and_the_bundle() {
+        let mut budget = ContextBudget::new(ContextLimits::with_max_bytes(40));
+        assert_eq!(budget.take("short").text.as_deref(), Some("short"));
+        assert!(!budget.truncated());
+
+        // One text takes at most a quarter of the budget, cut on a char
+        // boundary.
+        let long = budget.take("ééééééé");
+        assert_eq!(long.text.as_deref(), Some("ééééé"));
+        assert_eq!((long.bytes, long.truncated), (14, true));
+
+        assert_eq!(budget.take("0123456789").text.as_deref(), Some("0123456789"));
+        assert_eq!(budget.used(), 25);
+        assert_eq!(budget.take("0123456789").text.as_deref(), Some("0123456789"));
+        let last = budget.take("0123456789");
+        assert_eq!(last.text.as_deref(), Some("01234"));
+        let dropped = budget.take("anything");
+        assert_eq!((dropped.text, dropped.bytes, dropped.truncated), (None, 8, true));
+        assert_eq!(budget.used(), 40);
+        assert!(budget.truncated());
+
+        assert_eq!(ContextLimits::with_max_bytes(usize::MAX).max_bytes, MAX_CONTEXT_BYTES);
+    }
+}
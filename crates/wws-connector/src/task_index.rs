@@ -0,0 +1,524 @@
+//! Searching the task store.
+//!
+//! `swarm.search_tasks` filters tasks by status, assignee, tier, parent,
+//! description and creation time, newest or oldest first, a page at a time.
+//! [`TaskStore::search`](crate::storage::TaskStore::search) answers a
+//! [`TaskQuery`]; its default implementation scans every task, and the
+//! in-memory store answers from a [`TaskIndex`] instead, so a search only
+//! walks the tasks matching its most selective filter.
+//!
+//! Pages are ordered by creation time, then task ID. A cursor names the last
+//! task of a page, so paging stays stable while tasks are added.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Bound;
+
+use chrono::{DateTime, Utc};
+use wws_protocol::{Task, TaskStatus};
+
+use crate::storage::{KeyedStore, TaskStore};
+
+/// Tasks returned per page when the query does not say.
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+/// Most tasks returned per page.
+pub const MAX_SEARCH_LIMIT: usize = 500;
+
+/// Position of a task in search order: creation time, then task ID.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskCursor {
+    pub created_at: DateTime<Utc>,
+    pub task_id: String,
+}
+
+impl TaskCursor {
+    /// Cursor of `task`, stored under `task_id`.
+    pub fn of(task_id: &str, task: &Task) -> Self {
+        Self {
+            created_at: task.created_at,
+            task_id: task_id.to_string(),
+        }
+    }
+
+    /// The cursor as handed to clients: `<created_at nanos>/<task_id>`.
+    pub fn encode(&self) -> String {
+        let nanos = self.created_at.timestamp_nanos_opt().unwrap_or_default();
+        format!("{}/{}", nanos, self.task_id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (nanos, task_id) = cursor.split_once('/')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_nanos(nanos.parse().ok()?),
+            task_id: task_id.to_string(),
+        })
+    }
+}
+
+/// Which tasks a search returns, and which page of them.
+#[derive(Debug, Clone)]
+pub struct TaskQuery {
+    /// Empty means every status.
+    pub statuses: Vec<TaskStatus>,
+    /// DID of the assigned agent.
+    pub assignee: Option<String>,
+    pub tier: Option<u32>,
+    pub parent: Option<String>,
+    /// Lowercase substring of the description.
+    pub contains: Option<String>,
+    pub created_since: Option<DateTime<Utc>>,
+    pub created_until: Option<DateTime<Utc>>,
+    pub newest_first: bool,
+    /// Only tasks after this one in search order.
+    pub after: Option<TaskCursor>,
+    pub limit: usize,
+}
+
+impl Default for TaskQuery {
+    fn default() -> Self {
+        Self {
+            statuses: Vec::new(),
+            assignee: None,
+            tier: None,
+            parent: None,
+            contains: None,
+            created_since: None,
+            created_until: None,
+            newest_first: true,
+            after: None,
+            limit: DEFAULT_SEARCH_LIMIT,
+        }
+    }
+}
+
+impl TaskQuery {
+    /// Whether `task` passes the filters; the cursor is not considered.
+    pub fn matches(&self, task: &Task) -> bool {
+        (self.statuses.is_empty() || self.statuses.contains(&task.status))
+            && self
+                .assignee
+                .as_deref()
+                .is_none_or(|a| task.assigned_to.as_ref().is_some_and(|t| t.as_str() == a))
+            && self.tier.is_none_or(|t| task.tier_level == t)
+            && self
+                .parent
+                .as_deref()
+                .is_none_or(|p| task.parent_task_id.as_deref() == Some(p))
+            && self
+                .contains
+                .as_deref()
+                .is_none_or(|c| task.description.to_lowercase().contains(c))
+            && self.created_since.is_none_or(|t| task.created_at >= t)
+            && self.created_until.is_none_or(|t| task.created_at <= t)
+    }
+
+    /// Whether `cursor` comes after the query's cursor in its order.
+    fn is_after_cursor(&self, cursor: &TaskCursor) -> bool {
+        match &self.after {
+            None => true,
+            Some(after) if self.newest_first => cursor < after,
+            Some(after) => cursor > after,
+        }
+    }
+}
+
+/// One page of search results.
+#[derive(Debug, Clone, Default)]
+pub struct TaskPage {
+    pub task_ids: Vec<String>,
+    /// Tasks matching the filters across all pages.
+    pub total: usize,
+    /// Cursor of the next page, if there is one.
+    pub next: Option<TaskCursor>,
+}
+
+/// Page `query` out of matching tasks, visited in search order.
+fn paginate<'a>(query: &TaskQuery, ordered: impl Iterator<Item = &'a TaskCursor>) -> TaskPage {
+    let mut page = TaskPage::default();
+    let mut last = None;
+    for cursor in ordered {
+        page.total += 1;
+        if !query.is_after_cursor(cursor) {
+            continue;
+        }
+        if page.task_ids.len() < query.limit {
+            page.task_ids.push(cursor.task_id.clone());
+            last = Some(cursor);
+        } else if page.next.is_none() {
+            page.next = last.cloned();
+        }
+    }
+    page
+}
+
+/// Search by visiting every task, for stores without an index.
+pub fn scan<'a>(
+    tasks: impl Iterator<Item = (&'a String, &'a Task)>,
+    query: &TaskQuery,
+) -> TaskPage {
+    let mut matching: Vec<TaskCursor> = tasks
+        .filter(|(_, t)| query.matches(t))
+        .map(|(id, t)| TaskCursor::of(id, t))
+        .collect();
+    matching.sort();
+    if query.newest_first {
+        paginate(query, matching.iter().rev())
+    } else {
+        paginate(query, matching.iter())
+    }
+}
+
+/// The indexed fields of a task, as last indexed.
+#[derive(Debug, Clone)]
+struct Indexed {
+    cursor: TaskCursor,
+    status: TaskStatus,
+    assignee: Option<String>,
+    tier: u32,
+    parent: Option<String>,
+}
+
+impl Indexed {
+    fn of(task_id: &str, task: &Task) -> Self {
+        Self {
+            cursor: TaskCursor::of(task_id, task),
+            status: task.status,
+            assignee: task.assigned_to.as_ref().map(|a| a.to_string()),
+            tier: task.tier_level,
+            parent: task.parent_task_id.clone(),
+        }
+    }
+}
+
+/// Tasks in search order, overall and by status, assignee, tier and parent.
+#[derive(Debug, Clone, Default)]
+pub struct TaskIndex {
+    entries: HashMap<String, Indexed>,
+    by_created: BTreeSet<TaskCursor>,
+    by_status: Postings<TaskStatus>,
+    by_assignee: Postings<String>,
+    by_tier: Postings<u32>,
+    by_parent: Postings<String>,
+}
+
+type Postings<K> = HashMap<K, BTreeSet<TaskCursor>>;
+
+fn add_to<K: Hash + Eq>(index: &mut Postings<K>, key: K, cursor: &TaskCursor) {
+    index.entry(key).or_default().insert(cursor.clone());
+}
+
+fn remove_from<K: Hash + Eq>(index: &mut Postings<K>, key: &K, cursor: &TaskCursor) {
+    if let Some(set) = index.get_mut(key) {
+        set.remove(cursor);
+        if set.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+impl TaskIndex {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Index `task` under `task_id`, replacing what was indexed there.
+    pub fn insert(&mut self, task_id: &str, task: &Task) {
+        self.remove(task_id);
+        let entry = Indexed::of(task_id, task);
+        let cursor = &entry.cursor;
+        self.by_created.insert(cursor.clone());
+        add_to(&mut self.by_status, entry.status, cursor);
+        if let Some(assignee) = &entry.assignee {
+            add_to(&mut self.by_assignee, assignee.clone(), cursor);
+        }
+        add_to(&mut self.by_tier, entry.tier, cursor);
+        if let Some(parent) = &entry.parent {
+            add_to(&mut self.by_parent, parent.clone(), cursor);
+        }
+        self.entries.insert(task_id.to_string(), entry);
+    }
+
+    pub fn remove(&mut self, task_id: &str) {
+        let Some(entry) = self.entries.remove(task_id) else {
+            return;
+        };
+        let cursor = &entry.cursor;
+        self.by_created.remove(cursor);
+        remove_from(&mut self.by_status, &entry.status, cursor);
+        if let Some(assignee) = &entry.assignee {
+            remove_from(&mut self.by_assignee, assignee, cursor);
+        }
+        remove_from(&mut self.by_tier, &entry.tier, cursor);
+        if let Some(parent) = &entry.parent {
+            remove_from(&mut self.by_parent, parent, cursor);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The smallest set of tasks that can match `query`'s indexed filters.
+    fn candidates(&self, query: &TaskQuery) -> Vec<&TaskCursor> {
+        let mut sets: Vec<Vec<&BTreeSet<TaskCursor>>> = Vec::new();
+        if !query.statuses.is_empty() {
+            sets.push(
+                query
+                    .statuses
+                    .iter()
+                    .filter_map(|s| self.by_status.get(s))
+                    .collect(),
+            );
+        }
+        if let Some(assignee) = &query.assignee {
+            sets.push(self.by_assignee.get(assignee).into_iter().collect());
+        }
+        if let Some(tier) = query.tier {
+            sets.push(self.by_tier.get(&tier).into_iter().collect());
+        }
+        if let Some(parent) = &query.parent {
+            sets.push(self.by_parent.get(parent).into_iter().collect());
+        }
+        if let (Some(since), Some(until)) = (query.created_since, query.created_until) {
+            if since > until {
+                return Vec::new();
+            }
+        }
+        let range = (
+            query
+                .created_since
+                .map_or(Bound::Unbounded, |t| Bound::Included(range_start(t))),
+            query
+                .created_until
+                .map_or(Bound::Unbounded, |t| Bound::Included(range_end(t))),
+        );
+        let narrowest = sets
+            .into_iter()
+            .min_by_key(|set| set.iter().map(|s| s.len()).sum::<usize>());
+        match narrowest {
+            Some(sets) => {
+                let runs = sets.len();
+                let mut candidates: Vec<&TaskCursor> =
+                    sets.into_iter().flat_map(|s| s.range(range.clone())).collect();
+                // Several statuses yield several ordered runs.
+                if runs > 1 {
+                    candidates.sort();
+                }
+                candidates
+            }
+            None => self.by_created.range(range).collect(),
+        }
+    }
+
+    /// Search the indexed tasks; `get` looks up a task for the filters the
+    /// index does not cover.
+    pub fn search<'a>(&self, query: &TaskQuery, get: impl Fn(&str) -> Option<&'a Task>) -> TaskPage {
+        let matching: Vec<&TaskCursor> = self
+            .candidates(query)
+            .into_iter()
+            .filter(|cursor| get(&cursor.task_id).is_some_and(|task| query.matches(task)))
+            .collect();
+        if query.newest_first {
+            paginate(query, matching.into_iter().rev())
+        } else {
+            paginate(query, matching.into_iter())
+        }
+    }
+}
+
+fn range_start(created_at: DateTime<Utc>) -> TaskCursor {
+    TaskCursor {
+        created_at,
+        task_id: String::new(),
+    }
+}
+
+/// The last cursor created at `created_at`: task IDs are compared as
+/// strings, so one made of `char::MAX` sorts after any real ID.
+fn range_end(created_at: DateTime<Utc>) -> TaskCursor {
+    TaskCursor {
+        created_at,
+        task_id: char::MAX.to_string(),
+    }
+}
+
+/// Tasks kept in memory, with a [`TaskIndex`] over them.
+///
+/// A task borrowed mutably through `get_mut` may change any indexed field,
+/// so it is re-indexed before the next search.
+#[derive(Debug, Default)]
+pub struct IndexedTaskStore {
+    tasks: HashMap<String, Task>,
+    index: TaskIndex,
+    /// Tasks handed out mutably since they were last indexed.
+    dirty: HashSet<String>,
+    /// Every task was handed out mutably.
+    all_dirty: bool,
+}
+
+impl IndexedTaskStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring the index up to date with changes made in place.
+    fn reindex(&mut self) {
+        if self.all_dirty {
+            self.index.clear();
+            for (task_id, task) in &self.tasks {
+                self.index.insert(task_id, task);
+            }
+            self.all_dirty = false;
+            self.dirty.clear();
+            return;
+        }
+        for task_id in std::mem::take(&mut self.dirty) {
+            match self.tasks.get(&task_id) {
+                Some(task) => self.index.insert(&task_id, task),
+                None => self.index.remove(&task_id),
+            }
+        }
+    }
+}
+
+impl KeyedStore<Task> for IndexedTaskStore {
+    fn get(&self, task_id: &str) -> Option<&Task> {
+        self.tasks.get(task_id)
+    }
+
+    fn get_mut(&mut self, task_id: &str) -> Option<&mut Task> {
+        let task = self.tasks.get_mut(task_id)?;
+        self.dirty.insert(task_id.to_string());
+        Some(task)
+    }
+
+    fn insert(&mut self, task_id: String, task: Task) -> Option<Task> {
+        self.dirty.remove(&task_id);
+        self.index.insert(&task_id, &task);
+        self.tasks.insert(task_id, task)
+    }
+
+    fn remove(&mut self, task_id: &str) -> Option<Task> {
+        self.dirty.remove(task_id);
+        self.index.remove(task_id);
+        self.tasks.remove(task_id)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&String, &Task)> + '_> {
+        Box::new(self.tasks.iter())
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&String, &mut Task)> + '_> {
+        self.all_dirty = true;
+        Box::new(self.tasks.iter_mut())
+    }
+
+    fn len(&self) -> usize {
+        self.tasks.len()
+    }
+}
+
+impl TaskStore for IndexedTaskStore {
+    fn search(&mut self, query: &TaskQuery) -> TaskPage {
+        self.reindex();
+        let tasks = &self.tasks;
+        self.index.search(query, |id| tasks.get(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::AgentId;
+
+    fn task(description: &str, tier: u32, minutes_ago: i64) -> Task {
+        let mut task = Task::new(description.to_string(), tier, 1);
+        task.created_at = Utc::now() - chrono::Duration::minutes(minutes_ago);
+        task
+    }
+
+    fn ids(tasks: &[&Task]) -> Vec<String> {
+        tasks.iter().map(|t| t.task_id.clone()).collect()
+    }
+
+    #[test]
+    fn indexed_search_agrees_with_a_scan_and_follows_changes() {
+        let mut store: Box<dyn TaskStore> = Box::new(IndexedTaskStore::new());
+        let root = task("Plan the launch", 1, 60);
+        let mut draft = task("Draft the announcement", 2, 50);
+        draft.parent_task_id = Some(root.task_id.clone());
+        let mut review = task("Review the announcement", 2, 40);
+        review.parent_task_id = Some(root.task_id.clone());
+        review.status = TaskStatus::InProgress;
+        review.assigned_to = Some(AgentId::new("did:swarm:alice".to_string()));
+        let budget = task("Check the budget", 2, 30);
+        for t in [&root, &draft, &review, &budget] {
+            store.insert(t.task_id.clone(), t.clone());
+        }
+
+        let scanned = |store: &dyn TaskStore, query: &TaskQuery| scan(store.iter(), query).task_ids;
+        let queries = [
+            TaskQuery::default(),
+            TaskQuery { tier: Some(2), newest_first: false, ..Default::default() },
+            TaskQuery { parent: Some(root.task_id.clone()), ..Default::default() },
+            TaskQuery { contains: Some("announcement".into()), ..Default::default() },
+            TaskQuery {
+                statuses: vec![TaskStatus::Pending, TaskStatus::InProgress],
+                created_since: Some(draft.created_at),
+                created_until: Some(review.created_at),
+                ..Default::default()
+            },
+        ];
+        for query in &queries {
+            assert_eq!(store.search(query).task_ids, scanned(store.as_ref(), query));
+        }
+        let announcement = store.search(&queries[3]);
+        assert_eq!(announcement.task_ids, ids(&[&review, &draft]));
+        assert_eq!(announcement.total, 2);
+
+        // Changes made in place are picked up by the next search.
+        let by_alice = TaskQuery { assignee: Some("did:swarm:alice".into()), ..Default::default() };
+        assert_eq!(store.search(&by_alice).task_ids, ids(&[&review]));
+        let moved = store.get_mut(&draft.task_id).unwrap();
+        moved.assigned_to = Some(AgentId::new("did:swarm:alice".to_string()));
+        moved.status = TaskStatus::Completed;
+        assert_eq!(store.search(&by_alice).task_ids, ids(&[&review, &draft]));
+        let completed = TaskQuery { statuses: vec![TaskStatus::Completed], ..Default::default() };
+        assert_eq!(store.search(&completed).task_ids, ids(&[&draft]));
+        store.remove(&draft.task_id);
+        assert!(store.search(&completed).task_ids.is_empty());
+        assert_eq!(store.search(&by_alice).task_ids, ids(&[&review]));
+    }
+
+    #[test]
+    fn pages_follow_the_cursor_in_either_order() {
+        let mut store: Box<dyn TaskStore> = Box::new(IndexedTaskStore::new());
+        let tasks: Vec<Task> = (0..5).map(|i| task(&format!("step {}", i), 1, 10 - i)).collect();
+        for t in &tasks {
+            store.insert(t.task_id.clone(), t.clone());
+        }
+
+        let mut query = TaskQuery { limit: 2, newest_first: false, ..Default::default() };
+        let mut seen = Vec::new();
+        loop {
+            let page = store.search(&query);
+            assert_eq!(page.total, 5);
+            seen.extend(page.task_ids);
+            match page.next {
+                Some(next) => query.after = Some(TaskCursor::decode(&next.encode()).unwrap()),
+                None => break,
+            }
+        }
+        assert_eq!(seen, tasks.iter().map(|t| t.task_id.clone()).collect::<Vec<_>>());
+
+        let newest = TaskQuery { limit: 2, ..Default::default() };
+        let first = store.search(&newest);
+        assert_eq!(first.task_ids, ids(&[&tasks[4], &tasks[3]]));
+        let second = store.search(&TaskQuery { after: first.next, ..newest });
+        assert_eq!(second.task_ids, ids(&[&tasks[2], &tasks[1]]));
+    }
+}
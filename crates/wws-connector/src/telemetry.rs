@@ -0,0 +1,189 @@
+//! OpenTelemetry export of task lifecycle spans.
+//!
+//! With `[telemetry] otlp_endpoint` set, every stage a task passes on this
+//! node (inject, proposal commit and reveal, votes, plan selection,
+//! assignment, results, ...) is exported over OTLP/HTTP as a span lasting
+//! from the task's previous stage on this node to this one.
+//!
+//! Spans from different nodes meet in one trace without any trace context
+//! in the protocol messages: the trace ID is derived from the root task ID,
+//! and every stage span of the task tree is a child of a root span whose
+//! ID is derived the same way. The node the task was injected at emits that
+//! root span. Jaeger or Tempo then show the whole task across the swarm,
+//! each span tagged with the node that recorded it.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use opentelemetry::trace::{
+    Span, SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, Tracer,
+    TracerProvider,
+};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use sha2::{Digest, Sha256};
+
+use crate::config::TelemetryConfig;
+
+/// Instrumentation scope of the exported spans.
+const TRACER_NAME: &str = "wws-connector";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether spans are being exported.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Start exporting spans to the configured OTLP endpoint. Returns the
+/// provider to shut down on exit, which flushes pending spans, or `None`
+/// when no endpoint is configured.
+pub fn init(config: &TelemetryConfig, agent_name: &str) -> anyhow::Result<Option<SdkTracerProvider>> {
+    let Some(endpoint) = &config.otlp_endpoint else {
+        return Ok(None);
+    };
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint.clone())
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create OTLP exporter: {}", e))?;
+    let resource = Resource::builder()
+        .with_service_name(config.service_name.clone())
+        .with_attribute(KeyValue::new("service.instance.id", agent_name.to_string()))
+        .build();
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build();
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(Some(provider))
+}
+
+/// Trace ID of a task tree and span ID of its root span, the same on
+/// every node.
+pub fn trace_ids(root_task_id: &str) -> (TraceId, SpanId) {
+    let digest = Sha256::digest(format!("wws-task:{}", root_task_id).as_bytes());
+    let mut trace_id = [0u8; 16];
+    let mut span_id = [0u8; 8];
+    trace_id.copy_from_slice(&digest[..16]);
+    span_id.copy_from_slice(&digest[16..24]);
+    (TraceId::from_bytes(trace_id), SpanId::from_bytes(span_id))
+}
+
+/// One task stage recorded on this node.
+#[derive(Debug, Clone)]
+pub struct StageSpan<'a> {
+    pub root_task_id: &'a str,
+    pub task_id: &'a str,
+    pub stage: &'a str,
+    pub detail: &'a str,
+    /// Agent the stage is attributed to, if known.
+    pub actor: Option<&'a str>,
+    /// Agent ID of this connector.
+    pub node: &'a str,
+    /// The task's previous stage on this node, or `ended` for the first.
+    pub started: chrono::DateTime<chrono::Utc>,
+    pub ended: chrono::DateTime<chrono::Utc>,
+}
+
+/// Export `stage` through the global tracer provider, if enabled.
+pub fn record_stage(stage: &StageSpan<'_>) {
+    if is_enabled() {
+        export_stage(&opentelemetry::global::tracer_provider().tracer(TRACER_NAME), stage);
+    }
+}
+
+/// Export `stage` with `tracer`, and the tree's root span when this is the
+/// node the root task was injected at.
+pub fn export_stage<T: Tracer>(tracer: &T, stage: &StageSpan<'_>) {
+    let (trace_id, root_span_id) = trace_ids(stage.root_task_id);
+    let mut attributes = vec![
+        KeyValue::new("wws.task_id", stage.task_id.to_string()),
+        KeyValue::new("wws.root_task_id", stage.root_task_id.to_string()),
+        KeyValue::new("wws.stage", stage.stage.to_string()),
+        KeyValue::new("wws.node", stage.node.to_string()),
+    ];
+    if let Some(actor) = stage.actor {
+        attributes.push(KeyValue::new("wws.actor", actor.to_string()));
+    }
+
+    // Peers record "injected" too, but without an actor.
+    if stage.stage == "injected" && stage.actor.is_some() && stage.task_id == stage.root_task_id {
+        tracer
+            .span_builder(format!("task {}", stage.root_task_id))
+            .with_trace_id(trace_id)
+            .with_span_id(root_span_id)
+            .with_start_time(stage.ended)
+            .with_attributes(attributes.clone())
+            .start_with_context(tracer, &Context::new())
+            .end_with_timestamp(stage.ended.into());
+    }
+
+    let parent = Context::new().with_remote_span_context(SpanContext::new(
+        trace_id,
+        root_span_id,
+        TraceFlags::SAMPLED,
+        true,
+        TraceState::default(),
+    ));
+    if !stage.detail.is_empty() {
+        attributes.push(KeyValue::new("wws.detail", stage.detail.to_string()));
+    }
+    let mut span = tracer
+        .span_builder(format!("task.{}", stage.stage))
+        .with_start_time(stage.started.min(stage.ended))
+        .with_attributes(attributes)
+        .start_with_context(tracer, &parent);
+    span.end_with_timestamp(stage.ended.into());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_sdk::trace::InMemorySpanExporter;
+
+    #[test]
+    fn stages_on_different_nodes_share_the_task_trace() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer = provider.tracer(TRACER_NAME);
+        let now = chrono::Utc::now();
+        let stage = |task_id, stage, actor, node| StageSpan {
+            root_task_id: "root-1",
+            task_id,
+            stage,
+            detail: "",
+            actor,
+            node,
+            started: now - chrono::Duration::seconds(5),
+            ended: now,
+        };
+
+        // The injecting node, a peer seeing the injection, and an executor.
+        export_stage(&tracer, &stage("root-1", "injected", Some("did:swarm:a"), "did:swarm:a"));
+        export_stage(&tracer, &stage("root-1", "injected", None, "did:swarm:b"));
+        export_stage(&tracer, &stage("root-1-st-1", "result_submitted", Some("did:swarm:c"), "did:swarm:c"));
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let names: Vec<_> = spans.iter().map(|s| s.name.to_string()).collect();
+        assert_eq!(names, ["task root-1", "task.injected", "task.injected", "task.result_submitted"]);
+        let (trace_id, root_span_id) = trace_ids("root-1");
+        assert!(spans.iter().all(|s| s.span_context.trace_id() == trace_id));
+        assert_eq!(spans[0].span_context.span_id(), root_span_id);
+        assert!(spans[1..].iter().all(|s| s.parent_span_id == root_span_id));
+        let result = &spans[3];
+        assert!(result
+            .attributes
+            .iter()
+            .any(|kv| kv.key.as_str() == "wws.node" && kv.value.as_str() == "did:swarm:c"));
+        assert_eq!(
+            result.end_time.duration_since(result.start_time).unwrap(),
+            std::time::Duration::from_secs(5)
+        );
+        assert_ne!(trace_ids("root-2").0, trace_id);
+    }
+}
@@ -0,0 +1,269 @@
+//! On-disk persistence of the message trace.
+//!
+//! The in-memory `message_trace` keeps the last 5000 events and is lost on
+//! restart. With `[trace] dir` set, every event is also appended as one
+//! JSON line to a file in that directory. A new file is started once the
+//! current one would exceed `max_file_bytes`; files last written more than
+//! `max_age_secs` ago are deleted, and then the oldest ones until the rest
+//! fit in `max_total_bytes`. File names sort in creation order.
+//!
+//! [`read_trace`] reads the files back for `wws-connector trace`, which
+//! queries past traffic offline, without starting a connector. The same
+//! rotation backs the JSONL event log sink, under its own file prefix.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::TraceConfig;
+use crate::connector::MessageTraceEvent;
+
+const FILE_PREFIX: &str = "trace-";
+const FILE_SUFFIX: &str = ".jsonl";
+
+/// The trace file being written and the rotation policy.
+#[derive(Debug)]
+pub struct TraceLog {
+    dir: PathBuf,
+    prefix: &'static str,
+    file: File,
+    path: PathBuf,
+    bytes: u64,
+    max_file_bytes: u64,
+    max_total_bytes: u64,
+    max_age: std::time::Duration,
+    record_payloads: bool,
+}
+
+impl TraceLog {
+    /// Open the trace directory, creating it if needed, and continue the
+    /// newest file while it has room.
+    pub fn open(dir: &Path, config: &TraceConfig) -> std::io::Result<Self> {
+        Self::open_with_prefix(dir, FILE_PREFIX, config)
+    }
+
+    /// Like [`Self::open`], for files named `<prefix><timestamp>.jsonl`.
+    /// Files with other prefixes in `dir` are left alone.
+    pub fn open_with_prefix(
+        dir: &Path,
+        prefix: &'static str,
+        config: &TraceConfig,
+    ) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let newest = trace_files(dir, prefix)?.pop();
+        let (path, bytes) = match newest {
+            Some((path, bytes)) if bytes < config.max_file_bytes => (path, bytes),
+            _ => (new_file_path(dir, prefix), 0),
+        };
+        let mut log = Self {
+            dir: dir.to_path_buf(),
+            prefix,
+            file: OpenOptions::new().create(true).append(true).open(&path)?,
+            path,
+            bytes,
+            max_file_bytes: config.max_file_bytes,
+            max_total_bytes: config.max_total_bytes,
+            max_age: std::time::Duration::from_secs(config.max_age_secs),
+            record_payloads: config.record_payloads,
+        };
+        log.prune()?;
+        Ok(log)
+    }
+
+    /// Whether inbound messages are written with their payloads.
+    pub fn records_payloads(&self) -> bool {
+        self.record_payloads
+    }
+
+    /// Append `event`, rotating and pruning first when the current file
+    /// would grow past its cap.
+    pub fn append(&mut self, event: &impl serde::Serialize) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(event).map_err(std::io::Error::other)?;
+        line.push(b'\n');
+        if self.bytes > 0 && self.bytes + line.len() as u64 > self.max_file_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(&line)?;
+        self.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let mut path = new_file_path(&self.dir, self.prefix);
+        while path.exists() {
+            path = new_file_path(&self.dir, self.prefix);
+        }
+        self.file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.path = path;
+        self.bytes = 0;
+        self.prune()
+    }
+
+    /// Delete expired files, then the oldest ones beyond the total size
+    /// cap. The file being written is always kept.
+    fn prune(&mut self) -> std::io::Result<()> {
+        let now = std::time::SystemTime::now();
+        let mut kept = Vec::new();
+        for (path, bytes) in trace_files(&self.dir, self.prefix)? {
+            if path == self.path {
+                continue;
+            }
+            let expired = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > self.max_age);
+            if expired {
+                std::fs::remove_file(&path)?;
+            } else {
+                kept.push((path, bytes));
+            }
+        }
+        let mut total = self.bytes + kept.iter().map(|(_, bytes)| bytes).sum::<u64>();
+        for (path, bytes) in kept {
+            if total <= self.max_total_bytes {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            total -= bytes;
+        }
+        Ok(())
+    }
+}
+
+/// Which persisted events `wws-connector trace` prints.
+#[derive(Debug, Clone, Default)]
+pub struct TraceQuery {
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    pub method: Option<String>,
+    pub task_id: Option<String>,
+    pub peer: Option<String>,
+    pub direction: Option<String>,
+    /// Keep only the last this many matches.
+    pub limit: Option<usize>,
+}
+
+impl TraceQuery {
+    pub fn matches(&self, event: &MessageTraceEvent) -> bool {
+        self.since.is_none_or(|t| event.timestamp >= t)
+            && self.until.is_none_or(|t| event.timestamp <= t)
+            && self.method.as_ref().is_none_or(|m| event.method.as_ref() == Some(m))
+            && self.task_id.as_ref().is_none_or(|t| event.task_id.as_ref() == Some(t))
+            && self.peer.as_ref().is_none_or(|p| event.peer.as_ref() == Some(p))
+            && self.direction.as_ref().is_none_or(|d| &event.direction == d)
+    }
+}
+
+/// Events in the trace files of `dir` that match `query`, oldest first.
+/// Lines that do not parse, such as one cut short by a crash, are skipped.
+pub fn read_trace(dir: &Path, query: &TraceQuery) -> std::io::Result<Vec<MessageTraceEvent>> {
+    let mut events = Vec::new();
+    for (path, _) in trace_files(dir, FILE_PREFIX)? {
+        for line in BufReader::new(File::open(&path)?).lines() {
+            let Ok(event) = serde_json::from_str::<MessageTraceEvent>(&line?) else {
+                continue;
+            };
+            if query.matches(&event) {
+                events.push(event);
+            }
+        }
+    }
+    if let Some(limit) = query.limit {
+        events.drain(..events.len().saturating_sub(limit));
+    }
+    Ok(events)
+}
+
+/// Files in `dir` named with `prefix`, with their sizes, oldest first.
+fn trace_files(dir: &Path, prefix: &str) -> std::io::Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(prefix) || !name.ends_with(FILE_SUFFIX) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push((entry.path(), metadata.len()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn new_file_path(dir: &Path, prefix: &str) -> PathBuf {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.6fZ");
+    dir.join(format!("{}{}{}", prefix, stamp, FILE_SUFFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(n: usize) -> MessageTraceEvent {
+        MessageTraceEvent {
+            timestamp: chrono::Utc::now(),
+            direction: if n.is_multiple_of(2) { "inbound" } else { "outbound" }.to_string(),
+            peer: Some("12D3KooWpeer".to_string()),
+            topic: "/wws/1.0.0/s/public/tasks".to_string(),
+            method: Some(if n.is_multiple_of(3) { "task.assign" } else { "swarm.keepalive" }.to_string()),
+            task_id: Some(format!("task-{}", n)),
+            size_bytes: 100 + n,
+            outcome: "ok".to_string(),
+            payload: None,
+        }
+    }
+
+    #[test]
+    fn trace_files_rotate_within_caps_and_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TraceConfig {
+            dir: Some(dir.path().to_path_buf()),
+            max_file_bytes: 1024,
+            max_total_bytes: 4096,
+            max_age_secs: 3600,
+            record_payloads: false,
+        };
+        let mut log = TraceLog::open(dir.path(), &config).unwrap();
+        for n in 0..200 {
+            log.append(&event(n)).unwrap();
+        }
+        let files = trace_files(dir.path(), FILE_PREFIX).unwrap();
+        assert!(files.len() > 1);
+        assert!(files.iter().all(|(_, bytes)| *bytes <= config.max_file_bytes));
+        assert!(files.iter().map(|(_, bytes)| bytes).sum::<u64>() <= config.max_total_bytes);
+
+        // The oldest events were pruned; the newest are all there, in order.
+        let all = read_trace(dir.path(), &TraceQuery::default()).unwrap();
+        assert!(all.len() < 200);
+        assert_eq!(all.last().unwrap().task_id.as_deref(), Some("task-199"));
+        let first = all[0].size_bytes;
+        assert!(all.iter().enumerate().all(|(i, e)| e.size_bytes == first + i));
+
+        // A restart continues writing to the same directory.
+        drop(log);
+        let mut log = TraceLog::open(dir.path(), &config).unwrap();
+        log.append(&event(200)).unwrap();
+        let query = TraceQuery {
+            method: Some("task.assign".to_string()),
+            direction: Some("inbound".to_string()),
+            limit: Some(2),
+            ..TraceQuery::default()
+        };
+        let assigns = read_trace(dir.path(), &query).unwrap();
+        let ids: Vec<_> = assigns.iter().filter_map(|e| e.task_id.as_deref()).collect();
+        assert_eq!(ids, ["task-192", "task-198"]);
+
+        // Expired files go on the next rotation or restart.
+        let expiring = TraceConfig {
+            max_age_secs: 0,
+            ..config
+        };
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        drop(log);
+        TraceLog::open(dir.path(), &expiring).unwrap();
+        assert_eq!(trace_files(dir.path(), FILE_PREFIX).unwrap().len(), 1);
+    }
+}
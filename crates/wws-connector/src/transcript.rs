@@ -0,0 +1,64 @@
+//! Self-contained archives of a swarm run.
+//!
+//! `swarm.export_run` collects everything this connector saw of one task
+//! tree — the tasks themselves, their timelines, the deliberation thread,
+//! every ballot, the IRV rounds and the result artifacts — into a
+//! [`RunTranscript`], a plain JSON document that can be analysed offline.
+//! `swarm.import_run` loads a transcript back into a connector so the run
+//! can be inspected with the usual RPC methods and the operator console.
+//! Imported tasks are never executed.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use wws_protocol::{Artifact, BallotRecord, DeliberationMessage, IrvRound, Task};
+
+use crate::connector::TaskTimelineEvent;
+
+/// Version of the transcript layout this connector writes and reads.
+pub const TRANSCRIPT_FORMAT_VERSION: u32 = 1;
+
+/// One task tree and the decisions taken on it. Per-task maps are keyed by
+/// task ID and only hold tasks that have entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunTranscript {
+    pub format_version: u32,
+    pub root_task_id: String,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    /// Connector that wrote the transcript.
+    pub exported_by: String,
+    /// The root task first, then its descendants in creation order.
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub timelines: BTreeMap<String, Vec<TaskTimelineEvent>>,
+    #[serde(default)]
+    pub deliberation: BTreeMap<String, Vec<DeliberationMessage>>,
+    #[serde(default)]
+    pub ballots: BTreeMap<String, Vec<BallotRecord>>,
+    #[serde(default)]
+    pub irv_rounds: BTreeMap<String, Vec<IrvRound>>,
+    #[serde(default)]
+    pub artifacts: BTreeMap<String, Artifact>,
+    /// Textual results, for tasks whose result carried no artifact content.
+    #[serde(default)]
+    pub result_text: BTreeMap<String, String>,
+    #[serde(default)]
+    pub costs: BTreeMap<String, f64>,
+}
+
+impl RunTranscript {
+    /// Check that the transcript can be loaded: a known format whose root
+    /// task is part of the archive.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.format_version != TRANSCRIPT_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported transcript format {} (expected {})",
+                self.format_version, TRANSCRIPT_FORMAT_VERSION
+            ));
+        }
+        if !self.tasks.iter().any(|t| t.task_id == self.root_task_id) {
+            return Err(format!("Transcript lacks its root task {}", self.root_task_id));
+        }
+        Ok(())
+    }
+}
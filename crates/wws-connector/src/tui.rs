@@ -52,7 +52,7 @@ pub struct LogEntry {
 }
 
 /// Category of a log entry, used for coloring and filtering.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum LogCategory {
     Peer,
     Message,
@@ -66,7 +66,7 @@ pub enum LogCategory {
 
 impl LogCategory {
     /// Short label for display in the event log.
-    fn label(&self) -> &'static str {
+    pub fn label(&self) -> &'static str {
         match self {
             LogCategory::Peer => "PEER",
             LogCategory::Message => "MSG",
@@ -79,6 +79,22 @@ impl LogCategory {
         }
     }
 
+    /// Parse a category from its label ("ERR") or its name ("Error"),
+    /// ignoring case.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "PEER" => Some(LogCategory::Peer),
+            "MSG" | "MESSAGE" => Some(LogCategory::Message),
+            "TASK" => Some(LogCategory::Task),
+            "VOTE" => Some(LogCategory::Vote),
+            "EPOCH" => Some(LogCategory::Epoch),
+            "ERR" | "ERROR" => Some(LogCategory::Error),
+            "SYS" | "SYSTEM" => Some(LogCategory::System),
+            "SWARM" => Some(LogCategory::Swarm),
+            _ => None,
+        }
+    }
+
     /// Color associated with this log category.
     fn color(&self) -> Color {
         match self {
@@ -128,7 +144,7 @@ impl SwarmTui {
     async fn snapshot(&self) -> StateSnapshot {
         let state = self.state.read().await;
         let cascade_status = state.cascade.status();
-        let flow_summary = summarize_flow(&state.task_timelines, &state.event_log);
+        let flow_summary = summarize_flow(state.task_timelines.as_ref(), &state.event_log);
         let (tier1_count, tier2_count, tiern_count, executor_count) = summarize_tiers(&state);
 
         let current_swarm_id_str = state.current_swarm_id.as_str().to_string();
@@ -158,7 +174,7 @@ impl SwarmTui {
             epoch: state.epoch_manager.current_epoch(),
             status: format_status(&state.status),
             status_color: status_color(&state.status),
-            parent_id: state.parent_id.as_ref().map(|p| p.to_string()),
+            parent_id: state.parent_id.as_ref().map(|p| state.display_name(p.as_str())),
             active_tasks: state.task_set.elements(),
             peer_count: state.agent_set.len(),
             swarm_size: state.network_stats.total_agents,
@@ -801,7 +817,7 @@ fn summarize_tiers(state: &ConnectorState) -> (usize, usize, usize, usize) {
 }
 
 fn summarize_flow(
-    timelines: &std::collections::HashMap<String, Vec<crate::connector::TaskTimelineEvent>>,
+    timelines: &dyn crate::storage::TimelineStore,
     log: &[LogEntry],
 ) -> FlowSummary {
     let mut summary = FlowSummary::default();
@@ -976,18 +992,21 @@ mod tests {
                     stage: "injected".to_string(),
                     detail: "".to_string(),
                     actor: None,
+                    hlc: Default::default(),
                 },
                 crate::connector::TaskTimelineEvent {
                     timestamp: chrono::Utc::now(),
                     stage: "proposed".to_string(),
                     detail: "".to_string(),
                     actor: None,
+                    hlc: Default::default(),
                 },
                 crate::connector::TaskTimelineEvent {
                     timestamp: chrono::Utc::now(),
                     stage: "result_submitted".to_string(),
                     detail: "".to_string(),
                     actor: None,
+                    hlc: Default::default(),
                 },
             ],
         );
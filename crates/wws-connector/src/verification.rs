@@ -0,0 +1,258 @@
+//! Result verification by independent re-execution.
+//!
+//! With probability `consensus.verification_rate` a coordinator hands a
+//! completed subtask to a second agent of the same tier. Matching result
+//! hashes confirm the original; diverging ones put the task into
+//! [`TaskStatus::Disputed`](wws_protocol::TaskStatus::Disputed) and the
+//! parent's board votes on which result to accept. Each outcome is credited
+//! to (or charged against) the agents' reputation.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use wws_protocol::Artifact;
+use wws_state::ContentStore;
+
+/// `winning_plan_id` carried by a TaskAssignment that re-executes a task.
+pub const VERIFICATION_PLAN_ID: &str = "verify-reexecution";
+
+/// Seconds the board has to settle a dispute before the original result stands.
+pub const DISPUTE_VOTE_TIMEOUT_SECS: i64 = 300;
+
+/// Reputation points for a result confirmed by re-execution or a dispute vote.
+pub const VERIFICATION_PASS_POINTS: u64 = 2;
+
+/// Reputation points lost when the board rejects an agent's result.
+pub const VERIFICATION_FAIL_POINTS: u64 = 10;
+
+/// Errors returned when recording a dispute vote.
+#[derive(Debug, thiserror::Error)]
+pub enum DisputeError {
+    #[error("Task {0} is not disputed")]
+    NotDisputed(String),
+
+    #[error("Agent {0} is not on the dispute board")]
+    NotEligible(String),
+
+    #[error("Agent {0} did not produce either result")]
+    UnknownResult(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    /// Waiting for the verifier's result.
+    Pending,
+    /// Both results hashed the same; the original stands.
+    Matched,
+    /// Results diverged; the board is voting.
+    Disputed,
+    /// The board accepted one of the results.
+    Resolved,
+    /// The verifier never answered; the original stands.
+    Abandoned,
+}
+
+/// A completed task being re-executed by a second agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResultVerification {
+    pub task_id: String,
+    pub original_agent: String,
+    pub original_artifact: Artifact,
+    pub original_hash: String,
+    #[serde(skip)]
+    pub original_text: String,
+    pub verifier: String,
+    pub verifier_artifact: Option<Artifact>,
+    pub verifier_hash: Option<String>,
+    #[serde(skip)]
+    pub verifier_text: String,
+    pub status: VerificationStatus,
+    /// Agents allowed to vote on a dispute (never the two producers).
+    pub voters: Vec<String>,
+    /// voter -> agent whose result they accept.
+    pub votes: BTreeMap<String, String>,
+    pub accepted_agent: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub disputed_at: Option<DateTime<Utc>>,
+}
+
+impl ResultVerification {
+    pub fn new(
+        task_id: String,
+        original_agent: String,
+        original_artifact: Artifact,
+        original_text: String,
+        verifier: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let original_hash = result_hash(&original_artifact, &original_text);
+        Self {
+            task_id,
+            original_agent,
+            original_artifact,
+            original_hash,
+            original_text,
+            verifier,
+            verifier_artifact: None,
+            verifier_hash: None,
+            verifier_text: String::new(),
+            status: VerificationStatus::Pending,
+            voters: Vec::new(),
+            votes: BTreeMap::new(),
+            accepted_agent: None,
+            started_at: now,
+            disputed_at: None,
+        }
+    }
+
+    /// Compare the verifier's result with the original. Moves to `Matched`
+    /// or `Disputed` and returns the new status.
+    pub fn record_verifier_result(
+        &mut self,
+        artifact: Artifact,
+        text: String,
+        now: DateTime<Utc>,
+    ) -> VerificationStatus {
+        let hash = result_hash(&artifact, &text);
+        self.status = if hash == self.original_hash {
+            self.accepted_agent = Some(self.original_agent.clone());
+            VerificationStatus::Matched
+        } else {
+            self.disputed_at = Some(now);
+            VerificationStatus::Disputed
+        };
+        self.verifier_hash = Some(hash);
+        self.verifier_artifact = Some(artifact);
+        self.verifier_text = text;
+        self.status
+    }
+
+    /// Record a board member's choice. A later vote from the same voter
+    /// replaces the earlier one.
+    pub fn record_vote(&mut self, voter: &str, accept_agent: &str) -> Result<(), DisputeError> {
+        if self.status != VerificationStatus::Disputed {
+            return Err(DisputeError::NotDisputed(self.task_id.clone()));
+        }
+        if !self.voters.iter().any(|v| v == voter) {
+            return Err(DisputeError::NotEligible(voter.to_string()));
+        }
+        if accept_agent != self.original_agent && accept_agent != self.verifier {
+            return Err(DisputeError::UnknownResult(accept_agent.to_string()));
+        }
+        self.votes.insert(voter.to_string(), accept_agent.to_string());
+        Ok(())
+    }
+
+    /// The accepted agent once at least `required` votes are in: the
+    /// majority choice, with ties going to the original result.
+    pub fn tally(&self, required: usize) -> Option<String> {
+        if self.votes.len() < required.max(1) {
+            return None;
+        }
+        Some(self.majority())
+    }
+
+    /// Majority choice among the votes cast so far; the original on a tie.
+    pub fn majority(&self) -> String {
+        let for_verifier = self.votes.values().filter(|a| **a == self.verifier).count();
+        if for_verifier * 2 > self.votes.len() {
+            self.verifier.clone()
+        } else {
+            self.original_agent.clone()
+        }
+    }
+
+    /// Close the dispute in favour of `winner`.
+    pub fn resolve(&mut self, winner: String) {
+        self.status = VerificationStatus::Resolved;
+        self.accepted_agent = Some(winner);
+    }
+
+    /// The agent whose result lost the dispute, once resolved.
+    pub fn rejected_agent(&self) -> Option<&str> {
+        match self.accepted_agent.as_deref() {
+            Some(a) if a == self.original_agent => Some(self.verifier.as_str()),
+            Some(_) => Some(self.original_agent.as_str()),
+            None => None,
+        }
+    }
+
+    /// Artifact and result text produced by `agent`.
+    pub fn result_of(&self, agent: &str) -> Option<(Artifact, String)> {
+        if agent == self.original_agent {
+            Some((self.original_artifact.clone(), self.original_text.clone()))
+        } else if agent == self.verifier {
+            self.verifier_artifact
+                .clone()
+                .map(|a| (a, self.verifier_text.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Content hash of a result: the submitted text if any, else the artifact's
+/// CID, else the hash of its inline content.
+pub fn result_hash(artifact: &Artifact, text: &str) -> String {
+    if !text.is_empty() {
+        ContentStore::compute_cid(text.as_bytes())
+    } else if !artifact.content.is_empty() {
+        ContentStore::compute_cid(artifact.content.as_bytes())
+    } else {
+        artifact.content_cid.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact() -> Artifact {
+        serde_json::from_value(serde_json::json!({})).unwrap()
+    }
+
+    fn verification() -> ResultVerification {
+        ResultVerification::new(
+            "t1".to_string(),
+            "alice".to_string(),
+            artifact(),
+            "42".to_string(),
+            "bob".to_string(),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn identical_results_match() {
+        let mut v = verification();
+        let status = v.record_verifier_result(artifact(), "42".to_string(), Utc::now());
+        assert_eq!(status, VerificationStatus::Matched);
+        assert_eq!(v.accepted_agent.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn diverging_results_are_voted_on() {
+        let mut v = verification();
+        let status = v.record_verifier_result(artifact(), "41".to_string(), Utc::now());
+        assert_eq!(status, VerificationStatus::Disputed);
+        v.voters = vec!["carol".to_string(), "dave".to_string(), "erin".to_string()];
+
+        assert!(matches!(v.record_vote("alice", "alice"), Err(DisputeError::NotEligible(_))));
+        assert!(matches!(v.record_vote("carol", "mallory"), Err(DisputeError::UnknownResult(_))));
+
+        v.record_vote("carol", "bob").unwrap();
+        v.record_vote("dave", "alice").unwrap();
+        assert_eq!(v.tally(3), None);
+        // Ties keep the original result.
+        assert_eq!(v.majority(), "alice");
+
+        v.record_vote("erin", "bob").unwrap();
+        let winner = v.tally(3).unwrap();
+        assert_eq!(winner, "bob");
+        v.resolve(winner);
+        assert_eq!(v.rejected_agent(), Some("alice"));
+        assert!(matches!(v.record_vote("carol", "alice"), Err(DisputeError::NotDisputed(_))));
+    }
+}
@@ -0,0 +1,439 @@
+//! Voting windows: when ballots on a task close, and who has yet to vote.
+//!
+//! Once every committed plan of a task is revealed, each member opens the
+//! task's voting window, which closes when the voting stage times out: the
+//! round's start plus the swarm's voting timeout. The connector running the
+//! task's announcer — the board chair, or without a board the revealed
+//! proposer with the smallest DID — broadcasts `consensus.voting_open` with
+//! the plans on the ballot and the deadline, and members adopt the deadline
+//! it announces. Halfway to the deadline the announcer sends
+//! `consensus.vote_reminder` naming the eligible voters whose ballots have
+//! not arrived, and each connector running one of them puts the reminder in
+//! that agent's inbox. `swarm.get_voting_state` reports the deadline and the
+//! seconds left.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use wws_consensus::rfp::RfpPhase;
+use wws_protocol::{
+    AgentId, DirectMessage, MessageType, ProtocolMethod, SwarmTopics, VoteReminderParams, VotingOpenParams,
+};
+
+use crate::connector::{ConnectorState, WwsConnector};
+use crate::tui::LogCategory;
+
+/// A task's open voting window as this node sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VotingWindow {
+    /// Epoch of the voting round the window belongs to.
+    pub epoch: u64,
+    pub opened_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+    pub plan_ids: Vec<String>,
+    /// Agent whose announcement set the deadline, once one arrived.
+    pub announced_by: Option<AgentId>,
+    /// Whether the halfway reminder went out.
+    pub reminded: bool,
+}
+
+impl VotingWindow {
+    /// Whole seconds until the deadline, never negative.
+    pub fn remaining_secs(&self, now: DateTime<Utc>) -> i64 {
+        (self.deadline - now).num_seconds().max(0)
+    }
+
+    /// Whether the window is past its halfway point without a reminder.
+    pub fn reminder_due(&self, now: DateTime<Utc>) -> bool {
+        !self.reminded && now >= self.opened_at + (self.deadline - self.opened_at) / 2 && now < self.deadline
+    }
+}
+
+/// Agent that announces `task_id`'s voting window: the chair of its board,
+/// else the revealed proposer with the smallest DID.
+pub fn announcer(state: &ConnectorState, task_id: &str) -> Option<AgentId> {
+    if let Some(holon) = state.active_holons.get(task_id) {
+        return Some(holon.chair.clone());
+    }
+    state
+        .rfp_coordinators
+        .get(task_id)?
+        .reveals
+        .values()
+        .map(|r| r.plan.proposer.clone())
+        .min_by(|a, b| a.as_str().cmp(b.as_str()))
+}
+
+/// Open the window of every task whose plans are all revealed and whose
+/// window is not yet open for the current round. Returns the announcements
+/// this node makes, each with the agent to send it as.
+pub fn open_windows(state: &mut ConnectorState, now: DateTime<Utc>) -> Vec<(AgentId, VotingOpenParams)> {
+    let engines = &state.voting_engines;
+    state.voting_windows.retain(|task_id, _| engines.contains_key(task_id));
+    let mut ready: Vec<(String, u64, Vec<String>)> = state
+        .rfp_coordinators
+        .iter()
+        .filter(|(_, rfp)| matches!(rfp.phase(), RfpPhase::ReadyForVoting))
+        .filter_map(|(task_id, rfp)| {
+            let epoch = state.voting_engines.get(task_id)?.epoch();
+            if state.voting_windows.get(task_id).is_some_and(|w| w.epoch == epoch) {
+                return None;
+            }
+            let mut plan_ids: Vec<String> = rfp.reveals.values().map(|r| r.plan.plan_id.clone()).collect();
+            plan_ids.sort();
+            Some((task_id.clone(), epoch, plan_ids))
+        })
+        .collect();
+    ready.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut announcements = Vec::new();
+    for (task_id, epoch, plan_ids) in ready {
+        let Some(age_secs) = state.round_age_secs(&task_id, now) else {
+            continue;
+        };
+        let deadline = now + chrono::Duration::seconds(state.voting_stage_timeout_secs() - age_secs);
+        if deadline <= now {
+            continue;
+        }
+        state.voting_windows.insert(
+            task_id.clone(),
+            VotingWindow {
+                epoch,
+                opened_at: now,
+                deadline,
+                plan_ids: plan_ids.clone(),
+                announced_by: None,
+                reminded: false,
+            },
+        );
+        state.push_task_timeline_event(
+            &task_id,
+            "voting_open",
+            format!("Voting on {} plans open until {}", plan_ids.len(), deadline.to_rfc3339()),
+            None,
+        );
+        let Some(announcer) = announcer(state, &task_id).filter(|a| state.is_local_agent(a)) else {
+            continue;
+        };
+        if let Some(window) = state.voting_windows.get_mut(&task_id) {
+            window.announced_by = Some(announcer.clone());
+        }
+        announcements.push((
+            announcer.clone(),
+            VotingOpenParams {
+                task_id,
+                epoch,
+                plan_ids,
+                deadline,
+                announced_by: announcer,
+            },
+        ));
+    }
+    announcements
+}
+
+/// Reminders due from the windows this node announced, naming the eligible
+/// voters whose ballots have not arrived. Windows with no one left to
+/// remind are marked reminded without one.
+pub fn due_reminders(state: &mut ConnectorState, now: DateTime<Utc>) -> Vec<VoteReminderParams> {
+    let mut due: Vec<(String, u64, DateTime<Utc>, AgentId)> = state
+        .voting_windows
+        .iter()
+        .filter(|(_, w)| w.reminder_due(now))
+        .filter_map(|(task_id, w)| {
+            let announcer = w.announced_by.clone().filter(|a| state.is_local_agent(a))?;
+            Some((task_id.clone(), w.epoch, w.deadline, announcer))
+        })
+        .collect();
+    due.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut reminders = Vec::new();
+    for (task_id, epoch, deadline, sent_by) in due {
+        if let Some(window) = state.voting_windows.get_mut(&task_id) {
+            window.reminded = true;
+        }
+        let voted = state
+            .voting_engines
+            .get(&task_id)
+            .map(|v| v.voter_ids_for_debug())
+            .unwrap_or_default();
+        let (_, eligible) = WwsConnector::quorum_eligible_for_task(state, &task_id);
+        let mut pending_voters: Vec<AgentId> = eligible
+            .into_iter()
+            .filter(|member| !voted.contains(member))
+            .map(AgentId::new)
+            .collect();
+        pending_voters.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        if pending_voters.is_empty() {
+            continue;
+        }
+        reminders.push(VoteReminderParams {
+            task_id,
+            epoch,
+            deadline,
+            pending_voters,
+            sent_by,
+        });
+    }
+    reminders
+}
+
+/// Adopt the deadline of a received `consensus.voting_open` for a task
+/// being voted on here, when it comes from the task's announcer. Returns
+/// whether the window changed.
+pub fn accept_voting_open(state: &mut ConnectorState, params: VotingOpenParams) -> Result<bool, String> {
+    let Some(epoch) = state.voting_engines.get(&params.task_id).map(|v| v.epoch()) else {
+        return Ok(false);
+    };
+    if params.epoch != epoch {
+        return Err(format!("announced for epoch {}, voting is at epoch {}", params.epoch, epoch));
+    }
+    if let Some(expected) = announcer(state, &params.task_id) {
+        if expected != params.announced_by {
+            return Err(format!("{} does not announce task {}", params.announced_by, params.task_id));
+        }
+    }
+    let opened = state.voting_windows.get(&params.task_id).is_none_or(|w| w.epoch != epoch);
+    if opened {
+        state.voting_windows.insert(
+            params.task_id.clone(),
+            VotingWindow {
+                epoch,
+                opened_at: Utc::now(),
+                deadline: params.deadline,
+                plan_ids: Vec::new(),
+                announced_by: None,
+                reminded: false,
+            },
+        );
+        state.push_task_timeline_event(
+            &params.task_id,
+            "voting_open",
+            format!(
+                "Voting on {} plans open until {}",
+                params.plan_ids.len(),
+                params.deadline.to_rfc3339()
+            ),
+            Some(params.announced_by.to_string()),
+        );
+    }
+    let Some(window) = state.voting_windows.get_mut(&params.task_id) else {
+        return Ok(false);
+    };
+    if window.announced_by.as_ref() == Some(&params.announced_by) && window.deadline == params.deadline {
+        return Ok(false);
+    }
+    window.deadline = params.deadline;
+    window.plan_ids = params.plan_ids;
+    window.announced_by = Some(params.announced_by.clone());
+    state.push_log(
+        LogCategory::Vote,
+        format!(
+            "Voting on task {} open until {} (announced by {})",
+            params.task_id,
+            params.deadline.to_rfc3339(),
+            params.announced_by
+        ),
+    );
+    Ok(true)
+}
+
+/// Put a vote reminder in the inbox of every pending voter running here
+/// that has still not voted. Returns how many were reminded.
+pub fn deliver_reminder(state: &mut ConnectorState, params: &VoteReminderParams) -> usize {
+    if let Some(window) = state.voting_windows.get_mut(&params.task_id) {
+        window.reminded = true;
+    }
+    let voted = state
+        .voting_engines
+        .get(&params.task_id)
+        .map(|v| v.voter_ids_for_debug())
+        .unwrap_or_default();
+    let now = Utc::now();
+    let left = (params.deadline - now).num_seconds().max(0);
+    let mut reminded = 0;
+    for voter in &params.pending_voters {
+        if !state.is_local_agent(voter) || voted.iter().any(|v| v == voter.as_str()) {
+            continue;
+        }
+        state.push_direct_message(DirectMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            sender_did: params.sent_by.to_string(),
+            recipient_did: Some(voter.to_string()),
+            content: format!(
+                "Your ballot for task {} has not arrived; voting closes at {} ({}s left)",
+                params.task_id,
+                params.deadline.to_rfc3339(),
+                left
+            ),
+            message_type: MessageType::Work,
+            timestamp: now,
+        });
+        reminded += 1;
+    }
+    if reminded > 0 {
+        state.push_log(
+            LogCategory::Vote,
+            format!("Reminded {} local voters to vote on task {}", reminded, params.task_id),
+        );
+    }
+    reminded
+}
+
+/// Open due voting windows and publish this node's announcements and
+/// reminders on each task's voting topic.
+pub async fn publish_voting_windows(state: &Arc<RwLock<ConnectorState>>, network_handle: &wws_network::SwarmHandle) {
+    let outgoing = {
+        let mut state = state.write().await;
+        if state.epoch_manager.is_transitioning() {
+            return;
+        }
+        let now = Utc::now();
+        let mut outgoing = Vec::new();
+        for (announcer, params) in open_windows(&mut state, now) {
+            let topic = SwarmTopics::voting_for(&state.swarm_for_task(&params.task_id), &params.task_id);
+            let msg = state.message_as(
+                &announcer,
+                ProtocolMethod::VotingOpen.as_str(),
+                serde_json::to_value(&params).unwrap_or_default(),
+            );
+            outgoing.push((topic, msg));
+        }
+        for params in due_reminders(&mut state, now) {
+            deliver_reminder(&mut state, &params);
+            state.push_task_timeline_event(
+                &params.task_id,
+                "vote_reminder",
+                format!("{} eligible voters reminded to vote", params.pending_voters.len()),
+                Some(params.sent_by.to_string()),
+            );
+            let topic = SwarmTopics::voting_for(&state.swarm_for_task(&params.task_id), &params.task_id);
+            let msg = state.message_as(
+                &params.sent_by,
+                ProtocolMethod::VoteReminder.as_str(),
+                serde_json::to_value(&params).unwrap_or_default(),
+            );
+            outgoing.push((topic, msg));
+        }
+        outgoing
+    };
+    for (topic, msg) in outgoing {
+        let Ok(data) = serde_json::to_vec(&msg) else {
+            continue;
+        };
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            tracing::warn!(error = %e, method = %msg.method, "Failed to publish voting window message");
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connector::tests::test_state;
+    use wws_consensus::rfp::RfpCoordinator;
+    use wws_consensus::voting::{VotingConfig, VotingEngine};
+    use wws_protocol::{Plan, ProposalCommitParams, ProposalRevealParams, Task};
+
+    /// A state voting on `t1`, whose plans by `p1` and `p2` are revealed.
+    fn voting_state(agent: &str) -> ConnectorState {
+        let mut state = test_state();
+        state.agent_id = AgentId::new(agent.to_string());
+        let mut task = Task::new("Plan the launch".to_string(), 1, 1);
+        task.task_id = "t1".to_string();
+        let mut rfp = RfpCoordinator::new("t1".to_string(), 1, 2);
+        rfp.inject_task(&task).unwrap();
+        let plans: Vec<Plan> = ["did:swarm:p1", "did:swarm:p2"]
+            .into_iter()
+            .map(|p| Plan::new("t1".to_string(), AgentId::new(p.to_string()), 1))
+            .collect();
+        for plan in &plans {
+            rfp.record_commit(&ProposalCommitParams {
+                task_id: "t1".to_string(),
+                proposer: plan.proposer.clone(),
+                epoch: 1,
+                plan_hash: RfpCoordinator::compute_plan_hash(plan).unwrap(),
+                bond: 0,
+                selection: None,
+            })
+            .unwrap();
+        }
+        for plan in &plans {
+            rfp.record_reveal(&ProposalRevealParams {
+                task_id: "t1".to_string(),
+                plan: plan.clone(),
+            })
+            .unwrap();
+        }
+        state.task_details.insert("t1".to_string(), task);
+        state.rfp_coordinators.insert("t1".to_string(), rfp);
+        state
+            .voting_engines
+            .insert("t1".to_string(), VotingEngine::new(VotingConfig::default(), "t1".to_string(), 1));
+        state
+    }
+
+    #[test]
+    fn the_smallest_proposer_announces_the_deadline_once() {
+        let now = Utc::now();
+        let mut announcer = voting_state("did:swarm:p1");
+        let announcements = open_windows(&mut announcer, now);
+        assert_eq!(announcements.len(), 1);
+        let (sender, params) = announcements.into_iter().next().unwrap();
+        assert_eq!(sender.as_str(), "did:swarm:p1");
+        assert_eq!(params.plan_ids.len(), 2);
+        let window = announcer.voting_windows.get("t1").unwrap().clone();
+        assert_eq!(window.deadline, params.deadline);
+        assert!(window.remaining_secs(now) > 0);
+        assert!(window.remaining_secs(now) <= announcer.voting_stage_timeout_secs());
+        assert!(open_windows(&mut announcer, now).is_empty(), "window already open");
+
+        // Another proposer opens its own window but only adopts the deadline.
+        let mut member = voting_state("did:swarm:p2");
+        assert!(open_windows(&mut member, now).is_empty());
+        let mut forged = params.clone();
+        forged.announced_by = AgentId::new("did:swarm:p2".to_string());
+        assert!(accept_voting_open(&mut member, forged).is_err());
+        let mut later = params.clone();
+        later.deadline = params.deadline + chrono::Duration::seconds(5);
+        assert_eq!(accept_voting_open(&mut member, later.clone()), Ok(true));
+        assert_eq!(accept_voting_open(&mut member, later.clone()), Ok(false));
+        assert_eq!(member.voting_windows["t1"].deadline, later.deadline);
+
+        // Windows go with the voting round.
+        announcer.voting_engines.clear();
+        open_windows(&mut announcer, now);
+        assert!(announcer.voting_windows.is_empty());
+    }
+
+    #[test]
+    fn reminders_reach_local_voters_that_have_not_voted() {
+        let now = Utc::now();
+        let mut state = voting_state("did:swarm:p1");
+        open_windows(&mut state, now);
+        let window = state.voting_windows["t1"].clone();
+        assert!(!window.reminder_due(now));
+        let halfway = window.opened_at + (window.deadline - window.opened_at) / 2;
+        assert!(window.reminder_due(halfway));
+        assert!(!window.reminder_due(window.deadline));
+
+        let reminder = VoteReminderParams {
+            task_id: "t1".to_string(),
+            epoch: 1,
+            deadline: window.deadline,
+            pending_voters: vec![
+                AgentId::new("did:swarm:p1".to_string()),
+                AgentId::new("did:swarm:remote".to_string()),
+            ],
+            sent_by: AgentId::new("did:swarm:p1".to_string()),
+        };
+        assert_eq!(deliver_reminder(&mut state, &reminder), 1);
+        let inbox = &state.direct_messages;
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].recipient_did.as_deref(), Some("did:swarm:p1"));
+        assert!(state.voting_windows["t1"].reminded);
+        assert!(due_reminders(&mut state, halfway).is_empty());
+    }
+}
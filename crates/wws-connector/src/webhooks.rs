@@ -0,0 +1,351 @@
+//! Outbound webhook notifications.
+//!
+//! Each `[[webhooks]]` entry names a URL and the events it wants: a task
+//! completing, a plan vote being finalized, a task being reassigned or a
+//! new epoch starting. The connector POSTs a JSON [`WebhookPayload`] for
+//! every such event, so external systems such as chat bots or ticketing
+//! can react to swarm activity.
+//!
+//! Deliveries run in the background and never hold up the connector.
+//! With a `secret` configured, the body is signed with HMAC-SHA256 over
+//! `"{timestamp}.{body}"` and the signature sent as
+//! `X-WWS-Signature: sha256=<hex>`, next to `X-WWS-Timestamp`, so
+//! receivers can check origin and reject replays. Failed deliveries
+//! (connection errors, 408, 429 and 5xx responses) are retried with
+//! exponential backoff up to `max_attempts` times.
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+
+/// Delay before the first retry; each further retry doubles it.
+pub const WEBHOOK_BACKOFF_BASE_SECS: u64 = 1;
+/// Longest delay between two attempts.
+pub const WEBHOOK_BACKOFF_MAX_SECS: u64 = 60;
+/// Time allowed for one attempt.
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Swarm activity a webhook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A task's result was accepted or its subtasks were aggregated.
+    TaskCompleted,
+    /// A plan was selected for a task.
+    VoteFinalized,
+    /// A task moved to another agent: by an operator, after a timeout or
+    /// by work stealing.
+    TaskReassigned,
+    /// An epoch boundary was reached and a new epoch began.
+    EpochChanged,
+}
+
+impl WebhookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TaskCompleted => "task_completed",
+            Self::VoteFinalized => "vote_finalized",
+            Self::TaskReassigned => "task_reassigned",
+            Self::EpochChanged => "epoch_changed",
+        }
+    }
+
+    /// The event a task timeline stage announces, if any.
+    pub fn for_stage(stage: &str) -> Option<Self> {
+        match stage {
+            "result_submitted" | "aggregated" | "dispute_resolved" => Some(Self::TaskCompleted),
+            "plan_selected" => Some(Self::VoteFinalized),
+            "reassigned" | "stolen" => Some(Self::TaskReassigned),
+            _ => None,
+        }
+    }
+}
+
+/// One `[[webhooks]]` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// URL the payloads are POSTed to.
+    pub url: String,
+    /// Events sent to this URL; all of them when empty.
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    /// Key for the HMAC-SHA256 signature. Payloads are unsigned without it.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Attempts per payload before it is dropped.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+impl WebhookConfig {
+    fn wants(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Body of every webhook request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    /// Unique per event; the same across retries, so receivers can
+    /// deduplicate.
+    pub delivery_id: String,
+    pub event: WebhookEvent,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Agent ID of the connector that sent the notification.
+    pub connector: String,
+    /// Event details.
+    pub data: serde_json::Value,
+}
+
+/// A payload on its way to one URL.
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    pub url: String,
+    pub secret: Option<String>,
+    pub event: WebhookEvent,
+    pub delivery_id: String,
+    pub body: Vec<u8>,
+    pub max_attempts: u32,
+}
+
+/// Configured webhooks and the queue feeding the dispatcher.
+#[derive(Debug, Default)]
+pub struct Webhooks {
+    endpoints: Vec<WebhookConfig>,
+    connector: String,
+    queue: Option<mpsc::UnboundedSender<Delivery>>,
+}
+
+impl Webhooks {
+    /// Webhooks for `endpoints`, and the receiving end of their queue for
+    /// [`run_dispatcher`] when there is at least one.
+    pub fn new(
+        endpoints: Vec<WebhookConfig>,
+        connector: String,
+    ) -> (Self, Option<mpsc::UnboundedReceiver<Delivery>>) {
+        if endpoints.is_empty() {
+            return (Self::default(), None);
+        }
+        let (queue, rx) = mpsc::unbounded_channel();
+        let webhooks = Self {
+            endpoints,
+            connector,
+            queue: Some(queue),
+        };
+        (webhooks, Some(rx))
+    }
+
+    /// Queue `event` for every webhook subscribed to it.
+    pub fn notify(&self, event: WebhookEvent, data: serde_json::Value) {
+        let Some(queue) = &self.queue else {
+            return;
+        };
+        let payload = WebhookPayload {
+            delivery_id: uuid::Uuid::new_v4().to_string(),
+            event,
+            timestamp: chrono::Utc::now(),
+            connector: self.connector.clone(),
+            data,
+        };
+        let Ok(body) = serde_json::to_vec(&payload) else {
+            return;
+        };
+        for endpoint in self.endpoints.iter().filter(|e| e.wants(event)) {
+            let _ = queue.send(Delivery {
+                url: endpoint.url.clone(),
+                secret: endpoint.secret.clone(),
+                event,
+                delivery_id: payload.delivery_id.clone(),
+                body: body.clone(),
+                max_attempts: endpoint.max_attempts.max(1),
+            });
+        }
+    }
+}
+
+/// `sha256=<hex>` HMAC-SHA256 of `"{timestamp}.{body}"` under `secret`.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+/// Delay before attempt `attempt` (counting from 1) is retried.
+pub fn backoff(attempt: u32) -> Duration {
+    let secs = WEBHOOK_BACKOFF_BASE_SECS.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    Duration::from_secs(secs.min(WEBHOOK_BACKOFF_MAX_SECS))
+}
+
+/// Deliver queued payloads until every [`Webhooks`] sender is gone. Each
+/// delivery retries on its own, so a slow URL does not delay the others.
+pub async fn run_dispatcher(mut rx: mpsc::UnboundedReceiver<Delivery>) {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!(error = %e, "Webhooks disabled: failed to build HTTP client");
+            return;
+        }
+    };
+    while let Some(delivery) = rx.recv().await {
+        let client = client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = deliver(&client, &delivery).await {
+                tracing::warn!(
+                    url = %delivery.url,
+                    event = delivery.event.as_str(),
+                    delivery_id = %delivery.delivery_id,
+                    error = %e,
+                    "Webhook delivery failed"
+                );
+            }
+        });
+    }
+}
+
+/// POST `delivery`, retrying with backoff while the failure may pass.
+pub async fn deliver(client: &reqwest::Client, delivery: &Delivery) -> Result<(), String> {
+    let mut attempt = 1;
+    loop {
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut request = client
+            .post(&delivery.url)
+            .header("Content-Type", "application/json")
+            .header("X-WWS-Event", delivery.event.as_str())
+            .header("X-WWS-Delivery", &delivery.delivery_id)
+            .header("X-WWS-Timestamp", timestamp.to_string())
+            .body(delivery.body.clone());
+        if let Some(secret) = &delivery.secret {
+            request = request.header("X-WWS-Signature", sign(secret, timestamp, &delivery.body));
+        }
+        let error = match request.send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status.is_server_error()
+                    || status == reqwest::StatusCode::REQUEST_TIMEOUT
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                if !retryable {
+                    return Err(format!("rejected with {}", status));
+                }
+                format!("responded {}", status)
+            }
+            Err(e) => e.to_string(),
+        };
+        if attempt >= delivery.max_attempts {
+            return Err(format!("{} after {} attempts", error, attempt));
+        }
+        tracing::debug!(url = %delivery.url, attempt, error = %error, "Retrying webhook delivery");
+        tokio::time::sleep(backoff(attempt)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Answer one request per status in `statuses` and return the request
+    /// heads and bodies received.
+    async fn serve(
+        listener: tokio::net::TcpListener,
+        statuses: Vec<u16>,
+    ) -> Vec<(String, Vec<u8>)> {
+        let mut requests = Vec::new();
+        for status in statuses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut data = Vec::new();
+            let mut buf = [0u8; 4096];
+            let (head, body) = loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                data.extend_from_slice(&buf[..n]);
+                let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") else {
+                    continue;
+                };
+                let head = String::from_utf8_lossy(&data[..end]).to_lowercase();
+                let length: usize = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("content-length:"))
+                    .map(|v| v.trim().parse().unwrap())
+                    .unwrap_or(0);
+                if data.len() >= end + 4 + length {
+                    break (head, data[end + 4..end + 4 + length].to_vec());
+                }
+            };
+            let response = format!("HTTP/1.1 {} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+            socket.write_all(response.as_bytes()).await.unwrap();
+            requests.push((head, body));
+        }
+        requests
+    }
+
+    #[tokio::test]
+    async fn subscribed_webhooks_get_signed_payloads_with_retries() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(listener, vec![503, 200]));
+
+        let endpoints = vec![
+            WebhookConfig {
+                url: url.clone(),
+                events: vec![WebhookEvent::TaskCompleted],
+                secret: Some("s3cret".into()),
+                max_attempts: 3,
+            },
+            WebhookConfig {
+                url: "http://127.0.0.1:9/unused".into(),
+                events: vec![WebhookEvent::EpochChanged],
+                secret: None,
+                max_attempts: 3,
+            },
+        ];
+        let (webhooks, rx) = Webhooks::new(endpoints, "did:swarm:me".into());
+        let mut rx = rx.unwrap();
+        webhooks.notify(WebhookEvent::TaskCompleted, serde_json::json!({"task_id": "t1"}));
+        let delivery = rx.recv().await.unwrap();
+        assert!(rx.try_recv().is_err(), "only the subscribed URL gets the event");
+        assert_eq!(delivery.url, url);
+
+        let client = reqwest::Client::new();
+        deliver(&client, &delivery).await.unwrap();
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let (head, body) = &requests[1];
+        assert!(head.starts_with("post /hook"));
+        assert!(head.contains("x-wws-event: task_completed"));
+        let timestamp: i64 = head
+            .lines()
+            .find_map(|l| l.strip_prefix("x-wws-timestamp:"))
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        let signature = format!("x-wws-signature: {}", sign("s3cret", timestamp, body));
+        assert!(head.contains(&signature));
+        let payload: WebhookPayload = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload.event, WebhookEvent::TaskCompleted);
+        assert_eq!(payload.connector, "did:swarm:me");
+        assert_eq!(payload.data["task_id"], "t1");
+        // Retries carry the same delivery ID.
+        assert_eq!(requests[0].1, *body);
+
+        assert_eq!(backoff(1), Duration::from_secs(WEBHOOK_BACKOFF_BASE_SECS));
+        assert_eq!(backoff(2), Duration::from_secs(2 * WEBHOOK_BACKOFF_BASE_SECS));
+        assert_eq!(backoff(30), Duration::from_secs(WEBHOOK_BACKOFF_MAX_SECS));
+    }
+}
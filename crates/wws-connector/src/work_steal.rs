@@ -0,0 +1,99 @@
+//! Work stealing between sibling executors.
+//!
+//! An executor whose agent keeps polling for work but holds none sends
+//! `task.steal_request` to its parent. The parent takes the newest queued
+//! subtask — assigned, but not yet fetched by its assignee — of the sibling
+//! with the deepest queue, as reported in keep-alives, and reassigns it to
+//! the idle executor, recording the steal on the task's timeline. It
+//! arbitrates one transfer at a time: until the thief's keep-alive shows it
+//! holding work, or [`STEAL_SETTLE_SECS`] pass, further requests are
+//! declined and idle executors ask again later.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use wws_protocol::AgentLoad;
+
+/// Seconds an idle executor waits between requests to its parent.
+pub const STEAL_RETRY_SECS: i64 = 20;
+/// Seconds a granted transfer blocks further ones unless settled earlier.
+pub const STEAL_SETTLE_SECS: i64 = 30;
+/// `winning_plan_id` of assignments made by a steal.
+pub const STEAL_PLAN_ID: &str = "work-steal";
+
+/// A transfer a parent has granted.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkSteal {
+    pub task_id: String,
+    pub from: String,
+    pub to: String,
+    pub granted_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Work-stealing bookkeeping of one connector.
+#[derive(Debug, Default)]
+pub struct WorkStealing {
+    /// Transfer this connector granted as a parent, until it settles.
+    pub in_flight: Option<WorkSteal>,
+    /// When each local executor last asked its parent for work.
+    pub last_request: HashMap<String, chrono::DateTime<chrono::Utc>>,
+}
+
+impl WorkStealing {
+    /// Whether a granted transfer is still unsettled at `now`.
+    pub fn is_busy(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.in_flight
+            .as_ref()
+            .is_some_and(|s| (now - s.granted_at).num_seconds() < STEAL_SETTLE_SECS)
+    }
+
+    /// Settle the transfer in flight once its thief reports holding work.
+    pub fn note_load(&mut self, agent_id: &str, load: &AgentLoad) {
+        if self.in_flight.as_ref().is_some_and(|s| s.to == agent_id) && load.held() > 0 {
+            self.in_flight = None;
+        }
+    }
+
+    /// Whether `agent_id` may ask its parent for work again at `now`.
+    pub fn may_request(&self, agent_id: &str, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.last_request
+            .get(agent_id)
+            .is_none_or(|at| (now - *at).num_seconds() >= STEAL_RETRY_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_transfer_blocks_others_until_the_thief_holds_work() {
+        let now = chrono::Utc::now();
+        let mut stealing = WorkStealing {
+            in_flight: Some(WorkSteal {
+                task_id: "t-st-2".into(),
+                from: "busy".into(),
+                to: "idle".into(),
+                granted_at: now,
+            }),
+            ..WorkStealing::default()
+        };
+        assert!(stealing.is_busy(now));
+        assert!(!stealing.is_busy(now + chrono::Duration::seconds(STEAL_SETTLE_SECS)));
+
+        stealing.note_load("idle", &AgentLoad::default());
+        assert!(stealing.is_busy(now));
+        let holding = AgentLoad {
+            queue_depth: 1,
+            ..AgentLoad::default()
+        };
+        stealing.note_load("other", &holding);
+        assert!(stealing.is_busy(now));
+        stealing.note_load("idle", &holding);
+        assert!(!stealing.is_busy(now));
+
+        stealing.last_request.insert("idle".into(), now);
+        assert!(!stealing.may_request("idle", now));
+        assert!(stealing.may_request("idle", now + chrono::Duration::seconds(STEAL_RETRY_SECS)));
+    }
+}
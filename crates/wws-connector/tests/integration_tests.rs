@@ -79,6 +79,8 @@ fn e2e_task_lifecycle_messages() {
     let injection = TaskInjectionParams {
         task: task.clone(),
         originator: agent_id.clone(),
+        attachments: Vec::new(),
+        attempt: 0,
     };
     let injection_json = serde_json::to_value(&injection).unwrap();
     assert!(injection_json["task"]["status"] == "Pending");
@@ -96,6 +98,8 @@ fn e2e_task_lifecycle_messages() {
         proposer: agent_id.clone(),
         epoch: 106,
         plan_hash: plan_hash.clone(),
+        bond: 0,
+        selection: None,
     };
     let commit_json = serde_json::to_value(&commit).unwrap();
     assert_eq!(commit_json["plan_hash"].as_str().unwrap(), &plan_hash);
@@ -116,6 +120,8 @@ fn e2e_task_lifecycle_messages() {
         voter: agent_id.clone(),
         rankings: vec![plan.plan_id.clone()],
         critic_scores: std::collections::HashMap::new(),
+        abstain: false,
+        selection: None,
     };
     let vote_json = serde_json::to_value(&vote).unwrap();
     assert_eq!(vote_json["rankings"].as_array().unwrap().len(), 1);
@@ -131,6 +137,7 @@ fn e2e_task_lifecycle_messages() {
         size_bytes: 2048,
         created_at: chrono::Utc::now(),
         content: "analysis result".into(),
+        sealed: None,
     };
     let result_msg = ResultSubmissionParams {
         task_id: task.task_id.clone(),
@@ -138,6 +145,7 @@ fn e2e_task_lifecycle_messages() {
         artifact,
         merkle_proof: vec![],
         is_synthesis: false,
+        cost: None,
     };
     let result_json = serde_json::to_value(&result_msg).unwrap();
     assert_eq!(result_json["artifact"]["size_bytes"], 2048);
@@ -270,12 +278,14 @@ fn e2e_multilevel_cascade_decomposition_and_backprop() {
             description: "Domain A".into(),
             required_capabilities: vec!["analysis".into()],
             estimated_complexity: 0.6,
+            budget: None,
         },
         PlanSubtask {
             index: 1,
             description: "Domain B".into(),
             required_capabilities: vec!["analysis".into()],
             estimated_complexity: 0.4,
+            budget: None,
         },
     ];
 
@@ -302,12 +312,14 @@ fn e2e_multilevel_cascade_decomposition_and_backprop() {
             description: "Leaf A1".into(),
             required_capabilities: vec!["exec".into()],
             estimated_complexity: 0.5,
+            budget: None,
         },
         PlanSubtask {
             index: 1,
             description: "Leaf A2".into(),
             required_capabilities: vec!["exec".into()],
             estimated_complexity: 0.5,
+            budget: None,
         },
     ];
 
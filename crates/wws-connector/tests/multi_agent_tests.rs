@@ -270,6 +270,8 @@ async fn test_proposal_commit_topic_exchange_between_peers() {
         proposer: AgentId::new("did:swarm:test-proposer".to_string()),
         epoch: 1,
         plan_hash: "0123456789abcdef".to_string(),
+        bond: 0,
+        selection: None,
     };
     let msg = SwarmMessage::new(
         ProtocolMethod::ProposalCommit.as_str(),
@@ -562,6 +564,8 @@ fn test_full_consensus_flow_offline() {
         proposer: agent_a.clone(),
         epoch: 1,
         plan_hash: hash_a.clone(),
+        bond: 0,
+        selection: None,
     })
     .unwrap();
 
@@ -570,6 +574,8 @@ fn test_full_consensus_flow_offline() {
         proposer: agent_b.clone(),
         epoch: 1,
         plan_hash: hash_b.clone(),
+        bond: 0,
+        selection: None,
     })
     .unwrap();
 
@@ -596,6 +602,7 @@ fn test_full_consensus_flow_offline() {
         prohibit_self_vote: true,
         min_votes: 1,
         senate_seed: Some(42),
+        ..Default::default()
     };
     let mut voting = VotingEngine::new(voting_config, task_id.clone(), 1);
 
@@ -619,6 +626,7 @@ fn test_full_consensus_flow_offline() {
             epoch: 1,
             rankings: vec![plan_a.plan_id.clone(), plan_b.plan_id.clone()],
             critic_scores: std::collections::HashMap::new(),
+            abstain: false,
         };
         voting.record_vote(vote).unwrap();
     }
@@ -21,6 +21,25 @@ fn test_identity_persistence() {
     );
 }
 
+#[test]
+fn test_peer_id_and_did_follow_the_stored_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = dir.path().join("agent.key");
+    let peer_id = |key: &ed25519_dalek::SigningKey| {
+        wws_network::Keypair::ed25519_from_bytes(key.to_bytes())
+            .unwrap()
+            .public()
+            .to_peer_id()
+    };
+
+    let k1 = wws_protocol::crypto::load_or_create_keypair(&key_path).unwrap();
+    let k2 = wws_protocol::crypto::load_or_create_keypair(&key_path).unwrap();
+    assert_eq!(peer_id(&k1), peer_id(&k2), "DID must be stable across restarts");
+
+    let (k3, _) = wws_protocol::crypto::replace_keypair(&key_path).unwrap();
+    assert_ne!(peer_id(&k1), peer_id(&k3), "a new identity must get a new DID");
+}
+
 // ─── Mnemonic round-trip ─────────────────────────────────────────────────────
 
 #[test]
@@ -0,0 +1,198 @@
+//! Benchmarks for the consensus hot paths: recording proposal commits and
+//! reveals, recording ballots and running Instant Runoff Voting.
+//!
+//! Run with `cargo bench -p wws-consensus`.
+
+use std::collections::HashMap;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use wws_consensus::rfp::RfpCoordinator;
+use wws_consensus::voting::{VotingConfig, VotingEngine};
+use wws_protocol::{
+    AgentId, Plan, PlanSubtask, ProposalCommitParams, ProposalRevealParams, RankedVote, Task,
+};
+
+const EPOCH: u64 = 1;
+const CANDIDATES: usize = 8;
+
+fn make_plan(task_id: &str, proposer: usize) -> Plan {
+    let mut plan = Plan::new(
+        task_id.to_string(),
+        AgentId::new(format!("did:swarm:proposer-{}", proposer)),
+        EPOCH,
+    );
+    for index in 0..3 {
+        plan.subtasks.push(PlanSubtask {
+            index,
+            description: format!("Subtask {} of proposer {}", index, proposer),
+            required_capabilities: vec!["python".to_string()],
+            estimated_complexity: 0.5,
+            budget: None,
+        });
+    }
+    plan.rationale = "Benchmark plan".to_string();
+    plan
+}
+
+/// Plans of `proposers` proposers and the commits that announce them.
+fn proposals(
+    task_id: &str,
+    proposers: usize,
+) -> (Vec<ProposalCommitParams>, Vec<ProposalRevealParams>) {
+    (0..proposers)
+        .map(|i| {
+            let plan = make_plan(task_id, i);
+            let commit = ProposalCommitParams {
+                task_id: task_id.to_string(),
+                proposer: plan.proposer.clone(),
+                epoch: EPOCH,
+                plan_hash: RfpCoordinator::compute_plan_hash(&plan).unwrap(),
+                bond: 0,
+                selection: None,
+            };
+            let reveal = ProposalRevealParams {
+                task_id: task_id.to_string(),
+                plan,
+            };
+            (commit, reveal)
+        })
+        .unzip()
+}
+
+fn coordinator(task: &Task, proposers: usize) -> RfpCoordinator {
+    let mut rfp = RfpCoordinator::new(task.task_id.clone(), EPOCH, proposers);
+    rfp.inject_task(task).unwrap();
+    rfp
+}
+
+fn bench_rfp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rfp");
+    for proposers in [10, 100, 1_000] {
+        let task = Task::new("Benchmark task".into(), 1, EPOCH);
+        let (commits, reveals) = proposals(&task.task_id, proposers);
+        group.throughput(Throughput::Elements(proposers as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("record_commit", proposers),
+            &proposers,
+            |b, &n| {
+                b.iter_batched(
+                    || coordinator(&task, n),
+                    |mut rfp| {
+                        for commit in &commits {
+                            rfp.record_commit(commit).unwrap();
+                        }
+                        rfp
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("record_reveal", proposers),
+            &proposers,
+            |b, &n| {
+                b.iter_batched(
+                    || {
+                        let mut rfp = coordinator(&task, n);
+                        for commit in &commits {
+                            rfp.record_commit(commit).unwrap();
+                        }
+                        rfp
+                    },
+                    |mut rfp| {
+                        for reveal in &reveals {
+                            rfp.record_reveal(reveal).unwrap();
+                        }
+                        rfp
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Ballots spread evenly over the candidates, so IRV eliminates all but
+/// two before a majority forms.
+fn ballots(count: usize) -> Vec<RankedVote> {
+    (0..count)
+        .map(|i| {
+            let first = i.wrapping_mul(2_654_435_761) % CANDIDATES;
+            RankedVote {
+                voter: AgentId::new(format!("did:swarm:voter-{}", i)),
+                task_id: "bench-task".to_string(),
+                epoch: EPOCH,
+                rankings: (0..CANDIDATES)
+                    .map(|k| format!("plan-{}", (first + k) % CANDIDATES))
+                    .collect(),
+                critic_scores: HashMap::new(),
+                abstain: false,
+            }
+        })
+        .collect()
+}
+
+fn engine() -> VotingEngine {
+    let config = VotingConfig {
+        min_votes: 1,
+        ..Default::default()
+    };
+    let mut engine = VotingEngine::new(config, "bench-task".to_string(), EPOCH);
+    engine.set_proposals(
+        (0..CANDIDATES)
+            .map(|k| {
+                (
+                    format!("plan-{}", k),
+                    AgentId::new(format!("did:swarm:proposer-{}", k)),
+                )
+            })
+            .collect(),
+    );
+    engine
+}
+
+fn bench_voting(c: &mut Criterion) {
+    let mut group = c.benchmark_group("voting");
+    for count in [1_000, 5_000, 10_000] {
+        let votes = ballots(count);
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("record_vote", count),
+            &votes,
+            |b, votes| {
+                b.iter_batched(
+                    || (engine(), votes.clone()),
+                    |(mut engine, votes)| {
+                        for vote in votes {
+                            engine.record_vote(vote).unwrap();
+                        }
+                        engine
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("run_irv", count), &votes, |b, votes| {
+            b.iter_batched(
+                || {
+                    let mut engine = engine();
+                    for vote in votes.iter().cloned() {
+                        engine.record_vote(vote).unwrap();
+                    }
+                    engine
+                },
+                |mut engine| engine.run_irv().unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_rfp, bench_voting);
+criterion_main!(benches);
@@ -15,7 +15,9 @@
 //! whether a task should be further decomposed or executed atomically.
 
 use std::collections::HashMap;
+use std::fmt;
 
+use serde::{Deserialize, Serialize};
 use wws_protocol::{AgentId, Plan, Task, TaskStatus, Tier};
 
 use crate::ConsensusError;
@@ -32,6 +34,49 @@ pub enum StopCondition {
     BottomTier,
     /// The task complexity is below the decomposition threshold (0.1).
     LowComplexity(f64),
+    /// Spawning another sub-holon would exceed the maximum cascade depth.
+    DepthLimit(u32),
+    /// The parent task has already spawned its maximum number of sub-holons.
+    FanOutLimit(usize),
+    /// The root task's cascade tree already holds its maximum number of sub-holons.
+    NodeLimit(usize),
+}
+
+impl fmt::Display for StopCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopCondition::AtomicTask => write!(f, "task is atomic"),
+            StopCondition::BottomTier => write!(f, "agent is at the bottom tier"),
+            StopCondition::LowComplexity(c) => write!(f, "complexity {:.2} is below threshold", c),
+            StopCondition::DepthLimit(max) => write!(f, "cascade depth limit {} reached", max),
+            StopCondition::FanOutLimit(max) => write!(f, "cascade fan-out limit {} reached", max),
+            StopCondition::NodeLimit(max) => write!(f, "cascade node limit {} reached", max),
+        }
+    }
+}
+
+/// Bounds on how far a single root task may recursively decompose.
+///
+/// Only sub-holons (subtasks that run their own RFP/vote cycle) count
+/// against these limits; directly assigned subtasks are leaves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CascadeLimits {
+    /// Deepest sub-holon allowed below the root task (the root is depth 0).
+    pub max_depth: u32,
+    /// Most sub-holons a single task may spawn.
+    pub max_fan_out: usize,
+    /// Most sub-holons allowed in one root task's cascade tree.
+    pub max_nodes: usize,
+}
+
+impl Default for CascadeLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_fan_out: 10,
+            max_nodes: 50,
+        }
+    }
 }
 
 /// Describes a subtask assignment to a subordinate agent.
@@ -94,16 +139,33 @@ pub struct CascadeEngine {
     completed: HashMap<String, bool>,
     /// The root task ID for the entire cascade.
     root_task_id: Option<String>,
+    /// Limits enforced on sub-holon spawning.
+    limits: CascadeLimits,
+    /// Mapping from sub-holon task ID to the task that spawned it.
+    spawned_by: HashMap<String, String>,
+    /// Number of sub-holons spawned directly by each task.
+    fan_out: HashMap<String, usize>,
+    /// Number of sub-holons in each root task's cascade tree.
+    node_counts: HashMap<String, usize>,
 }
 
 impl CascadeEngine {
     /// Create a new cascade engine.
     pub fn new() -> Self {
+        Self::with_limits(CascadeLimits::default())
+    }
+
+    /// Create a new cascade engine enforcing the given limits.
+    pub fn with_limits(limits: CascadeLimits) -> Self {
         Self {
             levels: HashMap::new(),
             subtask_to_parent: HashMap::new(),
             completed: HashMap::new(),
             root_task_id: None,
+            limits,
+            spawned_by: HashMap::new(),
+            fan_out: HashMap::new(),
+            node_counts: HashMap::new(),
         }
     }
 
@@ -138,6 +200,9 @@ impl CascadeEngine {
             StopCondition::AtomicTask => true,
             StopCondition::BottomTier => true,
             StopCondition::LowComplexity(complexity) => complexity < 0.1,
+            StopCondition::DepthLimit(_)
+            | StopCondition::FanOutLimit(_)
+            | StopCondition::NodeLimit(_) => true,
         }
     }
 
@@ -337,12 +402,89 @@ impl CascadeEngine {
         self.completed.values().all(|&done| done)
     }
 
+    // ── Sub-holon Limits ───────────────────────────────────────────
+
+    /// The limits enforced on sub-holon spawning.
+    pub fn limits(&self) -> CascadeLimits {
+        self.limits
+    }
+
+    /// Depth of a task in its cascade tree (root tasks are depth 0).
+    pub fn depth_of(&self, task_id: &str) -> u32 {
+        let mut depth = 0;
+        let mut current = task_id;
+        while let Some(parent) = self.spawned_by.get(current) {
+            depth += 1;
+            current = parent;
+            // Guard against a malformed lineage looping forever.
+            if depth as usize > self.spawned_by.len() {
+                break;
+            }
+        }
+        depth
+    }
+
+    /// The root task of the cascade tree containing `task_id`.
+    pub fn root_of<'a>(&'a self, task_id: &'a str) -> &'a str {
+        let mut current = task_id;
+        let mut hops = 0;
+        while let Some(parent) = self.spawned_by.get(current) {
+            current = parent;
+            hops += 1;
+            if hops > self.spawned_by.len() {
+                break;
+            }
+        }
+        current
+    }
+
+    /// Number of sub-holons in the cascade tree rooted at `root_task_id`.
+    pub fn node_count(&self, root_task_id: &str) -> usize {
+        self.node_counts.get(root_task_id).copied().unwrap_or(0)
+    }
+
+    /// Check whether `parent_task_id` may spawn one more sub-holon.
+    ///
+    /// Returns the limit that would be exceeded, or `None` if spawning
+    /// is allowed. Callers should assign the subtask directly instead.
+    pub fn spawn_limit(&self, parent_task_id: &str) -> Option<StopCondition> {
+        if self.depth_of(parent_task_id) + 1 > self.limits.max_depth {
+            return Some(StopCondition::DepthLimit(self.limits.max_depth));
+        }
+        let children = self.fan_out.get(parent_task_id).copied().unwrap_or(0);
+        if children >= self.limits.max_fan_out {
+            return Some(StopCondition::FanOutLimit(self.limits.max_fan_out));
+        }
+        if self.node_count(self.root_of(parent_task_id)) >= self.limits.max_nodes {
+            return Some(StopCondition::NodeLimit(self.limits.max_nodes));
+        }
+        None
+    }
+
+    /// Record that `child_task_id` was spawned as a sub-holon of `parent_task_id`.
+    ///
+    /// Recording the same child twice has no effect, so both the spawning
+    /// coordinator and peers observing the injection can record it.
+    pub fn record_spawn(&mut self, parent_task_id: &str, child_task_id: &str) {
+        if self.spawned_by.contains_key(child_task_id) || parent_task_id == child_task_id {
+            return;
+        }
+        let root = self.root_of(parent_task_id).to_string();
+        self.spawned_by
+            .insert(child_task_id.to_string(), parent_task_id.to_string());
+        *self.fan_out.entry(parent_task_id.to_string()).or_insert(0) += 1;
+        *self.node_counts.entry(root).or_insert(0) += 1;
+    }
+
     /// Reset the cascade engine for a new task.
     pub fn reset(&mut self) {
         self.levels.clear();
         self.subtask_to_parent.clear();
         self.completed.clear();
         self.root_task_id = None;
+        self.spawned_by.clear();
+        self.fan_out.clear();
+        self.node_counts.clear();
     }
 }
 
@@ -369,18 +511,21 @@ mod tests {
                 description: "Part A".into(),
                 required_capabilities: vec![],
                 estimated_complexity: 0.3,
+                budget: None,
             },
             PlanSubtask {
                 index: 1,
                 description: "Part B".into(),
                 required_capabilities: vec![],
                 estimated_complexity: 0.4,
+                budget: None,
             },
             PlanSubtask {
                 index: 2,
                 description: "Part C".into(),
                 required_capabilities: vec![],
                 estimated_complexity: 0.3,
+                budget: None,
             },
         ];
         plan
@@ -436,4 +581,45 @@ mod tests {
 
         assert!(engine.is_complete());
     }
+
+    #[test]
+    fn test_spawn_limit_depth() {
+        let mut engine = CascadeEngine::with_limits(CascadeLimits {
+            max_depth: 2,
+            ..CascadeLimits::default()
+        });
+        assert_eq!(engine.spawn_limit("root"), None);
+        engine.record_spawn("root", "root-st-1");
+        assert_eq!(engine.spawn_limit("root-st-1"), None);
+        engine.record_spawn("root-st-1", "root-st-1-st-1");
+
+        assert_eq!(engine.depth_of("root-st-1-st-1"), 2);
+        assert_eq!(engine.root_of("root-st-1-st-1"), "root");
+        assert_eq!(
+            engine.spawn_limit("root-st-1-st-1"),
+            Some(StopCondition::DepthLimit(2))
+        );
+    }
+
+    #[test]
+    fn test_spawn_limit_fan_out_and_nodes() {
+        let mut engine = CascadeEngine::with_limits(CascadeLimits {
+            max_depth: 5,
+            max_fan_out: 2,
+            max_nodes: 3,
+        });
+        engine.record_spawn("root", "a");
+        engine.record_spawn("root", "b");
+        assert_eq!(engine.spawn_limit("root"), Some(StopCondition::FanOutLimit(2)));
+
+        // Re-recording a known child is a no-op.
+        engine.record_spawn("root", "b");
+        assert_eq!(engine.node_count("root"), 2);
+
+        engine.record_spawn("a", "a1");
+        assert_eq!(engine.node_count("root"), 3);
+        assert_eq!(engine.spawn_limit("b"), Some(StopCondition::NodeLimit(3)));
+        // Other root tasks are unaffected.
+        assert_eq!(engine.spawn_limit("other_root"), None);
+    }
 }
@@ -4,15 +4,18 @@
 //! - Request for Proposal (RFP) protocol with commit-reveal scheme
 //! - Ranked Choice Voting with Instant Runoff Voting (IRV)
 //! - Recursive decomposition cascade for multi-tier task distribution
+//! - Sortition committees of proposers and voters for large tiers
 
 pub mod cascade;
 pub mod mock_planner;
 pub mod rfp;
+pub mod sortition;
 pub mod voting;
 
 pub use cascade::CascadeEngine;
 pub use mock_planner::MockPlanGenerator;
 pub use rfp::{PlanGenerator, RfpCoordinator};
+pub use sortition::Committee;
 pub use voting::VotingEngine;
 
 use thiserror::Error;
@@ -26,6 +29,9 @@ pub enum ConsensusError {
     #[error("Proposal already committed for task {0} by agent {1}")]
     DuplicateCommit(String, String),
 
+    #[error("Plan already revised for task {0} by agent {1}")]
+    DuplicateRevision(String, String),
+
     #[error("Proposal hash mismatch: expected {expected}, got {got}")]
     HashMismatch { expected: String, got: String },
 
@@ -53,6 +59,9 @@ pub enum ConsensusError {
     #[error("Epoch mismatch: expected {expected}, got {got}")]
     EpochMismatch { expected: u64, got: u64 },
 
+    #[error("Agent {agent_id} is not on the committee: {reason}")]
+    NotOnCommittee { agent_id: String, reason: String },
+
     #[error("Task not found: {0}")]
     TaskNotFound(String),
 
@@ -8,12 +8,18 @@
 //!    only the SHA-256 hash of the plan. This prevents copying.
 //! 2. **Reveal Phase**: After all commits are received (or timeout),
 //!    agents reveal their full plans. Plans must match their committed hash.
-//! 3. **Evaluation**: Plans are passed to voting for selection.
+//! 3. **Critique and revision**: Board members score the revealed plans;
+//!    each proposer may then submit one revised plan that replaces its
+//!    original.
+//!
+//!    Until the RFP completes, a proposer may also withdraw its revealed
+//!    plan, which takes it out of the running.
+//! 4. **Evaluation**: Plans are passed to voting for selection.
 //!
 //! Plan generation is delegated to a `PlanGenerator` trait that abstracts
 //! the LLM/AI component, allowing different backends.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
 
@@ -21,8 +27,8 @@ use chrono::{DateTime, Utc};
 use sha2::{Digest, Sha256};
 
 use wws_protocol::{
-    AgentId, CriticScore, Plan, ProposalCommitParams, ProposalRevealParams, Task,
-    COMMIT_REVEAL_TIMEOUT_SECS,
+    AgentId, CriticScore, Plan, ProposalCommitParams, ProposalRevealParams,
+    ProposalRevisionParams, ProposalWithdrawParams, Task, COMMIT_REVEAL_TIMEOUT_SECS,
 };
 
 use crate::ConsensusError;
@@ -79,6 +85,8 @@ pub enum RfpPhase {
     RevealPhase,
     /// Critique phase: members score each other's proposals.
     CritiquePhase,
+    /// Revision phase: proposers may amend their plan once after critique.
+    RevisionPhase,
     /// All plans revealed; ready for voting.
     ReadyForVoting,
     /// RFP completed (plan selected).
@@ -111,7 +119,11 @@ pub struct RevealedProposal {
 /// 3. `transition_to_reveal()` - move to reveal phase
 /// 4. `record_reveal()` - collect and verify revealed plans
 /// 5. `transition_to_critique()` - move to critique phase (optional)
-/// 6. `finalize()` - get all verified proposals for voting
+/// 6. `record_revision()` - accept one amended plan per proposer (optional)
+///
+/// `record_withdrawal()` retracts a revealed plan at any point before
+/// `finalize()`.
+/// 7. `finalize()` - get all verified proposals for voting
 pub struct RfpCoordinator {
     task_id: String,
     epoch: u64,
@@ -130,6 +142,14 @@ pub struct RfpCoordinator {
     pub critique_scores: HashMap<AgentId, HashMap<String, CriticScore>>,
     /// Critique content messages.
     pub critique_content: HashMap<AgentId, String>,
+    /// Proposers that have already used their revision.
+    revised: HashSet<AgentId>,
+    /// Original proposals replaced by a revision, in revision order.
+    pub superseded: Vec<RevealedProposal>,
+    /// Proposals retracted by their proposers, in withdrawal order.
+    pub withdrawn: Vec<RevealedProposal>,
+    /// When the coordinator was created.
+    started_at: DateTime<Utc>,
 }
 
 impl RfpCoordinator {
@@ -146,6 +166,10 @@ impl RfpCoordinator {
             expected_proposers,
             critique_scores: HashMap::new(),
             critique_content: HashMap::new(),
+            revised: HashSet::new(),
+            superseded: Vec::new(),
+            withdrawn: Vec::new(),
+            started_at: Utc::now(),
         }
     }
 
@@ -299,6 +323,12 @@ impl RfpCoordinator {
         }
 
         let proposer = &params.plan.proposer;
+        if self.has_withdrawn(proposer) {
+            return Err(ConsensusError::RfpFailed(format!(
+                "Proposer {} withdrew its plan",
+                proposer
+            )));
+        }
 
         // Verify the reveal matches the commit.
         let commit = self.commits.get(proposer).ok_or_else(|| {
@@ -377,11 +407,144 @@ impl RfpCoordinator {
         Ok(())
     }
 
-    /// Transition from CritiquePhase to ReadyForVoting.
+    /// Replace a proposer's revealed plan with its revision.
+    ///
+    /// Only allowed once critiques have started, and only once per proposer.
+    /// The revision is not committed beforehand: every plan is already public
+    /// by this point. Moves the RFP into `RevisionPhase` and returns the
+    /// superseded proposal, which is also kept in `superseded`.
+    pub fn record_revision(
+        &mut self,
+        params: &ProposalRevisionParams,
+    ) -> Result<RevealedProposal, ConsensusError> {
+        if !matches!(self.phase, RfpPhase::CritiquePhase | RfpPhase::RevisionPhase) {
+            return Err(ConsensusError::RfpFailed(format!(
+                "Revisions are only accepted after critique (currently {:?})",
+                self.phase
+            )));
+        }
+
+        if params.task_id != self.task_id || params.plan.task_id != self.task_id {
+            return Err(ConsensusError::TaskNotFound(self.task_id.clone()));
+        }
+
+        let proposer = &params.plan.proposer;
+        let original = self.reveals.get(proposer).ok_or_else(|| {
+            ConsensusError::RfpFailed(format!("No revealed plan from proposer {}", proposer))
+        })?;
+        if original.plan.plan_id != params.original_plan_id {
+            return Err(ConsensusError::RfpFailed(format!(
+                "Plan {} is not the current plan of proposer {}",
+                params.original_plan_id, proposer
+            )));
+        }
+        if params.plan.plan_id == params.original_plan_id {
+            return Err(ConsensusError::RfpFailed(
+                "Revised plan must have a new plan ID".into(),
+            ));
+        }
+        if self.revised.contains(proposer) {
+            return Err(ConsensusError::DuplicateRevision(
+                self.task_id.clone(),
+                proposer.to_string(),
+            ));
+        }
+
+        let plan_hash = Self::compute_plan_hash(&params.plan)?;
+        let previous = self
+            .reveals
+            .insert(
+                proposer.clone(),
+                RevealedProposal {
+                    proposer: proposer.clone(),
+                    plan: params.plan.clone(),
+                    plan_hash,
+                },
+            )
+            .expect("original reveal checked above");
+        self.revised.insert(proposer.clone());
+        self.superseded.push(previous.clone());
+        self.phase = RfpPhase::RevisionPhase;
+
+        tracing::info!(
+            task_id = %self.task_id,
+            proposer = %proposer,
+            original_plan_id = %params.original_plan_id,
+            revised_plan_id = %params.plan.plan_id,
+            "Recorded plan revision"
+        );
+
+        Ok(previous)
+    }
+
+    /// Retract a proposer's revealed plan. Accepted from the reveal phase
+    /// until the RFP completes; returns the withdrawn proposal, which is
+    /// also kept in `withdrawn`.
+    pub fn record_withdrawal(
+        &mut self,
+        params: &ProposalWithdrawParams,
+    ) -> Result<RevealedProposal, ConsensusError> {
+        if matches!(
+            self.phase,
+            RfpPhase::Idle | RfpPhase::CommitPhase | RfpPhase::Completed
+        ) {
+            return Err(ConsensusError::RfpFailed(format!(
+                "Withdrawals are only accepted while plans are open (currently {:?})",
+                self.phase
+            )));
+        }
+
+        if params.task_id != self.task_id {
+            return Err(ConsensusError::TaskNotFound(self.task_id.clone()));
+        }
+
+        let current = self.reveals.get(&params.proposer).ok_or_else(|| {
+            ConsensusError::RfpFailed(format!(
+                "No revealed plan from proposer {}",
+                params.proposer
+            ))
+        })?;
+        if current.plan.plan_id != params.plan_id {
+            return Err(ConsensusError::RfpFailed(format!(
+                "Plan {} is not the current plan of proposer {}",
+                params.plan_id, params.proposer
+            )));
+        }
+
+        let withdrawn = self
+            .reveals
+            .remove(&params.proposer)
+            .expect("current reveal checked above");
+        self.withdrawn.push(withdrawn.clone());
+
+        tracing::info!(
+            task_id = %self.task_id,
+            proposer = %params.proposer,
+            plan_id = %params.plan_id,
+            "Recorded plan withdrawal"
+        );
+
+        Ok(withdrawn)
+    }
+
+    /// Whether a proposer has withdrawn its plan.
+    pub fn has_withdrawn(&self, proposer: &AgentId) -> bool {
+        self.withdrawn.iter().any(|w| &w.proposer == proposer)
+    }
+
+    /// Whether a proposer has already revised its plan.
+    pub fn has_revised(&self, proposer: &AgentId) -> bool {
+        self.revised.contains(proposer)
+    }
+
+    /// Transition from CritiquePhase or RevisionPhase to ReadyForVoting.
     pub fn transition_to_voting(&mut self) -> Result<(), ConsensusError> {
         if !matches!(
             self.phase,
-            RfpPhase::CritiquePhase | RfpPhase::RevealPhase | RfpPhase::ReadyForVoting
+            RfpPhase::CritiquePhase
+                | RfpPhase::RevisionPhase
+                | RfpPhase::RevealPhase
+                | RfpPhase::ReadyForVoting
         ) {
             return Err(ConsensusError::RfpFailed(format!(
                 "Cannot transition to voting from {:?}",
@@ -398,7 +561,10 @@ impl RfpCoordinator {
 
     /// Finalize the RFP and get all verified proposals for voting.
     pub fn finalize(&mut self) -> Result<Vec<RevealedProposal>, ConsensusError> {
-        if self.phase != RfpPhase::ReadyForVoting && self.phase != RfpPhase::RevealPhase {
+        if !matches!(
+            self.phase,
+            RfpPhase::ReadyForVoting | RfpPhase::RevealPhase | RfpPhase::RevisionPhase
+        ) {
             return Err(ConsensusError::RfpFailed(format!(
                 "Cannot finalize in phase {:?}",
                 self.phase
@@ -432,6 +598,16 @@ impl RfpCoordinator {
         &self.task_id
     }
 
+    /// Epoch the RFP round belongs to.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// When the coordinator was created.
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
     /// Get the number of commits received.
     pub fn commit_count(&self) -> usize {
         self.commits.len()
@@ -450,6 +626,25 @@ impl RfpCoordinator {
             .collect()
     }
 
+    /// The recorded commits as protocol messages, ordered by proposer, so
+    /// they can be replayed into another coordinator.
+    pub fn commit_params(&self) -> Vec<ProposalCommitParams> {
+        let mut commits: Vec<ProposalCommitParams> = self
+            .commits
+            .iter()
+            .map(|(proposer, pending)| ProposalCommitParams {
+                task_id: self.task_id.clone(),
+                proposer: proposer.clone(),
+                epoch: self.epoch,
+                plan_hash: pending.plan_hash.clone(),
+                bond: 0,
+                selection: None,
+            })
+            .collect();
+        commits.sort_by(|a, b| a.proposer.as_str().cmp(b.proposer.as_str()));
+        commits
+    }
+
     /// Compute the commit hash for a plan (for use by proposers).
     pub fn compute_plan_hash(plan: &Plan) -> Result<String, ConsensusError> {
         let plan_json = serde_json::to_vec(plan)
@@ -459,7 +654,7 @@ impl RfpCoordinator {
 }
 
 /// Hex-encode a byte slice.
-fn hex_encode(bytes: &[u8]) -> String {
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
@@ -479,6 +674,7 @@ mod tests {
             description: "Subtask A".to_string(),
             required_capabilities: vec!["python".to_string()],
             estimated_complexity: 0.5,
+            budget: None,
         });
         plan.rationale = "Test plan".to_string();
         plan
@@ -503,6 +699,8 @@ mod tests {
             proposer: AgentId::new("alice".into()),
             epoch: 1,
             plan_hash: hash,
+            bond: 0,
+            selection: None,
         })
         .unwrap();
 
@@ -537,6 +735,8 @@ mod tests {
             proposer: AgentId::new("alice".into()),
             epoch: 1,
             plan_hash: "fake_hash".into(),
+            bond: 0,
+            selection: None,
         })
         .unwrap();
 
@@ -565,6 +765,8 @@ mod tests {
             proposer: AgentId::new("alice".into()),
             epoch: 1,
             plan_hash: hash,
+            bond: 0,
+            selection: None,
         }).unwrap();
 
         rfp.record_reveal(&ProposalRevealParams {
@@ -613,12 +815,16 @@ mod tests {
             proposer: AgentId::new("alice".into()),
             epoch: 1,
             plan_hash: hash_alice,
+            bond: 0,
+            selection: None,
         }).unwrap();
         rfp.record_commit(&ProposalCommitParams {
             task_id: task_id.clone(),
             proposer: AgentId::new("bob".into()),
             epoch: 1,
             plan_hash: hash_bob,
+            bond: 0,
+            selection: None,
         }).unwrap();
 
         // Should auto-transition to reveal after 2 commits
@@ -681,6 +887,8 @@ mod tests {
             proposer: AgentId::new("alice".into()),
             epoch: 1,
             plan_hash: hash,
+            bond: 0,
+            selection: None,
         }).unwrap();
         rfp.record_reveal(&ProposalRevealParams {
             task_id: task_id.clone(),
@@ -745,6 +953,8 @@ mod tests {
             proposer: AgentId::new("alice".into()),
             epoch: 1,
             plan_hash: hash,
+            bond: 0,
+            selection: None,
         }).unwrap();
         rfp.record_reveal(&ProposalRevealParams { task_id: task_id.clone(), plan: plan.clone() }).unwrap();
         rfp.transition_to_critique().unwrap();
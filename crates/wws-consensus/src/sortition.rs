@@ -0,0 +1,231 @@
+//! Sortition: a verifiable random committee of proposers and voters.
+//!
+//! In a tier of hundreds of agents, waiting for every member to propose and
+//! vote makes each round as slow as the slowest of them. A committee of a
+//! configured size is drawn from the tier for each task instead, and only its
+//! members propose and vote.
+//!
+//! The draw is seeded from the task's epoch and ID, so every node with the
+//! same view of the tier draws the same committee, and any node can check an
+//! agent's seat: the agent's ticket is the hash of the seed and its DID.
+//! Agents are drawn without replacement in proportion to their weight
+//! (Efraimidis–Spirakis): a ticket read as `u` in (0, 1] gets the key
+//! `ln(u) / weight`, and the committee is the agents with the highest keys.
+
+use sha2::{Digest, Sha256};
+use wws_protocol::SelectionProof;
+
+use crate::rfp::hex_encode;
+use crate::ConsensusError;
+
+/// Domain separator of committee seeds.
+const SEED_DOMAIN: &[u8] = b"wws-sortition-v1";
+
+/// The seed of the committee for `task_id` in `epoch`.
+pub fn seed(epoch: u64, task_id: &str) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(SEED_DOMAIN)
+        .chain_update(epoch.to_be_bytes())
+        .chain_update(task_id.as_bytes())
+        .finalize()
+        .into()
+}
+
+/// An agent's ticket in the draw seeded by `seed`.
+pub fn ticket(seed: &[u8; 32], agent_id: &str) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(seed)
+        .chain_update(agent_id.as_bytes())
+        .finalize();
+    u64::from_be_bytes(digest[..8].try_into().expect("SHA-256 digest has 8 bytes"))
+}
+
+/// Draw key of a ticket held with `weight`. Agents without a positive
+/// weight are drawn last.
+fn key(ticket: u64, weight: f64) -> f64 {
+    if !(weight.is_finite() && weight > 0.0) {
+        return f64::NEG_INFINITY;
+    }
+    let u = (ticket as f64 + 1.0) / (u64::MAX as f64 + 1.0);
+    u.ln() / weight
+}
+
+/// The committee drawn for one task.
+#[derive(Debug, Clone)]
+pub struct Committee {
+    epoch: u64,
+    seed: [u8; 32],
+    size: usize,
+    eligible: usize,
+    /// Members and their keys, highest key first.
+    members: Vec<(String, f64)>,
+}
+
+impl Committee {
+    /// Draw `size` of `eligible` for `task_id` in `epoch`, each agent in
+    /// proportion to `weight(agent_id)`. Every eligible agent gets a seat
+    /// when there are no more of them than seats.
+    pub fn draw(
+        epoch: u64,
+        task_id: &str,
+        eligible: &[String],
+        size: usize,
+        weight: impl Fn(&str) -> f64,
+    ) -> Self {
+        let seed = seed(epoch, task_id);
+        let mut members: Vec<(String, f64)> = eligible
+            .iter()
+            .map(|id| (id.clone(), key(ticket(&seed, id), weight(id))))
+            .collect();
+        members.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        members.dedup_by(|a, b| a.0 == b.0);
+        members.truncate(size);
+        Self {
+            epoch,
+            seed,
+            size,
+            eligible: eligible.len(),
+            members,
+        }
+    }
+
+    /// Whether the committee is smaller than the pool it was drawn from.
+    pub fn is_sampled(&self) -> bool {
+        self.eligible > self.size
+    }
+
+    /// Member DIDs in draw order.
+    pub fn members(&self) -> Vec<String> {
+        self.members.iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    pub fn contains(&self, agent_id: &str) -> bool {
+        self.members.iter().any(|(id, _)| id == agent_id)
+    }
+
+    /// Whether `agent_id`, drawn with `weight`, earns a seat: its key is at
+    /// least the lowest key on the committee. This also admits an agent this
+    /// node did not know to be eligible, as long as its draw is good enough.
+    pub fn admits(&self, agent_id: &str, weight: f64) -> bool {
+        if self.contains(agent_id) || self.members.len() < self.size {
+            return true;
+        }
+        match self.members.last() {
+            Some((_, cutoff)) => {
+                let key = key(ticket(&self.seed, agent_id), weight);
+                key > f64::NEG_INFINITY && key >= *cutoff
+            }
+            None => false,
+        }
+    }
+
+    /// Proof of `agent_id`'s seat, drawn with `weight`, if it has one.
+    pub fn proof_for(&self, agent_id: &str, weight: f64) -> Option<SelectionProof> {
+        let rank = self.members.iter().position(|(id, _)| id == agent_id)? + 1;
+        Some(SelectionProof {
+            epoch: self.epoch,
+            seed: hex_encode(&self.seed),
+            ticket: hex_encode(&ticket(&self.seed, agent_id).to_be_bytes()),
+            weight,
+            rank,
+            committee_size: self.size,
+            eligible: self.eligible,
+        })
+    }
+
+    /// Check that `proof` is `agent_id`'s draw for this committee with the
+    /// weight this node gives it, and that the draw earns a seat.
+    pub fn verify(
+        &self,
+        agent_id: &str,
+        weight: f64,
+        proof: &SelectionProof,
+    ) -> Result<(), ConsensusError> {
+        let not_on_committee = |reason: String| ConsensusError::NotOnCommittee {
+            agent_id: agent_id.to_string(),
+            reason,
+        };
+        if proof.epoch != self.epoch || proof.seed != hex_encode(&self.seed) {
+            return Err(not_on_committee(format!(
+                "drawn in epoch {}, the committee is drawn in epoch {}",
+                proof.epoch, self.epoch
+            )));
+        }
+        if proof.ticket != hex_encode(&ticket(&self.seed, agent_id).to_be_bytes()) {
+            return Err(not_on_committee("ticket does not match the seed".to_string()));
+        }
+        if (proof.weight - weight).abs() > 1e-9 {
+            return Err(not_on_committee(format!(
+                "drawn with weight {}, expected {}",
+                proof.weight, weight
+            )));
+        }
+        if !self.admits(agent_id, weight) {
+            return Err(not_on_committee(format!(
+                "ticket ranks below the {} seats",
+                self.size
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("did:swarm:agent-{:03}", i)).collect()
+    }
+
+    #[test]
+    fn committees_are_reproducible_weighted_and_verifiable() {
+        let eligible = tier(200);
+        let a = Committee::draw(7, "task-1", &eligible, 20, |_| 1.0);
+        let b = Committee::draw(7, "task-1", &eligible, 20, |_| 1.0);
+        assert!(a.is_sampled());
+        assert_eq!(a.members().len(), 20);
+        assert_eq!(a.members(), b.members());
+        assert_ne!(
+            a.members(),
+            Committee::draw(8, "task-1", &eligible, 20, |_| 1.0).members()
+        );
+        assert_ne!(
+            a.members(),
+            Committee::draw(7, "task-2", &eligible, 20, |_| 1.0).members()
+        );
+
+        // Zero-weight agents never hold a seat; heavy agents usually do.
+        let heavy = |id: &str| match id {
+            "did:swarm:agent-000" => 0.0,
+            "did:swarm:agent-001" => 1000.0,
+            _ => 1.0,
+        };
+        let weighted = Committee::draw(7, "task-1", &eligible, 20, heavy);
+        assert!(!weighted.contains("did:swarm:agent-000"));
+        assert!(weighted.contains("did:swarm:agent-001"));
+
+        let member = a.members()[0].clone();
+        let proof = a.proof_for(&member, 1.0).unwrap();
+        assert_eq!(proof.rank, 1);
+        assert!(a.verify(&member, 1.0, &proof).is_ok());
+        assert!(a.verify(&member, 2.0, &proof).is_err());
+
+        let outsider = eligible.iter().find(|id| !a.contains(id)).unwrap();
+        assert!(!a.admits(outsider, 1.0));
+        let mut forged = proof.clone();
+        forged.ticket = hex_encode(&ticket(&a.seed, outsider).to_be_bytes());
+        assert!(a.verify(outsider, 1.0, &forged).is_err());
+        assert!(a.verify(outsider, 1.0, &proof).is_err());
+
+        let stale = Committee::draw(6, "task-1", &eligible, 20, |_| 1.0);
+        let stale_member = stale.members()[0].clone();
+        let stale_proof = stale.proof_for(&stale_member, 1.0).unwrap();
+        assert!(a.verify(&stale_member, 1.0, &stale_proof).is_err());
+
+        let small = Committee::draw(7, "task-1", &tier(5), 20, |_| 1.0);
+        assert!(!small.is_sampled());
+        assert_eq!(small.members().len(), 5);
+        assert!(small.admits("did:swarm:latecomer", 1.0));
+    }
+}
@@ -13,9 +13,31 @@
 //! 3. Otherwise, eliminate the plan with the fewest first-choice votes
 //! 4. Redistribute eliminated plan's votes to each voter's next preference
 //! 5. Repeat until a plan has majority or one plan remains
+//!
+//! Ballots may rank only some of the plans; once all of a ballot's choices
+//! are eliminated it no longer counts towards the majority. A voter may also
+//! abstain: the ballot counts for participation (quorum) but not for any
+//! plan.
+//!
+//! The engine keeps each ballot filed under its highest-ranked plan as
+//! ballots arrive, so the first-round tally is always at hand and each
+//! elimination round only moves the ballots of the plan it eliminated,
+//! instead of recounting every ballot every round.
+//!
+//! When several plans share the lowest tally, the configured
+//! [`TieBreakPolicy`] picks the one to eliminate and the choice is recorded
+//! on the `IrvRound`.
+//!
+//! With [`BallotMode::Blind`], voters only publish a commitment (the hash of
+//! their ballot and a secret salt) while voting is open, and reveal the
+//! ballot once the commitments are closed. Nobody can rank plans by what the
+//! early ballots say, and ballots that do not match their commitment or are
+//! never revealed are not counted.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use wws_protocol::{AgentId, CriticScore, RankedVote};
 use rand::seq::SliceRandom;
 
@@ -33,6 +55,13 @@ pub struct VotingConfig {
     pub min_votes: usize,
     /// Random seed for reproducible senate sampling (None = random).
     pub senate_seed: Option<u64>,
+    /// How to choose the plan to eliminate when several share the lowest tally.
+    pub tie_break: TieBreakPolicy,
+    /// Seed for `TieBreakPolicy::Random` (None = derived from the task ID and
+    /// epoch, so every node breaks the tie the same way).
+    pub tie_break_seed: Option<u64>,
+    /// Whether ballots are cast openly or committed and revealed later.
+    pub ballot_mode: BallotMode,
 }
 
 impl Default for VotingConfig {
@@ -42,10 +71,126 @@ impl Default for VotingConfig {
             prohibit_self_vote: true,
             min_votes: 1,
             senate_seed: None,
+            tie_break: TieBreakPolicy::default(),
+            tie_break_seed: None,
+            ballot_mode: BallotMode::default(),
+        }
+    }
+}
+
+/// How ballots are cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BallotMode {
+    /// Ballots are published and counted as they arrive.
+    #[default]
+    Open,
+    /// Voters commit to a ballot while voting is open and reveal it after
+    /// the commitments close.
+    Blind,
+}
+
+impl BallotMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Blind => "blind",
+        }
+    }
+}
+
+/// Commitment to a blind ballot: SHA-256 hex of the ballot and `salt`.
+///
+/// Critic scores are hashed in plan ID order, so the commitment does not
+/// depend on map iteration order.
+pub fn ballot_commitment(vote: &RankedVote, salt: &str) -> String {
+    hash_ballot(vote, Some(salt))
+}
+
+/// SHA-256 hex of a ballot as cast, quoted in ballot receipts.
+pub fn ballot_hash(vote: &RankedVote) -> String {
+    hash_ballot(vote, None)
+}
+
+fn hash_ballot(vote: &RankedVote, salt: Option<&str>) -> String {
+    #[derive(Serialize)]
+    struct Committed<'a> {
+        task_id: &'a str,
+        epoch: u64,
+        voter: &'a str,
+        rankings: &'a [String],
+        critic_scores: BTreeMap<&'a str, &'a CriticScore>,
+        abstain: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        salt: Option<&'a str>,
+    }
+    let committed = Committed {
+        task_id: &vote.task_id,
+        epoch: vote.epoch,
+        voter: vote.voter.as_str(),
+        rankings: &vote.rankings,
+        critic_scores: vote
+            .critic_scores
+            .iter()
+            .map(|(plan_id, score)| (plan_id.as_str(), score))
+            .collect(),
+        abstain: vote.abstain,
+        salt,
+    };
+    let bytes = serde_json::to_vec(&committed).unwrap_or_default();
+    Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Strategy for resolving ties between the lowest-ranked plans in an IRV round.
+///
+/// If the strategy cannot separate the tied plans, the plan with the lowest
+/// plan ID is eliminated and the round records `plan_id_order`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreakPolicy {
+    /// Eliminate the plan with the fewest first-preference votes in round 1.
+    #[default]
+    LowestFirstPreference,
+    /// Eliminate the plan with the lowest aggregate critic score.
+    CriticScore,
+    /// Eliminate a plan chosen at random; the seed is recorded.
+    Random,
+    /// Eliminate the plan the board chair ranked lowest.
+    EscalateToChair,
+}
+
+impl TieBreakPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LowestFirstPreference => "lowest_first_preference",
+            Self::CriticScore => "critic_score",
+            Self::Random => "random",
+            Self::EscalateToChair => "escalate_to_chair",
         }
     }
 }
 
+/// Tie-break label recorded when no policy could separate the tied plans.
+const PLAN_ID_ORDER: &str = "plan_id_order";
+
+/// Domain separator of derived tie-break seeds.
+const TIE_BREAK_SEED_DOMAIN: &[u8] = b"wws-tie-break-v1";
+
+/// The `TieBreakPolicy::Random` seed used for `task_id` in `epoch` when none
+/// is configured. Every node tallies independently, so the seed must come
+/// from data they all share.
+pub fn tie_break_seed(task_id: &str, epoch: u64) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(TIE_BREAK_SEED_DOMAIN)
+        .chain_update(epoch.to_be_bytes())
+        .chain_update(task_id.as_bytes())
+        .finalize();
+    u64::from_be_bytes(digest[..8].try_into().expect("SHA-256 digest has 8 bytes"))
+}
+
 /// Result of a voting round.
 #[derive(Debug, Clone)]
 pub struct VotingResult {
@@ -57,8 +202,10 @@ pub struct VotingResult {
     pub elimination_order: Vec<String>,
     /// Final vote counts for remaining plans.
     pub final_tallies: HashMap<String, usize>,
-    /// Total number of votes processed.
+    /// Total number of ranked ballots processed.
     pub total_votes: usize,
+    /// Number of abstaining ballots.
+    pub abstentions: usize,
     /// Aggregate critic scores for the winning plan.
     pub winner_critic_score: Option<CriticScore>,
 }
@@ -73,6 +220,10 @@ pub struct Ballot {
     pub critic_scores: HashMap<String, CriticScore>,
     /// Original rankings before any IRV elimination (for record-keeping).
     pub original_rankings: Vec<String>,
+    /// The voter abstained; the ballot ranks nothing.
+    pub abstain: bool,
+    /// `ballot_hash` of the vote as cast.
+    pub hash: String,
 }
 
 /// Coordinates Ranked Choice Voting with Instant Runoff for plan selection.
@@ -81,6 +232,9 @@ pub struct Ballot {
 /// 1. `set_proposals()` - register the plan IDs being voted on
 /// 2. `record_vote()` - collect ranked ballots from agents
 /// 3. `run_irv()` - execute the IRV algorithm and determine the winner
+///
+/// Blind ballots replace step 2 with `record_commitment()`, then
+/// `close_commitments()`, then `record_reveal()`.
 pub struct VotingEngine {
     config: VotingConfig,
     task_id: String,
@@ -89,14 +243,34 @@ pub struct VotingEngine {
     proposal_ids: HashSet<String>,
     /// Map from plan ID to proposer agent ID (for self-vote checking).
     plan_proposers: HashMap<String, AgentId>,
-    /// Collected ballots.
+    /// Collected ballots. Recorded through `record_vote` or `record_reveal`,
+    /// which keep the tallies below in step.
     pub ballots: Vec<Ballot>,
+    /// Indexes into `ballots` by the plan each ballot currently ranks
+    /// highest; the lengths are the first-round tally.
+    piles: HashMap<String, Vec<usize>>,
+    /// Number of abstaining ballots.
+    abstentions: usize,
+    /// Index into `ballots` of each voter's ballot.
+    voter_index: HashMap<AgentId, usize>,
     /// Agents selected for the senate (if sampling).
     senate: Option<HashSet<AgentId>>,
     /// Whether voting has been finalized.
     finalized: bool,
     /// IRV round history (populated after run_irv()).
     pub irv_rounds: Vec<wws_protocol::IrvRound>,
+    /// Board chair consulted by `TieBreakPolicy::EscalateToChair`.
+    chair: Option<AgentId>,
+    /// Blind ballot commitments by voter.
+    commitments: HashMap<AgentId, String>,
+    /// When the commitments were closed and reveals opened.
+    commitments_closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Verified reveals that arrived before the commitments closed here.
+    early_reveals: Vec<RankedVote>,
+    /// Plan IDs withdrawn by their proposers; never registered again.
+    withdrawn: HashSet<String>,
+    /// When the engine was created.
+    started_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl VotingEngine {
@@ -109,9 +283,18 @@ impl VotingEngine {
             proposal_ids: HashSet::new(),
             plan_proposers: HashMap::new(),
             ballots: Vec::new(),
+            piles: HashMap::new(),
+            abstentions: 0,
+            voter_index: HashMap::new(),
             senate: None,
             finalized: false,
             irv_rounds: Vec::new(),
+            chair: None,
+            commitments: HashMap::new(),
+            commitments_closed_at: None,
+            early_reveals: Vec::new(),
+            withdrawn: HashSet::new(),
+            started_at: chrono::Utc::now(),
         }
     }
 
@@ -120,12 +303,91 @@ impl VotingEngine {
     /// `proposals` maps plan_id to the proposer's agent_id.
     pub fn set_proposals(&mut self, proposals: HashMap<String, AgentId>) {
         for (plan_id, proposer) in &proposals {
+            if self.withdrawn.contains(plan_id) {
+                continue;
+            }
             self.proposal_ids.insert(plan_id.clone());
             self.plan_proposers
                 .insert(plan_id.clone(), proposer.clone());
         }
     }
 
+    /// Set the board chair whose ballot breaks ties under
+    /// `TieBreakPolicy::EscalateToChair`.
+    pub fn set_chair(&mut self, chair: AgentId) {
+        self.chair = Some(chair);
+    }
+
+    /// Swap a proposal for its revised version.
+    ///
+    /// Ballots that already ranked the original carry over to the revision.
+    pub fn replace_proposal(
+        &mut self,
+        old_plan_id: &str,
+        new_plan_id: &str,
+    ) -> Result<(), ConsensusError> {
+        if self.finalized {
+            return Err(ConsensusError::VotingError(
+                "Voting already finalized".into(),
+            ));
+        }
+        if !self.proposal_ids.remove(old_plan_id) {
+            return Err(ConsensusError::VotingError(format!(
+                "Unknown proposal {}",
+                old_plan_id
+            )));
+        }
+        self.proposal_ids.insert(new_plan_id.to_string());
+        if let Some(proposer) = self.plan_proposers.remove(old_plan_id) {
+            self.plan_proposers.insert(new_plan_id.to_string(), proposer);
+        }
+        if let Some(pile) = self.piles.remove(old_plan_id) {
+            self.piles.entry(new_plan_id.to_string()).or_default().extend(pile);
+        }
+        for ballot in &mut self.ballots {
+            for choice in ballot
+                .original_rankings
+                .iter_mut()
+                .chain(ballot.remaining_choices.iter_mut())
+            {
+                if choice == old_plan_id {
+                    *choice = new_plan_id.to_string();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Take a withdrawn proposal out of the running.
+    ///
+    /// Ballots that ranked it keep their other choices; a ballot left with
+    /// none no longer counts towards a plan.
+    pub fn withdraw_proposal(&mut self, plan_id: &str) -> Result<(), ConsensusError> {
+        if self.finalized {
+            return Err(ConsensusError::VotingError(
+                "Voting already finalized".into(),
+            ));
+        }
+        if !self.proposal_ids.remove(plan_id) {
+            return Err(ConsensusError::VotingError(format!(
+                "Unknown proposal {}",
+                plan_id
+            )));
+        }
+        self.plan_proposers.remove(plan_id);
+        self.withdrawn.insert(plan_id.to_string());
+        for ballot in &mut self.ballots {
+            ballot.original_rankings.retain(|id| id != plan_id);
+            ballot.remaining_choices.retain(|id| id != plan_id);
+        }
+        for index in self.piles.remove(plan_id).unwrap_or_default() {
+            if let Some(next) = self.ballots[index].remaining_choices.first() {
+                self.piles.entry(next.clone()).or_default().push(index);
+            }
+        }
+        Ok(())
+    }
+
     /// Select a senate from the list of eligible voters.
     ///
     /// If the voter pool is larger than `senate_size`, a random subset
@@ -166,6 +428,20 @@ impl VotingEngine {
     /// - Self-vote prohibition (voter cannot rank own plan first)
     /// - All ranked plan IDs are valid proposals
     pub fn record_vote(&mut self, vote: RankedVote) -> Result<(), ConsensusError> {
+        if self.config.ballot_mode == BallotMode::Blind {
+            return Err(ConsensusError::VotingError(format!(
+                "Ballots for task {} are blind; commit and reveal instead",
+                self.task_id
+            )));
+        }
+        let rankings = self.validate_vote(&vote)?;
+        self.push_ballot(vote, rankings);
+        Ok(())
+    }
+
+    /// Check `vote` as `record_vote` would, without recording it. Returns
+    /// the rankings that would be counted, none for an abstention.
+    pub fn validate_vote(&self, vote: &RankedVote) -> Result<Vec<String>, ConsensusError> {
         if self.finalized {
             return Err(ConsensusError::VotingError(
                 "Voting already finalized".into(),
@@ -193,6 +469,15 @@ impl VotingEngine {
             }
         }
 
+        if vote.abstain {
+            if !vote.rankings.is_empty() {
+                return Err(ConsensusError::VotingError(
+                    "An abstaining ballot cannot rank plans".into(),
+                ));
+            }
+            return Ok(Vec::new());
+        }
+
         // Self-vote prohibition: voter cannot rank their own plan first.
         if self.config.prohibit_self_vote && self.proposal_ids.len() > 1 {
             if let Some(first_choice) = vote.rankings.first() {
@@ -220,11 +505,25 @@ impl VotingEngine {
             ));
         }
 
+        Ok(valid_rankings)
+    }
+
+    fn push_ballot(&mut self, vote: RankedVote, valid_rankings: Vec<String>) {
+        let hash = ballot_hash(&vote);
+        let index = self.ballots.len();
+        if vote.abstain {
+            self.abstentions += 1;
+        } else if let Some(first) = valid_rankings.first() {
+            self.piles.entry(first.clone()).or_default().push(index);
+        }
+        self.voter_index.entry(vote.voter.clone()).or_insert(index);
         self.ballots.push(Ballot {
+            hash,
             voter: vote.voter.clone(),
             original_rankings: valid_rankings.clone(),
             remaining_choices: valid_rankings,
             critic_scores: vote.critic_scores,
+            abstain: vote.abstain,
         });
 
         tracing::debug!(
@@ -233,45 +532,159 @@ impl VotingEngine {
             ballots = self.ballots.len(),
             "Recorded vote"
         );
+    }
+
+    /// Record a blind ballot commitment from `voter` while commitments are
+    /// open. A voter commits once.
+    pub fn record_commitment(
+        &mut self,
+        voter: AgentId,
+        commitment: String,
+    ) -> Result<(), ConsensusError> {
+        if self.config.ballot_mode != BallotMode::Blind {
+            return Err(ConsensusError::VotingError(format!(
+                "Ballots for task {} are open",
+                self.task_id
+            )));
+        }
+        if self.finalized || self.commitments_closed_at.is_some() {
+            return Err(ConsensusError::VotingError(
+                "Ballot commitments are closed".into(),
+            ));
+        }
+        if let Some(ref senate) = self.senate {
+            if !senate.contains(&voter) {
+                return Err(ConsensusError::VotingError(format!(
+                    "Agent {} is not in the senate",
+                    voter
+                )));
+            }
+        }
+        if self.commitments.contains_key(&voter) {
+            return Err(ConsensusError::VotingError(format!(
+                "Agent {} already committed a ballot",
+                voter
+            )));
+        }
+        self.commitments.insert(voter, commitment);
+        Ok(())
+    }
+
+    /// Close the commitments and start accepting reveals, counting any
+    /// that arrived early.
+    pub fn close_commitments(&mut self, at: chrono::DateTime<chrono::Utc>) {
+        if self.commitments_closed_at.is_some() {
+            return;
+        }
+        self.commitments_closed_at = Some(at);
+        for vote in std::mem::take(&mut self.early_reveals) {
+            let voter = vote.voter.clone();
+            if let Err(e) = self.accept_reveal(vote) {
+                tracing::debug!(task_id = %self.task_id, voter = %voter, error = %e, "Dropped early ballot reveal");
+            }
+        }
+    }
+
+    /// When the commitments were closed, if they have been.
+    pub fn commitments_closed_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.commitments_closed_at
+    }
+
+    /// Record a revealed blind ballot. It must match the voter's commitment
+    /// and pass the checks of `record_vote`. A reveal that arrives before
+    /// the commitments close here, because another node closed them first,
+    /// is held until they do.
+    pub fn record_reveal(&mut self, vote: RankedVote, salt: &str) -> Result<(), ConsensusError> {
+        let expected = self.commitments.get(&vote.voter).ok_or_else(|| {
+            ConsensusError::VotingError(format!("Agent {} committed no ballot", vote.voter))
+        })?;
+        let got = ballot_commitment(&vote, salt);
+        if &got != expected {
+            return Err(ConsensusError::HashMismatch {
+                expected: expected.clone(),
+                got,
+            });
+        }
+        if self.commitments_closed_at.is_none() {
+            self.early_reveals.retain(|v| v.voter != vote.voter);
+            self.early_reveals.push(vote);
+            return Ok(());
+        }
+        self.accept_reveal(vote)
+    }
 
+    fn accept_reveal(&mut self, vote: RankedVote) -> Result<(), ConsensusError> {
+        if self.voter_index.contains_key(&vote.voter) {
+            return Err(ConsensusError::VotingError(format!(
+                "Agent {} already revealed a ballot",
+                vote.voter
+            )));
+        }
+        let rankings = self.validate_vote(&vote)?;
+        self.push_ballot(vote, rankings);
         Ok(())
     }
 
+    pub fn ballot_mode(&self) -> BallotMode {
+        self.config.ballot_mode
+    }
+
+    /// Whether ballots are blind.
+    pub fn is_blind(&self) -> bool {
+        self.config.ballot_mode == BallotMode::Blind
+    }
+
+    /// Number of blind ballot commitments.
+    pub fn commitment_count(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Voters who committed a blind ballot but have not revealed it.
+    pub fn unrevealed_voters(&self) -> Vec<AgentId> {
+        let mut voters: Vec<AgentId> = self
+            .commitments
+            .keys()
+            .filter(|voter| !self.voter_index.contains_key(*voter))
+            .cloned()
+            .collect();
+        voters.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        voters
+    }
+
     /// Execute the Instant Runoff Voting algorithm.
     ///
     /// Returns the winning plan and metadata about the election process.
     pub fn run_irv(&mut self) -> Result<VotingResult, ConsensusError> {
-        if self.ballots.len() < self.config.min_votes {
+        if self.is_blind() && self.commitments_closed_at.is_none() {
+            return Err(ConsensusError::VotingError(
+                "Ballot commitments are still open".into(),
+            ));
+        }
+        let ranked_ballots = self.ballot_count() - self.abstention_count();
+        if ranked_ballots == 0 || ranked_ballots < self.config.min_votes {
             return Err(ConsensusError::NoVotes(self.task_id.clone()));
         }
 
-        let mut active_ballots: Vec<Ballot> = self.ballots.clone();
+        // Each round works on the piles: an elimination moves only the
+        // eliminated plan's ballots to their next choice still running.
+        let mut piles = self.piles.clone();
+        for proposal_id in &self.proposal_ids {
+            piles.entry(proposal_id.clone()).or_default();
+        }
         let mut eliminated: HashSet<String> = HashSet::new();
         let mut elimination_order: Vec<String> = Vec::new();
+        let mut first_round_tallies: HashMap<String, usize> = HashMap::new();
         let mut round = 0;
 
         loop {
             round += 1;
 
             // Count first-choice votes for each active proposal.
-            let mut tallies: HashMap<String, usize> = HashMap::new();
-            for proposal_id in &self.proposal_ids {
-                if !eliminated.contains(proposal_id) {
-                    tallies.insert(proposal_id.clone(), 0);
-                }
-            }
-
-            let mut valid_ballot_count = 0;
-            for ballot in &active_ballots {
-                if let Some(first_choice) = ballot
-                    .remaining_choices
-                    .iter()
-                    .find(|id| !eliminated.contains(*id))
-                {
-                    *tallies.entry(first_choice.clone()).or_insert(0) += 1;
-                    valid_ballot_count += 1;
-                }
-            }
+            let tallies: HashMap<String, usize> = piles
+                .iter()
+                .map(|(id, pile)| (id.clone(), pile.len()))
+                .collect();
+            let valid_ballot_count: usize = tallies.values().sum();
 
             if tallies.is_empty() || valid_ballot_count == 0 {
                 return Err(ConsensusError::VotingError(
@@ -279,6 +692,10 @@ impl VotingEngine {
                 ));
             }
 
+            if round == 1 {
+                first_round_tallies = tallies.clone();
+            }
+
             let majority_threshold = valid_ballot_count / 2 + 1;
 
             tracing::debug!(
@@ -301,6 +718,8 @@ impl VotingEngine {
                         tallies: tallies.clone(),
                         eliminated: None,
                         continuing_candidates: tallies.keys().cloned().collect(),
+                        tie_break: None,
+                        tie_break_seed: None,
                     });
 
                     let winner_critic = self.aggregate_critic_scores(&winner);
@@ -311,21 +730,33 @@ impl VotingEngine {
                         rounds: round,
                         elimination_order,
                         final_tallies: tallies,
-                        total_votes: self.ballots.len(),
+                        total_votes: ranked_ballots,
+                        abstentions: self.abstention_count(),
                         winner_critic_score: winner_critic,
                     });
                 }
             }
 
             // Find the plan with fewest first-choice votes (to eliminate).
-            let (to_eliminate, _) = tallies
+            let min_count = *tallies.values().min().expect("tallies is non-empty");
+            let mut lowest: Vec<String> = tallies
                 .iter()
-                .min_by_key(|(_, &count)| count)
-                .expect("tallies is non-empty");
+                .filter(|(_, &count)| count == min_count)
+                .map(|(id, _)| id.clone())
+                .collect();
+            lowest.sort();
+            let (to_eliminate, tie_break, tie_break_seed) = if lowest.len() == 1 {
+                (lowest.remove(0), None, None)
+            } else {
+                let (id, policy, seed) = self.break_tie(&lowest, &first_round_tallies);
+                (id, Some(policy.to_string()), seed)
+            };
+            let to_eliminate = &to_eliminate;
 
             tracing::debug!(
                 round,
                 eliminated = %to_eliminate,
+                tie_break = ?tie_break,
                 "Eliminating plan with fewest first-choice votes"
             );
 
@@ -340,32 +771,97 @@ impl VotingEngine {
                 tallies: tallies.clone(),
                 eliminated: Some(to_eliminate.clone()),
                 continuing_candidates: continuing,
+                tie_break,
+                tie_break_seed,
             });
 
             eliminated.insert(to_eliminate.clone());
             elimination_order.push(to_eliminate.clone());
 
-            // Remove eliminated choices from all ballots.
-            for ballot in &mut active_ballots {
-                ballot
+            // Redistribute the eliminated plan's ballots; a ballot with no
+            // choice left no longer counts.
+            for index in piles.remove(to_eliminate).unwrap_or_default() {
+                if let Some(next) = self.ballots[index]
                     .remaining_choices
-                    .retain(|id| !eliminated.contains(id));
+                    .iter()
+                    .find(|id| !eliminated.contains(*id))
+                {
+                    piles.entry(next.clone()).or_default().push(index);
+                }
             }
         }
     }
 
+    /// Choose which of the tied plans (sorted by ID) to eliminate.
+    ///
+    /// Returns the plan, the name of the rule that decided, and the random
+    /// seed if one was used.
+    fn break_tie(
+        &self,
+        tied: &[String],
+        first_round_tallies: &HashMap<String, usize>,
+    ) -> (String, &'static str, Option<u64>) {
+        let policy = self.config.tie_break;
+        let decided = match policy {
+            TieBreakPolicy::LowestFirstPreference => unique_min(tied, |id| {
+                first_round_tallies.get(id).copied().unwrap_or(0) as f64
+            }),
+            TieBreakPolicy::CriticScore => unique_min(tied, |id| {
+                self.aggregate_critic_scores(id)
+                    .map(|score| score.aggregate())
+                    .unwrap_or(0.0)
+            }),
+            TieBreakPolicy::Random => {
+                use rand::SeedableRng;
+                let seed = self
+                    .config
+                    .tie_break_seed
+                    .unwrap_or_else(|| tie_break_seed(&self.task_id, self.epoch));
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                let choice = tied.choose(&mut rng).expect("tie has candidates").clone();
+                return (choice, policy.as_str(), Some(seed));
+            }
+            TieBreakPolicy::EscalateToChair => self
+                .chair
+                .as_ref()
+                .and_then(|chair| self.ballots.iter().find(|b| &b.voter == chair))
+                .and_then(|ballot| {
+                    // Higher position = ranked lower; unranked plans rank last.
+                    unique_min(tied, |id| {
+                        let position = ballot
+                            .original_rankings
+                            .iter()
+                            .position(|p| p == id)
+                            .unwrap_or(usize::MAX);
+                        -(position as f64)
+                    })
+                }),
+        };
+        match decided {
+            Some(id) => (id, policy.as_str(), None),
+            None => (tied[0].clone(), PLAN_ID_ORDER, None),
+        }
+    }
+
     /// Get IRV round history (populated after run_irv).
     pub fn irv_rounds(&self) -> &[wws_protocol::IrvRound] {
         &self.irv_rounds
     }
 
     /// Get ballot data as serializable JSON values for API exposure.
+    ///
+    /// Blind ballots stay hidden until the vote is finalized.
     pub fn ballots_as_json(&self) -> Vec<serde_json::Value> {
+        if self.is_blind() && !self.finalized {
+            return Vec::new();
+        }
         self.ballots.iter().map(|b| {
             serde_json::json!({
                 "voter": b.voter.to_string(),
                 "rankings": b.original_rankings,
                 "critic_scores": b.critic_scores,
+                "abstain": b.abstain,
+                "ballot_hash": b.hash,
             })
         }).collect()
     }
@@ -400,11 +896,43 @@ impl VotingEngine {
         })
     }
 
-    /// Get the number of ballots received.
+    /// 1-based position of `voter`'s ballot in the tally and its hash, once
+    /// the ballot has been counted.
+    pub fn ballot_position(&self, voter: &AgentId) -> Option<(usize, &str)> {
+        self.voter_index
+            .get(voter)
+            .map(|&idx| (idx + 1, self.ballots[idx].hash.as_str()))
+    }
+
+    /// Epoch the engine's round belongs to.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// When the engine was created.
+    pub fn started_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.started_at
+    }
+
+    /// Get the number of ballots received, abstentions included.
     pub fn ballot_count(&self) -> usize {
         self.ballots.len()
     }
 
+    /// Get the number of abstaining ballots.
+    pub fn abstention_count(&self) -> usize {
+        self.abstentions
+    }
+
+    /// First-choice votes for each registered plan as the ballots stand:
+    /// the first IRV round, without running it.
+    pub fn first_preferences(&self) -> HashMap<String, usize> {
+        self.proposal_ids
+            .iter()
+            .map(|id| (id.clone(), self.piles.get(id).map_or(0, Vec::len)))
+            .collect()
+    }
+
     /// Get the number of registered proposals.
     pub fn proposal_count(&self) -> usize {
         self.proposal_ids.len()
@@ -415,8 +943,12 @@ impl VotingEngine {
         self.finalized
     }
 
-    /// Debug view of voter IDs that already cast ballots.
+    /// Debug view of voter IDs that already cast ballots, or committed
+    /// one when ballots are blind.
     pub fn voter_ids_for_debug(&self) -> Vec<String> {
+        if self.is_blind() {
+            return self.commitments.keys().map(|v| v.to_string()).collect();
+        }
         self.ballots
             .iter()
             .map(|b| b.voter.to_string())
@@ -424,6 +956,17 @@ impl VotingEngine {
     }
 }
 
+/// The single candidate with the lowest key, or `None` if the lowest key is shared.
+fn unique_min(candidates: &[String], key: impl Fn(&str) -> f64) -> Option<String> {
+    let keyed: Vec<(f64, &String)> = candidates.iter().map(|c| (key(c), c)).collect();
+    let min = keyed.iter().map(|(k, _)| *k).fold(f64::INFINITY, f64::min);
+    let mut at_min = keyed.iter().filter(|(k, _)| *k == min);
+    match (at_min.next(), at_min.next()) {
+        (Some((_, id)), None) => Some((*id).clone()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -441,6 +984,7 @@ mod tests {
             epoch,
             rankings: rankings.into_iter().map(String::from).collect(),
             critic_scores: StdHashMap::new(),
+            abstain: false,
         }
     }
 
@@ -500,6 +1044,48 @@ mod tests {
         assert_eq!(result.elimination_order, vec!["planC".to_string()]);
     }
 
+    #[test]
+    fn test_first_preferences_follow_withdrawals_and_revisions() {
+        let mut engine = VotingEngine::new(
+            VotingConfig {
+                prohibit_self_vote: false,
+                ..Default::default()
+            },
+            "task1".into(),
+            1,
+        );
+        let mut proposals = HashMap::new();
+        for (plan, proposer) in [("planA", "alice"), ("planB", "bob"), ("planC", "carol")] {
+            proposals.insert(plan.to_string(), AgentId::new(proposer.into()));
+        }
+        engine.set_proposals(proposals);
+        engine.record_vote(make_vote("v1", "task1", 1, vec!["planA", "planB"])).unwrap();
+        engine.record_vote(make_vote("v2", "task1", 1, vec!["planC", "planB"])).unwrap();
+        engine.record_vote(make_vote("v3", "task1", 1, vec!["planC"])).unwrap();
+        engine.record_vote(make_vote("v4", "task1", 1, vec!["planB", "planA"])).unwrap();
+        let mut abstain = make_vote("v5", "task1", 1, vec![]);
+        abstain.abstain = true;
+        engine.record_vote(abstain).unwrap();
+        assert_eq!(engine.abstention_count(), 1);
+        assert_eq!(engine.ballot_position(&AgentId::new("v4".into())).unwrap().0, 4);
+
+        let tally = |engine: &VotingEngine, plan: &str| engine.first_preferences()[plan];
+        assert_eq!((tally(&engine, "planA"), tally(&engine, "planB"), tally(&engine, "planC")), (1, 1, 2));
+
+        // v2 moves on to planB; v3 ranked nothing else and drops out.
+        engine.withdraw_proposal("planC").unwrap();
+        assert!(!engine.first_preferences().contains_key("planC"));
+        assert_eq!((tally(&engine, "planA"), tally(&engine, "planB")), (1, 2));
+
+        engine.replace_proposal("planB", "planB2").unwrap();
+        assert_eq!(tally(&engine, "planB2"), 2);
+
+        let result = engine.run_irv().unwrap();
+        assert_eq!(result.winner, "planB2");
+        assert_eq!(result.final_tallies["planB2"], 2);
+        assert_eq!(result.total_votes, 4);
+    }
+
     #[test]
     fn test_self_vote_prohibition() {
         let mut engine = VotingEngine::new(VotingConfig::default(), "task1".into(), 1);
@@ -711,4 +1297,66 @@ mod tests {
             assert_eq!(round.task_id, "specific-task-id");
         }
     }
+
+    #[test]
+    fn test_blind_ballots_count_only_matching_reveals() {
+        let mut engine = VotingEngine::new(
+            VotingConfig {
+                prohibit_self_vote: false,
+                ballot_mode: BallotMode::Blind,
+                ..Default::default()
+            },
+            "task-1".into(),
+            1,
+        );
+        let mut proposals = HashMap::new();
+        proposals.insert("planA".to_string(), AgentId::new("a".into()));
+        proposals.insert("planB".to_string(), AgentId::new("b".into()));
+        engine.set_proposals(proposals);
+
+        let v1 = make_vote("v1", "task-1", 1, vec!["planA", "planB"]);
+        let v2 = make_vote("v2", "task-1", 1, vec!["planB", "planA"]);
+        let v3 = make_vote("v3", "task-1", 1, vec!["planA"]);
+        let v4 = make_vote("v4", "task-1", 1, vec!["planB"]);
+        assert!(engine.record_vote(v1.clone()).is_err());
+        for (vote, salt) in [(&v1, "s1"), (&v2, "s2"), (&v3, "s3"), (&v4, "s4")] {
+            engine
+                .record_commitment(vote.voter.clone(), ballot_commitment(vote, salt))
+                .unwrap();
+        }
+        assert!(engine
+            .record_commitment(v1.voter.clone(), ballot_commitment(&v1, "again"))
+            .is_err());
+
+        // Nothing is counted or shown while commitments are open; an early
+        // reveal is held until they close.
+        engine.record_reveal(v1.clone(), "s1").unwrap();
+        assert!(engine.run_irv().is_err());
+        assert_eq!(engine.commitment_count(), 4);
+        assert_eq!(engine.ballot_count(), 0);
+
+        engine.close_commitments(chrono::Utc::now());
+        assert_eq!(engine.ballot_count(), 1);
+        assert!(engine
+            .record_commitment(AgentId::new("late".into()), "00".into())
+            .is_err());
+        engine.record_reveal(v3, "s3").unwrap();
+        // A changed ballot does not match its commitment.
+        assert!(matches!(
+            engine.record_reveal(make_vote("v2", "task-1", 1, vec!["planA"]), "s2"),
+            Err(ConsensusError::HashMismatch { .. })
+        ));
+        assert!(engine.record_reveal(v1, "s1").is_err());
+        assert!(engine.ballots_as_json().is_empty());
+        assert_eq!(
+            engine.unrevealed_voters(),
+            [AgentId::new("v2".into()), AgentId::new("v4".into())]
+        );
+
+        // Unrevealed ballots are left out of the count.
+        let result = engine.run_irv().unwrap();
+        assert_eq!(result.winner, "planA");
+        assert_eq!(result.total_votes, 2);
+        assert_eq!(engine.ballots_as_json().len(), 2);
+    }
 }
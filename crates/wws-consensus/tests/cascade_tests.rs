@@ -22,6 +22,7 @@ fn cascade_assigns_subtasks_to_agents() {
             description: format!("Subtask {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.5,
+            budget: None,
         });
     }
 
@@ -71,6 +72,13 @@ fn cascade_stop_condition_normal_complexity() {
     );
 }
 
+#[test]
+fn cascade_stop_condition_limits() {
+    assert!(CascadeEngine::should_stop(StopCondition::DepthLimit(3)));
+    assert!(CascadeEngine::should_stop(StopCondition::FanOutLimit(10)));
+    assert!(CascadeEngine::should_stop(StopCondition::NodeLimit(50)));
+}
+
 #[test]
 fn cascade_prime_orchestrator_is_plan_proposer() {
     let plan = Plan::new(
@@ -91,6 +99,7 @@ fn cascade_subtask_count_matches_plan() {
             description: format!("Sub {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.3,
+            budget: None,
         });
     }
     let agents: Vec<AgentId> = (0..10)
@@ -109,6 +118,7 @@ fn cascade_fewer_agents_than_subtasks() {
             description: format!("Sub {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.3,
+            budget: None,
         });
     }
     // Only 5 agents for 10 subtasks
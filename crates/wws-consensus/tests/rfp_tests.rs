@@ -7,10 +7,14 @@
 //! - Phase transition enforcement
 //! - Multiple proposers
 //! - Edge cases: reveal without commit, finalize with no reveals
+//! - Plan revision and withdrawal
 
 use wws_consensus::rfp::{RfpCoordinator, RfpPhase};
 use wws_consensus::ConsensusError;
-use wws_protocol::{AgentId, Plan, PlanSubtask, ProposalCommitParams, ProposalRevealParams, Task};
+use wws_protocol::{
+    AgentId, Plan, PlanSubtask, ProposalCommitParams, ProposalRevealParams,
+    ProposalRevisionParams, ProposalWithdrawParams, Task,
+};
 
 // -----------------------------------------------------------------------
 // Helpers
@@ -28,6 +32,7 @@ fn make_plan(task_id: &str, proposer: &str, epoch: u64) -> Plan {
         description: "Subtask A".to_string(),
         required_capabilities: vec!["python".to_string()],
         estimated_complexity: 0.5,
+        budget: None,
     });
     plan.rationale = "Test plan".to_string();
     plan
@@ -47,6 +52,8 @@ fn commit_plan(
         proposer: AgentId::new(proposer.to_string()),
         epoch,
         plan_hash: hash.clone(),
+        bond: 0,
+        selection: None,
     })
     .unwrap();
     hash
@@ -160,6 +167,8 @@ fn rfp_hash_mismatch_rejected() {
         proposer: AgentId::new("alice".into()),
         epoch: 1,
         plan_hash: "fake_hash_that_will_not_match".into(),
+        bond: 0,
+        selection: None,
     })
     .unwrap();
 
@@ -192,6 +201,8 @@ fn rfp_correct_hash_accepted() {
         proposer: AgentId::new("alice".into()),
         epoch: 1,
         plan_hash: hash,
+        bond: 0,
+        selection: None,
     })
     .unwrap();
 
@@ -222,6 +233,8 @@ fn rfp_duplicate_commit_rejected() {
         proposer: AgentId::new("alice".into()),
         epoch: 1,
         plan_hash: hash.clone(),
+        bond: 0,
+        selection: None,
     })
     .unwrap();
 
@@ -231,6 +244,8 @@ fn rfp_duplicate_commit_rejected() {
         proposer: AgentId::new("alice".into()),
         epoch: 1,
         plan_hash: hash,
+        bond: 0,
+        selection: None,
     });
     assert!(
         matches!(result, Err(ConsensusError::DuplicateCommit(_, _))),
@@ -253,6 +268,8 @@ fn rfp_cannot_commit_before_inject() {
         proposer: AgentId::new("alice".into()),
         epoch: 1,
         plan_hash: "somehash".into(),
+        bond: 0,
+        selection: None,
     });
     assert!(result.is_err(), "Cannot commit before injecting a task");
 }
@@ -287,6 +304,8 @@ fn rfp_cannot_reveal_before_commit_phase_ends() {
         proposer: AgentId::new("alice".into()),
         epoch: 1,
         plan_hash: hash,
+        bond: 0,
+        selection: None,
     })
     .unwrap();
 
@@ -318,6 +337,8 @@ fn rfp_manual_transition_to_reveal() {
         proposer: AgentId::new("alice".into()),
         epoch: 1,
         plan_hash: hash,
+        bond: 0,
+        selection: None,
     })
     .unwrap();
 
@@ -430,6 +451,8 @@ fn rfp_epoch_mismatch_on_commit_rejected() {
         proposer: AgentId::new("alice".into()),
         epoch: 99, // wrong epoch
         plan_hash: "hash".into(),
+        bond: 0,
+        selection: None,
     });
     assert!(
         matches!(result, Err(ConsensusError::EpochMismatch { .. })),
@@ -466,6 +489,130 @@ fn different_plans_produce_different_hashes() {
     );
 }
 
+// ═══════════════════════════════════════════════════════════════
+// Plan Revision After Critique
+// ═══════════════════════════════════════════════════════════════
+
+/// Drive a single-proposer RFP into the critique phase.
+fn rfp_in_critique(task: &Task, plan: &Plan) -> RfpCoordinator {
+    let mut rfp = RfpCoordinator::new(task.task_id.clone(), 1, 1);
+    rfp.inject_task(task).unwrap();
+    commit_plan(&mut rfp, &task.task_id, "alice", 1, plan);
+    rfp.record_reveal(&ProposalRevealParams {
+        task_id: task.task_id.clone(),
+        plan: plan.clone(),
+    })
+    .unwrap();
+    rfp.transition_to_critique().unwrap();
+    rfp
+}
+
+#[test]
+fn rfp_revision_replaces_plan_once() {
+    let task = Task::new("Revise me".into(), 1, 1);
+    let original = make_plan(&task.task_id, "alice", 1);
+    let mut rfp = rfp_in_critique(&task, &original);
+
+    let mut revised = make_plan(&task.task_id, "alice", 1);
+    revised.rationale = "Split research from writing".to_string();
+    let params = ProposalRevisionParams {
+        task_id: task.task_id.clone(),
+        original_plan_id: original.plan_id.clone(),
+        plan: revised.clone(),
+        reason: "Critics flagged missing review step".to_string(),
+    };
+    let previous = rfp.record_revision(&params).unwrap();
+    assert_eq!(previous.plan.plan_id, original.plan_id);
+    assert_eq!(*rfp.phase(), RfpPhase::RevisionPhase);
+    assert_eq!(rfp.superseded.len(), 1);
+    assert!(rfp.has_revised(&AgentId::new("alice".into())));
+
+    // A second revision from the same proposer is refused.
+    let again = ProposalRevisionParams {
+        task_id: task.task_id.clone(),
+        original_plan_id: revised.plan_id.clone(),
+        plan: make_plan(&task.task_id, "alice", 1),
+        reason: String::new(),
+    };
+    assert!(matches!(
+        rfp.record_revision(&again),
+        Err(ConsensusError::DuplicateRevision(..))
+    ));
+
+    let proposals = rfp.finalize().unwrap();
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].plan.plan_id, revised.plan_id);
+}
+
+#[test]
+fn rfp_revision_before_critique_rejected() {
+    let task = Task::new("Too early".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 1);
+    rfp.inject_task(&task).unwrap();
+    let plan = make_plan(&task_id, "alice", 1);
+    commit_plan(&mut rfp, &task_id, "alice", 1, &plan);
+    rfp.record_reveal(&ProposalRevealParams {
+        task_id: task_id.clone(),
+        plan: plan.clone(),
+    })
+    .unwrap();
+
+    let result = rfp.record_revision(&ProposalRevisionParams {
+        task_id: task_id.clone(),
+        original_plan_id: plan.plan_id.clone(),
+        plan: make_plan(&task_id, "alice", 1),
+        reason: String::new(),
+    });
+    assert!(matches!(result, Err(ConsensusError::RfpFailed(_))));
+}
+
+// ═══════════════════════════════════════════════════════════════
+// Plan Withdrawal
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn rfp_withdrawal_removes_plan_for_good() {
+    let task = Task::new("Withdraw me".into(), 1, 1);
+    let plan = make_plan(&task.task_id, "alice", 1);
+    let mut rfp = rfp_in_critique(&task, &plan);
+    let withdraw = |plan_id: &str| ProposalWithdrawParams {
+        task_id: task.task_id.clone(),
+        plan_id: plan_id.to_string(),
+        proposer: AgentId::new("alice".into()),
+        reason: "Missed a dependency".to_string(),
+    };
+
+    assert!(matches!(
+        rfp.record_withdrawal(&withdraw("other-plan")),
+        Err(ConsensusError::RfpFailed(_))
+    ));
+    let withdrawn = rfp.record_withdrawal(&withdraw(&plan.plan_id)).unwrap();
+    assert_eq!(withdrawn.plan.plan_id, plan.plan_id);
+    assert_eq!(rfp.reveal_count(), 0);
+    assert!(rfp.has_withdrawn(&AgentId::new("alice".into())));
+    assert!(rfp.record_withdrawal(&withdraw(&plan.plan_id)).is_err());
+    rfp.transition_to_voting().unwrap();
+    assert!(matches!(rfp.finalize(), Err(ConsensusError::NoProposals(_))));
+}
+
+#[test]
+fn rfp_withdrawal_during_commit_phase_rejected() {
+    let task = Task::new("Not yet".into(), 1, 1);
+    let mut rfp = RfpCoordinator::new(task.task_id.clone(), 1, 2);
+    rfp.inject_task(&task).unwrap();
+    let plan = make_plan(&task.task_id, "alice", 1);
+    commit_plan(&mut rfp, &task.task_id, "alice", 1, &plan);
+
+    let result = rfp.record_withdrawal(&ProposalWithdrawParams {
+        task_id: task.task_id.clone(),
+        plan_id: plan.plan_id.clone(),
+        proposer: AgentId::new("alice".into()),
+        reason: String::new(),
+    });
+    assert!(matches!(result, Err(ConsensusError::RfpFailed(_))));
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Metadata Accessors
 // ═══════════════════════════════════════════════════════════════
@@ -9,7 +9,7 @@
 
 use std::collections::HashMap;
 
-use wws_consensus::voting::{VotingConfig, VotingEngine};
+use wws_consensus::voting::{tie_break_seed, TieBreakPolicy, VotingConfig, VotingEngine};
 use wws_consensus::ConsensusError;
 use wws_protocol::{AgentId, CriticScore, RankedVote};
 
@@ -25,6 +25,7 @@ fn vote(voter: &str, task_id: &str, epoch: u64, rankings: &[&str]) -> RankedVote
         epoch,
         rankings: rankings.iter().map(|s| s.to_string()).collect(),
         critic_scores: HashMap::new(),
+        abstain: false,
     }
 }
 
@@ -46,6 +47,7 @@ fn vote_with_scores(
         epoch,
         rankings: rankings.iter().map(|s| s.to_string()).collect(),
         critic_scores,
+        abstain: false,
     }
 }
 
@@ -258,6 +260,77 @@ fn irv_no_ballots_returns_error() {
     assert!(matches!(result.unwrap_err(), ConsensusError::NoVotes(_)));
 }
 
+// ═══════════════════════════════════════════════════════════════
+// Partial Rankings and Abstention
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn partial_rankings_exhaust_without_blocking_the_majority() {
+    let mut engine = engine_with_proposals(
+        "t1",
+        1,
+        &[("planA", "alice"), ("planB", "bob"), ("planC", "carol")],
+        false,
+    );
+    engine.record_vote(vote("v1", "t1", 1, &["planA"])).unwrap();
+    engine.record_vote(vote("v2", "t1", 1, &["planA"])).unwrap();
+    engine.record_vote(vote("v3", "t1", 1, &["planB", "planA"])).unwrap();
+    engine.record_vote(vote("v4", "t1", 1, &["planB"])).unwrap();
+    engine.record_vote(vote("v5", "t1", 1, &["planC"])).unwrap();
+
+    // planC goes first; its only ballot ranks nothing else and drops out,
+    // so 2 of the 4 remaining ballots are not a majority for planA.
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.elimination_order[0], "planC");
+    assert_eq!(result.total_votes, 5);
+}
+
+#[test]
+fn abstentions_count_for_participation_but_not_preference() {
+    let mut engine = engine_with_proposals("t1", 1, &[("planA", "alice"), ("planB", "bob")], false);
+    let abstain = |voter: &str| RankedVote {
+        abstain: true,
+        ..vote(voter, "t1", 1, &[])
+    };
+    let ranking_abstention = RankedVote {
+        abstain: true,
+        ..vote("v9", "t1", 1, &["planA"])
+    };
+    assert!(engine.record_vote(ranking_abstention).is_err());
+
+    engine.record_vote(abstain("v1")).unwrap();
+    // Abstentions alone elect nothing.
+    assert!(engine.run_irv().is_err());
+
+    engine.record_vote(abstain("v2")).unwrap();
+    engine.record_vote(vote("v3", "t1", 1, &["planB"])).unwrap();
+    assert_eq!(engine.ballot_count(), 3);
+    assert_eq!(engine.abstention_count(), 2);
+
+    // One ranked ballot out of three is still a majority of preferences.
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planB");
+    assert_eq!(result.rounds, 1);
+    assert_eq!(result.total_votes, 1);
+    assert_eq!(result.abstentions, 2);
+    assert_eq!(engine.irv_rounds()[0].tallies["planB"], 1);
+}
+
+#[test]
+fn counted_ballots_have_a_position_and_hash() {
+    let mut engine = engine_with_proposals("t1", 1, &[("planA", "alice"), ("planB", "bob")], false);
+    let first = vote("v1", "t1", 1, &["planA", "planB"]);
+    let second = vote("v2", "t1", 1, &["planB"]);
+    engine.record_vote(first.clone()).unwrap();
+    engine.record_vote(second.clone()).unwrap();
+
+    let (position, hash) = engine.ballot_position(&AgentId::new("v2".to_string())).unwrap();
+    assert_eq!(position, 2);
+    assert_eq!(hash, wws_consensus::voting::ballot_hash(&second));
+    assert_ne!(hash, wws_consensus::voting::ballot_hash(&first));
+    assert!(engine.ballot_position(&AgentId::new("v3".to_string())).is_none());
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Section 6.4  Self-Vote Prohibition
 // ═══════════════════════════════════════════════════════════════
@@ -358,6 +431,147 @@ fn winner_has_aggregated_critic_scores() {
     );
 }
 
+// ═══════════════════════════════════════════════════════════════
+// Tie-Break Policies
+// ═══════════════════════════════════════════════════════════════
+
+fn engine_with_tie_break(policy: TieBreakPolicy, proposals: &[&str]) -> VotingEngine {
+    let config = VotingConfig {
+        prohibit_self_vote: false,
+        tie_break: policy,
+        tie_break_seed: Some(7),
+        ..Default::default()
+    };
+    let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
+    engine.set_proposals(
+        proposals
+            .iter()
+            .map(|p| (p.to_string(), AgentId::new(format!("owner-{}", p))))
+            .collect(),
+    );
+    engine
+}
+
+#[test]
+fn tie_break_lowest_first_preference() {
+    // Round 1: A=4 B=3 C=2 D=1 -> D out, its vote moves to C.
+    // Round 2: A=4 B=3 C=3 -> B and C tie; C had fewer first preferences.
+    let mut engine = engine_with_tie_break(
+        TieBreakPolicy::LowestFirstPreference,
+        &["planA", "planB", "planC", "planD"],
+    );
+    let ballots: &[&[&str]] = &[
+        &["planA"], &["planA"], &["planA"], &["planA"],
+        &["planB"], &["planB"], &["planB"],
+        &["planC", "planB"], &["planC", "planB"],
+        &["planD", "planC", "planB"],
+    ];
+    for (i, rankings) in ballots.iter().enumerate() {
+        engine.record_vote(vote(&format!("v{}", i), "t1", 1, rankings)).unwrap();
+    }
+
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planB");
+    let rounds = engine.irv_rounds();
+    assert_eq!(rounds[0].tie_break, None);
+    assert_eq!(rounds[1].eliminated.as_deref(), Some("planC"));
+    assert_eq!(rounds[1].tie_break.as_deref(), Some("lowest_first_preference"));
+}
+
+#[test]
+fn tie_break_critic_score() {
+    let weak = CriticScore { feasibility: 0.2, parallelism: 0.2, completeness: 0.2, risk: 0.9 };
+    let strong = CriticScore { feasibility: 0.9, parallelism: 0.9, completeness: 0.9, risk: 0.1 };
+    let mut engine = engine_with_tie_break(TieBreakPolicy::CriticScore, &["planA", "planB"]);
+    engine
+        .record_vote(vote_with_scores(
+            "v1", "t1", 1, &["planA", "planB"],
+            &[("planA", weak.clone()), ("planB", strong.clone())],
+        ))
+        .unwrap();
+    engine
+        .record_vote(vote_with_scores(
+            "v2", "t1", 1, &["planB", "planA"],
+            &[("planA", weak), ("planB", strong)],
+        ))
+        .unwrap();
+
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planB");
+    assert_eq!(engine.irv_rounds()[0].tie_break.as_deref(), Some("critic_score"));
+}
+
+#[test]
+fn tie_break_random_records_seed_and_is_reproducible() {
+    let run = || {
+        let mut engine =
+            engine_with_tie_break(TieBreakPolicy::Random, &["planA", "planB", "planC"]);
+        engine.record_vote(vote("v1", "t1", 1, &["planA"])).unwrap();
+        engine.record_vote(vote("v2", "t1", 1, &["planB"])).unwrap();
+        engine.record_vote(vote("v3", "t1", 1, &["planC"])).unwrap();
+        let _ = engine.run_irv();
+        engine.irv_rounds()[0].clone()
+    };
+    let first = run();
+    assert_eq!(first.tie_break.as_deref(), Some("random"));
+    assert_eq!(first.tie_break_seed, Some(7));
+    assert_eq!(run().eliminated, first.eliminated);
+}
+
+#[test]
+fn tie_break_random_without_seed_agrees_across_engines() {
+    let run = || {
+        let config = VotingConfig {
+            prohibit_self_vote: false,
+            tie_break: TieBreakPolicy::Random,
+            ..Default::default()
+        };
+        let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
+        engine.set_proposals(
+            ["planA", "planB", "planC", "planD"]
+                .iter()
+                .map(|p| (p.to_string(), AgentId::new(format!("owner-{}", p))))
+                .collect(),
+        );
+        for (voter, plan) in [("v1", "planA"), ("v2", "planB"), ("v3", "planC"), ("v4", "planD")] {
+            engine.record_vote(vote(voter, "t1", 1, &[plan])).unwrap();
+        }
+        let _ = engine.run_irv();
+        engine.irv_rounds()[0].clone()
+    };
+    let first = run();
+    assert_eq!(first.tie_break_seed, Some(tie_break_seed("t1", 1)));
+    for _ in 0..8 {
+        assert_eq!(run().eliminated, first.eliminated);
+    }
+}
+
+#[test]
+fn tie_break_escalates_to_chair() {
+    let mut engine = engine_with_tie_break(TieBreakPolicy::EscalateToChair, &["planA", "planB"]);
+    engine.set_chair(AgentId::new("v2".into()));
+    engine.record_vote(vote("v1", "t1", 1, &["planA", "planB"])).unwrap();
+    engine.record_vote(vote("v2", "t1", 1, &["planB", "planA"])).unwrap();
+
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planB");
+    assert_eq!(engine.irv_rounds()[0].tie_break.as_deref(), Some("escalate_to_chair"));
+}
+
+#[test]
+fn tie_break_falls_back_to_plan_id_order() {
+    // Chair did not vote, so the policy cannot decide.
+    let mut engine = engine_with_tie_break(TieBreakPolicy::EscalateToChair, &["planA", "planB"]);
+    engine.set_chair(AgentId::new("absent".into()));
+    engine.record_vote(vote("v1", "t1", 1, &["planA", "planB"])).unwrap();
+    engine.record_vote(vote("v2", "t1", 1, &["planB", "planA"])).unwrap();
+
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planB");
+    assert_eq!(engine.irv_rounds()[0].eliminated.as_deref(), Some("planA"));
+    assert_eq!(engine.irv_rounds()[0].tie_break.as_deref(), Some("plan_id_order"));
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Error Handling
 // ═══════════════════════════════════════════════════════════════
@@ -444,6 +658,7 @@ fn senate_restricts_eligible_voters() {
         prohibit_self_vote: false,
         min_votes: 1,
         senate_seed: Some(42),
+        ..Default::default()
     };
     let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
     let mut proposals = HashMap::new();
@@ -495,3 +710,55 @@ fn proposal_and_ballot_counts() {
         .unwrap();
     assert_eq!(engine.ballot_count(), 1);
 }
+
+#[test]
+fn replaced_proposal_inherits_ballots() {
+    let mut engine = engine_with_proposals(
+        "t1",
+        1,
+        &[("planA", "alice"), ("planB", "bob")],
+        false,
+    );
+    engine
+        .record_vote(vote("v1", "t1", 1, &["planA", "planB"]))
+        .unwrap();
+
+    engine.replace_proposal("planA", "planA2").unwrap();
+    assert_eq!(engine.proposal_count(), 2);
+    assert!(engine.replace_proposal("planA", "planA3").is_err());
+
+    engine
+        .record_vote(vote("v2", "t1", 1, &["planA2", "planB"]))
+        .unwrap();
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planA2");
+}
+
+#[test]
+fn withdrawn_proposal_leaves_the_running() {
+    let mut engine = engine_with_proposals(
+        "t1",
+        1,
+        &[("planA", "alice"), ("planB", "bob"), ("planC", "carol")],
+        false,
+    );
+    engine.record_vote(vote("v1", "t1", 1, &["planA", "planC"])).unwrap();
+    engine.record_vote(vote("v2", "t1", 1, &["planA"])).unwrap();
+    engine.record_vote(vote("v3", "t1", 1, &["planB"])).unwrap();
+
+    engine.withdraw_proposal("planA").unwrap();
+    assert_eq!(engine.proposal_count(), 2);
+    assert!(engine.withdraw_proposal("planA").is_err());
+    // It cannot come back as a candidate or be voted for.
+    let mut again = HashMap::new();
+    again.insert("planA".to_string(), AgentId::new("alice".into()));
+    engine.set_proposals(again);
+    assert_eq!(engine.proposal_count(), 2);
+    assert!(engine.record_vote(vote("v4", "t1", 1, &["planA"])).is_err());
+
+    // v1 moves to planC; v2 ranked nothing else and is exhausted.
+    let result = engine.run_irv().unwrap();
+    assert!(!engine.irv_rounds()[0].tallies.contains_key("planA"));
+    assert_eq!(engine.irv_rounds()[0].tallies["planC"], 1);
+    assert!(["planB", "planC"].contains(&result.winner.as_str()));
+}
@@ -48,6 +48,8 @@ pub struct Candidate {
     pub agent_id: AgentId,
     pub score: NodeScore,
     pub composite: f64,
+    /// Capabilities from the candidacy announcement.
+    pub capabilities: Vec<String>,
 }
 
 /// Result of an election round.
@@ -156,6 +158,7 @@ impl ElectionManager {
                 agent_id: params.agent_id.clone(),
                 score: params.score.clone(),
                 composite,
+                capabilities: params.capabilities.clone(),
             },
         );
 
@@ -289,6 +292,22 @@ impl ElectionManager {
         self.finalized
     }
 
+    /// Whether a node with `score` meets the candidacy requirements.
+    pub fn is_eligible(&self, score: &NodeScore) -> bool {
+        score.composite_score() >= self.config.min_candidacy_score
+            && score.uptime >= self.config.min_uptime
+    }
+
+    /// Registered candidates, in no particular order.
+    pub fn candidates(&self) -> impl Iterator<Item = &Candidate> {
+        self.candidates.values()
+    }
+
+    /// Whether `voter` has voted in this election.
+    pub fn has_voted(&self, voter: &AgentId) -> bool {
+        self.votes.contains_key(voter)
+    }
+
     /// Get the number of registered candidates.
     pub fn candidate_count(&self) -> usize {
         self.candidates.len()
@@ -331,6 +350,7 @@ mod tests {
             epoch,
             score: make_score(agent, reputation, uptime),
             location_vector: VivaldiCoordinates::origin(),
+            capabilities: Vec::new(),
         }
     }
 
@@ -8,19 +8,40 @@
 //! 4. Stale state is garbage-collected
 //!
 //! Epochs are numbered monotonically. The first epoch starts at 1.
+//!
+//! With adaptive sizing enabled, each new epoch's length follows how
+//! volatile the previous one was: any Tier-1 leader failure or a churn rate
+//! (members joining or leaving, as a fraction of the swarm) at or above
+//! `HIGH_CHURN_RATE` halves it, a churn rate at or below `LOW_CHURN_RATE`
+//! grows it by half, and anything between keeps it. The result stays within
+//! the configured bounds. Because churn is counted per epoch, a shorter
+//! epoch sees less of it, so the length settles where churn per epoch sits
+//! between the two thresholds.
 
 use chrono::{DateTime, Utc};
 
 use wws_protocol::{AgentId, DEFAULT_EPOCH_DURATION_SECS};
 
+/// Churn rate at or above which an epoch counts as volatile.
+pub const HIGH_CHURN_RATE: f64 = 0.2;
+/// Churn rate at or below which an epoch counts as stable.
+pub const LOW_CHURN_RATE: f64 = 0.05;
+
 /// Configuration for epoch management.
 #[derive(Debug, Clone)]
 pub struct EpochConfig {
-    /// Duration of each epoch in seconds.
+    /// Duration of each epoch in seconds; the first epoch's duration when
+    /// adaptive sizing is on.
     pub duration_secs: u64,
     /// Grace period after epoch boundary before triggering re-election.
     /// Allows late keep-alives to arrive.
     pub grace_period_secs: u64,
+    /// Size each epoch from the volatility of the previous one.
+    pub adaptive: bool,
+    /// Shortest epoch adaptive sizing may choose, in seconds.
+    pub min_duration_secs: u64,
+    /// Longest epoch adaptive sizing may choose, in seconds.
+    pub max_duration_secs: u64,
 }
 
 impl Default for EpochConfig {
@@ -28,6 +49,9 @@ impl Default for EpochConfig {
         Self {
             duration_secs: DEFAULT_EPOCH_DURATION_SECS,
             grace_period_secs: 10,
+            adaptive: false,
+            min_duration_secs: DEFAULT_EPOCH_DURATION_SECS / 12,
+            max_duration_secs: DEFAULT_EPOCH_DURATION_SECS * 4,
         }
     }
 }
@@ -45,6 +69,12 @@ pub struct EpochInfo {
     pub tier1_leaders: Vec<AgentId>,
     /// Estimated swarm size at the start of this epoch.
     pub estimated_swarm_size: u64,
+    /// Length chosen for this epoch, in seconds.
+    pub duration_secs: u64,
+    /// Members that joined or left during this epoch.
+    pub membership_changes: u64,
+    /// Distinct Tier-1 leaders reported failed during this epoch.
+    pub leader_failures: u32,
 }
 
 /// Callback actions that should be taken when an epoch transition occurs.
@@ -73,19 +103,30 @@ pub struct EpochManager {
     history: Vec<EpochInfo>,
     /// Maximum history entries to retain.
     max_history: usize,
+    /// Leaders reported failed in the current epoch.
+    failed_leaders: std::collections::HashSet<AgentId>,
 }
 
 impl EpochManager {
     /// Create a new epoch manager, starting at epoch 1.
     pub fn new(config: EpochConfig) -> Self {
         let now = Utc::now();
-        let duration = chrono::Duration::seconds(config.duration_secs as i64);
+        let duration_secs = if config.adaptive {
+            config
+                .duration_secs
+                .clamp(config.min_duration_secs, config.max_duration_secs.max(config.min_duration_secs))
+        } else {
+            config.duration_secs
+        };
         let current = EpochInfo {
             epoch_number: 1,
             started_at: now,
-            ends_at: now + duration,
+            ends_at: now + chrono::Duration::seconds(duration_secs as i64),
             tier1_leaders: Vec::new(),
             estimated_swarm_size: 1,
+            duration_secs,
+            membership_changes: 0,
+            leader_failures: 0,
         };
 
         Self {
@@ -94,9 +135,49 @@ impl EpochManager {
             transition_in_progress: false,
             history: Vec::new(),
             max_history: 100,
+            failed_leaders: std::collections::HashSet::new(),
+        }
+    }
+
+    /// Count a member joining or leaving the swarm in the current epoch.
+    pub fn record_membership_change(&mut self) {
+        self.current.membership_changes += 1;
+    }
+
+    /// Count a failed Tier-1 leader in the current epoch. Repeated reports
+    /// of the same leader count once.
+    pub fn record_leader_failure(&mut self, leader: &AgentId) {
+        if self.failed_leaders.insert(leader.clone()) {
+            self.current.leader_failures += 1;
         }
     }
 
+    /// Members that joined or left during the current epoch, as a fraction
+    /// of the larger of its starting swarm size and `swarm_size`.
+    pub fn churn_rate(&self, swarm_size: u64) -> f64 {
+        let size = swarm_size.max(self.current.estimated_swarm_size).max(1);
+        self.current.membership_changes as f64 / size as f64
+    }
+
+    /// Length of the epoch that would follow the current one if it ended
+    /// now with `swarm_size` members.
+    pub fn next_duration_secs(&self, swarm_size: u64) -> u64 {
+        if !self.config.adaptive {
+            return self.config.duration_secs;
+        }
+        let current = self.current.duration_secs;
+        let churn = self.churn_rate(swarm_size);
+        let next = if self.current.leader_failures > 0 || churn >= HIGH_CHURN_RATE {
+            current / 2
+        } else if churn <= LOW_CHURN_RATE {
+            current.saturating_add(current / 2)
+        } else {
+            current
+        };
+        let min = self.config.min_duration_secs;
+        next.clamp(min, self.config.max_duration_secs.max(min))
+    }
+
     /// Check if the current epoch has expired and return any actions needed.
     ///
     /// Should be called periodically (e.g., every second or on each event loop tick).
@@ -143,15 +224,18 @@ impl EpochManager {
         tier1_leaders: Vec<AgentId>,
         estimated_swarm_size: u64,
     ) {
+        let duration_secs = self.next_duration_secs(estimated_swarm_size);
+
         // Archive the current epoch.
         let old = self.current.clone();
         self.history.push(old);
         if self.history.len() > self.max_history {
             self.history.remove(0);
         }
+        self.failed_leaders.clear();
 
         let now = Utc::now();
-        let duration = chrono::Duration::seconds(self.config.duration_secs as i64);
+        let duration = chrono::Duration::seconds(duration_secs as i64);
         let new_epoch_number = self.current.epoch_number + 1;
 
         self.current = EpochInfo {
@@ -160,6 +244,9 @@ impl EpochManager {
             ends_at: now + duration,
             tier1_leaders,
             estimated_swarm_size,
+            duration_secs,
+            membership_changes: 0,
+            leader_failures: 0,
         };
 
         self.transition_in_progress = false;
@@ -168,6 +255,7 @@ impl EpochManager {
             epoch = new_epoch_number,
             leaders = self.current.tier1_leaders.len(),
             swarm_size = estimated_swarm_size,
+            duration_secs,
             "Advanced to new epoch"
         );
     }
@@ -180,15 +268,19 @@ impl EpochManager {
         estimated_swarm_size: u64,
     ) {
         let now = Utc::now();
-        let duration = chrono::Duration::seconds(self.config.duration_secs as i64);
+        let duration_secs = self.current.duration_secs;
 
         self.current = EpochInfo {
             epoch_number,
             started_at: now,
-            ends_at: now + duration,
+            ends_at: now + chrono::Duration::seconds(duration_secs as i64),
             tier1_leaders,
             estimated_swarm_size,
+            duration_secs,
+            membership_changes: 0,
+            leader_failures: 0,
         };
+        self.failed_leaders.clear();
         self.transition_in_progress = false;
     }
 
@@ -222,9 +314,14 @@ impl EpochManager {
         self.transition_in_progress
     }
 
-    /// Get the epoch duration configuration.
+    /// Get the length of the current epoch in seconds.
     pub fn epoch_duration_secs(&self) -> u64 {
-        self.config.duration_secs
+        self.current.duration_secs
+    }
+
+    /// Past epochs, oldest first.
+    pub fn history(&self) -> &[EpochInfo] {
+        &self.history
     }
 
     /// Look up historical epoch info by epoch number.
@@ -242,7 +339,7 @@ impl EpochManager {
         wws_protocol::Epoch {
             epoch_number: self.current.epoch_number,
             started_at: self.current.started_at,
-            duration_secs: self.config.duration_secs,
+            duration_secs: self.current.duration_secs,
             tier1_leaders: self.current.tier1_leaders.clone(),
             estimated_swarm_size: self.current.estimated_swarm_size,
         }
@@ -279,6 +376,62 @@ mod tests {
         assert_eq!(em.current_leaders().len(), 2);
     }
 
+    fn adaptive(duration_secs: u64) -> EpochManager {
+        EpochManager::new(EpochConfig {
+            duration_secs,
+            adaptive: true,
+            min_duration_secs: 600,
+            max_duration_secs: 7200,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn stable_epochs_grow_up_to_the_maximum() {
+        let mut em = adaptive(3600);
+        em.advance_epoch(Vec::new(), 100);
+        assert_eq!(em.epoch_duration_secs(), 5400);
+        em.advance_epoch(Vec::new(), 100);
+        assert_eq!(em.epoch_duration_secs(), 7200);
+        assert_eq!(em.history()[1].duration_secs, 5400);
+    }
+
+    #[test]
+    fn churn_and_leader_failures_shorten_epochs() {
+        let mut em = adaptive(3600);
+        em.advance_epoch(Vec::new(), 100);
+        for _ in 0..10 {
+            em.record_membership_change();
+        }
+        // 10 of 100 members: neither stable nor volatile.
+        assert_eq!(em.next_duration_secs(100), 5400);
+        for _ in 0..10 {
+            em.record_membership_change();
+        }
+        assert_eq!(em.next_duration_secs(100), 2700);
+
+        em.advance_epoch(Vec::new(), 100);
+        assert_eq!(em.history().last().unwrap().membership_changes, 20);
+        let leader = AgentId::new("leader1".into());
+        em.record_leader_failure(&leader);
+        em.record_leader_failure(&leader);
+        assert_eq!(em.current_info().leader_failures, 1);
+        em.advance_epoch(Vec::new(), 100);
+        assert_eq!(em.epoch_duration_secs(), 1350);
+        em.advance_epoch(Vec::new(), 100);
+        assert_eq!(em.epoch_duration_secs(), 2025);
+    }
+
+    #[test]
+    fn fixed_epochs_ignore_volatility() {
+        let mut em = EpochManager::default();
+        em.record_leader_failure(&AgentId::new("leader1".into()));
+        em.advance_epoch(Vec::new(), 10);
+        assert_eq!(em.epoch_duration_secs(), DEFAULT_EPOCH_DURATION_SECS);
+        let shortest = adaptive(60);
+        assert_eq!(shortest.epoch_duration_secs(), 600);
+    }
+
     #[test]
     fn test_protocol_epoch_conversion() {
         let em = EpochManager::default();
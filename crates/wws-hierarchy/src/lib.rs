@@ -4,6 +4,7 @@
 //! - Dynamic pyramid allocation based on swarm size N and branching factor k
 //! - Weighted reputation-based elections for Tier-1 leaders
 //! - Geo-clustering via Vivaldi coordinates for latency-optimal assignment
+//! - Rendezvous-hashed tier and parent assignment that stays stable under churn
 //! - Leader failover with 30-second succession timeout
 //! - Epoch management for periodic re-elections
 
@@ -11,6 +12,7 @@ pub mod elections;
 pub mod epoch;
 pub mod geo_cluster;
 pub mod pyramid;
+pub mod rendezvous;
 pub mod succession;
 
 pub use elections::ElectionManager;
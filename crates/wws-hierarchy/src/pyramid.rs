@@ -255,6 +255,34 @@ impl PyramidAllocator {
 
         TierDistribution { tiers }
     }
+
+    /// Distribute N agents like [`distribute`](Self::distribute), when
+    /// `pinned` of them must sit in Tier-1 and `exempt` of them may only
+    /// execute.
+    ///
+    /// Tier-1 grows to hold every pinned agent, and the coordinating tiers
+    /// shrink, lowest first, until the agents that may coordinate fill
+    /// them. The sum of all tiers still equals `n`.
+    pub fn distribute_with_roles(n: u64, k: u64, pinned: u64, exempt: u64) -> TierDistribution {
+        let mut distribution = Self::distribute(n, k);
+        let tiers = &mut distribution.tiers;
+        if tiers.len() < 2 {
+            return distribution;
+        }
+        let bottom = tiers.len() - 1;
+        tiers[0] = tiers[0].max(pinned);
+        let mut excess = tiers[..bottom]
+            .iter()
+            .sum::<u64>()
+            .saturating_sub(n.saturating_sub(exempt));
+        for count in tiers[..bottom].iter_mut().rev() {
+            let cut = excess.min(*count);
+            *count -= cut;
+            excess -= cut;
+        }
+        tiers[bottom] = n.saturating_sub(tiers[..bottom].iter().sum::<u64>());
+        distribution
+    }
 }
 
 impl Default for PyramidAllocator {
@@ -0,0 +1,206 @@
+//! Stable tier and parent assignment by rendezvous hashing.
+//!
+//! Cutting the ID-sorted member list into tiers and handing out children
+//! by index moves nearly every agent to a new parent when one agent joins
+//! or leaves, since every later index shifts. Here an agent's tier comes
+//! from its rank by a hash of its ID, and each child goes to the parent with
+//! the highest rendezvous weight for the pair, so a child's parent depends
+//! only on its own ID and the members of the tier above.
+//!
+//! While the coordinating tiers keep their sizes, a single join or leave
+//! therefore changes the tier of at most one agent per tier boundary, and
+//! the parent only of children whose parent left the tier above or who
+//! rank a parent that entered it highest, about one in `p` of them for `p`
+//! parents. Nobody else moves. Parents are not capped, since a full parent
+//! would push children on to their next choice and a join would cascade
+//! through them; rendezvous weights spread children evenly in expectation
+//! instead. When the pyramid gains or loses a level the tier sizes change
+//! and the layout moves with them.
+//!
+//! Operators can constrain the layout per agent with a [`LayoutRole`]:
+//! pinned coordinators always sit in Tier-1 and exempt agents always
+//! execute. Agents without a role fill the remaining seats as above.
+//!
+//! Every node computes the same assignment from the same member set: the
+//! hash is FNV-1a with a SplitMix64 finalizer, fixed across platforms and
+//! builds.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use wws_protocol::{LayoutRole, Tier};
+
+/// Tier and parent of every member.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HierarchyAssignment {
+    pub tiers: BTreeMap<String, Tier>,
+    /// Parent of each member below Tier-1.
+    pub parents: BTreeMap<String, String>,
+}
+
+/// One difference between two assignments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HierarchyChange {
+    Joined {
+        agent_id: String,
+        tier: Tier,
+        parent: Option<String>,
+    },
+    Left {
+        agent_id: String,
+    },
+    TierChanged {
+        agent_id: String,
+        from: Tier,
+        to: Tier,
+    },
+    ParentChanged {
+        agent_id: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+impl fmt::Display for HierarchyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_none = |p: &Option<String>| p.clone().unwrap_or_else(|| "none".to_string());
+        match self {
+            Self::Joined { agent_id, tier, parent } => {
+                write!(f, "{} joined as {:?} under {}", agent_id, tier, or_none(parent))
+            }
+            Self::Left { agent_id } => write!(f, "{} left", agent_id),
+            Self::TierChanged { agent_id, from, to } => {
+                write!(f, "{} moved from {:?} to {:?}", agent_id, from, to)
+            }
+            Self::ParentChanged { agent_id, from, to } => {
+                write!(f, "{} moved from {} to {}", agent_id, or_none(from), or_none(to))
+            }
+        }
+    }
+}
+
+impl HierarchyAssignment {
+    /// What changed going from `previous` to this assignment, ordered by
+    /// agent ID.
+    pub fn diff(&self, previous: &HierarchyAssignment) -> Vec<HierarchyChange> {
+        let mut changes = Vec::new();
+        for (agent_id, tier) in &self.tiers {
+            let parent = self.parents.get(agent_id).cloned();
+            let Some(old_tier) = previous.tiers.get(agent_id) else {
+                changes.push(HierarchyChange::Joined {
+                    agent_id: agent_id.clone(),
+                    tier: *tier,
+                    parent,
+                });
+                continue;
+            };
+            if old_tier != tier {
+                changes.push(HierarchyChange::TierChanged {
+                    agent_id: agent_id.clone(),
+                    from: *old_tier,
+                    to: *tier,
+                });
+            }
+            let old_parent = previous.parents.get(agent_id).cloned();
+            if old_parent != parent {
+                changes.push(HierarchyChange::ParentChanged {
+                    agent_id: agent_id.clone(),
+                    from: old_parent,
+                    to: parent,
+                });
+            }
+        }
+        for agent_id in previous.tiers.keys() {
+            if !self.tiers.contains_key(agent_id) {
+                changes.push(HierarchyChange::Left {
+                    agent_id: agent_id.clone(),
+                });
+            }
+        }
+        changes
+    }
+}
+
+/// Deterministic 64-bit hash of `parts`.
+fn hash(parts: &[&str]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0xff)) {
+            h ^= byte as u64;
+            h = h.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+/// Tier of the agents at `level` (0 = top) of a pyramid with `levels` levels.
+fn tier_at(level: usize, levels: usize) -> Tier {
+    match level {
+        _ if levels == 1 || level == levels - 1 => Tier::Executor,
+        0 => Tier::Tier1,
+        1 => Tier::Tier2,
+        n => Tier::TierN(n as u32 + 1),
+    }
+}
+
+/// Place `members` into tiers of `tier_sizes` (index 0 = Tier-1, as from
+/// `PyramidAllocator::distribute`) and give every agent below Tier-1 a
+/// parent in the tier above.
+pub fn assign(members: &[String], tier_sizes: &[usize]) -> HierarchyAssignment {
+    assign_with_roles(members, tier_sizes, &BTreeMap::new())
+}
+
+/// [`assign`], honouring the [`LayoutRole`] of the members that have one:
+/// coordinators take Tier-1 seats ahead of everyone else, and executors
+/// stay at the bottom and parent no one. `tier_sizes` should come from
+/// `PyramidAllocator::distribute_with_roles` so that there is room for both.
+pub fn assign_with_roles(
+    members: &[String],
+    tier_sizes: &[usize],
+    roles: &BTreeMap<String, LayoutRole>,
+) -> HierarchyAssignment {
+    let mut ranked: Vec<&String> = members.iter().collect();
+    ranked.sort_by_key(|id| (hash(&[id.as_str()]), id.as_str()));
+    ranked.dedup();
+    let role_of = |id: &String| roles.get(id).copied();
+    let (exempt, ranked): (Vec<&String>, Vec<&String>) = ranked
+        .into_iter()
+        .partition(|id| role_of(id) == Some(LayoutRole::Executor));
+    let (pinned, free): (Vec<&String>, Vec<&String>) = ranked
+        .into_iter()
+        .partition(|id| role_of(id) == Some(LayoutRole::Coordinator));
+
+    let levels = tier_sizes.len().max(1);
+    let mut assignment = HierarchyAssignment::default();
+    let mut by_level: Vec<Vec<&String>> = vec![Vec::new(); levels];
+    let mut next = pinned.into_iter().chain(free);
+    for (level, size) in tier_sizes.iter().enumerate().take(levels - 1) {
+        by_level[level].extend(next.by_ref().take(*size));
+    }
+    // Whoever is left, exempt agents included, executes.
+    by_level[levels - 1].extend(next.chain(exempt));
+    for (level, agents) in by_level.iter().enumerate() {
+        for agent_id in agents {
+            assignment.tiers.insert((*agent_id).clone(), tier_at(level, levels));
+        }
+    }
+
+    for level in 1..levels {
+        // A tier emptied by role constraints is skipped over.
+        let Some(parents) = by_level[..level].iter().rev().find(|l| !l.is_empty()) else {
+            continue;
+        };
+        for child in &by_level[level] {
+            let parent = parents
+                .iter()
+                .max_by_key(|p| (hash(&[p.as_str(), child.as_str()]), p.as_str()))
+                .expect("parent tier is non-empty");
+            assignment.parents.insert((*child).clone(), (*parent).clone());
+        }
+    }
+    assignment
+}
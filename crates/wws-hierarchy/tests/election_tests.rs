@@ -30,6 +30,7 @@ fn make_candidacy(
             stake,
         },
         location_vector: VivaldiCoordinates::origin(),
+        capabilities: Vec::new(),
     }
 }
 
@@ -0,0 +1,192 @@
+//! Tests for rendezvous-hashed hierarchy assignment.
+//!
+//! Verifies that:
+//! - Every node derives the same assignment from the same member set
+//! - Parents sit one tier above their children
+//! - A single join or leave moves only the agents it has to
+//! - Pinned coordinators sit in Tier-1 and exempt agents only execute
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use wws_hierarchy::pyramid::PyramidAllocator;
+use wws_hierarchy::rendezvous::{assign, assign_with_roles, HierarchyAssignment, HierarchyChange};
+use wws_protocol::{LayoutRole, Tier};
+
+const K: u64 = 5;
+
+fn members(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("did:swarm:agent-{:03}", i)).collect()
+}
+
+fn layout(members: &[String]) -> HierarchyAssignment {
+    let sizes: Vec<usize> = PyramidAllocator::distribute(members.len() as u64, K)
+        .tiers
+        .iter()
+        .map(|n| *n as usize)
+        .collect();
+    assign(members, &sizes)
+}
+
+/// Agents whose tier or parent differs between two assignments, excluding
+/// the agents that joined or left.
+fn moved(changes: &[HierarchyChange]) -> BTreeSet<String> {
+    changes
+        .iter()
+        .filter_map(|c| match c {
+            HierarchyChange::TierChanged { agent_id, .. }
+            | HierarchyChange::ParentChanged { agent_id, .. } => Some(agent_id.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Agents that a change from `before` to `after` has to move: one per
+/// tier boundary crossed, and the children of every parent that left or
+/// entered its tier.
+fn forced(before: &HierarchyAssignment, after: &HierarchyAssignment) -> BTreeSet<String> {
+    let same_tier =
+        |id: &String| before.tiers.contains_key(id) && before.tiers.get(id) == after.tiers.get(id);
+    let mut agents: BTreeSet<String> = before
+        .tiers
+        .keys()
+        .filter(|id| after.tiers.contains_key(*id) && !same_tier(id))
+        .cloned()
+        .collect();
+    for assignment in [before, after] {
+        for (child, parent) in &assignment.parents {
+            if !same_tier(parent) {
+                agents.insert(child.clone());
+            }
+        }
+    }
+    agents
+}
+
+#[test]
+fn assignment_is_independent_of_member_order() {
+    let forward = members(40);
+    let mut backward = forward.clone();
+    backward.reverse();
+    assert_eq!(layout(&forward), layout(&backward));
+}
+
+#[test]
+fn parents_sit_one_tier_up() {
+    let all = members(60);
+    let assignment = layout(&all);
+    assert_eq!(assignment.tiers.len(), 60);
+    let count = |tier: Tier| assignment.tiers.values().filter(|t| **t == tier).count();
+    assert_eq!(count(Tier::Tier1), K as usize);
+
+    for (child, parent) in &assignment.parents {
+        assert!(assignment.tiers[parent] < assignment.tiers[child], "{} is not above {}", parent, child);
+    }
+    for (id, tier) in &assignment.tiers {
+        assert_eq!(assignment.parents.contains_key(id), *tier != Tier::Tier1);
+    }
+}
+
+#[test]
+fn joins_and_leaves_move_only_the_agents_they_must() {
+    let before = members(100);
+    let base = layout(&before);
+    assert!(layout(&before).diff(&base).is_empty());
+
+    // Checks one join or leave and returns how many agents it moved.
+    let check = |after: &HierarchyAssignment| {
+        let changes = after.diff(&base);
+        let moved = moved(&changes);
+        let forced = forced(&base, after);
+        let unforced: Vec<&String> = moved.difference(&forced).collect();
+        assert!(unforced.is_empty(), "{:?} moved without cause", unforced);
+        let crossed = changes
+            .iter()
+            .filter(|c| matches!(c, HierarchyChange::TierChanged { .. }))
+            .count();
+        // 100 members with k = 5 form three tiers, so two boundaries.
+        assert!(crossed <= 2, "{} agents changed tier", crossed);
+        moved.len()
+    };
+
+    let mut total = 0;
+    for gone in &before {
+        let mut joined = before.clone();
+        let newcomer = format!("{}x", gone);
+        joined.push(newcomer.clone());
+        let after = layout(&joined);
+        assert!(after.diff(&base).contains(&HierarchyChange::Joined {
+            agent_id: newcomer.clone(),
+            tier: after.tiers[&newcomer],
+            parent: after.parents.get(&newcomer).cloned(),
+        }));
+        let moved = check(&after);
+        if after.tiers[&newcomer] == Tier::Executor {
+            // Executors parent no one, so nobody else moves.
+            assert_eq!(moved, 0, "{} joining moved others", newcomer);
+        }
+        total += moved;
+
+        let left: Vec<String> = before.iter().filter(|id| *id != gone).cloned().collect();
+        let after = layout(&left);
+        assert!(after.diff(&base).contains(&HierarchyChange::Left {
+            agent_id: gone.clone()
+        }));
+        let moved = check(&after);
+        if base.tiers[gone] == Tier::Executor {
+            assert_eq!(moved, 0, "{} leaving moved others", gone);
+        }
+        total += moved;
+    }
+    // Re-laying out by sorted ID moves 17 agents per change on average.
+    let mean = total as f64 / 200.0;
+    assert!(mean < 4.0, "a join or leave moved {:.1} agents on average", mean);
+}
+
+#[test]
+fn pinned_coordinators_lead_and_exempt_agents_only_execute() {
+    let all = members(40);
+    let mut roles = BTreeMap::new();
+    // More pinned agents than the k Tier-1 seats, and most of the swarm
+    // exempt, so that both constraints reshape the pyramid.
+    for id in &all[..7] {
+        roles.insert(id.clone(), LayoutRole::Coordinator);
+    }
+    for id in &all[10..] {
+        roles.insert(id.clone(), LayoutRole::Executor);
+    }
+    let count = |role| roles.values().filter(|r| **r == role).count() as u64;
+    let sizes: Vec<usize> = PyramidAllocator::distribute_with_roles(
+        all.len() as u64,
+        K,
+        count(LayoutRole::Coordinator),
+        count(LayoutRole::Executor),
+    )
+    .tiers
+    .iter()
+    .map(|n| *n as usize)
+    .collect();
+    assert_eq!(sizes.iter().sum::<usize>(), all.len());
+    assert!(sizes[0] >= 7);
+    assert!(sizes[..sizes.len() - 1].iter().sum::<usize>() <= 10);
+
+    let assignment = assign_with_roles(&all, &sizes, &roles);
+    for (id, role) in &roles {
+        match role {
+            LayoutRole::Coordinator => assert_eq!(assignment.tiers[id], Tier::Tier1, "{}", id),
+            LayoutRole::Executor => {
+                assert_eq!(assignment.tiers[id], Tier::Executor, "{}", id);
+                assert!(!assignment.parents.values().any(|p| p == id), "{} parents", id);
+                assert!(assignment.parents.contains_key(id), "{} has no parent", id);
+            }
+        }
+    }
+
+    // Without roles the same call is a plain assignment.
+    let plain = layout(&all);
+    let sizes: Vec<usize> = PyramidAllocator::distribute(all.len() as u64, K)
+        .tiers
+        .iter()
+        .map(|n| *n as usize)
+        .collect();
+    assert_eq!(assign_with_roles(&all, &sizes, &BTreeMap::new()), plain);
+}
@@ -0,0 +1,277 @@
+//! Bandwidth and message-volume accounting per topic and per peer.
+//!
+//! The host counts the bytes and messages of every GossipSub message it
+//! publishes or receives against the message's topic, and of every direct
+//! request and response against the [`DIRECT_PROTOCOL`] pseudo-topic.
+//! Inbound traffic is also counted against the peer it came from; outbound
+//! gossip fans out to the mesh, so a peer's outbound counters only cover
+//! direct messages sent to it.
+//!
+//! Each topic's rate is measured over fixed windows. When a configured
+//! limit is set, the first window in which a topic goes over it yields a
+//! [`RateWarning`]; the topic warns again once a later window went over
+//! after one that stayed under.
+//!
+//! [`DIRECT_PROTOCOL`]: crate::direct::DIRECT_PROTOCOL
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use libp2p::PeerId;
+use serde::Serialize;
+
+/// Length of the window a topic's rate is measured over.
+pub const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Bytes and messages in each direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TrafficCounters {
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub messages_in: u64,
+    pub messages_out: u64,
+}
+
+impl TrafficCounters {
+    fn add_in(&mut self, bytes: usize) {
+        self.bytes_in += bytes as u64;
+        self.messages_in += 1;
+    }
+
+    fn add_out(&mut self, bytes: usize) {
+        self.bytes_out += bytes as u64;
+        self.messages_out += 1;
+    }
+
+    /// Bytes in both directions.
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes_in + self.bytes_out
+    }
+}
+
+/// Traffic on one topic.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicTraffic {
+    pub topic: String,
+    #[serde(flatten)]
+    pub counters: TrafficCounters,
+    /// Bytes per second in both directions over the last full window.
+    pub bytes_per_sec: f64,
+    /// Whether the last full window went over the configured limit.
+    pub over_limit: bool,
+}
+
+/// Traffic exchanged with one peer.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerTraffic {
+    pub peer_id: String,
+    #[serde(flatten)]
+    pub counters: TrafficCounters,
+}
+
+/// Serializable view of the host's traffic since it started.
+#[derive(Debug, Clone, Serialize)]
+pub struct BandwidthSnapshot {
+    pub total: TrafficCounters,
+    /// Every topic with traffic, busiest first.
+    pub topics: Vec<TopicTraffic>,
+    /// Every peer with traffic, busiest first.
+    pub peers: Vec<PeerTraffic>,
+    /// Per-topic limit that triggers a warning, in bytes per second.
+    pub topic_rate_limit: Option<u64>,
+    /// Seconds the counters cover.
+    pub uptime_secs: u64,
+}
+
+/// A topic that went over the configured rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateWarning {
+    pub topic: String,
+    pub bytes_per_sec: f64,
+    pub limit: u64,
+}
+
+#[derive(Debug, Clone)]
+struct TopicRecord {
+    counters: TrafficCounters,
+    window_start: Instant,
+    window_bytes: u64,
+    /// Rate of the last full window.
+    bytes_per_sec: f64,
+    /// Whether a warning was given since the topic last stayed under the
+    /// limit for a full window.
+    warned: bool,
+}
+
+impl TopicRecord {
+    fn new(now: Instant) -> Self {
+        Self {
+            counters: TrafficCounters::default(),
+            window_start: now,
+            window_bytes: 0,
+            bytes_per_sec: 0.0,
+            warned: false,
+        }
+    }
+
+    /// Close the current window if it has run out. A window with no
+    /// traffic after it resets the rate to 0.
+    fn roll(&mut self, limit: Option<u64>, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < RATE_WINDOW {
+            return;
+        }
+        self.bytes_per_sec = if elapsed < RATE_WINDOW * 2 {
+            self.window_bytes as f64 / RATE_WINDOW.as_secs_f64()
+        } else {
+            0.0
+        };
+        if limit.is_none_or(|limit| self.bytes_per_sec <= limit as f64) {
+            self.warned = false;
+        }
+        self.window_start = now;
+        self.window_bytes = 0;
+    }
+
+    /// Count `bytes` towards the current window, returning the rate that
+    /// went over `limit` the first time it does.
+    fn count(&mut self, bytes: usize, limit: Option<u64>, now: Instant) -> Option<f64> {
+        self.roll(limit, now);
+        self.window_bytes += bytes as u64;
+        let limit = limit?;
+        let rate = self.window_bytes as f64 / RATE_WINDOW.as_secs_f64();
+        if self.warned || rate <= limit as f64 {
+            return None;
+        }
+        self.warned = true;
+        Some(rate)
+    }
+
+    fn over_limit(&self, limit: Option<u64>) -> bool {
+        limit.is_some_and(|limit| self.bytes_per_sec > limit as f64)
+    }
+}
+
+/// Counts traffic per topic and per peer.
+#[derive(Debug)]
+pub struct BandwidthMeter {
+    topic_rate_limit: Option<u64>,
+    started: Instant,
+    total: TrafficCounters,
+    topics: HashMap<String, TopicRecord>,
+    peers: HashMap<PeerId, TrafficCounters>,
+}
+
+impl BandwidthMeter {
+    /// A meter warning when a topic carries more than `topic_rate_limit`
+    /// bytes per second; `None` never warns.
+    pub fn new(topic_rate_limit: Option<u64>, now: Instant) -> Self {
+        Self {
+            topic_rate_limit,
+            started: now,
+            total: TrafficCounters::default(),
+            topics: HashMap::new(),
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Count a message received on `topic` from `peer`.
+    pub fn record_inbound(
+        &mut self,
+        topic: &str,
+        peer: PeerId,
+        bytes: usize,
+        now: Instant,
+    ) -> Option<RateWarning> {
+        self.total.add_in(bytes);
+        self.peers.entry(peer).or_default().add_in(bytes);
+        let record = self
+            .topics
+            .entry(topic.to_string())
+            .or_insert_with(|| TopicRecord::new(now));
+        record.counters.add_in(bytes);
+        let rate = record.count(bytes, self.topic_rate_limit, now)?;
+        self.warning(topic, rate)
+    }
+
+    /// Count a message sent on `topic`, to `peer` when it went to a single
+    /// peer.
+    pub fn record_outbound(
+        &mut self,
+        topic: &str,
+        peer: Option<PeerId>,
+        bytes: usize,
+        now: Instant,
+    ) -> Option<RateWarning> {
+        self.total.add_out(bytes);
+        if let Some(peer) = peer {
+            self.peers.entry(peer).or_default().add_out(bytes);
+        }
+        let record = self
+            .topics
+            .entry(topic.to_string())
+            .or_insert_with(|| TopicRecord::new(now));
+        record.counters.add_out(bytes);
+        let rate = record.count(bytes, self.topic_rate_limit, now)?;
+        self.warning(topic, rate)
+    }
+
+    fn warning(&self, topic: &str, bytes_per_sec: f64) -> Option<RateWarning> {
+        Some(RateWarning {
+            topic: topic.to_string(),
+            bytes_per_sec,
+            limit: self.topic_rate_limit?,
+        })
+    }
+
+    /// Traffic per topic and per peer, busiest first.
+    pub fn snapshot(&mut self, now: Instant) -> BandwidthSnapshot {
+        let limit = self.topic_rate_limit;
+        let mut topics: Vec<TopicTraffic> = self
+            .topics
+            .iter_mut()
+            .map(|(topic, record)| {
+                record.roll(limit, now);
+                TopicTraffic {
+                    topic: topic.clone(),
+                    counters: record.counters,
+                    bytes_per_sec: record.bytes_per_sec,
+                    over_limit: record.over_limit(limit),
+                }
+            })
+            .collect();
+        topics.sort_by(|a, b| {
+            b.counters
+                .total_bytes()
+                .cmp(&a.counters.total_bytes())
+                .then_with(|| a.topic.cmp(&b.topic))
+        });
+        let mut peers: Vec<PeerTraffic> = self
+            .peers
+            .iter()
+            .map(|(peer, counters)| PeerTraffic {
+                peer_id: peer.to_string(),
+                counters: *counters,
+            })
+            .collect();
+        peers.sort_by(|a, b| {
+            b.counters
+                .total_bytes()
+                .cmp(&a.counters.total_bytes())
+                .then_with(|| a.peer_id.cmp(&b.peer_id))
+        });
+        BandwidthSnapshot {
+            total: self.total,
+            topics,
+            peers,
+            topic_rate_limit: limit,
+            uptime_secs: now.saturating_duration_since(self.started).as_secs(),
+        }
+    }
+
+    /// Forget a disconnected peer's counters. Its traffic stays in the
+    /// topic and total counters.
+    pub fn forget_peer(&mut self, peer: &PeerId) {
+        self.peers.remove(peer);
+    }
+}
@@ -1,5 +1,5 @@
 //! Custom NetworkBehaviour composing Kademlia + GossipSub + mDNS + Identify + Ping + AutoNAT
-//! + Circuit Relay client + DCUtR hole-punching.
+//! + Circuit Relay client + DCUtR hole-punching + direct request-response.
 //!
 //! This module defines the composite behaviour for the WWS network node.
 //! Each sub-behaviour handles a specific aspect of peer-to-peer communication:
@@ -11,16 +11,20 @@
 //! - **AutoNAT**: NAT traversal status detection
 //! - **Relay client**: Circuit relay for NAT traversal via public relay nodes
 //! - **DCUtR**: Direct Connection Upgrade through Relay (hole-punching)
+//! - **Direct**: Request-response between two connectors, bypassing gossip
 
 use std::time::Duration;
 
 use libp2p::{
-    autonat, dcutr, gossipsub, identify, kad, mdns, ping, relay,
+    autonat, dcutr, gossipsub, identify, kad, mdns, ping, relay, request_response,
     identity::Keypair,
     swarm::NetworkBehaviour,
     StreamProtocol,
 };
 
+use wws_protocol::SwarmMessage;
+
+use crate::direct::DIRECT_PROTOCOL;
 use crate::NetworkError;
 
 /// Composite NetworkBehaviour for a WWS node.
@@ -45,6 +49,8 @@ pub struct SwarmBehaviour {
     pub relay_client: relay::client::Behaviour,
     /// DCUtR: Direct Connection Upgrade through Relay for hole-punching.
     pub dcutr: dcutr::Behaviour,
+    /// Direct request-response carrying signed `SwarmMessage`s.
+    pub direct: request_response::json::Behaviour<SwarmMessage, SwarmMessage>,
 }
 
 /// Configuration for constructing the composite behaviour.
@@ -62,6 +68,8 @@ pub struct BehaviourConfig {
     pub mdns_query_interval: Duration,
     /// Ping interval.
     pub ping_interval: Duration,
+    /// Upper bound on any direct request; callers may wait less.
+    pub direct_request_timeout: Duration,
 }
 
 impl Default for BehaviourConfig {
@@ -73,6 +81,7 @@ impl Default for BehaviourConfig {
             gossipsub_strict: true,
             mdns_query_interval: Duration::from_secs(5),
             ping_interval: Duration::from_secs(15),
+            direct_request_timeout: Duration::from_secs(60),
         }
     }
 }
@@ -140,6 +149,16 @@ impl SwarmBehaviour {
         // Requires the relay_client to already be in scope; dcutr only needs peer_id.
         let dcutr = dcutr::Behaviour::new(peer_id);
 
+        // -- Direct request-response --
+        let direct = request_response::json::Behaviour::new(
+            [(
+                StreamProtocol::new(DIRECT_PROTOCOL),
+                request_response::ProtocolSupport::Full,
+            )],
+            request_response::Config::default()
+                .with_request_timeout(config.direct_request_timeout),
+        );
+
         Ok(Self {
             kademlia,
             gossipsub,
@@ -149,6 +168,7 @@ impl SwarmBehaviour {
             autonat,
             relay_client,
             dcutr,
+            direct,
         })
     }
 }
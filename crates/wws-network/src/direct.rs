@@ -0,0 +1,57 @@
+//! Direct connector-to-connector requests over libp2p request-response.
+//!
+//! Some interactions — state sync, artifact fetch, targeted reassignment —
+//! concern a single peer and should not be broadcast over GossipSub. The
+//! direct protocol carries one `SwarmMessage` as the request and one as the
+//! response, both JSON-encoded on a dedicated stream.
+//!
+//! GossipSub authenticates the publisher of every message; a raw stream
+//! does not, so both sides sign the message with their libp2p identity key
+//! and the receiver checks the signature against the key embedded in the
+//! sender's PeerId.
+
+use libp2p::identity::{Keypair, PublicKey};
+use libp2p::PeerId;
+use wws_protocol::SwarmMessage;
+
+/// Protocol name negotiated for direct requests.
+pub const DIRECT_PROTOCOL: &str = "/wws/direct/1.0.0";
+
+/// Sign `message` with `keypair`, replacing any existing signature.
+pub fn sign_message(keypair: &Keypair, message: &mut SwarmMessage) {
+    let payload = SwarmMessage::signing_payload(&message.method, &message.params);
+    message.signature = keypair
+        .sign(&payload)
+        .map(hex::encode)
+        .unwrap_or_default();
+}
+
+/// Whether `message` carries a valid signature by the identity key of `peer`.
+///
+/// Only PeerIds that inline their public key (Ed25519 and other short keys)
+/// can be checked; any other PeerId fails verification.
+pub fn verify_message(peer: &PeerId, message: &SwarmMessage) -> bool {
+    let Some(key) = public_key_of(peer) else {
+        return false;
+    };
+    let Ok(signature) = hex::decode(&message.signature) else {
+        return false;
+    };
+    let payload = SwarmMessage::signing_payload(&message.method, &message.params);
+    key.verify(&payload, &signature)
+}
+
+/// Size of `message` as sent on the wire.
+pub fn encoded_len(message: &SwarmMessage) -> usize {
+    serde_json::to_vec(message).map(|bytes| bytes.len()).unwrap_or(0)
+}
+
+/// Recover the public key inlined in a PeerId's identity multihash.
+fn public_key_of(peer: &PeerId) -> Option<PublicKey> {
+    const IDENTITY_MULTIHASH: u64 = 0x00;
+    let multihash = peer.as_ref();
+    if multihash.code() != IDENTITY_MULTIHASH {
+        return None;
+    }
+    PublicKey::try_decode_protobuf(multihash.digest()).ok()
+}
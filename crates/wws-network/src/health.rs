@@ -0,0 +1,87 @@
+//! Network self-diagnostics: a snapshot of the swarm host's view of its
+//! own connectivity and the pressure on its event loop.
+//!
+//! The host answers health queries on the control-plane lane, so a
+//! snapshot is still returned while bulk publishes are queued.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// GossipSub mesh membership for one subscribed topic.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicMeshHealth {
+    pub topic: String,
+    /// Peers in this node's mesh for the topic.
+    pub mesh_peers: usize,
+    /// Connected peers known to be subscribed to the topic.
+    pub subscribed_peers: usize,
+}
+
+/// Queue depth of one of the host's bounded channels.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelBacklog {
+    pub name: String,
+    pub queued: usize,
+    pub capacity: usize,
+}
+
+impl ChannelBacklog {
+    /// Fraction of the channel currently in use.
+    pub fn fill_ratio(&self) -> f64 {
+        if self.capacity == 0 {
+            0.0
+        } else {
+            self.queued as f64 / self.capacity as f64
+        }
+    }
+}
+
+/// Snapshot of the network layer's health.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkHealth {
+    pub connected_peers: usize,
+    /// Mesh membership for every subscribed topic, sorted by topic.
+    pub topics: Vec<TopicMeshHealth>,
+    /// Peers in the Kademlia routing table.
+    pub routing_table_size: usize,
+    /// Bootstrap peers from the configuration.
+    pub bootstrap_peers: usize,
+    /// Configured bootstrap peers with a live connection.
+    pub bootstrap_connected: usize,
+    /// Whether a Kademlia bootstrap query has completed.
+    pub bootstrap_complete: bool,
+    /// Delay of the most recent event-loop lag probe, in milliseconds.
+    pub event_loop_lag_ms: u64,
+    /// Worst probe delay since the previous health snapshot, in milliseconds.
+    pub event_loop_lag_peak_ms: u64,
+    pub channels: Vec<ChannelBacklog>,
+}
+
+/// Tracks how late the host's periodic lag probe fires.
+///
+/// A probe scheduled every interval that fires late means the event loop
+/// was busy handling other work for that long.
+#[derive(Debug, Clone, Default)]
+pub struct EventLoopLag {
+    last: Duration,
+    peak: Duration,
+}
+
+impl EventLoopLag {
+    /// Record the delay of one probe.
+    pub fn record(&mut self, lag: Duration) {
+        self.last = lag;
+        self.peak = self.peak.max(lag);
+    }
+
+    /// Delay of the most recent probe.
+    pub fn last(&self) -> Duration {
+        self.last
+    }
+
+    /// Worst delay since the previous call, resetting the peak.
+    pub fn take_peak(&mut self) -> Duration {
+        std::mem::replace(&mut self.peak, self.last)
+    }
+}
@@ -3,24 +3,40 @@
 //! Provides the foundational networking for the World Wide Swarm (WWS) protocol:
 //! - Peer discovery via mDNS and Kademlia DHT
 //! - Message passing via GossipSub pub/sub
+//! - Signed direct requests between two peers via request-response
 //! - Swarm size estimation from Kademlia routing table density
+//! - Peer reputation scoring with temporary bans for misbehaving peers
+//! - Bandwidth and message-volume accounting per topic and per peer
+//! - Optional per-topic payload encryption for private swarms
+//! - Health snapshots of mesh membership, DHT reach and event-loop pressure
+//! - AutoNAT reachability reporting, with relay listening while private
 //! - Transport configuration with TCP + Noise + Yamux
 
+pub mod bandwidth;
 pub mod behaviour;
+pub mod direct;
 pub mod discovery;
 pub mod dns_bootstrap;
+pub mod health;
 pub mod name_registry;
+pub mod peer_scoring;
+pub mod reachability;
 pub mod size_estimator;
 pub mod swarm_host;
 pub mod topics;
 pub mod transport;
 
+pub use bandwidth::{BandwidthSnapshot, PeerTraffic, TopicTraffic, TrafficCounters};
 pub use behaviour::SwarmBehaviour;
+pub use direct::DIRECT_PROTOCOL;
 pub use discovery::DiscoveryConfig;
-pub use libp2p::{Multiaddr, PeerId};
+pub use health::{ChannelBacklog, NetworkHealth, TopicMeshHealth};
+pub use peer_scoring::{PeerOffense, PeerScoreConfig, PeerScoreSnapshot};
+pub use reachability::{NatState, Reachability};
+pub use libp2p::{identity, identity::Keypair, Multiaddr, PeerId};
 pub use size_estimator::SwarmSizeEstimator;
-pub use swarm_host::{NetworkEvent, SwarmHandle, SwarmHost, SwarmHostConfig};
-pub use topics::TopicManager;
+pub use swarm_host::{NetworkEvent, PayloadCipher, SwarmHandle, SwarmHost, SwarmHostConfig};
+pub use topics::{MessagePriority, TopicManager};
 pub use transport::build_swarm;
 
 use thiserror::Error;
@@ -52,6 +68,12 @@ pub enum NetworkError {
     #[error("Internal channel closed")]
     ChannelClosed,
 
+    #[error("Direct request failed: {0}")]
+    RequestFailed(String),
+
+    #[error("Direct request timed out")]
+    RequestTimeout,
+
     #[error("Serialization error: {0}")]
     Serialization(String),
 
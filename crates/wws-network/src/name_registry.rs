@@ -40,6 +40,19 @@ impl NameRecord {
         now > self.expires_at && now <= self.expires_at + NAME_GRACE_SECS
     }
 
+    /// The record a verified `name.claim` establishes.
+    pub fn from_claim(claim: &wws_protocol::NameClaimParams) -> Self {
+        Self {
+            name: claim.name.clone(),
+            did: claim.did.clone(),
+            peer_id: claim.peer_id.clone(),
+            registered_at: claim.registered_at,
+            expires_at: claim.expires_at,
+            pow_nonce: claim.pow_nonce,
+            signature: hex::decode(&claim.signature).unwrap_or_default(),
+        }
+    }
+
     /// DHT storage key for this name record.
     pub fn dht_key(name: &str) -> Vec<u8> {
         use sha2::{Digest, Sha256};
@@ -0,0 +1,246 @@
+//! Per-peer reputation and temporary bans.
+//!
+//! Upper layers report misbehaviour (undecodable messages, invalid
+//! signatures, protocol violations) against the `PeerId` that propagated it.
+//! Each offense lowers the peer's score; the score recovers slowly while the
+//! peer behaves. A peer whose score falls to the ban threshold is
+//! disconnected and banned, for longer each time it offends again.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use libp2p::PeerId;
+use serde::Serialize;
+
+/// Kinds of misbehaviour a peer can be reported for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerOffense {
+    /// A message signature that does not verify.
+    InvalidSignature,
+    /// A payload that is not a valid protocol message.
+    ParseError,
+    /// A well-formed message that breaks the protocol (e.g. params that do
+    /// not match the method).
+    ProtocolViolation,
+}
+
+/// Scoring and ban parameters.
+#[derive(Debug, Clone)]
+pub struct PeerScoreConfig {
+    /// Score lost per invalid signature.
+    pub invalid_signature_penalty: f64,
+    /// Score lost per undecodable message.
+    pub parse_error_penalty: f64,
+    /// Score lost per protocol violation.
+    pub protocol_violation_penalty: f64,
+    /// Score recovered per minute without offenses, up to 0.
+    pub recovery_per_min: f64,
+    /// A peer is banned once its score drops to this value.
+    pub ban_threshold: f64,
+    /// Length of a first automatic ban; doubled for each earlier ban.
+    pub base_ban: Duration,
+    /// Longest automatic ban.
+    pub max_ban: Duration,
+}
+
+impl Default for PeerScoreConfig {
+    fn default() -> Self {
+        Self {
+            invalid_signature_penalty: 50.0,
+            parse_error_penalty: 10.0,
+            protocol_violation_penalty: 20.0,
+            recovery_per_min: 5.0,
+            ban_threshold: -100.0,
+            base_ban: Duration::from_secs(600),
+            max_ban: Duration::from_secs(86_400),
+        }
+    }
+}
+
+impl PeerScoreConfig {
+    fn penalty(&self, offense: PeerOffense) -> f64 {
+        match offense {
+            PeerOffense::InvalidSignature => self.invalid_signature_penalty,
+            PeerOffense::ParseError => self.parse_error_penalty,
+            PeerOffense::ProtocolViolation => self.protocol_violation_penalty,
+        }
+    }
+}
+
+/// Reputation record for one peer.
+#[derive(Debug, Clone)]
+struct PeerRecord {
+    score: f64,
+    /// When `score` was last brought up to date with recovery.
+    updated_at: Instant,
+    invalid_signatures: u64,
+    parse_errors: u64,
+    protocol_violations: u64,
+    ban_count: u32,
+    banned_until: Option<Instant>,
+    ban_reason: Option<String>,
+}
+
+impl PeerRecord {
+    fn new(now: Instant) -> Self {
+        Self {
+            score: 0.0,
+            updated_at: now,
+            invalid_signatures: 0,
+            parse_errors: 0,
+            protocol_violations: 0,
+            ban_count: 0,
+            banned_until: None,
+            ban_reason: None,
+        }
+    }
+
+    fn recover(&mut self, config: &PeerScoreConfig, now: Instant) {
+        let minutes = now.saturating_duration_since(self.updated_at).as_secs_f64() / 60.0;
+        self.score = (self.score + minutes * config.recovery_per_min).min(0.0);
+        self.updated_at = now;
+    }
+
+    fn is_banned(&self, now: Instant) -> bool {
+        self.banned_until.is_some_and(|until| now < until)
+    }
+}
+
+/// Serializable view of a peer's reputation.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerScoreSnapshot {
+    pub peer_id: String,
+    pub score: f64,
+    pub invalid_signatures: u64,
+    pub parse_errors: u64,
+    pub protocol_violations: u64,
+    pub ban_count: u32,
+    pub banned: bool,
+    /// Seconds left on the current ban.
+    pub ban_remaining_secs: Option<u64>,
+    pub ban_reason: Option<String>,
+}
+
+/// A ban that was just imposed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerBan {
+    pub peer: PeerId,
+    pub duration: Duration,
+    pub reason: String,
+}
+
+/// Tracks offenses and bans for every peer that misbehaved.
+#[derive(Debug, Default)]
+pub struct PeerScorer {
+    config: PeerScoreConfig,
+    peers: HashMap<PeerId, PeerRecord>,
+}
+
+impl PeerScorer {
+    pub fn new(config: PeerScoreConfig) -> Self {
+        Self {
+            config,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Record an offense. Returns the ban when this offense pushed the peer
+    /// to the ban threshold.
+    pub fn report(&mut self, peer: PeerId, offense: PeerOffense, now: Instant) -> Option<PeerBan> {
+        let config = &self.config;
+        let record = self.peers.entry(peer).or_insert_with(|| PeerRecord::new(now));
+        record.recover(config, now);
+        match offense {
+            PeerOffense::InvalidSignature => record.invalid_signatures += 1,
+            PeerOffense::ParseError => record.parse_errors += 1,
+            PeerOffense::ProtocolViolation => record.protocol_violations += 1,
+        }
+        record.score -= config.penalty(offense);
+        if record.is_banned(now) || record.score > config.ban_threshold {
+            return None;
+        }
+
+        let duration = config
+            .base_ban
+            .saturating_mul(2u32.saturating_pow(record.ban_count))
+            .min(config.max_ban);
+        let reason = format!(
+            "score {:.0} after {} invalid signatures, {} parse errors, {} protocol violations",
+            record.score, record.invalid_signatures, record.parse_errors, record.protocol_violations
+        );
+        Some(self.ban(peer, duration, reason, now))
+    }
+
+    /// Ban a peer for `duration`, replacing any current ban. The score
+    /// starts over from 0 when the ban ends.
+    pub fn ban(&mut self, peer: PeerId, duration: Duration, reason: String, now: Instant) -> PeerBan {
+        let record = self.peers.entry(peer).or_insert_with(|| PeerRecord::new(now));
+        record.score = 0.0;
+        record.updated_at = now;
+        record.ban_count += 1;
+        record.banned_until = Some(now + duration);
+        record.ban_reason = Some(reason.clone());
+        PeerBan {
+            peer,
+            duration,
+            reason,
+        }
+    }
+
+    /// Lift a peer's ban. Returns whether it was banned.
+    pub fn unban(&mut self, peer: &PeerId, now: Instant) -> bool {
+        match self.peers.get_mut(peer) {
+            Some(record) if record.is_banned(now) => {
+                record.banned_until = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_banned(&self, peer: &PeerId, now: Instant) -> bool {
+        self.peers.get(peer).is_some_and(|r| r.is_banned(now))
+    }
+
+    /// Clear bans that ran out, returning the peers they applied to.
+    pub fn expire_bans(&mut self, now: Instant) -> Vec<PeerId> {
+        let mut expired = Vec::new();
+        for (peer, record) in &mut self.peers {
+            if record.banned_until.is_some_and(|until| now >= until) {
+                record.banned_until = None;
+                expired.push(*peer);
+            }
+        }
+        expired
+    }
+
+    /// Every peer with a recorded offense or ban, lowest score first.
+    pub fn snapshot(&self, now: Instant) -> Vec<PeerScoreSnapshot> {
+        let mut scores: Vec<PeerScoreSnapshot> = self
+            .peers
+            .iter()
+            .map(|(peer, record)| {
+                let mut current = record.clone();
+                current.recover(&self.config, now);
+                let banned = record.is_banned(now);
+                PeerScoreSnapshot {
+                    peer_id: peer.to_string(),
+                    score: current.score,
+                    invalid_signatures: record.invalid_signatures,
+                    parse_errors: record.parse_errors,
+                    protocol_violations: record.protocol_violations,
+                    ban_count: record.ban_count,
+                    banned,
+                    ban_remaining_secs: record
+                        .banned_until
+                        .filter(|_| banned)
+                        .map(|until| until.saturating_duration_since(now).as_secs()),
+                    ban_reason: record.ban_reason.clone().filter(|_| banned),
+                }
+            })
+            .collect();
+        scores.sort_by(|a, b| a.score.total_cmp(&b.score));
+        scores
+    }
+}
@@ -0,0 +1,184 @@
+//! Public reachability of this node, as AutoNAT and peers report it.
+//!
+//! AutoNAT asks connected peers to dial this node back and settles on a
+//! status: public (with the address that was dialled), private or unknown.
+//! Peers running Identify also report the address they see this node at.
+//! When the node turns out to be private and relay-client mode is on, the
+//! host reserves a slot on a connected peer that offers the circuit relay
+//! hop protocol and listens through it, so others can still reach it. It
+//! drops the reservation once the node is found to be public again.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use libp2p::core::transport::ListenerId;
+use libp2p::multiaddr::Protocol;
+use libp2p::{autonat, Multiaddr, PeerId};
+use serde::Serialize;
+
+/// Observed addresses kept, most recent first.
+pub const MAX_OBSERVED_ADDRS: usize = 8;
+
+/// Whether other peers can dial this node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NatState {
+    /// No probe has settled yet.
+    #[default]
+    Unknown,
+    /// Peers could dial this node back.
+    Public,
+    /// Peers could not dial this node back.
+    Private,
+}
+
+/// Snapshot of this node's reachability.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Reachability {
+    pub status: NatState,
+    /// Address AutoNAT was dialled back on, when public.
+    pub public_address: Option<String>,
+    /// Probes agreeing with the status, up to AutoNAT's maximum.
+    pub confidence: usize,
+    /// Addresses confirmed as external, including configured ones.
+    pub external_addrs: Vec<String>,
+    /// Addresses peers reported seeing this node at, most recent first.
+    pub observed_addrs: Vec<String>,
+    /// Connected peers offering to relay.
+    pub relay_candidates: usize,
+    /// Relay this node listens through, while private.
+    pub relay_peer: Option<String>,
+    /// Whether the relay accepted the reservation.
+    pub relay_reserved: bool,
+}
+
+/// The relay a private node listens through.
+#[derive(Debug, Clone)]
+struct RelayListener {
+    peer: PeerId,
+    listener: ListenerId,
+    reserved: bool,
+}
+
+/// Reachability state kept by the swarm host.
+#[derive(Debug, Default)]
+pub struct ReachabilityTracker {
+    status: NatState,
+    public_address: Option<Multiaddr>,
+    observed: VecDeque<Multiaddr>,
+    /// Connected relay peers with an address to reach them on.
+    relay_candidates: BTreeMap<PeerId, Multiaddr>,
+    relay: Option<RelayListener>,
+}
+
+impl ReachabilityTracker {
+    pub fn status(&self) -> NatState {
+        self.status
+    }
+
+    /// Record a new AutoNAT status. Returns whether the status changed.
+    pub fn set_status(&mut self, status: &autonat::NatStatus) -> bool {
+        let (state, public_address) = match status {
+            autonat::NatStatus::Public(addr) => (NatState::Public, Some(addr.clone())),
+            autonat::NatStatus::Private => (NatState::Private, None),
+            autonat::NatStatus::Unknown => (NatState::Unknown, None),
+        };
+        let changed = state != self.status;
+        self.status = state;
+        self.public_address = public_address;
+        changed
+    }
+
+    /// Record an address a peer saw this node at.
+    pub fn observe(&mut self, addr: Multiaddr) {
+        self.observed.retain(|a| a != &addr);
+        self.observed.push_front(addr);
+        self.observed.truncate(MAX_OBSERVED_ADDRS);
+    }
+
+    /// Note that `peer`, reachable at `addr`, offers to relay.
+    pub fn add_relay_candidate(&mut self, peer: PeerId, addr: Multiaddr) {
+        self.relay_candidates.insert(peer, addr);
+    }
+
+    /// Forget a disconnected peer as a relay. Returns whether this node was
+    /// listening through it.
+    pub fn remove_peer(&mut self, peer: &PeerId) -> bool {
+        self.relay_candidates.remove(peer);
+        if self.relay.as_ref().is_some_and(|r| &r.peer == peer) {
+            self.relay = None;
+            return true;
+        }
+        false
+    }
+
+    /// The circuit address to listen on through a relay, when this node is
+    /// private and not yet relayed. Candidates are tried in peer ID order.
+    pub fn relay_to_use(&self) -> Option<(PeerId, Multiaddr)> {
+        if self.status != NatState::Private || self.relay.is_some() {
+            return None;
+        }
+        let (peer, addr) = self.relay_candidates.iter().next()?;
+        Some((*peer, circuit_addr(addr, *peer)))
+    }
+
+    /// Record that this node now listens through `peer` on `listener`.
+    pub fn relay_started(&mut self, peer: PeerId, listener: ListenerId) {
+        self.relay = Some(RelayListener {
+            peer,
+            listener,
+            reserved: false,
+        });
+    }
+
+    /// Record that `peer` accepted the reservation.
+    pub fn relay_reserved(&mut self, peer: &PeerId) {
+        if let Some(relay) = self.relay.as_mut().filter(|r| &r.peer == peer) {
+            relay.reserved = true;
+        }
+    }
+
+    /// Stop listening through the relay once the node is public. Returns
+    /// the listener to remove.
+    pub fn relay_to_drop(&mut self) -> Option<ListenerId> {
+        if self.status == NatState::Public {
+            return self.relay.take().map(|r| r.listener);
+        }
+        None
+    }
+
+    /// Snapshot with AutoNAT's current `confidence` and `external_addrs`,
+    /// the swarm's confirmed external addresses.
+    pub fn snapshot<'a>(
+        &self,
+        confidence: usize,
+        external_addrs: impl Iterator<Item = &'a Multiaddr>,
+    ) -> Reachability {
+        Reachability {
+            status: self.status,
+            public_address: self.public_address.as_ref().map(|a| a.to_string()),
+            confidence,
+            external_addrs: external_addrs.map(|a| a.to_string()).collect(),
+            observed_addrs: self.observed.iter().map(|a| a.to_string()).collect(),
+            relay_candidates: self.relay_candidates.len(),
+            relay_peer: self.relay.as_ref().map(|r| r.peer.to_string()),
+            relay_reserved: self.relay.as_ref().is_some_and(|r| r.reserved),
+        }
+    }
+}
+
+/// `addr` of `relay` extended to listen through it:
+/// `<addr>/p2p/<relay>/p2p-circuit`.
+pub fn circuit_addr(addr: &Multiaddr, relay: PeerId) -> Multiaddr {
+    let mut circuit: Multiaddr = addr
+        .iter()
+        .take_while(|p| !matches!(p, Protocol::P2p(_) | Protocol::P2pCircuit))
+        .collect();
+    circuit.push(Protocol::P2p(relay));
+    circuit.push(Protocol::P2pCircuit);
+    circuit
+}
+
+/// Whether `addr` goes through a relay.
+pub fn is_relayed(addr: &Multiaddr) -> bool {
+    addr.iter().any(|p| matches!(p, Protocol::P2pCircuit))
+}
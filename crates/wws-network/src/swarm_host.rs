@@ -6,21 +6,42 @@
 //! - `SwarmHandle` is a cheaply cloneable handle providing async methods
 //!   for publishing, subscribing, dialing, and querying the network.
 //! - Communication between the handle and the host uses bounded MPSC channels
-//!   for commands and a broadcast-style channel for events.
+//!   for commands and a broadcast-style channel for events. Control-plane
+//!   publishes get their own channel, which the host drains before the
+//!   others, so keepalives and votes are not stuck behind bulk results.
+//! - Direct requests to a single peer travel over request-response instead
+//!   of gossip. The host signs outgoing requests and responses with its
+//!   identity key, checks the signature of everything it receives, and
+//!   hands inbound requests to upper layers under a numeric id used to
+//!   answer them.
+//! - Every message published or received is counted by a `BandwidthMeter`,
+//!   per topic and per peer.
+//! - AutoNAT status, observed and confirmed external addresses are kept by
+//!   a `ReachabilityTracker`; a private node listens through a relay.
 
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use futures::StreamExt;
 use libp2p::gossipsub::IdentTopic;
 use libp2p::swarm::SwarmEvent;
-use libp2p::{gossipsub, identify, kad, mdns, ping, Multiaddr, PeerId, Swarm};
+use libp2p::identity::Keypair;
+use libp2p::request_response::{self, InboundRequestId, OutboundRequestId, ResponseChannel};
+use libp2p::{autonat, gossipsub, identify, kad, mdns, ping, relay, Multiaddr, PeerId, Swarm};
 use tokio::sync::{mpsc, oneshot};
+use wws_protocol::topic_keys::TopicKeyring;
+use wws_protocol::{ProtocolError, SwarmMessage};
 
+use crate::bandwidth::{BandwidthMeter, BandwidthSnapshot, RateWarning};
 use crate::behaviour::{SwarmBehaviour, SwarmBehaviourEvent};
+use crate::direct;
 use crate::discovery::{DiscoveryConfig, DiscoveryManager};
+use crate::health::{ChannelBacklog, EventLoopLag, NetworkHealth, TopicMeshHealth};
+use crate::peer_scoring::{PeerBan, PeerOffense, PeerScoreConfig, PeerScoreSnapshot, PeerScorer};
+use crate::reachability::{is_relayed, NatState, Reachability, ReachabilityTracker};
 use crate::size_estimator::SwarmSizeEstimator;
-use crate::topics::TopicManager;
+use crate::topics::{MessagePriority, TopicManager};
 use crate::transport::{self, TransportConfig};
 use crate::NetworkError;
 
@@ -39,10 +60,58 @@ pub struct SwarmHostConfig {
     pub discovery: DiscoveryConfig,
     /// Command channel buffer size.
     pub command_buffer: usize,
+    /// Buffer size of the control-plane publish lane.
+    pub control_buffer: usize,
     /// Event channel buffer size.
     pub event_buffer: usize,
     /// Interval between Kademlia random walks.
     pub random_walk_interval: Duration,
+    /// Peer reputation and automatic ban parameters.
+    pub peer_scoring: PeerScoreConfig,
+    /// Identity of the node, fixing its PeerId across restarts. `None`
+    /// generates a fresh one.
+    pub keypair: Option<libp2p::identity::Keypair>,
+    /// Interval of the probe measuring event-loop lag.
+    pub lag_probe_interval: Duration,
+    /// How long `SwarmHandle::request` waits for a direct response.
+    pub request_timeout: Duration,
+    /// Rate in bytes per second above which a topic's traffic is reported
+    /// with `NetworkEvent::TopicRateExceeded`. `None` never reports.
+    pub topic_rate_limit: Option<u64>,
+    /// Encrypts gossip payloads before they are published and decrypts
+    /// them on receipt. `None` sends them as they are.
+    pub cipher: Option<Arc<dyn PayloadCipher>>,
+    /// Listen through a connected relay while AutoNAT finds this node
+    /// private.
+    pub auto_relay: bool,
+    /// Addresses this node is reachable at from outside, e.g. a port
+    /// forwarded to `listen_addr`. Advertised to peers as confirmed
+    /// external addresses from the start.
+    pub external_addrs: Vec<Multiaddr>,
+}
+
+/// Encryption of GossipSub payloads by topic, e.g. a private swarm's
+/// [`TopicKeyring`]. Direct requests are not passed through it; they are
+/// already encrypted point to point by the transport.
+pub trait PayloadCipher: Send + Sync + std::fmt::Debug {
+    /// The payload to publish on `topic` in place of `payload`.
+    fn seal(&self, topic: &str, payload: Vec<u8>) -> Result<Vec<u8>, ProtocolError>;
+    /// The payload a message received on `topic` carries.
+    fn open(&self, topic: &str, payload: Vec<u8>) -> Result<Vec<u8>, ProtocolError>;
+}
+
+impl PayloadCipher for std::sync::RwLock<TopicKeyring> {
+    fn seal(&self, topic: &str, payload: Vec<u8>) -> Result<Vec<u8>, ProtocolError> {
+        self.read()
+            .map_err(|_| ProtocolError::Crypto("topic keyring poisoned".into()))?
+            .seal(topic, payload)
+    }
+
+    fn open(&self, topic: &str, payload: Vec<u8>) -> Result<Vec<u8>, ProtocolError> {
+        self.read()
+            .map_err(|_| ProtocolError::Crypto("topic keyring poisoned".into()))?
+            .open(topic, payload)
+    }
 }
 
 impl Default for SwarmHostConfig {
@@ -54,8 +123,17 @@ impl Default for SwarmHostConfig {
             transport: TransportConfig::default(),
             discovery: DiscoveryConfig::default(),
             command_buffer: 256,
+            control_buffer: 64,
             event_buffer: 256,
             random_walk_interval: Duration::from_secs(30),
+            peer_scoring: PeerScoreConfig::default(),
+            keypair: None,
+            lag_probe_interval: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(10),
+            topic_rate_limit: None,
+            cipher: None,
+            auto_relay: true,
+            external_addrs: Vec::new(),
         }
     }
 }
@@ -95,6 +173,35 @@ pub enum NetworkEvent {
     },
     /// Swarm is now listening on an address.
     Listening(Multiaddr),
+    /// A peer was disconnected and banned, automatically or by request.
+    PeerBanned {
+        peer: PeerId,
+        duration: Duration,
+        reason: String,
+    },
+    /// A DHT lookup started with `get_dht_record` found a record.
+    DhtRecordFound {
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    /// A peer sent a direct request with a valid signature. Answer it with
+    /// `SwarmHandle::respond` using `request_id`.
+    DirectRequest {
+        peer: PeerId,
+        request_id: u64,
+        message: SwarmMessage,
+    },
+    /// A topic's traffic went over the configured rate.
+    TopicRateExceeded {
+        topic: String,
+        bytes_per_sec: f64,
+        limit: u64,
+    },
+    /// AutoNAT settled on a different reachability status.
+    ReachabilityChanged {
+        status: NatState,
+        public_address: Option<Multiaddr>,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -164,6 +271,45 @@ enum SwarmCommand {
         swarm_id: String,
         reply: oneshot::Sender<Result<(), NetworkError>>,
     },
+    ReportPeer {
+        peer: PeerId,
+        offense: PeerOffense,
+    },
+    BanPeer {
+        peer: PeerId,
+        duration: Duration,
+        reason: String,
+        reply: oneshot::Sender<()>,
+    },
+    UnbanPeer {
+        peer: PeerId,
+        reply: oneshot::Sender<bool>,
+    },
+    GetPeerScores {
+        reply: oneshot::Sender<Vec<PeerScoreSnapshot>>,
+    },
+    GetHealth {
+        reply: oneshot::Sender<NetworkHealth>,
+    },
+    GetBandwidth {
+        reply: oneshot::Sender<BandwidthSnapshot>,
+    },
+    GetReachability {
+        reply: oneshot::Sender<Reachability>,
+    },
+    GetSubscribedTopics {
+        reply: oneshot::Sender<Vec<String>>,
+    },
+    SendRequest {
+        peer: PeerId,
+        message: SwarmMessage,
+        reply: oneshot::Sender<Result<SwarmMessage, NetworkError>>,
+    },
+    SendResponse {
+        request_id: u64,
+        message: SwarmMessage,
+        reply: oneshot::Sender<Result<(), NetworkError>>,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -176,7 +322,9 @@ enum SwarmCommand {
 #[derive(Clone)]
 pub struct SwarmHandle {
     command_tx: mpsc::Sender<SwarmCommand>,
+    control_tx: mpsc::Sender<SwarmCommand>,
     local_peer_id: PeerId,
+    request_timeout: Duration,
 }
 
 impl SwarmHandle {
@@ -185,10 +333,26 @@ impl SwarmHandle {
         self.local_peer_id
     }
 
-    /// Publish data to a GossipSub topic.
+    /// Publish data to a GossipSub topic, queued by the topic's
+    /// [`MessagePriority`].
     pub async fn publish(&self, topic: &str, data: Vec<u8>) -> Result<(), NetworkError> {
+        self.publish_with_priority(topic, data, MessagePriority::for_topic(topic))
+            .await
+    }
+
+    /// Publish data to a GossipSub topic with an explicit priority.
+    pub async fn publish_with_priority(
+        &self,
+        topic: &str,
+        data: Vec<u8>,
+        priority: MessagePriority,
+    ) -> Result<(), NetworkError> {
+        let lane = match priority {
+            MessagePriority::Control => &self.control_tx,
+            MessagePriority::Data => &self.command_tx,
+        };
         let (tx, rx) = oneshot::channel();
-        self.command_tx
+        lane
             .send(SwarmCommand::Publish {
                 topic: topic.to_string(),
                 data,
@@ -337,8 +501,8 @@ impl SwarmHandle {
         rx.await.map_err(|_| NetworkError::ChannelClosed)?
     }
 
-    /// Initiate a DHT get_record query. The result will arrive asynchronously
-    /// via Kademlia events. This is a fire-and-forget initiation.
+    /// Initiate a DHT get_record query. Each record found arrives
+    /// asynchronously as a `NetworkEvent::DhtRecordFound`.
     pub async fn get_dht_record(&self, key: Vec<u8>) -> Result<(), NetworkError> {
         let (tx, rx) = oneshot::channel();
         self.command_tx
@@ -360,6 +524,160 @@ impl SwarmHandle {
             .map_err(|_| NetworkError::ChannelClosed)?;
         rx.await.map_err(|_| NetworkError::ChannelClosed)?
     }
+
+    /// Report misbehaviour by a peer. Peers whose score drops to the ban
+    /// threshold are disconnected and banned.
+    pub async fn report_peer(&self, peer: PeerId, offense: PeerOffense) -> Result<(), NetworkError> {
+        self.command_tx
+            .send(SwarmCommand::ReportPeer { peer, offense })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Disconnect a peer and refuse it for `duration`.
+    pub async fn ban_peer(
+        &self,
+        peer: PeerId,
+        duration: Duration,
+        reason: String,
+    ) -> Result<(), NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::BanPeer {
+                peer,
+                duration,
+                reason,
+                reply: tx,
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Lift a peer's ban. Returns whether the peer was banned.
+    pub async fn unban_peer(&self, peer: PeerId) -> Result<bool, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::UnbanPeer { peer, reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Reputation of every peer with a recorded offense or ban.
+    pub async fn peer_scores(&self) -> Result<Vec<PeerScoreSnapshot>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::GetPeerScores { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Every GossipSub topic this node is currently subscribed to.
+    pub async fn subscribed_topics(&self) -> Result<Vec<String>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::GetSubscribedTopics { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Health snapshot of the network layer, including the backlog of
+    /// this handle's command lanes. Sent on the control lane so it is
+    /// answered ahead of queued bulk commands.
+    pub async fn health(&self) -> Result<NetworkHealth, NetworkError> {
+        let lanes = [("commands", &self.command_tx), ("control", &self.control_tx)]
+            .map(|(name, tx)| ChannelBacklog {
+                name: name.to_string(),
+                queued: tx.max_capacity() - tx.capacity(),
+                capacity: tx.max_capacity(),
+            });
+        let (tx, rx) = oneshot::channel();
+        self.control_tx
+            .send(SwarmCommand::GetHealth { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        let mut health = rx.await.map_err(|_| NetworkError::ChannelClosed)?;
+        health.channels.extend(lanes);
+        Ok(health)
+    }
+
+    /// Bytes and messages sent and received per topic and per peer. Sent
+    /// on the control lane like `health`.
+    pub async fn bandwidth(&self) -> Result<BandwidthSnapshot, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.control_tx
+            .send(SwarmCommand::GetBandwidth { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// AutoNAT status, external and observed addresses and relay use. Sent
+    /// on the control lane like `health`.
+    pub async fn reachability(&self) -> Result<Reachability, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.control_tx
+            .send(SwarmCommand::GetReachability { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Send a direct request to `peer` and wait for its response, using the
+    /// configured default timeout.
+    pub async fn request(
+        &self,
+        peer: PeerId,
+        message: SwarmMessage,
+    ) -> Result<SwarmMessage, NetworkError> {
+        self.request_with_timeout(peer, message, self.request_timeout)
+            .await
+    }
+
+    /// Send a direct request to `peer`, dialing it if needed, and wait up
+    /// to `timeout` for its response. The request is signed by the host;
+    /// a response whose signature does not match `peer` is an error.
+    /// libp2p fails the request if another dial to `peer` is already in
+    /// flight, so callers racing discovery may retry once connected.
+    pub async fn request_with_timeout(
+        &self,
+        peer: PeerId,
+        message: SwarmMessage,
+        timeout: Duration,
+    ) -> Result<SwarmMessage, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::SendRequest {
+                peer,
+                message,
+                reply: tx,
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        tokio::time::timeout(timeout, rx)
+            .await
+            .map_err(|_| NetworkError::RequestTimeout)?
+            .map_err(|_| NetworkError::ChannelClosed)?
+    }
+
+    /// Answer a `NetworkEvent::DirectRequest`. The response is signed by
+    /// the host. Fails if the request already timed out or its connection
+    /// closed.
+    pub async fn respond(&self, request_id: u64, message: SwarmMessage) -> Result<(), NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::SendResponse {
+                request_id,
+                message,
+                reply: tx,
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)?
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -374,6 +692,7 @@ impl SwarmHandle {
 pub struct SwarmHost {
     swarm: Swarm<SwarmBehaviour>,
     command_rx: mpsc::Receiver<SwarmCommand>,
+    control_rx: mpsc::Receiver<SwarmCommand>,
     event_tx: mpsc::Sender<NetworkEvent>,
     topic_manager: TopicManager,
     discovery_manager: DiscoveryManager,
@@ -382,6 +701,26 @@ pub struct SwarmHost {
     peer_rtt: HashMap<PeerId, Duration>,
     /// Interval timer for Kademlia random walks.
     random_walk_interval: Duration,
+    /// Offense scores and bans per peer.
+    peer_scorer: PeerScorer,
+    /// Interval of the event-loop lag probe.
+    lag_probe_interval: Duration,
+    /// How late the lag probe has been firing.
+    event_loop_lag: EventLoopLag,
+    /// Identity key used to sign direct requests and responses.
+    keypair: Keypair,
+    /// Direct requests awaiting a response, by libp2p request id.
+    pending_requests: HashMap<OutboundRequestId, oneshot::Sender<Result<SwarmMessage, NetworkError>>>,
+    /// Direct requests handed to upper layers and not yet answered.
+    inbound_requests: HashMap<u64, (PeerId, InboundRequestId, ResponseChannel<SwarmMessage>)>,
+    /// Id given to the next inbound direct request.
+    next_inbound_request: u64,
+    /// Traffic per topic and per peer.
+    bandwidth: BandwidthMeter,
+    cipher: Option<Arc<dyn PayloadCipher>>,
+    /// AutoNAT status, observed addresses and relay use.
+    reachability: ReachabilityTracker,
+    auto_relay: bool,
 }
 
 impl SwarmHost {
@@ -393,17 +732,22 @@ impl SwarmHost {
     pub fn new(
         config: SwarmHostConfig,
     ) -> Result<(Self, SwarmHandle, mpsc::Receiver<NetworkEvent>), NetworkError> {
-        let mut swarm = transport::build_swarm(config.transport)?;
+        let keypair = config.keypair.unwrap_or_else(Keypair::generate_ed25519);
+        let mut swarm = transport::build_swarm_with_keypair(keypair.clone(), config.transport)?;
 
         // Start listening.
         swarm
             .listen_on(config.listen_addr.clone())
             .map_err(|e| NetworkError::ListenError(e.to_string()))?;
+        for addr in config.external_addrs {
+            swarm.add_external_address(addr);
+        }
 
         let local_peer_id = *swarm.local_peer_id();
         tracing::info!(peer_id = %local_peer_id, "Local peer ID");
 
         let (command_tx, command_rx) = mpsc::channel(config.command_buffer);
+        let (control_tx, control_rx) = mpsc::channel(config.control_buffer);
         let (event_tx, event_rx) = mpsc::channel(config.event_buffer);
 
         let discovery_manager = DiscoveryManager::new(config.discovery);
@@ -413,17 +757,31 @@ impl SwarmHost {
         let host = Self {
             swarm,
             command_rx,
+            control_rx,
             event_tx,
             topic_manager,
             discovery_manager,
             size_estimator,
             peer_rtt: HashMap::new(),
             random_walk_interval: config.random_walk_interval,
+            peer_scorer: PeerScorer::new(config.peer_scoring),
+            lag_probe_interval: config.lag_probe_interval,
+            event_loop_lag: EventLoopLag::default(),
+            keypair,
+            pending_requests: HashMap::new(),
+            inbound_requests: HashMap::new(),
+            next_inbound_request: 0,
+            bandwidth: BandwidthMeter::new(config.topic_rate_limit, Instant::now()),
+            cipher: config.cipher,
+            reachability: ReachabilityTracker::default(),
+            auto_relay: config.auto_relay,
         };
 
         let handle = SwarmHandle {
             command_tx,
+            control_tx,
             local_peer_id,
+            request_timeout: config.request_timeout,
         };
 
         Ok((host, handle, event_rx))
@@ -440,13 +798,15 @@ impl SwarmHost {
 
         let mut walk_interval = tokio::time::interval(self.random_walk_interval);
         walk_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut lag_probe = tokio::time::interval(self.lag_probe_interval);
+        lag_probe.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         loop {
             tokio::select! {
                 event = self.swarm.select_next_some() => {
                     self.handle_swarm_event(event).await;
                 }
-                Some(cmd) = self.command_rx.recv() => {
+                Some(cmd) = next_command(&mut self.control_rx, &mut self.command_rx) => {
                     self.handle_command(cmd).await;
                 }
                 _ = walk_interval.tick() => {
@@ -456,6 +816,14 @@ impl SwarmHost {
                     // Update size estimate from connected peer count.
                     let peer_count = self.swarm.connected_peers().count();
                     self.size_estimator.update_from_peer_count(peer_count);
+                    for peer in self.peer_scorer.expire_bans(Instant::now()) {
+                        tracing::info!(peer = %peer, "Peer ban expired");
+                        self.swarm.behaviour_mut().gossipsub.remove_blacklisted_peer(&peer);
+                    }
+                }
+                scheduled = lag_probe.tick() => {
+                    self.event_loop_lag
+                        .record(tokio::time::Instant::now().saturating_duration_since(scheduled));
                 }
             }
         }
@@ -471,6 +839,11 @@ impl SwarmHost {
             SwarmEvent::ConnectionEstablished {
                 peer_id, endpoint, ..
             } => {
+                if self.peer_scorer.is_banned(&peer_id, Instant::now()) {
+                    tracing::debug!(peer = %peer_id, "Refusing connection from banned peer");
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return;
+                }
                 tracing::info!(
                     peer = %peer_id,
                     endpoint = ?endpoint,
@@ -479,13 +852,25 @@ impl SwarmHost {
                 self.discovery_manager.add_peer(peer_id);
                 let _ = self.event_tx.send(NetworkEvent::PeerConnected(peer_id)).await;
             }
-            SwarmEvent::ConnectionClosed { peer_id, cause, .. } => {
+            SwarmEvent::ConnectionClosed {
+                peer_id,
+                cause,
+                num_established,
+                ..
+            } => {
                 tracing::info!(
                     peer = %peer_id,
                     cause = ?cause,
                     "Connection closed"
                 );
                 self.peer_rtt.remove(&peer_id);
+                if num_established == 0 {
+                    self.bandwidth.forget_peer(&peer_id);
+                    if self.reachability.remove_peer(&peer_id) {
+                        tracing::info!(relay = %peer_id, "Lost connection to relay");
+                        self.update_relay();
+                    }
+                }
                 let _ = self
                     .event_tx
                     .send(NetworkEvent::PeerDisconnected(peer_id))
@@ -506,6 +891,9 @@ impl SwarmHost {
                 message,
                 ..
             }) => {
+                if self.peer_scorer.is_banned(&propagation_source, Instant::now()) {
+                    return;
+                }
                 let topic_str = self
                     .topic_manager
                     .resolve_topic(&message.topic)
@@ -518,13 +906,35 @@ impl SwarmHost {
                     bytes = message.data.len(),
                     "GossipSub message received"
                 );
+                let warning = self.bandwidth.record_inbound(
+                    &topic_str,
+                    propagation_source,
+                    message.data.len(),
+                    Instant::now(),
+                );
+                self.warn_rate(warning).await;
 
+                let data = match &self.cipher {
+                    Some(cipher) => match cipher.open(&topic_str, message.data) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            tracing::debug!(
+                                source = %propagation_source,
+                                topic = %topic_str,
+                                error = %e,
+                                "Dropping message that cannot be decrypted"
+                            );
+                            return;
+                        }
+                    },
+                    None => message.data,
+                };
                 let _ = self
                     .event_tx
                     .send(NetworkEvent::MessageReceived {
                         source: propagation_source,
                         topic: topic_str,
-                        data: message.data,
+                        data,
                     })
                     .await;
             }
@@ -558,8 +968,20 @@ impl SwarmHost {
             SwarmBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
                 result, ..
             }) => {
-                if let kad::QueryResult::Bootstrap(Ok(_)) = result {
-                    self.discovery_manager.on_bootstrap_complete();
+                match result {
+                    kad::QueryResult::Bootstrap(Ok(_)) => {
+                        self.discovery_manager.on_bootstrap_complete();
+                    }
+                    kad::QueryResult::GetRecord(Ok(kad::GetRecordOk::FoundRecord(found))) => {
+                        let _ = self
+                            .event_tx
+                            .send(NetworkEvent::DhtRecordFound {
+                                key: found.record.key.to_vec(),
+                                value: found.record.value,
+                            })
+                            .await;
+                    }
+                    _ => {}
                 }
             }
             SwarmBehaviourEvent::Identify(identify::Event::Received {
@@ -577,6 +999,13 @@ impl SwarmHost {
                         .kademlia
                         .add_address(&peer_id, addr.clone());
                 }
+                self.reachability.observe(info.observed_addr.clone());
+                if info.protocols.contains(&relay::HOP_PROTOCOL_NAME) {
+                    if let Some(addr) = info.listen_addrs.iter().find(|a| !is_relayed(a)) {
+                        self.reachability.add_relay_candidate(peer_id, addr.clone());
+                        self.update_relay();
+                    }
+                }
                 let _ = self
                     .event_tx
                     .send(NetworkEvent::PeerIdentified {
@@ -597,16 +1026,162 @@ impl SwarmHost {
                     .send(NetworkEvent::PingRtt { peer, rtt })
                     .await;
             }
+            SwarmBehaviourEvent::Autonat(autonat::Event::StatusChanged { new, .. }) => {
+                if !self.reachability.set_status(&new) {
+                    return;
+                }
+                tracing::info!(status = ?new, "Reachability changed");
+                self.update_relay();
+                let _ = self
+                    .event_tx
+                    .send(NetworkEvent::ReachabilityChanged {
+                        status: self.reachability.status(),
+                        public_address: self.swarm.behaviour().autonat.public_address().cloned(),
+                    })
+                    .await;
+            }
+            SwarmBehaviourEvent::RelayClient(relay::client::Event::ReservationReqAccepted {
+                relay_peer_id,
+                renewal: false,
+                ..
+            }) => {
+                tracing::info!(relay = %relay_peer_id, "Relay reservation accepted");
+                self.reachability.relay_reserved(&relay_peer_id);
+            }
+            SwarmBehaviourEvent::Direct(event) => self.handle_direct_event(event).await,
             _ => {}
         }
     }
 
+    /// Listen through a relay while private, and stop once public.
+    fn update_relay(&mut self) {
+        if let Some(listener) = self.reachability.relay_to_drop() {
+            self.swarm.remove_listener(listener);
+        }
+        if !self.auto_relay {
+            return;
+        }
+        let Some((relay, addr)) = self.reachability.relay_to_use() else {
+            return;
+        };
+        match self.swarm.listen_on(addr.clone()) {
+            Ok(listener) => {
+                tracing::info!(relay = %relay, addr = %addr, "Listening through relay");
+                self.reachability.relay_started(relay, listener);
+            }
+            Err(e) => {
+                tracing::warn!(relay = %relay, error = %e, "Cannot listen through relay");
+                self.reachability.remove_peer(&relay);
+                self.update_relay();
+            }
+        }
+    }
+
+    async fn handle_direct_event(
+        &mut self,
+        event: request_response::Event<SwarmMessage, SwarmMessage>,
+    ) {
+        match event {
+            request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Request {
+                        request_id,
+                        request,
+                        channel,
+                    },
+            } => {
+                if self.peer_scorer.is_banned(&peer, Instant::now()) {
+                    return;
+                }
+                if !direct::verify_message(&peer, &request) {
+                    tracing::warn!(peer = %peer, method = %request.method, "Dropping direct request with invalid signature");
+                    self.penalize(peer, PeerOffense::InvalidSignature).await;
+                    return;
+                }
+                let warning = self.bandwidth.record_inbound(
+                    direct::DIRECT_PROTOCOL,
+                    peer,
+                    direct::encoded_len(&request),
+                    Instant::now(),
+                );
+                self.warn_rate(warning).await;
+                let id = self.next_inbound_request;
+                self.next_inbound_request += 1;
+                self.inbound_requests.insert(id, (peer, request_id, channel));
+                let _ = self
+                    .event_tx
+                    .send(NetworkEvent::DirectRequest {
+                        peer,
+                        request_id: id,
+                        message: request,
+                    })
+                    .await;
+            }
+            request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Response {
+                        request_id,
+                        response,
+                    },
+            } => {
+                let warning = self.bandwidth.record_inbound(
+                    direct::DIRECT_PROTOCOL,
+                    peer,
+                    direct::encoded_len(&response),
+                    Instant::now(),
+                );
+                self.warn_rate(warning).await;
+                let Some(reply) = self.pending_requests.remove(&request_id) else {
+                    return;
+                };
+                if direct::verify_message(&peer, &response) {
+                    let _ = reply.send(Ok(response));
+                } else {
+                    let _ = reply.send(Err(NetworkError::RequestFailed(
+                        "response signature does not match the peer".to_string(),
+                    )));
+                    self.penalize(peer, PeerOffense::InvalidSignature).await;
+                }
+            }
+            request_response::Event::OutboundFailure {
+                peer,
+                request_id,
+                error,
+            } => {
+                tracing::debug!(peer = %peer, error = %error, "Direct request failed");
+                if let Some(reply) = self.pending_requests.remove(&request_id) {
+                    let _ = reply.send(Err(match error {
+                        request_response::OutboundFailure::Timeout => NetworkError::RequestTimeout,
+                        other => NetworkError::RequestFailed(other.to_string()),
+                    }));
+                }
+            }
+            request_response::Event::InboundFailure {
+                peer,
+                request_id,
+                error,
+            } => {
+                tracing::debug!(peer = %peer, error = %error, "Inbound direct request failed");
+                self.inbound_requests.retain(|_, (_, id, _)| *id != request_id);
+            }
+            request_response::Event::ResponseSent { .. } => {}
+        }
+    }
+
     // ---- Command Handling ----
 
     async fn handle_command(&mut self, cmd: SwarmCommand) {
         match cmd {
             SwarmCommand::Publish { topic, data, reply } => {
+                let bytes = data.len();
                 let result = self.publish_message(&topic, data);
+                if result.is_ok() {
+                    let warning =
+                        self.bandwidth.record_outbound(&topic, None, bytes, Instant::now());
+                    self.warn_rate(warning).await;
+                }
                 let _ = reply.send(result);
             }
             SwarmCommand::Subscribe { topic, reply } => {
@@ -721,11 +1296,222 @@ impl SwarmHost {
                     );
                 let _ = reply.send(result);
             }
+            SwarmCommand::ReportPeer { peer, offense } => {
+                tracing::debug!(peer = %peer, offense = ?offense, "Peer offense reported");
+                self.penalize(peer, offense).await;
+            }
+            SwarmCommand::BanPeer {
+                peer,
+                duration,
+                reason,
+                reply,
+            } => {
+                let ban = self.peer_scorer.ban(peer, duration, reason, Instant::now());
+                self.enforce_ban(ban).await;
+                let _ = reply.send(());
+            }
+            SwarmCommand::UnbanPeer { peer, reply } => {
+                let was_banned = self.peer_scorer.unban(&peer, Instant::now());
+                if was_banned {
+                    self.swarm.behaviour_mut().gossipsub.remove_blacklisted_peer(&peer);
+                }
+                let _ = reply.send(was_banned);
+            }
+            SwarmCommand::GetPeerScores { reply } => {
+                let _ = reply.send(self.peer_scorer.snapshot(Instant::now()));
+            }
+            SwarmCommand::GetHealth { reply } => {
+                let _ = reply.send(self.health_snapshot());
+            }
+            SwarmCommand::GetReachability { reply } => {
+                let autonat = &self.swarm.behaviour().autonat;
+                let snapshot = self
+                    .reachability
+                    .snapshot(autonat.confidence(), self.swarm.external_addresses());
+                let _ = reply.send(snapshot);
+            }
+            SwarmCommand::GetBandwidth { reply } => {
+                let _ = reply.send(self.bandwidth.snapshot(Instant::now()));
+            }
+            SwarmCommand::GetSubscribedTopics { reply } => {
+                let topics = self
+                    .swarm
+                    .behaviour()
+                    .gossipsub
+                    .topics()
+                    .map(|hash| {
+                        self.topic_manager
+                            .resolve_topic(hash)
+                            .map(|t| t.to_string())
+                            .unwrap_or_else(|| hash.to_string())
+                    })
+                    .collect();
+                let _ = reply.send(topics);
+            }
+            SwarmCommand::SendRequest {
+                peer,
+                mut message,
+                reply,
+            } => {
+                direct::sign_message(&self.keypair, &mut message);
+                let warning = self.bandwidth.record_outbound(
+                    direct::DIRECT_PROTOCOL,
+                    Some(peer),
+                    direct::encoded_len(&message),
+                    Instant::now(),
+                );
+                self.warn_rate(warning).await;
+                let request_id = self.swarm.behaviour_mut().direct.send_request(&peer, message);
+                self.pending_requests.insert(request_id, reply);
+            }
+            SwarmCommand::SendResponse {
+                request_id,
+                mut message,
+                reply,
+            } => {
+                let Some((peer, _, channel)) = self.inbound_requests.remove(&request_id) else {
+                    let _ = reply.send(Err(NetworkError::RequestFailed(format!(
+                        "no open direct request {request_id}"
+                    ))));
+                    return;
+                };
+                direct::sign_message(&self.keypair, &mut message);
+                let bytes = direct::encoded_len(&message);
+                let result = self
+                    .swarm
+                    .behaviour_mut()
+                    .direct
+                    .send_response(channel, message)
+                    .map_err(|_| {
+                        NetworkError::RequestFailed("requester is no longer waiting".to_string())
+                    });
+                if result.is_ok() {
+                    let warning = self.bandwidth.record_outbound(
+                        direct::DIRECT_PROTOCOL,
+                        Some(peer),
+                        bytes,
+                        Instant::now(),
+                    );
+                    self.warn_rate(warning).await;
+                }
+                let _ = reply.send(result);
+            }
+        }
+    }
+
+    /// Gather mesh, DHT, bootstrap, lag and event-channel diagnostics.
+    fn health_snapshot(&mut self) -> NetworkHealth {
+        let gossipsub = &self.swarm.behaviour().gossipsub;
+        let mut topics: Vec<TopicMeshHealth> = gossipsub
+            .topics()
+            .map(|hash| TopicMeshHealth {
+                topic: self
+                    .topic_manager
+                    .resolve_topic(hash)
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| hash.to_string()),
+                mesh_peers: gossipsub.mesh_peers(hash).count(),
+                subscribed_peers: gossipsub
+                    .all_peers()
+                    .filter(|(_, peer_topics)| peer_topics.contains(&hash))
+                    .count(),
+            })
+            .collect();
+        topics.sort_by(|a, b| a.topic.cmp(&b.topic));
+
+        let routing_table_size = self
+            .swarm
+            .behaviour_mut()
+            .kademlia
+            .kbuckets()
+            .map(|bucket| bucket.num_entries())
+            .sum();
+        let bootstrap_ids: Vec<PeerId> = self
+            .discovery_manager
+            .config()
+            .bootstrap_peers
+            .iter()
+            .map(|(id, _)| *id)
+            .collect();
+        let bootstrap_connected = bootstrap_ids
+            .iter()
+            .filter(|id| self.swarm.is_connected(id))
+            .count();
+
+        NetworkHealth {
+            connected_peers: self.swarm.connected_peers().count(),
+            topics,
+            routing_table_size,
+            bootstrap_peers: bootstrap_ids.len(),
+            bootstrap_connected,
+            bootstrap_complete: self.discovery_manager.is_bootstrap_complete(),
+            event_loop_lag_ms: self.event_loop_lag.last().as_millis() as u64,
+            event_loop_lag_peak_ms: self.event_loop_lag.take_peak().as_millis() as u64,
+            channels: vec![ChannelBacklog {
+                name: "events".to_string(),
+                queued: self.event_tx.max_capacity() - self.event_tx.capacity(),
+                capacity: self.event_tx.max_capacity(),
+            }],
         }
     }
 
+    /// Log a topic that went over the configured rate and tell upper layers.
+    async fn warn_rate(&mut self, warning: Option<RateWarning>) {
+        let Some(warning) = warning else {
+            return;
+        };
+        tracing::warn!(
+            topic = %warning.topic,
+            bytes_per_sec = warning.bytes_per_sec,
+            limit = warning.limit,
+            "Topic traffic over configured rate"
+        );
+        let _ = self
+            .event_tx
+            .send(NetworkEvent::TopicRateExceeded {
+                topic: warning.topic,
+                bytes_per_sec: warning.bytes_per_sec,
+                limit: warning.limit,
+            })
+            .await;
+    }
+
+    /// Record an offense and enforce the ban it triggers, if any.
+    async fn penalize(&mut self, peer: PeerId, offense: PeerOffense) {
+        if let Some(ban) = self.peer_scorer.report(peer, offense, Instant::now()) {
+            self.enforce_ban(ban).await;
+        }
+    }
+
+    /// Blacklist a banned peer in GossipSub, drop its connections and tell
+    /// upper layers.
+    async fn enforce_ban(&mut self, ban: PeerBan) {
+        tracing::warn!(
+            peer = %ban.peer,
+            duration_secs = ban.duration.as_secs(),
+            reason = %ban.reason,
+            "Banning peer"
+        );
+        self.swarm.behaviour_mut().gossipsub.blacklist_peer(&ban.peer);
+        let _ = self.swarm.disconnect_peer_id(ban.peer);
+        let _ = self
+            .event_tx
+            .send(NetworkEvent::PeerBanned {
+                peer: ban.peer,
+                duration: ban.duration,
+                reason: ban.reason,
+            })
+            .await;
+    }
+
     /// Internal helper to publish a message to a GossipSub topic.
     fn publish_message(&mut self, topic_str: &str, data: Vec<u8>) -> Result<(), NetworkError> {
+        let data = match &self.cipher {
+            Some(cipher) => cipher
+                .seal(topic_str, data)
+                .map_err(|e| NetworkError::PublishError(e.to_string()))?,
+            None => data,
+        };
         let topic = IdentTopic::new(topic_str);
         self.swarm
             .behaviour_mut()
@@ -740,3 +1526,17 @@ impl SwarmHost {
         &self.peer_rtt
     }
 }
+
+/// Receive the next command, taking queued control-plane commands first.
+/// Resolves to `None` once both channels are closed.
+async fn next_command(
+    control_rx: &mut mpsc::Receiver<SwarmCommand>,
+    command_rx: &mut mpsc::Receiver<SwarmCommand>,
+) -> Option<SwarmCommand> {
+    tokio::select! {
+        biased;
+        Some(cmd) = control_rx.recv() => Some(cmd),
+        Some(cmd) = command_rx.recv() => Some(cmd),
+        else => None,
+    }
+}
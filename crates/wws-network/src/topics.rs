@@ -10,6 +10,34 @@ use libp2p::gossipsub::{self, IdentTopic, TopicHash};
 
 use crate::NetworkError;
 
+/// Queueing priority of an outgoing message.
+///
+/// Control-plane messages keep consensus moving and are sent ahead of
+/// queued data-plane messages when the network handle is congested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessagePriority {
+    /// Keepalives, elections, proposals, votes, task assignments, board
+    /// formation and swarm parameters.
+    Control,
+    /// Results, direct messages, swarm announcements and any topic outside
+    /// the protocol namespace.
+    Data,
+}
+
+impl MessagePriority {
+    /// Priority of messages published on `topic`.
+    pub fn for_topic(topic: &str) -> Self {
+        let Some(path) = topic.strip_prefix(wws_protocol::TOPIC_PREFIX) else {
+            return MessagePriority::Data;
+        };
+        let mut segments = path.trim_start_matches('/').split('/');
+        match (segments.next(), segments.nth(1)) {
+            (Some("s"), Some(kind)) if !matches!(kind, "results" | "messages") => MessagePriority::Control,
+            _ => MessagePriority::Data,
+        }
+    }
+}
+
 /// Manages GossipSub topic subscriptions for a WWS node.
 ///
 /// Topics are organized by protocol function. Each topic is tracked
@@ -78,26 +106,36 @@ impl TopicManager {
 
     /// Subscribe to the core set of protocol topics that every node needs.
     ///
-    /// This includes: global swarm discovery, plus the default public swarm's
-    /// election, keepalive, and hierarchy topics.
+    /// This includes: global swarm discovery and name claims, plus the default
+    /// public swarm's election, keepalive, and hierarchy topics.
     pub fn subscribe_core_topics(
         &mut self,
         gossipsub: &mut gossipsub::Behaviour,
     ) -> Result<(), NetworkError> {
-        use wws_protocol::SwarmTopics;
-
-        // Global swarm discovery topic (shared across all swarms).
-        self.subscribe(gossipsub, &SwarmTopics::swarm_discovery())?;
-
-        // Default public swarm core topics.
-        self.subscribe(gossipsub, &SwarmTopics::election_tier1())?;
-        self.subscribe(gossipsub, &SwarmTopics::keepalive())?;
-        self.subscribe(gossipsub, &SwarmTopics::hierarchy())?;
+        for topic in Self::core_topics() {
+            self.subscribe(gossipsub, &topic)?;
+        }
 
         tracing::info!("Subscribed to core protocol topics");
         Ok(())
     }
 
+    /// The core topics subscribed by [`Self::subscribe_core_topics`].
+    pub fn core_topics() -> Vec<String> {
+        use wws_protocol::SwarmTopics;
+
+        vec![
+            // Global swarm discovery topic (shared across all swarms).
+            SwarmTopics::swarm_discovery(),
+            // Global wws:// name directory.
+            SwarmTopics::names(),
+            // Default public swarm core topics.
+            SwarmTopics::election_tier1(),
+            SwarmTopics::keepalive(),
+            SwarmTopics::hierarchy(),
+        ]
+    }
+
     /// Subscribe to all protocol topics for a specific swarm.
     ///
     /// This includes the swarm's election, keepalive, hierarchy, and
@@ -113,6 +151,7 @@ impl TopicManager {
         self.subscribe(gossipsub, &SwarmTopics::election_tier1_for(swarm_id))?;
         self.subscribe(gossipsub, &SwarmTopics::keepalive_for(swarm_id))?;
         self.subscribe(gossipsub, &SwarmTopics::hierarchy_for(swarm_id))?;
+        self.subscribe(gossipsub, &SwarmTopics::params_for(swarm_id))?;
 
         tracing::info!(swarm_id, "Subscribed to swarm-specific topics");
         Ok(())
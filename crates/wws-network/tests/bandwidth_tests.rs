@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use wws_network::bandwidth::*;
+use wws_network::PeerId;
+
+#[test]
+fn test_traffic_is_counted_per_topic_and_peer() {
+    let mut meter = BandwidthMeter::new(None, Instant::now());
+    let (a, b) = (PeerId::random(), PeerId::random());
+    let now = Instant::now();
+
+    meter.record_inbound("/wws/results", a, 300, now);
+    meter.record_inbound("/wws/results", b, 100, now);
+    meter.record_inbound("/wws/keepalive", a, 20, now);
+    meter.record_outbound("/wws/results", None, 50, now);
+    meter.record_outbound("/wws/direct/1.0.0", Some(b), 500, now);
+
+    let snapshot = meter.snapshot(now);
+    assert_eq!(snapshot.total.bytes_in, 420);
+    assert_eq!(snapshot.total.bytes_out, 550);
+    assert_eq!(snapshot.total.messages_in, 3);
+    let topics: Vec<&str> = snapshot.topics.iter().map(|t| t.topic.as_str()).collect();
+    assert_eq!(topics, ["/wws/direct/1.0.0", "/wws/results", "/wws/keepalive"]);
+    assert_eq!(snapshot.topics[1].counters.messages_in, 2);
+    assert_eq!(snapshot.topics[1].counters.bytes_out, 50);
+    assert_eq!(snapshot.peers[0].peer_id, b.to_string());
+    assert_eq!(snapshot.peers[0].counters.bytes_out, 500);
+    assert_eq!(snapshot.peers[1].counters.bytes_in, 320);
+
+    meter.forget_peer(&b);
+    let snapshot = meter.snapshot(now);
+    assert_eq!(snapshot.peers.len(), 1);
+    assert_eq!(snapshot.total.bytes_out, 550);
+}
+
+#[test]
+fn test_topic_over_rate_warns_once_per_burst() {
+    let start = Instant::now();
+    let mut meter = BandwidthMeter::new(Some(100), start);
+    let peer = PeerId::random();
+    let window = RATE_WINDOW.as_secs() as usize;
+
+    // 100 B/s over a window is at the limit, not over it.
+    assert_eq!(meter.record_inbound("/wws/bulk", peer, 100 * window, start), None);
+    let warning = meter.record_inbound("/wws/bulk", peer, 10, start).unwrap();
+    assert_eq!(warning.topic, "/wws/bulk");
+    assert_eq!(warning.limit, 100);
+    assert!(meter.record_outbound("/wws/bulk", None, 5000, start).is_none());
+
+    // Still over in the next window: no new warning.
+    let next = start + RATE_WINDOW;
+    assert!(meter.record_inbound("/wws/bulk", peer, 5000, next).is_none());
+    let snapshot = meter.snapshot(next);
+    assert!(snapshot.topics[0].over_limit);
+    assert!(snapshot.topics[0].bytes_per_sec > 100.0);
+
+    // A quiet window clears it, so the next burst warns again.
+    let quiet = next + RATE_WINDOW;
+    assert!(meter.record_inbound("/wws/bulk", peer, 10, quiet).is_none());
+    let after = quiet + RATE_WINDOW;
+    assert!(!meter.snapshot(after).topics[0].over_limit);
+    assert!(meter.record_inbound("/wws/bulk", peer, 5000, after).is_some());
+    assert_eq!(
+        meter.snapshot(after + Duration::from_secs(60)).topics[0].bytes_per_sec,
+        0.0
+    );
+}
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use serde_json::json;
+use wws_network::direct::{sign_message, verify_message};
+use tokio::sync::mpsc;
+use wws_network::{Keypair, NetworkError, NetworkEvent, PeerId, SwarmHandle, SwarmHost, SwarmHostConfig};
+use wws_protocol::SwarmMessage;
+
+fn local_config() -> SwarmHostConfig {
+    SwarmHostConfig {
+        listen_addr: "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+        ..Default::default()
+    }
+}
+
+/// Dial the server from the client and wait until the connection is up.
+async fn connect(
+    client: &SwarmHandle,
+    server: &SwarmHandle,
+    client_events: &mut mpsc::Receiver<NetworkEvent>,
+    server_events: &mut mpsc::Receiver<NetworkEvent>,
+) -> PeerId {
+    let server_addr = loop {
+        if let NetworkEvent::Listening(addr) = server_events.recv().await.unwrap() {
+            break addr;
+        }
+    };
+    client.dial(server_addr).await.unwrap();
+    // mDNS may connect other hosts of the test run first.
+    let server_id = server.local_peer_id();
+    loop {
+        if let NetworkEvent::PeerConnected(peer) = client_events.recv().await.unwrap() {
+            if peer == server_id {
+                return peer;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_signature_binds_message_to_peer() {
+    let keypair = Keypair::generate_ed25519();
+    let peer = keypair.public().to_peer_id();
+    let mut message = SwarmMessage::new("state.sync", json!({"task_id": "t1"}), String::new());
+    sign_message(&keypair, &mut message);
+    assert!(verify_message(&peer, &message));
+
+    let other = Keypair::generate_ed25519().public().to_peer_id();
+    assert!(!verify_message(&other, &message));
+
+    message.params = json!({"task_id": "t2"});
+    assert!(!verify_message(&peer, &message));
+}
+
+#[tokio::test]
+async fn test_request_and_response_between_hosts() {
+    let (server, server_handle, mut server_events) = SwarmHost::new(local_config()).unwrap();
+    let (client, client_handle, mut client_events) = SwarmHost::new(local_config()).unwrap();
+    tokio::spawn(server.run());
+    tokio::spawn(client.run());
+
+    let server_id = connect(&client_handle, &server_handle, &mut client_events, &mut server_events).await;
+    let client_id = client_handle.local_peer_id();
+
+    let responder = tokio::spawn(async move {
+        loop {
+            if let NetworkEvent::DirectRequest {
+                peer,
+                request_id,
+                message,
+            } = server_events.recv().await.unwrap()
+            {
+                assert_eq!(peer, client_id);
+                assert_eq!(message.method, "artifact.fetch");
+                let reply = SwarmMessage::new("direct.ack", json!({"accepted": true}), String::new());
+                server_handle.respond(request_id, reply).await.unwrap();
+                return server_handle;
+            }
+        }
+    });
+
+    let request = SwarmMessage::new("artifact.fetch", json!({"cid": "abc"}), String::new());
+    let response = client_handle
+        .request_with_timeout(server_id, request, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(response.method, "direct.ack");
+    assert!(verify_message(&server_id, &response));
+
+    let server_handle = responder.await.unwrap();
+    let stale = SwarmMessage::new("direct.ack", json!({}), String::new());
+    assert!(matches!(
+        server_handle.respond(0, stale).await,
+        Err(NetworkError::RequestFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_unanswered_request_times_out() {
+    let (server, server_handle, mut server_events) = SwarmHost::new(local_config()).unwrap();
+    let (client, client_handle, mut client_events) = SwarmHost::new(local_config()).unwrap();
+    tokio::spawn(server.run());
+    tokio::spawn(client.run());
+
+    let server_id = connect(&client_handle, &server_handle, &mut client_events, &mut server_events).await;
+
+    let request = SwarmMessage::new("state.sync", json!({}), String::new());
+    let result = client_handle
+        .request_with_timeout(server_id, request, Duration::from_millis(500))
+        .await;
+    assert!(matches!(result, Err(NetworkError::RequestTimeout)));
+}
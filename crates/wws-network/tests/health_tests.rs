@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use wws_network::{SwarmHost, SwarmHostConfig};
+
+#[tokio::test]
+async fn test_health_reports_subscriptions_and_lanes() {
+    let config = SwarmHostConfig {
+        listen_addr: "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+        ..Default::default()
+    };
+    let (host, handle, _events) = SwarmHost::new(config).unwrap();
+    tokio::spawn(host.run());
+
+    handle.subscribe("/wws/test/health").await.unwrap();
+    let health = tokio::time::timeout(Duration::from_secs(5), handle.health())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(health.connected_peers, 0);
+    assert_eq!(health.bootstrap_peers, 0);
+    let topic = health
+        .topics
+        .iter()
+        .find(|t| t.topic == "/wws/test/health")
+        .expect("subscribed topic is reported");
+    assert_eq!(topic.mesh_peers, 0);
+    let lanes: Vec<&str> = health.channels.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(lanes, ["events", "commands", "control"]);
+    assert!(health.channels.iter().all(|c| c.queued <= c.capacity));
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use wws_network::peer_scoring::*;
+use wws_network::PeerId;
+
+#[test]
+fn test_repeat_offender_is_banned_for_longer() {
+    let mut scorer = PeerScorer::default();
+    let peer = PeerId::random();
+    let now = Instant::now();
+
+    for _ in 0..4 {
+        assert_eq!(scorer.report(peer, PeerOffense::ProtocolViolation, now), None);
+    }
+    let ban = scorer.report(peer, PeerOffense::ProtocolViolation, now).unwrap();
+    assert_eq!(ban.duration, Duration::from_secs(600));
+    assert!(scorer.is_banned(&peer, now));
+
+    let after_ban = now + Duration::from_secs(601);
+    assert_eq!(scorer.expire_bans(after_ban), vec![peer]);
+    assert!(!scorer.is_banned(&peer, after_ban));
+
+    scorer.report(peer, PeerOffense::InvalidSignature, after_ban);
+    let second = scorer.report(peer, PeerOffense::InvalidSignature, after_ban).unwrap();
+    assert_eq!(second.duration, Duration::from_secs(1200));
+}
+
+#[test]
+fn test_score_recovers_over_time() {
+    let mut scorer = PeerScorer::default();
+    let peer = PeerId::random();
+    let now = Instant::now();
+
+    scorer.report(peer, PeerOffense::ParseError, now);
+    assert_eq!(scorer.snapshot(now)[0].score, -10.0);
+    let later = now + Duration::from_secs(60);
+    assert_eq!(scorer.snapshot(later)[0].score, -5.0);
+    assert_eq!(scorer.snapshot(later + Duration::from_secs(600))[0].score, 0.0);
+}
+
+#[test]
+fn test_manual_ban_and_unban() {
+    let mut scorer = PeerScorer::default();
+    let peer = PeerId::random();
+    let now = Instant::now();
+
+    scorer.ban(peer, Duration::from_secs(30), "spam".into(), now);
+    let snapshot = &scorer.snapshot(now)[0];
+    assert!(snapshot.banned);
+    assert_eq!(snapshot.ban_remaining_secs, Some(30));
+    assert_eq!(snapshot.ban_reason.as_deref(), Some("spam"));
+
+    assert!(scorer.unban(&peer, now));
+    assert!(!scorer.unban(&peer, now));
+    assert!(!scorer.is_banned(&peer, now));
+}
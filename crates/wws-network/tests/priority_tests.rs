@@ -0,0 +1,31 @@
+use wws_network::MessagePriority;
+use wws_protocol::SwarmTopics;
+
+#[test]
+fn test_consensus_topics_are_control_plane() {
+    for topic in [
+        SwarmTopics::keepalive(),
+        SwarmTopics::election_tier1_for("s1"),
+        SwarmTopics::proposals_for("s1", "t1"),
+        SwarmTopics::voting_for("s1", "t1"),
+        SwarmTopics::tasks_for("s1", 2),
+        SwarmTopics::hierarchy_for("s1"),
+        SwarmTopics::board_for("s1", "t1"),
+        SwarmTopics::params_for("s1"),
+    ] {
+        assert_eq!(MessagePriority::for_topic(&topic), MessagePriority::Control, "{topic}");
+    }
+}
+
+#[test]
+fn test_bulk_topics_are_data_plane() {
+    for topic in [
+        SwarmTopics::results_for("s1", "t1"),
+        SwarmTopics::messages_for("s1"),
+        SwarmTopics::swarm_discovery(),
+        SwarmTopics::swarm_announce("s1"),
+        "/other/keepalive".to_string(),
+    ] {
+        assert_eq!(MessagePriority::for_topic(&topic), MessagePriority::Data, "{topic}");
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use libp2p::autonat::NatStatus;
+use libp2p::core::transport::ListenerId;
+use wws_network::reachability::{circuit_addr, ReachabilityTracker, MAX_OBSERVED_ADDRS};
+use wws_network::{Multiaddr, NatState, PeerId, SwarmHost, SwarmHostConfig};
+
+fn addr(s: &str) -> Multiaddr {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_private_node_listens_through_a_relay_until_public() {
+    let mut tracker = ReachabilityTracker::default();
+    let relay = PeerId::random();
+    tracker.add_relay_candidate(relay, addr("/ip4/203.0.113.7/tcp/9000"));
+    assert_eq!(tracker.relay_to_use(), None, "no relay while unknown");
+
+    assert!(tracker.set_status(&NatStatus::Private));
+    assert!(!tracker.set_status(&NatStatus::Private));
+    let (peer, circuit) = tracker.relay_to_use().unwrap();
+    assert_eq!(peer, relay);
+    assert_eq!(
+        circuit,
+        addr(&format!("/ip4/203.0.113.7/tcp/9000/p2p/{}/p2p-circuit", relay))
+    );
+
+    let listener = ListenerId::next();
+    tracker.relay_started(relay, listener);
+    tracker.relay_reserved(&relay);
+    assert_eq!(tracker.relay_to_use(), None, "one relay at a time");
+    let snapshot = tracker.snapshot(2, std::iter::empty());
+    assert_eq!(snapshot.status, NatState::Private);
+    assert_eq!(snapshot.relay_peer, Some(relay.to_string()));
+    assert!(snapshot.relay_reserved);
+
+    assert_eq!(tracker.relay_to_drop(), None);
+    let public = addr("/ip4/198.51.100.2/tcp/9000");
+    assert!(tracker.set_status(&NatStatus::Public(public.clone())));
+    assert_eq!(tracker.relay_to_drop(), Some(listener));
+    let snapshot = tracker.snapshot(1, [public.clone()].iter());
+    assert_eq!(snapshot.public_address, Some(public.to_string()));
+    assert_eq!(snapshot.external_addrs, vec![public.to_string()]);
+    assert_eq!(snapshot.relay_peer, None);
+}
+
+#[test]
+fn test_losing_the_relay_moves_to_the_next_candidate() {
+    let mut tracker = ReachabilityTracker::default();
+    tracker.set_status(&NatStatus::Private);
+    let (a, b) = (PeerId::random(), PeerId::random());
+    tracker.add_relay_candidate(a, addr("/ip4/203.0.113.7/tcp/9000"));
+    tracker.add_relay_candidate(b, addr("/ip4/203.0.113.8/tcp/9000"));
+    let (first, _) = tracker.relay_to_use().unwrap();
+    tracker.relay_started(first, ListenerId::next());
+
+    assert!(tracker.remove_peer(&first));
+    let (next, _) = tracker.relay_to_use().unwrap();
+    assert_ne!(next, first);
+    assert!(!tracker.remove_peer(&PeerId::random()));
+}
+
+#[test]
+fn test_observed_addresses_keep_the_most_recent() {
+    let mut tracker = ReachabilityTracker::default();
+    for port in 0..MAX_OBSERVED_ADDRS as u16 + 2 {
+        tracker.observe(addr(&format!("/ip4/198.51.100.2/tcp/{}", port)));
+    }
+    tracker.observe(addr("/ip4/198.51.100.2/tcp/5"));
+    let observed = tracker.snapshot(0, std::iter::empty()).observed_addrs;
+    assert_eq!(observed.len(), MAX_OBSERVED_ADDRS);
+    assert_eq!(observed[0], "/ip4/198.51.100.2/tcp/5");
+    assert_eq!(observed.iter().filter(|a| a.ends_with("/tcp/5")).count(), 1);
+}
+
+#[test]
+fn test_circuit_addr_replaces_an_existing_peer_id() {
+    let relay = PeerId::random();
+    let with_id = addr(&format!("/ip4/203.0.113.7/tcp/9000/p2p/{}", relay));
+    assert_eq!(
+        circuit_addr(&with_id, relay),
+        addr(&format!("/ip4/203.0.113.7/tcp/9000/p2p/{}/p2p-circuit", relay))
+    );
+}
+
+#[tokio::test]
+async fn test_fresh_host_reports_unknown_reachability() {
+    let config = SwarmHostConfig {
+        listen_addr: "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+        ..Default::default()
+    };
+    let (host, handle, _events) = SwarmHost::new(config).unwrap();
+    tokio::spawn(host.run());
+
+    let reachability = tokio::time::timeout(Duration::from_secs(5), handle.reachability())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reachability.status, NatState::Unknown);
+    assert_eq!(reachability.confidence, 0);
+    assert_eq!(reachability.relay_peer, None);
+    assert!(reachability.external_addrs.is_empty());
+}
+
+#[tokio::test]
+async fn test_configured_external_addrs_are_advertised() {
+    let external = addr("/ip4/198.51.100.9/tcp/9000");
+    let config = SwarmHostConfig {
+        listen_addr: "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+        external_addrs: vec![external.clone()],
+        ..Default::default()
+    };
+    let (host, handle, _events) = SwarmHost::new(config).unwrap();
+    tokio::spawn(host.run());
+
+    let reachability = tokio::time::timeout(Duration::from_secs(5), handle.reachability())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reachability.external_addrs, vec![external.to_string()]);
+}
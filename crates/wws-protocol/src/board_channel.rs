@@ -0,0 +1,172 @@
+//! Encrypted deliberation channels for private holonic boards.
+//!
+//! A member accepting a private board invitation generates a
+//! [`ChannelKeypair`] and sends its public half with `board.accept`. The
+//! chair then draws a random [`GroupKey`] for the board and seals it to each
+//! selected member in `board.ready`: an ephemeral X25519 exchange with the
+//! member's key, HKDF-SHA256, and ChaCha20-Poly1305. Proposals and critiques
+//! for the task are afterwards published as `board.sealed` messages
+//! encrypted under the group key, so observers of the board topic outside
+//! the board only see ciphertext.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+use crate::messages::SealedBoardMessageParams;
+use crate::ProtocolError;
+
+const KEY_WRAP_INFO: &[u8] = b"wws/board-channel/key-wrap";
+const NONCE_LEN: usize = 12;
+
+/// A board member's key pair for receiving the group key.
+pub struct ChannelKeypair {
+    secret: StaticSecret,
+}
+
+impl ChannelKeypair {
+    pub fn generate() -> Self {
+        Self {
+            secret: StaticSecret::random_from_rng(rand::thread_rng()),
+        }
+    }
+
+    /// Hex-encoded X25519 public key, sent in `board.accept`.
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(PublicKey::from(&self.secret).as_bytes())
+    }
+}
+
+/// The group key sealed to one board member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedGroupKey {
+    /// Hex-encoded ephemeral X25519 public key of the chair.
+    pub ephemeral_key: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Symmetric key shared by the members of one board.
+#[derive(Clone)]
+pub struct GroupKey([u8; 32]);
+
+impl std::fmt::Debug for GroupKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("GroupKey(..)")
+    }
+}
+
+impl GroupKey {
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self(key)
+    }
+
+    /// Seal this key to the member owning `member_key_hex`.
+    pub fn seal_for(&self, member_key_hex: &str) -> Result<SealedGroupKey, ProtocolError> {
+        let member_key = PublicKey::from(decode_array::<32>(member_key_hex, "channel key")?);
+        let ephemeral = EphemeralSecret::random_from_rng(rand::thread_rng());
+        let ephemeral_key = PublicKey::from(&ephemeral);
+        let shared = ephemeral.diffie_hellman(&member_key);
+        let wrap_key = derive_wrap_key(shared.as_bytes(), &ephemeral_key, &member_key);
+        let (nonce, ciphertext) = encrypt(&wrap_key, &self.0, &[])?;
+        Ok(SealedGroupKey {
+            ephemeral_key: hex::encode(ephemeral_key.as_bytes()),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Recover a group key sealed to `keypair`.
+    pub fn open(sealed: &SealedGroupKey, keypair: &ChannelKeypair) -> Result<Self, ProtocolError> {
+        let ephemeral_key = PublicKey::from(decode_array::<32>(&sealed.ephemeral_key, "ephemeral key")?);
+        let own_key = PublicKey::from(&keypair.secret);
+        let shared = keypair.secret.diffie_hellman(&ephemeral_key);
+        let wrap_key = derive_wrap_key(shared.as_bytes(), &ephemeral_key, &own_key);
+        let key = decrypt(&wrap_key, &sealed.nonce, &sealed.ciphertext, &[])?;
+        let key: [u8; 32] = key
+            .try_into()
+            .map_err(|_| ProtocolError::Crypto("sealed group key has the wrong length".into()))?;
+        Ok(Self(key))
+    }
+
+    /// Encrypt a serialized protocol message for the board of `task_id`.
+    pub fn encrypt(&self, task_id: &str, plaintext: &[u8]) -> Result<SealedBoardMessageParams, ProtocolError> {
+        let (nonce, ciphertext) = encrypt(&self.0, plaintext, task_id.as_bytes())?;
+        Ok(SealedBoardMessageParams {
+            task_id: task_id.to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt a `board.sealed` message back into the serialized message.
+    pub fn decrypt(&self, sealed: &SealedBoardMessageParams) -> Result<Vec<u8>, ProtocolError> {
+        decrypt(&self.0, &sealed.nonce, &sealed.ciphertext, sealed.task_id.as_bytes())
+    }
+}
+
+fn derive_wrap_key(shared: &[u8; 32], ephemeral_key: &PublicKey, member_key: &PublicKey) -> [u8; 32] {
+    let mut info = KEY_WRAP_INFO.to_vec();
+    info.extend_from_slice(ephemeral_key.as_bytes());
+    info.extend_from_slice(member_key.as_bytes());
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, shared)
+        .expand(&info, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+fn encrypt(key: &[u8; 32], msg: &[u8], aad: &[u8]) -> Result<([u8; NONCE_LEN], Vec<u8>), ProtocolError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+        .map_err(|_| ProtocolError::Crypto("encryption failed".into()))?;
+    Ok((nonce, ciphertext))
+}
+
+fn decrypt(key: &[u8; 32], nonce_hex: &str, ciphertext_hex: &str, aad: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+    let nonce = decode_array::<NONCE_LEN>(nonce_hex, "nonce")?;
+    let ciphertext = hex::decode(ciphertext_hex)
+        .map_err(|e| ProtocolError::Crypto(format!("invalid ciphertext: {}", e)))?;
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad })
+        .map_err(|_| ProtocolError::Crypto("decryption failed".into()))
+}
+
+fn decode_array<const N: usize>(value: &str, what: &str) -> Result<[u8; N], ProtocolError> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ProtocolError::Crypto(format!("invalid {}", what)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_members_with_a_sealed_key_can_read_board_messages() {
+        let member = ChannelKeypair::generate();
+        let outsider = ChannelKeypair::generate();
+        let group_key = GroupKey::generate();
+
+        let sealed = group_key.seal_for(&member.public_key_hex()).unwrap();
+        let opened = GroupKey::open(&sealed, &member).unwrap();
+        assert!(GroupKey::open(&sealed, &outsider).is_err());
+
+        let message = group_key.encrypt("task-1", b"{\"method\":\"consensus.proposal_reveal\"}").unwrap();
+        assert_eq!(opened.decrypt(&message).unwrap(), b"{\"method\":\"consensus.proposal_reveal\"}");
+        assert!(GroupKey::generate().decrypt(&message).is_err());
+
+        // The ciphertext is bound to its task.
+        let moved = SealedBoardMessageParams { task_id: "task-2".into(), ..message };
+        assert!(opened.decrypt(&moved).is_err());
+    }
+}
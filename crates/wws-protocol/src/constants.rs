@@ -42,9 +42,18 @@ pub const DEFAULT_SWARM_NAME: &str = "WWS Public";
 /// DHT key prefix for swarm registry records.
 pub const SWARM_REGISTRY_PREFIX: &str = "/wws/registry/";
 
+/// DHT key prefix for signed swarm parameter records.
+pub const SWARM_PARAMS_PREFIX: &str = "/wws/swarm-params/";
+
 /// DHT key prefix for swarm membership records.
 pub const SWARM_MEMBERSHIP_PREFIX: &str = "/wws/membership/";
 
+/// DHT key prefix for DID documents, followed by the DID.
+pub const DID_DOCUMENT_PREFIX: &str = "/wws/did/";
+
+/// DHT key prefix for anchored Merkle roots of task results, followed by the task ID.
+pub const MERKLE_ANCHOR_PREFIX: &str = "/wws/merkle-root/";
+
 /// Swarm announcement interval in seconds.
 pub const SWARM_ANNOUNCE_INTERVAL_SECS: u64 = 30;
 
@@ -32,6 +32,22 @@ pub fn verify_signature(
         .map_err(|e| ProtocolError::InvalidSignature(e.to_string()))
 }
 
+/// Whether `signature_hex` is a valid signature of `payload` by the
+/// hex-encoded Ed25519 public key `key_hex`.
+pub fn verify_hex_signature(key_hex: &str, signature_hex: &str, payload: &[u8]) -> bool {
+    let key_bytes: Option<[u8; 32]> = hex::decode(key_hex).ok().and_then(|b| b.try_into().ok());
+    let sig_bytes: Option<[u8; 64]> = hex::decode(signature_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok());
+    let (Some(key_bytes), Some(sig_bytes)) = (key_bytes, sig_bytes) else {
+        return false;
+    };
+    let Ok(key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return false;
+    };
+    verify_signature(&key, payload, &Signature::from_bytes(&sig_bytes)).is_ok()
+}
+
 /// Compute SHA-256 hash of data.
 pub fn sha256(data: &[u8]) -> [u8; 32] {
     let hash = Sha256::digest(data);
@@ -107,25 +123,48 @@ pub fn load_or_create_keypair(path: &Path) -> Result<SigningKey, crate::Protocol
         let seed: [u8; 32] = seed_bytes.try_into().unwrap();
         Ok(SigningKey::from_bytes(&seed))
     } else {
-        // Create parent directory if needed
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| crate::ProtocolError::Crypto(format!("create dir: {e}")))?;
-        }
-        let mut rng = rand::thread_rng();
-        let key = SigningKey::generate(&mut rng);
-        let seed = key.to_bytes();
-        std::fs::write(path, seed)
-            .map_err(|e| crate::ProtocolError::Crypto(format!("write key file: {e}")))?;
-        // Set file permissions to 0600 (owner read/write only)
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
-                .map_err(|e| crate::ProtocolError::Crypto(format!("set permissions: {e}")))?;
-        }
-        Ok(key)
+        create_keypair(path)
+    }
+}
+
+/// Replace the keypair stored at `path` with a new one. The old key file,
+/// if any, is kept beside it as `<file>.<unix time>.old` rather than
+/// deleted; its path is returned along with the new key.
+pub fn replace_keypair(
+    path: &Path,
+) -> Result<(SigningKey, Option<std::path::PathBuf>), crate::ProtocolError> {
+    let backup = if path.exists() {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}.old", chrono::Utc::now().timestamp()));
+        let backup = path.with_file_name(name);
+        std::fs::rename(path, &backup)
+            .map_err(|e| crate::ProtocolError::Crypto(format!("move old key file: {e}")))?;
+        Some(backup)
+    } else {
+        None
+    };
+    Ok((create_keypair(path)?, backup))
+}
+
+fn create_keypair(path: &Path) -> Result<SigningKey, crate::ProtocolError> {
+    // Create parent directory if needed
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| crate::ProtocolError::Crypto(format!("create dir: {e}")))?;
+    }
+    let mut rng = rand::thread_rng();
+    let key = SigningKey::generate(&mut rng);
+    let seed = key.to_bytes();
+    std::fs::write(path, seed)
+        .map_err(|e| crate::ProtocolError::Crypto(format!("write key file: {e}")))?;
+    // Set file permissions to 0600 (owner read/write only)
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| crate::ProtocolError::Crypto(format!("set permissions: {e}")))?;
     }
+    Ok(key)
 }
 
 /// Derive a 24-word BIP-39 mnemonic from an Ed25519 signing key.
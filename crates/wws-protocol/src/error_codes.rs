@@ -0,0 +1,246 @@
+//! Error codes carried in `SwarmResponse.error.code`.
+//!
+//! One list for the connector's JSON-RPC API and for rejections on the
+//! direct P2P request-response protocol, so a client can branch on a
+//! named code instead of a bare number. The JSON-RPC 2.0 standard codes
+//! keep their values; the rest sit in the -32000 to -32099 range the
+//! standard reserves for servers. `swarm.describe_errors` lists them all.
+
+use serde::{Deserialize, Serialize};
+
+/// Broad class of an [`ErrorCode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The request itself was malformed.
+    Request,
+    /// The caller may not do this.
+    Auth,
+    /// What the request names does not exist.
+    Lookup,
+    /// Plan proposal and voting.
+    Consensus,
+    /// Task execution and results.
+    Task,
+    /// A peer's message failed verification.
+    Protocol,
+    /// The connector could not carry the request out.
+    Server,
+}
+
+/// A named error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The request is not valid JSON.
+    ParseError,
+    /// The JSON is not a valid request envelope.
+    InvalidRequest,
+    /// No such method.
+    MethodNotFound,
+    /// A parameter is missing or malformed.
+    InvalidParams,
+    /// The connector failed to carry out a valid request, e.g. a dial or a
+    /// publish failed; the message says why.
+    OperationFailed,
+    /// The caller is not a member, owner or authority for what it names.
+    NotPermitted,
+    /// The task, template, schedule, peer or other item named does not
+    /// exist here.
+    NotFound,
+    /// An aggregated result arrived before all subtasks and child holons
+    /// completed.
+    SubtasksIncomplete,
+    /// A root task's result arrived before the task was decomposed.
+    RootNotDecomposed,
+    /// The submitter is no longer the task's assignee.
+    NotAssignee,
+    /// A plan has no subtasks.
+    EmptyPlan,
+    /// Proposals are frozen while an epoch transition finalizes; retry.
+    ProposalsFrozen,
+    /// A result does not match the task's strict result schema.
+    ResultSchemaMismatch,
+    /// A plan violates the swarm's plan policy; `data` lists violations.
+    PlanPolicyViolation,
+    /// The RPC request carries no valid `auth` token.
+    Unauthenticated,
+    /// The RPC client's role or local-agent binding does not allow this.
+    Forbidden,
+    /// A peer's message signature does not verify.
+    InvalidSignature,
+    /// A peer sent a message its place in the hierarchy does not allow.
+    UnauthorizedSender,
+}
+
+impl ErrorCode {
+    /// Every code, in numeric order from the standard codes down.
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::ParseError,
+        ErrorCode::InvalidRequest,
+        ErrorCode::MethodNotFound,
+        ErrorCode::InvalidParams,
+        ErrorCode::OperationFailed,
+        ErrorCode::NotPermitted,
+        ErrorCode::NotFound,
+        ErrorCode::SubtasksIncomplete,
+        ErrorCode::RootNotDecomposed,
+        ErrorCode::NotAssignee,
+        ErrorCode::EmptyPlan,
+        ErrorCode::ProposalsFrozen,
+        ErrorCode::ResultSchemaMismatch,
+        ErrorCode::PlanPolicyViolation,
+        ErrorCode::Unauthenticated,
+        ErrorCode::Forbidden,
+        ErrorCode::InvalidSignature,
+        ErrorCode::UnauthorizedSender,
+    ];
+
+    /// The numeric code sent on the wire.
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorCode::ParseError => -32700,
+            ErrorCode::InvalidRequest => -32600,
+            ErrorCode::MethodNotFound => -32601,
+            ErrorCode::InvalidParams => -32602,
+            ErrorCode::OperationFailed => -32000,
+            ErrorCode::NotPermitted => -32001,
+            ErrorCode::NotFound => -32004,
+            ErrorCode::SubtasksIncomplete => -32010,
+            ErrorCode::RootNotDecomposed => -32011,
+            ErrorCode::NotAssignee => -32012,
+            ErrorCode::EmptyPlan => -32013,
+            ErrorCode::ProposalsFrozen => -32014,
+            ErrorCode::ResultSchemaMismatch => -32015,
+            ErrorCode::PlanPolicyViolation => -32016,
+            ErrorCode::Unauthenticated => -32020,
+            ErrorCode::Forbidden => -32021,
+            ErrorCode::InvalidSignature => -32030,
+            ErrorCode::UnauthorizedSender => -32031,
+        }
+    }
+
+    /// The code with numeric value `code`.
+    pub fn from_code(code: i32) -> Option<ErrorCode> {
+        Self::ALL.iter().copied().find(|c| c.code() == code)
+    }
+
+    /// The snake_case name, as serialized.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorCode::ParseError => "parse_error",
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::MethodNotFound => "method_not_found",
+            ErrorCode::InvalidParams => "invalid_params",
+            ErrorCode::OperationFailed => "operation_failed",
+            ErrorCode::NotPermitted => "not_permitted",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::SubtasksIncomplete => "subtasks_incomplete",
+            ErrorCode::RootNotDecomposed => "root_not_decomposed",
+            ErrorCode::NotAssignee => "not_assignee",
+            ErrorCode::EmptyPlan => "empty_plan",
+            ErrorCode::ProposalsFrozen => "proposals_frozen",
+            ErrorCode::ResultSchemaMismatch => "result_schema_mismatch",
+            ErrorCode::PlanPolicyViolation => "plan_policy_violation",
+            ErrorCode::Unauthenticated => "unauthenticated",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::InvalidSignature => "invalid_signature",
+            ErrorCode::UnauthorizedSender => "unauthorized_sender",
+        }
+    }
+
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            ErrorCode::ParseError
+            | ErrorCode::InvalidRequest
+            | ErrorCode::MethodNotFound
+            | ErrorCode::InvalidParams => ErrorCategory::Request,
+            ErrorCode::OperationFailed => ErrorCategory::Server,
+            ErrorCode::NotPermitted | ErrorCode::Unauthenticated | ErrorCode::Forbidden => {
+                ErrorCategory::Auth
+            }
+            ErrorCode::NotFound => ErrorCategory::Lookup,
+            ErrorCode::EmptyPlan
+            | ErrorCode::ProposalsFrozen
+            | ErrorCode::PlanPolicyViolation => ErrorCategory::Consensus,
+            ErrorCode::SubtasksIncomplete
+            | ErrorCode::RootNotDecomposed
+            | ErrorCode::NotAssignee
+            | ErrorCode::ResultSchemaMismatch => ErrorCategory::Task,
+            ErrorCode::InvalidSignature | ErrorCode::UnauthorizedSender => ErrorCategory::Protocol,
+        }
+    }
+
+    /// One-line explanation for clients and `swarm.describe_errors`.
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorCode::ParseError => "The request is not valid JSON",
+            ErrorCode::InvalidRequest => "The JSON is not a valid request envelope",
+            ErrorCode::MethodNotFound => "No such method",
+            ErrorCode::InvalidParams => "A parameter is missing or malformed",
+            ErrorCode::OperationFailed => {
+                "The connector could not carry out the request; the message says why"
+            }
+            ErrorCode::NotPermitted => {
+                "The caller is not a member, owner or authority for what it names"
+            }
+            ErrorCode::NotFound => "The task, template, schedule, peer or other item does not exist",
+            ErrorCode::SubtasksIncomplete => {
+                "An aggregated result arrived before all subtasks and child holons completed"
+            }
+            ErrorCode::RootNotDecomposed => {
+                "A root task's result arrived before the task was decomposed"
+            }
+            ErrorCode::NotAssignee => "The submitter is no longer the task's assignee",
+            ErrorCode::EmptyPlan => "The plan has no subtasks",
+            ErrorCode::ProposalsFrozen => {
+                "Proposals are frozen while an epoch transition finalizes; retry later"
+            }
+            ErrorCode::ResultSchemaMismatch => {
+                "The result does not match the task's strict result schema"
+            }
+            ErrorCode::PlanPolicyViolation => {
+                "The plan violates the swarm's plan policy; data lists the violations"
+            }
+            ErrorCode::Unauthenticated => "The request carries no valid auth token",
+            ErrorCode::Forbidden => {
+                "The client's role or local-agent binding does not allow this call"
+            }
+            ErrorCode::InvalidSignature => "The peer's message signature does not verify",
+            ErrorCode::UnauthorizedSender => {
+                "The peer's place in the hierarchy does not allow this message"
+            }
+        }
+    }
+}
+
+impl From<ErrorCode> for i32 {
+    fn from(code: ErrorCode) -> i32 {
+        code.code()
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name(), self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_and_round_trip() {
+        let mut codes: Vec<i32> = ErrorCode::ALL.iter().map(|c| c.code()).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+        for &code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.code()), Some(code));
+            let json = serde_json::to_value(code).unwrap();
+            assert_eq!(json, code.name());
+        }
+        assert_eq!(ErrorCode::from_code(-1), None);
+    }
+}
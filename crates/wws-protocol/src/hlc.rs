@@ -0,0 +1,107 @@
+//! Hybrid logical clock timestamps.
+//!
+//! Wall clocks on different nodes drift, so ordering task events by their
+//! wall-clock timestamp can place an effect before its cause. A hybrid
+//! logical clock pairs the physical time in milliseconds with a logical
+//! counter: every local event advances it, and every received message moves
+//! it past the sender's timestamp. An event caused by a message therefore
+//! always carries a larger timestamp than the message, while timestamps stay
+//! close to wall-clock time.
+//!
+//! Each process keeps one clock, available through [`global`].
+//! [`SwarmMessage::new`](crate::SwarmMessage::new) stamps outgoing messages
+//! with it, and the connector observes the stamp of every message it
+//! receives.
+
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+/// A hybrid logical clock reading. Ordered by physical time, then by the
+/// logical counter.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct HlcTimestamp {
+    /// Physical component, milliseconds since the Unix epoch.
+    pub wall_ms: i64,
+    /// Events within the same millisecond.
+    pub logical: u32,
+}
+
+/// A hybrid logical clock.
+#[derive(Debug, Default)]
+pub struct HybridClock {
+    last: Mutex<HlcTimestamp>,
+}
+
+impl HybridClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timestamp a local or send event.
+    pub fn now(&self) -> HlcTimestamp {
+        self.now_at(chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Advance past a timestamp received from another node and timestamp
+    /// the receive event.
+    pub fn observe(&self, remote: HlcTimestamp) -> HlcTimestamp {
+        self.observe_at(remote, chrono::Utc::now().timestamp_millis())
+    }
+
+    fn now_at(&self, physical_ms: i64) -> HlcTimestamp {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        *last = if physical_ms > last.wall_ms {
+            HlcTimestamp { wall_ms: physical_ms, logical: 0 }
+        } else {
+            HlcTimestamp { wall_ms: last.wall_ms, logical: last.logical + 1 }
+        };
+        *last
+    }
+
+    fn observe_at(&self, remote: HlcTimestamp, physical_ms: i64) -> HlcTimestamp {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let wall_ms = physical_ms.max(last.wall_ms).max(remote.wall_ms);
+        let logical = if wall_ms == last.wall_ms && wall_ms == remote.wall_ms {
+            last.logical.max(remote.logical) + 1
+        } else if wall_ms == last.wall_ms {
+            last.logical + 1
+        } else if wall_ms == remote.wall_ms {
+            remote.logical + 1
+        } else {
+            0
+        };
+        *last = HlcTimestamp { wall_ms, logical };
+        *last
+    }
+}
+
+/// The clock shared by everything in this process.
+pub fn global() -> &'static HybridClock {
+    static CLOCK: OnceLock<HybridClock> = OnceLock::new();
+    CLOCK.get_or_init(HybridClock::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receive_events_order_after_the_message_despite_clock_skew() {
+        let clock = HybridClock::new();
+        let first = clock.now_at(1_000);
+        let second = clock.now_at(1_000);
+        assert!(second > first);
+
+        // The sender's clock runs ahead of ours.
+        let remote = HlcTimestamp { wall_ms: 5_000, logical: 3 };
+        let received = clock.observe_at(remote, 1_001);
+        assert_eq!(received, HlcTimestamp { wall_ms: 5_000, logical: 4 });
+        assert!(clock.now_at(1_002) > received);
+
+        // A later physical time resets the counter.
+        assert_eq!(clock.now_at(6_000), HlcTimestamp { wall_ms: 6_000, logical: 0 });
+    }
+}
@@ -118,6 +118,124 @@ impl NodeScore {
     }
 }
 
+/// Verification method type of the Ed25519 keys in DID documents.
+pub const ED25519_VERIFICATION_KEY: &str = "Ed25519VerificationKey2020";
+
+/// A key that verifies signatures made by a DID's agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    /// `<did>#key-<n>`.
+    pub id: String,
+    #[serde(rename = "type")]
+    pub method_type: String,
+    pub controller: String,
+    /// Hex Ed25519 public key.
+    pub public_key_hex: String,
+}
+
+/// Where an agent can be reached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceEndpoint {
+    /// `<did>#<name>`.
+    pub id: String,
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub service_endpoint: String,
+}
+
+/// The document a `did:swarm` DID resolves to, published to the DHT under
+/// [`DidDocument::dht_key`]. It lists the keys that sign for the agent,
+/// where it can be reached and what it can do, and is signed with its
+/// first verification key, so any swarm can check it without trusting the
+/// node that served it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    /// The DID.
+    pub id: String,
+    pub verification_method: Vec<VerificationMethod>,
+    #[serde(default)]
+    pub service: Vec<ServiceEndpoint>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// When the document was issued; a newer one replaces an older one.
+    pub updated: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field, by the first
+    /// verification method.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl DidDocument {
+    /// A document for `did` signed with `key`, which becomes its only
+    /// verification method.
+    pub fn new(
+        did: &str,
+        key: &ed25519_dalek::SigningKey,
+        service: Vec<ServiceEndpoint>,
+        capabilities: Vec<String>,
+    ) -> Self {
+        let mut document = Self {
+            id: did.to_string(),
+            verification_method: vec![VerificationMethod {
+                id: format!("{}#key-1", did),
+                method_type: ED25519_VERIFICATION_KEY.to_string(),
+                controller: did.to_string(),
+                public_key_hex: hex::encode(key.verifying_key().as_bytes()),
+            }],
+            service,
+            capabilities,
+            updated: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        document.sign(key);
+        document
+    }
+
+    /// DHT storage key of the document for `did`.
+    pub fn dht_key(did: &str) -> Vec<u8> {
+        format!("{}{}", crate::constants::DID_DOCUMENT_PREFIX, did).into_bytes()
+    }
+
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        crate::messages::unsigned_payload(self)
+    }
+
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Public keys of the Ed25519 verification methods.
+    pub fn public_keys(&self) -> impl Iterator<Item = ed25519_dalek::VerifyingKey> + '_ {
+        self.verification_method
+            .iter()
+            .filter(|m| m.method_type == ED25519_VERIFICATION_KEY)
+            .filter_map(|m| hex::decode(&m.public_key_hex).ok())
+            .filter_map(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .filter_map(|bytes| ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok())
+    }
+
+    /// Whether the document is signed by its first verification method.
+    pub fn verify(&self) -> bool {
+        self.verification_method.first().is_some_and(|m| {
+            crate::crypto::verify_hex_signature(&m.public_key_hex, &self.signature, &self.signing_payload())
+        })
+    }
+
+    /// Whether `signature_hex` over `payload` was made by one of the
+    /// document's keys.
+    pub fn verify_signature(&self, payload: &[u8], signature_hex: &str) -> bool {
+        self.verification_method.iter().any(|m| {
+            m.method_type == ED25519_VERIFICATION_KEY
+                && crate::crypto::verify_hex_signature(&m.public_key_hex, signature_hex, payload)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
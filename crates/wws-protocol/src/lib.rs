@@ -3,20 +3,31 @@
 //! Implements the World Wide Swarm (WWS) message specification
 //! using JSON-RPC 2.0 envelope format with Ed25519 signatures.
 
+pub mod board_channel;
 pub mod identity;
 pub mod messages;
 pub mod types;
 pub mod error;
+pub mod error_codes;
 pub mod constants;
 pub mod crypto;
+pub mod hlc;
 pub mod key_rotation;
 pub mod replay;
+pub mod result_schema;
+pub mod result_seal;
+pub mod strict;
+pub mod topic_keys;
 
 pub use identity::*;
 pub use messages::*;
 pub use types::*;
 pub use error::*;
+pub use error_codes::{ErrorCategory, ErrorCode};
 pub use constants::*;
+pub use hlc::HlcTimestamp;
+pub use result_schema::ResultSchema;
+pub use strict::{decode_strict, FieldError, StrictError, StrictLimits};
 pub use key_rotation::{
     RotationAnnouncement,
     EmergencyRevocation,
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::constants::JSONRPC_VERSION;
+use crate::hlc::HlcTimestamp;
 use crate::identity::AgentId;
 use crate::types::*;
 
@@ -15,6 +16,9 @@ pub struct SwarmMessage {
     pub params: serde_json::Value,
     /// Ed25519 signature over the canonical JSON of (method + params)
     pub signature: String,
+    /// Sender's hybrid logical clock when the message was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hlc: Option<HlcTimestamp>,
 }
 
 impl SwarmMessage {
@@ -25,6 +29,7 @@ impl SwarmMessage {
             id: Some(uuid::Uuid::new_v4().to_string()),
             params,
             signature,
+            hlc: Some(crate::hlc::global().now()),
         }
     }
 
@@ -59,18 +64,33 @@ impl SwarmResponse {
         }
     }
 
-    pub fn error(id: Option<String>, code: i32, message: String) -> Self {
+    /// Error response; `code` is an [`crate::ErrorCode`] or a raw number.
+    pub fn error(id: Option<String>, code: impl Into<i32>, message: String) -> Self {
         Self {
             jsonrpc: JSONRPC_VERSION.to_string(),
             id,
             result: None,
             error: Some(RpcError {
-                code,
+                code: code.into(),
                 message,
                 data: None,
             }),
         }
     }
+
+    /// Error response carrying structured details in `error.data`.
+    pub fn error_with_data(
+        id: Option<String>,
+        code: impl Into<i32>,
+        message: String,
+        data: serde_json::Value,
+    ) -> Self {
+        let mut response = Self::error(id, code, message);
+        if let Some(error) = response.error.as_mut() {
+            error.data = Some(data);
+        }
+        response
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +101,13 @@ pub struct RpcError {
     pub data: Option<serde_json::Value>,
 }
 
+impl RpcError {
+    /// The named code, if `code` is one of [`crate::ErrorCode`]'s.
+    pub fn error_code(&self) -> Option<crate::ErrorCode> {
+        crate::ErrorCode::from_code(self.code)
+    }
+}
+
 // ── Specific Message Payloads ──
 
 /// Handshake message sent on peer connection.
@@ -102,6 +129,9 @@ pub struct CandidacyParams {
     pub epoch: u64,
     pub score: crate::identity::NodeScore,
     pub location_vector: crate::identity::VivaldiCoordinates,
+    /// Capabilities the candidate offers as a leader.
+    #[serde(default)]
+    pub capabilities: Vec<String>,
 }
 
 /// Election vote for a Tier-1 candidate.
@@ -112,7 +142,7 @@ pub struct ElectionVoteParams {
     pub candidate_rankings: Vec<AgentId>,
 }
 
-/// Tier assignment notification from parent to subordinate.
+/// Tier assignment notification, issued by a Tier-1 agent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TierAssignmentParams {
     pub assigned_agent: AgentId,
@@ -120,6 +150,40 @@ pub struct TierAssignmentParams {
     pub parent_id: AgentId,
     pub epoch: u64,
     pub branch_size: u64,
+    /// Agent that issued the assignment.
+    #[serde(default)]
+    pub issuer: AgentId,
+    /// Hex Ed25519 public key of the issuer.
+    #[serde(default)]
+    pub issuer_key: String,
+    /// Hex Ed25519 signature over every other field; empty when unsigned.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl TierAssignmentParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `issuer_key` to the signer's public key and sign the assignment.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.issuer_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `issuer_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.issuer_key, &self.signature, &self.signing_payload())
+    }
+
+    /// The issuer's public key, if `issuer_key` is well-formed.
+    pub fn issuer_verifying_key(&self) -> Option<ed25519_dalek::VerifyingKey> {
+        let bytes: [u8; 32] = hex::decode(&self.issuer_key).ok()?.try_into().ok()?;
+        ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok()
+    }
 }
 
 /// Task injection from external source or parent agent.
@@ -127,6 +191,22 @@ pub struct TierAssignmentParams {
 pub struct TaskInjectionParams {
     pub task: Task,
     pub originator: AgentId,
+    /// Content of the task's attachments that travels inline, so every
+    /// peer that sees the injection can serve them. Attachments without a
+    /// blob here are referenced by CID only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentBlob>,
+    /// How many times the task was re-tendered after drawing no plan; 0
+    /// for its first injection.
+    #[serde(default)]
+    pub attempt: u32,
+}
+
+/// Content of one task attachment, keyed by its CID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachmentBlob {
+    pub cid: String,
+    pub content: String,
 }
 
 /// Commit phase of proposal (hash only, plan hidden).
@@ -137,6 +217,36 @@ pub struct ProposalCommitParams {
     pub epoch: u64,
     /// SHA-256 hash of the full plan JSON
     pub plan_hash: String,
+    /// Reputation points the proposer stakes on its plan, slashed if the
+    /// plan is withdrawn late, fails hash verification or is flagged as
+    /// malicious. Zero for no bond.
+    #[serde(default)]
+    pub bond: u64,
+    /// The proposer's seat on the task's sortition committee, when the
+    /// proposer's tier draws one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<SelectionProof>,
+}
+
+/// Evidence that an agent was drawn onto a task's sortition committee.
+/// Anyone who knows the task's epoch can recompute the seed and ticket.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectionProof {
+    /// Epoch the committee was drawn for.
+    pub epoch: u64,
+    /// Hex SHA-256 seed derived from the epoch and task ID.
+    pub seed: String,
+    /// Hex of the agent's ticket, the first 8 bytes of
+    /// SHA-256(seed || agent DID).
+    pub ticket: String,
+    /// Weight the agent was drawn with.
+    pub weight: f64,
+    /// The agent's 1-based place in the draw.
+    pub rank: usize,
+    /// Seats on the committee.
+    pub committee_size: usize,
+    /// Agents the committee was drawn from, as the agent saw its tier.
+    pub eligible: usize,
 }
 
 /// Reveal phase of proposal (full plan disclosed).
@@ -146,6 +256,30 @@ pub struct ProposalRevealParams {
     pub plan: Plan,
 }
 
+/// A proposer's single amendment to its revealed plan after the critique round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalRevisionParams {
+    pub task_id: String,
+    /// Plan ID of the revealed plan being replaced.
+    pub original_plan_id: String,
+    /// The revised plan; must carry a new plan ID.
+    pub plan: Plan,
+    /// What changed in response to the critiques.
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// A proposer retracting its revealed plan before voting closes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalWithdrawParams {
+    pub task_id: String,
+    pub plan_id: String,
+    pub proposer: AgentId,
+    /// Why the plan is withdrawn.
+    #[serde(default)]
+    pub reason: String,
+}
+
 /// Ranked Choice Vote for plan selection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusVoteParams {
@@ -154,15 +288,203 @@ pub struct ConsensusVoteParams {
     pub voter: AgentId,
     pub rankings: Vec<String>,
     pub critic_scores: std::collections::HashMap<String, CriticScore>,
+    #[serde(default)]
+    pub abstain: bool,
+    /// The voter's seat on the task's sortition committee, when the voter's
+    /// tier draws one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<SelectionProof>,
+}
+
+/// Commitment to a blind ballot, published while the voting window is open.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusVoteCommitParams {
+    pub task_id: String,
+    pub epoch: u64,
+    pub voter: AgentId,
+    /// SHA-256 hex of the ballot and its salt.
+    pub commitment: String,
+}
+
+/// A blind ballot, revealed after the voting window has closed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusVoteRevealParams {
+    pub task_id: String,
+    pub epoch: u64,
+    pub voter: AgentId,
+    pub rankings: Vec<String>,
+    pub critic_scores: std::collections::HashMap<String, CriticScore>,
+    #[serde(default)]
+    pub abstain: bool,
+    pub salt: String,
+}
+
+/// Announcement that every committed plan has been revealed and ballots are
+/// accepted until `deadline`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VotingOpenParams {
+    pub task_id: String,
+    pub epoch: u64,
+    /// Plans on the ballot.
+    pub plan_ids: Vec<String>,
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    pub announced_by: AgentId,
+}
+
+/// Reminder, sent halfway through the voting window, naming the eligible
+/// voters whose ballots have not arrived.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteReminderParams {
+    pub task_id: String,
+    pub epoch: u64,
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    pub pending_voters: Vec<AgentId>,
+    pub sent_by: AgentId,
+}
+
+/// Signed acknowledgement that a ballot entered a node's tally.
+///
+/// The recording node issues one for every ballot it counts. A voter checks
+/// that `ballot_hash` matches the ballot it cast, and `swarm.get_ballots`
+/// on the recorder lists the same hash at `position` with this receipt's
+/// hash, so the ballot was counted as cast.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BallotReceiptParams {
+    pub task_id: String,
+    pub epoch: u64,
+    pub voter: AgentId,
+    /// SHA-256 hex of the ballot as cast.
+    pub ballot_hash: String,
+    /// 1-based position of the ballot in the recorder's tally.
+    pub position: usize,
+    /// Agent whose connector recorded the ballot.
+    pub recorder: AgentId,
+    /// Hex Ed25519 public key of the recorder.
+    pub recorder_key: String,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl BallotReceiptParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `recorder_key` to the signer's public key and sign the receipt.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.recorder_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `recorder_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.recorder_key, &self.signature, &self.signing_payload())
+    }
+
+    /// The recorder's public key, if `recorder_key` is well-formed.
+    pub fn recorder_verifying_key(&self) -> Option<ed25519_dalek::VerifyingKey> {
+        let bytes: [u8; 32] = hex::decode(&self.recorder_key).ok()?.try_into().ok()?;
+        ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok()
+    }
+
+    /// SHA-256 hex of the signed receipt, listed by `swarm.get_ballots`.
+    pub fn receipt_hash(&self) -> String {
+        crate::crypto::compute_cid(&serde_json::to_vec(self).unwrap_or_default())
+    }
+}
+
+/// Request for a task's deliberation state, sent by a node that subscribed
+/// to the task's topics after the commit phase started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStateRequestParams {
+    pub task_id: String,
+    pub requester: AgentId,
+}
+
+/// The board chair's answer to a `task.state_request`: everything a late
+/// subscriber missed, replayed through its own RFP and voting engine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStateSnapshotParams {
+    pub task_id: String,
+    pub epoch: u64,
+    pub responder: AgentId,
+    /// RFP phase on the responder, e.g. `RevealPhase`.
+    pub phase: String,
+    pub commits: Vec<ProposalCommitParams>,
+    pub reveals: Vec<ProposalRevealParams>,
+    /// Ballots counted so far; empty while a blind vote is undecided.
+    #[serde(default)]
+    pub ballots: Vec<ConsensusVoteParams>,
+}
+
+/// A task's decided state as one node sees it, exchanged to reconcile
+/// after a network partition heals.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDigest {
+    pub task_id: String,
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub assigned_to: Option<AgentId>,
+    /// HLC of the node's latest timeline event for the task. Of two
+    /// conflicting digests, the one with the later stamp wins.
+    #[serde(default)]
+    pub finality: HlcTimestamp,
+}
+
+/// Task digests sent over the direct protocol to a peer returning from a
+/// partition; the peer answers with its own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateReconcileParams {
+    pub sender: AgentId,
+    pub digests: Vec<TaskDigest>,
 }
 
-/// Task assignment from coordinator to subordinate.
+/// Task assignment from coordinator to subordinate. Only an agent above
+/// the assignee in the hierarchy may issue one.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskAssignmentParams {
     pub task: Task,
     pub assignee: AgentId,
     pub parent_task_id: String,
     pub winning_plan_id: String,
+    /// Agent that issued the assignment.
+    #[serde(default)]
+    pub issuer: AgentId,
+    /// Hex Ed25519 public key of the issuer.
+    #[serde(default)]
+    pub issuer_key: String,
+    /// Hex Ed25519 signature over every other field; empty when unsigned.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl TaskAssignmentParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `issuer_key` to the signer's public key and sign the assignment.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.issuer_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `issuer_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.issuer_key, &self.signature, &self.signing_payload())
+    }
+
+    /// The issuer's public key, if `issuer_key` is well-formed.
+    pub fn issuer_verifying_key(&self) -> Option<ed25519_dalek::VerifyingKey> {
+        let bytes: [u8; 32] = hex::decode(&self.issuer_key).ok()?.try_into().ok()?;
+        ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok()
+    }
 }
 
 /// Result submission from executor to coordinator.
@@ -177,6 +499,52 @@ pub struct ResultSubmissionParams {
     /// Optional flag: marks result as a coordinator synthesis (not raw execution).
     #[serde(default)]
     pub is_synthesis: bool,
+    /// Cost the submitting agent incurred producing this result, excluding
+    /// any subtasks (which report their own).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+}
+
+/// An assignee's report on a task it is still working on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskProgressParams {
+    pub task_id: String,
+    pub agent_id: AgentId,
+    /// Share of the work done, 0-100.
+    pub percent: u8,
+    /// What the agent is doing now.
+    #[serde(default)]
+    pub note: String,
+    /// Content ID of an intermediate result, if the agent stored one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intermediate_cid: Option<String>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// A coordinator giving up on finding a new assignee for an overdue task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskEscalationParams {
+    pub task_id: String,
+    pub parent_task_id: String,
+    /// The coordinator that could not reassign the task.
+    pub coordinator: AgentId,
+    /// The assignee that let the deadline pass.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_assignee: Option<AgentId>,
+    pub reason: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// A task given up after drawing no plan in its last allowed tender.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskTenderFailedParams {
+    pub task_id: String,
+    /// The node that tendered the task.
+    pub originator: AgentId,
+    /// Tenders made, the first included.
+    pub attempts: u32,
+    pub reason: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
 /// Verification result from coordinator back to subordinate.
@@ -188,6 +556,170 @@ pub struct VerificationResultParams {
     pub reason: Option<String>,
 }
 
+/// A board member's choice between two diverging results for a disputed task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisputeVoteParams {
+    pub task_id: String,
+    pub voter: AgentId,
+    /// The agent whose result the voter accepts: the original executor or the verifier.
+    pub accept_agent: AgentId,
+}
+
+/// A member's request to change the swarm parameters, put to a vote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamsChangeProposeParams {
+    pub swarm_id: SwarmId,
+    pub proposal_id: String,
+    /// Parameter version the change applies to; it lapses once another
+    /// version is issued.
+    pub base_version: u64,
+    pub changes: SwarmParameterChanges,
+    pub proposer: AgentId,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A member's vote on a parameter change proposal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamsChangeVoteParams {
+    pub swarm_id: SwarmId,
+    pub proposal_id: String,
+    pub voter: AgentId,
+    pub approve: bool,
+}
+
+/// Instruction from a swarm's creator for every member to move into
+/// another swarm, taking its open tasks along. Signed by the swarm's
+/// parameter authority; members ignore migrations signed by any other key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwarmMigrateParams {
+    pub migration_id: String,
+    pub source_swarm_id: SwarmId,
+    pub target_swarm_id: SwarmId,
+    /// Token for joining the target swarm when it is private.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_token: Option<SwarmToken>,
+    /// Hex Ed25519 public key of the source swarm's authority.
+    pub creator_key: String,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl SwarmMigrateParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `creator_key` to the signer's public key and sign the message.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.creator_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `creator_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.creator_key, &self.signature, &self.signing_payload())
+    }
+}
+
+/// Split of a swarm grown past its shard threshold into child swarms,
+/// issued by one of its Tier-1 leaders on the swarm's params topic. The
+/// Tier-1 leaders stay in the parent and join every child as federation
+/// gateways; every other member moves into the child `strategy` assigns it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwarmShardParams {
+    /// Same on every leader that plans the split in the same epoch.
+    pub shard_id: String,
+    pub parent_swarm_id: SwarmId,
+    pub epoch: u64,
+    pub strategy: ShardStrategy,
+    pub children: Vec<SwarmId>,
+    /// Tier-1 leaders of the parent, which stay and relay delegated tasks.
+    pub gateways: Vec<AgentId>,
+    /// Child index per Tier-1 leader under the geo strategy; a member goes
+    /// where its branch's leader points.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub branches: std::collections::BTreeMap<String, usize>,
+    /// Token for joining the children when the parent is private.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub child_token: Option<SwarmToken>,
+    pub issuer: AgentId,
+    /// Hex Ed25519 public key of the issuer.
+    pub issuer_key: String,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl SwarmShardParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `issuer_key` to the signer's public key and sign the split.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.issuer_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `issuer_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.issuer_key, &self.signature, &self.signing_payload())
+    }
+
+    /// The issuer's public key, if `issuer_key` is well-formed.
+    pub fn issuer_verifying_key(&self) -> Option<ed25519_dalek::VerifyingKey> {
+        let bytes: [u8; 32] = hex::decode(&self.issuer_key).ok()?.try_into().ok()?;
+        ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok()
+    }
+
+    /// The child `member` moves into. Under the geo strategy that is the
+    /// child of `branch_leader`, the Tier-1 leader above the member; a
+    /// member whose leader is unknown or unlisted falls back to the hash.
+    pub fn child_for(&self, member: &str, branch_leader: Option<&str>) -> Option<&SwarmId> {
+        if self.children.is_empty() {
+            return None;
+        }
+        let by_branch = match self.strategy {
+            ShardStrategy::Geo => branch_leader.and_then(|leader| self.branches.get(leader)),
+            ShardStrategy::Hash => None,
+        };
+        let index = match by_branch {
+            Some(index) => *index,
+            None => {
+                let digest = crate::crypto::sha256(member.as_bytes());
+                let mut prefix = [0u8; 8];
+                prefix.copy_from_slice(&digest[..8]);
+                (u64::from_be_bytes(prefix) % self.children.len() as u64) as usize
+            }
+        };
+        self.children.get(index % self.children.len())
+    }
+}
+
+/// Request to a federation gateway to inject a task into a swarm linked to
+/// the sender's, sent over the direct protocol. `task` holds the params of
+/// `swarm.inject_task`. The gateway answers with a `swarm.delegate` whose
+/// params are its RPC response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskDelegationParams {
+    pub sender: AgentId,
+    pub source_swarm_id: SwarmId,
+    pub target_swarm_id: SwarmId,
+    pub task: serde_json::Value,
+    /// Subtask in the source swarm standing in for the delegated task. The
+    /// gateway, then a bridge between both swarms, submits the task's
+    /// result as that subtask's once it completes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relay_to: Option<String>,
+}
+
 /// Keep-alive ping.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeepAliveParams {
@@ -198,7 +730,86 @@ pub struct KeepAliveParams {
     pub last_task_poll_at: Option<chrono::DateTime<chrono::Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_result_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Work the agent holds and the room it has left, for load-aware
+    /// assignment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<AgentLoad>,
+    /// Layout constraint the agent's operator set, honoured by every node
+    /// laying out the pyramid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<LayoutRole>,
     pub epoch: u64,
+    /// Length of the sender's current epoch in seconds, announced by a
+    /// connector in its own keep-alives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_duration_secs: Option<u64>,
+    /// Id of the private swarm topic key the sender holds, announced by a
+    /// connector in its own keep-alives when the swarm's topics are
+    /// encrypted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic_key: Option<String>,
+    /// Bootstrap the sender's view of the swarm descends from, announced by
+    /// a connector in its own keep-alives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<SwarmLineage>,
+    /// Services the agent offers, as in its DID document. Absent when it
+    /// has registered none; an empty list withdraws earlier ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub services: Option<Vec<crate::ServiceEndpoint>>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Below this fraction of spare CPU or memory an agent counts as saturated.
+pub const LOW_HEADROOM: f64 = 0.05;
+
+/// Load an agent reports in its keep-alives.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentLoad {
+    /// Tasks assigned to the agent that it has not started.
+    pub queue_depth: u32,
+    /// Tasks the agent is working on.
+    pub in_flight: u32,
+    /// Spare CPU of the agent's host, as a fraction of its cores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_headroom: Option<f64>,
+    /// Available memory of the agent's host, as a fraction of the total.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mem_headroom: Option<f64>,
+    /// Tasks the agent declares it can work on at once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<u32>,
+    /// IDs of the queued tasks, oldest first, so a parent can hand one to
+    /// an idle sibling.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub queued: Vec<String>,
+}
+
+impl AgentLoad {
+    /// Tasks the agent holds, queued or running.
+    pub fn held(&self) -> u32 {
+        self.queue_depth + self.in_flight
+    }
+
+    /// Whether the agent has no room for another task: it holds as many
+    /// as it declared it can run, or its host is short of CPU or memory.
+    pub fn is_saturated(&self) -> bool {
+        self.max_concurrency.is_some_and(|max| self.held() >= max)
+            || self.cpu_headroom.is_some_and(|h| h < LOW_HEADROOM)
+            || self.mem_headroom.is_some_and(|h| h < LOW_HEADROOM)
+    }
+
+    /// Held tasks per declared slot; an agent that declares no
+    /// concurrency is taken to run one task at a time.
+    pub fn utilization(&self) -> f64 {
+        self.held() as f64 / self.max_concurrency.unwrap_or(1).max(1) as f64
+    }
+}
+
+/// An idle executor asks its parent for a task queued at a sibling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkStealRequestParams {
+    pub agent_id: AgentId,
+    pub parent_id: AgentId,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
@@ -223,6 +834,10 @@ pub struct SwarmAnnounceParams {
     pub agent_count: u64,
     pub description: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Configured external multiaddrs of the announcing node, each ending
+    /// in `/p2p/<peer id>`, so peers can dial it through forwarded ports.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addrs: Vec<String>,
 }
 
 /// Request to join a swarm. For private swarms, includes token.
@@ -244,6 +859,39 @@ pub struct SwarmJoinResponseParams {
     pub reason: Option<String>,
 }
 
+/// The bootstrap a node's view of a swarm descends from. Two views of the
+/// same swarm ID with different lineages were bootstrapped independently
+/// and merge when they meet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwarmLineage {
+    /// Random ID drawn when the view was bootstrapped.
+    pub bootstrap_id: String,
+    pub bootstrapped_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Merge handshake between two independently bootstrapped views of a
+/// swarm, sent over the direct protocol to a peer of the other lineage.
+/// The peer merges and answers with its own, carrying the tasks the
+/// requester's digests lack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwarmMergeParams {
+    pub sender: AgentId,
+    pub swarm_id: SwarmId,
+    pub lineage: SwarmLineage,
+    pub epoch: u64,
+    /// Tier-1 leaders of the sender's current epoch.
+    #[serde(default)]
+    pub tier1_leaders: Vec<AgentId>,
+    /// Active members of the sender's view, itself included.
+    pub members: Vec<String>,
+    /// Digests of the sender's tasks, most recent first.
+    pub digests: Vec<TaskDigest>,
+    /// Tasks for the other side to replay: every task the sender knows in
+    /// a request, those missing from the request's digests in an answer.
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+}
+
 /// Leave a swarm notification.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwarmLeaveParams {
@@ -252,6 +900,122 @@ pub struct SwarmLeaveParams {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// A new epoch seed for a private swarm's topic keys, sealed to each
+/// member that may read the swarm's topics this epoch. Published on the
+/// keep-alive topic by the member issuing the epoch's key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpochRekeyParams {
+    pub swarm_id: SwarmId,
+    pub epoch: u64,
+    pub issuer: AgentId,
+    /// Hex Ed25519 public key of the issuer.
+    pub issuer_key: String,
+    /// Id of the topic key the seed derives, so members can check it.
+    pub key_id: String,
+    pub seeds: Vec<crate::topic_keys::SealedEpochSeed>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl EpochRekeyParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `issuer_key` to the signer's public key and sign the rekey.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.issuer_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `issuer_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.issuer_key, &self.signature, &self.signing_payload())
+    }
+
+    /// The issuer's public key, if `issuer_key` is well-formed.
+    pub fn issuer_verifying_key(&self) -> Option<ed25519_dalek::VerifyingKey> {
+        let bytes: [u8; 32] = hex::decode(&self.issuer_key).ok()?.try_into().ok()?;
+        ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok()
+    }
+}
+
+/// What a reputation attestation vouches for about its subject's work on a
+/// task. Timeliness and verification are attested separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationOutcome {
+    /// The result arrived before the task's deadline.
+    OnTime,
+    /// The result arrived after the task's deadline.
+    Late,
+    /// The result passed verification or won its dispute.
+    VerificationPassed,
+    /// The result failed verification or lost its dispute.
+    VerificationFailed,
+}
+
+impl AttestationOutcome {
+    /// Whether the outcome is about timeliness rather than verification.
+    pub fn is_timeliness(&self) -> bool {
+        matches!(self, Self::OnTime | Self::Late)
+    }
+}
+
+/// A statement, signed by the coordinator of a task's parent, of how its
+/// subject did on the task. Members aggregate attestations into a ledger
+/// so reputation is the same wherever it is read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationAttestation {
+    pub swarm_id: SwarmId,
+    pub subject: AgentId,
+    pub task_id: String,
+    pub outcome: AttestationOutcome,
+    pub issuer: AgentId,
+    /// Hex Ed25519 public key of the issuer.
+    pub issuer_key: String,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl ReputationAttestation {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `issuer_key` to the signer's public key and sign the attestation.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.issuer_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `issuer_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.issuer_key, &self.signature, &self.signing_payload())
+    }
+
+    /// The issuer's public key, if `issuer_key` is well-formed.
+    pub fn issuer_verifying_key(&self) -> Option<ed25519_dalek::VerifyingKey> {
+        let bytes: [u8; 32] = hex::decode(&self.issuer_key).ok()?.try_into().ok()?;
+        ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok()
+    }
+}
+
+/// A batch of reputation attestations, published on the keep-alive topic
+/// of the swarm they were issued in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationAttestParams {
+    pub attestations: Vec<ReputationAttestation>,
+}
+
 // ── Holonic Board Messages ──
 
 /// Board invitation from chair to local cluster.
@@ -264,6 +1028,10 @@ pub struct BoardInviteParams {
     pub required_capabilities: Vec<String>,
     pub capacity: usize,
     pub chair: AgentId,
+    /// Deliberation runs over an encrypted channel only board members can
+    /// read.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub private: bool,
 }
 
 /// Agent accepts a board invitation.
@@ -274,6 +1042,9 @@ pub struct BoardAcceptParams {
     pub active_tasks: u32,
     pub capabilities: Vec<String>,
     pub affinity_scores: std::collections::HashMap<String, f64>,
+    /// Hex X25519 key the chair seals a private board's group key to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_key: Option<String>,
 }
 
 /// Agent declines a board invitation.
@@ -290,6 +1061,9 @@ pub struct BoardReadyParams {
     pub chair_id: AgentId,
     pub members: Vec<AgentId>,
     pub adversarial_critic: Option<AgentId>,
+    /// A private board's group key, sealed to each member by DID.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub sealed_keys: std::collections::HashMap<String, crate::board_channel::SealedGroupKey>,
 }
 
 /// Board dissolves after task completion.
@@ -298,6 +1072,15 @@ pub struct BoardDissolveParams {
     pub task_id: String,
 }
 
+/// A deliberation message of a private board, encrypted under the board's
+/// group key. The plaintext is the serialized inner message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedBoardMessageParams {
+    pub task_id: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
 /// Critique message from a board member after proposals are revealed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscussionCritiqueParams {
@@ -306,6 +1089,10 @@ pub struct DiscussionCritiqueParams {
     pub round: u32,
     pub plan_scores: std::collections::HashMap<String, CriticScore>,
     pub content: String,
+    /// Plan IDs the critic considers malicious. Only the board's
+    /// adversarial critic's flags slash the proposers' bonds.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flagged_malicious: Vec<String>,
 }
 
 /// Direct P2P message between agents, propagated via GossipSub on the messages topic.
@@ -322,6 +1109,89 @@ pub struct DirectMessageParams {
     pub timestamp: String,
 }
 
+// ── Name Directory Messages ──
+
+/// Claim on a wws:// name, published on the names topic and stored in the
+/// DHT under the name's key. The earliest unexpired claim holds the name
+/// unless the directory's guardians award it to another claimant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NameClaimParams {
+    pub name: String,
+    pub did: String,
+    pub peer_id: String,
+    /// Unix seconds of the first claim; renewals keep it.
+    pub registered_at: u64,
+    pub expires_at: u64,
+    pub pow_nonce: u64,
+    /// Hex Ed25519 public key of the claimant.
+    pub public_key: String,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl NameClaimParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `public_key` to the signer's public key and sign the claim.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.public_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `public_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.public_key, &self.signature, &self.signing_payload())
+    }
+}
+
+/// A guardian's ruling on a name claimed by more than one DID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NameDisputeVoteParams {
+    pub name: String,
+    /// DID the guardian awards the name to.
+    pub award_to: String,
+    pub guardian_did: String,
+    /// Hex Ed25519 public key of the guardian.
+    pub guardian_key: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl NameDisputeVoteParams {
+    /// Canonical bytes covered by the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        unsigned_payload(self)
+    }
+
+    /// Set `guardian_key` to the signer's public key and sign the vote.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.guardian_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether the signature was made by the key in `guardian_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.guardian_key, &self.signature, &self.signing_payload())
+    }
+}
+
+/// JSON of a signed message without its `signature` field.
+pub(crate) fn unsigned_payload<T: Serialize>(message: &T) -> Vec<u8> {
+    let mut value = serde_json::to_value(message).unwrap_or_default();
+    if let Some(map) = value.as_object_mut() {
+        map.remove("signature");
+    }
+    serde_json::to_vec(&value).unwrap_or_default()
+}
+
 /// Enumeration of all protocol methods for pattern matching.
 #[derive(Debug, Clone)]
 pub enum ProtocolMethod {
@@ -332,24 +1202,51 @@ pub enum ProtocolMethod {
     TaskInjection,
     ProposalCommit,
     ProposalReveal,
+    ProposalRevision,
+    ProposalWithdraw,
     ConsensusVote,
+    ConsensusVoteCommit,
+    ConsensusVoteReveal,
+    VotingOpen,
+    VoteReminder,
+    BallotReceipt,
+    TaskStateRequest,
+    TaskStateSnapshot,
+    StateReconcile,
     TaskAssignment,
     ResultSubmission,
+    TaskProgress,
+    TaskEscalation,
+    TaskTenderFailed,
     VerificationResult,
+    DisputeVote,
     KeepAlive,
     AgentKeepAlive,
+    WorkStealRequest,
     Succession,
     SwarmAnnounce,
     SwarmJoin,
     SwarmJoinResponse,
+    SwarmMerge,
     SwarmLeave,
+    SwarmParams,
+    ParamsChangePropose,
+    ParamsChangeVote,
+    SwarmMigrate,
+    SwarmShard,
+    TaskDelegation,
+    EpochRekey,
+    ReputationAttest,
     BoardInvite,
     BoardAccept,
     BoardDecline,
     BoardReady,
     BoardDissolve,
+    BoardSealed,
     DiscussionCritique,
     AgentDirectMessage,
+    NameClaim,
+    NameDisputeVote,
 }
 
 impl ProtocolMethod {
@@ -362,24 +1259,51 @@ impl ProtocolMethod {
             Self::TaskInjection => "task.inject",
             Self::ProposalCommit => "consensus.proposal_commit",
             Self::ProposalReveal => "consensus.proposal_reveal",
+            Self::ProposalRevision => "consensus.proposal_revision",
+            Self::ProposalWithdraw => "consensus.proposal_withdraw",
             Self::ConsensusVote => "consensus.vote",
+            Self::ConsensusVoteCommit => "consensus.vote_commit",
+            Self::ConsensusVoteReveal => "consensus.vote_reveal",
+            Self::VotingOpen => "consensus.voting_open",
+            Self::VoteReminder => "consensus.vote_reminder",
+            Self::BallotReceipt => "consensus.ballot_receipt",
+            Self::TaskStateRequest => "task.state_request",
+            Self::TaskStateSnapshot => "task.state_snapshot",
+            Self::StateReconcile => "state.reconcile",
             Self::TaskAssignment => "task.assign",
             Self::ResultSubmission => "task.submit_result",
+            Self::TaskProgress => "task.progress",
+            Self::TaskEscalation => "task.escalate",
+            Self::TaskTenderFailed => "task.tender_failed",
             Self::VerificationResult => "task.verification",
+            Self::DisputeVote => "task.dispute_vote",
             Self::KeepAlive => "swarm.keepalive",
             Self::AgentKeepAlive => "agent.keepalive",
+            Self::WorkStealRequest => "task.steal_request",
             Self::Succession => "hierarchy.succession",
             Self::SwarmAnnounce => "swarm.announce",
             Self::SwarmJoin => "swarm.join",
             Self::SwarmJoinResponse => "swarm.join_response",
+            Self::SwarmMerge => "swarm.merge",
             Self::SwarmLeave => "swarm.leave",
+            Self::SwarmParams => "swarm.params",
+            Self::ParamsChangePropose => "swarm.params_propose",
+            Self::ParamsChangeVote => "swarm.params_vote",
+            Self::SwarmMigrate => "swarm.migrate",
+            Self::SwarmShard => "swarm.shard",
+            Self::TaskDelegation => "swarm.delegate",
+            Self::EpochRekey => "swarm.rekey",
+            Self::ReputationAttest => "reputation.attest",
             Self::BoardInvite => "board.invite",
             Self::BoardAccept => "board.accept",
             Self::BoardDecline => "board.decline",
             Self::BoardReady => "board.ready",
             Self::BoardDissolve => "board.dissolve",
+            Self::BoardSealed => "board.sealed",
             Self::DiscussionCritique => "discussion.critique",
             Self::AgentDirectMessage => "agent.direct_message",
+            Self::NameClaim => "name.claim",
+            Self::NameDisputeVote => "name.dispute_vote",
         }
     }
 
@@ -392,24 +1316,51 @@ impl ProtocolMethod {
             "task.inject" => Some(Self::TaskInjection),
             "consensus.proposal_commit" => Some(Self::ProposalCommit),
             "consensus.proposal_reveal" => Some(Self::ProposalReveal),
+            "consensus.proposal_revision" => Some(Self::ProposalRevision),
+            "consensus.proposal_withdraw" => Some(Self::ProposalWithdraw),
             "consensus.vote" => Some(Self::ConsensusVote),
+            "consensus.vote_commit" => Some(Self::ConsensusVoteCommit),
+            "consensus.vote_reveal" => Some(Self::ConsensusVoteReveal),
+            "consensus.voting_open" => Some(Self::VotingOpen),
+            "consensus.vote_reminder" => Some(Self::VoteReminder),
+            "consensus.ballot_receipt" => Some(Self::BallotReceipt),
+            "task.state_request" => Some(Self::TaskStateRequest),
+            "task.state_snapshot" => Some(Self::TaskStateSnapshot),
+            "state.reconcile" => Some(Self::StateReconcile),
             "task.assign" => Some(Self::TaskAssignment),
             "task.submit_result" => Some(Self::ResultSubmission),
+            "task.progress" => Some(Self::TaskProgress),
+            "task.escalate" => Some(Self::TaskEscalation),
+            "task.tender_failed" => Some(Self::TaskTenderFailed),
             "task.verification" => Some(Self::VerificationResult),
+            "task.dispute_vote" => Some(Self::DisputeVote),
             "swarm.keepalive" => Some(Self::KeepAlive),
             "agent.keepalive" => Some(Self::AgentKeepAlive),
+            "task.steal_request" => Some(Self::WorkStealRequest),
             "hierarchy.succession" => Some(Self::Succession),
             "swarm.announce" => Some(Self::SwarmAnnounce),
             "swarm.join" => Some(Self::SwarmJoin),
             "swarm.join_response" => Some(Self::SwarmJoinResponse),
+            "swarm.merge" => Some(Self::SwarmMerge),
             "swarm.leave" => Some(Self::SwarmLeave),
+            "swarm.params" => Some(Self::SwarmParams),
+            "swarm.params_propose" => Some(Self::ParamsChangePropose),
+            "swarm.params_vote" => Some(Self::ParamsChangeVote),
+            "swarm.migrate" => Some(Self::SwarmMigrate),
+            "swarm.shard" => Some(Self::SwarmShard),
+            "swarm.delegate" => Some(Self::TaskDelegation),
+            "swarm.rekey" => Some(Self::EpochRekey),
+            "reputation.attest" => Some(Self::ReputationAttest),
             "board.invite" => Some(Self::BoardInvite),
             "board.accept" => Some(Self::BoardAccept),
             "board.decline" => Some(Self::BoardDecline),
             "board.ready" => Some(Self::BoardReady),
             "board.dissolve" => Some(Self::BoardDissolve),
+            "board.sealed" => Some(Self::BoardSealed),
             "discussion.critique" => Some(Self::DiscussionCritique),
             "agent.direct_message" => Some(Self::AgentDirectMessage),
+            "name.claim" => Some(Self::NameClaim),
+            "name.dispute_vote" => Some(Self::NameDisputeVote),
             _ => None,
         }
     }
@@ -428,6 +1379,11 @@ impl SwarmTopics {
         format!("{}/swarm/discovery", crate::constants::TOPIC_PREFIX)
     }
 
+    /// Global wws:// name claims and guardian dispute votes.
+    pub fn names() -> String {
+        format!("{}/names", crate::constants::TOPIC_PREFIX)
+    }
+
     /// Swarm-specific announcement topic.
     pub fn swarm_announce(swarm_id: &str) -> String {
         format!("{}/swarm/{}/announce", crate::constants::TOPIC_PREFIX, swarm_id)
@@ -504,6 +1460,21 @@ impl SwarmTopics {
     pub fn messages_for(swarm_id: &str) -> String {
         format!("{}/s/{}/messages", crate::constants::TOPIC_PREFIX, swarm_id)
     }
+
+    /// Swarm parameter records, change proposals and votes, and
+    /// migrations and splits out of the swarm.
+    pub fn params_for(swarm_id: &str) -> String {
+        format!("{}/s/{}/params", crate::constants::TOPIC_PREFIX, swarm_id)
+    }
+
+    /// The swarm a swarm-scoped topic belongs to; `None` for global topics.
+    pub fn swarm_of(topic: &str) -> Option<&str> {
+        let rest = topic.strip_prefix(crate::constants::TOPIC_PREFIX)?;
+        let rest = rest
+            .strip_prefix("/s/")
+            .or_else(|| rest.strip_prefix("/swarm/").filter(|r| r.ends_with("/announce")))?;
+        rest.split('/').next().filter(|id| !id.is_empty())
+    }
 }
 
 #[cfg(test)]
@@ -530,8 +1501,13 @@ mod tests {
             ProtocolMethod::Handshake,
             ProtocolMethod::Candidacy,
             ProtocolMethod::ConsensusVote,
+            ProtocolMethod::ConsensusVoteCommit,
+            ProtocolMethod::ConsensusVoteReveal,
+            ProtocolMethod::VotingOpen,
+            ProtocolMethod::VoteReminder,
             ProtocolMethod::ResultSubmission,
             ProtocolMethod::AgentKeepAlive,
+            ProtocolMethod::WorkStealRequest,
         ];
         for method in methods {
             let s = method.as_str();
@@ -540,6 +1516,180 @@ mod tests {
         }
     }
 
+    #[test]
+    fn migrations_verify_only_as_signed() {
+        let key = crate::crypto::generate_keypair();
+        let mut migration = SwarmMigrateParams {
+            migration_id: "m1".into(),
+            source_swarm_id: SwarmId::new("old".into()),
+            target_swarm_id: SwarmId::new("new".into()),
+            target_token: None,
+            creator_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        assert!(!migration.verify());
+        migration.sign(&key);
+        assert!(migration.verify());
+
+        let redirected = SwarmMigrateParams {
+            target_swarm_id: SwarmId::new("elsewhere".into()),
+            ..migration
+        };
+        assert!(!redirected.verify());
+    }
+
+    #[test]
+    fn shards_verify_and_place_members_the_same_way_everywhere() {
+        let key = crate::crypto::generate_keypair();
+        let mut shard = SwarmShardParams {
+            shard_id: "lab@7".into(),
+            parent_swarm_id: SwarmId::new("lab".into()),
+            epoch: 7,
+            strategy: ShardStrategy::Geo,
+            children: vec![SwarmId::new("lab.7-0".into()), SwarmId::new("lab.7-1".into())],
+            gateways: vec![AgentId::new("did:swarm:leader-a".into())],
+            branches: [("did:swarm:leader-a".to_string(), 1)].into_iter().collect(),
+            child_token: None,
+            issuer: AgentId::new("did:swarm:leader-a".into()),
+            issuer_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        shard.sign(&key);
+        assert!(shard.verify());
+
+        assert_eq!(
+            shard.child_for("did:swarm:m1", Some("did:swarm:leader-a")).unwrap().as_str(),
+            "lab.7-1"
+        );
+        let hashed = shard.child_for("did:swarm:m1", None).unwrap().clone();
+        assert_eq!(shard.child_for("did:swarm:m1", Some("did:swarm:unknown")), Some(&hashed));
+
+        let rerouted = SwarmShardParams {
+            branches: [("did:swarm:leader-a".to_string(), 0)].into_iter().collect(),
+            ..shard
+        };
+        assert!(!rerouted.verify());
+    }
+
+    #[test]
+    fn attestations_verify_only_with_the_outcome_signed() {
+        let key = crate::crypto::generate_keypair();
+        let mut attestation = ReputationAttestation {
+            swarm_id: SwarmId::new("lab".into()),
+            subject: AgentId::new("did:swarm:worker".into()),
+            task_id: "task-1".into(),
+            outcome: AttestationOutcome::OnTime,
+            issuer: AgentId::new("did:swarm:chair".into()),
+            issuer_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        attestation.sign(&key);
+        assert!(attestation.verify());
+        assert_eq!(attestation.issuer_verifying_key(), Some(key.verifying_key()));
+
+        let upgraded = ReputationAttestation {
+            outcome: AttestationOutcome::VerificationPassed,
+            ..attestation
+        };
+        assert!(!upgraded.verify());
+    }
+
+    #[test]
+    fn renamed_claims_fail_verification() {
+        let key = crate::crypto::generate_keypair();
+        let mut claim = NameClaimParams {
+            name: "alice".into(),
+            did: "did:swarm:alice".into(),
+            peer_id: "12D3KooWalice".into(),
+            registered_at: 1_000,
+            expires_at: 87_400,
+            pow_nonce: 0,
+            public_key: String::new(),
+            signature: String::new(),
+        };
+        claim.sign(&key);
+        assert!(claim.verify());
+        assert!(!NameClaimParams { name: "bob".into(), ..claim }.verify());
+    }
+
+    #[test]
+    fn ballot_receipts_bind_the_recorder_key() {
+        let key = crate::crypto::generate_keypair();
+        let mut receipt = BallotReceiptParams {
+            task_id: "t1".into(),
+            epoch: 1,
+            voter: AgentId::new("did:swarm:voter".into()),
+            ballot_hash: "ab".repeat(32),
+            position: 3,
+            recorder: AgentId::new(crate::crypto::derive_agent_id(&key.verifying_key())),
+            recorder_key: String::new(),
+            recorded_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        receipt.sign(&key);
+        assert!(receipt.verify());
+        assert_eq!(receipt.recorder_verifying_key(), Some(key.verifying_key()));
+
+        let moved = BallotReceiptParams { position: 1, ..receipt.clone() };
+        assert!(!moved.verify());
+        assert_ne!(moved.receipt_hash(), receipt.receipt_hash());
+    }
+
+    #[test]
+    fn assignments_are_bound_to_their_issuer_key() {
+        let key = crate::crypto::generate_keypair();
+        let mut assignment = TaskAssignmentParams {
+            task: Task::new("Summarize".into(), 2, 4),
+            assignee: AgentId::new("did:swarm:executor".into()),
+            parent_task_id: "root".into(),
+            winning_plan_id: "plan-1".into(),
+            issuer: AgentId::new(crate::crypto::derive_agent_id(&key.verifying_key())),
+            issuer_key: String::new(),
+            signature: String::new(),
+        };
+        assert!(!assignment.verify());
+        assignment.sign(&key);
+        assert!(assignment.verify());
+        assert_eq!(assignment.issuer_verifying_key(), Some(key.verifying_key()));
+
+        let json = serde_json::to_value(&assignment).unwrap();
+        let parsed: TaskAssignmentParams = serde_json::from_value(json).unwrap();
+        assert!(parsed.verify());
+        let redirected = TaskAssignmentParams {
+            assignee: AgentId::new("did:swarm:mallory".into()),
+            ..parsed
+        };
+        assert!(!redirected.verify());
+
+        let mut tier = TierAssignmentParams {
+            assigned_agent: AgentId::new("did:swarm:executor".into()),
+            tier: Tier::Tier2,
+            parent_id: AgentId::new("did:swarm:leader".into()),
+            epoch: 4,
+            branch_size: 10,
+            issuer: AgentId::new("did:swarm:leader".into()),
+            issuer_key: String::new(),
+            signature: String::new(),
+        };
+        tier.sign(&key);
+        assert!(tier.verify());
+        tier.tier = Tier::Tier1;
+        assert!(!tier.verify());
+    }
+
+    #[test]
+    fn swarm_scoped_topics_name_their_swarm() {
+        assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::voting_for("lab", "t1")), Some("lab"));
+        assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::swarm_announce("lab")), Some("lab"));
+        assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::keepalive()), Some("public"));
+        assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::swarm_discovery()), None);
+        assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::names()), None);
+        assert_eq!(SwarmTopics::swarm_of("/other/s/lab/keepalive"), None);
+    }
+
     #[test]
     fn test_response_success() {
         let resp = SwarmResponse::success(Some("id-1".into()), serde_json::json!({"ok": true}));
@@ -560,7 +1710,16 @@ mod tests {
             ProtocolMethod::SwarmAnnounce,
             ProtocolMethod::SwarmJoin,
             ProtocolMethod::SwarmJoinResponse,
+            ProtocolMethod::SwarmMerge,
             ProtocolMethod::SwarmLeave,
+            ProtocolMethod::SwarmParams,
+            ProtocolMethod::ParamsChangePropose,
+            ProtocolMethod::ParamsChangeVote,
+            ProtocolMethod::SwarmMigrate,
+            ProtocolMethod::SwarmShard,
+            ProtocolMethod::TaskDelegation,
+            ProtocolMethod::EpochRekey,
+            ProtocolMethod::ReputationAttest,
         ];
         for method in methods {
             let s = method.as_str();
@@ -577,7 +1736,17 @@ mod tests {
             ProtocolMethod::BoardDecline,
             ProtocolMethod::BoardReady,
             ProtocolMethod::BoardDissolve,
+            ProtocolMethod::BoardSealed,
             ProtocolMethod::DiscussionCritique,
+            ProtocolMethod::ProposalRevision,
+            ProtocolMethod::ProposalWithdraw,
+            ProtocolMethod::BallotReceipt,
+            ProtocolMethod::TaskStateRequest,
+            ProtocolMethod::TaskStateSnapshot,
+            ProtocolMethod::StateReconcile,
+            ProtocolMethod::TaskProgress,
+            ProtocolMethod::TaskEscalation,
+            ProtocolMethod::TaskTenderFailed,
             ProtocolMethod::AgentDirectMessage,
         ];
         for method in methods {
@@ -691,6 +1860,7 @@ mod tests {
             required_capabilities: vec!["distributed-systems".to_string(), "consensus".to_string()],
             capacity: 5,
             chair: AgentId::new("did:swarm:chair".to_string()),
+            private: false,
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -716,6 +1886,7 @@ mod tests {
             active_tasks: 2,
             capabilities: vec!["consensus".to_string()],
             affinity_scores: affinity,
+            channel_key: None,
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -753,6 +1924,7 @@ mod tests {
                 adversarial.clone(),
             ],
             adversarial_critic: Some(adversarial.clone()),
+            sealed_keys: std::collections::HashMap::new(),
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -770,6 +1942,7 @@ mod tests {
             chair_id: AgentId::new("did:swarm:chair".to_string()),
             members: vec![AgentId::new("did:swarm:chair".to_string())],
             adversarial_critic: None,
+            sealed_keys: std::collections::HashMap::new(),
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -807,6 +1980,7 @@ mod tests {
             round: 2,
             plan_scores,
             content: "Plan 1 is superior in parallelism but plan 2 has better completeness coverage".to_string(),
+            flagged_malicious: vec![],
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -830,6 +2004,7 @@ mod tests {
             required_capabilities: vec!["oncology".to_string()],
             capacity: 7,
             chair: AgentId::new("did:swarm:root".to_string()),
+            private: false,
         };
 
         let msg = SwarmMessage::new(
@@ -0,0 +1,210 @@
+//! Result schemas: constraints a task's result artifacts must satisfy.
+//!
+//! A task may carry a [`ResultSchema`] listing the content types it accepts
+//! and a JSON Schema the result content must validate against, so the
+//! aggregation of subtask results can rely on their structure. The
+//! validator covers the subset of JSON Schema used to describe structured
+//! results: `type`, `enum`, `const`, `properties`, `required`,
+//! `additionalProperties`, `items`, `minItems`/`maxItems`,
+//! `minLength`/`maxLength` and `minimum`/`maximum`. Other keywords are
+//! ignored.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Constraints on the result artifacts submitted for a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultSchema {
+    /// Accepted MIME types, e.g. `application/json` or `text/*`. Empty
+    /// accepts any content type.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_types: Vec<String>,
+    /// JSON Schema the artifact content must validate against. The content
+    /// must then be a JSON document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_schema: Option<Value>,
+    /// Reject non-conforming results; when false they are accepted and
+    /// flagged.
+    #[serde(default = "default_strict")]
+    pub strict: bool,
+}
+
+fn default_strict() -> bool {
+    true
+}
+
+impl ResultSchema {
+    /// Every way `content` of type `content_type` breaks the schema; empty
+    /// when it conforms.
+    pub fn check(&self, content_type: &str, content: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+        if !self.content_types.is_empty()
+            && !self
+                .content_types
+                .iter()
+                .any(|accepted| content_type_matches(accepted, content_type))
+        {
+            violations.push(format!(
+                "content type {:?} is not one of {:?}",
+                content_type, self.content_types
+            ));
+        }
+        if let Some(schema) = &self.json_schema {
+            match serde_json::from_str::<Value>(content) {
+                Ok(value) => violations.extend(validate_json(schema, &value)),
+                Err(e) => violations.push(format!("content is not valid JSON: {}", e)),
+            }
+        }
+        violations
+    }
+}
+
+/// Whether `actual` matches the accepted MIME type `accepted`, ignoring
+/// parameters such as `; charset=utf-8`. `type/*` and `*/*` are wildcards.
+pub fn content_type_matches(accepted: &str, actual: &str) -> bool {
+    let essence = |s: &str| s.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    let (accepted, actual) = (essence(accepted), essence(actual));
+    if accepted == "*/*" || accepted == actual {
+        return true;
+    }
+    match accepted.strip_suffix("/*") {
+        Some(prefix) => actual.split('/').next() == Some(prefix),
+        None => false,
+    }
+}
+
+/// Validate `value` against `schema`, returning one message per violation
+/// with the JSON path it occurred at.
+pub fn validate_json(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "$", &mut errors);
+    errors
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value is allowed here", path));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| has_type(value, t)) {
+            errors.push(format!("{}: expected {}, found {}", path, allowed.join(" or "), type_name(value)));
+            return;
+        }
+    }
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            errors.push(format!("{}: {} is not one of the allowed values", path, value));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{}: expected {}", path, expected));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(key) {
+                        errors.push(format!("{}: missing required property {:?}", path, key));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, child) in object {
+                let child_path = format!("{}.{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => validate_at(child_schema, child, &child_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{}: unexpected property", child_path));
+                        }
+                        Some(extra) => validate_at(extra, child, &child_path, errors),
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    errors.push(format!("{}: expected at least {} items", path, min));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if items.len() as u64 > max {
+                    errors.push(format!("{}: expected at most {} items", path, max));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    errors.push(format!("{}: shorter than {} characters", path, min));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    errors.push(format!("{}: longer than {} characters", path, max));
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or(f64::NAN);
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if n < min {
+                    errors.push(format!("{}: {} is below the minimum {}", path, n, min));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if n > max {
+                    errors.push(format!("{}: {} is above the maximum {}", path, n, max));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
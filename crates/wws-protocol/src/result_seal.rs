@@ -0,0 +1,174 @@
+//! Results readable only by the members allowed to see them.
+//!
+//! A task whose `visibility` is not `public` has its result content sealed
+//! before it leaves the submitting node: the content is encrypted with a
+//! random key, and that key is sealed to the Ed25519 identity key of each
+//! allowed member (an ephemeral X25519 exchange with the key's Montgomery
+//! form, HKDF-SHA256 and ChaCha20-Poly1305), as epoch seeds are. The
+//! artifact then travels with an empty `content` and a [`SealedResult`];
+//! members outside the set see that a result exists, its CID and size, but
+//! not what it says.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use hkdf::Hkdf;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+use crate::ProtocolError;
+
+const NONCE_LEN: usize = 12;
+const KEY_WRAP_INFO: &[u8] = b"wws/result-seal/key-wrap";
+
+/// The content key sealed to one allowed member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedResultKey {
+    pub member: String,
+    /// Hex-encoded ephemeral X25519 public key of the submitter.
+    pub ephemeral_key: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Result content encrypted for a set of members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedResult {
+    pub keys: Vec<SealedResultKey>,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl SealedResult {
+    /// Encrypt the result `content` of `task_id` for `recipients`, each a
+    /// member and its identity key.
+    pub fn seal(
+        task_id: &str,
+        content: &[u8],
+        recipients: &[(String, VerifyingKey)],
+    ) -> Result<Self, ProtocolError> {
+        let mut content_key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut content_key);
+        let keys = recipients
+            .iter()
+            .map(|(member, key)| seal_key(&content_key, member, key))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (nonce, ciphertext) = encrypt(&content_key, content, task_id.as_bytes())?;
+        Ok(Self {
+            keys,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Members the result is sealed to.
+    pub fn members(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(|k| k.member.as_str())
+    }
+
+    /// Decrypt the result of `task_id` as `member`, owner of `identity`.
+    pub fn open(&self, task_id: &str, member: &str, identity: &SigningKey) -> Result<Vec<u8>, ProtocolError> {
+        let sealed = self
+            .keys
+            .iter()
+            .find(|k| k.member == member)
+            .ok_or_else(|| ProtocolError::Crypto(format!("result not sealed to {}", member)))?;
+        let content_key = open_key(sealed, identity)?;
+        let nonce = decode_hex(&self.nonce, "nonce")?;
+        let ciphertext = decode_hex(&self.ciphertext, "ciphertext")?;
+        decrypt(&content_key, &nonce, &ciphertext, task_id.as_bytes())
+    }
+}
+
+fn seal_key(content_key: &[u8; 32], member: &str, member_key: &VerifyingKey) -> Result<SealedResultKey, ProtocolError> {
+    let member_x = PublicKey::from(member_key.to_montgomery().to_bytes());
+    let ephemeral = EphemeralSecret::random_from_rng(rand::thread_rng());
+    let ephemeral_key = PublicKey::from(&ephemeral);
+    let shared = ephemeral.diffie_hellman(&member_x);
+    let wrap_key = derive_wrap_key(shared.as_bytes(), &ephemeral_key, &member_x);
+    let (nonce, ciphertext) = encrypt(&wrap_key, content_key, member.as_bytes())?;
+    Ok(SealedResultKey {
+        member: member.to_string(),
+        ephemeral_key: hex::encode(ephemeral_key.as_bytes()),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn open_key(sealed: &SealedResultKey, identity: &SigningKey) -> Result<[u8; 32], ProtocolError> {
+    let ephemeral_key: [u8; 32] = decode_hex(&sealed.ephemeral_key, "ephemeral key")?
+        .try_into()
+        .map_err(|_| ProtocolError::Crypto("invalid ephemeral key".into()))?;
+    let ephemeral_key = PublicKey::from(ephemeral_key);
+    let secret = StaticSecret::from(identity.to_scalar_bytes());
+    let own_key = PublicKey::from(identity.verifying_key().to_montgomery().to_bytes());
+    let shared = secret.diffie_hellman(&ephemeral_key);
+    let wrap_key = derive_wrap_key(shared.as_bytes(), &ephemeral_key, &own_key);
+    let nonce = decode_hex(&sealed.nonce, "nonce")?;
+    let ciphertext = decode_hex(&sealed.ciphertext, "ciphertext")?;
+    decrypt(&wrap_key, &nonce, &ciphertext, sealed.member.as_bytes())?
+        .try_into()
+        .map_err(|_| ProtocolError::Crypto("sealed result key has the wrong length".into()))
+}
+
+fn derive_wrap_key(shared: &[u8; 32], ephemeral_key: &PublicKey, member_key: &PublicKey) -> [u8; 32] {
+    let mut info = KEY_WRAP_INFO.to_vec();
+    info.extend_from_slice(ephemeral_key.as_bytes());
+    info.extend_from_slice(member_key.as_bytes());
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, shared)
+        .expand(&info, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+fn encrypt(key: &[u8; 32], msg: &[u8], aad: &[u8]) -> Result<([u8; NONCE_LEN], Vec<u8>), ProtocolError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+        .map_err(|_| ProtocolError::Crypto("encryption failed".into()))?;
+    Ok((nonce, ciphertext))
+}
+
+fn decrypt(key: &[u8; 32], nonce: &[u8], msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+    if nonce.len() != NONCE_LEN {
+        return Err(ProtocolError::Crypto("invalid nonce".into()));
+    }
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), Payload { msg, aad })
+        .map_err(|_| ProtocolError::Crypto("decryption failed".into()))
+}
+
+fn decode_hex(value: &str, what: &str) -> Result<Vec<u8>, ProtocolError> {
+    hex::decode(value).map_err(|e| ProtocolError::Crypto(format!("invalid {}: {}", what, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+
+    #[test]
+    fn results_open_only_for_allowed_members() {
+        let chair = generate_keypair();
+        let member = generate_keypair();
+        let outsider = generate_keypair();
+        let recipients = vec![
+            ("did:swarm:chair".to_string(), chair.verifying_key()),
+            ("did:swarm:member".to_string(), member.verifying_key()),
+        ];
+        let sealed = SealedResult::seal("task-1", b"the answer", &recipients).unwrap();
+        assert_eq!(sealed.members().collect::<Vec<_>>(), ["did:swarm:chair", "did:swarm:member"]);
+
+        assert_eq!(sealed.open("task-1", "did:swarm:member", &member).unwrap(), b"the answer");
+        assert_eq!(sealed.open("task-1", "did:swarm:chair", &chair).unwrap(), b"the answer");
+        assert!(sealed.open("task-1", "did:swarm:outsider", &outsider).is_err());
+        // A member's key does not open another member's copy.
+        assert!(sealed.open("task-1", "did:swarm:chair", &outsider).is_err());
+        // The content is bound to its task.
+        assert!(sealed.open("task-2", "did:swarm:member", &member).is_err());
+    }
+}
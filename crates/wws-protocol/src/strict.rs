@@ -0,0 +1,247 @@
+//! Strict decoding of message params received from untrusted peers.
+//!
+//! Plain `serde` decoding accepts whatever fits the target type: a string
+//! of any length, an array of millions of entries, arbitrarily deep
+//! nesting inside `serde_json::Value` fields, or a timestamp centuries
+//! away that throws off every deadline and staleness check. Strict decoding
+//! first walks the raw JSON and checks it against [`StrictLimits`], then
+//! decodes it into the params type. Every problem is reported as a
+//! [`FieldError`] naming the path of the offending field, so a rejected
+//! message says exactly what was wrong with it.
+//!
+//! Unknown fields are ignored by default, so older nodes keep accepting
+//! messages from newer ones; `deny_unknown_fields` rejects them instead.
+//! The check covers the top-level fields of the params struct.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Bounds applied to params before they are decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrictLimits {
+    /// Longest string, in bytes, anywhere in the params.
+    pub max_string_bytes: usize,
+    /// Most entries in any array.
+    pub max_array_len: usize,
+    /// Most keys in any object.
+    pub max_object_keys: usize,
+    /// Deepest nesting of arrays and objects.
+    pub max_depth: usize,
+    /// Furthest a timestamp may lie in the future, in seconds.
+    pub max_future_secs: i64,
+    /// Reject top-level fields the params type does not define.
+    pub deny_unknown_fields: bool,
+}
+
+impl Default for StrictLimits {
+    fn default() -> Self {
+        Self {
+            max_string_bytes: 64 * 1024,
+            max_array_len: 4096,
+            max_object_keys: 256,
+            max_depth: 32,
+            max_future_secs: 10 * 365 * 24 * 3600,
+            deny_unknown_fields: false,
+        }
+    }
+}
+
+/// Earliest timestamp accepted, 2000-01-01T00:00:00Z.
+pub const MIN_TIMESTAMP_SECS: i64 = 946_684_800;
+
+/// One problem found in the params, at `path` (e.g. `task.subtasks[3]`;
+/// empty for the params object itself).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub path: String,
+    pub problem: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.problem)
+        } else {
+            write!(f, "{}: {}", self.path, self.problem)
+        }
+    }
+}
+
+/// All problems found in one set of params.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictError(pub Vec<FieldError>);
+
+impl fmt::Display for StrictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self.0.iter().map(FieldError::to_string).collect();
+        write!(f, "{}", errors.join("; "))
+    }
+}
+
+impl std::error::Error for StrictError {}
+
+/// Check `params` against `limits` and decode them into `T`.
+pub fn decode_strict<T: DeserializeOwned>(
+    params: &serde_json::Value,
+    limits: &StrictLimits,
+) -> Result<T, StrictError> {
+    let mut errors = Vec::new();
+    check_value(params, &mut String::new(), 0, limits, Utc::now(), &mut errors);
+    if limits.deny_unknown_fields {
+        if let (Some(fields), Some(object)) = (field_names::<T>(), params.as_object()) {
+            errors.extend(
+                object
+                    .keys()
+                    .filter(|key| !fields.contains(&key.as_str()))
+                    .map(|key| FieldError {
+                        path: key.clone(),
+                        problem: "unknown field".to_string(),
+                    }),
+            );
+        }
+    }
+    if !errors.is_empty() {
+        return Err(StrictError(errors));
+    }
+    serde_path_to_error::deserialize(params).map_err(|e| {
+        let path = e.path().to_string();
+        StrictError(vec![FieldError {
+            path: if path == "." { String::new() } else { path },
+            problem: e.into_inner().to_string(),
+        }])
+    })
+}
+
+fn check_value(
+    value: &serde_json::Value,
+    path: &mut String,
+    depth: usize,
+    limits: &StrictLimits,
+    now: DateTime<Utc>,
+    errors: &mut Vec<FieldError>,
+) {
+    let mut fail = |problem: String| {
+        errors.push(FieldError {
+            path: path.clone(),
+            problem,
+        })
+    };
+    match value {
+        serde_json::Value::String(s) if s.len() > limits.max_string_bytes => fail(format!(
+            "string of {} bytes exceeds {}",
+            s.len(),
+            limits.max_string_bytes
+        )),
+        serde_json::Value::Array(items) => {
+            if depth >= limits.max_depth {
+                return fail(format!("nested deeper than {}", limits.max_depth));
+            }
+            if items.len() > limits.max_array_len {
+                return fail(format!(
+                    "array of {} entries exceeds {}",
+                    items.len(),
+                    limits.max_array_len
+                ));
+            }
+            for (i, item) in items.iter().enumerate() {
+                let len = path.len();
+                path.push_str(&format!("[{}]", i));
+                check_value(item, path, depth + 1, limits, now, errors);
+                path.truncate(len);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            if depth >= limits.max_depth {
+                return fail(format!("nested deeper than {}", limits.max_depth));
+            }
+            if fields.len() > limits.max_object_keys {
+                return fail(format!(
+                    "object of {} keys exceeds {}",
+                    fields.len(),
+                    limits.max_object_keys
+                ));
+            }
+            for (key, field) in fields {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+                if is_timestamp_field(key) {
+                    if let Some(problem) = timestamp_problem(field, limits, now) {
+                        errors.push(FieldError {
+                            path: path.clone(),
+                            problem,
+                        });
+                    }
+                }
+                check_value(field, path, depth + 1, limits, now, errors);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fields holding a point in time, by the protocol's naming convention.
+fn is_timestamp_field(key: &str) -> bool {
+    key == "timestamp" || key == "deadline" || key.ends_with("_at")
+}
+
+fn timestamp_problem(
+    value: &serde_json::Value,
+    limits: &StrictLimits,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let at = DateTime::parse_from_rfc3339(value.as_str()?).ok()?;
+    let secs = at.timestamp();
+    if secs < MIN_TIMESTAMP_SECS {
+        return Some(format!("timestamp {} is before 2000", at));
+    }
+    if secs > now.timestamp().saturating_add(limits.max_future_secs) {
+        return Some(format!(
+            "timestamp {} is more than {}s in the future",
+            at, limits.max_future_secs
+        ));
+    }
+    None
+}
+
+/// Names of the fields a struct type deserializes, or `None` for types
+/// that are not plain structs (maps, enums, flattened structs).
+pub fn field_names<T: DeserializeOwned>() -> Option<&'static [&'static str]> {
+    let mut fields = None;
+    let _ = T::deserialize(FieldProbe(&mut fields));
+    fields
+}
+
+/// A deserializer that records the field list `serde_derive` passes to
+/// `deserialize_struct` and then fails.
+struct FieldProbe<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for FieldProbe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("field probe"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(de::Error::custom("field probe"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
@@ -0,0 +1,394 @@
+//! Epoch-scoped topic keys for private swarms.
+//!
+//! A private swarm's swarm-scoped topics (`/s/<swarm_id>/...`) can be
+//! encrypted so that only members read them. Keys are derived with
+//! HKDF-SHA256 from the swarm token and a random [`EpochSeed`]:
+//!
+//! - the **admission key** comes from the token alone and only covers the
+//!   keep-alive topic, which carries membership and `swarm.rekey`, so a
+//!   node holding the token can announce itself and receive a seed;
+//! - the **epoch key** also mixes in the seed drawn for the current epoch.
+//!   At each epoch transition a new seed is sealed to the Ed25519 identity
+//!   key of every active member (an ephemeral X25519 exchange with the
+//!   key's Montgomery form, HKDF-SHA256 and ChaCha20-Poly1305) and
+//!   published in `swarm.rekey`. A member that left before the transition
+//!   gets no copy of the seed, so knowing the token stops being enough to
+//!   read new traffic.
+//!
+//! Encrypted payloads are `WWE1 | key id (8) | nonce (12) | ciphertext`,
+//! with the topic as associated data. A [`TopicKeyring`] keeps the last few
+//! epoch keys so messages sent just before a rekey can still be read.
+
+use std::collections::VecDeque;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use hkdf::Hkdf;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+use crate::messages::SwarmTopics;
+use crate::types::SwarmToken;
+use crate::ProtocolError;
+
+const ENVELOPE_MAGIC: &[u8; 4] = b"WWE1";
+const KEY_ID_LEN: usize = 8;
+const NONCE_LEN: usize = 12;
+const ADMISSION_INFO: &[u8] = b"wws/topic-keys/admission";
+const EPOCH_INFO: &[u8] = b"wws/topic-keys/epoch";
+const SEED_WRAP_INFO: &[u8] = b"wws/topic-keys/seed-wrap";
+
+/// Epoch keys kept after a rekey, for messages still in flight.
+pub const RETAINED_EPOCH_KEYS: usize = 3;
+
+/// Random secret drawn for one epoch.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EpochSeed([u8; 32]);
+
+impl std::fmt::Debug for EpochSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EpochSeed(..)")
+    }
+}
+
+/// An epoch seed sealed to one member's identity key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedEpochSeed {
+    pub member: String,
+    /// Hex-encoded ephemeral X25519 public key of the issuer.
+    pub ephemeral_key: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl EpochSeed {
+    pub fn generate() -> Self {
+        let mut seed = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut seed);
+        Self(seed)
+    }
+
+    /// Seal this seed to `member`, whose identity key is `member_key`.
+    pub fn seal_for(&self, member: &str, member_key: &VerifyingKey) -> Result<SealedEpochSeed, ProtocolError> {
+        let member_x = PublicKey::from(member_key.to_montgomery().to_bytes());
+        let ephemeral = EphemeralSecret::random_from_rng(rand::thread_rng());
+        let ephemeral_key = PublicKey::from(&ephemeral);
+        let shared = ephemeral.diffie_hellman(&member_x);
+        let wrap_key = derive_wrap_key(shared.as_bytes(), &ephemeral_key, &member_x);
+        let (nonce, ciphertext) = encrypt(&wrap_key, &self.0, member.as_bytes())?;
+        Ok(SealedEpochSeed {
+            member: member.to_string(),
+            ephemeral_key: hex::encode(ephemeral_key.as_bytes()),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Recover a seed sealed to the owner of `identity`.
+    pub fn open(sealed: &SealedEpochSeed, identity: &SigningKey) -> Result<Self, ProtocolError> {
+        let ephemeral_key = PublicKey::from(decode_array::<32>(&sealed.ephemeral_key, "ephemeral key")?);
+        let secret = StaticSecret::from(identity.to_scalar_bytes());
+        let own_key = PublicKey::from(identity.verifying_key().to_montgomery().to_bytes());
+        let shared = secret.diffie_hellman(&ephemeral_key);
+        let wrap_key = derive_wrap_key(shared.as_bytes(), &ephemeral_key, &own_key);
+        let nonce = decode_array::<NONCE_LEN>(&sealed.nonce, "nonce")?;
+        let ciphertext = hex::decode(&sealed.ciphertext)
+            .map_err(|e| ProtocolError::Crypto(format!("invalid ciphertext: {}", e)))?;
+        let seed = decrypt(&wrap_key, &nonce, &ciphertext, sealed.member.as_bytes())?;
+        let seed: [u8; 32] = seed
+            .try_into()
+            .map_err(|_| ProtocolError::Crypto("sealed epoch seed has the wrong length".into()))?;
+        Ok(Self(seed))
+    }
+}
+
+/// Symmetric key for a swarm's topics.
+#[derive(Clone)]
+pub struct TopicKey {
+    key: [u8; 32],
+    id: [u8; KEY_ID_LEN],
+}
+
+impl std::fmt::Debug for TopicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TopicKey({})", self.id_hex())
+    }
+}
+
+impl TopicKey {
+    fn derive(token: &SwarmToken, salt: Option<&[u8]>, info: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(salt, token.as_str().as_bytes())
+            .expand(info, &mut key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        let digest = Sha256::digest(key);
+        let mut id = [0u8; KEY_ID_LEN];
+        id.copy_from_slice(&digest[..KEY_ID_LEN]);
+        Self { key, id }
+    }
+
+    /// Key of the keep-alive topic, known to every holder of the token.
+    pub fn admission(token: &SwarmToken, swarm_id: &str) -> Self {
+        let mut info = ADMISSION_INFO.to_vec();
+        info.extend_from_slice(swarm_id.as_bytes());
+        Self::derive(token, None, &info)
+    }
+
+    /// Key of the swarm's other topics during `epoch`.
+    pub fn for_epoch(token: &SwarmToken, swarm_id: &str, epoch: u64, seed: &EpochSeed) -> Self {
+        let mut info = EPOCH_INFO.to_vec();
+        info.extend_from_slice(swarm_id.as_bytes());
+        info.extend_from_slice(&epoch.to_be_bytes());
+        Self::derive(token, Some(&seed.0), &info)
+    }
+
+    /// Hex id carried in every envelope sealed with this key.
+    pub fn id_hex(&self) -> String {
+        hex::encode(self.id)
+    }
+
+    fn seal(&self, topic: &str, plaintext: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        let (nonce, ciphertext) = encrypt(&self.key, plaintext, topic.as_bytes())?;
+        let mut envelope = Vec::with_capacity(4 + KEY_ID_LEN + NONCE_LEN + ciphertext.len());
+        envelope.extend_from_slice(ENVELOPE_MAGIC);
+        envelope.extend_from_slice(&self.id);
+        envelope.extend_from_slice(&nonce);
+        envelope.extend_from_slice(&ciphertext);
+        Ok(envelope)
+    }
+
+    fn open(&self, topic: &str, nonce: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        decrypt(&self.key, nonce, ciphertext, topic.as_bytes())
+    }
+}
+
+/// The key a member uses for the current epoch.
+#[derive(Debug, Clone)]
+pub struct EpochKey {
+    pub epoch: u64,
+    /// DID of the member that drew the seed.
+    pub issuer: String,
+    pub seed: EpochSeed,
+    pub key: TopicKey,
+}
+
+/// The topic keys of one private swarm.
+#[derive(Debug)]
+pub struct TopicKeyring {
+    swarm_id: String,
+    token: SwarmToken,
+    admission: TopicKey,
+    current: Option<EpochKey>,
+    retired: VecDeque<TopicKey>,
+}
+
+impl TopicKeyring {
+    pub fn new(swarm_id: &str, token: SwarmToken) -> Self {
+        Self {
+            swarm_id: swarm_id.to_string(),
+            admission: TopicKey::admission(&token, swarm_id),
+            token,
+            current: None,
+            retired: VecDeque::new(),
+        }
+    }
+
+    pub fn swarm_id(&self) -> &str {
+        &self.swarm_id
+    }
+
+    /// The key of the latest epoch, once a seed was adopted.
+    pub fn current(&self) -> Option<&EpochKey> {
+        self.current.as_ref()
+    }
+
+    /// Derive the key for `epoch` from `seed` without adopting it.
+    pub fn key_for(&self, epoch: u64, seed: &EpochSeed) -> TopicKey {
+        TopicKey::for_epoch(&self.token, &self.swarm_id, epoch, seed)
+    }
+
+    /// Make the seed `issuer` drew for `epoch` the current one. A seed for
+    /// an older epoch, or for the same epoch from an issuer sorting after
+    /// the current one, is ignored so concurrent issuers converge. Returns
+    /// whether the key changed.
+    pub fn adopt(&mut self, epoch: u64, issuer: &str, seed: EpochSeed) -> bool {
+        if let Some(current) = &self.current {
+            let newer = epoch > current.epoch
+                || (epoch == current.epoch && issuer < current.issuer.as_str());
+            if !newer {
+                return false;
+            }
+        }
+        let key = self.key_for(epoch, &seed);
+        if let Some(previous) = self.current.take() {
+            self.retired.push_front(previous.key);
+            self.retired.truncate(RETAINED_EPOCH_KEYS);
+        }
+        self.current = Some(EpochKey {
+            epoch,
+            issuer: issuer.to_string(),
+            seed,
+            key,
+        });
+        true
+    }
+
+    /// Whether `topic` is one of this swarm's topics, and so encrypted.
+    pub fn covers(&self, topic: &str) -> bool {
+        SwarmTopics::swarm_of(topic) == Some(self.swarm_id.as_str())
+            && !topic.ends_with("/announce")
+    }
+
+    fn is_admission_topic(&self, topic: &str) -> bool {
+        topic == SwarmTopics::keepalive_for(&self.swarm_id)
+    }
+
+    /// Encrypt a payload published on `topic`. Topics outside the swarm
+    /// are passed through; the swarm's other topics need an epoch key.
+    pub fn seal(&self, topic: &str, payload: Vec<u8>) -> Result<Vec<u8>, ProtocolError> {
+        if !self.covers(topic) {
+            return Ok(payload);
+        }
+        if self.is_admission_topic(topic) {
+            return self.admission.seal(topic, &payload);
+        }
+        match &self.current {
+            Some(current) => current.key.seal(topic, &payload),
+            None => Err(ProtocolError::Crypto(format!(
+                "no topic key for swarm {} yet",
+                self.swarm_id
+            ))),
+        }
+    }
+
+    /// Decrypt a payload received on `topic`. Plaintext on a covered topic
+    /// and envelopes under a key not held are errors.
+    pub fn open(&self, topic: &str, payload: Vec<u8>) -> Result<Vec<u8>, ProtocolError> {
+        if !self.covers(topic) {
+            return Ok(payload);
+        }
+        let header = 4 + KEY_ID_LEN + NONCE_LEN;
+        if payload.len() < header || &payload[..4] != ENVELOPE_MAGIC {
+            return Err(ProtocolError::Crypto(format!("unencrypted message on {}", topic)));
+        }
+        let id = &payload[4..4 + KEY_ID_LEN];
+        let (nonce, ciphertext) = payload[4 + KEY_ID_LEN..].split_at(NONCE_LEN);
+        let key = if self.is_admission_topic(topic) {
+            Some(&self.admission).filter(|key| key.id == id)
+        } else {
+            self.current
+                .iter()
+                .map(|current| &current.key)
+                .chain(self.retired.iter())
+                .find(|key| key.id == id)
+        };
+        let key = key.ok_or_else(|| {
+            ProtocolError::Crypto(format!("no topic key {} for {}", hex::encode(id), topic))
+        })?;
+        key.open(topic, nonce, ciphertext)
+    }
+}
+
+fn derive_wrap_key(shared: &[u8; 32], ephemeral_key: &PublicKey, member_key: &PublicKey) -> [u8; 32] {
+    let mut info = SEED_WRAP_INFO.to_vec();
+    info.extend_from_slice(ephemeral_key.as_bytes());
+    info.extend_from_slice(member_key.as_bytes());
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, shared)
+        .expand(&info, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+fn encrypt(key: &[u8; 32], msg: &[u8], aad: &[u8]) -> Result<([u8; NONCE_LEN], Vec<u8>), ProtocolError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+        .map_err(|_| ProtocolError::Crypto("encryption failed".into()))?;
+    Ok((nonce, ciphertext))
+}
+
+fn decrypt(key: &[u8; 32], nonce: &[u8], msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), Payload { msg, aad })
+        .map_err(|_| ProtocolError::Crypto("decryption failed".into()))
+}
+
+fn decode_array<const N: usize>(value: &str, what: &str) -> Result<[u8; N], ProtocolError> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ProtocolError::Crypto(format!("invalid {}", what)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::types::SwarmId;
+
+    fn keyring() -> TopicKeyring {
+        let token = SwarmToken::generate(&SwarmId::new("lab".into()), "hunter2");
+        TopicKeyring::new("lab", token)
+    }
+
+    #[test]
+    fn seeds_open_only_for_the_member_they_were_sealed_to() {
+        let member = generate_keypair();
+        let leaver = generate_keypair();
+        let seed = EpochSeed::generate();
+
+        let sealed = seed.seal_for("did:swarm:member", &member.verifying_key()).unwrap();
+        assert_eq!(EpochSeed::open(&sealed, &member).unwrap(), seed);
+        assert!(EpochSeed::open(&sealed, &leaver).is_err());
+
+        // The seal is bound to the member it names.
+        let renamed = SealedEpochSeed { member: "did:swarm:leaver".into(), ..sealed };
+        assert!(EpochSeed::open(&renamed, &member).is_err());
+    }
+
+    #[test]
+    fn a_rekey_locks_out_holders_of_only_the_old_seed() {
+        let tasks = SwarmTopics::tasks_for("lab", 1);
+        let keepalive = SwarmTopics::keepalive_for("lab");
+        let mut member = keyring();
+        let mut leaver = keyring();
+        assert!(member.seal(&tasks, b"task".to_vec()).is_err());
+
+        let old = EpochSeed::generate();
+        assert!(member.adopt(3, "did:swarm:a", old.clone()));
+        assert!(leaver.adopt(3, "did:swarm:a", old));
+        let before = member.seal(&tasks, b"before".to_vec()).unwrap();
+        assert_eq!(leaver.open(&tasks, before.clone()).unwrap(), b"before");
+
+        assert!(member.adopt(4, "did:swarm:a", EpochSeed::generate()));
+        let after = member.seal(&tasks, b"after".to_vec()).unwrap();
+        assert!(leaver.open(&tasks, after).is_err());
+        // Retired keys still read messages sent before the rekey.
+        assert_eq!(member.open(&tasks, before).unwrap(), b"before");
+
+        // The keep-alive topic stays readable with the token alone.
+        let hello = member.seal(&keepalive, b"hello".to_vec()).unwrap();
+        assert_eq!(keyring().open(&keepalive, hello).unwrap(), b"hello");
+        assert!(member.open(&tasks, b"{\"plain\":true}".to_vec()).is_err());
+
+        // Other swarms' topics are left alone.
+        let other = SwarmTopics::tasks_for("other", 1);
+        assert_eq!(member.seal(&other, b"x".to_vec()).unwrap(), b"x");
+    }
+
+    #[test]
+    fn concurrent_issuers_converge_on_the_lowest_did() {
+        let mut ring = keyring();
+        assert!(ring.adopt(5, "did:swarm:b", EpochSeed::generate()));
+        assert!(ring.adopt(5, "did:swarm:a", EpochSeed::generate()));
+        assert!(!ring.adopt(5, "did:swarm:c", EpochSeed::generate()));
+        assert!(!ring.adopt(4, "did:swarm:0", EpochSeed::generate()));
+        let current = ring.current().unwrap();
+        assert_eq!((current.epoch, current.issuer.as_str()), (5, "did:swarm:a"));
+    }
+}
@@ -28,6 +28,33 @@ impl Tier {
     }
 }
 
+/// Constraint an operator puts on where an agent sits in the pyramid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutRole {
+    /// Always placed in Tier-1, e.g. a trusted coordinator.
+    Coordinator,
+    /// Never coordinates: always a leaf executor, e.g. a GPU box.
+    Executor,
+}
+
+impl LayoutRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LayoutRole::Coordinator => "coordinator",
+            LayoutRole::Executor => "executor",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "coordinator" => Some(LayoutRole::Coordinator),
+            "executor" => Some(LayoutRole::Executor),
+            _ => None,
+        }
+    }
+}
+
 /// Current status of a task in the swarm.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum TaskStatus {
@@ -46,6 +73,13 @@ pub enum TaskStatus {
     Failed,
     /// Task was rejected during verification
     Rejected,
+    /// Re-execution produced a different result; the board is choosing one
+    Disputed,
+    /// Deadline passed without a result; still accepts a late result
+    Overdue,
+    /// Overdue with no agent to take it over; waits for a capable agent or
+    /// the operator, and still accepts a late result
+    Blocked,
 }
 
 /// A task in the swarm hierarchy.
@@ -73,6 +107,139 @@ pub struct Task {
     pub knowledge_domains: Vec<String>,
     #[serde(default)]
     pub tools_available: Vec<String>,
+    /// When set, the winning plan is held until an operator approves it.
+    #[serde(default)]
+    pub requires_approval: bool,
+    /// Operator-assigned priority; higher values are more urgent.
+    #[serde(default)]
+    pub priority: u8,
+    /// Spending limit for this task and all of its subtasks, in the cost
+    /// units agents report with their results. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<f64>,
+    /// Overrides the swarm's default quorum policy for this task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quorum: Option<QuorumPolicy>,
+    /// Tasks that must complete before this one is released to the swarm.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,    /// Content types and structure its result artifacts must have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_schema: Option<crate::result_schema::ResultSchema>,
+    /// How subtask results are combined into this task's result.
+    #[serde(default, skip_serializing_if = "AggregationStrategy::is_concatenate")]
+    pub aggregation: AggregationStrategy,
+    /// Overrides the swarm's default assignment strategy for this task's
+    /// subtasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignment: Option<AssignmentStrategy>,
+    /// Input documents the task works from, by content ID. Subtasks inherit
+    /// their parent's attachments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<TaskAttachment>,
+    /// Who may read the task's result. Subtasks inherit their parent's.
+    #[serde(default, skip_serializing_if = "ResultVisibility::is_public")]
+    pub visibility: ResultVisibility,
+    /// Set on a subtask delegated to another swarm: it stands in for the
+    /// task there and completes with its result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<RemoteExecution>,
+}
+
+/// Where a delegated subtask runs. Its bridge, a member of both swarms,
+/// injected the task into `swarm_id` and relays its result back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteExecution {
+    pub swarm_id: SwarmId,
+    pub bridge: AgentId,
+    /// ID of the task in `swarm_id`.
+    pub task_id: String,
+}
+
+/// An input document attached to a task. The content lives in the content
+/// store under `cid`; agents fetch it with `swarm.get_attachment`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskAttachment {
+    pub name: String,
+    pub cid: String,
+    /// Content size in bytes, or 0 when the injecting node did not hold it.
+    #[serde(default)]
+    pub size_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+/// How a parent task's result is built once all its subtasks complete.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationStrategy {
+    /// List each subtask's content CID.
+    #[default]
+    Concatenate,
+    /// Deep-merge the subtasks' JSON results into one JSON document.
+    JsonMerge,
+    /// Assign a synthesis subtask to the board chair; the parent completes
+    /// with the chair's synthesis result.
+    ChairSynthesis,
+}
+
+impl AggregationStrategy {
+    pub fn is_concatenate(&self) -> bool {
+        *self == AggregationStrategy::Concatenate
+    }
+}
+
+/// Who may read a task's result. Results that are not public are sealed to
+/// the allowed members before they are published.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultVisibility {
+    /// Every member.
+    #[default]
+    Public,
+    /// Members at the task's tier or above it.
+    TierAndAbove,
+    /// The chair and members of the board that deliberated the task.
+    BoardOnly,
+}
+
+impl ResultVisibility {
+    pub fn is_public(&self) -> bool {
+        *self == ResultVisibility::Public
+    }
+}
+
+/// How a coordinator picks the subordinate for each directly assigned
+/// subtask.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentStrategy {
+    /// Subordinates take turns in order.
+    RoundRobin,
+    /// The subordinate with the most room, passing over saturated ones.
+    #[default]
+    LeastLoaded,
+    /// The subordinate advertising most of the subtask's required
+    /// capabilities, then the least loaded.
+    CapabilityScore,
+    /// Work in proportion to reputation: the subordinate with the highest
+    /// reputation per task it already holds.
+    ReputationWeighted,
+    /// A uniformly random subordinate with room, drawn from a seeded RNG so
+    /// the same task is assigned the same way on every run.
+    RandomSeeded,
+}
+
+/// How an oversized swarm spreads its members over the child swarms it
+/// splits into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShardStrategy {
+    /// By a hash of each member's DID: even, and the same on every node.
+    #[default]
+    Hash,
+    /// By Tier-1 branch: members geo-clustered under the same Tier-1
+    /// leader land in the same child.
+    Geo,
 }
 
 impl Task {
@@ -94,6 +261,17 @@ impl Task {
             backtrack_allowed: false,
             knowledge_domains: Vec::new(),
             tools_available: Vec::new(),
+            requires_approval: false,
+            priority: 0,
+            budget: None,
+            quorum: None,
+            depends_on: Vec::new(),
+            result_schema: None,
+            aggregation: AggregationStrategy::default(),
+            assignment: None,
+            attachments: Vec::new(),
+            visibility: ResultVisibility::default(),
+            remote: None,
         }
     }
 }
@@ -104,6 +282,129 @@ impl Default for Task {
     }
 }
 
+/// How many participants a consensus stage must hear from before it may
+/// advance. The same policy governs proposal collection, ballot collection
+/// and board formation for a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QuorumPolicy {
+    /// A fixed number of participants, capped at the number eligible.
+    FixedCount { count: usize },
+    /// A fraction of the active members in the task's tier.
+    TierFraction { fraction: f64 },
+    /// A fraction of the agents that accepted the task's board invitation.
+    /// Falls back to the tier until a board has formed.
+    BoardFraction { fraction: f64 },
+    /// The responders' combined weight must reach `fraction` of the total
+    /// eligible weight. Agents missing from `weights` weigh 1.0.
+    Weighted {
+        fraction: f64,
+        #[serde(default)]
+        weights: std::collections::HashMap<String, f64>,
+    },
+}
+
+impl Default for QuorumPolicy {
+    /// Every active member of the tier, matching the protocol's original behaviour.
+    fn default() -> Self {
+        QuorumPolicy::TierFraction { fraction: 1.0 }
+    }
+}
+
+impl QuorumPolicy {
+    /// An agent's weight under the policy: its weight for
+    /// [`QuorumPolicy::Weighted`], 1.0 otherwise.
+    pub fn weight(&self, agent_id: &str) -> f64 {
+        match self {
+            QuorumPolicy::Weighted { weights, .. } => weight_of(weights, agent_id),
+            _ => 1.0,
+        }
+    }
+
+    /// Number of participants that must respond, given the eligible tier
+    /// members and the accepted board members. Always at least 1.
+    ///
+    /// For [`QuorumPolicy::Weighted`] this is the fewest heads that could
+    /// reach the threshold, i.e. the heaviest eligible agents first.
+    pub fn required(&self, eligible: &[String], board: &[String]) -> usize {
+        let required = match self {
+            QuorumPolicy::FixedCount { count } => (*count).min(eligible.len()),
+            QuorumPolicy::TierFraction { fraction } => fraction_of(eligible.len(), *fraction),
+            QuorumPolicy::BoardFraction { fraction } => {
+                let pool = if board.is_empty() { eligible.len() } else { board.len() };
+                fraction_of(pool, *fraction)
+            }
+            QuorumPolicy::Weighted { fraction, weights } => {
+                let mut sorted: Vec<f64> = eligible.iter().map(|id| weight_of(weights, id)).collect();
+                sorted.sort_by(|a, b| b.total_cmp(a));
+                let threshold = threshold(sorted.iter().sum(), *fraction);
+                let mut acc = 0.0;
+                sorted
+                    .iter()
+                    .position(|w| {
+                        acc += w;
+                        acc >= threshold
+                    })
+                    .map(|i| i + 1)
+                    .unwrap_or(sorted.len())
+            }
+        };
+        required.max(1)
+    }
+
+    /// Whether `responders` satisfy the policy. Duplicate responders count once.
+    pub fn is_met(&self, eligible: &[String], board: &[String], responders: &[String]) -> bool {
+        let mut distinct: Vec<&String> = responders.iter().collect();
+        distinct.sort();
+        distinct.dedup();
+        match self {
+            QuorumPolicy::Weighted { fraction, weights } => {
+                if eligible.is_empty() {
+                    return !distinct.is_empty();
+                }
+                let total: f64 = eligible.iter().map(|id| weight_of(weights, id)).sum();
+                let got: f64 = distinct
+                    .iter()
+                    .filter(|id| eligible.contains(id))
+                    .map(|id| weight_of(weights, id))
+                    .sum();
+                got >= threshold(total, *fraction)
+            }
+            _ => distinct.len() >= self.required(eligible, board),
+        }
+    }
+}
+
+fn clamp_fraction(fraction: f64) -> f64 {
+    if fraction.is_finite() {
+        fraction.clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Relative slack allowed below a fractional threshold, so a product that
+/// lands a rounding error above an integer (100 × 0.55 is
+/// 55.00000000000001) does not demand one more participant.
+const THRESHOLD_TOLERANCE: f64 = 1e-9;
+
+/// The share `fraction` of `total`, less the rounding tolerance.
+fn threshold(total: f64, fraction: f64) -> f64 {
+    total * clamp_fraction(fraction) * (1.0 - THRESHOLD_TOLERANCE)
+}
+
+fn fraction_of(pool: usize, fraction: f64) -> usize {
+    threshold(pool as f64, fraction).ceil() as usize
+}
+
+fn weight_of(weights: &std::collections::HashMap<String, f64>, agent_id: &str) -> f64 {
+    weights
+        .get(agent_id)
+        .copied()
+        .filter(|w| w.is_finite() && *w >= 0.0)
+        .unwrap_or(1.0)
+}
+
 /// A High-Level Decomposition Plan proposed by a Tier-1 agent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Plan {
@@ -122,6 +423,9 @@ pub struct Plan {
     /// Set server-side; clients may omit.
     #[serde(default = "chrono::Utc::now")]
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Total spend the proposer expects the plan to need.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<f64>,
 }
 
 fn default_parallelism() -> f64 {
@@ -139,6 +443,7 @@ impl Plan {
             rationale: String::new(),
             estimated_parallelism: 1.0,
             created_at: chrono::Utc::now(),
+            budget: None,
         }
     }
 }
@@ -150,6 +455,9 @@ pub struct PlanSubtask {
     pub description: String,
     pub required_capabilities: Vec<String>,
     pub estimated_complexity: f64,
+    /// Spending limit carried onto the subtask when it is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<f64>,
 }
 
 /// Result artifact from task execution.
@@ -179,6 +487,10 @@ pub struct Artifact {
     /// Human-readable content / result text.
     #[serde(default)]
     pub content: String,
+    /// The content sealed to the members allowed to read it, when the
+    /// task's result is not public; `content` is then empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sealed: Option<crate::result_seal::SealedResult>,
 }
 
 fn default_content_type() -> String {
@@ -208,9 +520,14 @@ pub struct RankedVote {
     pub voter: AgentId,
     pub task_id: String,
     pub epoch: u64,
-    /// Plan IDs ranked from most preferred to least preferred
+    /// Plan IDs ranked from most preferred to least preferred. A ballot may
+    /// rank only the plans its voter could evaluate.
     pub rankings: Vec<String>,
     pub critic_scores: std::collections::HashMap<String, CriticScore>,
+    /// Take part without a preference: the ballot counts towards quorum
+    /// but not towards any plan, and `rankings` must be empty.
+    #[serde(default)]
+    pub abstain: bool,
 }
 
 /// Epoch metadata.
@@ -329,6 +646,97 @@ impl std::fmt::Display for SwarmToken {
     }
 }
 
+/// Parameters every member of a swarm must agree on for quorum math and
+/// stage timing to line up. Issued and signed by the swarm's parameter
+/// authority (its creator); each change bumps `version`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwarmParameters {
+    pub swarm_id: SwarmId,
+    pub version: u64,
+    /// Pyramid branching factor; 0 derives it from the swarm size.
+    pub branching_factor: u32,
+    /// Seconds a proposal stage stays open before it is forced forward.
+    pub proposal_timeout_secs: u64,
+    /// Seconds a voting stage stays open before it is forced forward.
+    pub voting_timeout_secs: u64,
+    pub quorum: QuorumPolicy,
+    /// Hex Ed25519 public key of the authority that signed this record.
+    pub creator_key: String,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl SwarmParameters {
+    /// Canonical bytes covered by the signature. Keys are sorted by the
+    /// JSON value map, so the payload is the same on every node.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(map) = value.as_object_mut() {
+            map.remove("signature");
+        }
+        serde_json::to_vec(&value).unwrap_or_default()
+    }
+
+    /// Set `creator_key` to the signer's public key and sign the record.
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        self.creator_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = crate::crypto::sign_message(key, &self.signing_payload());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Whether `key` is the authority that issued this record.
+    pub fn is_issued_by(&self, key: &ed25519_dalek::VerifyingKey) -> bool {
+        self.creator_key == hex::encode(key.as_bytes())
+    }
+
+    /// Whether the signature was made by the key in `creator_key`.
+    pub fn verify(&self) -> bool {
+        crate::crypto::verify_hex_signature(&self.creator_key, &self.signature, &self.signing_payload())
+    }
+
+    /// A copy with `changes` applied, one version later and unsigned.
+    pub fn with_changes(
+        &self,
+        changes: &SwarmParameterChanges,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            swarm_id: self.swarm_id.clone(),
+            version: self.version + 1,
+            branching_factor: changes.branching_factor.unwrap_or(self.branching_factor),
+            proposal_timeout_secs: changes
+                .proposal_timeout_secs
+                .unwrap_or(self.proposal_timeout_secs),
+            voting_timeout_secs: changes.voting_timeout_secs.unwrap_or(self.voting_timeout_secs),
+            quorum: changes.quorum.clone().unwrap_or_else(|| self.quorum.clone()),
+            creator_key: self.creator_key.clone(),
+            issued_at: now,
+            signature: String::new(),
+        }
+    }
+}
+
+/// A requested change to [`SwarmParameters`]; unset fields keep their value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SwarmParameterChanges {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branching_factor: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposal_timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voting_timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quorum: Option<QuorumPolicy>,
+}
+
+impl SwarmParameterChanges {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Metadata about a swarm, stored in DHT and tracked locally.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwarmInfo {
@@ -399,7 +807,12 @@ pub struct HolonState {
     pub depth: u32,
     pub parent_holon: Option<String>,
     pub child_holons: Vec<String>,
+    /// Agent that delivered each subtask's result; for a child holon, the
+    /// agent that submitted the child board's result.
     pub subtask_assignments: std::collections::HashMap<String, AgentId>,
+    /// Content CID of each finished child holon's result artifact.
+    #[serde(default)]
+    pub child_artifacts: std::collections::HashMap<String, String>,
     pub status: HolonStatus,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -411,6 +824,10 @@ pub enum DeliberationType {
     CritiqueFeedback,
     Rebuttal,
     SynthesisResult,
+    /// A proposer's amended plan after critique; the transcript keeps both versions.
+    PlanRevision,
+    /// A proposer retracted its plan before voting closed.
+    PlanWithdrawal,
 }
 
 /// A message in the deliberation thread of a holon board.
@@ -425,6 +842,9 @@ pub struct DeliberationMessage {
     pub content: String,
     pub referenced_plan_id: Option<String>,
     pub critic_scores: Option<std::collections::HashMap<String, CriticScore>>,
+    /// Hybrid logical clock of the event, for ordering across nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hlc: Option<crate::HlcTimestamp>,
 }
 
 /// Per-voter ballot record for full deliberation visibility.
@@ -436,6 +856,9 @@ pub struct BallotRecord {
     pub critic_scores: std::collections::HashMap<String, CriticScore>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub irv_round_when_eliminated: Option<u32>,
+    /// The voter abstained.
+    #[serde(default)]
+    pub abstain: bool,
 }
 
 /// IRV round history for debugging and UI.
@@ -446,6 +869,12 @@ pub struct IrvRound {
     pub tallies: std::collections::HashMap<String, usize>,
     pub eliminated: Option<String>,
     pub continuing_candidates: Vec<String>,
+    /// Tie-break rule that chose `eliminated`, when several plans tied for last.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tie_break: Option<String>,
+    /// Seed used by a random tie-break, so the draw can be reproduced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tie_break_seed: Option<u64>,
 }
 
 /// Anti-bot verification challenge. Returned on first register_agent call.
@@ -571,6 +1000,36 @@ mod tests {
         assert_eq!(token1, token2);
     }
 
+    #[test]
+    fn test_swarm_parameters_sign_and_verify() {
+        let key = crate::crypto::generate_keypair();
+        let mut params = SwarmParameters {
+            swarm_id: SwarmId::new("s1".to_string()),
+            version: 1,
+            branching_factor: 0,
+            proposal_timeout_secs: 60,
+            voting_timeout_secs: 120,
+            quorum: QuorumPolicy::default(),
+            creator_key: String::new(),
+            issued_at: chrono::Utc::now(),
+            signature: String::new(),
+        };
+        params.sign(&key);
+        assert!(params.verify());
+
+        let changes = SwarmParameterChanges {
+            voting_timeout_secs: Some(30),
+            ..Default::default()
+        };
+        let mut next = params.with_changes(&changes, chrono::Utc::now());
+        assert_eq!((next.version, next.voting_timeout_secs), (2, 30));
+        assert!(!next.verify());
+        next.sign(&key);
+        assert!(next.verify());
+        next.branching_factor = 3;
+        assert!(!next.verify());
+    }
+
     #[test]
     fn test_swarm_info_public() {
         let creator = AgentId::new("did:swarm:test".to_string());
@@ -627,6 +1086,7 @@ mod tests {
             parent_holon: Some("parent-task-id".to_string()),
             child_holons: vec!["child-1".to_string(), "child-2".to_string()],
             subtask_assignments,
+            child_artifacts: HashMap::new(),
             status: HolonStatus::Deliberating,
             created_at: chrono::Utc::now(),
         };
@@ -657,6 +1117,7 @@ mod tests {
             parent_holon: None,
             child_holons: vec![],
             subtask_assignments: std::collections::HashMap::new(),
+            child_artifacts: std::collections::HashMap::new(),
             status: HolonStatus::Forming,
             created_at: chrono::Utc::now(),
         };
@@ -692,6 +1153,7 @@ mod tests {
             content: "Plan 1 has insufficient parallelism for subtask 3".to_string(),
             referenced_plan_id: Some("plan-1".to_string()),
             critic_scores: Some(scores),
+            hlc: None,
         };
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -721,6 +1183,7 @@ mod tests {
             content: "My proposal for decomposing the task".to_string(),
             referenced_plan_id: None,
             critic_scores: None,
+            hlc: None,
         };
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -737,6 +1200,8 @@ mod tests {
             DeliberationType::CritiqueFeedback,
             DeliberationType::Rebuttal,
             DeliberationType::SynthesisResult,
+            DeliberationType::PlanRevision,
+            DeliberationType::PlanWithdrawal,
         ];
         for t in types {
             let json = serde_json::to_string(&t).unwrap();
@@ -771,6 +1236,7 @@ mod tests {
             critic_scores,
             timestamp: chrono::Utc::now(),
             irv_round_when_eliminated: Some(2),
+            abstain: false,
         };
 
         let json = serde_json::to_string(&record).unwrap();
@@ -794,6 +1260,7 @@ mod tests {
             critic_scores: std::collections::HashMap::new(),
             timestamp: chrono::Utc::now(),
             irv_round_when_eliminated: None,
+            abstain: false,
         };
 
         let json = serde_json::to_string(&record).unwrap();
@@ -816,6 +1283,8 @@ mod tests {
             tallies,
             eliminated: Some("plan-C".to_string()),
             continuing_candidates: vec!["plan-A".to_string(), "plan-B".to_string()],
+            tie_break: None,
+            tie_break_seed: None,
         };
 
         let json = serde_json::to_string(&round).unwrap();
@@ -841,6 +1310,8 @@ mod tests {
             tallies,
             eliminated: None,
             continuing_candidates: vec!["plan-A".to_string()],
+            tie_break: None,
+            tie_break_seed: None,
         };
 
         let json = serde_json::to_string(&round).unwrap();
@@ -893,6 +1364,91 @@ mod tests {
         assert!(!task.backtrack_allowed);
         assert!(task.knowledge_domains.is_empty());
         assert!(task.tools_available.is_empty());
+        assert!(!task.requires_approval);
+    }
+
+    #[test]
+    fn test_task_requires_approval_roundtrip() {
+        let mut task = Task::new("Deploy to production".into(), 1, 2);
+        task.requires_approval = true;
+
+        let json = serde_json::to_string(&task).unwrap();
+        let restored: Task = serde_json::from_str(&json).unwrap();
+        assert!(restored.requires_approval);
+    }
+
+    #[test]
+    fn test_budget_fields_omitted_when_unset() {
+        let mut plan = Plan::new("task-1".into(), AgentId::new("did:swarm:a".into()), 1);
+        plan.subtasks.push(PlanSubtask {
+            index: 0,
+            description: "Research".into(),
+            required_capabilities: vec![],
+            estimated_complexity: 0.2,
+            budget: None,
+        });
+        let json = serde_json::to_value(&plan).unwrap();
+        assert!(json.get("budget").is_none());
+        assert!(json["subtasks"][0].get("budget").is_none());
+
+        let mut task = Task::new("Summarize".into(), 1, 1);
+        task.budget = Some(2.5);
+        let restored: Task = serde_json::from_str(&serde_json::to_string(&task).unwrap()).unwrap();
+        assert_eq!(restored.budget, Some(2.5));
+    }
+
+    #[test]
+    fn test_quorum_policy_required_counts() {
+        let tier: Vec<String> = (0..5).map(|i| format!("agent-{}", i)).collect();
+        let board: Vec<String> = tier[..2].to_vec();
+
+        assert_eq!(QuorumPolicy::default().required(&tier, &[]), 5);
+        assert_eq!(QuorumPolicy::TierFraction { fraction: 0.5 }.required(&tier, &[]), 3);
+        assert_eq!(QuorumPolicy::FixedCount { count: 9 }.required(&tier, &[]), 5);
+        assert_eq!(QuorumPolicy::FixedCount { count: 0 }.required(&tier, &[]), 1);
+        // Board fraction uses the tier until a board exists.
+        let board_policy = QuorumPolicy::BoardFraction { fraction: 1.0 };
+        assert_eq!(board_policy.required(&tier, &[]), 5);
+        assert_eq!(board_policy.required(&tier, &board), 2);
+        assert_eq!(QuorumPolicy::default().required(&[], &[]), 1);
+    }
+
+    #[test]
+    fn test_quorum_policy_fraction_is_not_rounded_up_past_the_product() {
+        let pool = |n: usize| -> Vec<String> { (0..n).map(|i| format!("agent-{}", i)).collect() };
+        let hundred = pool(100);
+        let fifty = pool(50);
+        let thirds = pool(3);
+
+        assert_eq!(QuorumPolicy::TierFraction { fraction: 0.55 }.required(&hundred, &[]), 55);
+        assert_eq!(QuorumPolicy::BoardFraction { fraction: 0.14 }.required(&fifty, &[]), 7);
+        assert_eq!(QuorumPolicy::TierFraction { fraction: 2.0 / 3.0 }.required(&thirds, &[]), 2);
+        assert_eq!(QuorumPolicy::TierFraction { fraction: 0.551 }.required(&hundred, &[]), 56);
+
+        let weighted = QuorumPolicy::Weighted { fraction: 0.55, weights: Default::default() };
+        assert_eq!(weighted.required(&hundred, &[]), 55);
+        assert!(weighted.is_met(&hundred, &[], &hundred[..55]));
+        assert!(!weighted.is_met(&hundred, &[], &hundred[..54]));
+    }
+
+    #[test]
+    fn test_quorum_policy_weighted() {
+        let tier: Vec<String> = vec!["a".into(), "b".into(), "c".into()];
+        let mut weights = std::collections::HashMap::new();
+        weights.insert("a".to_string(), 3.0);
+        let policy = QuorumPolicy::Weighted { fraction: 0.6, weights };
+
+        // a alone carries 3 of 5 total weight.
+        assert_eq!(policy.required(&tier, &[]), 1);
+        assert!(policy.is_met(&tier, &[], &["a".to_string()]));
+        assert!(!policy.is_met(&tier, &[], &["b".to_string(), "c".to_string()]));
+        // Non-eligible responders carry no weight.
+        assert!(!policy.is_met(&tier, &[], &["b".to_string(), "outsider".to_string()]));
+
+        let json = serde_json::to_value(&policy).unwrap();
+        assert_eq!(json["kind"], "weighted");
+        let restored: QuorumPolicy = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, policy);
     }
 
     #[test]
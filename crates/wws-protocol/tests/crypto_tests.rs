@@ -291,6 +291,23 @@ fn test_load_or_create_keypair_wrong_size() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_replace_keypair_keeps_old_key_aside() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("test.key");
+    let old = wws_protocol::crypto::load_or_create_keypair(&path).unwrap();
+    let (new, backup) = wws_protocol::crypto::replace_keypair(&path).unwrap();
+    assert_ne!(old.verifying_key().as_bytes(), new.verifying_key().as_bytes());
+    let reloaded = wws_protocol::crypto::load_or_create_keypair(&path).unwrap();
+    assert_eq!(new.verifying_key().as_bytes(), reloaded.verifying_key().as_bytes());
+    let restored = wws_protocol::crypto::load_or_create_keypair(&backup.unwrap()).unwrap();
+    assert_eq!(old.verifying_key().as_bytes(), restored.verifying_key().as_bytes());
+
+    let fresh = dir.path().join("fresh.key");
+    let (_, backup) = wws_protocol::crypto::replace_keypair(&fresh).unwrap();
+    assert!(fresh.exists() && backup.is_none());
+}
+
 // ═══════════════════════════════════════════════════════════════
 // § BIP-39 Mnemonic export/import and recovery key derivation
 // ═══════════════════════════════════════════════════════════════
@@ -305,3 +305,40 @@ fn agent_resources_optional_fields() {
     assert_eq!(parsed.gpu_vram_gb, None);
     assert_eq!(parsed.disk_gb, None);
 }
+
+// ═══════════════════════════════════════════════════════════════
+// § DID documents
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn did_document_is_self_signed_and_verifies_agent_signatures() {
+    let key = wws_protocol::crypto::generate_keypair();
+    let did = wws_protocol::crypto::derive_agent_id(&key.verifying_key());
+    let service = vec![ServiceEndpoint {
+        id: format!("{}#p2p", did),
+        service_type: "WwsPeer".into(),
+        service_endpoint: "/p2p/12D3KooWexample".into(),
+    }];
+    let doc = DidDocument::new(&did, &key, service, vec!["python-exec".into()]);
+    assert!(doc.verify());
+
+    // Survives the round trip through the DHT, in W3C field names.
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(json["verificationMethod"][0]["type"], ED25519_VERIFICATION_KEY);
+    assert_eq!(json["service"][0]["serviceEndpoint"], "/p2p/12D3KooWexample");
+    let parsed: DidDocument = serde_json::from_value(json).unwrap();
+    assert!(parsed.verify());
+    assert_eq!(parsed.public_keys().next(), Some(key.verifying_key()));
+
+    let mut tampered = parsed.clone();
+    tampered.capabilities.push("admin".into());
+    assert!(!tampered.verify());
+
+    let params = serde_json::json!({"task_id": "t1"});
+    let payload = wws_protocol::SwarmMessage::signing_payload("task.submit_result", &params);
+    let signature = hex::encode(wws_protocol::crypto::sign_message(&key, &payload).to_bytes());
+    assert!(doc.verify_signature(&payload, &signature));
+    let other = wws_protocol::crypto::generate_keypair();
+    let forged = hex::encode(wws_protocol::crypto::sign_message(&other, &payload).to_bytes());
+    assert!(!doc.verify_signature(&payload, &forged));
+}
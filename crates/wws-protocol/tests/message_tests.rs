@@ -150,6 +150,7 @@ fn all_protocol_methods_have_string_representation() {
         ProtocolMethod::TaskAssignment,
         ProtocolMethod::ResultSubmission,
         ProtocolMethod::VerificationResult,
+        ProtocolMethod::DisputeVote,
         ProtocolMethod::KeepAlive,
         ProtocolMethod::Succession,
     ];
@@ -287,6 +288,7 @@ fn candidacy_params_serialization() {
             y: 0.2,
             z: 0.3,
         },
+        capabilities: vec!["gpt-4".into()],
     };
     let json = serde_json::to_string(&params).unwrap();
     let parsed: CandidacyParams = serde_json::from_str(&json).unwrap();
@@ -312,6 +314,8 @@ fn consensus_vote_params_serialization() {
         voter: AgentId::new("did:swarm:voter".into()),
         rankings: vec!["plan-1".into(), "plan-2".into()],
         critic_scores,
+        abstain: false,
+        selection: None,
     };
     let json = serde_json::to_string(&params).unwrap();
     let parsed: ConsensusVoteParams = serde_json::from_str(&json).unwrap();
@@ -334,9 +338,11 @@ fn result_submission_params_serialization() {
             size_bytes: 4096,
             created_at: chrono::Utc::now(),
             content: "result content".into(),
+            sealed: None,
         },
         merkle_proof: vec!["hash1".into(), "hash2".into()],
         is_synthesis: false,
+        cost: None,
     };
     let json = serde_json::to_string(&params).unwrap();
     let parsed: ResultSubmissionParams = serde_json::from_str(&json).unwrap();
@@ -344,6 +350,38 @@ fn result_submission_params_serialization() {
     assert_eq!(parsed.merkle_proof.len(), 2);
 }
 
+#[test]
+fn task_injection_params_carry_attachments() {
+    let mut task = Task::new("Summarize the brief".into(), 1, 3);
+    task.attachments.push(TaskAttachment {
+        name: "brief.md".into(),
+        cid: "cid-brief".into(),
+        size_bytes: 7,
+        content_type: Some("text/markdown".into()),
+    });
+    let params = TaskInjectionParams {
+        task,
+        originator: AgentId::new("did:swarm:op".into()),
+        attachments: vec![AttachmentBlob {
+            cid: "cid-brief".into(),
+            content: "# Brief".into(),
+        }],
+        attempt: 0,
+    };
+    let json = serde_json::to_value(&params).unwrap();
+    let parsed: TaskInjectionParams = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.task.attachments[0].name, "brief.md");
+    assert_eq!(parsed.attachments[0].content, "# Brief");
+
+    // Injections from older peers carry neither field.
+    let mut legacy = serde_json::to_value(&params).unwrap();
+    legacy.as_object_mut().unwrap().remove("attachments");
+    legacy["task"].as_object_mut().unwrap().remove("attachments");
+    let parsed: TaskInjectionParams = serde_json::from_value(legacy).unwrap();
+    assert!(parsed.attachments.is_empty());
+    assert!(parsed.task.attachments.is_empty());
+}
+
 #[test]
 fn keepalive_params_serialization() {
     let params = KeepAliveParams {
@@ -351,12 +389,34 @@ fn keepalive_params_serialization() {
         agent_name: Some("alive".into()),
         last_task_poll_at: None,
         last_result_at: None,
+        load: Some(AgentLoad {
+            queue_depth: 1,
+            in_flight: 2,
+            max_concurrency: Some(3),
+            ..AgentLoad::default()
+        }),
+        role: Some(LayoutRole::Executor),
         epoch: 105,
+        epoch_duration_secs: Some(5400),
+        topic_key: None,
+        lineage: None,
+        services: None,
         timestamp: chrono::Utc::now(),
     };
     let json = serde_json::to_string(&params).unwrap();
     let parsed: KeepAliveParams = serde_json::from_str(&json).unwrap();
     assert_eq!(parsed.epoch, 105);
+    assert!(parsed.load.as_ref().unwrap().is_saturated());
+    assert!(json.contains("\"role\":\"executor\""));
+    assert_eq!(parsed.role, Some(LayoutRole::Executor));
+
+    // Keep-alives from nodes that report no load still parse.
+    let mut legacy = serde_json::to_value(&params).unwrap();
+    legacy.as_object_mut().unwrap().remove("load");
+    legacy.as_object_mut().unwrap().remove("role");
+    let parsed: KeepAliveParams = serde_json::from_value(legacy).unwrap();
+    assert!(parsed.load.is_none());
+    assert!(parsed.role.is_none());
 }
 
 #[test]
@@ -383,6 +443,9 @@ fn tier_assignment_params_serialization() {
         parent_id: AgentId::new("did:swarm:leader".into()),
         epoch: 106,
         branch_size: 85,
+        issuer: AgentId::new("did:swarm:leader".into()),
+        issuer_key: String::new(),
+        signature: String::new(),
     };
     let json = serde_json::to_string(&params).unwrap();
     let parsed: TierAssignmentParams = serde_json::from_str(&json).unwrap();
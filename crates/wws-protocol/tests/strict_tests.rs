@@ -0,0 +1,92 @@
+use serde_json::json;
+use wws_protocol::strict::field_names;
+use wws_protocol::*;
+
+fn leave(timestamp: &str) -> serde_json::Value {
+    json!({"swarm_id": "s1", "agent_id": "did:swarm:a", "timestamp": timestamp})
+}
+
+#[test]
+fn test_valid_params_decode() {
+    let params: SwarmLeaveParams =
+        decode_strict(&leave("2026-01-01T00:00:00Z"), &StrictLimits::default()).unwrap();
+    assert_eq!(params.agent_id.as_str(), "did:swarm:a");
+}
+
+#[test]
+fn test_type_errors_name_the_field() {
+    let task = Task::new("write the report".to_string(), 1, 1);
+    let mut params = json!({"task": task, "originator": "did:swarm:a"});
+    params["task"]["tier_level"] = json!("high");
+    let err = decode_strict::<TaskInjectionParams>(&params, &StrictLimits::default()).unwrap_err();
+    assert_eq!(err.0.len(), 1);
+    assert_eq!(err.0[0].path, "task.tier_level");
+}
+
+#[test]
+fn test_oversized_strings_and_arrays_are_reported_per_field() {
+    let limits = StrictLimits {
+        max_string_bytes: 16,
+        max_array_len: 2,
+        ..Default::default()
+    };
+    let mut task = Task::new("a description well past sixteen bytes".to_string(), 1, 1);
+    task.subtasks = vec!["s1".into(), "s2".into(), "s3".into()];
+    let params = json!({"task": task, "originator": "did:swarm:a"});
+    let err = decode_strict::<TaskInjectionParams>(&params, &limits).unwrap_err();
+    let paths: Vec<&str> = err.0.iter().map(|e| e.path.as_str()).collect();
+    assert!(paths.contains(&"task.description"), "{}", err);
+    assert!(paths.contains(&"task.subtasks"), "{}", err);
+    // The task ID is a UUID, longer than the limit too.
+    assert!(paths.contains(&"task.task_id"), "{}", err);
+}
+
+#[test]
+fn test_deep_nesting_is_rejected() {
+    let mut nested = json!("leaf");
+    for _ in 0..40 {
+        nested = json!([nested]);
+    }
+    let params = json!({"task_id": "t1", "requester": "did:swarm:a", "extra": nested});
+    let err = decode_strict::<TaskStateRequestParams>(&params, &StrictLimits::default()).unwrap_err();
+    assert!(err.0[0].problem.starts_with("nested deeper than"), "{}", err);
+}
+
+#[test]
+fn test_timestamps_outside_sane_range_are_rejected() {
+    let limits = StrictLimits::default();
+    let err = decode_strict::<SwarmLeaveParams>(&leave("1970-01-01T00:00:00Z"), &limits).unwrap_err();
+    assert_eq!(err.0[0].path, "timestamp");
+    let err = decode_strict::<SwarmLeaveParams>(&leave("9999-01-01T00:00:00Z"), &limits).unwrap_err();
+    assert!(err.0[0].problem.contains("in the future"), "{}", err);
+}
+
+#[test]
+fn test_unknown_fields_are_denied_only_when_asked() {
+    let mut params = leave("2026-01-01T00:00:00Z");
+    params["injected"] = json!("surprise");
+    assert!(decode_strict::<SwarmLeaveParams>(&params, &StrictLimits::default()).is_ok());
+
+    let strict = StrictLimits {
+        deny_unknown_fields: true,
+        ..Default::default()
+    };
+    let err = decode_strict::<SwarmLeaveParams>(&params, &strict).unwrap_err();
+    assert_eq!(
+        err.0,
+        vec![FieldError {
+            path: "injected".into(),
+            problem: "unknown field".into()
+        }]
+    );
+    assert_eq!(err.to_string(), "injected: unknown field");
+}
+
+#[test]
+fn test_field_names_of_params_structs() {
+    assert_eq!(
+        field_names::<SwarmLeaveParams>(),
+        Some(&["swarm_id", "agent_id", "timestamp"][..])
+    );
+    assert_eq!(field_names::<String>(), None);
+}
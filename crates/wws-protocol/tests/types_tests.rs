@@ -143,6 +143,7 @@ fn plan_subtask_ordering() {
             description: format!("Subtask {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.5,
+            budget: None,
         });
     }
     assert_eq!(plan.subtasks.len(), 10);
@@ -165,6 +166,7 @@ fn plan_serialization_roundtrip() {
         description: "Do thing".into(),
         required_capabilities: vec!["web-search".into()],
         estimated_complexity: 0.7,
+        budget: None,
     });
 
     let json = serde_json::to_string(&plan).unwrap();
@@ -318,6 +320,7 @@ fn artifact_serialization() {
         size_bytes: 1024,
         created_at: chrono::Utc::now(),
         content: "test artifact content".into(),
+        sealed: None,
     };
     let json = serde_json::to_string(&artifact).unwrap();
     let parsed: Artifact = serde_json::from_str(&json).unwrap();
@@ -407,6 +410,7 @@ fn ranked_vote_serialization() {
         epoch: 106,
         rankings: vec!["plan-a".into(), "plan-b".into(), "plan-c".into()],
         critic_scores,
+        abstain: false,
     };
     let json = serde_json::to_string(&vote).unwrap();
     let parsed: RankedVote = serde_json::from_str(&json).unwrap();
@@ -414,6 +418,52 @@ fn ranked_vote_serialization() {
     assert!(parsed.critic_scores.contains_key("plan-a"));
 }
 
+// ═══════════════════════════════════════════════════════════════
+// Result schemas
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn result_schema_checks_content_type_and_structure() {
+    use wws_protocol::ResultSchema;
+
+    let schema: ResultSchema = serde_json::from_value(serde_json::json!({
+        "content_types": ["application/json"],
+        "json_schema": {
+            "type": "object",
+            "required": ["summary", "score"],
+            "properties": {
+                "summary": {"type": "string", "minLength": 1},
+                "score": {"type": "number", "minimum": 0, "maximum": 1},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "additionalProperties": false
+        }
+    }))
+    .unwrap();
+    assert!(schema.strict);
+
+    let ok = r#"{"summary": "done", "score": 0.8, "tags": ["a"]}"#;
+    assert!(schema.check("application/json; charset=utf-8", ok).is_empty());
+
+    let bad = r#"{"score": 2, "tags": [1], "extra": true}"#;
+    let violations = schema.check("application/json", bad);
+    assert_eq!(violations.len(), 4, "{:?}", violations);
+    assert!(violations.iter().any(|v| v.contains("\"summary\"")));
+    assert!(violations.iter().any(|v| v.starts_with("$.tags[0]")));
+
+    assert_eq!(schema.check("text/plain", "not json").len(), 2);
+}
+
+#[test]
+fn result_schema_content_type_wildcards() {
+    use wws_protocol::result_schema::content_type_matches;
+
+    assert!(content_type_matches("text/*", "text/markdown"));
+    assert!(content_type_matches("*/*", "image/png"));
+    assert!(!content_type_matches("text/*", "application/json"));
+    assert!(!content_type_matches("application/json", "application/jsonl"));
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Constants
 // ═══════════════════════════════════════════════════════════════
@@ -9,53 +9,278 @@
 //! - Deduplication: identical content has the same CID
 //! - Integrity: any bit flip changes the CID
 //! - Location-independence: content is found by hash, not by location
+//!
+//! The store keeps content in memory by default. [`ContentStore::open`]
+//! backs it with a directory instead (one file per CID). Either mode can be
+//! capped with [`ContentStore::with_max_bytes`]; once the cap is exceeded,
+//! the least recently used content that is not pinned is evicted.
 
 use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use sha2::{Digest, Sha256};
 
+use crate::StateError;
+
+/// Where content bytes live.
+enum Backend {
+    Memory(HashMap<String, Vec<u8>>),
+    Disk(PathBuf),
+}
+
+/// Bookkeeping for one stored item.
+struct EntryMeta {
+    size: u64,
+    /// Logical access time; larger is more recent.
+    last_access: AtomicU64,
+}
+
 /// Content-addressed storage for artifacts.
 ///
 /// Stores content locally with optional DHT publishing for
 /// distributed retrieval. All content is identified by its
 /// SHA-256 hash (CID).
 pub struct ContentStore {
-    /// Local content storage: CID -> content bytes.
-    data: HashMap<String, Vec<u8>>,
+    /// Content bytes, in memory or on disk.
+    backend: Backend,
+    /// Size and recency of every stored CID.
+    entries: HashMap<String, EntryMeta>,
     /// Provider records: CID -> set of agent IDs that have the content.
     providers: HashMap<String, HashSet<String>>,
+    /// CIDs exempt from garbage collection.
+    pinned: HashSet<String>,
+    /// Total size of stored content, in bytes.
+    total_bytes: u64,
+    /// Size cap enforced by garbage collection; `None` means unbounded.
+    max_bytes: Option<u64>,
+    /// Source of logical access times for LRU ordering.
+    access_clock: AtomicU64,
 }
 
 impl ContentStore {
     /// Create a new empty content store.
     pub fn new() -> Self {
+        Self::with_backend(Backend::Memory(HashMap::new()))
+    }
+
+    /// Open a disk-backed store in `dir`, creating the directory if needed.
+    ///
+    /// Content already in the directory is indexed, oldest modification
+    /// time first, so it is the first to be evicted.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, StateError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| storage_error(&dir, e))?;
+
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(&dir).map_err(|e| storage_error(&dir, e))? {
+            let entry = entry.map_err(|e| storage_error(&dir, e))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !is_cid(&name) {
+                continue;
+            }
+            let metadata = entry.metadata().map_err(|e| storage_error(&entry.path(), e))?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified().ok();
+            found.push((modified, name, metadata.len()));
+        }
+        found.sort();
+
+        let mut store = Self::with_backend(Backend::Disk(dir));
+        for (_, cid, size) in found {
+            store.insert_entry(cid, size);
+        }
+        Ok(store)
+    }
+
+    /// Cap the store at `max_bytes`, evicting unpinned content if it is
+    /// already over.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self.gc();
+        self
+    }
+
+    fn with_backend(backend: Backend) -> Self {
         Self {
-            data: HashMap::new(),
+            backend,
+            entries: HashMap::new(),
             providers: HashMap::new(),
+            pinned: HashSet::new(),
+            total_bytes: 0,
+            max_bytes: None,
+            access_clock: AtomicU64::new(0),
         }
     }
 
     /// Store content and return its CID (SHA-256 hex).
     ///
     /// If content with the same CID already exists, this is a no-op
-    /// (deduplication). Returns the CID in either case.
+    /// (deduplication). Returns the CID in either case. Failures are
+    /// logged; use [`ContentStore::try_store`] to handle them.
     pub fn store(&mut self, data: &[u8]) -> String {
+        match self.try_store(data) {
+            Ok(cid) => cid,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to store content");
+                Self::compute_cid(data)
+            }
+        }
+    }
+
+    /// Store content and return its CID, reporting size-limit and disk errors.
+    ///
+    /// Storing may evict older unpinned content to stay under the size cap;
+    /// the newly stored item itself is never evicted by its own insertion.
+    pub fn try_store(&mut self, data: &[u8]) -> Result<String, StateError> {
         let cid = Self::compute_cid(data);
-        // Deduplicate: only insert if not already present.
-        self.data.entry(cid.clone()).or_insert_with(|| data.to_vec());
-        cid
+        if let Some(meta) = self.entries.get(&cid) {
+            self.touch(meta);
+            return Ok(cid);
+        }
+        if let Some(limit) = self.max_bytes {
+            if data.len() as u64 > limit {
+                return Err(StateError::ContentTooLarge {
+                    size: data.len(),
+                    limit: limit as usize,
+                });
+            }
+        }
+
+        match &mut self.backend {
+            Backend::Memory(data_map) => {
+                data_map.insert(cid.clone(), data.to_vec());
+            }
+            Backend::Disk(dir) => {
+                // Write to a temporary name first so a crash never leaves a
+                // truncated file under a valid CID.
+                let tmp = dir.join(format!(".{}.tmp", cid));
+                std::fs::write(&tmp, data).map_err(|e| storage_error(&tmp, e))?;
+                let path = dir.join(&cid);
+                std::fs::rename(&tmp, &path).map_err(|e| storage_error(&path, e))?;
+            }
+        }
+        self.insert_entry(cid.clone(), data.len() as u64);
+        self.evict_until_within_limit(Some(&cid));
+        Ok(cid)
     }
 
     /// Retrieve content by CID.
     ///
-    /// Returns `None` if the CID is not found in local storage.
+    /// Returns `None` if the CID is not found in local storage, or if the
+    /// on-disk copy no longer matches its CID.
     pub fn get(&self, cid: &str) -> Option<Vec<u8>> {
-        self.data.get(cid).cloned()
+        let meta = self.entries.get(cid)?;
+        let data = match &self.backend {
+            Backend::Memory(data_map) => data_map.get(cid).cloned()?,
+            Backend::Disk(dir) => {
+                let data = std::fs::read(dir.join(cid)).ok()?;
+                if Self::compute_cid(&data) != cid {
+                    tracing::warn!(cid, "Stored content does not match its CID");
+                    return None;
+                }
+                data
+            }
+        };
+        self.touch(meta);
+        Some(data)
     }
 
     /// Check if content exists locally.
     pub fn exists(&self, cid: &str) -> bool {
-        self.data.contains_key(cid)
+        self.entries.contains_key(cid)
+    }
+
+    /// Exempt a CID from garbage collection. The CID need not be stored yet.
+    pub fn pin(&mut self, cid: &str) {
+        self.pinned.insert(cid.to_string());
+    }
+
+    /// Make a CID eligible for garbage collection again.
+    pub fn unpin(&mut self, cid: &str) {
+        self.pinned.remove(cid);
+    }
+
+    /// Replace the whole pin set.
+    pub fn set_pins(&mut self, cids: HashSet<String>) {
+        self.pinned = cids;
+    }
+
+    pub fn is_pinned(&self, cid: &str) -> bool {
+        self.pinned.contains(cid)
+    }
+
+    /// Evict least recently used unpinned content until the store is within
+    /// its size cap. Returns the number of items evicted.
+    pub fn gc(&mut self) -> usize {
+        self.evict_until_within_limit(None)
+    }
+
+    /// Remove content by CID regardless of pins. Returns whether it existed.
+    pub fn remove(&mut self, cid: &str) -> bool {
+        let Some(meta) = self.entries.remove(cid) else {
+            return false;
+        };
+        self.total_bytes = self.total_bytes.saturating_sub(meta.size);
+        match &mut self.backend {
+            Backend::Memory(data_map) => {
+                data_map.remove(cid);
+            }
+            Backend::Disk(dir) => {
+                let path = dir.join(cid);
+                if let Err(e) = std::fs::remove_file(&path) {
+                    if e.kind() != std::io::ErrorKind::NotFound {
+                        tracing::warn!(error = %e, path = %path.display(), "Failed to delete stored content");
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn evict_until_within_limit(&mut self, keep: Option<&str>) -> usize {
+        let Some(limit) = self.max_bytes else {
+            return 0;
+        };
+        if self.total_bytes <= limit {
+            return 0;
+        }
+        let mut candidates: Vec<(u64, String)> = self
+            .entries
+            .iter()
+            .filter(|(cid, _)| !self.pinned.contains(*cid) && Some(cid.as_str()) != keep)
+            .map(|(cid, meta)| (meta.last_access.load(Ordering::Relaxed), cid.clone()))
+            .collect();
+        candidates.sort();
+
+        let mut evicted = 0;
+        for (_, cid) in candidates {
+            if self.total_bytes <= limit {
+                break;
+            }
+            self.remove(&cid);
+            evicted += 1;
+        }
+        evicted
+    }
+
+    fn insert_entry(&mut self, cid: String, size: u64) {
+        let tick = self.access_clock.fetch_add(1, Ordering::Relaxed) + 1;
+        self.total_bytes += size;
+        self.entries.insert(
+            cid,
+            EntryMeta {
+                size,
+                last_access: AtomicU64::new(tick),
+            },
+        );
+    }
+
+    fn touch(&self, meta: &EntryMeta) {
+        let tick = self.access_clock.fetch_add(1, Ordering::Relaxed) + 1;
+        meta.last_access.store(tick, Ordering::Relaxed);
     }
 
     /// Publish a provider record for a CID.
@@ -87,12 +312,30 @@ impl ContentStore {
 
     /// Get the total number of stored items.
     pub fn item_count(&self) -> usize {
-        self.data.len()
+        self.entries.len()
+    }
+
+    /// Total size of stored content, in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// Size cap enforced by garbage collection, if any.
+    pub fn max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+
+    /// Backing directory for disk-backed stores.
+    pub fn dir(&self) -> Option<&Path> {
+        match &self.backend {
+            Backend::Memory(_) => None,
+            Backend::Disk(dir) => Some(dir),
+        }
     }
 
     /// Get all CIDs in the store.
     pub fn all_cids(&self) -> Vec<String> {
-        self.data.keys().cloned().collect()
+        self.entries.keys().cloned().collect()
     }
 }
 
@@ -102,6 +345,15 @@ impl Default for ContentStore {
     }
 }
 
+fn storage_error(path: &Path, e: std::io::Error) -> StateError {
+    StateError::StorageError(format!("{}: {}", path.display(), e))
+}
+
+/// Whether `name` looks like a CID (64 lowercase hex characters).
+fn is_cid(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
 /// Hex-encode a byte slice into a lowercase hex string.
 fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
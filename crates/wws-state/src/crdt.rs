@@ -14,6 +14,12 @@
 //! **Add-wins semantics**: when one node adds an element concurrently
 //! with another node removing it, the add wins because the new unique
 //! tag is not present in the remote tombstone set.
+//!
+//! **Tombstone compaction**: every replica keeps a version vector of the
+//! operations it has observed, and learns its peers' vectors through merges.
+//! Once every live replica has observed a removal, the removal is causally
+//! stable: no replica can still re-introduce the removed tag, so both the
+//! tag and its tombstone can be dropped (see [`OrSet::compact`]).
 
 use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
@@ -24,7 +30,7 @@ use std::hash::Hash;
 /// Each tag is a pair of (node_id, counter) which is guaranteed
 /// to be unique across the entire swarm as long as node IDs are unique
 /// and counters are monotonically increasing per node.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct UniqueTag {
     pub node_id: String,
     pub counter: u64,
@@ -41,7 +47,8 @@ pub struct UniqueTag {
 /// # Semantics
 /// - Each `add` creates a fresh `UniqueTag` for the value.
 /// - `remove` moves all currently-known tags for a value into
-///   the tombstone set.
+///   the tombstone set, stamped with a fresh tag of its own so peers
+///   can tell when they have observed the removal.
 /// - An element is *present* if it has at least one tag that is
 ///   **not** in the tombstone set.
 /// - On merge, both entries and tombstones are unioned. Because a
@@ -53,10 +60,15 @@ pub struct OrSet<T: Clone + Eq + Hash + Display> {
     node_id: String,
     /// Map from values to the set of unique tags currently associated.
     entries: HashMap<T, HashSet<UniqueTag>>,
-    /// Set of tombstoned (removed) tags.
-    tombstones: HashSet<UniqueTag>,
+    /// Tombstoned (removed) tags, each mapped to the tag of the remove
+    /// operation that killed it.
+    tombstones: HashMap<UniqueTag, UniqueTag>,
     /// Monotonically increasing counter for generating unique tags.
     counter: u64,
+    /// Highest counter observed per node (this replica's version vector).
+    clock: HashMap<String, u64>,
+    /// Latest version vector learned for each peer replica.
+    peer_clocks: HashMap<String, HashMap<String, u64>>,
 }
 
 impl<T: Clone + Eq + Hash + Display> Clone for OrSet<T> {
@@ -66,6 +78,8 @@ impl<T: Clone + Eq + Hash + Display> Clone for OrSet<T> {
             entries: self.entries.clone(),
             tombstones: self.tombstones.clone(),
             counter: self.counter,
+            clock: self.clock.clone(),
+            peer_clocks: self.peer_clocks.clone(),
         }
     }
 }
@@ -76,8 +90,10 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
         Self {
             node_id,
             entries: HashMap::new(),
-            tombstones: HashSet::new(),
+            tombstones: HashMap::new(),
             counter: 0,
+            clock: HashMap::new(),
+            peer_clocks: HashMap::new(),
         }
     }
 
@@ -86,11 +102,7 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
     /// Each add generates a fresh unique tag, ensuring that concurrent
     /// adds are never lost even if a concurrent remove is in flight.
     pub fn add(&mut self, value: T) {
-        self.counter += 1;
-        let tag = UniqueTag {
-            node_id: self.node_id.clone(),
-            counter: self.counter,
-        };
+        let tag = self.next_tag();
         self.entries
             .entry(value)
             .or_insert_with(HashSet::new)
@@ -108,10 +120,29 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
         T: std::borrow::Borrow<Q>,
         Q: Hash + Eq,
     {
-        if let Some(tags) = self.entries.get(value) {
-            for tag in tags.iter() {
-                self.tombstones.insert(tag.clone());
-            }
+        let live: Vec<UniqueTag> = match self.entries.get(value) {
+            Some(tags) => tags
+                .iter()
+                .filter(|tag| !self.tombstones.contains_key(*tag))
+                .cloned()
+                .collect(),
+            None => return,
+        };
+        if live.is_empty() {
+            return;
+        }
+        let removal = self.next_tag();
+        for tag in live {
+            self.tombstones.insert(tag, removal.clone());
+        }
+    }
+
+    fn next_tag(&mut self) -> UniqueTag {
+        self.counter += 1;
+        self.clock.insert(self.node_id.clone(), self.counter);
+        UniqueTag {
+            node_id: self.node_id.clone(),
+            counter: self.counter,
         }
     }
 
@@ -125,7 +156,7 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
         Q: Hash + Eq,
     {
         if let Some(tags) = self.entries.get(value) {
-            tags.iter().any(|tag| !self.tombstones.contains(tag))
+            tags.iter().any(|tag| !self.tombstones.contains_key(tag))
         } else {
             false
         }
@@ -137,7 +168,7 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
     pub fn elements(&self) -> Vec<T> {
         self.entries
             .iter()
-            .filter(|(_, tags)| tags.iter().any(|tag| !self.tombstones.contains(tag)))
+            .filter(|(_, tags)| tags.iter().any(|tag| !self.tombstones.contains_key(tag)))
             .map(|(value, _)| value.clone())
             .collect()
     }
@@ -146,7 +177,7 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
     pub fn len(&self) -> usize {
         self.entries
             .iter()
-            .filter(|(_, tags)| tags.iter().any(|tag| !self.tombstones.contains(tag)))
+            .filter(|(_, tags)| tags.iter().any(|tag| !self.tombstones.contains_key(tag)))
             .count()
     }
 
@@ -162,6 +193,9 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
     /// Merge rules:
     /// - Entries are unioned: all tags from the remote set are added.
     /// - Tombstones are unioned: all tombstones from the remote set are added.
+    ///   A tag removed on both sides keeps the smaller removal tag.
+    /// - Version vectors are merged pointwise, and the remote replica's
+    ///   vector (plus the peer vectors it knows) is recorded for compaction.
     ///
     /// After merge, a value is present if it has at least one tag that
     /// is not in the combined tombstone set. This gives add-wins semantics
@@ -176,17 +210,96 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
         }
 
         // Union of tombstones.
-        for tag in &other.tombstones {
-            self.tombstones.insert(tag.clone());
+        for (tag, removal) in &other.tombstones {
+            match self.tombstones.get_mut(tag) {
+                Some(existing) if *existing <= *removal => {}
+                Some(existing) => *existing = removal.clone(),
+                None => {
+                    self.tombstones.insert(tag.clone(), removal.clone());
+                }
+            }
+        }
+
+        merge_clock(&mut self.clock, &other.clock);
+        // Keep our own counter ahead of anything issued under our node ID.
+        if let Some(&own) = self.clock.get(&self.node_id) {
+            self.counter = self.counter.max(own);
+        }
+        if other.node_id != self.node_id {
+            let known = self.peer_clocks.entry(other.node_id.clone()).or_default();
+            merge_clock(known, &other.clock);
+        }
+        for (peer, clock) in &other.peer_clocks {
+            if *peer != self.node_id {
+                merge_clock(self.peer_clocks.entry(peer.clone()).or_default(), clock);
+            }
         }
     }
 
+    /// Drop tombstones that every replica in `replicas` has observed,
+    /// together with the tags they killed. Returns the number of tombstones
+    /// removed.
+    ///
+    /// `replicas` must list every live replica of this set (this node is
+    /// always included). A replica whose version vector has not yet been
+    /// learned through a merge blocks compaction entirely. Peers not listed
+    /// are treated as departed and forgotten.
+    pub fn compact(&mut self, replicas: &[String]) -> usize {
+        self.peer_clocks
+            .retain(|peer, _| replicas.iter().any(|r| r == peer));
+
+        let mut clocks: Vec<&HashMap<String, u64>> = vec![&self.clock];
+        for replica in replicas.iter().filter(|r| **r != self.node_id) {
+            match self.peer_clocks.get(replica) {
+                Some(clock) => clocks.push(clock),
+                None => return 0,
+            }
+        }
+
+        let stable: HashSet<UniqueTag> = self
+            .tombstones
+            .iter()
+            .filter(|(_, removal)| {
+                clocks
+                    .iter()
+                    .all(|clock| clock.get(&removal.node_id).copied().unwrap_or(0) >= removal.counter)
+            })
+            .map(|(tag, _)| tag.clone())
+            .collect();
+
+        for tag in &stable {
+            self.tombstones.remove(tag);
+        }
+        self.entries.retain(|_, tags| {
+            tags.retain(|tag| !stable.contains(tag));
+            !tags.is_empty()
+        });
+        stable.len()
+    }
+
+    /// Number of tombstones currently retained.
+    pub fn tombstone_count(&self) -> usize {
+        self.tombstones.len()
+    }
+
+    /// This replica's version vector: the highest counter observed per node.
+    pub fn clock(&self) -> &HashMap<String, u64> {
+        &self.clock
+    }
+
     /// Get the node ID of this replica.
     pub fn node_id(&self) -> &str {
         &self.node_id
     }
 }
 
+fn merge_clock(into: &mut HashMap<String, u64>, from: &HashMap<String, u64>) {
+    for (node, counter) in from {
+        let entry = into.entry(node.clone()).or_insert(0);
+        *entry = (*entry).max(*counter);
+    }
+}
+
 /// Convenience type for tracking task statuses across the swarm.
 pub type TaskStatusSet = OrSet<String>;
 
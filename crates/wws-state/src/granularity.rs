@@ -14,8 +14,11 @@
 //! - If the task is atomic but multiple agents are available,
 //!   use redundant execution for reliability.
 //! - Strategy selection depends on the ratio of N_branch to k.
+//! - The complexity above which a subtask recurses into a sub-holon
+//!   adapts to swarm size, cascade depth and subordinate load.
 
 use std::cmp;
+use std::fmt;
 
 use wws_protocol::DEFAULT_BRANCHING_FACTOR;
 
@@ -111,6 +114,12 @@ pub struct GranularityConfig {
     pub decompose_complexity_threshold: f64,
     /// Minimum branch size for decomposition to make sense.
     pub min_branch_size_for_decomposition: u64,
+    /// Recursion threshold for a root task in a standard-sized, idle swarm.
+    pub base_recursion_threshold: f64,
+    /// Threshold increase per cascade level below the root task.
+    pub recursion_depth_step: f64,
+    /// Threshold decrease when every subordinate is fully utilized.
+    pub recursion_load_relief: f64,
 }
 
 impl Default for GranularityConfig {
@@ -122,10 +131,74 @@ impl Default for GranularityConfig {
             redundancy_factor: 3,
             decompose_complexity_threshold: 0.7,
             min_branch_size_for_decomposition: 3,
+            base_recursion_threshold: 0.4,
+            recursion_depth_step: 0.1,
+            recursion_load_relief: 0.2,
         }
     }
 }
 
+/// Inputs to the dynamic recursion threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecursionInputs {
+    /// Active members of the swarm.
+    pub swarm_size: u64,
+    /// Current branching factor (k) of the hierarchy.
+    pub branching_factor: u32,
+    /// Cascade depth of the task being decomposed (root tasks are 0).
+    pub depth: u32,
+    /// Mean utilization of the coordinator's subordinates (0.0-1.0).
+    pub load: f64,
+}
+
+/// A recursion threshold together with the inputs and adjustments behind it.
+///
+/// Subtasks whose estimated complexity exceeds `threshold` spawn a
+/// sub-holon; the rest are assigned directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecursionThreshold {
+    /// The inputs the threshold was computed from.
+    pub inputs: RecursionInputs,
+    /// Strategy implied by the swarm size and branching factor.
+    pub strategy: DecompositionStrategy,
+    /// Threshold before adjustments.
+    pub base: f64,
+    /// Adjustment for the swarm size (positive for small swarms).
+    pub size_adjustment: f64,
+    /// Adjustment for the cascade depth.
+    pub depth_adjustment: f64,
+    /// Adjustment for subordinate load (negative when busy).
+    pub load_adjustment: f64,
+    /// Final threshold, clamped to 0.1-0.95.
+    pub threshold: f64,
+}
+
+impl RecursionThreshold {
+    /// Whether a subtask of the given complexity should recurse.
+    pub fn should_recurse(&self, estimated_complexity: f64) -> bool {
+        estimated_complexity > self.threshold
+    }
+}
+
+impl fmt::Display for RecursionThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "recursion threshold {:.2} (base {:.2}; swarm size {} at k={} -> {:?} {:+.2}; depth {} {:+.2}; load {:.2} {:+.2})",
+            self.threshold,
+            self.base,
+            self.inputs.swarm_size,
+            self.inputs.branching_factor,
+            self.strategy,
+            self.size_adjustment,
+            self.inputs.depth,
+            self.depth_adjustment,
+            self.inputs.load,
+            self.load_adjustment,
+        )
+    }
+}
+
 /// Decision from the instance-based granularity algorithm.
 #[derive(Debug, Clone, PartialEq)]
 pub enum GranularityDecision {
@@ -198,6 +271,37 @@ impl GranularityAlgorithm {
         }
     }
 
+    /// Compute the complexity above which a subtask spawns a sub-holon.
+    ///
+    /// Small swarms (no more members than the branching factor) and deep
+    /// cascade levels raise the threshold, since there are few coordinators
+    /// left to run another RFP cycle. Very large swarms and busy
+    /// subordinates lower it, pushing work out to other coordinators.
+    pub fn recursion_threshold(&self, inputs: RecursionInputs) -> RecursionThreshold {
+        let strategy =
+            GranularityEngine::select_strategy(inputs.swarm_size, inputs.branching_factor, false);
+        let size_adjustment = match strategy {
+            DecompositionStrategy::DirectAssignment => 0.2,
+            DecompositionStrategy::MassiveParallelism => -0.1,
+            _ => 0.0,
+        };
+        let depth_adjustment = self.config.recursion_depth_step * inputs.depth as f64;
+        let load_adjustment = -self.config.recursion_load_relief * inputs.load.clamp(0.0, 1.0);
+        let base = self.config.base_recursion_threshold;
+        let threshold =
+            (base + size_adjustment + depth_adjustment + load_adjustment).clamp(0.1, 0.95);
+
+        RecursionThreshold {
+            inputs,
+            strategy,
+            base,
+            size_adjustment,
+            depth_adjustment,
+            load_adjustment,
+            threshold,
+        }
+    }
+
     fn compute_redundancy(&self, branch_size: u64) -> u32 {
         self.config
             .redundancy_factor
@@ -284,4 +388,38 @@ mod tests {
             GranularityDecision::Decompose { subtask_count: 10, .. }
         ));
     }
+
+    #[test]
+    fn test_recursion_threshold_adapts() {
+        let algo = GranularityAlgorithm::default();
+        let standard = algo.recursion_threshold(RecursionInputs {
+            swarm_size: 50,
+            branching_factor: 10,
+            depth: 0,
+            load: 0.0,
+        });
+        assert_eq!(standard.strategy, DecompositionStrategy::StandardDecomposition);
+        assert!((standard.threshold - 0.4).abs() < 1e-9);
+        assert!(standard.should_recurse(0.5));
+
+        // A small swarm at depth 2 rarely recurses.
+        let small_deep = algo.recursion_threshold(RecursionInputs {
+            swarm_size: 4,
+            branching_factor: 10,
+            depth: 2,
+            load: 0.0,
+        });
+        assert!((small_deep.threshold - 0.8).abs() < 1e-9);
+        assert!(!small_deep.should_recurse(0.5));
+
+        // Saturated subordinates push work out to sub-holons.
+        let busy = algo.recursion_threshold(RecursionInputs {
+            swarm_size: 50,
+            branching_factor: 10,
+            depth: 0,
+            load: 3.0,
+        });
+        assert!((busy.threshold - 0.2).abs() < 1e-9);
+        assert!(busy.to_string().starts_with("recursion threshold 0.20"));
+    }
 }
@@ -216,3 +216,81 @@ fn orset_merge_empty_sets() {
     set_a.merge(&set_b);
     assert!(set_a.elements().is_empty());
 }
+
+// ═══════════════════════════════════════════════════════════════
+// Tombstone compaction
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn orset_compact_single_replica_drops_tombstones() {
+    let mut set = OrSet::<String>::new("node".into());
+    for i in 0..10 {
+        set.add(format!("task-{}", i));
+        set.remove(&format!("task-{}", i));
+    }
+    set.add("live".to_string());
+    assert_eq!(set.tombstone_count(), 10);
+
+    assert_eq!(set.compact(&["node".to_string()]), 10);
+    assert_eq!(set.tombstone_count(), 0);
+    assert_eq!(set.elements(), vec!["live".to_string()]);
+}
+
+#[test]
+fn orset_compact_waits_for_every_replica() {
+    let replicas = vec!["a".to_string(), "b".to_string()];
+    let mut set_a = OrSet::<String>::new("a".into());
+    let mut set_b = OrSet::<String>::new("b".into());
+
+    set_a.add("x".to_string());
+    set_b.merge(&set_a);
+    set_a.merge(&set_b);
+    set_a.remove("x");
+
+    // B has not seen the removal yet.
+    assert_eq!(set_a.compact(&replicas), 0);
+
+    set_b.merge(&set_a);
+    set_a.merge(&set_b);
+    assert_eq!(set_a.compact(&replicas), 1);
+    assert_eq!(set_a.tombstone_count(), 0);
+
+    // B's replica still carries the tombstone, so merging it back cannot
+    // resurrect the element.
+    set_a.merge(&set_b);
+    assert!(!set_a.contains("x"));
+}
+
+#[test]
+fn orset_compact_keeps_concurrent_add() {
+    let replicas = vec!["a".to_string(), "b".to_string()];
+    let mut set_a = OrSet::<String>::new("a".into());
+    let mut set_b = OrSet::<String>::new("b".into());
+
+    set_a.add("x".to_string());
+    set_b.merge(&set_a);
+    set_b.remove("x");
+    set_a.add("x".to_string());
+
+    set_a.merge(&set_b);
+    set_b.merge(&set_a);
+    set_a.merge(&set_b);
+    set_a.compact(&replicas);
+    assert!(set_a.contains("x"), "Concurrent add must survive compaction");
+}
+
+#[test]
+fn orset_compact_forgets_departed_replicas() {
+    let mut set_a = OrSet::<String>::new("a".into());
+    let set_b = OrSet::<String>::new("b".into());
+    set_a.merge(&set_b);
+    set_a.add("x".to_string());
+    set_a.remove("x");
+
+    // "c" was never heard from, so nothing is stable while it is listed.
+    let with_c = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    assert_eq!(set_a.compact(&with_c), 0);
+
+    // b has not observed the removal either; once b leaves, a alone decides.
+    assert_eq!(set_a.compact(&["a".to_string()]), 1);
+}